@@ -0,0 +1,22 @@
+use std::collections::BTreeMap;
+
+use openvm::io::{read, reveal_u32};
+
+/// Allocates and frees buffers of varying sizes, keeping a bounded number of them alive, as a
+/// long-running guest processing a stream of requests would.
+pub fn main() {
+    let rounds: u32 = read();
+    let mut live = BTreeMap::new();
+    let mut checksum = 0u32;
+    for i in 0..rounds {
+        let len = (i.wrapping_mul(2654435761) >> 20) as usize % 4096 + 1;
+        let buffer = vec![i as u8; len];
+        checksum = checksum.wrapping_add(buffer.iter().map(|&byte| byte as u32).sum::<u32>());
+        live.insert(i % 64, buffer);
+        if let Some(buffer) = live.get_mut(&(i / 2 % 64)) {
+            buffer.extend_from_slice(&[1; 64]);
+        }
+    }
+    reveal_u32(checksum, 0);
+    reveal_u32(live.values().map(Vec::len).sum::<usize>() as u32, 1);
+}
@@ -0,0 +1,2 @@
+// This file is automatically generated by cargo openvm. Do not rename or edit.
+openvm_algebra_guest::moduli_macros::moduli_init! { "23475049829575779586189747898505103163112716888653728622447600555970347572071874968727235696024379630996529689804087317076094648549028807737962421148878204058627491730515715037867331116460019100387172284198848118228161659090310518078323182868782380810863677308189961636448806183811966868807428270776545356527616736129213145259520838731287285151371468662555835507775468515863713713821094734328157939991717947452360519193466858155050717874676991673049748008552832909383752652134902817108455701433529723073771244467284051638766686094171498786045821200560687351218296376090453252286657999444816074350420676925765433153691" }
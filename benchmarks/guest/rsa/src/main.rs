@@ -0,0 +1,21 @@
+use openvm::io::read_vec;
+use openvm_algebra_guest::{IntMod, PowBytes};
+
+openvm_algebra_guest::moduli_macros::moduli_declare! {
+    Rsa2048 { modulus = "0xB9F54D90 80A36CA8 024DBAE7 D23E4716 C5DA56D9 EAB616BF 00120EC6 DA6F1587 A9E306A4 C31CB224 524A1398 DEAE51E8 EF310591 C6C0381D 2649E1E2 B8B4F2EC 93ED2121 D8BD9E80 2A2E49C7 106DCE69 A2240EEF E2E20629 E3B80DC1 F1D81878 C02A03E1 FE4157AA 73E444C3 C4761617 1B75BE0E 5AC6A4F8 3498F13F F4D610B9 E400194D A05A8661 7815FA22 E546EFFF 05636EDC 0B02B49F 9C502929 A92B2F18 98A23CF3 2010EC9A 193C9369 E294E546 BFBFEA1B CDC68D70 F3DB1791 B00EBF3E 7AB1F2D5 24637EF4 301E0A68 EB85C2AE E313B24E 130AAEE9 312287AE 6AE098E3 ED5B2DAE 90ED6954 C95F2782 9A542DA7 F4F8BEDB 7C3C9872 A6288FDB B4C3D89B" },
+}
+
+openvm::init!();
+
+pub fn main() {
+    let message = Rsa2048::from_be_bytes(&read_vec()).unwrap();
+    let signature = Rsa2048::from_be_bytes(&read_vec()).unwrap();
+    let private_exponent = read_vec();
+
+    // Verification with the public exponent e = 65537
+    for _ in 0..5 {
+        assert_eq!(signature.pow_const::<65537>(), message);
+    }
+    // Signing with the private exponent
+    assert_eq!(message.pow_vartime(&private_exponent), signature);
+}
@@ -0,0 +1,57 @@
+use clap::Parser;
+use eyre::Result;
+use openvm_benchmarks_prove::util::BenchmarkCli;
+use openvm_circuit::arch::instructions::exe::VmExe;
+use openvm_rv32im_circuit::Rv32ImConfig;
+use openvm_rv32im_transpiler::{
+    Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
+};
+use openvm_sdk::StdIn;
+use openvm_stark_sdk::{bench::run_with_metric_collection, p3_baby_bear::BabyBear};
+use openvm_transpiler::{transpiler::Transpiler, FromElf};
+
+/// Compares the proving cost of the same allocation-heavy program with each heap allocator: the
+/// default bump allocator, `heap-embedded-alloc` and `heap-tlsf`.
+fn main() -> Result<()> {
+    let args = BenchmarkCli::parse();
+
+    let config = Rv32ImConfig::default();
+    let exes = [
+        ("bump", None),
+        ("embedded_alloc", Some("heap-embedded-alloc")),
+        ("tlsf", Some("heap-tlsf")),
+    ]
+    .map(|(allocator, feature)| {
+        let elf = args.build_bench_program_with_features(
+            "alloc_churn",
+            &config,
+            None,
+            &feature.into_iter().collect::<Vec<_>>(),
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<BabyBear>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        Ok((allocator, exe))
+    })
+    .into_iter()
+    .collect::<Result<Vec<_>>>()?;
+
+    run_with_metric_collection("OUTPUT_PATH", || -> Result<()> {
+        for (allocator, exe) in exes {
+            let rounds = 2_000u32;
+            let mut stdin = StdIn::default();
+            stdin.write(&rounds);
+            args.bench_from_exe(
+                format!("alloc_churn_{allocator}_program"),
+                config.clone(),
+                exe,
+                stdin,
+            )?;
+        }
+        Ok(())
+    })
+}
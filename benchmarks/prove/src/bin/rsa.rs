@@ -0,0 +1,56 @@
+use std::str::FromStr;
+
+use clap::Parser;
+use eyre::Result;
+use num_bigint::BigUint;
+use openvm_algebra_circuit::ModularExtension;
+use openvm_benchmarks_prove::util::BenchmarkCli;
+use openvm_circuit::arch::SystemConfig;
+use openvm_sdk::{config::SdkVmConfig, Sdk, StdIn};
+use openvm_stark_sdk::bench::run_with_metric_collection;
+use rand_chacha::{
+    rand_core::{RngCore, SeedableRng},
+    ChaCha8Rng,
+};
+
+const RSA_MODULUS: &str = "23475049829575779586189747898505103163112716888653728622447600555970347572071874968727235696024379630996529689804087317076094648549028807737962421148878204058627491730515715037867331116460019100387172284198848118228161659090310518078323182868782380810863677308189961636448806183811966868807428270776545356527616736129213145259520838731287285151371468662555835507775468515863713713821094734328157939991717947452360519193466858155050717874676991673049748008552832909383752652134902817108455701433529723073771244467284051638766686094171498786045821200560687351218296376090453252286657999444816074350420676925765433153691";
+const RSA_PRIVATE_EXPONENT: &str = "8df191c05080ee4a9c5f8ae0b359f85788b4ee00af2948d9888b401e47d3ed223dea5e42dbf00686b50d784203101ad3ebe88670ccbe22d71547e615729a24a7b30e9970c5898ff812ba7c7467b4f98f2645d1e5085131153e8e5a6a0559c6ec3cfa95362726e76ce3c3853dcdb3b98eefd60339dfceab540e8a03f4a6c5d3c353d6b775048ba4a276fae1e178148dc683315b72cc1d1972e0af3d1f7413d5715afca39f33714b7abbcbe87aed4b0f92a400a9afeaec533845f9c420e9ac35d90090493cacfb0ea78e61a8c0ab6525f945a64958b2a946afe01b46391e1de4c5289cf8f03e4c588dff8c5925358043b10347953dafa00e97f4ca6f37a426c541";
+
+fn main() -> Result<()> {
+    let args = BenchmarkCli::parse();
+
+    let modulus = BigUint::from_str(RSA_MODULUS).unwrap();
+    let mut system_config = SystemConfig::default().with_continuations();
+    // 256 byte moduli need an 18-bit range checker
+    system_config.memory_config.decomp = 18;
+    let vm_config = SdkVmConfig::builder()
+        .system(system_config.into())
+        .rv32i(Default::default())
+        .rv32m(Default::default())
+        .io(Default::default())
+        .modular(ModularExtension::new(vec![modulus.clone()]))
+        .build();
+    let elf = args.build_bench_program("rsa", &vm_config, None)?;
+    let sdk = Sdk::new();
+    let exe = sdk.transpile(elf, vm_config.transpiler()).unwrap();
+
+    run_with_metric_collection("OUTPUT_PATH", || -> Result<()> {
+        let private_exponent = BigUint::parse_bytes(RSA_PRIVATE_EXPONENT.as_bytes(), 16).unwrap();
+        let mut rng = ChaCha8Rng::seed_from_u64(12345);
+        let mut message = [0u8; 255];
+        rng.fill_bytes(&mut message);
+        let message = BigUint::from_bytes_be(&message);
+        let signature = message.modpow(&private_exponent, &modulus);
+
+        // Big endian and padded to the 256 bytes of an element
+        let to_bytes = |x: &BigUint| {
+            let bytes = x.to_bytes_be();
+            [vec![0; 256 - bytes.len()], bytes].concat()
+        };
+        let mut stdin = StdIn::default();
+        stdin.write_bytes(&to_bytes(&message));
+        stdin.write_bytes(&to_bytes(&signature));
+        stdin.write_bytes(&private_exponent.to_bytes_be());
+        args.bench_from_exe("rsa_program", vm_config, exe, stdin)
+    })
+}
@@ -54,6 +54,8 @@ fn main() -> Result<()> {
             app_vm_config,
             leaf_fri_params: app_fri_params.into(),
             compiler_options,
+            public_values_hasher: Default::default(),
+            expose_config_digest: false,
         };
         let (program, input_stream) = build_verification_program(vdata, compiler_options);
         let sdk = Sdk::new();
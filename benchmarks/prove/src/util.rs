@@ -2,8 +2,10 @@ use std::{path::PathBuf, sync::Arc};
 
 use clap::{command, Parser};
 use eyre::Result;
-use openvm_benchmarks_utils::{build_elf, get_programs_dir};
-use openvm_circuit::arch::{instructions::exe::VmExe, DefaultSegmentationStrategy, VmConfig};
+use openvm_benchmarks_utils::{build_elf_with_features, get_programs_dir};
+use openvm_circuit::arch::{
+    instructions::exe::VmExe, AdaptiveSegmentationStrategy, DefaultSegmentationStrategy, VmConfig,
+};
 use openvm_native_circuit::NativeConfig;
 use openvm_native_compiler::conversion::CompilerOptions;
 use openvm_sdk::{
@@ -65,6 +67,11 @@ pub struct BenchmarkCli {
     #[arg(short, long, alias = "max_segment_length")]
     pub max_segment_length: Option<usize>,
 
+    /// Segment with the adaptive segmentation strategy, which cuts segments at power-of-two trace
+    /// heights of the dominant chip. The max segment length, if set, is the max trace height.
+    #[arg(long)]
+    pub adaptive_segmentation: bool,
+
     /// Controls the arity (num_children) of the aggregation tree
     #[command(flatten)]
     pub agg_tree_config: AggregationTreeConfig,
@@ -80,7 +87,17 @@ impl BenchmarkCli {
         let leaf_log_blowup = self.leaf_log_blowup.unwrap_or(DEFAULT_LEAF_LOG_BLOWUP);
 
         app_vm_config.system_mut().profiling = self.profiling;
-        if let Some(max_segment_length) = self.max_segment_length {
+        if self.adaptive_segmentation {
+            let strategy = match self.max_segment_length {
+                Some(max_trace_height) => {
+                    AdaptiveSegmentationStrategy::new(max_trace_height, max_trace_height * 120)
+                }
+                None => AdaptiveSegmentationStrategy::default(),
+            };
+            app_vm_config
+                .system_mut()
+                .set_segmentation_strategy(Arc::new(strategy));
+        } else if let Some(max_segment_length) = self.max_segment_length {
             app_vm_config
                 .system_mut()
                 .set_segmentation_strategy(Arc::new(
@@ -101,6 +118,8 @@ impl BenchmarkCli {
                 enable_cycle_tracker: self.profiling,
                 ..Default::default()
             },
+            public_values_hasher: Default::default(),
+            expose_config_digest: false,
         }
     }
 
@@ -142,6 +161,20 @@ impl BenchmarkCli {
         vm_config: &VC,
         init_file_name: Option<&str>,
     ) -> Result<Elf>
+    where
+        VC: VmConfig<F>,
+    {
+        self.build_bench_program_with_features(program_name, vm_config, init_file_name, &[])
+    }
+
+    /// Builds the guest program with `features` enabled in addition to its default features.
+    pub fn build_bench_program_with_features<VC>(
+        &self,
+        program_name: &str,
+        vm_config: &VC,
+        init_file_name: Option<&str>,
+        features: &[&str],
+    ) -> Result<Elf>
     where
         VC: VmConfig<F>,
     {
@@ -153,7 +186,7 @@ impl BenchmarkCli {
         .to_string();
         let manifest_dir = get_programs_dir().join(program_name);
         vm_config.write_to_init_file(&manifest_dir, init_file_name)?;
-        build_elf(&manifest_dir, profile)
+        build_elf_with_features(&manifest_dir, profile, features)
     }
 
     pub fn bench_from_exe<VC>(
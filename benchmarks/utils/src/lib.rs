@@ -18,19 +18,40 @@ pub fn build_elf(manifest_dir: &PathBuf, profile: impl ToString) -> Result<Elf>
     build_elf_with_path(&pkg, profile, None)
 }
 
+/// Builds the guest program with `features` enabled in addition to its default features, e.g. to
+/// compare heap allocators.
+pub fn build_elf_with_features(
+    manifest_dir: &PathBuf,
+    profile: impl ToString,
+    features: &[&str],
+) -> Result<Elf> {
+    let pkg = get_package(manifest_dir);
+    build_package_elf(&pkg, profile, features, None)
+}
+
 pub fn build_elf_with_path(
     pkg: &Package,
     profile: impl ToString,
     elf_path: Option<&PathBuf>,
+) -> Result<Elf> {
+    build_package_elf(pkg, profile, &[], elf_path)
+}
+
+fn build_package_elf(
+    pkg: &Package,
+    profile: impl ToString,
+    features: &[&str],
+    elf_path: Option<&PathBuf>,
 ) -> Result<Elf> {
     // Use a temporary directory for the build
     let temp_dir = tempdir()?;
     let target_dir = temp_dir.path();
 
-    // Build guest with default features
+    // Build guest with default features, together with `features`
     let guest_opts = GuestOptions::default()
         .with_target_dir(target_dir)
-        .with_profile(profile.to_string());
+        .with_profile(profile.to_string())
+        .with_features(features);
 
     if let Err(Some(code)) = build_guest_package(pkg, &guest_opts, None, &None) {
         std::process::exit(code);
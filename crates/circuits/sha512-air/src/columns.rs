@@ -0,0 +1,141 @@
+//! WARNING: the order of fields in the structs is important, do not change it
+
+use openvm_circuit_primitives::{utils::not, AlignedBorrow};
+use openvm_stark_backend::p3_field::FieldAlgebra;
+
+use super::{
+    SHA512_HASH_WORDS, SHA512_ROUNDS_PER_ROW, SHA512_ROW_VAR_CNT, SHA512_WORD_BITS,
+    SHA512_WORD_U16S, SHA512_WORD_U8S,
+};
+
+/// In each SHA512 block:
+/// - First 20 rows use Sha512RoundCols
+/// - Final row uses Sha512DigestCols
+///
+/// Note that for soundness, we require that there is always a padding row after the last digest row
+/// in the trace. Right now, this is true because the unpadded height is a multiple of 21, and thus
+/// not a power of 2.
+///
+/// Sha512RoundCols and Sha512DigestCols share the same first 3 fields:
+/// - flags
+/// - work_vars/hash (same type, different name)
+/// - schedule_helper
+///
+/// This design allows for:
+/// 1. Common constraints to work on either struct type by accessing these shared fields
+/// 2. Specific constraints to use the appropriate struct, with flags helping to do conditional
+///    constraints
+///
+/// Note that the `Sha512WorkVarsCols` field it is used for different purposes in the two structs.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512RoundCols<T> {
+    pub flags: Sha512FlagsCols<T>,
+    /// Stores the current state of the working variables
+    pub work_vars: Sha512WorkVarsCols<T>,
+    pub schedule_helper: Sha512MessageHelperCols<T>,
+    pub message_schedule: Sha512MessageScheduleCols<T>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512DigestCols<T> {
+    pub flags: Sha512FlagsCols<T>,
+    /// Will serve as previous hash values for the next block.
+    ///     - on non-last blocks, this is the final hash of the current block
+    ///     - on last blocks, this is the initial hash value of the variant, see
+    ///       [crate::Sha512Variant::initial_hash].
+    /// The work variables constraints are applied on all rows, so `carry_a` and `carry_e`
+    /// must be filled in with dummy values to ensure these constraints hold.
+    pub hash: Sha512WorkVarsCols<T>,
+    pub schedule_helper: Sha512MessageHelperCols<T>,
+    /// The actual final hash values of the given block
+    /// Note: the above `hash` will be equal to `final_hash` unless we are on the last block
+    pub final_hash: [[T; SHA512_WORD_U8S]; SHA512_HASH_WORDS],
+    /// The final hash of the previous block
+    /// Note: will be constrained using interactions with the chip itself
+    pub prev_hash: [[T; SHA512_WORD_U16S]; SHA512_HASH_WORDS],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512MessageScheduleCols<T> {
+    /// The message schedule words as 64-bit integers
+    /// The first 16 words will be the message data
+    pub w: [[T; SHA512_WORD_BITS]; SHA512_ROUNDS_PER_ROW],
+    /// Will be message schedule carries for rows 4..20 and a buffer for rows 0..4 to be used
+    /// freely by wrapper chips Note: carries are 2 bit numbers represented using 2 cells as
+    /// individual bits
+    pub carry_or_buffer: [[T; SHA512_WORD_U8S]; SHA512_ROUNDS_PER_ROW],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512WorkVarsCols<T> {
+    /// `a` and `e` after each iteration as 64-bits
+    pub a: [[T; SHA512_WORD_BITS]; SHA512_ROUNDS_PER_ROW],
+    pub e: [[T; SHA512_WORD_BITS]; SHA512_ROUNDS_PER_ROW],
+    /// The carry's used for addition during each iteration when computing `a` and `e`
+    pub carry_a: [[T; SHA512_WORD_U16S]; SHA512_ROUNDS_PER_ROW],
+    pub carry_e: [[T; SHA512_WORD_U16S]; SHA512_ROUNDS_PER_ROW],
+}
+
+/// These are the columns that are used to help with the message schedule additions
+/// Note: these need to be correctly assigned for every row even on padding rows
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512MessageHelperCols<T> {
+    /// The following are used to move data forward to constrain the message schedule additions
+    /// The value of `w` (message schedule word) from 3 rounds ago
+    /// In general, `w_i` means `w` from `i` rounds ago
+    pub w_3: [[T; SHA512_WORD_U16S]; SHA512_ROUNDS_PER_ROW - 1],
+    /// Here intermediate(i) =  w_i + sig_0(w_{i+1})
+    /// Intermed_t represents the intermediate t rounds ago
+    /// This is needed to constrain the message schedule, since we can only constrain on two rows
+    /// at a time
+    pub intermed_4: [[T; SHA512_WORD_U16S]; SHA512_ROUNDS_PER_ROW],
+    pub intermed_8: [[T; SHA512_WORD_U16S]; SHA512_ROUNDS_PER_ROW],
+    pub intermed_12: [[T; SHA512_WORD_U16S]; SHA512_ROUNDS_PER_ROW],
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512FlagsCols<T> {
+    /// A flag that indicates if the current row is among the first 20 rows of a block.
+    pub is_round_row: T,
+    /// A flag that indicates if the current row is among the first 4 rows of a block.
+    pub is_first_4_rows: T,
+    /// A flag that indicates if the current row is the last (21st) row of a block.
+    pub is_digest_row: T,
+    // A flag that indicates if the current row is the last block of the message.
+    // This flag is only used in digest rows.
+    pub is_last_block: T,
+    /// We will encode the row index [0..21] using 6 cells
+    pub row_idx: [T; SHA512_ROW_VAR_CNT],
+    /// The index of the current block in the trace starting at 1.
+    /// Set to 0 on padding rows.
+    pub global_block_idx: T,
+    /// The index of the current block in the current message starting at 0.
+    /// Resets after every message.
+    /// Set to 0 on padding rows.
+    pub local_block_idx: T,
+}
+
+impl<O, T: Copy + core::ops::Add<Output = O>> Sha512FlagsCols<T> {
+    // This refers to the padding rows that are added to the air to make the trace length a power of
+    // 2. Not to be confused with the padding added to messages as part of the SHA hash
+    // function.
+    pub fn is_not_padding_row(&self) -> O {
+        self.is_round_row + self.is_digest_row
+    }
+
+    // This refers to the padding rows that are added to the air to make the trace length a power of
+    // 2. Not to be confused with the padding added to messages as part of the SHA hash
+    // function.
+    pub fn is_padding_row(&self) -> O
+    where
+        O: FieldAlgebra,
+    {
+        not(self.is_not_padding_row())
+    }
+}
@@ -0,0 +1,17 @@
+//! Implementation of the SHA512 compression function without padding
+//! This this AIR doesn't constrain any of the message padding
+//! The same AIR computes SHA384, which only differs from SHA512 in the initial hash value, see
+//! [Sha512Variant]
+
+mod air;
+mod columns;
+mod trace;
+mod utils;
+
+pub use air::*;
+pub use columns::*;
+pub use trace::*;
+pub use utils::*;
+
+#[cfg(test)]
+mod tests;
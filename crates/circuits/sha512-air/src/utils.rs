@@ -0,0 +1,373 @@
+use std::array;
+
+pub use openvm_circuit_primitives::utils::compose;
+use openvm_circuit_primitives::{
+    encoder::Encoder,
+    utils::{not, select},
+};
+use openvm_stark_backend::{p3_air::AirBuilder, p3_field::FieldAlgebra};
+use rand::{rngs::StdRng, Rng};
+
+use super::{Sha512DigestCols, Sha512RoundCols};
+
+// ==== Do not change these constants! ====
+/// Number of bits in a SHA512 word
+pub const SHA512_WORD_BITS: usize = 64;
+/// Number of 16-bit limbs in a SHA512 word
+pub const SHA512_WORD_U16S: usize = SHA512_WORD_BITS / 16;
+/// Number of 8-bit limbs in a SHA512 word
+pub const SHA512_WORD_U8S: usize = SHA512_WORD_BITS / 8;
+/// Number of words in a SHA512 block
+pub const SHA512_BLOCK_WORDS: usize = 16;
+/// Number of cells in a SHA512 block
+pub const SHA512_BLOCK_U8S: usize = SHA512_BLOCK_WORDS * SHA512_WORD_U8S;
+/// Number of bits in a SHA512 block
+pub const SHA512_BLOCK_BITS: usize = SHA512_BLOCK_WORDS * SHA512_WORD_BITS;
+/// Number of rows per block
+pub const SHA512_ROWS_PER_BLOCK: usize = 21;
+/// Number of rounds per block
+pub const SHA512_ROUNDS_PER_BLOCK: usize = 80;
+/// Number of rounds per row
+pub const SHA512_ROUNDS_PER_ROW: usize = 4;
+/// Number of words in a SHA512 hash
+pub const SHA512_HASH_WORDS: usize = 8;
+/// Number of vars needed to encode the row index with [Encoder]
+pub const SHA512_ROW_VAR_CNT: usize = 6;
+/// Width of the Sha512RoundCols
+pub const SHA512_ROUND_WIDTH: usize = Sha512RoundCols::<u8>::width();
+/// Width of the Sha512DigestCols
+pub const SHA512_DIGEST_WIDTH: usize = Sha512DigestCols::<u8>::width();
+/// Size of the buffer of the first 4 rows of a block (each row's size)
+pub const SHA512_BUFFER_SIZE: usize = SHA512_ROUNDS_PER_ROW * SHA512_WORD_U16S * 2;
+/// Width of the Sha512Cols
+pub const SHA512_WIDTH: usize = if SHA512_ROUND_WIDTH > SHA512_DIGEST_WIDTH {
+    SHA512_ROUND_WIDTH
+} else {
+    SHA512_DIGEST_WIDTH
+};
+/// SHA512 constant K's
+pub const SHA512_K: [u64; 80] = [
+    0x428a2f98d728ae22,
+    0x7137449123ef65cd,
+    0xb5c0fbcfec4d3b2f,
+    0xe9b5dba58189dbbc,
+    0x3956c25bf348b538,
+    0x59f111f1b605d019,
+    0x923f82a4af194f9b,
+    0xab1c5ed5da6d8118,
+    0xd807aa98a3030242,
+    0x12835b0145706fbe,
+    0x243185be4ee4b28c,
+    0x550c7dc3d5ffb4e2,
+    0x72be5d74f27b896f,
+    0x80deb1fe3b1696b1,
+    0x9bdc06a725c71235,
+    0xc19bf174cf692694,
+    0xe49b69c19ef14ad2,
+    0xefbe4786384f25e3,
+    0x0fc19dc68b8cd5b5,
+    0x240ca1cc77ac9c65,
+    0x2de92c6f592b0275,
+    0x4a7484aa6ea6e483,
+    0x5cb0a9dcbd41fbd4,
+    0x76f988da831153b5,
+    0x983e5152ee66dfab,
+    0xa831c66d2db43210,
+    0xb00327c898fb213f,
+    0xbf597fc7beef0ee4,
+    0xc6e00bf33da88fc2,
+    0xd5a79147930aa725,
+    0x06ca6351e003826f,
+    0x142929670a0e6e70,
+    0x27b70a8546d22ffc,
+    0x2e1b21385c26c926,
+    0x4d2c6dfc5ac42aed,
+    0x53380d139d95b3df,
+    0x650a73548baf63de,
+    0x766a0abb3c77b2a8,
+    0x81c2c92e47edaee6,
+    0x92722c851482353b,
+    0xa2bfe8a14cf10364,
+    0xa81a664bbc423001,
+    0xc24b8b70d0f89791,
+    0xc76c51a30654be30,
+    0xd192e819d6ef5218,
+    0xd69906245565a910,
+    0xf40e35855771202a,
+    0x106aa07032bbd1b8,
+    0x19a4c116b8d2d0c8,
+    0x1e376c085141ab53,
+    0x2748774cdf8eeb99,
+    0x34b0bcb5e19b48a8,
+    0x391c0cb3c5c95a63,
+    0x4ed8aa4ae3418acb,
+    0x5b9cca4f7763e373,
+    0x682e6ff3d6b2b8a3,
+    0x748f82ee5defb2fc,
+    0x78a5636f43172f60,
+    0x84c87814a1f0ab72,
+    0x8cc702081a6439ec,
+    0x90befffa23631e28,
+    0xa4506cebde82bde9,
+    0xbef9a3f7b2c67915,
+    0xc67178f2e372532b,
+    0xca273eceea26619c,
+    0xd186b8c721c0c207,
+    0xeada7dd6cde0eb1e,
+    0xf57d4f7fee6ed178,
+    0x06f067aa72176fba,
+    0x0a637dc5a2c898a6,
+    0x113f9804bef90dae,
+    0x1b710b35131c471b,
+    0x28db77f523047d84,
+    0x32caab7b40c72493,
+    0x3c9ebe0a15c9bebc,
+    0x431d67c49c100d4c,
+    0x4cc5d4becb3e42b6,
+    0x597f299cfc657e2a,
+    0x5fcb6fab3ad6faec,
+    0x6c44198c4a475817,
+];
+
+/// SHA512 initial hash values
+pub const SHA512_H: [u64; 8] = [
+    0x6a09e667f3bcc908,
+    0xbb67ae8584caa73b,
+    0x3c6ef372fe94f82b,
+    0xa54ff53a5f1d36f1,
+    0x510e527fade682d1,
+    0x9b05688c2b3e6c1f,
+    0x1f83d9abfb41bd6b,
+    0x5be0cd19137e2179,
+];
+
+/// SHA384 initial hash values
+pub const SHA384_H: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+/// The hash functions computed by [crate::Sha512Air]. They only differ in the initial hash value
+/// and in the number of bytes of the final hash that make up the digest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sha512Variant {
+    Sha512,
+    Sha384,
+}
+
+impl Sha512Variant {
+    /// The initial hash value of the first block of a message
+    pub const fn initial_hash(&self) -> [u64; SHA512_HASH_WORDS] {
+        match self {
+            Self::Sha512 => SHA512_H,
+            Self::Sha384 => SHA384_H,
+        }
+    }
+
+    /// Number of bytes of the digest, which is a prefix of the final hash
+    pub const fn digest_size(&self) -> usize {
+        match self {
+            Self::Sha512 => 64,
+            Self::Sha384 => 48,
+        }
+    }
+}
+
+/// Convert a u64 into a list of limbs in little endian
+pub fn u64_into_limbs<const NUM_LIMBS: usize>(num: u64) -> [u32; NUM_LIMBS] {
+    let limb_bits = 64 / NUM_LIMBS;
+    array::from_fn(|i| ((num >> (limb_bits * i)) & ((1 << limb_bits) - 1)) as u32)
+}
+
+/// Convert a list of limbs in little endian into a u64
+pub fn limbs_into_u64<const NUM_LIMBS: usize>(limbs: [u32; NUM_LIMBS]) -> u64 {
+    let limb_bits = 64 / NUM_LIMBS;
+    limbs
+        .iter()
+        .rev()
+        .fold(0, |acc, &limb| (acc << limb_bits) | limb as u64)
+}
+
+/// Rotates `bits` right by `n` bits, assumes `bits` is in little-endian
+#[inline]
+pub(crate) fn rotr<F: FieldAlgebra + Clone>(
+    bits: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    n: usize,
+) -> [F; SHA512_WORD_BITS] {
+    array::from_fn(|i| bits[(i + n) % SHA512_WORD_BITS].clone().into())
+}
+
+/// Shifts `bits` right by `n` bits, assumes `bits` is in little-endian
+#[inline]
+pub(crate) fn shr<F: FieldAlgebra + Clone>(
+    bits: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    n: usize,
+) -> [F; SHA512_WORD_BITS] {
+    array::from_fn(|i| {
+        if i + n < SHA512_WORD_BITS {
+            bits[i + n].clone().into()
+        } else {
+            F::ZERO
+        }
+    })
+}
+
+/// Computes x ^ y ^ z, where x, y, z are assumed to be boolean
+#[inline]
+pub(crate) fn xor_bit<F: FieldAlgebra + Clone>(
+    x: impl Into<F>,
+    y: impl Into<F>,
+    z: impl Into<F>,
+) -> F {
+    let (x, y, z) = (x.into(), y.into(), z.into());
+    (x.clone() * y.clone() * z.clone())
+        + (x.clone() * not::<F>(y.clone()) * not::<F>(z.clone()))
+        + (not::<F>(x.clone()) * y.clone() * not::<F>(z.clone()))
+        + (not::<F>(x) * not::<F>(y) * z)
+}
+
+/// Computes x ^ y ^ z, where x, y, z are [SHA512_WORD_BITS] bit numbers
+#[inline]
+pub(crate) fn xor<F: FieldAlgebra + Clone>(
+    x: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    y: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    z: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+) -> [F; SHA512_WORD_BITS] {
+    array::from_fn(|i| xor_bit(x[i].clone(), y[i].clone(), z[i].clone()))
+}
+
+/// Choose function from SHA512
+#[inline]
+pub fn ch(x: u64, y: u64, z: u64) -> u64 {
+    (x & y) ^ ((!x) & z)
+}
+
+/// Computes Ch(x,y,z), where x, y, z are [SHA512_WORD_BITS] bit numbers
+#[inline]
+pub(crate) fn ch_field<F: FieldAlgebra>(
+    x: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    y: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    z: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+) -> [F; SHA512_WORD_BITS] {
+    array::from_fn(|i| select(x[i].clone(), y[i].clone(), z[i].clone()))
+}
+
+/// Majority function from SHA512
+pub fn maj(x: u64, y: u64, z: u64) -> u64 {
+    (x & y) ^ (x & z) ^ (y & z)
+}
+
+/// Computes Maj(x,y,z), where x, y, z are [SHA512_WORD_BITS] bit numbers
+#[inline]
+pub(crate) fn maj_field<F: FieldAlgebra + Clone>(
+    x: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    y: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+    z: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+) -> [F; SHA512_WORD_BITS] {
+    array::from_fn(|i| {
+        let (x, y, z) = (
+            x[i].clone().into(),
+            y[i].clone().into(),
+            z[i].clone().into(),
+        );
+        x.clone() * y.clone() + x.clone() * z.clone() + y.clone() * z.clone() - F::TWO * x * y * z
+    })
+}
+
+/// Big sigma_0 function from SHA512
+pub fn big_sig0(x: u64) -> u64 {
+    x.rotate_right(28) ^ x.rotate_right(34) ^ x.rotate_right(39)
+}
+
+/// Computes BigSigma0(x), where x is a [SHA512_WORD_BITS] bit number in little-endian
+#[inline]
+pub(crate) fn big_sig0_field<F: FieldAlgebra + Clone>(
+    x: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+) -> [F; SHA512_WORD_BITS] {
+    xor(&rotr::<F>(x, 28), &rotr::<F>(x, 34), &rotr::<F>(x, 39))
+}
+
+/// Big sigma_1 function from SHA512
+pub fn big_sig1(x: u64) -> u64 {
+    x.rotate_right(14) ^ x.rotate_right(18) ^ x.rotate_right(41)
+}
+
+/// Computes BigSigma1(x), where x is a [SHA512_WORD_BITS] bit number in little-endian
+#[inline]
+pub(crate) fn big_sig1_field<F: FieldAlgebra + Clone>(
+    x: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+) -> [F; SHA512_WORD_BITS] {
+    xor(&rotr::<F>(x, 14), &rotr::<F>(x, 18), &rotr::<F>(x, 41))
+}
+
+/// Small sigma_0 function from SHA512
+pub fn small_sig0(x: u64) -> u64 {
+    x.rotate_right(1) ^ x.rotate_right(8) ^ (x >> 7)
+}
+
+/// Computes SmallSigma0(x), where x is a [SHA512_WORD_BITS] bit number in little-endian
+#[inline]
+pub(crate) fn small_sig0_field<F: FieldAlgebra + Clone>(
+    x: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+) -> [F; SHA512_WORD_BITS] {
+    xor(&rotr::<F>(x, 1), &rotr::<F>(x, 8), &shr::<F>(x, 7))
+}
+
+/// Small sigma_1 function from SHA512
+pub fn small_sig1(x: u64) -> u64 {
+    x.rotate_right(19) ^ x.rotate_right(61) ^ (x >> 6)
+}
+
+/// Computes SmallSigma1(x), where x is a [SHA512_WORD_BITS] bit number in little-endian
+#[inline]
+pub(crate) fn small_sig1_field<F: FieldAlgebra + Clone>(
+    x: &[impl Into<F> + Clone; SHA512_WORD_BITS],
+) -> [F; SHA512_WORD_BITS] {
+    xor(&rotr::<F>(x, 19), &rotr::<F>(x, 61), &shr::<F>(x, 6))
+}
+
+/// Generate a random message of a given length
+pub fn get_random_message(rng: &mut StdRng, len: usize) -> Vec<u8> {
+    let mut random_message: Vec<u8> = vec![0u8; len];
+    rng.fill(&mut random_message[..]);
+    random_message
+}
+
+/// Wrapper of `get_flag_pt` to get the flag pointer as an array
+pub fn get_flag_pt_array<const N: usize>(encoder: &Encoder, flag_idx: usize) -> [u32; N] {
+    encoder.get_flag_pt(flag_idx).try_into().unwrap()
+}
+
+/// Constrain the addition of [SHA512_WORD_BITS] bit words in 16-bit limbs
+/// It takes in the terms some in bits some in 16-bit limbs,
+/// the expected sum in bits and the carries
+pub fn constraint_word_addition<AB: AirBuilder>(
+    builder: &mut AB,
+    terms_bits: &[&[impl Into<AB::Expr> + Clone; SHA512_WORD_BITS]],
+    terms_limb: &[&[impl Into<AB::Expr> + Clone; SHA512_WORD_U16S]],
+    expected_sum: &[impl Into<AB::Expr> + Clone; SHA512_WORD_BITS],
+    carries: &[impl Into<AB::Expr> + Clone; SHA512_WORD_U16S],
+) {
+    for i in 0..SHA512_WORD_U16S {
+        let mut limb_sum = if i == 0 {
+            AB::Expr::ZERO
+        } else {
+            carries[i - 1].clone().into()
+        };
+        for term in terms_bits {
+            limb_sum += compose::<AB::Expr>(&term[i * 16..(i + 1) * 16], 1);
+        }
+        for term in terms_limb {
+            limb_sum += term[i].clone().into();
+        }
+        let expected_sum_limb = compose::<AB::Expr>(&expected_sum[i * 16..(i + 1) * 16], 1)
+            + carries[i].clone().into() * AB::Expr::from_canonical_u32(1 << 16);
+        builder.assert_eq(limb_sum, expected_sum_limb);
+    }
+}
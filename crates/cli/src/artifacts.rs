@@ -0,0 +1,165 @@
+use std::{
+    fs::{create_dir_all, remove_dir_all, write, OpenOptions},
+    io::Write,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use eyre::Result;
+use openvm_sdk::{commit::AppExecutionCommit, StdIn};
+use serde::{Deserialize, Serialize};
+
+use crate::default::{DEFAULT_APP_PK_NAME, DEFAULT_APP_VK_NAME};
+
+pub const ARTIFACT_DIR_NAME: &str = ".openvm";
+
+/// The per-project directory `${manifest_dir}/.openvm` where the CLI keeps everything it
+/// generates:
+///
+/// - `exe/${profile}/`: executables and symbol sidecars written by `build`
+/// - `commits/`: `${target_name}.commit.json` written by `commit`
+/// - `keys/`: the app proving and verifying keys written by `keygen`
+/// - `proofs/`: `${target_name}.{app,stark,evm}.proof` written by `prove`
+/// - `partial/`: intermediate proofs of `prove`, reused by `prove --resume`
+/// - `logs/`: `${target_name}.prove.log` with the stages of each `prove` run
+///
+/// The directory ignores itself in git, and is removed by `clean`.
+#[derive(Clone, Debug)]
+pub struct ArtifactDir {
+    root: PathBuf,
+}
+
+impl ArtifactDir {
+    pub fn new(manifest_dir: impl AsRef<Path>) -> Self {
+        Self {
+            root: manifest_dir.as_ref().join(ARTIFACT_DIR_NAME),
+        }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Creates the directory, together with a `.gitignore` so that its contents are never
+    /// committed. Called before writing any artifact, as the SDK writers only create the parent
+    /// directories of the files they write.
+    pub fn create(&self) -> Result<()> {
+        create_dir_all(&self.root)?;
+        let gitignore = self.root.join(".gitignore");
+        if !gitignore.exists() {
+            write(gitignore, "*\n")?;
+        }
+        Ok(())
+    }
+
+    pub fn exe_dir(&self, profile: &str) -> PathBuf {
+        self.root.join("exe").join(profile)
+    }
+
+    pub fn commit_path(&self, target_name: &str) -> PathBuf {
+        self.root
+            .join("commits")
+            .join(format!("{}.commit.json", target_name))
+    }
+
+    pub fn keys_dir(&self) -> PathBuf {
+        self.root.join("keys")
+    }
+
+    pub fn app_pk_path(&self) -> PathBuf {
+        self.keys_dir().join(DEFAULT_APP_PK_NAME)
+    }
+
+    pub fn app_vk_path(&self) -> PathBuf {
+        self.keys_dir().join(DEFAULT_APP_VK_NAME)
+    }
+
+    pub fn proofs_dir(&self) -> PathBuf {
+        self.root.join("proofs")
+    }
+
+    /// Path of the proof of `target_name`, where `kind` is one of `app`, `stark` or `evm`.
+    pub fn proof_path(&self, target_name: &str, kind: &str) -> PathBuf {
+        self.proofs_dir()
+            .join(format!("{}.{}.proof", target_name, kind))
+    }
+
+    pub fn partial_dir(&self) -> PathBuf {
+        self.root.join("partial")
+    }
+
+    pub fn partial_app_proof_path(&self, target_name: &str) -> PathBuf {
+        self.partial_dir()
+            .join(format!("{}.app.proof", target_name))
+    }
+
+    /// Path of the [ProofFingerprint] of the partial app proof of `target_name`.
+    pub fn partial_app_fingerprint_path(&self, target_name: &str) -> PathBuf {
+        self.partial_dir()
+            .join(format!("{}.app.fingerprint.json", target_name))
+    }
+
+    pub fn log_path(&self, target_name: &str) -> PathBuf {
+        self.root
+            .join("logs")
+            .join(format!("{}.prove.log", target_name))
+    }
+
+    /// Prints `message` and appends it to the prove log of `target_name`, prefixed with the
+    /// current UNIX time in seconds.
+    pub fn log(&self, target_name: &str, message: &str) -> Result<()> {
+        println!("[openvm] {}", message);
+        let path = self.log_path(target_name);
+        create_dir_all(path.parent().unwrap())?;
+        let time = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "[{}] {}", time, message)?;
+        Ok(())
+    }
+
+    /// Removes the partial proofs only if `partial_only` is set, and otherwise everything except
+    /// the keys, unless `keys` is also set. Returns the removed directories.
+    pub fn clean(&self, partial_only: bool, keys: bool) -> Result<Vec<PathBuf>> {
+        if !self.root.exists() {
+            return Ok(vec![]);
+        }
+        let dirs = if partial_only {
+            vec![self.partial_dir()]
+        } else if keys {
+            vec![self.root.clone()]
+        } else {
+            ["exe", "commits", "proofs", "partial", "logs"]
+                .iter()
+                .map(|dir| self.root.join(dir))
+                .collect()
+        };
+        let mut removed = vec![];
+        for dir in dirs {
+            if dir.exists() {
+                remove_dir_all(&dir)?;
+                removed.push(dir);
+            }
+        }
+        Ok(removed)
+    }
+}
+
+/// What a proof generated by `prove` depends on besides the proving keys. A proof in the artifact
+/// directory is only reused by `prove --resume` if its fingerprint matches the current one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofFingerprint {
+    pub app_exe_commit: String,
+    pub app_vm_commit: String,
+    /// SHA-256 digest of the input streams and key-value store of the [StdIn].
+    pub input_digest: String,
+}
+
+impl ProofFingerprint {
+    pub fn new(commits: &AppExecutionCommit, stdin: &StdIn) -> Self {
+        Self {
+            app_exe_commit: hex::encode(commits.app_exe_commit.as_slice()),
+            app_vm_commit: hex::encode(commits.app_vm_commit.as_slice()),
+            input_digest: hex::encode(stdin.digest()),
+        }
+    }
+}
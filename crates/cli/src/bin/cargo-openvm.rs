@@ -22,13 +22,16 @@ pub struct VmCli {
 #[allow(clippy::large_enum_variant)]
 pub enum VmCliCommands {
     Build(BuildCmd),
+    Clean(CleanCmd),
     Commit(CommitCmd),
     Keygen(KeygenCmd),
     Init(InitCmd),
+    Profile(ProfileCmd),
     Prove(ProveCmd),
     Run(RunCmd),
     #[cfg(feature = "evm-verify")]
     Setup(SetupCmd),
+    Stress(StressCmd),
     Verify(VerifyCmd),
 }
 
@@ -39,13 +42,16 @@ async fn main() -> Result<()> {
     setup_tracing_with_log_level(Level::WARN);
     match command {
         VmCliCommands::Build(cmd) => cmd.run(),
+        VmCliCommands::Clean(cmd) => cmd.run(),
         VmCliCommands::Commit(cmd) => cmd.run(),
         VmCliCommands::Keygen(cmd) => cmd.run(),
         VmCliCommands::Init(cmd) => cmd.run(),
+        VmCliCommands::Profile(cmd) => cmd.run(),
         VmCliCommands::Prove(cmd) => cmd.run(),
         VmCliCommands::Run(cmd) => cmd.run(),
         #[cfg(feature = "evm-verify")]
         VmCliCommands::Setup(cmd) => cmd.run().await,
+        VmCliCommands::Stress(cmd) => cmd.run(),
         VmCliCommands::Verify(cmd) => cmd.run(),
     }
 }
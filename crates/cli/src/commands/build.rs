@@ -8,14 +8,23 @@ use clap::Parser;
 use eyre::Result;
 use itertools::izip;
 use openvm_build::{
-    build_generic, get_package, get_workspace_packages, get_workspace_root, GuestOptions,
+    build_generic, find_unsupported_std_features, get_package, get_workspace_packages,
+    get_workspace_root, strip_elf, GuestOptions,
 };
 use openvm_circuit::arch::{InitFileGenerator, OPENVM_DEFAULT_INIT_FILE_NAME};
-use openvm_sdk::{fs::write_exe_to_file, Sdk};
-use openvm_transpiler::{elf::Elf, openvm_platform::memory::MEM_SIZE};
+use openvm_sdk::{
+    fs::{write_exe_to_file, write_symbols_to_file},
+    symbols::{SymbolSidecar, SYMBOL_SIDECAR_EXTENSION},
+    Sdk,
+};
+use openvm_transpiler::{
+    elf::{decode_fn_bounds, decode_source_map, Elf},
+    openvm_platform::memory::MEM_SIZE,
+};
 
-use crate::util::{
-    get_manifest_path_and_dir, get_target_dir, get_target_output_dir, read_config_toml_or_default,
+use crate::{
+    artifacts::ArtifactDir,
+    util::{get_manifest_path_and_dir, get_target_dir, read_config_toml_or_default},
 };
 
 #[derive(Parser)]
@@ -65,6 +74,41 @@ pub struct BuildArgs {
         help_heading = "OpenVM Options"
     )]
     pub init_file_name: String,
+
+    #[arg(
+        long,
+        help = "Strips debug info and other sections that are not loaded into memory from the ELFs before transpilation, keeping the original ELFs as ${elf}.debug and writing a ${target_name}.ovmsym symbol sidecar next to each executable",
+        help_heading = "OpenVM Options"
+    )]
+    pub strip: bool,
+
+    #[arg(
+        long,
+        help = "Keeps the function symbols and DWARF line info of the ELFs in the executables, so that execution errors and profiles report source locations; the program must be built with debug info, e.g. `debug = \"line-tables-only\"` in the profile",
+        help_heading = "OpenVM Options"
+    )]
+    pub debug_info: bool,
+
+    #[arg(
+        long,
+        help = "Stack size of the guest in bytes, which must be a multiple of 16 and at most 2098176; defaults to 2097152",
+        help_heading = "OpenVM Options"
+    )]
+    pub stack_size: Option<u32>,
+
+    #[arg(
+        long,
+        help = "Removes the instructions which cannot be reached from the entry point of the program during transpilation, which reduces the program commitment size and keygen time; the program must not compute code addresses with relative jump tables",
+        help_heading = "OpenVM Options"
+    )]
+    pub eliminate_dead_code: bool,
+
+    #[arg(
+        long,
+        help = "Prints the number of instructions of each function of the transpiled executables",
+        help_heading = "OpenVM Options"
+    )]
+    pub size_report: bool,
 }
 
 impl Default for BuildArgs {
@@ -74,6 +118,11 @@ impl Default for BuildArgs {
             config: None,
             output_dir: None,
             init_file_name: OPENVM_DEFAULT_INIT_FILE_NAME.to_string(),
+            strip: false,
+            debug_info: false,
+            stack_size: None,
+            eliminate_dead_code: false,
+            size_report: false,
         }
     }
 }
@@ -297,6 +346,7 @@ pub fn build(build_args: &BuildArgs, cargo_args: &BuildCargoArgs) -> Result<Path
         .with_rustc_flags(var("RUSTFLAGS").unwrap_or_default().split_whitespace());
 
     guest_options.target_dir = Some(target_dir.clone());
+    guest_options.stack_size = build_args.stack_size;
     guest_options
         .options
         .push(format!("--color={}", cargo_args.color));
@@ -373,13 +423,16 @@ pub fn build(build_args: &BuildArgs, cargo_args: &BuildCargoArgs) -> Result<Path
         if build_args.output_dir.is_some() {
             println!("[openvm] WARNING: Output directory set but transpilation skipped");
         }
+        if build_args.strip {
+            println!("[openvm] WARNING: ELFs are only stripped when transpiling");
+        }
         return Ok(elf_target_dir);
     }
 
     // Get all built packages
     let workspace_root = get_workspace_root(&manifest_path);
     let packages = if cargo_args.workspace || manifest_dir == workspace_root {
-        get_workspace_packages(manifest_dir)
+        get_workspace_packages(&manifest_dir)
             .into_iter()
             .filter(|pkg| {
                 (cargo_args.package.is_empty() || cargo_args.package.contains(&pkg.name))
@@ -387,7 +440,7 @@ pub fn build(build_args: &BuildArgs, cargo_args: &BuildCargoArgs) -> Result<Path
             })
             .collect()
     } else {
-        vec![get_package(manifest_dir)]
+        vec![get_package(&manifest_dir)]
     };
 
     // Find elf paths of all targets for all built packages
@@ -424,28 +477,77 @@ pub fn build(build_args: &BuildArgs, cargo_args: &BuildCargoArgs) -> Result<Path
         })
         .collect::<Vec<_>>();
 
-    // Transpile, storing in ${target_dir}/openvm/${profile} by default
-    let target_output_dir = get_target_output_dir(&target_dir, &cargo_args.profile);
+    // Transpile, storing in ${manifest_dir}/.openvm/exe/${profile} by default
+    let artifact_dir = ArtifactDir::new(&manifest_dir);
+    artifact_dir.create()?;
+    let target_output_dir = artifact_dir.exe_dir(&cargo_args.profile);
 
     println!("[openvm] Transpiling the package...");
     for (elf_path, target) in izip!(&elf_paths, &elf_targets) {
-        let transpiler = app_config.app_vm_config.transpiler();
+        // Stripping removes the symbols needed to find the unsupported features.
+        for feature in find_unsupported_std_features(&read(elf_path)?)? {
+            println!(
+                "[openvm] WARNING: {} uses a feature of std which fails in the zkVM: {feature}",
+                elf_path.display()
+            );
+        }
+        // The unstripped ELF is kept next to the stripped one, also if it was stripped before.
+        let debug_elf_path = if build_args.strip {
+            Some(strip_elf(elf_path)?)
+        } else {
+            None
+        };
+        let mut transpiler = app_config.transpiler()?;
+        if build_args.eliminate_dead_code {
+            transpiler = transpiler.with_dead_code_elimination();
+        }
         let data = read(elf_path.clone())?;
-        let elf = Elf::decode(&data, MEM_SIZE as u32)?;
-        let exe = Sdk::new().transpile(elf, transpiler)?;
+        let elf = if build_args.debug_info {
+            Elf::decode_with_debug_info(&data, MEM_SIZE as u32)?
+        } else {
+            Elf::decode(&data, MEM_SIZE as u32)?
+        };
+        elf.check_program_len(app_config.app_vm_config.system.config.max_program_len)?;
+        let sdk = Sdk::new();
+        sdk.check_extension_versions(&elf, &app_config.app_vm_config)?;
+        let mut exe = sdk.transpile(elf, transpiler)?;
+        if let (true, Some(debug_elf_path)) = (build_args.debug_info, &debug_elf_path) {
+            // The debug info was stripped, so it is read from the unstripped ELF.
+            let debug_data = read(debug_elf_path)?;
+            exe.fn_bounds = decode_fn_bounds(&debug_data)?;
+            exe.source_map = decode_source_map(&debug_data)?;
+        }
+        if build_args.size_report {
+            println!("[openvm] {}: {}", elf_path.display(), exe.size_report());
+        }
 
         let target_name = if target.is_example() {
             &format!("examples/{}", target.name)
         } else {
             &target.name
         };
-        let file_name = format!("{}.vmexe", target_name);
-        let file_path = target_output_dir.join(&file_name);
+        let mut file_names = vec![format!("{}.vmexe", target_name)];
+        if let Some(debug_elf_path) = debug_elf_path {
+            let symbols = SymbolSidecar::new(
+                &read(debug_elf_path)?,
+                &exe,
+                app_config.app_fri_params.fri_params,
+                &app_config.app_vm_config.system.config.memory_config,
+            )?;
+            let file_name = format!("{}.{}", target_name, SYMBOL_SIDECAR_EXTENSION);
+            write_symbols_to_file(&symbols, target_output_dir.join(&file_name))?;
+            file_names.push(file_name);
+        }
+        write_exe_to_file(exe, target_output_dir.join(&file_names[0]))?;
 
-        write_exe_to_file(exe, &file_path)?;
         if let Some(output_dir) = &build_args.output_dir {
             create_dir_all(output_dir)?;
-            copy(file_path, output_dir.join(file_name))?;
+            for file_name in file_names {
+                copy(
+                    target_output_dir.join(&file_name),
+                    output_dir.join(file_name),
+                )?;
+            }
         }
     }
 
@@ -0,0 +1,47 @@
+use clap::Parser;
+use eyre::Result;
+
+use super::KeygenCargoArgs;
+use crate::util::get_artifact_dir;
+
+#[derive(Parser)]
+#[command(
+    name = "clean",
+    about = "Remove the artifacts generated by OpenVM in ${manifest_dir}/.openvm"
+)]
+pub struct CleanCmd {
+    #[arg(
+        long,
+        help = "Only remove the partial proofs kept for 'cargo openvm prove --resume'",
+        help_heading = "OpenVM Options"
+    )]
+    partial: bool,
+
+    #[arg(
+        long,
+        conflicts_with = "partial",
+        help = "Also remove the app proving and verifying keys, which are kept by default",
+        help_heading = "OpenVM Options"
+    )]
+    keys: bool,
+
+    #[command(flatten)]
+    cargo_args: KeygenCargoArgs,
+}
+
+impl CleanCmd {
+    pub fn run(&self) -> Result<()> {
+        let artifact_dir = get_artifact_dir(&self.cargo_args.manifest_path)?;
+        let removed = artifact_dir.clean(self.partial, self.keys)?;
+        if removed.is_empty() {
+            println!(
+                "[openvm] Nothing to clean in {}",
+                artifact_dir.root().display()
+            );
+        }
+        for dir in removed {
+            println!("[openvm] Removed {}", dir.display());
+        }
+        Ok(())
+    }
+}
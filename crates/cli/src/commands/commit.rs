@@ -1,17 +1,25 @@
 use std::{
-    fs::{copy, create_dir_all},
+    fs::{copy, create_dir_all, write},
     path::PathBuf,
 };
 
 use clap::Parser;
 use eyre::Result;
 use openvm_circuit::arch::OPENVM_DEFAULT_INIT_FILE_NAME;
-use openvm_sdk::{commit::AppExecutionCommit, fs::write_to_file_json, Sdk};
+use openvm_sdk::{
+    commit::AppExecutionCommit,
+    fs::{
+        read_from_file_json, write_evm_proof_lib_to_folder, write_to_file_json, EVM_PROOF_LIB_NAME,
+    },
+    proof_lib::{generate_openvm_proof_lib, PublicValuesSchema},
+    Sdk,
+};
 
 use super::{RunArgs, RunCargoArgs};
 use crate::{
     commands::{load_app_pk, load_or_build_and_commit_exe},
-    util::{get_manifest_path_and_dir, get_target_dir, get_target_output_dir},
+    default::default_evm_halo2_verifier_path,
+    util::get_artifact_dir,
 };
 
 #[derive(Parser)]
@@ -23,7 +31,7 @@ pub struct CommitCmd {
     #[arg(
         long,
         action,
-        help = "Path to app proving key, by default will be ${target_dir}/openvm/app.pk",
+        help = "Path to app proving key, by default will be ${manifest_dir}/.openvm/keys/app.pk",
         help_heading = "OpenVM Options"
     )]
     pub app_pk: Option<PathBuf>,
@@ -58,6 +66,14 @@ pub struct CommitCmd {
     )]
     pub init_file_name: String,
 
+    #[arg(
+        long,
+        action,
+        help = "Path to a JSON ABI schema of the public values, if specified the OpenVmProofLib Solidity library will be generated next to the verifier contract in ~/.openvm/halo2/",
+        help_heading = "OpenVM Options"
+    )]
+    pub abi_schema: Option<PathBuf>,
+
     #[command(flatten)]
     cargo_args: RunCargoArgs,
 }
@@ -85,12 +101,10 @@ impl CommitCmd {
         println!("exe commit: {:?}", commits.app_exe_commit.to_bn254());
         println!("vm commit: {:?}", commits.app_vm_commit.to_bn254());
 
-        let (manifest_path, _) = get_manifest_path_and_dir(&self.cargo_args.manifest_path)?;
-        let target_dir = get_target_dir(&self.cargo_args.target_dir, &manifest_path);
-        let target_output_dir = get_target_output_dir(&target_dir, &self.cargo_args.profile);
-
+        let artifact_dir = get_artifact_dir(&self.cargo_args.manifest_path)?;
+        artifact_dir.create()?;
         let commit_name = format!("{}.commit.json", &target_name);
-        let commit_path = target_output_dir.join(&commit_name);
+        let commit_path = artifact_dir.commit_path(&target_name);
 
         write_to_file_json(&commit_path, commits)?;
         if let Some(output_dir) = &self.output_dir {
@@ -98,6 +112,15 @@ impl CommitCmd {
             copy(commit_path, output_dir.join(commit_name))?;
         }
 
+        if let Some(abi_schema) = &self.abi_schema {
+            let schema: PublicValuesSchema = read_from_file_json(abi_schema)?;
+            let proof_lib = generate_openvm_proof_lib(&schema, &commits)?;
+            write_evm_proof_lib_to_folder(&proof_lib, default_evm_halo2_verifier_path())?;
+            if let Some(output_dir) = &self.output_dir {
+                write(output_dir.join(EVM_PROOF_LIB_NAME), proof_lib)?;
+            }
+        }
+
         Ok(())
     }
 }
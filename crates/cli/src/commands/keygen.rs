@@ -11,11 +11,9 @@ use openvm_sdk::{
 };
 
 use crate::{
+    artifacts::ArtifactDir,
     default::{DEFAULT_APP_PK_NAME, DEFAULT_APP_VK_NAME},
-    util::{
-        get_app_pk_path, get_app_vk_path, get_manifest_path_and_dir, get_target_dir,
-        read_config_toml_or_default,
-    },
+    util::{get_manifest_path_and_dir, read_config_toml_or_default},
 };
 
 #[derive(Parser)]
@@ -41,14 +39,6 @@ pub struct KeygenCmd {
 
 #[derive(Parser)]
 pub struct KeygenCargoArgs {
-    #[arg(
-        long,
-        value_name = "DIR",
-        help = "Directory for all Cargo-generated artifacts and intermediate files",
-        help_heading = "Cargo Options"
-    )]
-    pub(crate) target_dir: Option<PathBuf>,
-
     #[arg(
         long,
         value_name = "PATH",
@@ -60,11 +50,11 @@ pub struct KeygenCargoArgs {
 
 impl KeygenCmd {
     pub fn run(&self) -> Result<()> {
-        let (manifest_path, manifest_dir) =
-            get_manifest_path_and_dir(&self.cargo_args.manifest_path)?;
-        let target_dir = get_target_dir(&self.cargo_args.target_dir, &manifest_path);
-        let app_pk_path = get_app_pk_path(&target_dir);
-        let app_vk_path = get_app_vk_path(&target_dir);
+        let (_, manifest_dir) = get_manifest_path_and_dir(&self.cargo_args.manifest_path)?;
+        let artifact_dir = ArtifactDir::new(&manifest_dir);
+        artifact_dir.create()?;
+        let app_pk_path = artifact_dir.app_pk_path();
+        let app_vk_path = artifact_dir.app_vk_path();
 
         keygen(
             self.config
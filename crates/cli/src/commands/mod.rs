@@ -1,6 +1,9 @@
 mod build;
 pub use build::*;
 
+mod clean;
+pub use clean::*;
+
 mod commit;
 pub use commit::*;
 
@@ -10,12 +13,18 @@ pub use keygen::*;
 mod init;
 pub use init::*;
 
+mod profile;
+pub use profile::*;
+
 mod prove;
 pub use prove::*;
 
 mod run;
 pub use run::*;
 
+mod stress;
+pub use stress::*;
+
 #[cfg(feature = "evm-verify")]
 mod setup;
 #[cfg(feature = "evm-verify")]
@@ -0,0 +1,120 @@
+use std::{fs::write, path::PathBuf};
+
+use clap::Parser;
+use eyre::{bail, Result};
+use openvm_sdk::{profiler::ProfileReport, Sdk};
+
+use super::{load_exe_and_config, RunArgs, RunCargoArgs};
+use crate::input::read_to_stdin;
+
+#[derive(Parser)]
+#[command(
+    name = "profile",
+    about = "Execute an OpenVM program without proving it and report where its cycles are spent"
+)]
+pub struct ProfileCmd {
+    #[clap(flatten)]
+    run_args: RunArgs,
+
+    #[clap(flatten)]
+    cargo_args: RunCargoArgs,
+
+    #[arg(
+        long,
+        default_value_t = 20,
+        help = "Number of opcodes, regions and functions to print, by decreasing cycles",
+        help_heading = "Profile Options"
+    )]
+    top: usize,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write the cycles of the functions along their call stacks to a speedscope JSON file, which requires the function symbols of the program",
+        help_heading = "Profile Options"
+    )]
+    speedscope: Option<PathBuf>,
+}
+
+impl ProfileCmd {
+    pub fn run(&self) -> Result<()> {
+        let (exe, app_config) = load_exe_and_config(&self.run_args, &self.cargo_args)?;
+        let report = Sdk::new().profile(
+            exe,
+            app_config.app_vm_config,
+            read_to_stdin(&self.run_args.input)?,
+        )?;
+        print_report(&report, self.top);
+        if let Some(path) = &self.speedscope {
+            let name = path
+                .file_stem()
+                .map_or("openvm".into(), |stem| stem.to_string_lossy());
+            let Some(speedscope) = report.to_speedscope(&name) else {
+                bail!("the program has no function symbols, build it without --strip");
+            };
+            write(path, serde_json::to_string(&speedscope)?)?;
+            println!(
+                "[openvm] Wrote the speedscope profile to {}",
+                path.display()
+            );
+        }
+        Ok(())
+    }
+}
+
+fn print_report(report: &ProfileReport, top: usize) {
+    let percent = |cycles: usize| 100.0 * cycles as f64 / report.total_cycles.max(1) as f64;
+    println!("Total cycles: {}", report.total_cycles);
+    println!("Segments:     {}", report.utilization.len());
+
+    println!("Opcodes:");
+    println!("  {:>12} {:>6}  opcode", "cycles", "%");
+    for (extension, opcode, count) in report.opcode_histogram().into_iter().take(top) {
+        println!(
+            "  {count:>12} {:>6.2}  {opcode} ({extension})",
+            percent(count)
+        );
+    }
+
+    println!("Chip rows:");
+    println!("  {:>12}  chip", "rows");
+    let mut chip_rows: Vec<_> = report.chip_rows().into_iter().collect();
+    chip_rows.sort_by(|a, b| b.1.cmp(&a.1));
+    for (air_name, rows) in chip_rows.into_iter().filter(|(_, rows)| *rows > 0) {
+        println!("  {rows:>12}  {air_name}");
+    }
+
+    if !report.regions.is_empty() {
+        println!("Regions:");
+        println!("  {:>12} {:>6} {:>8}  region", "cycles", "%", "count");
+        for region in report.regions.regions().into_iter().take(top) {
+            println!(
+                "  {:>12} {:>6.2} {:>8}  {}",
+                region.total_cycles,
+                percent(region.total_cycles as usize),
+                region.count,
+                region.name
+            );
+        }
+    }
+
+    let Some(profile) = &report.functions else {
+        println!("[openvm] No function profile was recorded, the program has no function symbols");
+        return;
+    };
+    println!("Functions:");
+    println!(
+        "  {:>12} {:>6} {:>12} {:>6}  function",
+        "self", "%", "total", "%"
+    );
+    for function in profile.functions().into_iter().take(top) {
+        println!(
+            "  {:>12} {:>6.2} {:>12} {:>6.2}  {}",
+            function.self_cycles,
+            percent(function.self_cycles),
+            function.total_cycles,
+            percent(function.total_cycles),
+            function.name
+        );
+    }
+}
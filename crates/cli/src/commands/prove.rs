@@ -1,27 +1,30 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{fs::remove_file, path::PathBuf, sync::Arc, time::Instant};
 
 use clap::Parser;
 use eyre::Result;
+use openvm_circuit::arch::ContinuationVmProof;
 #[cfg(feature = "evm-prove")]
 use openvm_sdk::fs::write_evm_proof_to_file;
 use openvm_sdk::{
     commit::AppExecutionCommit,
-    config::{AggregationTreeConfig, SdkVmConfig},
+    config::{AggregationTreeConfig, ProverPerfConfig, SdkVmConfig},
     fs::{
-        read_agg_stark_pk_from_file, read_app_pk_from_file, read_exe_from_file,
-        write_app_proof_to_file, write_to_file_json,
+        read_agg_stark_pk_from_file, read_app_pk_from_file, read_app_proof_from_file,
+        read_exe_from_file_checked, read_from_file_json, write_app_proof_to_file,
+        write_to_file_json,
     },
     keygen::AppProvingKey,
-    types::VmStarkProofBytes,
-    NonRootCommittedExe, Sdk,
+    types::{ProofMetadata, VmStarkProofBytes},
+    NonRootCommittedExe, Sdk, StdIn, SC,
 };
 
 use super::{RunArgs, RunCargoArgs};
 use crate::{
+    artifacts::{ArtifactDir, ProofFingerprint},
     commands::build,
     default::default_agg_stark_pk_path,
     input::read_to_stdin,
-    util::{get_app_pk_path, get_manifest_path_and_dir, get_single_target_name, get_target_dir},
+    util::{get_artifact_dir, get_single_target_name},
 };
 #[cfg(feature = "evm-prove")]
 use crate::{default::default_params_dir, util::read_default_agg_pk};
@@ -39,7 +42,7 @@ enum ProveSubCommand {
         #[arg(
             long,
             action,
-            help = "Path to app proof output, by default will be ./${bin_name}.app.proof",
+            help = "Path to app proof output, by default will be ${manifest_dir}/.openvm/proofs/${bin_name}.app.proof",
             help_heading = "Output"
         )]
         proof: Option<PathBuf>,
@@ -47,22 +50,33 @@ enum ProveSubCommand {
         #[arg(
             long,
             action,
-            help = "Path to app proving key, by default will be ${target_dir}/openvm/app.pk",
+            help = "Path to app proving key, by default will be ${manifest_dir}/.openvm/keys/app.pk",
             help_heading = "OpenVM Options"
         )]
         app_pk: Option<PathBuf>,
 
+        #[arg(
+            long,
+            action,
+            help = "Reuse the app proof of an earlier run in ${manifest_dir}/.openvm/partial if it was generated for the same executable, VM and input",
+            help_heading = "OpenVM Options"
+        )]
+        resume: bool,
+
         #[command(flatten)]
         run_args: RunArgs,
 
         #[command(flatten)]
         cargo_args: RunCargoArgs,
+
+        #[command(flatten)]
+        perf_config: ProverPerfConfig,
     },
     Stark {
         #[arg(
             long,
             action,
-            help = "Path to STARK proof output, by default will be ./${bin_name}.stark.proof",
+            help = "Path to STARK proof output, by default will be ${manifest_dir}/.openvm/proofs/${bin_name}.stark.proof",
             help_heading = "Output"
         )]
         proof: Option<PathBuf>,
@@ -70,11 +84,19 @@ enum ProveSubCommand {
         #[arg(
             long,
             action,
-            help = "Path to app proving key, by default will be ${target_dir}/openvm/app.pk",
+            help = "Path to app proving key, by default will be ${manifest_dir}/.openvm/keys/app.pk",
             help_heading = "OpenVM Options"
         )]
         app_pk: Option<PathBuf>,
 
+        #[arg(
+            long,
+            action,
+            help = "Reuse the app proof of an earlier run in ${manifest_dir}/.openvm/partial if it was generated for the same executable, VM and input",
+            help_heading = "OpenVM Options"
+        )]
+        resume: bool,
+
         #[command(flatten)]
         run_args: RunArgs,
 
@@ -83,13 +105,16 @@ enum ProveSubCommand {
 
         #[command(flatten)]
         agg_tree_config: AggregationTreeConfig,
+
+        #[command(flatten)]
+        perf_config: ProverPerfConfig,
     },
     #[cfg(feature = "evm-prove")]
     Evm {
         #[arg(
             long,
             action,
-            help = "Path to EVM proof output, by default will be ./${bin_name}.evm.proof",
+            help = "Path to EVM proof output, by default will be ${manifest_dir}/.openvm/proofs/${bin_name}.evm.proof",
             help_heading = "Output"
         )]
         proof: Option<PathBuf>,
@@ -97,11 +122,19 @@ enum ProveSubCommand {
         #[arg(
             long,
             action,
-            help = "Path to app proving key, by default will be ${target_dir}/openvm/app.pk",
+            help = "Path to app proving key, by default will be ${manifest_dir}/.openvm/keys/app.pk",
             help_heading = "OpenVM Options"
         )]
         app_pk: Option<PathBuf>,
 
+        #[arg(
+            long,
+            action,
+            help = "Reuse the app proof of an earlier run in ${manifest_dir}/.openvm/partial if it was generated for the same executable, VM and input",
+            help_heading = "OpenVM Options"
+        )]
+        resume: bool,
+
         #[command(flatten)]
         run_args: RunArgs,
 
@@ -110,6 +143,9 @@ enum ProveSubCommand {
 
         #[command(flatten)]
         agg_tree_config: AggregationTreeConfig,
+
+        #[command(flatten)]
+        perf_config: ProverPerfConfig,
     },
 }
 
@@ -119,35 +155,56 @@ impl ProveCmd {
             ProveSubCommand::App {
                 app_pk,
                 proof,
+                resume,
                 run_args,
                 cargo_args,
+                perf_config,
             } => {
-                let sdk = Sdk::new();
+                let sdk = Sdk::new().with_prover_perf_config(*perf_config);
                 let app_pk = load_app_pk(app_pk, cargo_args)?;
                 let (committed_exe, target_name) =
                     load_or_build_and_commit_exe(&sdk, run_args, cargo_args, &app_pk)?;
+                let artifact_dir = get_artifact_dir(&cargo_args.manifest_path)?;
+                artifact_dir.create()?;
 
-                let app_proof =
-                    sdk.generate_app_proof(app_pk, committed_exe, read_to_stdin(&run_args.input)?)?;
+                let app_proof = load_or_generate_app_proof(
+                    &sdk,
+                    app_pk,
+                    committed_exe,
+                    read_to_stdin(&run_args.input)?,
+                    &artifact_dir,
+                    &target_name,
+                    *resume,
+                )?;
 
                 let proof_path = if let Some(proof) = proof {
                     proof
                 } else {
-                    &PathBuf::from(format!("{}.app.proof", target_name))
+                    &artifact_dir.proof_path(&target_name, "app")
                 };
                 write_app_proof_to_file(app_proof, proof_path)?;
+                artifact_dir.log(
+                    &target_name,
+                    &format!("Wrote app proof to {}", proof_path.display()),
+                )?;
             }
             ProveSubCommand::Stark {
                 app_pk,
                 proof,
+                resume,
                 run_args,
                 cargo_args,
                 agg_tree_config,
+                perf_config,
             } => {
-                let sdk = Sdk::new().with_agg_tree_config(*agg_tree_config);
+                let sdk = Sdk::new()
+                    .with_agg_tree_config(*agg_tree_config)
+                    .with_prover_perf_config(*perf_config);
                 let app_pk = load_app_pk(app_pk, cargo_args)?;
                 let (committed_exe, target_name) =
                     load_or_build_and_commit_exe(&sdk, run_args, cargo_args, &app_pk)?;
+                let artifact_dir = get_artifact_dir(&cargo_args.manifest_path)?;
+                artifact_dir.create()?;
 
                 let commits = AppExecutionCommit::compute(
                     &app_pk.app_vm_pk.vm_config,
@@ -156,40 +213,62 @@ impl ProveCmd {
                 );
                 println!("exe commit: {:?}", commits.app_exe_commit.to_bn254());
                 println!("vm commit: {:?}", commits.app_vm_commit.to_bn254());
+                write_to_file_json(artifact_dir.commit_path(&target_name), commits)?;
 
                 let agg_stark_pk = read_agg_stark_pk_from_file(default_agg_stark_pk_path()).map_err(|e| {
                     eyre::eyre!("Failed to read aggregation proving key: {}\nPlease run 'cargo openvm setup' first", e)
                 })?;
-                let stark_proof = sdk.generate_e2e_stark_proof(
-                    app_pk,
+                app_pk.check_agg_compatibility(&agg_stark_pk).map_err(|e| {
+                    eyre::eyre!("{}\nPlease run 'cargo openvm setup --config <app config>' with the app config used by 'cargo openvm keygen'", e)
+                })?;
+                let start = Instant::now();
+                let app_proof = load_or_generate_app_proof(
+                    &sdk,
+                    app_pk.clone(),
                     committed_exe,
-                    agg_stark_pk,
                     read_to_stdin(&run_args.input)?,
+                    &artifact_dir,
+                    &target_name,
+                    *resume,
                 )?;
+                artifact_dir.log(&target_name, "Aggregating app proof into STARK proof")?;
+                let stark_proof = sdk.aggregate_app_proof(&app_pk, agg_stark_pk, app_proof)?;
+                let metadata = ProofMetadata::current().with_proving_time(start.elapsed());
 
-                let stark_proof_bytes = VmStarkProofBytes::new(commits, stark_proof)?;
+                let stark_proof_bytes =
+                    VmStarkProofBytes::new(commits, stark_proof)?.with_metadata(metadata);
 
                 let proof_path = if let Some(proof) = proof {
                     proof
                 } else {
-                    &PathBuf::from(format!("{}.stark.proof", target_name))
+                    &artifact_dir.proof_path(&target_name, "stark")
                 };
                 write_to_file_json(proof_path, stark_proof_bytes)?;
+                artifact_dir.log(
+                    &target_name,
+                    &format!("Wrote STARK proof to {}", proof_path.display()),
+                )?;
             }
             #[cfg(feature = "evm-prove")]
             ProveSubCommand::Evm {
                 app_pk,
                 proof,
+                resume,
                 run_args,
                 cargo_args,
                 agg_tree_config,
+                perf_config,
             } => {
                 use openvm_native_recursion::halo2::utils::CacheHalo2ParamsReader;
 
-                let sdk = Sdk::new().with_agg_tree_config(*agg_tree_config);
+                let sdk = Sdk::new()
+                    .with_agg_tree_config(*agg_tree_config)
+                    .with_prover_perf_config(*perf_config);
                 let app_pk = load_app_pk(app_pk, cargo_args)?;
                 let (committed_exe, target_name) =
                     load_or_build_and_commit_exe(&sdk, run_args, cargo_args, &app_pk)?;
+                let artifact_dir = get_artifact_dir(&cargo_args.manifest_path)?;
+                artifact_dir.create()?;
 
                 let commits = AppExecutionCommit::compute(
                     &app_pk.app_vm_pk.vm_config,
@@ -198,43 +277,113 @@ impl ProveCmd {
                 );
                 println!("exe commit: {:?}", commits.app_exe_commit.to_bn254());
                 println!("vm commit: {:?}", commits.app_vm_commit.to_bn254());
+                write_to_file_json(artifact_dir.commit_path(&target_name), commits)?;
 
                 println!("Generating EVM proof, this may take a lot of compute and memory...");
                 let agg_pk = read_default_agg_pk().map_err(|e| {
                     eyre::eyre!("Failed to read aggregation proving key: {}\nPlease run 'cargo openvm setup' first", e)
                 })?;
+                app_pk.check_agg_compatibility(&agg_pk.agg_stark_pk).map_err(|e| {
+                    eyre::eyre!("{}\nPlease run 'cargo openvm setup --evm --config <app config>' with the app config used by 'cargo openvm keygen'", e)
+                })?;
                 let params_reader = CacheHalo2ParamsReader::new(default_params_dir());
-                let evm_proof = sdk.generate_evm_proof(
-                    &params_reader,
-                    app_pk,
+                let start = Instant::now();
+                let app_proof = load_or_generate_app_proof(
+                    &sdk,
+                    app_pk.clone(),
                     committed_exe,
-                    agg_pk,
                     read_to_stdin(&run_args.input)?,
+                    &artifact_dir,
+                    &target_name,
+                    *resume,
                 )?;
+                artifact_dir.log(&target_name, "Aggregating app proof into EVM proof")?;
+                let mut evm_proof = sdk.generate_evm_proof_from_app_proof(
+                    &params_reader,
+                    &app_pk,
+                    agg_pk,
+                    app_proof,
+                )?;
+                evm_proof.metadata =
+                    Some(ProofMetadata::current().with_proving_time(start.elapsed()));
 
                 let proof_path = if let Some(proof) = proof {
                     proof
                 } else {
-                    &PathBuf::from(format!("{}.evm.proof", target_name))
+                    &artifact_dir.proof_path(&target_name, "evm")
                 };
                 write_evm_proof_to_file(evm_proof, proof_path)?;
+                artifact_dir.log(
+                    &target_name,
+                    &format!("Wrote EVM proof to {}", proof_path.display()),
+                )?;
             }
         }
         Ok(())
     }
 }
 
+/// Generates the app proof of `committed_exe` on `stdin`, keeping it in the artifact directory
+/// for later runs. If `resume` is set, the app proof of an earlier run is reused instead if its
+/// [ProofFingerprint] matches.
+fn load_or_generate_app_proof(
+    sdk: &Sdk,
+    app_pk: Arc<AppProvingKey<SdkVmConfig>>,
+    committed_exe: Arc<NonRootCommittedExe>,
+    stdin: StdIn,
+    artifact_dir: &ArtifactDir,
+    target_name: &str,
+    resume: bool,
+) -> Result<ContinuationVmProof<SC>> {
+    let commits = AppExecutionCommit::compute(
+        &app_pk.app_vm_pk.vm_config,
+        &committed_exe,
+        &app_pk.leaf_committed_exe,
+    );
+    let fingerprint = ProofFingerprint::new(&commits, &stdin);
+    let proof_path = artifact_dir.partial_app_proof_path(target_name);
+    let fingerprint_path = artifact_dir.partial_app_fingerprint_path(target_name);
+
+    if resume && proof_path.exists() && fingerprint_path.exists() {
+        let found: ProofFingerprint = read_from_file_json(&fingerprint_path)?;
+        if found == fingerprint {
+            artifact_dir.log(
+                target_name,
+                &format!("Reusing app proof at {}", proof_path.display()),
+            )?;
+            return read_app_proof_from_file(proof_path);
+        }
+        artifact_dir.log(
+            target_name,
+            "App proof of the earlier run is for a different executable, VM or input",
+        )?;
+    }
+
+    artifact_dir.log(target_name, "Generating app proof")?;
+    let start = Instant::now();
+    let app_proof = sdk.generate_app_proof(app_pk, committed_exe, stdin)?;
+    artifact_dir.log(
+        target_name,
+        &format!("Generated app proof in {:?}", start.elapsed()),
+    )?;
+
+    // The fingerprint is written after the proof, so that an interrupted write is not reused.
+    if fingerprint_path.exists() {
+        remove_file(&fingerprint_path)?;
+    }
+    write_app_proof_to_file(app_proof.clone(), &proof_path)?;
+    write_to_file_json(&fingerprint_path, fingerprint)?;
+    Ok(app_proof)
+}
+
 pub(crate) fn load_app_pk(
     app_pk: &Option<PathBuf>,
     cargo_args: &RunCargoArgs,
 ) -> Result<Arc<AppProvingKey<SdkVmConfig>>> {
-    let (manifest_path, _) = get_manifest_path_and_dir(&cargo_args.manifest_path)?;
-    let target_dir = get_target_dir(&cargo_args.target_dir, &manifest_path);
-
     let app_pk_path = if let Some(app_pk) = app_pk {
         app_pk.to_path_buf()
     } else {
-        get_app_pk_path(&target_dir)
+        get_artifact_dir(&cargo_args.manifest_path)?.app_pk_path()
     };
 
     Ok(Arc::new(read_app_pk_from_file(app_pk_path)?))
@@ -258,7 +407,7 @@ pub(crate) fn load_or_build_and_commit_exe(
         &output_dir.join(format!("{}.vmexe", target_name))
     };
 
-    let app_exe = read_exe_from_file(exe_path)?;
+    let app_exe = read_exe_from_file_checked(exe_path, &app_pk.app_vm_pk.vm_config.transpiler())?;
     let committed_exe = sdk.commit_app_exe(app_pk.app_fri_params(), app_exe)?;
     Ok((
         committed_exe,
@@ -2,8 +2,13 @@ use std::path::PathBuf;
 
 use clap::Parser;
 use eyre::Result;
-use openvm_circuit::arch::OPENVM_DEFAULT_INIT_FILE_NAME;
-use openvm_sdk::{fs::read_exe_from_file, Sdk};
+use openvm_circuit::arch::{instructions::exe::VmExe, HeapProfile, OPENVM_DEFAULT_INIT_FILE_NAME};
+use openvm_sdk::{
+    config::{AppConfig, SdkVmConfig},
+    fs::{read_exe_from_file_checked, read_symbols_from_file},
+    symbols::{symbolize_with_source, SYMBOL_SIDECAR_EXTENSION},
+    Sdk, F,
+};
 
 use super::{build, BuildArgs, BuildCargoArgs};
 use crate::{
@@ -19,6 +24,22 @@ pub struct RunCmd {
 
     #[clap(flatten)]
     cargo_args: RunCargoArgs,
+
+    #[arg(
+        long,
+        action,
+        help = "Print the heap profile of the program, which must be built with the `heap-profile` feature of `openvm`",
+        help_heading = "OpenVM Options"
+    )]
+    profile_memory: bool,
+
+    #[arg(
+        long,
+        action,
+        help = "Print the rows used by each chip in each segment, compared to the padded trace heights",
+        help_heading = "OpenVM Options"
+    )]
+    utilization: bool,
 }
 
 #[derive(Clone, Parser)]
@@ -229,33 +250,90 @@ impl From<RunCargoArgs> for BuildCargoArgs {
 
 impl RunCmd {
     pub fn run(&self) -> Result<()> {
-        let exe_path = if let Some(exe) = &self.run_args.exe {
-            exe
-        } else {
-            // Build and get the executable name
-            let target_name = get_single_target_name(&self.cargo_args)?;
-            let build_args = self.run_args.clone().into();
-            let cargo_args = self.cargo_args.clone().into();
-            let output_dir = build(&build_args, &cargo_args)?;
-            &output_dir.join(format!("{}.vmexe", target_name))
-        };
-
-        let (_, manifest_dir) = get_manifest_path_and_dir(&self.cargo_args.manifest_path)?;
-        let app_config = read_config_toml_or_default(
-            self.run_args
-                .config
-                .to_owned()
-                .unwrap_or_else(|| manifest_dir.join("openvm.toml")),
-        )?;
-        let exe = read_exe_from_file(exe_path)?;
-
+        let (exe, app_config) = load_exe_and_config(&self.run_args, &self.cargo_args)?;
+        let fn_bounds = exe.fn_bounds.clone();
+        let source_map = exe.source_map.clone();
         let sdk = Sdk::new();
-        let output = sdk.execute(
-            exe,
-            app_config.app_vm_config,
-            read_to_stdin(&self.run_args.input)?,
-        )?;
+        let (output, result) = sdk
+            .execute_with_stats(
+                exe,
+                app_config.app_vm_config,
+                read_to_stdin(&self.run_args.input)?,
+            )
+            .inspect_err(|err| {
+                if let (Some(pc), false) = (err.pc(), fn_bounds.is_empty() && source_map.is_empty())
+                {
+                    println!(
+                        "[openvm] Execution failed in {}",
+                        symbolize_with_source(&fn_bounds, &source_map, pc)
+                    );
+                }
+            })?;
         println!("Execution output: {:?}", output);
+        if self.profile_memory {
+            print_heap_profile(result.heap_profile.as_ref());
+        }
+        if self.utilization {
+            for (segment_idx, utilization) in result.utilization.iter().enumerate() {
+                println!("[openvm] Segment {segment_idx}: {utilization}");
+            }
+        }
         Ok(())
     }
 }
+
+fn print_heap_profile(profile: Option<&HeapProfile>) {
+    let Some(profile) = profile else {
+        println!(
+            "[openvm] No heap profile was recorded, build the program with the `heap-profile` feature of `openvm`"
+        );
+        return;
+    };
+    println!("Heap profile:");
+    println!("  allocations:     {}", profile.allocations);
+    println!("  deallocations:   {}", profile.deallocations);
+    println!("  reallocations:   {}", profile.reallocations);
+    println!("  allocated bytes: {}", profile.allocated_bytes);
+    println!("  current bytes:   {}", profile.current_bytes);
+    println!("  peak bytes:      {}", profile.peak_bytes);
+}
+
+/// Reads the executable specified by `--exe`, building it if not specified, together with the
+/// app config.
+pub(crate) fn load_exe_and_config(
+    run_args: &RunArgs,
+    cargo_args: &RunCargoArgs,
+) -> Result<(VmExe<F>, AppConfig<SdkVmConfig>)> {
+    let exe_path = if let Some(exe) = &run_args.exe {
+        exe
+    } else {
+        // Build and get the executable name
+        let target_name = get_single_target_name(cargo_args)?;
+        let build_args = run_args.clone().into();
+        let cargo_args = cargo_args.clone().into();
+        let output_dir = build(&build_args, &cargo_args)?;
+        &output_dir.join(format!("{}.vmexe", target_name))
+    };
+
+    let (_, manifest_dir) = get_manifest_path_and_dir(&cargo_args.manifest_path)?;
+    let app_config = read_config_toml_or_default(
+        run_args
+            .config
+            .to_owned()
+            .unwrap_or_else(|| manifest_dir.join("openvm.toml")),
+    )?;
+    let mut exe = read_exe_from_file_checked(exe_path, &app_config.app_vm_config.transpiler())?;
+    // The symbols of stripped ELFs are in the sidecar written by `build --strip`.
+    let symbols_path = exe_path.with_extension(SYMBOL_SIDECAR_EXTENSION);
+    if symbols_path.exists() {
+        let symbols = read_symbols_from_file(&symbols_path)?;
+        // A sidecar left over from an earlier build would symbolize the wrong functions.
+        symbols.check_exe(
+            &exe,
+            app_config.app_fri_params.fri_params,
+            &app_config.app_vm_config.system.config.memory_config,
+        )?;
+        symbols.apply_to_exe(&mut exe);
+    }
+    Ok((exe, app_config))
+}
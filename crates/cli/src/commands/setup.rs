@@ -11,10 +11,11 @@ use openvm_native_recursion::halo2::utils::CacheHalo2ParamsReader;
 use openvm_sdk::{
     config::{AggConfig, AggStarkConfig},
     fs::{
-        write_agg_halo2_pk_to_file, write_agg_stark_pk_to_file, write_evm_halo2_verifier_to_folder,
-        EVM_HALO2_VERIFIER_BASE_NAME, EVM_HALO2_VERIFIER_INTERFACE_NAME,
-        EVM_HALO2_VERIFIER_PARENT_NAME,
+        read_agg_stark_pk_from_file, write_agg_halo2_pk_to_file, write_agg_stark_pk_to_file,
+        write_evm_halo2_verifier_to_folder, EVM_HALO2_VERIFIER_BASE_NAME,
+        EVM_HALO2_VERIFIER_INTERFACE_NAME, EVM_HALO2_VERIFIER_PARENT_NAME,
     },
+    keygen::AggStarkProvingKey,
     DefaultStaticVerifierPvHandler, Sdk,
 };
 
@@ -23,7 +24,7 @@ use crate::{
         default_agg_halo2_pk_path, default_agg_stark_pk_path, default_asm_path,
         default_evm_halo2_verifier_path, default_params_dir,
     },
-    util::read_default_agg_pk,
+    util::{read_config_toml_or_default, read_default_agg_pk},
 };
 
 #[derive(Parser)]
@@ -44,6 +45,11 @@ pub struct SetupCmd {
         help = "force keygen even if the proving keys already exist"
     )]
     pub force_agg_keygen: bool,
+    #[arg(
+        long,
+        help = "Path to the OpenVM config .toml file of the app; if set, the aggregation proving keys and verifier contract are generated for its number of public values"
+    )]
+    pub config: Option<PathBuf>,
 }
 
 impl SetupCmd {
@@ -52,12 +58,21 @@ impl SetupCmd {
         let default_params_dir = default_params_dir();
         let default_evm_halo2_verifier_path = default_evm_halo2_verifier_path();
         let default_asm_path = default_asm_path();
+        let agg_config = if let Some(config) = &self.config {
+            AggConfig::for_app(&read_config_toml_or_default(config)?)
+        } else {
+            AggConfig::default()
+        };
         if !self.evm {
             if PathBuf::from(&default_agg_stark_pk_path).exists() {
-                println!("Aggregation stark proving key already exists");
-                return Ok(());
+                let agg_stark_pk = read_agg_stark_pk_from_file(&default_agg_stark_pk_path)?;
+                if is_compatible(&agg_stark_pk, &agg_config.agg_stark_config) {
+                    println!("Aggregation stark proving key already exists");
+                    return Ok(());
+                }
+                println!("Existing aggregation stark proving key does not match the app config, regenerating...");
             }
-            let agg_stark_config = AggStarkConfig::default();
+            let agg_stark_config = agg_config.agg_stark_config;
             let sdk = Sdk::new();
             let agg_stark_pk = sdk.agg_stark_keygen(agg_stark_config)?;
 
@@ -83,6 +98,10 @@ impl SetupCmd {
                     .join("interfaces")
                     .join(EVM_HALO2_VERIFIER_INTERFACE_NAME)
                     .exists()
+                && is_compatible(
+                    &read_agg_stark_pk_from_file(&default_agg_stark_pk_path)?,
+                    &agg_config.agg_stark_config,
+                )
             {
                 println!("Aggregation proving key and verifier contract already exist");
                 return Ok(());
@@ -94,14 +113,20 @@ impl SetupCmd {
 
             Self::download_params(10, 24).await?;
             let params_reader = CacheHalo2ParamsReader::new(&default_params_dir);
-            let agg_config = AggConfig::default();
             let sdk = Sdk::new();
 
-            let agg_pk = if !self.force_agg_keygen
+            let existing_agg_pk = if !self.force_agg_keygen
                 && PathBuf::from(&default_agg_stark_pk_path).exists()
                 && PathBuf::from(&default_agg_halo2_pk_path).exists()
             {
-                read_default_agg_pk()?
+                Some(read_default_agg_pk()?).filter(|agg_pk| {
+                    is_compatible(&agg_pk.agg_stark_pk, &agg_config.agg_stark_config)
+                })
+            } else {
+                None
+            };
+            let agg_pk = if let Some(agg_pk) = existing_agg_pk {
+                agg_pk
             } else {
                 println!("Generating proving key...");
                 sdk.agg_keygen(agg_config, &params_reader, &DefaultStaticVerifierPvHandler)?
@@ -166,3 +191,10 @@ impl SetupCmd {
         Ok(())
     }
 }
+
+/// Whether an existing aggregation proving key was generated for the same app-facing parameters as
+/// `agg_stark_config`.
+fn is_compatible(agg_stark_pk: &AggStarkProvingKey, agg_stark_config: &AggStarkConfig) -> bool {
+    agg_stark_pk.num_user_public_values() == agg_stark_config.max_num_user_public_values
+        && agg_stark_pk.leaf_vm_pk.fri_params == agg_stark_config.leaf_fri_params
+}
@@ -0,0 +1,307 @@
+use std::{
+    fmt,
+    fs::{create_dir_all, write},
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+};
+
+use clap::Parser;
+use eyre::{Context, Result};
+use openvm_circuit::{
+    arch::{ExecutionError, VmExecutor},
+    system::memory::tree::public_values::extract_public_values,
+};
+use openvm_sdk::{StdIn, F};
+use openvm_stark_backend::p3_field::PrimeField32;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use super::{load_exe_and_config, RunArgs, RunCargoArgs};
+use crate::input::{parse_json_input, read_bytes_into_stdin, read_to_input_bytes, to_json_input};
+
+#[derive(Parser)]
+#[command(
+    name = "stress",
+    about = "Run an OpenVM program repeatedly on randomized inputs"
+)]
+pub struct StressCmd {
+    #[clap(flatten)]
+    stress_args: StressArgs,
+
+    #[clap(flatten)]
+    run_args: RunArgs,
+
+    #[clap(flatten)]
+    cargo_args: RunCargoArgs,
+}
+
+#[derive(Clone, Parser)]
+pub struct StressArgs {
+    #[arg(
+        long,
+        default_value_t = 100,
+        help = "Number of inputs to run the program on",
+        help_heading = "Stress Options"
+    )]
+    pub iterations: u64,
+
+    #[arg(
+        long,
+        default_value_t = 2,
+        help = "Number of times the program is run on each input, the public values of all runs must match",
+        help_heading = "Stress Options"
+    )]
+    pub repeats: usize,
+
+    #[arg(
+        long,
+        help = "Maximum number of instructions a single run may execute",
+        help_heading = "Stress Options"
+    )]
+    pub max_cycles: Option<u64>,
+
+    #[arg(
+        long,
+        help = "Seed of the first iteration, iteration i uses seed + i; by default a random seed is used",
+        help_heading = "Stress Options"
+    )]
+    pub seed: Option<u64>,
+
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Executable that generates inputs, called with the iteration seed as its only argument and printing an input JSON to stdout; by default inputs are generated by mutating --input",
+        help_heading = "Stress Options"
+    )]
+    pub generator: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Directory that failing inputs will be written to, as input JSON files named after their iteration",
+        help_heading = "Stress Options"
+    )]
+    pub failures_dir: Option<PathBuf>,
+
+    #[arg(
+        long,
+        help = "Stop at the first failing input",
+        help_heading = "Stress Options"
+    )]
+    pub fail_fast: bool,
+}
+
+enum Failure {
+    Panic(String),
+    Execution(ExecutionError),
+    CycleBound { max_cycles: u64 },
+    Nondeterministic { runs: Vec<Vec<F>> },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Panic(msg) => write!(f, "host panicked: {msg}"),
+            Failure::Execution(err) => write!(f, "execution failed: {err}"),
+            Failure::CycleBound { max_cycles } => {
+                write!(
+                    f,
+                    "aborted after exceeding the bound of {max_cycles} cycles"
+                )
+            }
+            Failure::Nondeterministic { runs } => {
+                writeln!(f, "public values differ across runs of the same input:")?;
+                for (i, public_values) in runs.iter().enumerate() {
+                    writeln!(f, "  run {i}: {:?}", public_values)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl StressCmd {
+    pub fn run(&self) -> Result<()> {
+        let args = &self.stress_args;
+        if args.repeats == 0 {
+            return Err(eyre::eyre!("--repeats must be at least 1"));
+        }
+        let (exe, app_config) = load_exe_and_config(&self.run_args, &self.cargo_args)?;
+        let base_inputs = read_to_input_bytes(&self.run_args.input)?;
+        let seed = args.seed.unwrap_or_else(rand::random);
+        println!("Stress testing with seed {seed}");
+        if let Some(failures_dir) = &args.failures_dir {
+            create_dir_all(failures_dir)?;
+        }
+
+        let system_config = &app_config.app_vm_config.system.config;
+        let memory_dimensions = system_config.memory_config.memory_dimensions();
+        let num_public_values = system_config.num_public_values;
+        let mut vm = VmExecutor::new(app_config.app_vm_config);
+        vm.set_max_instructions(args.max_cycles);
+        let mut num_runs = 0;
+        let mut num_failures = 0;
+        let mut max_cycles_seen = 0;
+        for iteration in 0..args.iterations {
+            num_runs += 1;
+            let iteration_seed = seed.wrapping_add(iteration);
+            let inputs = if let Some(generator) = &args.generator {
+                generate_inputs(generator, iteration_seed)?
+            } else {
+                let mut inputs = base_inputs.clone();
+                mutate_inputs(&mut StdRng::seed_from_u64(iteration_seed), &mut inputs);
+                inputs
+            };
+            let mut stdin = StdIn::default();
+            for bytes in &inputs {
+                read_bytes_into_stdin(&mut stdin, bytes)?;
+            }
+
+            let mut runs = Vec::with_capacity(args.repeats);
+            let mut failure = None;
+            for _ in 0..args.repeats {
+                let result =
+                    catch_unwind(AssertUnwindSafe(|| vm.execute(exe.clone(), stdin.clone())));
+                match result {
+                    Ok(Ok(result)) => {
+                        max_cycles_seen = max_cycles_seen.max(result.total_cycles);
+                        runs.push(extract_public_values(
+                            &memory_dimensions,
+                            num_public_values,
+                            result.final_memory.as_ref().unwrap(),
+                        ));
+                    }
+                    Ok(Err(ExecutionError::InstructionLimitExceeded {
+                        max_instructions, ..
+                    })) => {
+                        failure = Some(Failure::CycleBound {
+                            max_cycles: max_instructions,
+                        });
+                        break;
+                    }
+                    Ok(Err(err)) => {
+                        failure = Some(Failure::Execution(err));
+                        break;
+                    }
+                    Err(payload) => {
+                        let msg = payload
+                            .downcast_ref::<&str>()
+                            .map(|s| s.to_string())
+                            .or_else(|| payload.downcast_ref::<String>().cloned())
+                            .unwrap_or_else(|| "unknown panic".to_string());
+                        failure = Some(Failure::Panic(msg));
+                        break;
+                    }
+                }
+            }
+            if failure.is_none() && runs.iter().any(|run| *run != runs[0]) {
+                failure = Some(Failure::Nondeterministic { runs });
+            }
+
+            if let Some(failure) = failure {
+                num_failures += 1;
+                println!("Iteration {iteration} (seed {iteration_seed}) failed: {failure}");
+                let input_json = to_json_input(&inputs);
+                if let Some(failures_dir) = &args.failures_dir {
+                    let path = failures_dir.join(format!("{iteration}.json"));
+                    write(&path, serde_json::to_vec_pretty(&input_json)?)?;
+                    println!("Failing input written to {}", path.display());
+                } else {
+                    println!("Failing input: {input_json}");
+                }
+                if args.fail_fast {
+                    break;
+                }
+            }
+        }
+
+        println!(
+            "Ran {} inputs with {} failures, max cycles executed: {}",
+            num_runs, num_failures, max_cycles_seen
+        );
+        if num_failures > 0 {
+            return Err(eyre::eyre!("{num_failures} inputs failed"));
+        }
+        Ok(())
+    }
+}
+
+fn generate_inputs(generator: &Path, seed: u64) -> Result<Vec<Vec<u8>>> {
+    let output = Command::new(generator)
+        .arg(seed.to_string())
+        .stderr(Stdio::inherit())
+        .output()
+        .with_context(|| format!("while executing generator {:?}", generator))?;
+    if !output.status.success() {
+        return Err(eyre::eyre!(
+            "Generator {:?} failed with status: {}",
+            generator,
+            output.status
+        ));
+    }
+    parse_json_input(&output.stdout)
+}
+
+/// Applies a few random mutations to `inputs`, each either adding a new random bytes input,
+/// removing an input, or changing the contents of an input. Field inputs are mutated a whole
+/// element at a time so they stay well-formed.
+fn mutate_inputs(rng: &mut StdRng, inputs: &mut Vec<Vec<u8>>) {
+    const MAX_NEW_INPUT_LEN: usize = 64;
+
+    for _ in 0..rng.gen_range(1..=4) {
+        if inputs.is_empty() || rng.gen_ratio(1, 8) {
+            let len = rng.gen_range(0..=MAX_NEW_INPUT_LEN);
+            let mut bytes = vec![0x01];
+            bytes.extend((0..len).map(|_| rng.gen::<u8>()));
+            let idx = rng.gen_range(0..=inputs.len());
+            inputs.insert(idx, bytes);
+            continue;
+        }
+        let idx = rng.gen_range(0..inputs.len());
+        if rng.gen_ratio(1, 8) {
+            inputs.remove(idx);
+            continue;
+        }
+        let input = &mut inputs[idx];
+        match input.first() {
+            Some(0x01) => mutate_bytes(rng, input, 1),
+            Some(0x02) => mutate_bytes(rng, input, 4),
+            _ => {}
+        }
+    }
+}
+
+/// Mutates the payload of `input`, which follows a one byte prefix, in units of `unit` bytes.
+fn mutate_bytes(rng: &mut StdRng, input: &mut Vec<u8>, unit: usize) {
+    let random_unit = |rng: &mut StdRng| -> Vec<u8> {
+        if unit == 1 {
+            vec![rng.gen()]
+        } else {
+            rng.gen_range(0..F::ORDER_U32).to_le_bytes().to_vec()
+        }
+    };
+    let num_units = (input.len() - 1) / unit;
+    let offset = |i: usize| 1 + i * unit;
+    match rng.gen_range(0..4) {
+        // Replace a unit
+        0 if num_units > 0 => {
+            let i = offset(rng.gen_range(0..num_units));
+            let new_unit = random_unit(rng);
+            input[i..i + unit].copy_from_slice(&new_unit);
+        }
+        // Remove a unit
+        1 if num_units > 0 => {
+            let i = offset(rng.gen_range(0..num_units));
+            input.drain(i..i + unit);
+        }
+        // Truncate
+        2 => {
+            input.truncate(offset(rng.gen_range(0..=num_units)));
+        }
+        // Insert a unit
+        _ => {
+            let i = offset(rng.gen_range(0..=num_units));
+            let new_unit = random_unit(rng);
+            input.splice(i..i, new_unit);
+        }
+    }
+}
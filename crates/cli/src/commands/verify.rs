@@ -1,4 +1,4 @@
-use std::path::{Path, PathBuf};
+use std::path::PathBuf;
 
 use clap::Parser;
 use eyre::Result;
@@ -16,7 +16,7 @@ use super::KeygenCargoArgs;
 use crate::default::default_evm_halo2_verifier_path;
 use crate::{
     default::default_agg_stark_pk_path,
-    util::{get_app_vk_path, get_files_with_ext, get_manifest_path_and_dir, get_target_dir},
+    util::{get_artifact_dir, get_files_with_ext},
 };
 
 #[derive(Parser)]
@@ -32,7 +32,7 @@ enum VerifySubCommand {
         #[arg(
             long,
             action,
-            help = "Path to app verifying key, by default will search for it in ${target_dir}/openvm/app.vk",
+            help = "Path to app verifying key, by default will search for it in ${manifest_dir}/.openvm/keys/app.vk",
             help_heading = "OpenVM Options"
         )]
         app_vk: Option<PathBuf>,
@@ -40,7 +40,7 @@ enum VerifySubCommand {
         #[arg(
             long,
             action,
-            help = "Path to app proof, by default will search the working directory for a file with extension .app.proof",
+            help = "Path to app proof, by default will search ${manifest_dir}/.openvm/proofs for a file with extension .app.proof",
             help_heading = "OpenVM Options"
         )]
         proof: Option<PathBuf>,
@@ -52,20 +52,26 @@ enum VerifySubCommand {
         #[arg(
             long,
             action,
-            help = "Path to STARK proof, by default will search the working directory for a file with extension .stark.proof",
+            help = "Path to STARK proof, by default will search ${manifest_dir}/.openvm/proofs for a file with extension .stark.proof",
             help_heading = "OpenVM Options"
         )]
         proof: Option<PathBuf>,
+
+        #[command(flatten)]
+        cargo_args: KeygenCargoArgs,
     },
     #[cfg(feature = "evm-verify")]
     Evm {
         #[arg(
             long,
             action,
-            help = "Path to EVM proof, by default will search the working directory for a file with extension .evm.proof",
+            help = "Path to EVM proof, by default will search ${manifest_dir}/.openvm/proofs for a file with extension .evm.proof",
             help_heading = "OpenVM Options"
         )]
         proof: Option<PathBuf>,
+
+        #[command(flatten)]
+        cargo_args: KeygenCargoArgs,
     },
 }
 
@@ -81,28 +87,20 @@ impl VerifyCmd {
                 let app_vk_path = if let Some(app_vk) = app_vk {
                     app_vk.to_path_buf()
                 } else {
-                    let (manifest_path, _) = get_manifest_path_and_dir(&cargo_args.manifest_path)?;
-                    let target_dir = get_target_dir(&cargo_args.target_dir, &manifest_path);
-                    get_app_vk_path(&target_dir)
+                    get_artifact_dir(&cargo_args.manifest_path)?.app_vk_path()
                 };
                 let app_vk = read_app_vk_from_file(app_vk_path)?;
 
                 let proof_path = if let Some(proof) = proof {
                     proof.clone()
                 } else {
-                    let files = get_files_with_ext(Path::new("."), "app.proof")?;
-                    if files.len() > 1 {
-                        return Err(eyre::eyre!("multiple .app.proof files found, please specify the path using option --proof"));
-                    } else if files.is_empty() {
-                        return Err(eyre::eyre!("no .app.proof file found, please specify the path using option --proof"));
-                    }
-                    files[0].clone()
+                    find_proof(&cargo_args.manifest_path, "app")?
                 };
                 println!("Verifying application proof at {}", proof_path.display());
                 let app_proof = read_app_proof_from_file(proof_path)?;
                 sdk.verify_app_proof(&app_vk, &app_proof)?;
             }
-            VerifySubCommand::Stark { proof } => {
+            VerifySubCommand::Stark { proof, cargo_args } => {
                 let agg_stark_pk = read_agg_stark_pk_from_file(default_agg_stark_pk_path())
                     .map_err(|e| {
                         eyre::eyre!(
@@ -113,13 +111,7 @@ impl VerifyCmd {
                 let proof_path = if let Some(proof) = proof {
                     proof.clone()
                 } else {
-                    let files = get_files_with_ext(Path::new("."), "stark.proof")?;
-                    if files.len() > 1 {
-                        return Err(eyre::eyre!("multiple .stark.proof files found, please specify the path using option --proof"));
-                    } else if files.is_empty() {
-                        return Err(eyre::eyre!("no .stark.proof file found, please specify the path using option --proof"));
-                    }
-                    files[0].clone()
+                    find_proof(&cargo_args.manifest_path, "stark")?
                 };
                 println!("Verifying STARK proof at {}", proof_path.display());
                 let stark_proof_bytes: VmStarkProofBytes = read_from_file_json(proof_path)?;
@@ -133,7 +125,7 @@ impl VerifyCmd {
                 )?;
             }
             #[cfg(feature = "evm-verify")]
-            VerifySubCommand::Evm { proof } => {
+            VerifySubCommand::Evm { proof, cargo_args } => {
                 use openvm_sdk::fs::{
                     read_evm_halo2_verifier_from_folder, read_evm_proof_from_file,
                 };
@@ -150,13 +142,7 @@ impl VerifyCmd {
                 let proof_path = if let Some(proof) = proof {
                     proof.clone()
                 } else {
-                    let files = get_files_with_ext(Path::new("."), "evm.proof")?;
-                    if files.len() > 1 {
-                        return Err(eyre::eyre!("multiple .evm.proof files found, please specify the path using option --proof"));
-                    } else if files.is_empty() {
-                        return Err(eyre::eyre!("no .evm.proof file found, please specify the path using option --proof"));
-                    }
-                    files[0].clone()
+                    find_proof(&cargo_args.manifest_path, "evm")?
                 };
                 println!("Verifying EVM proof at {}", proof_path.display());
                 let evm_proof = read_evm_proof_from_file(proof_path)?;
@@ -166,3 +152,27 @@ impl VerifyCmd {
         Ok(())
     }
 }
+
+/// Finds the single proof with extension `.${kind}.proof` in the artifact directory.
+fn find_proof(manifest_path: &Option<PathBuf>, kind: &str) -> Result<PathBuf> {
+    let proofs_dir = get_artifact_dir(manifest_path)?.proofs_dir();
+    let files = if proofs_dir.exists() {
+        get_files_with_ext(&proofs_dir, &format!("{}.proof", kind))?
+    } else {
+        vec![]
+    };
+    if files.len() > 1 {
+        return Err(eyre::eyre!(
+            "multiple .{}.proof files found in {}, please specify the path using option --proof",
+            kind,
+            proofs_dir.display()
+        ));
+    } else if files.is_empty() {
+        return Err(eyre::eyre!(
+            "no .{}.proof file found in {}, please specify the path using option --proof",
+            kind,
+            proofs_dir.display()
+        ));
+    }
+    Ok(files[0].clone())
+}
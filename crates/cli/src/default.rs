@@ -1,7 +1,6 @@
 use std::env;
 
-use openvm_sdk::config::{AppConfig, SdkVmConfig, DEFAULT_APP_LOG_BLOWUP, DEFAULT_LEAF_LOG_BLOWUP};
-use openvm_stark_sdk::config::FriParameters;
+pub use openvm_sdk::config::default_app_config;
 
 pub const DEFAULT_MANIFEST_DIR: &str = ".";
 
@@ -27,23 +26,3 @@ pub fn default_params_dir() -> String {
 pub fn default_evm_halo2_verifier_path() -> String {
     env::var("HOME").unwrap() + "/.openvm/halo2/"
 }
-
-pub fn default_app_config() -> AppConfig<SdkVmConfig> {
-    AppConfig {
-        app_fri_params: FriParameters::standard_with_100_bits_conjectured_security(
-            DEFAULT_APP_LOG_BLOWUP,
-        )
-        .into(),
-        app_vm_config: SdkVmConfig::builder()
-            .system(Default::default())
-            .rv32i(Default::default())
-            .rv32m(Default::default())
-            .io(Default::default())
-            .build(),
-        leaf_fri_params: FriParameters::standard_with_100_bits_conjectured_security(
-            DEFAULT_LEAF_LOG_BLOWUP,
-        )
-        .into(),
-        compiler_options: Default::default(),
-    }
-}
@@ -50,12 +50,12 @@ pub fn decode_hex_string(s: &str) -> Result<Vec<u8>> {
 
 pub fn read_bytes_into_stdin(stdin: &mut StdIn, bytes: &[u8]) -> Result<()> {
     // should either write_bytes or write_field
-    match bytes[0] {
-        0x01 => {
+    match bytes.first() {
+        Some(0x01) => {
             stdin.write_bytes(&bytes[1..]);
             Ok(())
         }
-        0x02 => {
+        Some(0x02) => {
             let data = &bytes[1..];
             if data.len() % 4 != 0 {
                 return Err(eyre::eyre!(
@@ -77,37 +77,50 @@ pub fn read_bytes_into_stdin(stdin: &mut StdIn, bytes: &[u8]) -> Result<()> {
 }
 
 pub fn read_to_stdin(input: &Option<Input>) -> Result<StdIn> {
-    match input {
-        Some(Input::FilePath(path)) => {
-            let mut stdin = StdIn::default();
-            // read the json
-            let bytes = read(path)?;
-            let json: serde_json::Value = serde_json::from_slice(&bytes)?;
-            json["input"]
-                .as_array()
-                .ok_or_else(|| eyre::eyre!("Input must be an array under 'input' key"))?
-                .iter()
-                .try_for_each(|inner| {
-                    inner
-                        .as_str()
-                        .ok_or_else(|| eyre::eyre!("Each value must be a hex string"))
-                        .and_then(|s| {
-                            if !is_valid_hex_string(s) {
-                                return Err(eyre::eyre!("Invalid hex string"));
-                            }
-                            let bytes = decode_hex_string(s)?;
-                            read_bytes_into_stdin(&mut stdin, &bytes)
-                        })
-                })?;
+    let mut stdin = StdIn::default();
+    for bytes in read_to_input_bytes(input)? {
+        read_bytes_into_stdin(&mut stdin, &bytes)?;
+    }
+    Ok(stdin)
+}
 
-            Ok(stdin)
-        }
-        Some(Input::HexBytes(hex_str)) => {
-            let mut stdin = StdIn::default();
-            let bytes = decode_hex_string(hex_str)?;
-            read_bytes_into_stdin(&mut stdin, &bytes)?;
-            Ok(stdin)
-        }
-        None => Ok(StdIn::default()),
+/// Reads the input into a list of byte strings, each prefixed with 0x01 or 0x02 as described in
+/// [Input].
+pub fn read_to_input_bytes(input: &Option<Input>) -> Result<Vec<Vec<u8>>> {
+    match input {
+        Some(Input::FilePath(path)) => parse_json_input(&read(path)?),
+        Some(Input::HexBytes(hex_str)) => Ok(vec![decode_hex_string(hex_str)?]),
+        None => Ok(Vec::new()),
     }
 }
+
+/// Parses a JSON input of the form `{"input": ["0x01...", "0x02..."]}`.
+pub fn parse_json_input(json: &[u8]) -> Result<Vec<Vec<u8>>> {
+    let json: serde_json::Value = serde_json::from_slice(json)?;
+    json["input"]
+        .as_array()
+        .ok_or_else(|| eyre::eyre!("Input must be an array under 'input' key"))?
+        .iter()
+        .map(|inner| {
+            inner
+                .as_str()
+                .ok_or_else(|| eyre::eyre!("Each value must be a hex string"))
+                .and_then(|s| {
+                    if !is_valid_hex_string(s) {
+                        return Err(eyre::eyre!("Invalid hex string"));
+                    }
+                    decode_hex_string(s)
+                })
+        })
+        .collect()
+}
+
+/// Inverse of [parse_json_input].
+pub fn to_json_input(inputs: &[Vec<u8>]) -> serde_json::Value {
+    serde_json::json!({
+        "input": inputs
+            .iter()
+            .map(|bytes| format!("0x{}", hex::encode(bytes)))
+            .collect::<Vec<_>>()
+    })
+}
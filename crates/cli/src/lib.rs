@@ -1,3 +1,4 @@
+pub mod artifacts;
 pub mod commands;
 pub mod default;
 pub mod input;
@@ -1,37 +1,15 @@
 use std::{
-    fs::{read_dir, read_to_string},
+    fs::read_dir,
     path::{Path, PathBuf},
 };
 
 use eyre::Result;
 use openvm_build::{get_in_scope_packages, get_workspace_packages};
-use openvm_sdk::config::{AppConfig, SdkVmConfig};
+pub use openvm_sdk::fs::read_config_toml_or_default;
 #[cfg(feature = "evm-prove")]
 use openvm_sdk::{fs::read_agg_stark_pk_from_file, keygen::AggProvingKey};
-use serde::de::DeserializeOwned;
 
-use crate::{
-    commands::RunCargoArgs,
-    default::{default_app_config, DEFAULT_APP_PK_NAME, DEFAULT_APP_VK_NAME},
-};
-
-pub(crate) fn read_to_struct_toml<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
-    let toml = read_to_string(path)?;
-    let ret = toml::from_str(&toml)?;
-    Ok(ret)
-}
-
-pub fn read_config_toml_or_default(config: impl AsRef<Path>) -> Result<AppConfig<SdkVmConfig>> {
-    if config.as_ref().exists() {
-        read_to_struct_toml(config)
-    } else {
-        println!(
-            "{:?} not found, using default application configuration",
-            config.as_ref()
-        );
-        Ok(default_app_config())
-    }
-}
+use crate::{artifacts::ArtifactDir, commands::RunCargoArgs};
 
 #[cfg(feature = "evm-prove")]
 pub fn read_default_agg_pk() -> Result<AggProvingKey> {
@@ -76,16 +54,10 @@ pub fn get_target_dir(target_dir: &Option<PathBuf>, manifest_path: &PathBuf) ->
         .unwrap_or_else(|| openvm_build::get_target_dir(manifest_path))
 }
 
-pub fn get_target_output_dir(target_dir: &Path, profile: &str) -> PathBuf {
-    target_dir.join("openvm").join(profile).to_path_buf()
-}
-
-pub fn get_app_pk_path(target_dir: &Path) -> PathBuf {
-    target_dir.join("openvm").join(DEFAULT_APP_PK_NAME)
-}
-
-pub fn get_app_vk_path(target_dir: &Path) -> PathBuf {
-    target_dir.join("openvm").join(DEFAULT_APP_VK_NAME)
+/// Returns the artifact directory of the package at `manifest_path`, see [ArtifactDir].
+pub fn get_artifact_dir(manifest_path: &Option<PathBuf>) -> Result<ArtifactDir> {
+    let (_, manifest_dir) = get_manifest_path_and_dir(manifest_path)?;
+    Ok(ArtifactDir::new(manifest_dir))
 }
 
 // Given the arguments to a run command, this function isolates the executable to
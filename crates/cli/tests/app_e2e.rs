@@ -13,7 +13,7 @@ use tempfile::tempdir;
 fn test_cli_app_e2e() -> Result<()> {
     let temp_dir = tempdir()?;
     run_cmd("cargo", &["install", "--path", ".", "--force", "--locked"])?;
-    let exe_path = "tests/programs/fibonacci/target/openvm/release/openvm-cli-example-test.vmexe";
+    let exe_path = "tests/programs/fibonacci/.openvm/exe/release/openvm-cli-example-test.vmexe";
     let temp_pk = temp_dir.path().join("app.pk");
     let temp_vk = temp_dir.path().join("app.vk");
     let temp_proof = temp_dir.path().join("fibonacci.app.proof");
@@ -54,6 +54,22 @@ fn test_cli_app_e2e() -> Result<()> {
         ],
     )?;
 
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "stress",
+            "--exe",
+            exe_path,
+            "--config",
+            "tests/programs/fibonacci/openvm.toml",
+            "--iterations",
+            "4",
+            "--seed",
+            "0",
+        ],
+    )?;
+
     run_cmd(
         "cargo",
         &[
@@ -109,6 +125,22 @@ fn test_cli_app_e2e_simplified() -> Result<()> {
             "fibonacci",
         ],
     )?;
+    // Reuses the app proof of the previous run from the artifact directory.
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "prove",
+            "app",
+            "--manifest-path",
+            "tests/programs/multi/Cargo.toml",
+            "--example",
+            "fibonacci",
+            "--resume",
+        ],
+    )?;
+    let log = read_to_string("tests/programs/multi/.openvm/logs/fibonacci.prove.log")?;
+    assert!(log.contains("Reusing app proof"));
     run_cmd(
         "cargo",
         &[
@@ -119,6 +151,18 @@ fn test_cli_app_e2e_simplified() -> Result<()> {
             "tests/programs/multi/Cargo.toml",
         ],
     )?;
+    run_cmd(
+        "cargo",
+        &[
+            "openvm",
+            "clean",
+            "--manifest-path",
+            "tests/programs/multi/Cargo.toml",
+        ],
+    )?;
+    let artifact_dir = Path::new("tests/programs/multi/.openvm");
+    assert!(artifact_dir.join("keys/app.pk").exists());
+    assert!(!artifact_dir.join("proofs").exists());
     Ok(())
 }
 
@@ -1,5 +1,11 @@
+use std::array;
+
 use openvm_circuit::{
-    arch::{instructions::program::Program, SystemConfig},
+    arch::{
+        hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
+        instructions::program::Program,
+        ExtensionVersions, SystemConfig,
+    },
     system::memory::tree::public_values::PUBLIC_VALUES_ADDRESS_SPACE_OFFSET,
 };
 use openvm_native_compiler::{conversion::CompilerOptions, prelude::*};
@@ -36,6 +42,9 @@ mod vars;
 pub struct LeafVmVerifierConfig {
     pub app_fri_params: FriParameters,
     pub app_system_config: SystemConfig,
+    /// Semantic versions of the extensions of the app VM. The program hashes them and asserts the
+    /// digest, so that they are part of the VM commit.
+    pub app_extension_versions: ExtensionVersions,
     pub compiler_options: CompilerOptions,
 }
 
@@ -48,6 +57,10 @@ impl LeafVmVerifierConfig {
         let mut builder = Builder::<C>::default();
 
         {
+            builder.cycle_tracker_start("CheckExtensionVersions");
+            self.check_extension_versions(&mut builder);
+            builder.cycle_tracker_end("CheckExtensionVersions");
+
             builder.cycle_tracker_start("InitializePcsConst");
             let pcs = TwoAdicFriPcsVariable {
                 config: const_fri_config(&mut builder, &self.app_fri_params),
@@ -105,6 +118,48 @@ impl LeafVmVerifierConfig {
         builder.compile_isa_with_options(self.compiler_options)
     }
 
+    /// Hashes the encoded extension versions and asserts that the digest matches the one computed
+    /// on the host. Two app VMs whose extensions only differ in their versions thus have
+    /// different leaf verifier programs and therefore different VM commits.
+    fn check_extension_versions(&self, builder: &mut Builder<C>) {
+        let chunks = self.extension_versions_chunks();
+        let hasher = vm_poseidon2_hasher();
+        let expected = chunks.iter().fold([F::ZERO; DIGEST_SIZE], |digest, chunk| {
+            hasher.compress(&digest, chunk)
+        });
+
+        let compressor = VariableP2Compressor::new(builder);
+        let mut digest: [Felt<F>; DIGEST_SIZE] = array::from_fn(|_| builder.eval(F::ZERO));
+        for chunk in &chunks {
+            let chunk = chunk.map(|value| builder.eval(value));
+            digest = compressor.compress(builder, &digest, &chunk);
+        }
+        for (computed, expected) in digest.into_iter().zip(expected) {
+            builder.assert_felt_eq(computed, expected);
+        }
+    }
+
+    /// Each extension is encoded as the length of its name, the bytes of its name and its
+    /// version. The encoding is zero-padded to a multiple of [DIGEST_SIZE].
+    fn extension_versions_chunks(&self) -> Vec<[F; DIGEST_SIZE]> {
+        let mut values: Vec<F> = self
+            .app_extension_versions
+            .iter()
+            .flat_map(|(name, version)| {
+                [name.len() as u32]
+                    .into_iter()
+                    .chain(name.bytes().map(u32::from))
+                    .chain([*version])
+            })
+            .map(F::from_wrapped_u32)
+            .collect();
+        values.resize(values.len().next_multiple_of(DIGEST_SIZE), F::ZERO);
+        values
+            .chunks_exact(DIGEST_SIZE)
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect()
+    }
+
     /// Read the public values root proof from the input stream and verify it.
     /// This verification must be consistent `openvm_circuit::system::memory::tree::public_values`.
     /// Returns the public values commit and the corresponding memory state root.
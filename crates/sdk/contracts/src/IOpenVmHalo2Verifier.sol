@@ -5,4 +5,8 @@ interface IOpenVmHalo2Verifier {
     function verify(bytes calldata publicValues, bytes calldata proofData, bytes32 appExeCommit, bytes32 appVmCommit)
         external
         view;
+
+    function publicValuesCommitKeccak256(bytes calldata publicValues) external pure returns (bytes32);
+
+    function publicValuesCommitSha256(bytes calldata publicValues) external pure returns (bytes32);
 }
@@ -69,6 +69,57 @@ contract OpenVmHalo2Verifier is Halo2Verifier, IOpenVmHalo2Verifier {
         }
     }
 
+    /// @notice Computes the commitment to the public values with keccak256, as
+    /// computed by `commit_user_public_values` with the Keccak256 hasher in the
+    /// OpenVM SDK.
+    ///
+    /// @param publicValues The PVs revealed by the OpenVM guest program.
+    function publicValuesCommitKeccak256(bytes calldata publicValues) external pure returns (bytes32) {
+        return _publicValuesMerkleRoot(publicValues, false);
+    }
+
+    /// @notice Computes the commitment to the public values with sha256, as
+    /// computed by `commit_user_public_values` with the Sha256 hasher in the
+    /// OpenVM SDK.
+    ///
+    /// @param publicValues The PVs revealed by the OpenVM guest program.
+    function publicValuesCommitSha256(bytes calldata publicValues) external pure returns (bytes32) {
+        return _publicValuesMerkleRoot(publicValues, true);
+    }
+
+    /// @dev The public values are right-padded with zeros to a power of two
+    /// number of 32 byte leaves. Each leaf is hashed, and each internal node
+    /// is the hash of the concatenation of its children.
+    function _publicValuesMerkleRoot(bytes calldata publicValues, bool useSha256) private pure returns (bytes32) {
+        if (publicValues.length != PUBLIC_VALUES_LENGTH) revert InvalidPublicValuesLength(PUBLIC_VALUES_LENGTH, publicValues.length);
+
+        uint256 numLeaves = 1;
+        while (numLeaves * 32 < PUBLIC_VALUES_LENGTH) {
+            numLeaves <<= 1;
+        }
+
+        bytes32[] memory nodes = new bytes32[](numLeaves);
+        for (uint256 i = 0; i < numLeaves; ++i) {
+            uint256 start = i * 32;
+            bytes32 leaf;
+            if (start < PUBLIC_VALUES_LENGTH) {
+                uint256 end = start + 32 < PUBLIC_VALUES_LENGTH ? start + 32 : PUBLIC_VALUES_LENGTH;
+                leaf = bytes32(publicValues[start:end]);
+            }
+            nodes[i] = _hash(abi.encodePacked(leaf), useSha256);
+        }
+        for (uint256 n = numLeaves; n > 1; n >>= 1) {
+            for (uint256 i = 0; i < n >> 1; ++i) {
+                nodes[i] = _hash(abi.encodePacked(nodes[2 * i], nodes[2 * i + 1]), useSha256);
+            }
+        }
+        return nodes[0];
+    }
+
+    function _hash(bytes memory data, bool useSha256) private pure returns (bytes32) {
+        return useSha256 ? sha256(data) : keccak256(data);
+    }
+
     /// @dev The assembly code should perform the same function as the following
     /// solidity code:
     //
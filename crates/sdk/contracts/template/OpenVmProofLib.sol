@@ -0,0 +1,93 @@
+// SPDX-License-Identifier: MIT OR Apache-2.0
+pragma solidity ^0.8.5;
+
+import { IOpenVmHalo2Verifier } from "./interfaces/IOpenVmHalo2Verifier.sol";
+
+/// @notice Helpers to verify proofs of a single OpenVM application and decode
+/// its public values into an app-defined struct. The library only depends on
+/// the `IOpenVmHalo2Verifier` interface, so it can be used with any deployed
+/// OpenVM verifier contract.
+library OpenVmProofLib {
+    /// @dev The public values are shorter than the schema
+    error InvalidPublicValuesLength(uint256 expected, uint256 actual);
+
+    /// @dev The app exe commit is not the commit of this application
+    error AppExeCommitMismatch(bytes32 expected, bytes32 actual);
+
+    /// @dev The app vm commit is not the commit of this application
+    error AppVmCommitMismatch(bytes32 expected, bytes32 actual);
+
+    /// @dev A `bool` public value is neither 0 nor 1
+    error InvalidBool(uint256 offset);
+
+    /// @dev The commitment to the OpenVM application executable.
+    bytes32 internal constant APP_EXE_COMMIT = {APP_EXE_COMMIT};
+
+    /// @dev The commitment to the VM configuration of the application.
+    bytes32 internal constant APP_VM_COMMIT = {APP_VM_COMMIT};
+
+    /// @dev The number of public value bytes decoded into `PublicValues`.
+    uint256 internal constant PUBLIC_VALUES_SIZE = {PUBLIC_VALUES_SIZE};
+
+    /// @dev The version of OpenVM that generated this library.
+    string internal constant OPENVM_VERSION = "{OPENVM_VERSION}";
+
+    struct PublicValues {
+{STRUCT_FIELDS}
+    }
+
+    /// @notice Verifies a proof of this application with `verifier` and
+    /// decodes its public values. Reverts if verification fails.
+    ///
+    /// @param verifier The deployed OpenVM verifier contract.
+    /// @param publicValues The PVs revealed by the OpenVM guest program.
+    /// @param proofData All components of the proof except the public values and
+    /// app exe and vm commits.
+    function verify(IOpenVmHalo2Verifier verifier, bytes calldata publicValues, bytes calldata proofData)
+        internal
+        view
+        returns (PublicValues memory)
+    {
+        verifier.verify(publicValues, proofData, APP_EXE_COMMIT, APP_VM_COMMIT);
+        return decodePublicValues(publicValues);
+    }
+
+    /// @notice Reverts if `appExeCommit` is not the commit of this application.
+    function checkAppExeCommit(bytes32 appExeCommit) internal pure {
+        if (appExeCommit != APP_EXE_COMMIT) revert AppExeCommitMismatch(APP_EXE_COMMIT, appExeCommit);
+    }
+
+    /// @notice Reverts if `appVmCommit` is not the VM commit of this application.
+    function checkAppVmCommit(bytes32 appVmCommit) internal pure {
+        if (appVmCommit != APP_VM_COMMIT) revert AppVmCommitMismatch(APP_VM_COMMIT, appVmCommit);
+    }
+
+    /// @notice Decodes the public values into `PublicValues`. The fields are
+    /// tightly packed in declaration order. Integers are little-endian, as
+    /// revealed by `reveal_u32` in the guest, while `bytesN` and `address`
+    /// values are read in order.
+    ///
+    /// @param publicValues The PVs revealed by the OpenVM guest program.
+    function decodePublicValues(bytes calldata publicValues) internal pure returns (PublicValues memory pvs) {
+        if (publicValues.length < PUBLIC_VALUES_SIZE) {
+            revert InvalidPublicValuesLength(PUBLIC_VALUES_SIZE, publicValues.length);
+        }
+{DECODE_FIELDS}
+    }
+
+    function _readUintLe(bytes calldata publicValues, uint256 offset, uint256 size)
+        private
+        pure
+        returns (uint256 value)
+    {
+        for (uint256 i = 0; i < size; ++i) {
+            value |= uint256(uint8(publicValues[offset + i])) << (8 * i);
+        }
+    }
+
+    function _readBool(bytes calldata publicValues, uint256 offset) private pure returns (bool) {
+        uint8 value = uint8(publicValues[offset]);
+        if (value > 1) revert InvalidBool(offset);
+        return value == 1;
+    }
+}
@@ -114,6 +114,25 @@ contract TemplateTest is Test {
         verifier.verify(pvs, _proofData, appExeCommit, appVmCommit);
     }
 
+    function test_PublicValuesCommit() public {
+        publicValuesLength = 40;
+        IOpenVmHalo2Verifier verifier = _compileAndDeployOpenVmVerifier(publicValuesLength);
+
+        bytes memory pvs = new bytes(publicValuesLength);
+        for (uint256 i = 0; i < publicValuesLength; i++) {
+            pvs[i] = bytes1(uint8(i));
+        }
+
+        // Same test vectors as the OpenVM SDK
+        assertEq(
+            verifier.publicValuesCommitKeccak256(pvs),
+            0xb36bb36dcacc3ecfc882ed1f75615b0cfbec2a196541bd7544032b519aca417e
+        );
+        assertEq(
+            verifier.publicValuesCommitSha256(pvs), 0x219d00b7ab7f3ab3d8faa7e68fa4589c3326f811e978798b85184466e448af74
+        );
+    }
+
     function _compileAndDeployOpenVmVerifier(uint256 _publicValuesLength)
         private
         returns (IOpenVmHalo2Verifier verifier)
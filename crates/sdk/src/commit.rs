@@ -1,8 +1,16 @@
 use std::{array::from_fn, sync::Arc};
 
+use eyre::Result;
 use num_bigint::BigUint;
+use openvm::io::{
+    extract_published_digest, public_values_commit, PUBLISHED_OFFSET, PUBLISHED_SIZE,
+};
 use openvm_circuit::{
-    arch::{instructions::exe::VmExe, VmConfig},
+    arch::{
+        hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
+        instructions::exe::VmExe,
+        VmConfig,
+    },
     system::program::trace::VmCommittedExe,
 };
 use openvm_native_compiler::ir::DIGEST_SIZE;
@@ -16,8 +24,10 @@ use openvm_stark_sdk::{
 };
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+use tiny_keccak::{Hasher as _, Keccak};
 
-use crate::{types::BN254_BYTES, NonRootCommittedExe, F, SC};
+use crate::{config::PublicValuesHasher, types::BN254_BYTES, NonRootCommittedExe, F, SC};
 
 /// Wrapper for an array of big-endian bytes, representing an unsigned big integer. Each commit can
 /// be converted to a Bn254Fr using the trivial identification as natural numbers or into a `u32`
@@ -99,6 +109,79 @@ impl AppExecutionCommit {
     }
 }
 
+/// Commits to the user public values with `hasher`.
+///
+/// For [PublicValuesHasher::Poseidon2], this is the Merkle root of the user public values as
+/// committed in the VM memory, as [CommitBytes]. For [PublicValuesHasher::Keccak256] and
+/// [PublicValuesHasher::Sha256], every public value must be a byte, and the bytes are committed
+/// with [openvm::io::public_values_commit], which guests can call too. This matches
+/// `publicValuesCommitKeccak256` and `publicValuesCommitSha256` of the EVM verifier.
+pub fn commit_user_public_values(
+    hasher: PublicValuesHasher,
+    user_public_values: &[F],
+) -> Result<[u8; 32]> {
+    let hash: fn(&[u8]) -> [u8; 32] = match hasher {
+        PublicValuesHasher::Poseidon2 => {
+            let root = vm_poseidon2_hasher().merkle_root(user_public_values);
+            return Ok(
+                *CommitBytes::from_u32_digest(&root.map(|x| x.as_canonical_u32())).as_slice(),
+            );
+        }
+        PublicValuesHasher::Keccak256 => |bytes| {
+            let mut output = [0u8; 32];
+            let mut hasher = Keccak::v256();
+            hasher.update(bytes);
+            hasher.finalize(&mut output);
+            output
+        },
+        PublicValuesHasher::Sha256 => |bytes| Sha256::digest(bytes).into(),
+    };
+
+    let bytes = user_public_values
+        .iter()
+        .map(|v| {
+            u8::try_from(v.as_canonical_u32())
+                .map_err(|_| eyre::eyre!("User public value {v} does not fit in a byte"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(public_values_commit(&bytes, hash))
+}
+
+/// Checks that `user_public_values`, one byte per value, commit to `data`, published by the guest
+/// with `openvm::io::publish_bytes`, one item per call. The user public values should be those of
+/// a verified proof, e.g. as returned by
+/// [verify_app_proof](crate::GenericSdk::verify_app_proof), which checks them against the final
+/// memory with their Merkle proof.
+pub fn check_published_bytes<I>(user_public_values: &[F], data: I) -> Result<()>
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    let public_values = user_public_values
+        .iter()
+        .map(|v| {
+            u8::try_from(v.as_canonical_u32())
+                .map_err(|_| eyre::eyre!("User public value {v} does not fit in a byte"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let Some(published_digest) = extract_published_digest(&public_values) else {
+        return Err(eyre::eyre!(
+            "Too few user public values to hold published data: expected at least {}, got {}",
+            PUBLISHED_OFFSET + PUBLISHED_SIZE,
+            public_values.len()
+        ));
+    };
+    let expected_digest = openvm::io::published_digest(data);
+    if published_digest != expected_digest {
+        return Err(eyre::eyre!(
+            "Invalid published data digest: expected {}, got {}",
+            hex::encode(expected_digest),
+            hex::encode(published_digest)
+        ));
+    }
+    Ok(())
+}
+
 pub fn commit_app_exe(
     app_fri_params: FriParameters,
     app_exe: impl Into<VmExe<F>>,
@@ -1,12 +1,16 @@
 use bon::Builder;
 use derive_more::derive::From;
 use openvm_algebra_circuit::{
-    Fp2Extension, Fp2ExtensionExecutor, Fp2ExtensionPeriphery, ModularExtension,
-    ModularExtensionExecutor, ModularExtensionPeriphery,
+    Fp2Extension, Fp2ExtensionExecutor, Fp2ExtensionPeriphery, Fp3Extension, Fp3ExtensionExecutor,
+    Fp3ExtensionPeriphery, ModularExtension, ModularExtensionExecutor, ModularExtensionPeriphery,
+};
+use openvm_algebra_transpiler::{
+    Fp2TranspilerExtension, Fp3TranspilerExtension, ModularTranspilerExtension,
 };
-use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
 use openvm_bigint_circuit::{Int256, Int256Executor, Int256Periphery};
 use openvm_bigint_transpiler::Int256TranspilerExtension;
+use openvm_blake3_circuit::{Blake3, Blake3Executor, Blake3Periphery};
+use openvm_blake3_transpiler::Blake3TranspilerExtension;
 use openvm_circuit::{
     arch::{
         InitFileGenerator, SystemConfig, SystemExecutor, SystemPeriphery, VmChipComplex, VmConfig,
@@ -30,15 +34,23 @@ use openvm_pairing_circuit::{
     PairingExtension, PairingExtensionExecutor, PairingExtensionPeriphery,
 };
 use openvm_pairing_transpiler::PairingTranspilerExtension;
+use openvm_poseidon2_circuit::{Poseidon2, Poseidon2Executor, Poseidon2Periphery};
+use openvm_poseidon2_transpiler::Poseidon2TranspilerExtension;
+use openvm_ripemd160_circuit::{Ripemd160, Ripemd160Executor, Ripemd160Periphery};
+use openvm_ripemd160_transpiler::Ripemd160TranspilerExtension;
+use openvm_rsa_circuit::{Rsa, RsaExecutor, RsaPeriphery};
+use openvm_rsa_transpiler::RsaTranspilerExtension;
 use openvm_rv32im_circuit::{
     Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
     Rv32MExecutor, Rv32MPeriphery,
 };
 use openvm_rv32im_transpiler::{
-    Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
+    Rv32ATranspilerExtension, Rv32CTranspilerExtension, Rv32ITranspilerExtension,
+    Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
 };
 use openvm_sha256_circuit::{Sha256, Sha256Executor, Sha256Periphery};
 use openvm_sha256_transpiler::Sha256TranspilerExtension;
+use openvm_softfloat_transpiler::SoftfloatTranspilerExtension;
 use openvm_stark_backend::p3_field::PrimeField32;
 use openvm_transpiler::transpiler::Transpiler;
 use serde::{Deserialize, Serialize};
@@ -54,13 +66,24 @@ pub struct SdkVmConfig {
     pub io: Option<UnitStruct>,
     pub keccak: Option<UnitStruct>,
     pub sha256: Option<UnitStruct>,
+    pub rsa: Option<UnitStruct>,
+    pub poseidon2: Option<UnitStruct>,
+    pub blake3: Option<UnitStruct>,
+    pub ripemd160: Option<UnitStruct>,
     pub native: Option<UnitStruct>,
     pub castf: Option<UnitStruct>,
+    /// Emulates the RISC-V F and D extensions in the guest, see [SoftfloatTranspilerExtension].
+    /// It only affects the transpiler.
+    pub softfloat: Option<UnitStruct>,
+    /// Lowers the word-sized instructions of the RISC-V A extension, see
+    /// [Rv32ATranspilerExtension]. It only affects the transpiler.
+    pub atomics: Option<UnitStruct>,
 
     pub rv32m: Option<Rv32M>,
     pub bigint: Option<Int256>,
     pub modular: Option<ModularExtension>,
     pub fp2: Option<Fp2Extension>,
+    pub fp3: Option<Fp3Extension>,
     pub pairing: Option<PairingExtension>,
     pub ecc: Option<WeierstrassExtension>,
 }
@@ -78,6 +101,14 @@ pub enum SdkVmConfigExecutor<F: PrimeField32> {
     #[any_enum]
     Sha256(Sha256Executor<F>),
     #[any_enum]
+    Rsa(RsaExecutor<F>),
+    #[any_enum]
+    Poseidon2(Poseidon2Executor<F>),
+    #[any_enum]
+    Blake3(Blake3Executor<F>),
+    #[any_enum]
+    Ripemd160(Ripemd160Executor<F>),
+    #[any_enum]
     Native(NativeExecutor<F>),
     #[any_enum]
     Rv32m(Rv32MExecutor<F>),
@@ -88,6 +119,8 @@ pub enum SdkVmConfigExecutor<F: PrimeField32> {
     #[any_enum]
     Fp2(Fp2ExtensionExecutor<F>),
     #[any_enum]
+    Fp3(Fp3ExtensionExecutor<F>),
+    #[any_enum]
     Pairing(PairingExtensionExecutor<F>),
     #[any_enum]
     Ecc(WeierstrassExtensionExecutor<F>),
@@ -108,6 +141,14 @@ pub enum SdkVmConfigPeriphery<F: PrimeField32> {
     #[any_enum]
     Sha256(Sha256Periphery<F>),
     #[any_enum]
+    Rsa(RsaPeriphery<F>),
+    #[any_enum]
+    Poseidon2(Poseidon2Periphery<F>),
+    #[any_enum]
+    Blake3(Blake3Periphery<F>),
+    #[any_enum]
+    Ripemd160(Ripemd160Periphery<F>),
+    #[any_enum]
     Native(NativePeriphery<F>),
     #[any_enum]
     Rv32m(Rv32MPeriphery<F>),
@@ -118,6 +159,8 @@ pub enum SdkVmConfigPeriphery<F: PrimeField32> {
     #[any_enum]
     Fp2(Fp2ExtensionPeriphery<F>),
     #[any_enum]
+    Fp3(Fp3ExtensionPeriphery<F>),
+    #[any_enum]
     Pairing(PairingExtensionPeriphery<F>),
     #[any_enum]
     Ecc(WeierstrassExtensionPeriphery<F>),
@@ -129,7 +172,12 @@ impl SdkVmConfig {
     pub fn transpiler(&self) -> Transpiler<F> {
         let mut transpiler = Transpiler::default();
         if self.rv32i.is_some() {
-            transpiler = transpiler.with_extension(Rv32ITranspilerExtension);
+            transpiler = transpiler
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32CTranspilerExtension);
+        }
+        if self.atomics.is_some() {
+            transpiler = transpiler.with_extension(Rv32ATranspilerExtension);
         }
         if self.io.is_some() {
             transpiler = transpiler.with_extension(Rv32IoTranspilerExtension);
@@ -140,6 +188,21 @@ impl SdkVmConfig {
         if self.sha256.is_some() {
             transpiler = transpiler.with_extension(Sha256TranspilerExtension);
         }
+        if self.rsa.is_some() {
+            transpiler = transpiler.with_extension(RsaTranspilerExtension);
+        }
+        if self.poseidon2.is_some() {
+            transpiler = transpiler.with_extension(Poseidon2TranspilerExtension);
+        }
+        if self.blake3.is_some() {
+            transpiler = transpiler.with_extension(Blake3TranspilerExtension);
+        }
+        if self.ripemd160.is_some() {
+            transpiler = transpiler.with_extension(Ripemd160TranspilerExtension);
+        }
+        if self.softfloat.is_some() {
+            transpiler = transpiler.with_extension(SoftfloatTranspilerExtension);
+        }
         if self.native.is_some() {
             transpiler = transpiler.with_extension(LongFormTranspilerExtension);
         }
@@ -155,12 +218,18 @@ impl SdkVmConfig {
         if self.fp2.is_some() {
             transpiler = transpiler.with_extension(Fp2TranspilerExtension);
         }
+        if self.fp3.is_some() {
+            transpiler = transpiler.with_extension(Fp3TranspilerExtension);
+        }
         if self.pairing.is_some() {
             transpiler = transpiler.with_extension(PairingTranspilerExtension);
         }
         if self.ecc.is_some() {
             transpiler = transpiler.with_extension(EccTranspilerExtension);
         }
+        if !self.system.config.hints_enabled {
+            transpiler = transpiler.without_hints();
+        }
         transpiler
     }
 }
@@ -194,6 +263,18 @@ impl<F: PrimeField32> VmConfig<F> for SdkVmConfig {
         if self.sha256.is_some() {
             complex = complex.extend(&Sha256)?;
         }
+        if self.rsa.is_some() {
+            complex = complex.extend(&Rsa)?;
+        }
+        if self.poseidon2.is_some() {
+            complex = complex.extend(&Poseidon2)?;
+        }
+        if self.blake3.is_some() {
+            complex = complex.extend(&Blake3)?;
+        }
+        if self.ripemd160.is_some() {
+            complex = complex.extend(&Ripemd160)?;
+        }
         if self.native.is_some() {
             complex = complex.extend(&Native)?;
         }
@@ -227,6 +308,9 @@ impl<F: PrimeField32> VmConfig<F> for SdkVmConfig {
         if let Some(ref fp2) = self.fp2 {
             complex = complex.extend(fp2)?;
         }
+        if let Some(ref fp3) = self.fp3 {
+            complex = complex.extend(fp3)?;
+        }
         if let Some(ref pairing) = self.pairing {
             complex = complex.extend(pairing)?;
         }
@@ -240,7 +324,8 @@ impl<F: PrimeField32> VmConfig<F> for SdkVmConfig {
 
 impl InitFileGenerator for SdkVmConfig {
     fn generate_init_file_contents(&self) -> Option<String> {
-        if self.modular.is_some() || self.fp2.is_some() || self.ecc.is_some() {
+        if self.modular.is_some() || self.fp2.is_some() || self.fp3.is_some() || self.ecc.is_some()
+        {
             let mut contents = String::new();
             contents.push_str(
                 "// This file is automatically generated by cargo openvm. Do not rename or edit.\n",
@@ -261,6 +346,16 @@ impl InitFileGenerator for SdkVmConfig {
                 contents.push('\n');
             }
 
+            if let Some(fp3_config) = &self.fp3 {
+                assert!(
+                    self.modular.is_some(),
+                    "ModularExtension is required for Fp3Extension"
+                );
+                let modular_config = self.modular.as_ref().unwrap();
+                contents.push_str(&fp3_config.generate_cubic_init(modular_config));
+                contents.push('\n');
+            }
+
             if let Some(ecc_config) = &self.ecc {
                 contents.push_str(&ecc_config.generate_sw_init());
                 contents.push('\n');
@@ -324,6 +419,30 @@ impl From<Sha256> for UnitStruct {
     }
 }
 
+impl From<Rsa> for UnitStruct {
+    fn from(_: Rsa) -> Self {
+        UnitStruct {}
+    }
+}
+
+impl From<Poseidon2> for UnitStruct {
+    fn from(_: Poseidon2) -> Self {
+        UnitStruct {}
+    }
+}
+
+impl From<Blake3> for UnitStruct {
+    fn from(_: Blake3) -> Self {
+        UnitStruct {}
+    }
+}
+
+impl From<Ripemd160> for UnitStruct {
+    fn from(_: Ripemd160) -> Self {
+        UnitStruct {}
+    }
+}
+
 impl From<Native> for UnitStruct {
     fn from(_: Native) -> Self {
         UnitStruct {}
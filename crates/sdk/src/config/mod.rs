@@ -1,12 +1,17 @@
 use clap::Args;
-use openvm_circuit::arch::DEFAULT_MAX_NUM_PUBLIC_VALUES;
+use eyre::{bail, Result};
+use openvm_circuit::arch::{VmConfig, DEFAULT_MAX_NUM_PUBLIC_VALUES};
 use openvm_continuations::verifier::{
     common::types::VmVerifierPvs, internal::types::InternalVmVerifierPvs,
 };
 use openvm_native_circuit::NativeConfig;
 use openvm_native_compiler::{conversion::CompilerOptions, ir::DIGEST_SIZE};
-use openvm_stark_sdk::config::FriParameters;
+use openvm_stark_sdk::{config::FriParameters, openvm_stark_backend::p3_field::FieldAlgebra};
+use openvm_transpiler::transpiler::Transpiler;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::F;
 
 mod global;
 pub use global::*;
@@ -21,6 +26,8 @@ const DEFAULT_NUM_CHILDREN_LEAF: usize = 1;
 const DEFAULT_NUM_CHILDREN_INTERNAL: usize = 3;
 const DEFAULT_MAX_INTERNAL_WRAPPER_LAYERS: usize = 4;
 
+const DEFAULT_MAX_SEGMENT_PARALLELISM: usize = 1;
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AppConfig<VC> {
     #[serde(default)]
@@ -31,6 +38,28 @@ pub struct AppConfig<VC> {
     /// Only for AggVM debugging. App VM users should not need this in regular flow.
     #[serde(default)]
     pub compiler_options: CompilerOptions,
+    /// Hash function of the user public values commitment reported to verifiers.
+    #[serde(default)]
+    pub public_values_hasher: PublicValuesHasher,
+    /// Whether the [config digest](AppConfig::config_digest) is readable by the guest program and
+    /// written to the last 32 user public values, see [AppConfig::transpiler].
+    #[serde(default)]
+    pub expose_config_digest: bool,
+}
+
+/// Hash function used to commit to the user public values for verifiers outside of OpenVM, see
+/// [commit_user_public_values](crate::commit::commit_user_public_values).
+///
+/// Inside the VM, the user public values are always part of the Poseidon2 memory Merkle tree.
+/// Keccak256 and SHA-256 are for verifiers, such as EVM chains, that cannot compute Poseidon2
+/// cheaply.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PublicValuesHasher {
+    #[default]
+    Poseidon2,
+    Keccak256,
+    Sha256,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -97,6 +126,56 @@ pub struct AggregationTreeConfig {
     // root currently always has 1 child for now
 }
 
+/// Bounds on the resources used to prove, so that proving fits smaller machines.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Args)]
+pub struct ProverPerfConfig {
+    /// Number of threads of the rayon thread pool which generates the traces and proves, or all
+    /// cores if unset. See [ProverPerfConfig::init_thread_pool].
+    #[arg(
+        long,
+        help = "Number of proving threads [default: number of cores]",
+        help_heading = "Prover Performance Options"
+    )]
+    pub num_threads: Option<usize>,
+    /// Maximum number of app segments proven at the same time. The segments are proven one at a
+    /// time by default, each using all threads.
+    #[arg(
+        long,
+        default_value_t = DEFAULT_MAX_SEGMENT_PARALLELISM,
+        help = "Maximum number of segments proven at the same time",
+        help_heading = "Prover Performance Options"
+    )]
+    pub max_segment_parallelism: usize,
+    /// Bytes of memory which the app segments proven at the same time may take, estimated from
+    /// their trace cells. A segment is always proven once no other segment is, even if its
+    /// estimate alone exceeds the budget.
+    #[arg(
+        long,
+        help = "Memory budget in bytes of the segments proven at the same time",
+        help_heading = "Prover Performance Options"
+    )]
+    pub memory_budget: Option<usize>,
+}
+
+impl ProverPerfConfig {
+    /// Configures the global rayon thread pool with [Self::num_threads] threads. The global pool
+    /// can only be configured before its first use, so if it is already running with another
+    /// number of threads, this logs a warning and keeps it.
+    pub fn init_thread_pool(&self) {
+        let Some(num_threads) = self.num_threads else {
+            return;
+        };
+        let result = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build_global();
+        if let Err(err) = result {
+            if rayon::current_num_threads() != num_threads {
+                tracing::warn!("cannot prove with {num_threads} threads: {err}");
+            }
+        }
+    }
+}
+
 impl<VC> AppConfig<VC> {
     pub fn new(app_fri_params: FriParameters, app_vm_config: VC) -> Self {
         Self {
@@ -104,6 +183,8 @@ impl<VC> AppConfig<VC> {
             app_vm_config,
             leaf_fri_params: Default::default(),
             compiler_options: Default::default(),
+            public_values_hasher: Default::default(),
+            expose_config_digest: false,
         }
     }
 
@@ -117,6 +198,104 @@ impl<VC> AppConfig<VC> {
             app_vm_config,
             leaf_fri_params: LeafFriParams::from(leaf_fri_params),
             compiler_options: Default::default(),
+            public_values_hasher: Default::default(),
+            expose_config_digest: false,
+        }
+    }
+}
+
+impl<VC: VmConfig<F>> AppConfig<VC> {
+    /// Number of user public values of the app VM. Aggregation configs derived with
+    /// [AggStarkConfig::for_app] or [AggConfig::for_app], and the root verifier and Solidity
+    /// verifier generated from them, use the same number.
+    pub fn num_public_values(&self) -> usize {
+        self.app_vm_config.system().num_public_values
+    }
+
+    pub fn with_num_public_values(mut self, num_public_values: usize) -> Self {
+        self.app_vm_config.system_mut().num_public_values = num_public_values;
+        self
+    }
+}
+
+impl<VC: Serialize> AppConfig<VC> {
+    /// The SHA-256 digest of the app VM config, which identifies the semantics of the VM. See
+    /// [AppConfig::expose_config_digest].
+    pub fn config_digest(&self) -> Result<[u8; 32]> {
+        Ok(Sha256::digest(bitcode::serialize(&self.app_vm_config)?).into())
+    }
+}
+
+impl AppConfig<SdkVmConfig> {
+    /// The transpiler of the app VM. If [AppConfig::expose_config_digest] is set, guest programs
+    /// can read the [config digest](AppConfig::config_digest) with
+    /// `openvm::platform::capabilities::config_digest`, and the digest is written to the last 32
+    /// user public values, so that application protocols can bind their outputs to the VM which
+    /// computed them. A program which overwrites them fails [check_config_digest], which the
+    /// verification of app proofs runs.
+    pub fn transpiler(&self) -> Result<Transpiler<F>> {
+        let transpiler = self.app_vm_config.transpiler();
+        if !self.expose_config_digest {
+            return Ok(transpiler);
+        }
+        let num_public_values = self.num_public_values();
+        if num_public_values < 64 {
+            bail!(
+                "the config digest takes the last 32 of the user public values, so there must be \
+                 at least 64, but there are {num_public_values}"
+            );
+        }
+        Ok(transpiler
+            .with_config_digest(self.config_digest()?)
+            .with_config_digest_public_value((num_public_values - 32) as u32))
+    }
+}
+
+/// Checks that `user_public_values` end with `config_digest`, which the transpiler of an app
+/// config with [AppConfig::expose_config_digest] writes there. The app verifying key holds the
+/// digest of the config it was generated from, so this binds the digest to the verified proof.
+pub fn check_config_digest(config_digest: &[u8; 32], user_public_values: &[F]) -> Result<()> {
+    let Some(start) = user_public_values.len().checked_sub(config_digest.len()) else {
+        bail!("there are fewer user public values than bytes of the config digest");
+    };
+    let written = &user_public_values[start..];
+    if written
+        .iter()
+        .zip(config_digest)
+        .any(|(value, &byte)| *value != F::from_canonical_u8(byte))
+    {
+        bail!("the user public values do not end with the config digest: {written:?}");
+    }
+    Ok(())
+}
+
+/// Panics if `num_public_values` cannot be committed to by the user public values Merkle tree,
+/// i.e. if it is not `DIGEST_SIZE` times a power of two.
+pub(crate) fn assert_valid_num_public_values(num_public_values: usize) {
+    assert!(
+        num_public_values % DIGEST_SIZE == 0 && (num_public_values / DIGEST_SIZE).is_power_of_two(),
+        "num_public_values must be {DIGEST_SIZE} times a power of two, got {num_public_values}"
+    );
+}
+
+impl AggStarkConfig {
+    /// Default aggregation config that is compatible with the app config: the leaf FRI
+    /// parameters and the number of user public values are taken from `app_config`.
+    pub fn for_app<VC: VmConfig<F>>(app_config: &AppConfig<VC>) -> Self {
+        Self {
+            max_num_user_public_values: app_config.num_public_values(),
+            leaf_fri_params: app_config.leaf_fri_params.fri_params,
+            ..Default::default()
+        }
+    }
+}
+
+impl AggConfig {
+    /// Default aggregation config whose STARK part is [AggStarkConfig::for_app].
+    pub fn for_app<VC: VmConfig<F>>(app_config: &AppConfig<VC>) -> Self {
+        Self {
+            agg_stark_config: AggStarkConfig::for_app(app_config),
+            ..Default::default()
         }
     }
 }
@@ -154,6 +333,30 @@ impl Default for AggConfig {
     }
 }
 
+/// The app config used when a program has no `openvm.toml`: the RV32IM VM with the default
+/// system config and FRI parameters.
+pub fn default_app_config() -> AppConfig<SdkVmConfig> {
+    AppConfig {
+        app_fri_params: FriParameters::standard_with_100_bits_conjectured_security(
+            DEFAULT_APP_LOG_BLOWUP,
+        )
+        .into(),
+        app_vm_config: SdkVmConfig::builder()
+            .system(Default::default())
+            .rv32i(Default::default())
+            .rv32m(Default::default())
+            .io(Default::default())
+            .build(),
+        leaf_fri_params: FriParameters::standard_with_100_bits_conjectured_security(
+            DEFAULT_LEAF_LOG_BLOWUP,
+        )
+        .into(),
+        compiler_options: Default::default(),
+        public_values_hasher: Default::default(),
+        expose_config_digest: false,
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AppFriParams {
     pub fri_params: FriParameters,
@@ -235,3 +438,13 @@ impl Default for AggregationTreeConfig {
         }
     }
 }
+
+impl Default for ProverPerfConfig {
+    fn default() -> Self {
+        Self {
+            num_threads: None,
+            max_segment_parallelism: DEFAULT_MAX_SEGMENT_PARALLELISM,
+            memory_budget: None,
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::{
-    fs::{create_dir_all, read, write, File},
-    path::Path,
+    env,
+    fs::{create_dir_all, read, read_to_string, write, File},
+    path::{Path, PathBuf},
 };
 
 use eyre::{Report, Result};
@@ -8,33 +9,73 @@ use openvm_circuit::arch::{instructions::exe::VmExe, ContinuationVmProof, VmConf
 use openvm_continuations::verifier::root::types::RootVmVerifierInput;
 #[cfg(feature = "evm-prove")]
 use openvm_native_recursion::halo2::wrapper::EvmVerifierByteCode;
+use openvm_transpiler::{metadata::TranspilerMetadataError, transpiler::Transpiler};
 use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     codec::{Decode, Encode},
+    config::{default_app_config, AppConfig, SdkVmConfig},
     keygen::{AggStarkProvingKey, AppProvingKey, AppVerifyingKey},
-    F, SC,
+    symbols::SymbolSidecar,
+    F, OPENVM_VERSION, SC,
 };
 #[cfg(feature = "evm-prove")]
 use crate::{
     keygen::Halo2ProvingKey,
     types::{EvmHalo2Verifier, EvmProof},
-    OPENVM_VERSION,
 };
 
 pub const EVM_HALO2_VERIFIER_INTERFACE_NAME: &str = "IOpenVmHalo2Verifier.sol";
 pub const EVM_HALO2_VERIFIER_PARENT_NAME: &str = "Halo2Verifier.sol";
 pub const EVM_HALO2_VERIFIER_BASE_NAME: &str = "OpenVmHalo2Verifier.sol";
+pub const EVM_PROOF_LIB_NAME: &str = "OpenVmProofLib.sol";
 pub const EVM_VERIFIER_ARTIFACT_FILENAME: &str = "verifier.bytecode.json";
 
 pub fn read_exe_from_file<P: AsRef<Path>>(path: P) -> Result<VmExe<F>> {
     read_from_file_bitcode(&path)
 }
 
+/// Reads an exe written by [write_exe_to_file] and checks its transpiler metadata against
+/// `transpiler`. An exe transpiled with a different opcode map is rejected, since its opcodes may
+/// mean something else to the VM. A different transpiler version or missing metadata only results
+/// in a warning: the exe can still be executed, but its commit may differ from the commit of the
+/// exe which the current transpiler produces from the same ELF.
+pub fn read_exe_from_file_checked<P: AsRef<Path>>(
+    path: P,
+    transpiler: &Transpiler<F>,
+) -> Result<VmExe<F>> {
+    let exe = read_exe_from_file(&path)?;
+    match transpiler.check_metadata(&exe) {
+        Ok(()) => {}
+        Err(err @ TranspilerMetadataError::OpcodeMapMismatch { .. }) => {
+            return Err(eyre::eyre!(
+                "{}: {err}; rebuild the exe with the current VM config",
+                path.as_ref().display()
+            ));
+        }
+        Err(err) => tracing::warn!("{}: {err}", path.as_ref().display()),
+    }
+    Ok(exe)
+}
+
 pub fn write_exe_to_file<P: AsRef<Path>>(exe: VmExe<F>, path: P) -> Result<()> {
     write_to_file_bitcode(&path, exe)
 }
 
+pub fn read_symbols_from_file<P: AsRef<Path>>(path: P) -> Result<SymbolSidecar> {
+    read_from_file_json(&path)
+}
+
+pub fn write_symbols_to_file<P: AsRef<Path>>(symbols: &SymbolSidecar, path: P) -> Result<()> {
+    write_to_file_json(&path, symbols)
+}
+
+/// The directory of the [KeyCache](crate::keygen::cache::KeyCache) of the SDK by default,
+/// `~/.openvm/cache`, or `None` if `HOME` is not set.
+pub fn default_cache_dir() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".openvm/cache"))
+}
+
 pub fn read_app_pk_from_file<VC: VmConfig<F>, P: AsRef<Path>>(
     path: P,
 ) -> Result<AppProvingKey<VC>> {
@@ -185,6 +226,22 @@ pub fn write_evm_halo2_verifier_to_folder<P: AsRef<Path>>(
     Ok(())
 }
 
+/// Writes the `OpenVmProofLib` Solidity library generated by
+/// [generate_openvm_proof_lib](crate::proof_lib::generate_openvm_proof_lib) next to the verifier
+/// contracts written by `write_evm_halo2_verifier_to_folder`, i.e. to
+/// `src/v[OPENVM_VERSION]/OpenVmProofLib.sol` in `folder`.
+///
+/// If the relevant directories do not exist, they will be created.
+pub fn write_evm_proof_lib_to_folder<P: AsRef<Path>>(proof_lib: &str, folder: P) -> Result<()> {
+    let folder = folder
+        .as_ref()
+        .join("src")
+        .join(format!("v{}", OPENVM_VERSION));
+    create_dir_all(&folder)?;
+    write(folder.join(EVM_PROOF_LIB_NAME), proof_lib)?;
+    Ok(())
+}
+
 pub fn read_object_from_file<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Result<T> {
     read_from_file_bitcode(path)
 }
@@ -219,6 +276,20 @@ pub fn read_from_file_json<T: DeserializeOwned, P: AsRef<Path>>(path: P) -> Resu
     Ok(ret)
 }
 
+/// Reads the app config from the `openvm.toml` at `path`, or returns the
+/// [default app config](default_app_config) if there is no file at `path`.
+pub fn read_config_toml_or_default<P: AsRef<Path>>(path: P) -> Result<AppConfig<SdkVmConfig>> {
+    if !path.as_ref().exists() {
+        println!(
+            "{:?} not found, using default application configuration",
+            path.as_ref()
+        );
+        return Ok(default_app_config());
+    }
+    let toml = read_to_string(&path).map_err(|e| read_error(&path, e.into()))?;
+    Ok(toml::from_str(&toml)?)
+}
+
 pub fn write_to_file_json<T: Serialize, P: AsRef<Path>>(path: P, data: T) -> Result<()> {
     if let Some(parent) = path.as_ref().parent() {
         create_dir_all(parent).map_err(|e| write_error(&path, e.into()))?;
@@ -0,0 +1,234 @@
+//! A disk cache of proving keys and committed exes, so that they are generated once per config
+//! instead of on every process start.
+//!
+//! Each entry is stored in its own file, named after its kind and the SHA-256 digest of the OpenVM
+//! version and of the bitcode encoding of everything the entry is generated from. The bitcode
+//! encoding of the entry is prefixed with a header holding [CACHE_MAGIC], [CACHE_FORMAT_VERSION],
+//! the digest the entry is cached under, the length of the encoding and its SHA-256 checksum. An
+//! entry whose header does not match, e.g. because it was truncated when its process was killed or
+//! written with another cache format, is generated again and overwritten.
+
+use std::{
+    fs::{create_dir_all, read},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use eyre::Result;
+use openvm_circuit::arch::{instructions::exe::VmExe, VmConfig};
+use openvm_stark_sdk::{config::FriParameters, openvm_stark_backend::Chip};
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+use thiserror::Error;
+
+use crate::{
+    commit::commit_app_exe,
+    config::{AggStarkConfig, AppConfig},
+    keygen::{AggStarkProvingKey, AppProvingKey},
+    NonRootCommittedExe, F, OPENVM_VERSION, SC,
+};
+
+/// The first bytes of every cache entry.
+pub const CACHE_MAGIC: [u8; 8] = *b"OVMCACHE";
+/// The version of the layout of cache entries. Entries with another version are generated again.
+pub const CACHE_FORMAT_VERSION: u32 = 1;
+
+/// Magic, format version, digest, payload length and payload checksum.
+const HEADER_LEN: usize = 8 + 4 + 32 + 8 + 32;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CacheEntryError {
+    #[error("not a cache entry")]
+    Magic,
+    #[error("cache format version {0}, expected {CACHE_FORMAT_VERSION}")]
+    FormatVersion(u32),
+    #[error("the entry was cached under another digest")]
+    Digest,
+    #[error("the entry has {actual} bytes of payload, expected {expected}")]
+    Length { expected: u64, actual: u64 },
+    #[error("the checksum of the payload does not match")]
+    Checksum,
+}
+
+/// A directory of cached proving keys and committed exes, see the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct KeyCache {
+    dir: PathBuf,
+}
+
+impl KeyCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Reads the app proving key for `config` from the cache, or generates and caches it.
+    pub fn app_pk<VC: VmConfig<F>>(&self, config: AppConfig<VC>) -> Result<AppProvingKey<VC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let digest = self.digest("app-pk", &config)?;
+        self.get_or_insert("app-pk", digest, || AppProvingKey::keygen(config))
+    }
+
+    /// Reads the aggregation STARK proving key for `config` from the cache, or generates and
+    /// caches it.
+    pub fn agg_stark_pk(&self, config: AggStarkConfig) -> Result<AggStarkProvingKey> {
+        let digest = self.digest("agg-stark-pk", &config)?;
+        self.get_or_insert("agg-stark-pk", digest, || {
+            AggStarkProvingKey::keygen(config)
+        })
+    }
+
+    /// Reads the commitment of `exe` with `app_fri_params` from the cache, or computes and caches
+    /// it.
+    pub fn committed_exe(
+        &self,
+        app_fri_params: FriParameters,
+        exe: VmExe<F>,
+    ) -> Result<Arc<NonRootCommittedExe>> {
+        let digest = self.digest("committed-exe", &(app_fri_params, &exe))?;
+        self.get_or_insert("committed-exe", digest, || {
+            commit_app_exe(app_fri_params, exe)
+        })
+    }
+
+    /// The path of the entry of kind `kind` cached under `digest`.
+    pub fn path(&self, kind: &str, digest: [u8; 32]) -> PathBuf {
+        self.dir.join(format!("{kind}-{}", hex::encode(digest)))
+    }
+
+    fn digest(&self, kind: &str, inputs: &impl Serialize) -> Result<[u8; 32]> {
+        let mut hasher = Sha256::new();
+        hasher.update(OPENVM_VERSION);
+        hasher.update(kind);
+        hasher.update(bitcode::serialize(inputs)?);
+        Ok(hasher.finalize().into())
+    }
+
+    /// Reads the entry of kind `kind` cached under `digest`, or generates it and writes it to the
+    /// cache. Failing to write the entry is not an error, since the generated value is still valid.
+    fn get_or_insert<T: Serialize + DeserializeOwned>(
+        &self,
+        kind: &str,
+        digest: [u8; 32],
+        generate: impl FnOnce() -> T,
+    ) -> Result<T> {
+        let path = self.path(kind, digest);
+        if path.exists() {
+            match read_entry(&path, digest) {
+                Ok(value) => return Ok(value),
+                Err(err) => tracing::warn!(
+                    "generating {} again, failed to read it: {err}",
+                    path.display()
+                ),
+            }
+        }
+        let value = generate();
+        if let Err(err) = self.write_entry(&path, digest, &value) {
+            tracing::warn!("failed to cache {}: {err}", path.display());
+        }
+        Ok(value)
+    }
+
+    /// Writes the entry to a temporary file first, so that other processes never read it partially
+    /// written.
+    fn write_entry<T: Serialize>(&self, path: &Path, digest: [u8; 32], value: &T) -> Result<()> {
+        create_dir_all(&self.dir)?;
+        let mut file = NamedTempFile::new_in(&self.dir)?;
+        file.write_all(&encode_entry(digest, &bitcode::serialize(value)?))?;
+        file.persist(path)?;
+        Ok(())
+    }
+}
+
+fn read_entry<T: DeserializeOwned>(path: &Path, digest: [u8; 32]) -> Result<T> {
+    let bytes = read(path)?;
+    let payload = decode_entry(&bytes, digest)?;
+    Ok(bitcode::deserialize(payload)?)
+}
+
+/// Prefixes `payload` with the header of an entry cached under `digest`.
+pub fn encode_entry(digest: [u8; 32], payload: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+    bytes.extend_from_slice(&CACHE_MAGIC);
+    bytes.extend_from_slice(&CACHE_FORMAT_VERSION.to_le_bytes());
+    bytes.extend_from_slice(&digest);
+    bytes.extend_from_slice(&(payload.len() as u64).to_le_bytes());
+    bytes.extend_from_slice(&Sha256::digest(payload));
+    bytes.extend_from_slice(payload);
+    bytes
+}
+
+/// Checks the header of an entry written by [encode_entry] against `digest` and returns its
+/// payload.
+pub fn decode_entry(bytes: &[u8], digest: [u8; 32]) -> Result<&[u8], CacheEntryError> {
+    if bytes.len() < HEADER_LEN || bytes[..8] != CACHE_MAGIC {
+        return Err(CacheEntryError::Magic);
+    }
+    let (header, payload) = bytes.split_at(HEADER_LEN);
+    let version = u32::from_le_bytes(header[8..12].try_into().unwrap());
+    if version != CACHE_FORMAT_VERSION {
+        return Err(CacheEntryError::FormatVersion(version));
+    }
+    if header[12..44] != digest {
+        return Err(CacheEntryError::Digest);
+    }
+    let expected = u64::from_le_bytes(header[44..52].try_into().unwrap());
+    if payload.len() as u64 != expected {
+        return Err(CacheEntryError::Length {
+            expected,
+            actual: payload.len() as u64,
+        });
+    }
+    if Sha256::digest(payload)[..] != header[52..] {
+        return Err(CacheEntryError::Checksum);
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_entry() {
+        let digest = [7; 32];
+        let payload = b"proving key";
+        let entry = encode_entry(digest, payload);
+        assert_eq!(decode_entry(&entry, digest), Ok(&payload[..]));
+
+        assert_eq!(decode_entry(&entry, [8; 32]), Err(CacheEntryError::Digest));
+        assert_eq!(
+            decode_entry(&entry[..entry.len() - 1], digest),
+            Err(CacheEntryError::Length {
+                expected: payload.len() as u64,
+                actual: payload.len() as u64 - 1,
+            })
+        );
+        assert_eq!(
+            decode_entry(&entry[..20], digest),
+            Err(CacheEntryError::Magic)
+        );
+
+        let mut corrupted = entry.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            decode_entry(&corrupted, digest),
+            Err(CacheEntryError::Checksum)
+        );
+
+        let mut other_version = entry;
+        other_version[8..12].copy_from_slice(&(CACHE_FORMAT_VERSION + 1).to_le_bytes());
+        assert_eq!(
+            decode_entry(&other_version, digest),
+            Err(CacheEntryError::FormatVersion(CACHE_FORMAT_VERSION + 1))
+        );
+    }
+}
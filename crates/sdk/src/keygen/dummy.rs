@@ -129,6 +129,10 @@ fn dummy_leaf_proof_impl<VC: VmConfig<F>>(
     let leaf_program = LeafVmVerifierConfig {
         app_fri_params: app_vm_pk.fri_params,
         app_system_config: app_vm_pk.vm_config.system().clone(),
+        app_extension_versions: app_vm_pk
+            .vm_config
+            .extension_versions()
+            .expect("Failed to create chip complex"),
         compiler_options: Default::default(),
     }
     .build_program(&app_vm_pk.vm_pk.get_vk());
@@ -43,13 +43,14 @@ use {
 
 use crate::{
     commit::babybear_digest_to_bn254,
-    config::{AggStarkConfig, AppConfig},
+    config::{assert_valid_num_public_values, AggStarkConfig, AppConfig, PublicValuesHasher},
     keygen::perm::AirIdPermutation,
     prover::vm::types::VmProvingKey,
     NonRootCommittedExe, RootSC, F, SC,
 };
 
 pub mod asm;
+pub mod cache;
 pub(crate) mod dummy;
 pub mod perm;
 #[cfg(feature = "evm-prove")]
@@ -60,6 +61,12 @@ pub struct AppProvingKey<VC> {
     pub leaf_committed_exe: Arc<NonRootCommittedExe>,
     pub leaf_fri_params: FriParameters,
     pub app_vm_pk: Arc<VmProvingKey<SC, VC>>,
+    #[serde(default)]
+    pub public_values_hasher: PublicValuesHasher,
+    /// The [config digest](AppConfig::config_digest) which the user public values end with, if
+    /// [AppConfig::expose_config_digest] is set.
+    #[serde(default)]
+    pub config_digest: Option<[u8; 32]>,
 }
 
 #[derive(Clone, Serialize, Deserialize)]
@@ -67,6 +74,12 @@ pub struct AppVerifyingKey {
     pub fri_params: FriParameters,
     pub app_vm_vk: MultiStarkVerifyingKey<SC>,
     pub memory_dimensions: MemoryDimensions,
+    #[serde(default)]
+    pub public_values_hasher: PublicValuesHasher,
+    /// The [config digest](AppConfig::config_digest) which the user public values of the proofs
+    /// must end with, see [check_config_digest](crate::config::check_config_digest).
+    #[serde(default)]
+    pub config_digest: Option<[u8; 32]>,
 }
 
 #[cfg(feature = "evm-prove")]
@@ -84,6 +97,18 @@ pub struct AggStarkProvingKey {
     pub root_verifier_pk: RootVerifierProvingKey,
 }
 
+/// The parts of an [AggStarkProvingKey] needed to verify end-to-end STARK proofs, see
+/// [Sdk::verify_e2e_stark_proof_with_vk](crate::Sdk::verify_e2e_stark_proof_with_vk).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AggStarkVerifyingKey {
+    pub leaf_fri_params: FriParameters,
+    pub leaf_vm_vk: MultiStarkVerifyingKey<SC>,
+    pub internal_fri_params: FriParameters,
+    pub internal_vm_vk: MultiStarkVerifyingKey<SC>,
+    /// Commitment to the program of the internal verifier.
+    pub internal_program_commit: [F; DIGEST_SIZE],
+}
+
 /// Attention: the size of this struct is VERY large, usually >10GB.
 #[cfg(feature = "evm-prove")]
 #[derive(Clone, Serialize, Deserialize)]
@@ -103,6 +128,12 @@ where
     VC::Periphery: Chip<SC>,
 {
     pub fn keygen(config: AppConfig<VC>) -> Self {
+        assert_valid_num_public_values(config.num_public_values());
+        let config_digest = config.expose_config_digest.then(|| {
+            config
+                .config_digest()
+                .expect("Failed to serialize the app VM config")
+        });
         let app_engine = BabyBearPoseidon2Engine::new(config.app_fri_params.fri_params);
         let app_vm_pk = {
             let vm = VirtualMachine::new(app_engine, config.app_vm_config.clone());
@@ -127,6 +158,10 @@ where
             let leaf_program = LeafVmVerifierConfig {
                 app_fri_params: config.app_fri_params.fri_params,
                 app_system_config: config.app_vm_config.system().clone(),
+                app_extension_versions: config
+                    .app_vm_config
+                    .extension_versions()
+                    .expect("Failed to create chip complex"),
                 compiler_options: config.compiler_options,
             }
             .build_program(&app_vm_pk.vm_pk.get_vk());
@@ -139,6 +174,8 @@ where
             leaf_committed_exe,
             leaf_fri_params: config.leaf_fri_params.fri_params,
             app_vm_pk: Arc::new(app_vm_pk),
+            public_values_hasher: config.public_values_hasher,
+            config_digest,
         }
     }
 
@@ -156,6 +193,8 @@ where
                 .system()
                 .memory_config
                 .memory_dimensions(),
+            public_values_hasher: self.public_values_hasher,
+            config_digest: self.config_digest,
         }
     }
 
@@ -172,6 +211,28 @@ where
     }
 }
 
+impl<VC: VmConfig<F>> AppProvingKey<VC> {
+    /// Returns an error if proofs of this app cannot be aggregated with `agg_stark_pk`.
+    pub fn check_agg_compatibility(&self, agg_stark_pk: &AggStarkProvingKey) -> eyre::Result<()> {
+        if self.leaf_fri_params != agg_stark_pk.leaf_vm_pk.fri_params {
+            return Err(eyre::eyre!(
+                "App VM is incompatible with Agg VM because of leaf FRI parameters: app uses {:?}, agg uses {:?}",
+                self.leaf_fri_params,
+                agg_stark_pk.leaf_vm_pk.fri_params
+            ));
+        }
+        let num_public_values = self.app_vm_pk.vm_config.system().num_public_values;
+        if num_public_values != agg_stark_pk.num_user_public_values() {
+            return Err(eyre::eyre!(
+                "App VM is incompatible with Agg VM because of the number of public values: app has {}, agg has {}",
+                num_public_values,
+                agg_stark_pk.num_user_public_values()
+            ));
+        }
+        Ok(())
+    }
+}
+
 /// Try to determine statically if there will be an issue with the recursive verifier size and log
 /// a warning if so.
 ///
@@ -262,6 +323,7 @@ impl AggStarkProvingKey {
     }
 
     pub fn dummy_proof_and_keygen(config: AggStarkConfig) -> (Self, Proof<SC>) {
+        assert_valid_num_public_values(config.max_num_user_public_values);
         let leaf_vm_config = config.leaf_vm_config();
         let internal_vm_config = config.internal_vm_config();
         let root_vm_config = config.root_verifier_vm_config();
@@ -376,6 +438,16 @@ impl AggStarkProvingKey {
         self.internal_committed_exe.get_program_commit().into()
     }
 
+    pub fn get_agg_stark_vk(&self) -> AggStarkVerifyingKey {
+        AggStarkVerifyingKey {
+            leaf_fri_params: self.leaf_vm_pk.fri_params,
+            leaf_vm_vk: self.leaf_vm_pk.vm_pk.get_vk(),
+            internal_fri_params: self.internal_vm_pk.fri_params,
+            internal_vm_vk: self.internal_vm_pk.vm_pk.get_vk(),
+            internal_program_commit: self.internal_program_commit(),
+        }
+    }
+
     pub fn num_user_public_values(&self) -> usize {
         self.root_verifier_pk
             .vm_pk
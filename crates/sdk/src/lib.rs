@@ -1,9 +1,17 @@
-use std::{borrow::Borrow, fs::read, marker::PhantomData, path::Path, sync::Arc};
+use std::{
+    borrow::Borrow,
+    fs::read,
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 #[cfg(feature = "evm-verify")]
 use alloy_sol_types::sol;
-use commit::{commit_app_exe, AppExecutionCommit};
-use config::{AggregationTreeConfig, AppConfig};
+use commit::{
+    check_published_bytes, commit_app_exe, commit_user_public_values, AppExecutionCommit,
+};
+use config::{AggregationTreeConfig, AppConfig, ProverPerfConfig};
 use eyre::Result;
 use keygen::{AppProvingKey, AppVerifyingKey};
 use openvm_build::{
@@ -13,9 +21,10 @@ use openvm_circuit::{
     arch::{
         hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
         instructions::exe::VmExe,
-        verify_segments, ContinuationVmProof, ExecutionError, InitFileGenerator,
-        VerifiedExecutionPayload, VmConfig, VmExecutor, CONNECTOR_AIR_ID, PROGRAM_AIR_ID,
-        PROGRAM_CACHED_TRACE_INDEX, PUBLIC_VALUES_AIR_ID,
+        verify_segments, ChannelHandler, ChannelTranscript, ContinuationVmProof, ExecutionError,
+        ExecutionResult, GuestOutput, HostChannels, InitFileGenerator, Streams, SystemConfig,
+        VerifiedExecutionPayload, VirtualMachine, VmConfig, VmExecutor, VmMemoryState,
+        CONNECTOR_AIR_ID, PROGRAM_AIR_ID, PROGRAM_CACHED_TRACE_INDEX, PUBLIC_VALUES_AIR_ID,
     },
     system::{
         memory::{tree::public_values::extract_public_values, CHUNK},
@@ -35,7 +44,7 @@ use openvm_continuations::verifier::{
 pub use openvm_continuations::{RootSC, C, F, SC};
 #[cfg(feature = "evm-prove")]
 use openvm_native_recursion::halo2::utils::Halo2ParamsReader;
-use openvm_stark_backend::proof::Proof;
+use openvm_stark_backend::{p3_field::PrimeField32, proof::Proof};
 use openvm_stark_sdk::{
     config::{baby_bear_poseidon2::BabyBearPoseidon2Engine, FriParameters},
     engine::StarkFriEngine,
@@ -48,22 +57,35 @@ use openvm_transpiler::{
     transpiler::{Transpiler, TranspilerError},
     FromElf,
 };
+use serde::Serialize;
 #[cfg(feature = "evm-verify")]
 use snark_verifier_sdk::{evm::gen_evm_verifier_sol_code, halo2::aggregation::AggregationCircuit};
 
 #[cfg(feature = "evm-prove")]
-use crate::{config::AggConfig, keygen::AggProvingKey, prover::EvmHalo2Prover, types::EvmProof};
 use crate::{
-    config::{AggStarkConfig, SdkVmConfig},
-    keygen::{asm::program_to_asm, AggStarkProvingKey},
-    prover::{AppProver, StarkProver},
+    config::AggConfig,
+    keygen::AggProvingKey,
+    prover::{EvmHalo2Prover, Halo2Prover},
+    types::EvmProof,
+};
+use crate::{
+    config::{check_config_digest, AggStarkConfig, SdkVmConfig},
+    keygen::{asm::program_to_asm, cache::KeyCache, AggStarkProvingKey, AggStarkVerifyingKey},
+    profiler::ProfileReport,
+    prover::{
+        AggStarkProver, AppProver, ProgressReceiver, ProvingProgress, ProvingTask, StarkProver,
+    },
 };
 
 pub mod codec;
 pub mod commit;
 pub mod config;
 pub mod keygen;
+pub mod profiler;
+pub mod proof_lib;
 pub mod prover;
+pub mod receipt;
+pub mod symbols;
 
 mod stdin;
 pub use stdin::*;
@@ -77,6 +99,7 @@ pub const EVM_HALO2_VERIFIER_INTERFACE: &str =
     include_str!("../contracts/src/IOpenVmHalo2Verifier.sol");
 pub const EVM_HALO2_VERIFIER_TEMPLATE: &str =
     include_str!("../contracts/template/OpenVmHalo2Verifier.sol");
+pub const EVM_PROOF_LIB_TEMPLATE: &str = include_str!("../contracts/template/OpenVmProofLib.sol");
 pub const OPENVM_VERSION: &str = concat!(
     env!("CARGO_PKG_VERSION_MAJOR"),
     ".",
@@ -101,10 +124,17 @@ pub struct VerifiedContinuationVmPayload {
     /// and a cryptographic compression function (for internal nodes).
     pub exe_commit: [F; CHUNK],
     pub user_public_values: Vec<F>,
+    /// Commitment to `user_public_values` with the hash function configured by
+    /// [AppConfig::public_values_hasher], see [commit_user_public_values].
+    pub user_public_values_commit: [u8; 32],
 }
 
 pub struct GenericSdk<E: StarkFriEngine<SC>> {
     agg_tree_config: AggregationTreeConfig,
+    prover_perf_config: ProverPerfConfig,
+    /// The cache of the proving keys and committed exes of the `_cached` methods, or `None` to not
+    /// cache them.
+    key_cache: Option<KeyCache>,
     _phantom: PhantomData<E>,
 }
 
@@ -112,6 +142,8 @@ impl<E: StarkFriEngine<SC>> Default for GenericSdk<E> {
     fn default() -> Self {
         Self {
             agg_tree_config: AggregationTreeConfig::default(),
+            prover_perf_config: ProverPerfConfig::default(),
+            key_cache: fs::default_cache_dir().map(KeyCache::new),
             _phantom: PhantomData,
         }
     }
@@ -133,6 +165,37 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         &self.agg_tree_config
     }
 
+    /// Bounds the threads, the segments proven at the same time and the memory of the app proofs,
+    /// see [ProverPerfConfig]. The number of threads applies to the global rayon thread pool right
+    /// away.
+    pub fn with_prover_perf_config(mut self, prover_perf_config: ProverPerfConfig) -> Self {
+        prover_perf_config.init_thread_pool();
+        self.prover_perf_config = prover_perf_config;
+        self
+    }
+
+    pub fn prover_perf_config(&self) -> &ProverPerfConfig {
+        &self.prover_perf_config
+    }
+
+    /// Sets the directory of the [KeyCache] used by [Self::app_keygen_cached],
+    /// [Self::agg_stark_keygen_cached] and [Self::commit_app_exe_cached], which is
+    /// [fs::default_cache_dir] by default.
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.key_cache = Some(KeyCache::new(dir));
+        self
+    }
+
+    /// Disables the [KeyCache], so that the `_cached` methods generate their output every time.
+    pub fn without_cache(mut self) -> Self {
+        self.key_cache = None;
+        self
+    }
+
+    pub fn key_cache(&self) -> Option<&KeyCache> {
+        self.key_cache.as_ref()
+    }
+
     pub fn build<P: AsRef<Path>>(
         &self,
         guest_opts: GuestOptions,
@@ -157,7 +220,56 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
 
         let elf_path = find_unique_executable(pkg_dir, target_dir, target_filter)?;
         let data = read(&elf_path)?;
-        Elf::decode(&data, MEM_SIZE as u32)
+        let elf = Elf::decode(&data, MEM_SIZE as u32)?;
+        elf.check_program_len(vm_config.system.config.max_program_len)?;
+        self.check_extension_versions(&elf, vm_config)?;
+        Ok(elf)
+    }
+
+    /// Checks that the VM of `vm_config` has every extension the guest program `elf` requires,
+    /// at the version the program was built against, see [Elf::extension_versions], and has the
+    /// optional extensions of the program, see [Elf::optional_extension_versions], at that version
+    /// or not at all. A program built against another version of an extension could silently
+    /// compute different results.
+    pub fn check_extension_versions<VC: VmConfig<F>>(
+        &self,
+        elf: &Elf,
+        vm_config: &VC,
+    ) -> Result<()> {
+        let vm_versions = vm_config.extension_versions()?;
+        let mismatches: Vec<_> = elf
+            .extension_versions()
+            .iter()
+            .filter_map(|(name, version)| match vm_versions.get(name) {
+                Some(vm_version) if vm_version == version => None,
+                Some(vm_version) => Some(format!(
+                    "program requires version {version} of extension {name}, but the VM has \
+                     version {vm_version}"
+                )),
+                None => Some(format!(
+                    "program requires version {version} of extension {name}, which the VM does \
+                     not have"
+                )),
+            })
+            .chain(
+                elf.optional_extension_versions()
+                    .iter()
+                    .filter_map(|(name, version)| match vm_versions.get(name) {
+                        Some(vm_version) if vm_version != version => Some(format!(
+                            "program supports version {version} of extension {name}, but the VM \
+                             has version {vm_version}"
+                        )),
+                        _ => None,
+                    }),
+            )
+            .collect();
+        if !mismatches.is_empty() {
+            eyre::bail!(
+                "guest program does not match the VM config:\n  {}",
+                mismatches.join("\n  ")
+            );
+        }
+        Ok(())
     }
 
     pub fn transpile(
@@ -174,18 +286,179 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         vm_config: VC,
         inputs: StdIn,
     ) -> Result<Vec<F>, ExecutionError>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        self.execute_with_stats(exe, vm_config, inputs)
+            .map(|(public_values, _)| public_values)
+    }
+
+    /// Executes the program and returns the public values together with the execution result,
+    /// which includes the number of instructions executed, in total and per opcode.
+    pub fn execute_with_stats<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+    ) -> Result<(Vec<F>, ExecutionResult<F>), ExecutionError>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let vm = VmExecutor::new(vm_config);
+        let result = vm.execute(exe, inputs)?;
+        let public_values = extract_public_values(
+            &vm.config.system().memory_config.memory_dimensions(),
+            vm.config.system().num_public_values,
+            result.final_memory.as_ref().unwrap(),
+        );
+        Ok((public_values, result))
+    }
+
+    /// Executes the program without proving it, and reports the number of instructions executed
+    /// in total and per opcode, and the rows used by each chip in each segment. If the executable
+    /// has function symbols, e.g. it was transpiled from an ELF which was not stripped, the
+    /// report also holds the cycles of each function along its call stacks, which can be exported
+    /// with [ProfileReport::to_speedscope]. Recording the call stacks slows down the execution.
+    pub fn profile<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+    ) -> Result<ProfileReport, ExecutionError>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let mut vm = VmExecutor::new(vm_config);
+        vm.set_function_profiling(!exe.fn_bounds.is_empty());
+        let result = vm.execute(exe, inputs)?;
+        Ok(ProfileReport::new(result))
+    }
+
+    /// Executes the program and returns the public values together with the text printed by the
+    /// guest, which is not written to stdout. Each entry of the output is marked with the segment
+    /// and the number of instructions executed before it was printed.
+    pub fn execute_with_output<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+    ) -> Result<(Vec<F>, GuestOutput), ExecutionError>
     where
         VC::Executor: Chip<SC>,
         VC::Periphery: Chip<SC>,
     {
         let vm = VmExecutor::new(vm_config);
-        let final_memory = vm.execute(exe, inputs)?;
+        let result = vm.execute(exe, Streams::from(inputs).with_output_capture())?;
         let public_values = extract_public_values(
             &vm.config.system().memory_config.memory_dimensions(),
             vm.config.system().num_public_values,
-            final_memory.as_ref().unwrap(),
+            result.final_memory.as_ref().unwrap(),
         );
-        Ok(public_values)
+        Ok((
+            public_values,
+            result.output.expect("output should be captured"),
+        ))
+    }
+
+    /// Executes the program with `handler` serving the channels the guest opens with
+    /// `openvm::io::channel`, and returns the public values together with the transcript of the
+    /// messages exchanged. The execution is not deterministic, so to prove it, pass the transcript
+    /// to the input with [StdIn::with_channel_transcript], which makes the prover replay it.
+    pub fn execute_interactive<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+        handler: impl ChannelHandler + 'static,
+    ) -> Result<(Vec<F>, ChannelTranscript), ExecutionError>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let vm = VmExecutor::new(vm_config);
+        let input = Streams::from(inputs).with_channels(HostChannels::interactive(handler));
+        let result = vm.execute(exe, input)?;
+        let public_values = extract_public_values(
+            &vm.config.system().memory_config.memory_dimensions(),
+            vm.config.system().num_public_values,
+            result.final_memory.as_ref().unwrap(),
+        );
+        Ok((public_values, result.channel_transcript))
+    }
+
+    /// Executes a program which runs in phases separated by `openvm::process::pause`, and returns
+    /// the final public values. At every pause, `on_pause` is called with the user public values
+    /// committed so far, and returns the input of the next phase, whose input streams are
+    /// appended to the remaining input.
+    pub fn execute_phases<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+        on_pause: impl FnMut(&[F]) -> StdIn,
+    ) -> Result<Vec<F>, ExecutionError>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let vm = VmExecutor::new(vm_config);
+        let on_pause = phase_input_from_public_values(vm.config.system(), on_pause);
+        let result = vm.execute_phases(exe, inputs, on_pause)?;
+        Ok(extract_public_values(
+            &vm.config.system().memory_config.memory_dimensions(),
+            vm.config.system().num_public_values,
+            result.final_memory.as_ref().unwrap(),
+        ))
+    }
+
+    /// Runs a fast end-to-end pipeline for integration tests, with empty input: see
+    /// [smoke_test_with_input](Self::smoke_test_with_input).
+    pub fn smoke_test<VC: VmConfig<F>>(&self, exe: VmExe<F>, vm_config: VC) -> Result<()>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        self.smoke_test_with_input(exe, vm_config, StdIn::default())
+    }
+
+    /// Executes the program, proves its execution as a single segment and verifies the proof, to
+    /// check quickly that `vm_config` is wired correctly for the program, e.g. that every
+    /// extension the program uses is configured and that all chips generate valid traces.
+    ///
+    /// The proof uses FRI parameters for testing, which give no security, and only the app VM is
+    /// proven, so no keys of the aggregation VMs are generated. The program must run in a single
+    /// segment.
+    pub fn smoke_test_with_input<VC: VmConfig<F>>(
+        &self,
+        exe: VmExe<F>,
+        vm_config: VC,
+        inputs: StdIn,
+    ) -> Result<()>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let mut log_blowup = 1;
+        while vm_config.system().max_constraint_degree > (1 << log_blowup) + 1 {
+            log_blowup += 1;
+        }
+        let engine = E::new(FriParameters::new_for_testing(log_blowup));
+        let vm = VirtualMachine::new(engine, vm_config);
+        let pk = vm.keygen();
+        let result = vm.execute_and_generate(exe, inputs)?;
+        let num_segments = result.per_segment.len();
+        if num_segments != 1 {
+            return Err(eyre::eyre!(
+                "Smoke test program must run in a single segment, but ran in {}",
+                num_segments
+            ));
+        }
+        let proofs = vm.prove(&pk, result);
+        vm.verify(&pk.get_vk(), proofs)?;
+        Ok(())
     }
 
     pub fn commit_app_exe(
@@ -197,6 +470,19 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         Ok(committed_exe)
     }
 
+    /// Like [Self::commit_app_exe], but reads the committed exe from the [KeyCache] if `exe` was
+    /// committed with the same FRI parameters before, and writes it there otherwise.
+    pub fn commit_app_exe_cached(
+        &self,
+        app_fri_params: FriParameters,
+        exe: VmExe<F>,
+    ) -> Result<Arc<NonRootCommittedExe>> {
+        match &self.key_cache {
+            Some(key_cache) => key_cache.committed_exe(app_fri_params, exe),
+            None => self.commit_app_exe(app_fri_params, exe),
+        }
+    }
+
     pub fn app_keygen<VC: VmConfig<F>>(&self, config: AppConfig<VC>) -> Result<AppProvingKey<VC>>
     where
         VC::Executor: Chip<SC>,
@@ -206,6 +492,51 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         Ok(app_pk)
     }
 
+    /// Like [Self::app_keygen], but reads the proving key from the [KeyCache] if it was generated
+    /// for the same config and OpenVM version before, and writes it there otherwise. A cached key
+    /// which cannot be read or fails its integrity checks is generated again.
+    pub fn app_keygen_cached<VC: VmConfig<F>>(
+        &self,
+        config: AppConfig<VC>,
+    ) -> Result<AppProvingKey<VC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        match &self.key_cache {
+            Some(key_cache) => key_cache.app_pk(config),
+            None => self.app_keygen(config),
+        }
+    }
+
+    /// Proves the guest program of the ELF at `elf_path` on `inputs` with the default app FRI
+    /// parameters: decodes and transpiles the ELF for `vm_config`, commits the executable, and
+    /// generates the app proof with the proving key from [Self::app_keygen_cached].
+    pub fn prove_from_elf<P: AsRef<Path>>(
+        &self,
+        elf_path: P,
+        vm_config: SdkVmConfig,
+        inputs: StdIn,
+    ) -> Result<ContinuationVmProof<SC>> {
+        let data = read(elf_path.as_ref())?;
+        let elf = Elf::decode(&data, MEM_SIZE as u32)?;
+        elf.check_program_len(vm_config.system.config.max_program_len)?;
+        self.check_extension_versions(&elf, &vm_config)?;
+        let exe = self.transpile(elf, vm_config.transpiler())?;
+
+        let app_config = AppConfig {
+            app_fri_params: Default::default(),
+            app_vm_config: vm_config,
+            leaf_fri_params: Default::default(),
+            compiler_options: Default::default(),
+            public_values_hasher: Default::default(),
+            expose_config_digest: false,
+        };
+        let app_pk = Arc::new(self.app_keygen_cached(app_config)?);
+        let committed_exe = self.commit_app_exe_cached(app_pk.app_fri_params(), exe)?;
+        self.generate_app_proof(app_pk, committed_exe, inputs)
+    }
+
     pub fn generate_app_proof<VC: VmConfig<F>>(
         &self,
         app_pk: Arc<AppProvingKey<VC>>,
@@ -216,11 +547,77 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         VC::Executor: Chip<SC>,
         VC::Periphery: Chip<SC>,
     {
-        let app_prover = AppProver::<VC, E>::new(app_pk.app_vm_pk.clone(), app_committed_exe);
+        let app_prover = AppProver::<VC, E>::new(app_pk.app_vm_pk.clone(), app_committed_exe)
+            .with_perf_config(self.prover_perf_config);
         let proof = app_prover.generate_app_proof(inputs);
         Ok(proof)
     }
 
+    /// Generates the proof of [Self::generate_app_proof], keeping the input and the proof of
+    /// every segment in `checkpoint_dir` so that a stopped prover can continue with
+    /// [Self::resume_app_proof], see [ProvingCheckpoint](prover::ProvingCheckpoint).
+    pub fn generate_app_proof_with_checkpoint<VC: VmConfig<F> + Serialize>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_committed_exe: Arc<NonRootCommittedExe>,
+        inputs: StdIn,
+        checkpoint_dir: impl AsRef<Path>,
+    ) -> Result<ContinuationVmProof<SC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let app_prover = AppProver::<VC, E>::new(app_pk.app_vm_pk.clone(), app_committed_exe)
+            .with_perf_config(self.prover_perf_config);
+        app_prover.generate_app_proof_with_checkpoint(inputs, checkpoint_dir)
+    }
+
+    /// Resumes [Self::generate_app_proof_with_checkpoint] from `checkpoint_dir`, proving only
+    /// the segments missing from the checkpoint.
+    pub fn resume_app_proof<VC: VmConfig<F> + Serialize>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_committed_exe: Arc<NonRootCommittedExe>,
+        checkpoint_dir: impl AsRef<Path>,
+    ) -> Result<ContinuationVmProof<SC>>
+    where
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let app_prover = AppProver::<VC, E>::new(app_pk.app_vm_pk.clone(), app_committed_exe)
+            .with_perf_config(self.prover_perf_config);
+        app_prover.resume_app_proof(checkpoint_dir)
+    }
+
+    /// Generates the proof of [Self::generate_app_proof] on a thread of its own. The returned task
+    /// resolves to the proof, and the receiver reports every segment once it is proven.
+    /// Cancelling or dropping the task stops proving after the segment which is being proven.
+    pub fn generate_app_proof_async<VC>(
+        &self,
+        app_pk: Arc<AppProvingKey<VC>>,
+        app_committed_exe: Arc<NonRootCommittedExe>,
+        inputs: StdIn,
+    ) -> (ProvingTask<ContinuationVmProof<SC>>, ProgressReceiver)
+    where
+        VC: VmConfig<F> + Send + Sync + 'static,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+        E: 'static,
+    {
+        let perf_config = self.prover_perf_config;
+        ProvingTask::spawn(move |reporter| {
+            let app_prover = AppProver::<VC, E>::new(app_pk.app_vm_pk.clone(), app_committed_exe)
+                .with_perf_config(perf_config);
+            let proof = app_prover.generate_app_proof_with_progress(inputs, |segment| {
+                reporter.report(ProvingProgress::SegmentProven { segment })
+            })?;
+            reporter.report(ProvingProgress::AppProofGenerated {
+                num_segments: proof.per_segment.len(),
+            })?;
+            Ok(proof)
+        })
+    }
+
     /// Verifies the [ContinuationVmProof], which is a collection of STARK proofs as well as
     /// additional Merkle proof for user public values.
     ///
@@ -245,12 +642,37 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
             .user_public_values
             .verify(&hasher, app_vk.memory_dimensions, final_memory_root)?;
 
+        let user_public_values = proof.user_public_values.public_values.clone();
+        if let Some(config_digest) = &app_vk.config_digest {
+            check_config_digest(config_digest, &user_public_values)?;
+        }
+        let user_public_values_commit =
+            commit_user_public_values(app_vk.public_values_hasher, &user_public_values)?;
         Ok(VerifiedContinuationVmPayload {
             exe_commit,
-            user_public_values: proof.user_public_values.public_values.clone(),
+            user_public_values,
+            user_public_values_commit,
         })
     }
 
+    /// Verifies `proof` with [Self::verify_app_proof] and checks that its user public values,
+    /// which the Merkle proof binds to the final memory, commit to `data`, see
+    /// [check_published_bytes].
+    pub fn verify_app_proof_published_bytes<I>(
+        &self,
+        app_vk: &AppVerifyingKey,
+        proof: &ContinuationVmProof<SC>,
+        data: I,
+    ) -> Result<VerifiedContinuationVmPayload>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let payload = self.verify_app_proof(app_vk, proof)?;
+        check_published_bytes(&payload.user_public_values, data)?;
+        Ok(payload)
+    }
+
     pub fn verify_app_proof_without_continuations(
         &self,
         app_vk: &AppVerifyingKey,
@@ -277,6 +699,15 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         Ok(agg_pk)
     }
 
+    /// Like [Self::agg_stark_keygen], but reads the proving key from the [KeyCache] if it was
+    /// generated for the same config and OpenVM version before, and writes it there otherwise.
+    pub fn agg_stark_keygen_cached(&self, config: AggStarkConfig) -> Result<AggStarkProvingKey> {
+        match &self.key_cache {
+            Some(key_cache) => key_cache.agg_stark_pk(config),
+            None => self.agg_stark_keygen(config),
+        }
+    }
+
     pub fn generate_root_verifier_asm(&self, agg_stark_pk: &AggStarkProvingKey) -> String {
         let kernel_asm = RootVmVerifierConfig {
             leaf_fri_params: agg_stark_pk.leaf_vm_pk.fri_params,
@@ -307,7 +738,8 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         VC::Periphery: Chip<SC>,
     {
         let stark_prover =
-            StarkProver::<VC, E>::new(app_pk, app_exe, agg_stark_pk, self.agg_tree_config);
+            StarkProver::<VC, E>::new(app_pk, app_exe, agg_stark_pk, self.agg_tree_config)
+                .with_perf_config(self.prover_perf_config);
         let proof = stark_prover.generate_root_verifier_input(inputs);
         Ok(proof)
     }
@@ -324,17 +756,54 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         VC::Periphery: Chip<SC>,
     {
         let stark_prover =
-            StarkProver::<VC, E>::new(app_pk, app_exe, agg_stark_pk, self.agg_tree_config);
+            StarkProver::<VC, E>::new(app_pk, app_exe, agg_stark_pk, self.agg_tree_config)
+                .with_perf_config(self.prover_perf_config);
         let proof = stark_prover.generate_e2e_stark_proof(inputs);
         Ok(proof)
     }
 
+    /// Aggregates an app proof, e.g. one generated earlier by [Self::generate_app_proof], into an
+    /// e2e STARK proof without proving the app execution again.
+    pub fn aggregate_app_proof<VC: VmConfig<F>>(
+        &self,
+        app_pk: &AppProvingKey<VC>,
+        agg_stark_pk: AggStarkProvingKey,
+        app_proof: ContinuationVmProof<SC>,
+    ) -> Result<VmStarkProof<SC>> {
+        app_pk.check_agg_compatibility(&agg_stark_pk)?;
+        let agg_prover = AggStarkProver::<E>::new(
+            agg_stark_pk,
+            app_pk.leaf_committed_exe.clone(),
+            self.agg_tree_config,
+        );
+        let leaf_proofs = agg_prover.generate_leaf_proofs(&app_proof);
+        let proof = agg_prover
+            .aggregate_leaf_proofs(leaf_proofs, app_proof.user_public_values.public_values);
+        Ok(proof)
+    }
+
     pub fn verify_e2e_stark_proof(
         &self,
         agg_stark_pk: &AggStarkProvingKey,
         proof: &VmStarkProof<SC>,
         expected_exe_commit: &Bn254Fr,
         expected_vm_commit: &Bn254Fr,
+    ) -> Result<AppExecutionCommit> {
+        self.verify_e2e_stark_proof_with_vk(
+            &agg_stark_pk.get_agg_stark_vk(),
+            proof,
+            expected_exe_commit,
+            expected_vm_commit,
+        )
+    }
+
+    /// Like [Self::verify_e2e_stark_proof], with only the verifying key of the aggregation.
+    pub fn verify_e2e_stark_proof_with_vk(
+        &self,
+        agg_stark_vk: &AggStarkVerifyingKey,
+        proof: &VmStarkProof<SC>,
+        expected_exe_commit: &Bn254Fr,
+        expected_vm_commit: &Bn254Fr,
     ) -> Result<AppExecutionCommit> {
         if proof.proof.per_air.len() < 3 {
             return Err(eyre::eyre!(
@@ -352,12 +821,9 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
 
         let program_commit =
             proof.proof.commitments.main_trace[PROGRAM_CACHED_TRACE_INDEX].as_ref();
-        let internal_commit: &[_; CHUNK] = &agg_stark_pk
-            .internal_committed_exe
-            .get_program_commit()
-            .into();
+        let internal_commit = &agg_stark_vk.internal_program_commit;
 
-        let (vm_pk, vm_commit) = if program_commit == internal_commit {
+        let (fri_params, vm_vk, vm_commit) = if program_commit == internal_commit {
             let internal_pvs: &InternalVmVerifierPvs<_> = public_values_air_proof_data
                 .public_values
                 .as_slice()
@@ -370,14 +836,19 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
                 ));
             }
             (
-                &agg_stark_pk.internal_vm_pk,
+                agg_stark_vk.internal_fri_params,
+                &agg_stark_vk.internal_vm_vk,
                 internal_pvs.extra_pvs.leaf_verifier_commit,
             )
         } else {
-            (&agg_stark_pk.leaf_vm_pk, *program_commit)
+            (
+                agg_stark_vk.leaf_fri_params,
+                &agg_stark_vk.leaf_vm_vk,
+                *program_commit,
+            )
         };
-        let e = E::new(vm_pk.fri_params);
-        e.verify(&vm_pk.vm_pk.get_vk(), &proof.proof)?;
+        let e = E::new(fri_params);
+        e.verify(vm_vk, &proof.proof)?;
 
         let pvs: &VmVerifierPvs<_> =
             public_values_air_proof_data.public_values[..VmVerifierPvs::<u8>::width()].borrow();
@@ -429,6 +900,16 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         Ok(app_commit)
     }
 
+    /// Checks that the user public values of a verified proof commit to `data`, see
+    /// [check_published_bytes].
+    pub fn verify_published_bytes<I>(&self, user_public_values: &[F], data: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        check_published_bytes(user_public_values, data)
+    }
+
     #[cfg(feature = "evm-prove")]
     pub fn generate_evm_proof<VC: VmConfig<F>>(
         &self,
@@ -443,11 +924,37 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         VC::Periphery: Chip<SC>,
     {
         let e2e_prover =
-            EvmHalo2Prover::<VC, E>::new(reader, app_pk, app_exe, agg_pk, self.agg_tree_config);
+            EvmHalo2Prover::<VC, E>::new(reader, app_pk, app_exe, agg_pk, self.agg_tree_config)
+                .with_perf_config(self.prover_perf_config);
         let proof = e2e_prover.generate_proof_for_evm(inputs);
         Ok(proof)
     }
 
+    /// Generates an EVM proof from an app proof, e.g. one generated earlier by
+    /// [Self::generate_app_proof], without proving the app execution again.
+    #[cfg(feature = "evm-prove")]
+    pub fn generate_evm_proof_from_app_proof<VC: VmConfig<F>>(
+        &self,
+        reader: &impl Halo2ParamsReader,
+        app_pk: &AppProvingKey<VC>,
+        agg_pk: AggProvingKey,
+        app_proof: ContinuationVmProof<SC>,
+    ) -> Result<EvmProof> {
+        let AggProvingKey {
+            agg_stark_pk,
+            halo2_pk,
+        } = agg_pk;
+        app_pk.check_agg_compatibility(&agg_stark_pk)?;
+        let agg_prover = AggStarkProver::<E>::new(
+            agg_stark_pk,
+            app_pk.leaf_committed_exe.clone(),
+            self.agg_tree_config,
+        );
+        let root_proof = agg_prover.generate_root_proof(app_proof);
+        let proof = Halo2Prover::new(reader, halo2_pk).prove_for_evm(&root_proof);
+        Ok(proof)
+    }
+
     #[cfg(feature = "evm-verify")]
     pub fn generate_halo2_verifier_solidity(
         &self,
@@ -502,6 +1009,14 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
             _ => panic!("Unexpected amount of instance columns in the static verifier wrapper"),
         };
 
+        if pvs_length != agg_pk.agg_stark_pk.num_user_public_values() {
+            return Err(eyre::eyre!(
+                "Static verifier wrapper exposes {} user public values, but the root verifier has {}",
+                pvs_length,
+                agg_pk.agg_stark_pk.num_user_public_values()
+            ));
+        }
+
         assert!(
             pvs_length <= 8192,
             "OpenVM Halo2 verifier contract does not support more than 8192 public values"
@@ -699,3 +1214,19 @@ impl<E: StarkFriEngine<SC>> GenericSdk<E> {
         Ok(gas_cost)
     }
 }
+
+/// Adapts `on_pause`, which receives the user public values at a pause and returns the input of
+/// the next phase, to a pause handler of the VM, which receives the memory at the pause.
+pub(crate) fn phase_input_from_public_values<'a>(
+    system: &'a SystemConfig,
+    mut on_pause: impl FnMut(&[F]) -> StdIn + 'a,
+) -> impl FnMut(&VmMemoryState<F>) -> Vec<Vec<F>> + 'a {
+    move |memory| {
+        let public_values = extract_public_values(
+            &system.memory_config.memory_dimensions(),
+            system.num_public_values,
+            memory,
+        );
+        Streams::from(on_pause(&public_values)).input_stream.into()
+    }
+}
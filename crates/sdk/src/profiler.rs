@@ -0,0 +1,109 @@
+//! Cycle accounting of an execution, see [crate::GenericSdk::profile].
+
+use std::collections::BTreeMap;
+
+use openvm_circuit::arch::{
+    ExecutionResult, FunctionProfile, OpcodeCounts, RegionProfile, SegmentUtilization,
+};
+use serde_json::{json, Value};
+
+use crate::F;
+
+/// Where the cycles of an execution were spent, by opcode, by chip, by the regions labeled by the
+/// guest and, if the executable has function symbols, by function.
+#[derive(Clone, Debug)]
+pub struct ProfileReport {
+    /// Total number of instructions executed across all segments.
+    pub total_cycles: usize,
+    /// Number of times each opcode was executed, keyed by extension and then by opcode name.
+    pub opcode_counts: OpcodeCounts,
+    /// The rows used by the chips of each segment.
+    pub utilization: Vec<SegmentUtilization>,
+    /// The cycles of each function along its call stacks, if the executable has function symbols.
+    pub functions: Option<FunctionProfile>,
+    /// The cycles of the regions labeled with `openvm::profile::start_region!`.
+    pub regions: RegionProfile,
+}
+
+impl ProfileReport {
+    pub fn new(result: ExecutionResult<F>) -> Self {
+        Self {
+            total_cycles: result.total_cycles,
+            opcode_counts: result.opcode_counts,
+            utilization: result.utilization,
+            functions: result.function_profile,
+            regions: result.region_profile,
+        }
+    }
+
+    /// The opcodes as `(extension, opcode, count)`, by decreasing count.
+    pub fn opcode_histogram(&self) -> Vec<(&str, &str, usize)> {
+        let mut histogram: Vec<_> = self
+            .opcode_counts
+            .iter()
+            .flat_map(|(extension, counts)| {
+                counts
+                    .iter()
+                    .map(move |(opcode, &count)| (extension.as_str(), opcode.as_str(), count))
+            })
+            .collect();
+        histogram.sort_by(|a, b| b.2.cmp(&a.2));
+        histogram
+    }
+
+    /// The rows used by each chip whose height depends on the execution, summed over all
+    /// segments, keyed by AIR name.
+    pub fn chip_rows(&self) -> BTreeMap<&str, usize> {
+        let mut rows = BTreeMap::new();
+        for chip in self.utilization.iter().flat_map(|segment| &segment.chips) {
+            if !chip.fixed_height {
+                *rows.entry(chip.air_name.as_str()).or_default() += chip.rows;
+            }
+        }
+        rows
+    }
+
+    /// The function profile as a sampled profile in the
+    /// [speedscope file format](https://www.speedscope.app/file-format-schema.json), with one
+    /// sample per call stack weighted by its cycles. Returns `None` without function symbols.
+    pub fn to_speedscope(&self, name: &str) -> Option<Value> {
+        let profile = self.functions.as_ref()?;
+        let mut frame_ids = BTreeMap::new();
+        let mut frames = vec![];
+        let mut samples = vec![];
+        let mut weights = vec![];
+        for (idx, frame) in profile.frames().iter().enumerate() {
+            if frame.self_cycles == 0 {
+                continue;
+            }
+            let sample: Vec<usize> = profile
+                .call_stack(idx)
+                .into_iter()
+                .map(|function| {
+                    *frame_ids.entry(function).or_insert_with(|| {
+                        frames.push(json!({ "name": profile.function_name(function) }));
+                        frames.len() - 1
+                    })
+                })
+                .collect();
+            samples.push(sample);
+            weights.push(frame.self_cycles);
+        }
+        Some(json!({
+            "$schema": "https://www.speedscope.app/file-format-schema.json",
+            "exporter": "openvm",
+            "name": name,
+            "activeProfileIndex": 0,
+            "shared": { "frames": frames },
+            "profiles": [{
+                "type": "sampled",
+                "name": name,
+                "unit": "none",
+                "startValue": 0,
+                "endValue": profile.total_cycles(),
+                "samples": samples,
+                "weights": weights,
+            }],
+        }))
+    }
+}
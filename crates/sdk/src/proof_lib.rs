@@ -0,0 +1,155 @@
+use std::{collections::HashSet, fmt::Write};
+
+use eyre::{bail, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{commit::AppExecutionCommit, EVM_PROOF_LIB_TEMPLATE, OPENVM_VERSION};
+
+/// Layout of the user public values of an app, in the format of the parameters of a Solidity JSON
+/// ABI, e.g. `[{ "name": "result", "type": "uint64" }, { "name": "owner", "type": "address" }]`.
+///
+/// The fields are tightly packed in order from the start of the user public values. Integers are
+/// little-endian, as revealed by `reveal_u32` in the guest, while `bytesN` and `address` values
+/// are in order. A `bool` is a single byte that must be 0 or 1.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PublicValuesSchema(pub Vec<AbiParam>);
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AbiParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// The Solidity value types supported in a [PublicValuesSchema].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum SolType {
+    Bool,
+    Address,
+    Uint(usize),
+    Int(usize),
+    FixedBytes(usize),
+}
+
+impl SolType {
+    fn parse(ty: &str) -> Option<Self> {
+        let bits = |s: &str| {
+            let bits = if s.is_empty() {
+                Some(256)
+            } else {
+                s.parse().ok()
+            };
+            bits.filter(|&b: &usize| b > 0 && b <= 256 && b % 8 == 0)
+        };
+        match ty {
+            "bool" => Some(Self::Bool),
+            "address" => Some(Self::Address),
+            _ => {
+                if let Some(s) = ty.strip_prefix("uint") {
+                    bits(s).map(Self::Uint)
+                } else if let Some(s) = ty.strip_prefix("int") {
+                    bits(s).map(Self::Int)
+                } else if let Some(s) = ty.strip_prefix("bytes") {
+                    s.parse()
+                        .ok()
+                        .filter(|n| (1..=32).contains(n))
+                        .map(Self::FixedBytes)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Number of public value bytes taken by the type.
+    fn size(&self) -> usize {
+        match self {
+            Self::Bool => 1,
+            Self::Address => 20,
+            Self::Uint(bits) | Self::Int(bits) => bits / 8,
+            Self::FixedBytes(n) => *n,
+        }
+    }
+
+    fn name(&self) -> String {
+        match self {
+            Self::Bool => "bool".to_string(),
+            Self::Address => "address".to_string(),
+            Self::Uint(bits) => format!("uint{bits}"),
+            Self::Int(bits) => format!("int{bits}"),
+            Self::FixedBytes(n) => format!("bytes{n}"),
+        }
+    }
+
+    /// Solidity expression reading the type from `publicValues` at `offset`.
+    fn decode_expr(&self, offset: usize) -> String {
+        let size = self.size();
+        match self {
+            Self::Bool => format!("_readBool(publicValues, {offset})"),
+            Self::Address => format!("address(bytes20(publicValues[{offset}:{}]))", offset + size),
+            Self::Uint(bits) => format!("uint{bits}(_readUintLe(publicValues, {offset}, {size}))"),
+            Self::Int(bits) => {
+                format!("int{bits}(uint{bits}(_readUintLe(publicValues, {offset}, {size})))")
+            }
+            Self::FixedBytes(n) => format!("bytes{n}(publicValues[{offset}:{}])", offset + size),
+        }
+    }
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Generates the `OpenVmProofLib` Solidity library for the app with commits `app_commit` whose user
+/// public values follow `schema`. The library imports the `IOpenVmHalo2Verifier` interface from
+/// `./interfaces/`, so it is meant to be placed next to the verifier contract.
+pub fn generate_openvm_proof_lib(
+    schema: &PublicValuesSchema,
+    app_commit: &AppExecutionCommit,
+) -> Result<String> {
+    if schema.0.is_empty() {
+        bail!("Public values schema must have at least one field");
+    }
+    let mut names = HashSet::new();
+    let mut struct_fields = String::new();
+    let mut decode_fields = String::new();
+    let mut offset = 0;
+    for AbiParam { name, ty } in &schema.0 {
+        if !is_identifier(name) {
+            bail!("Invalid public values field name {name:?}");
+        }
+        if !names.insert(name) {
+            bail!("Duplicate public values field name {name:?}");
+        }
+        let Some(sol_type) = SolType::parse(ty) else {
+            bail!("Unsupported type {ty:?} of public values field {name:?}");
+        };
+        writeln!(struct_fields, "        {} {name};", sol_type.name())?;
+        writeln!(
+            decode_fields,
+            "        pvs.{name} = {};",
+            sol_type.decode_expr(offset)
+        )?;
+        offset += sol_type.size();
+    }
+
+    let commit_hex = |bytes: &[u8]| format!("0x{}", hex::encode(bytes));
+    Ok(EVM_PROOF_LIB_TEMPLATE
+        .replace(
+            "{APP_EXE_COMMIT}",
+            &commit_hex(app_commit.app_exe_commit.as_slice()),
+        )
+        .replace(
+            "{APP_VM_COMMIT}",
+            &commit_hex(app_commit.app_vm_commit.as_slice()),
+        )
+        .replace("{PUBLIC_VALUES_SIZE}", &offset.to_string())
+        .replace("{OPENVM_VERSION}", OPENVM_VERSION)
+        .replace("{STRUCT_FIELDS}\n", &struct_fields)
+        .replace("{DECODE_FIELDS}\n", &decode_fields))
+}
@@ -1,14 +1,21 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
+use eyre::Result;
 use getset::Getters;
-use openvm_circuit::arch::{ContinuationVmProof, VmConfig};
+use openvm_circuit::arch::{ContinuationVmProof, GuestOutput, VmConfig};
 use openvm_stark_backend::{proof::Proof, Chip};
 use openvm_stark_sdk::engine::StarkFriEngine;
+use serde::Serialize;
 use tracing::info_span;
 
 use super::vm::SingleSegmentVmProver;
 use crate::{
-    prover::vm::{local::VmLocalProver, types::VmProvingKey, ContinuationVmProver},
+    config::ProverPerfConfig,
+    phase_input_from_public_values,
+    prover::{
+        vm::{local::VmLocalProver, types::VmProvingKey, ContinuationVmProver},
+        ProvingCheckpoint,
+    },
     NonRootCommittedExe, StdIn, F, SC,
 };
 
@@ -40,6 +47,15 @@ impl<VC, E: StarkFriEngine<SC>> AppProver<VC, E> {
         self.set_program_name(program_name);
         self
     }
+    /// Sets the bounds on the segments proven at the same time, see [ProverPerfConfig].
+    pub fn set_perf_config(&mut self, perf_config: ProverPerfConfig) -> &mut Self {
+        self.app_prover.set_perf_config(perf_config);
+        self
+    }
+    pub fn with_perf_config(mut self, perf_config: ProverPerfConfig) -> Self {
+        self.set_perf_config(perf_config);
+        self
+    }
 
     /// Generates proof for every continuation segment
     pub fn generate_app_proof(&self, input: StdIn) -> ContinuationVmProof<SC>
@@ -67,6 +83,176 @@ impl<VC, E: StarkFriEngine<SC>> AppProver<VC, E> {
         })
     }
 
+    /// Generates proof for every continuation segment like [Self::generate_app_proof], calling
+    /// `on_segment_proven` with the index of every segment once it is proven. Proving stops at the
+    /// first error `on_segment_proven` returns.
+    pub fn generate_app_proof_with_progress<Err>(
+        &self,
+        input: StdIn,
+        on_segment_proven: impl FnMut(usize) -> Result<(), Err>,
+    ) -> Result<ContinuationVmProof<SC>, Err>
+    where
+        VC: VmConfig<F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        assert!(
+            self.vm_config().system().continuation_enabled,
+            "Use generate_app_proof_without_continuations instead."
+        );
+        info_span!(
+            "app proof",
+            group = self
+                .program_name
+                .as_ref()
+                .unwrap_or(&"app_proof".to_string())
+        )
+        .in_scope(|| {
+            #[cfg(feature = "bench-metrics")]
+            metrics::counter!("fri.log_blowup")
+                .absolute(self.app_prover.pk.fri_params.log_blowup as u64);
+            self.app_prover
+                .prove_with_progress(input, on_segment_proven)
+        })
+    }
+
+    /// Generates proof for every continuation segment like [Self::generate_app_proof], keeping the
+    /// input and the proof of every segment in the checkpoint directory `checkpoint_dir`, see
+    /// [ProvingCheckpoint]. Segments which the checkpoint already holds for the same program,
+    /// config and input are not proven again.
+    pub fn generate_app_proof_with_checkpoint(
+        &self,
+        input: StdIn,
+        checkpoint_dir: impl AsRef<Path>,
+    ) -> Result<ContinuationVmProof<SC>>
+    where
+        VC: VmConfig<F> + Serialize,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let checkpoint = ProvingCheckpoint::open(
+            checkpoint_dir.as_ref(),
+            &self.app_prover.committed_exe.exe,
+            self.vm_config(),
+            self.app_prover.pk.fri_params,
+            &input,
+        )?;
+        Ok(self.generate_app_proof_from_checkpoint(input, checkpoint))
+    }
+
+    /// Resumes [Self::generate_app_proof_with_checkpoint] with the input kept in
+    /// `checkpoint_dir`, proving only the segments missing from the checkpoint.
+    pub fn resume_app_proof(
+        &self,
+        checkpoint_dir: impl AsRef<Path>,
+    ) -> Result<ContinuationVmProof<SC>>
+    where
+        VC: VmConfig<F> + Serialize,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let (checkpoint, input) = ProvingCheckpoint::resume(
+            checkpoint_dir.as_ref(),
+            &self.app_prover.committed_exe.exe,
+            self.vm_config(),
+            self.app_prover.pk.fri_params,
+        )?;
+        Ok(self.generate_app_proof_from_checkpoint(input, checkpoint))
+    }
+
+    fn generate_app_proof_from_checkpoint(
+        &self,
+        input: StdIn,
+        mut checkpoint: ProvingCheckpoint,
+    ) -> ContinuationVmProof<SC>
+    where
+        VC: VmConfig<F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        assert!(
+            self.vm_config().system().continuation_enabled,
+            "Checkpoints require continuations."
+        );
+        info_span!(
+            "app proof",
+            group = self
+                .program_name
+                .as_ref()
+                .unwrap_or(&"app_proof".to_string())
+        )
+        .in_scope(|| {
+            #[cfg(feature = "bench-metrics")]
+            metrics::counter!("fri.log_blowup")
+                .absolute(self.app_prover.pk.fri_params.log_blowup as u64);
+            let Ok(proof) = self.app_prover.prove_with_hooks(input, &mut checkpoint);
+            proof
+        })
+    }
+
+    /// Generates proof for every continuation segment, and returns it together with the text
+    /// printed by the guest, which is not written to stdout.
+    pub fn generate_app_proof_with_output(
+        &self,
+        input: StdIn,
+    ) -> (ContinuationVmProof<SC>, GuestOutput)
+    where
+        VC: VmConfig<F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        assert!(
+            self.vm_config().system().continuation_enabled,
+            "Output capture requires continuations."
+        );
+        info_span!(
+            "app proof",
+            group = self
+                .program_name
+                .as_ref()
+                .unwrap_or(&"app_proof".to_string())
+        )
+        .in_scope(|| {
+            #[cfg(feature = "bench-metrics")]
+            metrics::counter!("fri.log_blowup")
+                .absolute(self.app_prover.pk.fri_params.log_blowup as u64);
+            self.app_prover.prove_with_output(input)
+        })
+    }
+
+    /// Generates proof for every continuation segment of a program which runs in phases separated
+    /// by `openvm::process::pause`, see [Sdk::execute_phases](crate::Sdk::execute_phases). The
+    /// proof covers all phases.
+    pub fn generate_app_proof_phases(
+        &self,
+        input: StdIn,
+        on_pause: impl FnMut(&[F]) -> StdIn,
+    ) -> ContinuationVmProof<SC>
+    where
+        VC: VmConfig<F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        assert!(
+            self.vm_config().system().continuation_enabled,
+            "Phases require continuations."
+        );
+        info_span!(
+            "app proof",
+            group = self
+                .program_name
+                .as_ref()
+                .unwrap_or(&"app_proof".to_string())
+        )
+        .in_scope(|| {
+            #[cfg(feature = "bench-metrics")]
+            metrics::counter!("fri.log_blowup")
+                .absolute(self.app_prover.pk.fri_params.log_blowup as u64);
+            let on_pause = phase_input_from_public_values(self.vm_config().system(), on_pause);
+            self.app_prover.prove_phases(input, on_pause)
+        })
+    }
+
     pub fn generate_app_proof_without_continuations(&self, input: StdIn) -> Proof<SC>
     where
         VC: VmConfig<F>,
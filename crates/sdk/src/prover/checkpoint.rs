@@ -0,0 +1,188 @@
+//! Checkpoints of continuation proving, so that a prover which is killed, e.g. on a preempted
+//! machine, resumes from the last proven segment instead of proving every segment again.
+//!
+//! A checkpoint is a directory holding the input of the proof, the number of times the
+//! segmentation was made stricter, and the proof of every segment proven so far, each in a file of
+//! its own written in the entry format of the [key cache](crate::keygen::cache). Entries are
+//! written under the digest of the executable, VM config, FRI parameters and input they were
+//! proven with, so entries of another proof in the same directory are ignored and overwritten.
+
+use std::{
+    fs::{create_dir_all, read},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use eyre::{eyre, Result};
+use openvm_circuit::arch::instructions::exe::VmExe;
+use openvm_stark_backend::proof::Proof;
+use openvm_stark_sdk::config::FriParameters;
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::{Digest, Sha256};
+use tempfile::NamedTempFile;
+
+use crate::{
+    keygen::cache::{decode_entry, encode_entry, CacheEntryError},
+    prover::vm::local::SegmentHooks,
+    StdIn, F, OPENVM_VERSION, SC,
+};
+
+const INPUT_FILE: &str = "input";
+const RETRIES_FILE: &str = "segmentation-retries";
+
+/// The checkpoint directory of a proof, see the [module](self) documentation.
+#[derive(Clone, Debug)]
+pub struct ProvingCheckpoint {
+    dir: PathBuf,
+    /// The digest of the executable, VM config, FRI parameters and input.
+    proof_digest: [u8; 32],
+    segmentation_retries: usize,
+}
+
+impl ProvingCheckpoint {
+    /// Opens the checkpoint in `dir` of proving `exe` on `input` with `vm_config` and
+    /// `fri_params`, creating the directory if needed. Segments proven into `dir` for anything
+    /// else are proven again.
+    pub fn open<VC: Serialize>(
+        dir: impl Into<PathBuf>,
+        exe: &VmExe<F>,
+        vm_config: &VC,
+        fri_params: FriParameters,
+        input: &StdIn,
+    ) -> Result<Self> {
+        let dir = dir.into();
+        let prover_digest = prover_digest(exe, vm_config, fri_params)?;
+        create_dir_all(&dir)?;
+        write_entry(&dir, INPUT_FILE, prover_digest, input)?;
+        Ok(Self::with_input(dir, prover_digest, input))
+    }
+
+    /// Opens the checkpoint in `dir` like [Self::open], with the input it was opened with.
+    pub fn resume<VC: Serialize>(
+        dir: impl Into<PathBuf>,
+        exe: &VmExe<F>,
+        vm_config: &VC,
+        fri_params: FriParameters,
+    ) -> Result<(Self, StdIn)> {
+        let dir = dir.into();
+        let prover_digest = prover_digest(exe, vm_config, fri_params)?;
+        let input: StdIn = read_entry(&dir, INPUT_FILE, prover_digest)?.ok_or_else(|| {
+            eyre!(
+                "{} holds no checkpoint of this program and config",
+                dir.display()
+            )
+        })?;
+        Ok((Self::with_input(dir, prover_digest, &input), input))
+    }
+
+    fn with_input(dir: PathBuf, prover_digest: [u8; 32], input: &StdIn) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(prover_digest);
+        hasher.update(input.digest());
+        let proof_digest = hasher.finalize().into();
+        let segmentation_retries = read_entry(&dir, RETRIES_FILE, proof_digest)
+            .unwrap_or_else(|err| {
+                tracing::warn!("{err}");
+                None
+            })
+            .unwrap_or(0);
+        Self {
+            dir,
+            proof_digest,
+            segmentation_retries,
+        }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// The number of segments, counting from the first, whose proofs the checkpoint holds.
+    pub fn num_proven_segments(&self) -> usize {
+        let digest = self.segment_digest();
+        (0..)
+            .take_while(|&segment| {
+                let proof = read_entry::<Proof<SC>>(&self.dir, &segment_file(segment), digest);
+                matches!(proof, Ok(Some(_)))
+            })
+            .count()
+    }
+
+    /// Segment proofs are only valid for the segmentation they were proven with.
+    fn segment_digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.proof_digest);
+        hasher.update((self.segmentation_retries as u64).to_le_bytes());
+        hasher.finalize().into()
+    }
+}
+
+impl SegmentHooks<SC> for ProvingCheckpoint {
+    type Error = std::convert::Infallible;
+
+    fn segmentation_retries(&self) -> usize {
+        self.segmentation_retries
+    }
+
+    fn proven_segment(&mut self, segment: usize) -> Option<Proof<SC>> {
+        read_entry(&self.dir, &segment_file(segment), self.segment_digest()).unwrap_or_else(|err| {
+            tracing::warn!("proving segment {segment} again: {err}");
+            None
+        })
+    }
+
+    /// Failing to write the checkpoint is not an error, since proving can go on without it.
+    fn on_segment_proven(&mut self, segment: usize, proof: &Proof<SC>) -> Result<(), Self::Error> {
+        let digest = self.segment_digest();
+        if let Err(err) = write_entry(&self.dir, &segment_file(segment), digest, proof) {
+            tracing::warn!("failed to checkpoint segment {segment}: {err}");
+        }
+        Ok(())
+    }
+
+    fn on_segmentation_retry(&mut self, retries: usize) {
+        self.segmentation_retries = retries;
+        if let Err(err) = write_entry(&self.dir, RETRIES_FILE, self.proof_digest, &retries) {
+            tracing::warn!("failed to checkpoint the segmentation: {err}");
+        }
+    }
+}
+
+fn prover_digest<VC: Serialize>(
+    exe: &VmExe<F>,
+    vm_config: &VC,
+    fri_params: FriParameters,
+) -> Result<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    hasher.update(OPENVM_VERSION);
+    hasher.update(bitcode::serialize(&(exe, vm_config, fri_params))?);
+    Ok(hasher.finalize().into())
+}
+
+fn segment_file(segment: usize) -> String {
+    format!("segment-{segment}")
+}
+
+/// Reads the entry `name` of the checkpoint in `dir`, which is `None` if it is missing or was
+/// written for another proof.
+fn read_entry<T: DeserializeOwned>(dir: &Path, name: &str, digest: [u8; 32]) -> Result<Option<T>> {
+    let path = dir.join(name);
+    if !path.exists() {
+        return Ok(None);
+    }
+    let bytes = read(&path)?;
+    match decode_entry(&bytes, digest) {
+        Ok(payload) => Ok(Some(bitcode::deserialize(payload)?)),
+        Err(CacheEntryError::Digest) => Ok(None),
+        Err(err) => Err(eyre!("{}: {err}", path.display())),
+    }
+}
+
+/// Writes the entry to a temporary file first, so that a prover killed while writing it leaves
+/// the previous entry intact.
+fn write_entry<T: Serialize>(dir: &Path, name: &str, digest: [u8; 32], value: &T) -> Result<()> {
+    let mut file = NamedTempFile::new_in(dir)?;
+    file.write_all(&encode_entry(digest, &bitcode::serialize(value)?))?;
+    file.persist(dir.join(name))?;
+    Ok(())
+}
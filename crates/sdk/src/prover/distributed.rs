@@ -0,0 +1,268 @@
+//! Proving the segments of an app proof on remote workers.
+//!
+//! A coordinator executes the program once with [AppProver::segment_proving_tasks], which produces
+//! a [SegmentProvingTask] per segment holding the state the segment starts from. The tasks are
+//! serializable, so they can be shipped to workers as soon as they are produced. Each worker holds
+//! the same app proving key and committed exe, executes its segment from the state of its task
+//! and proves it with [AppProver::prove_segment_task]. The coordinator then combines the
+//! [SegmentProof]s into the [ContinuationVmProof] with the [SegmentProofCombiner] returned with
+//! the tasks.
+
+use std::{collections::HashMap, sync::Arc};
+
+use openvm_circuit::{
+    arch::{
+        hasher::poseidon2::vm_poseidon2_hasher, instructions::exe::Overlays, ChannelTranscript,
+        ContinuationVmProof, ExecutionError, GenerationError, HeapProfile, HostChannels,
+        RegionProfile, Streams, VmConfig, VmExecutor, VmExecutorNextSegmentState, VmMemoryState,
+    },
+    system::memory::{tree::public_values::UserPublicValuesProof, CHUNK},
+};
+use openvm_stark_backend::{proof::Proof, Chip};
+use openvm_stark_sdk::engine::{StarkEngine, StarkFriEngine};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info_span;
+
+use crate::{
+    prover::{vm::types::VmProvingKey, AppProver},
+    StdIn, F, SC,
+};
+
+/// The proving of one segment, to be shipped to a worker, see the [module](self) documentation.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct SegmentProvingTask {
+    /// Index of the segment in the app proof.
+    pub segment: usize,
+    /// How many times the segmentation strategy of the VM config is made stricter, see
+    /// [AppProver::segment_proving_tasks].
+    pub segmentation_retries: usize,
+    pub pc: u32,
+    pub memory: VmMemoryState<F>,
+    pub input_stream: Vec<Vec<F>>,
+    pub hint_stream: Vec<F>,
+    pub hint_queue: u32,
+    pub hint_queues: Vec<(u32, Vec<F>)>,
+    pub hint_space: Vec<Vec<F>>,
+    /// The key-value store of the input, which is the same for all segments.
+    pub kv_store: HashMap<Vec<u8>, Vec<u8>>,
+    pub instret: u64,
+    pub panic_message: Option<String>,
+    pub heap_profile: Option<HeapProfile>,
+    pub region_profile: RegionProfile,
+    /// The transcript of the channels of the input, and the number of its messages replayed
+    /// before the segment.
+    pub channel_transcript: ChannelTranscript,
+    pub channels_replayed: usize,
+}
+
+impl SegmentProvingTask {
+    fn new(
+        segment: usize,
+        segmentation_retries: usize,
+        state: &VmExecutorNextSegmentState<F>,
+        kv_store: &HashMap<Vec<u8>, Vec<u8>>,
+    ) -> Self {
+        let input = &state.input;
+        Self {
+            segment,
+            segmentation_retries,
+            pc: state.pc,
+            memory: state.memory.clone(),
+            input_stream: input.input_stream.iter().cloned().collect(),
+            hint_stream: input.hint_stream.iter().copied().collect(),
+            hint_queue: input.hint_queue,
+            hint_queues: input
+                .hint_queues
+                .iter()
+                .map(|(&queue, hints)| (queue, hints.iter().copied().collect()))
+                .collect(),
+            hint_space: input.hint_space.clone(),
+            kv_store: kv_store.clone(),
+            instret: input.instret,
+            panic_message: input.panic_message.clone(),
+            heap_profile: input.heap_profile.clone(),
+            region_profile: input.region_profile.clone(),
+            channel_transcript: input.channels.transcript().clone(),
+            channels_replayed: input.channels.replayed(),
+        }
+    }
+
+    /// The state the segment starts from. The text printed by the guest is discarded, since it
+    /// was already printed when the coordinator executed the program.
+    fn into_state(self, overlays: &Overlays) -> VmExecutorNextSegmentState<F> {
+        let mut input = Streams::new(self.input_stream).with_output_capture();
+        input.hint_stream = self.hint_stream.into();
+        input.hint_queue = self.hint_queue;
+        input.hint_queues = self
+            .hint_queues
+            .into_iter()
+            .map(|(queue, hints)| (queue, hints.into()))
+            .collect();
+        input.hint_space = self.hint_space;
+        input.kv_store = Arc::new(self.kv_store);
+        input.segment = self.segment;
+        input.instret = self.instret;
+        input.panic_message = self.panic_message;
+        input.heap_profile = self.heap_profile;
+        input.region_profile = self.region_profile;
+        input.channels = HostChannels::replay(self.channel_transcript, self.channels_replayed);
+        VmExecutorNextSegmentState::new(self.memory, input.with_overlays(overlays), self.pc)
+    }
+}
+
+/// The proof of the segment of a [SegmentProvingTask], to be shipped back to the coordinator.
+#[derive(Serialize, Deserialize)]
+pub struct SegmentProof {
+    pub segment: usize,
+    pub proof: Proof<SC>,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CombineError {
+    #[error("segment {0} is out of range")]
+    OutOfRange(usize),
+    #[error("segment {0} was proven more than once")]
+    Duplicate(usize),
+    #[error("segment {0} was not proven")]
+    Missing(usize),
+}
+
+/// Combines the [SegmentProof]s of the tasks of [AppProver::segment_proving_tasks] into the app
+/// proof.
+pub struct SegmentProofCombiner {
+    num_segments: usize,
+    user_public_values: UserPublicValuesProof<{ CHUNK }, F>,
+}
+
+impl SegmentProofCombiner {
+    pub fn num_segments(&self) -> usize {
+        self.num_segments
+    }
+
+    /// Returns the app proof made of `proofs`, in any order, which must hold the proof of every
+    /// segment exactly once.
+    pub fn combine(
+        self,
+        proofs: impl IntoIterator<Item = SegmentProof>,
+    ) -> Result<ContinuationVmProof<SC>, CombineError> {
+        let mut per_segment: Vec<Option<Proof<SC>>> =
+            (0..self.num_segments).map(|_| None).collect();
+        for SegmentProof { segment, proof } in proofs {
+            let slot = per_segment
+                .get_mut(segment)
+                .ok_or(CombineError::OutOfRange(segment))?;
+            if slot.replace(proof).is_some() {
+                return Err(CombineError::Duplicate(segment));
+            }
+        }
+        let per_segment = per_segment
+            .into_iter()
+            .enumerate()
+            .map(|(segment, proof)| proof.ok_or(CombineError::Missing(segment)))
+            .collect::<Result<_, _>>()?;
+        Ok(ContinuationVmProof {
+            per_segment,
+            user_public_values: self.user_public_values,
+        })
+    }
+}
+
+impl<VC, E: StarkFriEngine<SC>> AppProver<VC, E>
+where
+    VC: VmConfig<F>,
+    VC::Executor: Chip<SC>,
+    VC::Periphery: Chip<SC>,
+{
+    /// Executes the program on `input` and calls `on_task` with the task of every segment once it
+    /// is executed, so that the task can be shipped to a worker while the execution continues.
+    /// Returns the combiner of the proofs of the tasks.
+    ///
+    /// If a worker fails with [GenerationError::TraceHeightsLimitExceeded], the segmentation is
+    /// too lenient for the trace height limits, and the tasks have to be produced again with one
+    /// more `segmentation_retries`, which makes the segmentation stricter.
+    pub fn segment_proving_tasks(
+        &self,
+        input: StdIn,
+        segmentation_retries: usize,
+        mut on_task: impl FnMut(SegmentProvingTask),
+    ) -> Result<SegmentProofCombiner, ExecutionError> {
+        assert!(
+            self.vm_config().system().continuation_enabled,
+            "Distributed proving requires continuations."
+        );
+        let pk = &self.app_prover().pk;
+        let exe = &self.app_prover().committed_exe.exe;
+        let executor = segment_executor(pk, segmentation_retries);
+        let kv_store = input.kv_store.clone();
+        let mut memory = VmMemoryState::<F>::from_mem_config(&pk.vm_config.system().memory_config);
+        memory.extend(exe.init_memory.clone());
+        let input = Streams::from(input).with_overlays(&exe.overlays);
+        let mut state = VmExecutorNextSegmentState::new(memory, input, exe.pc_start);
+        let mut segment = 0;
+        let final_memory = loop {
+            on_task(SegmentProvingTask::new(
+                segment,
+                segmentation_retries,
+                &state,
+                &kv_store,
+            ));
+            let result = info_span!("execute_segment", segment)
+                .in_scope(|| executor.execute_until_segment(exe.clone(), state))?;
+            match result.next_state {
+                Some(next_state) => state = next_state,
+                None => break result.segment.final_memory.unwrap(),
+            }
+            segment += 1;
+        };
+        let system = pk.vm_config.system();
+        Ok(SegmentProofCombiner {
+            num_segments: segment + 1,
+            user_public_values: UserPublicValuesProof::compute(
+                system.memory_config.memory_dimensions(),
+                system.num_public_values,
+                &vm_poseidon2_hasher(),
+                &final_memory,
+            ),
+        })
+    }
+
+    /// Executes the segment of `task` and proves it, on a worker holding the same app proving key
+    /// and committed exe as the coordinator which produced the task.
+    pub fn prove_segment_task(
+        &self,
+        task: SegmentProvingTask,
+    ) -> Result<SegmentProof, GenerationError> {
+        let segment = task.segment;
+        let prover = self.app_prover();
+        let executor = segment_executor(&prover.pk, task.segmentation_retries);
+        let exe = &prover.committed_exe.exe;
+        let state = task.into_state(&exe.overlays);
+        let result = info_span!("execute_segment", segment)
+            .in_scope(|| executor.execute_until_segment(exe.clone(), state))?;
+        let proof_input = info_span!("trace_gen", segment).in_scope(|| {
+            result
+                .segment
+                .generate_proof_input(Some(prover.committed_exe.committed_program.clone()))
+        })?;
+        let proof = info_span!("prove_segment", segment)
+            .in_scope(|| prover.engine().prove(&prover.pk.vm_pk, proof_input));
+        Ok(SegmentProof { segment, proof })
+    }
+}
+
+/// The executor of the segments, which segments the execution the same way on the coordinator and
+/// on the workers.
+fn segment_executor<VC: VmConfig<F>>(
+    pk: &VmProvingKey<SC, VC>,
+    segmentation_retries: usize,
+) -> VmExecutor<F, VC> {
+    let mut executor = VmExecutor::new(pk.vm_config.clone());
+    executor.set_trace_height_constraints(pk.vm_pk.trace_height_constraints.clone());
+    for _ in 0..segmentation_retries {
+        let sys_config = executor.config.system_mut();
+        let new_seg_strat = sys_config.segmentation_strategy.stricter_strategy();
+        sys_config.set_segmentation_strategy(new_seg_strat);
+    }
+    executor
+}
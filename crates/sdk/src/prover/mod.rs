@@ -1,19 +1,24 @@
 mod agg;
 mod app;
+mod checkpoint;
+pub mod distributed;
 #[cfg(feature = "evm-prove")]
 mod halo2;
 mod root;
 mod stark;
+mod task;
 pub mod vm;
 
 pub use agg::*;
 pub use app::*;
+pub use checkpoint::*;
 #[cfg(feature = "evm-prove")]
 pub use evm::*;
 #[cfg(feature = "evm-prove")]
 pub use halo2::*;
 pub use root::*;
 pub use stark::*;
+pub use task::*;
 
 #[cfg(feature = "evm-prove")]
 mod evm {
@@ -25,7 +30,7 @@ mod evm {
 
     use super::{Halo2Prover, StarkProver};
     use crate::{
-        config::AggregationTreeConfig,
+        config::{AggregationTreeConfig, ProverPerfConfig},
         keygen::{AggProvingKey, AppProvingKey},
         stdin::StdIn,
         types::EvmProof,
@@ -64,6 +69,10 @@ mod evm {
             self.stark_prover.set_program_name(program_name);
             self
         }
+        pub fn with_perf_config(mut self, perf_config: ProverPerfConfig) -> Self {
+            self.stark_prover.set_perf_config(perf_config);
+            self
+        }
 
         pub fn generate_proof_for_evm(&self, input: StdIn) -> EvmProof
         where
@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+
 use async_trait::async_trait;
 use openvm_circuit::arch::{SingleSegmentVmExecutor, Streams};
 use openvm_continuations::verifier::root::types::RootVmVerifierInput;
@@ -19,6 +21,8 @@ use crate::{
 pub struct RootVerifierLocalProver {
     pub root_verifier_pk: RootVerifierProvingKey,
     executor_for_heights: SingleSegmentVmExecutor<F, NativeConfig>,
+    /// The engine of all proofs of this prover, created by the first proof.
+    engine: OnceLock<BabyBearPoseidon2RootEngine>,
 }
 
 impl RootVerifierLocalProver {
@@ -28,6 +32,7 @@ impl RootVerifierLocalProver {
         Self {
             root_verifier_pk,
             executor_for_heights,
+            engine: OnceLock::new(),
         }
     }
     pub fn execute_for_air_heights(&self, input: RootVmVerifierInput<SC>) -> Vec<usize> {
@@ -76,8 +81,10 @@ impl SingleSegmentVmProver<RootSC> for RootVerifierLocalProver {
             // Overwrite the AIR ID.
             proof_input.per_air[i].0 = i;
         }
-        let e = BabyBearPoseidon2RootEngine::new(*self.fri_params());
-        e.prove(&self.root_verifier_pk.vm_pk.vm_pk, proof_input)
+        let engine = self
+            .engine
+            .get_or_init(|| BabyBearPoseidon2RootEngine::new(*self.fri_params()));
+        engine.prove(&self.root_verifier_pk.vm_pk.vm_pk, proof_input)
     }
 }
 
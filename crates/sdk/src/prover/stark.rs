@@ -1,16 +1,18 @@
-use std::sync::Arc;
+use std::{path::Path, sync::Arc};
 
-use openvm_circuit::arch::VmConfig;
+use eyre::Result;
+use openvm_circuit::arch::{ContinuationVmProof, VmConfig};
 use openvm_continuations::verifier::{
     internal::types::VmStarkProof, root::types::RootVmVerifierInput,
 };
 use openvm_stark_backend::{proof::Proof, Chip};
 use openvm_stark_sdk::engine::StarkFriEngine;
+use serde::Serialize;
 
 use crate::{
-    config::AggregationTreeConfig,
+    config::{AggregationTreeConfig, ProverPerfConfig},
     keygen::{AggStarkProvingKey, AppProvingKey},
-    prover::{agg::AggStarkProver, app::AppProver},
+    prover::{agg::AggStarkProver, app::AppProver, ProgressReceiver, ProvingProgress, ProvingTask},
     NonRootCommittedExe, RootSC, StdIn, F, SC,
 };
 
@@ -28,15 +30,9 @@ impl<VC, E: StarkFriEngine<SC>> StarkProver<VC, E> {
     where
         VC: VmConfig<F>,
     {
-        assert_eq!(
-            app_pk.leaf_fri_params, agg_stark_pk.leaf_vm_pk.fri_params,
-            "App VM is incompatible with Agg VM because of leaf FRI parameters"
-        );
-        assert_eq!(
-            app_pk.app_vm_pk.vm_config.system().num_public_values,
-            agg_stark_pk.num_user_public_values(),
-            "App VM is incompatible with Agg VM  because of the number of public values"
-        );
+        if let Err(e) = app_pk.check_agg_compatibility(&agg_stark_pk) {
+            panic!("{e}");
+        }
 
         Self {
             app_prover: AppProver::new(app_pk.app_vm_pk.clone(), app_committed_exe),
@@ -51,6 +47,18 @@ impl<VC, E: StarkFriEngine<SC>> StarkProver<VC, E> {
         self.app_prover.set_program_name(program_name);
         self
     }
+    /// Bounds the threads, the segments proven at the same time and the memory of the app proof,
+    /// see [ProverPerfConfig]. The number of threads applies to the global rayon thread pool, so
+    /// only the first config setting it in the process takes effect.
+    pub fn set_perf_config(&mut self, perf_config: ProverPerfConfig) -> &mut Self {
+        perf_config.init_thread_pool();
+        self.app_prover.set_perf_config(perf_config);
+        self
+    }
+    pub fn with_perf_config(mut self, perf_config: ProverPerfConfig) -> Self {
+        self.set_perf_config(perf_config);
+        self
+    }
     pub fn generate_proof_for_outer_recursion(&self, input: StdIn) -> Proof<RootSC>
     where
         VC: VmConfig<F>,
@@ -78,8 +86,76 @@ impl<VC, E: StarkFriEngine<SC>> StarkProver<VC, E> {
         VC::Periphery: Chip<SC>,
     {
         let app_proof = self.app_prover.generate_app_proof(input);
+        self.aggregate_app_proof(app_proof)
+    }
+
+    /// Generates the proof of [Self::generate_e2e_stark_proof], keeping the proofs of the
+    /// segments of the app proof in `checkpoint_dir`, see
+    /// [AppProver::generate_app_proof_with_checkpoint].
+    pub fn generate_e2e_stark_proof_with_checkpoint(
+        &self,
+        input: StdIn,
+        checkpoint_dir: impl AsRef<Path>,
+    ) -> Result<VmStarkProof<SC>>
+    where
+        VC: VmConfig<F> + Serialize,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let app_proof = self
+            .app_prover
+            .generate_app_proof_with_checkpoint(input, checkpoint_dir)?;
+        Ok(self.aggregate_app_proof(app_proof))
+    }
+
+    /// Resumes [Self::generate_e2e_stark_proof_with_checkpoint] from `checkpoint_dir` after the
+    /// prover was stopped, proving only the segments missing from the checkpoint.
+    pub fn resume(&self, checkpoint_dir: impl AsRef<Path>) -> Result<VmStarkProof<SC>>
+    where
+        VC: VmConfig<F> + Serialize,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        let app_proof = self.app_prover.resume_app_proof(checkpoint_dir)?;
+        Ok(self.aggregate_app_proof(app_proof))
+    }
+
+    fn aggregate_app_proof(&self, app_proof: ContinuationVmProof<SC>) -> VmStarkProof<SC> {
         let leaf_proofs = self.agg_prover.generate_leaf_proofs(&app_proof);
         self.agg_prover
             .aggregate_leaf_proofs(leaf_proofs, app_proof.user_public_values.public_values)
     }
+
+    /// Generates the proof of [Self::generate_e2e_stark_proof] on a thread of its own. The
+    /// returned task resolves to the proof, and the receiver reports the proven segments and the
+    /// stages of the aggregation as they complete. Cancelling or dropping the task stops proving
+    /// at the next report.
+    pub fn prove_stream(
+        self: Arc<Self>,
+        input: StdIn,
+    ) -> (ProvingTask<VmStarkProof<SC>>, ProgressReceiver)
+    where
+        Self: Send + Sync + 'static,
+        VC: VmConfig<F>,
+        VC::Executor: Chip<SC>,
+        VC::Periphery: Chip<SC>,
+    {
+        ProvingTask::spawn(move |reporter| {
+            let app_proof = self
+                .app_prover
+                .generate_app_proof_with_progress(input, |segment| {
+                    reporter.report(ProvingProgress::SegmentProven { segment })
+                })?;
+            reporter.report(ProvingProgress::AppProofGenerated {
+                num_segments: app_proof.per_segment.len(),
+            })?;
+            let leaf_proofs = self.agg_prover.generate_leaf_proofs(&app_proof);
+            reporter.report(ProvingProgress::LeafProofsGenerated {
+                num_proofs: leaf_proofs.len(),
+            })?;
+            Ok(self
+                .agg_prover
+                .aggregate_leaf_proofs(leaf_proofs, app_proof.user_public_values.public_values))
+        })
+    }
 }
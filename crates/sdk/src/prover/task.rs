@@ -0,0 +1,139 @@
+use std::{
+    future::Future,
+    panic::{catch_unwind, AssertUnwindSafe},
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+    thread,
+};
+
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+
+/// The progress of a [ProvingTask], received from its [ProgressReceiver].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProvingProgress {
+    /// The app proof of the segment with index `segment` was generated. If the segmentation of
+    /// the execution has to be retried, the segments are proven, and reported, again.
+    SegmentProven { segment: usize },
+    /// The app proofs of all `num_segments` segments were generated.
+    AppProofGenerated { num_segments: usize },
+    /// The `num_proofs` leaf proofs aggregating the app proof were generated.
+    LeafProofsGenerated { num_proofs: usize },
+}
+
+pub type ProgressReceiver = mpsc::UnboundedReceiver<ProvingProgress>;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ProvingError {
+    #[error("proving was cancelled")]
+    Cancelled,
+    #[error("proving panicked: {0}")]
+    Panicked(String),
+}
+
+/// Reports the progress of a proof generated by a [ProvingTask], and tells whether the task was
+/// cancelled.
+pub struct ProgressReporter {
+    sender: mpsc::UnboundedSender<ProvingProgress>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl ProgressReporter {
+    /// Sends `progress` to the [ProgressReceiver] of the task, if it was not dropped, and returns
+    /// [ProvingError::Cancelled] if the task was cancelled, so that proving stops at the next
+    /// report.
+    pub fn report(&self, progress: ProvingProgress) -> Result<(), ProvingError> {
+        if self.cancelled.load(Ordering::Relaxed) {
+            return Err(ProvingError::Cancelled);
+        }
+        let _ = self.sender.send(progress);
+        Ok(())
+    }
+}
+
+/// Cancels a [ProvingTask] from anywhere, see [ProvingTask::cancel_handle].
+#[derive(Clone, Debug)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+    /// Makes the task stop proving at its next progress report, e.g. after the segment which is
+    /// being proven, and resolve to [ProvingError::Cancelled].
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// A proof generated on a thread of its own, which resolves to the proof once it is generated.
+///
+/// Proving does not block the thread polling the task, and the task does not depend on any
+/// particular async runtime. Dropping the task cancels it.
+pub struct ProvingTask<T> {
+    result: oneshot::Receiver<Result<T, ProvingError>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl<T: Send + 'static> ProvingTask<T> {
+    /// Runs `prove` on a new thread, which reports its progress to the returned receiver. A panic
+    /// of `prove` resolves the task to [ProvingError::Panicked].
+    pub fn spawn(
+        prove: impl FnOnce(&ProgressReporter) -> Result<T, ProvingError> + Send + 'static,
+    ) -> (Self, ProgressReceiver) {
+        let (result_sender, result) = oneshot::channel();
+        let (sender, progress) = mpsc::unbounded_channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let reporter = ProgressReporter {
+            sender,
+            cancelled: cancelled.clone(),
+        };
+        thread::Builder::new()
+            .name("openvm-prover".to_string())
+            .spawn(move || {
+                let output =
+                    catch_unwind(AssertUnwindSafe(|| prove(&reporter))).unwrap_or_else(|payload| {
+                        let msg = payload
+                            .downcast_ref::<&str>()
+                            .map(|s| s.to_string())
+                            .or_else(|| payload.downcast_ref::<String>().cloned())
+                            .unwrap_or_else(|| "unknown panic".to_string());
+                        Err(ProvingError::Panicked(msg))
+                    });
+                let _ = result_sender.send(output);
+            })
+            .expect("failed to spawn the proving thread");
+        (Self { result, cancelled }, progress)
+    }
+}
+
+impl<T> ProvingTask<T> {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn cancel_handle(&self) -> CancelHandle {
+        CancelHandle(self.cancelled.clone())
+    }
+}
+
+impl<T> Future for ProvingTask<T> {
+    type Output = Result<T, ProvingError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.result).poll(cx).map(|output| {
+            output.unwrap_or_else(|_| {
+                Err(ProvingError::Panicked(
+                    "the proving thread exited".to_string(),
+                ))
+            })
+        })
+    }
+}
+
+impl<T> Drop for ProvingTask<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
@@ -1,31 +1,61 @@
-use std::{marker::PhantomData, mem, sync::Arc};
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    marker::PhantomData,
+    mem,
+    panic::{self, resume_unwind, AssertUnwindSafe},
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, OnceLock,
+    },
+    thread::{self, Scope},
+};
 
 use async_trait::async_trait;
 use openvm_circuit::{
     arch::{
-        hasher::poseidon2::vm_poseidon2_hasher, GenerationError, SingleSegmentVmExecutor, Streams,
-        VirtualMachine, VmComplexTraceHeights, VmConfig,
+        hasher::poseidon2::vm_poseidon2_hasher, GenerationError, GuestOutput,
+        SingleSegmentVmExecutor, Streams, VmComplexTraceHeights, VmConfig, VmExecutor,
+        VmMemoryState,
+    },
+    system::{
+        memory::{tree::public_values::UserPublicValuesProof, MemoryImage},
+        program::trace::VmCommittedExe,
     },
-    system::{memory::tree::public_values::UserPublicValuesProof, program::trace::VmCommittedExe},
 };
 use openvm_stark_backend::{
     config::{StarkGenericConfig, Val},
+    keygen::types::MultiStarkProvingKey,
     p3_field::PrimeField32,
     proof::Proof,
+    prover::types::ProofInput,
     Chip,
 };
-use openvm_stark_sdk::{config::FriParameters, engine::StarkFriEngine};
+use openvm_stark_sdk::{
+    config::FriParameters,
+    engine::{StarkEngine, StarkFriEngine},
+};
 use tracing::info_span;
 
-use crate::prover::vm::{
-    types::VmProvingKey, AsyncContinuationVmProver, AsyncSingleSegmentVmProver,
-    ContinuationVmProof, ContinuationVmProver, SingleSegmentVmProver,
+use crate::{
+    config::ProverPerfConfig,
+    prover::vm::{
+        types::VmProvingKey, AsyncContinuationVmProver, AsyncSingleSegmentVmProver,
+        ContinuationVmProof, ContinuationVmProver, SingleSegmentVmProver,
+    },
 };
 
 pub struct VmLocalProver<SC: StarkGenericConfig, VC, E: StarkFriEngine<SC>> {
     pub pk: Arc<VmProvingKey<SC, VC>>,
     pub committed_exe: Arc<VmCommittedExe<SC>>,
     overridden_heights: Option<VmComplexTraceHeights>,
+    perf_config: ProverPerfConfig,
+    /// The engine of the proofs of this prover, created by the first proof. The engine owns the
+    /// precomputations of the PCS, such as the twiddle factors of its DFT, so sharing it saves
+    /// recomputing them for every segment and aggregation proof. Segments proven concurrently
+    /// use the engines of their worker threads instead, see
+    /// [VmLocalProver::prove_segments_concurrently].
+    engine: OnceLock<E>,
     _marker: PhantomData<E>,
 }
 
@@ -35,6 +65,8 @@ impl<SC: StarkGenericConfig, VC, E: StarkFriEngine<SC>> VmLocalProver<SC, VC, E>
             pk,
             committed_exe,
             overridden_heights: None,
+            perf_config: ProverPerfConfig::default(),
+            engine: OnceLock::new(),
             _marker: PhantomData,
         }
     }
@@ -48,6 +80,8 @@ impl<SC: StarkGenericConfig, VC, E: StarkFriEngine<SC>> VmLocalProver<SC, VC, E>
             pk,
             committed_exe,
             overridden_heights,
+            perf_config: ProverPerfConfig::default(),
+            engine: OnceLock::new(),
             _marker: PhantomData,
         }
     }
@@ -56,6 +90,17 @@ impl<SC: StarkGenericConfig, VC, E: StarkFriEngine<SC>> VmLocalProver<SC, VC, E>
         self.overridden_heights = Some(overridden_heights);
     }
 
+    /// Sets the bounds on the segments proven at the same time by [ContinuationVmProver::prove]
+    /// and the other proofs with continuations. The number of threads is that of the global rayon
+    /// thread pool, see [ProverPerfConfig::init_thread_pool].
+    pub fn set_perf_config(&mut self, perf_config: ProverPerfConfig) {
+        self.perf_config = perf_config;
+    }
+
+    pub fn perf_config(&self) -> &ProverPerfConfig {
+        &self.perf_config
+    }
+
     pub fn vm_config(&self) -> &VC {
         &self.pk.vm_config
     }
@@ -63,53 +108,145 @@ impl<SC: StarkGenericConfig, VC, E: StarkFriEngine<SC>> VmLocalProver<SC, VC, E>
     pub(crate) fn fri_params(&self) -> &FriParameters {
         &self.pk.fri_params
     }
+
+    /// The engine proving with the FRI parameters of the proving key, shared by all proofs of
+    /// this prover.
+    pub fn engine(&self) -> &E {
+        self.engine.get_or_init(|| E::new(self.pk.fri_params))
+    }
 }
 
 const MAX_SEGMENTATION_RETRIES: usize = 4;
 
-impl<SC: StarkGenericConfig, VC: VmConfig<Val<SC>>, E: StarkFriEngine<SC>> ContinuationVmProver<SC>
-    for VmLocalProver<SC, VC, E>
+impl<SC: StarkGenericConfig, VC: VmConfig<Val<SC>>, E: StarkFriEngine<SC>> VmLocalProver<SC, VC, E>
 where
     Val<SC>: PrimeField32,
     VC::Executor: Chip<SC>,
     VC::Periphery: Chip<SC>,
+    // The segments are proven on threads of their own.
+    MultiStarkProvingKey<SC>: Sync,
+    ProofInput<SC>: Send,
+    Proof<SC>: Send,
 {
-    fn prove(&self, input: impl Into<Streams<Val<SC>>>) -> ContinuationVmProof<SC> {
+    /// Proves like [ContinuationVmProver::prove], and also returns the text printed by the guest
+    /// instead of writing it to stdout.
+    pub fn prove_with_output(
+        &self,
+        input: impl Into<Streams<Val<SC>>>,
+    ) -> (ContinuationVmProof<SC>, GuestOutput) {
+        let Ok((proof, output)) =
+            self.prove_continuations(input.into().with_output_capture(), |_| vec![], &mut NoHooks);
+        (proof, output.expect("output should be captured"))
+    }
+
+    /// Proves a program which runs in phases separated by pauses, supplying more input at every
+    /// pause with `on_pause`, see
+    /// [VmExecutor::execute_phases_and_then](openvm_circuit::arch::VmExecutor::execute_phases_and_then).
+    /// The proof covers all phases.
+    pub fn prove_phases(
+        &self,
+        input: impl Into<Streams<Val<SC>>>,
+        on_pause: impl FnMut(&VmMemoryState<Val<SC>>) -> Vec<Vec<Val<SC>>>,
+    ) -> ContinuationVmProof<SC> {
+        let Ok((proof, _)) = self.prove_continuations(input.into(), on_pause, &mut NoHooks);
+        proof
+    }
+
+    /// Proves like [ContinuationVmProver::prove], calling `on_segment_proven` with the index of
+    /// every segment once it is proven. Proving stops at the first error `on_segment_proven`
+    /// returns, e.g. to cancel it.
+    pub fn prove_with_progress<Err>(
+        &self,
+        input: impl Into<Streams<Val<SC>>>,
+        on_segment_proven: impl FnMut(usize) -> Result<(), Err>,
+    ) -> Result<ContinuationVmProof<SC>, Err> {
+        self.prove_with_hooks(input, &mut OnSegmentProven(on_segment_proven))
+    }
+
+    /// Proves like [ContinuationVmProver::prove], reusing the segment proofs which `hooks` kept
+    /// from an earlier run and passing it the newly proven ones, see [SegmentHooks].
+    pub fn prove_with_hooks<H: SegmentHooks<SC>>(
+        &self,
+        input: impl Into<Streams<Val<SC>>>,
+        hooks: &mut H,
+    ) -> Result<ContinuationVmProof<SC>, H::Error> {
+        self.prove_continuations(input.into(), |_| vec![], hooks)
+            .map(|(proof, _)| proof)
+    }
+
+    fn prove_continuations<H: SegmentHooks<SC>>(
+        &self,
+        input: Streams<Val<SC>>,
+        mut on_pause: impl FnMut(&VmMemoryState<Val<SC>>) -> Vec<Vec<Val<SC>>>,
+        hooks: &mut H,
+    ) -> Result<(ContinuationVmProof<SC>, Option<GuestOutput>), H::Error> {
         assert!(self.pk.vm_config.system().continuation_enabled);
-        let e = E::new(self.pk.fri_params);
-        let trace_height_constraints = self.pk.vm_pk.trace_height_constraints.clone();
-        let mut vm = VirtualMachine::new_with_overridden_trace_heights(
-            e,
+        let engine = self.engine();
+        let mut executor = VmExecutor::new_with_overridden_trace_heights(
             self.pk.vm_config.clone(),
             self.overridden_heights.clone(),
         );
-        vm.set_trace_height_constraints(trace_height_constraints.clone());
+        executor.set_trace_height_constraints(self.pk.vm_pk.trace_height_constraints.clone());
         let mut final_memory = None;
+        let mut output = None;
         let VmCommittedExe {
             exe,
             committed_program,
         } = self.committed_exe.as_ref();
-        let input = input.into();
 
         // This loop should typically iterate exactly once. Only in exceptional cases will the
         // segmentation produce an invalid segment and we will have to retry.
-        let mut retries = 0;
+        let mut retries = hooks.segmentation_retries();
+        for _ in 0..retries {
+            let sys_config = executor.config.system_mut();
+            let new_seg_strat = sys_config.segmentation_strategy.stricter_strategy();
+            sys_config.set_segmentation_strategy(new_seg_strat);
+        }
         let per_segment = loop {
-            match vm.executor.execute_and_then(
-                exe.clone(),
-                input.clone(),
-                |seg_idx, mut seg| {
-                    final_memory = mem::take(&mut seg.final_memory);
-                    let proof_input = info_span!("trace_gen", segment = seg_idx)
-                        .in_scope(|| seg.generate_proof_input(Some(committed_program.clone())))?;
-                    info_span!("prove_segment", segment = seg_idx)
-                        .in_scope(|| Ok(vm.engine.prove(&self.pk.vm_pk, proof_input)))
-                },
-                GenerationError::Execution,
-            ) {
+            let proven = if self.perf_config.max_segment_parallelism > 1 {
+                self.prove_segments_concurrently(
+                    &executor,
+                    input.clone(),
+                    &mut on_pause,
+                    hooks,
+                    &mut final_memory,
+                    &mut output,
+                )
+            } else {
+                executor.execute_phases_and_then(
+                    exe.clone(),
+                    input.clone(),
+                    &mut on_pause,
+                    |seg_idx, mut seg| {
+                        final_memory = mem::take(&mut seg.final_memory);
+                        output = seg.chip_complex.take_streams().output;
+                        if let Some(proof) = hooks.proven_segment(seg_idx) {
+                            tracing::info!("reusing the proof of segment {seg_idx}");
+                            return Ok(proof);
+                        }
+                        let proof_input = info_span!("trace_gen", segment = seg_idx)
+                            .in_scope(|| {
+                                seg.release_memory_images();
+                                seg.generate_proof_input(Some(committed_program.clone()))
+                            })
+                            .map_err(SegmentError::Generation)?;
+                        let proof = info_span!("prove_segment", segment = seg_idx)
+                            .in_scope(|| engine.prove(&self.pk.vm_pk, proof_input));
+                        hooks
+                            .on_segment_proven(seg_idx, &proof)
+                            .map_err(SegmentError::Stopped)?;
+                        Ok(proof)
+                    },
+                    |err| SegmentError::Generation(GenerationError::Execution(err)),
+                )
+            };
+            match proven {
                 Ok(per_segment) => break per_segment,
-                Err(GenerationError::Execution(err)) => panic!("execution error: {err}"),
-                Err(GenerationError::TraceHeightsLimitExceeded) => {
+                Err(SegmentError::Stopped(err)) => return Err(err),
+                Err(SegmentError::Generation(GenerationError::Execution(err))) => {
+                    panic!("execution error: {err}")
+                }
+                Err(SegmentError::Generation(GenerationError::TraceHeightsLimitExceeded)) => {
                     if retries >= MAX_SEGMENTATION_RETRIES {
                         panic!(
                             "trace heights limit exceeded after {MAX_SEGMENTATION_RETRIES} retries"
@@ -119,7 +256,8 @@ where
                     tracing::info!(
                         "trace heights limit exceeded; retrying execution (attempt {retries})"
                     );
-                    let sys_config = vm.executor.config.system_mut();
+                    hooks.on_segmentation_retry(retries);
+                    let sys_config = executor.config.system_mut();
                     let new_seg_strat = sys_config.segmentation_strategy.stricter_strategy();
                     sys_config.set_segmentation_strategy(new_seg_strat);
                     // continue
@@ -133,10 +271,273 @@ where
             &vm_poseidon2_hasher(),
             final_memory.as_ref().unwrap(),
         );
-        ContinuationVmProof {
+        let proof = ContinuationVmProof {
             per_segment,
             user_public_values,
+        };
+        Ok((proof, output))
+    }
+
+    /// Executes the program and proves its segments like [Self::prove_continuations], but proves
+    /// up to [ProverPerfConfig::max_segment_parallelism] segments at the same time on as many
+    /// worker threads, while the execution goes on. Each worker creates an engine of its own for
+    /// its first segment, so the engine need not be shared across threads. A segment waits for
+    /// earlier ones to be proven before its trace is generated if the estimated memory of the
+    /// segments being proven would exceed [ProverPerfConfig::memory_budget].
+    fn prove_segments_concurrently<H: SegmentHooks<SC>>(
+        &self,
+        executor: &VmExecutor<Val<SC>, VC>,
+        input: Streams<Val<SC>>,
+        on_pause: impl FnMut(&VmMemoryState<Val<SC>>) -> Vec<Vec<Val<SC>>>,
+        hooks: &mut H,
+        final_memory: &mut Option<MemoryImage<Val<SC>>>,
+        output: &mut Option<GuestOutput>,
+    ) -> Result<Vec<Proof<SC>>, SegmentError<H::Error>> {
+        let ProverPerfConfig {
+            max_segment_parallelism,
+            memory_budget,
+            ..
+        } = self.perf_config;
+        let VmCommittedExe {
+            exe,
+            committed_program,
+        } = self.committed_exe.as_ref();
+        let fri_params = self.pk.fri_params;
+        thread::scope(|scope| {
+            let mut proofs = Vec::new();
+            let mut in_flight = InFlightSegments::start::<E>(
+                scope,
+                max_segment_parallelism,
+                fri_params,
+                &self.pk.vm_pk,
+            );
+            executor.execute_phases_and_then(
+                exe.clone(),
+                input,
+                on_pause,
+                |seg_idx, mut seg| {
+                    *final_memory = mem::take(&mut seg.final_memory);
+                    *output = seg.chip_complex.take_streams().output;
+                    proofs.push(None);
+                    if let Some(proof) = hooks.proven_segment(seg_idx) {
+                        tracing::info!("reusing the proof of segment {seg_idx}");
+                        proofs[seg_idx] = Some(proof);
+                        return Ok(());
+                    }
+                    let memory = estimated_proving_memory::<SC>(
+                        seg.current_trace_cells().iter().sum(),
+                        fri_params.log_blowup,
+                    );
+                    while in_flight.must_wait(memory, max_segment_parallelism, memory_budget) {
+                        in_flight.join_oldest(&mut proofs, hooks)?;
+                    }
+                    let proof_input = info_span!("trace_gen", segment = seg_idx)
+                        .in_scope(|| {
+                            seg.release_memory_images();
+                            seg.generate_proof_input(Some(committed_program.clone()))
+                        })
+                        .map_err(SegmentError::Generation)?;
+                    in_flight.submit(seg_idx, memory, proof_input);
+                    Ok(())
+                },
+                |err| SegmentError::Generation(GenerationError::Execution(err)),
+            )?;
+            while !in_flight.is_empty() {
+                in_flight.join_oldest(&mut proofs, hooks)?;
+            }
+            Ok(proofs.into_iter().map(Option::unwrap).collect())
+        })
+    }
+}
+
+/// The segments being proven by [VmLocalProver::prove_segments_concurrently], in order, and the
+/// worker threads proving them.
+struct InFlightSegments<SC: StarkGenericConfig> {
+    segments: VecDeque<(usize, usize)>,
+    /// The estimated memory of the segments, see [estimated_proving_memory].
+    memory: usize,
+    /// The segments to be proven by the first idle worker.
+    jobs: Sender<(usize, ProofInput<SC>)>,
+    /// The proofs of the workers, in the order they are proven.
+    proofs: Receiver<(usize, thread::Result<Proof<SC>>)>,
+}
+
+impl<SC: StarkGenericConfig> InFlightSegments<SC>
+where
+    MultiStarkProvingKey<SC>: Sync,
+    ProofInput<SC>: Send,
+    Proof<SC>: Send,
+{
+    /// Spawns `num_workers` worker threads in `scope`, which run until `self` is dropped.
+    fn start<'scope, E: StarkFriEngine<SC>>(
+        scope: &'scope Scope<'scope, '_>,
+        num_workers: usize,
+        fri_params: FriParameters,
+        vm_pk: &'scope MultiStarkProvingKey<SC>,
+    ) -> Self {
+        let (jobs, job_receiver) = mpsc::channel::<(usize, ProofInput<SC>)>();
+        let (proof_sender, proofs) = mpsc::channel();
+        let job_receiver = Arc::new(Mutex::new(job_receiver));
+        for _ in 0..num_workers {
+            let job_receiver = job_receiver.clone();
+            let proof_sender = proof_sender.clone();
+            scope.spawn(move || {
+                let mut engine = None;
+                loop {
+                    // The lock is released before the segment is proven.
+                    let job = job_receiver.lock().unwrap().recv();
+                    let Ok((segment, proof_input)) = job else {
+                        return;
+                    };
+                    let proof = panic::catch_unwind(AssertUnwindSafe(|| {
+                        let engine = engine.get_or_insert_with(|| E::new(fri_params));
+                        info_span!("prove_segment", segment)
+                            .in_scope(|| engine.prove(vm_pk, proof_input))
+                    }));
+                    if proof_sender.send((segment, proof)).is_err() {
+                        return;
+                    }
+                }
+            });
         }
+        Self {
+            segments: VecDeque::new(),
+            memory: 0,
+            jobs,
+            proofs,
+        }
+    }
+
+    /// Whether to wait for the oldest segment to be proven before the trace of a segment with an
+    /// estimated memory of `memory` is generated. A segment is always proven once no other
+    /// segment is.
+    fn must_wait(
+        &self,
+        memory: usize,
+        max_segment_parallelism: usize,
+        memory_budget: Option<usize>,
+    ) -> bool {
+        !self.segments.is_empty()
+            && (self.segments.len() >= max_segment_parallelism
+                || memory_budget.is_some_and(|budget| self.memory + memory > budget))
+    }
+
+    fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Passes `segment` to the first idle worker.
+    fn submit(&mut self, segment: usize, memory: usize, proof_input: ProofInput<SC>) {
+        self.memory += memory;
+        self.segments.push_back((segment, memory));
+        self.jobs
+            .send((segment, proof_input))
+            .expect("the workers run until the segments are dropped");
+    }
+
+    /// Waits for the oldest segment to be proven and passes its proof to `hooks`. The proofs of
+    /// later segments proven in the meantime are kept in `proofs`.
+    fn join_oldest<H: SegmentHooks<SC>>(
+        &mut self,
+        proofs: &mut [Option<Proof<SC>>],
+        hooks: &mut H,
+    ) -> Result<(), SegmentError<H::Error>> {
+        let (segment, memory) = self.segments.pop_front().unwrap();
+        while proofs[segment].is_none() {
+            let (proven, proof) = self
+                .proofs
+                .recv()
+                .expect("the workers run until the segments are dropped");
+            proofs[proven] = Some(proof.unwrap_or_else(|panic| resume_unwind(panic)));
+        }
+        self.memory -= memory;
+        hooks
+            .on_segment_proven(segment, proofs[segment].as_ref().unwrap())
+            .map_err(SegmentError::Stopped)?;
+        Ok(())
+    }
+}
+
+/// A rough estimate of the memory needed to prove a segment whose traces have `trace_cells`
+/// cells: the traces are extended by the blowup factor to be committed, and the permutation and
+/// quotient traces take about as much again.
+fn estimated_proving_memory<SC: StarkGenericConfig>(
+    trace_cells: usize,
+    log_blowup: usize,
+) -> usize {
+    (trace_cells * mem::size_of::<Val<SC>>()) << (log_blowup + 1)
+}
+
+/// Hooks of [VmLocalProver::prove_with_hooks] into the proving of each segment, e.g. to keep the
+/// segment proofs so that proving resumes where it stopped, see
+/// [ProvingCheckpoint](crate::prover::ProvingCheckpoint).
+pub trait SegmentHooks<SC: StarkGenericConfig> {
+    type Error;
+
+    /// How many times the segmentation was made stricter in an earlier run, see
+    /// [SegmentHooks::on_segmentation_retry].
+    fn segmentation_retries(&self) -> usize {
+        0
+    }
+
+    /// Returns the proof of `segment` kept from an earlier run with the same segmentation, which
+    /// is used instead of proving the segment again.
+    fn proven_segment(&mut self, _segment: usize) -> Option<Proof<SC>> {
+        None
+    }
+
+    /// Called with every segment once it is proven. Proving stops at the first error.
+    fn on_segment_proven(&mut self, segment: usize, proof: &Proof<SC>) -> Result<(), Self::Error>;
+
+    /// Called when a segment exceeds the trace height limits and the segmentation is made
+    /// stricter for the `retries`-th time, which invalidates the segments proven before.
+    fn on_segmentation_retry(&mut self, _retries: usize) {}
+}
+
+/// [SegmentHooks] which do nothing.
+struct NoHooks;
+
+impl<SC: StarkGenericConfig> SegmentHooks<SC> for NoHooks {
+    type Error = Infallible;
+
+    fn on_segment_proven(&mut self, _: usize, _: &Proof<SC>) -> Result<(), Infallible> {
+        Ok(())
+    }
+}
+
+/// [SegmentHooks] which pass the index of every proven segment to a callback.
+struct OnSegmentProven<F>(F);
+
+impl<SC: StarkGenericConfig, Err, F: FnMut(usize) -> Result<(), Err>> SegmentHooks<SC>
+    for OnSegmentProven<F>
+{
+    type Error = Err;
+
+    fn on_segment_proven(&mut self, segment: usize, _: &Proof<SC>) -> Result<(), Err> {
+        (self.0)(segment)
+    }
+}
+
+/// Why proving a segment in [VmLocalProver::prove_continuations] failed.
+enum SegmentError<Err> {
+    Generation(GenerationError),
+    /// The callback of the proven segments returned an error.
+    Stopped(Err),
+}
+
+impl<SC: StarkGenericConfig, VC: VmConfig<Val<SC>>, E: StarkFriEngine<SC>> ContinuationVmProver<SC>
+    for VmLocalProver<SC, VC, E>
+where
+    Val<SC>: PrimeField32,
+    VC::Executor: Chip<SC>,
+    VC::Periphery: Chip<SC>,
+    MultiStarkProvingKey<SC>: Sync,
+    ProofInput<SC>: Send,
+    Proof<SC>: Send,
+{
+    fn prove(&self, input: impl Into<Streams<Val<SC>>>) -> ContinuationVmProof<SC> {
+        let Ok(proof) = self.prove_with_hooks(input, &mut NoHooks);
+        proof
     }
 }
 
@@ -148,6 +549,9 @@ where
     Val<SC>: PrimeField32,
     VC::Executor: Chip<SC>,
     VC::Periphery: Chip<SC>,
+    MultiStarkProvingKey<SC>: Sync,
+    ProofInput<SC>: Send,
+    Proof<SC>: Send,
 {
     async fn prove(
         &self,
@@ -166,7 +570,6 @@ where
 {
     fn prove(&self, input: impl Into<Streams<Val<SC>>>) -> Proof<SC> {
         assert!(!self.pk.vm_config.system().continuation_enabled);
-        let e = E::new(self.pk.fri_params);
         // note: use SingleSegmentVmExecutor so there's not a "segment" label in metrics
         let executor = {
             let mut executor = SingleSegmentVmExecutor::new(self.pk.vm_config.clone());
@@ -176,8 +579,7 @@ where
         let proof_input = executor
             .execute_and_generate(self.committed_exe.clone(), input)
             .unwrap();
-        let vm = VirtualMachine::new(e, executor.config);
-        vm.prove_single(&self.pk.vm_pk, proof_input)
+        self.engine().prove(&self.pk.vm_pk, proof_input)
     }
 }
 
@@ -0,0 +1,186 @@
+use eyre::Result;
+use openvm::io::{extract_failure, journal, Failure, Journal};
+use openvm_circuit::{
+    arch::hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
+    system::memory::CHUNK,
+};
+use openvm_continuations::{verifier::internal::types::VmStarkProof, SC};
+use openvm_stark_backend::{
+    p3_field::{FieldAlgebra, PrimeField32},
+    proof::Proof,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_with::serde_as;
+
+use crate::{
+    codec::{Decode, Encode},
+    commit::{check_published_bytes, AppExecutionCommit, CommitBytes},
+    keygen::AggStarkVerifyingKey,
+    types::ProofMetadata,
+    Sdk, StdIn, F,
+};
+
+/// The statement proven by a [Receipt]: which program was run, on which VM, with which inputs.
+#[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+pub struct ReceiptClaim {
+    #[serde(flatten)]
+    pub app_commit: AppExecutionCommit,
+    /// Commitment to the input stream the program was run with, see [commit_stdin].
+    ///
+    /// The proof does not bind the inputs, so this is only meaningful to a verifier that also
+    /// holds the inputs and checks them with [Receipt::verify_input].
+    pub input_commit: CommitBytes,
+}
+
+/// A self-contained proof of a guest program execution, bundling the [ReceiptClaim], the user
+/// public values (journal) and the end-to-end STARK proof.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Receipt {
+    pub claim: ReceiptClaim,
+    /// User public values revealed by the guest, one byte per public value.
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub journal: Vec<u8>,
+    /// Encoded [Proof] of the root verifier.
+    #[serde_as(as = "serde_with::hex::Hex")]
+    pub proof: Vec<u8>,
+    /// Non-committed metadata, ignored by verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProofMetadata>,
+}
+
+impl Receipt {
+    pub fn new(
+        app_commit: AppExecutionCommit,
+        inputs: &StdIn,
+        proof: VmStarkProof<SC>,
+    ) -> Result<Self> {
+        let journal = proof
+            .user_public_values
+            .iter()
+            .map(|v| {
+                u8::try_from(v.as_canonical_u32())
+                    .map_err(|_| eyre::eyre!("User public value {v} does not fit in a byte"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self {
+            claim: ReceiptClaim {
+                app_commit,
+                input_commit: commit_stdin(inputs),
+            },
+            journal,
+            proof: proof.proof.encode_to_vec()?,
+            metadata: None,
+        })
+    }
+
+    pub fn with_metadata(mut self, metadata: ProofMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
+
+    /// Decodes the end-to-end STARK proof, with the journal as its user public values.
+    pub fn stark_proof(&self) -> Result<VmStarkProof<SC>> {
+        Ok(VmStarkProof {
+            proof: Proof::decode_from_bytes(&self.proof)?,
+            user_public_values: self
+                .journal
+                .iter()
+                .map(|&b| F::from_canonical_u8(b))
+                .collect(),
+        })
+    }
+
+    /// Decodes the value committed by the guest with `openvm::io::commit`.
+    pub fn decode_journal<T: DeserializeOwned>(&self) -> Result<Journal<T>> {
+        Ok(journal(&self.journal)?)
+    }
+
+    /// Verifies the proof against the aggregation STARK verifying key and checks that it proves
+    /// the claimed app commits and journal.
+    pub fn verify(&self, agg_stark_vk: &AggStarkVerifyingKey) -> Result<()> {
+        Sdk::new().verify_e2e_stark_proof_with_vk(
+            agg_stark_vk,
+            &self.stark_proof()?,
+            &self.claim.app_commit.app_exe_commit.to_bn254(),
+            &self.claim.app_commit.app_vm_commit.to_bn254(),
+        )?;
+        Ok(())
+    }
+
+    /// Returns the failure committed by the guest with `openvm::ensure!`, or `None` if the program
+    /// did not fail a check. A verified receipt with a failure proves that the program stopped
+    /// at the failed check.
+    pub fn failure(&self) -> Option<Failure> {
+        extract_failure(&self.journal)
+    }
+
+    /// Checks that the journal commits to `data`, published by the guest with
+    /// `openvm::io::publish_bytes`, one item per call.
+    pub fn verify_published<I>(&self, data: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let user_public_values: Vec<F> = self
+            .journal
+            .iter()
+            .map(|&b| F::from_canonical_u8(b))
+            .collect();
+        check_published_bytes(&user_public_values, data)
+    }
+
+    /// Checks that the claimed input commitment matches `inputs`.
+    pub fn verify_input(&self, inputs: &StdIn) -> Result<()> {
+        let input_commit = commit_stdin(inputs);
+        if input_commit.as_slice() != self.claim.input_commit.as_slice() {
+            return Err(eyre::eyre!(
+                "Invalid input commit: expected {:?}, got {:?}",
+                self.claim.input_commit,
+                input_commit
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Commits to the input stream by absorbing the number of input buffers and key-value pairs, then
+/// every input buffer in order, then every key-value pair (sorted by key) into a Poseidon2 hash
+/// chain. Each of these is absorbed as its length followed by its contents, right-padded with zeros
+/// to a multiple of `CHUNK`.
+pub fn commit_stdin(inputs: &StdIn) -> CommitBytes {
+    let hasher = vm_poseidon2_hasher();
+    let mut lens = [F::ZERO; CHUNK];
+    lens[0] = F::from_canonical_usize(inputs.buffer.len());
+    lens[1] = F::from_canonical_usize(inputs.kv_store.len());
+    let mut state = hasher.compress(&[F::ZERO; CHUNK], &lens);
+    let mut absorb = |values: &[F]| {
+        let mut len = [F::ZERO; CHUNK];
+        len[0] = F::from_canonical_usize(values.len());
+        state = hasher.compress(&state, &len);
+        for chunk in values.chunks(CHUNK) {
+            let mut padded = [F::ZERO; CHUNK];
+            padded[..chunk.len()].copy_from_slice(chunk);
+            state = hasher.compress(&state, &padded);
+        }
+    };
+    for input in &inputs.buffer {
+        absorb(input);
+    }
+    let mut kv_store: Vec<_> = inputs.kv_store.iter().collect();
+    kv_store.sort();
+    for (key, value) in kv_store {
+        absorb(
+            &key.iter()
+                .map(|&b| F::from_canonical_u8(b))
+                .collect::<Vec<_>>(),
+        );
+        absorb(
+            &value
+                .iter()
+                .map(|&b| F::from_canonical_u8(b))
+                .collect::<Vec<_>>(),
+        );
+    }
+    CommitBytes::from_u32_digest(&state.map(|x| x.as_canonical_u32()))
+}
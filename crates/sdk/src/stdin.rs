@@ -1,11 +1,13 @@
 use std::{
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     sync::Arc,
 };
 
-use openvm_circuit::arch::Streams;
-use openvm_stark_backend::p3_field::FieldAlgebra;
+use openvm::io::inputs::{InputWrite, Inputs};
+use openvm_circuit::arch::{ChannelTranscript, HostChannels, Streams};
+use openvm_stark_backend::p3_field::{FieldAlgebra, PrimeField32};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
 use crate::F;
 
@@ -13,6 +15,13 @@ use crate::F;
 pub struct StdIn {
     pub buffer: VecDeque<Vec<F>>,
     pub kv_store: HashMap<Vec<u8>, Vec<u8>>,
+    /// The transcript of the channels of the guest, which the execution replays, see
+    /// [Sdk::execute_interactive](crate::Sdk::execute_interactive).
+    #[serde(default)]
+    pub channel_transcript: ChannelTranscript,
+    /// Whether the last input stream consists of frames written by [StdIn::write_frame].
+    #[serde(skip)]
+    in_frames: bool,
 }
 
 impl StdIn {
@@ -35,14 +44,132 @@ impl StdIn {
     pub fn write_bytes(&mut self, data: &[u8]) {
         let field_data = data.iter().map(|b| F::from_canonical_u8(*b)).collect();
         self.buffer.push_back(field_data);
+        self.in_frames = false;
     }
 
     pub fn write_field(&mut self, data: &[F]) {
         self.buffer.push_back(data.to_vec());
+        self.in_frames = false;
     }
+
+    /// Writes `data` as a length-prefixed frame, to be read by the guest with
+    /// `openvm::io::Reader::read_frame`. Consecutive frames are written to the same input stream,
+    /// which the guest reads with a single `Reader`, and any other write starts a new input stream.
+    pub fn write_frame(&mut self, data: &[u8]) {
+        let padded_len = data.len().next_multiple_of(4);
+        let frame = (data.len() as u32)
+            .to_le_bytes()
+            .into_iter()
+            .chain(data.iter().copied())
+            .chain(std::iter::repeat_n(0, padded_len - data.len()))
+            .map(F::from_canonical_u8);
+        match self.buffer.back_mut() {
+            Some(stream) if self.in_frames => stream.extend(frame),
+            _ => self.buffer.push_back(frame.collect()),
+        }
+        self.in_frames = true;
+    }
+
+    /// Serializes `data` and writes it as a frame, to be read by the guest with
+    /// `openvm::io::Reader::read_as`. See [StdIn::write_frame].
+    pub fn write_frame_as<T: Serialize>(&mut self, data: &T) {
+        let words = openvm::serde::to_vec(data).unwrap();
+        let bytes: Vec<u8> = words.into_iter().flat_map(|w| w.to_le_bytes()).collect();
+        self.write_frame(&bytes);
+    }
+
     pub fn add_key_value(&mut self, key: Vec<u8>, value: Vec<u8>) {
         self.kv_store.insert(key, value);
     }
+
+    /// Registers `hint` under `key`, to be read by the guest with `openvm::io::hint_by_key`, in
+    /// any order. Unlike [StdIn::add_key_value], the value is stored as is.
+    pub fn add_keyed_hint(&mut self, key: &[u8], hint: &[u8]) {
+        self.kv_store.insert(key.to_vec(), hint.to_vec());
+    }
+
+    /// Supplies the seed of the `getrandom-seeded` backend of the guest, which expands it into the
+    /// bytes returned by `getrandom`, see `openvm::getrandom`.
+    pub fn add_getrandom_seed(&mut self, seed: [u8; openvm::getrandom::SEED_SIZE]) {
+        self.add_keyed_hint(openvm::getrandom::SEED_KEY, &seed);
+    }
+
+    /// Replays `transcript` through the channels of the guest, see
+    /// [Sdk::execute_interactive](crate::Sdk::execute_interactive).
+    pub fn with_channel_transcript(mut self, transcript: ChannelTranscript) -> Self {
+        self.channel_transcript = transcript;
+        self
+    }
+
+    /// SHA-256 digest of the input streams, key-value store and channel transcript, which
+    /// identifies the input of a proof.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update((self.buffer.len() as u64).to_le_bytes());
+        for stream in &self.buffer {
+            hasher.update((stream.len() as u64).to_le_bytes());
+            for f in stream {
+                hasher.update(f.as_canonical_u32().to_le_bytes());
+            }
+        }
+        // The key-value store is hashed in the order of its keys to be deterministic.
+        let kv_store: BTreeMap<_, _> = self.kv_store.iter().collect();
+        hasher.update((kv_store.len() as u64).to_le_bytes());
+        for (key, value) in kv_store {
+            hasher.update((key.len() as u64).to_le_bytes());
+            hasher.update(key);
+            hasher.update((value.len() as u64).to_le_bytes());
+            hasher.update(value);
+        }
+        // Inputs without channel messages keep the digest they had before channels existed.
+        if !self.channel_transcript.is_empty() {
+            hasher.update(bitcode::serialize(&self.channel_transcript).unwrap());
+        }
+        hasher.finalize().into()
+    }
+}
+
+impl InputWrite for StdIn {
+    fn write<T: Serialize>(&mut self, value: &T) {
+        StdIn::write(self, value);
+    }
+}
+
+/// Builds the [StdIn] of a guest program from structs deriving `openvm::io::Inputs`, which the
+/// guest reads back with `openvm::io::read_inputs`. Sharing the struct between the host and the
+/// guest guarantees that the inputs are written in the order the guest reads them.
+#[derive(Default)]
+pub struct WitnessBuilder {
+    stdin: StdIn,
+}
+
+impl WitnessBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the fields of `inputs` as consecutive input streams, in declaration order.
+    pub fn inputs<T: Inputs>(mut self, inputs: &T) -> Self {
+        inputs.write_fields(&mut self.stdin);
+        self
+    }
+
+    /// Registers a keyed hint, see [StdIn::add_keyed_hint]. Keyed hints are not ordered, so they
+    /// can be added at any point.
+    pub fn keyed_hint(mut self, key: &[u8], hint: &[u8]) -> Self {
+        self.stdin.add_keyed_hint(key, hint);
+        self
+    }
+
+    pub fn build(self) -> StdIn {
+        self.stdin
+    }
+}
+
+impl From<WitnessBuilder> for StdIn {
+    fn from(builder: WitnessBuilder) -> Self {
+        builder.build()
+    }
 }
 
 impl From<StdIn> for Streams<F> {
@@ -53,6 +180,7 @@ impl From<StdIn> for Streams<F> {
         }
         let mut ret = Streams::new(data);
         ret.kv_store = Arc::new(std_in.kv_store);
+        ret.channels = HostChannels::replay(std_in.channel_transcript, 0);
         ret
     }
 }
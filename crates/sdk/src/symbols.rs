@@ -0,0 +1,165 @@
+//! The `.ovmsym` symbol sidecar of an executable.
+//!
+//! A sidecar holds the function symbols of the ELF an executable was transpiled from, so that the
+//! profiler can attribute cycles to functions and execution errors can be symbolized even when
+//! the ELF has been stripped. It is tied to the executable by its exe commit, and to the
+//! unstripped ELF, which holds the full debug info for the debugger, by the digest of its DWARF
+//! line mapping.
+
+use eyre::{bail, Result};
+use openvm_circuit::arch::{
+    instructions::exe::{FnBound, FnBounds, SourceMap, VmExe},
+    MemoryConfig,
+};
+use openvm_native_compiler::ir::DIGEST_SIZE;
+use openvm_stark_backend::p3_field::PrimeField32;
+use openvm_stark_sdk::config::FriParameters;
+use openvm_transpiler::elf::{decode_fn_bounds, decode_section};
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    commit::{commit_app_exe, CommitBytes},
+    F, OPENVM_VERSION,
+};
+
+/// File extension of symbol sidecars.
+pub const SYMBOL_SIDECAR_EXTENSION: &str = "ovmsym";
+/// The DWARF section mapping pcs to source lines.
+pub const DEBUG_LINE_SECTION: &str = ".debug_line";
+
+/// The contents of a `.ovmsym` file.
+#[serde_as]
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SymbolSidecar {
+    /// The version of OpenVM which produced the sidecar.
+    pub version: String,
+    /// The exe commit of the executable, as in
+    /// [AppExecutionCommit::app_exe_commit](crate::commit::AppExecutionCommit::app_exe_commit).
+    pub app_exe_commit: CommitBytes,
+    /// The functions of the program, keyed by start address.
+    pub fn_bounds: FnBounds,
+    /// SHA-256 digest of the [DEBUG_LINE_SECTION] of the unstripped ELF, if it has one.
+    #[serde_as(as = "Option<serde_with::hex::Hex>")]
+    pub debug_line_digest: Option<[u8; 32]>,
+}
+
+impl SymbolSidecar {
+    /// Creates the sidecar of `exe`, which was transpiled from the unstripped ELF `elf`.
+    pub fn new(
+        elf: &[u8],
+        exe: &VmExe<F>,
+        app_fri_params: FriParameters,
+        memory_config: &MemoryConfig,
+    ) -> Result<Self> {
+        Self::from_elf(elf, app_exe_commit(exe, app_fri_params, memory_config))
+    }
+
+    /// Creates the sidecar of the executable with exe commit `app_exe_commit`, which was
+    /// transpiled from the unstripped ELF `elf`.
+    pub fn from_elf(elf: &[u8], app_exe_commit: CommitBytes) -> Result<Self> {
+        let fn_bounds = decode_fn_bounds(elf)?;
+        if fn_bounds.is_empty() {
+            bail!("ELF has no function symbols, it must be read before it is stripped");
+        }
+        Ok(Self {
+            version: OPENVM_VERSION.to_string(),
+            app_exe_commit,
+            fn_bounds,
+            debug_line_digest: debug_line_digest(elf)?,
+        })
+    }
+
+    /// Returns an error unless the sidecar belongs to the executable with exe commit
+    /// `app_exe_commit`.
+    pub fn check_exe_commit(&self, app_exe_commit: &CommitBytes) -> Result<()> {
+        if self.app_exe_commit.as_slice() != app_exe_commit.as_slice() {
+            bail!(
+                "symbol sidecar is for exe commit {:?}, not {:?}",
+                self.app_exe_commit.to_bn254(),
+                app_exe_commit.to_bn254()
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns an error unless the sidecar belongs to `exe`, see [Self::check_exe_commit].
+    pub fn check_exe(
+        &self,
+        exe: &VmExe<F>,
+        app_fri_params: FriParameters,
+        memory_config: &MemoryConfig,
+    ) -> Result<()> {
+        self.check_exe_commit(&app_exe_commit(exe, app_fri_params, memory_config))
+    }
+
+    /// Returns an error unless `elf` is the unstripped ELF the sidecar was created from, as far as
+    /// its DWARF line mapping is concerned.
+    pub fn check_debug_elf(&self, elf: &[u8]) -> Result<()> {
+        if debug_line_digest(elf)? != self.debug_line_digest {
+            bail!("ELF does not match the line mapping of the symbol sidecar");
+        }
+        Ok(())
+    }
+
+    /// Sets the function bounds of `exe`, used by the profiler, to the symbols of the sidecar.
+    /// The function bounds are not part of the exe commit, so [Self::check_exe] should be called
+    /// first.
+    pub fn apply_to_exe(&self, exe: &mut VmExe<F>) {
+        exe.fn_bounds = self.fn_bounds.clone();
+    }
+
+    /// Returns the function containing `pc`, if any.
+    pub fn function_at(&self, pc: u32) -> Option<&FnBound> {
+        function_at(&self.fn_bounds, pc)
+    }
+
+    /// See [symbolize].
+    pub fn symbolize(&self, pc: u32) -> String {
+        symbolize(&self.fn_bounds, pc)
+    }
+}
+
+/// The exe commit of `exe`, which ties sidecars to it.
+fn app_exe_commit(
+    exe: &VmExe<F>,
+    app_fri_params: FriParameters,
+    memory_config: &MemoryConfig,
+) -> CommitBytes {
+    let committed_exe = commit_app_exe(app_fri_params, exe.clone());
+    let exe_commit: [F; DIGEST_SIZE] = committed_exe.compute_exe_commit(memory_config).into();
+    CommitBytes::from_u32_digest(&exe_commit.map(|x| x.as_canonical_u32()))
+}
+
+/// Returns the function of `fn_bounds` containing `pc`, if any.
+pub fn function_at(fn_bounds: &FnBounds, pc: u32) -> Option<&FnBound> {
+    fn_bounds
+        .range(..=pc)
+        .next_back()
+        .map(|(_, bound)| bound)
+        .filter(|bound| pc <= bound.end)
+}
+
+/// Formats `pc` as the function of `fn_bounds` containing it and the offset into it, e.g.
+/// `main+0x1c`, or as a plain address if no function contains it.
+pub fn symbolize(fn_bounds: &FnBounds, pc: u32) -> String {
+    match function_at(fn_bounds, pc) {
+        Some(bound) => format!("{}+{:#x}", bound.name, pc - bound.start),
+        None => format!("{pc:#010x}"),
+    }
+}
+
+/// Like [symbolize], followed by the source location of `pc` in `source_map` if it is known, e.g.
+/// `main+0x1c at src/main.rs:12`.
+pub fn symbolize_with_source(fn_bounds: &FnBounds, source_map: &SourceMap, pc: u32) -> String {
+    let symbol = symbolize(fn_bounds, pc);
+    match source_map.location(pc) {
+        Some((file, line)) => format!("{symbol} at {file}:{line}"),
+        None => symbol,
+    }
+}
+
+fn debug_line_digest(elf: &[u8]) -> Result<Option<[u8; 32]>> {
+    Ok(decode_section(elf, DEBUG_LINE_SECTION)?.map(|data| Sha256::digest(data).into()))
+}
@@ -1,4 +1,4 @@
-use std::io::Cursor;
+use std::{collections::BTreeMap, io::Cursor, time::Duration};
 
 use eyre::Result;
 use openvm_continuations::{verifier::internal::types::VmStarkProof, SC};
@@ -17,6 +17,7 @@ use {
 use crate::{
     codec::{decode_vec, encode_slice, Decode, Encode},
     commit::AppExecutionCommit,
+    OPENVM_VERSION,
 };
 
 /// Number of bytes in a Bn254Fr.
@@ -27,6 +28,60 @@ pub const NUM_BN254_ACCUMULATOR: usize = 12;
 #[cfg(feature = "evm-prove")]
 const NUM_BN254_PROOF: usize = 43;
 
+/// Operational data attached to a proof, such as the prover version, proving time and machine
+/// information. It is not committed to by the proof and is ignored by verification, so it must not
+/// be trusted.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProofMetadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prover_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proving_time_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub machine: Option<MachineInfo>,
+    /// Free-form annotations.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub annotations: BTreeMap<String, String>,
+}
+
+impl ProofMetadata {
+    /// Metadata with the current OpenVM version and machine information.
+    pub fn current() -> Self {
+        Self {
+            prover_version: Some(OPENVM_VERSION.to_string()),
+            machine: Some(MachineInfo::current()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_proving_time(mut self, proving_time: Duration) -> Self {
+        self.proving_time_ms = Some(proving_time.as_millis() as u64);
+        self
+    }
+
+    pub fn with_annotation(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.annotations.insert(key.into(), value.into());
+        self
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MachineInfo {
+    pub os: String,
+    pub arch: String,
+    pub num_cpus: usize,
+}
+
+impl MachineInfo {
+    pub fn current() -> Self {
+        Self {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            num_cpus: std::thread::available_parallelism().map_or(1, |n| n.get()),
+        }
+    }
+}
+
 #[cfg(feature = "evm-prove")]
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct EvmHalo2Verifier {
@@ -60,6 +115,9 @@ pub struct EvmProof {
     pub user_public_values: Vec<u8>,
     /// The concatenation of `accumulator` and `proof`.
     pub proof_data: ProofData,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    /// Non-committed metadata, ignored by verification.
+    pub metadata: Option<ProofMetadata>,
 }
 
 #[cfg(feature = "evm-prove")]
@@ -88,6 +146,7 @@ impl EvmProof {
             user_public_values,
             app_commit,
             proof_data,
+            ..
         } = self;
 
         let ProofData { accumulator, proof } = proof_data;
@@ -157,6 +216,7 @@ impl TryFrom<RawEvmProof> for EvmProof {
                 accumulator: evm_accumulator,
                 proof,
             },
+            metadata: None,
         })
     }
 }
@@ -169,6 +229,7 @@ impl TryFrom<EvmProof> for RawEvmProof {
             mut app_commit,
             user_public_values,
             proof_data,
+            ..
         } = evm_openvm_proof;
 
         app_commit.app_exe_commit.reverse();
@@ -224,6 +285,9 @@ pub struct VmStarkProofBytes {
     pub user_public_values: Vec<u8>,
     #[serde_as(as = "serde_with::hex::Hex")]
     pub proof: Vec<u8>,
+    /// Non-committed metadata, ignored by verification.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<ProofMetadata>,
 }
 
 impl VmStarkProofBytes {
@@ -234,8 +298,14 @@ impl VmStarkProofBytes {
             app_commit,
             user_public_values,
             proof: proof.proof.encode_to_vec()?,
+            metadata: None,
         })
     }
+
+    pub fn with_metadata(mut self, metadata: ProofMetadata) -> Self {
+        self.metadata = Some(metadata);
+        self
+    }
 }
 
 impl TryFrom<VmStarkProofBytes> for VmStarkProof<SC> {
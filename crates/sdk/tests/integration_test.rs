@@ -1,11 +1,13 @@
-use std::{borrow::Borrow, path::PathBuf, sync::Arc};
+use std::{borrow::Borrow, convert::Infallible, path::PathBuf, sync::Arc, time::Duration};
 
 use eyre::Result;
 use openvm_build::GuestOptions;
 use openvm_circuit::{
     arch::{
-        hasher::poseidon2::vm_poseidon2_hasher, ContinuationVmProof, ExecutionError,
-        GenerationError, SingleSegmentVmExecutor, SystemConfig, VmConfig, VmExecutor,
+        hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
+        instructions::{exe::VmExe, program::Program},
+        ContinuationVmProof, ExecutionError, GenerationError, SingleSegmentVmExecutor,
+        SystemConfig, VmConfig, VmExecutor,
     },
     system::{memory::tree::public_values::UserPublicValuesProof, program::trace::VmCommittedExe},
 };
@@ -21,18 +23,34 @@ use openvm_rv32im_transpiler::{
 };
 use openvm_sdk::{
     codec::{Decode, Encode},
-    config::{AggStarkConfig, AppConfig, SdkSystemConfig, SdkVmConfig},
+    commit::{commit_app_exe, commit_user_public_values, AppExecutionCommit, CommitBytes},
+    config::{
+        check_config_digest, AggStarkConfig, AppConfig, ProverPerfConfig, PublicValuesHasher,
+        SdkSystemConfig, SdkVmConfig,
+    },
     keygen::AppProvingKey,
-    Sdk, StdIn,
+    proof_lib::{generate_openvm_proof_lib, PublicValuesSchema},
+    prover::{
+        distributed::{CombineError, SegmentProof, SegmentProvingTask},
+        vm::local::SegmentHooks,
+        AppProver, ProvingCheckpoint, ProvingError, ProvingProgress,
+    },
+    receipt::{commit_stdin, Receipt, ReceiptClaim},
+    symbols::SymbolSidecar,
+    types::ProofMetadata,
+    Sdk, StdIn, OPENVM_VERSION,
 };
-use openvm_stark_backend::{keygen::types::LinearConstraint, p3_matrix::Matrix};
+use openvm_stark_backend::{keygen::types::LinearConstraint, p3_matrix::Matrix, proof::Proof};
 use openvm_stark_sdk::{
     config::{
         baby_bear_poseidon2::{BabyBearPoseidon2Config, BabyBearPoseidon2Engine},
         setup_tracing, FriParameters,
     },
     engine::{StarkEngine, StarkFriEngine},
-    openvm_stark_backend::{p3_field::FieldAlgebra, Chip},
+    openvm_stark_backend::{
+        p3_field::{FieldAlgebra, PrimeField32},
+        Chip,
+    },
     p3_baby_bear::BabyBear,
 };
 use openvm_transpiler::transpiler::Transpiler;
@@ -55,7 +73,6 @@ use {
         vars::StarkProofVariable,
     },
     openvm_sdk::{
-        commit::AppExecutionCommit,
         config::{AggConfig, Halo2Config},
         types::{EvmHalo2Verifier, EvmProof},
         DefaultStaticVerifierPvHandler,
@@ -112,25 +129,25 @@ where
     Ok(runtime_pvs)
 }
 
+fn fibonacci_program(n: usize) -> Program<F> {
+    let mut builder = Builder::<C>::default();
+    let a: Felt<F> = builder.eval(F::ZERO);
+    let b: Felt<F> = builder.eval(F::ONE);
+    let c: Felt<F> = builder.uninit();
+    builder.range(0, n).for_each(|_, builder| {
+        builder.assign(&c, a + b);
+        builder.assign(&a, b);
+        builder.assign(&b, c);
+    });
+    builder.halt();
+    builder.compile_isa()
+}
+
 fn app_committed_exe_for_test(app_log_blowup: usize) -> Arc<VmCommittedExe<SC>> {
-    let program = {
-        let n = 200;
-        let mut builder = Builder::<C>::default();
-        let a: Felt<F> = builder.eval(F::ZERO);
-        let b: Felt<F> = builder.eval(F::ONE);
-        let c: Felt<F> = builder.uninit();
-        builder.range(0, n).for_each(|_, builder| {
-            builder.assign(&c, a + b);
-            builder.assign(&a, b);
-            builder.assign(&b, c);
-        });
-        builder.halt();
-        builder.compile_isa()
-    };
     Sdk::new()
         .commit_app_exe(
             FriParameters::new_for_testing(app_log_blowup),
-            program.into(),
+            fibonacci_program(200).into(),
         )
         .unwrap()
 }
@@ -177,6 +194,8 @@ fn small_test_app_config(app_log_blowup: usize) -> AppConfig<NativeConfig> {
             enable_cycle_tracker: true,
             ..Default::default()
         },
+        public_values_hasher: Default::default(),
+        expose_config_digest: false,
     }
 }
 
@@ -568,6 +587,459 @@ fn test_inner_proof_codec_roundtrip() -> eyre::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_receipt_input_commit_and_serde() -> eyre::Result<()> {
+    let mut stdin = StdIn::default();
+    stdin.write(&[1u32, 2, 3]);
+    stdin.add_key_value(b"key".to_vec(), b"value".to_vec());
+
+    let receipt = Receipt {
+        claim: ReceiptClaim {
+            app_commit: AppExecutionCommit::from_field_commit(
+                [BabyBear::ONE; DIGEST_SIZE],
+                [BabyBear::TWO; DIGEST_SIZE],
+            ),
+            input_commit: commit_stdin(&stdin),
+        },
+        journal: vec![0, 1, 2, 255],
+        proof: vec![],
+        metadata: None,
+    };
+    receipt.verify_input(&stdin)?;
+
+    // Moving a buffer into the key-value store must change the commitment.
+    let mut other = StdIn::default();
+    other.write(&[1u32, 2, 3]);
+    other.write_bytes(b"key");
+    other.write_bytes(b"value");
+    assert!(receipt.verify_input(&other).is_err());
+    assert!(receipt.verify_input(&StdIn::default()).is_err());
+
+    let decoded: Receipt = serde_json::from_slice(&serde_json::to_vec(&receipt)?)?;
+    decoded.verify_input(&stdin)?;
+    assert!(decoded.metadata.is_none());
+    assert_eq!(decoded.journal, receipt.journal);
+    assert_eq!(
+        decoded.claim.app_commit.app_exe_commit.as_slice(),
+        receipt.claim.app_commit.app_exe_commit.as_slice()
+    );
+    assert_eq!(
+        decoded.claim.app_commit.app_vm_commit.as_slice(),
+        receipt.claim.app_commit.app_vm_commit.as_slice()
+    );
+
+    // Metadata round trips but does not affect the claim.
+    let metadata = ProofMetadata::current()
+        .with_proving_time(Duration::from_millis(1234))
+        .with_annotation("fleet", "test");
+    let annotated = receipt.clone().with_metadata(metadata.clone());
+    let decoded: Receipt = serde_json::from_slice(&serde_json::to_vec(&annotated)?)?;
+    assert_eq!(decoded.metadata, Some(metadata));
+    decoded.verify_input(&stdin)?;
+    Ok(())
+}
+
+#[test]
+fn test_receipt_verify_published() -> eyre::Result<()> {
+    let mut journal = vec![0u8; openvm::io::PUBLISHED_OFFSET];
+    journal.extend(openvm::io::published_digest([
+        b"hello ".as_slice(),
+        b"world",
+    ]));
+    let receipt = Receipt {
+        claim: ReceiptClaim {
+            app_commit: AppExecutionCommit::from_field_commit(
+                [BabyBear::ONE; DIGEST_SIZE],
+                [BabyBear::TWO; DIGEST_SIZE],
+            ),
+            input_commit: commit_stdin(&StdIn::default()),
+        },
+        journal,
+        proof: vec![],
+        metadata: None,
+    };
+    receipt.verify_published([b"hello ".as_slice(), b"world"])?;
+    // The commitment binds how the data was split into calls.
+    assert!(receipt.verify_published([b"hello world"]).is_err());
+
+    let truncated = Receipt {
+        journal: receipt.journal[..openvm::io::PUBLISHED_OFFSET].to_vec(),
+        ..receipt
+    };
+    assert!(truncated
+        .verify_published([b"hello ".as_slice(), b"world"])
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_commit_user_public_values() -> eyre::Result<()> {
+    // Same test vectors as the EVM verifier
+    let pvs: Vec<_> = (0..40).map(BabyBear::from_canonical_u32).collect();
+    assert_eq!(
+        hex::encode(commit_user_public_values(
+            PublicValuesHasher::Keccak256,
+            &pvs
+        )?),
+        "b36bb36dcacc3ecfc882ed1f75615b0cfbec2a196541bd7544032b519aca417e"
+    );
+    assert_eq!(
+        hex::encode(commit_user_public_values(PublicValuesHasher::Sha256, &pvs)?),
+        "219d00b7ab7f3ab3d8faa7e68fa4589c3326f811e978798b85184466e448af74"
+    );
+
+    let pvs: Vec<_> = (0..NUM_PUB_VALUES as u32)
+        .map(|i| BabyBear::from_canonical_u32(i * 1000))
+        .collect();
+    let root = vm_poseidon2_hasher().merkle_root(&pvs);
+    assert_eq!(
+        &commit_user_public_values(PublicValuesHasher::Poseidon2, &pvs)?,
+        CommitBytes::from_u32_digest(&root.map(|x| x.as_canonical_u32())).as_slice()
+    );
+    // Only bytes can be hashed with Keccak256 and SHA-256
+    assert!(commit_user_public_values(PublicValuesHasher::Keccak256, &pvs).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_generate_openvm_proof_lib() -> eyre::Result<()> {
+    let app_commit = AppExecutionCommit {
+        app_exe_commit: CommitBytes::new([0x11; 32]),
+        app_vm_commit: CommitBytes::new([0x22; 32]),
+    };
+    let schema: PublicValuesSchema = serde_json::from_str(
+        r#"[
+            { "name": "digest", "type": "bytes32" },
+            { "name": "count", "type": "uint32" },
+            { "name": "delta", "type": "int64" },
+            { "name": "owner", "type": "address" },
+            { "name": "ok", "type": "bool" }
+        ]"#,
+    )?;
+    let proof_lib = generate_openvm_proof_lib(&schema, &app_commit)?;
+    for line in [
+        &format!("APP_EXE_COMMIT = 0x{}", "11".repeat(32)),
+        &format!("APP_VM_COMMIT = 0x{}", "22".repeat(32)),
+        "PUBLIC_VALUES_SIZE = 65;",
+        "        uint32 count;\n        int64 delta;\n",
+        "pvs.digest = bytes32(publicValues[0:32]);",
+        "pvs.count = uint32(_readUintLe(publicValues, 32, 4));",
+        "pvs.delta = int64(uint64(_readUintLe(publicValues, 36, 8)));",
+        "pvs.owner = address(bytes20(publicValues[44:64]));",
+        "pvs.ok = _readBool(publicValues, 64);",
+    ] {
+        assert!(proof_lib.contains(line), "missing {line:?}");
+    }
+    // All placeholders of the template are filled in
+    assert!(!proof_lib.contains("{APP_") && !proof_lib.contains("_FIELDS}"));
+
+    for invalid in [
+        r#"[]"#,
+        r#"[{ "name": "x", "type": "uint7" }]"#,
+        r#"[{ "name": "x", "type": "bytes" }]"#,
+        r#"[{ "name": "x", "type": "uint256[]" }]"#,
+        r#"[{ "name": "1x", "type": "bool" }]"#,
+        r#"[{ "name": "x", "type": "bool" }, { "name": "x", "type": "bool" }]"#,
+    ] {
+        let schema: PublicValuesSchema = serde_json::from_str(invalid)?;
+        assert!(generate_openvm_proof_lib(&schema, &app_commit).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_agg_stark_config_for_app() {
+    let app_config = small_test_app_config(1).with_num_public_values(4 * NUM_PUB_VALUES);
+    let agg_stark_config = AggStarkConfig::for_app(&app_config);
+    assert_eq!(
+        agg_stark_config.max_num_user_public_values,
+        app_config.num_public_values()
+    );
+    assert_eq!(
+        agg_stark_config.leaf_fri_params,
+        app_config.leaf_fri_params.fri_params
+    );
+    assert_eq!(
+        agg_stark_config
+            .root_verifier_vm_config()
+            .system
+            .num_public_values,
+        2 * DIGEST_SIZE + 4 * NUM_PUB_VALUES
+    );
+}
+
+#[test]
+#[should_panic(expected = "num_public_values must be")]
+fn test_app_keygen_invalid_num_public_values() {
+    AppProvingKey::keygen(small_test_app_config(1).with_num_public_values(3 * DIGEST_SIZE));
+}
+
+#[test]
+fn test_check_config_digest() -> Result<()> {
+    let config_digest = [7; 32];
+    let mut user_public_values = vec![F::ZERO; 64];
+    for (value, byte) in user_public_values[32..].iter_mut().zip(config_digest) {
+        *value = F::from_canonical_u8(byte);
+    }
+    check_config_digest(&config_digest, &user_public_values)?;
+    assert!(check_config_digest(&config_digest, &user_public_values[..16]).is_err());
+    // The program overwrote a byte of the digest.
+    user_public_values[40] = F::ZERO;
+    assert!(check_config_digest(&config_digest, &user_public_values).is_err());
+
+    // The verification of app proofs checks the digest of the verifying key.
+    let app_pk = Arc::new(AppProvingKey::keygen(small_test_app_config(1)));
+    let sdk = Sdk::new();
+    let proof = sdk.generate_app_proof(
+        app_pk.clone(),
+        app_committed_exe_for_test(1),
+        StdIn::default(),
+    )?;
+    let mut app_vk = app_pk.get_app_vk();
+    sdk.verify_app_proof(&app_vk, &proof)?;
+    app_vk.config_digest = Some(config_digest);
+    assert!(sdk.verify_app_proof(&app_vk, &proof).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_app_keygen_cached() -> Result<()> {
+    let cache_dir = tempfile::tempdir()?;
+    let sdk = Sdk::new().with_cache_dir(cache_dir.path());
+    let vk = |app_pk: &AppProvingKey<NativeConfig>| serde_json::to_vec(&app_pk.get_app_vk());
+
+    let app_pk = sdk.app_keygen_cached(small_test_app_config(1))?;
+    let cached: Vec<_> = std::fs::read_dir(cache_dir.path())?.collect::<Result<_, _>>()?;
+    assert_eq!(cached.len(), 1);
+    let cached_app_pk = sdk.app_keygen_cached(small_test_app_config(1))?;
+    assert_eq!(vk(&cached_app_pk)?, vk(&app_pk)?);
+
+    // A different config gets its own key, and a corrupted key is generated again.
+    sdk.app_keygen_cached(small_test_app_config(2))?;
+    assert_eq!(std::fs::read_dir(cache_dir.path())?.count(), 2);
+    let mut entry = std::fs::read(cached[0].path())?;
+    *entry.last_mut().unwrap() ^= 1;
+    std::fs::write(cached[0].path(), &entry)?;
+    let regenerated_app_pk = sdk.app_keygen_cached(small_test_app_config(1))?;
+    assert_eq!(vk(&regenerated_app_pk)?, vk(&app_pk)?);
+    assert_ne!(std::fs::read(cached[0].path())?, entry);
+    Ok(())
+}
+
+#[test]
+fn test_commit_app_exe_cached() -> Result<()> {
+    let cache_dir = tempfile::tempdir()?;
+    let sdk = Sdk::new().with_cache_dir(cache_dir.path());
+    let committed_exe = app_committed_exe_for_test(1);
+    let fri_params = FriParameters::new_for_testing(1);
+
+    let commit = |committed_exe: Arc<VmCommittedExe<SC>>| committed_exe.get_program_commit();
+    let exe = committed_exe.exe.clone();
+    let first = sdk.commit_app_exe_cached(fri_params, exe.clone())?;
+    let cached = sdk.commit_app_exe_cached(fri_params, exe)?;
+    assert_eq!(commit(first), commit(committed_exe.clone()));
+    assert_eq!(commit(cached), commit(committed_exe));
+    assert_eq!(std::fs::read_dir(cache_dir.path())?.count(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_generate_app_proof_async() -> Result<()> {
+    let app_pk = Arc::new(AppProvingKey::keygen(small_test_app_config(1)));
+    let app_committed_exe = app_committed_exe_for_test(1);
+    let sdk = Sdk::new();
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+
+    let (task, mut progress) =
+        sdk.generate_app_proof_async(app_pk.clone(), app_committed_exe.clone(), StdIn::default());
+    let proof = runtime.block_on(task)?;
+    let num_segments = proof.per_segment.len();
+    assert!(num_segments > 2);
+    let expected: Vec<_> = (0..num_segments)
+        .map(|segment| ProvingProgress::SegmentProven { segment })
+        .chain([ProvingProgress::AppProofGenerated { num_segments }])
+        .collect();
+    let reported: Vec<_> = std::iter::from_fn(|| progress.try_recv().ok()).collect();
+    assert_eq!(reported, expected);
+    sdk.verify_app_proof(&app_pk.get_app_vk(), &proof)?;
+
+    // Proving stops at the first segment proven after the task is cancelled.
+    let (task, mut progress) =
+        sdk.generate_app_proof_async(app_pk, app_committed_exe, StdIn::default());
+    assert_eq!(
+        progress.blocking_recv(),
+        Some(ProvingProgress::SegmentProven { segment: 0 })
+    );
+    task.cancel_handle().cancel();
+    assert_eq!(runtime.block_on(task).err(), Some(ProvingError::Cancelled));
+    let remaining = std::iter::from_fn(|| progress.blocking_recv()).count();
+    assert!(remaining < num_segments - 1);
+    Ok(())
+}
+
+#[test]
+fn test_app_proof_checkpoint() -> Result<()> {
+    let app_pk = Arc::new(AppProvingKey::keygen(small_test_app_config(1)));
+    let app_committed_exe = app_committed_exe_for_test(1);
+    let sdk = Sdk::new();
+    let checkpoint_dir = tempfile::tempdir()?;
+    let open_checkpoint = || {
+        let (checkpoint, _) = ProvingCheckpoint::resume(
+            checkpoint_dir.path(),
+            &app_committed_exe.exe,
+            &app_pk.app_vm_pk.vm_config,
+            app_pk.app_vm_pk.fri_params,
+        )?;
+        eyre::Ok(checkpoint)
+    };
+
+    let proof = sdk.generate_app_proof_with_checkpoint(
+        app_pk.clone(),
+        app_committed_exe.clone(),
+        StdIn::default(),
+        checkpoint_dir.path(),
+    )?;
+    let num_segments = proof.per_segment.len();
+    assert!(num_segments > 2);
+    assert_eq!(open_checkpoint()?.num_proven_segments(), num_segments);
+
+    // Simulate a prover killed while writing the second segment.
+    for segment in 2..num_segments {
+        std::fs::remove_file(checkpoint_dir.path().join(format!("segment-{segment}")))?;
+    }
+    std::fs::write(checkpoint_dir.path().join("segment-1"), b"truncated")?;
+    assert_eq!(open_checkpoint()?.num_proven_segments(), 1);
+
+    let resumed = sdk.resume_app_proof(
+        app_pk.clone(),
+        app_committed_exe.clone(),
+        checkpoint_dir.path(),
+    )?;
+    assert_eq!(resumed.per_segment.len(), num_segments);
+    assert_eq!(open_checkpoint()?.num_proven_segments(), num_segments);
+    sdk.verify_app_proof(&app_pk.get_app_vk(), &resumed)?;
+
+    // Another input does not reuse the segments proven for the first one.
+    let mut input = StdIn::default();
+    input.write(&1u32);
+    let checkpoint = ProvingCheckpoint::open(
+        checkpoint_dir.path(),
+        &app_committed_exe.exe,
+        &app_pk.app_vm_pk.vm_config,
+        app_pk.app_vm_pk.fri_params,
+        &input,
+    )?;
+    assert_eq!(checkpoint.num_proven_segments(), 0);
+
+    let empty_dir = tempfile::tempdir()?;
+    assert!(sdk
+        .resume_app_proof(app_pk, app_committed_exe, empty_dir.path())
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_distributed_segment_proving() -> Result<()> {
+    let app_pk = Arc::new(AppProvingKey::keygen(small_test_app_config(1)));
+    let app_committed_exe = app_committed_exe_for_test(1);
+    let coordinator = AppProver::<_, BabyBearPoseidon2Engine>::new(
+        app_pk.app_vm_pk.clone(),
+        app_committed_exe.clone(),
+    );
+    let worker =
+        AppProver::<_, BabyBearPoseidon2Engine>::new(app_pk.app_vm_pk.clone(), app_committed_exe);
+
+    let mut tasks = vec![];
+    let combiner = coordinator.segment_proving_tasks(StdIn::default(), 0, |task| {
+        tasks.push(bitcode::serialize(&task).unwrap())
+    })?;
+    assert!(combiner.num_segments() > 2);
+    assert_eq!(tasks.len(), combiner.num_segments());
+
+    // Workers may finish in any order.
+    let proofs: Vec<SegmentProof> = tasks
+        .iter()
+        .rev()
+        .map(|task| {
+            let task: SegmentProvingTask = bitcode::deserialize(task).unwrap();
+            let proof = worker.prove_segment_task(task).unwrap();
+            bitcode::deserialize(&bitcode::serialize(&proof).unwrap()).unwrap()
+        })
+        .collect();
+    let proof = combiner.combine(proofs)?;
+    Sdk::new().verify_app_proof(&app_pk.get_app_vk(), &proof)?;
+
+    let combiner = coordinator.segment_proving_tasks(StdIn::default(), 0, |_| {})?;
+    assert_eq!(combiner.combine([]).err(), Some(CombineError::Missing(0)));
+    Ok(())
+}
+
+/// [SegmentHooks] which record the largest number of segments started but not yet proven.
+#[derive(Default)]
+struct MaxInFlight {
+    started: usize,
+    proven: usize,
+    max: usize,
+}
+
+impl SegmentHooks<SC> for MaxInFlight {
+    type Error = Infallible;
+
+    fn proven_segment(&mut self, _: usize) -> Option<Proof<SC>> {
+        self.started += 1;
+        None
+    }
+
+    fn on_segment_proven(&mut self, _: usize, _: &Proof<SC>) -> Result<(), Infallible> {
+        self.max = self.max.max(self.started - self.proven);
+        self.proven += 1;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_concurrent_app_proof() -> Result<()> {
+    let app_pk = Arc::new(AppProvingKey::keygen(small_test_app_config(1)));
+    let app_committed_exe = app_committed_exe_for_test(1);
+    let app_prover = |perf_config| {
+        AppProver::<_, BabyBearPoseidon2Engine>::new(
+            app_pk.app_vm_pk.clone(),
+            app_committed_exe.clone(),
+        )
+        .with_perf_config(perf_config)
+    };
+    let concurrent = ProverPerfConfig {
+        max_segment_parallelism: 4,
+        ..Default::default()
+    };
+
+    let sequential_proof =
+        app_prover(ProverPerfConfig::default()).generate_app_proof(StdIn::default());
+    let concurrent_proof = app_prover(concurrent).generate_app_proof(StdIn::default());
+    assert!(sequential_proof.per_segment.len() > 2);
+    assert_eq!(
+        bitcode::serialize(&concurrent_proof)?,
+        bitcode::serialize(&sequential_proof)?
+    );
+    Sdk::new().verify_app_proof(&app_pk.get_app_vk(), &concurrent_proof)?;
+
+    // A budget below the memory of any segment allows a single segment in flight, besides the
+    // one waiting for its trace to be generated.
+    let mut hooks = MaxInFlight::default();
+    let Ok(budgeted_proof) = app_prover(ProverPerfConfig {
+        memory_budget: Some(1),
+        ..concurrent
+    })
+    .app_prover()
+    .prove_with_hooks(StdIn::default(), &mut hooks);
+    assert_eq!(hooks.max, 2);
+    assert_eq!(
+        bitcode::serialize(&budgeted_proof)?,
+        bitcode::serialize(&sequential_proof)?
+    );
+    Ok(())
+}
+
 #[test]
 fn test_segmentation_retry() {
     setup_tracing();
@@ -623,3 +1095,41 @@ fn test_segmentation_retry() {
         .sum();
     assert!(new_total_height < total_height);
 }
+
+#[test]
+fn test_smoke_test() {
+    let exe = app_committed_exe_for_test(1).exe.clone();
+    let sdk = Sdk::new();
+    sdk.smoke_test(
+        exe.clone(),
+        NativeConfig::new(SystemConfig::default().with_continuations(), Native),
+    )
+    .unwrap();
+
+    let err = sdk
+        .smoke_test(exe, small_test_app_config(1).app_vm_config)
+        .unwrap_err();
+    assert!(err.to_string().contains("single segment"));
+}
+
+#[test]
+fn test_symbol_sidecar_check_exe() {
+    let fri_params = FriParameters::new_for_testing(1);
+    let memory_config = SystemConfig::default().memory_config;
+    let [exe, other_exe] = [200, 100].map(|n| VmExe::from(fibonacci_program(n)));
+    let exe_commit: [F; DIGEST_SIZE] = commit_app_exe(fri_params, exe.clone())
+        .compute_exe_commit(&memory_config)
+        .into();
+    let symbols = SymbolSidecar {
+        version: OPENVM_VERSION.to_string(),
+        app_exe_commit: AppExecutionCommit::from_field_commit(exe_commit, exe_commit)
+            .app_exe_commit,
+        fn_bounds: Default::default(),
+        debug_line_digest: None,
+    };
+    symbols.check_exe(&exe, fri_params, &memory_config).unwrap();
+    // The sidecar of an earlier build is rejected instead of symbolizing the wrong functions.
+    assert!(symbols
+        .check_exe(&other_exe, fri_params, &memory_config)
+        .is_err());
+}
@@ -18,6 +18,13 @@ pub struct GuestOptions {
     pub target_dir: Option<PathBuf>,
     /// Custom options to pass as args to `cargo build`.
     pub options: Vec<String>,
+    /// Whether to strip the sections that are not loaded into memory from the built executables,
+    /// keeping the unstripped executables as sidecar files for the profiler and debugger. See
+    /// [crate::strip_elf].
+    pub strip: bool,
+    /// Stack size of the guest in bytes, passed to the guest as the `OPENVM_STACK_SIZE`
+    /// environment variable. See `openvm_platform::memory::STACK_SIZE`.
+    pub stack_size: Option<u32>,
 }
 
 impl GuestOptions {
@@ -54,6 +61,18 @@ impl GuestOptions {
         self
     }
 
+    /// Strip the built executables. See [GuestOptions::strip].
+    pub fn with_strip(mut self) -> Self {
+        self.strip = true;
+        self
+    }
+
+    /// Set the stack size of the guest in bytes. See [GuestOptions::stack_size].
+    pub fn with_stack_size(mut self, stack_size: u32) -> Self {
+        self.stack_size = Some(stack_size);
+        self
+    }
+
     #[allow(dead_code)]
     pub(crate) fn with_metadata(mut self, metadata: GuestMetadata) -> Self {
         self.rustc_flags = metadata.rustc_flags.unwrap_or_default();
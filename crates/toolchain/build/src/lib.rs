@@ -13,11 +13,14 @@ use std::{
 };
 
 use cargo_metadata::{Metadata, MetadataCommand, Package};
-use openvm_platform::memory;
+use openvm_overlay::OVERLAY_SECTION;
+use openvm_platform::{extension_versions::EXTENSION_VERSIONS_SECTION, memory};
 
-pub use self::config::GuestOptions;
+pub use self::{config::GuestOptions, strip::*, unsupported::*};
 
 mod config;
+mod strip;
+mod unsupported;
 
 /// The rustc compiler [target](https://doc.rust-lang.org/rustc/targets/index.html).
 pub const RUSTC_TARGET: &str = "riscv32im-risc0-zkvm-elf";
@@ -25,6 +28,7 @@ const RUSTUP_TOOLCHAIN_NAME: &str = "nightly-2025-02-14";
 const BUILD_LOCKED_ENV: &str = "OPENVM_BUILD_LOCKED";
 const SKIP_BUILD_ENV: &str = "OPENVM_SKIP_BUILD";
 const GUEST_LOGFILE_ENV: &str = "OPENVM_GUEST_LOGFILE";
+const LINKER_SCRIPT_FILE_NAME: &str = "openvm.ld";
 const ALLOWED_CARGO_ENVS: &[&str] = &["CARGO_HOME"];
 
 /// Returns the given cargo Package from the metadata in the Cargo.toml manifest
@@ -312,6 +316,29 @@ pub(crate) fn encode_rust_flags(rustc_flags: &[&str]) -> String {
     .join("\x1f")
 }
 
+/// Returns a linker script that augments the default layout by placing the `.openvm` section,
+/// which holds data emitted by VM extensions, at [memory::EXTENSION_DATA_START] after all other
+/// program sections. This way the addresses of the program's own data do not depend on the set of
+/// enabled extensions.
+///
+/// The [OVERLAY_SECTION] section, which holds the contents of overlays, and the
+/// [EXTENSION_VERSIONS_SECTION] section are not allocated, so that they are not part of the initial
+/// memory image.
+fn linker_script_contents() -> String {
+    format!(
+        concat!(
+            "SECTIONS\n{{\n",
+            "  .openvm 0x{:08X} : {{ KEEP(*(.openvm)) }}\n",
+            "  {overlay} (INFO) : {{ KEEP(*({overlay})) }}\n",
+            "  {versions} (INFO) : {{ KEEP(*({versions})) }}\n",
+            "}}\nINSERT AFTER .bss;\n",
+        ),
+        memory::EXTENSION_DATA_START,
+        overlay = OVERLAY_SECTION,
+        versions = EXTENSION_VERSIONS_SECTION,
+    )
+}
+
 // HACK: Attempt to bypass the parent cargo output capture and
 // send directly to the tty, if available.  This way we get
 // progress messages from the inner cargo so the user doesn't
@@ -370,9 +397,81 @@ pub fn build_guest_package(
     }
 
     let res = build_generic(&new_opts);
+    if let Ok(path) = &res {
+        for elf_path in package_executables(pkg, path, target_filter) {
+            // The symbols are needed to find the unsupported features, so they are found first.
+            warn_unsupported_std_features(&elf_path);
+            if guest_opts.strip {
+                if let Err(err) = strip_elf(&elf_path) {
+                    tty_println(&format!(
+                        "openvm build: Failed to strip {}: {err}",
+                        elf_path.display()
+                    ));
+                }
+            }
+        }
+    }
     res.map(|path| if example { path.join("examples") } else { path })
 }
 
+/// Returns the paths of the built bin and example executables of `pkg` that match
+/// `target_filter`.
+fn package_executables(
+    pkg: &Package,
+    path: &Path,
+    target_filter: &Option<TargetFilter>,
+) -> Vec<PathBuf> {
+    let mut elf_paths = Vec::new();
+    for target in &pkg.targets {
+        let kind = if target.is_example() {
+            "example"
+        } else if target.is_bin() {
+            "bin"
+        } else {
+            continue;
+        };
+        if let Some(target_filter) = target_filter {
+            if target_filter.kind != kind || target_filter.name != target.name {
+                continue;
+            }
+        }
+        let elf_path = if target.is_example() {
+            path.join("examples")
+        } else {
+            path.to_path_buf()
+        }
+        .join(&target.name);
+        // Targets not selected by the cargo options may never have been built.
+        if elf_path.exists() {
+            elf_paths.push(elf_path);
+        }
+    }
+    elf_paths
+}
+
+/// Warns about the features of the standard library which the ELF at `elf_path` uses but the zkVM
+/// does not support, see [find_unsupported_std_features].
+fn warn_unsupported_std_features(elf_path: &Path) {
+    let features = fs::read(elf_path)
+        .map_err(eyre::Report::from)
+        .and_then(|data| find_unsupported_std_features(&data));
+    match features {
+        Ok(features) => {
+            for feature in features {
+                tty_println(&format!(
+                    "openvm build: WARNING: {} uses a feature of std which fails in the zkVM: \
+                     {feature}",
+                    elf_path.display()
+                ));
+            }
+        }
+        Err(err) => tty_println(&format!(
+            "openvm build: Failed to check {} for unsupported std features: {err}",
+            elf_path.display()
+        )),
+    }
+}
+
 /// Generic wrapper call to cargo build
 pub fn build_generic(guest_opts: &GuestOptions) -> Result<PathBuf, Option<i32>> {
     if is_skip_build() || guest_opts.target_dir.is_none() {
@@ -389,7 +488,11 @@ pub fn build_generic(guest_opts: &GuestOptions) -> Result<PathBuf, Option<i32>>
 
     let target_dir = guest_opts.target_dir.as_ref().unwrap();
     fs::create_dir_all(target_dir).unwrap();
-    let rust_flags: Vec<_> = guest_opts.rustc_flags.iter().map(|s| s.as_str()).collect();
+    let linker_script = target_dir.join(LINKER_SCRIPT_FILE_NAME);
+    fs::write(&linker_script, linker_script_contents()).unwrap();
+    let linker_script_arg = format!("link-arg=-T{}", linker_script.display());
+    let mut rust_flags: Vec<_> = guest_opts.rustc_flags.iter().map(|s| s.as_str()).collect();
+    rust_flags.extend(["-C", &linker_script_arg]);
 
     let mut cmd = cargo_command("build", &rust_flags);
 
@@ -397,6 +500,9 @@ pub fn build_generic(guest_opts: &GuestOptions) -> Result<PathBuf, Option<i32>>
         cmd.args(["--features", guest_opts.features.join(",").as_str()]);
     }
     cmd.args(["--target-dir", target_dir.to_str().unwrap()]);
+    if let Some(stack_size) = guest_opts.stack_size {
+        cmd.env("OPENVM_STACK_SIZE", stack_size.to_string());
+    }
 
     let profile = if let Some(profile) = &guest_opts.profile {
         profile
@@ -0,0 +1,193 @@
+//! Removal of the sections of a guest ELF that are not loaded into memory.
+//!
+//! The transpiler only reads the loaded segments of an ELF, its [OVERLAY_SECTION] and its
+//! [EXTENSION_VERSIONS_SECTION], so the debug info, symbol table and other metadata can be removed
+//! without changing the program. The original ELF is kept next to the stripped one, for the
+//! profiler and debugger.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use eyre::{bail, ensure, Result};
+use openvm_overlay::OVERLAY_SECTION;
+use openvm_platform::extension_versions::EXTENSION_VERSIONS_SECTION;
+
+/// Extension appended to the path of a stripped ELF for the original, unstripped ELF.
+pub const DEBUG_SIDECAR_EXTENSION: &str = "debug";
+
+const EHDR_SIZE: usize = 52;
+const SHDR_SIZE: usize = 40;
+const SHT_NOBITS: u32 = 8;
+const SHF_ALLOC: u32 = 0x2;
+
+/// Strips the ELF at `elf_path` in place, after copying the original to the sidecar path returned
+/// by [debug_sidecar_path], which is also returned.
+///
+/// An ELF that is already stripped, e.g. because cargo did not rebuild it, is left as is, along
+/// with its sidecar.
+pub fn strip_elf(elf_path: impl AsRef<Path>) -> Result<PathBuf> {
+    let elf_path = elf_path.as_ref();
+    let data = fs::read(elf_path)?;
+    let stripped = strip_elf_bytes(&data)?;
+    let sidecar_path = debug_sidecar_path(elf_path);
+    if stripped.len() >= data.len() {
+        return Ok(sidecar_path);
+    }
+    fs::write(&sidecar_path, &data)?;
+    fs::write(elf_path, stripped)?;
+    Ok(sidecar_path)
+}
+
+/// Returns the path at which [strip_elf] keeps the unstripped ELF of `elf_path`.
+pub fn debug_sidecar_path(elf_path: impl AsRef<Path>) -> PathBuf {
+    let mut path = elf_path.as_ref().as_os_str().to_owned();
+    path.push(".");
+    path.push(DEBUG_SIDECAR_EXTENSION);
+    path.into()
+}
+
+/// Returns a copy of the 32-bit little-endian ELF `data` without the sections that are neither
+/// loaded into memory nor the [OVERLAY_SECTION] or [EXTENSION_VERSIONS_SECTION]. The loaded
+/// segments keep their file offsets.
+pub fn strip_elf_bytes(data: &[u8]) -> Result<Vec<u8>> {
+    ensure!(
+        data.len() >= EHDR_SIZE && data[..4] == *b"\x7fELF",
+        "not an ELF file"
+    );
+    ensure!(
+        data[4] == 1 && data[5] == 1,
+        "only 32-bit little-endian ELF files can be stripped"
+    );
+    let phoff = read_u32(data, 28)? as usize;
+    let shoff = read_u32(data, 32)? as usize;
+    let phentsize = read_u16(data, 42)? as usize;
+    let phnum = read_u16(data, 44)? as usize;
+    let shentsize = read_u16(data, 46)? as usize;
+    let shnum = read_u16(data, 48)? as usize;
+    let shstrndx = read_u16(data, 50)? as usize;
+    if shnum == 0 {
+        return Ok(data.to_vec());
+    }
+    ensure!(shentsize == SHDR_SIZE, "unexpected section header size");
+
+    // Everything up to the end of the last segment is kept as is.
+    let mut kept_len = EHDR_SIZE.max(phoff + phnum * phentsize);
+    for i in 0..phnum {
+        let phdr = phoff + i * phentsize;
+        let offset = read_u32(data, phdr + 4)? as usize;
+        let filesz = read_u32(data, phdr + 16)? as usize;
+        kept_len = kept_len.max(offset + filesz);
+    }
+    ensure!(kept_len <= data.len(), "segment exceeds the file");
+
+    let shdrs = (0..shnum)
+        .map(|i| {
+            let start = shoff + i * SHDR_SIZE;
+            data.get(start..start + SHDR_SIZE)
+                .ok_or_else(|| eyre::eyre!("section header exceeds the file"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let Some(shstrtab) = shdrs.get(shstrndx) else {
+        bail!("missing section name table");
+    };
+    let shstrtab = section_data(data, shstrtab)?;
+    let section_name = |shdr: &[u8]| -> Result<&[u8]> {
+        let start = read_u32(shdr, 0)? as usize;
+        let name = shstrtab
+            .get(start..)
+            .ok_or_else(|| eyre::eyre!("section name exceeds the name table"))?;
+        Ok(&name[..name.iter().position(|&b| b == 0).unwrap_or(name.len())])
+    };
+
+    // Section indices in the stripped ELF, where index 0 is the null section.
+    let mut new_indices = vec![0u32; shnum];
+    let mut kept = Vec::new();
+    for (i, shdr) in shdrs.iter().enumerate().skip(1) {
+        if (read_u32(shdr, 8)? & SHF_ALLOC) != 0
+            || section_name(shdr)? == OVERLAY_SECTION.as_bytes()
+            || section_name(shdr)? == EXTENSION_VERSIONS_SECTION.as_bytes()
+        {
+            kept.push(i);
+            new_indices[i] = kept.len() as u32;
+        }
+    }
+
+    let mut output = data[..kept_len].to_vec();
+    // Kept sections outside of the segments are appended after them, followed by the new section
+    // name table and section header table.
+    let mut new_shdrs = vec![[0u8; SHDR_SIZE]];
+    let mut names = vec![0u8];
+    for &i in &kept {
+        let mut shdr: [u8; SHDR_SIZE] = shdrs[i].try_into().unwrap();
+        write_u32(&mut shdr, 0, names.len() as u32);
+        names.extend_from_slice(section_name(shdrs[i])?);
+        names.push(0);
+
+        let offset = read_u32(&shdr, 16)? as usize;
+        let size = read_u32(&shdr, 20)? as usize;
+        if read_u32(&shdr, 4)? != SHT_NOBITS && offset + size > kept_len {
+            let align = (read_u32(&shdr, 32)? as usize).max(1);
+            output.resize(output.len().next_multiple_of(align), 0);
+            write_u32(&mut shdr, 16, output.len() as u32);
+            output.extend_from_slice(section_data(data, shdrs[i])?);
+        }
+        let link = read_u32(&shdr, 24)? as usize;
+        write_u32(&mut shdr, 24, new_indices.get(link).copied().unwrap_or(0));
+        new_shdrs.push(shdr);
+    }
+
+    // The new section name table comes last.
+    let mut shstrtab_shdr = [0u8; SHDR_SIZE];
+    write_u32(&mut shstrtab_shdr, 0, names.len() as u32);
+    names.extend_from_slice(b".shstrtab\0");
+    write_u32(&mut shstrtab_shdr, 4, 3); // SHT_STRTAB
+    write_u32(&mut shstrtab_shdr, 16, output.len() as u32);
+    write_u32(&mut shstrtab_shdr, 20, names.len() as u32);
+    write_u32(&mut shstrtab_shdr, 32, 1);
+    output.extend_from_slice(&names);
+    new_shdrs.push(shstrtab_shdr);
+
+    output.resize(output.len().next_multiple_of(4), 0);
+    let new_shoff = output.len() as u32;
+    for shdr in &new_shdrs {
+        output.extend_from_slice(shdr);
+    }
+    write_u32(&mut output, 32, new_shoff);
+    write_u16(&mut output, 48, new_shdrs.len() as u16);
+    write_u16(&mut output, 50, (new_shdrs.len() - 1) as u16);
+    Ok(output)
+}
+
+fn section_data<'a>(data: &'a [u8], shdr: &[u8]) -> Result<&'a [u8]> {
+    if read_u32(shdr, 4)? == SHT_NOBITS {
+        return Ok(&[]);
+    }
+    let offset = read_u32(shdr, 16)? as usize;
+    let size = read_u32(shdr, 20)? as usize;
+    data.get(offset..offset + size)
+        .ok_or_else(|| eyre::eyre!("section exceeds the file"))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    let bytes = data
+        .get(offset..offset + 2)
+        .ok_or_else(|| eyre::eyre!("unexpected end of ELF"))?;
+    Ok(u16::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    let bytes = data
+        .get(offset..offset + 4)
+        .ok_or_else(|| eyre::eyre!("unexpected end of ELF"))?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn write_u16(data: &mut [u8], offset: usize, value: u16) {
+    data[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32(data: &mut [u8], offset: usize, value: u32) {
+    data[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
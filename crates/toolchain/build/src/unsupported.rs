@@ -0,0 +1,268 @@
+//! Detection of the standard library features which the zkVM target does not support.
+//!
+//! The zkVM target of the standard library implements networking, processes, the file system,
+//! threads and clocks with stubs which fail at runtime, so a guest using them builds fine and only
+//! fails when its execution reaches them. [find_unsupported_std_features] looks up the functions
+//! of these features in the symbol table of a guest ELF, and follows the direct calls to them back
+//! to the crates outside of the standard library which request them.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::{self, Display},
+};
+
+use elf::{
+    abi::{SHF_EXECINSTR, STT_FUNC},
+    endian::LittleEndian,
+    ElfBytes,
+};
+use eyre::Result;
+
+/// Prefixes of the demangled paths of the standard library functions which fail on the zkVM, with
+/// the feature they belong to.
+const UNSUPPORTED_STD_PATHS: &[(&str, &str)] = &[
+    ("std::net::", "networking"),
+    ("std::sys::net::", "networking"),
+    ("std::process::Command::", "processes"),
+    ("std::process::Child::", "processes"),
+    ("std::sys::process::", "processes"),
+    ("std::fs::", "the file system"),
+    ("std::sys::fs::", "the file system"),
+    ("std::thread::spawn", "threads"),
+    ("std::thread::scope", "threads"),
+    ("std::thread::Builder::spawn", "threads"),
+    ("std::time::Instant::now", "clocks"),
+    ("std::time::SystemTime::now", "clocks"),
+];
+
+/// Crates whose functions are followed to their callers, so that the crate requesting a feature is
+/// reported instead of the standard library.
+const STD_CRATES: &[&str] = &["std", "core", "alloc"];
+
+/// A standard library feature which is referenced by a guest but not supported by the zkVM.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnsupportedStdFeature {
+    /// The name of the feature, e.g. `networking`.
+    pub feature: &'static str,
+    /// The demangled names of the functions of the feature in the guest.
+    pub functions: BTreeSet<String>,
+    /// The crates outside of the standard library which call the functions, directly or through
+    /// other functions of the standard library.
+    pub crates: BTreeSet<String>,
+}
+
+impl Display for UnsupportedStdFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} is requested by {} through {}",
+            self.feature,
+            self.crates.iter().cloned().collect::<Vec<_>>().join(", "),
+            self.functions
+                .iter()
+                .cloned()
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+/// Returns the unsupported standard library features which the crates of the guest ELF `elf` call,
+/// see the [module](self) documentation.
+///
+/// Only direct calls are followed, so a feature used through a function pointer or trait object
+/// is not reported. Neither are features only used by the standard library itself, e.g. by its
+/// panic handler. There are none if the ELF has no symbol table, e.g. because it was stripped.
+pub fn find_unsupported_std_features(elf: &[u8]) -> Result<Vec<UnsupportedStdFeature>> {
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(elf)
+        .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+    let Some((symtab, strtab)) = elf
+        .symbol_table()
+        .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?
+    else {
+        return Ok(vec![]);
+    };
+    // The size and demangled name of each function, keyed by start address.
+    let mut functions = BTreeMap::new();
+    for symbol in symtab
+        .iter()
+        .filter(|symbol| symbol.st_symtype() == STT_FUNC && symbol.st_value != 0)
+    {
+        let name = strtab
+            .get(symbol.st_name as usize)
+            .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+        functions.insert(
+            symbol.st_value as u32,
+            (
+                symbol.st_size as u32,
+                format!("{:#}", rustc_demangle::demangle(name)),
+            ),
+        );
+    }
+
+    // The callers of each function, keyed by the start addresses of both.
+    let mut callers: HashMap<u32, BTreeSet<u32>> = HashMap::new();
+    if let Some(shdrs) = elf.section_headers().filter(|_| !functions.is_empty()) {
+        for shdr in shdrs
+            .iter()
+            .filter(|shdr| shdr.sh_flags & SHF_EXECINSTR as u64 != 0)
+        {
+            let (data, _) = elf
+                .section_data(&shdr)
+                .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+            for (pc, target) in direct_jumps(shdr.sh_addr as u32, data) {
+                let caller = functions
+                    .range(..=pc)
+                    .next_back()
+                    .filter(|(&start, &(size, _))| pc - start < size);
+                if let Some((&caller, _)) = caller {
+                    if caller != target && functions.contains_key(&target) {
+                        callers.entry(target).or_default().insert(caller);
+                    }
+                }
+            }
+        }
+    }
+
+    let mut features: BTreeMap<&str, UnsupportedStdFeature> = BTreeMap::new();
+    for (&start, (_, name)) in &functions {
+        let Some(feature) = unsupported_feature(name) else {
+            continue;
+        };
+        let crates = requesting_crates(start, &functions, &callers);
+        if crates.is_empty() {
+            continue;
+        }
+        let entry = features
+            .entry(feature)
+            .or_insert_with(|| UnsupportedStdFeature {
+                feature,
+                functions: BTreeSet::new(),
+                crates: BTreeSet::new(),
+            });
+        // Functions only called by other functions of unsupported features, e.g. the stubs of
+        // the platform, are left out.
+        if !callers[&start]
+            .iter()
+            .all(|caller| unsupported_feature(&functions[caller].1).is_some())
+        {
+            entry.functions.insert(name.clone());
+        }
+        entry.crates.extend(crates);
+    }
+    Ok(features.into_values().collect())
+}
+
+/// Returns the unsupported feature of the demangled function `name`, if any.
+fn unsupported_feature(name: &str) -> Option<&'static str> {
+    UNSUPPORTED_STD_PATHS
+        .iter()
+        .find(|(prefix, _)| name.trim_start_matches('<').starts_with(prefix))
+        .map(|&(_, feature)| feature)
+}
+
+/// Returns the crates outside of the standard library which call the function starting at
+/// `start`, following the callers which belong to the standard library.
+fn requesting_crates(
+    start: u32,
+    functions: &BTreeMap<u32, (u32, String)>,
+    callers: &HashMap<u32, BTreeSet<u32>>,
+) -> BTreeSet<String> {
+    let mut crates = BTreeSet::new();
+    let mut visited = BTreeSet::from([start]);
+    let mut pending = vec![start];
+    while let Some(callee) = pending.pop() {
+        for &caller in callers.get(&callee).into_iter().flatten() {
+            let krate = crate_name(&functions[&caller].1);
+            if STD_CRATES.contains(&krate) {
+                if visited.insert(caller) {
+                    pending.push(caller);
+                }
+            } else if !krate.is_empty() {
+                crates.insert(krate.to_string());
+            }
+        }
+    }
+    crates
+}
+
+/// Returns the crate of the demangled function `name`, which is the first segment of its path or,
+/// for a trait method, of the path of its type.
+fn crate_name(name: &str) -> &str {
+    let path = name.trim_start_matches(['<', '&', '*']);
+    path.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .next()
+        .unwrap_or_default()
+}
+
+/// Returns the pc and target of each direct jump in the RISC-V code `data` which starts at `addr`:
+/// `jal`, `jalr` on the register set by the `auipc` right before it, and the compressed `c.jal` and
+/// `c.j`.
+fn direct_jumps(addr: u32, data: &[u8]) -> Vec<(u32, u32)> {
+    let mut jumps = Vec::new();
+    // The destination register and result of the previous instruction, if it was an `auipc`.
+    let mut auipc: Option<(u32, u32)> = None;
+    let mut offset = 0;
+    while let Some(bytes) = data.get(offset..offset + 2) {
+        let pc = addr.wrapping_add(offset as u32);
+        let low = u16::from_le_bytes(bytes.try_into().unwrap());
+        if low & 0b11 != 0b11 {
+            let funct3 = low >> 13;
+            if low & 0b11 == 0b01 && (funct3 == 0b001 || funct3 == 0b101) {
+                jumps.push((pc, pc.wrapping_add(cj_offset(low) as u32)));
+            }
+            auipc = None;
+            offset += 2;
+            continue;
+        }
+        let Some(bytes) = data.get(offset..offset + 4) else {
+            break;
+        };
+        let insn = u32::from_le_bytes(bytes.try_into().unwrap());
+        let rd = (insn >> 7) & 0x1f;
+        let rs1 = (insn >> 15) & 0x1f;
+        match insn & 0x7f {
+            // jal
+            0x6f => jumps.push((pc, pc.wrapping_add(j_offset(insn) as u32))),
+            // jalr
+            0x67 => {
+                if let Some((reg, base)) = auipc {
+                    if reg == rs1 {
+                        let target = base.wrapping_add(((insn as i32) >> 20) as u32);
+                        jumps.push((pc, target));
+                    }
+                }
+            }
+            _ => {}
+        }
+        auipc = (insn & 0x7f == 0x17).then(|| (rd, pc.wrapping_add(insn & 0xffff_f000)));
+        offset += 4;
+    }
+    jumps
+}
+
+/// Returns the sign-extended offset of the J-type instruction `insn`.
+fn j_offset(insn: u32) -> i32 {
+    let imm = ((insn >> 31) << 20)
+        | (((insn >> 21) & 0x3ff) << 1)
+        | (((insn >> 20) & 1) << 11)
+        | (((insn >> 12) & 0xff) << 12);
+    ((imm << 11) as i32) >> 11
+}
+
+/// Returns the sign-extended offset of the CJ-type instruction `insn`.
+fn cj_offset(insn: u16) -> i32 {
+    let bit = |from: u32, to: u32| ((insn as u32 >> from) & 1) << to;
+    let imm = bit(12, 11)
+        | bit(11, 4)
+        | bit(10, 9)
+        | bit(9, 8)
+        | bit(8, 10)
+        | bit(7, 6)
+        | bit(6, 7)
+        | bit(5, 3)
+        | bit(4, 2)
+        | bit(3, 1)
+        | bit(2, 5);
+    ((imm << 20) as i32) >> 20
+}
@@ -0,0 +1,73 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex::FromHexError;
+
+openvm::entry!(main);
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+struct Bytes {
+    #[serde(with = "hex")]
+    data: alloc::vec::Vec<u8>,
+}
+
+pub fn main() {
+    // Checks from the tests of lib.rs of hex.
+    let mut buf = [0u8; 12];
+    hex::encode_to_slice(b"foobar", &mut buf).unwrap();
+    assert_eq!(&buf, b"666f6f626172");
+    let mut bytes = [0u8; 6];
+    hex::decode_to_slice("666f6f626172", &mut bytes).unwrap();
+    assert_eq!(&bytes, b"foobar");
+    assert_eq!(
+        hex::decode_to_slice("666f6f62617", &mut bytes),
+        Err(FromHexError::OddLength)
+    );
+    assert_eq!(
+        hex::decode_to_slice("66ag", &mut [0u8; 2]),
+        Err(FromHexError::InvalidHexCharacter { c: 'g', index: 3 })
+    );
+    assert_eq!(
+        hex::decode_to_slice("666f", &mut [0u8; 1]),
+        Err(FromHexError::InvalidStringLength)
+    );
+    assert_eq!(
+        <[u8; 4] as hex::FromHex>::from_hex("0102ABcd"),
+        Ok([1, 2, 0xab, 0xcd])
+    );
+
+    #[cfg(feature = "alloc")]
+    {
+        use alloc::vec;
+
+        assert_eq!(hex::encode("foobar"), "666f6f626172");
+        assert_eq!(hex::encode_upper([0xde, 0xad, 0xbe, 0xef]), "DEADBEEF");
+        assert_eq!(hex::decode("666F6F626172"), Ok(b"foobar".to_vec()));
+        assert_eq!(
+            <alloc::vec::Vec<u8> as hex::FromHex>::from_hex("00ff"),
+            Ok(vec![0, 0xff])
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    {
+        let value = Bytes {
+            data: alloc::vec![1, 2, 0xff],
+        };
+        let words = openvm::serde::to_vec(&value).unwrap();
+        assert_eq!(
+            openvm::serde::from_slice::<Bytes, u32>(&words).unwrap(),
+            value
+        );
+    }
+
+    #[cfg(feature = "std")]
+    {
+        let err: Box<dyn std::error::Error> = Box::new(FromHexError::OddLength);
+        assert_eq!(err.to_string(), "Odd number of digits");
+    }
+}
@@ -0,0 +1,70 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+// Used by the init! macro.
+#[allow(unused)]
+use k256::Secp256k1Point;
+
+openvm::init!();
+
+openvm::entry!(main);
+
+pub fn main() {
+    #[cfg(feature = "arithmetic")]
+    {
+        use k256::{
+            elliptic_curve::{CurveArithmetic, Group},
+            Secp256k1,
+        };
+
+        // The first vectors of test_vectors/group.rs of k256.
+        let generator = <Secp256k1 as CurveArithmetic>::ProjectivePoint::generator();
+        assert_eq!(
+            generator.x_be_bytes(),
+            hex!("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798")
+        );
+        let double = generator + generator;
+        assert_eq!(
+            double.x_be_bytes(),
+            hex!("C6047F9441ED7D6D3045406E95C07CD85C778E4B8CEF3CA7ABAC09B95C709EE5")
+        );
+        assert_eq!(
+            double.y_be_bytes(),
+            hex!("1AE168FEA63DC339A3C58419466CEAEEF7F632653266D0E1236431A950CFE52A")
+        );
+        assert_eq!(generator * k256::Scalar::from(2u64), double);
+    }
+
+    #[cfg(feature = "ecdsa")]
+    {
+        use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+        use sha2::{Digest, Sha256};
+
+        // Checks from the recovery tests of ecdsa/recoverable.rs of k256.
+        let vectors = [
+            (
+                hex!("021a7a569e91dbf60581509c7fc946d1003b60c7dee85299538db6353538d59574"),
+                hex!(
+                    "ce53abb3721bafc561408ce8ff99c909f7f0b18a2f788649d6470162ab1aa032"
+                    "3971edc523a6d6453f3fb6128d318d9db1a5ff3386feb1047d9816e780039d52"
+                ),
+                RecoveryId::new(false, false),
+            ),
+            (
+                hex!("036d6caac248af96f6afa7f904f550253a0f3ef3f5aa2fe6838a95b216691468e2"),
+                hex!(
+                    "46c05b6368a44b8810d79859441d819b8e7cdc8bfd371e35c53196f4bcacdb51"
+                    "35c7facce2a97b95eacba8a586d87b7958aaf8368ab29cee481f76e871dbd9cb"
+                ),
+                RecoveryId::new(true, false),
+            ),
+        ];
+        let digest = Sha256::digest(b"example message");
+        for (pk, sig, recid) in vectors {
+            let sig = Signature::try_from(sig.as_slice()).unwrap();
+            let key = VerifyingKey::recover_from_prehash(&digest, &sig, recid).unwrap();
+            assert_eq!(&key.to_sec1_bytes(true)[..], &pk[..]);
+        }
+    }
+}
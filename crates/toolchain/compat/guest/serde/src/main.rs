@@ -0,0 +1,91 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use core::fmt::Debug;
+
+use openvm::serde::{from_slice, to_vec};
+use serde::{
+    de::{value::Error, DeserializeOwned, IntoDeserializer},
+    Deserialize, Serialize,
+};
+
+openvm::entry!(main);
+
+fn assert_roundtrip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: T) {
+    let words = to_vec(&value).unwrap();
+    assert_eq!(from_slice::<T, u32>(&words).unwrap(), value);
+}
+
+#[cfg(feature = "derive")]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+struct Struct {
+    a: u32,
+    b: Option<i64>,
+    #[serde(default)]
+    c: Vec<u8>,
+}
+
+#[cfg(feature = "derive")]
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+enum Enum {
+    Unit,
+    Newtype(u16),
+    Tuple(u8, bool),
+    Struct { x: i32, y: String },
+}
+
+pub fn main() {
+    // Primitives from `IntoDeserializer`, as in test_value.rs of serde.
+    let value: Result<u32, Error> = u32::deserialize(42u32.into_deserializer());
+    assert_eq!(value, Ok(42));
+    let value: Result<i8, Error> = i8::deserialize(300i32.into_deserializer());
+    assert!(value.is_err());
+    let value: Result<bool, Error> = bool::deserialize(true.into_deserializer());
+    assert_eq!(value, Ok(true));
+
+    // Some of the impls of test_de.rs and test_ser.rs.
+    assert_roundtrip(0u8);
+    assert_roundtrip(u64::MAX);
+    assert_roundtrip(i128::MIN);
+    assert_roundtrip('\u{1f600}');
+    assert_roundtrip((1u16, -2i32, false));
+    assert_roundtrip([1u32, 2, 3, 4]);
+    assert_roundtrip(Some(5u32));
+    assert_roundtrip(None::<u32>);
+    assert_roundtrip(String::from("abc"));
+    assert_roundtrip(vec![vec![1u8], vec![], vec![2, 3]]);
+    assert_roundtrip(BTreeMap::from([
+        (1u32, String::from("a")),
+        (2, String::from("b")),
+    ]));
+
+    #[cfg(feature = "derive")]
+    {
+        assert_roundtrip(Struct {
+            a: 1,
+            b: Some(-1),
+            c: vec![1, 2],
+        });
+        assert_roundtrip(Enum::Unit);
+        assert_roundtrip(Enum::Newtype(7));
+        assert_roundtrip(Enum::Tuple(1, true));
+        assert_roundtrip(Enum::Struct {
+            x: -3,
+            y: String::from("y"),
+        });
+    }
+
+    #[cfg(feature = "std")]
+    {
+        use std::{collections::HashMap, net::Ipv4Addr, time::Duration};
+
+        assert_roundtrip(Duration::new(1, 2));
+        assert_roundtrip(Ipv4Addr::new(127, 0, 0, 1));
+        let value: HashMap<u32, u32> = HashMap::from([(1, 2), (3, 4)]);
+        let words = to_vec(&value).unwrap();
+        assert_eq!(from_slice::<HashMap<u32, u32>, u32>(&words).unwrap(), value);
+    }
+}
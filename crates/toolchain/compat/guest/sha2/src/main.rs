@@ -0,0 +1,70 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+use sha2::{Digest, Sha224, Sha256, Sha384, Sha512, Sha512_256};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Some of the known-answer tests of tests/mod.rs of sha2.
+    assert_eq!(
+        Sha256::digest(b"")[..],
+        hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+    );
+    assert_eq!(
+        Sha256::digest(b"abc")[..],
+        hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad")
+    );
+    assert_eq!(
+        Sha224::digest(b"abc")[..],
+        hex!("23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7")
+    );
+    assert_eq!(
+        Sha384::digest(b"abc")[..],
+        hex!(
+            "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed"
+            "8086072ba1e7cc2358baeca134c825a7"
+        )
+    );
+    assert_eq!(
+        Sha512::digest(b"abc")[..],
+        hex!(
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a"
+            "2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        )
+    );
+    assert_eq!(
+        Sha512_256::digest(b"abc")[..],
+        hex!("53048e2681941ef99b2e29b76b4c7dabe4c2d0c634fc6d46e0e2f13107e7af23")
+    );
+
+    // Hashing in chunks crossing the block boundaries, as in the one million 'a' test.
+    let mut hasher = Sha256::new();
+    for _ in 0..1000 {
+        hasher.update([b'a'; 1000]);
+    }
+    assert_eq!(
+        hasher.finalize()[..],
+        hex!("cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0")
+    );
+
+    #[cfg(feature = "oid")]
+    {
+        use sha2::digest::const_oid::{AssociatedOid, ObjectIdentifier};
+
+        assert_eq!(
+            Sha256::OID,
+            ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1")
+        );
+    }
+
+    #[cfg(feature = "std")]
+    {
+        use std::io::Write;
+
+        let mut hasher = Sha256::new();
+        hasher.write_all(b"abc").unwrap();
+        assert_eq!(hasher.finalize(), Sha256::digest(b"abc"));
+    }
+}
@@ -0,0 +1,167 @@
+//! Runs checks ported from the tests of popular crates in the OpenVM and reports which of their
+//! features work, see the README of the crate. The upstream test suites are not run.
+
+use std::{fmt::Write, fs::read, path::PathBuf};
+
+use eyre::{bail, Result};
+use openvm_build::{build_guest_package, get_package, guest_methods, GuestOptions};
+use openvm_sdk::{fs::read_config_toml_or_default, Sdk, StdIn};
+use openvm_transpiler::{elf::Elf, openvm_platform::memory::MEM_SIZE};
+use tempfile::tempdir;
+
+/// A crate of the matrix, whose ported checks are run by the guest program in `guest/{name}`.
+pub struct Case {
+    pub name: &'static str,
+    /// The sets of features of the guest program to test. Each feature of the program enables
+    /// the feature of the crate with the same name, and the crate is built without its default
+    /// features.
+    pub feature_sets: &'static [&'static [&'static str]],
+}
+
+pub const CASES: &[Case] = &[
+    Case {
+        name: "serde",
+        feature_sets: &[&[], &["derive"], &["std"]],
+    },
+    Case {
+        name: "hex",
+        feature_sets: &[&[], &["alloc"], &["serde"], &["std"]],
+    },
+    Case {
+        name: "sha2",
+        feature_sets: &[&[], &["oid"], &["std"]],
+    },
+    Case {
+        name: "k256",
+        feature_sets: &[&["arithmetic"], &["ecdsa"], &["ecdsa", "std"]],
+    },
+];
+
+pub fn guest_dir(case: &Case) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("guest")
+        .join(case.name)
+}
+
+/// The outcome of running the guest program of a [Case] with a feature set.
+pub enum Outcome {
+    /// The program failed to compile, see the output of cargo.
+    BuildFailed,
+    TranspileFailed(String),
+    ExecutionFailed(String),
+    Passed {
+        /// The number of instructions executed.
+        instructions: usize,
+    },
+}
+
+/// Builds the guest program of `case` with `features`, transpiles it with the VM config of its
+/// `openvm.toml` and executes it. Errors which are not the fault of the crate, such as a missing
+/// toolchain, are returned as errors instead of outcomes.
+pub fn run_case(case: &Case, features: &[&str]) -> Result<Outcome> {
+    let dir = guest_dir(case);
+    let app_config = read_config_toml_or_default(dir.join("openvm.toml"))?;
+    app_config.app_vm_config.write_to_init_file(&dir, None)?;
+
+    let pkg = get_package(&dir);
+    let target_dir = tempdir()?;
+    let guest_opts = GuestOptions::default()
+        .with_target_dir(target_dir.path())
+        .with_features(features);
+    match build_guest_package(&pkg, &guest_opts, None, &None) {
+        Ok(_) => {}
+        Err(Some(_)) => return Ok(Outcome::BuildFailed),
+        Err(None) => bail!("cannot build {} while OPENVM_SKIP_BUILD is set", case.name),
+    }
+    let elf_path = guest_methods(
+        &pkg,
+        target_dir.path(),
+        &guest_opts.features,
+        &guest_opts.profile,
+    )
+    .pop()
+    .unwrap();
+    let elf = Elf::decode(&read(elf_path)?, MEM_SIZE as u32)?;
+
+    let sdk = Sdk::new();
+    let exe = match sdk.transpile(elf, app_config.transpiler()?) {
+        Ok(exe) => exe,
+        Err(err) => return Ok(Outcome::TranspileFailed(err.to_string())),
+    };
+    Ok(
+        match sdk.execute_with_stats(exe, app_config.app_vm_config, StdIn::default()) {
+            Ok((_, result)) => Outcome::Passed {
+                instructions: result.total_cycles,
+            },
+            Err(err) => Outcome::ExecutionFailed(err.to_string()),
+        },
+    )
+}
+
+/// The outcomes of the feature sets of the cases run, in order.
+#[derive(Default)]
+pub struct Report {
+    pub rows: Vec<(&'static str, &'static [&'static str], Outcome)>,
+}
+
+impl Report {
+    /// The report as a Markdown table with a row per feature set.
+    pub fn to_markdown(&self) -> String {
+        let mut table = String::from(
+            "| Crate | Features | Status | Instructions |\n| --- | --- | --- | --- |\n",
+        );
+        for (name, features, outcome) in &self.rows {
+            let features = if features.is_empty() {
+                "(none)".to_string()
+            } else {
+                features.join(", ")
+            };
+            let (status, instructions) = match outcome {
+                Outcome::BuildFailed => ("fails to build".to_string(), String::new()),
+                Outcome::TranspileFailed(err) => {
+                    (format!("fails to transpile: {err}"), String::new())
+                }
+                Outcome::ExecutionFailed(err) => (format!("fails: {err}"), String::new()),
+                Outcome::Passed { instructions } => {
+                    ("ported checks pass".to_string(), instructions.to_string())
+                }
+            };
+            // Errors may span lines, which would break the table.
+            let status = status.replace('\n', " ").replace('|', "\\|");
+            writeln!(table, "| {name} | {features} | {status} | {instructions} |").unwrap();
+        }
+        table
+    }
+
+    pub fn num_passed(&self) -> usize {
+        self.rows
+            .iter()
+            .filter(|(_, _, outcome)| matches!(outcome, Outcome::Passed { .. }))
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::read_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_cases_declare_their_features() {
+        for case in CASES {
+            let manifest: toml::Table = read_to_string(guest_dir(case).join("Cargo.toml"))
+                .unwrap()
+                .parse()
+                .unwrap();
+            let declared = manifest["features"].as_table().unwrap();
+            for feature in case.feature_sets.iter().copied().flatten() {
+                assert!(
+                    declared.contains_key(*feature),
+                    "guest program of {} has no feature {feature}",
+                    case.name
+                );
+            }
+        }
+    }
+}
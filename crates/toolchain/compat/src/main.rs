@@ -0,0 +1,66 @@
+use std::{fs::write, path::PathBuf};
+
+use clap::Parser;
+use eyre::Result;
+use openvm_compat::{run_case, Report, CASES};
+
+#[derive(Parser)]
+#[command(author, version, about = "OpenVM compatibility matrix of popular crates")]
+struct Cli {
+    /// Crates to test (if not specified, all crates of the matrix are tested)
+    #[arg(short, long, value_delimiter = ',')]
+    crates: Vec<String>,
+
+    /// Path to write the report to, as a Markdown table
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// List the crates of the matrix and their feature sets and exit
+    #[arg(short, long)]
+    list: bool,
+}
+
+fn main() -> Result<()> {
+    let cli = Cli::parse();
+
+    if cli.list {
+        for case in CASES {
+            let feature_sets: Vec<_> = case
+                .feature_sets
+                .iter()
+                .map(|features| format!("[{}]", features.join(", ")))
+                .collect();
+            println!("{}: {}", case.name, feature_sets.join(" "));
+        }
+        return Ok(());
+    }
+
+    for name in &cli.crates {
+        if !CASES.iter().any(|case| case.name == name) {
+            eyre::bail!("{name} is not in the matrix, see --list");
+        }
+    }
+    let mut report = Report::default();
+    for case in CASES
+        .iter()
+        .filter(|case| cli.crates.is_empty() || cli.crates.iter().any(|name| name == case.name))
+    {
+        for &features in case.feature_sets {
+            println!("[openvm-compat] {} [{}]", case.name, features.join(", "));
+            let outcome = run_case(case, features)?;
+            report.rows.push((case.name, features, outcome));
+        }
+    }
+
+    let table = report.to_markdown();
+    println!("{table}");
+    println!(
+        "[openvm-compat] {} of {} feature sets pass",
+        report.num_passed(),
+        report.rows.len()
+    );
+    if let Some(output) = cli.output {
+        write(output, table)?;
+    }
+    Ok(())
+}
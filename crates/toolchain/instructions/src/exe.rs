@@ -1,14 +1,52 @@
-use std::collections::BTreeMap;
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display},
+};
 
 use openvm_stark_backend::p3_field::Field;
 use serde::{Deserialize, Serialize};
 
-use crate::program::Program;
+use crate::program::{Program, DEFAULT_PC_STEP};
 
 /// Memory image is a map from (address space, address) to word.
 pub type MemoryImage<F> = BTreeMap<(u32, u32), F>;
 /// Stores the starting address, end address, and name of a set of function.
 pub type FnBounds = BTreeMap<u32, FnBound>;
+/// Map from the Merkle root of an overlay to its contents. See the `openvm-overlay` crate.
+pub type Overlays = BTreeMap<[u8; 32], Vec<u8>>;
+
+/// A line of a source file, as recorded in the DWARF line info of the program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    /// Index of the file in [SourceMap::files].
+    pub file: u32,
+    pub line: u32,
+}
+
+/// Map from pc to the source line the instruction at that pc was compiled from.
+///
+/// Every entry starts a run of instructions up to the next entry, and `None` marks the end of a
+/// run of instructions with line info, so instructions past it have no source location.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceMap {
+    /// Paths of the source files, indexed by [SourceLocation::file].
+    pub files: Vec<String>,
+    pub lines: BTreeMap<u32, Option<SourceLocation>>,
+}
+
+impl SourceMap {
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// The file and line the instruction at `pc` was compiled from, if known.
+    pub fn location(&self, pc: u32) -> Option<(&str, u32)> {
+        let (_, location) = self.lines.range(..=pc).next_back()?;
+        let location = (*location)?;
+        let file = self.files.get(location.file as usize)?;
+        Some((file, location.line))
+    }
+}
 
 /// Executable program for OpenVM.
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
@@ -25,6 +63,12 @@ pub struct VmExe<F> {
     pub init_memory: MemoryImage<F>,
     /// Starting + ending bounds for each function.
     pub fn_bounds: FnBounds,
+    /// Read-only data which is not part of the initial memory image, and is instead paged into
+    /// memory on first access through `hint_load_by_key`.
+    pub overlays: Overlays,
+    /// Source locations of the instructions, empty unless the exe was transpiled from an ELF
+    /// decoded with its debug info.
+    pub source_map: SourceMap,
 }
 
 impl<F> VmExe<F> {
@@ -34,6 +78,8 @@ impl<F> VmExe<F> {
             pc_start: 0,
             init_memory: BTreeMap::new(),
             fn_bounds: Default::default(),
+            overlays: Default::default(),
+            source_map: Default::default(),
         }
     }
     pub fn with_pc_start(mut self, pc_start: u32) -> Self {
@@ -44,6 +90,64 @@ impl<F> VmExe<F> {
         self.init_memory = init_memory;
         self
     }
+
+    /// Counts the instructions of the program per function of [Self::fn_bounds], which are only
+    /// known if the exe was transpiled from an ELF with a symbol table.
+    pub fn size_report(&self) -> SizeReport {
+        let mut counts = BTreeMap::<u32, usize>::new();
+        let mut other = 0;
+        for (index, _) in self
+            .program
+            .instructions_and_debug_infos
+            .iter()
+            .enumerate()
+            .filter(|(_, instruction)| instruction.is_some())
+        {
+            let pc = self.program.pc_base + self.program.step * index as u32;
+            // The end of a function is the address of its last word.
+            match self.fn_bounds.range(..=pc).next_back() {
+                Some((&start, bound)) if pc < bound.end + DEFAULT_PC_STEP => {
+                    *counts.entry(start).or_default() += 1
+                }
+                _ => other += 1,
+            }
+        }
+        let mut functions: Vec<_> = counts
+            .into_iter()
+            .map(|(start, count)| (self.fn_bounds[&start].name.clone(), count))
+            .collect();
+        functions.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+        SizeReport { functions, other }
+    }
+}
+
+/// The number of instructions of a [VmExe] per function, see [VmExe::size_report].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SizeReport {
+    /// Names and numbers of instructions of the functions with instructions, largest first.
+    pub functions: Vec<(String, usize)>,
+    /// The number of instructions outside of every function, such as the instructions the
+    /// transpiler places after the program, or all of them if the exe has no function bounds.
+    pub other: usize,
+}
+
+impl SizeReport {
+    pub fn total(&self) -> usize {
+        self.functions.iter().map(|(_, count)| count).sum::<usize>() + self.other
+    }
+}
+
+impl Display for SizeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "instructions per function:")?;
+        for (name, count) in &self.functions {
+            writeln!(f, "  {count:>10}  {name}")?;
+        }
+        if self.other > 0 {
+            writeln!(f, "  {:>10}  (outside of functions)", self.other)?;
+        }
+        write!(f, "  {:>10}  total", self.total())
+    }
 }
 
 impl<F: Field> From<Program<F>> for VmExe<F> {
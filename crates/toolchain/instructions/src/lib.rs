@@ -2,6 +2,8 @@
 
 #![allow(non_camel_case_types)]
 
+use std::ops::Range;
+
 use openvm_instructions_derive::LocalOpcode;
 use openvm_stark_backend::p3_field::Field;
 use serde::{Deserialize, Serialize};
@@ -30,6 +32,11 @@ pub trait LocalOpcode {
     }
 }
 
+/// Global opcodes reserved for VM extensions maintained outside of this repository. The opcodes of
+/// the extensions of this repository are all below this range, so out-of-tree extensions which take
+/// their `opcode_offset`s from it never collide with them.
+pub const EXTERNAL_OPCODE_RANGE: Range<usize> = 0x1000..0x10000;
+
 #[repr(C)]
 #[derive(Copy, Clone, Debug, Hash, PartialEq, Eq, derive_new::new, Serialize, Deserialize)]
 pub struct VmOpcode(usize);
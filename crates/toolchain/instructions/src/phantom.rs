@@ -17,4 +17,7 @@ pub enum SysPhantom {
     CtStart,
     /// End tracing
     CtEnd,
+    /// Ends the segment after this instruction, so that the host can supply more input before
+    /// execution continues. Requires continuations.
+    Pause,
 }
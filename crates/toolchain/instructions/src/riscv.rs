@@ -5,3 +5,5 @@ pub const RV32_CELL_BITS: usize = 8;
 pub const RV32_IMM_AS: u32 = 0;
 pub const RV32_REGISTER_AS: u32 = 1;
 pub const RV32_MEMORY_AS: u32 = 2;
+/// The address space of the user public values with continuations.
+pub const RV32_PUBLIC_VALUES_AS: u32 = 3;
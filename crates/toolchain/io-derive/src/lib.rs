@@ -0,0 +1,67 @@
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, Index};
+
+/// Implements `openvm::io::Inputs` for a struct, writing and reading one input stream per field
+/// in declaration order. Every field must implement `Serialize` and `DeserializeOwned`.
+#[proc_macro_derive(Inputs)]
+pub fn inputs_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let name = &ast.ident;
+
+    let fields = match &ast.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return syn::Error::new_spanned(name, "Inputs can only be derived for structs")
+                .to_compile_error()
+                .into();
+        }
+    };
+
+    let mut generics = ast.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for field in fields {
+        let ty = &field.ty;
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::openvm::io::inputs::InputValue));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let members: Vec<_> = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(i);
+                quote!(#index)
+            }
+        })
+        .collect();
+    let construct = match fields {
+        Fields::Named(_) => quote!(Self { #(#members: reader.read()),* }),
+        Fields::Unnamed(_) => {
+            let reads = members.iter().map(|_| quote!(reader.read()));
+            quote!(Self(#(#reads),*))
+        }
+        Fields::Unit => quote!(Self),
+    };
+
+    TokenStream::from(quote! {
+        impl #impl_generics ::openvm::io::inputs::Inputs for #name #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn write_fields<W: ::openvm::io::inputs::InputWrite>(&self, writer: &mut W) {
+                #(writer.write(&self.#members);)*
+            }
+
+            #[allow(unused_variables)]
+            fn read_fields<R: ::openvm::io::inputs::InputRead>(reader: &mut R) -> Self {
+                // Struct expressions evaluate their fields in the order they are written.
+                #construct
+            }
+        }
+    })
+}
@@ -0,0 +1,71 @@
+use std::collections::VecDeque;
+
+use openvm::io::inputs::{InputRead, InputWrite, Inputs};
+
+#[derive(Default)]
+struct Streams(VecDeque<Vec<u32>>);
+
+impl InputWrite for Streams {
+    fn write<T: serde::Serialize>(&mut self, value: &T) {
+        self.0.push_back(openvm::serde::to_vec(value).unwrap());
+    }
+}
+
+impl InputRead for Streams {
+    fn read<T: serde::de::DeserializeOwned>(&mut self) -> T {
+        openvm::serde::from_slice(&self.0.pop_front().unwrap()).unwrap()
+    }
+}
+
+#[derive(Inputs, Debug, PartialEq)]
+struct Named {
+    n: u64,
+    data: Vec<u8>,
+    name: String,
+}
+
+#[derive(Inputs, Debug, PartialEq)]
+struct Tuple(u32, Option<u8>);
+
+#[derive(Inputs, Debug, PartialEq)]
+struct Generic<T> {
+    items: Vec<T>,
+}
+
+fn roundtrip<T: Inputs>(inputs: &T) -> (T, usize) {
+    let mut streams = Streams::default();
+    inputs.write_fields(&mut streams);
+    let num_streams = streams.0.len();
+    (T::read_fields(&mut streams), num_streams)
+}
+
+#[test]
+fn test_named_fields_in_order() {
+    let inputs = Named {
+        n: 7,
+        data: vec![1, 2, 3],
+        name: "abc".to_string(),
+    };
+    let mut streams = Streams::default();
+    inputs.write_fields(&mut streams);
+    assert_eq!(streams.0.len(), 3);
+    assert_eq!(streams.read::<u64>(), 7);
+    assert_eq!(streams.read::<Vec<u8>>(), vec![1, 2, 3]);
+    assert_eq!(streams.read::<String>(), "abc");
+}
+
+#[test]
+fn test_roundtrip() {
+    let named = Named {
+        n: u64::MAX,
+        data: vec![],
+        name: "x".to_string(),
+    };
+    assert_eq!(roundtrip(&named), (named, 3));
+    let tuple = Tuple(5, Some(1));
+    assert_eq!(roundtrip(&tuple), (tuple, 2));
+    let generic = Generic {
+        items: vec![1u16, 2],
+    };
+    assert_eq!(roundtrip(&generic), (generic, 1));
+}
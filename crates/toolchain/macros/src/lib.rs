@@ -4,6 +4,9 @@ use syn::{
     Expr, Ident, Token,
 };
 
+mod schema;
+pub use schema::*;
+
 pub struct MacroArgs {
     pub items: Vec<Item>,
 }
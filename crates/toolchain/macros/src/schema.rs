@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use syn::{Expr, ExprLit, ExprPath, ExprUnary, Ident, Lit, LitStr, Path, UnOp};
+
+use crate::Item;
+
+/// A parameter accepted by the items of a macro, see [Item::parse_params].
+#[derive(Clone, Copy, Debug)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    pub required: bool,
+}
+
+impl ParamSpec {
+    pub const fn required(name: &'static str) -> Self {
+        Self {
+            name,
+            required: true,
+        }
+    }
+
+    pub const fn optional(name: &'static str) -> Self {
+        Self {
+            name,
+            required: false,
+        }
+    }
+}
+
+/// The parameters of an [Item], checked against a schema by [Item::parse_params].
+pub struct ItemParams<'a> {
+    values: BTreeMap<&'static str, &'a Expr>,
+}
+
+impl Item {
+    /// Checks the parameters of the item against `schema`, reporting every unknown or duplicate
+    /// parameter at its name, with a suggestion for misspelled names, and every missing required
+    /// parameter at the name of the item.
+    pub fn parse_params(&self, schema: &[ParamSpec]) -> syn::Result<ItemParams<'_>> {
+        let mut values = BTreeMap::new();
+        let mut errors = Vec::new();
+        for param in &self.params {
+            let name = param.name.to_string();
+            match schema.iter().find(|spec| spec.name == name) {
+                Some(spec) => {
+                    if values.insert(spec.name, &param.value).is_some() {
+                        errors.push(syn::Error::new_spanned(
+                            &param.name,
+                            format!("duplicate parameter `{name}`"),
+                        ));
+                    }
+                }
+                None => errors.push(unknown_param_error(&param.name, &self.name, schema)),
+            }
+        }
+        for spec in schema {
+            if spec.required && !values.contains_key(spec.name) {
+                errors.push(syn::Error::new_spanned(
+                    &self.name,
+                    format!(
+                        "missing required parameter `{}` for `{}`",
+                        spec.name, self.name
+                    ),
+                ));
+            }
+        }
+        match errors.into_iter().reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        }) {
+            Some(error) => Err(error),
+            None => Ok(ItemParams { values }),
+        }
+    }
+}
+
+fn unknown_param_error(name: &Ident, item_name: &Ident, schema: &[ParamSpec]) -> syn::Error {
+    let name_str = name.to_string();
+    let message = match schema
+        .iter()
+        .map(|spec| (edit_distance(&name_str, spec.name), spec.name))
+        .filter(|&(distance, expected)| distance <= expected.len().div_ceil(3))
+        .min()
+    {
+        Some((_, suggestion)) => format!(
+            "unknown parameter `{name_str}` for `{item_name}`; did you mean `{suggestion}`?"
+        ),
+        None => format!(
+            "unknown parameter `{name_str}` for `{item_name}`; expected one of {}",
+            schema
+                .iter()
+                .map(|spec| format!("`{}`", spec.name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    };
+    syn::Error::new_spanned(name, message)
+}
+
+/// Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
+
+impl<'a> ItemParams<'a> {
+    /// The value of the parameter `name`, if it was given.
+    pub fn get(&self, name: &str) -> Option<&'a Expr> {
+        self.values.get(name).copied()
+    }
+
+    /// The value of the parameter `name`, which must be a type path, if it was given.
+    pub fn path(&self, name: &str) -> syn::Result<Option<Path>> {
+        self.parse(name, "a type", |value| match value {
+            Expr::Path(ExprPath { path, .. }) => Some(path.clone()),
+            _ => None,
+        })
+    }
+
+    /// The value of the parameter `name`, which must be a string literal, if it was given.
+    pub fn lit_str(&self, name: &str) -> syn::Result<Option<LitStr>> {
+        self.parse(name, "a string literal", |value| match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Some(lit.clone()),
+            _ => None,
+        })
+    }
+
+    /// The value of the parameter `name`, which must be a `usize` literal, if it was given.
+    pub fn usize(&self, name: &str) -> syn::Result<Option<usize>> {
+        self.parse(name, "a usize", |value| match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int.base10_parse().ok(),
+            _ => None,
+        })
+    }
+
+    /// The value of the parameter `name`, which must be an integer literal, possibly negated, if
+    /// it was given.
+    pub fn isize(&self, name: &str) -> syn::Result<Option<isize>> {
+        self.parse(name, "an integer", parse_isize)
+    }
+
+    fn parse<T>(
+        &self,
+        name: &str,
+        expected: &str,
+        parse: impl FnOnce(&Expr) -> Option<T>,
+    ) -> syn::Result<Option<T>> {
+        self.get(name)
+            .map(|value| {
+                parse(value).ok_or_else(|| {
+                    syn::Error::new_spanned(
+                        value,
+                        format!("Expected {expected} for macro argument `{name}`"),
+                    )
+                })
+            })
+            .transpose()
+    }
+}
+
+fn parse_isize(expr: &Expr) -> Option<isize> {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(int), ..
+        }) => int.base10_parse::<isize>().ok(),
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr,
+            ..
+        }) => parse_isize(expr).map(|x| -x),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MacroArgs;
+
+    const SCHEMA: &[ParamSpec] = &[ParamSpec::required("modulus"), ParamSpec::optional("repr")];
+
+    fn parse_params(input: &str) -> Result<(), String> {
+        let args: MacroArgs = syn::parse_str(input).unwrap();
+        args.items[0]
+            .parse_params(SCHEMA)
+            .map(|_| ())
+            .map_err(|error| {
+                error
+                    .into_iter()
+                    .map(|error| error.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+    }
+
+    #[test]
+    fn test_parse_params() {
+        let args: MacroArgs = syn::parse_str(r#"Mod1 { modulus = "7", repr = -3 }"#).unwrap();
+        let params = args.items[0].parse_params(SCHEMA).unwrap();
+        assert_eq!(params.lit_str("modulus").unwrap().unwrap().value(), "7");
+        assert_eq!(params.isize("repr").unwrap(), Some(-3));
+        assert!(params.path("modulus").is_err());
+        assert_eq!(params.usize("missing").unwrap(), None);
+    }
+
+    #[test]
+    fn test_parse_params_errors() {
+        assert_eq!(
+            parse_params(r#"Mod1 { modulos = "7" }"#).unwrap_err(),
+            "unknown parameter `modulos` for `Mod1`; did you mean `modulus`?\n\
+             missing required parameter `modulus` for `Mod1`"
+        );
+        assert_eq!(
+            parse_params(r#"Mod1 { modulus = "7", size = 3 }"#).unwrap_err(),
+            "unknown parameter `size` for `Mod1`; expected one of `modulus`, `repr`"
+        );
+        assert_eq!(
+            parse_params(r#"Mod1 { modulus = "7", modulus = "11" }"#).unwrap_err(),
+            "duplicate parameter `modulus`"
+        );
+    }
+}
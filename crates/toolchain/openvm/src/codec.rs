@@ -0,0 +1,291 @@
+//! Hex and base64 (RFC 4648) encoding and decoding.
+//!
+//! The codecs convert a group of bytes at a time, 2 bytes to 4 hex digits and 3 bytes to 4 base64
+//! digits, and look every digit up in a table instead of branching on its range, so that a digit
+//! costs a load rather than a chain of comparisons in the zkVM.
+//!
+//! Decoding is strict, so that every byte string has a single encoding: whitespace is rejected,
+//! and so are base64 padding of the wrong length and nonzero unused bits in the last digit. The
+//! padding itself is optional.
+
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt::{Display, Formatter};
+
+/// An error decoding hex or base64.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input has a length which no encoded byte string has.
+    InvalidLength,
+    /// The byte at this index of the input is not a digit.
+    InvalidByte(usize),
+    /// The padding or the unused bits of the last digit are not canonical.
+    InvalidPadding,
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        match self {
+            Self::InvalidLength => f.write_str("Invalid length"),
+            Self::InvalidByte(index) => write!(f, "Invalid digit at index {index}"),
+            Self::InvalidPadding => f.write_str("Invalid padding"),
+        }
+    }
+}
+
+/// The value of bytes which are not digits in the decoding tables.
+const INVALID: u8 = 0xff;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// The two hex digits of each byte.
+const HEX_ENCODE: [[u8; 2]; 256] = {
+    let mut table = [[0; 2]; 256];
+    let mut byte = 0;
+    while byte < 256 {
+        table[byte] = [HEX_DIGITS[byte >> 4], HEX_DIGITS[byte & 0xf]];
+        byte += 1;
+    }
+    table
+};
+
+/// The value of each hex digit, in either case.
+const HEX_DECODE: [u8; 256] = {
+    let mut table = [INVALID; 256];
+    let mut value = 0;
+    while value < 16 {
+        table[HEX_DIGITS[value] as usize] = value as u8;
+        table[HEX_DIGITS[value].to_ascii_uppercase() as usize] = value as u8;
+        value += 1;
+    }
+    table
+};
+
+const BASE64_STANDARD: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_SAFE: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+const BASE64_STANDARD_DECODE: [u8; 256] = base64_decode_table(BASE64_STANDARD);
+const BASE64_URL_SAFE_DECODE: [u8; 256] = base64_decode_table(BASE64_URL_SAFE);
+
+const fn base64_decode_table(alphabet: &[u8; 64]) -> [u8; 256] {
+    let mut table = [INVALID; 256];
+    let mut value = 0;
+    while value < 64 {
+        table[alphabet[value] as usize] = value as u8;
+        value += 1;
+    }
+    table
+}
+
+/// Encodes `data` as lowercase hex.
+pub fn encode_hex(data: &[u8]) -> String {
+    let mut out = vec![0; 2 * data.len()];
+    let mut pairs = data.chunks_exact(2);
+    for (digits, pair) in out.chunks_exact_mut(4).zip(&mut pairs) {
+        let [a, b] = HEX_ENCODE[pair[0] as usize];
+        let [c, d] = HEX_ENCODE[pair[1] as usize];
+        digits.copy_from_slice(&[a, b, c, d]);
+    }
+    if let [byte] = pairs.remainder() {
+        let start = out.len() - 2;
+        out[start..].copy_from_slice(&HEX_ENCODE[*byte as usize]);
+    }
+    // SAFETY: hex digits are ASCII.
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+/// Decodes hex, with digits in either case and without a `0x` prefix.
+pub fn decode_hex(input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    if input.len() % 2 != 0 {
+        return Err(DecodeError::InvalidLength);
+    }
+    let mut out = vec![0; input.len() / 2];
+    let mut groups = input.chunks_exact(4);
+    for (bytes, digits) in out.chunks_exact_mut(2).zip(&mut groups) {
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| HEX_DECODE[digits[i] as usize]);
+        if (a | b | c | d) & 0xf0 != 0 {
+            return Err(invalid_byte(input, &HEX_DECODE));
+        }
+        bytes.copy_from_slice(&[(a << 4) | b, (c << 4) | d]);
+    }
+    if let [a, b] = groups.remainder() {
+        let (a, b) = (HEX_DECODE[*a as usize], HEX_DECODE[*b as usize]);
+        if (a | b) & 0xf0 != 0 {
+            return Err(invalid_byte(input, &HEX_DECODE));
+        }
+        *out.last_mut().unwrap() = (a << 4) | b;
+    }
+    Ok(out)
+}
+
+/// Encodes `data` as base64 with the standard alphabet and padding.
+pub fn encode_base64(data: &[u8]) -> String {
+    encode_base64_with(data, BASE64_STANDARD, true)
+}
+
+/// Encodes `data` as base64 with the URL-safe alphabet and without padding, as in JSON Web
+/// Tokens.
+pub fn encode_base64_url(data: &[u8]) -> String {
+    encode_base64_with(data, BASE64_URL_SAFE, false)
+}
+
+/// Decodes base64 with the standard alphabet.
+pub fn decode_base64(input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    decode_base64_with(input, &BASE64_STANDARD_DECODE)
+}
+
+/// Decodes base64 with the URL-safe alphabet.
+pub fn decode_base64_url(input: &[u8]) -> Result<Vec<u8>, DecodeError> {
+    decode_base64_with(input, &BASE64_URL_SAFE_DECODE)
+}
+
+fn encode_base64_with(data: &[u8], alphabet: &[u8; 64], padding: bool) -> String {
+    let len = if padding {
+        data.len().div_ceil(3) * 4
+    } else {
+        (4 * data.len()).div_ceil(3)
+    };
+    let mut out = vec![b'='; len];
+    let mut groups = data.chunks_exact(3);
+    for (digits, bytes) in out.chunks_exact_mut(4).zip(&mut groups) {
+        let bits = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        digits.copy_from_slice(
+            &[18, 12, 6, 0].map(|shift| alphabet[((bits >> shift) & 63) as usize]),
+        );
+    }
+    let rest = groups.remainder();
+    if !rest.is_empty() {
+        let bits = u32::from_be_bytes([0, rest[0], rest.get(1).copied().unwrap_or(0), 0]);
+        let start = data.len() / 3 * 4;
+        for (i, digit) in out[start..start + rest.len() + 1].iter_mut().enumerate() {
+            *digit = alphabet[((bits >> (18 - 6 * i)) & 63) as usize];
+        }
+    }
+    // SAFETY: base64 digits and padding are ASCII.
+    unsafe { String::from_utf8_unchecked(out) }
+}
+
+fn decode_base64_with(input: &[u8], table: &[u8; 256]) -> Result<Vec<u8>, DecodeError> {
+    let (digits, padding) = match input {
+        [digits @ .., b'=', b'='] => (digits, 2),
+        [digits @ .., b'='] => (digits, 1),
+        _ => (input, 0),
+    };
+    if digits.len() % 4 == 1 {
+        return Err(DecodeError::InvalidLength);
+    }
+    if padding > 0 && (digits.len() + padding) % 4 != 0 {
+        return Err(DecodeError::InvalidPadding);
+    }
+    let mut out = vec![0; digits.len() * 3 / 4];
+    let mut groups = digits.chunks_exact(4);
+    for (bytes, group) in out.chunks_exact_mut(3).zip(&mut groups) {
+        let [a, b, c, d] = [0, 1, 2, 3].map(|i| table[group[i] as usize]);
+        if (a | b | c | d) & 0xc0 != 0 {
+            return Err(invalid_byte(digits, table));
+        }
+        let bits = ((a as u32) << 18) | ((b as u32) << 12) | ((c as u32) << 6) | d as u32;
+        bytes.copy_from_slice(&bits.to_be_bytes()[1..]);
+    }
+    let rest = groups.remainder();
+    if !rest.is_empty() {
+        let mut bits = 0;
+        let mut all = 0;
+        for (i, digit) in rest.iter().enumerate() {
+            let value = table[*digit as usize];
+            all |= value;
+            bits |= (value as u32) << (18 - 6 * i);
+        }
+        if all & 0xc0 != 0 {
+            return Err(invalid_byte(digits, table));
+        }
+        // The unused low bits of the last digit must be zero.
+        let len = rest.len() - 1;
+        if bits & (0xff_ffff >> (8 * len)) != 0 {
+            return Err(DecodeError::InvalidPadding);
+        }
+        let start = out.len() - len;
+        out[start..].copy_from_slice(&bits.to_be_bytes()[1..1 + len]);
+    }
+    Ok(out)
+}
+
+/// Returns the error for the first byte of `input` which is not a digit of `table`.
+fn invalid_byte(input: &[u8], table: &[u8; 256]) -> DecodeError {
+    let index = input
+        .iter()
+        .position(|&c| table[c as usize] == INVALID)
+        .unwrap();
+    DecodeError::InvalidByte(index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The test vectors of RFC 4648 Section 10.
+    const VECTORS: [(&str, &str, &str); 7] = [
+        ("", "", ""),
+        ("f", "Zg==", "66"),
+        ("fo", "Zm8=", "666f"),
+        ("foo", "Zm9v", "666f6f"),
+        ("foob", "Zm9vYg==", "666f6f62"),
+        ("fooba", "Zm9vYmE=", "666f6f6261"),
+        ("foobar", "Zm9vYmFy", "666f6f626172"),
+    ];
+
+    #[test]
+    fn test_vectors() {
+        for (data, base64, hex) in VECTORS {
+            assert_eq!(encode_base64(data.as_bytes()), base64);
+            assert_eq!(decode_base64(base64.as_bytes()).unwrap(), data.as_bytes());
+            assert_eq!(encode_hex(data.as_bytes()), hex);
+            assert_eq!(decode_hex(hex.as_bytes()).unwrap(), data.as_bytes());
+
+            let unpadded = base64.trim_end_matches('=');
+            assert_eq!(encode_base64_url(data.as_bytes()), unpadded);
+            assert_eq!(decode_base64(unpadded.as_bytes()).unwrap(), data.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let data: Vec<u8> = (0..=255).rev().collect();
+        for len in 0..data.len() {
+            let data = &data[..len];
+            assert_eq!(decode_hex(encode_hex(data).as_bytes()).unwrap(), data);
+            assert_eq!(decode_base64(encode_base64(data).as_bytes()).unwrap(), data);
+            assert_eq!(
+                decode_base64_url(encode_base64_url(data).as_bytes()).unwrap(),
+                data
+            );
+        }
+        assert_eq!(encode_base64(&[0xfb, 0xff]), "+/8=");
+        assert_eq!(encode_base64_url(&[0xfb, 0xff]), "-_8");
+        assert_eq!(decode_hex(b"C0fFeE").unwrap(), [0xc0, 0xff, 0xee]);
+    }
+
+    #[test]
+    fn test_invalid() {
+        assert_eq!(decode_hex(b"abc"), Err(DecodeError::InvalidLength));
+        assert_eq!(decode_hex(b"0x00"), Err(DecodeError::InvalidByte(1)));
+        assert_eq!(decode_hex(b"0000 0"), Err(DecodeError::InvalidByte(4)));
+
+        assert_eq!(decode_base64(b"Zm9vY"), Err(DecodeError::InvalidLength));
+        assert_eq!(decode_base64(b"Zm9=vw=="), Err(DecodeError::InvalidByte(3)));
+        assert_eq!(
+            decode_base64(b"Zm9v\nYmF"),
+            Err(DecodeError::InvalidByte(4))
+        );
+        assert_eq!(decode_base64(b"Zg="), Err(DecodeError::InvalidPadding));
+        assert_eq!(decode_base64(b"Zm9v="), Err(DecodeError::InvalidPadding));
+        assert_eq!(decode_base64(b"Zm9v===="), Err(DecodeError::InvalidByte(4)));
+        // Nonzero unused bits.
+        assert_eq!(decode_base64(b"Zh=="), Err(DecodeError::InvalidPadding));
+        assert_eq!(decode_base64(b"Zm9="), Err(DecodeError::InvalidPadding));
+        // Each alphabet rejects the digits of the other.
+        assert_eq!(decode_base64(b"-_8"), Err(DecodeError::InvalidByte(0)));
+        assert_eq!(decode_base64_url(b"+/8"), Err(DecodeError::InvalidByte(0)));
+    }
+}
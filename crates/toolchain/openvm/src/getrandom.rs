@@ -1,10 +1,32 @@
-//! [getrandom] custom backend implementations. The implementations are feature-gated. The default
-//! feature enables "getrandom-unsupported", which is a backend that always errors. This should be
-//! used when `getrandom` is never called but pulled in as a dependency unavoidably. If no feature
-//! is enabled, then no custom implementation is registered, and the user must supply their own as
-//! described in the [getrandom] documentation.
+//! [getrandom] custom backend implementations. The implementations are feature-gated:
+//! - "getrandom-unsupported", enabled by default, registers a backend that always errors. This
+//!   should be used when `getrandom` is never called but pulled in as a dependency unavoidably.
+//! - "getrandom-seeded" registers a backend that expands a 32-byte seed supplied by the host with
+//!   ChaCha20, and takes precedence over "getrandom-unsupported". The seed is the keyed hint
+//!   [SEED_KEY], read when random bytes are first requested, and also seeds the randomness of the
+//!   standard library when the `std` feature is enabled. With "getrandom-publish-seed", the seed is
+//!   published with `io::publish_bytes` when it is read, so the public values commit to the
+//!   randomness used by the program.
+//!
+//! If no feature is enabled, then no custom implementation is registered, and the user must supply
+//! their own as described in the [getrandom] documentation.
+//!
+//! The bytes of the seeded backend only depend on the seed, so a program is deterministic for a
+//! given seed, but they are only as unpredictable as the seed: the host chooses the seed, and
+//! whoever knows it can predict every random byte.
+//!
+//! [getrandom]: https://docs.rs/getrandom
 
-#[cfg(feature = "getrandom-unsupported")]
+/// Key of the keyed hint holding the seed of the "getrandom-seeded" backend.
+pub const SEED_KEY: &[u8] = b"openvm:getrandom-seed";
+/// Number of bytes of the seed of the "getrandom-seeded" backend.
+pub const SEED_SIZE: usize = 32;
+
+#[cfg(all(
+    target_os = "zkvm",
+    feature = "getrandom-unsupported",
+    not(feature = "getrandom-seeded")
+))]
 #[no_mangle]
 unsafe extern "Rust" fn __getrandom_v03_custom(
     _dest: *mut u8,
@@ -16,7 +38,11 @@ unsafe extern "Rust" fn __getrandom_v03_custom(
 /// This entrypoint for getrandom is used for versions < 0.3
 // The ABI is defined here: https://github.com/rust-random/getrandom/blob/ce4144b2c16fe1422037c93e267e6a52336e0834/src/custom.rs#L74
 // @dev If you try to use the `getrandom_v02::Error`, it somehow triggers std library
-#[cfg(feature = "getrandom-unsupported")]
+#[cfg(all(
+    target_os = "zkvm",
+    feature = "getrandom-unsupported",
+    not(feature = "getrandom-seeded")
+))]
 #[no_mangle]
 unsafe fn __getrandom_custom(dest: *mut u8, len: usize) -> u32 {
     __getrandom_v03_custom(dest, len)
@@ -24,3 +50,53 @@ unsafe fn __getrandom_custom(dest: *mut u8, len: usize) -> u32 {
         .err()
         .unwrap_or(0) as u32
 }
+
+#[cfg(all(target_os = "zkvm", feature = "getrandom-seeded"))]
+pub(crate) use seeded::fill_bytes;
+
+#[cfg(all(target_os = "zkvm", feature = "getrandom-seeded"))]
+mod seeded {
+    use core::ptr::addr_of_mut;
+
+    use rand_chacha::{
+        rand_core::{RngCore, SeedableRng},
+        ChaCha20Rng,
+    };
+
+    use super::{SEED_KEY, SEED_SIZE};
+
+    static mut RNG: Option<ChaCha20Rng> = None;
+
+    fn read_seed() -> [u8; SEED_SIZE] {
+        let seed: [u8; SEED_SIZE] = crate::io::hint_by_key(SEED_KEY)
+            .try_into()
+            .expect("getrandom seed must be 32 bytes");
+        #[cfg(feature = "getrandom-publish-seed")]
+        crate::io::publish_bytes(&seed);
+        seed
+    }
+
+    /// Fills `dest` with the next bytes of the ChaCha20 stream of the seed.
+    pub(crate) fn fill_bytes(dest: &mut [u8]) {
+        // SAFETY: the zkVM is single-threaded, and the RNG is only accessed here.
+        let rng = unsafe { &mut *addr_of_mut!(RNG) }
+            .get_or_insert_with(|| ChaCha20Rng::from_seed(read_seed()));
+        rng.fill_bytes(dest);
+    }
+
+    #[no_mangle]
+    unsafe extern "Rust" fn __getrandom_v03_custom(
+        dest: *mut u8,
+        len: usize,
+    ) -> Result<(), getrandom::Error> {
+        fill_bytes(core::slice::from_raw_parts_mut(dest, len));
+        Ok(())
+    }
+
+    /// This entrypoint for getrandom is used for versions < 0.3, see the unsupported backend.
+    #[no_mangle]
+    unsafe fn __getrandom_custom(dest: *mut u8, len: usize) -> u32 {
+        fill_bytes(core::slice::from_raw_parts_mut(dest, len));
+        0
+    }
+}
@@ -80,6 +80,32 @@ pub fn hint_input() {
     unimplemented!("hint_input not supported on no_std host")
 }
 
+/// The lengths of the input vectors which have not been read yet, in the order they are read in.
+/// Inputs from stdin are read into memory, as a single input vector.
+pub fn input_lens() -> Vec<usize> {
+    #[cfg(feature = "std")]
+    {
+        HINTS.with_borrow_mut(|hints| {
+            if let HostInputStream::Stdin = hints {
+                use std::io::Read;
+                let mut buf = Vec::new();
+                std::io::stdin()
+                    .read_to_end(&mut buf)
+                    .expect("Failed to read from stdin");
+                let hint = [&(buf.len() as u32).to_le_bytes(), &buf[..]].concat();
+                *hints = HostInputStream::Internal(alloc::vec![hint]);
+            }
+            let HostInputStream::Internal(hints) = hints else {
+                unreachable!()
+            };
+            // Each hint is prefixed with its length.
+            hints.iter().rev().map(|hint| hint.len() - 4).collect()
+        })
+    }
+    #[cfg(not(feature = "std"))]
+    unimplemented!("input_lens not supported on no_std host")
+}
+
 /// Read the next `n` bytes from the hint stream.
 pub fn read_n_bytes(_n: usize) -> Vec<u8> {
     #[cfg(feature = "std")]
@@ -114,4 +140,12 @@ mod tests {
         assert_eq!(read_n_bytes(8), vec![4, 0, 0, 0, 1, 2, 3, 4]);
         assert_eq!(read_vec(), vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_input_lens() {
+        set_hints(vec![vec![1, 2, 3], vec![], vec![4; 9]]);
+        assert_eq!(input_lens(), vec![3, 0, 9]);
+        assert_eq!(read_vec(), vec![1, 2, 3]);
+        assert_eq!(input_lens(), vec![0, 9]);
+    }
 }
@@ -0,0 +1,56 @@
+use alloc::vec::Vec;
+
+/// One end of a channel between the guest and the host, see [channel].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Channel {
+    id: u32,
+}
+
+/// Opens the channel with id `id` between the guest and the host, to exchange messages with the
+/// host while the program runs, e.g. to debug it from a REPL or to fetch its witness incrementally.
+///
+/// Channels are meant for development. The messages received are chosen by the host and are not
+/// constrained by the proof, and an execution which serves the channels interactively is not
+/// deterministic. The host records the messages of an interactive execution in a transcript, and
+/// every other execution, including the one which is proven, replays the transcript instead.
+/// Replaying fails if the program sends a message which differs from the recorded one.
+pub fn channel(id: u32) -> Channel {
+    Channel { id }
+}
+
+impl Channel {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Sends `data` to the host.
+    #[allow(unused_variables)]
+    pub fn send(&self, data: &[u8]) {
+        #[cfg(target_os = "zkvm")]
+        {
+            // The host reads the id of the channel from the start of the message.
+            let mut message = Vec::with_capacity(4 + data.len());
+            message.extend_from_slice(&self.id.to_le_bytes());
+            message.extend_from_slice(data);
+            openvm_rv32im_guest::channel_send(message.as_ptr(), message.len());
+        }
+        #[cfg(not(target_os = "zkvm"))]
+        panic!("channels cannot be used on non-zkVM platforms");
+    }
+
+    /// Receives the next message from the host, through its own hint queue so that the rest of
+    /// the input stream is kept.
+    pub fn recv(&self) -> Vec<u8> {
+        #[cfg(target_os = "zkvm")]
+        {
+            use openvm_rv32im_guest::{with_hint_queue, HintQueue};
+
+            with_hint_queue(HintQueue::Channel, || {
+                openvm_rv32im_guest::channel_recv(self.id);
+                super::read_vec_by_len(super::read_u32() as usize)
+            })
+        }
+        #[cfg(not(target_os = "zkvm"))]
+        panic!("channels cannot be used on non-zkVM platforms");
+    }
+}
@@ -0,0 +1,174 @@
+//! Failure codes committed by [ensure](crate::ensure).
+//!
+//! When an [ensure](crate::ensure) fails, the program reveals a [Failure], the code passed to the
+//! macro together with the source location of the call, as the [FAILURE_SIZE] bytes of the user
+//! public output from [FAILURE_OFFSET], right after the [published](super::publish_bytes) data.
+//! It then exits with exit code 0, so that the failed execution can be proven like any other. A
+//! verifier or a dashboard decodes the reason of the failure from the public values of the proof
+//! with [extract_failure], without running the program again.
+//!
+//! The failure is encoded as five little-endian `u32`: the flag [FAILED], the code, the
+//! [file_hash] of the source file, the line and the column. A successful program leaves these
+//! bytes zero, so the flag tells a failure with code 0 apart from a success.
+
+use core::panic::Location;
+
+use openvm_platform::WORD_SIZE;
+
+use super::{reveal_u32, PUBLISHED_OFFSET, PUBLISHED_SIZE};
+
+/// Byte offset in the user public output of the failure recorded by [fail].
+pub const FAILURE_OFFSET: usize = PUBLISHED_OFFSET + PUBLISHED_SIZE;
+/// Number of bytes of user public output occupied by the failure recorded by [fail].
+pub const FAILURE_SIZE: usize = 5 * WORD_SIZE;
+/// Value of the first word of the failure recorded by [fail].
+pub const FAILED: u32 = 1;
+
+/// The reason a program stopped with [fail].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Failure {
+    pub code: u32,
+    /// The [file_hash] of the path of the source file of the failed check.
+    pub file_hash: u32,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl Failure {
+    /// Returns whether the failed check is in the source file at `file`, the path as given by
+    /// [core::file].
+    pub fn is_in_file(&self, file: &str) -> bool {
+        self.file_hash == file_hash(file)
+    }
+
+    /// Encodes the failure into the format described in the [module documentation](self).
+    pub fn encode(&self) -> [u8; FAILURE_SIZE] {
+        let mut bytes = [0; FAILURE_SIZE];
+        for (chunk, word) in bytes.chunks_exact_mut(WORD_SIZE).zip([
+            FAILED,
+            self.code,
+            self.file_hash,
+            self.line,
+            self.column,
+        ]) {
+            chunk.copy_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+/// The 32-bit FNV-1a hash of `file`, which identifies a source file in a [Failure].
+pub const fn file_hash(file: &str) -> u32 {
+    let bytes = file.as_bytes();
+    let mut hash: u32 = 0x811c9dc5;
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u32;
+        hash = hash.wrapping_mul(0x01000193);
+        i += 1;
+    }
+    hash
+}
+
+/// Extracts the failure recorded by [fail] from the user public output `public_values`, or
+/// returns `None` if the program did not fail or the output is too short to hold a failure.
+pub fn extract_failure(public_values: &[u8]) -> Option<Failure> {
+    let bytes = public_values.get(FAILURE_OFFSET..FAILURE_OFFSET + FAILURE_SIZE)?;
+    let word = |i: usize| {
+        u32::from_le_bytes(
+            bytes[i * WORD_SIZE..(i + 1) * WORD_SIZE]
+                .try_into()
+                .unwrap(),
+        )
+    };
+    if word(0) != FAILED {
+        return None;
+    }
+    Some(Failure {
+        code: word(1),
+        file_hash: word(2),
+        line: word(3),
+        column: word(4),
+    })
+}
+
+/// Reveals `code` and the location of the caller as a [Failure] and exits the program with exit
+/// code 0, so that the failure is part of the public values of the proof. The failure is also
+/// printed. Usually called through [ensure](crate::ensure).
+///
+/// Note: this overwrites any data previously revealed at bytes [FAILURE_OFFSET] to
+/// `FAILURE_OFFSET + FAILURE_SIZE` of the user public output.
+#[track_caller]
+pub fn fail(code: u32) -> ! {
+    let location = Location::caller();
+    let failure = Failure {
+        code,
+        file_hash: file_hash(location.file()),
+        line: location.line(),
+        column: location.column(),
+    };
+    for (i_u32, chunk) in failure.encode().chunks_exact(WORD_SIZE).enumerate() {
+        let x = u32::from_le_bytes(chunk.try_into().unwrap());
+        reveal_u32(x, FAILURE_OFFSET / WORD_SIZE + i_u32);
+    }
+    let message = alloc::format!("ensure failed with code {code} at {location}");
+    #[cfg(target_os = "zkvm")]
+    {
+        super::println(&message);
+        openvm_platform::rust_rt::terminate::<0>();
+        unreachable!()
+    }
+    #[cfg(not(target_os = "zkvm"))]
+    panic!("{message}")
+}
+
+/// Stops the program with [fail] if `cond` is false, committing the failure code `code` and the
+/// source location of the check to the user public output, see the
+/// [failure](crate::io::failure) module.
+///
+/// ```ignore
+/// openvm::ensure!(balance >= amount, ERR_INSUFFICIENT_BALANCE);
+/// ```
+#[macro_export]
+macro_rules! ensure {
+    ($cond:expr, $code:expr $(,)?) => {
+        if !$cond {
+            $crate::io::failure::fail($code);
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_failure() {
+        let failure = Failure {
+            code: 42,
+            file_hash: file_hash("src/main.rs"),
+            line: 7,
+            column: 5,
+        };
+        let mut public_values = [0u8; FAILURE_OFFSET + FAILURE_SIZE];
+        assert_eq!(extract_failure(&public_values), None);
+        public_values[FAILURE_OFFSET..].copy_from_slice(&failure.encode());
+        assert_eq!(extract_failure(&public_values), Some(failure));
+        assert!(failure.is_in_file("src/main.rs"));
+        assert!(!failure.is_in_file("src/lib.rs"));
+        assert_eq!(extract_failure(&public_values[1..]), None);
+
+        // A failure with code 0 is told apart from a success by the flag.
+        let failure = Failure { code: 0, ..failure };
+        public_values[FAILURE_OFFSET..].copy_from_slice(&failure.encode());
+        assert_eq!(extract_failure(&public_values), Some(failure));
+    }
+
+    #[test]
+    #[should_panic = "ensure failed with code 3"]
+    fn test_ensure() {
+        let x = core::hint::black_box(2);
+        crate::ensure!(x == 2, 1);
+        crate::ensure!(x == 4, 3);
+    }
+}
@@ -0,0 +1,63 @@
+//! Inputs of a guest program described by a struct shared between the host and the guest.
+//!
+//! The host writes the inputs of a guest as a sequence of input streams, which the guest must
+//! read in the same order with the same types. Deriving [Inputs] for a struct turns it into the
+//! schema of the inputs: the host writes one input stream per field in declaration order, with
+//! `WitnessBuilder::inputs` of the SDK, and the guest reads them back with [read_inputs]. Since
+//! both sides go through the same struct, they cannot disagree on the order of the inputs.
+//!
+//! ```ignore
+//! #[derive(openvm::io::Inputs)]
+//! pub struct Inputs {
+//!     pub n: u64,
+//!     pub data: Vec<u8>,
+//! }
+//!
+//! // guest
+//! let Inputs { n, data } = openvm::io::read_inputs();
+//! ```
+
+pub use openvm_io_derive::Inputs;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// A struct whose fields are written as consecutive input streams, see the
+/// [module documentation](self). Implement it with `#[derive(Inputs)]`.
+pub trait Inputs: Sized {
+    /// Writes every field with `writer`, in declaration order.
+    fn write_fields<W: InputWrite>(&self, writer: &mut W);
+
+    /// Reads every field with `reader`, in declaration order.
+    fn read_fields<R: InputRead>(reader: &mut R) -> Self;
+}
+
+/// A value which can be a field of [Inputs].
+pub trait InputValue: Serialize + DeserializeOwned {}
+
+impl<T: Serialize + DeserializeOwned> InputValue for T {}
+
+/// Writes each value as its own input stream.
+pub trait InputWrite {
+    fn write<T: Serialize>(&mut self, value: &T);
+}
+
+/// Reads each value from its own input stream.
+pub trait InputRead {
+    fn read<T: DeserializeOwned>(&mut self) -> T;
+}
+
+/// Reads values from the next input streams with [read](super::read).
+#[cfg(feature = "hints")]
+pub struct InputStreams;
+
+#[cfg(feature = "hints")]
+impl InputRead for InputStreams {
+    fn read<T: DeserializeOwned>(&mut self) -> T {
+        super::read()
+    }
+}
+
+/// Reads `T` from the next input streams, one per field, as written by the host.
+#[cfg(feature = "hints")]
+pub fn read_inputs<T: Inputs>() -> T {
+    T::read_fields(&mut InputStreams)
+}
@@ -0,0 +1,113 @@
+//! Canonical encoding of a committed value into the user public output.
+//!
+//! The journal occupies the first [JOURNAL_SIZE] bytes of the user public output. The first word
+//! is the length in bytes of the value serialized with [crate::serde]. If the serialized value fits
+//! in the remaining [JOURNAL_INLINE_SIZE] bytes, it is stored there, zero padded. Otherwise, the
+//! remaining bytes hold the first [JOURNAL_INLINE_SIZE] bytes of the SHA-256 digest of the
+//! serialized value.
+
+use alloc::vec::Vec;
+
+use openvm_platform::WORD_SIZE;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Number of bytes of user public output occupied by the journal.
+pub const JOURNAL_SIZE: usize = 32;
+/// Maximum number of bytes of a serialized value that is stored in the journal as-is.
+pub const JOURNAL_INLINE_SIZE: usize = JOURNAL_SIZE - WORD_SIZE;
+
+/// A decoded journal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Journal<T> {
+    /// The committed value, which was small enough to be stored in the journal.
+    Value(T),
+    /// The length and truncated SHA-256 digest of the serialized committed value.
+    Digest {
+        len: usize,
+        digest: [u8; JOURNAL_INLINE_SIZE],
+    },
+}
+
+impl<T: Serialize> Journal<T> {
+    /// Returns whether this journal is the journal of `value`.
+    pub fn matches(&self, value: &T) -> bool {
+        match self {
+            Self::Value(v) => crate::serde::to_vec(v).ok() == crate::serde::to_vec(value).ok(),
+            Self::Digest { len, digest } => {
+                let journal = encode_journal(value);
+                journal[..WORD_SIZE] == (*len as u32).to_le_bytes()
+                    && journal[WORD_SIZE..] == digest[..]
+            }
+        }
+    }
+}
+
+/// Encodes `value` into the journal format described in the [module documentation](self).
+pub fn encode_journal<T: Serialize + ?Sized>(value: &T) -> [u8; JOURNAL_SIZE] {
+    let words = crate::serde::to_vec(value).expect("failed to serialize committed value");
+    let bytes: Vec<u8> = words.into_iter().flat_map(|w| w.to_le_bytes()).collect();
+    let mut journal = [0u8; JOURNAL_SIZE];
+    journal[..WORD_SIZE].copy_from_slice(&(bytes.len() as u32).to_le_bytes());
+    if bytes.len() <= JOURNAL_INLINE_SIZE {
+        journal[WORD_SIZE..WORD_SIZE + bytes.len()].copy_from_slice(&bytes);
+    } else {
+        let digest = super::sha256(&[&bytes]);
+        journal[WORD_SIZE..].copy_from_slice(&digest[..JOURNAL_INLINE_SIZE]);
+    }
+    journal
+}
+
+/// Decodes the journal from the user public output `public_values`, as committed by
+/// [super::commit].
+pub fn journal<T: DeserializeOwned>(public_values: &[u8]) -> crate::serde::Result<Journal<T>> {
+    if public_values.len() < JOURNAL_SIZE {
+        return Err(crate::serde::Error::DeserializeUnexpectedEnd);
+    }
+    let len = u32::from_le_bytes(public_values[..WORD_SIZE].try_into().unwrap()) as usize;
+    let body = &public_values[WORD_SIZE..JOURNAL_SIZE];
+    if len <= JOURNAL_INLINE_SIZE {
+        if len % WORD_SIZE != 0 || body[len..].iter().any(|&b| b != 0) {
+            return Err(crate::serde::Error::Custom("non-canonical journal".into()));
+        }
+        crate::serde::from_slice(&body[..len]).map(Journal::Value)
+    } else {
+        Ok(Journal::Digest {
+            len,
+            digest: body.try_into().unwrap(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_inline_journal() {
+        let value = (7u32, [1u8, 2, 3]);
+        let bytes = encode_journal(&value);
+        let decoded = journal::<(u32, [u8; 3])>(&bytes).unwrap();
+        assert_eq!(decoded, Journal::Value(value));
+        assert!(decoded.matches(&value));
+        assert!(!decoded.matches(&(8u32, [1u8, 2, 3])));
+    }
+
+    #[test]
+    fn test_digest_journal() {
+        let value: Vec<u32> = (0..100).collect();
+        let bytes = encode_journal(&value);
+        let decoded = journal::<Vec<u32>>(&bytes).unwrap();
+        assert!(matches!(decoded, Journal::Digest { len: 404, .. }));
+        assert!(decoded.matches(&value));
+        assert!(!decoded.matches(&(1..101).collect()));
+    }
+
+    #[test]
+    fn test_non_canonical_journal() {
+        let mut bytes = encode_journal(&1u32);
+        bytes[JOURNAL_SIZE - 1] = 1;
+        assert!(journal::<u32>(&bytes).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! JSON input and output for guests which consume JSON data, such as oracle responses or API
+//! payloads, built on `serde_json` without the `std` IO traits.
+//!
+//! [read_json] deserializes the next input stream, written by the host as raw JSON bytes, e.g.
+//! with `StdIn::write_bytes`. [reveal_json] publishes the JSON encoding of a value with
+//! [publish_bytes], so the host can check it with
+//! [published_digest](super::published_digest). For streaming, [HintReader] reads an input stream
+//! through a buffer and [PublishWriter] collects output to publish. With the `std` feature they
+//! implement `std::io::Read` and `std::io::Write`, for use with `serde_json::from_reader` and
+//! `serde_json::to_writer`.
+
+use alloc::{vec, vec::Vec};
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{publish_bytes, read_vec, Reader};
+use crate::serde::WordRead;
+
+/// Number of bytes [HintReader] reads from the hint stream at a time.
+const HINT_READER_BUF_SIZE: usize = 1024;
+
+/// Deserialize the next input stream, holding the JSON encoding of a `T`, into a `T`.
+pub fn read_json<T: DeserializeOwned>() -> T {
+    serde_json::from_slice(&read_vec()).expect("input stream is not valid JSON")
+}
+
+/// Publish the JSON encoding of `value` with [publish_bytes], in a single call.
+pub fn reveal_json<T: Serialize + ?Sized>(value: &T) {
+    let json = serde_json::to_vec(value).expect("failed to serialize value to JSON");
+    publish_bytes(&json);
+}
+
+/// Buffered reader of the bytes of the next input stream.
+pub struct HintReader {
+    reader: Reader,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl HintReader {
+    /// Resets the hint stream to the next input stream, like [Reader::new].
+    pub fn new() -> Self {
+        Self {
+            reader: Reader::new(),
+            buf: vec![],
+            pos: 0,
+        }
+    }
+
+    /// Reads up to `out.len()` bytes into `out` and returns the number of bytes read, which is
+    /// zero once the input stream has been read to the end.
+    pub fn read(&mut self, out: &mut [u8]) -> usize {
+        if self.pos == self.buf.len() {
+            let len = self.reader.bytes_remaining.min(HINT_READER_BUF_SIZE);
+            self.buf.resize(len, 0);
+            self.reader
+                .read_padded_bytes(&mut self.buf)
+                .expect("input stream ended early");
+            self.pos = 0;
+        }
+        let len = out.len().min(self.buf.len() - self.pos);
+        out[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        len
+    }
+
+    /// Appends the rest of the input stream to `out`.
+    pub fn read_to_end(&mut self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.buf[self.pos..]);
+        self.pos = self.buf.len();
+        // The hint stream is read into a new buffer, as it is written a word at a time and the end
+        // of `out` need not be word aligned.
+        let mut rest = vec![0u8; self.reader.bytes_remaining];
+        self.reader
+            .read_padded_bytes(&mut rest)
+            .expect("input stream ended early");
+        out.extend_from_slice(&rest);
+    }
+}
+
+impl Default for HintReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Read for HintReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Ok(HintReader::read(self, buf))
+    }
+}
+
+/// Buffered writer whose contents are published with [publish_bytes] in a single call by
+/// [PublishWriter::finish], so the commitment does not depend on how the contents were written.
+#[derive(Default)]
+pub struct PublishWriter {
+    buf: Vec<u8>,
+}
+
+impl PublishWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Publishes everything written so far.
+    pub fn finish(self) {
+        publish_bytes(&self.buf);
+    }
+}
+
+impl core::fmt::Write for PublishWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        self.write(s.as_bytes());
+        Ok(())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::io::Write for PublishWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        PublishWriter::write(self, buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
@@ -1,48 +1,178 @@
 //! User IO functions
 
+#[cfg(feature = "hints")]
 use alloc::vec::Vec;
-#[cfg(target_os = "zkvm")]
+#[cfg(all(target_os = "zkvm", feature = "hints"))]
 use core::alloc::Layout;
 use core::fmt::Write;
 
-#[cfg(target_os = "zkvm")]
+#[cfg(all(feature = "hints", any(feature = "serde", target_os = "zkvm")))]
+use openvm_platform::WORD_SIZE;
+#[cfg(all(target_os = "zkvm", feature = "hints"))]
 use openvm_rv32im_guest::{hint_buffer_u32, hint_input, hint_store_u32};
-use serde::de::DeserializeOwned;
+#[cfg(all(feature = "serde", feature = "hints"))]
+use serde::de::{Deserialize, DeserializeOwned};
+#[cfg(feature = "serde")]
+use serde::Serialize;
 
-#[cfg(not(target_os = "zkvm"))]
+#[cfg(all(not(target_os = "zkvm"), feature = "hints"))]
 use crate::host::{hint_input, read_n_bytes, read_u32};
-use crate::serde::Deserializer;
+#[cfg(feature = "hints")]
+use crate::memory::{alloc_aligned, AlignedBytes};
+#[cfg(all(feature = "serde", feature = "hints"))]
+use crate::serde::{from_words, Deserializer, WordRead};
 
+#[cfg(feature = "hints")]
+mod channel;
+#[cfg(feature = "serde")]
+pub mod failure;
+#[cfg(feature = "serde")]
+pub mod inputs;
+#[cfg(feature = "serde")]
+mod journal;
+#[cfg(feature = "json")]
+pub mod json;
+#[cfg(feature = "serde")]
+mod publish;
+#[cfg(all(feature = "serde", feature = "hints"))]
 mod read;
 
+#[cfg(feature = "hints")]
+pub use channel::{channel, Channel};
+#[cfg(feature = "serde")]
+pub use failure::{extract_failure, fail, Failure, FAILED, FAILURE_OFFSET, FAILURE_SIZE};
+#[cfg(all(feature = "serde", feature = "hints"))]
+pub use inputs::read_inputs;
+#[cfg(feature = "serde")]
+pub use inputs::Inputs;
+#[cfg(feature = "serde")]
+pub use journal::{encode_journal, journal, Journal, JOURNAL_INLINE_SIZE, JOURNAL_SIZE};
 pub use openvm_platform::print::{print, println};
+#[cfg(feature = "serde")]
+pub use publish::{
+    challenge, derive_challenge, extract_published_digest, publish_bytes, published_digest,
+    PUBLISHED_OFFSET, PUBLISHED_SIZE,
+};
+#[cfg(all(feature = "serde", feature = "hints"))]
+pub use read::Reader;
 
 /// Read `size: u32` and then `size` bytes from the hint stream into a vector.
+#[cfg(feature = "hints")]
 pub fn read_vec() -> Vec<u8> {
     hint_input();
     read_vec_by_len(read_u32() as usize)
 }
 
+/// Read `size: u32` and then `size` bytes from the hint stream into a buffer aligned to `align`
+/// bytes, like [read_vec]. Use it for inputs which are cast in place to types with a larger
+/// alignment than `u8`, such as the `IntMod` arrays of the modular arithmetic intrinsics.
+///
+/// # Panics
+/// If `align` is not a power of two.
+#[cfg(feature = "hints")]
+pub fn read_vec_aligned(align: usize) -> AlignedBytes {
+    hint_input();
+    let len = read_u32() as usize;
+    let mut bytes = alloc_aligned(len, align);
+    #[cfg(target_os = "zkvm")]
+    hint_buffer_u32!(bytes.as_mut_ptr(), bytes.capacity() / WORD_SIZE);
+    #[cfg(not(target_os = "zkvm"))]
+    bytes.copy_from_slice(&read_n_bytes(len));
+    bytes
+}
+
+/// The length in bytes of the next input stream, which the next call to [read_vec] or [read]
+/// consumes, or `None` if all input streams have been read. No input stream is consumed.
+///
+/// Like the inputs themselves, the length is hinted by the host and is not constrained by the
+/// proof.
+#[cfg(feature = "hints")]
+pub fn peek_len() -> Option<usize> {
+    let [count, next, _] = input_lens();
+    (count > 0).then_some(next)
+}
+
+/// The number of input streams which have not been read yet. Programs taking a variable number
+/// of inputs, e.g. one per transaction, can read them until this is zero instead of reading their
+/// number first.
+#[cfg(feature = "hints")]
+pub fn remaining_inputs() -> usize {
+    input_lens()[0]
+}
+
+/// The total length in bytes of the input streams which have not been read yet.
+#[cfg(feature = "hints")]
+pub fn remaining_hint_bytes() -> usize {
+    input_lens()[2]
+}
+
+/// The number of remaining input streams, the length of the next one and their total length.
+#[cfg(feature = "hints")]
+fn input_lens() -> [usize; 3] {
+    #[cfg(target_os = "zkvm")]
+    {
+        use openvm_rv32im_guest::{hint_input_len, with_hint_queue, HintQueue};
+
+        let mut words = [0u32; 3];
+        with_hint_queue(HintQueue::InputLen, || {
+            hint_input_len();
+            let (ptr, len) = (words.as_mut_ptr(), words.len());
+            hint_buffer_u32!(ptr, len);
+        });
+        words.map(|word| word as usize)
+    }
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        let lens = crate::host::input_lens();
+        [
+            lens.len(),
+            lens.first().copied().unwrap_or(0),
+            lens.iter().sum(),
+        ]
+    }
+}
+
 /// Deserialize the next item from the next input stream into a type `T`.
+#[cfg(all(feature = "serde", feature = "hints"))]
 pub fn read<T: DeserializeOwned>() -> T {
-    let reader = read::Reader::new();
+    let reader = Reader::new();
     let mut deserializer = Deserializer::new(reader);
     T::deserialize(&mut deserializer).unwrap()
 }
 
+/// Deserialize the next item from the next input stream into a type `T` which borrows from the
+/// input, e.g. a struct with `&str` or `&[u8]` fields.
+///
+/// Unlike [read], which copies every string and byte vector into its own allocation, the input
+/// stream is read into a single buffer and the borrowed fields of `T` point into it. The buffer
+/// is never freed, so this is meant for large inputs which are read once, such as witnesses.
+/// See [from_words] for how byte slices must be serialized.
+#[cfg(all(feature = "serde", feature = "hints"))]
+pub fn read_borrowed<T: Deserialize<'static>>() -> T {
+    let mut reader = Reader::new();
+    let num_bytes = reader.bytes_remaining;
+    assert_eq!(
+        num_bytes % WORD_SIZE,
+        0,
+        "serialized inputs are made of words"
+    );
+    let mut words = alloc::vec![0u32; num_bytes / WORD_SIZE];
+    reader.read_words(&mut words).unwrap();
+    from_words(words.leak()).unwrap()
+}
+
+#[cfg(feature = "hints")]
 pub fn foo() {
     // let reader = read::Reader::new();
     hint_input();
     // let bytes_remaining = read_u32() as usize;
     // let bytes: Vec<u8> = read_n_bytes(4);
-
-
 }
 
 /// Read the next 4 bytes from the hint stream into a register.
 /// Because [hint_store_u32] stores a word to memory, this function first reads to memory and then
 /// loads from memory to register.
-#[cfg(target_os = "zkvm")]
+#[cfg(all(target_os = "zkvm", feature = "hints"))]
 #[inline(always)]
 #[allow(asm_sub_register)]
 pub fn read_u32() -> u32 {
@@ -56,6 +186,7 @@ pub fn read_u32() -> u32 {
     result
 }
 
+#[cfg(all(feature = "serde", feature = "hints"))]
 fn hint_store_word(ptr: *mut u32) {
     #[cfg(target_os = "zkvm")]
     hint_store_u32!(ptr);
@@ -66,6 +197,7 @@ fn hint_store_word(ptr: *mut u32) {
 }
 
 /// Load hints by key and append into the input stream.
+#[cfg(feature = "hints")]
 #[allow(unused_variables)]
 #[inline(always)]
 pub fn hint_load_by_key(key: &[u8]) {
@@ -75,7 +207,31 @@ pub fn hint_load_by_key(key: &[u8]) {
     panic!("hint_load_by_key cannot run on non-zkVM platforms");
 }
 
+/// Read the keyed hint with the given key, registered by the host with `StdIn::add_keyed_hint`.
+///
+/// Unlike the input streams, keyed hints can be read in any order and any number of times, so
+/// libraries can look up their own hints without coordinating with the rest of the program.
+/// Keyed hints share the key-value store of [hint_load_by_key]. The program fails if there is no
+/// hint with the given key. Keyed hints are read through their own hint queue, so reading one
+/// keeps the rest of the input stream a [Reader] is reading.
+#[cfg(feature = "hints")]
+#[allow(unused_variables)]
+pub fn hint_by_key(key: &[u8]) -> Vec<u8> {
+    #[cfg(target_os = "zkvm")]
+    {
+        use openvm_rv32im_guest::{with_hint_queue, HintQueue};
+
+        with_hint_queue(HintQueue::ByKey, || {
+            openvm_rv32im_guest::hint_by_key(key.as_ptr(), key.len() as u32);
+            read_vec_by_len(read_u32() as usize)
+        })
+    }
+    #[cfg(not(target_os = "zkvm"))]
+    panic!("hint_by_key cannot run on non-zkVM platforms");
+}
+
 /// Read the next `len` bytes from the hint stream into a vector.
+#[cfg(feature = "hints")]
 pub(crate) fn read_vec_by_len(len: usize) -> Vec<u8> {
     let num_words = len.div_ceil(4);
     let capacity = num_words * 4;
@@ -121,6 +277,51 @@ pub fn reveal_bytes32(bytes: [u8; 32]) {
     }
 }
 
+/// Commits to the user public values `public_values`, one byte per value, with the hash function
+/// `hash`: the bytes are right-padded with zeros to a power of two number of 32 byte leaves, each
+/// leaf is hashed and each internal node is the hash of the concatenation of its children.
+///
+/// With `openvm_keccak256::keccak256` or `openvm_sha2::sha256` as `hash`, this is the commitment
+/// which the SDK computes for the Keccak256 and SHA-256 public values hashers and which the EVM
+/// verifier checks, so that a guest can compute it, e.g. to commit to the public values of the
+/// proofs it aggregates.
+pub fn public_values_commit(public_values: &[u8], hash: impl Fn(&[u8]) -> [u8; 32]) -> [u8; 32] {
+    let num_leaves = public_values.len().div_ceil(32).next_power_of_two();
+    let mut bytes = public_values.to_vec();
+    bytes.resize(num_leaves * 32, 0);
+    let mut nodes: alloc::vec::Vec<_> = bytes.chunks_exact(32).map(&hash).collect();
+    while nodes.len() > 1 {
+        nodes = nodes
+            .chunks_exact(2)
+            .map(|c| hash(&[c[0], c[1]].concat()))
+            .collect();
+    }
+    nodes[0]
+}
+
+/// Commit `value` to the journal, i.e. publish its canonical encoding as the first
+/// [JOURNAL_SIZE] bytes of the user public output. Values that do not fit are committed by their
+/// SHA-256 digest, computed with the sha256 extension. The host can decode the journal with
+/// [journal].
+///
+/// Note: this overwrites any previously committed value and any data previously published in
+/// the first [JOURNAL_SIZE] bytes of the user public output.
+#[cfg(feature = "serde")]
+pub fn commit<T: Serialize + ?Sized>(value: &T) {
+    let journal = encode_journal(value);
+    for (i_u32, chunk) in journal.chunks_exact(4).enumerate() {
+        let x = u32::from_le_bytes(chunk.try_into().unwrap());
+        reveal_u32(x, i_u32);
+    }
+}
+
+/// Returns the SHA-256 digest of the concatenation of `parts`, computed with the sha256 extension
+/// in the guest.
+#[cfg(feature = "serde")]
+fn sha256(parts: &[&[u8]]) -> [u8; 32] {
+    openvm_sha2::sha256(&parts.concat())
+}
+
 /// Publish `x` as the `index`-th u32 output.
 ///
 /// This is a low-level API. It is **highly recommended** that developers use [reveal_bytes32]
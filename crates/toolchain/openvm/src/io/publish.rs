@@ -0,0 +1,136 @@
+//! Commitment to data of arbitrary length published by the guest.
+//!
+//! Each call to [publish_bytes] absorbs its data into a SHA-256 hash chain, whose state is the
+//! digest of the previous state, the length of the data as a little-endian `u32` and the data. The
+//! state starts at zero and is revealed after every call as the [PUBLISHED_SIZE] bytes of the
+//! user public output from [PUBLISHED_OFFSET], right after the [journal](super::journal), so the
+//! final public values commit to everything published. The host recomputes the commitment from
+//! the published data with [published_digest]. The guest computes the digests with the sha256
+//! extension, which the VM config must therefore include.
+//!
+//! The hash chain also serves as a Fiat-Shamir transcript: [challenge] derives pseudorandom bytes
+//! from the current state, so a challenge is bound to everything published before it was drawn
+//! and the guest needs no randomness from the host. The host rederives the challenges from the
+//! published data with [derive_challenge].
+
+use openvm_platform::WORD_SIZE;
+
+use super::{reveal_u32, sha256, JOURNAL_SIZE};
+
+/// Byte offset in the user public output of the commitment to the published data.
+pub const PUBLISHED_OFFSET: usize = JOURNAL_SIZE;
+/// Number of bytes of user public output occupied by the commitment to the published data.
+pub const PUBLISHED_SIZE: usize = 32;
+
+/// Domain separator of challenges, which keeps them distinct from states of the hash chain.
+const CHALLENGE_DOMAIN: &[u8] = b"openvm-challenge";
+
+/// The state of the hash chain.
+static mut STATE: [u8; PUBLISHED_SIZE] = [0; PUBLISHED_SIZE];
+/// The number of challenges drawn since the state last changed.
+static mut NUM_CHALLENGES: u32 = 0;
+
+/// Returns the state of the hash chain after absorbing `data` into `state`.
+fn absorb(state: &[u8; PUBLISHED_SIZE], data: &[u8]) -> [u8; PUBLISHED_SIZE] {
+    sha256(&[state, &(data.len() as u32).to_le_bytes(), data])
+}
+
+/// Publish `data` as part of the user public output, by absorbing it into the commitment
+/// described in the [module documentation](self). Unlike [reveal_bytes32](super::reveal_bytes32),
+/// the data can be of any length and is published in as many calls as convenient, e.g. while it
+/// is computed.
+///
+/// Note: this overwrites any data previously revealed at bytes [PUBLISHED_OFFSET] to
+/// `PUBLISHED_OFFSET + PUBLISHED_SIZE` of the user public output.
+pub fn publish_bytes(data: &[u8]) {
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    let state = absorb(&unsafe { STATE }, data);
+    unsafe {
+        STATE = state;
+        NUM_CHALLENGES = 0;
+    }
+    for (i_u32, chunk) in state.chunks_exact(WORD_SIZE).enumerate() {
+        let x = u32::from_le_bytes(chunk.try_into().unwrap());
+        reveal_u32(x, PUBLISHED_OFFSET / WORD_SIZE + i_u32);
+    }
+}
+
+/// Draws a challenge bound to all data published so far with [publish_bytes] and to `label`.
+/// Challenges drawn between the same two calls to [publish_bytes] are distinct, even if they have
+/// the same label.
+///
+/// The challenge is only bound to data which was published before it was drawn, so a protocol
+/// must publish every message the challenge depends on first.
+pub fn challenge(label: &[u8]) -> [u8; 32] {
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    let (state, index) = unsafe { (STATE, NUM_CHALLENGES) };
+    unsafe { NUM_CHALLENGES += 1 };
+    derive_challenge(&state, index, label)
+}
+
+/// Derives the `index`-th challenge with `label` drawn by [challenge] while the hash chain was in
+/// state `state`, which is computed from the data published so far with [published_digest].
+pub fn derive_challenge(state: &[u8; PUBLISHED_SIZE], index: u32, label: &[u8]) -> [u8; 32] {
+    sha256(&[
+        CHALLENGE_DOMAIN,
+        state,
+        &index.to_le_bytes(),
+        &(label.len() as u32).to_le_bytes(),
+        label,
+    ])
+}
+
+/// Computes the commitment revealed by calling [publish_bytes] on each item of `data` in order.
+pub fn published_digest<I>(data: I) -> [u8; PUBLISHED_SIZE]
+where
+    I: IntoIterator,
+    I::Item: AsRef<[u8]>,
+{
+    data.into_iter().fold([0; PUBLISHED_SIZE], |state, data| {
+        absorb(&state, data.as_ref())
+    })
+}
+
+/// Extracts the commitment to the published data from the user public output `public_values`,
+/// or returns `None` if it is too short to hold one.
+pub fn extract_published_digest(public_values: &[u8]) -> Option<[u8; PUBLISHED_SIZE]> {
+    public_values
+        .get(PUBLISHED_OFFSET..PUBLISHED_OFFSET + PUBLISHED_SIZE)
+        .map(|digest| digest.try_into().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_published_digest() {
+        // Splitting the data differently changes the commitment.
+        let whole = published_digest([b"hello world".as_slice()]);
+        let split = published_digest([b"hello ".as_slice(), b"world"]);
+        assert_ne!(whole, split);
+        assert_eq!(published_digest::<[&[u8]; 0]>([]), [0; PUBLISHED_SIZE]);
+
+        let mut public_values = [0u8; PUBLISHED_OFFSET + PUBLISHED_SIZE];
+        public_values[PUBLISHED_OFFSET..].copy_from_slice(&split);
+        assert_eq!(extract_published_digest(&public_values), Some(split));
+        assert_eq!(extract_published_digest(&public_values[1..]), None);
+    }
+
+    #[test]
+    fn test_challenge() {
+        // Challenges drawn before anything is published only depend on their index and label.
+        let first = challenge(b"alpha");
+        assert_eq!(first, derive_challenge(&[0; PUBLISHED_SIZE], 0, b"alpha"));
+        assert_ne!(challenge(b"alpha"), first);
+
+        publish_bytes(b"message");
+        let state = published_digest([b"message"]);
+        assert_eq!(challenge(b"alpha"), derive_challenge(&state, 0, b"alpha"));
+        assert_eq!(challenge(b"beta"), derive_challenge(&state, 1, b"beta"));
+        assert_ne!(
+            derive_challenge(&state, 0, b"alpha"),
+            derive_challenge(&state, 0, b"beta")
+        );
+    }
+}
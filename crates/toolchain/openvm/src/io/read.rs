@@ -1,17 +1,24 @@
+use alloc::{vec, vec::Vec};
 use core::mem::MaybeUninit;
 
 use openvm_platform::WORD_SIZE;
 #[cfg(target_os = "zkvm")]
 use openvm_rv32im_guest::hint_buffer_u32;
+use serde::de::DeserializeOwned;
 
 use super::hint_store_word;
-use crate::serde::WordRead;
+use crate::serde::{Deserializer, WordRead};
 
 /// Provides a Reader for reading serialized data from the hint stream.
-#[derive(Copy, Clone)]
+///
+/// Besides deserializing a single value from an input stream, as [read](super::read) does, a
+/// reader can read an input stream written by the host as a sequence of frames, each a byte
+/// length as a little-endian `u32` followed by that many bytes, padded to a multiple of 4 bytes.
 pub struct Reader {
     /// The number of bytes remaining in the hint stream.
     pub bytes_remaining: usize,
+    /// The length of the next frame, if it has already been read by [Reader::peek_len].
+    next_frame_len: Option<usize>,
 }
 
 impl Reader {
@@ -22,7 +29,52 @@ impl Reader {
     pub fn new() -> Self {
         super::hint_input();
         let bytes_remaining = super::read_u32() as usize;
-        Self { bytes_remaining }
+        Self {
+            bytes_remaining,
+            next_frame_len: None,
+        }
+    }
+
+    /// Returns the length in bytes of the next frame without consuming it, or `None` if there
+    /// are no frames left in the input stream.
+    pub fn peek_len(&mut self) -> Option<usize> {
+        if self.next_frame_len.is_none() && self.bytes_remaining > 0 {
+            let mut len = [0u32];
+            self.read_words(&mut len)
+                .expect("input stream ended in a frame length");
+            self.next_frame_len = Some(len[0] as usize);
+        }
+        self.next_frame_len
+    }
+
+    /// Reads the next frame, or returns `None` if there are no frames left in the input stream.
+    pub fn read_frame(&mut self) -> Option<Vec<u8>> {
+        let len = self.peek_len()?;
+        self.next_frame_len = None;
+        let mut bytes = vec![0u8; len];
+        self.read_padded_bytes(&mut bytes)
+            .expect("input stream ended in a frame");
+        Some(bytes)
+    }
+
+    /// Reads the next frame and deserializes it into a type `T`, or returns `None` if there are
+    /// no frames left in the input stream. The frame must have been serialized with
+    /// [to_vec](crate::serde::to_vec).
+    pub fn read_as<T: DeserializeOwned>(&mut self) -> Option<T> {
+        let len = self.peek_len()?;
+        self.next_frame_len = None;
+        assert_eq!(len % WORD_SIZE, 0, "serialized frames are made of words");
+        let mut words = vec![0u32; len / WORD_SIZE];
+        self.read_words(&mut words)
+            .expect("input stream ended in a frame");
+        let mut deserializer = Deserializer::new(words.as_slice());
+        Some(T::deserialize(&mut deserializer).unwrap())
+    }
+}
+
+impl Default for Reader {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
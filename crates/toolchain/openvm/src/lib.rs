@@ -4,7 +4,7 @@
 #![deny(rustdoc::broken_intra_doc_links)]
 #![cfg_attr(docsrs, feature(doc_cfg, doc_auto_cfg))]
 
-#[macro_use]
+#[cfg_attr(feature = "serde", macro_use)]
 extern crate alloc;
 
 // always include rust_rt so the memory allocator is enabled
@@ -18,18 +18,26 @@ use openvm_platform::rust_rt;
 #[cfg(target_os = "zkvm")]
 pub use openvm_rv32im_guest::*;
 
-#[cfg(target_os = "zkvm")]
-mod getrandom;
+pub mod codec;
+pub mod getrandom;
 pub mod io;
+pub mod memory;
+#[cfg(feature = "overlay")]
+pub mod overlay;
 #[cfg(all(feature = "std", target_os = "zkvm"))]
 pub mod pal_abi;
 pub mod process;
+pub mod profile;
+#[cfg(feature = "rayon-compat")]
+pub mod rayon;
+#[cfg(feature = "serde")]
 pub mod serde;
+pub mod thread;
 
-#[cfg(not(target_os = "zkvm"))]
+#[cfg(all(not(target_os = "zkvm"), feature = "host-math"))]
 pub mod utils;
 
-#[cfg(not(target_os = "zkvm"))]
+#[cfg(all(not(target_os = "zkvm"), feature = "hints"))]
 pub mod host;
 
 #[cfg(target_os = "zkvm")]
@@ -103,6 +111,8 @@ macro_rules! entry {
 unsafe extern "C" fn __start() -> ! {
     #[cfg(feature = "heap-embedded-alloc")]
     openvm_platform::heap::embedded::init();
+    #[cfg(feature = "softfloat")]
+    openvm_softfloat_guest::init();
 
     {
         extern "C" {
@@ -115,6 +125,12 @@ unsafe extern "C" fn __start() -> ! {
     unreachable!()
 }
 
+// The RISC-V instructions and the IO instructions of the runtime.
+openvm_platform::require_extension_version!("rv32i", 1);
+openvm_platform::require_extension_version!("rv32m", 1);
+openvm_platform::require_extension_version!("rv32io", 1);
+
+// Depends on the stack size the guest is built with, see [openvm_platform::memory::STACK_SIZE].
 #[cfg(target_os = "zkvm")]
 static STACK_TOP: u32 = openvm_platform::memory::STACK_TOP;
 
@@ -159,12 +175,54 @@ pub fn memory_barrier<T>(ptr: *const T) {
 #[panic_handler]
 fn panic_impl(panic_info: &core::panic::PanicInfo) -> ! {
     use core::fmt::Write;
-    let mut writer = crate::io::Writer;
-    let _ = write!(writer, "{}\n", panic_info);
-    openvm_platform::rust_rt::terminate::<1>();
+
+    // The message is formatted on the stack, since the panic may have been raised because the
+    // heap is exhausted.
+    let mut message = PanicMessage::new();
+    let _ = write!(message, "{}", panic_info);
+    process::panic_with_message(message.as_str());
     unreachable!()
 }
 
+/// Messages longer than [PANIC_MESSAGE_MAX_LEN] bytes are truncated.
+#[cfg(all(target_os = "zkvm", not(feature = "std")))]
+const PANIC_MESSAGE_MAX_LEN: usize = 512;
+
+/// Fixed size buffer the panic message is formatted into.
+#[cfg(all(target_os = "zkvm", not(feature = "std")))]
+struct PanicMessage {
+    buf: [u8; PANIC_MESSAGE_MAX_LEN],
+    len: usize,
+}
+
+#[cfg(all(target_os = "zkvm", not(feature = "std")))]
+impl PanicMessage {
+    fn new() -> Self {
+        Self {
+            buf: [0; PANIC_MESSAGE_MAX_LEN],
+            len: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // SAFETY: only whole characters are written to the buffer.
+        unsafe { core::str::from_utf8_unchecked(&self.buf[..self.len]) }
+    }
+}
+
+#[cfg(all(target_os = "zkvm", not(feature = "std")))]
+impl core::fmt::Write for PanicMessage {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        let mut end = s.len().min(PANIC_MESSAGE_MAX_LEN - self.len);
+        while !s.is_char_boundary(end) {
+            end -= 1;
+        }
+        self.buf[self.len..self.len + end].copy_from_slice(&s.as_bytes()[..end]);
+        self.len += end;
+        Ok(())
+    }
+}
+
 // Includes the openvm_init.rs file generated at build time
 #[macro_export]
 macro_rules! init {
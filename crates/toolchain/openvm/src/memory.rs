@@ -0,0 +1,130 @@
+//! Byte buffers with a guaranteed alignment.
+//!
+//! Intrinsics such as modular arithmetic operate on `IntMod` arrays, which must be aligned to 4,
+//! 16 or 32 bytes. A `Vec<u8>` is only guaranteed to be aligned to 1 byte, so casting its bytes
+//! to an `IntMod` array is undefined behavior unless they are first copied into an aligned array.
+//! An [AlignedBytes] buffer can be cast in place instead.
+
+use alloc::alloc::{alloc_zeroed, dealloc, handle_alloc_error};
+use core::{
+    alloc::Layout,
+    fmt,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    slice,
+};
+
+use openvm_platform::WORD_SIZE;
+
+/// A heap-allocated byte buffer whose start is aligned to [AlignedBytes::align] bytes.
+///
+/// The allocation is rounded up to a whole number of words, so the buffer can be filled word by
+/// word from the hint stream.
+pub struct AlignedBytes {
+    ptr: NonNull<u8>,
+    len: usize,
+    /// The layout of the allocation, whose size is zero if nothing was allocated.
+    layout: Layout,
+}
+
+/// Allocates `len` zeroed bytes aligned to `align` bytes.
+///
+/// # Panics
+/// If `align` is not a power of two.
+pub fn alloc_aligned(len: usize, align: usize) -> AlignedBytes {
+    let layout = Layout::from_size_align(len.next_multiple_of(WORD_SIZE), align)
+        .expect("alignment must be a power of two");
+    let ptr = if layout.size() == 0 {
+        // A dangling pointer with the requested alignment, which is valid for zero-sized slices.
+        NonNull::new(align as *mut u8).unwrap()
+    } else {
+        // SAFETY: the layout has a non-zero size.
+        let ptr = unsafe { alloc_zeroed(layout) };
+        NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(layout))
+    };
+    AlignedBytes { ptr, len, layout }
+}
+
+impl AlignedBytes {
+    pub fn align(&self) -> usize {
+        self.layout.align()
+    }
+
+    /// Number of bytes allocated, which is `len` rounded up to a whole number of words.
+    pub fn capacity(&self) -> usize {
+        self.layout.size()
+    }
+
+    pub fn as_ptr(&self) -> *const u8 {
+        self.ptr.as_ptr()
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.ptr.as_ptr()
+    }
+}
+
+impl Deref for AlignedBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        // SAFETY: the allocation holds `len` initialized bytes.
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl DerefMut for AlignedBytes {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        // SAFETY: the allocation holds `len` initialized bytes and is owned by `self`.
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl Drop for AlignedBytes {
+    fn drop(&mut self) {
+        if self.layout.size() != 0 {
+            // SAFETY: the buffer was allocated with the same layout.
+            unsafe { dealloc(self.ptr.as_ptr(), self.layout) }
+        }
+    }
+}
+
+impl fmt::Debug for AlignedBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AlignedBytes")
+            .field("align", &self.align())
+            .field("bytes", &&**self)
+            .finish()
+    }
+}
+
+// SAFETY: the buffer is owned by `AlignedBytes` like the bytes of a `Vec<u8>`.
+unsafe impl Send for AlignedBytes {}
+// SAFETY: shared references only give shared access to the bytes.
+unsafe impl Sync for AlignedBytes {}
+
+#[cfg(test)]
+mod tests {
+    use super::alloc_aligned;
+
+    #[test]
+    fn test_alloc_aligned() {
+        for align in [4, 16, 32] {
+            for len in [0, 1, 5, 32, 100] {
+                let mut bytes = alloc_aligned(len, align);
+                assert_eq!(bytes.as_ptr() as usize % align, 0);
+                assert_eq!(bytes.len(), len);
+                assert!(bytes.capacity() >= len && bytes.capacity() % 4 == 0);
+                assert!(bytes.iter().all(|&b| b == 0));
+                bytes.fill(7);
+                assert!(bytes.iter().all(|&b| b == 7));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic = "alignment must be a power of two"]
+    fn test_alloc_aligned_invalid_align() {
+        alloc_aligned(8, 12);
+    }
+}
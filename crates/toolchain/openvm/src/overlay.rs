@@ -0,0 +1,185 @@
+//! Overlays: large read-only data embedded with [include_overlay_bytes] which is paged into guest
+//! memory on first access instead of being part of the initial memory image.
+//!
+//! Only the Merkle root and the length of an overlay are part of the program. The first access to a
+//! page of the overlay loads the page from the host with
+//! [hint_load_by_key](crate::io::hint_load_by_key) and verifies it against the root, so untouched
+//! pages add neither to the initial memory image nor to the execution trace. The pages are hashed
+//! with the sha256 extension, which the VM config must therefore include.
+
+use core::ops::Range;
+#[cfg(target_os = "zkvm")]
+use core::{alloc::Layout, cell::UnsafeCell};
+
+pub use openvm_overlay::{Digest, PAGE_SIZE};
+pub use openvm_overlay_macros::include_overlay_bytes;
+
+/// Read-only data embedded with [include_overlay_bytes].
+pub struct Overlay {
+    root: Digest,
+    len: usize,
+    #[cfg(not(target_os = "zkvm"))]
+    data: &'static [u8],
+    #[cfg(target_os = "zkvm")]
+    pages: UnsafeCell<Pages>,
+}
+
+/// Guest memory holding the pages of an [Overlay] loaded so far.
+#[cfg(target_os = "zkvm")]
+struct Pages {
+    /// Buffer of `num_pages * PAGE_SIZE` bytes, or null before the first access.
+    buffer: *mut u8,
+    /// Bitmap of the loaded pages, or null before the first access.
+    loaded: *mut u32,
+}
+
+// SAFETY: guest programs are single-threaded.
+#[cfg(target_os = "zkvm")]
+unsafe impl Sync for Overlay {}
+
+impl Overlay {
+    #[doc(hidden)]
+    #[cfg(target_os = "zkvm")]
+    pub const fn new(root: Digest, len: usize) -> Self {
+        Self {
+            root,
+            len,
+            pages: UnsafeCell::new(Pages {
+                buffer: core::ptr::null_mut(),
+                loaded: core::ptr::null_mut(),
+            }),
+        }
+    }
+
+    #[doc(hidden)]
+    #[cfg(not(target_os = "zkvm"))]
+    pub const fn new(root: Digest, data: &'static [u8]) -> Self {
+        Self {
+            root,
+            len: data.len(),
+            data,
+        }
+    }
+
+    /// Returns the Merkle root of the overlay.
+    pub const fn root(&self) -> &Digest {
+        &self.root
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the bytes of the overlay in `range`, loading the pages they belong to if necessary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `range` is out of bounds, or if the host serves a page which does not match the
+    /// root of the overlay.
+    pub fn get(&self, range: Range<usize>) -> &[u8] {
+        assert!(
+            range.start <= range.end && range.end <= self.len,
+            "range out of bounds of the overlay"
+        );
+        #[cfg(not(target_os = "zkvm"))]
+        {
+            &self.data[range]
+        }
+        #[cfg(target_os = "zkvm")]
+        {
+            if range.is_empty() {
+                return &[];
+            }
+            for index in range.start / PAGE_SIZE..=(range.end - 1) / PAGE_SIZE {
+                self.load_page(index);
+            }
+            // SAFETY: the pages covering `range` are loaded, and loaded pages are never written to
+            // again.
+            unsafe {
+                let buffer = (*self.pages.get()).buffer;
+                core::slice::from_raw_parts(buffer.add(range.start), range.len())
+            }
+        }
+    }
+
+    /// Returns all bytes of the overlay, loading every page.
+    pub fn as_slice(&self) -> &[u8] {
+        self.get(0..self.len)
+    }
+
+    #[cfg(target_os = "zkvm")]
+    fn load_page(&self, index: usize) {
+        use openvm_overlay::{page_key, proof_len, verify_page, DIGEST_SIZE};
+
+        use crate::io::{hint_load_by_key, read_u32, read_vec_by_len};
+
+        let num_pages = openvm_overlay::num_pages(self.len);
+        // SAFETY: guest programs are single-threaded, and no reference to `Pages` outlives this
+        // function.
+        let pages = unsafe { &mut *self.pages.get() };
+        if pages.buffer.is_null() {
+            // The buffer is never freed, so slices into it returned by `get` are valid for the
+            // lifetime of the overlay. Memory which is allocated but never written to does not
+            // need to be merkleized.
+            unsafe {
+                pages.buffer =
+                    alloc::alloc::alloc(Layout::from_size_align(num_pages * PAGE_SIZE, 4).unwrap());
+                pages.loaded = alloc::alloc::alloc_zeroed(
+                    Layout::array::<u32>(num_pages.div_ceil(32)).unwrap(),
+                ) as *mut u32;
+            }
+        }
+        let (word, bit) = (index / 32, 1 << (index % 32));
+        // SAFETY: the bitmap holds `num_pages.div_ceil(32)` words and `index < num_pages`.
+        let loaded = unsafe { &mut *pages.loaded.add(word) };
+        if *loaded & bit != 0 {
+            return;
+        }
+
+        let num_siblings = proof_len(self.len);
+        hint_load_by_key(&page_key(&self.root, index as u32));
+        openvm_rv32im_guest::hint_input();
+        assert_eq!(
+            read_u32() as usize,
+            PAGE_SIZE + num_siblings * DIGEST_SIZE,
+            "overlay page hint has the wrong length"
+        );
+        // SAFETY: the page lies within the buffer, which is 4-byte aligned, and has not been
+        // loaded, so no slice returned by `get` overlaps it.
+        let page = unsafe {
+            let ptr = pages.buffer.add(index * PAGE_SIZE);
+            openvm_rv32im_guest::hint_buffer_u32!(ptr, PAGE_SIZE / 4);
+            core::slice::from_raw_parts(ptr, PAGE_SIZE)
+        };
+        let siblings = read_vec_by_len(num_siblings * DIGEST_SIZE);
+        assert!(
+            verify_page(&self.root, self.len, index, page, &siblings),
+            "overlay page does not match the root"
+        );
+        *loaded |= bit;
+    }
+}
+
+/// Record of the [OVERLAY_SECTION](openvm_overlay::OVERLAY_SECTION) section holding the contents of
+/// an overlay of `LEN` bytes, followed by `PAD` bytes of padding.
+#[doc(hidden)]
+#[repr(C, align(4))]
+pub struct OverlaySection<const LEN: usize, const PAD: usize> {
+    len: u32,
+    data: [u8; LEN],
+    padding: [u8; PAD],
+}
+
+impl<const LEN: usize, const PAD: usize> OverlaySection<LEN, PAD> {
+    pub const fn new(data: [u8; LEN]) -> Self {
+        Self {
+            len: (LEN as u32).to_le(),
+            data,
+            padding: [0; PAD],
+        }
+    }
+}
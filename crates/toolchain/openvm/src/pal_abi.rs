@@ -4,8 +4,13 @@
 /// This will be removed once a dedicated rust toolchain is used because OpenVM does not handle
 /// system operations in the same way: there is no operating system and even the standard
 /// library should be directly handled with intrinsics.
+/// The ABI has no threads: the standard library implements `std::thread` for this target with
+/// its generic unsupported threads, which never call into the ABI, so `std::thread::spawn`
+/// panics and cannot be shimmed here. Guests use the green threads of [crate::thread] instead.
 use openvm_platform::{fileno::*, memory::sys_alloc_aligned, rust_rt::terminate, WORD_SIZE};
-use openvm_rv32im_guest::{hint_buffer_u32, hint_random, raw_print_str_from_bytes};
+#[cfg(not(feature = "getrandom-seeded"))]
+use openvm_rv32im_guest::{hint_buffer_u32, hint_random, with_hint_queue, HintQueue};
+use openvm_rv32im_guest::{raw_print_str_from_bytes, raw_record_panic_from_bytes};
 
 const DIGEST_WORDS: usize = 8;
 
@@ -72,8 +77,16 @@ pub unsafe extern "C" fn sys_sha_buffer(
 /// `recv_buf` must be aligned and dereferenceable.
 #[no_mangle]
 pub unsafe extern "C" fn sys_rand(recv_buf: *mut u32, words: usize) {
-    hint_random(words);
-    hint_buffer_u32!(recv_buf, words);
+    #[cfg(feature = "getrandom-seeded")]
+    crate::getrandom::fill_bytes(core::slice::from_raw_parts_mut(
+        recv_buf as *mut u8,
+        words * WORD_SIZE,
+    ));
+    #[cfg(not(feature = "getrandom-seeded"))]
+    with_hint_queue(HintQueue::Random, || {
+        hint_random(words);
+        hint_buffer_u32!(recv_buf, words);
+    });
 }
 
 /// # Safety
@@ -82,6 +95,7 @@ pub unsafe extern "C" fn sys_rand(recv_buf: *mut u32, words: usize) {
 #[no_mangle]
 unsafe extern "C" fn sys_panic(msg_ptr: *const u8, len: usize) -> ! {
     raw_print_str_from_bytes(msg_ptr, len);
+    raw_record_panic_from_bytes(msg_ptr, len);
     terminate::<{ exit_code::PANIC }>();
     unreachable!()
 }
@@ -9,3 +9,31 @@ pub fn exit() {
 pub fn panic() {
     openvm_platform::rust_rt::terminate::<1>();
 }
+
+/// Print `message` and exit the program with exit code 1, recording `message` as the panic
+/// message which the host reports together with the exit code.
+#[cfg(target_os = "zkvm")]
+pub fn panic_with_message(message: &str) {
+    crate::io::println(message);
+    openvm_rv32im_guest::raw_record_panic_from_bytes(message.as_ptr(), message.len());
+    panic();
+}
+
+/// Pause the program, ending the current phase. The host inspects the user public values revealed
+/// so far, e.g. with `Sdk::execute_phases`, and supplies the input of the next phase before
+/// execution continues. All phases are covered by the same proof, but values revealed in an
+/// earlier phase can be overwritten later, so outputs which a later phase depends on should be
+/// published with `openvm::io::publish_bytes` to bind them to the final public
+/// values. Requires continuations.
+#[cfg(target_os = "zkvm")]
+pub fn pause() {
+    openvm_rv32im_guest::pause();
+}
+
+/// Returns the heap usage of the program so far: the number of allocations, the bytes allocated
+/// and the bytes currently and at most allocated. Requires the `heap-profile` feature, which also
+/// reports the heap usage to the host when the program terminates.
+#[cfg(all(feature = "heap-profile", target_os = "zkvm"))]
+pub fn heap_stats() -> openvm_platform::heap::profile::HeapStats {
+    openvm_platform::heap::profile::heap_stats()
+}
@@ -0,0 +1,46 @@
+//! Regions of the program labeled for profiling. The host attributes the cycles executed between
+//! [start_region] and the matching [end_region] to the name of the region, and reports them in
+//! `ExecutionResult::region_profile` and with `cargo openvm profile`.
+//!
+//! ```ignore
+//! openvm::profile::start_region!("verify_sig");
+//! let valid = verify(&signature, &message);
+//! openvm::profile::end_region!();
+//! ```
+//!
+//! Regions nest, and [end_region] ends the innermost open region. The annotations do not change
+//! the state of the program and are not constrained by the proof. They do nothing when not
+//! running in the zkVM.
+
+/// Starts the region `name`. Usually called through [start_region].
+#[inline(always)]
+#[allow(unused_variables)]
+pub fn start(name: &str) {
+    #[cfg(target_os = "zkvm")]
+    openvm_rv32im_guest::raw_region_start(name.as_ptr(), name.len());
+}
+
+/// Ends the innermost open region. Usually called through [end_region].
+#[inline(always)]
+pub fn end() {
+    #[cfg(target_os = "zkvm")]
+    openvm_rv32im_guest::region_end();
+}
+
+/// Starts the profiling region `name`, see the [profile](crate::profile) module.
+#[macro_export]
+macro_rules! start_region {
+    ($name:expr $(,)?) => {
+        $crate::profile::start($name)
+    };
+}
+
+/// Ends the innermost open profiling region, see the [profile](crate::profile) module.
+#[macro_export]
+macro_rules! end_region {
+    () => {
+        $crate::profile::end()
+    };
+}
+
+pub use crate::{end_region, start_region};
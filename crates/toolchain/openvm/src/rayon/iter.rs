@@ -0,0 +1,835 @@
+//! Parallel iterators, which run as the sequential iterators they wrap.
+
+use alloc::vec::Vec;
+use core::{cmp::Ordering, convert::Infallible, iter, ops::ControlFlow};
+
+/// A parallel iterator yielding the items of the sequential iterator `I`, in order.
+#[derive(Clone, Debug)]
+pub struct ParIter<I>(I);
+
+impl<I: Iterator> From<I> for ParIter<I> {
+    fn from(iter: I) -> Self {
+        Self(iter)
+    }
+}
+
+impl<I: Iterator> IntoIterator for ParIter<I> {
+    type Item = I::Item;
+    type IntoIter = I;
+
+    fn into_iter(self) -> I {
+        self.0
+    }
+}
+
+/// Creates a parallel iterator that endlessly repeats `elt`.
+pub fn repeat<T: Clone>(elt: T) -> ParIter<iter::Repeat<T>> {
+    ParIter(iter::repeat(elt))
+}
+
+/// Creates a parallel iterator that yields `elt` `n` times.
+pub fn repeatn<T: Clone>(elt: T, n: usize) -> ParIter<iter::Take<iter::Repeat<T>>> {
+    ParIter(iter::repeat(elt).take(n))
+}
+
+/// Creates a parallel iterator that yields nothing.
+pub fn empty<T>() -> ParIter<iter::Empty<T>> {
+    ParIter(iter::empty())
+}
+
+/// Creates a parallel iterator that yields `item` once.
+pub fn once<T>(item: T) -> ParIter<iter::Once<T>> {
+    ParIter(iter::once(item))
+}
+
+/// Conversion into a parallel iterator, implemented for everything that implements
+/// [IntoIterator].
+pub trait IntoParallelIterator {
+    type Iter: ParallelIterator<Item = Self::Item>;
+    type Item;
+
+    fn into_par_iter(self) -> Self::Iter;
+}
+
+impl<T: IntoIterator> IntoParallelIterator for T {
+    type Iter = ParIter<T::IntoIter>;
+    type Item = T::Item;
+
+    fn into_par_iter(self) -> Self::Iter {
+        ParIter(self.into_iter())
+    }
+}
+
+/// Borrowing conversion into a parallel iterator, e.g. `vec.par_iter()`.
+pub trait IntoParallelRefIterator<'data> {
+    type Iter: ParallelIterator<Item = Self::Item>;
+    type Item: 'data;
+
+    fn par_iter(&'data self) -> Self::Iter;
+}
+
+impl<'data, I: 'data + ?Sized> IntoParallelRefIterator<'data> for I
+where
+    &'data I: IntoParallelIterator,
+{
+    type Iter = <&'data I as IntoParallelIterator>::Iter;
+    type Item = <&'data I as IntoParallelIterator>::Item;
+
+    fn par_iter(&'data self) -> Self::Iter {
+        self.into_par_iter()
+    }
+}
+
+/// Mutably borrowing conversion into a parallel iterator, e.g. `vec.par_iter_mut()`.
+pub trait IntoParallelRefMutIterator<'data> {
+    type Iter: ParallelIterator<Item = Self::Item>;
+    type Item: 'data;
+
+    fn par_iter_mut(&'data mut self) -> Self::Iter;
+}
+
+impl<'data, I: 'data + ?Sized> IntoParallelRefMutIterator<'data> for I
+where
+    &'data mut I: IntoParallelIterator,
+{
+    type Iter = <&'data mut I as IntoParallelIterator>::Iter;
+    type Item = <&'data mut I as IntoParallelIterator>::Item;
+
+    fn par_iter_mut(&'data mut self) -> Self::Iter {
+        self.into_par_iter()
+    }
+}
+
+/// Conversion of a sequential iterator into a parallel iterator.
+pub trait ParallelBridge: Sized {
+    fn par_bridge(self) -> ParIter<Self>;
+}
+
+impl<T: Iterator> ParallelBridge for T {
+    fn par_bridge(self) -> ParIter<Self> {
+        ParIter(self)
+    }
+}
+
+/// Creation of a collection from a parallel iterator, implemented for everything that implements
+/// [FromIterator].
+pub trait FromParallelIterator<T> {
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = T>;
+}
+
+impl<T, C: FromIterator<T>> FromParallelIterator<T> for C {
+    fn from_par_iter<I>(par_iter: I) -> Self
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        par_iter.into_par_iter().into_seq().collect()
+    }
+}
+
+/// Extension of a collection with a parallel iterator, implemented for everything that
+/// implements [Extend].
+pub trait ParallelExtend<T> {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = T>;
+}
+
+impl<T, C: Extend<T>> ParallelExtend<T> for C {
+    fn par_extend<I>(&mut self, par_iter: I)
+    where
+        I: IntoParallelIterator<Item = T>,
+    {
+        self.extend(par_iter.into_par_iter().into_seq())
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl<T> Sealed for Option<T> {}
+    impl<T, E> Sealed for Result<T, E> {}
+}
+
+/// The types which short-circuit in fallible operations such as
+/// [ParallelIterator::try_for_each]: [Option] and [Result].
+pub trait Try: private::Sealed {
+    type Output;
+    type Residual;
+
+    fn from_output(output: Self::Output) -> Self;
+    fn from_residual(residual: Self::Residual) -> Self;
+    fn branch(self) -> ControlFlow<Self::Residual, Self::Output>;
+}
+
+impl<T> Try for Option<T> {
+    type Output = T;
+    type Residual = Option<Infallible>;
+
+    fn from_output(output: T) -> Self {
+        Some(output)
+    }
+
+    fn from_residual(_residual: Option<Infallible>) -> Self {
+        None
+    }
+
+    fn branch(self) -> ControlFlow<Option<Infallible>, T> {
+        match self {
+            Some(output) => ControlFlow::Continue(output),
+            None => ControlFlow::Break(None),
+        }
+    }
+}
+
+impl<T, E> Try for Result<T, E> {
+    type Output = T;
+    type Residual = Result<Infallible, E>;
+
+    fn from_output(output: T) -> Self {
+        Ok(output)
+    }
+
+    fn from_residual(residual: Result<Infallible, E>) -> Self {
+        match residual {
+            Err(e) => Err(e),
+        }
+    }
+
+    fn branch(self) -> ControlFlow<Result<Infallible, E>, T> {
+        match self {
+            Ok(output) => ControlFlow::Continue(output),
+            Err(e) => ControlFlow::Break(Err(e)),
+        }
+    }
+}
+
+/// Folds `iter` with a fallible `op`, stopping at the first failure.
+fn try_fold_seq<I, T, R, F>(iter: I, init: T, mut op: F) -> R
+where
+    I: Iterator,
+    F: FnMut(T, I::Item) -> R,
+    R: Try<Output = T>,
+{
+    let mut acc = init;
+    for item in iter {
+        match op(acc, item).branch() {
+            ControlFlow::Continue(next) => acc = next,
+            ControlFlow::Break(residual) => return R::from_residual(residual),
+        }
+    }
+    R::from_output(acc)
+}
+
+/// A parallel iterator, which runs as its sequential iterator [ParallelIterator::SeqIter].
+///
+/// The methods have the semantics of the `rayon` methods of the same name. Where `rayon` leaves
+/// the order of evaluation unspecified, items are processed in the order of the sequential
+/// iterator, so e.g. [ParallelIterator::find_any] returns the first match.
+pub trait ParallelIterator: Sized {
+    type Item;
+    /// The sequential iterator this runs as.
+    type SeqIter: Iterator<Item = Self::Item>;
+
+    /// Converts this into the sequential iterator it runs as.
+    fn into_seq(self) -> Self::SeqIter;
+
+    fn for_each<OP>(self, op: OP)
+    where
+        OP: FnMut(Self::Item),
+    {
+        self.into_seq().for_each(op)
+    }
+
+    fn for_each_with<OP, T>(self, mut init: T, mut op: OP)
+    where
+        OP: FnMut(&mut T, Self::Item),
+    {
+        self.into_seq().for_each(|item| op(&mut init, item))
+    }
+
+    fn for_each_init<OP, INIT, T>(self, mut init: INIT, mut op: OP)
+    where
+        OP: FnMut(&mut T, Self::Item),
+        INIT: FnMut() -> T,
+    {
+        let mut state = init();
+        self.into_seq().for_each(|item| op(&mut state, item))
+    }
+
+    fn try_for_each<OP, R>(self, mut op: OP) -> R
+    where
+        OP: FnMut(Self::Item) -> R,
+        R: Try<Output = ()>,
+    {
+        try_fold_seq(self.into_seq(), (), |(), item| op(item))
+    }
+
+    fn try_for_each_with<OP, T, R>(self, mut init: T, mut op: OP) -> R
+    where
+        OP: FnMut(&mut T, Self::Item) -> R,
+        R: Try<Output = ()>,
+    {
+        try_fold_seq(self.into_seq(), (), |(), item| op(&mut init, item))
+    }
+
+    fn try_for_each_init<OP, INIT, T, R>(self, mut init: INIT, mut op: OP) -> R
+    where
+        OP: FnMut(&mut T, Self::Item) -> R,
+        INIT: FnMut() -> T,
+        R: Try<Output = ()>,
+    {
+        let mut state = init();
+        try_fold_seq(self.into_seq(), (), |(), item| op(&mut state, item))
+    }
+
+    fn count(self) -> usize {
+        self.into_seq().count()
+    }
+
+    fn map<F, R>(self, map_op: F) -> ParIter<iter::Map<Self::SeqIter, F>>
+    where
+        F: FnMut(Self::Item) -> R,
+    {
+        ParIter(self.into_seq().map(map_op))
+    }
+
+    fn map_with<F, T, R>(self, mut init: T, mut map_op: F) -> ParIter<impl Iterator<Item = R>>
+    where
+        F: FnMut(&mut T, Self::Item) -> R,
+    {
+        ParIter(self.into_seq().map(move |item| map_op(&mut init, item)))
+    }
+
+    fn map_init<F, INIT, T, R>(
+        self,
+        mut init: INIT,
+        mut map_op: F,
+    ) -> ParIter<impl Iterator<Item = R>>
+    where
+        F: FnMut(&mut T, Self::Item) -> R,
+        INIT: FnMut() -> T,
+    {
+        let mut state = init();
+        ParIter(self.into_seq().map(move |item| map_op(&mut state, item)))
+    }
+
+    fn cloned<'a, T>(self) -> ParIter<iter::Cloned<Self::SeqIter>>
+    where
+        T: 'a + Clone,
+        Self: ParallelIterator<Item = &'a T>,
+    {
+        ParIter(self.into_seq().cloned())
+    }
+
+    fn copied<'a, T>(self) -> ParIter<iter::Copied<Self::SeqIter>>
+    where
+        T: 'a + Copy,
+        Self: ParallelIterator<Item = &'a T>,
+    {
+        ParIter(self.into_seq().copied())
+    }
+
+    fn inspect<OP>(self, inspect_op: OP) -> ParIter<iter::Inspect<Self::SeqIter, OP>>
+    where
+        OP: FnMut(&Self::Item),
+    {
+        ParIter(self.into_seq().inspect(inspect_op))
+    }
+
+    fn update<F>(self, mut update_op: F) -> ParIter<impl Iterator<Item = Self::Item>>
+    where
+        F: FnMut(&mut Self::Item),
+    {
+        ParIter(self.into_seq().map(move |mut item| {
+            update_op(&mut item);
+            item
+        }))
+    }
+
+    fn filter<P>(self, filter_op: P) -> ParIter<iter::Filter<Self::SeqIter, P>>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        ParIter(self.into_seq().filter(filter_op))
+    }
+
+    fn filter_map<P, R>(self, filter_op: P) -> ParIter<iter::FilterMap<Self::SeqIter, P>>
+    where
+        P: FnMut(Self::Item) -> Option<R>,
+    {
+        ParIter(self.into_seq().filter_map(filter_op))
+    }
+
+    fn flat_map<F, PI>(self, mut map_op: F) -> ParIter<impl Iterator<Item = PI::Item>>
+    where
+        F: FnMut(Self::Item) -> PI,
+        PI: IntoParallelIterator,
+    {
+        ParIter(
+            self.into_seq()
+                .flat_map(move |item| map_op(item).into_par_iter().into_seq()),
+        )
+    }
+
+    fn flat_map_iter<F, SI>(self, map_op: F) -> ParIter<iter::FlatMap<Self::SeqIter, SI, F>>
+    where
+        F: FnMut(Self::Item) -> SI,
+        SI: IntoIterator,
+    {
+        ParIter(self.into_seq().flat_map(map_op))
+    }
+
+    fn flatten(self) -> ParIter<impl Iterator<Item = <Self::Item as IntoParallelIterator>::Item>>
+    where
+        Self::Item: IntoParallelIterator,
+    {
+        ParIter(
+            self.into_seq()
+                .flat_map(|item| item.into_par_iter().into_seq()),
+        )
+    }
+
+    fn flatten_iter(self) -> ParIter<iter::Flatten<Self::SeqIter>>
+    where
+        Self::Item: IntoIterator,
+    {
+        ParIter(self.into_seq().flatten())
+    }
+
+    fn reduce<OP, ID>(self, identity: ID, op: OP) -> Self::Item
+    where
+        OP: FnMut(Self::Item, Self::Item) -> Self::Item,
+        ID: FnOnce() -> Self::Item,
+    {
+        self.into_seq().fold(identity(), op)
+    }
+
+    fn reduce_with<OP>(self, op: OP) -> Option<Self::Item>
+    where
+        OP: FnMut(Self::Item, Self::Item) -> Self::Item,
+    {
+        self.into_seq().reduce(op)
+    }
+
+    fn try_reduce<T, OP, ID>(self, identity: ID, mut op: OP) -> Self::Item
+    where
+        OP: FnMut(T, T) -> Self::Item,
+        ID: FnOnce() -> T,
+        Self::Item: Try<Output = T>,
+    {
+        try_fold_seq(self.into_seq(), identity(), |acc, item| {
+            match item.branch() {
+                ControlFlow::Continue(item) => op(acc, item),
+                ControlFlow::Break(residual) => Self::Item::from_residual(residual),
+            }
+        })
+    }
+
+    /// Folds all items into a single accumulator, so the returned iterator yields one item.
+    fn fold<T, ID, F>(self, identity: ID, fold_op: F) -> ParIter<impl Iterator<Item = T>>
+    where
+        F: FnMut(T, Self::Item) -> T,
+        ID: FnOnce() -> T,
+    {
+        ParIter(iter::once_with(move || {
+            self.into_seq().fold(identity(), fold_op)
+        }))
+    }
+
+    /// Folds all items into a single accumulator, so the returned iterator yields one item.
+    fn fold_with<F, T>(self, init: T, fold_op: F) -> ParIter<impl Iterator<Item = T>>
+    where
+        F: FnMut(T, Self::Item) -> T,
+    {
+        ParIter(iter::once_with(move || self.into_seq().fold(init, fold_op)))
+    }
+
+    fn sum<S>(self) -> S
+    where
+        S: iter::Sum<Self::Item>,
+    {
+        self.into_seq().sum()
+    }
+
+    fn product<P>(self) -> P
+    where
+        P: iter::Product<Self::Item>,
+    {
+        self.into_seq().product()
+    }
+
+    fn min(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.into_seq().min()
+    }
+
+    fn min_by<F>(self, f: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.into_seq().min_by(f)
+    }
+
+    fn min_by_key<K, F>(self, f: F) -> Option<Self::Item>
+    where
+        K: Ord,
+        F: FnMut(&Self::Item) -> K,
+    {
+        self.into_seq().min_by_key(f)
+    }
+
+    fn max(self) -> Option<Self::Item>
+    where
+        Self::Item: Ord,
+    {
+        self.into_seq().max()
+    }
+
+    fn max_by<F>(self, f: F) -> Option<Self::Item>
+    where
+        F: FnMut(&Self::Item, &Self::Item) -> Ordering,
+    {
+        self.into_seq().max_by(f)
+    }
+
+    fn max_by_key<K, F>(self, f: F) -> Option<Self::Item>
+    where
+        K: Ord,
+        F: FnMut(&Self::Item) -> K,
+    {
+        self.into_seq().max_by_key(f)
+    }
+
+    fn chain<C>(
+        self,
+        chain: C,
+    ) -> ParIter<iter::Chain<Self::SeqIter, <C::Iter as ParallelIterator>::SeqIter>>
+    where
+        C: IntoParallelIterator<Item = Self::Item>,
+    {
+        ParIter(self.into_seq().chain(chain.into_par_iter().into_seq()))
+    }
+
+    fn find_any<P>(self, predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.find_first(predicate)
+    }
+
+    fn find_first<P>(self, predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.into_seq().find(predicate)
+    }
+
+    fn find_last<P>(self, predicate: P) -> Option<Self::Item>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        self.into_seq().filter(predicate).last()
+    }
+
+    fn find_map_any<P, R>(self, predicate: P) -> Option<R>
+    where
+        P: FnMut(Self::Item) -> Option<R>,
+    {
+        self.find_map_first(predicate)
+    }
+
+    fn find_map_first<P, R>(self, predicate: P) -> Option<R>
+    where
+        P: FnMut(Self::Item) -> Option<R>,
+    {
+        self.into_seq().find_map(predicate)
+    }
+
+    fn find_map_last<P, R>(self, predicate: P) -> Option<R>
+    where
+        P: FnMut(Self::Item) -> Option<R>,
+    {
+        self.into_seq().filter_map(predicate).last()
+    }
+
+    fn any<P>(self, predicate: P) -> bool
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        self.into_seq().any(predicate)
+    }
+
+    fn all<P>(self, predicate: P) -> bool
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        self.into_seq().all(predicate)
+    }
+
+    fn while_some<T>(self) -> ParIter<impl Iterator<Item = T>>
+    where
+        Self: ParallelIterator<Item = Option<T>>,
+    {
+        ParIter(self.into_seq().map_while(|item| item))
+    }
+
+    fn panic_fuse(self) -> Self {
+        self
+    }
+
+    fn take_any(self, n: usize) -> ParIter<iter::Take<Self::SeqIter>> {
+        ParIter(self.into_seq().take(n))
+    }
+
+    fn skip_any(self, n: usize) -> ParIter<iter::Skip<Self::SeqIter>> {
+        ParIter(self.into_seq().skip(n))
+    }
+
+    fn take_any_while<P>(self, predicate: P) -> ParIter<iter::TakeWhile<Self::SeqIter, P>>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        ParIter(self.into_seq().take_while(predicate))
+    }
+
+    fn skip_any_while<P>(self, predicate: P) -> ParIter<iter::SkipWhile<Self::SeqIter, P>>
+    where
+        P: FnMut(&Self::Item) -> bool,
+    {
+        ParIter(self.into_seq().skip_while(predicate))
+    }
+
+    fn collect<C>(self) -> C
+    where
+        C: FromParallelIterator<Self::Item>,
+    {
+        C::from_par_iter(ParIter(self.into_seq()))
+    }
+
+    fn unzip<A, B, FromA, FromB>(self) -> (FromA, FromB)
+    where
+        Self: ParallelIterator<Item = (A, B)>,
+        FromA: Default + Extend<A>,
+        FromB: Default + Extend<B>,
+    {
+        self.into_seq().unzip()
+    }
+
+    fn partition<A, B, P>(self, predicate: P) -> (A, B)
+    where
+        A: Default + Extend<Self::Item>,
+        B: Default + Extend<Self::Item>,
+        P: FnMut(&Self::Item) -> bool,
+    {
+        let mut left = A::default();
+        let mut right = B::default();
+        let mut predicate = predicate;
+        for item in self.into_seq() {
+            if predicate(&item) {
+                left.extend(iter::once(item));
+            } else {
+                right.extend(iter::once(item));
+            }
+        }
+        (left, right)
+    }
+
+    /// The exact length, if it is known in advance.
+    fn opt_len(&self) -> Option<usize>;
+}
+
+impl<I: Iterator> ParallelIterator for ParIter<I> {
+    type Item = I::Item;
+    type SeqIter = I;
+
+    fn into_seq(self) -> I {
+        self.0
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        match self.0.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        }
+    }
+}
+
+/// A parallel iterator with a known length, which supports positional adapters.
+pub trait IndexedParallelIterator: ParallelIterator {
+    /// The number of items.
+    fn len(&self) -> usize {
+        self.opt_len()
+            .expect("the length of the parallel iterator is not known")
+    }
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn collect_into_vec(self, target: &mut Vec<Self::Item>) {
+        target.clear();
+        target.extend(self.into_seq());
+    }
+
+    fn unzip_into_vecs<A, B>(self, left: &mut Vec<A>, right: &mut Vec<B>)
+    where
+        Self: IndexedParallelIterator<Item = (A, B)>,
+    {
+        left.clear();
+        right.clear();
+        for (a, b) in self.into_seq() {
+            left.push(a);
+            right.push(b);
+        }
+    }
+
+    fn zip<Z>(
+        self,
+        zip_op: Z,
+    ) -> ParIter<iter::Zip<Self::SeqIter, <Z::Iter as ParallelIterator>::SeqIter>>
+    where
+        Z: IntoParallelIterator,
+        Z::Iter: IndexedParallelIterator,
+    {
+        ParIter(self.into_seq().zip(zip_op.into_par_iter().into_seq()))
+    }
+
+    /// Like [IndexedParallelIterator::zip], but panics if the lengths differ.
+    fn zip_eq<Z>(
+        self,
+        zip_op: Z,
+    ) -> ParIter<iter::Zip<Self::SeqIter, <Z::Iter as ParallelIterator>::SeqIter>>
+    where
+        Z: IntoParallelIterator,
+        Z::Iter: IndexedParallelIterator,
+    {
+        let other = zip_op.into_par_iter();
+        assert_eq!(
+            self.len(),
+            other.len(),
+            "zipped iterators have different lengths"
+        );
+        ParIter(self.into_seq().zip(other.into_seq()))
+    }
+
+    /// Splits the items into vectors of `chunk_size` items, except for the last one.
+    fn chunks(self, chunk_size: usize) -> ParIter<impl Iterator<Item = Vec<Self::Item>>> {
+        assert!(chunk_size != 0, "chunk_size must not be zero");
+        let mut iter = self.into_seq();
+        ParIter(iter::from_fn(move || {
+            let chunk: Vec<_> = iter.by_ref().take(chunk_size).collect();
+            (!chunk.is_empty()).then_some(chunk)
+        }))
+    }
+
+    fn cmp<I>(self, other: I) -> Ordering
+    where
+        I: IntoParallelIterator<Item = Self::Item>,
+        I::Iter: IndexedParallelIterator,
+        Self::Item: Ord,
+    {
+        self.into_seq().cmp(other.into_par_iter().into_seq())
+    }
+
+    fn partial_cmp<I>(self, other: I) -> Option<Ordering>
+    where
+        I: IntoParallelIterator,
+        I::Iter: IndexedParallelIterator,
+        Self::Item: PartialOrd<I::Item>,
+    {
+        self.into_seq()
+            .partial_cmp(other.into_par_iter().into_seq())
+    }
+
+    fn eq<I>(self, other: I) -> bool
+    where
+        I: IntoParallelIterator,
+        I::Iter: IndexedParallelIterator,
+        Self::Item: PartialEq<I::Item>,
+    {
+        self.into_seq().eq(other.into_par_iter().into_seq())
+    }
+
+    fn ne<I>(self, other: I) -> bool
+    where
+        I: IntoParallelIterator,
+        I::Iter: IndexedParallelIterator,
+        Self::Item: PartialEq<I::Item>,
+    {
+        !self.eq(other)
+    }
+
+    fn enumerate(self) -> ParIter<iter::Enumerate<Self::SeqIter>> {
+        ParIter(self.into_seq().enumerate())
+    }
+
+    fn step_by(self, step: usize) -> ParIter<iter::StepBy<Self::SeqIter>> {
+        ParIter(self.into_seq().step_by(step))
+    }
+
+    fn skip(self, n: usize) -> ParIter<iter::Skip<Self::SeqIter>> {
+        ParIter(self.into_seq().skip(n))
+    }
+
+    fn take(self, n: usize) -> ParIter<iter::Take<Self::SeqIter>> {
+        ParIter(self.into_seq().take(n))
+    }
+
+    fn position_any<P>(self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        self.position_first(predicate)
+    }
+
+    fn position_first<P>(self, predicate: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        self.into_seq().position(predicate)
+    }
+
+    fn position_last<P>(self, mut predicate: P) -> Option<usize>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        self.into_seq()
+            .enumerate()
+            .filter_map(|(i, item)| predicate(item).then_some(i))
+            .last()
+    }
+
+    fn positions<P>(self, mut predicate: P) -> ParIter<impl Iterator<Item = usize>>
+    where
+        P: FnMut(Self::Item) -> bool,
+    {
+        ParIter(
+            self.into_seq()
+                .enumerate()
+                .filter_map(move |(i, item)| predicate(item).then_some(i)),
+        )
+    }
+
+    fn rev(self) -> ParIter<iter::Rev<Self::SeqIter>>
+    where
+        Self::SeqIter: DoubleEndedIterator,
+    {
+        ParIter(self.into_seq().rev())
+    }
+
+    fn with_min_len(self, _min: usize) -> Self {
+        self
+    }
+
+    fn with_max_len(self, _max: usize) -> Self {
+        self
+    }
+}
+
+/// Every parallel iterator is indexed, although [IndexedParallelIterator::len] panics if the
+/// length is not known in advance.
+impl<I: Iterator> IndexedParallelIterator for ParIter<I> {}
@@ -0,0 +1,278 @@
+//! A sequential stand-in for a subset of the `rayon` API.
+//!
+//! Parallel iterators run as ordinary iterators on the calling thread, in the order of the
+//! underlying sequential iterator. [join], [spawn] and [scope] run their closures on the
+//! threads of [crate::thread], which only switch when a thread yields, so closures which do not
+//! yield run to completion one after the other:
+//! - [join] runs `oper_a` and then `oper_b`,
+//! - [spawn] and [Scope::spawn] run the closure immediately.
+//!
+//! This is not a drop-in replacement for `rayon`. Common uses of the prelude, [join], [scope] and
+//! [ThreadPoolBuilder] compile unchanged with `openvm::rayon` in place of `rayon`, but:
+//! - every adapter returns an [iter::ParIter] instead of the adapter types of `rayon`, such as
+//!   `rayon::iter::Map`, so code naming those types does not compile,
+//! - [ParallelIterator] is implemented through a sequential iterator rather than
+//!   `rayon::iter::plumbing`, so implementations of the `rayon` traits do not carry over,
+//! - methods without a sequential counterpart here, such as `interleave`, `fold_chunks`,
+//!   `spawn_fifo` or `ThreadPool::broadcast`, are missing,
+//! - spawned work runs before [spawn] returns, so a spawned closure which waits for the code
+//!   after the [spawn], e.g. on a channel, blocks forever.
+//!
+//! Since there is a single thread, reductions such as [ParallelIterator::reduce] and
+//! [ParallelIterator::fold] combine items in order, and the result is deterministic even for
+//! operations that are not associative.
+//!
+//! [ParallelIterator]: iter::ParallelIterator
+//! [ParallelIterator::reduce]: iter::ParallelIterator::reduce
+//! [ParallelIterator::fold]: iter::ParallelIterator::fold
+
+use core::{fmt, marker::PhantomData};
+
+use crate::thread;
+
+pub mod iter;
+pub mod slice;
+pub mod str;
+
+/// The traits to import to use parallel iterators.
+pub mod prelude {
+    pub use super::{
+        iter::{
+            FromParallelIterator, IndexedParallelIterator, IntoParallelIterator,
+            IntoParallelRefIterator, IntoParallelRefMutIterator, ParallelBridge, ParallelExtend,
+            ParallelIterator,
+        },
+        slice::{ParallelDrainRange, ParallelSlice, ParallelSliceMut},
+        str::ParallelString,
+    };
+}
+
+/// Runs `oper_a` and then `oper_b`, and returns both results.
+pub fn join<A, B, RA, RB>(oper_a: A, oper_b: B) -> (RA, RB)
+where
+    A: FnOnce() -> RA + Send,
+    B: FnOnce() -> RB + Send,
+    RA: Send,
+    RB: Send,
+{
+    thread::scope(|s| {
+        let a = s.spawn(oper_a);
+        let b = s.spawn(oper_b);
+        (a.join().unwrap(), b.join().unwrap())
+    })
+}
+
+/// Runs `func` immediately.
+pub fn spawn<F>(func: F)
+where
+    F: FnOnce() + Send + 'static,
+{
+    func();
+}
+
+/// A scope to spawn work which may borrow from outside the scope.
+pub struct Scope<'scope> {
+    marker: PhantomData<&'scope mut &'scope ()>,
+}
+
+impl<'scope> Scope<'scope> {
+    /// Runs `body` immediately.
+    pub fn spawn<BODY>(&self, body: BODY)
+    where
+        BODY: FnOnce(&Scope<'scope>) + Send + 'scope,
+    {
+        thread::scope(|s| {
+            s.spawn(|| body(self));
+        });
+    }
+}
+
+/// Creates a scope for spawning work. All spawned work has run when this returns.
+pub fn scope<'scope, OP, R>(op: OP) -> R
+where
+    OP: FnOnce(&Scope<'scope>) -> R + Send,
+    R: Send,
+{
+    op(&Scope {
+        marker: PhantomData,
+    })
+}
+
+/// The same as [scope], since all work runs on the calling thread.
+pub fn in_place_scope<'scope, OP, R>(op: OP) -> R
+where
+    OP: FnOnce(&Scope<'scope>) -> R,
+{
+    op(&Scope {
+        marker: PhantomData,
+    })
+}
+
+/// Always 1.
+pub fn current_num_threads() -> usize {
+    1
+}
+
+/// Always `Some(0)`: the calling thread is the only worker.
+pub fn current_thread_index() -> Option<usize> {
+    Some(0)
+}
+
+/// Always 1.
+pub fn max_num_threads() -> usize {
+    1
+}
+
+/// A thread pool which runs all work on the calling thread.
+#[derive(Debug)]
+pub struct ThreadPool {
+    _private: (),
+}
+
+impl ThreadPool {
+    /// Runs `op`.
+    pub fn install<OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce() -> R + Send,
+        R: Send,
+    {
+        op()
+    }
+
+    /// See [join].
+    pub fn join<A, B, RA, RB>(&self, oper_a: A, oper_b: B) -> (RA, RB)
+    where
+        A: FnOnce() -> RA + Send,
+        B: FnOnce() -> RB + Send,
+        RA: Send,
+        RB: Send,
+    {
+        join(oper_a, oper_b)
+    }
+
+    /// See [scope].
+    pub fn scope<'scope, OP, R>(&self, op: OP) -> R
+    where
+        OP: FnOnce(&Scope<'scope>) -> R + Send,
+        R: Send,
+    {
+        scope(op)
+    }
+
+    /// See [spawn].
+    pub fn spawn<OP>(&self, op: OP)
+    where
+        OP: FnOnce() + Send + 'static,
+    {
+        spawn(op)
+    }
+
+    /// Always 1.
+    pub fn current_num_threads(&self) -> usize {
+        1
+    }
+
+    /// See [current_thread_index].
+    pub fn current_thread_index(&self) -> Option<usize> {
+        current_thread_index()
+    }
+}
+
+/// A builder for a [ThreadPool]. The configuration is ignored.
+#[derive(Debug, Default)]
+pub struct ThreadPoolBuilder {
+    _private: (),
+}
+
+impl ThreadPoolBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(self) -> Result<ThreadPool, ThreadPoolBuildError> {
+        Ok(ThreadPool { _private: () })
+    }
+
+    /// Does nothing, since the global pool is always the calling thread.
+    pub fn build_global(self) -> Result<(), ThreadPoolBuildError> {
+        Ok(())
+    }
+
+    pub fn num_threads(self, _num_threads: usize) -> Self {
+        self
+    }
+
+    pub fn thread_name<F>(self, _closure: F) -> Self
+    where
+        F: FnMut(usize) -> alloc::string::String + 'static,
+    {
+        self
+    }
+
+    pub fn stack_size(self, _stack_size: usize) -> Self {
+        self
+    }
+}
+
+/// The error of building a [ThreadPool], which never occurs.
+#[derive(Debug)]
+pub struct ThreadPoolBuildError {
+    _private: (),
+}
+
+impl fmt::Display for ThreadPoolBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("failed to build the thread pool")
+    }
+}
+
+impl core::error::Error for ThreadPoolBuildError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::{prelude::*, *};
+
+    #[test]
+    fn test_join_and_scope_order() {
+        let counter = AtomicUsize::new(0);
+        let next = || counter.fetch_add(1, Ordering::Relaxed);
+        assert_eq!(join(next, next), (0, 1));
+
+        let mut order = vec![];
+        scope(|s| {
+            s.spawn(|_| order.push(next()));
+        });
+        order.push(next());
+        assert_eq!(order, [2, 3]);
+    }
+
+    #[test]
+    fn test_parallel_iterators() {
+        let mut v: Vec<u32> = (0..100u32).into_par_iter().rev().collect();
+        let sum: u32 = v.par_iter().map(|x| x * 2).sum();
+        assert_eq!(sum, 9900);
+
+        v.par_sort_unstable();
+        assert!(v.par_iter().enumerate().all(|(i, &x)| i as u32 == x));
+
+        v.par_chunks_mut(10).for_each(|chunk| chunk.reverse());
+        assert_eq!(v[..3], [9, 8, 7]);
+
+        let total = v
+            .par_iter()
+            .fold(|| 0u64, |acc, &x| acc + x as u64)
+            .reduce(|| 0, |a, b| a + b);
+        assert_eq!(total, 4950);
+
+        let (even, odd): (Vec<u32>, Vec<u32>) = v.par_iter().partition(|&&x| x % 2 == 0);
+        assert_eq!((even.len(), odd.len()), (50, 50));
+
+        let found: Result<(), u32> =
+            v.par_iter()
+                .try_for_each(|&x| if x == 42 { Err(x) } else { Ok(()) });
+        assert_eq!(found, Err(42));
+    }
+}
@@ -0,0 +1,148 @@
+//! Parallel iterators over slices and parallel sorting, all of which run sequentially.
+
+use alloc::vec::{self, Vec};
+use core::{cmp::Ordering, ops::RangeBounds, slice};
+
+use super::iter::ParIter;
+
+/// Parallel methods on shared slices.
+pub trait ParallelSlice<T> {
+    fn as_parallel_slice(&self) -> &[T];
+
+    fn par_split<P>(&self, separator: P) -> ParIter<slice::Split<'_, T, P>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        ParIter::from(self.as_parallel_slice().split(separator))
+    }
+
+    fn par_windows(&self, window_size: usize) -> ParIter<slice::Windows<'_, T>> {
+        ParIter::from(self.as_parallel_slice().windows(window_size))
+    }
+
+    fn par_chunks(&self, chunk_size: usize) -> ParIter<slice::Chunks<'_, T>> {
+        ParIter::from(self.as_parallel_slice().chunks(chunk_size))
+    }
+
+    fn par_chunks_exact(&self, chunk_size: usize) -> ParIter<slice::ChunksExact<'_, T>> {
+        ParIter::from(self.as_parallel_slice().chunks_exact(chunk_size))
+    }
+
+    fn par_rchunks(&self, chunk_size: usize) -> ParIter<slice::RChunks<'_, T>> {
+        ParIter::from(self.as_parallel_slice().rchunks(chunk_size))
+    }
+
+    fn par_rchunks_exact(&self, chunk_size: usize) -> ParIter<slice::RChunksExact<'_, T>> {
+        ParIter::from(self.as_parallel_slice().rchunks_exact(chunk_size))
+    }
+}
+
+impl<T> ParallelSlice<T> for [T] {
+    fn as_parallel_slice(&self) -> &[T] {
+        self
+    }
+}
+
+/// Parallel methods on mutable slices.
+pub trait ParallelSliceMut<T> {
+    fn as_parallel_slice_mut(&mut self) -> &mut [T];
+
+    fn par_split_mut<P>(&mut self, separator: P) -> ParIter<slice::SplitMut<'_, T, P>>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        ParIter::from(self.as_parallel_slice_mut().split_mut(separator))
+    }
+
+    fn par_chunks_mut(&mut self, chunk_size: usize) -> ParIter<slice::ChunksMut<'_, T>> {
+        ParIter::from(self.as_parallel_slice_mut().chunks_mut(chunk_size))
+    }
+
+    fn par_chunks_exact_mut(&mut self, chunk_size: usize) -> ParIter<slice::ChunksExactMut<'_, T>> {
+        ParIter::from(self.as_parallel_slice_mut().chunks_exact_mut(chunk_size))
+    }
+
+    fn par_rchunks_mut(&mut self, chunk_size: usize) -> ParIter<slice::RChunksMut<'_, T>> {
+        ParIter::from(self.as_parallel_slice_mut().rchunks_mut(chunk_size))
+    }
+
+    fn par_rchunks_exact_mut(
+        &mut self,
+        chunk_size: usize,
+    ) -> ParIter<slice::RChunksExactMut<'_, T>> {
+        ParIter::from(self.as_parallel_slice_mut().rchunks_exact_mut(chunk_size))
+    }
+
+    fn par_sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_parallel_slice_mut().sort()
+    }
+
+    fn par_sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_parallel_slice_mut().sort_by(compare)
+    }
+
+    fn par_sort_by_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.as_parallel_slice_mut().sort_by_key(f)
+    }
+
+    fn par_sort_by_cached_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.as_parallel_slice_mut().sort_by_cached_key(f)
+    }
+
+    fn par_sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_parallel_slice_mut().sort_unstable()
+    }
+
+    fn par_sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_parallel_slice_mut().sort_unstable_by(compare)
+    }
+
+    fn par_sort_unstable_by_key<K, F>(&mut self, f: F)
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.as_parallel_slice_mut().sort_unstable_by_key(f)
+    }
+}
+
+impl<T> ParallelSliceMut<T> for [T] {
+    fn as_parallel_slice_mut(&mut self) -> &mut [T] {
+        self
+    }
+}
+
+/// Parallel draining of a range of items.
+pub trait ParallelDrainRange<Idx = usize> {
+    type Iter;
+
+    fn par_drain<R: RangeBounds<Idx>>(self, range: R) -> Self::Iter;
+}
+
+impl<'data, T> ParallelDrainRange<usize> for &'data mut Vec<T> {
+    type Iter = ParIter<vec::Drain<'data, T>>;
+
+    fn par_drain<R: RangeBounds<usize>>(self, range: R) -> Self::Iter {
+        ParIter::from(self.drain(range))
+    }
+}
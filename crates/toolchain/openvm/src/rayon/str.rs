@@ -0,0 +1,44 @@
+//! Parallel iterators over strings, all of which run sequentially.
+
+use core::str;
+
+use super::iter::ParIter;
+
+/// Parallel methods on string slices.
+pub trait ParallelString {
+    fn as_parallel_string(&self) -> &str;
+
+    fn par_chars(&self) -> ParIter<str::Chars<'_>> {
+        ParIter::from(self.as_parallel_string().chars())
+    }
+
+    fn par_char_indices(&self) -> ParIter<str::CharIndices<'_>> {
+        ParIter::from(self.as_parallel_string().char_indices())
+    }
+
+    fn par_bytes(&self) -> ParIter<str::Bytes<'_>> {
+        ParIter::from(self.as_parallel_string().bytes())
+    }
+
+    fn par_encode_utf16(&self) -> ParIter<str::EncodeUtf16<'_>> {
+        ParIter::from(self.as_parallel_string().encode_utf16())
+    }
+
+    fn par_lines(&self) -> ParIter<str::Lines<'_>> {
+        ParIter::from(self.as_parallel_string().lines())
+    }
+
+    fn par_split_whitespace(&self) -> ParIter<str::SplitWhitespace<'_>> {
+        ParIter::from(self.as_parallel_string().split_whitespace())
+    }
+
+    fn par_split_ascii_whitespace(&self) -> ParIter<str::SplitAsciiWhitespace<'_>> {
+        ParIter::from(self.as_parallel_string().split_ascii_whitespace())
+    }
+}
+
+impl ParallelString for str {
+    fn as_parallel_string(&self) -> &str {
+        self
+    }
+}
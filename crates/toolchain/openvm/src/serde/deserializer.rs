@@ -2,7 +2,7 @@ use alloc::{string::String, vec::Vec};
 
 use bytemuck::Pod;
 use openvm_platform::{align_up, WORD_SIZE};
-use serde::de::{DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed, IntoDeserializer, Visitor};
 
 use super::err::{Error, Result};
 
@@ -16,6 +16,17 @@ pub trait WordRead {
     /// padding up to the next word boundary.  Returns an error if EOF was
     /// encountered.
     fn read_padded_bytes(&mut self, bytes: &mut [u8]) -> Result<()>;
+
+    /// Borrow the next `len` bytes from input, and discard the padding up
+    /// to the next word boundary.  Returns `Ok(None)` without consuming any
+    /// input if the input cannot be borrowed for `'de`, in which case the
+    /// bytes are copied with [read_padded_bytes](Self::read_padded_bytes).
+    fn borrow_padded_bytes<'de>(&mut self, _len: usize) -> Result<Option<&'de [u8]>>
+    where
+        Self: 'de,
+    {
+        Ok(None)
+    }
 }
 
 // Allow borrowed WordReads to work transparently
@@ -27,9 +38,16 @@ impl<R: WordRead + ?Sized> WordRead for &mut R {
     fn read_padded_bytes(&mut self, bytes: &mut [u8]) -> Result<()> {
         (**self).read_padded_bytes(bytes)
     }
+
+    fn borrow_padded_bytes<'de>(&mut self, len: usize) -> Result<Option<&'de [u8]>>
+    where
+        Self: 'de,
+    {
+        (**self).borrow_padded_bytes(len)
+    }
 }
 
-impl WordRead for &[u32] {
+impl<'a> WordRead for &'a [u32] {
     fn read_words(&mut self, out: &mut [u32]) -> Result<()> {
         if out.len() > self.len() {
             Err(Error::DeserializeUnexpectedEnd)
@@ -50,6 +68,20 @@ impl WordRead for &[u32] {
             Ok(())
         }
     }
+
+    fn borrow_padded_bytes<'de>(&mut self, len: usize) -> Result<Option<&'de [u8]>>
+    where
+        Self: 'de,
+    {
+        let words: &'a [u32] = self;
+        let bytes: &'a [u8] = bytemuck::cast_slice(words);
+        if len > bytes.len() {
+            Err(Error::DeserializeUnexpectedEnd)
+        } else {
+            (_, *self) = words.split_at(align_up(len, WORD_SIZE) / WORD_SIZE);
+            Ok(Some(&bytes[..len]))
+        }
+    }
 }
 
 /// Deserialize a slice into the specified type.
@@ -73,6 +105,17 @@ pub fn from_slice<T: DeserializeOwned, P: Pod>(slice: &[P]) -> Result<T> {
     }
 }
 
+/// Deserialize `words` into the specified type, borrowing from `words`.
+///
+/// Unlike [from_slice], `T` may borrow strings and byte slices from `words`
+/// instead of copying them, e.g. a struct with `&str` or `&[u8]` fields. Byte
+/// slices can only be borrowed if they were serialized as bytes, e.g. with
+/// `serde_bytes`, since `[u8]` is otherwise serialized as a sequence of words.
+pub fn from_words<'de, T: Deserialize<'de>>(words: &'de [u32]) -> Result<T> {
+    let mut deserializer = Deserializer::new(words);
+    T::deserialize(&mut deserializer)
+}
+
 /// Enables deserializing from a WordRead
 pub struct Deserializer<'de, R: WordRead + 'de> {
     reader: R,
@@ -322,6 +365,10 @@ impl<'de, R: WordRead + 'de> serde::Deserializer<'de> for &'_ mut Deserializer<'
         V: Visitor<'de>,
     {
         let len_bytes = self.try_take_word()? as usize;
+        if let Some(bytes) = self.reader.borrow_padded_bytes(len_bytes)? {
+            let s = core::str::from_utf8(bytes).map_err(|_| Error::DeserializeBadChar)?;
+            return visitor.visit_borrowed_str(s);
+        }
         // Optimization opportunity: consider using MaybeUninit
         let mut bytes = vec![0u8; len_bytes];
         self.reader.read_padded_bytes(&mut bytes)?;
@@ -340,6 +387,9 @@ impl<'de, R: WordRead + 'de> serde::Deserializer<'de> for &'_ mut Deserializer<'
         V: Visitor<'de>,
     {
         let len_bytes = self.try_take_word()? as usize;
+        if let Some(bytes) = self.reader.borrow_padded_bytes(len_bytes)? {
+            return visitor.visit_borrowed_bytes(bytes);
+        }
         // We always allocate vec to be word-aligned
         let capacity = len_bytes.div_ceil(WORD_SIZE) * WORD_SIZE;
         // SAFETY: read_padded_bytes **must** error if the
@@ -577,4 +627,25 @@ mod tests {
         };
         assert_eq!(expected, from_slice(&words).unwrap());
     }
+
+    #[test]
+    fn test_borrowed() {
+        #[derive(Deserialize, PartialEq, Debug)]
+        struct Test<'a> {
+            name: &'a str,
+            data: &'a [u8],
+        }
+
+        let words = [3, 0x00636261, 5, 0x04030201, 0x00000005];
+        let test: Test = from_words(&words).unwrap();
+        assert_eq!(
+            test,
+            Test {
+                name: "abc",
+                data: &[1, 2, 3, 4, 5],
+            }
+        );
+        assert_eq!(test.name.as_ptr(), words[1..].as_ptr() as *const u8);
+        assert_eq!(test.data.as_ptr(), words[3..].as_ptr() as *const u8);
+    }
 }
@@ -7,7 +7,7 @@ mod deserializer;
 mod err;
 mod serializer;
 
-pub use deserializer::{from_slice, Deserializer, WordRead};
+pub use deserializer::{from_slice, from_words, Deserializer, WordRead};
 pub use err::{Error, Result};
 pub use serializer::{to_vec, to_vec_with_capacity, Serializer, WordWrite};
 
@@ -0,0 +1,346 @@
+//! Green threads for the zkVM, see the [module docs](super).
+//!
+//! Every spawned thread has its own stack on the heap. Switching threads saves the registers
+//! preserved across calls of the running thread and restores those of the next thread, so a
+//! switch looks like an ordinary function call to both threads.
+
+use alloc::{
+    alloc::{alloc, dealloc, handle_alloc_error},
+    boxed::Box,
+    collections::BTreeMap,
+    rc::Rc,
+    vec::Vec,
+};
+use core::{alloc::Layout, cell::UnsafeCell, marker::PhantomData, mem, ptr::NonNull};
+
+use super::STACK_SIZE;
+
+/// The registers preserved across calls, saved when a thread is switched out.
+#[repr(C)]
+#[derive(Default)]
+struct Context {
+    ra: usize,
+    sp: usize,
+    s: [usize; 12],
+}
+
+core::arch::global_asm!(
+    ".global openvm_thread_switch",
+    "openvm_thread_switch:",
+    "sw ra, 0(a0)",
+    "sw sp, 4(a0)",
+    "sw s0, 8(a0)",
+    "sw s1, 12(a0)",
+    "sw s2, 16(a0)",
+    "sw s3, 20(a0)",
+    "sw s4, 24(a0)",
+    "sw s5, 28(a0)",
+    "sw s6, 32(a0)",
+    "sw s7, 36(a0)",
+    "sw s8, 40(a0)",
+    "sw s9, 44(a0)",
+    "sw s10, 48(a0)",
+    "sw s11, 52(a0)",
+    "lw ra, 0(a1)",
+    "lw sp, 4(a1)",
+    "lw s0, 8(a1)",
+    "lw s1, 12(a1)",
+    "lw s2, 16(a1)",
+    "lw s3, 20(a1)",
+    "lw s4, 24(a1)",
+    "lw s5, 28(a1)",
+    "lw s6, 32(a1)",
+    "lw s7, 36(a1)",
+    "lw s8, 40(a1)",
+    "lw s9, 44(a1)",
+    "lw s10, 48(a1)",
+    "lw s11, 52(a1)",
+    "ret",
+);
+
+extern "C" {
+    /// Saves the context of the running thread to `from` and resumes the thread of `to`.
+    fn openvm_thread_switch(from: *mut Context, to: *const Context);
+}
+
+struct Stack(NonNull<u8>);
+
+impl Stack {
+    // SAFETY: 16 is a power of two and the size does not overflow when rounded up to it.
+    const LAYOUT: Layout = unsafe { Layout::from_size_align_unchecked(STACK_SIZE, 16) };
+
+    fn new() -> Self {
+        // SAFETY: the layout has a non-zero size.
+        let ptr = unsafe { alloc(Self::LAYOUT) };
+        Self(NonNull::new(ptr).unwrap_or_else(|| handle_alloc_error(Self::LAYOUT)))
+    }
+
+    fn top(&self) -> usize {
+        self.0.as_ptr() as usize + STACK_SIZE
+    }
+}
+
+impl Drop for Stack {
+    fn drop(&mut self) {
+        // SAFETY: the stack was allocated with the same layout.
+        unsafe { dealloc(self.0.as_ptr(), Self::LAYOUT) }
+    }
+}
+
+struct Thread {
+    context: Context,
+    /// The stack of a spawned thread, freed when the thread is joined, or after it finishes if
+    /// it is detached. `None` for the main thread, which runs on the stack of the program.
+    _stack: Option<Stack>,
+    /// The closure of the thread, until it starts.
+    task: Option<Box<dyn FnOnce()>>,
+    finished: bool,
+    /// Whether the handle of the thread was dropped without joining it.
+    detached: bool,
+    /// The thread this thread is waiting to join.
+    joining: Option<usize>,
+}
+
+impl Thread {
+    fn main() -> Self {
+        Self {
+            context: Context::default(),
+            _stack: None,
+            task: None,
+            finished: false,
+            detached: false,
+            joining: None,
+        }
+    }
+}
+
+struct Scheduler {
+    /// The threads which have neither been joined nor finished detached, by id in the order of
+    /// spawning. The main thread has id 0 and is added when the first thread is spawned.
+    threads: BTreeMap<usize, Thread>,
+    current: usize,
+    next_id: usize,
+}
+
+impl Scheduler {
+    fn is_runnable(&self, thread: &Thread) -> bool {
+        !thread.finished
+            && thread
+                .joining
+                .is_none_or(|id| self.threads.get(&id).is_none_or(|t| t.finished))
+    }
+
+    /// The first runnable thread after the current one, wrapping around to the main thread.
+    fn next(&self) -> Option<usize> {
+        self.threads
+            .range(self.current + 1..)
+            .chain(self.threads.range(..=self.current))
+            .find(|(_, thread)| self.is_runnable(thread))
+            .map(|(&id, _)| id)
+    }
+
+    fn current_thread(&mut self) -> &mut Thread {
+        self.threads.get_mut(&self.current).unwrap()
+    }
+
+    /// Frees the finished detached threads, except the current one, whose stack is in use.
+    fn remove_detached(&mut self) {
+        let current = self.current;
+        self.threads
+            .retain(|&id, thread| id == current || !(thread.detached && thread.finished));
+    }
+}
+
+struct SchedulerCell(UnsafeCell<Scheduler>);
+
+// SAFETY: the zkVM is single-threaded, so the scheduler is never accessed concurrently.
+unsafe impl Sync for SchedulerCell {}
+
+static SCHEDULER: SchedulerCell = SchedulerCell(UnsafeCell::new(Scheduler {
+    threads: BTreeMap::new(),
+    current: 0,
+    next_id: 1,
+}));
+
+/// # Safety
+/// The returned reference must not be used after the current thread is switched out.
+unsafe fn scheduler() -> &'static mut Scheduler {
+    &mut *SCHEDULER.0.get()
+}
+
+/// Switches to the next runnable thread, unless the current thread is the only one.
+///
+/// # Panics
+/// If no thread is runnable, as every unfinished thread is joining another.
+fn schedule() {
+    // SAFETY: the reference to the scheduler is dropped before the switch.
+    let (from, to) = unsafe {
+        let s = scheduler();
+        s.remove_detached();
+        let next = s
+            .next()
+            .expect("deadlock: every unfinished thread is joining another thread");
+        if next == s.current {
+            return;
+        }
+        let from = &mut s.current_thread().context as *mut Context;
+        let to = &s.threads[&next].context as *const Context;
+        s.current = next;
+        (from, to)
+    };
+    // SAFETY: `to` was saved by a previous switch or set up by `spawn`.
+    unsafe { openvm_thread_switch(from, to) }
+}
+
+/// The entry point of spawned threads, which `spawn` sets as their return address.
+extern "C" fn thread_main() -> ! {
+    // SAFETY: the reference is dropped before the task runs.
+    let task = unsafe { scheduler() }.current_thread().task.take().unwrap();
+    task();
+    // SAFETY: the reference is dropped before the switch.
+    unsafe { scheduler() }.current_thread().finished = true;
+    schedule();
+    unreachable!("a finished thread was resumed");
+}
+
+fn spawn(task: Box<dyn FnOnce()>) -> usize {
+    // SAFETY: no switch happens in this function.
+    let s = unsafe { scheduler() };
+    s.threads.entry(0).or_insert_with(Thread::main);
+    let id = s.next_id;
+    s.next_id += 1;
+    let stack = Stack::new();
+    let context = Context {
+        ra: thread_main as *const () as usize,
+        sp: stack.top(),
+        ..Default::default()
+    };
+    s.threads.insert(
+        id,
+        Thread {
+            context,
+            _stack: Some(stack),
+            task: Some(task),
+            finished: false,
+            detached: false,
+            joining: None,
+        },
+    );
+    id
+}
+
+/// Waits for the thread `id` to finish and frees its stack.
+fn join(id: usize) {
+    // SAFETY: the reference is dropped before the switch.
+    let finished = unsafe {
+        let s = scheduler();
+        assert_ne!(id, s.current, "a thread cannot join itself");
+        let finished = s.threads[&id].finished;
+        if !finished {
+            s.current_thread().joining = Some(id);
+        }
+        finished
+    };
+    if !finished {
+        schedule();
+    }
+    // SAFETY: no switch happens after this point.
+    let s = unsafe { scheduler() };
+    s.current_thread().joining = None;
+    s.threads.remove(&id);
+}
+
+/// Lets the thread `id` run without being joined. It is freed after it finishes.
+fn detach(id: usize) {
+    // SAFETY: no switch happens in this function.
+    let s = unsafe { scheduler() };
+    s.threads.get_mut(&id).unwrap().detached = true;
+    s.remove_detached();
+}
+
+fn is_finished(id: usize) -> bool {
+    // SAFETY: no switch happens in this function.
+    unsafe { scheduler() }.threads[&id].finished
+}
+
+pub(super) fn yield_now() {
+    schedule();
+}
+
+/// A spawned thread and the slot for its output, which the thread shares until it finishes.
+pub(super) struct Task<'a, T> {
+    /// The thread, until it is joined or detached.
+    id: Option<usize>,
+    output: Rc<UnsafeCell<Option<T>>>,
+    _marker: PhantomData<&'a ()>,
+}
+
+// SAFETY: the zkVM is single-threaded, so the output is never shared across hardware threads.
+unsafe impl<T: Send> Send for Task<'_, T> {}
+
+impl<'a, T: 'a> Task<'a, T> {
+    pub(super) fn new(f: impl FnOnce() -> T + Send + 'a, scope: Option<&ScopeThreads>) -> Self {
+        let output = Rc::new(UnsafeCell::new(None));
+        let slot = output.clone();
+        // SAFETY: the slot is only accessed through `output` after the thread has finished.
+        let task: Box<dyn FnOnce() + 'a> = Box::new(move || unsafe { *slot.get() = Some(f()) });
+        // SAFETY: `'a` is `'static` unless the thread is scoped, and a scoped thread is joined at
+        // the end of its scope if it was detached or its task was leaked.
+        let task: Box<dyn FnOnce() + 'static> = unsafe { mem::transmute(task) };
+        let id = spawn(task);
+        if let Some(scope) = scope {
+            scope.push(id);
+        }
+        Self {
+            id: Some(id),
+            output,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Task<'_, T> {
+    pub(super) fn run(&mut self) -> T {
+        join(self.id.take().expect("thread has already run"));
+        // SAFETY: the thread has finished, so it no longer accesses the slot.
+        unsafe { &mut *self.output.get() }.take().unwrap()
+    }
+
+    pub(super) fn is_finished(&self) -> bool {
+        self.id.is_none_or(is_finished)
+    }
+}
+
+impl<T> Drop for Task<'_, T> {
+    fn drop(&mut self) {
+        if let Some(id) = self.id.take() {
+            detach(id);
+        }
+    }
+}
+
+/// The threads spawned in a scope, which are joined at the end of the scope in case they were
+/// detached or their tasks were leaked.
+#[derive(Default)]
+pub(super) struct ScopeThreads(UnsafeCell<Vec<usize>>);
+
+// SAFETY: the zkVM is single-threaded, and no switch happens while the list is borrowed.
+unsafe impl Sync for ScopeThreads {}
+
+impl ScopeThreads {
+    fn push(&self, id: usize) {
+        // SAFETY: see the `Sync` impl.
+        unsafe { &mut *self.0.get() }.push(id);
+    }
+
+    pub(super) fn join_all(&self) {
+        // SAFETY: see the `Sync` impl.
+        let ids = mem::take(unsafe { &mut *self.0.get() });
+        for id in ids {
+            // SAFETY: the reference is dropped before the join.
+            if unsafe { scheduler() }.threads.contains_key(&id) {
+                join(id);
+            }
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! A deterministic stand-in for `std::thread`.
+//!
+//! The zkVM has a single hardware thread, so spawned threads never run concurrently. Instead,
+//! they are green threads which the guest switches between cooperatively. The running thread
+//! keeps running until it:
+//! - yields with [yield_now] or [sleep],
+//! - joins a thread which has not finished, or
+//! - finishes.
+//!
+//! The next thread to run is then the first runnable thread spawned after the running one,
+//! wrapping around to the main thread. A spawned thread therefore first runs when the thread that
+//! spawned it switches. Switches only happen at these points and the order only depends on the
+//! program, so every execution interleaves the threads the same way. Threads can wait on each
+//! other by yielding, e.g. until another thread has set a flag. If every unfinished thread is
+//! waiting to join another one, the guest panics.
+//!
+//! Dropping a handle without joining it detaches the thread, as in `std::thread`: the thread is
+//! still switched to, and is freed once it finishes. A detached thread which has not finished
+//! when the guest terminates never finishes. All threads spawned in a [scope] have finished when
+//! the scope returns. Each spawned thread has a stack of [STACK_SIZE] bytes on the heap, whose
+//! overflow is not detected.
+//!
+//! The functions have the same signatures and bounds as in `std::thread`, so a crate can use
+//! this module in place of `std::thread` when built for the zkVM. The zkVM target of the standard
+//! library implements `std::thread` with its generic unsupported threads, which never call into
+//! the [platform ABI](crate::pal_abi), so `std::thread::spawn` itself still panics.
+//!
+//! On other targets, threads cannot be switched: a spawned thread runs to completion on the
+//! calling thread when its handle is joined or dropped, and [yield_now] does nothing. Running a
+//! detached thread right away is one of the schedules `std::thread` allows.
+
+use core::{any::Any, convert::Infallible, marker::PhantomData, num::NonZeroUsize, time::Duration};
+
+#[cfg(target_os = "zkvm")]
+use self::green as imp;
+#[cfg(not(target_os = "zkvm"))]
+use self::serial as imp;
+
+#[cfg(target_os = "zkvm")]
+mod green;
+#[cfg(not(target_os = "zkvm"))]
+mod serial;
+
+/// Size in bytes of the stack of a spawned thread on the zkVM.
+pub const STACK_SIZE: usize = 64 * 1024;
+
+/// The result of joining a thread. A panic aborts the guest, so joining never returns an error.
+pub type Result<T> = core::result::Result<T, alloc::boxed::Box<dyn Any + Send + 'static>>;
+
+/// An owned permission to join on a thread.
+pub struct JoinHandle<T>(imp::Task<'static, T>);
+
+impl<T> JoinHandle<T> {
+    /// Waits for the thread to finish and returns its output.
+    pub fn join(mut self) -> Result<T> {
+        Ok(self.0.run())
+    }
+
+    /// Whether the thread has finished. Always `false` on targets other than the zkVM, where the
+    /// thread only runs when its handle is joined or dropped.
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+}
+
+/// Spawns a thread which runs `f`.
+pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    JoinHandle(imp::Task::new(f, None))
+}
+
+/// A scope to spawn threads which may borrow from outside the scope.
+pub struct Scope<'scope, 'env: 'scope> {
+    threads: imp::ScopeThreads,
+    scope: PhantomData<&'scope mut &'scope ()>,
+    env: PhantomData<&'env mut &'env ()>,
+}
+
+/// An owned permission to join on a scoped thread.
+pub struct ScopedJoinHandle<'scope, T>(imp::Task<'scope, T>);
+
+impl<T> ScopedJoinHandle<'_, T> {
+    /// Waits for the thread to finish and returns its output.
+    pub fn join(mut self) -> Result<T> {
+        Ok(self.0.run())
+    }
+
+    /// Whether the thread has finished, see [JoinHandle::is_finished].
+    pub fn is_finished(&self) -> bool {
+        self.0.is_finished()
+    }
+}
+
+impl<'scope> Scope<'scope, '_> {
+    /// Spawns a scoped thread which runs `f`.
+    pub fn spawn<F, T>(&'scope self, f: F) -> ScopedJoinHandle<'scope, T>
+    where
+        F: FnOnce() -> T + Send + 'scope,
+        T: Send + 'scope,
+    {
+        ScopedJoinHandle(imp::Task::new(f, Some(&self.threads)))
+    }
+}
+
+/// Creates a scope for spawning scoped threads. Every thread spawned in the scope has finished
+/// when this returns.
+pub fn scope<'env, F, T>(f: F) -> T
+where
+    F: for<'scope> FnOnce(&'scope Scope<'scope, 'env>) -> T,
+{
+    let scope = Scope {
+        threads: Default::default(),
+        scope: PhantomData,
+        env: PhantomData,
+    };
+    let output = f(&scope);
+    scope.threads.join_all();
+    output
+}
+
+/// Switches to the next runnable thread, if any, on the zkVM. Does nothing on other targets.
+pub fn yield_now() {
+    imp::yield_now()
+}
+
+/// Yields with [yield_now], since the guest has no clock.
+pub fn sleep(_dur: Duration) {
+    yield_now()
+}
+
+/// Always 1.
+pub fn available_parallelism() -> core::result::Result<NonZeroUsize, Infallible> {
+    Ok(NonZeroUsize::MIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+
+    #[test]
+    fn test_spawn_join() {
+        let handles: Vec<_> = (0..4u32).map(|i| spawn(move || i * i)).collect();
+        let squares: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        assert_eq!(squares, [0, 1, 4, 9]);
+    }
+
+    #[test]
+    fn test_detached_thread_runs_on_drop() {
+        static RUNS: AtomicUsize = AtomicUsize::new(0);
+        let handle = spawn(|| RUNS.fetch_add(1, Ordering::Relaxed));
+        assert_eq!(RUNS.load(Ordering::Relaxed), 0);
+        drop(handle);
+        assert_eq!(RUNS.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_scoped_threads_run_in_join_order() {
+        let counter = AtomicUsize::new(0);
+        let next = || counter.fetch_add(1, Ordering::Relaxed);
+        scope(|s| {
+            let first = s.spawn(next);
+            let second = s.spawn(next);
+            assert_eq!(second.join().unwrap(), 0);
+            assert_eq!(first.join().unwrap(), 1);
+            s.spawn(next);
+            assert_eq!(counter.load(Ordering::Relaxed), 3);
+        });
+    }
+}
@@ -0,0 +1,41 @@
+//! Serial threads for targets other than the zkVM, where threads cannot be switched: a thread runs
+//! to completion on the calling thread when it is joined.
+
+use alloc::boxed::Box;
+
+/// A closure which runs at most once: when it is first run or when it is dropped, which is when
+/// a detached thread runs.
+pub(super) struct Task<'a, T>(Option<Box<dyn FnOnce() -> T + Send + 'a>>);
+
+impl<'a, T> Task<'a, T> {
+    pub(super) fn new(f: impl FnOnce() -> T + Send + 'a, _scope: Option<&ScopeThreads>) -> Self {
+        Self(Some(Box::new(f)))
+    }
+
+    pub(super) fn run(&mut self) -> T {
+        (self.0.take().expect("thread has already run"))()
+    }
+
+    /// Always `false`, since the thread only runs when it is joined.
+    pub(super) fn is_finished(&self) -> bool {
+        false
+    }
+}
+
+impl<T> Drop for Task<'_, T> {
+    fn drop(&mut self) {
+        if let Some(f) = self.0.take() {
+            f();
+        }
+    }
+}
+
+/// Threads never outlive their [Task], so there is nothing to join at the end of a scope.
+#[derive(Default)]
+pub(super) struct ScopeThreads;
+
+impl ScopeThreads {
+    pub(super) fn join_all(&self) {}
+}
+
+pub(super) fn yield_now() {}
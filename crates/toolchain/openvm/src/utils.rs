@@ -1,12 +1,184 @@
-#[cfg(not(target_os = "zkvm"))]
+//! Host-side helpers for preparing guest inputs and checking guest outputs, shared by host tooling
+//! and tests.
+//!
+//! - Limb conversions between [BigUint] and little-endian limbs of any width, matching the
+//!   little-endian byte layout of the modular and elliptic curve guest types.
+//! - Parsing of numeric literals with the semantics of the `moduli_declare!` macros, see
+//!   [string_to_bytes].
+//! - Decoding of hex strings and, with the `json` feature, loading of test vectors.
+
+use alloc::{string::String, vec::Vec};
+
 use num_bigint::BigUint;
 
-#[inline]
-#[cfg(not(target_os = "zkvm"))]
-#[allow(dead_code)]
 /// Convert a `BigUint` to a `[u8; NUM_LIMBS]` in little-endian format.
+///
+/// # Panics
+/// If `x` does not fit in `NUM_LIMBS` bytes.
+#[inline]
 pub fn biguint_to_limbs<const NUM_LIMBS: usize>(x: &BigUint) -> [u8; NUM_LIMBS] {
-    let mut sm = x.to_bytes_le();
-    sm.resize(NUM_LIMBS, 0);
-    sm.try_into().unwrap()
+    biguint_to_limbs_with_bits::<NUM_LIMBS>(x, 8).map(|limb| limb as u8)
+}
+
+/// Convert a `BigUint` to `NUM_LIMBS` little-endian limbs of `limb_bits` bits each.
+///
+/// # Panics
+/// If `limb_bits` is not in `1..=32`, or if `x` does not fit in `NUM_LIMBS` limbs.
+pub fn biguint_to_limbs_with_bits<const NUM_LIMBS: usize>(
+    x: &BigUint,
+    limb_bits: usize,
+) -> [u32; NUM_LIMBS] {
+    assert!(
+        (1..=32).contains(&limb_bits),
+        "limb_bits must be between 1 and 32"
+    );
+    assert!(
+        x.bits() <= (NUM_LIMBS * limb_bits) as u64,
+        "{x} does not fit in {NUM_LIMBS} limbs of {limb_bits} bits"
+    );
+    let mask = (BigUint::from(1u8) << limb_bits) - 1u8;
+    core::array::from_fn(|i| {
+        let limb = (x >> (i * limb_bits)) & &mask;
+        limb.iter_u32_digits().next().unwrap_or(0)
+    })
+}
+
+/// Convert little-endian limbs of `limb_bits` bits each to a `BigUint`. Inverse of
+/// [biguint_to_limbs_with_bits].
+///
+/// # Panics
+/// If `limb_bits` is not in `1..=32`, or if a limb does not fit in `limb_bits` bits.
+pub fn limbs_to_biguint(limbs: &[u32], limb_bits: usize) -> BigUint {
+    assert!(
+        (1..=32).contains(&limb_bits),
+        "limb_bits must be between 1 and 32"
+    );
+    limbs.iter().rev().fold(BigUint::default(), |acc, &limb| {
+        assert!(
+            limb_bits == 32 || limb >> limb_bits == 0,
+            "limb {limb} does not fit in {limb_bits} bits"
+        );
+        (acc << limb_bits) + limb
+    })
+}
+
+/// Parse a numeric literal into its little-endian bytes, with the semantics of the
+/// `moduli_declare!` macros: a literal starting with `0x` is hexadecimal, may contain whitespace,
+/// and has one byte per two hex digits, including leading zeros; any other literal is decimal and
+/// has the minimal number of bytes, at least one.
+///
+/// Returns `None` if the literal contains an invalid digit.
+pub fn string_to_bytes(s: &str) -> Option<Vec<u8>> {
+    if let Some(hex) = s.strip_prefix("0x") {
+        let digits: Vec<char> = hex.chars().filter(|c| !c.is_whitespace()).collect();
+        // Bytes are taken from the end, so an odd number of digits leaves one digit for the most
+        // significant byte.
+        return digits
+            .rchunks(2)
+            .map(|byte| u8::from_str_radix(&byte.iter().collect::<String>(), 16).ok())
+            .collect();
+    }
+    if s.is_empty() {
+        return Some(Vec::new());
+    }
+    if !s.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    BigUint::parse_bytes(s.as_bytes(), 10).map(|x| x.to_bytes_le())
+}
+
+/// Parse a numeric literal, hexadecimal if it starts with `0x` and decimal otherwise, as in
+/// [string_to_bytes].
+pub fn parse_biguint(s: &str) -> Option<BigUint> {
+    string_to_bytes(s).map(|bytes| BigUint::from_bytes_le(&bytes))
+}
+
+/// Decode a hex string, with an optional `0x` prefix, into bytes in the order they are written.
+/// Returns `None` if the string has an odd number of digits or an invalid digit.
+pub fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    crate::codec::decode_hex(s.strip_prefix("0x").unwrap_or(s).as_bytes()).ok()
+}
+
+/// Deserialize test vectors from a JSON array.
+///
+/// # Panics
+/// If `json` is not a JSON array of `T`.
+#[cfg(feature = "json")]
+pub fn parse_test_vectors<T: serde::de::DeserializeOwned>(json: &str) -> Vec<T> {
+    serde_json::from_str(json).expect("test vectors are not a valid JSON array")
+}
+
+/// Read test vectors from a file holding a JSON array, see [parse_test_vectors].
+///
+/// # Panics
+/// If the file cannot be read or does not hold a JSON array of `T`.
+#[cfg(all(feature = "json", feature = "std"))]
+pub fn load_test_vectors<T: serde::de::DeserializeOwned>(
+    path: impl AsRef<std::path::Path>,
+) -> Vec<T> {
+    let path = path.as_ref();
+    let json = std::fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read test vectors from {}: {err}", path.display()));
+    parse_test_vectors(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+
+    #[test]
+    fn test_limbs_roundtrip() {
+        let x = BigUint::parse_bytes(b"123456789abcdef0123456789", 16).unwrap();
+        for limb_bits in [1, 8, 12, 29, 32] {
+            let limbs = biguint_to_limbs_with_bits::<128>(&x, limb_bits);
+            assert_eq!(limbs_to_biguint(&limbs, limb_bits), x);
+        }
+        assert_eq!(
+            biguint_to_limbs::<4>(&BigUint::from(0x01020304u32)),
+            [4, 3, 2, 1]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_limbs_overflow() {
+        biguint_to_limbs::<2>(&BigUint::from(0x10000u32));
+    }
+
+    #[test]
+    fn test_string_to_bytes_matches_macros() {
+        for s in [
+            "0",
+            "255",
+            "256",
+            "1000000007",
+            "0x1",
+            "0x0001",
+            "0xabc",
+            "0xFFFF FFFF",
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+            "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47",
+        ] {
+            assert_eq!(
+                string_to_bytes(s).unwrap(),
+                openvm_macros_common::string_to_bytes(s),
+                "{s}"
+            );
+        }
+        assert_eq!(string_to_bytes("0x0001"), Some(vec![1, 0]));
+        assert_eq!(string_to_bytes("12a"), None);
+        assert_eq!(string_to_bytes("0xg0"), None);
+        assert_eq!(parse_biguint("0x100"), parse_biguint("256"));
+    }
+
+    #[test]
+    fn test_decode_hex() {
+        assert_eq!(decode_hex("0x0102ff"), Some(vec![1, 2, 255]));
+        assert_eq!(decode_hex("0102FF"), Some(vec![1, 2, 255]));
+        assert_eq!(decode_hex("012"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex("+f"), None);
+    }
 }
@@ -0,0 +1,60 @@
+extern crate proc_macro;
+
+use std::path::PathBuf;
+
+use openvm_overlay::OverlayTree;
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Embeds the file at the given path, relative to the root of the crate being compiled, as an
+/// overlay. Evaluates to a `&'static openvm::overlay::Overlay`.
+///
+/// When compiling for the zkVM, the contents of the file are placed in the
+/// [openvm_overlay::OVERLAY_SECTION] section instead of the initial memory image, and only the
+/// root and length of the overlay are part of the program.
+#[proc_macro]
+pub fn include_overlay_bytes(input: TokenStream) -> TokenStream {
+    let path_lit = parse_macro_input!(input as LitStr);
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is not set");
+    let path = PathBuf::from(manifest_dir).join(path_lit.value());
+    let data = match std::fs::read(&path) {
+        Ok(data) => data,
+        Err(err) => {
+            return syn::Error::new(
+                path_lit.span(),
+                format!("failed to read {}: {err}", path.display()),
+            )
+            .to_compile_error()
+            .into();
+        }
+    };
+    if u32::try_from(data.len()).is_err() {
+        return syn::Error::new(path_lit.span(), "overlay does not fit in guest memory")
+            .to_compile_error()
+            .into();
+    }
+
+    let root = OverlayTree::new(&data).root();
+    let len = data.len();
+    let padding = len.next_multiple_of(4) - len;
+    let path = path.to_str().expect("overlay path is not valid UTF-8");
+    let section = openvm_overlay::OVERLAY_SECTION;
+
+    TokenStream::from(quote! {
+        {
+            #[cfg(target_os = "zkvm")]
+            #[used]
+            #[link_section = #section]
+            static OVERLAY_SECTION: ::openvm::overlay::OverlaySection<#len, #padding> =
+                ::openvm::overlay::OverlaySection::new(*include_bytes!(#path));
+            #[cfg(target_os = "zkvm")]
+            static OVERLAY: ::openvm::overlay::Overlay =
+                ::openvm::overlay::Overlay::new([#(#root),*], #len);
+            #[cfg(not(target_os = "zkvm"))]
+            static OVERLAY: ::openvm::overlay::Overlay =
+                ::openvm::overlay::Overlay::new([#(#root),*], include_bytes!(#path));
+            &OVERLAY
+        }
+    })
+}
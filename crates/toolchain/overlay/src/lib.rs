@@ -0,0 +1,207 @@
+//! Commitments to overlays: read-only byte strings which are shipped with a guest program outside
+//! of its initial memory image, and paged into guest memory on first access.
+//!
+//! An overlay is split into pages of [PAGE_SIZE] bytes, the last page being padded with zeros. The
+//! overlay is committed to by the root of a binary Merkle tree whose leaves are the SHA-256 hashes
+//! of its pages, padded with [EMPTY_DIGEST] up to a power of two. Leaves and internal nodes are
+//! hashed with distinct domain separators. The guest program embeds the root of each overlay it
+//! uses, and the host serves each page together with its Merkle proof under [page_key]. The digests
+//! are computed with `openvm-sha2`, so in the guest they use the sha256 extension.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{vec, vec::Vec};
+
+use openvm_sha2::Sha256;
+
+/// Name of the ELF section holding the contents of all overlays of a guest program. The section is
+/// not allocated, so its contents are not part of the initial memory image.
+///
+/// The section is a sequence of records, each consisting of the length of the overlay as a
+/// little-endian `u32`, followed by the contents of the overlay padded with zeros to a multiple of
+/// 4 bytes.
+pub const OVERLAY_SECTION: &str = ".openvm_overlay";
+/// Number of bytes paged into guest memory at a time.
+pub const PAGE_SIZE: usize = 1024;
+/// Number of bytes in a digest.
+pub const DIGEST_SIZE: usize = 32;
+/// Prefix of the `hint_load_by_key` key under which a page of an overlay is served.
+pub const KEY_PREFIX: &[u8] = b"openvm_overlay";
+/// Digest of a leaf beyond the last page, and root of the empty overlay.
+pub const EMPTY_DIGEST: Digest = [0; DIGEST_SIZE];
+
+const LEAF_DOMAIN: u8 = 0;
+const NODE_DOMAIN: u8 = 1;
+
+pub type Digest = [u8; DIGEST_SIZE];
+
+/// Returns the number of pages of an overlay of `len` bytes.
+pub const fn num_pages(len: usize) -> usize {
+    len.div_ceil(PAGE_SIZE)
+}
+
+/// Returns the number of siblings in the Merkle proof of a page of an overlay of `len` bytes.
+pub const fn proof_len(len: usize) -> usize {
+    num_pages(len).next_power_of_two().trailing_zeros() as usize
+}
+
+/// Returns the digest of a page. Pages shorter than [PAGE_SIZE] are padded with zeros.
+pub fn hash_page(page: &[u8]) -> Digest {
+    assert!(page.len() <= PAGE_SIZE, "page is too long");
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_DOMAIN]);
+    hasher.update(page);
+    hasher.update(&[0; PAGE_SIZE][page.len()..]);
+    hasher.finalize()
+}
+
+/// Returns the digest of an internal node with children `left` and `right`.
+pub fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_DOMAIN]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize()
+}
+
+/// Returns the `hint_load_by_key` key under which page `index` of the overlay with root `root` is
+/// served.
+pub fn page_key(root: &Digest, index: u32) -> Vec<u8> {
+    let mut key = Vec::with_capacity(KEY_PREFIX.len() + DIGEST_SIZE + 4);
+    key.extend_from_slice(KEY_PREFIX);
+    key.extend_from_slice(root);
+    key.extend_from_slice(&index.to_le_bytes());
+    key
+}
+
+/// Returns whether `page` is page `index` of an overlay of `len` bytes with root `root`, given the
+/// concatenated digests of its siblings from the leaf up to the root. `page` must be padded to
+/// [PAGE_SIZE] bytes.
+pub fn verify_page(root: &Digest, len: usize, index: usize, page: &[u8], siblings: &[u8]) -> bool {
+    if index >= num_pages(len)
+        || page.len() != PAGE_SIZE
+        || siblings.len() != proof_len(len) * DIGEST_SIZE
+    {
+        return false;
+    }
+    let mut node = hash_page(page);
+    let mut index = index;
+    for sibling in siblings.chunks_exact(DIGEST_SIZE) {
+        let sibling = sibling.try_into().unwrap();
+        node = if index & 1 == 0 {
+            hash_node(&node, sibling)
+        } else {
+            hash_node(sibling, &node)
+        };
+        index >>= 1;
+    }
+    node == *root
+}
+
+/// The Merkle tree of an overlay, used by the host to commit to the overlay and to serve its
+/// pages.
+#[derive(Clone, Debug)]
+pub struct OverlayTree {
+    /// The layers of the tree, from the leaves up to the root.
+    layers: Vec<Vec<Digest>>,
+}
+
+impl OverlayTree {
+    pub fn new(data: &[u8]) -> Self {
+        let mut leaves: Vec<Digest> = data.chunks(PAGE_SIZE).map(hash_page).collect();
+        leaves.resize(leaves.len().next_power_of_two(), EMPTY_DIGEST);
+        let mut layers = vec![leaves];
+        while layers.last().unwrap().len() > 1 {
+            let layer = layers
+                .last()
+                .unwrap()
+                .chunks_exact(2)
+                .map(|pair| hash_node(&pair[0], &pair[1]))
+                .collect();
+            layers.push(layer);
+        }
+        Self { layers }
+    }
+
+    pub fn root(&self) -> Digest {
+        self.layers.last().unwrap()[0]
+    }
+
+    /// Returns the concatenated digests of the siblings of page `index`, from the leaf up to the
+    /// root.
+    pub fn proof(&self, index: usize) -> Vec<u8> {
+        let mut index = index;
+        let mut proof = Vec::with_capacity((self.layers.len() - 1) * DIGEST_SIZE);
+        for layer in &self.layers[..self.layers.len() - 1] {
+            proof.extend_from_slice(&layer[index ^ 1]);
+            index >>= 1;
+        }
+        proof
+    }
+}
+
+/// Returns the hint served for page `index` of `data`: the page padded to [PAGE_SIZE] bytes,
+/// followed by its Merkle proof in `tree`.
+pub fn page_hint(data: &[u8], tree: &OverlayTree, index: usize) -> Vec<u8> {
+    let start = index * PAGE_SIZE;
+    let end = data.len().min(start + PAGE_SIZE);
+    let mut hint = data[start..end].to_vec();
+    hint.resize(PAGE_SIZE, 0);
+    hint.extend(tree.proof(index));
+    hint
+}
+
+/// Returns the contents of the overlays in the contents of an [OVERLAY_SECTION] section, or `None`
+/// if the section is malformed.
+pub fn parse_section(mut section: &[u8]) -> Option<Vec<&[u8]>> {
+    let mut overlays = Vec::new();
+    while !section.is_empty() {
+        let len = u32::from_le_bytes(section.get(..4)?.try_into().unwrap()) as usize;
+        overlays.push(section.get(4..4 + len)?);
+        section = section.get(4 + len.next_multiple_of(4)..)?;
+    }
+    Some(overlays)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_tree() {
+        for len in [0, 1, PAGE_SIZE, PAGE_SIZE + 1, 5 * PAGE_SIZE - 3] {
+            let data: Vec<u8> = (0..len).map(|i| (i * 7) as u8).collect();
+            let tree = OverlayTree::new(&data);
+            if len == 0 {
+                assert_eq!(tree.root(), EMPTY_DIGEST);
+            }
+            for index in 0..num_pages(len) {
+                let hint = page_hint(&data, &tree, index);
+                let (page, siblings) = hint.split_at(PAGE_SIZE);
+                assert!(verify_page(&tree.root(), len, index, page, siblings));
+
+                let mut bad_page = page.to_vec();
+                bad_page[0] ^= 1;
+                assert!(!verify_page(&tree.root(), len, index, &bad_page, siblings));
+            }
+            assert!(!verify_page(
+                &tree.root(),
+                len,
+                num_pages(len),
+                &[0; PAGE_SIZE],
+                &tree.proof(0)
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_section() {
+        let section = [2, 0, 0, 0, 7, 8, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 9, 0, 0, 0];
+        assert_eq!(
+            parse_section(&section).unwrap(),
+            [&[7, 8][..], &[][..], &[9][..]]
+        );
+        assert!(parse_section(&section[..6]).is_none());
+    }
+}
@@ -0,0 +1,4 @@
+fn main() {
+    // `memory::STACK_SIZE` is read from this variable when the crate is compiled.
+    println!("cargo:rerun-if-env-changed=OPENVM_STACK_SIZE");
+}
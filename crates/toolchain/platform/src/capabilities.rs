@@ -0,0 +1,101 @@
+//! Detection of the custom instructions the VM running a guest program supports.
+//!
+//! The guest library of a VM extension can check with [has_insn](crate::has_insn) whether the VM
+//! supports its instructions, and fall back to a software implementation otherwise, so that a
+//! single guest program runs on VMs with and without the extension, at different costs.
+//!
+//! A probe is the custom-1 I-type instruction with [PROBE_FUNCT3] and the probed instruction as
+//! the next word. The transpiler replaces the probe with an instruction setting its `rd` to 1 if
+//! one of its extensions transpiles the probed instruction, and to 0 otherwise, and the probed
+//! instruction with a no-op. The answer is part of the program, so it is fixed by the program
+//! commitment instead of being hinted by the host. A program which probes may contain
+//! instructions of extensions the transpiler does not have, which must not be reached.
+//!
+//! A probe whose probed word is itself a probe, see [config_digest_query], instead queries a word
+//! of the digest of the VM config the program was transpiled for, which [config_digest] reads.
+
+/// This is custom-1 defined in RISC-V spec document
+pub const PROBE_OPCODE: u8 = 0x2b;
+pub const PROBE_FUNCT3: u8 = 0b111;
+
+/// Returns the R-type instruction with the given `opcode`, `funct3` and `funct7`, and `x0` as
+/// every register, to probe for with [has_insn](crate::has_insn).
+pub const fn insn_r(opcode: u8, funct3: u8, funct7: u8) -> u32 {
+    (funct7 as u32) << 25 | (funct3 as u32) << 12 | opcode as u32
+}
+
+/// The number of little-endian words of the VM config digest, see [config_digest].
+pub const CONFIG_DIGEST_WORDS: u32 = 8;
+
+/// Returns the probed word which queries the `index`-th word of the VM config digest. It is a
+/// probe with `index` as its immediate, so no extension transpiles it.
+pub const fn config_digest_query(index: u32) -> u32 {
+    index << 20 | (PROBE_FUNCT3 as u32) << 12 | PROBE_OPCODE as u32
+}
+
+/// Returns the digest of the config of the VM the program was transpiled for. The transpiler
+/// resolves it to constants like the other probes, so it is fixed by the program commitment, and
+/// fails to transpile a program which reads it unless it was given a digest. See the
+/// [capabilities](crate::capabilities) module.
+///
+/// All zeros outside of the zkVM.
+pub fn config_digest() -> [u8; 32] {
+    #[allow(unused_mut)]
+    let mut digest = [0u8; 32];
+    #[cfg(target_os = "zkvm")]
+    {
+        macro_rules! read_words {
+            ($($index:literal)*) => {$(
+                let word: u32;
+                unsafe {
+                    core::arch::asm!(
+                        ".insn i {opcode}, {funct3}, {rd}, x0, 0",
+                        ".word {query}",
+                        opcode = const PROBE_OPCODE,
+                        funct3 = const PROBE_FUNCT3,
+                        query = const config_digest_query($index),
+                        rd = out(reg) word,
+                        options(nomem, nostack, pure),
+                    );
+                }
+                digest[4 * $index..4 * $index + 4].copy_from_slice(&word.to_le_bytes());
+            )*};
+        }
+        read_words!(0 1 2 3 4 5 6 7);
+    }
+    digest
+}
+
+/// Returns whether the VM supports the instruction `$insn`, which must be a constant `u32`, e.g.
+/// built with [insn_r](crate::capabilities::insn_r). See the
+/// [capabilities](crate::capabilities) module.
+///
+/// Always `false` outside of the zkVM.
+#[macro_export]
+macro_rules! has_insn {
+    ($insn:expr) => {{
+        const INSN: u32 = $insn;
+        #[cfg(target_os = "zkvm")]
+        let supported: u32 = {
+            let supported;
+            unsafe {
+                core::arch::asm!(
+                    ".insn i {opcode}, {funct3}, {rd}, x0, 0",
+                    ".word {insn}",
+                    opcode = const $crate::capabilities::PROBE_OPCODE,
+                    funct3 = const $crate::capabilities::PROBE_FUNCT3,
+                    insn = const INSN,
+                    rd = out(reg) supported,
+                    options(nomem, nostack, pure),
+                );
+            }
+            supported
+        };
+        #[cfg(not(target_os = "zkvm"))]
+        let supported: u32 = {
+            let _ = INSN;
+            0
+        };
+        supported != 0
+    }};
+}
@@ -0,0 +1,82 @@
+//! Semantic versions of the VM extensions a guest program was built against.
+//!
+//! The guest library of a VM extension records the version of the extension it was written for
+//! with [require_extension_version](crate::require_extension_version). The host compares the
+//! recorded versions with the versions of the extensions of the VM, so that a guest built against
+//! different instruction semantics is rejected instead of silently computing different results.
+//! A guest library which falls back to software when the VM does not have its extension, see the
+//! [capabilities](crate::capabilities) module, records its version with
+//! [optional_extension_version](crate::optional_extension_version) instead, which only rejects VMs
+//! having the extension at another version.
+
+/// Name of the ELF section holding the extension versions required by a guest program. The
+/// section is not allocated, so it is not part of the initial memory image.
+///
+/// The section is a sequence of records, each consisting of the length of the extension name as a
+/// little-endian `u32`, followed by the name padded with zeros to a multiple of 4 bytes, followed
+/// by the version as a little-endian `u32`, with [OPTIONAL_VERSION] set if the extension is
+/// optional.
+pub const EXTENSION_VERSIONS_SECTION: &str = ".openvm_versions";
+
+/// Flag of the recorded versions of optional extensions.
+pub const OPTIONAL_VERSION: u32 = 1 << 31;
+
+/// Returns the length in bytes of the record of the extension named `name`.
+pub const fn record_len(name: &str) -> usize {
+    8 + name.len().next_multiple_of(4)
+}
+
+/// Returns the record of version `version` of the extension named `name`, where `N` must be
+/// [record_len] of `name`.
+pub const fn encode_record<const N: usize>(name: &str, version: u32) -> [u8; N] {
+    assert!(N == record_len(name), "wrong record length");
+    let name = name.as_bytes();
+    let len = (name.len() as u32).to_le_bytes();
+    let version = version.to_le_bytes();
+    let mut record = [0; N];
+    let mut i = 0;
+    while i < 4 {
+        record[i] = len[i];
+        record[N - 4 + i] = version[i];
+        i += 1;
+    }
+    let mut i = 0;
+    while i < name.len() {
+        record[4 + i] = name[i];
+        i += 1;
+    }
+    record
+}
+
+/// Records in the ELF that the guest program requires version `$version` of the VM extension
+/// named `$name`, see the [extension_versions](crate::extension_versions) module.
+///
+/// The record is only linked if the object file holding it is, so it should be placed next to
+/// the functions using the instructions of the extension.
+#[macro_export]
+macro_rules! require_extension_version {
+    ($name:expr, $version:expr) => {
+        #[cfg(target_os = "zkvm")]
+        const _: () = {
+            const NAME: &str = $name;
+            #[used]
+            #[link_section = ".openvm_versions"]
+            static RECORD: [u8; $crate::extension_versions::record_len(NAME)] =
+                $crate::extension_versions::encode_record(NAME, $version);
+        };
+    };
+}
+
+/// Records in the ELF that the guest program requires version `$version` of the VM extension
+/// named `$name` if the VM has the extension, like
+/// [require_extension_version](crate::require_extension_version), for guest libraries which fall
+/// back to software without it.
+#[macro_export]
+macro_rules! optional_extension_version {
+    ($name:expr, $version:expr) => {
+        $crate::require_extension_version!(
+            $name,
+            $version | $crate::extension_versions::OPTIONAL_VERSION
+        );
+    };
+}
@@ -2,7 +2,7 @@ use core::alloc::{GlobalAlloc, Layout};
 
 use crate::memory::sys_alloc_aligned;
 
-#[global_allocator]
+#[cfg_attr(not(feature = "heap-profile"), global_allocator)]
 pub static HEAP: BumpPointerAlloc = BumpPointerAlloc;
 
 pub struct BumpPointerAlloc;
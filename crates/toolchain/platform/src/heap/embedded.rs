@@ -1,7 +1,7 @@
 use critical_section::RawRestoreState;
 use embedded_alloc::LlffHeap as Heap;
 
-#[global_allocator]
+#[cfg_attr(not(feature = "heap-profile"), global_allocator)]
 pub static HEAP: Heap = Heap::empty();
 
 struct CriticalSection;
@@ -18,14 +18,7 @@ unsafe impl critical_section::Impl for CriticalSection {
 }
 
 pub fn init() {
-    extern "C" {
-        static _end: u8;
-    }
-    let heap_pos: usize = unsafe { (&_end) as *const u8 as usize };
-    if heap_pos > crate::memory::GUEST_MAX_MEM {
-        crate::print::println("Not enough memory for heap.");
-        crate::rust_rt::terminate::<1>();
-    }
+    let heap_pos: usize = crate::memory::heap_start();
     let heap_size: usize = crate::memory::GUEST_MAX_MEM - heap_pos;
     unsafe { HEAP.init(heap_pos, heap_size) }
 }
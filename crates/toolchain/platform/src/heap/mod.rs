@@ -1,5 +1,30 @@
-#[cfg(not(feature = "heap-embedded-alloc"))]
+#[cfg(all(feature = "heap-embedded-alloc", feature = "heap-tlsf"))]
+compile_error!("at most one of the `heap-embedded-alloc` and `heap-tlsf` features can be enabled");
+
+#[cfg(not(any(feature = "heap-embedded-alloc", feature = "heap-tlsf")))]
 mod bump;
 
 #[cfg(feature = "heap-embedded-alloc")]
 pub mod embedded;
+
+#[cfg(feature = "heap-tlsf")]
+pub mod tlsf;
+
+#[cfg(feature = "heap-profile")]
+pub mod profile;
+
+/// Shrinks the heap to its live allocations where the allocator supports it, to be called at
+/// natural checkpoints of long-running programs, such as after a phase which allocated and freed
+/// a lot of memory.
+///
+/// With the `heap-tlsf` allocator, the free block at the end of the heap is returned to the system
+/// allocator, so that it is allocated again before any memory past it and later allocations touch
+/// less new memory. Only the tail of the heap is returned: blocks are never moved, so free memory
+/// before the last live allocation stays in the heap, to be reused by the allocator itself. The
+/// returned memory is not zeroed. It is only returned if no other user of the system allocator
+/// allocated after the heap. The bump allocator never frees memory and the `heap-embedded-alloc`
+/// allocator owns the whole heap, so this does nothing with them.
+pub fn compact() {
+    #[cfg(feature = "heap-tlsf")]
+    tlsf::HEAP.compact();
+}
@@ -0,0 +1,152 @@
+//! Heap profiling allocator, enabled by the `heap-profile` feature.
+//!
+//! [ProfilingAlloc] wraps the heap allocator selected by the other features and counts the
+//! allocations and bytes allocated, the bytes currently allocated and their peak. The counters are
+//! reported to the host by [report] when the program terminates with
+//! [terminate](crate::rust_rt::terminate).
+//!
+//! Only the totals are tracked: the allocator is called through the `__rust_alloc` shims, so its
+//! return address does not identify the caller, and the guest is not built with frame pointers to
+//! walk the stack further.
+//!
+//! The bump allocator never frees memory, so with it `current_bytes` counts the bytes which the
+//! program still uses, while the heap itself grows by `allocated_bytes`.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+};
+
+#[cfg(not(any(feature = "heap-embedded-alloc", feature = "heap-tlsf")))]
+use super::bump::{BumpPointerAlloc as InnerAlloc, HEAP as INNER_HEAP};
+#[cfg(feature = "heap-embedded-alloc")]
+use super::embedded::HEAP as INNER_HEAP;
+#[cfg(feature = "heap-tlsf")]
+use super::tlsf::{TlsfHeap as InnerAlloc, HEAP as INNER_HEAP};
+
+#[cfg(feature = "heap-embedded-alloc")]
+type InnerAlloc = embedded_alloc::LlffHeap;
+
+#[global_allocator]
+static PROFILED_HEAP: ProfilingAlloc<InnerAlloc> = ProfilingAlloc::new(&INNER_HEAP);
+
+/// Heap usage counters, returned by [heap_stats].
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Number of calls to `alloc` and `alloc_zeroed`.
+    pub allocations: u32,
+    pub deallocations: u32,
+    pub reallocations: u32,
+    /// Total number of bytes allocated, including the growth of reallocations.
+    pub allocated_bytes: u32,
+    /// Number of bytes allocated and not deallocated.
+    pub current_bytes: u32,
+    /// Maximum of `current_bytes` so far.
+    pub peak_bytes: u32,
+}
+
+impl HeapStats {
+    const ZERO: Self = Self {
+        allocations: 0,
+        deallocations: 0,
+        reallocations: 0,
+        allocated_bytes: 0,
+        current_bytes: 0,
+        peak_bytes: 0,
+    };
+
+    fn add_bytes(&mut self, bytes: usize) {
+        self.allocated_bytes = self.allocated_bytes.saturating_add(bytes as u32);
+        self.current_bytes = self.current_bytes.saturating_add(bytes as u32);
+        self.peak_bytes = self.peak_bytes.max(self.current_bytes);
+    }
+
+    fn remove_bytes(&mut self, bytes: usize) {
+        self.current_bytes = self.current_bytes.saturating_sub(bytes as u32);
+    }
+}
+
+/// Allocator which forwards to `inner` and counts the allocations, see the [module
+/// docs](self).
+pub struct ProfilingAlloc<A: 'static> {
+    inner: &'static A,
+    stats: UnsafeCell<HeapStats>,
+}
+
+// SAFETY: the zkVM is single-threaded, so the stats are never accessed concurrently.
+unsafe impl<A: Sync> Sync for ProfilingAlloc<A> {}
+
+impl<A: GlobalAlloc> ProfilingAlloc<A> {
+    pub const fn new(inner: &'static A) -> Self {
+        Self {
+            inner,
+            stats: UnsafeCell::new(HeapStats::ZERO),
+        }
+    }
+
+    /// Counts an allocation of `bytes`.
+    fn record_alloc(&self, bytes: usize) {
+        // SAFETY: single-threaded, and no reference to the stats outlives this call.
+        let stats = unsafe { &mut *self.stats.get() };
+        stats.allocations += 1;
+        stats.add_bytes(bytes);
+    }
+}
+
+unsafe impl<A: GlobalAlloc> GlobalAlloc for ProfilingAlloc<A> {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = self.inner.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            self.record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        self.inner.dealloc(ptr, layout);
+        let stats = &mut *self.stats.get();
+        stats.deallocations += 1;
+        stats.remove_bytes(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = self.inner.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            let stats = &mut *self.stats.get();
+            stats.reallocations += 1;
+            stats.remove_bytes(layout.size());
+            stats.add_bytes(new_size);
+            // Only the growth counts towards the allocated bytes.
+            stats.allocated_bytes = stats
+                .allocated_bytes
+                .saturating_sub(layout.size().min(new_size) as u32);
+        }
+        new_ptr
+    }
+}
+
+/// Returns the heap usage of the program so far.
+pub fn heap_stats() -> HeapStats {
+    // SAFETY: single-threaded, and the stats are copied out.
+    unsafe { *PROFILED_HEAP.stats.get() }
+}
+
+/// Sends the heap usage counters of the program to the host, which keeps the last ones it was
+/// sent. Called when the program terminates.
+pub fn report() {
+    // The stats are `repr(C)` `u32`s, which the host reads as little-endian words.
+    let stats = PROFILED_HEAP.stats.get();
+    openvm_rv32im_guest::raw_record_heap_stats(
+        stats as *const u8,
+        core::mem::size_of::<HeapStats>(),
+    );
+}
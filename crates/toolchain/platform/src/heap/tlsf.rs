@@ -0,0 +1,398 @@
+//! Two-level segregated fit (TLSF) allocator, enabled by the `heap-tlsf` feature.
+//!
+//! Free blocks are kept in segregated free lists indexed by a first level, the power of two of
+//! their size, and a second level, which splits each power of two into [SL_COUNT] ranges. A free
+//! block of sufficient size is found in constant time from the bitmaps of the non-empty lists, and
+//! freed blocks are merged with their free neighbours, so memory is reused by long-running guests.
+//!
+//! The allocator is tuned for the zkVM memory model, where the proving cost grows with the memory
+//! which is touched and where untouched memory is zero:
+//! - The heap is not initialized up front. Blocks are carved out of the memory returned by
+//!   [sys_alloc_aligned] only when no free block fits, so no memory past the peak heap usage is
+//!   touched, and the allocator coexists with other users of [sys_alloc_aligned].
+//! - `alloc_zeroed` does not zero blocks taken from fresh memory, which is zero, unless it was part
+//!   of the heap before a [compact](super::compact).
+//! - `realloc` grows a block in place when the next block is free, avoiding the copy.
+//! - [compact](super::compact) returns the free block at the end of the heap to
+//!   [sys_alloc_aligned], so memory freed at the end of the heap is allocated again before any
+//!   memory past it is touched. Free blocks before the last used block stay in the heap, since
+//!   blocks are never moved.
+
+use core::{
+    alloc::{GlobalAlloc, Layout},
+    cell::UnsafeCell,
+    mem::size_of,
+    ptr::{self, null_mut},
+};
+
+use crate::memory::{sys_alloc_aligned, sys_dealloc_tail, GUEST_MAX_MEM};
+
+#[cfg_attr(not(feature = "heap-profile"), global_allocator)]
+pub static HEAP: TlsfHeap = TlsfHeap::empty();
+
+/// Alignment of blocks, and granularity of block sizes.
+const ALIGN: usize = 8;
+/// Size of the header preceding the payload of every block.
+const HEADER_SIZE: usize = size_of::<BlockHeader>();
+/// Size of the smallest block, which must hold the free list links of a free block.
+const MIN_BLOCK_SIZE: usize = size_of::<FreeBlock>();
+/// Flag set in [BlockHeader::size] of free blocks. Sizes are multiples of [ALIGN], so the low bits
+/// are free for flags.
+const FREE: usize = 1;
+
+const SL_LOG2: u32 = 4;
+/// Number of second-level free lists for each first level.
+pub const SL_COUNT: usize = 1 << SL_LOG2;
+const FL_SHIFT: u32 = SL_LOG2 + ALIGN.trailing_zeros();
+/// Blocks smaller than this are all in the first first-level list, split linearly by size.
+const SMALL_BLOCK_SIZE: usize = 1 << FL_SHIFT;
+const FL_COUNT: usize = (usize::BITS - FL_SHIFT) as usize + 1;
+
+/// Header of every block. The blocks of a pool are laid out contiguously and end with a sentinel,
+/// a used block of size zero, so the next block of a block is always valid.
+#[repr(C)]
+struct BlockHeader {
+    /// The previous block of the same pool, or null for the first block.
+    prev_phys: *mut BlockHeader,
+    /// Size of the block including the header, together with the [FREE] flag.
+    size: usize,
+}
+
+/// Layout of a free block, whose payload holds the links of its free list.
+#[repr(C)]
+struct FreeBlock {
+    header: BlockHeader,
+    next_free: *mut FreeBlock,
+    prev_free: *mut FreeBlock,
+}
+
+impl BlockHeader {
+    fn size(&self) -> usize {
+        self.size & !FREE
+    }
+
+    fn is_free(&self) -> bool {
+        self.size & FREE != 0
+    }
+
+    /// # Safety
+    /// `block` must not be a sentinel.
+    unsafe fn next_phys(block: *mut Self) -> *mut Self {
+        (block as *mut u8).add((*block).size()) as *mut Self
+    }
+
+    unsafe fn payload(block: *mut Self) -> *mut u8 {
+        (block as *mut u8).add(HEADER_SIZE)
+    }
+
+    unsafe fn from_payload(ptr: *mut u8) -> *mut Self {
+        ptr.sub(HEADER_SIZE) as *mut Self
+    }
+}
+
+/// Returns the free list of blocks of `size`.
+fn mapping(size: usize) -> (usize, usize) {
+    if size < SMALL_BLOCK_SIZE {
+        (0, size / (SMALL_BLOCK_SIZE / SL_COUNT))
+    } else {
+        let log2 = usize::BITS - 1 - size.leading_zeros();
+        let fl = (log2 - FL_SHIFT + 1) as usize;
+        let sl = (size >> (log2 - SL_LOG2)) ^ SL_COUNT;
+        (fl, sl)
+    }
+}
+
+/// Returns the first free list whose blocks are all at least `size`.
+fn mapping_search(size: usize) -> (usize, usize) {
+    if size < SMALL_BLOCK_SIZE {
+        mapping(size)
+    } else {
+        let log2 = usize::BITS - 1 - size.leading_zeros();
+        mapping(size + (1 << (log2 - SL_LOG2)) - 1)
+    }
+}
+
+const fn round_up(value: usize, align: usize) -> usize {
+    (value + align - 1) & !(align - 1)
+}
+
+struct Tlsf {
+    fl_bitmap: u32,
+    sl_bitmaps: [u32; FL_COUNT],
+    free_lists: [[*mut FreeBlock; SL_COUNT]; FL_COUNT],
+    /// End of the last pool, past its sentinel.
+    pool_end: usize,
+    /// End of the memory which was part of a pool, which is past `pool_end` if the heap was
+    /// trimmed. The memory before it may be nonzero.
+    touched_end: usize,
+}
+
+/// TLSF allocator, see the [module docs](self).
+pub struct TlsfHeap {
+    tlsf: UnsafeCell<Tlsf>,
+}
+
+// SAFETY: the zkVM is single-threaded, so the allocator is never accessed concurrently.
+unsafe impl Sync for TlsfHeap {}
+
+impl TlsfHeap {
+    pub const fn empty() -> Self {
+        Self {
+            tlsf: UnsafeCell::new(Tlsf {
+                fl_bitmap: 0,
+                sl_bitmaps: [0; FL_COUNT],
+                free_lists: [[null_mut(); SL_COUNT]; FL_COUNT],
+                pool_end: 0,
+                touched_end: 0,
+            }),
+        }
+    }
+
+    #[allow(clippy::mut_from_ref)]
+    unsafe fn tlsf(&self) -> &mut Tlsf {
+        &mut *self.tlsf.get()
+    }
+
+    /// Returns the free block at the end of the heap to [sys_alloc_aligned], see
+    /// [compact](super::compact).
+    pub fn compact(&self) {
+        // SAFETY: the allocator is not in use, as the zkVM is single-threaded.
+        unsafe { self.tlsf().trim() }
+    }
+}
+
+impl Tlsf {
+    unsafe fn insert(&mut self, block: *mut FreeBlock) {
+        let (fl, sl) = mapping((*block).header.size());
+        let head = self.free_lists[fl][sl];
+        (*block).next_free = head;
+        (*block).prev_free = null_mut();
+        if !head.is_null() {
+            (*head).prev_free = block;
+        }
+        self.free_lists[fl][sl] = block;
+        self.fl_bitmap |= 1 << fl;
+        self.sl_bitmaps[fl] |= 1 << sl;
+    }
+
+    unsafe fn remove(&mut self, block: *mut FreeBlock) {
+        let (fl, sl) = mapping((*block).header.size());
+        let (next, prev) = ((*block).next_free, (*block).prev_free);
+        if !next.is_null() {
+            (*next).prev_free = prev;
+        }
+        if !prev.is_null() {
+            (*prev).next_free = next;
+        }
+        if self.free_lists[fl][sl] == block {
+            self.free_lists[fl][sl] = next;
+            if next.is_null() {
+                self.sl_bitmaps[fl] &= !(1 << sl);
+                if self.sl_bitmaps[fl] == 0 {
+                    self.fl_bitmap &= !(1 << fl);
+                }
+            }
+        }
+    }
+
+    /// Returns the first non-empty free list at or after `(fl, sl)`.
+    fn find_suitable(&self, fl: usize, sl: usize) -> Option<(usize, usize)> {
+        let sl_map = self.sl_bitmaps[fl] & (!0u32 << sl);
+        if sl_map != 0 {
+            return Some((fl, sl_map.trailing_zeros() as usize));
+        }
+        let fl_map = self.fl_bitmap & (!0u32).checked_shl(fl as u32 + 1).unwrap_or(0);
+        if fl_map == 0 {
+            return None;
+        }
+        let fl = fl_map.trailing_zeros() as usize;
+        Some((fl, self.sl_bitmaps[fl].trailing_zeros() as usize))
+    }
+
+    /// Takes a used block of at least `size` bytes, from the free lists or else from fresh memory.
+    /// Also returns whether the payload of the block is fresh memory which was never part of the
+    /// heap, and is therefore zero.
+    unsafe fn take(&mut self, size: usize) -> (*mut BlockHeader, bool) {
+        let (fl, sl) = mapping_search(size);
+        match self.find_suitable(fl, sl) {
+            Some((fl, sl)) => {
+                let block = self.free_lists[fl][sl];
+                self.remove(block);
+                (*block).header.size &= !FREE;
+                (block as *mut BlockHeader, false)
+            }
+            None => self.grow(size),
+        }
+    }
+
+    /// Allocates a used block of at least `size` bytes from fresh memory. The block extends the
+    /// last pool if the memory is contiguous with it, and otherwise starts a new pool.
+    unsafe fn grow(&mut self, size: usize) -> (*mut BlockHeader, bool) {
+        let mem = sys_alloc_aligned(size + HEADER_SIZE, ALIGN) as usize;
+        let fresh = mem >= self.touched_end;
+        let (block, block_size) = if mem == self.pool_end {
+            // The sentinel of the last pool becomes the header of the new block.
+            ((mem - HEADER_SIZE) as *mut BlockHeader, size + HEADER_SIZE)
+        } else {
+            let block = mem as *mut BlockHeader;
+            (*block).prev_phys = null_mut();
+            (block, size)
+        };
+        (*block).size = block_size;
+        let sentinel = BlockHeader::next_phys(block);
+        (*sentinel).prev_phys = block;
+        (*sentinel).size = 0;
+        self.pool_end = sentinel as usize + HEADER_SIZE;
+        self.touched_end = self.touched_end.max(self.pool_end);
+
+        let prev = (*block).prev_phys;
+        if !prev.is_null() && (*prev).is_free() {
+            self.remove(prev as *mut FreeBlock);
+            (*prev).size = (*prev).size() + block_size;
+            (*sentinel).prev_phys = prev;
+            (prev, false)
+        } else {
+            (block, fresh)
+        }
+    }
+
+    /// Shrinks the used `block` to `size` bytes if the rest is large enough to be a block, which
+    /// is freed.
+    unsafe fn split(&mut self, block: *mut BlockHeader, size: usize) {
+        let block_size = (*block).size();
+        if block_size >= size + MIN_BLOCK_SIZE {
+            let rest = (block as *mut u8).add(size) as *mut BlockHeader;
+            (*rest).prev_phys = block;
+            (*rest).size = block_size - size;
+            (*BlockHeader::next_phys(rest)).prev_phys = rest;
+            (*block).size = size;
+            self.release(rest);
+        }
+    }
+
+    /// Frees the used `block`, merging it with its free neighbours.
+    unsafe fn release(&mut self, mut block: *mut BlockHeader) {
+        let next = BlockHeader::next_phys(block);
+        if (*next).is_free() {
+            self.remove(next as *mut FreeBlock);
+            (*block).size += (*next).size();
+            (*BlockHeader::next_phys(block)).prev_phys = block;
+        }
+        let prev = (*block).prev_phys;
+        if !prev.is_null() && (*prev).is_free() {
+            self.remove(prev as *mut FreeBlock);
+            (*prev).size = (*prev).size() + (*block).size();
+            (*BlockHeader::next_phys(prev)).prev_phys = prev;
+            block = prev;
+        }
+        (*block).size |= FREE;
+        self.insert(block as *mut FreeBlock);
+    }
+
+    /// Allocates a block for `layout` and returns its payload, together with whether the payload
+    /// is fresh memory.
+    unsafe fn alloc(&mut self, layout: Layout) -> (*mut u8, bool) {
+        if layout.size() > GUEST_MAX_MEM {
+            return (null_mut(), false);
+        }
+        let size = (round_up(layout.size(), ALIGN) + HEADER_SIZE).max(MIN_BLOCK_SIZE);
+        let align = layout.align();
+        if align <= ALIGN {
+            let (block, fresh) = self.take(size);
+            self.split(block, size);
+            return (BlockHeader::payload(block), fresh);
+        }
+
+        // Take a block with room for a free block before the aligned payload.
+        let (mut block, fresh) = self.take(size + align + MIN_BLOCK_SIZE);
+        let start = BlockHeader::payload(block) as usize;
+        let mut aligned = round_up(start, align);
+        if aligned != start {
+            if aligned - start < MIN_BLOCK_SIZE {
+                aligned = round_up(start + MIN_BLOCK_SIZE, align);
+            }
+            let gap = aligned - start;
+            let aligned_block = (block as *mut u8).add(gap) as *mut BlockHeader;
+            (*aligned_block).prev_phys = block;
+            (*aligned_block).size = (*block).size() - gap;
+            (*BlockHeader::next_phys(aligned_block)).prev_phys = aligned_block;
+            (*block).size = gap;
+            self.release(block);
+            block = aligned_block;
+        }
+        self.split(block, size);
+        (BlockHeader::payload(block), fresh)
+    }
+
+    /// Returns the last block of the last pool to [sys_alloc_aligned] if it is free and the pool
+    /// ends at the end of the memory allocated by [sys_alloc_aligned]. The header of the block
+    /// becomes the sentinel of the pool.
+    unsafe fn trim(&mut self) {
+        if self.pool_end == 0 {
+            return;
+        }
+        let sentinel = (self.pool_end - HEADER_SIZE) as *mut BlockHeader;
+        let last = (*sentinel).prev_phys;
+        if last.is_null() || !(*last).is_free() {
+            return;
+        }
+        // The free list links of the block are in the memory which is returned, so the block is
+        // removed from its free list first.
+        self.remove(last as *mut FreeBlock);
+        let end = BlockHeader::payload(last) as usize;
+        if sys_dealloc_tail(end, self.pool_end) {
+            (*last).size = 0;
+            self.pool_end = end;
+        } else {
+            self.insert(last as *mut FreeBlock);
+        }
+    }
+
+    /// Resizes the block of `ptr` to hold `new_size` bytes in place, if possible.
+    unsafe fn resize_in_place(&mut self, ptr: *mut u8, new_size: usize) -> bool {
+        let block = BlockHeader::from_payload(ptr);
+        let size = (round_up(new_size, ALIGN) + HEADER_SIZE).max(MIN_BLOCK_SIZE);
+        if size > (*block).size() {
+            let next = BlockHeader::next_phys(block);
+            if !(*next).is_free() || (*block).size() + (*next).size() < size {
+                return false;
+            }
+            self.remove(next as *mut FreeBlock);
+            (*block).size += (*next).size();
+            (*BlockHeader::next_phys(block)).prev_phys = block;
+        }
+        self.split(block, size);
+        true
+    }
+}
+
+unsafe impl GlobalAlloc for TlsfHeap {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        self.tlsf().alloc(layout).0
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let (ptr, fresh) = self.tlsf().alloc(layout);
+        if !ptr.is_null() && !fresh {
+            ptr::write_bytes(ptr, 0, layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, _: Layout) {
+        self.tlsf().release(BlockHeader::from_payload(ptr));
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if new_size <= GUEST_MAX_MEM && self.tlsf().resize_in_place(ptr, new_size) {
+            return ptr;
+        }
+        let new_layout = Layout::from_size_align_unchecked(new_size, layout.align());
+        let new_ptr = self.alloc(new_layout);
+        if !new_ptr.is_null() {
+            ptr::copy_nonoverlapping(ptr, new_ptr, layout.size().min(new_size));
+            self.dealloc(ptr, layout);
+        }
+        new_ptr
+    }
+}
@@ -6,6 +6,8 @@
 
 #[cfg(all(feature = "rust-runtime", target_os = "zkvm"))]
 pub use openvm_custom_insn::{custom_insn_i, custom_insn_r};
+pub mod capabilities;
+pub mod extension_versions;
 #[cfg(all(feature = "rust-runtime", target_os = "zkvm"))]
 pub mod heap;
 #[cfg(all(feature = "export-libm", target_os = "zkvm"))]
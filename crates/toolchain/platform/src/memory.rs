@@ -3,17 +3,79 @@ pub const MEM_SIZE: usize = 1 << MEM_BITS;
 pub const GUEST_MIN_MEM: usize = 0x0000_0400;
 pub const GUEST_MAX_MEM: usize = MEM_SIZE;
 
-/// Top of stack; stack grows down from this location.
-pub const STACK_TOP: u32 = 0x0020_0400;
+/// Size of the stack guard region, which starts at [GUEST_MIN_MEM] and ends at [STACK_BOTTOM]. The
+/// VM fails reads and writes of this region with a stack overflow error, so that a stack which
+/// grows past [STACK_BOTTOM] does not silently overwrite other memory. A single stack frame larger
+/// than the guard can still skip over it.
+pub const STACK_GUARD_SIZE: u32 = 0x1000;
+/// Lowest address of the stack.
+pub const STACK_BOTTOM: u32 = GUEST_MIN_MEM as u32 + STACK_GUARD_SIZE;
+/// Largest stack size, for which the stack ends right below [TEXT_START].
+pub const MAX_STACK_SIZE: u32 = TEXT_START - STACK_BOTTOM;
+/// Stack size used unless the guest is built with the `OPENVM_STACK_SIZE` environment variable.
+pub const DEFAULT_STACK_SIZE: u32 = 0x0020_0000;
+/// Size of the stack in bytes, which is set at build time by the `OPENVM_STACK_SIZE` environment
+/// variable, in decimal, and is [DEFAULT_STACK_SIZE] otherwise. It must be a multiple of 16 and
+/// at most [MAX_STACK_SIZE].
+pub const STACK_SIZE: u32 = match option_env!("OPENVM_STACK_SIZE") {
+    Some(size) => parse_stack_size(size),
+    None => DEFAULT_STACK_SIZE,
+};
+/// Top of stack; stack grows down from this location to [STACK_BOTTOM].
+pub const STACK_TOP: u32 = STACK_BOTTOM + STACK_SIZE;
 /// Program (text followed by data and then bss) gets loaded in
-/// starting at this location.  HEAP begins right afterwards.
-pub const TEXT_START: u32 = 0x0020_0800;
+/// starting at this location. It must end before [EXTENSION_DATA_START].
+pub const TEXT_START: u32 = 0x0020_1800;
+/// Start of the region holding data emitted by VM extensions into the `.openvm` linker section,
+/// such as the moduli serialized by `moduli_init!`. Keeping this data out of the program's own
+/// data sections means enabling an extension does not move any other static.
+pub const EXTENSION_DATA_START: u32 = 0x03F0_0000;
+/// Start of the heap, which extends up to [GUEST_MAX_MEM]. The heap base is fixed, so heap
+/// addresses do not depend on the size of the program or on the set of enabled extensions.
+pub const HEAP_START: u32 = 0x0400_0000;
+
+const _: () = assert!(
+    STACK_SIZE % 16 == 0 && STACK_SIZE <= MAX_STACK_SIZE,
+    "OPENVM_STACK_SIZE must be a multiple of 16 and at most MAX_STACK_SIZE"
+);
+
+const fn parse_stack_size(size: &str) -> u32 {
+    let digits = size.as_bytes();
+    assert!(!digits.is_empty(), "OPENVM_STACK_SIZE must not be empty");
+    let mut value: u32 = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        assert!(
+            digits[i].is_ascii_digit(),
+            "OPENVM_STACK_SIZE must be a decimal number"
+        );
+        value = match value.checked_mul(10) {
+            Some(value) => value,
+            None => panic!("OPENVM_STACK_SIZE is too large"),
+        };
+        value = match value.checked_add((digits[i] - b'0') as u32) {
+            Some(value) => value,
+            None => panic!("OPENVM_STACK_SIZE is too large"),
+        };
+        i += 1;
+    }
+    value
+}
+
+/// Returns whether `addr` is within the stack guard region, see [STACK_GUARD_SIZE].
+pub const fn is_stack_guard(addr: u32) -> bool {
+    GUEST_MIN_MEM as u32 <= addr && addr < STACK_BOTTOM
+}
 
 /// Returns whether `addr` is within guest memory bounds.
 pub fn is_guest_memory(addr: u32) -> bool {
     GUEST_MIN_MEM <= (addr as usize) && (addr as usize) < GUEST_MAX_MEM
 }
 
+/// Pointer to next heap address to use, or 0 if the heap has not yet been initialized.
+#[cfg(feature = "rust-runtime")]
+static mut HEAP_POS: usize = 0;
+
 /// # Safety
 ///
 /// This function should be safe to call, but clippy complains if it is not marked as `unsafe`.
@@ -22,27 +84,12 @@ pub fn is_guest_memory(addr: u32) -> bool {
 pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u8 {
     use crate::print::println;
 
-    #[cfg(target_os = "zkvm")]
-    extern "C" {
-        // This symbol is defined by the loader and marks the end
-        // of all elf sections, so this is where we start our
-        // heap.
-        //
-        // This is generated automatically by the linker; see
-        // https://lld.llvm.org/ELF/linker_script.html#sections-command
-        static _end: u8;
-    }
-
-    // Pointer to next heap address to use, or 0 if the heap has not yet been
-    // initialized.
-    static mut HEAP_POS: usize = 0;
-
     // SAFETY: Single threaded, so nothing else can touch this while we're working.
     let mut heap_pos = unsafe { HEAP_POS };
 
     #[cfg(target_os = "zkvm")]
     if heap_pos == 0 {
-        heap_pos = unsafe { (&_end) as *const u8 as usize };
+        heap_pos = heap_start();
     }
 
     // Honor requested alignment if larger than word size.
@@ -66,3 +113,42 @@ pub unsafe extern "C" fn sys_alloc_aligned(bytes: usize, align: usize) -> *mut u
     }
     heap_pos as *mut u8
 }
+
+/// Returns the memory from `start` to `end` to [sys_alloc_aligned], to be allocated again, if
+/// `end` is the end of the memory allocated so far. Returns whether the memory was returned.
+///
+/// The memory is not zeroed, so unlike memory which was never allocated, it still holds what was
+/// written to it when it is allocated again.
+///
+/// # Safety
+/// The memory from `start` to `end` must have been allocated by [sys_alloc_aligned] and must not
+/// be used after it is returned.
+#[cfg(all(feature = "heap-tlsf", target_os = "zkvm"))]
+pub(crate) unsafe fn sys_dealloc_tail(start: usize, end: usize) -> bool {
+    // SAFETY: Single threaded, so nothing else can touch this while we're working.
+    if unsafe { HEAP_POS } != end {
+        return false;
+    }
+    unsafe { HEAP_POS = start };
+    true
+}
+
+/// Returns [HEAP_START], terminating the program if the loaded ELF extends past it.
+#[cfg(all(feature = "rust-runtime", target_os = "zkvm"))]
+pub(crate) fn heap_start() -> usize {
+    extern "C" {
+        // This symbol is defined by the loader and marks the end
+        // of all elf sections, which must not overlap the heap.
+        //
+        // This is generated automatically by the linker; see
+        // https://lld.llvm.org/ELF/linker_script.html#sections-command
+        static _end: u8;
+    }
+
+    let program_end = unsafe { (&_end) as *const u8 as usize };
+    if program_end > HEAP_START as usize {
+        crate::print::println("ERROR: Program does not fit below the heap, program terminating.");
+        crate::rust_rt::terminate::<1>();
+    }
+    HEAP_START as usize
+}
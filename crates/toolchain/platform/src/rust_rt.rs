@@ -16,6 +16,8 @@ extern crate alloc;
 
 #[inline(always)]
 pub fn terminate<const EXIT_CODE: u8>() {
+    #[cfg(all(feature = "heap-profile", target_os = "zkvm"))]
+    crate::heap::profile::report();
     #[cfg(target_os = "zkvm")]
     crate::custom_insn_i!(
         opcode = SYSTEM_OPCODE,
@@ -66,11 +66,42 @@ pub fn build_example_program_at_path_with_features<S: AsRef<str>>(
     features: impl IntoIterator<Item = S> + Clone,
     init_config: &impl InitFileGenerator,
 ) -> Result<Elf> {
+    let data = build_example_elf_at_path(manifest_dir, example_name, features, [], init_config)?;
+    Elf::decode(&data, MEM_SIZE as u32)
+}
+
+/// Like [build_example_program_at_path], but builds the program with DWARF line info and decodes
+/// it with [Elf::decode_with_debug_info].
+pub fn build_example_program_at_path_with_debug_info(
+    manifest_dir: PathBuf,
+    example_name: &str,
+    init_config: &impl InitFileGenerator,
+) -> Result<Elf> {
+    let data = build_example_elf_at_path::<&str>(
+        manifest_dir,
+        example_name,
+        [],
+        ["--config", "profile.release.debug=\"line-tables-only\""],
+        init_config,
+    )?;
+    Elf::decode_with_debug_info(&data, MEM_SIZE as u32)
+}
+
+/// Builds the example `example_name` of the package at `manifest_dir`, passing `options` to
+/// `cargo build`, and returns the contents of its ELF.
+pub fn build_example_elf_at_path<S: AsRef<str>>(
+    manifest_dir: PathBuf,
+    example_name: &str,
+    features: impl IntoIterator<Item = S> + Clone,
+    options: impl IntoIterator<Item = &'static str>,
+    init_config: &impl InitFileGenerator,
+) -> Result<Vec<u8>> {
     let pkg = get_package(&manifest_dir);
     let target_dir = tempdir()?;
     // Build guest with default features
     let guest_opts = GuestOptions::default()
         .with_features(features.clone())
+        .with_options(options)
         .with_target_dir(target_dir.path());
     let features = features
         .into_iter()
@@ -113,5 +144,5 @@ pub fn build_example_program_at_path_with_features<S: AsRef<str>>(
         .expect("Could not find target binary");
     let data = read(&elf_path).with_context(|| format!("Path not found: {:?}", elf_path))?;
     target_dir.close()?;
-    Elf::decode(&data, MEM_SIZE as u32)
+    Ok(data)
 }
@@ -19,7 +19,9 @@ type F = BabyBear;
 #[ignore = "must run makefile"]
 fn test_rv32im_riscv_vector_runtime() -> Result<()> {
     let skip_list = ["rv32ui-p-ma_data", "rv32ui-p-fence_i"];
-    let config = Rv32ImConfig::default();
+    let mut config = Rv32ImConfig::default();
+    // The test vectors keep their data in the stack guard region of OpenVM guests.
+    config.rv32i.system.stack_guard = false;
     let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("rv32im-test-vectors/tests");
     for entry in read_dir(dir)? {
         let entry = entry?;
@@ -58,7 +60,9 @@ fn test_rv32im_riscv_vector_runtime() -> Result<()> {
 #[test]
 #[ignore = "long prover tests"]
 fn test_rv32im_riscv_vector_prove() -> Result<()> {
-    let config = Rv32ImConfig::default();
+    let mut config = Rv32ImConfig::default();
+    // The test vectors keep their data in the stack guard region of OpenVM guests.
+    config.rv32i.system.stack_guard = false;
     let skip_list = ["rv32ui-p-ma_data", "rv32ui-p-fence_i"];
     let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("rv32im-test-vectors/tests");
     for entry in read_dir(dir)? {
@@ -1,5 +1,6 @@
 use std::{
-    fs::read,
+    collections::BTreeMap,
+    fs::{copy, read},
     path::{Path, PathBuf},
 };
 
@@ -11,24 +12,33 @@ use openvm_algebra_circuit::{
 };
 use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
 use openvm_bigint_circuit::{Int256, Int256Executor, Int256Periphery};
+use openvm_build::{find_unsupported_std_features, strip_elf};
 use openvm_circuit::{
-    arch::{InitFileGenerator, SystemConfig, VmExecutor},
+    arch::{ExecutionError, InitFileGenerator, SystemConfig, VmExecutor},
     derive::VmConfig,
     utils::air_test,
 };
 use openvm_ecc_circuit::{SECP256K1_MODULUS, SECP256K1_ORDER};
-use openvm_instructions::exe::VmExe;
+use openvm_instructions::{exe::VmExe, instruction::Instruction, VmOpcode};
 use openvm_platform::memory::MEM_SIZE;
 use openvm_rv32im_circuit::{
     Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32ImConfig, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery,
     Rv32M, Rv32MExecutor, Rv32MPeriphery,
 };
 use openvm_rv32im_transpiler::{
-    Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
+    Rv32ATranspilerExtension, Rv32CTranspilerExtension, Rv32ITranspilerExtension,
+    Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
 };
+use openvm_softfloat_transpiler::SoftfloatTranspilerExtension;
 use openvm_stark_backend::p3_field::PrimeField32;
 use openvm_stark_sdk::p3_baby_bear::BabyBear;
-use openvm_transpiler::{elf::Elf, transpiler::Transpiler, FromElf};
+use openvm_transpiler::{
+    compressed::expand,
+    elf::Elf,
+    metadata::OpcodeMap,
+    transpiler::{Transpiler, TranspilerError},
+    FromElf, InstructionDispatch, TranspilerExtension, TranspilerOutput,
+};
 use serde::{Deserialize, Serialize};
 use test_case::test_case;
 
@@ -49,6 +59,90 @@ fn test_decode_elf() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_program_too_large() -> Result<()> {
+    let elf = get_elf("tests/data/rv32im-fib-from-as")?;
+    let program_len = elf.instructions.len();
+    elf.check_program_len(program_len)?;
+    let err = elf.check_program_len(program_len - 1).unwrap_err();
+    assert_eq!(err.program_len, program_len);
+    assert!(err.section_sizes.0.iter().any(|(name, _)| name == ".text"));
+    assert!(err.to_string().contains("opt-level"));
+    Ok(())
+}
+
+#[test]
+fn test_strip_elf() -> Result<()> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let tmp_dir = tempfile::tempdir()?;
+    let elf_path = tmp_dir.path().join("rv32im-fib-from-as");
+    copy(dir.join("tests/data/rv32im-fib-from-as"), &elf_path)?;
+    let sidecar_path = strip_elf(&elf_path)?;
+    let original = read(&sidecar_path)?;
+    let stripped = read(&elf_path)?;
+    assert!(stripped.len() < original.len());
+    // Stripping again keeps the original in the sidecar.
+    strip_elf(&elf_path)?;
+    assert_eq!(read(&sidecar_path)?, original);
+
+    let original = Elf::decode(&original, MEM_SIZE as u32)?;
+    let stripped = Elf::decode(&stripped, MEM_SIZE as u32)?;
+    assert_eq!(stripped.instructions, original.instructions);
+    let transpiler = || {
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension)
+    };
+    let original = VmExe::from_elf(original, transpiler())?;
+    let stripped = VmExe::from_elf(stripped, transpiler())?;
+    assert_eq!(stripped.pc_start, original.pc_start);
+    assert_eq!(stripped.init_memory, original.init_memory);
+    VmExecutor::<F, _>::new(Rv32ImConfig::default()).execute(stripped, vec![])?;
+    Ok(())
+}
+
+// To create the object from unsupported-std.S, run `llvm-mc -triple=riscv32 -mattr=+m,+c,-relax
+// -filetype=obj unsupported-std.S -o rv32imc-unsupported-std-from-as`
+#[test]
+fn test_find_unsupported_std_features() -> Result<()> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let data = read(dir.join("tests/data/rv32imc-unsupported-std-from-as"))?;
+    let features = find_unsupported_std_features(&data)?;
+    let summary = features
+        .iter()
+        .map(|feature| {
+            (
+                feature.feature,
+                feature.functions.iter().cloned().collect::<Vec<_>>(),
+                feature.crates.iter().cloned().collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    // The clock is only used by the standard library, and the network stub of the platform only
+    // through `TcpStream::connect`.
+    assert_eq!(
+        summary,
+        [
+            (
+                "networking",
+                vec!["std::net::tcp::TcpStream::connect".to_string()],
+                vec!["reqwest".to_string()]
+            ),
+            (
+                "the file system",
+                vec!["std::fs::File::open".to_string()],
+                vec!["guest".to_string()]
+            ),
+        ]
+    );
+
+    // Programs which do not use std have none.
+    let elf = read(dir.join("tests/data/rv32im-fib-from-as"))?;
+    assert!(find_unsupported_std_features(&elf)?.is_empty());
+    Ok(())
+}
+
 // To create ELF directly from .S file, `brew install riscv-gnu-toolchain` and run
 // `riscv64-unknown-elf-gcc -march=rv32im -mabi=ilp32 -nostartfiles -e _start -Ttext 0 fib.S -o
 // rv32im-fib-from-as` riscv64-unknown-elf-gcc supports rv32im if you set -march target
@@ -85,6 +179,224 @@ fn test_rv32im_runtime(elf_path: &str) -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_expand_compressed() {
+    // c.li a0, 1
+    assert_eq!(expand(0x4505), Some(0x00100513));
+    // c.mv a0, a1
+    assert_eq!(expand(0x852e), Some(0x00b00533));
+    // c.addi sp, -16
+    assert_eq!(expand(0x1141), Some(0xff010113));
+    // c.lw a0, 0(a1)
+    assert_eq!(expand(0x4188), Some(0x0005a503));
+    // c.sw a0, 4(a1)
+    assert_eq!(expand(0xc1c8), Some(0x00a5a223));
+    // c.jr ra
+    assert_eq!(expand(0x8082), Some(0x00008067));
+    // c.flw fa0, 0(a1)
+    assert_eq!(expand(0x6188), None);
+}
+
+// To create the ELF from compressed.S, run `llvm-mc -triple=riscv32 -mattr=+m,+c -filetype=obj
+// compressed.S -o compressed.o` and `ld.lld -N --image-base=0 -e _start -Ttext 0 compressed.o -o
+// rv32imc-compressed-from-as`, and likewise without `+c` for rv32im-compressed-from-as.
+#[test]
+fn test_rv32imc_runtime() -> Result<()> {
+    let elf = get_elf("tests/data/rv32imc-compressed-from-as")?;
+    assert!(elf.is_compressed());
+    let exe = VmExe::from_elf(
+        elf,
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension)
+            .with_extension(Rv32CTranspilerExtension),
+    )?;
+    assert_eq!(exe.program.step, 2);
+    let executor = VmExecutor::<F, _>::new(Rv32ImConfig::default());
+    executor.execute(exe.clone(), vec![])?;
+
+    // The 16-bit instructions placed out of line make the program larger than without the C
+    // extension, although its ELF is smaller.
+    let uncompressed = VmExe::from_elf(
+        get_elf("tests/data/rv32im-compressed-from-as")?,
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension),
+    )?;
+    assert_eq!(uncompressed.program.num_defined_instructions(), 52);
+    assert_eq!(exe.program.num_defined_instructions(), 127);
+    Ok(())
+}
+
+// To create the ELF from softfloat.S, run `llvm-mc -triple=riscv32 -mattr=+m,+f,+d -filetype=obj
+// softfloat.S -o softfloat.o` and `ld.lld -N --image-base=0 -e _start -Ttext 0 softfloat.o -o
+// rv32imfd-softfloat-from-as`
+#[test]
+fn test_softfloat_traps() -> Result<()> {
+    let elf = get_elf("tests/data/rv32imfd-softfloat-from-as")?;
+    let transpiler = || {
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension)
+    };
+    // Without the extension, the floating-point instructions cannot be transpiled.
+    assert!(VmExe::from_elf(elf.clone(), transpiler()).is_err());
+
+    let exe = VmExe::from_elf(
+        elf,
+        transpiler().with_extension(SoftfloatTranspilerExtension),
+    )?;
+    let executor = VmExecutor::<F, _>::new(Rv32ImConfig::default());
+    executor.execute(exe, vec![])?;
+    Ok(())
+}
+
+// To create the ELFs from atomics.S and atomics-misaligned.S, run `llvm-mc -triple=riscv32
+// -mattr=+m,+a -filetype=obj atomics.S -o atomics.o` and `ld.lld -N --image-base=0 -e _start
+// -Ttext 0 atomics.o -o rv32ima-atomics-from-as`, and likewise for atomics-misaligned.S.
+#[test]
+fn test_rv32ima_runtime() -> Result<()> {
+    let transpiler = || {
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension)
+    };
+    let elf = get_elf("tests/data/rv32ima-atomics-from-as")?;
+    // Without the extension, the atomic instructions cannot be transpiled.
+    assert!(VmExe::from_elf(elf.clone(), transpiler()).is_err());
+
+    let exe = VmExe::from_elf(elf, transpiler().with_extension(Rv32ATranspilerExtension))?;
+    let executor = VmExecutor::<F, _>::new(Rv32ImConfig::default());
+    executor.execute(exe, vec![])?;
+
+    let elf = get_elf("tests/data/rv32ima-atomics-misaligned-from-as")?;
+    let exe = VmExe::from_elf(elf, transpiler().with_extension(Rv32ATranspilerExtension))?;
+    executor.execute(exe.clone(), vec![])?;
+    // `amoadd.w`, `lr.w` and `sc.w` with a misaligned address.
+    for pc_start in [0x10, 0x20, 0x30] {
+        let mut exe = exe.clone();
+        exe.pc_start = pc_start;
+        assert!(matches!(
+            executor.execute(exe, vec![]),
+            Err(ExecutionError::FailedWithExitCode(2))
+        ));
+    }
+    Ok(())
+}
+
+// To create the ELF from dce.S, run `llvm-mc -triple=riscv32 -mattr=+m -filetype=obj dce.S -o
+// dce.o` and `ld.lld --image-base=0 -e _start -Ttext 0x10000 -Tdata 0x20000 dce.o -o
+// rv32im-dce-from-as`
+#[test]
+fn test_dead_code_elimination() -> Result<()> {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let data = read(dir.join("tests/data/rv32im-dce-from-as"))?;
+    let elf = Elf::decode_with_debug_info(&data, MEM_SIZE as u32)?;
+    let transpiler = || {
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension)
+    };
+    let exe = VmExe::from_elf(elf.clone(), transpiler())?;
+    let reduced = VmExe::from_elf(elf, transpiler().with_dead_code_elimination())?;
+
+    let count = |exe: &VmExe<F>, name: &str| {
+        let report = exe.size_report();
+        assert_eq!(report.total(), exe.program.num_defined_instructions());
+        report
+            .functions
+            .into_iter()
+            .find(|(function, _)| function == name)
+            .map_or(0, |(_, count)| count)
+    };
+    assert_eq!(count(&exe, "dead"), 2049);
+    assert_eq!(count(&reduced, "dead"), 0);
+    for name in ["direct", "by_pointer", "by_address"] {
+        assert_eq!(count(&reduced, name), count(&exe, name));
+    }
+    assert_eq!(
+        exe.program.num_defined_instructions() - reduced.program.num_defined_instructions(),
+        2049
+    );
+
+    let executor = VmExecutor::<F, _>::new(Rv32ImConfig::default());
+    executor.execute(reduced, vec![])?;
+    Ok(())
+}
+
+/// An external extension transpiling the instructions of `dispatch` to `opcode`.
+struct ExternalExtension {
+    dispatch: InstructionDispatch,
+    opcode: usize,
+}
+
+impl TranspilerExtension<F> for ExternalExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        self.dispatch
+            .matches(*instruction_stream.first()?)
+            .then(|| {
+                TranspilerOutput::one_to_one(Instruction::from_usize(
+                    VmOpcode::from_usize(self.opcode),
+                    [],
+                ))
+            })
+    }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap(BTreeMap::from([(self.opcode, "EXTERNAL".to_string())]))
+    }
+
+    fn dispatch(&self) -> Vec<InstructionDispatch> {
+        vec![self.dispatch.clone()]
+    }
+}
+
+#[test]
+fn test_external_extension_conflicts() -> Result<()> {
+    let external = |funct7, opcode| ExternalExtension {
+        dispatch: InstructionDispatch::new(0x0b, 0b110, funct7),
+        opcode,
+    };
+    let transpiler = || {
+        Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension)
+            .try_with_extension(external(0..=3, 0x1000))
+    };
+    transpiler()?.try_with_extension(external(4..=5, 0x1001))?;
+
+    let result = transpiler()?.try_with_extension(external(3..=5, 0x1001));
+    assert!(matches!(result, Err(TranspilerError::DispatchConflict(_))));
+    let result = transpiler()?.try_with_extension(external(4..=5, 0x1000));
+    assert!(matches!(
+        result,
+        Err(TranspilerError::OpcodeConflict { opcode: 0x1000, .. })
+    ));
+    let result = Transpiler::<F>::default().try_with_extension(external(0..=3, 0x200));
+    assert!(matches!(
+        result,
+        Err(TranspilerError::OpcodeOutOfRange { opcode: 0x200, .. })
+    ));
+
+    // `mul` is transpiled by the M extension, which has no dispatch.
+    let result = Transpiler::<F>::default()
+        .with_extension(Rv32MTranspilerExtension)
+        .try_with_extension(ExternalExtension {
+            dispatch: InstructionDispatch::new(0x33, 0b000, 1..=1),
+            opcode: 0x1000,
+        });
+    assert!(matches!(result, Err(TranspilerError::DispatchConflict(_))));
+    let result = Transpiler::<F>::default().try_with_extension(Rv32MTranspilerExtension);
+    assert!(matches!(result, Err(TranspilerError::MissingDispatch)));
+    Ok(())
+}
+
 #[derive(Clone, Debug, VmConfig, Serialize, Deserialize)]
 pub struct Rv32ModularFp2Int256Config {
     #[system]
@@ -0,0 +1,210 @@
+//! Expansion of the instructions of the RISC-V compressed (C) extension.
+//!
+//! Every 16-bit instruction of RV32C, except for the floating-point loads and stores, is a
+//! shorter encoding of a 32-bit RV32I instruction. [expand] returns that instruction, which has
+//! the same effect except that the next instruction, and the return address written by `c.jal`
+//! and `c.jalr`, are 2 bytes after the compressed instruction instead of 4.
+
+/// Returns the length in bytes of the instruction whose first 16 bits are the low bits of
+/// `instruction`. Instructions longer than 32 bits are not supported.
+pub fn instruction_len(instruction: u32) -> u32 {
+    if instruction & 0b11 == 0b11 {
+        4
+    } else {
+        2
+    }
+}
+
+const OPCODE_LOAD: u32 = 0b0000011;
+const OPCODE_OP_IMM: u32 = 0b0010011;
+const OPCODE_STORE: u32 = 0b0100011;
+const OPCODE_OP: u32 = 0b0110011;
+const OPCODE_LUI: u32 = 0b0110111;
+const OPCODE_BRANCH: u32 = 0b1100011;
+const OPCODE_JALR: u32 = 0b1100111;
+const OPCODE_JAL: u32 = 0b1101111;
+const OPCODE_SYSTEM: u32 = 0b1110011;
+
+/// Returns the 32-bit instruction equivalent to the compressed instruction `c`, or `None` if it
+/// is not a valid RV32C instruction or is a floating-point instruction.
+pub fn expand(c: u16) -> Option<u32> {
+    let c = c as u32;
+    let bits = |hi: u32, lo: u32| (c >> lo) & ((1 << (hi - lo + 1)) - 1);
+    // Register fields of the full and the 3-bit register forms.
+    let rd = bits(11, 7);
+    let rs2 = bits(6, 2);
+    let rd_prime = bits(4, 2) + 8;
+    let rs1_prime = bits(9, 7) + 8;
+    // The 6-bit signed immediate of c.addi, c.li, c.andi and the shift amount of the shifts.
+    let imm6 = sign_extend((bits(12, 12) << 5) | bits(6, 2), 6);
+    let shamt = (bits(12, 12) << 5) | bits(6, 2);
+    // The word offset of c.lw and c.sw.
+    let lw_offset = (bits(12, 10) << 3) | (bits(6, 6) << 2) | (bits(5, 5) << 6);
+    // The offset of c.j and c.jal.
+    let j_offset = sign_extend(
+        (bits(12, 12) << 11)
+            | (bits(11, 11) << 4)
+            | (bits(10, 9) << 8)
+            | (bits(8, 8) << 10)
+            | (bits(7, 7) << 6)
+            | (bits(6, 6) << 7)
+            | (bits(5, 3) << 1)
+            | (bits(2, 2) << 5),
+        12,
+    );
+    // The offset of c.beqz and c.bnez.
+    let b_offset = sign_extend(
+        (bits(12, 12) << 8)
+            | (bits(11, 10) << 3)
+            | (bits(6, 5) << 6)
+            | (bits(4, 3) << 1)
+            | (bits(2, 2) << 5),
+        9,
+    );
+
+    let instruction = match (bits(1, 0), bits(15, 13)) {
+        // c.addi4spn
+        (0b00, 0b000) => {
+            let imm =
+                (bits(12, 11) << 4) | (bits(10, 7) << 6) | (bits(6, 6) << 2) | (bits(5, 5) << 3);
+            if imm == 0 {
+                return None;
+            }
+            i_type(OPCODE_OP_IMM, rd_prime, 0b000, 2, imm)
+        }
+        // c.lw
+        (0b00, 0b010) => i_type(OPCODE_LOAD, rd_prime, 0b010, rs1_prime, lw_offset),
+        // c.sw
+        (0b00, 0b110) => s_type(0b010, rs1_prime, rd_prime, lw_offset),
+        // c.addi, or c.nop if rd is x0
+        (0b01, 0b000) => i_type(OPCODE_OP_IMM, rd, 0b000, rd, imm6),
+        // c.jal
+        (0b01, 0b001) => j_type(1, j_offset),
+        // c.li
+        (0b01, 0b010) => i_type(OPCODE_OP_IMM, rd, 0b000, 0, imm6),
+        (0b01, 0b011) => {
+            if rd == 2 {
+                // c.addi16sp
+                let imm = sign_extend(
+                    (bits(12, 12) << 9)
+                        | (bits(6, 6) << 4)
+                        | (bits(5, 5) << 6)
+                        | (bits(4, 3) << 7)
+                        | (bits(2, 2) << 5),
+                    10,
+                );
+                if imm == 0 {
+                    return None;
+                }
+                i_type(OPCODE_OP_IMM, 2, 0b000, 2, imm)
+            } else {
+                // c.lui
+                let imm = sign_extend((bits(12, 12) << 17) | (bits(6, 2) << 12), 18);
+                if imm == 0 {
+                    return None;
+                }
+                imm & 0xfffff000 | (rd << 7) | OPCODE_LUI
+            }
+        }
+        (0b01, 0b100) => match bits(11, 10) {
+            // c.srli and c.srai, whose shift amount must fit in 5 bits in RV32
+            0b00 | 0b01 if shamt < 32 => {
+                let funct7 = bits(10, 10) << 5;
+                i_type(
+                    OPCODE_OP_IMM,
+                    rs1_prime,
+                    0b101,
+                    rs1_prime,
+                    (funct7 << 5) | shamt,
+                )
+            }
+            // c.andi
+            0b10 => i_type(OPCODE_OP_IMM, rs1_prime, 0b111, rs1_prime, imm6),
+            // c.sub, c.xor, c.or and c.and
+            0b11 if bits(12, 12) == 0 => {
+                let (funct3, funct7) = match bits(6, 5) {
+                    0b00 => (0b000, 0b0100000),
+                    0b01 => (0b100, 0),
+                    0b10 => (0b110, 0),
+                    _ => (0b111, 0),
+                };
+                r_type(rs1_prime, funct3, rs1_prime, rd_prime, funct7)
+            }
+            _ => return None,
+        },
+        // c.j
+        (0b01, 0b101) => j_type(0, j_offset),
+        // c.beqz and c.bnez
+        (0b01, 0b110) => b_type(0b000, rs1_prime, b_offset),
+        (0b01, 0b111) => b_type(0b001, rs1_prime, b_offset),
+        // c.slli
+        (0b10, 0b000) if shamt < 32 => i_type(OPCODE_OP_IMM, rd, 0b001, rd, shamt),
+        // c.lwsp
+        (0b10, 0b010) if rd != 0 => {
+            let offset = (bits(12, 12) << 5) | (bits(6, 4) << 2) | (bits(3, 2) << 6);
+            i_type(OPCODE_LOAD, rd, 0b010, 2, offset)
+        }
+        (0b10, 0b100) => match (bits(12, 12), rd, rs2) {
+            // c.jr
+            (0, 0, 0) => return None,
+            (0, rs1, 0) => i_type(OPCODE_JALR, 0, 0b000, rs1, 0),
+            // c.mv
+            (0, rd, rs2) => r_type(rd, 0b000, 0, rs2, 0),
+            // c.ebreak
+            (1, 0, 0) => i_type(OPCODE_SYSTEM, 0, 0b000, 0, 1),
+            // c.jalr
+            (1, rs1, 0) => i_type(OPCODE_JALR, 1, 0b000, rs1, 0),
+            // c.add
+            (_, rd, rs2) => r_type(rd, 0b000, rd, rs2, 0),
+        },
+        // c.swsp
+        (0b10, 0b110) => {
+            let offset = (bits(12, 9) << 2) | (bits(8, 7) << 6);
+            s_type(0b010, 2, rs2, offset)
+        }
+        _ => return None,
+    };
+    Some(instruction)
+}
+
+/// Sign-extends the low `bits` bits of `value`.
+fn sign_extend(value: u32, bits: u32) -> u32 {
+    (((value << (32 - bits)) as i32) >> (32 - bits)) as u32
+}
+
+fn i_type(opcode: u32, rd: u32, funct3: u32, rs1: u32, imm: u32) -> u32 {
+    ((imm & 0xfff) << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | opcode
+}
+
+fn s_type(funct3: u32, rs1: u32, rs2: u32, imm: u32) -> u32 {
+    (((imm >> 5) & 0x7f) << 25)
+        | (rs2 << 20)
+        | (rs1 << 15)
+        | (funct3 << 12)
+        | ((imm & 0x1f) << 7)
+        | OPCODE_STORE
+}
+
+fn r_type(rd: u32, funct3: u32, rs1: u32, rs2: u32, funct7: u32) -> u32 {
+    (funct7 << 25) | (rs2 << 20) | (rs1 << 15) | (funct3 << 12) | (rd << 7) | OPCODE_OP
+}
+
+/// A branch comparing `rs1` with x0.
+fn b_type(funct3: u32, rs1: u32, imm: u32) -> u32 {
+    (((imm >> 12) & 1) << 31)
+        | (((imm >> 5) & 0x3f) << 25)
+        | (rs1 << 15)
+        | (funct3 << 12)
+        | (((imm >> 1) & 0xf) << 8)
+        | (((imm >> 11) & 1) << 7)
+        | OPCODE_BRANCH
+}
+
+fn j_type(rd: u32, imm: u32) -> u32 {
+    (((imm >> 20) & 1) << 31)
+        | (((imm >> 1) & 0x3ff) << 21)
+        | (((imm >> 11) & 1) << 20)
+        | (((imm >> 12) & 0xff) << 12)
+        | (rd << 7)
+        | OPCODE_JAL
+}
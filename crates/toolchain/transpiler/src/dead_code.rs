@@ -0,0 +1,207 @@
+//! Elimination of the instructions of a program which cannot be executed.
+//!
+//! An instruction is reachable if it can be reached from the entry point of the program by
+//! falling through, branches, direct jumps and indirect jumps. The targets of indirect jumps are
+//! the code addresses the program can compute, which are assumed to be
+//! - the address computed by an `auipc` or `lui` followed by a `jalr`, or by an `addi` which
+//!   overwrites the same register, as in `call`, `tail` and `la`; an `auipc` or `lui` followed by a
+//!   load which overwrites the same register computes the address of data,
+//! - the addresses within 2 KiB of the value of any other reachable `auipc` or `lui`, which covers
+//!   the addresses they are combined with a 12-bit immediate into later on,
+//! - the words of the initial memory image, such as function pointers, vtables and jump tables with
+//!   absolute entries,
+//! - the return addresses of calls, which are the instructions following them.
+//!
+//! Programs computing code addresses otherwise, e.g. with the jump tables of relative offsets of
+//! position-independent code, must not be transpiled with dead code elimination.
+
+use openvm_instructions::instruction::Instruction;
+use rrs_lib::instruction_formats::{BType, IType, JType, UType};
+
+use crate::{
+    compressed::{expand, instruction_len},
+    elf::Elf,
+};
+
+const OPCODE_LOAD: u32 = 0b0000011;
+const OPCODE_OP_IMM: u32 = 0b0010011;
+const OPCODE_LUI: u32 = 0b0110111;
+const OPCODE_AUIPC: u32 = 0b0010111;
+const OPCODE_BRANCH: u32 = 0b1100011;
+const OPCODE_JALR: u32 = 0b1100111;
+const OPCODE_JAL: u32 = 0b1101111;
+
+/// The distance from the value of an `auipc` or `lui` to the addresses computed from it.
+const MAX_OFFSET: i64 = 1 << 11;
+
+/// Returns whether each instruction of `elf` is reachable, see the [module](self) documentation.
+pub fn reachable_instructions(elf: &Elf) -> Vec<bool> {
+    let mut addresses = Vec::with_capacity(elf.instructions.len());
+    let mut address = elf.pc_base;
+    // Instructions of compressed programs are decoded as their 32-bit equivalent.
+    let mut decoded = Vec::with_capacity(elf.instructions.len());
+    for &instruction in &elf.instructions {
+        addresses.push(address);
+        let len = if elf.compressed {
+            instruction_len(instruction)
+        } else {
+            4
+        };
+        decoded.push(if len == 2 {
+            expand(instruction as u16)
+        } else {
+            Some(instruction)
+        });
+        address += len;
+    }
+    let mut reachability = Reachability::new(addresses);
+
+    reachability.visit_address(elf.pc_start);
+    for &word in elf.memory_image.values() {
+        reachability.visit_address(word);
+    }
+    while let Some(index) = reachability.stack.pop() {
+        let pc = reachability.addresses[index];
+        let Some(instruction) = decoded[index] else {
+            reachability.visit(index + 1);
+            continue;
+        };
+        match instruction & 0x7f {
+            OPCODE_JAL => {
+                let jal = JType::new(instruction);
+                reachability.visit_address(pc.wrapping_add(jal.imm as u32));
+                if jal.rd != 0 {
+                    reachability.visit(index + 1);
+                }
+            }
+            OPCODE_JALR => {
+                // The targets of indirect jumps are visited as the addresses are computed.
+                if IType::new(instruction).rd != 0 {
+                    reachability.visit(index + 1);
+                }
+            }
+            OPCODE_BRANCH => {
+                let branch = BType::new(instruction);
+                reachability.visit_address(pc.wrapping_add(branch.imm as u32));
+                reachability.visit(index + 1);
+            }
+            opcode @ (OPCODE_AUIPC | OPCODE_LUI) => {
+                let upper = UType::new(instruction);
+                let value = if opcode == OPCODE_AUIPC {
+                    pc.wrapping_add(upper.imm as u32)
+                } else {
+                    upper.imm as u32
+                };
+                let next = decoded.get(index + 1).copied().flatten();
+                let consumer = next
+                    .map(|next| (next & 0x7f, IType::new(next)))
+                    .filter(|(_, next)| upper.rd != 0 && next.rs1 == upper.rd);
+                match consumer {
+                    Some((OPCODE_JALR, jalr)) => {
+                        reachability.visit_address(value.wrapping_add(jalr.imm as u32))
+                    }
+                    // `addi` with funct3 0.
+                    Some((OPCODE_OP_IMM, addi)) if addi.funct3 == 0 && addi.rd == upper.rd => {
+                        reachability.visit_address(value.wrapping_add(addi.imm as u32))
+                    }
+                    Some((OPCODE_LOAD, load)) if load.rd == upper.rd => {}
+                    _ => reachability
+                        .visit_range(value as i64 - MAX_OFFSET, value as i64 + MAX_OFFSET - 1),
+                }
+                reachability.visit(index + 1);
+            }
+            _ => reachability.visit(index + 1),
+        }
+    }
+    let Reachability {
+        addresses,
+        next_unvisited,
+        ..
+    } = reachability;
+    (0..addresses.len())
+        .map(|index| next_unvisited[index] != index)
+        .collect()
+}
+
+/// Removes the unreachable instructions of `elf` from `program`, its transpilation with `slot_size`
+/// bytes of RISC-V instructions per slot. The instructions the transpiler placed out of line, past
+/// the slots of the RISC-V instructions, are kept.
+pub(crate) fn eliminate_dead_code<F>(
+    elf: &Elf,
+    program: &mut [Option<Instruction<F>>],
+    slot_size: u32,
+) {
+    let mut slot = 0;
+    for (&instruction, reachable) in elf.instructions.iter().zip(reachable_instructions(elf)) {
+        let len = if elf.compressed {
+            instruction_len(instruction)
+        } else {
+            4
+        };
+        let slots = (len / slot_size) as usize;
+        if !reachable {
+            program[slot..slot + slots]
+                .iter_mut()
+                .for_each(|instruction| *instruction = None);
+        }
+        slot += slots;
+    }
+}
+
+struct Reachability {
+    /// The address of each instruction, in increasing order.
+    addresses: Vec<u32>,
+    /// For each instruction, the first instruction at or after it which is not visited, with one
+    /// more entry for the end of the program. Entries of visited instructions are only updated
+    /// when they are looked up, as in a union-find structure.
+    next_unvisited: Vec<usize>,
+    /// Visited instructions whose successors are not visited yet.
+    stack: Vec<usize>,
+}
+
+impl Reachability {
+    fn new(addresses: Vec<u32>) -> Self {
+        Self {
+            next_unvisited: (0..=addresses.len()).collect(),
+            addresses,
+            stack: Vec::new(),
+        }
+    }
+
+    fn find_unvisited(&mut self, index: usize) -> usize {
+        let mut next = index;
+        while self.next_unvisited[next] != next {
+            next = self.next_unvisited[next];
+        }
+        let mut index = index;
+        while index != next {
+            index = std::mem::replace(&mut self.next_unvisited[index], next);
+        }
+        next
+    }
+
+    fn visit(&mut self, index: usize) {
+        if index < self.addresses.len() && self.next_unvisited[index] == index {
+            self.next_unvisited[index] = index + 1;
+            self.stack.push(index);
+        }
+    }
+
+    /// Visits the instruction at `address`, if there is one.
+    fn visit_address(&mut self, address: u32) {
+        if let Ok(index) = self.addresses.binary_search(&address) {
+            self.visit(index);
+        }
+    }
+
+    /// Visits the instructions at the addresses from `start` to `end`, both inclusive.
+    fn visit_range(&mut self, start: i64, end: i64) {
+        let first = self.addresses.partition_point(|&a| (a as i64) < start);
+        let last = self.addresses.partition_point(|&a| a as i64 <= end);
+        let mut index = self.find_unvisited(first);
+        while index < last {
+            self.visit(index);
+            index = self.find_unvisited(index + 1);
+        }
+    }
+}
@@ -1,6 +1,10 @@
 // Initial version taken from https://github.com/succinctlabs/sp1/blob/v2.0.0/crates/core/executor/src/disassembler/elf.rs under MIT License
 // and https://github.com/risc0/risc0/blob/f61379bf69b24d56e49d6af96a3b284961dcc498/risc0/binfmt/src/elf.rs#L34 under Apache License
-use std::{cmp::min, collections::BTreeMap, fmt::Debug};
+use std::{
+    cmp::min,
+    collections::BTreeMap,
+    fmt::{self, Debug, Display},
+};
 #[cfg(feature = "function-span")]
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -8,16 +12,27 @@ use std::{
 };
 
 use elf::{
-    abi::{EM_RISCV, ET_EXEC, PF_X, PT_LOAD},
+    abi::{EM_RISCV, ET_EXEC, PF_X, PT_LOAD, SHF_ALLOC, SHT_NOBITS, STT_FUNC},
     endian::LittleEndian,
     file::Class,
     ElfBytes,
 };
 use eyre::{self, bail, ContextCompat};
-#[cfg(feature = "function-span")]
-use openvm_instructions::exe::FnBound;
-use openvm_instructions::{exe::FnBounds, program::MAX_ALLOWED_PC};
-use openvm_platform::WORD_SIZE;
+use openvm_instructions::{
+    exe::{FnBound, FnBounds, SourceLocation, SourceMap},
+    program::MAX_ALLOWED_PC,
+};
+use openvm_overlay::{parse_section, OVERLAY_SECTION};
+use openvm_platform::{
+    extension_versions::{EXTENSION_VERSIONS_SECTION, OPTIONAL_VERSION},
+    WORD_SIZE,
+};
+use thiserror::Error;
+
+use crate::compressed::instruction_len;
+
+/// The flag of the ELF header marking programs which may contain compressed instructions.
+const EF_RISCV_RVC: u32 = 0x1;
 
 /// RISC-V 32IM ELF (Executable and Linkable Format) File.
 ///
@@ -26,12 +41,16 @@ use openvm_platform::WORD_SIZE;
 ///
 /// - Base Integer Instruction Set (I)
 /// - Integer Multiplication and Division (M)
+/// - Optionally, Compressed Instructions (C), see [Elf::is_compressed]
 ///
 /// This format is commonly used in embedded systems and is supported by many compilers.
 #[derive(Debug, Clone)]
 pub struct Elf {
-    /// The instructions of the program encoded as 32-bits.
+    /// The instructions of the program encoded as 32-bits. If the program is
+    /// [compressed](Elf::is_compressed), 16-bit instructions are in the low half of their entry.
     pub instructions: Vec<u32>,
+    /// Whether the program may contain 16-bit instructions of the compressed (C) extension.
+    pub(crate) compressed: bool,
     /// The start address of the program.
     pub(crate) pc_start: u32,
     /// The base address of the program.
@@ -40,24 +59,155 @@ pub struct Elf {
     pub(crate) memory_image: BTreeMap<u32, u32>,
     /// Debug info for spanning benchmark metrics by function.
     pub(crate) fn_bounds: FnBounds,
+    /// The contents of the overlays of the program, which are not part of the memory image.
+    pub(crate) overlays: Vec<Vec<u8>>,
+    /// The sizes of the sections loaded into memory, see [Elf::section_sizes].
+    pub(crate) section_sizes: SectionSizes,
+    /// The versions of the VM extensions the program requires, see [Elf::extension_versions].
+    pub(crate) extension_versions: BTreeMap<String, u32>,
+    /// The versions of the optional VM extensions of the program, see
+    /// [Elf::optional_extension_versions].
+    pub(crate) optional_extension_versions: BTreeMap<String, u32>,
+    /// Source locations of the instructions, only decoded by [Elf::decode_with_debug_info].
+    pub(crate) source_map: SourceMap,
+}
+
+/// The names and sizes in bytes of the sections of an ELF which are loaded into memory, in
+/// address order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SectionSizes(pub Vec<(String, u64)>);
+
+impl Display for SectionSizes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "section sizes:")?;
+        for (name, size) in &self.0 {
+            writeln!(f, "  {name:<24} {size:>12} bytes")?;
+        }
+        write!(
+            f,
+            "to reduce the program size, consider building with `opt-level = \"z\"` and \
+             `strip = true` (or `debug = false`) in the profile, and removing unused dependencies"
+        )
+    }
+}
+
+/// Error returned when a program has more instructions than the VM allows.
+#[derive(Error, Debug)]
+#[error(
+    "program has {program_len} instructions, which exceeds the maximum of {max_program_len}\n\
+     {section_sizes}"
+)]
+pub struct ProgramTooLargeError {
+    pub program_len: usize,
+    pub max_program_len: usize,
+    pub section_sizes: SectionSizes,
 }
 
 impl Elf {
     /// Create a new [Elf].
     pub(crate) const fn new(
         instructions: Vec<u32>,
+        compressed: bool,
         pc_start: u32,
         pc_base: u32,
         memory_image: BTreeMap<u32, u32>,
         fn_bounds: FnBounds,
+        overlays: Vec<Vec<u8>>,
+        section_sizes: SectionSizes,
+        extension_versions: BTreeMap<String, u32>,
+        optional_extension_versions: BTreeMap<String, u32>,
     ) -> Self {
         Self {
             instructions,
+            compressed,
             pc_start,
             pc_base,
             memory_image,
             fn_bounds,
+            overlays,
+            section_sizes,
+            extension_versions,
+            optional_extension_versions,
+            source_map: SourceMap {
+                files: Vec::new(),
+                lines: BTreeMap::new(),
+            },
+        }
+    }
+
+    /// Like [Elf::decode], but also decodes the source locations of the instructions from the
+    /// DWARF line info of the ELF, and the function bounds from its symbol table if the
+    /// "function-span" feature did not already, so that execution errors and profiles can be
+    /// reported by function and source line.
+    ///
+    /// Both are empty if the ELF was built without debug info or stripped.
+    pub fn decode_with_debug_info(input: &[u8], max_mem: u32) -> eyre::Result<Self> {
+        let mut elf = Self::decode(input, max_mem)?;
+        elf.source_map = decode_source_map(input)?;
+        if elf.fn_bounds.is_empty() {
+            elf.fn_bounds = decode_fn_bounds(input)?;
+        }
+        Ok(elf)
+    }
+
+    /// The source locations of the instructions, empty unless the ELF was decoded with
+    /// [Elf::decode_with_debug_info].
+    pub fn source_map(&self) -> &SourceMap {
+        &self.source_map
+    }
+
+    /// Whether the ELF was built for a target with the compressed (C) extension, such as
+    /// `riscv32imc`, as recorded in its header. Its instructions are then transpiled into a
+    /// program with a pc step of 2, see
+    /// [Transpiler::transpile_compressed](crate::transpiler::Transpiler::transpile_compressed).
+    pub fn is_compressed(&self) -> bool {
+        self.compressed
+    }
+
+    /// The number of slots of the transpiled program taken by the instructions of the ELF, one
+    /// per 2 bytes of instructions if the ELF is [compressed](Elf::is_compressed).
+    fn program_len(&self) -> usize {
+        if self.compressed {
+            self.instructions
+                .iter()
+                .map(|&instruction| instruction_len(instruction) as usize / 2)
+                .sum()
+        } else {
+            self.instructions.len()
+        }
+    }
+
+    /// The names and sizes of the sections of the ELF which are loaded into memory.
+    pub fn section_sizes(&self) -> &SectionSizes {
+        &self.section_sizes
+    }
+
+    /// The semantic versions of the VM extensions the program was built against, by extension
+    /// name, as recorded by the guest libraries of the extensions in the
+    /// [EXTENSION_VERSIONS_SECTION] section.
+    pub fn extension_versions(&self) -> &BTreeMap<String, u32> {
+        &self.extension_versions
+    }
+
+    /// The semantic versions of the VM extensions the program uses if the VM has them, and falls
+    /// back to software for otherwise, by extension name, as recorded by
+    /// [optional_extension_version](openvm_platform::optional_extension_version).
+    pub fn optional_extension_versions(&self) -> &BTreeMap<String, u32> {
+        &self.optional_extension_versions
+    }
+
+    /// Checks that the program has at most `max_program_len` instructions, so that a program too
+    /// large for the VM is reported before it is transpiled and committed.
+    pub fn check_program_len(&self, max_program_len: usize) -> Result<(), ProgramTooLargeError> {
+        let program_len = self.program_len();
+        if program_len > max_program_len {
+            return Err(ProgramTooLargeError {
+                program_len,
+                max_program_len,
+                section_sizes: self.section_sizes.clone(),
+            });
         }
+        Ok(())
     }
 
     /// Parse the ELF file into a vector of 32-bit encoded instructions and the first memory
@@ -147,11 +297,39 @@ impl Elf {
             .try_into()
             .map_err(|err| eyre::eyre!("e_entry was larger than 32 bits. {err}"))?;
 
+        let compressed = elf.ehdr.e_flags & EF_RISCV_RVC != 0;
+
         // Make sure the entrypoint is valid.
-        if entry >= max_mem || entry % WORD_SIZE as u32 != 0 {
+        let instruction_alignment = if compressed { 2 } else { WORD_SIZE as u32 };
+        if entry >= max_mem || entry % instruction_alignment != 0 {
             bail!("Invalid entrypoint");
         }
 
+        // Record the sizes of the sections loaded into memory, to report them when the program
+        // does not fit.
+        let mut sections = Vec::new();
+        if let (Some(shdrs), Some(strtab)) = elf
+            .section_headers_with_strtab()
+            .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?
+        {
+            for shdr in shdrs
+                .iter()
+                .filter(|shdr| (shdr.sh_flags & SHF_ALLOC as u64) != 0 && shdr.sh_size > 0)
+            {
+                let name = strtab
+                    .get(shdr.sh_name as usize)
+                    .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+                sections.push((shdr.sh_addr, name.to_string(), shdr.sh_size));
+            }
+        }
+        sections.sort();
+        let section_sizes = SectionSizes(
+            sections
+                .into_iter()
+                .map(|(_, name, size)| (name, size))
+                .collect(),
+        );
+
         // Get the segments of the ELF file.
         let segments = elf
             .segments()
@@ -168,13 +346,13 @@ impl Elf {
             // Get the file size of the segment as an u32.
             let file_size: u32 = segment.p_filesz.try_into()?;
             if file_size >= max_mem {
-                bail!("invalid segment file_size");
+                bail!("invalid segment file_size {file_size}\n{section_sizes}");
             }
 
             // Get the memory size of the segment as an u32.
             let mem_size: u32 = segment.p_memsz.try_into()?;
             if mem_size >= max_mem {
-                bail!("Invalid segment mem_size");
+                bail!("Invalid segment mem_size {mem_size}\n{section_sizes}");
             }
 
             // Get the virtual address of the segment as an u32.
@@ -199,10 +377,10 @@ impl Elf {
                     .ok_or_else(|| eyre::eyre!("vaddr overflow"))?;
                 if addr >= max_mem {
                     bail!(
-                        "address [0x{addr:08x}] exceeds maximum address for guest programs [0x{max_mem:08x}]"
+                        "address [0x{addr:08x}] exceeds maximum address for guest programs [0x{max_mem:08x}]\n{section_sizes}"
                     );
                 } else if addr > MAX_ALLOWED_PC && (segment.p_flags & PF_X) != 0 {
-                    bail!("instruction address [0x{addr:08x}] exceeds maximum PC [0x{MAX_ALLOWED_PC:08x}]");
+                    bail!("instruction address [0x{addr:08x}] exceeds maximum PC [0x{MAX_ALLOWED_PC:08x}]\n{section_sizes}");
                 }
 
                 // If we are reading past the end of the file, then break.
@@ -226,12 +404,253 @@ impl Elf {
             }
         }
 
+        // Read the overlays, which are in a section that is not part of any segment.
+        let mut overlays = Vec::new();
+        if let Some(shdr) = elf
+            .section_header_by_name(OVERLAY_SECTION)
+            .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?
+        {
+            let (data, compression) = elf
+                .section_data(&shdr)
+                .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+            if compression.is_some() {
+                bail!("Compressed {OVERLAY_SECTION} section is not supported");
+            }
+            overlays = parse_section(data)
+                .with_context(|| format!("Malformed {OVERLAY_SECTION} section"))?
+                .into_iter()
+                .map(|overlay| overlay.to_vec())
+                .collect();
+        }
+
+        let mut extension_versions = BTreeMap::new();
+        let mut optional_extension_versions = BTreeMap::new();
+        if let Some(shdr) = elf
+            .section_header_by_name(EXTENSION_VERSIONS_SECTION)
+            .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?
+        {
+            let (data, compression) = elf
+                .section_data(&shdr)
+                .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+            if compression.is_some() {
+                bail!("Compressed {EXTENSION_VERSIONS_SECTION} section is not supported");
+            }
+            for (name, version) in parse_extension_versions(data)
+                .with_context(|| format!("Malformed {EXTENSION_VERSIONS_SECTION} section"))?
+            {
+                let (versions, version) = if version & OPTIONAL_VERSION != 0 {
+                    (
+                        &mut optional_extension_versions,
+                        version & !OPTIONAL_VERSION,
+                    )
+                } else {
+                    (&mut extension_versions, version)
+                };
+                match versions.insert(name.to_string(), version) {
+                    Some(other) if other != version => {
+                        bail!("Program requires both version {other} and {version} of extension {name}")
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if compressed {
+            instructions = split_compressed(&instructions);
+        }
+
         Ok(Elf::new(
             instructions,
+            compressed,
             entry,
             base_address,
             image,
             fn_bounds,
+            overlays,
+            section_sizes,
+            extension_versions,
+            optional_extension_versions,
         ))
     }
 }
+
+/// Splits the words of the executable segments of a program using the compressed (C) extension
+/// into its instructions, with 16-bit instructions in the low half of their entry.
+fn split_compressed(words: &[u32]) -> Vec<u32> {
+    let halfwords: Vec<u32> = words
+        .iter()
+        .flat_map(|word| [word & 0xffff, word >> 16])
+        .collect();
+    let mut instructions = Vec::with_capacity(halfwords.len());
+    let mut i = 0;
+    while i < halfwords.len() {
+        if instruction_len(halfwords[i]) == 4 {
+            let upper = halfwords.get(i + 1).copied().unwrap_or(0);
+            instructions.push(halfwords[i] | upper << 16);
+            i += 2;
+        } else {
+            instructions.push(halfwords[i]);
+            i += 1;
+        }
+    }
+    instructions
+}
+
+/// Returns the extension names and versions in the contents of an [EXTENSION_VERSIONS_SECTION]
+/// section, or `None` if the section is malformed.
+fn parse_extension_versions(mut section: &[u8]) -> Option<Vec<(&str, u32)>> {
+    let mut versions = Vec::new();
+    while !section.is_empty() {
+        let len = u32::from_le_bytes(section.get(..4)?.try_into().unwrap()) as usize;
+        let name = std::str::from_utf8(section.get(4..4 + len)?).ok()?;
+        section = section.get(4 + len.next_multiple_of(4)..)?;
+        versions.push((
+            name,
+            u32::from_le_bytes(section.get(..4)?.try_into().unwrap()),
+        ));
+        section = &section[4..];
+    }
+    Some(versions)
+}
+
+/// Returns the bounds of the functions in the symbol table of the ELF `input`, keyed by start
+/// address and with demangled names. There are none if the ELF has no symbol table, e.g. because
+/// it was stripped.
+pub fn decode_fn_bounds(input: &[u8]) -> eyre::Result<FnBounds> {
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(input)
+        .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+    let mut fn_bounds = FnBounds::new();
+    let Some((symtab, strtab)) = elf
+        .symbol_table()
+        .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?
+    else {
+        return Ok(fn_bounds);
+    };
+    for symbol in symtab
+        .iter()
+        .filter(|symbol| symbol.st_symtype() == STT_FUNC)
+    {
+        let name = strtab
+            .get(symbol.st_name as usize)
+            .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+        let start = symbol.st_value as u32;
+        fn_bounds.insert(
+            start,
+            FnBound {
+                start,
+                end: (symbol.st_value + symbol.st_size).saturating_sub(WORD_SIZE as u64) as u32,
+                name: rustc_demangle::demangle(name).to_string(),
+            },
+        );
+    }
+    Ok(fn_bounds)
+}
+
+/// Returns the source locations of the instructions of the ELF `input`, from the line programs in
+/// its `.debug_line` section. The map is empty if the ELF has no DWARF line info.
+pub fn decode_source_map(input: &[u8]) -> eyre::Result<SourceMap> {
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(input)
+        .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+    let load_section = |id: gimli::SectionId| -> eyre::Result<&[u8]> {
+        let Some(shdr) = elf
+            .section_header_by_name(id.name())
+            .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?
+        else {
+            return Ok(&[]);
+        };
+        let (data, compression) = elf
+            .section_data(&shdr)
+            .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+        if compression.is_some() {
+            bail!("Compressed {} section is not supported", id.name());
+        }
+        Ok(data)
+    };
+    let sections = gimli::DwarfSections::load(load_section)?;
+    let dwarf = sections.borrow(|section| gimli::EndianSlice::new(section, gimli::LittleEndian));
+
+    let mut source_map = SourceMap::default();
+    let mut file_indices: BTreeMap<String, u32> = BTreeMap::new();
+    let mut units = dwarf.units();
+    while let Some(header) = units.next()? {
+        let unit = dwarf.unit(header)?;
+        let Some(program) = unit.line_program.clone() else {
+            continue;
+        };
+        let mut rows = program.rows();
+        // Sequences of functions removed by the linker are relocated to address 0.
+        let mut skip_sequence = false;
+        let mut sequence_start = true;
+        while let Some((header, row)) = rows.next_row()? {
+            let address = row.address() as u32;
+            if sequence_start {
+                skip_sequence = address == 0;
+                sequence_start = false;
+            }
+            if row.end_sequence() {
+                if !skip_sequence {
+                    source_map.lines.entry(address).or_insert(None);
+                }
+                sequence_start = true;
+                continue;
+            }
+            if skip_sequence {
+                continue;
+            }
+            let Some(line) = row.line() else {
+                continue;
+            };
+            let Some(file) = row.file(header) else {
+                continue;
+            };
+            let mut path = String::new();
+            if let Some(dir) = file.directory(header) {
+                path = dwarf
+                    .attr_string(&unit, dir)?
+                    .to_string_lossy()
+                    .into_owned();
+            }
+            let name = dwarf.attr_string(&unit, file.path_name())?;
+            let name = name.to_string_lossy();
+            if path.is_empty() || name.starts_with('/') {
+                path = name.into_owned();
+            } else {
+                path = format!("{path}/{name}");
+            }
+            let next_index = source_map.files.len() as u32;
+            let file = *file_indices.entry(path).or_insert_with_key(|path| {
+                source_map.files.push(path.clone());
+                next_index
+            });
+            source_map.lines.insert(
+                address,
+                Some(SourceLocation {
+                    file,
+                    line: line.get() as u32,
+                }),
+            );
+        }
+    }
+    Ok(source_map)
+}
+
+/// Returns the contents of the section `name` of the ELF `input`, as stored in the file, if it has
+/// such a section.
+pub fn decode_section<'a>(input: &'a [u8], name: &str) -> eyre::Result<Option<&'a [u8]>> {
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(input)
+        .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?;
+    let Some(shdr) = elf
+        .section_header_by_name(name)
+        .map_err(|err| eyre::eyre!("Elf parse error: {err}"))?
+    else {
+        return Ok(None);
+    };
+    if shdr.sh_type == SHT_NOBITS {
+        return Ok(Some(&[]));
+    }
+    let start = shdr.sh_offset as usize;
+    let data = input
+        .get(start..start + shdr.sh_size as usize)
+        .ok_or_else(|| eyre::eyre!("Section {name} exceeds the file"))?;
+    Ok(Some(data))
+}
@@ -1,7 +1,13 @@
+use std::ops::RangeInclusive;
+
 use openvm_instructions::instruction::Instruction;
 
+use crate::metadata::OpcodeMap;
+
 /// Trait to add custom RISC-V instruction transpilation to OpenVM instruction format.
-/// RISC-V instructions always come in 32-bit chunks.
+/// RISC-V instructions always come in 32-bit chunks. In programs using the compressed (C)
+/// extension, a 16-bit instruction is in the low half of its chunk, and its low two bits are not
+/// both set, unlike those of 32-bit instructions.
 /// An important feature is that multiple 32-bit RISC-V instructions can be transpiled into a single
 /// OpenVM instruction. See [process_custom](Self::process_custom) for details.
 pub trait TranspilerExtension<F> {
@@ -9,14 +15,93 @@ pub trait TranspilerExtension<F> {
     /// processed, presented as 32-bit chunks. The [process_custom](Self::process_custom) should
     /// determine if it knows how to transpile the next contiguous section of RISC-V
     /// instructions into an [`Instruction`]. It returns `None` if it cannot transpile.
-    /// Otherwise it returns `TranspilerOutput { instructions, used_u32s }` to indicate that
-    /// `instruction_stream[..used_u32s]` should be transpiled into `instructions`.
+    /// Otherwise it returns `TranspilerOutput { instructions, used_u32s, .. }` to indicate that
+    /// `instruction_stream[..used_u32s]` should be transpiled into `instructions`, or a
+    /// [TranspilerOutput::out_of_line] if a single RISC-V instruction needs more than one
+    /// instruction.
     fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>>;
+
+    /// The opcodes this extension transpiles to. Their digest is recorded in the metadata of
+    /// transpiled exes, so that an exe can be checked against the transpiler of a VM config.
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default()
+    }
+
+    /// The custom RISC-V instructions this extension transpiles. If it is not empty, the
+    /// extension is only offered the instructions matching one of the returned dispatches, and
+    /// [Transpiler::try_with_extension](crate::transpiler::Transpiler::try_with_extension) rejects
+    /// extensions whose dispatches overlap. If it is empty, the extension is offered every
+    /// instruction, and cannot be registered with `try_with_extension`.
+    fn dispatch(&self) -> Vec<InstructionDispatch> {
+        vec![]
+    }
+
+    /// Whether `instruction`, which this extension transpiled, provides hints to the guest or
+    /// reads them, so that
+    /// [Transpiler::without_hints](crate::transpiler::Transpiler::without_hints) rejects it.
+    fn provides_hints(&self, _instruction: &Instruction<F>) -> bool {
+        false
+    }
+}
+
+/// The custom RISC-V instructions with the given major `opcode` and `funct3`, and a `funct7` in the
+/// range `funct7`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InstructionDispatch {
+    pub opcode: u8,
+    pub funct3: u8,
+    pub funct7: RangeInclusive<u8>,
+}
+
+impl InstructionDispatch {
+    pub fn new(opcode: u8, funct3: u8, funct7: RangeInclusive<u8>) -> Self {
+        Self {
+            opcode,
+            funct3,
+            funct7,
+        }
+    }
+
+    /// The instructions with the given `opcode` and `funct3`, whatever their `funct7`.
+    pub fn all_funct7(opcode: u8, funct3: u8) -> Self {
+        Self::new(opcode, funct3, 0..=0x7f)
+    }
+
+    pub fn matches(&self, instruction: u32) -> bool {
+        let opcode = (instruction & 0x7f) as u8;
+        let funct3 = ((instruction >> 12) & 0b111) as u8;
+        let funct7 = (instruction >> 25) as u8;
+        (opcode, funct3) == (self.opcode, self.funct3) && self.funct7.contains(&funct7)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        (self.opcode, self.funct3) == (other.opcode, other.funct3)
+            && self.funct7.start() <= other.funct7.end()
+            && other.funct7.start() <= self.funct7.end()
+    }
 }
 
 pub struct TranspilerOutput<F> {
     pub instructions: Vec<Option<Instruction<F>>>,
     pub used_u32s: usize,
+    pub out_of_line: Option<OutOfLine<F>>,
+}
+
+/// Instructions replacing a single RISC-V instruction, which cannot be placed in its slot
+/// without shifting the pcs of the following instructions.
+///
+/// The transpiler places `body` after the program, fills the slot of the RISC-V instruction with
+/// a jump to `body`, and ends `body` with a jump back to the instruction after the slot. The jumps
+/// are built by the extension, since the transpiler does not know the opcodes of jumps.
+pub struct OutOfLine<F> {
+    /// Returns the instructions replacing the RISC-V instruction, given the number of bytes from
+    /// its slot to the first of them, which relative jumps in the body must account for.
+    pub body: Box<dyn FnOnce(usize) -> Vec<Instruction<F>>>,
+    /// Returns the instruction jumping forward by the given number of bytes.
+    pub jump_forward: fn(usize) -> Instruction<F>,
+    /// Returns the instructions jumping back by the given number of bytes, counted from the pc
+    /// of the first returned instruction.
+    pub jump_back: fn(usize) -> Vec<Instruction<F>>,
 }
 
 impl<F> TranspilerOutput<F> {
@@ -24,6 +109,7 @@ impl<F> TranspilerOutput<F> {
         Self {
             instructions: vec![Some(instruction)],
             used_u32s: 1,
+            out_of_line: None,
         }
     }
 
@@ -31,6 +117,7 @@ impl<F> TranspilerOutput<F> {
         Self {
             instructions: vec![Some(instruction)],
             used_u32s,
+            out_of_line: None,
         }
     }
 
@@ -38,6 +125,17 @@ impl<F> TranspilerOutput<F> {
         Self {
             instructions: (0..gap_length).map(|_| None).collect(),
             used_u32s,
+            out_of_line: None,
+        }
+    }
+
+    /// Transpiles one RISC-V instruction into the instructions of `out_of_line`, see
+    /// [OutOfLine].
+    pub fn out_of_line(out_of_line: OutOfLine<F>) -> Self {
+        Self {
+            instructions: vec![None],
+            used_u32s: 1,
+            out_of_line: Some(out_of_line),
         }
     }
 }
@@ -5,18 +5,22 @@ use openvm_instructions::{
     exe::VmExe,
     program::{Program, DEFAULT_PC_STEP},
 };
+use openvm_overlay::OverlayTree;
 pub use openvm_platform;
 use openvm_stark_backend::p3_field::PrimeField32;
 use transpiler::{Transpiler, TranspilerError};
 
 use crate::util::elf_memory_image_to_openvm_memory_image;
 
+pub mod compressed;
+pub mod dead_code;
 pub mod elf;
+pub mod metadata;
 pub mod transpiler;
 pub mod util;
 
 mod extension;
-pub use extension::{TranspilerExtension, TranspilerOutput};
+pub use extension::{InstructionDispatch, OutOfLine, TranspilerExtension, TranspilerOutput};
 
 pub trait FromElf {
     type ElfContext;
@@ -27,20 +31,39 @@ pub trait FromElf {
 
 impl<F: PrimeField32> FromElf for VmExe<F> {
     type ElfContext = Transpiler<F>;
+    /// Transpiles `elf`, with a pc step of 2 if it uses compressed instructions, and records the
+    /// [TranspilerMetadata](metadata::TranspilerMetadata) of `transpiler` in the initial memory
+    /// image of the exe, together with its
+    /// [config digest public value](Transpiler::with_config_digest_public_value) if it has one.
+    /// Unreachable instructions are removed if `transpiler` is built
+    /// [with dead code elimination](Transpiler::with_dead_code_elimination).
     fn from_elf(elf: Elf, transpiler: Self::ElfContext) -> Result<Self, TranspilerError> {
-        let instructions = transpiler.transpile(&elf.instructions)?;
-        let program = Program::new_without_debug_infos_with_option(
-            &instructions,
-            DEFAULT_PC_STEP,
-            elf.pc_base,
-        );
-        let init_memory = elf_memory_image_to_openvm_memory_image(elf.memory_image);
+        let (mut instructions, pc_step) = if elf.compressed {
+            (transpiler.transpile_compressed(&elf.instructions)?, 2)
+        } else {
+            (transpiler.transpile(&elf.instructions)?, DEFAULT_PC_STEP)
+        };
+        if transpiler.eliminates_dead_code() {
+            dead_code::eliminate_dead_code(&elf, &mut instructions, pc_step);
+        }
+        let program =
+            Program::new_without_debug_infos_with_option(&instructions, pc_step, elf.pc_base);
+        let mut init_memory = elf_memory_image_to_openvm_memory_image(elf.memory_image);
+        transpiler.metadata().write_to(&mut init_memory);
+        transpiler.write_config_digest_public_value(&mut init_memory);
+        let overlays = elf
+            .overlays
+            .into_iter()
+            .map(|overlay| (OverlayTree::new(&overlay).root(), overlay))
+            .collect();
 
         Ok(VmExe {
             program,
             pc_start: elf.pc_start,
             init_memory,
             fn_bounds: elf.fn_bounds,
+            overlays,
+            source_map: elf.source_map,
         })
     }
 }
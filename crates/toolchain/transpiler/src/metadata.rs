@@ -0,0 +1,130 @@
+use std::{collections::BTreeMap, fmt::Debug};
+
+use openvm_instructions::{
+    exe::{MemoryImage, VmExe},
+    riscv::RV32_REGISTER_AS,
+    LocalOpcode,
+};
+use openvm_stark_backend::p3_field::PrimeField32;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+/// Version of the transpiler, recorded in the metadata of every transpiled [`VmExe`].
+pub const TRANSPILER_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Address space of the transpiler metadata in the initial memory image. The metadata is stored in
+/// the register address space past the 32 registers, which is never accessed by RISC-V programs.
+pub const TRANSPILER_METADATA_AS: u32 = RV32_REGISTER_AS;
+/// Start address of the transpiler metadata in [`TRANSPILER_METADATA_AS`].
+pub const TRANSPILER_METADATA_PTR: u32 = 1 << 16;
+
+/// Map from the global opcodes a [`TranspilerExtension`](crate::TranspilerExtension) transpiles
+/// to, to the names of their [`LocalOpcode`] variants.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct OpcodeMap(pub BTreeMap<usize, String>);
+
+impl OpcodeMap {
+    /// Adds all `opcodes` of one [`LocalOpcode`] class, e.g. `BaseAluOpcode::iter()`.
+    pub fn with_opcodes<T: LocalOpcode + Debug>(
+        mut self,
+        opcodes: impl IntoIterator<Item = T>,
+    ) -> Self {
+        self.0.extend(
+            opcodes
+                .into_iter()
+                .map(|opcode| (opcode.global_opcode().as_usize(), format!("{opcode:?}"))),
+        );
+        self
+    }
+
+    pub fn extend(&mut self, other: OpcodeMap) {
+        self.0.extend(other.0);
+    }
+
+    /// SHA-256 digest of the map, hashing each opcode as a little-endian `u32` followed by the
+    /// length-prefixed name.
+    pub fn digest(&self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        for (opcode, name) in &self.0 {
+            hasher.update((*opcode as u32).to_le_bytes());
+            hasher.update((name.len() as u32).to_le_bytes());
+            hasher.update(name.as_bytes());
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// Transpiler version and opcode map digest with which a [`VmExe`] was transpiled.
+///
+/// The metadata is written to the initial memory image at [`TRANSPILER_METADATA_PTR`], so it is
+/// part of the preimage of the exe commit: the same ELF transpiled by a different transpiler
+/// version has a different commit even if the program is unchanged, and the metadata cannot be
+/// modified without changing the commit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TranspilerMetadata {
+    pub version: String,
+    pub opcode_map_digest: [u8; 32],
+}
+
+impl TranspilerMetadata {
+    /// The metadata is encoded as the opcode map digest, followed by the length of the version as
+    /// a little-endian `u32`, followed by the version, with one byte per memory cell.
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.opcode_map_digest.to_vec();
+        bytes.extend((self.version.len() as u32).to_le_bytes());
+        bytes.extend(self.version.as_bytes());
+        bytes
+    }
+
+    /// Writes the metadata into the initial memory image of an exe.
+    pub fn write_to<F: PrimeField32>(&self, init_memory: &mut MemoryImage<F>) {
+        for (i, byte) in self.to_bytes().into_iter().enumerate() {
+            init_memory.insert(
+                (TRANSPILER_METADATA_AS, TRANSPILER_METADATA_PTR + i as u32),
+                F::from_canonical_u8(byte),
+            );
+        }
+    }
+
+    /// Reads the metadata from the initial memory image of `exe`. Returns `None` if `exe` was not
+    /// transpiled from an ELF, or was transpiled before the metadata was recorded.
+    pub fn read<F: PrimeField32>(exe: &VmExe<F>) -> Option<Self> {
+        let read_bytes = |start: u32, len: u32| -> Option<Vec<u8>> {
+            (start..start + len)
+                .map(|ptr| {
+                    let cell = exe
+                        .init_memory
+                        .get(&(TRANSPILER_METADATA_AS, TRANSPILER_METADATA_PTR + ptr))?;
+                    u8::try_from(cell.as_canonical_u32()).ok()
+                })
+                .collect()
+        };
+        let opcode_map_digest = read_bytes(0, 32)?.try_into().unwrap();
+        let version_len = u32::from_le_bytes(read_bytes(32, 4)?.try_into().unwrap());
+        let version = String::from_utf8(read_bytes(36, version_len)?).ok()?;
+        Some(Self {
+            version,
+            opcode_map_digest,
+        })
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum TranspilerMetadataError {
+    #[error("exe has no transpiler metadata")]
+    Missing,
+    #[error(
+        "exe was transpiled with a different opcode map: expected digest {}, found {}",
+        hex(.expected),
+        hex(.found)
+    )]
+    OpcodeMapMismatch { expected: [u8; 32], found: [u8; 32] },
+    #[error(
+        "exe was transpiled by openvm-transpiler {found}, but the current version is {expected}"
+    )]
+    VersionMismatch { expected: String, found: String },
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
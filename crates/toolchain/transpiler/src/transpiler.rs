@@ -1,15 +1,60 @@
-use std::rc::Rc;
+use std::{borrow::Cow, iter, rc::Rc};
 
-use openvm_instructions::instruction::Instruction;
+use openvm_instructions::{
+    exe::{MemoryImage, VmExe},
+    instruction::Instruction,
+    program::DEFAULT_PC_STEP,
+    riscv::RV32_PUBLIC_VALUES_AS,
+    EXTERNAL_OPCODE_RANGE,
+};
+use openvm_platform::capabilities::{CONFIG_DIGEST_WORDS, PROBE_FUNCT3, PROBE_OPCODE};
 use openvm_stark_backend::p3_field::PrimeField32;
 use thiserror::Error;
 
-use crate::TranspilerExtension;
+use crate::{
+    compressed::instruction_len,
+    metadata::{OpcodeMap, TranspilerMetadata, TranspilerMetadataError, TRANSPILER_VERSION},
+    InstructionDispatch, TranspilerExtension, TranspilerOutput,
+};
+
+/// The major opcodes of the custom RISC-V instructions, custom-0 and custom-1.
+const CUSTOM_OPCODES: [u32; 2] = [0x0b, 0x2b];
+/// The RISC-V `addi x0, x0, 0` instruction.
+const NOP: u32 = 0x13;
+/// The major opcode of the RISC-V `lui` instruction.
+const LUI: u32 = 0x37;
 
 /// Collection of [`TranspilerExtension`]s.
-/// The transpiler can be configured to transpile any ELF in 32-bit chunks.
+/// The transpiler can be configured to transpile any ELF in 32-bit chunks, and ELFs using the
+/// compressed (C) extension with [Self::transpile_compressed].
+///
+/// Extensions defined outside of this repository should be registered with
+/// [Self::try_with_extension], which checks that they claim no instructions or opcodes of the
+/// other extensions nor the probes of the [capabilities](openvm_platform::capabilities) module,
+/// and that they take their opcodes from
+/// [EXTERNAL_OPCODE_RANGE](openvm_instructions::EXTERNAL_OPCODE_RANGE).
 pub struct Transpiler<F> {
-    processors: Vec<Rc<dyn TranspilerExtension<F>>>,
+    processors: Vec<Processor<F>>,
+    dead_code_elimination: bool,
+    /// Whether programs may use hints, see [Self::without_hints].
+    hints_enabled: bool,
+    /// The digest of the VM config, see [Self::with_config_digest].
+    config_digest: Option<[u8; 32]>,
+    /// The first byte of the user public values holding the config digest, see
+    /// [Self::with_config_digest_public_value].
+    config_digest_public_value: Option<u32>,
+}
+
+struct Processor<F> {
+    extension: Rc<dyn TranspilerExtension<F>>,
+    /// The [TranspilerExtension::dispatch] of the extension.
+    dispatch: Vec<InstructionDispatch>,
+}
+
+impl<F> Processor<F> {
+    fn accepts(&self, instruction: u32) -> bool {
+        self.dispatch.is_empty() || self.dispatch.iter().any(|d| d.matches(instruction))
+    }
 }
 
 impl<F: PrimeField32> Default for Transpiler<F> {
@@ -24,52 +69,391 @@ pub enum TranspilerError {
     AmbiguousNextInstruction,
     #[error("couldn't parse the next instruction: {0:032b}")]
     ParseError(u32),
+    #[error("instructions {0:?} are claimed by more than one extension")]
+    DispatchConflict(InstructionDispatch),
+    #[error("opcode {opcode} ({name}) is used by more than one extension")]
+    OpcodeConflict { opcode: usize, name: String },
+    #[error("opcode {opcode} ({name}) of an external extension is not in EXTERNAL_OPCODE_RANGE")]
+    OpcodeOutOfRange { opcode: usize, name: String },
+    #[error(
+        "an external extension must claim its instructions with TranspilerExtension::dispatch"
+    )]
+    MissingDispatch,
+    #[error("the program reads the VM config digest, but the transpiler has none")]
+    MissingConfigDigest,
+    #[error("instruction {0:032b} provides hints, which are disabled")]
+    HintsDisabled(u32),
 }
 
 impl<F: PrimeField32> Transpiler<F> {
     pub fn new() -> Self {
-        Self { processors: vec![] }
+        Self {
+            processors: vec![],
+            dead_code_elimination: false,
+            hints_enabled: true,
+            config_digest: None,
+            config_digest_public_value: None,
+        }
+    }
+
+    pub fn with_processor(mut self, proc: Rc<dyn TranspilerExtension<F>>) -> Self {
+        self.processors.push(Processor {
+            dispatch: proc.dispatch(),
+            extension: proc,
+        });
+        self
+    }
+
+    /// Removes the instructions which cannot be executed from the exes transpiled from ELFs, see
+    /// [dead_code](crate::dead_code) for the code addresses the program is assumed to compute.
+    /// This reduces the size of the program commitment, and so the keygen time, of large guests.
+    pub fn with_dead_code_elimination(mut self) -> Self {
+        self.dead_code_elimination = true;
+        self
+    }
+
+    pub fn eliminates_dead_code(&self) -> bool {
+        self.dead_code_elimination
+    }
+
+    /// Rejects programs with an instruction which [provides
+    /// hints](TranspilerExtension::provides_hints), for VMs whose system config disables hints,
+    /// so that such programs fail to transpile instead of failing during execution.
+    pub fn without_hints(mut self) -> Self {
+        self.hints_enabled = false;
+        self
+    }
+
+    pub fn hints_enabled(&self) -> bool {
+        self.hints_enabled
+    }
+
+    /// Resolves the reads of the VM config digest of the
+    /// [capabilities](openvm_platform::capabilities) module to `digest`, which should identify the
+    /// config of the VM the programs are transpiled for. Transpiling a program which reads the
+    /// digest fails without one.
+    pub fn with_config_digest(mut self, digest: [u8; 32]) -> Self {
+        self.config_digest = Some(digest);
+        self
+    }
+
+    pub fn config_digest(&self) -> Option<&[u8; 32]> {
+        self.config_digest.as_ref()
     }
 
-    pub fn with_processor(self, proc: Rc<dyn TranspilerExtension<F>>) -> Self {
-        let mut procs = self.processors;
-        procs.push(proc);
-        Self { processors: procs }
+    /// Also writes the config digest of [Self::with_config_digest] to the user public values of
+    /// the exes transpiled from ELFs, starting at byte `ptr`, by initializing their memory. The
+    /// digest stays a public value of the proof unless the program overwrites it. Requires
+    /// continuations, in which the user public values are memory.
+    pub fn with_config_digest_public_value(mut self, ptr: u32) -> Self {
+        self.config_digest_public_value = Some(ptr);
+        self
+    }
+
+    /// Writes the config digest to the user public values of the initial memory image of an exe,
+    /// if the transpiler has [a digest](Self::with_config_digest) and
+    /// [a public value for it](Self::with_config_digest_public_value).
+    pub fn write_config_digest_public_value(&self, init_memory: &mut MemoryImage<F>) {
+        let (Some(digest), Some(ptr)) = (self.config_digest, self.config_digest_public_value)
+        else {
+            return;
+        };
+        for (i, byte) in digest.into_iter().enumerate() {
+            init_memory.insert(
+                (RV32_PUBLIC_VALUES_AS, ptr + i as u32),
+                F::from_canonical_u8(byte),
+            );
+        }
     }
 
     pub fn with_extension<T: TranspilerExtension<F> + 'static>(self, ext: T) -> Self {
         self.with_processor(Rc::new(ext))
     }
 
+    /// Adds the processor of an external extension like [Self::with_processor]. Fails unless
+    /// - its [TranspilerExtension::dispatch] is not empty,
+    /// - its dispatch overlaps neither the dispatch of another processor, nor the probes, nor an
+    ///   instruction which a processor without a dispatch transpiles, and
+    /// - the opcodes of its [TranspilerExtension::opcode_map] are in
+    ///   [EXTERNAL_OPCODE_RANGE](openvm_instructions::EXTERNAL_OPCODE_RANGE) and not in the opcode
+    ///   map of another processor.
+    pub fn try_with_processor(
+        self,
+        proc: Rc<dyn TranspilerExtension<F>>,
+    ) -> Result<Self, TranspilerError> {
+        let dispatch = proc.dispatch();
+        if dispatch.is_empty() {
+            return Err(TranspilerError::MissingDispatch);
+        }
+        let probes = InstructionDispatch::all_funct7(PROBE_OPCODE, PROBE_FUNCT3);
+        for dispatch in dispatch {
+            let overlaps = self
+                .processors
+                .iter()
+                .flat_map(|other| &other.dispatch)
+                .chain([&probes])
+                .any(|other| other.overlaps(&dispatch));
+            // The processors without a dispatch are offered the instructions of the dispatch,
+            // with `x0` as every register.
+            let transpiled = dispatch.funct7.clone().any(|funct7| {
+                let instruction =
+                    (funct7 as u32) << 25 | (dispatch.funct3 as u32) << 12 | dispatch.opcode as u32;
+                self.processors
+                    .iter()
+                    .filter(|other| other.dispatch.is_empty())
+                    .any(|other| other.extension.process_custom(&[instruction]).is_some())
+            });
+            if overlaps || transpiled {
+                return Err(TranspilerError::DispatchConflict(dispatch));
+            }
+        }
+        let opcode_map = self.opcode_map();
+        for (opcode, name) in proc.opcode_map().0 {
+            if !EXTERNAL_OPCODE_RANGE.contains(&opcode) {
+                return Err(TranspilerError::OpcodeOutOfRange { opcode, name });
+            }
+            if opcode_map.0.contains_key(&opcode) {
+                return Err(TranspilerError::OpcodeConflict { opcode, name });
+            }
+        }
+        Ok(self.with_processor(proc))
+    }
+
+    /// Adds `ext` like [Self::with_extension], with the checks of [Self::try_with_processor].
+    pub fn try_with_extension<T: TranspilerExtension<F> + 'static>(
+        self,
+        ext: T,
+    ) -> Result<Self, TranspilerError> {
+        self.try_with_processor(Rc::new(ext))
+    }
+
     /// Iterates over a sequence of 32-bit RISC-V instructions `instructions_u32`. The iterator
     /// applies every processor in the [`Transpiler`] to determine if one of them knows how to
     /// transpile the current instruction (and possibly a contiguous section of following
     /// instructions). If so, it advances the iterator by the amount specified by the processor.
     /// The transpiler will panic if two different processors claim to know how to transpile the
-    /// same instruction to avoid ambiguity.
+    /// same instruction to avoid ambiguity. Processors which declare a
+    /// [TranspilerExtension::dispatch] are only applied to the instructions it matches.
+    ///
+    /// The bodies of [OutOfLine](crate::OutOfLine) outputs are appended after the program, in
+    /// the order of their RISC-V instructions.
+    ///
+    /// The probes of the [capabilities](openvm_platform::capabilities) module are resolved
+    /// first. A program which probes may contain custom instructions which no processor
+    /// transpiles, which are left as empty slots that fail execution if reached.
     pub fn transpile(
         &self,
         instructions_u32: &[u32],
     ) -> Result<Vec<Option<Instruction<F>>>, TranspilerError> {
+        self.transpile_slots(instructions_u32, false)
+    }
+
+    /// Like [Self::transpile], for the instructions of a program using the compressed (C)
+    /// extension, as decoded by [Elf::decode](crate::elf::Elf::decode): a 16-bit instruction is
+    /// in the low half of its entry of `instructions_u32`.
+    ///
+    /// The program has a slot per 2 bytes of RISC-V instructions, and must be run with a pc step
+    /// of 2. Since instructions advance the pc by [DEFAULT_PC_STEP], every instruction is followed
+    /// by an empty slot, so a 16-bit instruction only fits its slot if it does not continue to
+    /// the next instruction, and is otherwise transpiled [out of line](crate::OutOfLine).
+    pub fn transpile_compressed(
+        &self,
+        instructions_u32: &[u32],
+    ) -> Result<Vec<Option<Instruction<F>>>, TranspilerError> {
+        self.transpile_slots(instructions_u32, true)
+    }
+
+    fn transpile_slots(
+        &self,
+        instructions_u32: &[u32],
+        compressed: bool,
+    ) -> Result<Vec<Option<Instruction<F>>>, TranspilerError> {
+        // The number of bytes of RISC-V instructions per slot of the program.
+        let slot_size = if compressed {
+            2
+        } else {
+            DEFAULT_PC_STEP as usize
+        };
+        // Follows every instruction with the empty slots it advances the pc over.
+        let spread = |instructions: Vec<Option<Instruction<F>>>| {
+            instructions.into_iter().flat_map(move |instruction| {
+                iter::once(instruction)
+                    .chain((1..DEFAULT_PC_STEP as usize / slot_size).map(|_| None))
+            })
+        };
+
+        let instructions_u32 = self.resolve_probes(instructions_u32)?;
+        let probes = matches!(instructions_u32, Cow::Owned(_));
         let mut instructions = Vec::new();
+        let mut out_of_line = Vec::new();
         let mut ptr = 0;
         while ptr < instructions_u32.len() {
             let mut options = self
                 .processors
                 .iter()
-                .map(|proc| proc.process_custom(&instructions_u32[ptr..]))
-                .filter(|opt| opt.is_some())
+                .filter(|proc| proc.accepts(instructions_u32[ptr]))
+                .filter_map(|proc| {
+                    let output = proc.extension.process_custom(&instructions_u32[ptr..])?;
+                    Some((Some(proc), output))
+                })
                 .collect::<Vec<_>>();
             if options.is_empty() {
-                return Err(TranspilerError::ParseError(instructions_u32[ptr]));
+                if probes && CUSTOM_OPCODES.contains(&(instructions_u32[ptr] & 0x7f)) {
+                    options.push((None, TranspilerOutput::gap(1, 1)));
+                } else {
+                    return Err(TranspilerError::ParseError(instructions_u32[ptr]));
+                }
             }
             if options.len() > 1 {
                 return Err(TranspilerError::AmbiguousNextInstruction);
             }
-            let transpiler_output = options.pop().unwrap().unwrap();
-            instructions.extend(transpiler_output.instructions);
+            let (proc, transpiler_output) = options.pop().unwrap();
+            if self.rejects_hints(proc, transpiler_output.instructions.iter().flatten()) {
+                return Err(TranspilerError::HintsDisabled(instructions_u32[ptr]));
+            }
+            let slot = instructions.len();
+            instructions.extend(spread(transpiler_output.instructions));
+            if compressed {
+                // Keep the slots of the following RISC-V instructions at their addresses.
+                let used_bytes: usize = instructions_u32[ptr..ptr + transpiler_output.used_u32s]
+                    .iter()
+                    .map(|&i| instruction_len(i) as usize)
+                    .sum();
+                let next_slot = slot + used_bytes / slot_size;
+                if instructions.len() > next_slot {
+                    assert!(
+                        instructions[next_slot..].iter().all(Option::is_none),
+                        "transpiled instructions overlap the next RISC-V instruction"
+                    );
+                    instructions.truncate(next_slot);
+                }
+                instructions.resize_with(next_slot, || None);
+            }
+            if let Some(output) = transpiler_output.out_of_line {
+                out_of_line.push((slot, instructions.len(), output, ptr, proc));
+            }
             ptr += transpiler_output.used_u32s;
         }
+        for (slot, next_slot, output, ptr, proc) in out_of_line {
+            let start = instructions.len();
+            let distance = (start - slot) * slot_size;
+            instructions[slot] = Some((output.jump_forward)(distance));
+            let body = (output.body)(distance);
+            if self.rejects_hints(proc, body.iter()) {
+                return Err(TranspilerError::HintsDisabled(instructions_u32[ptr]));
+            }
+            instructions.extend(spread(body.into_iter().map(Some).collect()));
+            let end = instructions.len();
+            instructions.extend(spread(
+                (output.jump_back)((end - next_slot) * slot_size)
+                    .into_iter()
+                    .map(Some)
+                    .collect(),
+            ));
+        }
         Ok(instructions)
     }
+
+    /// Whether hints are [disabled](Self::without_hints) and one of the `instructions` which `proc`
+    /// transpiled provides hints.
+    fn rejects_hints<'a>(
+        &self,
+        proc: Option<&Processor<F>>,
+        mut instructions: impl Iterator<Item = &'a Instruction<F>>,
+    ) -> bool
+    where
+        F: 'a,
+    {
+        !self.hints_enabled
+            && proc.is_some_and(|proc| instructions.any(|i| proc.extension.provides_hints(i)))
+    }
+
+    /// Replaces every probe of the [capabilities](openvm_platform::capabilities) module in
+    /// `instructions_u32` with `addi rd, x0, 1` if a processor transpiles the probed instruction
+    /// and `addi rd, x0, 0` otherwise, and the probed instruction with a no-op. A probe querying a
+    /// word of the config digest and its query are replaced with `lui` and `addi` instructions
+    /// setting `rd` to the word. Returns the instructions unchanged if there are no probes.
+    ///
+    /// `instructions_u32` has an entry per instruction, also for
+    /// [compressed](Self::transpile_compressed) programs, so probes at addresses which are only
+    /// 2-byte aligned are resolved as well.
+    fn resolve_probes<'a>(
+        &self,
+        instructions_u32: &'a [u32],
+    ) -> Result<Cow<'a, [u32]>, TranspilerError> {
+        let is_probe = |instruction: u32| {
+            instruction & 0x7f == PROBE_OPCODE as u32
+                && (instruction >> 12) & 0b111 == PROBE_FUNCT3 as u32
+        };
+        let mut instructions_u32 = Cow::Borrowed(instructions_u32);
+        let mut ptr = 0;
+        while ptr + 1 < instructions_u32.len() {
+            if !is_probe(instructions_u32[ptr]) {
+                ptr += 1;
+                continue;
+            }
+            let probed = instructions_u32[ptr + 1];
+            let rd = instructions_u32[ptr] & (0x1f << 7);
+            let resolved = if is_probe(probed) {
+                let index = probed >> 20;
+                if index >= CONFIG_DIGEST_WORDS {
+                    return Err(TranspilerError::ParseError(probed));
+                }
+                let digest = self
+                    .config_digest
+                    .ok_or(TranspilerError::MissingConfigDigest)?;
+                let start = 4 * index as usize;
+                let word = u32::from_le_bytes(digest[start..start + 4].try_into().unwrap());
+                // `addi` sign-extends its immediate, which `lui` compensates for.
+                let low = ((word << 20) as i32 >> 20) as u32;
+                let high = word.wrapping_sub(low) & !0xfff;
+                [high | rd | LUI, low << 20 | rd << 8 | rd | NOP]
+            } else {
+                let supported = self.processors.iter().any(|proc| {
+                    proc.accepts(probed) && proc.extension.process_custom(&[probed]).is_some()
+                });
+                [(supported as u32) << 20 | rd | NOP, NOP]
+            };
+            instructions_u32.to_mut()[ptr..ptr + 2].copy_from_slice(&resolved);
+            ptr += 2;
+        }
+        Ok(instructions_u32)
+    }
+
+    /// The opcodes of all processors of the transpiler.
+    pub fn opcode_map(&self) -> OpcodeMap {
+        let mut opcode_map = OpcodeMap::default();
+        for proc in &self.processors {
+            opcode_map.extend(proc.extension.opcode_map());
+        }
+        opcode_map
+    }
+
+    /// The metadata recorded in exes transpiled by this transpiler.
+    pub fn metadata(&self) -> TranspilerMetadata {
+        TranspilerMetadata {
+            version: TRANSPILER_VERSION.to_string(),
+            opcode_map_digest: self.opcode_map().digest(),
+        }
+    }
+
+    /// Checks that `exe` was transpiled by this version of the transpiler with the same opcode map,
+    /// i.e. that transpiling its ELF again would result in the same exe commit.
+    pub fn check_metadata(&self, exe: &VmExe<F>) -> Result<(), TranspilerMetadataError> {
+        let found = TranspilerMetadata::read(exe).ok_or(TranspilerMetadataError::Missing)?;
+        let expected = self.metadata();
+        if found.opcode_map_digest != expected.opcode_map_digest {
+            return Err(TranspilerMetadataError::OpcodeMapMismatch {
+                expected: expected.opcode_map_digest,
+                found: found.opcode_map_digest,
+            });
+        }
+        if found.version != expected.version {
+            return Err(TranspilerMetadataError::VersionMismatch {
+                expected: expected.version,
+                found: found.version,
+            });
+        }
+        Ok(())
+    }
 }
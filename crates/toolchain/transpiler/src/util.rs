@@ -5,7 +5,7 @@ use openvm_instructions::{
     instruction::Instruction,
     riscv::{RV32_MEMORY_AS, RV32_REGISTER_NUM_LIMBS},
     utils::isize_to_field,
-    LocalOpcode, SystemOpcode, VmOpcode,
+    LocalOpcode, PhantomDiscriminant, SystemOpcode, VmOpcode,
 };
 use openvm_stark_backend::p3_field::PrimeField32;
 use rrs_lib::instruction_formats::{BType, IType, ITypeShamt, JType, RType, SType, UType};
@@ -14,6 +14,15 @@ fn i12_to_u24(imm: i32) -> u32 {
     (imm as u32) & 0xffffff
 }
 
+/// The discriminant of `instruction` if it is a phantom instruction, see
+/// [Instruction::phantom].
+pub fn phantom_discriminant<F: PrimeField32>(
+    instruction: &Instruction<F>,
+) -> Option<PhantomDiscriminant> {
+    (instruction.opcode == SystemOpcode::PHANTOM.global_opcode())
+        .then(|| PhantomDiscriminant(instruction.c.as_canonical_u32() as u16))
+}
+
 /// Create a new [`Instruction`] from an R-type instruction.
 pub fn from_r_type<F: PrimeField32>(
     opcode: usize,
@@ -0,0 +1,250 @@
+//! Randomized constraint tests for the AIR of a chip.
+//!
+//! Given a function generating a random valid trace of a chip, [ConstraintTests] checks that:
+//! - every generated trace passes verification, and
+//! - every single-cell mutation of a used row of the trace of the chip under test fails
+//!   verification, either by a constraint or by an unbalanced bus.
+//!
+//! This gives extension authors baseline soundness testing of their AIRs, on top of the targeted
+//! negative tests of specific attacks. A mutation which is accepted is reported with its case, row
+//! and column, and is either a missing constraint or a column which is unconstrained on purpose and
+//! should be ignored with [ConstraintTests::ignore_columns].
+//!
+//! ```ignore
+//! ConstraintTests::new(|rng| {
+//!     let mut tester = VmChipTestBuilder::default();
+//!     let mut chip = ...;
+//!     // execute random instructions on `chip`
+//!     ConstraintTestCase::new(tester.build(), chip)
+//!         .load(bitwise_chip)
+//!         .finalize()
+//! })
+//! .run();
+//! ```
+
+use std::collections::BTreeSet;
+
+use openvm_circuit::arch::testing::VmChipTester;
+use openvm_stark_backend::{
+    p3_field::{FieldAlgebra, PrimeField32},
+    p3_matrix::Matrix,
+    utils::disable_debug_builder,
+    Chip,
+};
+use openvm_stark_sdk::{config::baby_bear_blake3::BabyBearBlake3Config, p3_baby_bear::BabyBear};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+type SC = BabyBearBlake3Config;
+type F = BabyBear;
+
+/// A random valid trace of the chip under test, together with the chips balancing its buses.
+pub struct ConstraintTestCase {
+    tester: VmChipTester<SC>,
+    /// Index of the chip under test in `tester.air_proof_inputs`.
+    air_index: usize,
+    /// Number of rows of the trace of the chip under test holding records. The remaining rows are
+    /// padding and are not mutated.
+    num_rows: usize,
+}
+
+impl ConstraintTestCase {
+    /// Loads `chip` as the chip under test into `tester`, which is returned by
+    /// [VmChipTestBuilder::build](openvm_circuit::arch::testing::VmChipTestBuilder::build).
+    ///
+    /// # Panics
+    /// If `chip` has no records.
+    pub fn new<C: Chip<SC>>(tester: VmChipTester<SC>, chip: C) -> Self {
+        let num_rows = chip.current_trace_height();
+        assert!(
+            num_rows > 0,
+            "chip under test {} has no records",
+            chip.air_name()
+        );
+        let air_index = tester.air_proof_inputs.len();
+        Self {
+            tester: tester.load(chip),
+            air_index,
+            num_rows,
+        }
+    }
+
+    /// Loads another chip, such as a lookup chip the chip under test sends to.
+    pub fn load<C: Chip<SC>>(mut self, chip: C) -> Self {
+        self.tester = self.tester.load(chip);
+        self
+    }
+
+    /// Loads the memory chips. Must be called after all other chips are loaded.
+    pub fn finalize(mut self) -> Self {
+        self.tester = self.tester.finalize();
+        self
+    }
+
+    fn width(&self) -> usize {
+        self.tester.air_proof_inputs[self.air_index]
+            .1
+            .raw
+            .common_main
+            .as_ref()
+            .expect("chip under test has no common main trace")
+            .width()
+    }
+
+    /// Returns a tester whose trace of the chip under test has `value` at `(row, col)`.
+    fn mutate(&self, row: usize, col: usize, value: F) -> VmChipTester<SC> {
+        let mut air_proof_inputs = self.tester.air_proof_inputs.clone();
+        let trace = air_proof_inputs[self.air_index]
+            .1
+            .raw
+            .common_main
+            .as_mut()
+            .unwrap();
+        let width = trace.width();
+        trace.values[row * width + col] = value;
+        VmChipTester {
+            memory: None,
+            air_proof_inputs,
+        }
+    }
+}
+
+/// A single-cell mutation of a valid trace which passed verification.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AcceptedMutation {
+    pub case: usize,
+    pub row: usize,
+    pub col: usize,
+    pub original: F,
+    pub mutated: F,
+}
+
+/// Randomized positive and negative constraint tests, see the [crate docs](crate).
+pub struct ConstraintTests<G> {
+    generate: G,
+    seed: u64,
+    num_cases: usize,
+    mutations_per_case: usize,
+    ignored_columns: BTreeSet<usize>,
+}
+
+impl<G: Fn(&mut StdRng) -> ConstraintTestCase> ConstraintTests<G> {
+    /// `generate` returns a random valid trace of the chip under test, finalized by
+    /// [ConstraintTestCase::finalize].
+    pub fn new(generate: G) -> Self {
+        Self {
+            generate,
+            seed: 0,
+            num_cases: 2,
+            mutations_per_case: 16,
+            ignored_columns: BTreeSet::new(),
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    pub fn with_num_cases(mut self, num_cases: usize) -> Self {
+        self.num_cases = num_cases;
+        self
+    }
+
+    pub fn with_mutations_per_case(mut self, mutations_per_case: usize) -> Self {
+        self.mutations_per_case = mutations_per_case;
+        self
+    }
+
+    /// Columns of the chip under test which are never mutated, because they are unconstrained on
+    /// purpose.
+    pub fn ignore_columns(mut self, cols: impl IntoIterator<Item = usize>) -> Self {
+        self.ignored_columns.extend(cols);
+        self
+    }
+
+    /// Runs the tests and returns the accepted mutations.
+    ///
+    /// # Panics
+    /// If a generated trace fails verification.
+    pub fn find_accepted_mutations(&self) -> Vec<AcceptedMutation> {
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let cases: Vec<_> = (0..self.num_cases)
+            .map(|case| {
+                let test_case = (self.generate)(&mut rng);
+                if let Err(err) = test_case.tester.simple_test() {
+                    panic!("valid trace of case {case} failed verification: {err:?}");
+                }
+                test_case
+            })
+            .collect();
+
+        disable_debug_builder();
+        let mut accepted = vec![];
+        for (case, test_case) in cases.iter().enumerate() {
+            let cols: Vec<_> = (0..test_case.width())
+                .filter(|col| !self.ignored_columns.contains(col))
+                .collect();
+            assert!(!cols.is_empty(), "all columns are ignored");
+            for _ in 0..self.mutations_per_case {
+                let row = rng.gen_range(0..test_case.num_rows);
+                let col = cols[rng.gen_range(0..cols.len())];
+                let original = test_case.tester.air_proof_inputs[test_case.air_index]
+                    .1
+                    .raw
+                    .common_main
+                    .as_ref()
+                    .unwrap()
+                    .get(row, col);
+                let mutated = mutate_value(original, &mut rng);
+                if test_case.mutate(row, col, mutated).simple_test().is_ok() {
+                    accepted.push(AcceptedMutation {
+                        case,
+                        row,
+                        col,
+                        original,
+                        mutated,
+                    });
+                }
+            }
+        }
+        accepted
+    }
+
+    /// Runs the tests, panicking if a generated trace fails verification or if a mutation of it
+    /// passes verification.
+    pub fn run(&self) {
+        let accepted = self.find_accepted_mutations();
+        assert!(
+            accepted.is_empty(),
+            "{} mutations passed verification:\n{}",
+            accepted.len(),
+            accepted
+                .iter()
+                .map(|m| format!(
+                    "  case {}, row {}, col {}: {} -> {}",
+                    m.case, m.row, m.col, m.original, m.mutated
+                ))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+}
+
+/// Returns a value different from `original`: half of the time off by one, which is the mutation
+/// most likely to slip through range checks, and otherwise uniformly random.
+fn mutate_value(original: F, rng: &mut StdRng) -> F {
+    if rng.gen_bool(0.5) {
+        if rng.gen_bool(0.5) {
+            original + F::ONE
+        } else {
+            original - F::ONE
+        }
+    } else {
+        loop {
+            let value = F::from_canonical_u32(rng.gen_range(0..F::ORDER_U32));
+            if value != original {
+                return value;
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::sync::{Arc, Mutex};
+
+use eyre::bail;
+use serde::{Deserialize, Serialize};
+
+/// A message exchanged through a channel between the guest and the host.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ChannelMessage {
+    /// Sent by the guest to the host.
+    Sent { channel: u32, data: Vec<u8> },
+    /// Received by the guest from the host.
+    Received { channel: u32, data: Vec<u8> },
+}
+
+/// The messages exchanged through the channels of an execution, in order.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChannelTranscript {
+    pub messages: Vec<ChannelMessage>,
+}
+
+impl ChannelTranscript {
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+}
+
+/// The host end of the channels of an interactive execution, see [HostChannels::interactive].
+pub trait ChannelHandler: Send {
+    /// Called with the `data` the guest sends through `channel`.
+    fn send(&mut self, channel: u32, data: &[u8]);
+    /// Returns the data the guest receives from `channel`.
+    fn recv(&mut self, channel: u32) -> Vec<u8>;
+}
+
+/// The host end of the channels the guest opens with `openvm::io::channel`.
+///
+/// An interactive execution passes the messages to a [ChannelHandler], e.g. to debug the guest
+/// from a REPL or to fetch its witness incrementally, and records them in a transcript. Any other
+/// execution replays a transcript: the guest receives the recorded messages, and execution fails
+/// if the guest sends anything else than what was recorded. Proving replays the transcript of an
+/// interactive execution, so that the proof is deterministic.
+#[derive(Clone, Default)]
+pub struct HostChannels {
+    handler: Option<Arc<Mutex<dyn ChannelHandler>>>,
+    transcript: ChannelTranscript,
+    /// The number of messages of the transcript which were replayed.
+    replayed: usize,
+}
+
+impl HostChannels {
+    /// Channels which pass the messages to `handler` and record them.
+    pub fn interactive(handler: impl ChannelHandler + 'static) -> Self {
+        Self {
+            handler: Some(Arc::new(Mutex::new(handler))),
+            ..Default::default()
+        }
+    }
+
+    /// Channels which replay `transcript`, starting from the message with index `replayed`.
+    pub fn replay(transcript: ChannelTranscript, replayed: usize) -> Self {
+        Self {
+            handler: None,
+            transcript,
+            replayed,
+        }
+    }
+
+    pub fn is_interactive(&self) -> bool {
+        self.handler.is_some()
+    }
+
+    /// The messages recorded by interactive channels, or replayed otherwise.
+    pub fn transcript(&self) -> &ChannelTranscript {
+        &self.transcript
+    }
+
+    pub fn into_transcript(self) -> ChannelTranscript {
+        self.transcript
+    }
+
+    /// The number of messages of the transcript which were replayed.
+    pub fn replayed(&self) -> usize {
+        self.replayed
+    }
+
+    /// The guest sends `data` through `channel`.
+    pub fn send(&mut self, channel: u32, data: Vec<u8>) -> eyre::Result<()> {
+        if let Some(handler) = &self.handler {
+            handler.lock().unwrap().send(channel, &data);
+            self.transcript
+                .messages
+                .push(ChannelMessage::Sent { channel, data });
+            return Ok(());
+        }
+        let message = ChannelMessage::Sent { channel, data };
+        match self.transcript.messages.get(self.replayed) {
+            Some(recorded) if *recorded == message => {
+                self.replayed += 1;
+                Ok(())
+            }
+            _ => bail!(
+                "the guest sent a message through channel {channel} which is not message {} of \
+                 the channel transcript",
+                self.replayed
+            ),
+        }
+    }
+
+    /// The guest receives the next message of `channel`.
+    pub fn recv(&mut self, channel: u32) -> eyre::Result<Vec<u8>> {
+        if let Some(handler) = &self.handler {
+            let data = handler.lock().unwrap().recv(channel);
+            self.transcript.messages.push(ChannelMessage::Received {
+                channel,
+                data: data.clone(),
+            });
+            return Ok(data);
+        }
+        match self.transcript.messages.get(self.replayed) {
+            Some(ChannelMessage::Received {
+                channel: recorded,
+                data,
+            }) if *recorded == channel => {
+                self.replayed += 1;
+                Ok(data.clone())
+            }
+            _ => bail!(
+                "the guest receives from channel {channel}, but message {} of the channel \
+                 transcript is not received from it",
+                self.replayed
+            ),
+        }
+    }
+}
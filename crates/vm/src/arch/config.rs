@@ -7,9 +7,9 @@ use openvm_stark_backend::{p3_field::PrimeField32, ChipUsageGetter};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::{
-    segment::DefaultSegmentationStrategy, AnyEnum, InstructionExecutor, SegmentationStrategy,
-    SystemComplex, SystemExecutor, SystemPeriphery, VmChipComplex, VmInventoryError,
-    PUBLIC_VALUES_AIR_ID,
+    segment::{AdaptiveSegmentationStrategy, DefaultSegmentationStrategy},
+    AnyEnum, ExtensionVersions, InstructionExecutor, SegmentationStrategy, SystemComplex,
+    SystemExecutor, SystemPeriphery, VmChipComplex, VmInventoryError, PUBLIC_VALUES_AIR_ID,
 };
 use crate::system::memory::BOUNDARY_AIR_OFFSET;
 
@@ -17,6 +17,8 @@ use crate::system::memory::BOUNDARY_AIR_OFFSET;
 // allows log_blowup = 1
 const DEFAULT_POSEIDON2_MAX_CONSTRAINT_DEGREE: usize = 3;
 pub const DEFAULT_MAX_NUM_PUBLIC_VALUES: usize = 32;
+/// Default maximum number of instructions in a program, i.e. 16 MiB of RISC-V code.
+pub const DEFAULT_MAX_PROGRAM_LEN: usize = 1 << 22;
 /// Width of Poseidon2 VM uses.
 pub const POSEIDON2_WIDTH: usize = 16;
 /// Returns a Poseidon2 config for the VM.
@@ -37,6 +39,12 @@ pub trait VmConfig<F: PrimeField32>:
     fn create_chip_complex(
         &self,
     ) -> Result<VmChipComplex<F, Self::Executor, Self::Periphery>, VmInventoryError>;
+
+    /// The semantic versions of the extensions of the VM, see
+    /// [VmExtension::version](super::VmExtension::version).
+    fn extension_versions(&self) -> Result<ExtensionVersions, VmInventoryError> {
+        Ok(self.create_chip_complex()?.extension_versions().clone())
+    }
 }
 
 pub const OPENVM_DEFAULT_INIT_FILE_BASENAME: &str = "openvm_init";
@@ -86,6 +94,25 @@ pub struct MemoryConfig {
     pub max_access_adapter_n: usize,
     /// An expected upper bound on the number of memory accesses.
     pub access_capacity: usize,
+    /// Where the executor stores the pages of memory touched by the guest. Only used in
+    /// execution.
+    #[serde(default)]
+    #[new(default)]
+    pub backing: MemoryBacking,
+}
+
+/// Storage of the pages of memory touched by the guest.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryBacking {
+    /// Pages are allocated on the heap.
+    #[default]
+    Heap,
+    /// Pages are mapped from sparse temporary files in [std::env::temp_dir], so that the
+    /// operating system can write pages back to disk instead of keeping all of them in RAM. Meant
+    /// for guests which touch several GB of memory, at the cost of slower execution once the
+    /// touched pages exceed the RAM of the host.
+    Disk,
 }
 
 impl Default for MemoryConfig {
@@ -116,9 +143,28 @@ pub struct SystemConfig {
     /// verifier cannot read public values directly, but they can decommit the public values
     /// from the memory merkle root.
     pub num_public_values: usize,
+    /// The maximum number of instructions in a program. Programs are checked against it when
+    /// they are built, so that a program too large to prove is reported early.
+    #[serde(default = "default_max_program_len")]
+    pub max_program_len: usize,
     /// Whether to collect detailed profiling metrics.
     /// **Warning**: this slows down the runtime.
     pub profiling: bool,
+    /// Whether reads and writes of the stack guard region of RV32 guests, which is below the stack
+    /// of programs built with the OpenVM toolchain, fail with a stack overflow error. Applies to
+    /// every instruction accessing RV32 memory, and is only checked during execution. Should be
+    /// disabled for programs with a different memory layout.
+    #[serde(default = "default_stack_guard")]
+    pub stack_guard: bool,
+    /// Whether guests may receive hints, including their input stream. When disabled, every
+    /// phantom sub-instruction which provides hints fails with [ExecutionError::HintsDisabled], so
+    /// that the execution only depends on the program and its initial memory. Transpilers built
+    /// with `Transpiler::without_hints` reject programs which provide hints, so that they fail
+    /// before execution.
+    ///
+    /// [ExecutionError::HintsDisabled]: super::ExecutionError::HintsDisabled
+    #[serde(default = "default_hints_enabled")]
+    pub hints_enabled: bool,
     /// Segmentation strategy
     /// This field is skipped in serde as it's only used in execution and
     /// not needed after any serialize/deserialize.
@@ -126,6 +172,18 @@ pub struct SystemConfig {
     pub segmentation_strategy: Arc<dyn SegmentationStrategy>,
 }
 
+fn default_max_program_len() -> usize {
+    DEFAULT_MAX_PROGRAM_LEN
+}
+
+fn default_stack_guard() -> bool {
+    true
+}
+
+fn default_hints_enabled() -> bool {
+    true
+}
+
 pub fn get_default_segmentation_strategy() -> Arc<DefaultSegmentationStrategy> {
     Arc::new(DefaultSegmentationStrategy::default())
 }
@@ -152,8 +210,11 @@ impl SystemConfig {
             continuation_enabled: false,
             memory_config,
             num_public_values,
+            max_program_len: DEFAULT_MAX_PROGRAM_LEN,
             segmentation_strategy,
             profiling: false,
+            stack_guard: true,
+            hints_enabled: true,
         }
     }
 
@@ -177,6 +238,11 @@ impl SystemConfig {
         self
     }
 
+    pub fn with_max_program_len(mut self, max_program_len: usize) -> Self {
+        self.max_program_len = max_program_len;
+        self
+    }
+
     pub fn with_max_segment_len(mut self, max_segment_len: usize) -> Self {
         self.segmentation_strategy = Arc::new(
             DefaultSegmentationStrategy::new_with_max_segment_len(max_segment_len),
@@ -184,6 +250,18 @@ impl SystemConfig {
         self
     }
 
+    /// Segments with [AdaptiveSegmentationStrategy], so that the padded traces of the chips
+    /// dominating the segments are filled.
+    pub fn with_adaptive_segmentation(mut self) -> Self {
+        self.segmentation_strategy = Arc::new(AdaptiveSegmentationStrategy::default());
+        self
+    }
+
+    pub fn with_memory_backing(mut self, backing: MemoryBacking) -> Self {
+        self.memory_config.backing = backing;
+        self
+    }
+
     pub fn set_segmentation_strategy(&mut self, strategy: Arc<dyn SegmentationStrategy>) {
         self.segmentation_strategy = strategy;
     }
@@ -198,6 +276,26 @@ impl SystemConfig {
         self
     }
 
+    pub fn with_stack_guard(mut self) -> Self {
+        self.stack_guard = true;
+        self
+    }
+
+    pub fn without_stack_guard(mut self) -> Self {
+        self.stack_guard = false;
+        self
+    }
+
+    pub fn with_hints(mut self) -> Self {
+        self.hints_enabled = true;
+        self
+    }
+
+    pub fn without_hints(mut self) -> Self {
+        self.hints_enabled = false;
+        self
+    }
+
     pub fn has_public_values_chip(&self) -> bool {
         !self.continuation_enabled && self.num_public_values > 0
     }
@@ -62,10 +62,54 @@ pub enum ExecutionError {
         discriminant: PhantomDiscriminant,
         inner: eyre::Error,
     },
+    #[error("at pc {pc}, phantom sub-instruction {} provides hints, which are disabled", .discriminant.0)]
+    HintsDisabled {
+        pc: u32,
+        discriminant: PhantomDiscriminant,
+    },
+    #[error(
+        "at pc {pc}, stack overflow: access to address {address:#x} in the stack guard region"
+    )]
+    StackOverflow { pc: u32, address: u32 },
+    #[error("at pc {pc}, program paused but continuations are not enabled")]
+    PauseWithoutContinuations { pc: u32 },
+    #[error("at pc {pc}, opcode {opcode} got invalid operands: {reason}")]
+    InvalidOperands {
+        pc: u32,
+        opcode: VmOpcode,
+        reason: String,
+    },
+    #[error("at pc {pc}, program exceeded the limit of {max_instructions} instructions")]
+    InstructionLimitExceeded { pc: u32, max_instructions: u64 },
     #[error("program must terminate")]
     DidNotTerminate,
     #[error("program exit code {0}")]
     FailedWithExitCode(u32),
+    #[error("program panicked with exit code {exit_code}: {message}")]
+    Panicked { exit_code: u32, message: String },
+}
+
+impl ExecutionError {
+    /// Returns the pc at which execution failed, if the error is tied to an instruction.
+    pub fn pc(&self) -> Option<u32> {
+        match self {
+            Self::Fail { pc }
+            | Self::PcNotFound { pc, .. }
+            | Self::PcOutOfBounds { pc, .. }
+            | Self::DisabledOperation { pc, .. }
+            | Self::HintOutOfBounds { pc }
+            | Self::PublicValueIndexOutOfBounds { pc, .. }
+            | Self::PublicValueNotEqual { pc, .. }
+            | Self::PhantomNotFound { pc, .. }
+            | Self::Phantom { pc, .. }
+            | Self::HintsDisabled { pc, .. }
+            | Self::StackOverflow { pc, .. }
+            | Self::PauseWithoutContinuations { pc }
+            | Self::InvalidOperands { pc, .. }
+            | Self::InstructionLimitExceeded { pc, .. } => Some(*pc),
+            Self::DidNotTerminate | Self::FailedWithExitCode(_) | Self::Panicked { .. } => None,
+        }
+    }
 }
 
 pub trait InstructionExecutor<F> {
@@ -332,4 +376,11 @@ pub trait PhantomSubExecutor<F>: Send {
         b: F,
         c_upper: u16,
     ) -> eyre::Result<()>;
+
+    /// Whether the sub-instruction provides hints to the guest, so that it is rejected when
+    /// hints are disabled in the [SystemConfig](super::SystemConfig). Only sub-instructions which
+    /// leave the streams the guest reads from untouched, e.g. to print, should return `false`.
+    fn provides_hints(&self) -> bool {
+        true
+    }
 }
@@ -1,6 +1,7 @@
 use std::{
     any::{Any, TypeId},
     cell::RefCell,
+    collections::BTreeMap,
     iter::once,
     sync::{Arc, Mutex},
 };
@@ -19,6 +20,7 @@ use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
 use openvm_instructions::{
     program::Program, LocalOpcode, PhantomDiscriminant, PublishOpcode, SystemOpcode, VmOpcode,
 };
+use openvm_platform::memory::{GUEST_MIN_MEM, STACK_BOTTOM};
 use openvm_stark_backend::{
     config::{Domain, StarkGenericConfig},
     interaction::{BusIndex, PermutationCheckBus},
@@ -53,6 +55,13 @@ use crate::system::{
     public_values::{core::PublicValuesCoreChip, PublicValuesChip},
 };
 
+/// The name under which the executors of the system are grouped, see
+/// [VmChipComplex::extension_name].
+pub const SYSTEM_EXTENSION_NAME: &str = "System";
+
+/// Semantic versions of the extensions of a VM by extension name, see [VmExtension::version].
+pub type ExtensionVersions = BTreeMap<String, u32>;
+
 /// Global AIR ID in the VM circuit verifying key.
 pub const PROGRAM_AIR_ID: usize = 0;
 /// ProgramAir is the first AIR so its cached trace should be the first main trace.
@@ -82,6 +91,16 @@ pub trait VmExtension<F: PrimeField32> {
     /// `StarkGenericConfig` generic parameter.
     type Periphery: AnyEnum;
 
+    /// Name and semantic version of the extension, which are recorded in the VM commit and
+    /// checked against the versions a guest program was built against. The version must be
+    /// bumped whenever the behavior of an instruction of the extension changes, so that programs
+    /// expecting the old behavior are rejected instead of silently computing different results.
+    ///
+    /// Extensions without a version are not recorded.
+    fn version(&self) -> Option<(&'static str, u32)> {
+        None
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -92,6 +111,10 @@ impl<F: PrimeField32, E: VmExtension<F>> VmExtension<F> for Option<E> {
     type Executor = E::Executor;
     type Periphery = E::Periphery;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        self.as_ref().and_then(E::version)
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -235,6 +258,12 @@ pub enum VmInventoryError {
     PhantomSubExecutorExists { discriminant: PhantomDiscriminant },
     #[error("Chip {name} not found")]
     ChipNotFound { name: String },
+    #[error("Extension {name} has both version {version} and {other_version}")]
+    ExtensionVersionConflict {
+        name: &'static str,
+        version: u32,
+        other_version: u32,
+    },
 }
 
 impl<E, P> Default for VmInventory<E, P> {
@@ -326,6 +355,12 @@ impl<E, P> VmInventory<E, P> {
         self.executors.get(*id)
     }
 
+    /// Returns the ID of the executor which handles `opcode`, i.e., its index in
+    /// [Self::executors].
+    pub fn get_executor_id(&self, opcode: VmOpcode) -> Option<ExecutorId> {
+        self.instruction_lookup.get(&opcode).copied()
+    }
+
     pub fn get_mut_executor(&mut self, opcode: &VmOpcode) -> Option<&mut E> {
         let id = self.instruction_lookup.get(opcode)?;
         self.executors.get_mut(*id)
@@ -436,12 +471,18 @@ pub struct VmChipComplex<F: PrimeField32, E, P> {
     /// - PublicValuesChip if continuations disabled
     /// - Poseidon2Chip if continuations enabled
     pub inventory: VmInventory<E, P>,
+    /// Names of the extensions added to the inventory, each with the ID of its first executor.
+    /// Executors before the first extension belong to the system.
+    extension_names: Vec<(ExecutorId, &'static str)>,
+    /// Versions of the extensions added to the inventory which have one.
+    #[getset(get = "pub")]
+    extension_versions: ExtensionVersions,
     overridden_inventory_heights: Option<VmInventoryTraceHeights>,
 
     /// Absolute maximum value a trace height can be and still be provable.
     max_trace_height: usize,
 
-    streams: Arc<Mutex<Streams<F>>>,
+    pub(crate) streams: Arc<Mutex<Streams<F>>>,
     bus_idx_mgr: BusIndexManager,
 }
 
@@ -541,7 +582,7 @@ impl<F: PrimeField32> SystemComplex<F> {
             VariableRangeCheckerBus::new(bus_idx_mgr.new_bus_idx(), config.memory_config.decomp);
 
         let range_checker = SharedVariableRangeCheckerChip::new(range_bus);
-        let memory_controller = if config.continuation_enabled {
+        let mut memory_controller = if config.continuation_enabled {
             MemoryController::with_persistent_memory(
                 memory_bus,
                 config.memory_config,
@@ -556,6 +597,9 @@ impl<F: PrimeField32> SystemComplex<F> {
                 range_checker.clone(),
             )
         };
+        if config.stack_guard {
+            memory_controller.set_stack_guard(GUEST_MIN_MEM as u32..STACK_BOTTOM);
+        }
         let memory_bridge = memory_controller.memory_bridge();
         let offline_memory = memory_controller.offline_memory();
         let program_chip = ProgramChip::new(program_bus);
@@ -605,6 +649,7 @@ impl<F: PrimeField32> SystemComplex<F> {
         let mut phantom_chip =
             PhantomChip::new(execution_bus, program_bus, SystemOpcode::CLASS_OFFSET);
         phantom_chip.set_streams(streams.clone());
+        phantom_chip.set_hints_enabled(config.hints_enabled);
         inventory
             .add_executor(RefCell::new(phantom_chip), [phantom_opcode])
             .unwrap();
@@ -630,6 +675,8 @@ impl<F: PrimeField32> SystemComplex<F> {
             config,
             base,
             inventory,
+            extension_names: vec![],
+            extension_versions: ExtensionVersions::new(),
             bus_idx_mgr,
             streams,
             overridden_inventory_heights: None,
@@ -678,11 +725,27 @@ impl<F: PrimeField32, E, P> VmChipComplex<F, E, P> {
         Ext::Executor: Into<E3>,
         Ext::Periphery: Into<P3>,
     {
+        if let Some((name, version)) = config.version() {
+            match self.extension_versions.insert(name.to_string(), version) {
+                Some(other_version) if other_version != version => {
+                    return Err(VmInventoryError::ExtensionVersionConflict {
+                        name,
+                        version,
+                        other_version,
+                    });
+                }
+                _ => {}
+            }
+        }
         let mut builder = self.inventory_builder();
         let inventory_ext = config.build(&mut builder)?;
         self.bus_idx_mgr = builder.bus_idx_mgr;
+        let first_executor_id = self.inventory.executors.len();
         let mut ext_complex = self.transmute();
         ext_complex.append(inventory_ext.transmute())?;
+        ext_complex
+            .extension_names
+            .push((first_executor_id, short_type_name::<Ext>()));
         Ok(ext_complex)
     }
 
@@ -695,6 +758,8 @@ impl<F: PrimeField32, E, P> VmChipComplex<F, E, P> {
             config: self.config,
             base: self.base,
             inventory: self.inventory.transmute(),
+            extension_names: self.extension_names,
+            extension_versions: self.extension_versions,
             bus_idx_mgr: self.bus_idx_mgr,
             streams: self.streams,
             overridden_inventory_heights: self.overridden_inventory_heights,
@@ -708,6 +773,19 @@ impl<F: PrimeField32, E, P> VmChipComplex<F, E, P> {
         self.inventory.append(other)
     }
 
+    /// Returns the name of the extension whose executor handles `opcode`, or `None` if no
+    /// executor handles it. Executors of the system are attributed to [SYSTEM_EXTENSION_NAME].
+    pub fn extension_name(&self, opcode: VmOpcode) -> Option<&'static str> {
+        let id = self.inventory.get_executor_id(opcode)?;
+        let name = self
+            .extension_names
+            .iter()
+            .rev()
+            .find(|(first_id, _)| *first_id <= id)
+            .map_or(SYSTEM_EXTENSION_NAME, |(_, name)| *name);
+        Some(name)
+    }
+
     pub fn program_chip(&self) -> &ProgramChip<F> {
         &self.base.program_chip
     }
@@ -1201,6 +1279,13 @@ pub fn generate_air_proof_input<SC: StarkGenericConfig, C: Chip<SC>>(
     proof_input
 }
 
+/// The name of the type `T`, without its module path or generic arguments.
+fn short_type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap();
+    name.rsplit("::").next().unwrap()
+}
+
 /// A helper trait for downcasting types that may be enums.
 pub trait AnyEnum {
     /// Recursively "unwraps" enum and casts to `Any` for downcasting.
@@ -1340,6 +1425,12 @@ mod tests {
         assert_eq!(e.as_any_kind().downcast_ref::<u64>(), Some(&3));
     }
 
+    #[test]
+    fn test_short_type_name() {
+        assert_eq!(short_type_name::<SystemConfig>(), "SystemConfig");
+        assert_eq!(short_type_name::<VmInventory<u8, u8>>(), "VmInventory");
+    }
+
     #[test]
     fn test_system_bus_indices() {
         let config = SystemConfig::default().with_continuations();
@@ -0,0 +1,164 @@
+use std::collections::BTreeMap;
+
+use openvm_stark_backend::p3_field::PrimeField32;
+
+use crate::system::memory::{online::MemoryUndoEntry, MemoryController, MemoryImage};
+
+/// An instruction executed while recording a [MemoryHistory].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HistoryStep {
+    pub pc: u32,
+    /// The timestamp when the instruction started.
+    pub timestamp: u32,
+    /// Index of the first write of the instruction in the undo log.
+    first_write: usize,
+}
+
+/// The history of the memory during an execution, to step backwards through it from a fault.
+///
+/// This is the data a debugger would need to step backwards, and there is no debugger in the
+/// tree: it is only exposed through
+/// [VmExecutor::execute_with_memory_history](super::VmExecutor::execute_with_memory_history).
+///
+/// The memory before every executed instruction is reconstructed from the memory at the end of
+/// the execution and an undo log holding every write with the data it overwrote, by undoing the
+/// writes after the instruction. To bound the number of writes undone, the steps are grouped into
+/// intervals of `snapshot_interval` steps, and each complete interval keeps a diff holding the
+/// value before the interval of every cell written in it. The memory before a step is then
+/// reconstructed by undoing the steps of the last, incomplete interval, applying the diffs of the
+/// intervals after the step, and undoing the remaining at most `snapshot_interval - 1` steps. A
+/// diff only holds the cells written in its interval, so the memory is never copied while
+/// recording.
+///
+/// Steps are numbered from 0 across segments, and only instructions handled by an executor are
+/// steps: phantom instructions handled by the segment itself, such as a debug panic, are not.
+pub struct MemoryHistory<F> {
+    snapshot_interval: usize,
+    steps: Vec<HistoryStep>,
+    undo_log: Vec<MemoryUndoEntry<F>>,
+    /// The values of the cells written in each complete interval, before the interval.
+    interval_diffs: Vec<BTreeMap<(u32, u32), F>>,
+    /// The memory after the last step, set when the execution stops.
+    final_memory: Option<MemoryImage<F>>,
+}
+
+impl<F: PrimeField32> MemoryHistory<F> {
+    pub fn new(snapshot_interval: usize) -> Self {
+        assert!(snapshot_interval > 0, "snapshot interval must be positive");
+        Self {
+            snapshot_interval,
+            steps: vec![],
+            undo_log: vec![],
+            interval_diffs: vec![],
+            final_memory: None,
+        }
+    }
+
+    pub fn num_steps(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn step(&self, step: usize) -> &HistoryStep {
+        &self.steps[step]
+    }
+
+    /// The writes of `step`, in the order they happened.
+    pub fn writes(&self, step: usize) -> &[MemoryUndoEntry<F>] {
+        let end = self
+            .steps
+            .get(step + 1)
+            .map_or(self.undo_log.len(), |next| next.first_write);
+        &self.undo_log[self.steps[step].first_write..end]
+    }
+
+    /// The last step before `step` which wrote to the cell at `(address_space, pointer)`, which
+    /// is where to look when the cell holds an unexpected value at `step`.
+    pub fn last_write_before(
+        &self,
+        step: usize,
+        address_space: u32,
+        pointer: u32,
+    ) -> Option<usize> {
+        (0..step).rev().find(|&s| {
+            self.writes(s).iter().any(|write| {
+                write.address_space == address_space
+                    && (write.pointer..write.pointer + write.data.len() as u32).contains(&pointer)
+            })
+        })
+    }
+
+    /// Reverts the writes of `step` in `memory`, which turns the memory after `step` into the
+    /// memory before it.
+    pub fn undo_step(&self, memory: &mut MemoryImage<F>, step: usize) {
+        for write in self.writes(step).iter().rev() {
+            for (i, &value) in write.prev_data.iter().enumerate() {
+                memory.insert(&(write.address_space, write.pointer + i as u32), value);
+            }
+        }
+    }
+
+    /// The memory before `step`, or at the end of the execution if `step` is the number of
+    /// steps.
+    pub fn memory_before(&self, step: usize) -> MemoryImage<F> {
+        assert!(step <= self.num_steps(), "step {step} was not executed");
+        let mut memory = self
+            .final_memory
+            .clone()
+            .expect("memory history is still being recorded");
+        let incomplete_start = self.interval_diffs.len() * self.snapshot_interval;
+        for s in (step.max(incomplete_start)..self.num_steps()).rev() {
+            self.undo_step(&mut memory, s);
+        }
+        if step < incomplete_start {
+            let first_interval = step.div_ceil(self.snapshot_interval);
+            for diff in self.interval_diffs[first_interval..].iter().rev() {
+                for (&address, &value) in diff {
+                    memory.insert(&address, value);
+                }
+            }
+            for s in (step..first_interval * self.snapshot_interval).rev() {
+                self.undo_step(&mut memory, s);
+            }
+        }
+        memory
+    }
+
+    /// Starts a step, assigning the writes since the previous step started to it.
+    pub(crate) fn begin_step(
+        &mut self,
+        pc: u32,
+        timestamp: u32,
+        memory_controller: &mut MemoryController<F>,
+    ) {
+        self.undo_log.append(&mut memory_controller.take_undo_log());
+        memory_controller.enable_undo_log();
+        let step = self.num_steps();
+        if step > 0 && step % self.snapshot_interval == 0 {
+            self.close_interval(step - self.snapshot_interval);
+        }
+        self.steps.push(HistoryStep {
+            pc,
+            timestamp,
+            first_write: self.undo_log.len(),
+        });
+    }
+
+    /// Records the diff of the interval starting at `start`, whose writes are all in the undo
+    /// log.
+    fn close_interval(&mut self, start: usize) {
+        let mut diff = BTreeMap::new();
+        for write in &self.undo_log[self.steps[start].first_write..] {
+            for (i, &value) in write.prev_data.iter().enumerate() {
+                diff.entry((write.address_space, write.pointer + i as u32))
+                    .or_insert(value);
+            }
+        }
+        self.interval_diffs.push(diff);
+    }
+
+    /// Ends the last step when the execution of a segment stops, normally or with an error.
+    pub(crate) fn finish(&mut self, memory_controller: &mut MemoryController<F>) {
+        self.undo_log.append(&mut memory_controller.take_undo_log());
+        self.final_memory = Some(memory_controller.memory_image().clone());
+    }
+}
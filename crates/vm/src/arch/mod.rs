@@ -1,13 +1,21 @@
+/// Channels between the guest and the host.
+mod channel;
 mod config;
 /// Instruction execution traits and types.
 /// Execution bus and interface.
 mod execution;
 /// Traits and builders to compose collections of chips into a virtual machine.
 mod extensions;
+/// Memory history for stepping backwards through an execution.
+mod history;
 /// Traits and wrappers to facilitate VM chip integration
 mod integration_api;
+/// Cycles of the functions of a program along its call stacks.
+mod profile;
 /// Runtime execution and segmentation
 pub mod segment;
+/// Utilization of the traces of the chips of a segment.
+mod utilization;
 /// Top level [VirtualMachine] constructor and API.
 pub mod vm;
 
@@ -18,9 +26,13 @@ pub mod hasher;
 #[cfg(any(test, feature = "test-utils"))]
 pub mod testing;
 
+pub use channel::*;
 pub use config::*;
 pub use execution::*;
 pub use extensions::*;
+pub use history::*;
 pub use integration_api::*;
+pub use profile::*;
 pub use segment::*;
+pub use utilization::*;
 pub use vm::*;
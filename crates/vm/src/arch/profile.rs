@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+
+use openvm_instructions::exe::{FnBound, FnBounds};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+
+/// The name of the frames of instructions outside of every function.
+pub const UNKNOWN_FUNCTION: &str = "[unknown]";
+
+/// A frame of the call tree of a [FunctionProfile].
+#[derive(Clone, Debug)]
+pub struct CallNode {
+    /// The start address of the function of the frame, or `None` for instructions outside of
+    /// every function.
+    pub function: Option<u32>,
+    /// The calling frame, or `None` for the frames called by the root.
+    pub parent: Option<usize>,
+    /// The number of instructions executed in the frame, excluding those of its callees.
+    pub self_cycles: usize,
+    children: FxHashMap<Option<u32>, usize>,
+}
+
+/// The cycles spent in one function, see [FunctionProfile::functions].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FunctionCycles {
+    pub name: String,
+    /// The number of instructions executed in the function, excluding those of its callees.
+    pub self_cycles: usize,
+    /// The number of instructions executed in the function and its callees.
+    pub total_cycles: usize,
+}
+
+/// The instructions executed by each function of a program, along the call stacks they were
+/// executed from.
+///
+/// Calls and returns are inferred from the function bounds of the program: jumping to the start
+/// of a function calls it, and jumping into the middle of a function returns to the innermost
+/// frame of that function. A jump into the middle of a function without a frame, e.g. by a
+/// tail call after inlining, calls it. A recursive call only returns once the control flow
+/// leaves the function, so the cycles of the caller after the recursive call are attributed to
+/// the callee.
+///
+/// Only instructions handled by an executor are counted, as for [MemoryHistory].
+///
+/// [MemoryHistory]: super::MemoryHistory
+#[derive(Clone, Debug)]
+pub struct FunctionProfile {
+    fn_bounds: FnBounds,
+    /// The call tree, where every frame comes after its parent. The root at index 0 is not a
+    /// frame of the program and executes no instructions.
+    nodes: Vec<CallNode>,
+    /// The frame of the last instruction.
+    current: usize,
+    /// The first and last pc of the function of the current frame, which do not change the frame.
+    current_pcs: Option<(u32, u32)>,
+}
+
+impl FunctionProfile {
+    pub fn new(fn_bounds: FnBounds) -> Self {
+        Self {
+            fn_bounds,
+            nodes: vec![CallNode {
+                function: None,
+                parent: None,
+                self_cycles: 0,
+                children: FxHashMap::default(),
+            }],
+            current: 0,
+            current_pcs: None,
+        }
+    }
+
+    /// Records the execution of the instruction at `pc`.
+    pub(crate) fn step(&mut self, pc: u32) {
+        if !matches!(self.current_pcs, Some((start, end)) if start <= pc && pc <= end) {
+            self.enter(pc);
+        }
+        self.nodes[self.current].self_cycles += 1;
+    }
+
+    fn enter(&mut self, pc: u32) {
+        let bound = self
+            .fn_bounds
+            .range(..=pc)
+            .next_back()
+            .map(|(_, bound)| bound)
+            .filter(|bound| pc <= bound.end);
+        let function = bound.map(|bound| bound.start);
+        self.current_pcs = bound.map(|bound| (bound.start, bound.end));
+        if bound.is_none_or(|bound| pc != bound.start) {
+            let mut node = Some(self.current);
+            while let Some(idx) = node.filter(|&idx| idx != 0) {
+                if self.nodes[idx].function == function {
+                    self.current = idx;
+                    return;
+                }
+                node = self.nodes[idx].parent;
+            }
+        }
+        self.current = self.child(self.current, function);
+    }
+
+    fn child(&mut self, parent: usize, function: Option<u32>) -> usize {
+        if let Some(&child) = self.nodes[parent].children.get(&function) {
+            return child;
+        }
+        let child = self.nodes.len();
+        self.nodes.push(CallNode {
+            function,
+            parent: (parent != 0).then_some(parent),
+            self_cycles: 0,
+            children: FxHashMap::default(),
+        });
+        self.nodes[parent].children.insert(function, child);
+        child
+    }
+
+    pub fn fn_bounds(&self) -> &FnBounds {
+        &self.fn_bounds
+    }
+
+    /// The frames of the call tree, where every frame comes after its parent. Frames with the same
+    /// call stack are merged.
+    pub fn frames(&self) -> &[CallNode] {
+        &self.nodes[1..]
+    }
+
+    /// The name of the function starting at `function`, see [CallNode::function].
+    pub fn function_name(&self, function: Option<u32>) -> &str {
+        function
+            .and_then(|start| self.fn_bounds.get(&start))
+            .map_or(UNKNOWN_FUNCTION, |bound: &FnBound| &bound.name)
+    }
+
+    /// The functions of the call stack of the frame at `idx` in [Self::frames], from the
+    /// outermost to the frame itself.
+    pub fn call_stack(&self, idx: usize) -> Vec<Option<u32>> {
+        let mut stack = vec![];
+        let mut node = Some(idx + 1);
+        while let Some(idx) = node {
+            stack.push(self.nodes[idx].function);
+            node = self.nodes[idx].parent;
+        }
+        stack.reverse();
+        stack
+    }
+
+    /// The total number of instructions recorded.
+    pub fn total_cycles(&self) -> usize {
+        self.nodes.iter().map(|node| node.self_cycles).sum()
+    }
+
+    /// The cycles of every function which executed instructions, by decreasing total cycles.
+    /// The total cycles of a recursive function only count its outermost frames.
+    pub fn functions(&self) -> Vec<FunctionCycles> {
+        let mut subtree_cycles: Vec<usize> = self.nodes.iter().map(|n| n.self_cycles).collect();
+        for idx in (1..self.nodes.len()).rev() {
+            if let Some(parent) = self.nodes[idx].parent {
+                subtree_cycles[parent] += subtree_cycles[idx];
+            }
+        }
+        let mut functions = BTreeMap::<Option<u32>, (usize, usize)>::new();
+        for (idx, node) in self.nodes.iter().enumerate().skip(1) {
+            let (self_cycles, total_cycles) = functions.entry(node.function).or_default();
+            *self_cycles += node.self_cycles;
+            let mut ancestor = node.parent;
+            while let Some(parent) = ancestor {
+                if self.nodes[parent].function == node.function {
+                    break;
+                }
+                ancestor = self.nodes[parent].parent;
+            }
+            if ancestor.is_none() {
+                *total_cycles += subtree_cycles[idx];
+            }
+        }
+        let mut functions: Vec<_> = functions
+            .into_iter()
+            .map(|(function, (self_cycles, total_cycles))| FunctionCycles {
+                name: self.function_name(function).to_string(),
+                self_cycles,
+                total_cycles,
+            })
+            .collect();
+        functions.sort_by(|a, b| b.total_cycles.cmp(&a.total_cycles));
+        functions
+    }
+}
+
+/// The cycles spent in one region, see [RegionProfile::regions].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionCycles {
+    pub name: String,
+    /// The number of times the region was entered.
+    pub count: usize,
+    /// The number of instructions executed in the region, including its nested regions.
+    pub total_cycles: u64,
+}
+
+/// The instructions executed in the regions the guest labels with `openvm::profile::start_region!`
+/// and `openvm::profile::end_region!`.
+///
+/// Regions nest: ending a region ends the innermost open one. The instructions of a region are
+/// those executed after its start and before its end, excluding the two annotations. The cycles
+/// of a region nested in a region with the same name are only counted once. Regions still open
+/// when the program terminates and ends without an open region are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RegionProfile {
+    /// The open regions, from the outermost, with the number of instructions executed before
+    /// their start.
+    open: Vec<(String, u64)>,
+    regions: BTreeMap<String, RegionCycles>,
+}
+
+impl RegionProfile {
+    /// Opens the region `name` at the annotation executed after `instret` instructions.
+    pub fn start(&mut self, name: String, instret: u64) {
+        self.open.push((name, instret));
+    }
+
+    /// Ends the innermost open region at the annotation executed after `instret` instructions.
+    pub fn end(&mut self, instret: u64) {
+        let Some((name, start)) = self.open.pop() else {
+            return;
+        };
+        let is_outermost = self.open.iter().all(|(open, _)| *open != name);
+        let region = self
+            .regions
+            .entry(name.clone())
+            .or_insert_with(|| RegionCycles {
+                name,
+                count: 0,
+                total_cycles: 0,
+            });
+        region.count += 1;
+        if is_outermost {
+            region.total_cycles += instret.saturating_sub(start + 1);
+        }
+    }
+
+    /// Whether no region was ended.
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    /// The regions which were ended, by decreasing total cycles.
+    pub fn regions(&self) -> Vec<RegionCycles> {
+        let mut regions: Vec<_> = self.regions.values().cloned().collect();
+        regions.sort_by(|a, b| b.total_cycles.cmp(&a.total_cycles));
+        regions
+    }
+}
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use backtrace::Backtrace;
+use itertools::zip_eq;
 use openvm_instructions::{
     exe::FnBounds,
     instruction::{DebugInfo, Instruction},
@@ -15,10 +16,12 @@ use openvm_stark_backend::{
     utils::metrics_span,
     Chip,
 };
+use rustc_hash::FxHashMap;
 
 use super::{
-    ExecutionError, GenerationError, Streams, SystemBase, SystemConfig, VmChipComplex,
-    VmComplexTraceHeights, VmConfig,
+    ChipUtilization, ExecutionError, FunctionProfile, GenerationError, MemoryHistory, OpcodeCounts,
+    SegmentUtilization, Streams, SystemBase, SystemConfig, VmChipComplex, VmComplexTraceHeights,
+    VmConfig, SYSTEM_EXTENSION_NAME,
 };
 #[cfg(feature = "bench-metrics")]
 use crate::metrics::VmMetrics;
@@ -31,6 +34,7 @@ use crate::{
 const SEGMENT_CHECK_INTERVAL: usize = 100;
 
 const DEFAULT_MAX_SEGMENT_LEN: usize = (1 << 22) - 100;
+const DEFAULT_MAX_TRACE_HEIGHT: usize = 1 << 22;
 // a heuristic number for the maximum number of cells per chip in a segment
 // a few reasons for this number:
 //  1. `VmAirWrapper<Rv32BaseAluAdapterAir, BaseAluCoreAir<4, 8>` is
@@ -138,6 +142,94 @@ impl SegmentationStrategy for DefaultSegmentationStrategy {
     }
 }
 
+/// Adaptive segmentation strategy: segment just before the trace of a chip would outgrow the
+/// largest power-of-two height allowed by its cell budget.
+///
+/// Traces are padded to a power of two, so a chip whose trace stops slightly above a power of two
+/// wastes almost half of its padded trace. The width of each chip is measured from its current
+/// height and cells, which lets the chip dominating the segment fill its padded trace instead of
+/// stopping at an arbitrary height, e.g. a wide chip reaching the cell limit of
+/// [DefaultSegmentationStrategy].
+#[derive(Debug, Clone)]
+pub struct AdaptiveSegmentationStrategy {
+    max_trace_height: usize,
+    max_cells_per_chip_in_segment: usize,
+}
+
+impl Default for AdaptiveSegmentationStrategy {
+    fn default() -> Self {
+        Self {
+            max_trace_height: DEFAULT_MAX_TRACE_HEIGHT,
+            max_cells_per_chip_in_segment: DEFAULT_MAX_CELLS_PER_CHIP_IN_SEGMENT,
+        }
+    }
+}
+
+impl AdaptiveSegmentationStrategy {
+    pub fn new(max_trace_height: usize, max_cells_per_chip_in_segment: usize) -> Self {
+        Self {
+            max_trace_height,
+            max_cells_per_chip_in_segment,
+        }
+    }
+
+    pub fn max_trace_height(&self) -> usize {
+        self.max_trace_height
+    }
+
+    /// The height above which a chip with `height` rows and `num_cells` cells should segment.
+    ///
+    /// Heights are only checked every [SEGMENT_CHECK_INTERVAL] instructions, so this leaves that
+    /// many rows below the power-of-two height allowed for the chip.
+    fn height_threshold(&self, height: usize, num_cells: usize) -> usize {
+        let width = num_cells.div_ceil(height).max(1);
+        let max_height = self
+            .max_trace_height
+            .min(self.max_cells_per_chip_in_segment / width)
+            .max(1);
+        // The largest power of two not exceeding `max_height`.
+        let padded_height = 1usize << max_height.ilog2();
+        padded_height
+            .saturating_sub(SEGMENT_CHECK_INTERVAL)
+            .max(padded_height / 2)
+    }
+}
+
+impl SegmentationStrategy for AdaptiveSegmentationStrategy {
+    fn should_segment(
+        &self,
+        air_names: &[String],
+        trace_heights: &[usize],
+        trace_cells: &[usize],
+    ) -> bool {
+        for (i, (&height, &num_cells)) in zip_eq(trace_heights, trace_cells).enumerate() {
+            if height == 0 {
+                continue;
+            }
+            let threshold = self.height_threshold(height, num_cells);
+            if height > threshold {
+                tracing::info!(
+                    "Should segment because chip {} (name: {}) has height {} above {}",
+                    i,
+                    air_names[i],
+                    height,
+                    threshold
+                );
+                return true;
+            }
+        }
+        false
+    }
+
+    fn stricter_strategy(&self) -> Arc<dyn SegmentationStrategy> {
+        Arc::new(Self {
+            max_trace_height: self.max_trace_height / SEGMENTATION_BACKOFF_FACTOR,
+            max_cells_per_chip_in_segment: self.max_cells_per_chip_in_segment
+                / SEGMENTATION_BACKOFF_FACTOR,
+        })
+    }
+}
+
 pub struct ExecutionSegment<F, VC>
 where
     F: PrimeField32,
@@ -149,6 +241,15 @@ where
 
     pub since_last_segment_check: usize,
     pub trace_height_constraints: Vec<LinearConstraint>,
+    /// Records the memory before every instruction if set, see
+    /// [VmExecutor::execute_with_memory_history](super::VmExecutor::execute_with_memory_history).
+    pub(crate) memory_history: Option<MemoryHistory<F>>,
+    /// Records the call stack of every instruction if set, see
+    /// [VmExecutor::set_function_profiling](super::VmExecutor::set_function_profiling).
+    pub(crate) function_profile: Option<FunctionProfile>,
+    /// Aborts the execution once the program executed more instructions than this, see
+    /// [VmExecutor::set_max_instructions](super::VmExecutor::set_max_instructions).
+    pub(crate) max_instructions: Option<u64>,
 
     /// Air names for debug purposes only.
     pub(crate) air_names: Vec<String>,
@@ -160,6 +261,8 @@ where
 pub struct ExecutionSegmentState {
     pub pc: u32,
     pub is_terminated: bool,
+    /// Whether the segment ended because the program paused.
+    pub is_paused: bool,
 }
 
 impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
@@ -191,6 +294,9 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             final_memory: None,
             air_names,
             trace_height_constraints,
+            memory_history: None,
+            function_profile: None,
+            max_instructions: None,
             #[cfg(feature = "bench-metrics")]
             metrics: VmMetrics {
                 fn_bounds,
@@ -224,12 +330,18 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             .begin(ExecutionState::new(pc, timestamp));
 
         let mut did_terminate = false;
+        let mut pause_pc = None;
+        let instret_start = self.chip_complex.streams.lock().unwrap().instret;
+        let mut num_instructions: u64 = 0;
 
         loop {
             #[allow(unused_variables)]
             let (opcode, dsl_instr) = {
                 let Self {
                     chip_complex,
+                    memory_history,
+                    function_profile,
+                    max_instructions,
                     #[cfg(feature = "bench-metrics")]
                     metrics,
                     ..
@@ -293,19 +405,43 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
                                 .cycle_tracker
                                 .end(dsl_instr.cloned().unwrap_or("Default".to_string()))
                         }
+                        Some(SysPhantom::Pause) => pause_pc = Some(pc),
+                        None => {
+                            // Phantom sub-executors may need the current instruction.
+                            chip_complex.streams.lock().unwrap().instret =
+                                instret_start + num_instructions;
+                        }
                         _ => {}
                     }
                 }
                 prev_backtrace = trace.cloned();
 
                 if let Some(executor) = chip_complex.inventory.get_mut_executor(&opcode) {
+                    if let Some(history) = memory_history {
+                        history.begin_step(pc, timestamp, memory_controller);
+                    }
+                    if let Some(profile) = function_profile {
+                        profile.step(pc);
+                    }
                     let next_state = InstructionExecutor::execute(
                         executor,
                         memory_controller,
                         instruction,
                         ExecutionState::new(pc, timestamp),
                     )?;
+                    if let Some(address) = memory_controller.take_stack_guard_access() {
+                        return Err(ExecutionError::StackOverflow { pc, address });
+                    }
                     assert!(next_state.timestamp > timestamp);
+                    num_instructions += 1;
+                    if let Some(max_instructions) = *max_instructions {
+                        if instret_start + num_instructions > max_instructions {
+                            return Err(ExecutionError::InstructionLimitExceeded {
+                                pc,
+                                max_instructions,
+                            });
+                        }
+                    }
                     pc = next_state.pc;
                     timestamp = next_state.timestamp;
                 } else {
@@ -317,13 +453,23 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
             #[cfg(feature = "bench-metrics")]
             self.update_instruction_metrics(pc, opcode, dsl_instr);
 
-            if self.should_segment() {
+            if let Some(pc) = pause_pc {
+                if !self.system_config().continuation_enabled {
+                    return Err(ExecutionError::PauseWithoutContinuations { pc });
+                }
+            }
+            if pause_pc.is_some() || self.should_segment() {
                 self.chip_complex
                     .connector_chip_mut()
                     .end(ExecutionState::new(pc, timestamp), None);
                 break;
             }
         }
+        {
+            let mut streams = self.chip_complex.streams.lock().unwrap();
+            streams.segment += 1;
+            streams.instret = instret_start + num_instructions;
+        }
         self.final_memory = Some(
             self.chip_complex
                 .base
@@ -335,6 +481,7 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
         Ok(ExecutionSegmentState {
             pc,
             is_terminated: did_terminate,
+            is_paused: pause_pc.is_some(),
         })
     }
 
@@ -358,6 +505,17 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
         })
     }
 
+    /// Finalizes the memory of the segment and drops its memory images, which
+    /// [Self::generate_proof_input] does not need, so that they are not held while the traces are
+    /// generated and proven.
+    pub fn release_memory_images(&mut self) {
+        self.chip_complex.finalize_memory();
+        self.chip_complex
+            .base
+            .memory_controller
+            .release_memory_images();
+    }
+
     /// Returns bool of whether to switch to next segment or not. This is called every clock cycle
     /// inside of Core trace generation.
     fn should_segment(&mut self) -> bool {
@@ -384,4 +542,99 @@ impl<F: PrimeField32, VC: VmConfig<F>> ExecutionSegment<F, VC> {
     pub fn current_trace_cells(&self) -> Vec<usize> {
         self.chip_complex.current_trace_cells()
     }
+
+    /// Returns the rows used by each chip in this segment, to compare them with the padded heights
+    /// of the traces. The memory is finalized first, as for trace generation, since finalizing
+    /// adds the rows of the touched memory to the memory chips and the hashes of the Merkle tree
+    /// to the Poseidon2 chip.
+    pub fn utilization(&mut self) -> SegmentUtilization {
+        self.chip_complex.finalize_memory();
+        let chips = zip_eq(
+            zip_eq(&self.air_names, self.chip_complex.current_trace_heights()),
+            self.chip_complex.dynamic_trace_heights(),
+        )
+        .map(|((air_name, rows), dynamic_rows)| ChipUtilization {
+            air_name: air_name.clone(),
+            rows,
+            fixed_height: rows != dynamic_rows,
+        })
+        .collect();
+        SegmentUtilization {
+            cycles: self
+                .chip_complex
+                .program_chip()
+                .execution_frequencies
+                .iter()
+                .sum(),
+            chips,
+        }
+    }
+
+    /// Returns the number of times each opcode was executed in this segment, grouped by
+    /// extension. The counts are derived from the execution frequencies of the program chip, so
+    /// they cost nothing during execution.
+    pub fn opcode_counts(&self) -> OpcodeCounts {
+        let program_chip = self.chip_complex.program_chip();
+        let mut frequencies = FxHashMap::<VmOpcode, usize>::default();
+        for (&frequency, instruction) in zip_eq(
+            &program_chip.execution_frequencies,
+            &program_chip.program.instructions_and_debug_infos,
+        ) {
+            if let Some((instruction, _)) = instruction.as_ref().filter(|_| frequency > 0) {
+                *frequencies.entry(instruction.opcode).or_default() += frequency;
+            }
+        }
+
+        let mut counts = OpcodeCounts::new();
+        for (opcode, frequency) in frequencies {
+            // Only TERMINATE is handled by the segment itself rather than by an executor.
+            let (extension, opcode_name) = match self.chip_complex.inventory.get_executor(opcode) {
+                Some(executor) => (
+                    self.chip_complex.extension_name(opcode).unwrap(),
+                    executor.get_opcode_name(opcode.as_usize()),
+                ),
+                None if opcode == SystemOpcode::TERMINATE.global_opcode() => (
+                    SYSTEM_EXTENSION_NAME,
+                    format!("{:?}", SystemOpcode::TERMINATE),
+                ),
+                None => (SYSTEM_EXTENSION_NAME, opcode.to_string()),
+            };
+            *counts
+                .entry(extension.to_string())
+                .or_default()
+                .entry(opcode_name)
+                .or_default() += frequency;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AdaptiveSegmentationStrategy, SegmentationStrategy};
+
+    fn should_segment(
+        strategy: &AdaptiveSegmentationStrategy,
+        height: usize,
+        width: usize,
+    ) -> bool {
+        strategy.should_segment(&["chip".to_string()], &[height], &[height * width])
+    }
+
+    #[test]
+    fn test_adaptive_segmentation() {
+        let strategy = AdaptiveSegmentationStrategy::new(1 << 20, 1 << 26);
+        // Narrow chips are limited by the max trace height.
+        assert!(!should_segment(&strategy, (1 << 20) - 100, 32));
+        assert!(should_segment(&strategy, (1 << 20) - 99, 32));
+        // A chip of width 100 may have 671088 rows, but segments before its trace is padded
+        // from 2^19 to 2^20 rows.
+        assert!(!should_segment(&strategy, (1 << 19) - 100, 100));
+        assert!(should_segment(&strategy, (1 << 19) - 99, 100));
+        // Chips without rows never segment.
+        assert!(!strategy.should_segment(&["chip".to_string()], &[0], &[0]));
+
+        let stricter = strategy.stricter_strategy();
+        assert!(stricter.should_segment(&["chip".to_string()], &[1 << 18], &[32 << 18]));
+    }
 }
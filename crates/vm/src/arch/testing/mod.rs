@@ -272,9 +272,8 @@ impl VmChipTestBuilder<BabyBear> {
     }
 }
 
-impl<F: PrimeField32> Default for VmChipTestBuilder<F> {
-    fn default() -> Self {
-        let mem_config = MemoryConfig::default();
+impl<F: PrimeField32> VmChipTestBuilder<F> {
+    pub fn volatile(mem_config: MemoryConfig) -> Self {
         let range_checker = SharedVariableRangeCheckerChip::new(VariableRangeCheckerBus::new(
             RANGE_CHECKER_BUS,
             mem_config.decomp,
@@ -295,6 +294,12 @@ impl<F: PrimeField32> Default for VmChipTestBuilder<F> {
     }
 }
 
+impl<F: PrimeField32> Default for VmChipTestBuilder<F> {
+    fn default() -> Self {
+        Self::volatile(MemoryConfig::default())
+    }
+}
+
 pub struct VmChipTester<SC: StarkGenericConfig> {
     pub memory: Option<MemoryTester<Val<SC>>>,
     pub air_proof_inputs: Vec<(AirRef<SC>, AirProofInput<SC>)>,
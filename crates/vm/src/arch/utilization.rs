@@ -0,0 +1,156 @@
+use std::fmt::{self, Display};
+
+use openvm_circuit_primitives::utils::next_power_of_two_or_zero;
+
+/// A chip is reported as imbalanced when its padded height is at least this many times the padded
+/// height of every other chip.
+const IMBALANCE_RATIO: usize = 8;
+/// The dominating chip is reported as wasteful when it fills at most this percentage of its
+/// padded height.
+const MAX_WASTEFUL_FILL_PERCENT: usize = 60;
+/// Chips shorter than this are too cheap to prove for their padding to matter.
+const MIN_REPORTED_HEIGHT: usize = 1 << 10;
+
+/// The rows used by one chip in a segment, see [SegmentUtilization].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ChipUtilization {
+    pub air_name: String,
+    /// The number of rows of the trace before padding.
+    pub rows: usize,
+    /// Whether the height of the trace does not depend on the execution, as for the program chip
+    /// and lookup tables.
+    pub fixed_height: bool,
+}
+
+impl ChipUtilization {
+    /// The height of the trace after padding to a power of two.
+    pub fn padded_height(&self) -> usize {
+        next_power_of_two_or_zero(self.rows)
+    }
+
+    pub fn padding(&self) -> usize {
+        self.padded_height() - self.rows
+    }
+}
+
+/// How well a segment uses the traces of its chips, see [ExecutionSegment::utilization].
+///
+/// The cost of proving a segment grows with the padded heights of its traces, so rows of padding
+/// are proven for nothing, and a chip much taller than all others dominates the cost of the
+/// segment. [Self::suggestions] points out both cases.
+///
+/// [ExecutionSegment::utilization]: super::ExecutionSegment::utilization
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SegmentUtilization {
+    /// The number of instructions executed in the segment.
+    pub cycles: usize,
+    /// The chips of the segment, in the order of their AIRs.
+    pub chips: Vec<ChipUtilization>,
+}
+
+impl SegmentUtilization {
+    /// The chip with the largest padded height among the chips whose height depends on the
+    /// execution.
+    pub fn dominating_chip(&self) -> Option<&ChipUtilization> {
+        self.dynamic_chips()
+            .filter(|chip| chip.rows > 0)
+            .max_by_key(|chip| (chip.padded_height(), chip.rows))
+    }
+
+    /// The total number of rows of padding of the chips whose height depends on the execution.
+    pub fn padding(&self) -> usize {
+        self.dynamic_chips().map(ChipUtilization::padding).sum()
+    }
+
+    /// The total padded height of the chips whose height depends on the execution.
+    pub fn padded_height(&self) -> usize {
+        self.dynamic_chips()
+            .map(ChipUtilization::padded_height)
+            .sum()
+    }
+
+    /// Suggests changes to the segmentation which would balance the heights of the chips or
+    /// reduce their padding, in the order of their expected savings.
+    pub fn suggestions(&self) -> Vec<String> {
+        let mut suggestions = Vec::new();
+        let Some(dominating) = self.dominating_chip() else {
+            return suggestions;
+        };
+        let padded_height = dominating.padded_height();
+        if padded_height < MIN_REPORTED_HEIGHT {
+            return suggestions;
+        }
+        let next = self
+            .dynamic_chips()
+            .filter(|chip| !std::ptr::eq(*chip, dominating))
+            .max_by_key(|chip| chip.padded_height());
+        if let Some(next) = next {
+            let ratio = padded_height / next.padded_height().max(1);
+            if ratio >= IMBALANCE_RATIO {
+                suggestions.push(format!(
+                    "{} is {ratio} times taller than the next chip ({}), so it dominates the \
+                     cost of proving the segment and bounds its length; if a phase of the program \
+                     uses it heavily, executing that phase in segments of its own, e.g. by \
+                     limiting the cells per chip of the segmentation strategy, lets the segments \
+                     of the other phases run longer",
+                    dominating.air_name, next.air_name
+                ));
+            }
+        }
+        let fill_percent = dominating.rows * 100 / padded_height;
+        if fill_percent <= MAX_WASTEFUL_FILL_PERCENT && self.cycles > 0 {
+            // Assumes the rows of the dominating chip grow with the number of instructions.
+            let cycles = self.cycles * (padded_height / 2) / dominating.rows;
+            suggestions.push(format!(
+                "{} fills {fill_percent}% of its padded height of {padded_height}; a maximum \
+                 segment length a little below {}, set with `SystemConfig::with_max_segment_len`, \
+                 would fit it in half the rows, with about {cycles} instructions per segment",
+                dominating.air_name,
+                padded_height / 2
+            ));
+        }
+        suggestions
+    }
+
+    fn dynamic_chips(&self) -> impl Iterator<Item = &ChipUtilization> {
+        self.chips.iter().filter(|chip| !chip.fixed_height)
+    }
+}
+
+impl Display for SegmentUtilization {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} instructions", self.cycles)?;
+        writeln!(f, "  {:>10} {:>10} {:>6}  air", "rows", "padded", "fill")?;
+        let mut chips: Vec<_> = self.chips.iter().filter(|chip| chip.rows > 0).collect();
+        chips.sort_by_key(|chip| std::cmp::Reverse((chip.padded_height(), chip.rows)));
+        for chip in chips {
+            write!(
+                f,
+                "  {:>10} {:>10} {:>5}%  {}",
+                chip.rows,
+                chip.padded_height(),
+                chip.rows * 100 / chip.padded_height(),
+                chip.air_name
+            )?;
+            if chip.fixed_height {
+                write!(f, " (fixed height)")?;
+            }
+            writeln!(f)?;
+        }
+        let padded_height = self.padded_height();
+        if let Some(percent) = (self.padding() * 100).checked_div(padded_height) {
+            writeln!(
+                f,
+                "  padding: {} of {padded_height} rows ({percent}%)",
+                self.padding()
+            )?;
+        }
+        if let Some(dominating) = self.dominating_chip() {
+            write!(f, "  dominated by {}", dominating.air_name)?;
+        }
+        for suggestion in self.suggestions() {
+            write!(f, "\n  suggestion: {suggestion}")?;
+        }
+        Ok(())
+    }
+}
@@ -1,13 +1,14 @@
 use std::{
     borrow::Borrow,
-    collections::{HashMap, VecDeque},
+    collections::{BTreeMap, HashMap, VecDeque},
     marker::PhantomData,
     mem,
     sync::Arc,
 };
 
 use openvm_circuit::system::program::trace::compute_exe_commit;
-use openvm_instructions::exe::VmExe;
+use openvm_instructions::exe::{Overlays, VmExe};
+use openvm_overlay::{num_pages, page_hint, page_key, OverlayTree};
 use openvm_stark_backend::{
     config::{Com, Domain, StarkGenericConfig, Val},
     engine::StarkEngine,
@@ -25,7 +26,8 @@ use thiserror::Error;
 use tracing::info_span;
 
 use super::{
-    ExecutionError, VmComplexTraceHeights, VmConfig, CONNECTOR_AIR_ID, MERKLE_AIR_ID,
+    ChannelTranscript, ExecutionError, FunctionProfile, HostChannels, MemoryHistory, RegionProfile,
+    SegmentUtilization, VmComplexTraceHeights, VmConfig, CONNECTOR_AIR_ID, MERKLE_AIR_ID,
     PROGRAM_AIR_ID, PROGRAM_CACHED_TRACE_INDEX,
 };
 #[cfg(feature = "bench-metrics")]
@@ -66,14 +68,143 @@ impl KvStore for HashMap<Vec<u8>, Vec<u8>> {
     }
 }
 
+/// A [KvStore] which serves the pages of overlays, and defers all other keys to an inner store.
+pub struct OverlayKvStore {
+    pages: HashMap<Vec<u8>, Vec<u8>>,
+    inner: Arc<dyn KvStore>,
+}
+
+impl OverlayKvStore {
+    pub fn new(overlays: &Overlays, inner: Arc<dyn KvStore>) -> Self {
+        let mut pages = HashMap::new();
+        for (root, data) in overlays {
+            let tree = OverlayTree::new(data);
+            assert_eq!(tree.root(), *root, "overlay does not match its root");
+            for index in 0..num_pages(data.len()) {
+                // Each page is hinted as a single byte vector, in the encoding of
+                // `hint_load_by_key_encode`.
+                let hint = page_hint(data, &tree, index);
+                let mut value = Vec::with_capacity(8 + 4 * hint.len());
+                value.extend(1u32.to_le_bytes());
+                value.extend((hint.len() as u32).to_le_bytes());
+                value.extend(
+                    hint.into_iter()
+                        .flat_map(|byte| u32::from(byte).to_le_bytes()),
+                );
+                pages.insert(page_key(root, index as u32), value);
+            }
+        }
+        Self { pages, inner }
+    }
+}
+
+impl KvStore for OverlayKvStore {
+    fn get(&self, key: &[u8]) -> Option<&[u8]> {
+        match self.pages.get(key) {
+            Some(value) => Some(value.as_slice()),
+            None => self.inner.get(key),
+        }
+    }
+}
+
+/// The hint queue selected at the start of execution, which user hints are read from.
+pub const DEFAULT_HINT_QUEUE: u32 = 0;
+
+/// Text printed by the guest, together with the point of execution at which it was printed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestOutputEntry {
+    /// Index of the segment which printed the text.
+    pub segment: usize,
+    /// Number of instructions executed, across all segments, before the instruction which
+    /// printed the text.
+    pub instret: u64,
+    pub text: String,
+}
+
+/// Log of the text printed by the guest during execution, see [Streams::with_output_capture].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GuestOutput {
+    pub entries: Vec<GuestOutputEntry>,
+}
+
+impl GuestOutput {
+    /// All printed text, concatenated.
+    pub fn text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| entry.text.as_str())
+            .collect()
+    }
+}
+
+/// Heap usage reported by the guest when it terminates, if it was built with the `heap-profile`
+/// feature of `openvm`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeapProfile {
+    /// Number of calls to `alloc` and `alloc_zeroed`.
+    pub allocations: u32,
+    pub deallocations: u32,
+    pub reallocations: u32,
+    /// Total number of bytes allocated, including the growth of reallocations.
+    pub allocated_bytes: u32,
+    /// Number of bytes allocated and not deallocated when the guest terminated.
+    pub current_bytes: u32,
+    /// Maximum of `current_bytes` over the execution.
+    pub peak_bytes: u32,
+}
+
+impl HeapProfile {
+    /// Decodes the heap profile from the six little-endian counters written by the guest. Returns
+    /// `None` if `bytes` is not a valid encoding.
+    pub fn decode(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != 6 * 4 {
+            return None;
+        }
+        let words: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(|word| u32::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+        Some(Self {
+            allocations: words[0],
+            deallocations: words[1],
+            reallocations: words[2],
+            allocated_bytes: words[3],
+            current_bytes: words[4],
+            peak_bytes: words[5],
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct Streams<F> {
     pub input_stream: VecDeque<Vec<F>>,
+    /// The selected hint queue, which hints are written to and read from.
     pub hint_stream: VecDeque<F>,
+    /// The id of the selected hint queue.
+    pub hint_queue: u32,
+    /// The hint queues which are not selected, by id. Extensions hint through their own queue so
+    /// that their hints do not clobber hints of other extensions which have not been fully read.
+    pub hint_queues: BTreeMap<u32, VecDeque<F>>,
     pub hint_space: Vec<Vec<F>>,
     /// The key-value store for hints. Both key and value are byte arrays. Executors which
     /// read `kv_store` need to encode the key and decode the value.
     pub kv_store: Arc<dyn KvStore>,
+    /// If set, text printed by the guest is logged here instead of written to stdout.
+    pub output: Option<GuestOutput>,
+    /// Index of the segment being executed.
+    pub segment: usize,
+    /// Number of instructions executed, across all segments, before the current instruction.
+    /// This is only kept up to date for phantom instructions which are not system phantoms.
+    pub instret: u64,
+    /// The panic message recorded by the guest, reported if it terminates with a nonzero exit
+    /// code.
+    pub panic_message: Option<String>,
+    /// The heap profile reported by the guest when it terminates.
+    pub heap_profile: Option<HeapProfile>,
+    /// The cycles of the regions labeled by the guest.
+    pub region_profile: RegionProfile,
+    /// The channels the guest opens with `openvm::io::channel`.
+    pub channels: HostChannels,
 }
 
 impl<F> Streams<F> {
@@ -81,10 +212,66 @@ impl<F> Streams<F> {
         Self {
             input_stream: input_stream.into(),
             hint_stream: VecDeque::default(),
+            hint_queue: DEFAULT_HINT_QUEUE,
+            hint_queues: BTreeMap::new(),
             hint_space: Vec::default(),
             kv_store: Arc::new(HashMap::new()),
+            output: None,
+            segment: 0,
+            instret: 0,
+            panic_message: None,
+            heap_profile: None,
+            region_profile: RegionProfile::default(),
+            channels: HostChannels::default(),
+        }
+    }
+
+    /// Captures the text printed by the guest into [Self::output].
+    pub fn with_output_capture(mut self) -> Self {
+        self.output = Some(GuestOutput::default());
+        self
+    }
+
+    /// Serves the channels of the guest with `channels`.
+    pub fn with_channels(mut self, channels: HostChannels) -> Self {
+        self.channels = channels;
+        self
+    }
+
+    /// Prints `text` on behalf of the guest, to [Self::output] if output is captured and to
+    /// stdout otherwise.
+    pub fn print(&mut self, text: &str) {
+        match self.output.as_mut() {
+            Some(output) => output.entries.push(GuestOutputEntry {
+                segment: self.segment,
+                instret: self.instret,
+                text: text.to_string(),
+            }),
+            None => print!("{text}"),
         }
     }
+
+    /// Selects the hint queue with id `queue` as [Self::hint_stream], keeping the contents of the
+    /// previously selected queue.
+    pub fn select_hint_queue(&mut self, queue: u32) {
+        if queue == self.hint_queue {
+            return;
+        }
+        let selected = self.hint_queues.remove(&queue).unwrap_or_default();
+        let previous = mem::replace(&mut self.hint_stream, selected);
+        if !previous.is_empty() {
+            self.hint_queues.insert(self.hint_queue, previous);
+        }
+        self.hint_queue = queue;
+    }
+
+    /// Additionally serves the pages of `overlays` through `kv_store`.
+    pub fn with_overlays(mut self, overlays: &Overlays) -> Self {
+        if !overlays.is_empty() {
+            self.kv_store = Arc::new(OverlayKvStore::new(overlays, self.kv_store));
+        }
+        self
+    }
 }
 
 impl<F> Default for Streams<F> {
@@ -109,6 +296,12 @@ pub struct VmExecutor<F, VC> {
     pub config: VC,
     pub overridden_heights: Option<VmComplexTraceHeights>,
     pub trace_height_constraints: Vec<LinearConstraint>,
+    /// Whether to record a [FunctionProfile] in [Self::execute], see
+    /// [Self::set_function_profiling].
+    pub function_profiling: bool,
+    /// The number of instructions after which executions are aborted, see
+    /// [Self::set_max_instructions].
+    pub max_instructions: Option<u64>,
     _marker: PhantomData<F>,
 }
 
@@ -123,12 +316,44 @@ pub struct VmExecutorResult<SC: StarkGenericConfig> {
     pub per_segment: Vec<ProofInput<SC>>,
     /// When VM is running on persistent mode, public values are stored in a special memory space.
     pub final_memory: Option<VmMemoryState<Val<SC>>>,
+    /// The text printed by the guest, if output was captured.
+    pub output: Option<GuestOutput>,
+}
+
+/// Number of times each opcode was executed, keyed by the name of the extension handling the
+/// opcode and then by the name of the opcode.
+pub type OpcodeCounts = BTreeMap<String, BTreeMap<String, usize>>;
+
+/// The result of executing a program to termination with [VmExecutor::execute].
+pub struct ExecutionResult<F> {
+    /// The memory at the end of the last segment.
+    pub final_memory: Option<VmMemoryState<F>>,
+    /// Total number of instructions executed across all segments.
+    pub total_cycles: usize,
+    /// Number of times each opcode was executed across all segments.
+    pub opcode_counts: OpcodeCounts,
+    /// The rows used by the chips of each segment.
+    pub utilization: Vec<SegmentUtilization>,
+    /// The text printed by the guest, if output was captured.
+    pub output: Option<GuestOutput>,
+    /// The heap profile reported by the guest, if it was built with the `heap-profile` feature
+    /// of `openvm`.
+    pub heap_profile: Option<HeapProfile>,
+    /// The messages exchanged through the channels of the guest, see [HostChannels].
+    pub channel_transcript: ChannelTranscript,
+    /// The cycles of each function of the program, if function profiling was enabled with
+    /// [VmExecutor::set_function_profiling].
+    pub function_profile: Option<FunctionProfile>,
+    /// The cycles of the regions labeled by the guest with `openvm::profile::start_region!`.
+    pub region_profile: RegionProfile,
 }
 
 pub struct VmExecutorNextSegmentState<F: PrimeField32> {
     pub memory: MemoryImage<F>,
     pub input: Streams<F>,
     pub pc: u32,
+    /// The function profile recorded up to this segment, if any.
+    pub function_profile: Option<FunctionProfile>,
     #[cfg(feature = "bench-metrics")]
     pub metrics: VmMetrics,
 }
@@ -139,6 +364,7 @@ impl<F: PrimeField32> VmExecutorNextSegmentState<F> {
             memory,
             input: input.into(),
             pc,
+            function_profile: None,
             #[cfg(feature = "bench-metrics")]
             metrics: VmMetrics::default(),
         }
@@ -148,6 +374,8 @@ impl<F: PrimeField32> VmExecutorNextSegmentState<F> {
 pub struct VmExecutorOneSegmentResult<F: PrimeField32, VC: VmConfig<F>> {
     pub segment: ExecutionSegment<F, VC>,
     pub next_state: Option<VmExecutorNextSegmentState<F>>,
+    /// Whether the segment ended because the program paused.
+    pub is_paused: bool,
 }
 
 impl<F, VC> VmExecutor<F, VC>
@@ -174,10 +402,27 @@ where
             config,
             overridden_heights,
             trace_height_constraints: vec![],
+            function_profiling: false,
+            max_instructions: None,
             _marker: Default::default(),
         }
     }
 
+    /// Sets whether executions record the call stack of every instruction, to report the cycles
+    /// of each function of the program in [ExecutionResult::function_profile]. Functions are
+    /// read from the function bounds of the executable, so the executable must have been
+    /// transpiled from an ELF with symbols. This slows down the execution.
+    pub fn set_function_profiling(&mut self, enabled: bool) {
+        self.function_profiling = enabled;
+    }
+
+    /// Sets the number of instructions, counted across all segments, after which executions fail
+    /// with [ExecutionError::InstructionLimitExceeded] instead of running further. There is no
+    /// limit by default.
+    pub fn set_max_instructions(&mut self, max_instructions: Option<u64>) {
+        self.max_instructions = max_instructions;
+    }
+
     pub fn continuation_enabled(&self) -> bool {
         self.config.system().continuation_enabled
     }
@@ -191,20 +436,36 @@ where
         &self,
         exe: impl Into<VmExe<F>>,
         input: impl Into<Streams<F>>,
+        f: impl FnMut(usize, ExecutionSegment<F, VC>) -> Result<R, E>,
+        map_err: impl Fn(ExecutionError) -> E,
+    ) -> Result<Vec<R>, E> {
+        self.execute_phases_and_then(exe, input, |_| vec![], f, map_err)
+    }
+
+    /// Executes the program in segments like [Self::execute_and_then], for programs which run in
+    /// phases separated by pauses. Whenever the program pauses, `on_pause` is called with the
+    /// memory at the pause, and the input vectors it returns are appended to the input stream
+    /// before execution continues. `on_pause` must be deterministic, as the execution may be
+    /// repeated.
+    pub fn execute_phases_and_then<R, E>(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+        mut on_pause: impl FnMut(&VmMemoryState<F>) -> Vec<Vec<F>>,
         mut f: impl FnMut(usize, ExecutionSegment<F, VC>) -> Result<R, E>,
         map_err: impl Fn(ExecutionError) -> E,
     ) -> Result<Vec<R>, E> {
         let mem_config = self.config.system().memory_config;
         let exe = exe.into();
         let mut segment_results = vec![];
-        let memory = AddressMap::from_iter(
-            mem_config.as_offset,
-            1 << mem_config.as_height,
-            1 << mem_config.pointer_max_bits,
-            exe.init_memory.clone(),
-        );
+        let mut memory = AddressMap::from_mem_config(&mem_config);
+        memory.extend(exe.init_memory.clone());
         let pc = exe.pc_start;
+        let input = input.into().with_overlays(&exe.overlays);
         let mut state = VmExecutorNextSegmentState::new(memory, input, pc);
+        if self.function_profiling {
+            state.function_profile = Some(FunctionProfile::new(exe.fn_bounds.clone()));
+        }
 
         #[cfg(feature = "bench-metrics")]
         {
@@ -219,10 +480,14 @@ where
                 .execute_until_segment(exe.clone(), state)
                 .map_err(&map_err)?;
             segment_results.push(f(segment_idx, one_segment_result.segment)?);
-            if one_segment_result.next_state.is_none() {
+            let Some(next_state) = one_segment_result.next_state else {
                 break;
+            };
+            state = next_state;
+            if one_segment_result.is_paused {
+                let input = on_pause(&state.memory);
+                state.input.input_stream.extend(input);
             }
-            state = one_segment_result.next_state.unwrap();
             segment_idx += 1;
         }
         tracing::debug!("Number of continuation segments: {}", segment_results.len());
@@ -240,6 +505,51 @@ where
         self.execute_and_then(exe, input, |_, seg| Ok(seg), |err| err)
     }
 
+    /// Executes the program to termination or to the first error like [Self::execute], while
+    /// recording the writes to the memory, so that the memory before every instruction can be
+    /// inspected after a fault. The diff of the cells written in every `snapshot_interval`
+    /// instructions is kept, which trades memory usage for the number of writes undone to
+    /// reconstruct the memory at an instruction, see [MemoryHistory].
+    ///
+    /// The program must not pause.
+    pub fn execute_with_memory_history(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+        snapshot_interval: usize,
+    ) -> (Result<(), ExecutionError>, MemoryHistory<F>) {
+        let mem_config = self.config.system().memory_config;
+        let exe = exe.into();
+        let mut memory = AddressMap::from_mem_config(&mem_config);
+        memory.extend(exe.init_memory.clone());
+        let mut input = input.into().with_overlays(&exe.overlays);
+        let mut pc = exe.pc_start;
+        let mut history = MemoryHistory::new(snapshot_interval);
+        loop {
+            let mut segment = ExecutionSegment::new(
+                &self.config,
+                exe.program.clone(),
+                input,
+                Some(memory),
+                self.trace_height_constraints.clone(),
+                exe.fn_bounds.clone(),
+            );
+            segment.memory_history = Some(history);
+            let result = segment.execute_from_pc(pc);
+            history = segment.memory_history.take().unwrap();
+            history.finish(&mut segment.chip_complex.base.memory_controller);
+            match result {
+                Ok(state) if !state.is_terminated => {
+                    assert!(!state.is_paused, "memory history does not support pauses");
+                    memory = segment.final_memory.take().unwrap();
+                    input = segment.chip_complex.take_streams();
+                    pc = state.pc;
+                }
+                result => return (result.map(|_| ()), history),
+            }
+        }
+    }
+
     /// Executes a program until a segmentation happens.
     /// Returns the last segment and the vm state for next segment.
     /// This is so that the tracegen and proving of this segment can be immediately started (on a
@@ -258,6 +568,8 @@ where
             self.trace_height_constraints.clone(),
             exe.fn_bounds.clone(),
         );
+        segment.function_profile = from_state.function_profile;
+        segment.max_instructions = self.max_instructions;
         #[cfg(feature = "bench-metrics")]
         {
             segment.metrics = from_state.metrics;
@@ -271,6 +583,7 @@ where
             return Ok(VmExecutorOneSegmentResult {
                 segment,
                 next_state: None,
+                is_paused: false,
             });
         }
 
@@ -291,42 +604,91 @@ where
         let metrics = segment.metrics.partial_take();
         Ok(VmExecutorOneSegmentResult {
             segment,
+            is_paused: state.is_paused,
             next_state: Some(VmExecutorNextSegmentState {
                 memory: final_memory,
                 input: streams,
                 pc: state.pc,
+                function_profile: segment.function_profile.take(),
                 #[cfg(feature = "bench-metrics")]
                 metrics,
             }),
         })
     }
 
+    /// Executes the program to termination, and returns the final memory together with the
+    /// number of instructions executed, in total and per opcode.
     pub fn execute(
         &self,
         exe: impl Into<VmExe<F>>,
         input: impl Into<Streams<F>>,
-    ) -> Result<Option<VmMemoryState<F>>, ExecutionError> {
+    ) -> Result<ExecutionResult<F>, ExecutionError> {
+        self.execute_phases(exe, input, |_| vec![])
+    }
+
+    /// Executes a program which runs in phases to termination like [Self::execute], supplying
+    /// more input at every pause with `on_pause`, see [Self::execute_phases_and_then].
+    pub fn execute_phases(
+        &self,
+        exe: impl Into<VmExe<F>>,
+        input: impl Into<Streams<F>>,
+        on_pause: impl FnMut(&VmMemoryState<F>) -> Vec<Vec<F>>,
+    ) -> Result<ExecutionResult<F>, ExecutionError> {
         let mut last = None;
-        self.execute_and_then(
+        let mut opcode_counts = OpcodeCounts::new();
+        let mut utilization = vec![];
+        let cycles_per_segment = self.execute_phases_and_then(
             exe,
             input,
-            |_, seg| {
+            on_pause,
+            |_, mut seg| {
+                let cycles: usize = seg
+                    .chip_complex
+                    .program_chip()
+                    .execution_frequencies
+                    .iter()
+                    .sum();
+                for (extension, counts) in seg.opcode_counts() {
+                    let extension_counts = opcode_counts.entry(extension).or_default();
+                    for (opcode_name, count) in counts {
+                        *extension_counts.entry(opcode_name).or_default() += count;
+                    }
+                }
+                utilization.push(seg.utilization());
                 last = Some(seg);
-                Ok(())
+                Ok(cycles)
             },
             |err| err,
         )?;
-        let last = last.expect("at least one segment must be executed");
+        let mut last = last.expect("at least one segment must be executed");
+        let streams = last.chip_complex.take_streams();
         let final_memory = last.final_memory;
+        let function_profile = last.function_profile;
         let end_state =
             last.chip_complex.connector_chip().boundary_states[1].expect("end state must be set");
         if end_state.is_terminate != 1 {
             return Err(ExecutionError::DidNotTerminate);
         }
         if end_state.exit_code != ExitCode::Success as u32 {
-            return Err(ExecutionError::FailedWithExitCode(end_state.exit_code));
+            return Err(match streams.panic_message {
+                Some(message) => ExecutionError::Panicked {
+                    exit_code: end_state.exit_code,
+                    message,
+                },
+                None => ExecutionError::FailedWithExitCode(end_state.exit_code),
+            });
         }
-        Ok(final_memory)
+        Ok(ExecutionResult {
+            final_memory,
+            total_cycles: cycles_per_segment.into_iter().sum(),
+            opcode_counts,
+            utilization,
+            output: streams.output,
+            heap_profile: streams.heap_profile,
+            channel_transcript: streams.channels.into_transcript(),
+            function_profile,
+            region_profile: streams.region_profile,
+        })
     }
 
     pub fn execute_and_generate<SC: StarkGenericConfig>(
@@ -371,13 +733,15 @@ where
         VC::Periphery: Chip<SC>,
     {
         let mut final_memory = None;
+        let mut output = None;
         let per_segment = self.execute_and_then(
             exe,
             input,
             |seg_idx, mut seg| {
-                // Note: this will only be Some on the last segment; otherwise it is
+                // Note: these will only be Some on the last segment; otherwise they are
                 // already moved into next segment state
                 final_memory = mem::take(&mut seg.final_memory);
+                output = seg.chip_complex.take_streams().output;
                 tracing::info_span!("trace_gen", segment = seg_idx)
                     .in_scope(|| seg.generate_proof_input(committed_program.clone()))
             },
@@ -387,6 +751,7 @@ where
         Ok(VmExecutorResult {
             per_segment,
             final_memory,
+            output,
         })
     }
 
@@ -498,7 +863,7 @@ where
         let mut segment = ExecutionSegment::new(
             &self.config,
             exe.program.clone(),
-            input.into(),
+            input.into().with_overlays(&exe.overlays),
             None,
             self.trace_height_constraints.clone(),
             exe.fn_bounds.clone(),
@@ -614,7 +979,7 @@ where
         &self,
         exe: impl Into<VmExe<F>>,
         input: impl Into<Streams<F>>,
-    ) -> Result<Option<VmMemoryState<F>>, ExecutionError> {
+    ) -> Result<ExecutionResult<F>, ExecutionError> {
         self.executor.execute(exe, input)
     }
 
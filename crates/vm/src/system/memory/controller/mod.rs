@@ -4,6 +4,7 @@ use std::{
     iter,
     marker::PhantomData,
     mem,
+    ops::Range,
     sync::{Arc, Mutex},
 };
 
@@ -15,6 +16,7 @@ use openvm_circuit_primitives::{
     var_range::{SharedVariableRangeCheckerChip, VariableRangeCheckerBus},
     TraceSubRowGenerator,
 };
+use openvm_instructions::riscv::RV32_MEMORY_AS;
 use openvm_stark_backend::{
     config::{Domain, StarkGenericConfig},
     interaction::PermutationCheckBus,
@@ -43,7 +45,7 @@ use crate::{
             MemoryBaseAuxCols, MemoryBridge, MemoryBus, MemoryReadAuxCols,
             MemoryReadOrImmediateAuxCols, MemoryWriteAuxCols, AUX_LEN,
         },
-        online::{Memory, MemoryLogEntry},
+        online::{Memory, MemoryLogEntry, MemoryUndoEntry},
         persistent::PersistentBoundaryChip,
         tree::MemoryNode,
     },
@@ -104,6 +106,10 @@ pub struct MemoryController<F> {
     pub access_adapters: AccessAdapterInventory<F>,
     // Filled during finalization.
     final_state: Option<FinalState<F>>,
+    /// Pointers of RV32 memory whose reads and writes are recorded, see [Self::set_stack_guard].
+    stack_guard: Option<Range<u32>>,
+    /// Pointer of the first access into `stack_guard` which was not taken yet.
+    stack_guard_access: Option<u32>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -260,6 +266,8 @@ impl<F: PrimeField32> MemoryController<F> {
             range_checker,
             range_checker_bus,
             final_state: None,
+            stack_guard: None,
+            stack_guard_access: None,
         }
     }
 
@@ -311,6 +319,8 @@ impl<F: PrimeField32> MemoryController<F> {
             range_checker,
             range_checker_bus,
             final_state: None,
+            stack_guard: None,
+            stack_guard_access: None,
         }
     }
 
@@ -374,6 +384,32 @@ impl<F: PrimeField32> MemoryController<F> {
         )
     }
 
+    /// Records the reads and writes of RV32 memory at pointers within `stack_guard`, the stack
+    /// guard region of the guest, to be returned by [Self::take_stack_guard_access]. The
+    /// accesses are still performed, and the check is not constrained.
+    pub fn set_stack_guard(&mut self, stack_guard: Range<u32>) {
+        self.stack_guard = Some(stack_guard);
+    }
+
+    /// Returns the pointer of the first access into the stack guard region since the last call,
+    /// if any, see [Self::set_stack_guard].
+    pub fn take_stack_guard_access(&mut self) -> Option<u32> {
+        self.stack_guard_access.take()
+    }
+
+    fn check_stack_guard(&mut self, address_space: u32, pointer: u32, len: usize) {
+        let Some(stack_guard) = &self.stack_guard else {
+            return;
+        };
+        if address_space == RV32_MEMORY_AS
+            && self.stack_guard_access.is_none()
+            && pointer < stack_guard.end
+            && stack_guard.start < pointer + len as u32
+        {
+            self.stack_guard_access = Some(pointer.max(stack_guard.start));
+        }
+    }
+
     pub fn read_cell(&mut self, address_space: F, pointer: F) -> (RecordId, F) {
         let (record_id, [data]) = self.read(address_space, pointer);
         (record_id, data)
@@ -387,6 +423,7 @@ impl<F: PrimeField32> MemoryController<F> {
             "memory out of bounds: {ptr_u32:?}",
         );
 
+        self.check_stack_guard(address_space_u32, ptr_u32, N);
         let (record_id, values) = self.memory.read::<N>(address_space_u32, ptr_u32);
 
         (record_id, values)
@@ -430,6 +467,7 @@ impl<F: PrimeField32> MemoryController<F> {
             "memory out of bounds: {ptr_u32:?}",
         );
 
+        self.check_stack_guard(address_space_u32, ptr_u32, N);
         self.memory.write(address_space_u32, ptr_u32, data)
     }
 
@@ -556,6 +594,27 @@ impl<F: PrimeField32> MemoryController<F> {
         };
     }
 
+    /// Drops the memory images of the segment, which trace generation does not need once the
+    /// memory is finalized. [Self::memory_image] is empty afterwards.
+    pub fn release_memory_images(&mut self) {
+        assert!(
+            self.final_state.is_some(),
+            "memory must be finalized before its images are released"
+        );
+        let as_offset = self.mem_config.as_offset;
+        let empty = move || AddressMap {
+            paged_vecs: vec![],
+            as_offset,
+        };
+        self.memory.data = empty();
+        if let MemoryInterface::Persistent { initial_memory, .. } = &mut self.interface_chip {
+            *initial_memory = empty();
+        }
+        if let Some(FinalState::Persistent(state)) = &mut self.final_state {
+            state.final_memory = Default::default();
+        }
+    }
+
     pub fn generate_air_proof_inputs<SC: StarkGenericConfig>(self) -> Vec<AirProofInput<SC>>
     where
         Domain<SC>: PolynomialSpace<Val = F>,
@@ -713,6 +772,20 @@ impl<F: PrimeField32> MemoryController<F> {
     pub fn take_memory_logs(&mut self) -> Vec<MemoryLogEntry<F>> {
         std::mem::take(&mut self.memory.log)
     }
+
+    /// Starts recording every write with the data it overwrites, until the initial memory is set.
+    pub fn enable_undo_log(&mut self) {
+        self.memory.undo_log.get_or_insert_with(Vec::new);
+    }
+
+    /// Returns the writes recorded since the undo log was last taken, oldest first.
+    pub fn take_undo_log(&mut self) -> Vec<MemoryUndoEntry<F>> {
+        self.memory
+            .undo_log
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
 }
 
 pub struct MemoryAuxColsFactory<T> {
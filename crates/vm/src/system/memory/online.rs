@@ -24,6 +24,15 @@ pub enum MemoryLogEntry<T> {
     IncrementTimestampBy(u32),
 }
 
+/// A write to memory together with the data it overwrote, so that the write can be undone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryUndoEntry<T> {
+    pub address_space: u32,
+    pub pointer: u32,
+    pub prev_data: Vec<T>,
+    pub data: Vec<T>,
+}
+
 /// A simple data structure to read to/write from memory.
 ///
 /// Stores a log of memory accesses to reconstruct aspects of memory state for trace generation.
@@ -31,6 +40,8 @@ pub enum MemoryLogEntry<T> {
 pub struct Memory<F> {
     pub(super) data: AddressMap<F, PAGE_SIZE>,
     pub(super) log: Vec<MemoryLogEntry<F>>,
+    /// Writes since the undo log was last taken, if it is enabled.
+    pub(super) undo_log: Option<Vec<MemoryUndoEntry<F>>>,
     timestamp: u32,
 }
 
@@ -40,6 +51,7 @@ impl<F: PrimeField32> Memory<F> {
             data: AddressMap::from_mem_config(mem_config),
             timestamp: INITIAL_TIMESTAMP + 1,
             log: Vec::with_capacity(mem_config.access_capacity),
+            undo_log: None,
         }
     }
 
@@ -49,6 +61,7 @@ impl<F: PrimeField32> Memory<F> {
             data: image,
             timestamp: INITIAL_TIMESTAMP + 1,
             log: Vec::with_capacity(access_capacity),
+            undo_log: None,
         }
     }
 
@@ -68,6 +81,14 @@ impl<F: PrimeField32> Memory<F> {
         assert!(N.is_power_of_two());
 
         let prev_data = self.data.set_range(&(address_space, pointer), &values);
+        if let Some(undo_log) = &mut self.undo_log {
+            undo_log.push(MemoryUndoEntry {
+                address_space,
+                pointer,
+                prev_data: prev_data.to_vec(),
+                data: values.to_vec(),
+            });
+        }
 
         self.log.push(MemoryLogEntry::Write {
             address_space,
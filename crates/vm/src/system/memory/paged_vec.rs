@@ -1,16 +1,180 @@
-use std::{mem::MaybeUninit, ops::Range, ptr};
+use std::{
+    fmt::{self, Debug},
+    iter,
+    mem::{size_of, MaybeUninit},
+    ops::{Deref, DerefMut, Range},
+    ptr::{self, NonNull},
+    sync::Arc,
+};
 
-use serde::{Deserialize, Serialize};
+use memmap2::MmapMut;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::arch::MemoryConfig;
+use crate::arch::{MemoryBacking, MemoryConfig};
 
 /// (address_space, pointer)
 pub type Address = (u32, u32);
 pub const PAGE_SIZE: usize = 1 << 12;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PagedVec<T, const PAGE_SIZE: usize> {
-    pub pages: Vec<Option<Vec<T>>>,
+    pub pages: Vec<Option<Page<T>>>,
+    /// The file the pages are mapped from, if the pages are stored on disk. Deserialized vectors
+    /// are stored on the heap.
+    #[serde(skip)]
+    file: Option<Arc<PageFile>>,
+}
+
+/// A page of a [PagedVec], allocated on the heap or mapped from the file of the vector.
+pub struct Page<T>(PageData<T>);
+
+enum PageData<T> {
+    Heap(Vec<T>),
+    Mapped {
+        ptr: NonNull<T>,
+        len: usize,
+        _file: Arc<PageFile>,
+    },
+}
+
+// SAFETY: a mapped page owns its elements, which no other page overlaps.
+unsafe impl<T: Send> Send for Page<T> {}
+unsafe impl<T: Sync> Sync for Page<T> {}
+
+impl<T> Deref for Page<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match &self.0 {
+            PageData::Heap(values) => values,
+            // SAFETY: the elements were initialized when the page was created, and the mapping
+            // lives as long as the page.
+            PageData::Mapped { ptr, len, .. } => unsafe {
+                std::slice::from_raw_parts(ptr.as_ptr(), *len)
+            },
+        }
+    }
+}
+
+impl<T> DerefMut for Page<T> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match &mut self.0 {
+            PageData::Heap(values) => values,
+            // SAFETY: as in `deref`, and the page is borrowed mutably.
+            PageData::Mapped { ptr, len, .. } => unsafe {
+                std::slice::from_raw_parts_mut(ptr.as_ptr(), *len)
+            },
+        }
+    }
+}
+
+impl<T> Drop for PageData<T> {
+    fn drop(&mut self) {
+        if let PageData::Mapped { ptr, len, .. } = self {
+            // SAFETY: the elements are initialized and dropped only here.
+            unsafe { ptr::drop_in_place(ptr::slice_from_raw_parts_mut(ptr.as_ptr(), *len)) };
+        }
+    }
+}
+
+impl<T: Debug> Debug for Page<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.deref().fmt(f)
+    }
+}
+
+// Pages are serialized like vectors, whether they are mapped or not.
+impl<T: Serialize> Serialize for Page<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.deref().serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for Page<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::deserialize(deserializer).map(|values| Page(PageData::Heap(values)))
+    }
+}
+
+/// A sparse temporary file mapped into memory. Only the pages which have been touched take up
+/// space on disk, and the operating system may write them back to the file and evict them from
+/// RAM.
+#[derive(Debug)]
+struct PageFile {
+    map: MmapMut,
+    base: NonNull<u8>,
+}
+
+// SAFETY: `base` points into `map`, and the pages allocated from it do not overlap.
+unsafe impl Send for PageFile {}
+unsafe impl Sync for PageFile {}
+
+impl PageFile {
+    fn new(len: usize) -> Self {
+        let file = tempfile::tempfile().expect("failed to create the file of memory pages");
+        file.set_len(len as u64)
+            .expect("failed to resize the file of memory pages");
+        // SAFETY: the file is unlinked, so no other process can modify it.
+        let mut map =
+            unsafe { MmapMut::map_mut(&file) }.expect("failed to map the file of memory pages");
+        let base = NonNull::new(map.as_mut_ptr()).expect("mapping is not null");
+        Self { map, base }
+    }
+}
+
+/// Creates page `index` with `values`, mapped from `file` if there is one.
+fn new_page<T, const PAGE_SIZE: usize>(
+    file: Option<&Arc<PageFile>>,
+    index: usize,
+    values: impl Iterator<Item = T>,
+) -> Page<T> {
+    let Some(file) = file else {
+        return Page(PageData::Heap(values.collect()));
+    };
+    let offset = index * PAGE_SIZE * size_of::<T>();
+    assert!(offset + PAGE_SIZE * size_of::<T>() <= file.map.len());
+    // SAFETY: the page is within the mapping, which is aligned to the pages of the operating
+    // system, and pages of index `index` are created at most once per file.
+    let ptr = unsafe { file.base.add(offset).cast::<T>() };
+    let mut len = 0;
+    for value in values.take(PAGE_SIZE) {
+        unsafe { ptr.as_ptr().add(len).write(value) };
+        len += 1;
+    }
+    assert_eq!(len, PAGE_SIZE);
+    Page(PageData::Mapped {
+        ptr,
+        len,
+        _file: file.clone(),
+    })
+}
+
+fn default_page<T: Default + Clone, const PAGE_SIZE: usize>(
+    file: Option<&Arc<PageFile>>,
+    index: usize,
+) -> Page<T> {
+    new_page::<_, PAGE_SIZE>(file, index, iter::repeat_n(T::default(), PAGE_SIZE))
+}
+
+impl<T: Clone, const PAGE_SIZE: usize> Clone for PagedVec<T, PAGE_SIZE> {
+    /// Clones the vector into a new file if it is stored on disk.
+    fn clone(&self) -> Self {
+        let file = self
+            .file
+            .as_ref()
+            .map(|file| Arc::new(PageFile::new(file.map.len())));
+        let pages = self
+            .pages
+            .iter()
+            .enumerate()
+            .map(|(index, page)| {
+                page.as_ref().map(|page| {
+                    new_page::<_, PAGE_SIZE>(file.as_ref(), index, page.iter().cloned())
+                })
+            })
+            .collect();
+        Self { pages, file }
+    }
 }
 
 // ------------------------------------------------------------------
@@ -58,26 +222,27 @@ impl<T: Default + Clone, const PAGE_SIZE: usize> PagedVec<T, PAGE_SIZE> {
     fn set_range_generic(&mut self, start: usize, len: usize, new: *const T, dst: *mut T) {
         let start_page = start / PAGE_SIZE;
         let end_page = (start + len - 1) / PAGE_SIZE;
+        let file = self.file.as_ref();
         unsafe {
             if start_page == end_page {
                 let offset = start % PAGE_SIZE;
-                let page =
-                    self.pages[start_page].get_or_insert_with(|| vec![T::default(); PAGE_SIZE]);
+                let page = self.pages[start_page]
+                    .get_or_insert_with(|| default_page::<_, PAGE_SIZE>(file, start_page));
                 ptr::copy_nonoverlapping(page.as_ptr().add(offset), dst, len);
                 ptr::copy_nonoverlapping(new, page.as_mut_ptr().add(offset), len);
             } else {
                 let offset = start % PAGE_SIZE;
                 let first_part = PAGE_SIZE - offset;
                 {
-                    let page =
-                        self.pages[start_page].get_or_insert_with(|| vec![T::default(); PAGE_SIZE]);
+                    let page = self.pages[start_page]
+                        .get_or_insert_with(|| default_page::<_, PAGE_SIZE>(file, start_page));
                     ptr::copy_nonoverlapping(page.as_ptr().add(offset), dst, first_part);
                     ptr::copy_nonoverlapping(new, page.as_mut_ptr().add(offset), first_part);
                 }
                 let second_part = len - first_part;
                 {
-                    let page =
-                        self.pages[end_page].get_or_insert_with(|| vec![T::default(); PAGE_SIZE]);
+                    let page = self.pages[end_page]
+                        .get_or_insert_with(|| default_page::<_, PAGE_SIZE>(file, end_page));
                     ptr::copy_nonoverlapping(page.as_ptr(), dst.add(first_part), second_part);
                     ptr::copy_nonoverlapping(new.add(first_part), page.as_mut_ptr(), second_part);
                 }
@@ -91,10 +256,26 @@ impl<T: Default + Clone, const PAGE_SIZE: usize> PagedVec<T, PAGE_SIZE> {
 impl<T: Default + Clone, const PAGE_SIZE: usize> PagedVec<T, PAGE_SIZE> {
     pub fn new(num_pages: usize) -> Self {
         Self {
-            pages: vec![None; num_pages],
+            pages: iter::repeat_with(|| None).take(num_pages).collect(),
+            file: None,
+        }
+    }
+
+    /// Creates a vector whose pages are mapped from a sparse temporary file, see
+    /// [MemoryBacking::Disk].
+    pub fn new_on_disk(num_pages: usize) -> Self {
+        Self {
+            file: Some(Arc::new(PageFile::new(
+                num_pages * PAGE_SIZE * size_of::<T>(),
+            ))),
+            ..Self::new(num_pages)
         }
     }
 
+    pub fn is_on_disk(&self) -> bool {
+        self.file.is_some()
+    }
+
     pub fn get(&self, index: usize) -> Option<&T> {
         let page_idx = index / PAGE_SIZE;
         self.pages[page_idx]
@@ -114,7 +295,9 @@ impl<T: Default + Clone, const PAGE_SIZE: usize> PagedVec<T, PAGE_SIZE> {
         if let Some(page) = self.pages[page_idx].as_mut() {
             Some(std::mem::replace(&mut page[index % PAGE_SIZE], value))
         } else {
-            let page = self.pages[page_idx].get_or_insert_with(|| vec![T::default(); PAGE_SIZE]);
+            let file = self.file.as_ref();
+            let page = self.pages[page_idx]
+                .get_or_insert_with(|| default_page::<_, PAGE_SIZE>(file, page_idx));
             page[index % PAGE_SIZE] = value;
             None
         }
@@ -241,16 +424,31 @@ impl<T: Clone + Default, const PAGE_SIZE: usize> Default for AddressMap<T, PAGE_
 
 impl<T: Clone + Default, const PAGE_SIZE: usize> AddressMap<T, PAGE_SIZE> {
     pub fn new(as_offset: u32, as_cnt: usize, mem_size: usize) -> Self {
+        Self::new_with_backing(as_offset, as_cnt, mem_size, MemoryBacking::Heap)
+    }
+    pub fn new_with_backing(
+        as_offset: u32,
+        as_cnt: usize,
+        mem_size: usize,
+        backing: MemoryBacking,
+    ) -> Self {
+        let num_pages = mem_size.div_ceil(PAGE_SIZE);
         Self {
-            paged_vecs: vec![PagedVec::new(mem_size.div_ceil(PAGE_SIZE)); as_cnt],
+            paged_vecs: (0..as_cnt)
+                .map(|_| match backing {
+                    MemoryBacking::Heap => PagedVec::new(num_pages),
+                    MemoryBacking::Disk => PagedVec::new_on_disk(num_pages),
+                })
+                .collect(),
             as_offset,
         }
     }
     pub fn from_mem_config(mem_config: &MemoryConfig) -> Self {
-        Self::new(
+        Self::new_with_backing(
             mem_config.as_offset,
             1 << mem_config.as_height,
             1 << mem_config.pointer_max_bits,
+            mem_config.backing,
         )
     }
     pub fn items(&self) -> impl Iterator<Item = (Address, T)> + '_ {
@@ -282,10 +480,16 @@ impl<T: Clone + Default, const PAGE_SIZE: usize> AddressMap<T, PAGE_SIZE> {
         iter: impl IntoIterator<Item = (Address, T)>,
     ) -> Self {
         let mut vec = Self::new(as_offset, as_cnt, mem_size);
+        vec.extend(iter);
+        vec
+    }
+}
+
+impl<T: Clone + Default, const PAGE_SIZE: usize> Extend<(Address, T)> for AddressMap<T, PAGE_SIZE> {
+    fn extend<I: IntoIterator<Item = (Address, T)>>(&mut self, iter: I) {
         for (address, data) in iter {
-            vec.insert(&address, data);
+            self.insert(&address, data);
         }
-        vec
     }
 }
 
@@ -444,4 +648,25 @@ mod tests {
         assert_eq!(contents[6], (10, 0));
         assert_eq!(contents[7], (11, 0));
     }
+
+    #[test]
+    fn test_on_disk() {
+        // Pages of 4 KiB, the size of the pages of the operating system.
+        let mut v = PagedVec::<u32, 1024>::new_on_disk(3);
+        assert!(v.is_on_disk());
+        assert!(v.is_empty());
+        v.set_range(1020..1030, &[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+        assert_eq!(
+            v.range_vec(1018..1032),
+            [0, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 0, 0]
+        );
+        assert!(v.pages[2].is_none());
+
+        let w = v.clone();
+        assert!(w.is_on_disk());
+        v.set(1020, 42);
+        assert_eq!(v.get(1020), Some(&42));
+        assert_eq!(w.get(1020), Some(&1));
+        assert_eq!(w.iter().count(), 2048);
+    }
 }
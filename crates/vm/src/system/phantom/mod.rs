@@ -93,6 +93,7 @@ pub struct PhantomChip<F> {
     pub rows: Vec<PhantomCols<F>>,
     streams: OnceLock<Arc<Mutex<Streams<F>>>>,
     phantom_executors: FxHashMap<PhantomDiscriminant, Box<dyn PhantomSubExecutor<F>>>,
+    hints_enabled: bool,
 }
 
 impl<F> PhantomChip<F> {
@@ -105,9 +106,16 @@ impl<F> PhantomChip<F> {
             rows: vec![],
             streams: OnceLock::new(),
             phantom_executors: FxHashMap::default(),
+            hints_enabled: true,
         }
     }
 
+    /// When hints are disabled, sub-instructions which [provide
+    /// hints](PhantomSubExecutor::provides_hints) fail with [ExecutionError::HintsDisabled].
+    pub fn set_hints_enabled(&mut self, hints_enabled: bool) {
+        self.hints_enabled = hints_enabled;
+    }
+
     pub fn set_streams(&mut self, streams: Arc<Mutex<Streams<F>>>) {
         if self.streams.set(streams).is_err() {
             panic!("Streams should only be set once");
@@ -148,6 +156,12 @@ impl<F: PrimeField32> InstructionExecutor<F> for PhantomChip<F> {
                     pc: from_state.pc,
                     discriminant,
                 })?;
+            if !self.hints_enabled && sub_executor.provides_hints() {
+                return Err(ExecutionError::HintsDisabled {
+                    pc: from_state.pc,
+                    discriminant,
+                });
+            }
             let mut streams = self.streams.get().unwrap().lock().unwrap();
             sub_executor
                 .as_mut()
@@ -7,9 +7,10 @@ use std::{
 use openvm_circuit::{
     arch::{
         hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
-        ChipId, ExecutionSegment, MemoryConfig, SingleSegmentVmExecutor, SystemConfig,
-        SystemTraceHeights, VirtualMachine, VmComplexTraceHeights, VmConfig,
-        VmInventoryTraceHeights,
+        AdaptiveSegmentationStrategy, ChipId, DefaultSegmentationStrategy, ExecutionError,
+        ExecutionSegment, MemoryConfig, SegmentationStrategy, SingleSegmentVmExecutor,
+        SystemConfig, SystemTraceHeights, VirtualMachine, VmComplexTraceHeights, VmConfig,
+        VmExecutor, VmInventoryTraceHeights, PROGRAM_AIR_ID, SYSTEM_EXTENSION_NAME,
     },
     system::{
         memory::{MemoryTraceHeights, VolatileMemoryTraceHeights, CHUNK},
@@ -18,7 +19,7 @@ use openvm_circuit::{
     utils::{air_test, air_test_with_min_segments},
 };
 use openvm_instructions::{
-    exe::VmExe,
+    exe::{FnBound, VmExe},
     instruction::Instruction,
     program::{Program, DEFAULT_PC_STEP},
     LocalOpcode, PhantomDiscriminant,
@@ -111,6 +112,278 @@ fn test_vm_1() {
     air_test(test_native_config(), program);
 }
 
+#[test]
+fn test_vm_opcode_counts() {
+    let n = 6;
+    // The countdown loop of `test_vm_1`.
+    let instructions = vec![
+        Instruction::large_from_isize(ADD.global_opcode(), 0, n, 0, 4, 0, 0, 0),
+        Instruction::from_isize(
+            NativeBranchEqualOpcode(BEQ).global_opcode(),
+            0,
+            0,
+            3 * DEFAULT_PC_STEP as isize,
+            4,
+            0,
+        ),
+        Instruction::large_from_isize(SUB.global_opcode(), 0, 0, 1, 4, 4, 0, 0),
+        Instruction::from_isize(
+            JAL.global_opcode(),
+            2,
+            -2 * DEFAULT_PC_STEP as isize,
+            0,
+            4,
+            0,
+        ),
+        Instruction::from_isize(TERMINATE.global_opcode(), 0, 0, 0, 0, 0),
+    ];
+    let program = Program::from_instructions(&instructions);
+
+    let executor = VmExecutor::<BabyBear, _>::new(test_native_config());
+    let result = executor.execute(program, vec![]).unwrap();
+    let n = n as usize;
+    assert_eq!(result.total_cycles, 3 * n + 3);
+    let expected = BTreeMap::from([
+        (
+            "Native".to_string(),
+            BTreeMap::from([
+                ("ADD".to_string(), 1),
+                ("BEQ".to_string(), n + 1),
+                ("SUB".to_string(), n),
+                ("JAL".to_string(), n),
+            ]),
+        ),
+        (
+            SYSTEM_EXTENSION_NAME.to_string(),
+            BTreeMap::from([("TERMINATE".to_string(), 1)]),
+        ),
+    ]);
+    assert_eq!(result.opcode_counts, expected);
+}
+
+#[test]
+fn test_vm_utilization() {
+    let program = Program::from_instructions(&[
+        Instruction::large_from_isize(ADD.global_opcode(), 0, 4, 0, 4, 0, 0, 0),
+        Instruction::large_from_isize(SUB.global_opcode(), 0, 0, 1, 4, 4, 0, 0),
+        Instruction::from_isize(TERMINATE.global_opcode(), 0, 0, 0, 0, 0),
+    ]);
+
+    let executor = VmExecutor::<BabyBear, _>::new(test_native_config());
+    let result = executor.execute(program, vec![]).unwrap();
+    let [utilization] = &result.utilization[..] else {
+        panic!("expected a single segment");
+    };
+    assert_eq!(utilization.cycles, 3);
+    // The program chip has a row per instruction, whether executed or not.
+    let program_chip = &utilization.chips[PROGRAM_AIR_ID];
+    assert!(program_chip.fixed_height);
+    assert_eq!((program_chip.rows, program_chip.padded_height()), (3, 4));
+    let dominating = utilization.dominating_chip().unwrap();
+    assert!(!dominating.fixed_height && dominating.rows > 0);
+    // The traces are too short for their padding to matter.
+    assert!(utilization.suggestions().is_empty());
+}
+
+#[test]
+fn test_vm_utilization_suggestions() {
+    // Writes 1 to 1100 distinct cells, which fill 53% of the padded height of 2048 of both the
+    // field arithmetic chip and the memory boundary chip.
+    let num_cells = 1100;
+    let mut instructions: Vec<_> = (0..num_cells)
+        .map(|i| Instruction::large_from_isize(ADD.global_opcode(), i, 1, 0, 4, 0, 0, 0))
+        .collect();
+    instructions.push(Instruction::from_isize(
+        TERMINATE.global_opcode(),
+        0,
+        0,
+        0,
+        0,
+        0,
+    ));
+    let program = Program::from_instructions(&instructions);
+
+    let executor = VmExecutor::<BabyBear, _>::new(test_native_config());
+    let result = executor.execute(program, vec![]).unwrap();
+    let [utilization] = &result.utilization[..] else {
+        panic!("expected a single segment");
+    };
+    // The boundary chip only has rows once the memory is finalized.
+    let boundary = utilization
+        .chips
+        .iter()
+        .find(|chip| chip.air_name == "Boundary")
+        .unwrap();
+    assert_eq!(boundary.rows, num_cells as usize);
+    let suggestions = utilization.suggestions();
+    assert_eq!(suggestions.len(), 1);
+    assert!(suggestions[0].contains("fills 53% of its padded height of 2048"));
+}
+
+#[test]
+fn test_vm_function_profile() {
+    // `main` calls `f`, which jumps back into `main`.
+    let program = Program::from_instructions(&[
+        Instruction::large_from_isize(ADD.global_opcode(), 0, 4, 0, 4, 0, 0, 0),
+        Instruction::from_isize(
+            JAL.global_opcode(),
+            2,
+            3 * DEFAULT_PC_STEP as isize,
+            0,
+            4,
+            0,
+        ),
+        Instruction::large_from_isize(SUB.global_opcode(), 0, 0, 1, 4, 4, 0, 0),
+        Instruction::from_isize(TERMINATE.global_opcode(), 0, 0, 0, 0, 0),
+        Instruction::large_from_isize(ADD.global_opcode(), 1, 1, 0, 4, 0, 0, 0),
+        Instruction::from_isize(
+            JAL.global_opcode(),
+            2,
+            -3 * DEFAULT_PC_STEP as isize,
+            0,
+            4,
+            0,
+        ),
+    ]);
+    let function = |name: &str, start: u32, end: u32| {
+        let (start, end) = (start * DEFAULT_PC_STEP, end * DEFAULT_PC_STEP);
+        let name = name.to_string();
+        (start, FnBound { start, end, name })
+    };
+    let mut exe = VmExe::from(program);
+    exe.fn_bounds = BTreeMap::from([function("main", 0, 3), function("f", 4, 5)]);
+
+    let mut executor = VmExecutor::<BabyBear, _>::new(test_native_config());
+    executor.set_function_profiling(true);
+    let result = executor.execute(exe, vec![]).unwrap();
+    let profile = result.function_profile.unwrap();
+    // TERMINATE is not handled by an executor.
+    assert_eq!(profile.total_cycles(), 5);
+    let stacks: Vec<_> = (0..profile.frames().len())
+        .map(|idx| {
+            let stack = profile.call_stack(idx);
+            let names: Vec<_> = stack.iter().map(|&f| profile.function_name(f)).collect();
+            (names.join(";"), profile.frames()[idx].self_cycles)
+        })
+        .collect();
+    assert_eq!(stacks, [("main".to_string(), 3), ("main;f".to_string(), 2)]);
+    let functions: Vec<_> = profile
+        .functions()
+        .into_iter()
+        .map(|f| (f.name, f.self_cycles, f.total_cycles))
+        .collect();
+    assert_eq!(
+        functions,
+        [("main".to_string(), 3, 5), ("f".to_string(), 2, 2)]
+    );
+}
+
+#[test]
+fn test_vm_max_instructions() {
+    // Loops forever, over several segments.
+    let program = Program::from_instructions(&[
+        Instruction::large_from_isize(ADD.global_opcode(), 0, 1, 0, 4, 0, 0, 0),
+        Instruction::from_isize(JAL.global_opcode(), 2, -(DEFAULT_PC_STEP as isize), 0, 4, 0),
+    ]);
+    let mut config = test_native_continuations_config();
+    config.system = config.system.with_max_segment_len(200);
+
+    let mut executor = VmExecutor::<BabyBear, _>::new(config);
+    executor.set_max_instructions(Some(1000));
+    let mut num_segments = 0;
+    let result = executor.execute_and_then(
+        program,
+        vec![],
+        |_, _| {
+            num_segments += 1;
+            Ok(())
+        },
+        |err| err,
+    );
+    assert!(matches!(
+        result,
+        Err(ExecutionError::InstructionLimitExceeded {
+            pc: 0,
+            max_instructions: 1000
+        })
+    ));
+    assert!(num_segments > 1);
+}
+
+#[test]
+fn test_vm_adaptive_segmentation() {
+    // A countdown loop adding to a cell 8 times per iteration, so that the field arithmetic chip
+    // dominates the segments.
+    let n = 2000;
+    let adds_per_iteration = 8;
+    let mut instructions = vec![
+        Instruction::large_from_isize(ADD.global_opcode(), 0, n, 0, 4, 0, 0, 0),
+        Instruction::from_isize(
+            NativeBranchEqualOpcode(BEQ).global_opcode(),
+            0,
+            0,
+            (3 + adds_per_iteration) * DEFAULT_PC_STEP as isize,
+            4,
+            0,
+        ),
+    ];
+    instructions.extend(
+        (0..adds_per_iteration)
+            .map(|_| Instruction::large_from_isize(ADD.global_opcode(), 1, 1, 1, 4, 4, 0, 0)),
+    );
+    instructions.extend([
+        Instruction::large_from_isize(SUB.global_opcode(), 0, 0, 1, 4, 4, 0, 0),
+        Instruction::from_isize(
+            JAL.global_opcode(),
+            2,
+            -(2 + adds_per_iteration) * DEFAULT_PC_STEP as isize,
+            0,
+            4,
+            0,
+        ),
+        Instruction::from_isize(TERMINATE.global_opcode(), 0, 0, 0, 0, 0),
+    ]);
+    let program = Program::from_instructions(&instructions);
+
+    // The total padded cells of the chips whose height depends on the execution, over all
+    // segments.
+    let padded_cells = |strategy: Arc<dyn SegmentationStrategy>| {
+        let mut config = test_native_continuations_config();
+        config.system.set_segmentation_strategy(strategy);
+        let executor = VmExecutor::<BabyBear, _>::new(config);
+        let per_segment = executor
+            .execute_and_then(
+                program.clone(),
+                vec![],
+                |_, mut seg| {
+                    let utilization = seg.utilization();
+                    let cells = seg.current_trace_cells();
+                    Ok(zip(&utilization.chips, cells)
+                        .filter(|(chip, _)| !chip.fixed_height && chip.rows > 0)
+                        .map(|(chip, cells)| cells / chip.rows * chip.padded_height())
+                        .sum::<usize>())
+                },
+                |err| err,
+            )
+            .unwrap();
+        assert!(per_segment.len() > 1);
+        per_segment.into_iter().sum::<usize>()
+    };
+    // The default strategy stops the field arithmetic chip a little above 3000 rows, which are
+    // padded to 4096, while the adaptive one stops it a little below 2048.
+    let default = padded_cells(Arc::new(
+        DefaultSegmentationStrategy::new_with_max_segment_len(3000),
+    ));
+    let adaptive = padded_cells(Arc::new(AdaptiveSegmentationStrategy::new(
+        3000,
+        3000 * 120,
+    )));
+    assert!(
+        adaptive * 10 < default * 9,
+        "adaptive segmentation should pad at least 10% fewer cells: {adaptive} vs {default}"
+    );
+}
+
 #[test]
 fn test_vm_override_executor_height() {
     let e = BabyBearPoseidon2Engine::new(FriParameters::standard_fast());
@@ -326,10 +599,58 @@ fn test_vm_initial_memory() {
         pc_start: 0,
         init_memory,
         fn_bounds: Default::default(),
+        overlays: Default::default(),
+        source_map: Default::default(),
     };
     air_test(config, exe);
 }
 
+#[test]
+fn test_vm_memory_history() {
+    // Program that sets mem[(4, 0)] to 5, then to 7, and then fails.
+    let program = Program::from_instructions(&[
+        Instruction::large_from_isize(ADD.global_opcode(), 0, 5, 0, 4, 0, 0, 0),
+        Instruction::large_from_isize(ADD.global_opcode(), 1, 3, 0, 4, 0, 0, 0),
+        Instruction::large_from_isize(ADD.global_opcode(), 0, 7, 0, 4, 0, 0, 0),
+        Instruction::<BabyBear>::from_isize(
+            PHANTOM.global_opcode(),
+            0,
+            0,
+            SysPhantom::DebugPanic as isize,
+            0,
+            0,
+        ),
+    ]);
+
+    let executor = VmExecutor::<BabyBear, _>::new(test_native_continuations_config());
+    // The memory is reconstructed from the undo log alone with an interval of 4, and from the
+    // diffs of complete intervals as well with smaller ones.
+    for snapshot_interval in [4, 2, 1] {
+        let (result, history) =
+            executor.execute_with_memory_history(program.clone(), vec![], snapshot_interval);
+        assert!(matches!(result, Err(ExecutionError::Fail { pc }) if pc == 3 * DEFAULT_PC_STEP));
+        assert_eq!(history.num_steps(), 3);
+        assert_eq!(history.step(2).pc, 2 * DEFAULT_PC_STEP);
+        assert_eq!(history.last_write_before(3, 4, 0), Some(2));
+        assert_eq!(history.last_write_before(2, 4, 0), Some(0));
+        assert_eq!(history.last_write_before(3, 4, 2), None);
+
+        let cell = |step: usize, pointer: u32| {
+            history
+                .memory_before(step)
+                .get(&(4, pointer))
+                .copied()
+                .unwrap_or_default()
+        };
+        let expected =
+            [[0, 0], [5, 0], [5, 3], [7, 3]].map(|cells| cells.map(BabyBear::from_canonical_u32));
+        for (step, [value, other]) in expected.into_iter().enumerate() {
+            assert_eq!(cell(step, 0), value);
+            assert_eq!(cell(step, 1), other);
+        }
+    }
+}
+
 #[test]
 fn test_vm_1_persistent() {
     let engine = BabyBearPoseidon2Engine::new(FriParameters::standard_fast());
@@ -84,3 +84,47 @@ impl InitFileGenerator for Rv32ModularWithFp2Config {
         ))
     }
 }
+
+#[derive(Clone, Debug, VmConfig, Serialize, Deserialize)]
+pub struct Rv32ModularWithFp3Config {
+    #[system]
+    pub system: SystemConfig,
+    #[extension]
+    pub base: Rv32I,
+    #[extension]
+    pub mul: Rv32M,
+    #[extension]
+    pub io: Rv32Io,
+    #[extension]
+    pub modular: ModularExtension,
+    #[extension]
+    pub fp3: Fp3Extension,
+}
+
+impl Rv32ModularWithFp3Config {
+    /// Takes `(name, modulus, non_residue)` for each cubic extension field.
+    pub fn new(moduli_with_names: Vec<(String, BigUint, isize)>) -> Self {
+        let moduli = moduli_with_names
+            .iter()
+            .map(|(_, modulus, _)| modulus.clone())
+            .collect();
+        Self {
+            system: SystemConfig::default().with_continuations(),
+            base: Default::default(),
+            mul: Default::default(),
+            io: Default::default(),
+            modular: ModularExtension::new(moduli),
+            fp3: Fp3Extension::new(moduli_with_names),
+        }
+    }
+}
+
+impl InitFileGenerator for Rv32ModularWithFp3Config {
+    fn generate_init_file_contents(&self) -> Option<String> {
+        Some(format!(
+            "// This file is automatically generated by cargo openvm. Do not rename or edit.\n{}\n{}\n",
+            self.modular.generate_moduli_init(),
+            self.fp3.generate_cubic_init(&self.modular)
+        ))
+    }
+}
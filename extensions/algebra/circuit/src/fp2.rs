@@ -2,28 +2,47 @@ use std::{cell::RefCell, rc::Rc};
 
 use openvm_mod_circuit_builder::{ExprBuilder, FieldVariable, SymbolicExpr};
 
-/// Quadratic field extension of `Fp` defined by `Fp2 = Fp[u]/(1 + u^2)`. Assumes that `-1` is not a
-/// quadratic residue in `Fp`, which is equivalent to `p` being congruent to `3 (mod 4)`.
+/// Quadratic field extension of `Fp` defined by `Fp2 = Fp[u]/(u^2 - non_residue)`. Assumes that
+/// `non_residue` is not a quadratic residue in `Fp`, so that `X^2 - non_residue` is irreducible.
+/// The default non-residue is `-1`, which is a non-residue if and only if `p` is congruent to
+/// `3 (mod 4)`.
 /// Extends Mod Builder to work with Fp2 variables.
 #[derive(Clone)]
 pub struct Fp2 {
     pub c0: FieldVariable,
     pub c1: FieldVariable,
+    pub non_residue: isize,
 }
 
 impl Fp2 {
     pub fn new(builder: Rc<RefCell<ExprBuilder>>) -> Self {
+        Self::new_with_non_residue(builder, -1)
+    }
+
+    pub fn new_with_non_residue(builder: Rc<RefCell<ExprBuilder>>, non_residue: isize) -> Self {
         let c0 = ExprBuilder::new_input(builder.clone());
         let c1 = ExprBuilder::new_input(builder.clone());
-        Fp2 { c0, c1 }
+        Fp2 {
+            c0,
+            c1,
+            non_residue,
+        }
     }
 
     pub fn new_var(builder: Rc<RefCell<ExprBuilder>>) -> ((usize, usize), Fp2) {
+        Self::new_var_with_non_residue(builder, -1)
+    }
+
+    pub fn new_var_with_non_residue(
+        builder: Rc<RefCell<ExprBuilder>>,
+        non_residue: isize,
+    ) -> ((usize, usize), Fp2) {
         let (c0_idx, c0) = builder.borrow_mut().new_var();
         let (c1_idx, c1) = builder.borrow_mut().new_var();
         let fp2 = Fp2 {
             c0: FieldVariable::from_var(builder.clone(), c0),
             c1: FieldVariable::from_var(builder.clone(), c1),
+            non_residue,
         };
         ((c0_idx, c1_idx), fp2)
     }
@@ -40,32 +59,63 @@ impl Fp2 {
     }
 
     pub fn add(&mut self, other: &mut Fp2) -> Fp2 {
+        assert_eq!(self.non_residue, other.non_residue);
         Fp2 {
             c0: &mut self.c0 + &mut other.c0,
             c1: &mut self.c1 + &mut other.c1,
+            non_residue: self.non_residue,
         }
     }
 
     pub fn sub(&mut self, other: &mut Fp2) -> Fp2 {
+        assert_eq!(self.non_residue, other.non_residue);
         Fp2 {
             c0: &mut self.c0 - &mut other.c0,
             c1: &mut self.c1 - &mut other.c1,
+            non_residue: self.non_residue,
         }
     }
 
     pub fn mul(&mut self, other: &mut Fp2) -> Fp2 {
-        let c0 = &mut self.c0 * &mut other.c0 - &mut self.c1 * &mut other.c1;
+        assert_eq!(self.non_residue, other.non_residue);
+        let c0 = if self.non_residue == -1 {
+            &mut self.c0 * &mut other.c0 - &mut self.c1 * &mut other.c1
+        } else {
+            &mut self.c0 * &mut other.c0 + (&mut self.c1 * &mut other.c1).int_mul(self.non_residue)
+        };
         let c1 = &mut self.c0 * &mut other.c1 + &mut self.c1 * &mut other.c0;
-        Fp2 { c0, c1 }
+        Fp2 {
+            c0,
+            c1,
+            non_residue: self.non_residue,
+        }
     }
 
     pub fn square(&mut self) -> Fp2 {
-        let c0 = self.c0.square() - self.c1.square();
+        let c0 = if self.non_residue == -1 {
+            self.c0.square() - self.c1.square()
+        } else {
+            self.c0.square() + self.c1.square().int_mul(self.non_residue)
+        };
         let c1 = (&mut self.c0 * &mut self.c1).int_mul(2);
-        Fp2 { c0, c1 }
+        Fp2 {
+            c0,
+            c1,
+            non_residue: self.non_residue,
+        }
+    }
+
+    /// Returns `a - non_residue * b`.
+    fn sub_non_residue_mul(&self, a: SymbolicExpr, b: SymbolicExpr) -> SymbolicExpr {
+        if self.non_residue == -1 {
+            a + b
+        } else {
+            a - SymbolicExpr::IntMul(Box::new(b), self.non_residue)
+        }
     }
 
     pub fn div(&mut self, other: &mut Fp2) -> Fp2 {
+        assert_eq!(self.non_residue, other.non_residue);
         let builder = self.c0.builder.borrow();
         let prime = builder.prime.clone();
         let limb_bits = builder.limb_bits;
@@ -81,54 +131,54 @@ impl Fp2 {
         // Compute should not be affected by whether auto save is triggered.
         // So we must do compute first.
         // Compute z0
-        let compute_denom = &other.c0.expr * &other.c0.expr + &other.c1.expr * &other.c1.expr;
-        let compute_z0_nom = &self.c0.expr * &other.c0.expr + &self.c1.expr * &other.c1.expr;
+        let compute_denom = self.sub_non_residue_mul(
+            &other.c0.expr * &other.c0.expr,
+            &other.c1.expr * &other.c1.expr,
+        );
+        let compute_z0_nom = self.sub_non_residue_mul(
+            &self.c0.expr * &other.c0.expr,
+            &self.c1.expr * &other.c1.expr,
+        );
         let compute_z0 = &compute_z0_nom / &compute_denom;
         // Compute z1
         let compute_z1_nom = &self.c1.expr * &other.c0.expr - &self.c0.expr * &other.c1.expr;
         let compute_z1 = &compute_z1_nom / &compute_denom;
 
         // We will constrain
-        //  (1) x0 = y0*z0 - y1*z1 and
+        //  (1) x0 = y0*z0 + n*y1*z1 and
         //  (2) x1 = y1*z0 + y0*z1
-        // which implies z0 and z1 are computed as above.
-        // Observe (1)*y0 + (2)*y1 yields x0*y0 + x1*y1 = z0(y0^2 + y1^2) and so z0 = (x0*y0 +
-        // x1*y1) / (y0^2 + y1^2) as needed. Observe (1)*(-y1) + (2)*y0 yields x1*y0 - x0*y1
-        // = z1(y0^2 + y1^2) and so z1 = (x1*y0 - x0*y1) / (y0^2 + y1^2) as needed.
-
-        // Constraint 1: x0 = y0*z0 - y1*z1
-        let constraint1 = &self.c0.expr - &other.c0.expr * &fake_z0 + &other.c1.expr * &fake_z1;
-        let carry_bits =
-            constraint1.constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs, &proper_max);
-        if carry_bits > self.c0.max_carry_bits {
-            self.save();
-        }
-        let constraint1 = &self.c0.expr - &other.c0.expr * &fake_z0 + &other.c1.expr * &fake_z1;
-        let carry_bits =
-            constraint1.constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs, &proper_max);
-        if carry_bits > self.c0.max_carry_bits {
-            other.save();
-        }
-
+        // where n is the non-residue, which implies z0 and z1 are computed as above.
+        // Observe (1)*y0 - (2)*n*y1 yields x0*y0 - n*x1*y1 = z0(y0^2 - n*y1^2) and so z0 = (x0*y0 -
+        // n*x1*y1) / (y0^2 - n*y1^2) as needed. Observe (1)*(-y1) + (2)*y0 yields x1*y0 - x0*y1
+        // = z1(y0^2 - n*y1^2) and so z1 = (x1*y0 - x0*y1) / (y0^2 - n*y1^2) as needed.
+
+        // Constraint 1: x0 = y0*z0 + n*y1*z1
+        let constraint1 = |x: &Fp2, y: &Fp2, z0: &SymbolicExpr, z1: &SymbolicExpr| {
+            x.sub_non_residue_mul(&x.c0.expr - &y.c0.expr * z0, &y.c1.expr * z1)
+        };
         // Constraint 2: x1 = y1*z0 + y0*z1
-        let constraint2 = &self.c1.expr - &other.c1.expr * &fake_z0 - &other.c0.expr * &fake_z1;
-        let carry_bits =
-            constraint2.constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs, &proper_max);
-        if carry_bits > self.c0.max_carry_bits {
-            self.save();
-        }
-        let constraint2 = &self.c1.expr - &other.c1.expr * &fake_z0 - &other.c0.expr * &fake_z1;
-        let carry_bits =
-            constraint2.constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs, &proper_max);
-        if carry_bits > self.c0.max_carry_bits {
-            other.save();
+        let constraint2 = |x: &Fp2, y: &Fp2, z0: &SymbolicExpr, z1: &SymbolicExpr| {
+            &x.c1.expr - &y.c1.expr * z0 - &y.c0.expr * z1
+        };
+
+        for constraint in [constraint1, constraint2] {
+            let carry_bits = constraint(self, other, &fake_z0, &fake_z1)
+                .constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs, &proper_max);
+            if carry_bits > self.c0.max_carry_bits {
+                self.save();
+            }
+            let carry_bits = constraint(self, other, &fake_z0, &fake_z1)
+                .constraint_carry_bits_with_pq(&prime, limb_bits, num_limbs, &proper_max);
+            if carry_bits > self.c0.max_carry_bits {
+                other.save();
+            }
         }
 
         let mut builder = self.c0.builder.borrow_mut();
         let (z0_idx, z0) = builder.new_var();
         let (z1_idx, z1) = builder.new_var();
-        let constraint1 = &self.c0.expr - &other.c0.expr * &z0 + &other.c1.expr * &z1;
-        let constraint2 = &self.c1.expr - &other.c1.expr * &z0 - &other.c0.expr * &z1;
+        let constraint1 = constraint1(self, other, &z0, &z1);
+        let constraint2 = constraint2(self, other, &z0, &z1);
         builder.set_compute(z0_idx, compute_z0);
         builder.set_compute(z1_idx, compute_z1);
         builder.set_constraint(z0_idx, constraint1);
@@ -140,6 +190,7 @@ impl Fp2 {
         Fp2 {
             c0: z0_var,
             c1: z1_var,
+            non_residue: self.non_residue,
         }
     }
 
@@ -147,6 +198,7 @@ impl Fp2 {
         Fp2 {
             c0: &mut self.c0 * fp,
             c1: &mut self.c1 * fp,
+            non_residue: self.non_residue,
         }
     }
 
@@ -154,14 +206,21 @@ impl Fp2 {
         Fp2 {
             c0: self.c0.int_add(c[0]),
             c1: self.c1.int_add(c[1]),
+            non_residue: self.non_residue,
         }
     }
 
     // c is like a Fp2, but with both c0 and c1 being very small numbers.
     pub fn int_mul(&mut self, c: [isize; 2]) -> Fp2 {
+        let c0 = if self.non_residue == -1 {
+            self.c0.int_mul(c[0]) - self.c1.int_mul(c[1])
+        } else {
+            self.c0.int_mul(c[0]) + self.c1.int_mul(c[1] * self.non_residue)
+        };
         Fp2 {
-            c0: self.c0.int_mul(c[0]) - self.c1.int_mul(c[1]),
+            c0,
             c1: self.c0.int_mul(c[1]) + self.c1.int_mul(c[0]),
+            non_residue: self.non_residue,
         }
     }
 
@@ -170,9 +229,11 @@ impl Fp2 {
     }
 
     pub fn select(flag_id: usize, a: &Fp2, b: &Fp2) -> Fp2 {
+        assert_eq!(a.non_residue, b.non_residue);
         Fp2 {
             c0: FieldVariable::select(flag_id, &a.c0, &b.c0),
             c1: FieldVariable::select(flag_id, &a.c1, &b.c1),
+            non_residue: a.non_residue,
         }
     }
 }
@@ -314,4 +375,54 @@ mod tests {
         )
         .expect("Verification failed");
     }
+
+    #[test]
+    fn test_fp2_non_residue() {
+        let prime = BN254_MODULUS.clone();
+        let non_residue = -5;
+        let (range_checker, builder) = setup(&prime);
+
+        let mut x_fp2 = Fp2::new_with_non_residue(builder.clone(), non_residue);
+        let mut y_fp2 = Fp2::new_with_non_residue(builder.clone(), non_residue);
+        let mut xy = x_fp2.mul(&mut y_fp2);
+        xy.save();
+        let _r = x_fp2.div(&mut y_fp2);
+
+        let builder = builder.borrow().clone();
+        let air = FieldExpr::new(builder, range_checker.bus(), false);
+        let width = BaseAir::<BabyBear>::width(&air);
+
+        let inputs = two_fp2_input(&bn254_fq2_random(3), &bn254_fq2_random(7));
+        let [x0, x1, y0, y1] = inputs.clone().try_into().unwrap();
+        let nr = &prime - BigUint::from(5u32);
+        let expected_mul = [
+            (&x0 * &y0 + &nr * &x1 * &y1) % &prime,
+            (&x0 * &y1 + &x1 * &y0) % &prime,
+        ];
+        let norm = (&y0 * &y0 + (&prime - &nr) * &y1 * &y1) % &prime;
+        let norm_inv = norm.modpow(&(&prime - 2u32), &prime);
+        let expected_div = [
+            (&x0 * &y0 + (&prime - &nr) * &x1 * &y1) % &prime * &norm_inv % &prime,
+            (&x1 * &y0 + &prime - &x0 * &y1 % &prime) % &prime * &norm_inv % &prime,
+        ];
+
+        let mut row = BabyBear::zero_vec(width);
+        air.generate_subrow((&range_checker, inputs, vec![]), &mut row);
+        let FieldExprCols { vars, .. } = air.load_vars(&row);
+        let trace = RowMajorMatrix::new(row, width);
+        let range_trace = range_checker.generate_trace();
+        assert_eq!(vars.len(), 4);
+        let vars: Vec<_> = vars
+            .iter()
+            .map(|var| evaluate_biguint(var, LIMB_BITS))
+            .collect();
+        assert_eq!(vars[..2], expected_mul);
+        assert_eq!(vars[2..], expected_div);
+
+        BabyBearBlake3Engine::run_simple_test_no_pis_fast(
+            any_rap_arc_vec![air, range_checker.air],
+            vec![trace, range_trace],
+        )
+        .expect("Verification failed");
+    }
 }
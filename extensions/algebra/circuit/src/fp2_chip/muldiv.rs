@@ -4,6 +4,7 @@ use std::{
     sync::{Arc, Mutex},
 };
 
+use num_bigint::BigUint;
 use openvm_algebra_transpiler::Fp2Opcode;
 use openvm_circuit::{arch::VmChipWrapper, system::memory::OfflineMemory};
 use openvm_circuit_derive::InstructionExecutor;
@@ -36,11 +37,13 @@ impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
     pub fn new(
         adapter: Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
         config: ExprBuilderConfig,
+        non_residue: isize,
         offset: usize,
         range_checker: SharedVariableRangeCheckerChip,
         offline_memory: Arc<Mutex<OfflineMemory<F>>>,
     ) -> Self {
-        let (expr, is_mul_flag, is_div_flag) = fp2_muldiv_expr(config, range_checker.bus());
+        let (expr, is_mul_flag, is_div_flag) =
+            fp2_muldiv_expr(config, non_residue, range_checker.bus());
         let core = FieldExpressionCoreChip::new(
             expr,
             offset,
@@ -60,24 +63,39 @@ impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
 
 pub fn fp2_muldiv_expr(
     config: ExprBuilderConfig,
+    non_residue: isize,
     range_bus: VariableRangeCheckerBus,
 ) -> (FieldExpr, usize, usize) {
     config.check_valid();
+    // The setup instruction also checks the non-residue, so that a guest cannot use the chip for a
+    // different quadratic extension than the one it was configured for.
+    let non_residue_mod_p = if non_residue < 0 {
+        &config.modulus - BigUint::from(non_residue.unsigned_abs())
+    } else {
+        BigUint::from(non_residue.unsigned_abs())
+    };
     let builder = ExprBuilder::new(config, range_bus.range_max_bits);
     let builder = Rc::new(RefCell::new(builder));
 
-    let x = Fp2::new(builder.clone());
-    let mut y = Fp2::new(builder.clone());
+    let x = Fp2::new_with_non_residue(builder.clone(), non_residue);
+    let mut y = Fp2::new_with_non_residue(builder.clone(), non_residue);
     let is_mul_flag = builder.borrow_mut().new_flag();
     let is_div_flag = builder.borrow_mut().new_flag();
-    let (z_idx, mut z) = Fp2::new_var(builder.clone());
+    let (z_idx, mut z) = Fp2::new_var_with_non_residue(builder.clone(), non_residue);
 
     let mut lvar = Fp2::select(is_mul_flag, &x, &z);
 
-    let mut rvar = Fp2::select(is_mul_flag, &z, &x);
+    let setup_x = Fp2 {
+        c0: x.c0.clone(),
+        c1: x.c0.clone(),
+        non_residue,
+    };
+    let div_or_setup_x = Fp2::select(is_div_flag, &x, &setup_x);
+    let mut rvar = Fp2::select(is_mul_flag, &z, &div_or_setup_x);
     let fp2_constraint = lvar.mul(&mut y).sub(&mut rvar);
-    // When it's SETUP op, the constraints is z * y - x = 0, it still works as:
-    // x.c0 = x.c1 = p == 0, y.c0 = y.c1 = 0, so whatever z is, z * 0 - 0 = 0
+    // When it's SETUP op, the constraints is z * y - (x.c0, x.c0) = 0, it still works as:
+    // x.c0 = p == 0, y.c0 = y.c1 = 0, so whatever z is, z * 0 - 0 = 0. Note that x.c1 holds the
+    // non-residue, which is nonzero, so it cannot appear in the constraint.
 
     z.save_output();
     builder
@@ -89,9 +107,22 @@ pub fn fp2_muldiv_expr(
 
     // Compute expression has to be done manually at the SymbolicExpr level.
     // Otherwise it saves the quotient and introduces new variables.
-    let compute_z0_div = (&x.c0.expr * &y.c0.expr + &x.c1.expr * &y.c1.expr)
-        / (&y.c0.expr * &y.c0.expr + &y.c1.expr * &y.c1.expr);
-    let compute_z0_mul = &x.c0.expr * &y.c0.expr - &x.c1.expr * &y.c1.expr;
+    let (compute_z0_mul, compute_z0_div_nom, compute_denom) = if non_residue == -1 {
+        (
+            &x.c0.expr * &y.c0.expr - &x.c1.expr * &y.c1.expr,
+            &x.c0.expr * &y.c0.expr + &x.c1.expr * &y.c1.expr,
+            &y.c0.expr * &y.c0.expr + &y.c1.expr * &y.c1.expr,
+        )
+    } else {
+        let non_residue_mul =
+            |expr: SymbolicExpr| SymbolicExpr::IntMul(Box::new(expr), non_residue);
+        (
+            &x.c0.expr * &y.c0.expr + non_residue_mul(&x.c1.expr * &y.c1.expr),
+            &x.c0.expr * &y.c0.expr - non_residue_mul(&x.c1.expr * &y.c1.expr),
+            &y.c0.expr * &y.c0.expr - non_residue_mul(&y.c1.expr * &y.c1.expr),
+        )
+    };
+    let compute_z0_div = &compute_z0_div_nom / &compute_denom;
     let compute_z0 = SymbolicExpr::Select(
         is_mul_flag,
         Box::new(compute_z0_mul),
@@ -101,8 +132,7 @@ pub fn fp2_muldiv_expr(
             Box::new(x.c0.expr.clone()),
         )),
     );
-    let compute_z1_div = (&x.c1.expr * &y.c0.expr - &x.c0.expr * &y.c1.expr)
-        / (&y.c0.expr * &y.c0.expr + &y.c1.expr * &y.c1.expr);
+    let compute_z1_div = (&x.c1.expr * &y.c0.expr - &x.c0.expr * &y.c1.expr) / compute_denom;
     let compute_z1_mul = &x.c1.expr * &y.c0.expr + &x.c0.expr * &y.c1.expr;
     let compute_z1 = SymbolicExpr::Select(
         is_mul_flag,
@@ -118,7 +148,7 @@ pub fn fp2_muldiv_expr(
 
     let builder = builder.borrow().clone();
     (
-        FieldExpr::new(builder, range_bus, true),
+        FieldExpr::new_with_setup_values(builder, range_bus, true, vec![non_residue_mod_p]),
         is_mul_flag,
         is_div_flag,
     )
@@ -172,6 +202,7 @@ mod tests {
         let mut chip = Fp2MulDivChip::new(
             adapter,
             config,
+            -1,
             Fp2Opcode::CLASS_OFFSET,
             tester.range_checker(),
             tester.offline_memory_mutex_arc(),
@@ -221,12 +252,14 @@ mod tests {
                     .map(BabyBear::from_canonical_u32)
             })
             .collect_vec();
+        let non_residue = biguint_to_limbs::<NUM_LIMBS>(&modulus - 1u32, LIMB_BITS)
+            .map(BabyBear::from_canonical_u32);
         let modulus =
             biguint_to_limbs::<NUM_LIMBS>(modulus, LIMB_BITS).map(BabyBear::from_canonical_u32);
         let zero = [BabyBear::ZERO; NUM_LIMBS];
         let setup_instruction = rv32_write_heap_default(
             &mut tester,
-            vec![modulus, zero],
+            vec![modulus, non_residue],
             vec![zero; 2],
             chip.0.core.air.offset + Fp2Opcode::SETUP_MULDIV as usize,
         );
@@ -30,9 +30,23 @@ pub struct Fp2Extension {
     // name must match the struct name defined by complex_declare
     #[serde_as(as = "Vec<(_, DisplayFromStr)>")]
     pub supported_moduli: Vec<(String, BigUint)>,
+    // (name, non_residue)
+    // non_residue must match the one passed to complex_declare, which defaults to -1
+    #[new(default)]
+    #[serde(default)]
+    pub non_residues: Vec<(String, isize)>,
 }
 
 impl Fp2Extension {
+    /// Returns the non-residue `n` defining the quadratic extension `Fp[X]/(X^2 - n)` declared
+    /// under `name`.
+    pub fn non_residue(&self, name: &str) -> isize {
+        self.non_residues
+            .iter()
+            .find(|(n, _)| n == name)
+            .map_or(-1, |(_, non_residue)| *non_residue)
+    }
+
     pub fn generate_complex_init(&self, modular_config: &ModularExtension) -> String {
         fn get_index_of_modulus(modulus: &BigUint, modular_config: &ModularExtension) -> usize {
             modular_config
@@ -80,6 +94,10 @@ impl<F: PrimeField32> VmExtension<F> for Fp2Extension {
     type Executor = Fp2ExtensionExecutor<F>;
     type Periphery = Fp2ExtensionPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("fp2", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -108,7 +126,8 @@ impl<F: PrimeField32> VmExtension<F> for Fp2Extension {
         let addsub_opcodes = (Fp2Opcode::ADD as usize)..=(Fp2Opcode::SETUP_ADDSUB as usize);
         let muldiv_opcodes = (Fp2Opcode::MUL as usize)..=(Fp2Opcode::SETUP_MULDIV as usize);
 
-        for (i, (_, modulus)) in self.supported_moduli.iter().enumerate() {
+        for (i, (name, modulus)) in self.supported_moduli.iter().enumerate() {
+            let non_residue = self.non_residue(name);
             // determine the number of bytes needed to represent a prime field element
             let bytes = modulus.bits().div_ceil(8);
             let start_offset = Fp2Opcode::CLASS_OFFSET + i * Fp2Opcode::COUNT;
@@ -155,6 +174,7 @@ impl<F: PrimeField32> VmExtension<F> for Fp2Extension {
                 let muldiv_chip = Fp2MulDivChip::new(
                     adapter_chip_32.clone(),
                     config32.clone(),
+                    non_residue,
                     start_offset,
                     range_checker.clone(),
                     offline_memory.clone(),
@@ -182,6 +202,7 @@ impl<F: PrimeField32> VmExtension<F> for Fp2Extension {
                 let muldiv_chip = Fp2MulDivChip::new(
                     adapter_chip_48.clone(),
                     config48.clone(),
+                    non_residue,
                     start_offset,
                     range_checker.clone(),
                     offline_memory.clone(),
@@ -0,0 +1,124 @@
+use std::{cell::RefCell, rc::Rc};
+
+use openvm_mod_circuit_builder::{ExprBuilder, FieldVariable};
+
+/// Cubic field extension of `Fp` defined by `Fp3 = Fp[w]/(w^3 - non_residue)`. Assumes that
+/// `non_residue` is not a cube in `Fp`, so that `X^3 - non_residue` is irreducible.
+/// Extends Mod Builder to work with Fp3 variables.
+#[derive(Clone)]
+pub struct Fp3 {
+    pub c0: FieldVariable,
+    pub c1: FieldVariable,
+    pub c2: FieldVariable,
+    pub non_residue: isize,
+}
+
+impl Fp3 {
+    pub fn new(builder: Rc<RefCell<ExprBuilder>>, non_residue: isize) -> Self {
+        let c0 = ExprBuilder::new_input(builder.clone());
+        let c1 = ExprBuilder::new_input(builder.clone());
+        let c2 = ExprBuilder::new_input(builder.clone());
+        Fp3 {
+            c0,
+            c1,
+            c2,
+            non_residue,
+        }
+    }
+
+    pub fn new_var(
+        builder: Rc<RefCell<ExprBuilder>>,
+        non_residue: isize,
+    ) -> ((usize, usize, usize), Fp3) {
+        let (c0_idx, c0) = builder.borrow_mut().new_var();
+        let (c1_idx, c1) = builder.borrow_mut().new_var();
+        let (c2_idx, c2) = builder.borrow_mut().new_var();
+        let fp3 = Fp3 {
+            c0: FieldVariable::from_var(builder.clone(), c0),
+            c1: FieldVariable::from_var(builder.clone(), c1),
+            c2: FieldVariable::from_var(builder.clone(), c2),
+            non_residue,
+        };
+        ((c0_idx, c1_idx, c2_idx), fp3)
+    }
+
+    pub fn save(&mut self) -> [usize; 3] {
+        let c0_idx = self.c0.save();
+        let c1_idx = self.c1.save();
+        let c2_idx = self.c2.save();
+        [c0_idx, c1_idx, c2_idx]
+    }
+
+    pub fn save_output(&mut self) {
+        self.c0.save_output();
+        self.c1.save_output();
+        self.c2.save_output();
+    }
+
+    pub fn add(&mut self, other: &mut Fp3) -> Fp3 {
+        assert_eq!(self.non_residue, other.non_residue);
+        Fp3 {
+            c0: &mut self.c0 + &mut other.c0,
+            c1: &mut self.c1 + &mut other.c1,
+            c2: &mut self.c2 + &mut other.c2,
+            non_residue: self.non_residue,
+        }
+    }
+
+    pub fn sub(&mut self, other: &mut Fp3) -> Fp3 {
+        assert_eq!(self.non_residue, other.non_residue);
+        Fp3 {
+            c0: &mut self.c0 - &mut other.c0,
+            c1: &mut self.c1 - &mut other.c1,
+            c2: &mut self.c2 - &mut other.c2,
+            non_residue: self.non_residue,
+        }
+    }
+
+    pub fn mul(&mut self, other: &mut Fp3) -> Fp3 {
+        assert_eq!(self.non_residue, other.non_residue);
+        let n = self.non_residue;
+        let c0 = &mut self.c0 * &mut other.c0
+            + (&mut self.c1 * &mut other.c2 + &mut self.c2 * &mut other.c1).int_mul(n);
+        let c1 = &mut self.c0 * &mut other.c1
+            + &mut self.c1 * &mut other.c0
+            + (&mut self.c2 * &mut other.c2).int_mul(n);
+        let c2 = &mut self.c0 * &mut other.c2
+            + &mut self.c1 * &mut other.c1
+            + &mut self.c2 * &mut other.c0;
+        Fp3 {
+            c0,
+            c1,
+            c2,
+            non_residue: n,
+        }
+    }
+
+    pub fn scalar_mul(&mut self, fp: &mut FieldVariable) -> Fp3 {
+        Fp3 {
+            c0: &mut self.c0 * fp,
+            c1: &mut self.c1 * fp,
+            c2: &mut self.c2 * fp,
+            non_residue: self.non_residue,
+        }
+    }
+
+    pub fn neg(&mut self) -> Fp3 {
+        Fp3 {
+            c0: self.c0.int_mul(-1),
+            c1: self.c1.int_mul(-1),
+            c2: self.c2.int_mul(-1),
+            non_residue: self.non_residue,
+        }
+    }
+
+    pub fn select(flag_id: usize, a: &Fp3, b: &Fp3) -> Fp3 {
+        assert_eq!(a.non_residue, b.non_residue);
+        Fp3 {
+            c0: FieldVariable::select(flag_id, &a.c0, &b.c0),
+            c1: FieldVariable::select(flag_id, &a.c1, &b.c1),
+            c2: FieldVariable::select(flag_id, &a.c2, &b.c2),
+            non_residue: a.non_residue,
+        }
+    }
+}
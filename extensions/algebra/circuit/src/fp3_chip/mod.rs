@@ -0,0 +1,5 @@
+mod addsub;
+pub use addsub::*;
+
+mod muldiv;
+pub use muldiv::*;
@@ -0,0 +1,285 @@
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, Mutex},
+};
+
+use openvm_algebra_transpiler::Fp3Opcode;
+use openvm_circuit::{arch::VmChipWrapper, system::memory::OfflineMemory};
+use openvm_circuit_derive::InstructionExecutor;
+use openvm_circuit_primitives::var_range::{
+    SharedVariableRangeCheckerChip, VariableRangeCheckerBus,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_mod_circuit_builder::{
+    ExprBuilder, ExprBuilderConfig, FieldExpr, FieldExpressionCoreChip, SymbolicExpr,
+};
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+use openvm_stark_backend::p3_field::PrimeField32;
+
+use crate::Fp3;
+
+// Input: Fp3 * 2
+// Output: Fp3
+#[derive(Chip, ChipUsageGetter, InstructionExecutor)]
+pub struct Fp3MulDivChip<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>(
+    pub  VmChipWrapper<
+        F,
+        Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        FieldExpressionCoreChip,
+    >,
+);
+
+impl<F: PrimeField32, const BLOCKS: usize, const BLOCK_SIZE: usize>
+    Fp3MulDivChip<F, BLOCKS, BLOCK_SIZE>
+{
+    pub fn new(
+        adapter: Rv32VecHeapAdapterChip<F, 2, BLOCKS, BLOCKS, BLOCK_SIZE, BLOCK_SIZE>,
+        config: ExprBuilderConfig,
+        non_residue: isize,
+        offset: usize,
+        range_checker: SharedVariableRangeCheckerChip,
+        offline_memory: Arc<Mutex<OfflineMemory<F>>>,
+    ) -> Self {
+        let (expr, is_mul_flag, is_div_flag) =
+            fp3_muldiv_expr(config, non_residue, range_checker.bus());
+        let core = FieldExpressionCoreChip::new(
+            expr,
+            offset,
+            vec![
+                Fp3Opcode::MUL as usize,
+                Fp3Opcode::DIV as usize,
+                Fp3Opcode::SETUP_MULDIV as usize,
+            ],
+            vec![is_mul_flag, is_div_flag],
+            range_checker,
+            "Fp3MulDiv",
+            false,
+        );
+        Self(VmChipWrapper::new(adapter, core, offline_memory))
+    }
+}
+
+pub fn fp3_muldiv_expr(
+    config: ExprBuilderConfig,
+    non_residue: isize,
+    range_bus: VariableRangeCheckerBus,
+) -> (FieldExpr, usize, usize) {
+    config.check_valid();
+    let builder = ExprBuilder::new(config, range_bus.range_max_bits);
+    let builder = Rc::new(RefCell::new(builder));
+
+    let x = Fp3::new(builder.clone(), non_residue);
+    let mut y = Fp3::new(builder.clone(), non_residue);
+    let is_mul_flag = builder.borrow_mut().new_flag();
+    let is_div_flag = builder.borrow_mut().new_flag();
+    let (z_idx, mut z) = Fp3::new_var(builder.clone(), non_residue);
+
+    let mut lvar = Fp3::select(is_mul_flag, &x, &z);
+
+    let mut rvar = Fp3::select(is_mul_flag, &z, &x);
+    let fp3_constraint = lvar.mul(&mut y).sub(&mut rvar);
+    // When it's SETUP op, the constraints is z * y - x = 0, it still works as:
+    // x.c0 = x.c1 = x.c2 = p == 0, y = 0, so whatever z is, z * 0 - 0 = 0
+
+    z.save_output();
+    builder
+        .borrow_mut()
+        .set_constraint(z_idx.0, fp3_constraint.c0.expr);
+    builder
+        .borrow_mut()
+        .set_constraint(z_idx.1, fp3_constraint.c1.expr);
+    builder
+        .borrow_mut()
+        .set_constraint(z_idx.2, fp3_constraint.c2.expr);
+
+    // Compute expression has to be done manually at the SymbolicExpr level.
+    // Otherwise it saves the quotient and introduces new variables.
+    let n = non_residue;
+    let int_mul = |expr: SymbolicExpr| SymbolicExpr::IntMul(Box::new(expr), n);
+    let (x0, x1, x2) = (&x.c0.expr, &x.c1.expr, &x.c2.expr);
+    let (y0, y1, y2) = (&y.c0.expr, &y.c1.expr, &y.c2.expr);
+
+    // x / y = x * t / norm(y), where t = t0 + t1 * w + t2 * w^2 satisfies y * t = norm(y).
+    let t0 = y0 * y0 - int_mul(y1 * y2);
+    let t1 = int_mul(y2 * y2) - y0 * y1;
+    let t2 = y1 * y1 - y0 * y2;
+    let norm = y0 * &t0 + int_mul(y2 * &t1 + y1 * &t2);
+
+    let compute_z0_div = (x0 * &t0 + int_mul(x1 * &t2 + x2 * &t1)) / &norm;
+    let compute_z0_mul = x0 * y0 + int_mul(x1 * y2 + x2 * y1);
+    let compute_z0 = SymbolicExpr::Select(
+        is_mul_flag,
+        Box::new(compute_z0_mul),
+        Box::new(SymbolicExpr::Select(
+            is_div_flag,
+            Box::new(compute_z0_div),
+            Box::new(x0.clone()),
+        )),
+    );
+    let compute_z1_div = (x0 * &t1 + x1 * &t0 + int_mul(x2 * &t2)) / &norm;
+    let compute_z1_mul = x0 * y1 + x1 * y0 + int_mul(x2 * y2);
+    let compute_z1 = SymbolicExpr::Select(
+        is_mul_flag,
+        Box::new(compute_z1_mul),
+        Box::new(SymbolicExpr::Select(
+            is_div_flag,
+            Box::new(compute_z1_div),
+            Box::new(x1.clone()),
+        )),
+    );
+    let compute_z2_div = (x0 * &t2 + x1 * &t1 + x2 * &t0) / &norm;
+    let compute_z2_mul = x0 * y2 + x1 * y1 + x2 * y0;
+    let compute_z2 = SymbolicExpr::Select(
+        is_mul_flag,
+        Box::new(compute_z2_mul),
+        Box::new(SymbolicExpr::Select(
+            is_div_flag,
+            Box::new(compute_z2_div),
+            Box::new(x2.clone()),
+        )),
+    );
+    builder.borrow_mut().set_compute(z_idx.0, compute_z0);
+    builder.borrow_mut().set_compute(z_idx.1, compute_z1);
+    builder.borrow_mut().set_compute(z_idx.2, compute_z2);
+
+    let builder = builder.borrow().clone();
+    (
+        FieldExpr::new(builder, range_bus, true),
+        is_mul_flag,
+        is_div_flag,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+
+    use halo2curves_axiom::{bn256::Fq, ff::Field};
+    use itertools::Itertools;
+    use num_bigint::BigUint;
+    use openvm_algebra_transpiler::Fp3Opcode;
+    use openvm_circuit::arch::testing::{VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS};
+    use openvm_circuit_primitives::bitwise_op_lookup::{
+        BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+    };
+    use openvm_instructions::{riscv::RV32_CELL_BITS, LocalOpcode};
+    use openvm_mod_circuit_builder::{
+        test_utils::{biguint_to_limbs, bn254_fq_to_biguint},
+        ExprBuilderConfig,
+    };
+    use openvm_pairing_guest::bn254::BN254_MODULUS;
+    use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapAdapterChip};
+    use openvm_stark_backend::p3_field::FieldAlgebra;
+    use openvm_stark_sdk::p3_baby_bear::BabyBear;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::Fp3MulDivChip;
+
+    const NUM_LIMBS: usize = 32;
+    const LIMB_BITS: usize = 8;
+    // 3 is not a cube modulo the BN254 base field prime
+    const NON_RESIDUE: u32 = 3;
+    type F = BabyBear;
+
+    fn fp3_mul(x: &[BigUint], y: &[BigUint], modulus: &BigUint) -> Vec<BigUint> {
+        let n = BigUint::from(NON_RESIDUE);
+        vec![
+            (&x[0] * &y[0] + &n * (&x[1] * &y[2] + &x[2] * &y[1])) % modulus,
+            (&x[0] * &y[1] + &x[1] * &y[0] + &n * &x[2] * &y[2]) % modulus,
+            (&x[0] * &y[2] + &x[1] * &y[1] + &x[2] * &y[0]) % modulus,
+        ]
+    }
+
+    #[test]
+    fn test_fp3_muldiv() {
+        let mut tester: VmChipTestBuilder<F> = VmChipTestBuilder::default();
+        let modulus = BN254_MODULUS.clone();
+        let config = ExprBuilderConfig {
+            modulus: modulus.clone(),
+            num_limbs: NUM_LIMBS,
+            limb_bits: LIMB_BITS,
+        };
+        let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+        let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+        let adapter = Rv32VecHeapAdapterChip::<F, 2, 3, 3, NUM_LIMBS, NUM_LIMBS>::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        );
+        let mut chip = Fp3MulDivChip::new(
+            adapter,
+            config,
+            NON_RESIDUE as isize,
+            Fp3Opcode::CLASS_OFFSET,
+            tester.range_checker(),
+            tester.offline_memory_mutex_arc(),
+        );
+        assert_eq!(
+            chip.0.core.expr().builder.num_variables,
+            3,
+            "Fp3MulDiv should only introduce new z Fp3 variable (3 Fp var)"
+        );
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let inputs: Vec<BigUint> = (0..6)
+            .map(|_| bn254_fq_to_biguint(Fq::random(&mut rng)))
+            .collect();
+        let (x, y) = inputs.split_at(3);
+
+        let expected_mul = fp3_mul(x, y, &modulus);
+        let r_mul = chip
+            .0
+            .core
+            .expr()
+            .execute_with_output(inputs.clone(), vec![true, false]);
+        assert_eq!(r_mul, expected_mul);
+
+        // x / y is checked by multiplying it back by y
+        let r_div = chip
+            .0
+            .core
+            .expr()
+            .execute_with_output(inputs.clone(), vec![false, true]);
+        assert_eq!(r_div.len(), 3);
+        assert_eq!(fp3_mul(&r_div, y, &modulus), x);
+
+        let to_limbs = |v: &[BigUint]| {
+            v.iter()
+                .map(|x| {
+                    biguint_to_limbs::<NUM_LIMBS>(x.clone(), LIMB_BITS)
+                        .map(BabyBear::from_canonical_u32)
+                })
+                .collect_vec()
+        };
+        let x_limbs = to_limbs(x);
+        let y_limbs = to_limbs(y);
+        let modulus =
+            biguint_to_limbs::<NUM_LIMBS>(modulus, LIMB_BITS).map(BabyBear::from_canonical_u32);
+        let zero = [BabyBear::ZERO; NUM_LIMBS];
+        let setup_instruction = rv32_write_heap_default(
+            &mut tester,
+            vec![modulus, zero, zero],
+            vec![zero; 3],
+            chip.0.core.air.offset + Fp3Opcode::SETUP_MULDIV as usize,
+        );
+        let instruction1 = rv32_write_heap_default(
+            &mut tester,
+            x_limbs.clone(),
+            y_limbs.clone(),
+            chip.0.core.air.offset + Fp3Opcode::MUL as usize,
+        );
+        let instruction2 = rv32_write_heap_default(
+            &mut tester,
+            x_limbs,
+            y_limbs,
+            chip.0.core.air.offset + Fp3Opcode::DIV as usize,
+        );
+        tester.execute(&mut chip, &setup_instruction);
+        tester.execute(&mut chip, &instruction1);
+        tester.execute(&mut chip, &instruction2);
+        let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+        tester.simple_test().expect("Verification failed");
+    }
+}
@@ -1,11 +1,16 @@
 pub mod fp2_chip;
+pub mod fp3_chip;
 pub mod modular_chip;
 
 mod fp2;
 pub use fp2::*;
+mod fp3;
+pub use fp3::*;
 mod modular_extension;
 pub use modular_extension::*;
 mod fp2_extension;
 pub use fp2_extension::*;
+mod fp3_extension;
+pub use fp3_extension::*;
 mod config;
 pub use config::*;
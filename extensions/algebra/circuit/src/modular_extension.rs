@@ -56,6 +56,14 @@ pub enum ModularExtensionExecutor<F: PrimeField32> {
     ModularAddSubRv32_48(ModularAddSubChip<F, 3, 16>),
     ModularMulDivRv32_48(ModularMulDivChip<F, 3, 16>),
     ModularIsEqualRv32_48(ModularIsEqualChip<F, 3, 16, 48>),
+    // 256 limbs prime, e.g. RSA-2048 moduli
+    ModularAddSubRv32_256(ModularAddSubChip<F, 8, 32>),
+    ModularMulDivRv32_256(ModularMulDivChip<F, 8, 32>),
+    ModularIsEqualRv32_256(ModularIsEqualChip<F, 8, 32, 256>),
+    // 512 limbs prime, e.g. RSA-4096 moduli
+    ModularAddSubRv32_512(ModularAddSubChip<F, 16, 32>),
+    ModularMulDivRv32_512(ModularMulDivChip<F, 16, 32>),
+    ModularIsEqualRv32_512(ModularIsEqualChip<F, 16, 32, 512>),
 }
 
 #[derive(ChipUsageGetter, Chip, AnyEnum, From)]
@@ -69,6 +77,10 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
     type Executor = ModularExtensionExecutor<F>;
     type Periphery = ModularExtensionPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("modular", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -117,6 +129,16 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                 num_limbs: 48,
                 limb_bits: 8,
             };
+            let config256 = ExprBuilderConfig {
+                modulus: modulus.clone(),
+                num_limbs: 256,
+                limb_bits: 8,
+            };
+            let config512 = ExprBuilderConfig {
+                modulus: modulus.clone(),
+                num_limbs: 512,
+                limb_bits: 8,
+            };
             let adapter_chip_32 = Rv32VecHeapAdapterChip::new(
                 execution_bus,
                 program_bus,
@@ -131,6 +153,20 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                 address_bits,
                 bitwise_lu_chip.clone(),
             );
+            let adapter_chip_256 = Rv32VecHeapAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            );
+            let adapter_chip_512 = Rv32VecHeapAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            );
 
             if bytes <= 32 {
                 let addsub_chip = ModularAddSubChip::new(
@@ -228,6 +264,104 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
                         .clone()
                         .map(|x| VmOpcode::from_usize(x + start_offset)),
                 )?;
+            } else if bytes <= 256 {
+                assert_range_checker_supports(256, range_checker.range_max_bits());
+                let addsub_chip = ModularAddSubChip::new(
+                    adapter_chip_256.clone(),
+                    config256.clone(),
+                    start_offset,
+                    range_checker.clone(),
+                    offline_memory.clone(),
+                );
+                inventory.add_executor(
+                    ModularExtensionExecutor::ModularAddSubRv32_256(addsub_chip),
+                    addsub_opcodes
+                        .clone()
+                        .map(|x| VmOpcode::from_usize(x + start_offset)),
+                )?;
+                let muldiv_chip = ModularMulDivChip::new(
+                    adapter_chip_256.clone(),
+                    config256.clone(),
+                    start_offset,
+                    range_checker.clone(),
+                    offline_memory.clone(),
+                );
+                inventory.add_executor(
+                    ModularExtensionExecutor::ModularMulDivRv32_256(muldiv_chip),
+                    muldiv_opcodes
+                        .clone()
+                        .map(|x| VmOpcode::from_usize(x + start_offset)),
+                )?;
+                let isequal_chip = ModularIsEqualChip::new(
+                    Rv32IsEqualModAdapterChip::new(
+                        execution_bus,
+                        program_bus,
+                        memory_bridge,
+                        address_bits,
+                        bitwise_lu_chip.clone(),
+                    ),
+                    ModularIsEqualCoreChip::new(
+                        modulus.clone(),
+                        bitwise_lu_chip.clone(),
+                        start_offset,
+                    ),
+                    offline_memory.clone(),
+                );
+                inventory.add_executor(
+                    ModularExtensionExecutor::ModularIsEqualRv32_256(isequal_chip),
+                    iseq_opcodes
+                        .clone()
+                        .map(|x| VmOpcode::from_usize(x + start_offset)),
+                )?;
+            } else if bytes <= 512 {
+                assert_range_checker_supports(512, range_checker.range_max_bits());
+                let addsub_chip = ModularAddSubChip::new(
+                    adapter_chip_512.clone(),
+                    config512.clone(),
+                    start_offset,
+                    range_checker.clone(),
+                    offline_memory.clone(),
+                );
+                inventory.add_executor(
+                    ModularExtensionExecutor::ModularAddSubRv32_512(addsub_chip),
+                    addsub_opcodes
+                        .clone()
+                        .map(|x| VmOpcode::from_usize(x + start_offset)),
+                )?;
+                let muldiv_chip = ModularMulDivChip::new(
+                    adapter_chip_512.clone(),
+                    config512.clone(),
+                    start_offset,
+                    range_checker.clone(),
+                    offline_memory.clone(),
+                );
+                inventory.add_executor(
+                    ModularExtensionExecutor::ModularMulDivRv32_512(muldiv_chip),
+                    muldiv_opcodes
+                        .clone()
+                        .map(|x| VmOpcode::from_usize(x + start_offset)),
+                )?;
+                let isequal_chip = ModularIsEqualChip::new(
+                    Rv32IsEqualModAdapterChip::new(
+                        execution_bus,
+                        program_bus,
+                        memory_bridge,
+                        address_bits,
+                        bitwise_lu_chip.clone(),
+                    ),
+                    ModularIsEqualCoreChip::new(
+                        modulus.clone(),
+                        bitwise_lu_chip.clone(),
+                        start_offset,
+                    ),
+                    offline_memory.clone(),
+                );
+                inventory.add_executor(
+                    ModularExtensionExecutor::ModularIsEqualRv32_512(isequal_chip),
+                    iseq_opcodes
+                        .clone()
+                        .map(|x| VmOpcode::from_usize(x + start_offset)),
+                )?;
             } else {
                 panic!("Modulus too large");
             }
@@ -244,10 +378,28 @@ impl<F: PrimeField32> VmExtension<F> for ModularExtension {
             PhantomDiscriminant(ModularPhantom::HintSqrt as u16),
         )?;
 
+        let inverse_hint_sub_ex = phantom::InverseHintSubEx::new(self.supported_moduli.clone());
+        builder.add_phantom_sub_executor(
+            inverse_hint_sub_ex,
+            PhantomDiscriminant(ModularPhantom::HintInverse as u16),
+        )?;
+
         Ok(inventory)
     }
 }
 
+// The carries of a product of two `num_limbs`-limb field elements (with 8-bit limbs), minus the
+// quotient times the modulus, must be range checked. Larger moduli need a larger range checker
+// than the default, which is configured by `decomp` in the memory config.
+fn assert_range_checker_supports(num_limbs: usize, range_checker_bits: usize) {
+    let overflow_bits = 2 * 8 + (2 * num_limbs).next_power_of_two().trailing_zeros() as usize;
+    let carry_bits = overflow_bits - 8 + 1;
+    assert!(
+        carry_bits <= range_checker_bits,
+        "Moduli with {num_limbs} limbs require a range checker with at least {carry_bits} bits, but it has {range_checker_bits} bits; increase `decomp` in the memory config"
+    );
+}
+
 pub(crate) mod phantom {
     use std::{
         iter::{once, repeat},
@@ -298,24 +450,8 @@ pub(crate) mod phantom {
                 );
             }
             let modulus = &self.supported_moduli[mod_idx];
-            let num_limbs: usize = if modulus.bits().div_ceil(8) <= 32 {
-                32
-            } else if modulus.bits().div_ceil(8) <= 48 {
-                48
-            } else {
-                bail!("Modulus too large")
-            };
-
-            let rs1 = unsafe_read_rv32_register(memory, a);
-            let mut x_limbs: Vec<u8> = Vec::with_capacity(num_limbs);
-            for i in 0..num_limbs {
-                let limb = memory.unsafe_read_cell(
-                    F::from_canonical_u32(RV32_MEMORY_AS),
-                    F::from_canonical_u32(rs1 + i as u32),
-                );
-                x_limbs.push(limb.as_canonical_u32() as u8);
-            }
-            let x = BigUint::from_bytes_le(&x_limbs);
+            let num_limbs = num_limbs(modulus)?;
+            let x = read_limbs(memory, a, num_limbs);
 
             let (success, sqrt) = match mod_sqrt(&x, modulus, &self.non_qrs[mod_idx]) {
                 Some(sqrt) => (true, sqrt),
@@ -388,14 +524,7 @@ pub(crate) mod phantom {
                 );
             }
             let modulus = &self.supported_moduli[mod_idx];
-
-            let num_limbs: usize = if modulus.bits().div_ceil(8) <= 32 {
-                32
-            } else if modulus.bits().div_ceil(8) <= 48 {
-                48
-            } else {
-                bail!("Modulus too large")
-            };
+            let num_limbs = num_limbs(modulus)?;
 
             let hint_bytes = self.non_qrs[mod_idx]
                 .to_bytes_le()
@@ -408,6 +537,85 @@ pub(crate) mod phantom {
             Ok(())
         }
     }
+
+    #[derive(derive_new::new)]
+    pub struct InverseHintSubEx {
+        supported_moduli: Vec<BigUint>,
+    }
+
+    // Given x returns whether x is invertible and, if it is, its inverse.
+    impl<F: PrimeField32> PhantomSubExecutor<F> for InverseHintSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            _: F,
+            c_upper: u16,
+        ) -> eyre::Result<()> {
+            let mod_idx = c_upper as usize;
+            if mod_idx >= self.supported_moduli.len() {
+                bail!(
+                    "Modulus index {mod_idx} out of range: {} supported moduli",
+                    self.supported_moduli.len()
+                );
+            }
+            let modulus = &self.supported_moduli[mod_idx];
+            let num_limbs = num_limbs(modulus)?;
+            let x = read_limbs(memory, a, num_limbs);
+
+            let inv = (x % modulus).modinv(modulus);
+            let hint_bytes = once(F::from_bool(inv.is_some()))
+                .chain(repeat(F::ZERO))
+                .take(4)
+                .chain(
+                    inv.unwrap_or_default()
+                        .to_bytes_le()
+                        .into_iter()
+                        .map(F::from_canonical_u8)
+                        .chain(repeat(F::ZERO))
+                        .take(num_limbs),
+                )
+                .collect();
+            streams.hint_stream = hint_bytes;
+            Ok(())
+        }
+    }
+
+    fn num_limbs(modulus: &BigUint) -> eyre::Result<usize> {
+        if modulus.bits().div_ceil(8) <= 32 {
+            Ok(32)
+        } else if modulus.bits().div_ceil(8) <= 48 {
+            Ok(48)
+        } else if modulus.bits().div_ceil(8) <= 256 {
+            Ok(256)
+        } else if modulus.bits().div_ceil(8) <= 512 {
+            Ok(512)
+        } else {
+            bail!("Modulus too large")
+        }
+    }
+
+    // Reads the `num_limbs` little-endian limbs pointed to by register `a`.
+    fn read_limbs<F: PrimeField32>(
+        memory: &MemoryController<F>,
+        a: F,
+        num_limbs: usize,
+    ) -> BigUint {
+        let rs1 = unsafe_read_rv32_register(memory, a);
+        let limbs: Vec<u8> = (0..num_limbs)
+            .map(|i| {
+                memory
+                    .unsafe_read_cell(
+                        F::from_canonical_u32(RV32_MEMORY_AS),
+                        F::from_canonical_u32(rs1 + i as u32),
+                    )
+                    .as_canonical_u32() as u8
+            })
+            .collect();
+        BigUint::from_bytes_le(&limbs)
+    }
 }
 
 /// Find the square root of `x` modulo `modulus` with `non_qr` a
@@ -1,10 +1,10 @@
 extern crate proc_macro;
 
-use openvm_macros_common::MacroArgs;
+use openvm_macros_common::{MacroArgs, ParamSpec};
 use proc_macro::TokenStream;
 use syn::{
     parse::{Parse, ParseStream},
-    parse_macro_input, Expr, ExprPath, Path, Token,
+    parse_macro_input, Expr, Path, Token,
 };
 
 /// This macro is used to declare the complex extension fields.
@@ -12,9 +12,11 @@ use syn::{
 /// ```rust
 /// complex_declare! {
 ///     Complex1 { mod_type = Mod1 },
-///     Complex2 { mod_type = Mod2 },
+///     Complex2 { mod_type = Mod2, non_residue = -5 },
 /// }
 /// ```
+/// The extension is defined by the irreducible polynomial `X^2 - non_residue`, so `non_residue`
+/// must not be a square in the base field. It defaults to `-1`, giving `X^2 + 1`.
 #[proc_macro]
 pub fn complex_declare(input: TokenStream) -> TokenStream {
     let MacroArgs { items } = parse_macro_input!(input as MacroArgs);
@@ -26,25 +28,41 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
     for item in items.into_iter() {
         let struct_name = item.name.to_string();
         let struct_name = syn::Ident::new(&struct_name, span.into());
-        let mut intmod_type: Option<syn::Path> = None;
-        for param in item.params {
-            match param.name.to_string().as_str() {
-                "mod_type" => {
-                    if let syn::Expr::Path(ExprPath { path, .. }) = param.value {
-                        intmod_type = Some(path)
-                    } else {
-                        return syn::Error::new_spanned(param.value, "Expected a type")
-                            .to_compile_error()
-                            .into();
-                    }
-                }
-                _ => {
-                    panic!("Unknown parameter {}", param.name);
-                }
-            }
-        }
-
-        let intmod_type = intmod_type.expect("mod_type parameter is required");
+        let params = match item.parse_params(&[
+            ParamSpec::required("mod_type"),
+            ParamSpec::optional("non_residue"),
+        ]) {
+            Ok(params) => params,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let intmod_type = match params.path("mod_type") {
+            Ok(intmod_type) => intmod_type.unwrap(),
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let non_residue = match params.isize("non_residue") {
+            Ok(non_residue) => non_residue.unwrap_or(-1),
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        let non_residue_abs = u32::try_from(non_residue.unsigned_abs())
+            .expect("non_residue must fit in a u32 in absolute value");
+        let mul_by_non_residue = if non_residue == -1 {
+            quote::quote! { -x }
+        } else if non_residue < 0 {
+            quote::quote! { -(x * <#intmod_type as openvm_algebra_guest::IntMod>::from_u32(#non_residue_abs)) }
+        } else {
+            quote::quote! { x * <#intmod_type as openvm_algebra_guest::IntMod>::from_u32(#non_residue_abs) }
+        };
+        let non_residue_doc = format!(
+            "Quadratic extension field of `{}` with irreducible polynomial `X^2 {} {}`.",
+            quote::quote!(#intmod_type),
+            if non_residue < 0 { "+" } else { "-" },
+            non_residue_abs
+        );
+        let non_residue_doc_2 = format!(
+            "Elements are represented as `c0 + c1 * u` where `u^2 = {}`.",
+            non_residue
+        );
 
         macro_rules! create_extern_func {
             ($name:ident) => {
@@ -60,22 +78,32 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
         create_extern_func!(complex_div_extern_func);
         create_extern_func!(complex_setup_extern_func);
 
+        let serde_derive = if cfg!(feature = "serde") {
+            quote::quote! {
+                #[derive(::openvm_algebra_guest::serde::Serialize, ::openvm_algebra_guest::serde::Deserialize)]
+                #[serde(crate = "::openvm_algebra_guest::serde")]
+            }
+        } else {
+            quote::quote! {}
+        };
+
         let result = TokenStream::from(quote::quote_spanned! { span.into() =>
             extern "C" {
                 fn #complex_add_extern_func(rd: usize, rs1: usize, rs2: usize);
                 fn #complex_sub_extern_func(rd: usize, rs1: usize, rs2: usize);
                 fn #complex_mul_extern_func(rd: usize, rs1: usize, rs2: usize);
                 fn #complex_div_extern_func(rd: usize, rs1: usize, rs2: usize);
-                fn #complex_setup_extern_func();
+                fn #complex_setup_extern_func(setup_record: usize);
             }
 
 
-            /// Quadratic extension field of `#intmod_type` with irreducible polynomial `X^2 + 1`.
-            /// Elements are represented as `c0 + c1 * u` where `u^2 = -1`.
+            #[doc = #non_residue_doc]
+            #[doc = #non_residue_doc_2]
             ///
             /// Memory alignment follows alignment of `#intmod_type`.
             /// Memory layout is concatenation of `c0` and `c1`.
-            #[derive(Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+            #[derive(Clone, PartialEq, Eq)]
+            #serde_derive
             #[repr(C)]
             pub struct #struct_name {
                 /// Real coordinate
@@ -102,6 +130,12 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
                     self.c1.neg_assign();
                 }
 
+                /// Multiplies a base field element by the non-residue `u^2`.
+                #[inline(always)]
+                fn mul_by_non_residue(x: #intmod_type) -> #intmod_type {
+                    #mul_by_non_residue
+                }
+
                 /// Implementation of AddAssign.
                 #[inline(always)]
                 fn add_assign_impl(&mut self, other: &Self) {
@@ -152,7 +186,7 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
                         let (c0, c1) = (&self.c0, &self.c1);
                         let (d0, d1) = (&other.c0, &other.c1);
                         *self = Self::new(
-                            c0.clone() * d0 - c1.clone() * d1,
+                            c0.clone() * d0 + Self::mul_by_non_residue(c1.clone() * d1),
                             c0.clone() * d1 + c1.clone() * d0,
                         );
                     }
@@ -176,9 +210,9 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
                     {
                         let (c0, c1) = (&self.c0, &self.c1);
                         let (d0, d1) = (&other.c0, &other.c1);
-                        let denom = openvm_algebra_guest::DivUnsafe::div_unsafe(<#intmod_type as openvm_algebra_guest::IntMod>::ONE, d0.square() + d1.square());
+                        let denom = openvm_algebra_guest::DivUnsafe::div_unsafe(<#intmod_type as openvm_algebra_guest::IntMod>::ONE, d0.square() - Self::mul_by_non_residue(d1.square()));
                         *self = Self::new(
-                            denom.clone() * (c0.clone() * d0 + c1.clone() * d1),
+                            denom.clone() * (c0.clone() * d0 - Self::mul_by_non_residue(c1.clone() * d1)),
                             denom * &(c1.clone() * d0 - c0.clone() * d1),
                         );
                     }
@@ -294,11 +328,17 @@ pub fn complex_declare(input: TokenStream) -> TokenStream {
 
                 // Helper function to call the setup instruction on first use
                 fn set_up_once() {
-                    static is_setup: ::openvm_algebra_guest::once_cell::race::OnceBool = ::openvm_algebra_guest::once_cell::race::OnceBool::new();
-                    is_setup.get_or_init(|| {
-                        unsafe { #complex_setup_extern_func(); }
-                        true
-                    });
+                    static is_setup: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+                    if !is_setup.load(core::sync::atomic::Ordering::Relaxed) {
+                        // The setup record is the modulus followed by the non-residue, which the
+                        // chips check against their configuration.
+                        let setup_record = Self::new(
+                            <#intmod_type as openvm_algebra_guest::IntMod>::from_repr(<#intmod_type as openvm_algebra_guest::IntMod>::MODULUS),
+                            Self::mul_by_non_residue(<#intmod_type as openvm_algebra_guest::IntMod>::ONE),
+                        );
+                        unsafe { #complex_setup_extern_func(&setup_record as *const Self as usize); }
+                        is_setup.store(true, core::sync::atomic::Ordering::Relaxed);
+                    }
                 }
             }
 
@@ -552,28 +592,14 @@ pub fn complex_init(input: TokenStream) -> TokenStream {
     for (complex_idx, item) in items.into_iter().enumerate() {
         let struct_name = item.name.to_string();
         let struct_name = syn::Ident::new(&struct_name, span.into());
-        let mut intmod_idx: Option<usize> = None;
-        for param in item.params {
-            match param.name.to_string().as_str() {
-                "mod_idx" => {
-                    if let syn::Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Int(int),
-                        ..
-                    }) = param.value
-                    {
-                        intmod_idx = Some(int.base10_parse::<usize>().unwrap());
-                    } else {
-                        return syn::Error::new_spanned(param.value, "Expected usize")
-                            .to_compile_error()
-                            .into();
-                    }
-                }
-                _ => {
-                    panic!("Unknown parameter {}", param.name);
-                }
-            }
-        }
-        let mod_idx = intmod_idx.expect("mod_idx is required");
+        let params = match item.parse_params(&[ParamSpec::required("mod_idx")]) {
+            Ok(params) => params,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let mod_idx = match params.usize("mod_idx") {
+            Ok(mod_idx) => mod_idx.unwrap(),
+            Err(err) => return err.to_compile_error().into(),
+        };
 
         println!(
             "[init] complex #{} = {} (mod_idx = {})",
@@ -611,11 +637,10 @@ pub fn complex_init(input: TokenStream) -> TokenStream {
 
         externs.push(quote::quote_spanned! { span.into() =>
             #[no_mangle]
-            extern "C" fn #setup_extern_func() {
+            extern "C" fn #setup_extern_func(setup_record: usize) {
                 #[cfg(target_os = "zkvm")]
                 {
-                    use super::openvm_intrinsics_meta_do_not_type_this_by_yourself::{two_modular_limbs_list, limb_list_borders};
-                    let two_modulus_bytes = &two_modular_limbs_list[limb_list_borders[#mod_idx]..limb_list_borders[#mod_idx + 1]];
+                    use super::openvm_intrinsics_meta_do_not_type_this_by_yourself::limb_list_borders;
 
                     // We are going to use the numeric representation of the `rs2` register to distinguish the chip to setup.
                     // The transpiler will transform this instruction, based on whether `rs2` is `x0` or `x1`, into a `SETUP_ADDSUB` or `SETUP_MULDIV` instruction.
@@ -627,7 +652,7 @@ pub fn complex_init(input: TokenStream) -> TokenStream {
                             + #complex_idx
                                 * (::openvm_algebra_guest::ComplexExtFieldBaseFunct7::COMPLEX_EXT_FIELD_MAX_KINDS as usize),
                         rd = In uninit.as_mut_ptr(),
-                        rs1 = In two_modulus_bytes.as_ptr(),
+                        rs1 = In setup_record as *const u8,
                         rs2 = Const "x0" // will be parsed as 0 and therefore transpiled to SETUP_ADDMOD
                     );
                     openvm::platform::custom_insn_r!(
@@ -637,7 +662,7 @@ pub fn complex_init(input: TokenStream) -> TokenStream {
                             + #complex_idx
                                 * (::openvm_algebra_guest::ComplexExtFieldBaseFunct7::COMPLEX_EXT_FIELD_MAX_KINDS as usize),
                         rd = In uninit.as_mut_ptr(),
-                        rs1 = In two_modulus_bytes.as_ptr(),
+                        rs1 = In setup_record as *const u8,
                         rs2 = Const "x1" // will be parsed as 1 and therefore transpiled to SETUP_MULDIV
                     );
                 }
@@ -649,6 +674,8 @@ pub fn complex_init(input: TokenStream) -> TokenStream {
         #[allow(non_snake_case)]
         #[cfg(target_os = "zkvm")]
         mod openvm_intrinsics_ffi_complex {
+            openvm::platform::require_extension_version!("fp2", 1);
+
             #(#externs)*
         }
     })
@@ -0,0 +1,758 @@
+extern crate proc_macro;
+
+use openvm_macros_common::{MacroArgs, ParamSpec};
+use proc_macro::TokenStream;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Expr, Path, Token,
+};
+
+/// This macro is used to declare the cubic extension fields.
+/// Usage:
+/// ```rust
+/// cubic_declare! {
+///     Cubic1 { mod_type = Mod1, non_residue = 3 },
+///     Cubic2 { mod_type = Mod2, non_residue = -2 },
+/// }
+/// ```
+/// The extension is defined by the irreducible polynomial `X^3 - non_residue`, so `non_residue`
+/// must not be a cube in the base field.
+#[proc_macro]
+pub fn cubic_declare(input: TokenStream) -> TokenStream {
+    let MacroArgs { items } = parse_macro_input!(input as MacroArgs);
+
+    let mut output = Vec::new();
+
+    let span = proc_macro::Span::call_site();
+
+    for item in items.into_iter() {
+        let struct_name = item.name.to_string();
+        let struct_name = syn::Ident::new(&struct_name, span.into());
+        let params = match item.parse_params(&[
+            ParamSpec::required("mod_type"),
+            ParamSpec::required("non_residue"),
+        ]) {
+            Ok(params) => params,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let intmod_type = match params.path("mod_type") {
+            Ok(intmod_type) => intmod_type.unwrap(),
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let non_residue = match params.isize("non_residue") {
+            Ok(non_residue) => non_residue.unwrap(),
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let non_residue_abs = u32::try_from(non_residue.unsigned_abs())
+            .expect("non_residue must fit in a u32 in absolute value");
+        let mul_by_non_residue = if non_residue < 0 {
+            quote::quote! { -(x * <#intmod_type as openvm_algebra_guest::IntMod>::from_u32(#non_residue_abs)) }
+        } else {
+            quote::quote! { x * <#intmod_type as openvm_algebra_guest::IntMod>::from_u32(#non_residue_abs) }
+        };
+        let non_residue_doc = format!(
+            "Cubic extension field of `{}` with irreducible polynomial `X^3 {} {}`.",
+            quote::quote!(#intmod_type),
+            if non_residue < 0 { "+" } else { "-" },
+            non_residue_abs
+        );
+        let non_residue_doc_2 = format!(
+            "Elements are represented as `c0 + c1 * w + c2 * w^2` where `w^3 = {}`.",
+            non_residue
+        );
+
+        macro_rules! create_extern_func {
+            ($name:ident) => {
+                let $name = syn::Ident::new(
+                    &format!("{}_{}", stringify!($name), struct_name),
+                    span.into(),
+                );
+            };
+        }
+        create_extern_func!(cubic_add_extern_func);
+        create_extern_func!(cubic_sub_extern_func);
+        create_extern_func!(cubic_mul_extern_func);
+        create_extern_func!(cubic_div_extern_func);
+        create_extern_func!(cubic_setup_extern_func);
+
+        let serde_derive = if cfg!(feature = "serde") {
+            quote::quote! {
+                #[derive(::openvm_algebra_guest::serde::Serialize, ::openvm_algebra_guest::serde::Deserialize)]
+                #[serde(crate = "::openvm_algebra_guest::serde")]
+            }
+        } else {
+            quote::quote! {}
+        };
+
+        let result = TokenStream::from(quote::quote_spanned! { span.into() =>
+            extern "C" {
+                fn #cubic_add_extern_func(rd: usize, rs1: usize, rs2: usize);
+                fn #cubic_sub_extern_func(rd: usize, rs1: usize, rs2: usize);
+                fn #cubic_mul_extern_func(rd: usize, rs1: usize, rs2: usize);
+                fn #cubic_div_extern_func(rd: usize, rs1: usize, rs2: usize);
+                fn #cubic_setup_extern_func();
+            }
+
+
+            #[doc = #non_residue_doc]
+            #[doc = #non_residue_doc_2]
+            ///
+            /// Memory alignment follows alignment of the base field.
+            /// Memory layout is concatenation of `c0`, `c1` and `c2`.
+            #[derive(Clone, PartialEq, Eq)]
+            #serde_derive
+            #[repr(C)]
+            pub struct #struct_name {
+                /// Coefficient of `1`
+                pub c0: #intmod_type,
+                /// Coefficient of `w`
+                pub c1: #intmod_type,
+                /// Coefficient of `w^2`
+                pub c2: #intmod_type,
+            }
+
+            impl #struct_name {
+                pub const fn new(c0: #intmod_type, c1: #intmod_type, c2: #intmod_type) -> Self {
+                    Self { c0, c1, c2 }
+                }
+            }
+
+            impl #struct_name {
+                // Zero element (i.e. additive identity)
+                pub const ZERO: Self = Self::new(<#intmod_type as openvm_algebra_guest::IntMod>::ZERO, <#intmod_type as openvm_algebra_guest::IntMod>::ZERO, <#intmod_type as openvm_algebra_guest::IntMod>::ZERO);
+
+                // One element (i.e. multiplicative identity)
+                pub const ONE: Self = Self::new(<#intmod_type as openvm_algebra_guest::IntMod>::ONE, <#intmod_type as openvm_algebra_guest::IntMod>::ZERO, <#intmod_type as openvm_algebra_guest::IntMod>::ZERO);
+
+                pub fn neg_assign(&mut self) {
+                    self.c0.neg_assign();
+                    self.c1.neg_assign();
+                    self.c2.neg_assign();
+                }
+
+                /// Multiplies a base field element by the non-residue `w^3`.
+                #[cfg(not(target_os = "zkvm"))]
+                #[inline(always)]
+                fn mul_by_non_residue(x: #intmod_type) -> #intmod_type {
+                    #mul_by_non_residue
+                }
+
+                /// Implementation of AddAssign.
+                #[inline(always)]
+                fn add_assign_impl(&mut self, other: &Self) {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        self.c0 += &other.c0;
+                        self.c1 += &other.c1;
+                        self.c2 += &other.c2;
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        unsafe {
+                            #cubic_add_extern_func(
+                                self as *mut Self as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                    }
+                }
+
+                /// Implementation of SubAssign.
+                #[inline(always)]
+                fn sub_assign_impl(&mut self, other: &Self) {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        self.c0 -= &other.c0;
+                        self.c1 -= &other.c1;
+                        self.c2 -= &other.c2;
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        unsafe {
+                            #cubic_sub_extern_func(
+                                self as *mut Self as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                    }
+                }
+
+                /// Implementation of MulAssign.
+                #[inline(always)]
+                fn mul_assign_impl(&mut self, other: &Self) {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let (a0, a1, a2) = (&self.c0, &self.c1, &self.c2);
+                        let (b0, b1, b2) = (&other.c0, &other.c1, &other.c2);
+                        *self = Self::new(
+                            a0.clone() * b0
+                                + Self::mul_by_non_residue(a1.clone() * b2 + a2.clone() * b1),
+                            a0.clone() * b1
+                                + a1.clone() * b0
+                                + Self::mul_by_non_residue(a2.clone() * b2),
+                            a0.clone() * b2 + a1.clone() * b1 + a2.clone() * b0,
+                        );
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        unsafe {
+                            #cubic_mul_extern_func(
+                                self as *mut Self as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                    }
+                }
+
+                /// Implementation of DivAssignUnsafe.
+                #[inline(always)]
+                fn div_assign_unsafe_impl(&mut self, other: &Self) {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let (a0, a1, a2) = (&self.c0, &self.c1, &self.c2);
+                        let (b0, b1, b2) = (&other.c0, &other.c1, &other.c2);
+                        // `t0 + t1 * w + t2 * w^2` is the adjugate of `other`, whose product with
+                        // `other` is the norm of `other`.
+                        let t0 = <#intmod_type as openvm_algebra_guest::IntMod>::square(b0) - Self::mul_by_non_residue(b1.clone() * b2);
+                        let t1 = Self::mul_by_non_residue(<#intmod_type as openvm_algebra_guest::IntMod>::square(b2)) - b0.clone() * b1;
+                        let t2 = <#intmod_type as openvm_algebra_guest::IntMod>::square(b1) - b0.clone() * b2;
+                        let norm = b0.clone() * &t0
+                            + Self::mul_by_non_residue(b2.clone() * &t1 + b1.clone() * &t2);
+                        let denom = openvm_algebra_guest::DivUnsafe::div_unsafe(<#intmod_type as openvm_algebra_guest::IntMod>::ONE, norm);
+                        *self = Self::new(
+                            denom.clone()
+                                * (a0.clone() * &t0
+                                    + Self::mul_by_non_residue(a1.clone() * &t2 + a2.clone() * &t1)),
+                            denom.clone()
+                                * (a0.clone() * &t1
+                                    + a1.clone() * &t0
+                                    + Self::mul_by_non_residue(a2.clone() * &t2)),
+                            denom * (a0.clone() * &t2 + a1.clone() * &t1 + a2.clone() * &t0),
+                        );
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        unsafe {
+                            #cubic_div_extern_func(
+                                self as *mut Self as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                    }
+                }
+
+                /// Implementation of Add that doesn't cause zkvm to use an additional store.
+                fn add_refs_impl(&self, other: &Self) -> Self {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let mut res = self.clone();
+                        res.add_assign_impl(other);
+                        res
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        let mut uninit: core::mem::MaybeUninit<Self> = core::mem::MaybeUninit::uninit();
+                        unsafe {
+                            #cubic_add_extern_func(
+                                uninit.as_mut_ptr() as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                        unsafe { uninit.assume_init() }
+                    }
+                }
+
+                /// Implementation of Sub that doesn't cause zkvm to use an additional store.
+                #[inline(always)]
+                fn sub_refs_impl(&self, other: &Self) -> Self {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let mut res = self.clone();
+                        res.sub_assign_impl(other);
+                        res
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        let mut uninit: core::mem::MaybeUninit<Self> = core::mem::MaybeUninit::uninit();
+                        unsafe {
+                            #cubic_sub_extern_func(
+                                uninit.as_mut_ptr() as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                        unsafe { uninit.assume_init() }
+                    }
+                }
+
+                /// Implementation of Mul that doesn't cause zkvm to use an additional store.
+                ///
+                /// SAFETY: dst_ptr must be pointer for `&mut Self`.
+                /// It will only be written to at the end of the function.
+                #[inline(always)]
+                unsafe fn mul_refs_impl(&self, other: &Self, dst_ptr: *mut Self) {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let mut res = self.clone();
+                        res.mul_assign_impl(other);
+                        let dst = unsafe { &mut *dst_ptr };
+                        *dst = res;
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        unsafe {
+                            #cubic_mul_extern_func(
+                                dst_ptr as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                    }
+                }
+
+                /// Implementation of DivUnsafe that doesn't cause zkvm to use an additional store.
+                #[inline(always)]
+                fn div_unsafe_refs_impl(&self, other: &Self) -> Self {
+                    #[cfg(not(target_os = "zkvm"))]
+                    {
+                        let mut res = self.clone();
+                        res.div_assign_unsafe_impl(other);
+                        res
+                    }
+                    #[cfg(target_os = "zkvm")]
+                    {
+                        Self::set_up_once();
+                        let mut uninit: core::mem::MaybeUninit<Self> = core::mem::MaybeUninit::uninit();
+                        unsafe {
+                            #cubic_div_extern_func(
+                                uninit.as_mut_ptr() as usize,
+                                self as *const Self as usize,
+                                other as *const Self as usize
+                            );
+                        }
+                        unsafe { uninit.assume_init() }
+                    }
+                }
+
+                // Helper function to call the setup instruction on first use
+                fn set_up_once() {
+                    static is_setup: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+                    if !is_setup.load(core::sync::atomic::Ordering::Relaxed) {
+                        unsafe { #cubic_setup_extern_func(); }
+                        is_setup.store(true, core::sync::atomic::Ordering::Relaxed);
+                    }
+                }
+            }
+
+            impl<'a> core::ops::AddAssign<&'a #struct_name> for #struct_name {
+                #[inline(always)]
+                fn add_assign(&mut self, other: &'a #struct_name) {
+                    self.add_assign_impl(other);
+                }
+            }
+
+            impl core::ops::AddAssign for #struct_name {
+                #[inline(always)]
+                fn add_assign(&mut self, other: Self) {
+                    self.add_assign_impl(&other);
+                }
+            }
+
+            impl core::ops::Add for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn add(mut self, other: Self) -> Self::Output {
+                    self += other;
+                    self
+                }
+            }
+
+            impl<'a> core::ops::Add<&'a #struct_name> for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn add(mut self, other: &'a #struct_name) -> Self::Output {
+                    self += other;
+                    self
+                }
+            }
+
+            impl<'a> core::ops::Add<&'a #struct_name> for &#struct_name {
+                type Output = #struct_name;
+                #[inline(always)]
+                fn add(self, other: &'a #struct_name) -> Self::Output {
+                    self.add_refs_impl(other)
+                }
+            }
+
+            impl<'a> core::ops::SubAssign<&'a #struct_name> for #struct_name {
+                #[inline(always)]
+                fn sub_assign(&mut self, other: &'a #struct_name) {
+                    self.sub_assign_impl(other);
+                }
+            }
+
+            impl core::ops::SubAssign for #struct_name {
+                #[inline(always)]
+                fn sub_assign(&mut self, other: Self) {
+                    self.sub_assign_impl(&other);
+                }
+            }
+
+            impl core::ops::Sub for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn sub(mut self, other: Self) -> Self::Output {
+                    self -= other;
+                    self
+                }
+            }
+
+            impl<'a> core::ops::Sub<&'a #struct_name> for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn sub(mut self, other: &'a #struct_name) -> Self::Output {
+                    self -= other;
+                    self
+                }
+            }
+
+            impl<'a> core::ops::Sub<&'a #struct_name> for &#struct_name {
+                type Output = #struct_name;
+                #[inline(always)]
+                fn sub(self, other: &'a #struct_name) -> Self::Output {
+                    self.sub_refs_impl(other)
+                }
+            }
+
+            impl<'a> core::ops::MulAssign<&'a #struct_name> for #struct_name {
+                #[inline(always)]
+                fn mul_assign(&mut self, other: &'a #struct_name) {
+                    self.mul_assign_impl(other);
+                }
+            }
+
+            impl core::ops::MulAssign for #struct_name {
+                #[inline(always)]
+                fn mul_assign(&mut self, other: Self) {
+                    self.mul_assign_impl(&other);
+                }
+            }
+
+            impl core::ops::Mul for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn mul(mut self, other: Self) -> Self::Output {
+                    self *= other;
+                    self
+                }
+            }
+
+            impl<'a> core::ops::Mul<&'a #struct_name> for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn mul(mut self, other: &'a #struct_name) -> Self::Output {
+                    self *= other;
+                    self
+                }
+            }
+
+            impl<'a> core::ops::Mul<&'a #struct_name> for &'a #struct_name {
+                type Output = #struct_name;
+                #[inline(always)]
+                fn mul(self, other: &'a #struct_name) -> Self::Output {
+                    let mut uninit: core::mem::MaybeUninit<#struct_name> = core::mem::MaybeUninit::uninit();
+                    unsafe {
+                        self.mul_refs_impl(other, uninit.as_mut_ptr());
+                        uninit.assume_init()
+                    }
+                }
+            }
+
+            impl<'a> openvm_algebra_guest::DivAssignUnsafe<&'a #struct_name> for #struct_name {
+                #[inline(always)]
+                fn div_assign_unsafe(&mut self, other: &'a #struct_name) {
+                    self.div_assign_unsafe_impl(other);
+                }
+            }
+
+            impl openvm_algebra_guest::DivAssignUnsafe for #struct_name {
+                #[inline(always)]
+                fn div_assign_unsafe(&mut self, other: Self) {
+                    self.div_assign_unsafe_impl(&other);
+                }
+            }
+
+            impl openvm_algebra_guest::DivUnsafe for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn div_unsafe(mut self, other: Self) -> Self::Output {
+                    self = self.div_unsafe_refs_impl(&other);
+                    self
+                }
+            }
+
+            impl<'a> openvm_algebra_guest::DivUnsafe<&'a #struct_name> for #struct_name {
+                type Output = Self;
+                #[inline(always)]
+                fn div_unsafe(mut self, other: &'a #struct_name) -> Self::Output {
+                    self = self.div_unsafe_refs_impl(other);
+                    self
+                }
+            }
+
+            impl<'a> openvm_algebra_guest::DivUnsafe<&'a #struct_name> for &#struct_name {
+                type Output = #struct_name;
+                #[inline(always)]
+                fn div_unsafe(self, other: &'a #struct_name) -> Self::Output {
+                    self.div_unsafe_refs_impl(other)
+                }
+            }
+
+            impl<'a> core::iter::Sum<&'a #struct_name> for #struct_name {
+                fn sum<I: core::iter::Iterator<Item = &'a #struct_name>>(iter: I) -> Self {
+                    iter.fold(Self::ZERO, |acc, x| &acc + x)
+                }
+            }
+
+            impl core::iter::Sum for #struct_name {
+                fn sum<I: core::iter::Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ZERO, |acc, x| &acc + &x)
+                }
+            }
+
+            impl<'a> core::iter::Product<&'a #struct_name> for #struct_name {
+                fn product<I: core::iter::Iterator<Item = &'a #struct_name>>(iter: I) -> Self {
+                    iter.fold(Self::ONE, |acc, x| &acc * x)
+                }
+            }
+
+            impl core::iter::Product for #struct_name {
+                fn product<I: core::iter::Iterator<Item = Self>>(iter: I) -> Self {
+                    iter.fold(Self::ONE, |acc, x| &acc * &x)
+                }
+            }
+
+            impl core::ops::Neg for #struct_name {
+                type Output = #struct_name;
+                fn neg(self) -> Self::Output {
+                    Self::ZERO - &self
+                }
+            }
+
+            impl core::ops::Neg for &#struct_name {
+                type Output = #struct_name;
+                fn neg(self) -> Self::Output {
+                    #struct_name::ZERO - self
+                }
+            }
+
+            impl core::fmt::Debug for #struct_name {
+                fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                    write!(f, "{:?} + {:?} * w + {:?} * w^2", self.c0, self.c1, self.c2)
+                }
+            }
+        });
+        output.push(result);
+    }
+
+    TokenStream::from_iter(output)
+}
+
+/// This macro is used to initialize the cubic extension fields.
+/// It must be called after `moduli_init!` is called.
+///
+/// Usage:
+/// ```rust
+/// moduli_init!("998244353", "1000000007");
+///
+/// cubic_init!(Cubic2 { mod_idx = 1 }, Cubic1 { mod_idx = 0 });
+/// ```
+/// In particular, the order of cubic types in the macro doesn't have to match the order of moduli
+/// in `moduli_init!`, but they should be accompanied by the `mod_idx` corresponding to the order in
+/// the `moduli_init!` macro (not `moduli_declare!`).
+#[proc_macro]
+pub fn cubic_init(input: TokenStream) -> TokenStream {
+    let MacroArgs { items } = parse_macro_input!(input as MacroArgs);
+
+    let mut externs = Vec::new();
+
+    let span = proc_macro::Span::call_site();
+
+    for (cubic_idx, item) in items.into_iter().enumerate() {
+        let struct_name = item.name.to_string();
+        let struct_name = syn::Ident::new(&struct_name, span.into());
+        let params = match item.parse_params(&[ParamSpec::required("mod_idx")]) {
+            Ok(params) => params,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let mod_idx = match params.usize("mod_idx") {
+            Ok(mod_idx) => mod_idx.unwrap(),
+            Err(err) => return err.to_compile_error().into(),
+        };
+
+        println!(
+            "[init] cubic #{} = {} (mod_idx = {})",
+            cubic_idx, struct_name, mod_idx
+        );
+
+        for op_type in ["add", "sub", "mul", "div"] {
+            let func_name = syn::Ident::new(
+                &format!("cubic_{}_extern_func_{}", op_type, struct_name),
+                span.into(),
+            );
+            let mut chars = op_type.chars().collect::<Vec<_>>();
+            chars[0] = chars[0].to_ascii_uppercase();
+            let local_opcode = syn::Ident::new(&chars.iter().collect::<String>(), span.into());
+            externs.push(quote::quote_spanned! { span.into() =>
+                #[no_mangle]
+                extern "C" fn #func_name(rd: usize, rs1: usize, rs2: usize) {
+                    openvm::platform::custom_insn_r!(
+                        opcode = openvm_algebra_guest::OPCODE,
+                        funct3 = openvm_algebra_guest::CUBIC_EXT_FIELD_FUNCT3,
+                        funct7 = openvm_algebra_guest::CubicExtFieldBaseFunct7::#local_opcode as usize
+                            + #cubic_idx * (openvm_algebra_guest::CubicExtFieldBaseFunct7::CUBIC_EXT_FIELD_MAX_KINDS as usize),
+                        rd = In rd,
+                        rs1 = In rs1,
+                        rs2 = In rs2
+                    )
+                }
+            });
+        }
+
+        let setup_extern_func = syn::Ident::new(
+            &format!("cubic_setup_extern_func_{}", struct_name),
+            span.into(),
+        );
+
+        externs.push(quote::quote_spanned! { span.into() =>
+            #[no_mangle]
+            extern "C" fn #setup_extern_func() {
+                #[cfg(target_os = "zkvm")]
+                {
+                    use super::openvm_intrinsics_meta_do_not_type_this_by_yourself::{two_modular_limbs_list, limb_list_borders};
+                    const NUM_LIMBS: usize = (limb_list_borders[#mod_idx + 1] - limb_list_borders[#mod_idx]) / 2;
+                    let two_modulus_bytes = &two_modular_limbs_list[limb_list_borders[#mod_idx]..limb_list_borders[#mod_idx + 1]];
+                    // The setup instruction reads a whole `Fp3` element, so it needs three copies of the modulus.
+                    let mut three_modulus_bytes = [0u8; 3 * NUM_LIMBS];
+                    three_modulus_bytes[..2 * NUM_LIMBS].copy_from_slice(two_modulus_bytes);
+                    three_modulus_bytes[2 * NUM_LIMBS..].copy_from_slice(&two_modulus_bytes[..NUM_LIMBS]);
+
+                    // We are going to use the numeric representation of the `rs2` register to distinguish the chip to setup.
+                    // The transpiler will transform this instruction, based on whether `rs2` is `x0` or `x1`, into a `SETUP_ADDSUB` or `SETUP_MULDIV` instruction.
+                    let mut uninit: core::mem::MaybeUninit<[u8; 3 * NUM_LIMBS]> = core::mem::MaybeUninit::uninit();
+                    openvm::platform::custom_insn_r!(
+                        opcode = ::openvm_algebra_guest::OPCODE,
+                        funct3 = ::openvm_algebra_guest::CUBIC_EXT_FIELD_FUNCT3,
+                        funct7 = ::openvm_algebra_guest::CubicExtFieldBaseFunct7::Setup as usize
+                            + #cubic_idx
+                                * (::openvm_algebra_guest::CubicExtFieldBaseFunct7::CUBIC_EXT_FIELD_MAX_KINDS as usize),
+                        rd = In uninit.as_mut_ptr(),
+                        rs1 = In three_modulus_bytes.as_ptr(),
+                        rs2 = Const "x0" // will be parsed as 0 and therefore transpiled to SETUP_ADDSUB
+                    );
+                    openvm::platform::custom_insn_r!(
+                        opcode = ::openvm_algebra_guest::OPCODE,
+                        funct3 = ::openvm_algebra_guest::CUBIC_EXT_FIELD_FUNCT3,
+                        funct7 = ::openvm_algebra_guest::CubicExtFieldBaseFunct7::Setup as usize
+                            + #cubic_idx
+                                * (::openvm_algebra_guest::CubicExtFieldBaseFunct7::CUBIC_EXT_FIELD_MAX_KINDS as usize),
+                        rd = In uninit.as_mut_ptr(),
+                        rs1 = In three_modulus_bytes.as_ptr(),
+                        rs2 = Const "x1" // will be parsed as 1 and therefore transpiled to SETUP_MULDIV
+                    );
+                }
+            }
+        });
+    }
+
+    TokenStream::from(quote::quote_spanned! { span.into() =>
+        #[allow(non_snake_case)]
+        #[cfg(target_os = "zkvm")]
+        mod openvm_intrinsics_ffi_cubic {
+            openvm::platform::require_extension_version!("fp3", 1);
+
+            #(#externs)*
+        }
+    })
+}
+
+struct CubicSimpleItem {
+    items: Vec<Path>,
+}
+
+impl Parse for CubicSimpleItem {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let items = input.parse_terminated(<Expr as Parse>::parse, Token![,])?;
+        Ok(Self {
+            items: items
+                .into_iter()
+                .map(|e| {
+                    if let Expr::Path(p) = e {
+                        p.path
+                    } else {
+                        panic!("expected path");
+                    }
+                })
+                .collect(),
+        })
+    }
+}
+
+#[proc_macro]
+pub fn cubic_impl_field(input: TokenStream) -> TokenStream {
+    let CubicSimpleItem { items } = parse_macro_input!(input as CubicSimpleItem);
+
+    let mut output = Vec::new();
+
+    let span = proc_macro::Span::call_site();
+
+    for item in items.into_iter() {
+        let str_path = item
+            .segments
+            .iter()
+            .map(|x| x.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("_");
+        let struct_name = syn::Ident::new(&str_path, span.into());
+
+        output.push(quote::quote_spanned! { span.into() =>
+            impl openvm_algebra_guest::field::Field for #struct_name {
+                type SelfRef<'a>
+                    = &'a Self
+                where
+                    Self: 'a;
+
+                const ZERO: Self = Self::ZERO;
+                const ONE: Self = Self::ONE;
+
+                fn double_assign(&mut self) {
+                    openvm_algebra_guest::field::Field::double_assign(&mut self.c0);
+                    openvm_algebra_guest::field::Field::double_assign(&mut self.c1);
+                    openvm_algebra_guest::field::Field::double_assign(&mut self.c2);
+                }
+
+                fn square_assign(&mut self) {
+                    unsafe {
+                        self.mul_refs_impl(self, self as *const Self as *mut Self);
+                    }
+                }
+            }
+        });
+    }
+
+    TokenStream::from(quote::quote_spanned! { span.into() =>
+        #(#output)*
+    })
+}
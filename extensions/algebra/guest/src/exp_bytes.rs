@@ -1,6 +1,6 @@
 use core::ops::Mul;
 
-use crate::Field;
+use crate::{Field, IntMod};
 
 pub trait ExpBytes: Field {
     /// Exponentiates a field element by a value with a sign in big endian byte order
@@ -35,3 +35,65 @@ pub trait ExpBytes: Field {
 }
 
 impl<F: Field> ExpBytes for F where for<'a> &'a Self: Mul<&'a Self, Output = Self> {}
+
+/// Exponentiation of [IntMod] elements by a fixed 4-bit window.
+///
+/// The modulus setup is checked once per exponentiation rather than on every multiplication.
+pub trait PowBytes: IntMod {
+    /// Exponentiates by a value in big endian byte order, using a fixed 4-bit window with a
+    /// precomputed table of `self^0, ..., self^15`. The running time depends on the exponent.
+    fn pow_vartime(&self, exp_be: &[u8]) -> Self {
+        // Leading zeros only contribute squarings of one
+        let mut nibbles = exp_be
+            .iter()
+            .flat_map(|&b| [b >> 4, b & 0xf])
+            .skip_while(|&nibble| nibble == 0);
+        let Some(first) = nibbles.next() else {
+            return Self::ONE;
+        };
+
+        Self::set_up_once();
+        let mut table: [Self; 16] = core::array::from_fn(|_| Self::ONE);
+        table[1] = self.clone();
+        for i in 2..16 {
+            // SAFETY: setup was called above
+            table[i] = unsafe { table[i - 1].mul_ref::<false>(self) };
+        }
+
+        let mut res = table[first as usize].clone();
+        for nibble in nibbles {
+            for _ in 0..4 {
+                res = unsafe { res.mul_ref::<false>(&res) };
+            }
+            if nibble != 0 {
+                res = unsafe { res.mul_ref::<false>(&table[nibble as usize]) };
+            }
+        }
+        res
+    }
+
+    /// Exponentiates by a constant known at compile time, such as the RSA public exponent `65537`.
+    fn pow_const<const EXP: u64>(&self) -> Self {
+        // Building the window table costs 14 multiplications, which only pays off when the exponent
+        // has many set bits. Otherwise plain square-and-multiply is cheaper.
+        if EXP.count_ones() > 16 {
+            return self.pow_vartime(&EXP.to_be_bytes());
+        }
+        if EXP == 0 {
+            return Self::ONE;
+        }
+
+        Self::set_up_once();
+        let mut res = self.clone();
+        for i in (0..u64::BITS - 1 - EXP.leading_zeros()).rev() {
+            // SAFETY: setup was called above
+            res = unsafe { res.mul_ref::<false>(&res) };
+            if (EXP >> i) & 1 == 1 {
+                res = unsafe { res.mul_ref::<false>(self) };
+            }
+        }
+        res
+    }
+}
+
+impl<F: IntMod> PowBytes for F {}
@@ -0,0 +1,100 @@
+//! Software implementation of the modular arithmetic instructions, used by the externs created by
+//! `moduli_init!` when the VM does not have the modular arithmetic extension.
+//!
+//! Elements are little-endian byte arrays as long as `modulus`, given by their addresses like the
+//! operands of the instructions. Like the instructions, the results are reduced.
+
+use core::slice;
+
+use num_bigint::BigUint;
+
+use crate::host_hints;
+
+unsafe fn read(ptr: usize, modulus: &[u8]) -> BigUint {
+    BigUint::from_bytes_le(slice::from_raw_parts(ptr as *const u8, modulus.len()))
+}
+
+unsafe fn write(ptr: usize, value: &BigUint, modulus: &[u8]) {
+    let out = slice::from_raw_parts_mut(ptr as *mut u8, modulus.len());
+    let bytes = value.to_bytes_le();
+    out.fill(0);
+    out[..bytes.len()].copy_from_slice(&bytes);
+}
+
+/// # Safety
+/// `rd`, `rs1` and `rs2` must point to elements, see the [module](self) documentation.
+pub unsafe fn add(rd: usize, rs1: usize, rs2: usize, modulus: &[u8]) {
+    let n = BigUint::from_bytes_le(modulus);
+    let value = (read(rs1, modulus) + read(rs2, modulus)) % &n;
+    write(rd, &value, modulus);
+}
+
+/// # Safety
+/// `rd`, `rs1` and `rs2` must point to elements, see the [module](self) documentation.
+pub unsafe fn sub(rd: usize, rs1: usize, rs2: usize, modulus: &[u8]) {
+    let n = BigUint::from_bytes_le(modulus);
+    let value = (read(rs1, modulus) + &n - read(rs2, modulus) % &n) % &n;
+    write(rd, &value, modulus);
+}
+
+/// # Safety
+/// `rd`, `rs1` and `rs2` must point to elements, see the [module](self) documentation.
+pub unsafe fn mul(rd: usize, rs1: usize, rs2: usize, modulus: &[u8]) {
+    let n = BigUint::from_bytes_le(modulus);
+    let value = read(rs1, modulus) * read(rs2, modulus) % &n;
+    write(rd, &value, modulus);
+}
+
+/// Panics if the divisor is not invertible, which the VM cannot prove either.
+///
+/// # Safety
+/// `rd`, `rs1` and `rs2` must point to elements, see the [module](self) documentation.
+pub unsafe fn div(rd: usize, rs1: usize, rs2: usize, modulus: &[u8]) {
+    let n = BigUint::from_bytes_le(modulus);
+    let inv = (read(rs2, modulus) % &n)
+        .modinv(&n)
+        .expect("division by a non-invertible element");
+    let value = read(rs1, modulus) * inv % &n;
+    write(rd, &value, modulus);
+}
+
+/// Like the instruction, panics if an operand is not reduced.
+///
+/// # Safety
+/// `rs1` and `rs2` must point to elements, see the [module](self) documentation.
+pub unsafe fn is_eq(rs1: usize, rs2: usize, modulus: &[u8]) -> bool {
+    let n = BigUint::from_bytes_le(modulus);
+    let (a, b) = (read(rs1, modulus), read(rs2, modulus));
+    assert!(a < n && b < n, "operand is not reduced");
+    a == b
+}
+
+/// Returns whether `x` is invertible, and writes its inverse to `inv` if it is.
+pub fn inverse(x: &[u8], modulus: &[u8], inv: &mut [u8]) -> bool {
+    let n = BigUint::from_bytes_le(modulus);
+    match (BigUint::from_bytes_le(x) % &n).modinv(&n) {
+        Some(value) => {
+            unsafe { write(inv.as_mut_ptr() as usize, &value, modulus) };
+            true
+        }
+        None => false,
+    }
+}
+
+/// Returns whether `x` is a square, and writes to `sqrt` a square root of `x` if it is, and of
+/// `x * non_qr` otherwise, like the square root hint.
+pub fn sqrt(x: &[u8], modulus: &[u8], non_qr: &[u8], sqrt: &mut [u8]) -> bool {
+    let (is_square, value) = host_hints::sqrt_hint(
+        &BigUint::from_bytes_le(x),
+        &BigUint::from_bytes_le(modulus),
+        &BigUint::from_bytes_le(non_qr),
+    );
+    unsafe { write(sqrt.as_mut_ptr() as usize, &value, modulus) };
+    is_square
+}
+
+/// Writes a quadratic non-residue modulo the prime `modulus` to `non_qr`.
+pub fn non_qr(modulus: &[u8], non_qr: &mut [u8]) {
+    let value = host_hints::find_non_qr(&BigUint::from_bytes_le(modulus));
+    unsafe { write(non_qr.as_mut_ptr() as usize, &value, modulus) };
+}
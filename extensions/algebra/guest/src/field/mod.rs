@@ -50,12 +50,13 @@ pub trait Field:
     /// Square `self` in-place
     fn square_assign(&mut self);
 
-    /// Unchecked inversion. See [DivUnsafe].
-    ///
-    /// ## Panics
-    /// If `self` is zero.
-    fn invert(&self) -> Self {
-        Self::ONE.div_unsafe(self)
+    /// Returns the multiplicative inverse of `self`, or `None` if `self` is zero.
+    fn invert(&self) -> Option<Self> {
+        if self == &Self::ZERO {
+            None
+        } else {
+            Some(Self::ONE.div_unsafe(self))
+        }
     }
 }
 
@@ -0,0 +1,121 @@
+//! Host-side emulation of the square root and non-residue hints served by the VM, used by the
+//! structs created by `moduli_declare!` when not running in the zkVM.
+
+use num_bigint::BigUint;
+
+/// Returns a square root of `x` modulo the odd prime `modulus`, or `None` if `x` is not a square.
+/// `non_qr` must be a quadratic non-residue modulo `modulus`.
+pub fn mod_sqrt(x: &BigUint, modulus: &BigUint, non_qr: &BigUint) -> Option<BigUint> {
+    let one = BigUint::from(1u32);
+    let x = x % modulus;
+    if x == BigUint::ZERO {
+        return Some(x);
+    }
+    if modulus % 4u32 == BigUint::from(3u32) {
+        // x^(1/2) = x^((p+1)/4) when p = 3 mod 4
+        let sqrt = x.modpow(&((modulus + &one) >> 2), modulus);
+        return (&sqrt * &sqrt % modulus == x).then_some(sqrt);
+    }
+
+    // Tonelli-Shanks algorithm
+    // https://en.wikipedia.org/wiki/Tonelli%E2%80%93Shanks_algorithm#The_algorithm
+    // Write p - 1 = q * 2^s with q odd.
+    let s = (modulus - &one).trailing_zeros().unwrap();
+    let q = (modulus - &one) >> s;
+    let mut m = s;
+    let mut c = non_qr.modpow(&q, modulus);
+    let mut t = x.modpow(&q, modulus);
+    let mut r = x.modpow(&((&q + &one) >> 1), modulus);
+    while t != one {
+        // Find the least i such that t^(2^i) = 1.
+        let mut i = 0;
+        let mut t_pow = t.clone();
+        while t_pow != one {
+            t_pow = &t_pow * &t_pow % modulus;
+            i += 1;
+            if i == m {
+                // x is not a square
+                return None;
+            }
+        }
+        let b = c.modpow(&(BigUint::from(1u32) << (m - i - 1)), modulus);
+        m = i;
+        c = &b * &b % modulus;
+        t = t * &c % modulus;
+        r = r * b % modulus;
+    }
+    Some(r)
+}
+
+/// Returns a quadratic non-residue modulo the odd prime `modulus`.
+///
+/// Unlike the VM, which picks a random non-residue when neither shortcut applies, this returns the
+/// smallest one, so the result may differ from the hint served by the VM. Any non-residue is a
+/// valid hint.
+pub fn find_non_qr(modulus: &BigUint) -> BigUint {
+    let one = BigUint::from(1u32);
+    if modulus % 4u32 == BigUint::from(3u32) {
+        // p = 3 mod 4 then -1 is a non-quadratic residue
+        modulus - one
+    } else if modulus % 8u32 == BigUint::from(5u32) {
+        // p = 5 mod 8 then 2 is a non-quadratic residue
+        // since 2^((p-1)/2) = (-1)^((p^2-1)/8)
+        BigUint::from(2u32)
+    } else {
+        // By Euler's criterion, n is a non-quadratic residue if and only if n^((p-1)/2) = -1.
+        let exponent = (modulus - &one) >> 1;
+        let minus_one = modulus - &one;
+        let mut non_qr = BigUint::from(2u32);
+        while non_qr.modpow(&exponent, modulus) != minus_one {
+            non_qr += 1u32;
+        }
+        non_qr
+    }
+}
+
+/// Returns the square root hint for `x`: `(true, sqrt)` with `sqrt` a square root of `x` if `x` is
+/// a square, and `(false, sqrt)` with `sqrt` a square root of `x * non_qr` otherwise.
+pub fn sqrt_hint(x: &BigUint, modulus: &BigUint, non_qr: &BigUint) -> (bool, BigUint) {
+    match mod_sqrt(x, modulus, non_qr) {
+        Some(sqrt) => (true, sqrt),
+        None => {
+            let sqrt = mod_sqrt(&(x * non_qr), modulus, non_qr)
+                .expect("Either x or x * non_qr should be a square");
+            (false, sqrt)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn test_sqrt_hint() {
+        // Primes which are 3 mod 4, 5 mod 8, and 1 mod 8 with 2-adicity 3, 5 and 23.
+        for modulus in [1000000007u64, 1000000021, 1000000009, 1000000033, 998244353] {
+            let modulus = BigUint::from(modulus);
+            let non_qr = find_non_qr(&modulus);
+            let exponent = (&modulus - 1u32) >> 1;
+            assert_eq!(non_qr.modpow(&exponent, &modulus), &modulus - 1u32);
+
+            let xs: Vec<BigUint> = (0u32..200)
+                .map(|i| BigUint::from(i) * 0x9e3779b9u32 % &modulus)
+                .collect();
+            for x in xs {
+                let is_square = x == BigUint::ZERO || x.modpow(&exponent, &modulus) == 1u32.into();
+                let (hint_is_square, sqrt) = sqrt_hint(&x, &modulus, &non_qr);
+                assert_eq!(hint_is_square, is_square);
+                assert!(sqrt < modulus);
+                let expected = if is_square {
+                    x.clone()
+                } else {
+                    &x * &non_qr % &modulus
+                };
+                assert_eq!(&sqrt * &sqrt % &modulus, expected);
+            }
+        }
+    }
+}
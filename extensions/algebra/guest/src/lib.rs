@@ -5,6 +5,7 @@ extern crate self as openvm_algebra_guest;
 pub const OPCODE: u8 = 0x2b;
 pub const MODULAR_ARITHMETIC_FUNCT3: u8 = 0b000;
 pub const COMPLEX_EXT_FIELD_FUNCT3: u8 = 0b010;
+pub const CUBIC_EXT_FIELD_FUNCT3: u8 = 0b100;
 
 /// Modular arithmetic is configurable.
 /// The funct7 field equals `mod_idx * MODULAR_ARITHMETIC_MAX_KINDS + base_funct7`.
@@ -18,6 +19,7 @@ pub enum ModArithBaseFunct7 {
     IsEqMod,
     SetupMod,
     HintNonQr,
+    /// Hints a square root if `rs2 = x0`, or an inverse if `rs2 = x1`.
     HintSqrt,
 }
 
@@ -25,6 +27,16 @@ impl ModArithBaseFunct7 {
     pub const MODULAR_ARITHMETIC_MAX_KINDS: u8 = 8;
 }
 
+/// Returns whether the VM has the modular arithmetic extension. Without it, the structs created by
+/// `moduli_declare!` compute in software with the `fallback` feature.
+pub fn has_modular_arithmetic() -> bool {
+    openvm_platform::has_insn!(openvm_platform::capabilities::insn_r(
+        OPCODE,
+        MODULAR_ARITHMETIC_FUNCT3,
+        ModArithBaseFunct7::AddMod as u8
+    ))
+}
+
 /// Complex extension field is configurable.
 /// The funct7 field equals `fp2_idx * COMPLEX_EXT_FIELD_MAX_KINDS + base_funct7`.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
@@ -41,6 +53,22 @@ impl ComplexExtFieldBaseFunct7 {
     pub const COMPLEX_EXT_FIELD_MAX_KINDS: u8 = 8;
 }
 
+/// Cubic extension field is configurable.
+/// The funct7 field equals `fp3_idx * CUBIC_EXT_FIELD_MAX_KINDS + base_funct7`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
+#[repr(u8)]
+pub enum CubicExtFieldBaseFunct7 {
+    Add = 0,
+    Sub,
+    Mul,
+    Div,
+    Setup,
+}
+
+impl CubicExtFieldBaseFunct7 {
+    pub const CUBIC_EXT_FIELD_MAX_KINDS: u8 = 8;
+}
+
 /// Modular arithmetic traits for use with OpenVM intrinsics.
 extern crate alloc;
 
@@ -52,14 +80,20 @@ use core::{
 };
 
 pub use field::Field;
-#[cfg(not(target_os = "zkvm"))]
+#[cfg(all(not(target_os = "zkvm"), feature = "host-math"))]
+pub use num_bigint;
+#[cfg(all(not(target_os = "zkvm"), feature = "host-math"))]
 use num_bigint::BigUint;
 pub use openvm_algebra_complex_macros as complex_macros;
+pub use openvm_algebra_cubic_macros as cubic_macros;
 pub use openvm_algebra_moduli_macros as moduli_macros;
 #[cfg(target_os = "zkvm")]
 pub use openvm_custom_insn;
 #[cfg(target_os = "zkvm")]
 pub use openvm_rv32im_guest;
+#[cfg(feature = "serde")]
+pub use serde;
+#[cfg(feature = "serde")]
 pub use serde_big_array::BigArray;
 use strum_macros::FromRepr;
 
@@ -71,9 +105,21 @@ mod halo2curves;
 
 /// Exponentiation by bytes
 mod exp_bytes;
+/// Software modular arithmetic used in the zkVM when the VM lacks the modular arithmetic extension
+#[cfg(all(target_os = "zkvm", feature = "fallback"))]
+#[doc(hidden)]
+pub mod fallback;
 /// Field traits
 pub mod field;
+/// Host-side emulation of the hints used by `Sqrt`
+#[cfg(any(
+    all(not(target_os = "zkvm"), feature = "host-math"),
+    all(target_os = "zkvm", feature = "fallback")
+))]
+#[doc(hidden)]
+pub mod host_hints;
 pub use exp_bytes::*;
+#[cfg(feature = "hints")]
 pub use once_cell;
 
 /// Division operation that is undefined behavior when the denominator is not invertible.
@@ -186,15 +232,15 @@ pub trait IntMod:
     fn to_be_bytes(&self) -> Self::Repr;
 
     /// Modulus N as a BigUint.
-    #[cfg(not(target_os = "zkvm"))]
+    #[cfg(all(not(target_os = "zkvm"), feature = "host-math"))]
     fn modulus_biguint() -> BigUint;
 
     /// Creates a new IntMod from a BigUint.
-    #[cfg(not(target_os = "zkvm"))]
+    #[cfg(all(not(target_os = "zkvm"), feature = "host-math"))]
     fn from_biguint(biguint: BigUint) -> Self;
 
     /// Value of this IntMod as a BigUint.
-    #[cfg(not(target_os = "zkvm"))]
+    #[cfg(all(not(target_os = "zkvm"), feature = "host-math"))]
     fn as_biguint(&self) -> BigUint;
 
     fn neg_assign(&mut self);
@@ -226,6 +272,34 @@ pub trait IntMod:
         ret
     }
 
+    /// Replaces every element of `elements` by its inverse using Montgomery's trick: one division
+    /// and `3(n - 1)` multiplications for `n` elements.
+    ///
+    /// Undefined behavior if any element is not invertible, as for [DivUnsafe].
+    fn batch_invert(elements: &mut [Self]) {
+        let Some((first, rest)) = elements.split_first() else {
+            return;
+        };
+        Self::set_up_once();
+        // prefix_products[i] = elements[0] * ... * elements[i]
+        let mut prefix_products = Vec::with_capacity(elements.len());
+        prefix_products.push(first.clone());
+        for x in rest {
+            // SAFETY: setup was called above
+            let product = unsafe { prefix_products.last().unwrap().mul_ref::<false>(x) };
+            prefix_products.push(product);
+        }
+
+        // inv = 1 / (elements[0] * ... * elements[i]) at the start of iteration i
+        let mut inv = Self::ONE.div_unsafe(prefix_products.pop().unwrap());
+        for (x, prefix_product) in elements[1..].iter_mut().zip(prefix_products).rev() {
+            let x_inv = unsafe { inv.mul_ref::<false>(&prefix_product) };
+            inv = unsafe { inv.mul_ref::<false>(x) };
+            *x = x_inv;
+        }
+        elements[0] = inv;
+    }
+
     /// VM specific concept: during guest execution, it is not enforced that the representation
     /// of `Self` must be the unique integer less than the modulus. The guest code may sometimes
     /// want to enforce that the representation is the canonical one less than the modulus.
@@ -257,6 +331,14 @@ pub trait IntMod:
     ///   `Self::set_up_once()`. Only set `CHECK_SETUP` to `false` if you are sure that setup has
     ///   been called already.
     unsafe fn add_ref<const CHECK_SETUP: bool>(&self, other: &Self) -> Self;
+
+    /// Multiply two elements.
+    ///
+    /// # Safety
+    /// - If `CHECK_SETUP` is true, checks if setup has been called for this curve and if not, calls
+    ///   `Self::set_up_once()`. Only set `CHECK_SETUP` to `false` if you are sure that setup has
+    ///   been called already.
+    unsafe fn mul_ref<const CHECK_SETUP: bool>(&self, other: &Self) -> Self;
 }
 
 // Ref: https://docs.rs/elliptic-curve/latest/elliptic_curve/ops/trait.Reduce.html
@@ -5,7 +5,7 @@ use std::sync::atomic::AtomicUsize;
 
 use num_bigint::BigUint;
 use num_prime::nt_funcs::is_prime;
-use openvm_macros_common::{string_to_bytes, MacroArgs};
+use openvm_macros_common::{string_to_bytes, MacroArgs, ParamSpec};
 use proc_macro::TokenStream;
 use quote::format_ident;
 use syn::{
@@ -15,6 +15,54 @@ use syn::{
 
 static MOD_IDX: AtomicUsize = AtomicUsize::new(0);
 
+/// Returns the number of limbs and the memory block size used to represent elements of a modulus
+/// with `num_bytes` bytes. These must match the modular arithmetic chips of the VM, which operate
+/// on operands made up of `limbs / block_size` memory blocks.
+fn limbs_and_block_size(num_bytes: usize) -> (usize, usize) {
+    if num_bytes <= 32 {
+        (32, 32)
+    } else if num_bytes <= 48 {
+        (48, 16)
+    } else if num_bytes <= 256 {
+        (256, 32)
+    } else if num_bytes <= 512 {
+        (512, 32)
+    } else {
+        panic!("limbs must be at most 512");
+    }
+}
+
+/// Emits the `BigUint` implementation of an operation when not running in the zkVM. Without the
+/// `host-math` feature it emits a `compile_error!` instead, which is placed in code compiled only
+/// for the host, so that guests build without the feature but the host does not.
+macro_rules! host_math {
+    ($($body:tt)*) => {
+        if cfg!(feature = "host-math") {
+            quote::quote! { $($body)* }
+        } else {
+            quote::quote! {
+                ::core::compile_error!("modular arithmetic on the host requires the `host-math` feature of `openvm-algebra-guest`")
+            }
+        }
+    };
+}
+
+/// Emits a check which runs `body` instead of the instructions when the VM does not have the
+/// modular arithmetic extension. Without the `fallback` feature it emits nothing.
+macro_rules! fallback {
+    ($($body:tt)*) => {
+        if cfg!(feature = "fallback") {
+            quote::quote! {
+                if !::openvm_algebra_guest::has_modular_arithmetic() {
+                    $($body)*
+                }
+            }
+        } else {
+            quote::quote! {}
+        }
+    };
+}
+
 /// This macro generates the code to setup the modulus for a given prime. Also it places the moduli
 /// into a special static variable to be later extracted from the ELF and used by the VM. Usage:
 /// ```
@@ -25,6 +73,15 @@ static MOD_IDX: AtomicUsize = AtomicUsize::new(0);
 /// ```
 /// This creates two structs, `Bls12381` and `Bn254`, each representing the modular arithmetic class
 /// (implementing `Add`, `Sub` and so on).
+///
+/// An optional `serde_repr = "montgomery"` parameter makes the struct (de)serialize in Montgomery
+/// form, byte-compatible with the raw representation of arkworks and halo2curves field elements,
+/// and generates `from_montgomery_le_bytes` and `to_montgomery_le_bytes` conversion helpers. The
+/// modulus must be odd. The default is `serde_repr = "canonical"`.
+///
+/// Only serialization is affected: elements are kept in canonical form on the host as well as in
+/// the guest, since the guest intrinsics operate on it, so every conversion to or from Montgomery
+/// form costs a modular multiplication.
 #[proc_macro]
 pub fn moduli_declare(input: TokenStream) -> TokenStream {
     let MacroArgs { items } = parse_macro_input!(input as MacroArgs);
@@ -36,48 +93,40 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
     for item in items {
         let struct_name = item.name.to_string();
         let struct_name = syn::Ident::new(&struct_name, span.into());
-        let mut modulus: Option<String> = None;
-        for param in item.params {
-            match param.name.to_string().as_str() {
-                "modulus" => {
-                    if let syn::Expr::Lit(syn::ExprLit {
-                        lit: syn::Lit::Str(value),
-                        ..
-                    }) = param.value
-                    {
-                        modulus = Some(value.value());
-                    } else {
-                        return syn::Error::new_spanned(
-                            param.value,
-                            "Expected a string literal for macro argument `modulus`",
-                        )
-                        .to_compile_error()
-                        .into();
-                    }
-                }
+        let params = match item.parse_params(&[
+            ParamSpec::required("modulus"),
+            ParamSpec::optional("serde_repr"),
+        ]) {
+            Ok(params) => params,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let modulus = match params.lit_str("modulus") {
+            Ok(modulus) => modulus.unwrap().value(),
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let montgomery = match params.lit_str("serde_repr") {
+            Ok(None) => false,
+            Ok(Some(serde_repr)) => match serde_repr.value().as_str() {
+                "canonical" => false,
+                "montgomery" => true,
                 _ => {
-                    panic!("Unknown parameter {}", param.name);
+                    return syn::Error::new_spanned(
+                        serde_repr,
+                        "Expected \"canonical\" or \"montgomery\" for macro argument `serde_repr`",
+                    )
+                    .to_compile_error()
+                    .into();
                 }
-            }
-        }
+            },
+            Err(err) => return err.to_compile_error().into(),
+        };
 
         // Parsing the parameters is over at this point
 
         let mod_idx = MOD_IDX.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
 
-        let modulus = modulus.expect("modulus parameter is required");
         let modulus_bytes = string_to_bytes(&modulus);
-        let mut limbs = modulus_bytes.len();
-        let mut block_size = 32;
-
-        if limbs <= 32 {
-            limbs = 32;
-        } else if limbs <= 48 {
-            limbs = 48;
-            block_size = 16;
-        } else {
-            panic!("limbs must be at most 48");
-        }
+        let (limbs, block_size) = limbs_and_block_size(modulus_bytes.len());
 
         let modulus_bytes = modulus_bytes
             .into_iter()
@@ -106,6 +155,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
         create_extern_func!(is_eq_extern_func);
         create_extern_func!(hint_sqrt_extern_func);
         create_extern_func!(hint_non_qr_extern_func);
+        create_extern_func!(hint_inverse_extern_func);
         create_extern_func!(moduli_setup_extern_func);
 
         let block_size = proc_macro::Literal::usize_unsuffixed(block_size);
@@ -113,6 +163,157 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
 
         let module_name = format_ident!("algebra_impl_{}", mod_idx);
 
+        let modulus_biguint = BigUint::from_bytes_le(&modulus_bytes);
+
+        // With the Montgomery representation, elements are still stored in canonical form since
+        // that is what the intrinsics operate on, but they are (de)serialized in Montgomery form.
+        let (serde_impl, montgomery_impl) = if montgomery {
+            if modulus_biguint.bits() == 0 || !modulus_biguint.bit(0) {
+                return syn::Error::new_spanned(
+                    struct_name,
+                    "Montgomery representation requires an odd modulus",
+                )
+                .to_compile_error()
+                .into();
+            }
+            // R = 2^(64 * n) where n is the number of 64-bit words needed to hold the modulus,
+            // as in arkworks and halo2curves.
+            let r_bits = modulus_biguint.bits().div_ceil(64) * 64;
+            let r = (BigUint::from(1u8) << r_bits) % &modulus_biguint;
+            let r_inv = r
+                .modinv(&modulus_biguint)
+                .expect("R is invertible modulo an odd modulus");
+            let to_limbs = |x: BigUint| {
+                x.to_bytes_le()
+                    .into_iter()
+                    .chain(core::iter::repeat(0u8))
+                    .take(limbs)
+                    .collect::<Vec<_>>()
+            };
+            let r_bytes = to_limbs(r);
+            let r_inv_bytes = to_limbs(r_inv);
+            let montgomery_impl = quote::quote_spanned! { span.into() =>
+                impl #struct_name {
+                    /// R modulo the modulus, where R is the Montgomery radix.
+                    const MONTGOMERY_R: Self = Self::from_const_bytes([#(#r_bytes),*]);
+                    /// The inverse of R modulo the modulus.
+                    const MONTGOMERY_R_INV: Self = Self::from_const_bytes([#(#r_inv_bytes),*]);
+
+                    /// Creates an element from its Montgomery form `self * R`, as little-endian
+                    /// bytes. This is the raw representation of arkworks and halo2curves field
+                    /// elements.
+                    pub fn from_montgomery_le_bytes(bytes: &[u8]) -> Self {
+                        <Self as openvm_algebra_guest::IntMod>::from_le_bytes_unchecked(bytes)
+                            * &Self::MONTGOMERY_R_INV
+                    }
+
+                    /// Returns the Montgomery form `self * R` of this element as canonical
+                    /// little-endian bytes.
+                    pub fn to_montgomery_le_bytes(&self) -> [u8; #limbs] {
+                        let mont = self * &Self::MONTGOMERY_R;
+                        openvm_algebra_guest::IntMod::assert_reduced(&mont);
+                        mont.0
+                    }
+                }
+
+            };
+            let serde_impl = quote::quote_spanned! { span.into() =>
+                impl ::openvm_algebra_guest::serde::Serialize for #struct_name {
+                    fn serialize<S: ::openvm_algebra_guest::serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+                        <[u8; #limbs] as ::openvm_algebra_guest::BigArray<'_, u8>>::serialize(
+                            &self.to_montgomery_le_bytes(),
+                            serializer,
+                        )
+                    }
+                }
+
+                impl<'de> ::openvm_algebra_guest::serde::Deserialize<'de> for #struct_name {
+                    fn deserialize<D: ::openvm_algebra_guest::serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+                        let bytes = <[u8; #limbs] as ::openvm_algebra_guest::BigArray<'de, u8>>::deserialize(deserializer)?;
+                        Ok(Self::from_montgomery_le_bytes(&bytes))
+                    }
+                }
+            };
+            (serde_impl, montgomery_impl)
+        } else {
+            (quote::quote! {}, quote::quote! {})
+        };
+        // Serialization is only implemented with the `serde` feature, the canonical representation
+        // is derived.
+        let (serde_derive, serde_struct_attr, serde_field_attr, serde_impl) = if !cfg!(
+            feature = "serde"
+        ) {
+            (
+                quote::quote! {},
+                quote::quote! {},
+                quote::quote! {},
+                quote::quote! {},
+            )
+        } else if montgomery {
+            (
+                quote::quote! {},
+                quote::quote! {},
+                quote::quote! {},
+                serde_impl,
+            )
+        } else {
+            (
+                quote::quote! { ::openvm_algebra_guest::serde::Serialize, ::openvm_algebra_guest::serde::Deserialize },
+                quote::quote! { #[serde(crate = "::openvm_algebra_guest::serde")] },
+                quote::quote! { #[serde(with = "::openvm_algebra_guest::BigArray")] },
+                quote::quote! {},
+            )
+        };
+
+        let host_add_assign = host_math! {
+            *self = Self::from_biguint(
+                (self.as_biguint() + other.as_biguint()) % Self::modulus_biguint(),
+            );
+        };
+        let host_sub_assign = host_math! {
+            let modulus = Self::modulus_biguint();
+            *self = Self::from_biguint(
+                (self.as_biguint() + modulus.clone() - other.as_biguint()) % modulus,
+            );
+        };
+        let host_mul_assign = host_math! {
+            *self = Self::from_biguint(
+                (self.as_biguint() * other.as_biguint()) % Self::modulus_biguint(),
+            );
+        };
+        let host_div_assign = host_math! {
+            let modulus = Self::modulus_biguint();
+            let inv = other.as_biguint().modinv(&modulus).unwrap();
+            *self = Self::from_biguint((self.as_biguint() * inv) % modulus);
+        };
+        let host_div = host_math! {
+            let modulus = Self::modulus_biguint();
+            let inv = other.as_biguint().modinv(&modulus).unwrap();
+            Self::from_biguint((self.as_biguint() * inv) % modulus)
+        };
+        let biguint_impl = if cfg!(feature = "host-math") {
+            quote::quote! {
+                #[cfg(not(target_os = "zkvm"))]
+                fn modulus_biguint() -> ::openvm_algebra_guest::num_bigint::BigUint {
+                    ::openvm_algebra_guest::num_bigint::BigUint::from_bytes_le(&Self::MODULUS)
+                }
+
+                #[cfg(not(target_os = "zkvm"))]
+                fn from_biguint(biguint: ::openvm_algebra_guest::num_bigint::BigUint) -> Self {
+                    let mut bytes = biguint.to_bytes_le();
+                    bytes.resize(#limbs, 0);
+                    Self::from_le_bytes_unchecked(&bytes)
+                }
+
+                #[cfg(not(target_os = "zkvm"))]
+                fn as_biguint(&self) -> ::openvm_algebra_guest::num_bigint::BigUint {
+                    ::openvm_algebra_guest::num_bigint::BigUint::from_bytes_le(self.as_le_bytes())
+                }
+            }
+        } else {
+            quote::quote! {}
+        };
+
         let result = TokenStream::from(quote::quote_spanned! { span.into() =>
             /// An element of the ring of integers modulo a positive integer.
             /// The element is internally represented as a fixed size array of bytes.
@@ -125,9 +326,14 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
             ///
             /// See [`assert_reduced`](openvm_algebra_guest::IntMod::assert_reduced) and
             /// [`is_reduced`](openvm_algebra_guest::IntMod::is_reduced).
-            #[derive(Clone, Eq, serde::Serialize, serde::Deserialize)]
+            #[derive(Clone, Eq, #serde_derive)]
+            #serde_struct_attr
             #[repr(C, align(#block_size))]
-            pub struct #struct_name(#[serde(with = "openvm_algebra_guest::BigArray")] [u8; #limbs]);
+            pub struct #struct_name(#serde_field_attr [u8; #limbs]);
+
+            #montgomery_impl
+
+            #serde_impl
 
             extern "C" {
                 fn #add_extern_func(rd: usize, rs1: usize, rs2: usize);
@@ -137,6 +343,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 fn #is_eq_extern_func(rs1: usize, rs2: usize) -> bool;
                 fn #hint_sqrt_extern_func(rs1: usize);
                 fn #hint_non_qr_extern_func();
+                fn #hint_inverse_extern_func(rs1: usize);
                 fn #moduli_setup_extern_func();
             }
 
@@ -158,9 +365,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 fn add_assign_impl(&mut self, other: &Self) {
                     #[cfg(not(target_os = "zkvm"))]
                     {
-                        *self = Self::from_biguint(
-                            (self.as_biguint() + other.as_biguint()) % Self::modulus_biguint(),
-                        );
+                        #host_add_assign
                     }
                     #[cfg(target_os = "zkvm")]
                     {
@@ -179,10 +384,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 fn sub_assign_impl(&mut self, other: &Self) {
                     #[cfg(not(target_os = "zkvm"))]
                     {
-                        let modulus = Self::modulus_biguint();
-                        *self = Self::from_biguint(
-                            (self.as_biguint() + modulus.clone() - other.as_biguint()) % modulus,
-                        );
+                        #host_sub_assign
                     }
                     #[cfg(target_os = "zkvm")]
                     {
@@ -201,9 +403,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 fn mul_assign_impl(&mut self, other: &Self) {
                     #[cfg(not(target_os = "zkvm"))]
                     {
-                        *self = Self::from_biguint(
-                            (self.as_biguint() * other.as_biguint()) % Self::modulus_biguint(),
-                        );
+                        #host_mul_assign
                     }
                     #[cfg(target_os = "zkvm")]
                     {
@@ -222,9 +422,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 fn div_assign_unsafe_impl(&mut self, other: &Self) {
                     #[cfg(not(target_os = "zkvm"))]
                     {
-                        let modulus = Self::modulus_biguint();
-                        let inv = other.as_biguint().modinv(&modulus).unwrap();
-                        *self = Self::from_biguint((self.as_biguint() * inv) % modulus);
+                        #host_div_assign
                     }
                     #[cfg(target_os = "zkvm")]
                     {
@@ -292,7 +490,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 /// SAFETY: `dst_ptr` must be a raw pointer to `&mut Self`.
                 /// It will be written to only at the very end .
                 #[inline(always)]
-                unsafe fn mul_refs_impl(&self, other: &Self, dst_ptr: *mut Self) {
+                unsafe fn mul_refs_impl<const CHECK_SETUP: bool>(&self, other: &Self, dst_ptr: *mut Self) {
                     #[cfg(not(target_os = "zkvm"))]
                     {
                         let mut res = self.clone();
@@ -303,7 +501,9 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                     }
                     #[cfg(target_os = "zkvm")]
                     {
-                        Self::set_up_once();
+                        if CHECK_SETUP {
+                            Self::set_up_once();
+                        }
                         unsafe {
                             #mul_extern_func(
                                 dst_ptr as usize,
@@ -318,9 +518,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 fn div_unsafe_refs_impl(&self, other: &Self) -> Self {
                     #[cfg(not(target_os = "zkvm"))]
                     {
-                        let modulus = Self::modulus_biguint();
-                        let inv = other.as_biguint().modinv(&modulus).unwrap();
-                        Self::from_biguint((self.as_biguint() * inv) % modulus)
+                        #host_div
                     }
                     #[cfg(target_os = "zkvm")]
                     {
@@ -356,11 +554,11 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 #[inline(always)]
                 #[cfg(target_os = "zkvm")]
                 fn set_up_once() {
-                    static is_setup: ::openvm_algebra_guest::once_cell::race::OnceBool = ::openvm_algebra_guest::once_cell::race::OnceBool::new();
-                    is_setup.get_or_init(|| {
+                    static is_setup: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+                    if !is_setup.load(core::sync::atomic::Ordering::Relaxed) {
                         unsafe { #moduli_setup_extern_func(); }
-                        true
-                    });
+                        is_setup.store(true, core::sync::atomic::Ordering::Relaxed);
+                    }
                 }
                 #[inline(always)]
                 #[cfg(not(target_os = "zkvm"))]
@@ -449,20 +647,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                         core::array::from_fn(|i| self.0[#limbs - 1 - i])
                     }
 
-                    #[cfg(not(target_os = "zkvm"))]
-                    fn modulus_biguint() -> num_bigint::BigUint {
-                        num_bigint::BigUint::from_bytes_le(&Self::MODULUS)
-                    }
-
-                    #[cfg(not(target_os = "zkvm"))]
-                    fn from_biguint(biguint: num_bigint::BigUint) -> Self {
-                        Self(openvm::utils::biguint_to_limbs(&biguint))
-                    }
-
-                    #[cfg(not(target_os = "zkvm"))]
-                    fn as_biguint(&self) -> num_bigint::BigUint {
-                        num_bigint::BigUint::from_bytes_le(self.as_le_bytes())
-                    }
+                    #biguint_impl
 
                     #[inline(always)]
                     fn neg_assign(&mut self) {
@@ -487,7 +672,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                         unsafe {
                             // SAFETY: we borrow self as &Self and as *mut Self but
                             // the latter will only be written to at the very end.
-                            self.mul_refs_impl(self, self as *const Self as *mut Self);
+                            self.mul_refs_impl::<true>(self, self as *const Self as *mut Self);
                         }
                     }
 
@@ -544,6 +729,13 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                         self.add_refs_impl::<CHECK_SETUP>(other, uninit.as_mut_ptr());
                         uninit.assume_init()
                     }
+
+                    #[inline(always)]
+                    unsafe fn mul_ref<const CHECK_SETUP: bool>(&self, other: &Self) -> Self {
+                        let mut uninit: core::mem::MaybeUninit<#struct_name> = core::mem::MaybeUninit::uninit();
+                        self.mul_refs_impl::<CHECK_SETUP>(other, uninit.as_mut_ptr());
+                        uninit.assume_init()
+                    }
                 }
 
                 impl<'a> core::ops::AddAssign<&'a #struct_name> for #struct_name {
@@ -669,7 +861,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                     fn mul(self, other: &'a #struct_name) -> Self::Output {
                         let mut uninit: core::mem::MaybeUninit<#struct_name> = core::mem::MaybeUninit::uninit();
                         unsafe {
-                            self.mul_refs_impl(other, uninit.as_mut_ptr());
+                            self.mul_refs_impl::<true>(other, uninit.as_mut_ptr());
                             uninit.assume_init()
                         }
                     }
@@ -792,12 +984,22 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
 
         output.push(result);
 
-        let modulus_biguint = BigUint::from_bytes_le(&modulus_bytes);
         let modulus_is_prime = is_prime(&modulus_biguint, None);
 
         if modulus_is_prime.probably() {
-            // implement Field and Sqrt traits for prime moduli
-            let field_and_sqrt_impl = TokenStream::from(quote::quote_spanned! { span.into() =>
+            // implement Field and Sqrt traits for prime moduli, the hint-based parts only with the
+            // `hints` feature
+            let hints = cfg!(feature = "hints");
+            let field_invert = if hints {
+                quote::quote! {
+                    fn invert(&self) -> Option<Self> {
+                        #struct_name::invert(self)
+                    }
+                }
+            } else {
+                quote::quote! {}
+            };
+            let field_impl = TokenStream::from(quote::quote_spanned! { span.into() =>
                 impl ::openvm_algebra_guest::Field for #struct_name {
                     const ZERO: Self = <Self as ::openvm_algebra_guest::IntMod>::ZERO;
                     const ONE: Self = <Self as ::openvm_algebra_guest::IntMod>::ONE;
@@ -812,8 +1014,65 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                         ::openvm_algebra_guest::IntMod::square_assign(self);
                     }
 
+                    #field_invert
                 }
+            });
+            output.push(field_impl);
+            if !hints {
+                continue;
+            }
 
+            let host_invert = host_math! {
+                let modulus = <Self as ::openvm_algebra_guest::IntMod>::modulus_biguint();
+                match (<Self as ::openvm_algebra_guest::IntMod>::as_biguint(self) % &modulus).modinv(&modulus) {
+                    Some(inv) => Some((true, <Self as ::openvm_algebra_guest::IntMod>::from_biguint(inv))),
+                    None => Some((false, <Self as ::openvm_algebra_guest::IntMod>::ZERO)),
+                }
+            };
+            let host_sqrt = host_math! {
+                let modulus = <Self as ::openvm_algebra_guest::IntMod>::modulus_biguint();
+                let non_qr = <Self as ::openvm_algebra_guest::IntMod>::as_biguint(Self::get_non_qr());
+                let (is_square, sqrt) = ::openvm_algebra_guest::host_hints::sqrt_hint(
+                    &<Self as ::openvm_algebra_guest::IntMod>::as_biguint(self),
+                    &modulus,
+                    &non_qr,
+                );
+                Some((is_square, <Self as ::openvm_algebra_guest::IntMod>::from_biguint(sqrt)))
+            };
+            let host_non_qr = host_math! {
+                let modulus = <Self as ::openvm_algebra_guest::IntMod>::modulus_biguint();
+                alloc::boxed::Box::new(<Self as ::openvm_algebra_guest::IntMod>::from_biguint(
+                    ::openvm_algebra_guest::host_hints::find_non_qr(&modulus),
+                ))
+            };
+            let fallback_invert = fallback! {
+                let mut inv = Self([0u8; #limbs]);
+                let is_invertible = ::openvm_algebra_guest::fallback::inverse(
+                    &self.0,
+                    &<Self as ::openvm_algebra_guest::IntMod>::MODULUS,
+                    &mut inv.0,
+                );
+                return Some((is_invertible, inv));
+            };
+            let fallback_sqrt = fallback! {
+                let mut sqrt = Self([0u8; #limbs]);
+                let is_square = ::openvm_algebra_guest::fallback::sqrt(
+                    &self.0,
+                    &<Self as ::openvm_algebra_guest::IntMod>::MODULUS,
+                    &Self::get_non_qr().0,
+                    &mut sqrt.0,
+                );
+                return Some((is_square, sqrt));
+            };
+            let fallback_non_qr = fallback! {
+                let mut non_qr = Self([0u8; #limbs]);
+                ::openvm_algebra_guest::fallback::non_qr(
+                    &<Self as ::openvm_algebra_guest::IntMod>::MODULUS,
+                    &mut non_qr.0,
+                );
+                return alloc::boxed::Box::new(non_qr);
+            };
+            let sqrt_impl = TokenStream::from(quote::quote_spanned! { span.into() =>
                 impl openvm_algebra_guest::Sqrt for #struct_name {
                     // Returns a sqrt of self if it exists, otherwise None.
                     // Note that we use a hint-based approach to prove whether the square root exists.
@@ -837,6 +1096,78 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 }
 
                 impl #struct_name {
+                    /// Returns the multiplicative inverse of `self`, or `None` if `self` is zero.
+                    ///
+                    /// The inverse is hinted by the host and checked with a single multiplication,
+                    /// so unlike [DivUnsafe](::openvm_algebra_guest::DivUnsafe) this fails
+                    /// deterministically on zero.
+                    pub fn invert(&self) -> Option<Self> {
+                        match self.honest_host_invert() {
+                            Some(inv) => inv,
+                            // host is dishonest
+                            None => {
+                                // host is dishonest, enter infinite loop
+                                loop {
+                                    openvm::io::println("ERROR: Inverse hint is invalid. Entering infinite loop.");
+                                }
+                            }
+                        }
+                    }
+
+                    // Returns None if the hint is incorrect (i.e. the host is dishonest)
+                    // Returns Some(None) if the hint proves that self is zero
+                    // Otherwise, returns Some(Some(inv)) where inv is the inverse of self
+                    fn honest_host_invert(&self) -> Option<Option<Self>> {
+                        let (is_invertible, inv) = self.hint_inverse_impl()?;
+
+                        if is_invertible {
+                            // ensure inv < modulus
+                            <Self as ::openvm_algebra_guest::IntMod>::assert_reduced(&inv);
+                            if &(self * &inv) == &<Self as ::openvm_algebra_guest::IntMod>::ONE {
+                                Some(Some(inv))
+                            } else {
+                                None
+                            }
+                        } else if self == &<Self as ::openvm_algebra_guest::IntMod>::ZERO {
+                            Some(None)
+                        } else {
+                            None
+                        }
+                    }
+
+                    // Returns None if the hint is malformed.
+                    // Otherwise, returns Some((is_invertible, inv)) where inv is the inverse of self
+                    // if is_invertible is true, and unspecified otherwise.
+                    fn hint_inverse_impl(&self) -> Option<(bool, Self)> {
+                        #[cfg(not(target_os = "zkvm"))]
+                        {
+                            #host_invert
+                        }
+                        #[cfg(target_os = "zkvm")]
+                        {
+                            use ::openvm_algebra_guest::{openvm_custom_insn, openvm_rv32im_guest}; // needed for hint_store_u32!, hint_buffer_u32! and hint_select
+
+                            #fallback_invert
+                            let is_invertible = core::mem::MaybeUninit::<u32>::uninit();
+                            let inv = core::mem::MaybeUninit::<#struct_name>::uninit();
+                            unsafe {
+                                let previous_hint_queue = openvm_rv32im_guest::selected_hint_queue();
+                                openvm_rv32im_guest::hint_select(openvm_rv32im_guest::HintQueue::Modular as u32);
+                                #hint_inverse_extern_func(self as *const #struct_name as usize);
+                                let is_invertible_ptr = is_invertible.as_ptr() as *const u32;
+                                openvm_rv32im_guest::hint_store_u32!(is_invertible_ptr);
+                                openvm_rv32im_guest::hint_buffer_u32!(inv.as_ptr() as *const u8, <#struct_name as ::openvm_algebra_guest::IntMod>::NUM_LIMBS / 4);
+                                openvm_rv32im_guest::hint_select(previous_hint_queue);
+                                let is_invertible = is_invertible.assume_init();
+                                if is_invertible == 0 || is_invertible == 1 {
+                                    Some((is_invertible == 1, inv.assume_init()))
+                                } else {
+                                    None
+                                }
+                            }
+                        }
+                    }
+
                     // Returns None if the hint is incorrect (i.e. the host is dishonest)
                     // Returns Some(None) if the hint proves that self is not a quadratic residue
                     // Otherwise, returns Some(Some(sqrt)) where sqrt is a square root of self
@@ -871,19 +1202,23 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                     fn hint_sqrt_impl(&self) -> Option<(bool, Self)> {
                         #[cfg(not(target_os = "zkvm"))]
                         {
-                            unimplemented!();
+                            #host_sqrt
                         }
                         #[cfg(target_os = "zkvm")]
                         {
-                            use ::openvm_algebra_guest::{openvm_custom_insn, openvm_rv32im_guest}; // needed for hint_store_u32! and hint_buffer_u32!
+                            use ::openvm_algebra_guest::{openvm_custom_insn, openvm_rv32im_guest}; // needed for hint_store_u32!, hint_buffer_u32! and hint_select
 
+                            #fallback_sqrt
                             let is_square = core::mem::MaybeUninit::<u32>::uninit();
                             let sqrt = core::mem::MaybeUninit::<#struct_name>::uninit();
                             unsafe {
+                                let previous_hint_queue = openvm_rv32im_guest::selected_hint_queue();
+                                openvm_rv32im_guest::hint_select(openvm_rv32im_guest::HintQueue::Modular as u32);
                                 #hint_sqrt_extern_func(self as *const #struct_name as usize);
                                 let is_square_ptr = is_square.as_ptr() as *const u32;
                                 openvm_rv32im_guest::hint_store_u32!(is_square_ptr);
                                 openvm_rv32im_guest::hint_buffer_u32!(sqrt.as_ptr() as *const u8, <#struct_name as ::openvm_algebra_guest::IntMod>::NUM_LIMBS / 4);
+                                openvm_rv32im_guest::hint_select(previous_hint_queue);
                                 let is_square = is_square.assume_init();
                                 if is_square == 0 || is_square == 1 {
                                     Some((is_square == 1, sqrt.assume_init()))
@@ -898,18 +1233,22 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                     fn init_non_qr() -> alloc::boxed::Box<#struct_name> {
                         #[cfg(not(target_os = "zkvm"))]
                         {
-                            unimplemented!();
+                            #host_non_qr
                         }
                         #[cfg(target_os = "zkvm")]
                         {
-                            use ::openvm_algebra_guest::{openvm_custom_insn, openvm_rv32im_guest}; // needed for hint_buffer_u32!
+                            use ::openvm_algebra_guest::{openvm_custom_insn, openvm_rv32im_guest}; // needed for hint_buffer_u32! and hint_select
 
+                            #fallback_non_qr
                             let mut non_qr_uninit = core::mem::MaybeUninit::<Self>::uninit();
                             let mut non_qr;
                             unsafe {
+                                let previous_hint_queue = openvm_rv32im_guest::selected_hint_queue();
+                                openvm_rv32im_guest::hint_select(openvm_rv32im_guest::HintQueue::Modular as u32);
                                 #hint_non_qr_extern_func();
                                 let ptr = non_qr_uninit.as_ptr() as *const u8;
                                 openvm_rv32im_guest::hint_buffer_u32!(ptr, <Self as ::openvm_algebra_guest::IntMod>::NUM_LIMBS / 4);
+                                openvm_rv32im_guest::hint_select(previous_hint_queue);
                                 non_qr = non_qr_uninit.assume_init();
                             }
                             // ensure non_qr < modulus
@@ -940,7 +1279,7 @@ pub fn moduli_declare(input: TokenStream) -> TokenStream {
                 }
             });
 
-            output.push(field_and_sqrt_impl);
+            output.push(sqrt_impl);
         }
     }
 
@@ -979,17 +1318,7 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
         println!("[init] modulus #{} = {}", mod_idx, modulus);
 
         let modulus_bytes = string_to_bytes(&modulus);
-        let mut limbs = modulus_bytes.len();
-        let mut block_size = 32;
-
-        if limbs <= 32 {
-            limbs = 32;
-        } else if limbs <= 48 {
-            limbs = 48;
-            block_size = 16;
-        } else {
-            panic!("limbs must be at most 48");
-        }
+        let (limbs, block_size) = limbs_and_block_size(modulus_bytes.len());
 
         let block_size = proc_macro::Literal::usize_unsuffixed(block_size);
         let block_size = syn::Lit::new(block_size.to_string().parse::<_>().unwrap());
@@ -1048,9 +1377,16 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
                 &format!("{}Mod", chars.iter().collect::<String>()),
                 span.into(),
             );
+            let op = syn::Ident::new(op_type, span.into());
+            let fallback = fallback! {
+                return unsafe {
+                    ::openvm_algebra_guest::fallback::#op(rd, rs1, rs2, &[#(#modulus_bytes),*])
+                };
+            };
             externs.push(quote::quote_spanned! { span.into() =>
                 #[no_mangle]
                 extern "C" fn #func_name(rd: usize, rs1: usize, rs2: usize) {
+                    #fallback
                     openvm::platform::custom_insn_r!(
                         opcode = ::openvm_algebra_guest::OPCODE,
                         funct3 = ::openvm_algebra_guest::MODULAR_ARITHMETIC_FUNCT3 as usize,
@@ -1065,9 +1401,15 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
 
         let is_eq_extern_func =
             syn::Ident::new(&format!("is_eq_extern_func_{}", modulus_hex), span.into());
+        let fallback = fallback! {
+            return unsafe {
+                ::openvm_algebra_guest::fallback::is_eq(rs1, rs2, &[#(#modulus_bytes),*])
+            };
+        };
         externs.push(quote::quote_spanned! { span.into() =>
             #[no_mangle]
             extern "C" fn #is_eq_extern_func(rs1: usize, rs2: usize) -> bool {
+                #fallback
                 let mut x: u32;
                 openvm::platform::custom_insn_r!(
                     opcode = ::openvm_algebra_guest::OPCODE,
@@ -1081,49 +1423,74 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
             }
         });
 
-        let hint_non_qr_extern_func = syn::Ident::new(
-            &format!("hint_non_qr_extern_func_{}", modulus_hex),
-            span.into(),
-        );
-        externs.push(quote::quote_spanned! { span.into() =>
-            #[no_mangle]
-            extern "C" fn #hint_non_qr_extern_func() {
-                openvm::platform::custom_insn_r!(
-                    opcode = ::openvm_algebra_guest::OPCODE,
-                    funct3 = ::openvm_algebra_guest::MODULAR_ARITHMETIC_FUNCT3 as usize,
-                    funct7 = ::openvm_algebra_guest::ModArithBaseFunct7::HintNonQr as usize + #mod_idx * (::openvm_algebra_guest::ModArithBaseFunct7::MODULAR_ARITHMETIC_MAX_KINDS as usize),
-                    rd = Const "x0",
-                    rs1 = Const "x0",
-                    rs2 = Const "x0"
-                );
-            }
+        // The hint instructions are only used with the `hints` feature.
+        if cfg!(feature = "hints") {
+            let hint_non_qr_extern_func = syn::Ident::new(
+                &format!("hint_non_qr_extern_func_{}", modulus_hex),
+                span.into(),
+            );
+            externs.push(quote::quote_spanned! { span.into() =>
+                #[no_mangle]
+                extern "C" fn #hint_non_qr_extern_func() {
+                    openvm::platform::custom_insn_r!(
+                        opcode = ::openvm_algebra_guest::OPCODE,
+                        funct3 = ::openvm_algebra_guest::MODULAR_ARITHMETIC_FUNCT3 as usize,
+                        funct7 = ::openvm_algebra_guest::ModArithBaseFunct7::HintNonQr as usize + #mod_idx * (::openvm_algebra_guest::ModArithBaseFunct7::MODULAR_ARITHMETIC_MAX_KINDS as usize),
+                        rd = Const "x0",
+                        rs1 = Const "x0",
+                        rs2 = Const "x0"
+                    );
+                }
 
 
-        });
+            });
 
-        // This function will be defined regardless of whether the modulus is prime or not,
-        // but it will be called only if the modulus is prime.
-        let hint_sqrt_extern_func = syn::Ident::new(
-            &format!("hint_sqrt_extern_func_{}", modulus_hex),
-            span.into(),
-        );
-        externs.push(quote::quote_spanned! { span.into() =>
-            #[no_mangle]
-            extern "C" fn #hint_sqrt_extern_func(rs1: usize) {
-                openvm::platform::custom_insn_r!(
-                    opcode = ::openvm_algebra_guest::OPCODE,
-                    funct3 = ::openvm_algebra_guest::MODULAR_ARITHMETIC_FUNCT3 as usize,
-                    funct7 = ::openvm_algebra_guest::ModArithBaseFunct7::HintSqrt as usize + #mod_idx * (::openvm_algebra_guest::ModArithBaseFunct7::MODULAR_ARITHMETIC_MAX_KINDS as usize),
-                    rd = Const "x0",
-                    rs1 = In rs1,
-                    rs2 = Const "x0"
-                );
-            }
-        });
+            // This function will be defined regardless of whether the modulus is prime or not,
+            // but it will be called only if the modulus is prime.
+            let hint_sqrt_extern_func = syn::Ident::new(
+                &format!("hint_sqrt_extern_func_{}", modulus_hex),
+                span.into(),
+            );
+            externs.push(quote::quote_spanned! { span.into() =>
+                #[no_mangle]
+                extern "C" fn #hint_sqrt_extern_func(rs1: usize) {
+                    openvm::platform::custom_insn_r!(
+                        opcode = ::openvm_algebra_guest::OPCODE,
+                        funct3 = ::openvm_algebra_guest::MODULAR_ARITHMETIC_FUNCT3 as usize,
+                        funct7 = ::openvm_algebra_guest::ModArithBaseFunct7::HintSqrt as usize + #mod_idx * (::openvm_algebra_guest::ModArithBaseFunct7::MODULAR_ARITHMETIC_MAX_KINDS as usize),
+                        rd = Const "x0",
+                        rs1 = In rs1,
+                        rs2 = Const "x0" // will be parsed as 0 and therefore transpiled to HINT_SQRT
+                    );
+                }
+            });
 
+            // Shares the funct7 of the sqrt hint, distinguished by `rs2`.
+            let hint_inverse_extern_func = syn::Ident::new(
+                &format!("hint_inverse_extern_func_{}", modulus_hex),
+                span.into(),
+            );
+            externs.push(quote::quote_spanned! { span.into() =>
+                #[no_mangle]
+                extern "C" fn #hint_inverse_extern_func(rs1: usize) {
+                    openvm::platform::custom_insn_r!(
+                        opcode = ::openvm_algebra_guest::OPCODE,
+                        funct3 = ::openvm_algebra_guest::MODULAR_ARITHMETIC_FUNCT3 as usize,
+                        funct7 = ::openvm_algebra_guest::ModArithBaseFunct7::HintSqrt as usize + #mod_idx * (::openvm_algebra_guest::ModArithBaseFunct7::MODULAR_ARITHMETIC_MAX_KINDS as usize),
+                        rd = Const "x0",
+                        rs1 = In rs1,
+                        rs2 = Const "x1" // will be parsed as 1 and therefore transpiled to HINT_INVERSE
+                    );
+                }
+            });
+        }
+
+        // There is nothing to set up without the extension.
+        let fallback = fallback! { return; };
         externs.push(quote::quote_spanned! { span.into() =>
             #[no_mangle]
             extern "C" fn #setup_extern_func() {
+                #fallback
                 #[cfg(target_os = "zkvm")]
                 {
                     let mut ptr = 0;
@@ -1182,6 +1549,12 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
         });
     }
 
+    // Programs which fall back to software run on VMs without the extension.
+    let require_extension_version = if cfg!(feature = "fallback") {
+        quote::quote! { openvm::platform::optional_extension_version!("modular", 1); }
+    } else {
+        quote::quote! { openvm::platform::require_extension_version!("modular", 1); }
+    };
     let total_limbs_cnt = two_modular_limbs_flattened_list.len();
     let cnt_limbs_list_len = limb_list_borders.len();
     TokenStream::from(quote::quote_spanned! { span.into() =>
@@ -1189,6 +1562,8 @@ pub fn moduli_init(input: TokenStream) -> TokenStream {
         #[allow(non_snake_case)]
         #[cfg(target_os = "zkvm")]
         mod openvm_intrinsics_ffi {
+            #require_extension_version
+
             #(#externs)*
         }
         #[allow(non_snake_case, non_upper_case_globals)]
@@ -0,0 +1,53 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use openvm_algebra_guest::{field::ComplexConjugate, DivAssignUnsafe, DivUnsafe, IntMod};
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_macros::moduli_declare! {
+    Bls12_377Fp { modulus = "0x01ae3a4617c510eac63b05c06ca1493b1a22d9f300f5138f1ef3622fba094800170b5d44300000008508c00000000001" }
+}
+
+// -1 is a square modulo the BLS12-377 base field prime, but -5 is not, so X^2 + 5 is irreducible.
+openvm_algebra_complex_macros::complex_declare! {
+    Bls12_377Fp2 { mod_type = Bls12_377Fp, non_residue = -5 }
+}
+
+openvm::init!("openvm_init_complex_bls12_377.rs");
+
+pub fn main() {
+    // u^2 = -5
+    let u = Bls12_377Fp2::new(Bls12_377Fp::ZERO, Bls12_377Fp::ONE);
+    assert_eq!(
+        &u * &u,
+        Bls12_377Fp2::new(-Bls12_377Fp::from_u8(5), Bls12_377Fp::ZERO)
+    );
+
+    let mut a = Bls12_377Fp2::new(Bls12_377Fp::from_u32(10), Bls12_377Fp::from_u32(21));
+    let mut b = Bls12_377Fp2::new(Bls12_377Fp::from_u32(32), Bls12_377Fp::from_u32(47));
+
+    for _ in 0..16 {
+        let c = &a + &b;
+        assert_eq!(&c - &b, a);
+        assert_eq!(&a * &c, &(&a * &a) + &(&a * &b));
+
+        let quotient = a.clone().div_unsafe(&b);
+        assert_eq!(&quotient * &b, a);
+
+        let mut inverse = Bls12_377Fp2::ONE;
+        inverse.div_assign_unsafe(&b);
+        assert_eq!(&inverse * &b, Bls12_377Fp2::ONE);
+
+        // The norm of `a` lies in the base field.
+        let norm = &a * &a.clone().conjugate();
+        assert_eq!(norm.c1, Bls12_377Fp::ZERO);
+
+        a *= &b;
+        b *= &a;
+    }
+
+    assert_ne!(a, b);
+}
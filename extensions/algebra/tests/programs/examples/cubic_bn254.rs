@@ -0,0 +1,58 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use openvm_algebra_guest::{DivAssignUnsafe, DivUnsafe, IntMod};
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_macros::moduli_declare! {
+    Bn254Fp { modulus = "21888242871839275222246405745257275088696311157297823662689037894645226208583" }
+}
+
+// 3 is not a cube modulo the BN254 base field prime, so X^3 - 3 is irreducible.
+openvm_algebra_cubic_macros::cubic_declare! {
+    Bn254Fp3 { mod_type = Bn254Fp, non_residue = 3 }
+}
+
+openvm::init!("openvm_init_cubic_bn254.rs");
+
+pub fn main() {
+    // w^3 = 3
+    let w = Bn254Fp3::new(Bn254Fp::ZERO, Bn254Fp::ONE, Bn254Fp::ZERO);
+    assert_eq!(
+        &(&w * &w) * &w,
+        Bn254Fp3::new(Bn254Fp::from_u8(3), Bn254Fp::ZERO, Bn254Fp::ZERO)
+    );
+
+    let mut a = Bn254Fp3::new(
+        Bn254Fp::from_u32(10),
+        Bn254Fp::from_u32(21),
+        Bn254Fp::from_u32(33),
+    );
+    let mut b = Bn254Fp3::new(
+        Bn254Fp::from_u32(32),
+        Bn254Fp::from_u32(47),
+        Bn254Fp::from_u32(59),
+    );
+
+    for _ in 0..16 {
+        let c = &a + &b;
+        assert_eq!(&c - &b, a);
+        assert_eq!(&a * &c, &(&a * &a) + &(&a * &b));
+
+        let quotient = a.clone().div_unsafe(&b);
+        assert_eq!(&quotient * &b, a);
+
+        let mut inverse = Bn254Fp3::ONE;
+        inverse.div_assign_unsafe(&b);
+        assert_eq!(&inverse * &b, Bn254Fp3::ONE);
+
+        a *= &b;
+        b *= &a;
+    }
+
+    assert_ne!(a, b);
+    assert_eq!(&a - &a, Bn254Fp3::ZERO);
+}
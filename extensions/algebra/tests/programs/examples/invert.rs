@@ -0,0 +1,43 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use openvm_algebra_guest::IntMod;
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_macros::moduli_declare! {
+    Secp256k1Coord {
+        modulus = "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE FFFFFC2F",
+    }
+}
+
+openvm::init!("openvm_init_invert.rs");
+
+pub fn main() {
+    let one = <Secp256k1Coord as IntMod>::ONE;
+    let zero = <Secp256k1Coord as IntMod>::ZERO;
+
+    for x in [1u32, 2, 3, 7, 1 << 31] {
+        let a = Secp256k1Coord::from_u32(x);
+        let inv = a.invert().unwrap();
+        assert_eq!(&a * &inv, one);
+    }
+
+    let minus_one = &zero - &one;
+    assert_eq!(minus_one.invert(), Some(minus_one.clone()));
+
+    assert_eq!(zero.invert(), None);
+
+    let mut batch: [Secp256k1Coord; 0] = [];
+    Secp256k1Coord::batch_invert(&mut batch);
+    let elements = [1u32, 2, 3, 7, 1 << 31].map(Secp256k1Coord::from_u32);
+    for n in 1..=elements.len() {
+        let mut batch = elements[..n].to_vec();
+        Secp256k1Coord::batch_invert(&mut batch);
+        for (x, inv) in elements.iter().zip(&batch) {
+            assert_eq!(x * inv, one);
+        }
+    }
+}
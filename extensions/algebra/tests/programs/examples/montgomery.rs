@@ -0,0 +1,45 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm_algebra_guest::IntMod;
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_macros::moduli_declare! {
+    Bn254Fp {
+        modulus = "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+        serde_repr = "montgomery",
+    }
+}
+
+openvm::init!("openvm_init_montgomery.rs");
+
+// 2^256 mod p, the Montgomery form of one
+const R: [u8; 32] = [
+    0x9d, 0x0d, 0x8f, 0xc5, 0x8d, 0x43, 0x5d, 0xd3, 0x3d, 0x0b, 0xc7, 0xf5, 0x28, 0xeb, 0x78, 0x0a,
+    0x2c, 0x46, 0x79, 0x78, 0x6f, 0xa3, 0x6e, 0x66, 0x2f, 0xdf, 0x07, 0x9a, 0xc1, 0x77, 0x0a, 0x0e,
+];
+
+fn serialize<T: serde::Serialize>(x: &T) -> Vec<u32> {
+    openvm::serde::to_vec(x).unwrap()
+}
+
+pub fn main() {
+    let one = <Bn254Fp as IntMod>::ONE;
+    assert_eq!(one.to_montgomery_le_bytes(), R);
+    assert_eq!(Bn254Fp::from_montgomery_le_bytes(&R), one);
+    // serializes the same as the raw Montgomery bytes
+    assert_eq!(serialize(&one), serialize(&R));
+    assert_eq!(serialize(&<Bn254Fp as IntMod>::ZERO), serialize(&[0u8; 32]));
+
+    let a = Bn254Fp::from_u32(1234567) * Bn254Fp::from_u64(0xdeadbeefcafe);
+    let mont = a.to_montgomery_le_bytes();
+    assert_eq!(Bn254Fp::from_montgomery_le_bytes(&mont), a);
+    assert_eq!(serialize(&a), serialize(&mont));
+    let b: Bn254Fp = openvm::serde::from_slice(&serialize(&a)).unwrap();
+    assert_eq!(a, b);
+}
@@ -0,0 +1,53 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use openvm_algebra_guest::{DivUnsafe, ExpBytes, IntMod, PowBytes};
+
+openvm::entry!(main);
+
+openvm_algebra_moduli_macros::moduli_declare! {
+    Secp256k1Coord {
+        modulus = "0xFFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFE FFFFFC2F",
+    }
+}
+
+openvm::init!("openvm_init_pow.rs");
+
+pub fn main() {
+    let one = <Secp256k1Coord as IntMod>::ONE;
+    let zero = <Secp256k1Coord as IntMod>::ZERO;
+    let minus_one = &zero - &one;
+    let a = Secp256k1Coord::from_u32(0x9e3779b9);
+
+    assert_eq!(a.pow_vartime(&[]), one);
+    assert_eq!(a.pow_vartime(&[0, 0]), one);
+    assert_eq!(a.pow_vartime(&[0, 1]), a);
+    assert_eq!(a.pow_vartime(&[0x0f]), a.exp_bytes(true, &[0x0f]));
+    assert_eq!(
+        a.pow_vartime(&[0x10, 0x01]),
+        a.exp_bytes(true, &[0x10, 0x01])
+    );
+
+    // a^(p-1) = 1 and a^(p-2) = 1/a by Fermat's little theorem
+    let p_minus_1 = minus_one.to_be_bytes();
+    assert_eq!(a.pow_vartime(&p_minus_1), one);
+    let p_minus_2 = (&minus_one - &one).to_be_bytes();
+    assert_eq!(a.pow_vartime(&p_minus_2), one.clone().div_unsafe(&a));
+    // (p-1)/2 is the Legendre symbol, and -1 is not a square since p = 3 mod 4
+    let half = minus_one.clone().div_unsafe(Secp256k1Coord::from_u8(2));
+    assert_eq!(minus_one.pow_vartime(&half.to_be_bytes()), minus_one);
+
+    assert_eq!(a.pow_const::<0>(), one);
+    assert_eq!(a.pow_const::<1>(), a);
+    assert_eq!(a.pow_const::<3>(), a.cube());
+    assert_eq!(
+        a.pow_const::<65537>(),
+        a.pow_vartime(&65537u64.to_be_bytes())
+    );
+    assert_eq!(
+        a.pow_const::<0xdeadbeefcafebabe>(),
+        a.exp_bytes(true, &0xdeadbeefcafebabeu64.to_be_bytes())
+    );
+}
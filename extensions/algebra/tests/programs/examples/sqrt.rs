@@ -3,7 +3,7 @@
 
 extern crate alloc;
 
-use openvm_algebra_guest::{Field, IntMod, Sqrt};
+use openvm_algebra_guest::{IntMod, Sqrt};
 
 openvm::entry!(main);
 
@@ -26,7 +26,7 @@ pub fn main() {
     // See https://math.stackexchange.com/questions/735400/if-p-equiv-3-mod-4-with-p-prime-prove-1-is-a-non-quadratic-residue-modulo
     assert_eq!(sqrt, None);
 
-    let expected = b * Secp256k1Coord::from_u32(2).invert();
+    let expected = b * Secp256k1Coord::from_u32(2).invert().unwrap();
     let c = expected.square();
     let result = c.sqrt();
     assert!(result == Some(expected.clone()) || result == Some(-expected));
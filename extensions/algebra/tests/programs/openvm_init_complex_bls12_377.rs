@@ -0,0 +1,3 @@
+// This file is automatically generated by cargo openvm. Do not rename or edit.
+openvm_algebra_guest::moduli_macros::moduli_init! { "258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177" }
+openvm_algebra_guest::complex_macros::complex_init! { Bls12_377Fp2 { mod_idx = 0 } }
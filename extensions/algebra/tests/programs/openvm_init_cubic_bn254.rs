@@ -0,0 +1,3 @@
+// This file is automatically generated by cargo openvm. Do not rename or edit.
+openvm_algebra_guest::moduli_macros::moduli_init! { "21888242871839275222246405745257275088696311157297823662689037894645226208583" }
+openvm_algebra_guest::cubic_macros::cubic_init! { Bn254Fp3 { mod_idx = 0 } }
@@ -6,16 +6,23 @@ mod tests {
     use num_bigint::BigUint;
     use openvm_algebra_circuit::{
         Fp2Extension, ModularExtension, Rv32ModularConfig, Rv32ModularWithFp2Config,
+        Rv32ModularWithFp3Config,
+    };
+    use openvm_algebra_transpiler::{
+        Fp2TranspilerExtension, Fp3TranspilerExtension, ModularTranspilerExtension,
     };
-    use openvm_algebra_transpiler::{Fp2TranspilerExtension, ModularTranspilerExtension};
     use openvm_circuit::{arch::SystemConfig, utils::air_test};
     use openvm_ecc_circuit::SECP256K1_CONFIG;
     use openvm_instructions::exe::VmExe;
+    use openvm_rv32im_circuit::Rv32ImConfig;
     use openvm_rv32im_transpiler::{
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
     };
     use openvm_stark_sdk::p3_baby_bear::BabyBear;
-    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir, NoInitFile};
+    use openvm_toolchain_tests::{
+        build_example_program_at_path, build_example_program_at_path_with_features,
+        get_programs_dir, NoInitFile,
+    };
     use openvm_transpiler::{transpiler::Transpiler, FromElf};
 
     type F = BabyBear;
@@ -55,6 +62,49 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_modular_fallback() -> Result<()> {
+        // The example includes the init file of `test_modular`, which is checked in.
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "little",
+            ["fallback"],
+            &config,
+        )?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_moduli() -> Result<()> {
+        let moduli = vec![
+            (BigUint::from(1u32) << 2048) - 1u32,
+            (BigUint::from(1u32) << 4096) - 1u32,
+        ];
+        let mut config = Rv32ModularConfig::new(moduli);
+        // The carries of 512-limb products need a 19-bit range checker.
+        config.system.memory_config.decomp = 19;
+        let elf = build_example_program_at_path(get_programs_dir!(), "large_moduli", &config)?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     fn test_complex_two_moduli() -> Result<()> {
         let config = Rv32ModularWithFp2Config::new(vec![
@@ -82,6 +132,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_complex_bls12_377() -> Result<()> {
+        let mut config = Rv32ModularWithFp2Config::new(vec![(
+            "Bls12_377Fp2".to_string(),
+            BigUint::from_str("258664426012969094010652733694893533536393512754914660539884262666720468348340822774968888139573360124440321458177").unwrap(),
+        )]);
+        config.fp2.non_residues = vec![("Bls12_377Fp2".to_string(), -5)];
+        let elf = build_example_program_at_path(get_programs_dir!(), "complex_bls12_377", &config)?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Fp2TranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     fn test_complex_redundant_modulus() -> Result<()> {
         let config = Rv32ModularWithFp2Config {
@@ -138,6 +209,30 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_cubic() -> Result<()> {
+        let config = Rv32ModularWithFp3Config::new(vec![(
+            "Bn254Fp3".to_string(),
+            BigUint::from_str(
+                "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+            )
+            .unwrap(),
+            3,
+        )]);
+        let elf = build_example_program_at_path(get_programs_dir!(), "cubic_bn254", &config)?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Fp3TranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn test_invalid_setup() {
@@ -181,4 +276,55 @@ mod tests {
         air_test(config, openvm_exe);
         Ok(())
     }
+
+    #[test]
+    fn test_invert() -> Result<()> {
+        let config = Rv32ModularConfig::new(vec![SECP256K1_CONFIG.modulus.clone()]);
+        let elf = build_example_program_at_path(get_programs_dir!(), "invert", &config)?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pow() -> Result<()> {
+        let config = Rv32ModularConfig::new(vec![SECP256K1_CONFIG.modulus.clone()]);
+        let elf = build_example_program_at_path(get_programs_dir!(), "pow", &config)?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_montgomery() -> Result<()> {
+        let config = Rv32ModularConfig::new(vec![BigUint::from_str(
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+        )
+        .unwrap()]);
+        let elf = build_example_program_at_path(get_programs_dir!(), "montgomery", &config)?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
 }
@@ -1,6 +1,6 @@
 use openvm_algebra_guest::{
-    ComplexExtFieldBaseFunct7, ModArithBaseFunct7, COMPLEX_EXT_FIELD_FUNCT3,
-    MODULAR_ARITHMETIC_FUNCT3, OPCODE,
+    ComplexExtFieldBaseFunct7, CubicExtFieldBaseFunct7, ModArithBaseFunct7,
+    COMPLEX_EXT_FIELD_FUNCT3, CUBIC_EXT_FIELD_FUNCT3, MODULAR_ARITHMETIC_FUNCT3, OPCODE,
 };
 use openvm_instructions::{
     instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, LocalOpcode, PhantomDiscriminant,
@@ -8,9 +8,13 @@ use openvm_instructions::{
 };
 use openvm_instructions_derive::LocalOpcode;
 use openvm_stark_backend::p3_field::PrimeField32;
-use openvm_transpiler::{util::from_r_type, TranspilerExtension, TranspilerOutput};
+use openvm_transpiler::{
+    metadata::OpcodeMap,
+    util::{from_r_type, phantom_discriminant},
+    TranspilerExtension, TranspilerOutput,
+};
 use rrs_lib::instruction_formats::RType;
-use strum::{EnumCount, EnumIter, FromRepr};
+use strum::{EnumCount, EnumIter, FromRepr, IntoEnumIterator};
 
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
@@ -34,6 +38,7 @@ pub enum Rv32ModularArithmeticOpcode {
 pub enum ModularPhantom {
     HintNonQr = 0x50,
     HintSqrt = 0x51,
+    HintInverse = 0x52,
 }
 
 #[derive(
@@ -51,12 +56,30 @@ pub enum Fp2Opcode {
     SETUP_MULDIV,
 }
 
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
+)]
+#[opcode_offset = 0x780]
+#[repr(usize)]
+#[allow(non_camel_case_types)]
+pub enum Fp3Opcode {
+    ADD,
+    SUB,
+    SETUP_ADDSUB,
+    MUL,
+    DIV,
+    SETUP_MULDIV,
+}
+
 #[derive(Default)]
 pub struct ModularTranspilerExtension;
 
 #[derive(Default)]
 pub struct Fp2TranspilerExtension;
 
+#[derive(Default)]
+pub struct Fp3TranspilerExtension;
+
 impl<F: PrimeField32> TranspilerExtension<F> for ModularTranspilerExtension {
     fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
         if instruction_stream.is_empty() {
@@ -120,9 +143,13 @@ impl<F: PrimeField32> TranspilerExtension<F> for ModularTranspilerExtension {
                 ))
             } else if base_funct7 == ModArithBaseFunct7::HintSqrt as u8 {
                 assert_eq!(dec_insn.rd, 0);
-                assert_eq!(dec_insn.rs2, 0);
+                let phantom = match dec_insn.rs2 {
+                    0 => ModularPhantom::HintSqrt,
+                    1 => ModularPhantom::HintInverse,
+                    _ => panic!("invalid opcode"),
+                };
                 Some(Instruction::phantom(
-                    PhantomDiscriminant(ModularPhantom::HintSqrt as u16),
+                    PhantomDiscriminant(phantom as u16),
                     F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
                     F::ZERO,
                     mod_idx as u16,
@@ -161,6 +188,15 @@ impl<F: PrimeField32> TranspilerExtension<F> for ModularTranspilerExtension {
         };
         instruction.map(TranspilerOutput::one_to_one)
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default().with_opcodes(Rv32ModularArithmeticOpcode::iter())
+    }
+
+    /// The only phantom sub-instructions of the extension are hints.
+    fn provides_hints(&self, instruction: &Instruction<F>) -> bool {
+        phantom_discriminant(instruction).is_some()
+    }
 }
 
 impl<F: PrimeField32> TranspilerExtension<F> for Fp2TranspilerExtension {
@@ -231,4 +267,80 @@ impl<F: PrimeField32> TranspilerExtension<F> for Fp2TranspilerExtension {
         };
         instruction.map(TranspilerOutput::one_to_one)
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default().with_opcodes(Fp2Opcode::iter())
+    }
+}
+
+impl<F: PrimeField32> TranspilerExtension<F> for Fp3TranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        if instruction_stream.is_empty() {
+            return None;
+        }
+        let instruction_u32 = instruction_stream[0];
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+
+        if opcode != OPCODE {
+            return None;
+        }
+        if funct3 != CUBIC_EXT_FIELD_FUNCT3 {
+            return None;
+        }
+
+        let instruction = {
+            assert!(
+                Fp3Opcode::COUNT <= CubicExtFieldBaseFunct7::CUBIC_EXT_FIELD_MAX_KINDS as usize
+            );
+            let dec_insn = RType::new(instruction_u32);
+            let base_funct7 =
+                (dec_insn.funct7 as u8) % CubicExtFieldBaseFunct7::CUBIC_EXT_FIELD_MAX_KINDS;
+            let cubic_idx_shift = ((dec_insn.funct7 as u8)
+                / CubicExtFieldBaseFunct7::CUBIC_EXT_FIELD_MAX_KINDS)
+                as usize
+                * Fp3Opcode::COUNT;
+
+            if base_funct7 == CubicExtFieldBaseFunct7::Setup as u8 {
+                let local_opcode = match dec_insn.rs2 {
+                    0 => Fp3Opcode::SETUP_ADDSUB,
+                    1 => Fp3Opcode::SETUP_MULDIV,
+                    _ => panic!("invalid opcode"),
+                };
+                Some(Instruction::new(
+                    VmOpcode::from_usize(local_opcode.global_opcode().as_usize() + cubic_idx_shift),
+                    F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                    F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                    F::ZERO, // rs2 = 0
+                    F::ONE,  // d_as = 1
+                    F::TWO,  // e_as = 2
+                    F::ZERO,
+                    F::ZERO,
+                ))
+            } else {
+                let global_opcode = match CubicExtFieldBaseFunct7::from_repr(base_funct7) {
+                    Some(CubicExtFieldBaseFunct7::Add) => {
+                        Fp3Opcode::ADD as usize + Fp3Opcode::CLASS_OFFSET
+                    }
+                    Some(CubicExtFieldBaseFunct7::Sub) => {
+                        Fp3Opcode::SUB as usize + Fp3Opcode::CLASS_OFFSET
+                    }
+                    Some(CubicExtFieldBaseFunct7::Mul) => {
+                        Fp3Opcode::MUL as usize + Fp3Opcode::CLASS_OFFSET
+                    }
+                    Some(CubicExtFieldBaseFunct7::Div) => {
+                        Fp3Opcode::DIV as usize + Fp3Opcode::CLASS_OFFSET
+                    }
+                    _ => unimplemented!(),
+                };
+                let global_opcode = global_opcode + cubic_idx_shift;
+                Some(from_r_type(global_opcode, 2, &dec_insn, true))
+            }
+        };
+        instruction.map(TranspilerOutput::one_to_one)
+    }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default().with_opcodes(Fp3Opcode::iter())
+    }
 }
@@ -95,6 +95,10 @@ impl<F: PrimeField32> VmExtension<F> for Int256 {
     type Executor = Int256Executor<F>;
     type Periphery = Int256Periphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("int256", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
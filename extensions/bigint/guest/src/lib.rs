@@ -7,6 +7,8 @@ pub const OPCODE: u8 = 0x0b;
 pub const INT256_FUNCT3: u8 = 0b101;
 pub const BEQ256_FUNCT3: u8 = 0b110;
 
+openvm_platform::require_extension_version!("int256", 1);
+
 /// funct7 options for 256-bit integer instructions.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
 #[repr(u8)]
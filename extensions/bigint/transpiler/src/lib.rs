@@ -8,7 +8,9 @@ use openvm_rv32im_transpiler::{
     BaseAluOpcode, BranchEqualOpcode, BranchLessThanOpcode, LessThanOpcode, MulOpcode, ShiftOpcode,
 };
 use openvm_stark_backend::p3_field::PrimeField32;
-use openvm_transpiler::{util::from_r_type, TranspilerExtension, TranspilerOutput};
+use openvm_transpiler::{
+    metadata::OpcodeMap, util::from_r_type, TranspilerExtension, TranspilerOutput,
+};
 use rrs_lib::instruction_formats::{BType, RType};
 use strum::IntoEnumIterator;
 
@@ -156,4 +158,14 @@ impl<F: PrimeField32> TranspilerExtension<F> for Int256TranspilerExtension {
         };
         instruction.map(TranspilerOutput::one_to_one)
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default()
+            .with_opcodes(Rv32BaseAlu256Opcode::iter())
+            .with_opcodes(Rv32Shift256Opcode::iter())
+            .with_opcodes(Rv32LessThan256Opcode::iter())
+            .with_opcodes(Rv32BranchEqual256Opcode::iter())
+            .with_opcodes(Rv32BranchLessThan256Opcode::iter())
+            .with_opcodes(Rv32Mul256Opcode::iter())
+    }
 }
@@ -0,0 +1,217 @@
+use std::{
+    array::from_fn,
+    borrow::{Borrow, BorrowMut},
+};
+
+use openvm_blake3_transpiler::Rv32Blake3Opcode;
+use openvm_circuit::arch::{
+    AdapterAirContext, AdapterRuntimeContext, DynAdapterInterface, DynArray, MinimalInstruction,
+    Result, VmAdapterInterface, VmCoreAir, VmCoreChip,
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::{BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip},
+    utils::compose,
+    AlignedBorrow,
+};
+use openvm_instructions::{
+    instruction::Instruction,
+    riscv::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS},
+    LocalOpcode,
+};
+use openvm_stark_backend::{
+    interaction::{InteractionBuilder, LookupBus},
+    p3_air::BaseAir,
+    p3_field::{Field, FieldAlgebra, PrimeField32},
+    rap::BaseAirWithPublicValues,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{SharedBlake3CompressChip, BLAKE3_BLOCK_WORDS, BLAKE3_CV_WORDS, BLAKE3_PARAMS_WORDS};
+
+// The words are read and written as 4 byte little-endian words, and range checked as bytes. The
+// compression itself is proven by the compression chip, which it is looked up from.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct Blake3CoreCols<T> {
+    pub is_valid: T,
+    pub params: [[T; RV32_REGISTER_NUM_LIMBS]; BLAKE3_PARAMS_WORDS],
+    pub block: [[T; RV32_REGISTER_NUM_LIMBS]; BLAKE3_BLOCK_WORDS],
+    pub output: [[T; RV32_REGISTER_NUM_LIMBS]; BLAKE3_CV_WORDS],
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Blake3CoreAir {
+    pub bitwise_bus: BitwiseOperationLookupBus,
+    pub compress_bus: LookupBus,
+    pub offset: usize,
+}
+
+impl<F: Field> BaseAir<F> for Blake3CoreAir {
+    fn width(&self) -> usize {
+        Blake3CoreCols::<F>::width()
+    }
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for Blake3CoreAir {}
+
+impl<AB: InteractionBuilder, I> VmCoreAir<AB, I> for Blake3CoreAir
+where
+    I: VmAdapterInterface<AB::Expr>,
+    AdapterAirContext<AB::Expr, I>:
+        From<AdapterAirContext<AB::Expr, DynAdapterInterface<AB::Expr>>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Blake3CoreCols<AB::Var> = local_core.borrow();
+        let is_valid = cols.is_valid;
+        builder.assert_bool(is_valid);
+
+        let bytes = [
+            cols.params.concat(),
+            cols.block.concat(),
+            cols.output.concat(),
+        ]
+        .concat();
+        for pair in bytes.chunks_exact(2) {
+            self.bitwise_bus
+                .send_range(pair[0], pair[1])
+                .eval(builder, is_valid);
+        }
+        let limbs = bytes
+            .chunks_exact(2)
+            .map(|pair| compose::<AB::Expr>(pair, RV32_CELL_BITS));
+        self.compress_bus.lookup_key(builder, limbs, is_valid);
+
+        let reads: Vec<AB::Expr> = [cols.params.concat(), cols.block.concat()]
+            .concat()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let writes: Vec<AB::Expr> = cols.output.concat().into_iter().map(Into::into).collect();
+        let instruction = MinimalInstruction {
+            is_valid: is_valid.into(),
+            opcode: AB::Expr::from_canonical_usize(
+                self.offset + Rv32Blake3Opcode::COMPRESS.local_usize(),
+            ),
+        };
+        let ctx: AdapterAirContext<_, DynAdapterInterface<_>> = AdapterAirContext {
+            to_pc: None,
+            reads: reads.into(),
+            writes: writes.into(),
+            instruction: instruction.into(),
+        };
+        ctx.into()
+    }
+
+    fn start_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Blake3CoreRecord<T> {
+    pub params: [[T; RV32_REGISTER_NUM_LIMBS]; BLAKE3_PARAMS_WORDS],
+    pub block: [[T; RV32_REGISTER_NUM_LIMBS]; BLAKE3_BLOCK_WORDS],
+    pub output: [[T; RV32_REGISTER_NUM_LIMBS]; BLAKE3_CV_WORDS],
+}
+
+pub struct Blake3CoreChip {
+    pub air: Blake3CoreAir,
+    pub compress_chip: SharedBlake3CompressChip,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Blake3CoreChip {
+    pub fn new(
+        compress_chip: SharedBlake3CompressChip,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        offset: usize,
+    ) -> Self {
+        Self {
+            air: Blake3CoreAir {
+                bitwise_bus: bitwise_lookup_chip.bus(),
+                compress_bus: compress_chip.bus(),
+                offset,
+            },
+            compress_chip,
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+fn to_word<F: PrimeField32>(bytes: &[F; RV32_REGISTER_NUM_LIMBS]) -> u32 {
+    u32::from_le_bytes(bytes.map(|x| x.as_canonical_u32() as u8))
+}
+
+impl<F: PrimeField32, I> VmCoreChip<F, I> for Blake3CoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<DynArray<F>>,
+    AdapterRuntimeContext<F, I>: From<AdapterRuntimeContext<F, DynAdapterInterface<F>>>,
+{
+    type Record = Blake3CoreRecord<F>;
+    type Air = Blake3CoreAir;
+
+    fn execute_instruction(
+        &self,
+        _instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> Result<(AdapterRuntimeContext<F, I>, Self::Record)> {
+        let data: DynArray<_> = reads.into();
+        assert_eq!(
+            data.0.len(),
+            (BLAKE3_PARAMS_WORDS + BLAKE3_BLOCK_WORDS) * RV32_REGISTER_NUM_LIMBS
+        );
+        let (params_data, block_data) = data
+            .0
+            .split_at(BLAKE3_PARAMS_WORDS * RV32_REGISTER_NUM_LIMBS);
+        let params: [[F; RV32_REGISTER_NUM_LIMBS]; BLAKE3_PARAMS_WORDS] =
+            from_fn(|i| from_fn(|j| params_data[i * RV32_REGISTER_NUM_LIMBS + j]));
+        let block: [[F; RV32_REGISTER_NUM_LIMBS]; BLAKE3_BLOCK_WORDS] =
+            from_fn(|i| from_fn(|j| block_data[i * RV32_REGISTER_NUM_LIMBS + j]));
+
+        let output = self
+            .compress_chip
+            .compress_and_record(params.map(|w| to_word(&w)), block.map(|w| to_word(&w)));
+        let record = Blake3CoreRecord {
+            params,
+            block,
+            output: output.map(|word| word.to_le_bytes().map(F::from_canonical_u8)),
+        };
+        let bytes = [
+            record.params.concat(),
+            record.block.concat(),
+            record.output.concat(),
+        ]
+        .concat();
+        for pair in bytes.chunks_exact(2) {
+            self.bitwise_lookup_chip
+                .request_range(pair[0].as_canonical_u32(), pair[1].as_canonical_u32());
+        }
+
+        let ctx =
+            AdapterRuntimeContext::<_, DynAdapterInterface<_>>::without_pc(record.output.concat());
+        Ok((ctx.into(), record))
+    }
+
+    fn get_opcode_name(&self, _opcode: usize) -> String {
+        format!("{:?}", Rv32Blake3Opcode::COMPRESS)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let cols: &mut Blake3CoreCols<F> = row_slice.borrow_mut();
+        cols.is_valid = F::ONE;
+        cols.params = record.params;
+        cols.block = record.block;
+        cols.output = record.output;
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
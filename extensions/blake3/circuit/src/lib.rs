@@ -0,0 +1,19 @@
+//! The BLAKE3 compression function, exposed to RISC-V guests on words in memory. Compressions are
+//! proven one round per row by a chip shared by the instructions, which look them up on a bus.
+use openvm_circuit::arch::VmChipWrapper;
+use openvm_rv32_adapters::Rv32VecHeapTwoReadsAdapterChip;
+
+mod compress;
+mod extension;
+mod periphery;
+pub use compress::*;
+pub use extension::*;
+pub use periphery::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Reads the chaining value and parameters, 12 words, from `rs1` and the block, 16 words, from
+/// `rs2`, and writes the new chaining value, 8 words, to `rd`.
+pub type Rv32Blake3Chip<F> =
+    VmChipWrapper<F, Rv32VecHeapTwoReadsAdapterChip<F, 12, 16, 8, 4, 4>, Blake3CoreChip>;
@@ -0,0 +1,497 @@
+use std::{
+    array::from_fn,
+    borrow::{Borrow, BorrowMut},
+    sync::{Arc, Mutex},
+};
+
+use openvm_blake3_guest::{blake3_compress, IV, MSG_PERMUTATION};
+use openvm_circuit_primitives::{
+    utils::{compose, next_power_of_two_or_zero},
+    AlignedBorrow,
+};
+use openvm_stark_backend::{
+    config::{StarkGenericConfig, Val},
+    interaction::{InteractionBuilder, LookupBus},
+    p3_air::{Air, AirBuilder, BaseAir},
+    p3_field::{Field, FieldAlgebra, PrimeField32},
+    p3_matrix::{dense::RowMajorMatrix, Matrix},
+    p3_maybe_rayon::prelude::*,
+    prover::types::AirProofInput,
+    rap::{get_air_name, BaseAirWithPublicValues, PartitionedBaseAir},
+    AirRef, Chip, ChipUsageGetter,
+};
+
+pub const BLAKE3_ROUNDS: usize = 7;
+pub const BLAKE3_STATE_WORDS: usize = 16;
+pub const BLAKE3_CV_WORDS: usize = 8;
+/// The chaining value followed by the low and high words of the counter, the block length and
+/// the flags.
+pub const BLAKE3_PARAMS_WORDS: usize = 12;
+pub const BLAKE3_BLOCK_WORDS: usize = 16;
+/// Words are constrained as bits, and sent on the bus as 16-bit limbs.
+pub const WORD_BITS: usize = 32;
+pub const LIMB_BITS: usize = 16;
+pub const WORD_LIMBS: usize = WORD_BITS / LIMB_BITS;
+/// The number of fields of a lookup on the compression bus: the chaining value, counter, block
+/// length and flags, the block and the new chaining value, as 16-bit limbs.
+pub const BLAKE3_BUS_WIDTH: usize =
+    (BLAKE3_PARAMS_WORDS + BLAKE3_BLOCK_WORDS + BLAKE3_CV_WORDS) * WORD_LIMBS;
+
+/// The state words updated by each of the 8 applications of the G function in a round: first the
+/// columns, then the diagonals of the 4x4 state. The i-th application mixes in message words 2i
+/// and 2i + 1.
+const G_INDICES: [[usize; 4]; 8] = [
+    [0, 4, 8, 12],
+    [1, 5, 9, 13],
+    [2, 6, 10, 14],
+    [3, 7, 11, 15],
+    [0, 5, 10, 15],
+    [1, 6, 11, 12],
+    [2, 7, 8, 13],
+    [3, 4, 9, 14],
+];
+
+/// The intermediate values of one application of the G function. The values `d1` and `b1` are
+/// not stored since they are degree 2 expressions in the other columns.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct Blake3GCols<T> {
+    pub a1: [T; WORD_BITS],
+    pub c1: [T; WORD_BITS],
+    pub a2: [T; WORD_BITS],
+    pub b2: [T; WORD_BITS],
+    pub c2: [T; WORD_BITS],
+    pub d2: [T; WORD_BITS],
+    /// The carries out of each limb of `a + b + mx`, in `{0, 1, 2}`.
+    pub a1_carry: [T; WORD_LIMBS],
+    /// The carries out of each limb of `c + d1`, in `{0, 1}`.
+    pub c1_carry: [T; WORD_LIMBS],
+    pub a2_carry: [T; WORD_LIMBS],
+    pub c2_carry: [T; WORD_LIMBS],
+}
+
+// Each compression takes 7 rows, one per round. Row `r` of a compression holds the state before
+// round `r` in bits, the message words permuted `r` times, and every G function of the round. The
+// new chaining value is copied to all rows of the compression, and the compression is added to
+// the bus on its first row.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct Blake3CompressCols<T> {
+    /// One-hot round indicator, all zero on padding rows.
+    pub round: [T; BLAKE3_ROUNDS],
+    /// The number of lookups of the compression, only nonzero on the first row.
+    pub mult: T,
+    pub state: [[T; WORD_BITS]; BLAKE3_STATE_WORDS],
+    pub msg: [[T; WORD_LIMBS]; BLAKE3_BLOCK_WORDS],
+    pub g: [Blake3GCols<T>; 8],
+    pub output: [[T; WORD_LIMBS]; BLAKE3_CV_WORDS],
+}
+
+#[derive(Clone, Copy, Debug, derive_new::new)]
+pub struct Blake3CompressAir {
+    pub bus: LookupBus,
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for Blake3CompressAir {}
+impl<F: Field> PartitionedBaseAir<F> for Blake3CompressAir {}
+impl<F: Field> BaseAir<F> for Blake3CompressAir {
+    fn width(&self) -> usize {
+        Blake3CompressCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder> Air<AB> for Blake3CompressAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Blake3CompressCols<AB::Var> = (*local).borrow();
+        let next: &Blake3CompressCols<AB::Var> = (*next).borrow();
+
+        // A compression starts with round 0 and goes through every round on consecutive rows.
+        for flag in local.round {
+            builder.assert_bool(flag);
+        }
+        let is_enabled = sum::<AB>(&local.round);
+        let is_not_last_round = sum::<AB>(&local.round[..BLAKE3_ROUNDS - 1]);
+        builder.assert_bool(is_enabled);
+        builder
+            .when_first_row()
+            .assert_zero(sum::<AB>(&local.round[1..]));
+        for r in 0..BLAKE3_ROUNDS - 1 {
+            builder
+                .when_transition()
+                .assert_eq(next.round[r + 1], local.round[r]);
+        }
+        builder
+            .when_last_row()
+            .assert_zero(is_not_last_round.clone());
+        builder.assert_zero(local.mult * (AB::Expr::ONE - local.round[0]));
+
+        for bit in local.state.as_flattened() {
+            builder.assert_bool(*bit);
+        }
+        for (i, word) in IV[..4].iter().enumerate() {
+            for (j, &bit) in local.state[8 + i].iter().enumerate() {
+                builder
+                    .when(local.round[0])
+                    .assert_eq(bit, AB::Expr::from_canonical_u32((word >> j) & 1));
+            }
+        }
+
+        let mut state: [&[AB::Var; WORD_BITS]; BLAKE3_STATE_WORDS] = from_fn(|i| &local.state[i]);
+        for (k, (g, [a, b, c, d])) in local.g.iter().zip(G_INDICES).enumerate() {
+            eval_g(
+                builder,
+                [state[a], state[b], state[c], state[d]],
+                [&local.msg[2 * k], &local.msg[2 * k + 1]],
+                g,
+            );
+            [state[a], state[b], state[c], state[d]] = [&g.a2, &g.b2, &g.c2, &g.d2];
+        }
+
+        // Hand the state, the permuted message and the output to the next round.
+        let mut when_next_round = builder.when_transition();
+        let mut when_next_round = when_next_round.when(is_not_last_round);
+        for (next_word, word) in next.state.iter().zip(state) {
+            for (&next_bit, &bit) in next_word.iter().zip(word) {
+                when_next_round.assert_eq(next_bit, bit);
+            }
+        }
+        for (next_word, &i) in next.msg.iter().zip(MSG_PERMUTATION.iter()) {
+            for (&next_limb, &limb) in next_word.iter().zip(&local.msg[i]) {
+                when_next_round.assert_eq(next_limb, limb);
+            }
+        }
+        for (next_word, word) in next.output.iter().zip(&local.output) {
+            for (&next_limb, &limb) in next_word.iter().zip(word) {
+                when_next_round.assert_eq(next_limb, limb);
+            }
+        }
+
+        for i in 0..BLAKE3_CV_WORDS {
+            let output_bits =
+                xor_words::<AB::Expr>(&state[i].map(Into::into), &state[i + 8].map(Into::into));
+            for (&limb, expected) in local.output[i].iter().zip(limbs(&output_bits)) {
+                builder
+                    .when(local.round[BLAKE3_ROUNDS - 1])
+                    .assert_eq(limb, expected);
+            }
+        }
+
+        let input_limbs = local.state[..BLAKE3_CV_WORDS]
+            .iter()
+            .chain(&local.state[12..])
+            .flat_map(|word| limbs::<AB::Expr>(&word.map(Into::into)));
+        let fields = input_limbs
+            .chain(local.msg.as_flattened().iter().map(|&limb| limb.into()))
+            .chain(local.output.as_flattened().iter().map(|&limb| limb.into()));
+        self.bus.add_key_with_lookups(builder, fields, local.mult);
+    }
+}
+
+fn sum<AB: AirBuilder>(vars: &[AB::Var]) -> AB::Expr {
+    vars.iter().fold(AB::Expr::ZERO, |acc, &var| acc + var)
+}
+
+fn xor<E: FieldAlgebra>(x: E, y: E) -> E {
+    x.clone() + y.clone() - x * y * E::TWO
+}
+
+fn xor_words<E: FieldAlgebra>(x: &[E; WORD_BITS], y: &[E; WORD_BITS]) -> [E; WORD_BITS] {
+    from_fn(|j| xor(x[j].clone(), y[j].clone()))
+}
+
+fn limbs<E: FieldAlgebra>(bits: &[E; WORD_BITS]) -> [E; WORD_LIMBS] {
+    from_fn(|i| compose(&bits[i * LIMB_BITS..(i + 1) * LIMB_BITS], 1))
+}
+
+/// Bit `j` of `x` rotated right by `n` is bit `j + n` of `x`.
+fn rotate_right<E: Clone>(bits: &[E; WORD_BITS], n: usize) -> [E; WORD_BITS] {
+    from_fn(|j| bits[(j + n) % WORD_BITS].clone())
+}
+
+/// Constrains one application of the G function:
+/// ```text
+/// a1 = a + b + mx,  d1 = (d ^ a1) >>> 16,  c1 = c + d1,  b1 = (b ^ c1) >>> 12,
+/// a2 = a1 + b1 + my,  d2 = (d1 ^ a2) >>> 8,  c2 = c1 + d2,  b2 = (b1 ^ c2) >>> 7.
+/// ```
+/// The inputs must be boolean, and so are the stored outputs. The constraints have degree 3.
+fn eval_g<AB: AirBuilder>(
+    builder: &mut AB,
+    [a, b, c, d]: [&[AB::Var; WORD_BITS]; 4],
+    [mx, my]: [&[AB::Var; WORD_LIMBS]; 2],
+    g: &Blake3GCols<AB::Var>,
+) {
+    for bit in [g.a1, g.c1, g.a2, g.b2, g.c2, g.d2].as_flattened() {
+        builder.assert_bool(*bit);
+    }
+    for carry in [g.c1_carry, g.c2_carry].as_flattened() {
+        builder.assert_bool(*carry);
+    }
+    for carry in [g.a1_carry, g.a2_carry].as_flattened() {
+        builder.assert_zero(*carry * (AB::Expr::ONE - *carry) * (AB::Expr::TWO - *carry));
+    }
+
+    let expr = |bits: &[AB::Var; WORD_BITS]| bits.map(Into::<AB::Expr>::into);
+    let (a, b, c, d) = (expr(a), expr(b), expr(c), expr(d));
+    let (a1, c1, a2, c2) = (expr(&g.a1), expr(&g.c1), expr(&g.a2), expr(&g.c2));
+    let d1 = rotate_right(&xor_words(&d, &a1), 16);
+    let b1 = rotate_right(&xor_words(&b, &c1), 12);
+
+    eval_add(
+        builder,
+        &[limbs(&a), limbs(&b), mx.map(Into::into)],
+        &g.a1,
+        &g.a1_carry,
+    );
+    eval_add(builder, &[limbs(&c), limbs(&d1)], &g.c1, &g.c1_carry);
+    eval_add(
+        builder,
+        &[limbs(&a1), limbs(&b1), my.map(Into::into)],
+        &g.a2,
+        &g.a2_carry,
+    );
+    for (&bit, expected) in g.d2.iter().zip(rotate_right(&xor_words(&d1, &a2), 8)) {
+        builder.assert_eq(bit, expected);
+    }
+    eval_add(
+        builder,
+        &[limbs(&c1), limbs(&expr(&g.d2))],
+        &g.c2,
+        &g.c2_carry,
+    );
+    for (&bit, expected) in g.b2.iter().zip(rotate_right(&xor_words(&b1, &c2), 7)) {
+        builder.assert_eq(bit, expected);
+    }
+}
+
+/// Constrains `sum` to be the sum of `summands` modulo `2^32`, with the given carries out of
+/// each limb. The carry out of the high limb is dropped.
+fn eval_add<AB: AirBuilder>(
+    builder: &mut AB,
+    summands: &[[AB::Expr; WORD_LIMBS]],
+    sum: &[AB::Var; WORD_BITS],
+    carry: &[AB::Var; WORD_LIMBS],
+) {
+    let sum = limbs(&sum.map(Into::<AB::Expr>::into));
+    let mut carry_in = AB::Expr::ZERO;
+    for i in 0..WORD_LIMBS {
+        let total = summands
+            .iter()
+            .fold(carry_in, |acc, summand| acc + summand[i].clone());
+        builder.assert_eq(
+            total,
+            sum[i].clone() + carry[i] * AB::Expr::from_canonical_u32(1 << LIMB_BITS),
+        );
+        carry_in = carry[i].into();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Blake3CompressRecord {
+    pub params: [u32; BLAKE3_PARAMS_WORDS],
+    pub block: [u32; BLAKE3_BLOCK_WORDS],
+}
+
+/// Proves the BLAKE3 compressions looked up on its bus by the BLAKE3 instructions.
+#[derive(Debug)]
+pub struct Blake3CompressChip {
+    pub air: Blake3CompressAir,
+    records: Mutex<Vec<Blake3CompressRecord>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SharedBlake3CompressChip(Arc<Blake3CompressChip>);
+
+impl Blake3CompressChip {
+    pub fn new(bus: LookupBus) -> Self {
+        Self {
+            air: Blake3CompressAir::new(bus),
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn bus(&self) -> LookupBus {
+        self.air.bus
+    }
+
+    /// Returns the new chaining value of the compression described by `params` and `block`, and
+    /// records the compression to be proven.
+    pub fn compress_and_record(
+        &self,
+        params: [u32; BLAKE3_PARAMS_WORDS],
+        block: [u32; BLAKE3_BLOCK_WORDS],
+    ) -> [u32; BLAKE3_CV_WORDS] {
+        let output = compress(&params, &block);
+        self.records
+            .lock()
+            .unwrap()
+            .push(Blake3CompressRecord { params, block });
+        output
+    }
+
+    /// Generates the trace of the compressions recorded so far, and clears them.
+    pub fn generate_trace<F: PrimeField32>(&self) -> RowMajorMatrix<F> {
+        let records = std::mem::take(&mut *self.records.lock().unwrap());
+        let width = Blake3CompressCols::<F>::width();
+        let height = next_power_of_two_or_zero(records.len() * BLAKE3_ROUNDS);
+        let mut values = F::zero_vec(height * width);
+        values
+            .par_chunks_mut(width * BLAKE3_ROUNDS)
+            .zip(records.par_iter())
+            .for_each(|(rows, record)| generate_compression_rows(rows, record));
+        RowMajorMatrix::new(values, width)
+    }
+}
+
+impl SharedBlake3CompressChip {
+    pub fn new(bus: LookupBus) -> Self {
+        Self(Arc::new(Blake3CompressChip::new(bus)))
+    }
+
+    pub fn bus(&self) -> LookupBus {
+        self.0.bus()
+    }
+
+    pub fn compress_and_record(
+        &self,
+        params: [u32; BLAKE3_PARAMS_WORDS],
+        block: [u32; BLAKE3_BLOCK_WORDS],
+    ) -> [u32; BLAKE3_CV_WORDS] {
+        self.0.compress_and_record(params, block)
+    }
+}
+
+fn compress(
+    params: &[u32; BLAKE3_PARAMS_WORDS],
+    block: &[u32; BLAKE3_BLOCK_WORDS],
+) -> [u32; BLAKE3_CV_WORDS] {
+    let cv = from_fn(|i| params[i]);
+    let counter = params[8] as u64 | (params[9] as u64) << 32;
+    blake3_compress(&cv, block, counter, params[10], params[11])
+}
+
+fn generate_compression_rows<F: PrimeField32>(rows: &mut [F], record: &Blake3CompressRecord) {
+    let width = Blake3CompressCols::<F>::width();
+    let output = compress(&record.params, &record.block);
+    let mut state: [u32; BLAKE3_STATE_WORDS] = from_fn(|i| match i {
+        0..8 => record.params[i],
+        8..12 => IV[i - 8],
+        _ => record.params[i - 4],
+    });
+    let mut msg = record.block;
+    for (r, row) in rows.chunks_exact_mut(width).enumerate() {
+        let cols: &mut Blake3CompressCols<F> = row.borrow_mut();
+        cols.round[r] = F::ONE;
+        if r == 0 {
+            cols.mult = F::ONE;
+        }
+        cols.state = state.map(word_bits);
+        cols.msg = msg.map(word_limbs);
+        for (k, (g, indices)) in cols.g.iter_mut().zip(G_INDICES).enumerate() {
+            generate_g(g, &mut state, indices, msg[2 * k], msg[2 * k + 1]);
+        }
+        cols.output = output.map(word_limbs);
+        msg = from_fn(|i| msg[MSG_PERMUTATION[i]]);
+    }
+}
+
+fn generate_g<F: PrimeField32>(
+    g: &mut Blake3GCols<F>,
+    state: &mut [u32; BLAKE3_STATE_WORDS],
+    [a, b, c, d]: [usize; 4],
+    mx: u32,
+    my: u32,
+) {
+    let (a1, a1_carry) = add_with_carries(&[state[a], state[b], mx]);
+    let d1 = (state[d] ^ a1).rotate_right(16);
+    let (c1, c1_carry) = add_with_carries(&[state[c], d1]);
+    let b1 = (state[b] ^ c1).rotate_right(12);
+    let (a2, a2_carry) = add_with_carries(&[a1, b1, my]);
+    let d2 = (d1 ^ a2).rotate_right(8);
+    let (c2, c2_carry) = add_with_carries(&[c1, d2]);
+    let b2 = (b1 ^ c2).rotate_right(7);
+
+    [g.a1, g.c1, g.a2, g.b2, g.c2, g.d2] = [a1, c1, a2, b2, c2, d2].map(word_bits);
+    [g.a1_carry, g.c1_carry, g.a2_carry, g.c2_carry] =
+        [a1_carry, c1_carry, a2_carry, c2_carry].map(|carry| carry.map(F::from_canonical_u32));
+    [state[a], state[b], state[c], state[d]] = [a2, b2, c2, d2];
+}
+
+/// The sum modulo `2^32` and the carries out of each 16-bit limb.
+fn add_with_carries(summands: &[u32]) -> (u32, [u32; WORD_LIMBS]) {
+    let lo: u32 = summands.iter().map(|x| x & 0xffff).sum();
+    let hi: u32 = summands.iter().map(|x| x >> LIMB_BITS).sum::<u32>() + (lo >> LIMB_BITS);
+    (
+        ((hi & 0xffff) << LIMB_BITS) | (lo & 0xffff),
+        [lo >> LIMB_BITS, hi >> LIMB_BITS],
+    )
+}
+
+fn word_bits<F: FieldAlgebra>(word: u32) -> [F; WORD_BITS] {
+    from_fn(|j| F::from_bool((word >> j) & 1 == 1))
+}
+
+fn word_limbs<F: FieldAlgebra>(word: u32) -> [F; WORD_LIMBS] {
+    from_fn(|i| F::from_canonical_u32((word >> (i * LIMB_BITS)) & 0xffff))
+}
+
+impl<SC: StarkGenericConfig> Chip<SC> for Blake3CompressChip
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        Arc::new(self.air)
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        AirProofInput::simple_no_pis(self.generate_trace::<Val<SC>>())
+    }
+}
+
+impl<SC: StarkGenericConfig> Chip<SC> for SharedBlake3CompressChip
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        self.0.air()
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        AirProofInput::simple_no_pis(self.0.generate_trace::<Val<SC>>())
+    }
+}
+
+impl ChipUsageGetter for Blake3CompressChip {
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.records.lock().unwrap().len() * BLAKE3_ROUNDS
+    }
+
+    fn trace_width(&self) -> usize {
+        Blake3CompressCols::<u8>::width()
+    }
+}
+
+impl ChipUsageGetter for SharedBlake3CompressChip {
+    fn air_name(&self) -> String {
+        self.0.air_name()
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.0.current_trace_height()
+    }
+
+    fn trace_width(&self) -> usize {
+        self.0.trace_width()
+    }
+}
+
+impl AsRef<Blake3CompressChip> for SharedBlake3CompressChip {
+    fn as_ref(&self) -> &Blake3CompressChip {
+        &self.0
+    }
+}
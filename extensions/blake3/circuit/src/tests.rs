@@ -0,0 +1,161 @@
+use std::{array::from_fn, borrow::BorrowMut};
+
+use openvm_blake3_guest::{blake3_compress, CHUNK_END, CHUNK_START, PARENT, ROOT};
+use openvm_blake3_transpiler::Rv32Blake3Opcode;
+use openvm_circuit::arch::testing::{VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_instructions::{riscv::RV32_CELL_BITS, LocalOpcode};
+use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapTwoReadsAdapterChip};
+use openvm_stark_backend::{
+    interaction::{BusIndex, LookupBus},
+    p3_field::{FieldAlgebra, PrimeField32},
+    p3_matrix::Matrix,
+    utils::disable_debug_builder,
+    verifier::VerificationError,
+};
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, utils::create_seeded_rng};
+use rand::Rng;
+
+use crate::{
+    Blake3CoreChip, Blake3CoreCols, Rv32Blake3Chip, SharedBlake3CompressChip, BLAKE3_BLOCK_WORDS,
+    BLAKE3_PARAMS_WORDS,
+};
+
+type F = BabyBear;
+
+const BLAKE3_COMPRESS_BUS: BusIndex = 13;
+
+fn to_heap(words: &[u32]) -> Vec<[F; 4]> {
+    words
+        .iter()
+        .map(|word| word.to_le_bytes().map(F::from_canonical_u8))
+        .collect()
+}
+
+fn build_blake3_test(
+    inputs: Vec<([u32; BLAKE3_PARAMS_WORDS], [u32; BLAKE3_BLOCK_WORDS])>,
+) -> (
+    VmChipTestBuilder<F>,
+    Rv32Blake3Chip<F>,
+    SharedBlake3CompressChip,
+    SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+) {
+    let mut tester = VmChipTestBuilder::default();
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let compress_chip = SharedBlake3CompressChip::new(LookupBus::new(BLAKE3_COMPRESS_BUS));
+    let mut chip = Rv32Blake3Chip::new(
+        Rv32VecHeapTwoReadsAdapterChip::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        ),
+        Blake3CoreChip::new(
+            compress_chip.clone(),
+            bitwise_chip.clone(),
+            Rv32Blake3Opcode::CLASS_OFFSET,
+        ),
+        tester.offline_memory_mutex_arc(),
+    );
+
+    for (params, block) in inputs {
+        let instruction = rv32_write_heap_default(
+            &mut tester,
+            to_heap(&params),
+            to_heap(&block),
+            Rv32Blake3Opcode::COMPRESS.global_opcode().as_usize(),
+        );
+        tester.execute(&mut chip, &instruction);
+
+        let rd_ptr = instruction.a.as_canonical_u32() as usize;
+        let out_ptr = u32::from_le_bytes(
+            tester
+                .read::<4>(1, rd_ptr)
+                .map(|x| x.as_canonical_u32() as u8),
+        ) as usize;
+        let output: [u32; 8] = from_fn(|i| {
+            u32::from_le_bytes(
+                tester
+                    .read::<4>(2, out_ptr + 4 * i)
+                    .map(|x| x.as_canonical_u32() as u8),
+            )
+        });
+        let counter = params[8] as u64 | (params[9] as u64) << 32;
+        let expected = blake3_compress(
+            &from_fn(|i| params[i]),
+            &block,
+            counter,
+            params[10],
+            params[11],
+        );
+        assert_eq!(output, expected);
+    }
+    (tester, chip, compress_chip, bitwise_chip)
+}
+
+#[test]
+fn test_blake3_compress() {
+    let mut rng = create_seeded_rng();
+    let flags = [
+        CHUNK_START,
+        0,
+        CHUNK_END,
+        CHUNK_START | CHUNK_END | ROOT,
+        PARENT,
+        PARENT | ROOT,
+    ];
+    let mut inputs: Vec<_> = flags
+        .into_iter()
+        .map(|flags| {
+            let mut params: [u32; BLAKE3_PARAMS_WORDS] = from_fn(|_| rng.gen());
+            params[10] = rng.gen_range(0..=64);
+            params[11] = flags;
+            (params, from_fn(|_| rng.gen()))
+        })
+        .collect();
+    inputs.push(([0; BLAKE3_PARAMS_WORDS], [0; BLAKE3_BLOCK_WORDS]));
+    inputs.push((
+        [u32::MAX; BLAKE3_PARAMS_WORDS],
+        [u32::MAX; BLAKE3_BLOCK_WORDS],
+    ));
+
+    let (tester, chip, compress_chip, bitwise_chip) = build_blake3_test(inputs);
+    let tester = tester
+        .build()
+        .load(chip)
+        .load(compress_chip)
+        .load(bitwise_chip)
+        .finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+// An output that is not the compression of the inputs has no matching compression on the bus.
+#[test]
+fn test_blake3_compress_wrong_output() {
+    let (tester, chip, compress_chip, bitwise_chip) =
+        build_blake3_test(vec![([1; BLAKE3_PARAMS_WORDS], [2; BLAKE3_BLOCK_WORDS])]);
+    let mut tester = tester
+        .build()
+        .load(chip)
+        .load(compress_chip)
+        .load(bitwise_chip)
+        .finalize();
+
+    let trace = tester.air_proof_inputs[2]
+        .1
+        .raw
+        .common_main
+        .as_mut()
+        .unwrap();
+    let core_start = trace.width() - Blake3CoreCols::<F>::width();
+    let row = trace.row_mut(0);
+    let cols: &mut Blake3CoreCols<F> = row[core_start..].borrow_mut();
+    cols.output[0][0] = F::from_canonical_u32((cols.output[0][0].as_canonical_u32() + 1) % 256);
+
+    disable_debug_builder();
+    tester.simple_test_with_expected_error(VerificationError::ChallengePhaseError);
+}
@@ -0,0 +1,182 @@
+#![no_std]
+
+//! The BLAKE3 hash function.
+//!
+//! In the zkVM, [blake3_compress] is a single instruction of the BLAKE3 extension, and [blake3]
+//! builds the chunk and parent tree of the hash from it. Outside the zkVM the compression
+//! function falls back to a portable implementation.
+
+/// This is custom-0 defined in RISC-V spec document
+pub const OPCODE: u8 = 0x0b;
+pub const BLAKE3_FUNCT3: u8 = 0b100;
+/// Compresses the chaining value and parameters at `rs1` with the block at `rs2` and writes the
+/// new chaining value to `rd`.
+pub const BLAKE3_FUNCT7: u8 = 0x5;
+
+openvm_platform::require_extension_version!("blake3", 1);
+
+/// The number of bytes of a BLAKE3 digest.
+pub const BLAKE3_OUT_LEN: usize = 32;
+/// The number of bytes of a message block.
+pub const BLAKE3_BLOCK_LEN: usize = 64;
+/// The number of bytes of a chunk, the leaves of the BLAKE3 tree.
+pub const BLAKE3_CHUNK_LEN: usize = 1024;
+
+pub const CHUNK_START: u32 = 1 << 0;
+pub const CHUNK_END: u32 = 1 << 1;
+pub const PARENT: u32 = 1 << 2;
+pub const ROOT: u32 = 1 << 3;
+
+pub const IV: [u32; 8] = [
+    0x6A09E667, 0xBB67AE85, 0x3C6EF372, 0xA54FF53A, 0x510E527F, 0x9B05688C, 0x1F83D9AB, 0x5BE0CD19,
+];
+
+/// The permutation applied to the message words between rounds.
+pub const MSG_PERMUTATION: [usize; 16] = [2, 6, 3, 10, 7, 0, 4, 13, 1, 11, 12, 5, 9, 14, 15, 8];
+
+/// Enough chaining values for inputs of up to `2^54` chunks.
+const MAX_DEPTH: usize = 54;
+
+/// The BLAKE3 compression function, truncated to the new chaining value.
+///
+/// The instruction reads `cv`, the low and high words of `counter`, `block_len` and `flags` as
+/// 12 consecutive words from `rs1`, and the 16 words of `block` from `rs2`.
+#[inline(always)]
+pub fn blake3_compress(
+    cv: &[u32; 8],
+    block: &[u32; 16],
+    counter: u64,
+    block_len: u32,
+    flags: u32,
+) -> [u32; 8] {
+    #[cfg(target_os = "zkvm")]
+    {
+        let mut params = [0u32; 12];
+        params[..8].copy_from_slice(cv);
+        params[8] = counter as u32;
+        params[9] = (counter >> 32) as u32;
+        params[10] = block_len;
+        params[11] = flags;
+        let mut output = core::mem::MaybeUninit::<[u32; 8]>::uninit();
+        openvm_platform::custom_insn_r!(
+            opcode = OPCODE,
+            funct3 = BLAKE3_FUNCT3,
+            funct7 = BLAKE3_FUNCT7,
+            rd = In output.as_mut_ptr(),
+            rs1 = In params.as_ptr(),
+            rs2 = In block.as_ptr()
+        );
+        unsafe { output.assume_init() }
+    }
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        let mut state: [u32; 16] = core::array::from_fn(|i| match i {
+            0..8 => cv[i],
+            8..12 => IV[i - 8],
+            12 => counter as u32,
+            13 => (counter >> 32) as u32,
+            14 => block_len,
+            _ => flags,
+        });
+        let mut msg = *block;
+        for round in 0..7 {
+            if round > 0 {
+                msg = core::array::from_fn(|i| msg[MSG_PERMUTATION[i]]);
+            }
+            g(&mut state, 0, 4, 8, 12, msg[0], msg[1]);
+            g(&mut state, 1, 5, 9, 13, msg[2], msg[3]);
+            g(&mut state, 2, 6, 10, 14, msg[4], msg[5]);
+            g(&mut state, 3, 7, 11, 15, msg[6], msg[7]);
+            g(&mut state, 0, 5, 10, 15, msg[8], msg[9]);
+            g(&mut state, 1, 6, 11, 12, msg[10], msg[11]);
+            g(&mut state, 2, 7, 8, 13, msg[12], msg[13]);
+            g(&mut state, 3, 4, 9, 14, msg[14], msg[15]);
+        }
+        core::array::from_fn(|i| state[i] ^ state[i + 8])
+    }
+}
+
+#[cfg(not(target_os = "zkvm"))]
+#[inline(always)]
+fn g(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize, mx: u32, my: u32) {
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(mx);
+    state[d] = (state[d] ^ state[a]).rotate_right(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(12);
+    state[a] = state[a].wrapping_add(state[b]).wrapping_add(my);
+    state[d] = (state[d] ^ state[a]).rotate_right(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_right(7);
+}
+
+/// Hashes `input` with BLAKE3 in its default mode, without a key.
+pub fn blake3(input: &[u8]) -> [u8; BLAKE3_OUT_LEN] {
+    let num_chunks = input.len().div_ceil(BLAKE3_CHUNK_LEN).max(1);
+    let mut chunks = input.chunks(BLAKE3_CHUNK_LEN);
+
+    // The chaining values of complete subtrees, merged as soon as a sibling is available.
+    let mut stack = [[0u32; 8]; MAX_DEPTH];
+    let mut stack_len = 0;
+    for counter in 0..num_chunks - 1 {
+        let mut cv = chunk_cv(chunks.next().unwrap(), counter as u64, 0);
+        let mut total_chunks = counter + 1;
+        while total_chunks & 1 == 0 {
+            stack_len -= 1;
+            cv = parent_cv(&stack[stack_len], &cv, 0);
+            total_chunks >>= 1;
+        }
+        stack[stack_len] = cv;
+        stack_len += 1;
+    }
+
+    let last_chunk = chunks.next().unwrap_or(&[]);
+    let root = if stack_len == 0 {
+        chunk_cv(last_chunk, 0, ROOT)
+    } else {
+        let mut cv = chunk_cv(last_chunk, (num_chunks - 1) as u64, 0);
+        while stack_len > 1 {
+            stack_len -= 1;
+            cv = parent_cv(&stack[stack_len], &cv, 0);
+        }
+        parent_cv(&stack[0], &cv, ROOT)
+    };
+
+    let mut output = [0u8; BLAKE3_OUT_LEN];
+    for (bytes, word) in output.chunks_exact_mut(4).zip(root) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    output
+}
+
+/// The chaining value of a chunk of at most [BLAKE3_CHUNK_LEN] bytes. `root_flag` is added to
+/// the flags of the last block.
+fn chunk_cv(chunk: &[u8], counter: u64, root_flag: u32) -> [u32; 8] {
+    let num_blocks = chunk.len().div_ceil(BLAKE3_BLOCK_LEN).max(1);
+    let mut cv = IV;
+    for i in 0..num_blocks {
+        let start = (i * BLAKE3_BLOCK_LEN).min(chunk.len());
+        let end = ((i + 1) * BLAKE3_BLOCK_LEN).min(chunk.len());
+        let mut block_bytes = [0u8; BLAKE3_BLOCK_LEN];
+        block_bytes[..end - start].copy_from_slice(&chunk[start..end]);
+        let block = core::array::from_fn(|j| {
+            u32::from_le_bytes(block_bytes[4 * j..4 * j + 4].try_into().unwrap())
+        });
+
+        let mut flags = 0;
+        if i == 0 {
+            flags |= CHUNK_START;
+        }
+        if i == num_blocks - 1 {
+            flags |= CHUNK_END | root_flag;
+        }
+        cv = blake3_compress(&cv, &block, counter, (end - start) as u32, flags);
+    }
+    cv
+}
+
+fn parent_cv(left: &[u32; 8], right: &[u32; 8], root_flag: u32) -> [u32; 8] {
+    let mut block = [0u32; 16];
+    block[..8].copy_from_slice(left);
+    block[8..].copy_from_slice(right);
+    blake3_compress(&IV, &block, 0, BLAKE3_BLOCK_LEN as u32, PARENT | root_flag)
+}
@@ -0,0 +1,20 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::io::read;
+use openvm_blake3_guest::{blake3, BLAKE3_OUT_LEN};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let inputs: Vec<Vec<u8>> = read();
+    let digests: Vec<[u8; BLAKE3_OUT_LEN]> = read();
+    assert_eq!(inputs.len(), digests.len());
+    for (input, expected) in inputs.iter().zip(digests) {
+        assert_eq!(blake3(input), expected);
+    }
+}
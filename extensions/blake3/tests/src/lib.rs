@@ -0,0 +1,69 @@
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+    use openvm_blake3_guest::blake3;
+    use openvm_circuit::{arch::instructions::exe::VmExe, utils::air_test_with_min_segments};
+    use openvm_sdk::{
+        config::{AppConfig, SdkVmConfig},
+        StdIn,
+    };
+    use openvm_stark_sdk::utils::create_seeded_rng;
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::FromElf;
+    use rand::Rng;
+
+    fn blake3_config() -> Result<SdkVmConfig> {
+        Ok(toml::from_str::<AppConfig<SdkVmConfig>>(include_str!(
+            "../programs/openvm_blake3.toml"
+        ))?
+        .app_vm_config)
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_blake3() -> Result<()> {
+        let mut rng = create_seeded_rng();
+        let config = blake3_config()?;
+        let elf = build_example_program_at_path(get_programs_dir!(), "blake3", &config)?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+
+        // Lengths around the block and chunk boundaries, and a tree of more than two chunks.
+        let inputs: Vec<Vec<u8>> = [0, 1, 63, 64, 65, 1023, 1024, 1025, 2048, 3073]
+            .into_iter()
+            .map(|len| (0..len).map(|_| rng.gen()).collect())
+            .collect();
+        let digests: Vec<_> = inputs.iter().map(|input| blake3(input)).collect();
+
+        let mut input = StdIn::default();
+        input.write(&inputs);
+        input.write(&digests);
+        air_test_with_min_segments(config, openvm_exe, input, 1);
+        Ok(())
+    }
+
+    /// Test vectors from the BLAKE3 reference implementation, whose inputs are the bytes `i % 251`.
+    #[test]
+    fn test_blake3_vectors() {
+        let vectors = [
+            (
+                0,
+                "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262",
+            ),
+            (
+                1024,
+                "42214739f095a406f3fc83deb889744ac00df831c10daa55189b5d121c855af7",
+            ),
+            (
+                1025,
+                "d00278ae47eb27b34faecf67b4fe263f82d5412916c1ffd97c8cb7fb814b8444",
+            ),
+        ];
+        for (len, expected) in vectors {
+            let input: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+            assert_eq!(hex(&blake3(&input)), expected);
+        }
+    }
+}
@@ -97,6 +97,10 @@ impl<F: PrimeField32> VmExtension<F> for WeierstrassExtension {
     type Executor = WeierstrassExtensionExecutor<F>;
     type Periphery = WeierstrassExtensionPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("weierstrass", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -2,7 +2,8 @@ use core::ops::Neg;
 
 use openvm_algebra_guest::Field;
 
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[repr(C)]
 pub struct AffinePoint<F> {
     pub x: F,
@@ -18,7 +18,7 @@ use elliptic_curve::{
     subtle::{Choice, ConditionallySelectable, CtOption},
     CurveArithmetic, FieldBytes, FieldBytesEncoding, FieldBytesSize, PrimeCurve,
 };
-use openvm_algebra_guest::{DivUnsafe, IntMod, Reduce};
+use openvm_algebra_guest::{Field, IntMod, Reduce};
 
 use crate::{
     weierstrass::{FromCompressed, IntrinsicCurve, WeierstrassPoint},
@@ -221,7 +221,7 @@ where
     C: IntrinsicCurve + PrimeCurve,
     C::Point: WeierstrassPoint + CyclicGroup + FromCompressed<Coordinate<C>> + VerifyCustomHook<C>,
     Coordinate<C>: IntMod,
-    C::Scalar: IntMod + Reduce,
+    C::Scalar: IntMod + Field + Reduce,
     for<'a> &'a C::Point: Add<&'a C::Point, Output = C::Point>,
     for<'a> &'a Coordinate<C>: Mul<&'a Coordinate<C>, Output = Coordinate<C>>,
     FieldBytesSize<C>: ModulusSize,
@@ -306,9 +306,8 @@ where
     SignatureSize<C>: ArrayLength<u8>,
     C::Point: WeierstrassPoint + CyclicGroup + FromCompressed<Coordinate<C>> + VerifyCustomHook<C>,
     Coordinate<C>: IntMod,
-    <C as IntrinsicCurve>::Scalar: IntMod + Reduce,
+    <C as IntrinsicCurve>::Scalar: IntMod + Field + Reduce,
     for<'a> &'a C::Point: Add<&'a C::Point, Output = C::Point>,
-    for<'a> &'a Scalar<C>: DivUnsafe<&'a Scalar<C>, Output = Scalar<C>>,
 {
     fn verify_digest(&self, msg_digest: D, signature: &Signature<C>) -> Result<()> {
         PrehashVerifier::<Signature<C>>::verify_prehash(
@@ -325,9 +324,8 @@ where
     SignatureSize<C>: ArrayLength<u8>,
     C::Point: WeierstrassPoint + CyclicGroup + FromCompressed<Coordinate<C>> + VerifyCustomHook<C>,
     Coordinate<C>: IntMod,
-    C::Scalar: IntMod + Reduce,
+    C::Scalar: IntMod + Field + Reduce,
     for<'a> &'a C::Point: Add<&'a C::Point, Output = C::Point>,
-    for<'a> &'a Scalar<C>: DivUnsafe<&'a Scalar<C>, Output = Scalar<C>>,
 {
     fn verify_prehash(&self, prehash: &[u8], signature: &Signature<C>) -> Result<()> {
         self.inner.as_affine().verify_hook(prehash, signature)?;
@@ -345,9 +343,8 @@ where
     SignatureSize<C>: ArrayLength<u8>,
     C::Point: WeierstrassPoint + CyclicGroup + FromCompressed<Coordinate<C>> + VerifyCustomHook<C>,
     Coordinate<C>: IntMod,
-    <C as IntrinsicCurve>::Scalar: IntMod + Reduce,
+    <C as IntrinsicCurve>::Scalar: IntMod + Field + Reduce,
     for<'a> &'a C::Point: Add<&'a C::Point, Output = C::Point>,
-    for<'a> &'a Scalar<C>: DivUnsafe<&'a Scalar<C>, Output = Scalar<C>>,
 {
     fn verify(&self, msg: &[u8], signature: &Signature<C>) -> Result<()> {
         self.verify_digest(C::Digest::new_with_prefix(msg), signature)
@@ -415,7 +412,7 @@ where
     C: IntrinsicCurve + PrimeCurve,
     C::Point: WeierstrassPoint + CyclicGroup + FromCompressed<Coordinate<C>>,
     Coordinate<C>: IntMod,
-    C::Scalar: IntMod + Reduce,
+    C::Scalar: IntMod + Field + Reduce,
 {
     /// ## Assumption
     /// To use this implementation, the `Signature<C>`, `Coordinate<C>`, and `FieldBytes<C>` should
@@ -444,7 +441,7 @@ where
         // Note: Scalar internally stores using little endian
         let r = Scalar::<C>::from_be_bytes(r_be).ok_or_else(Error::new)?;
         let s = Scalar::<C>::from_be_bytes(s_be).ok_or_else(Error::new)?;
-        if r == Scalar::<C>::ZERO || s == Scalar::<C>::ZERO {
+        if s == <Scalar<C> as IntMod>::ZERO {
             return Err(Error::new());
         }
 
@@ -484,8 +481,10 @@ where
         // The point R decompressed from x-coordinate `r`
         let R: C::Point = FromCompressed::decompress(x, &rec_id).ok_or_else(Error::new)?;
 
-        let neg_u1 = z.div_unsafe(&r);
-        let u2 = s.div_unsafe(&r);
+        // Fails on zero `r`
+        let r_inv = Field::invert(&r).ok_or_else(Error::new)?;
+        let neg_u1 = z * &r_inv;
+        let u2 = s * &r_inv;
         let NEG_G = C::Point::NEG_GENERATOR;
         let point = <C as IntrinsicCurve>::msm(&[neg_u1, u2], &[NEG_G, R]);
         let vk = VerifyingKey::from_affine(point)?;
@@ -502,9 +501,8 @@ where
     C: IntrinsicCurve + PrimeCurve,
     C::Point: WeierstrassPoint + CyclicGroup + FromCompressed<Coordinate<C>>,
     Coordinate<C>: IntMod,
-    C::Scalar: IntMod + Reduce,
+    C::Scalar: IntMod + Field + Reduce,
     for<'a> &'a C::Point: Add<&'a C::Point, Output = C::Point>,
-    for<'a> &'a Scalar<C>: DivUnsafe<&'a Scalar<C>, Output = Scalar<C>>,
 {
     // This should get compiled out:
     assert!(Scalar::<C>::NUM_LIMBS <= Coordinate::<C>::NUM_LIMBS);
@@ -515,7 +513,7 @@ where
     // Note: Scalar internally stores using little endian
     let r = Scalar::<C>::from_be_bytes(r_be).ok_or_else(Error::new)?;
     let s = Scalar::<C>::from_be_bytes(s_be).ok_or_else(Error::new)?;
-    if r == Scalar::<C>::ZERO || s == Scalar::<C>::ZERO {
+    if r == <Scalar<C> as IntMod>::ZERO {
         return Err(Error::new());
     }
 
@@ -527,8 +525,10 @@ where
     // we don't need to reduce because IntMod is up to modular equivalence
     let z = Scalar::<C>::from_be_bytes_unchecked(&prehash_bytes[..prehash_bytes.len() - trim]);
 
-    let u1 = z.div_unsafe(&s);
-    let u2 = (&r).div_unsafe(&s);
+    // Fails on zero `s`
+    let s_inv = Field::invert(&s).ok_or_else(Error::new)?;
+    let u1 = z * &s_inv;
+    let u2 = s_inv * &r;
 
     let G = C::Point::GENERATOR;
     // public key
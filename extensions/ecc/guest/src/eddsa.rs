@@ -0,0 +1,381 @@
+use core::{
+    fmt::{self, Debug},
+    marker::PhantomData,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+};
+
+use ecdsa_core::{Error, Result};
+use openvm_algebra_guest::{DivUnsafe, IntMod, Reduce, Sqrt};
+use openvm_sha2::Sha512;
+
+use crate::Group;
+
+/// Ed25519 curve and its coordinate and scalar fields.
+pub mod ed25519;
+
+/// Twisted Edwards curve `a * x^2 + y^2 = 1 + d * x^2 * y^2` over the field `Coordinate`.
+///
+/// The addition law implemented by [EdwardsPoint] is complete, and hence the curve arithmetic is
+/// only sound, when `a` is a square and `d` is a non-square in `Coordinate`.
+pub trait TwistedEdwardsCurve: Sized {
+    /// Field of the curve coordinates. Must be a prime field.
+    type Coordinate: IntMod + Sqrt;
+    /// Scalar field of the prime order subgroup generated by [Self::GENERATOR].
+    type Scalar: IntMod + Reduce;
+
+    const CURVE_A: Self::Coordinate;
+    const CURVE_D: Self::Coordinate;
+    /// Generator of the prime order subgroup.
+    const GENERATOR: EdwardsPoint<Self>;
+    /// Base 2 logarithm of the cofactor.
+    const LOG_COFACTOR: usize;
+}
+
+/// Affine point on a twisted Edwards curve. Unlike short Weierstrass points, the identity
+/// `(0, 1)` is an affine point so no special handling is required.
+pub struct EdwardsPoint<C: TwistedEdwardsCurve> {
+    x: C::Coordinate,
+    y: C::Coordinate,
+    _marker: PhantomData<C>,
+}
+
+impl<C: TwistedEdwardsCurve> EdwardsPoint<C> {
+    /// Does not check that `(x, y)` is on the curve.
+    pub const fn from_xy_unchecked(x: C::Coordinate, y: C::Coordinate) -> Self {
+        Self {
+            x,
+            y,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns `None` if `(x, y)` is not on the curve.
+    pub fn from_xy(x: C::Coordinate, y: C::Coordinate) -> Option<Self> {
+        let x2 = x.square();
+        let y2 = y.square();
+        let lhs = C::CURVE_A * &x2 + &y2;
+        let rhs = <C::Coordinate as IntMod>::ONE + C::CURVE_D * &x2 * &y2;
+        (lhs == rhs).then(|| Self::from_xy_unchecked(x, y))
+    }
+
+    pub fn x(&self) -> &C::Coordinate {
+        &self.x
+    }
+
+    pub fn y(&self) -> &C::Coordinate {
+        &self.y
+    }
+
+    /// Decodes a point from its RFC 8032 encoding: the little-endian `y` coordinate with the most
+    /// significant bit of the last byte set to the least significant bit of `x`.
+    ///
+    /// Returns `None` if `y` is not canonical or does not correspond to a point on the curve.
+    pub fn from_compressed(bytes: &[u8]) -> Option<Self> {
+        let num_bytes = <C::Coordinate as IntMod>::NUM_LIMBS;
+        if bytes.len() != num_bytes {
+            return None;
+        }
+        let x_sign = bytes[num_bytes - 1] >> 7;
+        let mut y_bytes = bytes.to_vec();
+        y_bytes[num_bytes - 1] &= 0x7f;
+        let y = C::Coordinate::from_le_bytes(&y_bytes)?;
+
+        // x^2 = (1 - y^2) / (a - d * y^2). The denominator is never zero since `d` is a
+        // non-square.
+        let y2 = y.square();
+        let num = <C::Coordinate as IntMod>::ONE - &y2;
+        let denom = C::CURVE_A - C::CURVE_D * &y2;
+        let x = num.div_unsafe(&denom).sqrt()?;
+        // `sqrt` returns the canonical representation so the parity of `x` is well-defined.
+        let x = if x == <C::Coordinate as IntMod>::ZERO {
+            if x_sign == 1 {
+                return None;
+            }
+            x
+        } else if x.as_le_bytes()[0] & 1 != x_sign {
+            -x
+        } else {
+            x
+        };
+        Some(Self::from_xy_unchecked(x, y))
+    }
+
+    /// Scalar multiplication by the little-endian integer `scalar`, using double-and-add.
+    pub fn mul_le_bytes(&self, scalar: &[u8]) -> Self {
+        let mut acc = <Self as Group>::IDENTITY;
+        for byte in scalar.iter().rev() {
+            for i in (0..8).rev() {
+                acc.double_assign();
+                if (byte >> i) & 1 == 1 {
+                    acc += self;
+                }
+            }
+        }
+        acc
+    }
+
+    /// Multiplies by the cofactor of the curve.
+    pub fn mul_by_cofactor(&self) -> Self {
+        let mut res = self.clone();
+        for _ in 0..C::LOG_COFACTOR {
+            res.double_assign();
+        }
+        res
+    }
+
+    fn add_impl(&self, other: &Self) -> Self {
+        // x3 = (x1 * y2 + y1 * x2) / (1 + d * x1 * x2 * y1 * y2)
+        // y3 = (y1 * y2 - a * x1 * x2) / (1 - d * x1 * x2 * y1 * y2)
+        let x1x2 = self.x.clone() * &other.x;
+        let y1y2 = self.y.clone() * &other.y;
+        let t = C::CURVE_D * &x1x2 * &y1y2;
+        let x_num = self.x.clone() * &other.y + self.y.clone() * &other.x;
+        let y_num = y1y2 - C::CURVE_A * &x1x2;
+        let x = x_num.div_unsafe(<C::Coordinate as IntMod>::ONE + &t);
+        let y = y_num.div_unsafe(<C::Coordinate as IntMod>::ONE - t);
+        Self::from_xy_unchecked(x, y)
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Clone for EdwardsPoint<C> {
+    fn clone(&self) -> Self {
+        Self::from_xy_unchecked(self.x.clone(), self.y.clone())
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Debug for EdwardsPoint<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EdwardsPoint")
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .finish()
+    }
+}
+
+impl<C: TwistedEdwardsCurve> PartialEq for EdwardsPoint<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.x == other.x && self.y == other.y
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Eq for EdwardsPoint<C> {}
+
+impl<C: TwistedEdwardsCurve> Group for EdwardsPoint<C> {
+    type SelfRef<'a>
+        = &'a Self
+    where
+        Self: 'a;
+
+    const IDENTITY: Self = Self {
+        x: <C::Coordinate as IntMod>::ZERO,
+        y: <C::Coordinate as IntMod>::ONE,
+        _marker: PhantomData,
+    };
+
+    fn is_identity(&self) -> bool {
+        self == &Self::IDENTITY
+    }
+
+    fn double(&self) -> Self {
+        self.add_impl(self)
+    }
+
+    fn double_assign(&mut self) {
+        *self = self.double();
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Neg for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::from_xy_unchecked(-self.x, self.y)
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Neg for &EdwardsPoint<C> {
+    type Output = EdwardsPoint<C>;
+
+    fn neg(self) -> EdwardsPoint<C> {
+        self.clone().neg()
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Add for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.add_impl(&other)
+    }
+}
+
+impl<'a, C: TwistedEdwardsCurve> Add<&'a EdwardsPoint<C>> for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn add(self, other: &'a Self) -> Self {
+        self.add_impl(other)
+    }
+}
+
+impl<'a, C: TwistedEdwardsCurve> Add<&'a EdwardsPoint<C>> for &EdwardsPoint<C> {
+    type Output = EdwardsPoint<C>;
+
+    fn add(self, other: &'a EdwardsPoint<C>) -> EdwardsPoint<C> {
+        self.add_impl(other)
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Sub for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.add_impl(&other.neg())
+    }
+}
+
+impl<'a, C: TwistedEdwardsCurve> Sub<&'a EdwardsPoint<C>> for EdwardsPoint<C> {
+    type Output = Self;
+
+    fn sub(self, other: &'a Self) -> Self {
+        self.add_impl(&other.neg())
+    }
+}
+
+impl<'a, C: TwistedEdwardsCurve> Sub<&'a EdwardsPoint<C>> for &EdwardsPoint<C> {
+    type Output = EdwardsPoint<C>;
+
+    fn sub(self, other: &'a EdwardsPoint<C>) -> EdwardsPoint<C> {
+        self.add_impl(&other.neg())
+    }
+}
+
+impl<C: TwistedEdwardsCurve> AddAssign for EdwardsPoint<C> {
+    fn add_assign(&mut self, other: Self) {
+        *self = self.add_impl(&other);
+    }
+}
+
+impl<'a, C: TwistedEdwardsCurve> AddAssign<&'a EdwardsPoint<C>> for EdwardsPoint<C> {
+    fn add_assign(&mut self, other: &'a Self) {
+        *self = self.add_impl(other);
+    }
+}
+
+impl<C: TwistedEdwardsCurve> SubAssign for EdwardsPoint<C> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = self.add_impl(&other.neg());
+    }
+}
+
+impl<'a, C: TwistedEdwardsCurve> SubAssign<&'a EdwardsPoint<C>> for EdwardsPoint<C> {
+    fn sub_assign(&mut self, other: &'a Self) {
+        *self = self.add_impl(&other.neg());
+    }
+}
+
+/// EdDSA signature `(R, S)` in its RFC 8032 encoding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Signature {
+    r: [u8; 32],
+    s: [u8; 32],
+}
+
+impl Signature {
+    pub fn from_bytes(bytes: &[u8; 64]) -> Self {
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&bytes[..32]);
+        s.copy_from_slice(&bytes[32..]);
+        Self { r, s }
+    }
+
+    pub fn from_slice(bytes: &[u8]) -> Result<Self> {
+        let bytes: &[u8; 64] = bytes.try_into().map_err(|_| Error::new())?;
+        Ok(Self::from_bytes(bytes))
+    }
+
+    pub fn r_bytes(&self) -> &[u8; 32] {
+        &self.r
+    }
+
+    pub fn s_bytes(&self) -> &[u8; 32] {
+        &self.s
+    }
+
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0u8; 64];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..].copy_from_slice(&self.s);
+        bytes
+    }
+}
+
+/// EdDSA public key with 32-byte point encodings and SHA-512 as the hash function, as in Ed25519.
+pub struct VerifyingKey<C: TwistedEdwardsCurve> {
+    point: EdwardsPoint<C>,
+    /// The encoding is kept since it is hashed as-is during verification.
+    bytes: [u8; 32],
+}
+
+impl<C: TwistedEdwardsCurve> Clone for VerifyingKey<C> {
+    fn clone(&self) -> Self {
+        Self {
+            point: self.point.clone(),
+            bytes: self.bytes,
+        }
+    }
+}
+
+impl<C: TwistedEdwardsCurve> Debug for VerifyingKey<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifyingKey")
+            .field("point", &self.point)
+            .finish()
+    }
+}
+
+impl<C: TwistedEdwardsCurve> VerifyingKey<C> {
+    /// Decodes a public key. Fails if the encoding is not a valid curve point.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Result<Self> {
+        let point = EdwardsPoint::from_compressed(bytes).ok_or_else(Error::new)?;
+        Ok(Self {
+            point,
+            bytes: *bytes,
+        })
+    }
+
+    pub fn as_point(&self) -> &EdwardsPoint<C> {
+        &self.point
+    }
+
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.bytes
+    }
+
+    /// Cofactored verification of `signature` on `msg` as specified in
+    /// [RFC 8032, Section 5.1.7](https://www.rfc-editor.org/rfc/rfc8032#section-5.1.7):
+    /// checks `[2^c][S]B = [2^c]R + [2^c][k]A` where `k = SHA-512(R || A || msg) mod L`.
+    ///
+    /// Rejects signatures with a non-canonical `S` or an undecodable `R`.
+    pub fn verify(&self, msg: &[u8], signature: &Signature) -> Result<()> {
+        let r = EdwardsPoint::<C>::from_compressed(&signature.r).ok_or_else(Error::new)?;
+        // `from_le_bytes` rejects `S >= L`, which rules out signature malleability.
+        let s = C::Scalar::from_le_bytes(&signature.s).ok_or_else(Error::new)?;
+
+        let digest = Sha512::new()
+            .chain_update(signature.r)
+            .chain_update(self.bytes)
+            .chain_update(msg)
+            .finalize();
+        let k = C::Scalar::reduce_le_bytes(&digest);
+
+        let sb = C::GENERATOR.mul_le_bytes(s.as_le_bytes());
+        let ka = self.point.mul_le_bytes(k.as_le_bytes());
+        let diff = sb - ka - r;
+        if diff.mul_by_cofactor().is_identity() {
+            Ok(())
+        } else {
+            Err(Error::new())
+        }
+    }
+}
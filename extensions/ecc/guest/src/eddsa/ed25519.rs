@@ -0,0 +1,49 @@
+use hex_literal::hex;
+use openvm_algebra_guest::IntMod;
+use openvm_algebra_moduli_macros::moduli_declare;
+
+use super::{EdwardsPoint, TwistedEdwardsCurve, VerifyingKey as EddsaVerifyingKey};
+
+// The moduli must be included in the `moduli_init!` call of any guest program using this curve,
+// in this order (coordinate field, then scalar field).
+moduli_declare! {
+    Ed25519Coord { modulus = "0x7FFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFFF FFFFFFED" },
+    Ed25519Scalar { modulus = "0x10000000 00000000 00000000 00000000 14DEF9DE A2F79CD6 5812631A 5CF5D3ED" },
+}
+
+/// The Ed25519 curve `-x^2 + y^2 = 1 - (121665 / 121666) * x^2 * y^2` over `2^255 - 19`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Ed25519;
+
+pub type Ed25519Point = EdwardsPoint<Ed25519>;
+pub type VerifyingKey = EddsaVerifyingKey<Ed25519>;
+
+impl TwistedEdwardsCurve for Ed25519 {
+    type Coordinate = Ed25519Coord;
+    type Scalar = Ed25519Scalar;
+
+    // from_const_bytes takes a little endian byte string
+    const CURVE_A: Ed25519Coord = Ed25519Coord::from_const_bytes(hex!(
+        "ECFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF7F"
+    ));
+    const CURVE_D: Ed25519Coord = Ed25519Coord::from_const_bytes(hex!(
+        "A3785913CA4DEB75ABD841414D0A700098E879777940C78C73FE6F2BEE6C0352"
+    ));
+    // The constants are taken from: https://www.rfc-editor.org/rfc/rfc8032#section-5.1
+    const GENERATOR: Ed25519Point = EdwardsPoint::from_xy_unchecked(
+        Ed25519Coord::from_const_bytes(hex!(
+            "1AD5258F602D56C9B2A7259560C72C695CDCD6FD31E2A4C0FE536ECDD3366921"
+        )),
+        Ed25519Coord::from_const_bytes(hex!(
+            "5866666666666666666666666666666666666666666666666666666666666666"
+        )),
+    );
+    const LOG_COFACTOR: usize = 3;
+}
+
+impl Ed25519Point {
+    /// Decodes a point from its 32-byte RFC 8032 encoding.
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        Self::from_compressed(bytes)
+    }
+}
@@ -0,0 +1,30 @@
+//! Software implementation of the short Weierstrass instructions, used by the externs created by
+//! `sw_init!` when the VM does not have the short Weierstrass extension. The points are given by
+//! their addresses like the operands of the instructions.
+
+use openvm_algebra_guest::DivUnsafe;
+
+use crate::weierstrass::WeierstrassPoint;
+
+/// # Safety
+/// `rd`, `rs1` and `rs2` must point to points of `P`, with `rs1` and `rs2` neither equal nor
+/// opposite and neither the identity.
+pub unsafe fn add_ne<P: WeierstrassPoint>(rd: usize, rs1: usize, rs2: usize) {
+    let (p1, p2) = (&*(rs1 as *const P), &*(rs2 as *const P));
+    let lambda = (p2.y().clone() - p1.y()).div_unsafe(&(p2.x().clone() - p1.x()));
+    let x3 = lambda.clone() * &lambda - p1.x() - p2.x();
+    let y3 = lambda * &(p1.x().clone() - &x3) - p1.y();
+    core::ptr::write(rd as *mut P, P::from_xy_unchecked(x3, y3));
+}
+
+/// # Safety
+/// `rd` and `rs1` must point to points of `P`, with `rs1` of order greater than 2.
+pub unsafe fn double<P: WeierstrassPoint>(rd: usize, rs1: usize) {
+    let p = &*(rs1 as *const P);
+    let x_squared = p.x().clone() * p.x();
+    let lambda = (x_squared.clone() + &x_squared + &x_squared + &P::CURVE_A)
+        .div_unsafe(&(p.y().clone() + p.y()));
+    let x3 = lambda.clone() * &lambda - p.x() - p.x();
+    let y3 = lambda * &(p.x().clone() - &x3) - p.y();
+    core::ptr::write(rd as *mut P, P::from_xy_unchecked(x3, y3));
+}
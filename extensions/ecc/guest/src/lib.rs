@@ -3,9 +3,10 @@ extern crate self as openvm_ecc_guest;
 #[macro_use]
 extern crate alloc;
 
-pub use once_cell;
 pub use openvm_algebra_guest as algebra;
 pub use openvm_ecc_sw_macros as sw_macros;
+#[cfg(feature = "serde")]
+pub use serde;
 use strum_macros::FromRepr;
 
 mod affine_point;
@@ -17,6 +18,13 @@ pub use msm::*;
 
 /// Optimized ECDSA implementation with the same functional interface as the `ecdsa` crate
 pub mod ecdsa;
+/// EdDSA signature verification on twisted Edwards curves, including Ed25519
+#[cfg(feature = "eddsa")]
+pub mod eddsa;
+/// Software implementation of the short Weierstrass instructions, used when the VM lacks them
+#[cfg(all(target_os = "zkvm", feature = "fallback"))]
+#[doc(hidden)]
+pub mod fallback;
 /// Weierstrass curve traits
 pub mod weierstrass;
 
@@ -37,3 +45,13 @@ pub enum SwBaseFunct7 {
 impl SwBaseFunct7 {
     pub const SHORT_WEIERSTRASS_MAX_KINDS: u8 = 8;
 }
+
+/// Returns whether the VM has the short Weierstrass extension. Without it, the curves created by
+/// `sw_declare!` compute in software with the `fallback` feature.
+pub fn has_weierstrass() -> bool {
+    openvm::platform::has_insn!(openvm::platform::capabilities::insn_r(
+        OPCODE,
+        SW_FUNCT3,
+        SwBaseFunct7::SwAddNe as u8
+    ))
+}
@@ -1,7 +1,8 @@
 use alloc::vec::Vec;
 use core::ops::Mul;
 
-use openvm_algebra_guest::{Field, IntMod};
+pub use elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, ExpandMsgXof, Expander};
+use openvm_algebra_guest::{DivUnsafe, Field, IntMod, Reduce, Sqrt};
 
 use super::group::Group;
 
@@ -121,11 +122,123 @@ pub trait FromCompressed<Coordinate> {
     /// corresponding y-coordinate that satisfies the elliptic curve equation. If successful, it
     /// returns the point as an instance of Self. If the point cannot be decompressed, it returns
     /// None.
+    ///
+    /// Only the lowest bit of `rec_id` is used: it is the parity of the canonical representation of
+    /// the `y`-coordinate. In particular, if `y = 0` is the only solution, decompression fails when
+    /// the bit is 1.
     fn decompress(x: Coordinate, rec_id: &u8) -> Option<Self>
     where
         Self: core::marker::Sized;
 }
 
+/// Hashing to a short Weierstrass curve as specified in
+/// [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380).
+///
+/// Curves declared with `sw_declare!` and a `z` parameter implement this trait using the
+/// simplified SWU map directly on the curve, which requires `a * b != 0` and a prime order
+/// curve. Curves with `a = 0` (e.g. secp256k1, BLS12-381) or with a non-trivial cofactor
+/// should implement it by hand, evaluating [map_to_curve_sswu] on an isogenous curve and
+/// overriding [HashToCurve::clear_cofactor].
+pub trait HashToCurve: Group {
+    /// The field that messages are hashed to before being mapped to the curve.
+    type FieldElement: IntMod + Reduce;
+
+    /// Deterministically maps a field element to a point on the curve.
+    fn map_to_curve(u: &Self::FieldElement) -> Self;
+
+    /// Maps a point on the curve to the prime order subgroup.
+    fn clear_cofactor(self) -> Self {
+        self
+    }
+
+    /// Hashes `msgs` to a uniformly distributed point on the curve, with domain separation tag
+    /// `dsts`. The concatenation of `msgs` (resp. `dsts`) is used as the message (resp. tag).
+    fn hash_to_curve<'a, X: ExpandMsg<'a>>(
+        msgs: &[&[u8]],
+        dsts: &'a [&'a [u8]],
+    ) -> elliptic_curve::Result<Self> {
+        let [u0, u1] = hash_to_field::<X, Self::FieldElement, 2>(msgs, dsts)?;
+        Ok((Self::map_to_curve(&u0) + Self::map_to_curve(&u1)).clear_cofactor())
+    }
+
+    /// Encodes `msgs` to a point on the curve, with domain separation tag `dsts`. Unlike
+    /// [HashToCurve::hash_to_curve], the output is not uniformly distributed.
+    fn encode_to_curve<'a, X: ExpandMsg<'a>>(
+        msgs: &[&[u8]],
+        dsts: &'a [&'a [u8]],
+    ) -> elliptic_curve::Result<Self> {
+        let [u] = hash_to_field::<X, Self::FieldElement, 1>(msgs, dsts)?;
+        Ok(Self::map_to_curve(&u).clear_cofactor())
+    }
+}
+
+/// Hashes `msgs` to `N` field elements as in
+/// [RFC 9380, Section 5.2](https://www.rfc-editor.org/rfc/rfc9380#section-5.2), for a security
+/// level of 128 bits.
+pub fn hash_to_field<'a, X: ExpandMsg<'a>, F: IntMod + Reduce, const N: usize>(
+    msgs: &[&[u8]],
+    dsts: &'a [&'a [u8]],
+) -> elliptic_curve::Result<[F; N]> {
+    // L = ceil((ceil(log2(p)) + k) / 8) with k = 128
+    let modulus = F::MODULUS;
+    let modulus = modulus.as_ref();
+    let top = modulus.iter().rposition(|&b| b != 0).unwrap();
+    let modulus_bits = 8 * top + (8 - modulus[top].leading_zeros() as usize);
+    let len = (modulus_bits + 128).div_ceil(8);
+
+    let mut expander = X::expand_message(msgs, dsts, N * len)?;
+    let mut bytes = vec![0u8; len];
+    Ok(core::array::from_fn(|_| {
+        expander.fill_bytes(&mut bytes);
+        F::reduce_be_bytes(&bytes)
+    }))
+}
+
+/// The simplified Shallue-van de Woestijne-Ulas map of
+/// [RFC 9380, Section 6.6.2](https://www.rfc-editor.org/rfc/rfc9380#section-6.6.2), which
+/// requires `a * b != 0`. `z` must be a non-square such that `x^3 + a x + b - z` is
+/// irreducible and `(x^3 + a x + b)(b / (z a))` is a square.
+///
+/// This implementation is not constant time.
+pub fn map_to_curve_sswu<P>(u: &P::Coordinate, z: &P::Coordinate) -> P
+where
+    P: WeierstrassPoint,
+    P::Coordinate: IntMod + Sqrt,
+    for<'a> &'a P::Coordinate: Mul<&'a P::Coordinate, Output = P::Coordinate>,
+{
+    let zero = <P::Coordinate as IntMod>::ZERO;
+    let one = <P::Coordinate as IntMod>::ONE;
+    let a = &P::CURVE_A;
+    let b = &P::CURVE_B;
+    let g = |x: &P::Coordinate| x * x * x + a * x + b;
+
+    let tv1 = z * &(u * u);
+    let tv2 = &tv1 * &tv1 + &tv1;
+    let x1 = if tv2 == zero {
+        b.clone().div_unsafe(z * a)
+    } else {
+        (-b.clone()).div_unsafe(a) * (one.clone() + one.div_unsafe(tv2))
+    };
+    let (x, y) = match g(&x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            let x2 = tv1 * x1;
+            let y = g(&x2)
+                .sqrt()
+                .expect("g(x1) or g(x2) must be a square for a valid z");
+            (x2, y)
+        }
+    };
+    let y = if sgn0(u) != sgn0(&y) { -y } else { y };
+    P::from_xy_unchecked(x, y)
+}
+
+// sgn0 of RFC 9380, Section 4.1 for prime fields.
+fn sgn0<F: IntMod>(x: &F) -> bool {
+    x.assert_reduced();
+    x.as_le_bytes()[0] & 1 == 1
+}
+
 /// A trait for elliptic curves that bridges the openvm types and external types with
 /// CurveArithmetic etc. Implement this for external curves with corresponding openvm point and
 /// scalar types.
@@ -268,6 +381,28 @@ where
     }
 }
 
+/// Derives `Serialize` and `Deserialize` for the given item when the `serde` feature is enabled,
+/// so that crates using [impl_sw_affine] do not need to depend on `serde` directly.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __derive_serde {
+    ($item:item) => {
+        #[derive($crate::serde::Serialize, $crate::serde::Deserialize)]
+        #[serde(crate = "openvm_ecc_guest::serde")]
+        $item
+    };
+}
+
+#[cfg(not(feature = "serde"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __derive_serde {
+    ($item:item) => {
+        $item
+    };
+}
+
 /// Macro to generate a newtype wrapper for [AffinePoint](crate::AffinePoint)
 /// that implements elliptic curve operations by using the underlying field operations according to
 /// the [formulas](https://www.hyperelliptic.org/EFD/g1p/auto-shortw.html) for short Weierstrass curves.
@@ -286,9 +421,11 @@ macro_rules! impl_sw_affine {
     ($struct_name:ident, $field:ty, $three:expr, $b:expr) => {
         /// A newtype wrapper for [AffinePoint] that implements elliptic curve operations
         /// by using the underlying field operations according to the [formulas](https://www.hyperelliptic.org/EFD/g1p/auto-shortw.html) for short Weierstrass curves.
-        #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
-        #[repr(transparent)]
-        pub struct $struct_name(AffinePoint<$field>);
+        $crate::__derive_serde! {
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            #[repr(transparent)]
+            pub struct $struct_name(AffinePoint<$field>);
+        }
 
         impl $struct_name {
             pub const fn new(x: $field, y: $field) -> Self {
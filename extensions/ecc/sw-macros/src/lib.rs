@@ -1,11 +1,11 @@
 extern crate proc_macro;
 
-use openvm_macros_common::MacroArgs;
+use openvm_macros_common::{MacroArgs, ParamSpec};
 use proc_macro::TokenStream;
 use quote::format_ident;
 use syn::{
     parse::{Parse, ParseStream},
-    parse_macro_input, Expr, ExprPath, Path, Token,
+    parse_macro_input, Expr, Path, Token,
 };
 
 /// This macro generates the code to setup the elliptic curve for a given modular type. Also it
@@ -17,6 +17,10 @@ use syn::{
 /// }
 /// ```
 ///
+/// The optional `z` parameter is the non-square `Z` of the simplified SWU map (RFC 9380, Section
+/// 6.6.2). If given, `HashToCurve` is implemented for the curve, which then requires `a * b != 0`,
+/// a prime modulus and a prime order curve.
+///
 /// For this macro to work, you must import the `elliptic_curve` crate and the `openvm_ecc_guest`
 /// crate.
 #[proc_macro]
@@ -31,42 +35,53 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
         let struct_name = item.name.to_string();
         let struct_name = syn::Ident::new(&struct_name, span.into());
         let struct_path: syn::Path = syn::parse_quote!(#struct_name);
-        let mut intmod_type: Option<syn::Path> = None;
-        let mut const_a: Option<syn::Expr> = None;
-        let mut const_b: Option<syn::Expr> = None;
-        for param in item.params {
-            match param.name.to_string().as_str() {
-                // Note that mod_type must have NUM_LIMBS divisible by 4
-                "mod_type" => {
-                    if let syn::Expr::Path(ExprPath { path, .. }) = param.value {
-                        intmod_type = Some(path)
-                    } else {
-                        return syn::Error::new_spanned(param.value, "Expected a type")
-                            .to_compile_error()
-                            .into();
-                    }
-                }
-                "a" => {
-                    // We currently leave it to the compiler to check if the expression is actually
-                    // a constant
-                    const_a = Some(param.value);
-                }
-                "b" => {
-                    // We currently leave it to the compiler to check if the expression is actually
-                    // a constant
-                    const_b = Some(param.value);
-                }
-                _ => {
-                    panic!("Unknown parameter {}", param.name);
-                }
-            }
-        }
+        let params = match item.parse_params(&[
+            // Note that mod_type must have NUM_LIMBS divisible by 4
+            ParamSpec::required("mod_type"),
+            ParamSpec::optional("a"),
+            ParamSpec::required("b"),
+            // Non-square used by the simplified SWU map for hashing to the curve
+            ParamSpec::optional("z"),
+        ]) {
+            Ok(params) => params,
+            Err(err) => return err.to_compile_error().into(),
+        };
+        let intmod_type = match params.path("mod_type") {
+            Ok(intmod_type) => intmod_type.unwrap(),
+            Err(err) => return err.to_compile_error().into(),
+        };
+        // We currently leave it to the compiler to check if the expressions of the coefficients
+        // are actually constants
+        let const_a = params.get("a").cloned();
+        let const_b = params.get("b").cloned().unwrap();
+        let const_z = params.get("z").cloned();
 
-        let intmod_type = intmod_type.expect("mod_type parameter is required");
         // const_a is optional, default to 0
         let const_a = const_a
             .unwrap_or(syn::parse_quote!(<#intmod_type as openvm_algebra_guest::IntMod>::ZERO));
-        let const_b = const_b.expect("constant b coefficient is required");
+        // const_z is optional; if given, hashing to the curve is implemented with the simplified
+        // SWU map
+        // Hashing to the curve and decompression both need square roots, which are hint-based
+        let hints = cfg!(feature = "hints");
+        let hash_to_curve_impl = const_z.map(|const_z| {
+            if !hints {
+                return syn::Error::new_spanned(
+                    const_z,
+                    "Hashing to the curve requires the `hints` feature of `openvm-ecc-guest`",
+                )
+                .to_compile_error();
+            }
+            quote::quote_spanned! { span.into() =>
+                impl ::openvm_ecc_guest::weierstrass::HashToCurve for #struct_name {
+                    type FieldElement = #intmod_type;
+
+                    fn map_to_curve(u: &#intmod_type) -> Self {
+                        let z: #intmod_type = #const_z;
+                        ::openvm_ecc_guest::weierstrass::map_to_curve_sswu(u, &z)
+                    }
+                }
+            }
+        });
 
         macro_rules! create_extern_func {
             ($name:ident) => {
@@ -91,6 +106,47 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
 
         let group_ops_mod_name = format_ident!("{}_ops", struct_name.to_string().to_lowercase());
 
+        let from_compressed_impl = if hints {
+            quote::quote_spanned! { span.into() =>
+                impl ::openvm_ecc_guest::weierstrass::FromCompressed<#intmod_type> for #struct_name {
+                    fn decompress(x: #intmod_type, rec_id: &u8) -> Option<Self> {
+                        use openvm_algebra_guest::Sqrt;
+                        let y_squared = &x * &x * &x + &<#struct_name as ::openvm_ecc_guest::weierstrass::WeierstrassPoint>::CURVE_A * &x + &<#struct_name as ::openvm_ecc_guest::weierstrass::WeierstrassPoint>::CURVE_B;
+                        let y = y_squared.sqrt();
+                        match y {
+                            None => None,
+                            Some(y) => {
+                                let correct_y = if y.as_le_bytes()[0] & 1 == *rec_id & 1 {
+                                    y
+                                } else {
+                                    -y
+                                };
+                                // The parity is that of the canonical representation, so make sure
+                                // correct_y < modulus, as is already checked for y by sqrt()
+                                <#intmod_type as openvm_algebra_guest::IntMod>::assert_reduced(&correct_y);
+                                // If y = 0 then negating y doesn't change its parity
+                                if correct_y.as_le_bytes()[0] & 1 != *rec_id & 1 {
+                                    return None;
+                                }
+                                // In order for sqrt() to return Some, we are guaranteed that y * y == y_squared, which already proves (x, correct_y) is on the curve
+                                Some(<#struct_name as ::openvm_ecc_guest::weierstrass::WeierstrassPoint>::from_xy_unchecked(x, correct_y))
+                            }
+                        }
+                    }
+                }
+            }
+        } else {
+            quote::quote! {}
+        };
+        let serde_derive = if cfg!(feature = "serde") {
+            quote::quote! {
+                #[derive(::openvm_ecc_guest::serde::Serialize, ::openvm_ecc_guest::serde::Deserialize)]
+                #[serde(crate = "::openvm_ecc_guest::serde")]
+            }
+        } else {
+            quote::quote! {}
+        };
+
         let result = TokenStream::from(quote::quote_spanned! { span.into() =>
             extern "C" {
                 fn #sw_add_ne_extern_func(rd: usize, rs1: usize, rs2: usize);
@@ -98,7 +154,8 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                 fn #sw_setup_extern_func();
             }
 
-            #[derive(Eq, PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+            #[derive(Eq, PartialEq, Clone, Debug)]
+            #serde_derive
             #[repr(C)]
             pub struct #struct_name {
                 x: #intmod_type,
@@ -195,12 +252,12 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                 #[inline(always)]
                 #[cfg(target_os = "zkvm")]
                 fn set_up_once() {
-                    static is_setup: ::openvm_ecc_guest::once_cell::race::OnceBool = ::openvm_ecc_guest::once_cell::race::OnceBool::new();
-                    is_setup.get_or_init(|| {
+                    static is_setup: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+                    if !is_setup.load(core::sync::atomic::Ordering::Relaxed) {
                         unsafe { #sw_setup_extern_func(); }
                         <#intmod_type as openvm_algebra_guest::IntMod>::set_up_once();
-                        true
-                    });
+                        is_setup.store(true, core::sync::atomic::Ordering::Relaxed);
+                    }
                 }
 
                 #[inline(always)]
@@ -372,35 +429,15 @@ pub fn sw_declare(input: TokenStream) -> TokenStream {
                 }
             }
 
+            #hash_to_curve_impl
+
             mod #group_ops_mod_name {
-                use ::openvm_ecc_guest::{weierstrass::{WeierstrassPoint, FromCompressed}, impl_sw_group_ops, algebra::IntMod};
+                use ::openvm_ecc_guest::{weierstrass::WeierstrassPoint, impl_sw_group_ops, algebra::IntMod};
                 use super::*;
 
                 impl_sw_group_ops!(#struct_name, #intmod_type);
 
-                impl FromCompressed<#intmod_type> for #struct_name {
-                    fn decompress(x: #intmod_type, rec_id: &u8) -> Option<Self> {
-                        use openvm_algebra_guest::Sqrt;
-                        let y_squared = &x * &x * &x + &<#struct_name as ::openvm_ecc_guest::weierstrass::WeierstrassPoint>::CURVE_A * &x + &<#struct_name as ::openvm_ecc_guest::weierstrass::WeierstrassPoint>::CURVE_B;
-                        let y = y_squared.sqrt();
-                        match y {
-                            None => None,
-                            Some(y) => {
-                                let correct_y = if y.as_le_bytes()[0] & 1 == *rec_id & 1 {
-                                    y
-                                } else {
-                                    -y
-                                };
-                                // If y = 0 then negating y doesn't change its parity
-                                if correct_y.as_le_bytes()[0] & 1 != *rec_id & 1 {
-                                    return None;
-                                }
-                                // In order for sqrt() to return Some, we are guaranteed that y * y == y_squared, which already proves (x, correct_y) is on the curve
-                                Some(<#struct_name as ::openvm_ecc_guest::weierstrass::WeierstrassPoint>::from_xy_unchecked(x, correct_y))
-                            }
-                        }
-                    }
-                }
+                #from_compressed_impl
             }
         });
         output.push(result);
@@ -431,6 +468,22 @@ impl Parse for SwDefine {
     }
 }
 
+/// Emits a check which runs `body` instead of the instructions when the VM does not have the
+/// short Weierstrass extension. Without the `fallback` feature it emits nothing.
+macro_rules! fallback {
+    ($($body:tt)*) => {
+        if cfg!(feature = "fallback") {
+            quote::quote! {
+                if !::openvm_ecc_guest::has_weierstrass() {
+                    $($body)*
+                }
+            }
+        } else {
+            quote::quote! {}
+        }
+    };
+}
+
 #[proc_macro]
 pub fn sw_init(input: TokenStream) -> TokenStream {
     let SwDefine { items } = parse_macro_input!(input as SwDefine);
@@ -453,9 +506,21 @@ pub fn sw_init(input: TokenStream) -> TokenStream {
         let setup_extern_func =
             syn::Ident::new(&format!("sw_setup_extern_func_{}", str_path), span.into());
 
+        let add_ne_fallback = fallback! {
+            use super::#item;
+            return unsafe { ::openvm_ecc_guest::fallback::add_ne::<#item>(rd, rs1, rs2) };
+        };
+        let double_fallback = fallback! {
+            use super::#item;
+            return unsafe { ::openvm_ecc_guest::fallback::double::<#item>(rd, rs1) };
+        };
+        // There is nothing to set up without the extension.
+        let setup_fallback = fallback! { return; };
+
         externs.push(quote::quote_spanned! { span.into() =>
             #[no_mangle]
             extern "C" fn #add_ne_extern_func(rd: usize, rs1: usize, rs2: usize) {
+                #add_ne_fallback
                 openvm::platform::custom_insn_r!(
                     opcode = OPCODE,
                     funct3 = SW_FUNCT3 as usize,
@@ -469,6 +534,7 @@ pub fn sw_init(input: TokenStream) -> TokenStream {
 
             #[no_mangle]
             extern "C" fn #double_extern_func(rd: usize, rs1: usize) {
+                #double_fallback
                 openvm::platform::custom_insn_r!(
                     opcode = OPCODE,
                     funct3 = SW_FUNCT3 as usize,
@@ -482,6 +548,7 @@ pub fn sw_init(input: TokenStream) -> TokenStream {
 
             #[no_mangle]
             extern "C" fn #setup_extern_func() {
+                #setup_fallback
                 #[cfg(target_os = "zkvm")]
                 {
                     use super::#item;
@@ -521,12 +588,20 @@ pub fn sw_init(input: TokenStream) -> TokenStream {
         });
     }
 
+    // Programs which fall back to software run on VMs without the extension.
+    let require_extension_version = if cfg!(feature = "fallback") {
+        quote::quote! { openvm::platform::optional_extension_version!("weierstrass", 1); }
+    } else {
+        quote::quote! { openvm::platform::require_extension_version!("weierstrass", 1); }
+    };
     TokenStream::from(quote::quote_spanned! { span.into() =>
         #[allow(non_snake_case)]
         #[cfg(target_os = "zkvm")]
         mod openvm_intrinsics_ffi_2 {
             use ::openvm_ecc_guest::{OPCODE, SW_FUNCT3, SwBaseFunct7};
 
+            #require_extension_version
+
             #(#externs)*
         }
     })
@@ -11,7 +11,11 @@ use openvm_ecc_guest::{
     weierstrass::{FromCompressed, WeierstrassPoint},
     Group,
 };
-use openvm_k256::{Secp256k1Coord, Secp256k1Point};
+use openvm_ecc_test_programs::{
+    DecompressionTestVector, FP1MOD4_DECOMPRESSION_TEST_VECTORS,
+    FP5MOD8_DECOMPRESSION_TEST_VECTORS, SECP256K1_DECOMPRESSION_TEST_VECTORS,
+};
+use openvm_k256::Secp256k1Point;
 
 openvm::entry!(main);
 
@@ -22,7 +26,6 @@ openvm_algebra_moduli_macros::moduli_declare! {
     Fp1mod4 { modulus = "0xffffffffffffffffffffffffffffffff000000000000000000000001" },
 }
 
-// const CURVE_B_5MOD8: Fp5mod8 = Fp5mod8::from_const_u8(3);
 const CURVE_B_5MOD8: Fp5mod8 = Fp5mod8::from_const_u8(6);
 
 const CURVE_A_1MOD4: Fp1mod4 = Fp1mod4::from_const_bytes(hex!(
@@ -48,53 +51,44 @@ openvm::init!("openvm_init_decompress_k256.rs");
 
 // test decompression under an honest host
 pub fn main() {
+    // Points computed by the test on the host, one for each curve
     let bytes = read_vec();
-    let x = Secp256k1Coord::from_le_bytes_unchecked(&bytes[..32]);
-    let y = Secp256k1Coord::from_le_bytes_unchecked(&bytes[32..64]);
-    let rec_id = y.as_le_bytes()[0] & 1;
-
-    test_possible_decompression::<Secp256k1Point>(&x, &y, rec_id);
-    // x = 5 is not on the x-coordinate of any point on the Secp256k1 curve
-    test_impossible_decompression::<Secp256k1Point>(&Secp256k1Coord::from_u8(5), rec_id);
-
-    let x = Fp5mod8::from_le_bytes_unchecked(&bytes[64..96]);
-    let y = Fp5mod8::from_le_bytes_unchecked(&bytes[96..128]);
-    let rec_id = y.as_le_bytes()[0] & 1;
+    test_input_decompression::<Secp256k1Point>(&bytes[..64]);
+    test_input_decompression::<CurvePoint5mod8>(&bytes[64..128]);
+    test_input_decompression::<CurvePoint1mod4>(&bytes[128..192]);
 
-    test_possible_decompression::<CurvePoint5mod8>(&x, &y, rec_id);
-    // x = 0 is not on the x-coordinate of any point on the CurvePoint5mod8 curve
-    test_impossible_decompression::<CurvePoint5mod8>(&Fp5mod8::ZERO, rec_id);
-    // this x is such that y^2 = x^3 + 6 = 0
-    // we want to test the case where y^2 = 0 and rec_id = 1
-    let x = Fp5mod8::from_le_bytes_unchecked(&hex!(
-        "d634a701c3b9b8cbf7797988be3953b442863b74d2d5c4d5f1a9de3c0c256d90"
-    ));
-    test_possible_decompression::<CurvePoint5mod8>(&x, &Fp5mod8::ZERO, 0);
-    test_impossible_decompression::<CurvePoint5mod8>(&x, 1);
-
-    let x = Fp1mod4::from_le_bytes_unchecked(&bytes[128..160]);
-    let y = Fp1mod4::from_le_bytes_unchecked(&bytes[160..192]);
-    let rec_id = y.as_le_bytes()[0] & 1;
-
-    test_possible_decompression::<CurvePoint1mod4>(&x, &y, rec_id);
-    // x = 1 is not on the x-coordinate of any point on the CurvePoint1mod4 curve
-    test_impossible_decompression::<CurvePoint1mod4>(&Fp1mod4::from_u8(1), rec_id);
+    test_decompression::<Secp256k1Point>(&SECP256K1_DECOMPRESSION_TEST_VECTORS);
+    test_decompression::<CurvePoint5mod8>(&FP5MOD8_DECOMPRESSION_TEST_VECTORS);
+    test_decompression::<CurvePoint1mod4>(&FP1MOD4_DECOMPRESSION_TEST_VECTORS);
 }
 
-fn test_possible_decompression<P: WeierstrassPoint + FromCompressed<P::Coordinate>>(
-    x: &P::Coordinate,
-    y: &P::Coordinate,
-    rec_id: u8,
-) {
+fn test_input_decompression<P: WeierstrassPoint + FromCompressed<P::Coordinate>>(bytes: &[u8])
+where
+    P::Coordinate: IntMod,
+{
+    let x = P::Coordinate::from_le_bytes_unchecked(&bytes[..32]);
+    let y = P::Coordinate::from_le_bytes_unchecked(&bytes[32..64]);
+    let rec_id = y.as_le_bytes()[0] & 1;
     let p = P::decompress(x.clone(), &rec_id).unwrap();
-    assert_eq!(p.x(), x);
-    assert_eq!(p.y(), y);
+    assert_eq!(p.x(), &x);
+    assert_eq!(p.y(), &y);
 }
 
-fn test_impossible_decompression<P: WeierstrassPoint + FromCompressed<P::Coordinate>>(
-    x: &P::Coordinate,
-    rec_id: u8,
-) {
-    let p = P::decompress(x.clone(), &rec_id);
-    assert!(p.is_none());
+fn test_decompression<P: WeierstrassPoint + FromCompressed<P::Coordinate>>(
+    vectors: &[DecompressionTestVector],
+) where
+    P::Coordinate: IntMod,
+{
+    for vector in vectors {
+        let x = P::Coordinate::from_le_bytes_unchecked(&vector.x);
+        let p = P::decompress(x.clone(), &vector.rec_id);
+        match &vector.y {
+            Some(y) => {
+                let p = p.unwrap();
+                assert_eq!(p.x(), &x);
+                assert_eq!(p.y(), &P::Coordinate::from_le_bytes_unchecked(y));
+            }
+            None => assert!(p.is_none()),
+        }
+    }
 }
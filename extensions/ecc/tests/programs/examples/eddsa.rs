@@ -0,0 +1,64 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+use openvm_ecc_guest::eddsa::{
+    ed25519::{Ed25519Point, VerifyingKey},
+    Signature,
+};
+
+openvm::entry!(main);
+
+openvm::init!("openvm_init_eddsa_ed25519.rs");
+
+// Test vectors are taken from: https://www.rfc-editor.org/rfc/rfc8032#section-7.1
+pub fn main() {
+    let test_vectors: [(&[u8; 32], &[u8], &[u8; 64]); 2] = [
+        (
+            &hex!("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"),
+            b"",
+            &hex!(
+                "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb8821590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b"
+            ),
+        ),
+        (
+            &hex!("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c"),
+            &hex!("72"),
+            &hex!(
+                "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00"
+            ),
+        ),
+    ];
+
+    for (public_key, msg, signature) in test_vectors {
+        let vk = VerifyingKey::from_bytes(public_key).unwrap();
+        let signature = Signature::from_bytes(signature);
+        vk.verify(msg, &signature).unwrap();
+
+        // Wrong message
+        assert!(vk.verify(b"wrong message", &signature).is_err());
+
+        // Tampered R
+        let mut bad_sig = signature.to_bytes();
+        bad_sig[0] ^= 1;
+        assert!(vk.verify(msg, &Signature::from_bytes(&bad_sig)).is_err());
+
+        // Non-canonical S: S + L
+        let mut bad_sig = signature.to_bytes();
+        let mut carry = 0u16;
+        for (i, l) in hex!("edd3f55c1a631258d69cf7a2def9de1400000000000000000000000000000010")
+            .iter()
+            .enumerate()
+        {
+            let sum = bad_sig[32 + i] as u16 + *l as u16 + carry;
+            bad_sig[32 + i] = sum as u8;
+            carry = sum >> 8;
+        }
+        assert!(vk.verify(msg, &Signature::from_bytes(&bad_sig)).is_err());
+    }
+
+    // y = 2 does not correspond to a point on the curve
+    let mut bad_point = [0u8; 32];
+    bad_point[0] = 2;
+    assert!(Ed25519Point::from_bytes(&bad_point).is_none());
+}
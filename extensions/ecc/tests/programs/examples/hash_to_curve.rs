@@ -0,0 +1,75 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+use openvm_algebra_guest::IntMod;
+use openvm_ecc_guest::weierstrass::{ExpandMsgXmd, HashToCurve, WeierstrassPoint};
+use openvm_p256::{P256Coord, P256Point};
+use sha2::Sha256;
+
+openvm::entry!(main);
+
+openvm::init!("openvm_init_hash_to_curve_p256.rs");
+
+// Test vectors from RFC 9380, Appendix J.1.1
+const RO_DST: &[u8] = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_RO_";
+const NU_DST: &[u8] = b"QUUX-V01-CS02-with-P256_XMD:SHA-256_SSWU_NU_";
+
+fn assert_point(p: &P256Point, x: [u8; 32], y: [u8; 32]) {
+    assert_eq!(p.x(), &P256Coord::from_be_bytes_unchecked(&x));
+    assert_eq!(p.y(), &P256Coord::from_be_bytes_unchecked(&y));
+}
+
+pub fn main() {
+    let ro_vectors: [(&[u8], [u8; 32], [u8; 32]); 4] = [
+        (
+            b"",
+            hex!("2c15230b26dbc6fc9a37051158c95b79656e17a1a920b11394ca91c44247d3e4"),
+            hex!("8a7a74985cc5c776cdfe4b1f19884970453912e9d31528c060be9ab5c43e8415"),
+        ),
+        (
+            b"abc",
+            hex!("0bb8b87485551aa43ed54f009230450b492fead5f1cc91658775dac4a3388a0f"),
+            hex!("5c41b3d0731a27a7b14bc0bf0ccded2d8751f83493404c84a88e71ffd424212e"),
+        ),
+        (
+            b"abcdef0123456789",
+            hex!("65038ac8f2b1def042a5df0b33b1f4eca6bff7cb0f9c6c1526811864e544ed80"),
+            hex!("cad44d40a656e7aff4002a8de287abc8ae0482b5ae825822bb870d6df9b56ca3"),
+        ),
+        (
+            b"q128_qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq",
+            hex!("4be61ee205094282ba8a2042bcb48d88dfbb609301c49aa8b078533dc65a0b5d"),
+            hex!("98f8df449a072c4721d241a3b1236d3caccba603f916ca680f4539d2bfb3c29e"),
+        ),
+    ];
+    for (msg, x, y) in ro_vectors {
+        let p = P256Point::hash_to_curve::<ExpandMsgXmd<Sha256>>(&[msg], &[RO_DST]).unwrap();
+        assert_point(&p, x, y);
+    }
+
+    let nu_vectors: [(&[u8], [u8; 32], [u8; 32]); 2] = [
+        (
+            b"",
+            hex!("f871caad25ea3b59c16cf87c1894902f7e7b2c822c3d3f73596c5ace8ddd14d1"),
+            hex!("87b9ae23335bee057b99bac1e68588b18b5691af476234b8971bc4f011ddc99b"),
+        ),
+        (
+            b"abc",
+            hex!("fc3f5d734e8dce41ddac49f47dd2b8a57257522a865c124ed02b92b5237befa4"),
+            hex!("fe4d197ecf5a62645b9690599e1d80e82c500b22ac705a0b421fac7b47157866"),
+        ),
+    ];
+    for (msg, x, y) in nu_vectors {
+        let p = P256Point::encode_to_curve::<ExpandMsgXmd<Sha256>>(&[msg], &[NU_DST]).unwrap();
+        assert_point(&p, x, y);
+    }
+
+    // The message and tag are split across slices
+    let p = P256Point::hash_to_curve::<ExpandMsgXmd<Sha256>>(
+        &[b"a", b"bc"],
+        &[&RO_DST[..10], &RO_DST[10..]],
+    )
+    .unwrap();
+    assert_point(&p, ro_vectors[1].1, ro_vectors[1].2);
+}
@@ -4,6 +4,7 @@ extern crate alloc;
 
 use alloc::vec::Vec;
 
+use hex_literal::hex;
 use serde::{Deserialize, Serialize};
 use serde_with::{serde_as, Bytes};
 
@@ -30,3 +31,101 @@ pub struct Sec1DecodingTestVector {
     pub bytes: Vec<u8>,
     pub ok: bool,
 }
+
+/// Point decompression test vector, with coordinates in little-endian
+pub struct DecompressionTestVector {
+    pub x: [u8; 32],
+    pub rec_id: u8,
+    /// The decompressed `y`-coordinate, or `None` if decompression must fail
+    pub y: Option<[u8; 32]>,
+}
+
+/// Decompression test vectors for secp256k1
+pub const SECP256K1_DECOMPRESSION_TEST_VECTORS: [DecompressionTestVector; 4] = [
+    // 3 * G
+    DecompressionTestVector {
+        x: hex!("f936e0bc13f10186b0996f8345c831b529529df8854f344910c35892018a30f9"),
+        rec_id: 0,
+        y: Some(hex!(
+            "72e6b88475fdb96c1b23c23499a9006556f3372ae637e30f14e82d630f7b8f38"
+        )),
+    },
+    // -3 * G
+    DecompressionTestVector {
+        x: hex!("f936e0bc13f10186b0996f8345c831b529529df8854f344910c35892018a30f9"),
+        rec_id: 1,
+        y: Some(hex!(
+            "bd15477b89024693e4dc3dcb6656ff9aa90cc8d519c81cf0eb17d29cf08470c7"
+        )),
+    },
+    // x = 5 is not the x-coordinate of any point on the curve
+    DecompressionTestVector {
+        x: hex!("0500000000000000000000000000000000000000000000000000000000000000"),
+        rec_id: 0,
+        y: None,
+    },
+    DecompressionTestVector {
+        x: hex!("0500000000000000000000000000000000000000000000000000000000000000"),
+        rec_id: 1,
+        y: None,
+    },
+];
+
+/// Decompression test vectors for `y^2 = x^3 + 6` over a prime that is 5 mod 8
+pub const FP5MOD8_DECOMPRESSION_TEST_VECTORS: [DecompressionTestVector; 5] = [
+    DecompressionTestVector {
+        x: hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+        rec_id: 0,
+        y: Some(hex!(
+            "da203a2e7d3a58b7d8cabe8b094a22bdcfc29a50aabd72faf171e7a90b5c102b"
+        )),
+    },
+    DecompressionTestVector {
+        x: hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+        rec_id: 1,
+        y: Some(hex!(
+            "73ddc5d182c5a74827354174f6b5dd42303d65af55428d050e8e1856f4a3efd4"
+        )),
+    },
+    // x = 0 is not the x-coordinate of any point on the curve
+    DecompressionTestVector {
+        x: hex!("0000000000000000000000000000000000000000000000000000000000000000"),
+        rec_id: 0,
+        y: None,
+    },
+    // y^2 = x^3 + 6 = 0, so y = 0 is the only square root and it is even
+    DecompressionTestVector {
+        x: hex!("d634a701c3b9b8cbf7797988be3953b442863b74d2d5c4d5f1a9de3c0c256d90"),
+        rec_id: 0,
+        y: Some([0; 32]),
+    },
+    DecompressionTestVector {
+        x: hex!("d634a701c3b9b8cbf7797988be3953b442863b74d2d5c4d5f1a9de3c0c256d90"),
+        rec_id: 1,
+        y: None,
+    },
+];
+
+/// Decompression test vectors for P-224, whose prime is 1 mod 4
+pub const FP1MOD4_DECOMPRESSION_TEST_VECTORS: [DecompressionTestVector; 3] = [
+    DecompressionTestVector {
+        x: hex!("211d5c11d68032342211c256d3c1034ab99013327fbfb46bbd0c0eb700000000"),
+        rec_id: 0,
+        y: Some(hex!(
+            "347e00859981d5446447075aa07543cde6df224cfb23f7b5886337bd00000000"
+        )),
+    },
+    DecompressionTestVector {
+        x: hex!("211d5c11d68032342211c256d3c1034ab99013327fbfb46bbd0c0eb700000000"),
+        rec_id: 1,
+        y: Some(hex!(
+            "cd81ff7a667e2abb9bb8f8a55e8abc321920ddb304dc084a779cc84200000000"
+        )),
+    },
+    // x = 1 is not the x-coordinate of any point on the curve
+    DecompressionTestVector {
+        x: hex!("0100000000000000000000000000000000000000000000000000000000000000"),
+        rec_id: 0,
+        y: None,
+    },
+];
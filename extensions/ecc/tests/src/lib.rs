@@ -15,6 +15,7 @@ mod tests {
     };
     use openvm_ecc_circuit::{CurveConfig, Rv32WeierstrassConfig, P256_CONFIG, SECP256K1_CONFIG};
     use openvm_ecc_transpiler::EccTranspilerExtension;
+    use openvm_rv32im_circuit::Rv32ImConfig;
     use openvm_rv32im_transpiler::{
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
     };
@@ -79,6 +80,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_hash_to_curve() -> Result<()> {
+        let config = Rv32WeierstrassConfig::new(vec![P256_CONFIG.clone()]);
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "hash_to_curve",
+            ["p256"],
+            &config,
+        )?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(EccTranspilerExtension)
+                .with_extension(ModularTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     fn test_ec_two_curves() -> Result<()> {
         let config =
@@ -186,6 +209,27 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ecdsa_fallback() -> Result<()> {
+        // The example includes the init file of `test_ecdsa`, which is checked in.
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "ecdsa",
+            ["k256", "fallback"],
+            &config,
+        )?;
+        let openvm_exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     fn test_p256_ecdsa_recover() -> Result<()> {
         let config =
@@ -240,6 +284,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_ed25519_eddsa() -> Result<()> {
+        let config = toml::from_str::<AppConfig<SdkVmConfig>>(include_str!(
+            "../programs/openvm_ed25519.toml"
+        ))?
+        .app_vm_config;
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "eddsa",
+            ["ed25519"],
+            &NoInitFile, // using already created file
+        )?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
     #[test]
     #[should_panic]
     fn test_invalid_setup() {
@@ -10,11 +10,13 @@ use openvm_circuit_derive::{AnyEnum, InstructionExecutor, VmConfig};
 use openvm_circuit_primitives::bitwise_op_lookup::BitwiseOperationLookupBus;
 use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
 use openvm_instructions::*;
+use openvm_keccak256_transpiler::Rv32KeccakfOpcode;
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
 use openvm_rv32im_circuit::{
     Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
     Rv32MExecutor, Rv32MPeriphery,
 };
-use openvm_stark_backend::p3_field::PrimeField32;
+use openvm_stark_backend::{interaction::LookupBus, p3_field::PrimeField32};
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
 
@@ -55,11 +57,13 @@ pub struct Keccak256;
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
 pub enum Keccak256Executor<F: PrimeField32> {
     Keccak256(KeccakVmChip<F>),
+    Keccakf(Rv32KeccakfChip<F>),
 }
 
 #[derive(From, ChipUsageGetter, Chip, AnyEnum)]
 pub enum Keccak256Periphery<F: PrimeField32> {
     BitwiseOperationLookup(SharedBitwiseOperationLookupChip<8>),
+    Keccakf(SharedKeccakfPermuteChip),
     Phantom(PhantomChip<F>),
 }
 
@@ -67,6 +71,10 @@ impl<F: PrimeField32> VmExtension<F> for Keccak256 {
     type Executor = Keccak256Executor<F>;
     type Periphery = Keccak256Periphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("keccak256", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -96,15 +104,35 @@ impl<F: PrimeField32> VmExtension<F> for Keccak256 {
             program_bus,
             memory_bridge,
             address_bits,
-            bitwise_lu_chip,
+            bitwise_lu_chip.clone(),
             Rv32KeccakOpcode::CLASS_OFFSET,
-            offline_memory,
+            offline_memory.clone(),
         );
         inventory.add_executor(
             keccak_chip,
             Rv32KeccakOpcode::iter().map(|x| x.global_opcode()),
         )?;
 
+        let permute_chip = SharedKeccakfPermuteChip::new(LookupBus::new(builder.new_bus_idx()));
+        inventory.add_periphery_chip(permute_chip.clone());
+
+        let keccakf_chip = Rv32KeccakfChip::new(
+            Rv32VecHeapAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            ),
+            KeccakfCoreChip::new(
+                permute_chip,
+                bitwise_lu_chip,
+                Rv32KeccakfOpcode::CLASS_OFFSET,
+            ),
+            offline_memory,
+        );
+        inventory.add_executor(keccakf_chip, [Rv32KeccakfOpcode::KECCAKF.global_opcode()])?;
+
         Ok(inventory)
     }
 }
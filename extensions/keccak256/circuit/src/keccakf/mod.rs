@@ -0,0 +1,26 @@
+//! The keccak-f permutation, exposed to RISC-V guests on the 25 lanes of a sponge state in memory
+//! so that guests can run the sponge themselves. Permutations are proven one round per row by a
+//! chip shared by the instructions, which look them up on a bus.
+use openvm_circuit::arch::VmChipWrapper;
+use openvm_instructions::riscv::RV32_REGISTER_NUM_LIMBS;
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+
+use crate::KECCAK_WIDTH_BYTES;
+
+mod periphery;
+mod permute;
+pub use periphery::*;
+pub use permute::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Number of 4-byte words in the sponge state.
+pub const KECCAK_WIDTH_WORDS: usize = KECCAK_WIDTH_BYTES / RV32_REGISTER_NUM_LIMBS;
+
+/// Reads the state, 50 words, from `rs1` and writes its permutation, 50 words, to `rd`.
+pub type Rv32KeccakfChip<F> = VmChipWrapper<
+    F,
+    Rv32VecHeapAdapterChip<F, 1, KECCAK_WIDTH_WORDS, KECCAK_WIDTH_WORDS, 4, 4>,
+    KeccakfCoreChip,
+>;
@@ -0,0 +1,201 @@
+use std::{
+    borrow::{Borrow, BorrowMut},
+    sync::{Arc, Mutex},
+};
+
+use openvm_circuit_primitives::{utils::not, AlignedBorrow};
+use openvm_stark_backend::{
+    air_builders::sub::SubAirBuilder,
+    config::{StarkGenericConfig, Val},
+    interaction::{InteractionBuilder, LookupBus},
+    p3_air::{Air, BaseAir},
+    p3_field::{Field, PrimeField32},
+    p3_matrix::{dense::RowMajorMatrix, Matrix},
+    p3_maybe_rayon::prelude::*,
+    prover::types::AirProofInput,
+    rap::{get_air_name, BaseAirWithPublicValues, PartitionedBaseAir},
+    AirRef, Chip, ChipUsageGetter,
+};
+use p3_keccak_air::{
+    generate_trace_rows, KeccakAir, KeccakCols as KeccakPermCols,
+    NUM_KECCAK_COLS as NUM_KECCAK_PERM_COLS, NUM_ROUNDS, U64_LIMBS,
+};
+
+use crate::utils::keccak_f;
+
+// Each permutation takes `NUM_ROUNDS` rows of the keccak-f AIR, whose columns come first. The
+// preimage is constant across the rows of a permutation, so the permutation is added to the bus on
+// its last round, where the postimage is available.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct KeccakfPermuteCols<T> {
+    pub inner: KeccakPermCols<T>,
+    /// The number of lookups of the permutation, only nonzero on the last round.
+    pub mult: T,
+}
+
+#[derive(Clone, Copy, Debug, derive_new::new)]
+pub struct KeccakfPermuteAir {
+    pub bus: LookupBus,
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for KeccakfPermuteAir {}
+impl<F: Field> PartitionedBaseAir<F> for KeccakfPermuteAir {}
+impl<F: Field> BaseAir<F> for KeccakfPermuteAir {
+    fn width(&self) -> usize {
+        KeccakfPermuteCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder> Air<AB> for KeccakfPermuteAir {
+    fn eval(&self, builder: &mut AB) {
+        let mut sub_builder =
+            SubAirBuilder::<AB, KeccakAir, AB::Var>::new(builder, 0..NUM_KECCAK_PERM_COLS);
+        KeccakAir {}.eval(&mut sub_builder);
+
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &KeccakfPermuteCols<AB::Var> = (*local).borrow();
+        let is_last_round = *local.inner.step_flags.last().unwrap();
+        builder.assert_zero(local.mult * not::<AB::Expr>(is_last_round));
+
+        // The lanes are in the order of the state in memory, lane `x + 5y` at index `x + 5y`, as
+        // 16-bit limbs.
+        let preimage = (0..25)
+            .flat_map(|i| (0..U64_LIMBS).map(move |limb| local.inner.preimage[i / 5][i % 5][limb]));
+        let postimage = (0..25).flat_map(|i| {
+            (0..U64_LIMBS).map(move |limb| local.inner.a_prime_prime_prime(i / 5, i % 5, limb))
+        });
+        self.bus
+            .add_key_with_lookups(builder, preimage.chain(postimage), local.mult);
+    }
+}
+
+/// Proves the keccak-f permutations looked up on its bus by the keccak-f instructions.
+#[derive(Debug)]
+pub struct KeccakfPermuteChip {
+    pub air: KeccakfPermuteAir,
+    records: Mutex<Vec<[u64; 25]>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SharedKeccakfPermuteChip(Arc<KeccakfPermuteChip>);
+
+impl KeccakfPermuteChip {
+    pub fn new(bus: LookupBus) -> Self {
+        Self {
+            air: KeccakfPermuteAir::new(bus),
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn bus(&self) -> LookupBus {
+        self.air.bus
+    }
+
+    /// Returns the permutation of `state`, and records the permutation to be proven.
+    pub fn permute_and_record(&self, state: [u64; 25]) -> [u64; 25] {
+        self.records.lock().unwrap().push(state);
+        keccak_f(state)
+    }
+
+    /// Generates the trace of the permutations recorded so far, and clears them.
+    pub fn generate_trace<F: PrimeField32>(&self) -> RowMajorMatrix<F> {
+        let records = std::mem::take(&mut *self.records.lock().unwrap());
+        let width = KeccakfPermuteCols::<F>::width();
+        if records.is_empty() {
+            return RowMajorMatrix::new(Vec::new(), width);
+        }
+        let num_permutations = records.len();
+        // Pads with permutations of the zero state, which are not looked up.
+        let p3_keccak_trace: RowMajorMatrix<F> = generate_trace_rows(records, 0);
+        let height = p3_keccak_trace.height();
+        let mut values = F::zero_vec(height * width);
+        values
+            .par_chunks_mut(width)
+            .zip(p3_keccak_trace.values.par_chunks(NUM_KECCAK_PERM_COLS))
+            .enumerate()
+            .for_each(|(i, (row, p3_row))| {
+                row[..NUM_KECCAK_PERM_COLS].copy_from_slice(p3_row);
+                let cols: &mut KeccakfPermuteCols<F> = row.borrow_mut();
+                if i % NUM_ROUNDS == NUM_ROUNDS - 1 && i / NUM_ROUNDS < num_permutations {
+                    cols.mult = F::ONE;
+                }
+            });
+        RowMajorMatrix::new(values, width)
+    }
+}
+
+impl SharedKeccakfPermuteChip {
+    pub fn new(bus: LookupBus) -> Self {
+        Self(Arc::new(KeccakfPermuteChip::new(bus)))
+    }
+
+    pub fn bus(&self) -> LookupBus {
+        self.0.bus()
+    }
+
+    pub fn permute_and_record(&self, state: [u64; 25]) -> [u64; 25] {
+        self.0.permute_and_record(state)
+    }
+}
+
+impl<SC: StarkGenericConfig> Chip<SC> for KeccakfPermuteChip
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        Arc::new(self.air)
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        AirProofInput::simple_no_pis(self.generate_trace::<Val<SC>>())
+    }
+}
+
+impl<SC: StarkGenericConfig> Chip<SC> for SharedKeccakfPermuteChip
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        self.0.air()
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        AirProofInput::simple_no_pis(self.0.generate_trace::<Val<SC>>())
+    }
+}
+
+impl ChipUsageGetter for KeccakfPermuteChip {
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.records.lock().unwrap().len() * NUM_ROUNDS
+    }
+
+    fn trace_width(&self) -> usize {
+        KeccakfPermuteCols::<u8>::width()
+    }
+}
+
+impl ChipUsageGetter for SharedKeccakfPermuteChip {
+    fn air_name(&self) -> String {
+        self.0.air_name()
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.0.current_trace_height()
+    }
+
+    fn trace_width(&self) -> usize {
+        self.0.trace_width()
+    }
+}
+
+impl AsRef<KeccakfPermuteChip> for SharedKeccakfPermuteChip {
+    fn as_ref(&self) -> &KeccakfPermuteChip {
+        &self.0
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    array::from_fn,
+    borrow::{Borrow, BorrowMut},
+};
+
+use openvm_circuit::arch::{
+    AdapterAirContext, AdapterRuntimeContext, DynAdapterInterface, DynArray, MinimalInstruction,
+    Result, VmAdapterInterface, VmCoreAir, VmCoreChip,
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::{BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip},
+    utils::compose,
+    AlignedBorrow,
+};
+use openvm_instructions::{
+    instruction::Instruction,
+    riscv::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS},
+    LocalOpcode,
+};
+use openvm_keccak256_transpiler::Rv32KeccakfOpcode;
+use openvm_stark_backend::{
+    interaction::{InteractionBuilder, LookupBus},
+    p3_air::BaseAir,
+    p3_field::{Field, FieldAlgebra, PrimeField32},
+    rap::BaseAirWithPublicValues,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_big_array::BigArray;
+
+use super::{SharedKeccakfPermuteChip, KECCAK_WIDTH_WORDS};
+
+// The lanes are read and written as pairs of 4 byte little-endian words, and range checked as
+// bytes. The permutation itself is proven by the permutation chip, which it is looked up from.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct KeccakfCoreCols<T> {
+    pub is_valid: T,
+    pub input: [[T; RV32_REGISTER_NUM_LIMBS]; KECCAK_WIDTH_WORDS],
+    pub output: [[T; RV32_REGISTER_NUM_LIMBS]; KECCAK_WIDTH_WORDS],
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct KeccakfCoreAir {
+    pub bitwise_bus: BitwiseOperationLookupBus,
+    pub permute_bus: LookupBus,
+    pub offset: usize,
+}
+
+impl<F: Field> BaseAir<F> for KeccakfCoreAir {
+    fn width(&self) -> usize {
+        KeccakfCoreCols::<F>::width()
+    }
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for KeccakfCoreAir {}
+
+impl<AB: InteractionBuilder, I> VmCoreAir<AB, I> for KeccakfCoreAir
+where
+    I: VmAdapterInterface<AB::Expr>,
+    AdapterAirContext<AB::Expr, I>:
+        From<AdapterAirContext<AB::Expr, DynAdapterInterface<AB::Expr>>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &KeccakfCoreCols<AB::Var> = local_core.borrow();
+        let is_valid = cols.is_valid;
+        builder.assert_bool(is_valid);
+
+        let bytes = [cols.input.concat(), cols.output.concat()].concat();
+        for pair in bytes.chunks_exact(2) {
+            self.bitwise_bus
+                .send_range(pair[0], pair[1])
+                .eval(builder, is_valid);
+        }
+        let limbs = bytes
+            .chunks_exact(2)
+            .map(|pair| compose::<AB::Expr>(pair, RV32_CELL_BITS));
+        self.permute_bus.lookup_key(builder, limbs, is_valid);
+
+        let reads: Vec<AB::Expr> = cols.input.concat().into_iter().map(Into::into).collect();
+        let writes: Vec<AB::Expr> = cols.output.concat().into_iter().map(Into::into).collect();
+        let instruction = MinimalInstruction {
+            is_valid: is_valid.into(),
+            opcode: AB::Expr::from_canonical_usize(
+                self.offset + Rv32KeccakfOpcode::KECCAKF.local_usize(),
+            ),
+        };
+        let ctx: AdapterAirContext<_, DynAdapterInterface<_>> = AdapterAirContext {
+            to_pc: None,
+            reads: reads.into(),
+            writes: writes.into(),
+            instruction: instruction.into(),
+        };
+        ctx.into()
+    }
+
+    fn start_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[serde(bound = "T: Serialize + DeserializeOwned")]
+pub struct KeccakfCoreRecord<T> {
+    #[serde(with = "BigArray")]
+    pub input: [[T; RV32_REGISTER_NUM_LIMBS]; KECCAK_WIDTH_WORDS],
+    #[serde(with = "BigArray")]
+    pub output: [[T; RV32_REGISTER_NUM_LIMBS]; KECCAK_WIDTH_WORDS],
+}
+
+pub struct KeccakfCoreChip {
+    pub air: KeccakfCoreAir,
+    pub permute_chip: SharedKeccakfPermuteChip,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl KeccakfCoreChip {
+    pub fn new(
+        permute_chip: SharedKeccakfPermuteChip,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        offset: usize,
+    ) -> Self {
+        Self {
+            air: KeccakfCoreAir {
+                bitwise_bus: bitwise_lookup_chip.bus(),
+                permute_bus: permute_chip.bus(),
+                offset,
+            },
+            permute_chip,
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+/// The lanes of the state, each of which is two consecutive words.
+fn to_lanes<F: PrimeField32>(
+    words: &[[F; RV32_REGISTER_NUM_LIMBS]; KECCAK_WIDTH_WORDS],
+) -> [u64; 25] {
+    from_fn(|i| {
+        let bytes: [u8; 8] = from_fn(|j| {
+            words[2 * i + j / RV32_REGISTER_NUM_LIMBS][j % RV32_REGISTER_NUM_LIMBS]
+                .as_canonical_u32() as u8
+        });
+        u64::from_le_bytes(bytes)
+    })
+}
+
+impl<F: PrimeField32, I> VmCoreChip<F, I> for KeccakfCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<DynArray<F>>,
+    AdapterRuntimeContext<F, I>: From<AdapterRuntimeContext<F, DynAdapterInterface<F>>>,
+{
+    type Record = KeccakfCoreRecord<F>;
+    type Air = KeccakfCoreAir;
+
+    fn execute_instruction(
+        &self,
+        _instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> Result<(AdapterRuntimeContext<F, I>, Self::Record)> {
+        let data: DynArray<_> = reads.into();
+        assert_eq!(data.0.len(), KECCAK_WIDTH_WORDS * RV32_REGISTER_NUM_LIMBS);
+        let input: [[F; RV32_REGISTER_NUM_LIMBS]; KECCAK_WIDTH_WORDS] =
+            from_fn(|i| from_fn(|j| data.0[i * RV32_REGISTER_NUM_LIMBS + j]));
+
+        let output = self.permute_chip.permute_and_record(to_lanes(&input));
+        let output_bytes: Vec<F> = output
+            .iter()
+            .flat_map(|lane| lane.to_le_bytes())
+            .map(F::from_canonical_u8)
+            .collect();
+        let record = KeccakfCoreRecord {
+            input,
+            output: from_fn(|i| from_fn(|j| output_bytes[i * RV32_REGISTER_NUM_LIMBS + j])),
+        };
+        let bytes = [record.input.concat(), record.output.concat()].concat();
+        for pair in bytes.chunks_exact(2) {
+            self.bitwise_lookup_chip
+                .request_range(pair[0].as_canonical_u32(), pair[1].as_canonical_u32());
+        }
+
+        let ctx =
+            AdapterRuntimeContext::<_, DynAdapterInterface<_>>::without_pc(record.output.concat());
+        Ok((ctx.into(), record))
+    }
+
+    fn get_opcode_name(&self, _opcode: usize) -> String {
+        format!("{:?}", Rv32KeccakfOpcode::KECCAKF)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let cols: &mut KeccakfCoreCols<F> = row_slice.borrow_mut();
+        cols.is_valid = F::ONE;
+        cols.input = record.input;
+        cols.output = record.output;
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
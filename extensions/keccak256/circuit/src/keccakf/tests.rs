@@ -0,0 +1,132 @@
+use std::{array::from_fn, borrow::BorrowMut};
+
+use openvm_circuit::arch::testing::{VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_instructions::{riscv::RV32_CELL_BITS, LocalOpcode};
+use openvm_keccak256_transpiler::Rv32KeccakfOpcode;
+use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapAdapterChip};
+use openvm_stark_backend::{
+    interaction::{BusIndex, LookupBus},
+    p3_field::{FieldAlgebra, PrimeField32},
+    p3_matrix::Matrix,
+    utils::disable_debug_builder,
+    verifier::VerificationError,
+};
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, utils::create_seeded_rng};
+use rand::Rng;
+
+use super::{
+    KeccakfCoreChip, KeccakfCoreCols, Rv32KeccakfChip, SharedKeccakfPermuteChip, KECCAK_WIDTH_WORDS,
+};
+use crate::utils::keccak_f;
+
+type F = BabyBear;
+
+const KECCAKF_PERMUTE_BUS: BusIndex = 13;
+
+fn build_keccakf_test(
+    inputs: Vec<[u64; 25]>,
+) -> (
+    VmChipTestBuilder<F>,
+    Rv32KeccakfChip<F>,
+    SharedKeccakfPermuteChip,
+    SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+) {
+    let mut tester = VmChipTestBuilder::default();
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let permute_chip = SharedKeccakfPermuteChip::new(LookupBus::new(KECCAKF_PERMUTE_BUS));
+    let mut chip = Rv32KeccakfChip::new(
+        Rv32VecHeapAdapterChip::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        ),
+        KeccakfCoreChip::new(
+            permute_chip.clone(),
+            bitwise_chip.clone(),
+            Rv32KeccakfOpcode::CLASS_OFFSET,
+        ),
+        tester.offline_memory_mutex_arc(),
+    );
+
+    for input in inputs {
+        let instruction = rv32_write_heap_default(
+            &mut tester,
+            input
+                .iter()
+                .flat_map(|lane| lane.to_le_bytes())
+                .collect::<Vec<_>>()
+                .chunks_exact(4)
+                .map(|word| from_fn(|i| F::from_canonical_u8(word[i])))
+                .collect(),
+            vec![],
+            Rv32KeccakfOpcode::KECCAKF.global_opcode().as_usize(),
+        );
+        tester.execute(&mut chip, &instruction);
+
+        let rd_ptr = instruction.a.as_canonical_u32() as usize;
+        let out_ptr = u32::from_le_bytes(
+            tester
+                .read::<4>(1, rd_ptr)
+                .map(|x| x.as_canonical_u32() as u8),
+        ) as usize;
+        let words: [u32; KECCAK_WIDTH_WORDS] = from_fn(|i| {
+            u32::from_le_bytes(
+                tester
+                    .read::<4>(2, out_ptr + 4 * i)
+                    .map(|x| x.as_canonical_u32() as u8),
+            )
+        });
+        let output: [u64; 25] = from_fn(|i| words[2 * i] as u64 | (words[2 * i + 1] as u64) << 32);
+        assert_eq!(output, keccak_f(input));
+    }
+    (tester, chip, permute_chip, bitwise_chip)
+}
+
+#[test]
+fn test_keccakf() {
+    let mut rng = create_seeded_rng();
+    let mut inputs: Vec<[u64; 25]> = (0..5).map(|_| from_fn(|_| rng.gen())).collect();
+    inputs.push([0; 25]);
+    inputs.push([u64::MAX; 25]);
+
+    let (tester, chip, permute_chip, bitwise_chip) = build_keccakf_test(inputs);
+    let tester = tester
+        .build()
+        .load(chip)
+        .load(permute_chip)
+        .load(bitwise_chip)
+        .finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+// An output that is not the permutation of the input has no matching permutation on the bus.
+#[test]
+fn test_keccakf_wrong_output() {
+    let (tester, chip, permute_chip, bitwise_chip) = build_keccakf_test(vec![[1; 25]]);
+    let mut tester = tester
+        .build()
+        .load(chip)
+        .load(permute_chip)
+        .load(bitwise_chip)
+        .finalize();
+
+    let trace = tester.air_proof_inputs[2]
+        .1
+        .raw
+        .common_main
+        .as_mut()
+        .unwrap();
+    let core_start = trace.width() - KeccakfCoreCols::<F>::width();
+    let row = trace.row_mut(0);
+    let cols: &mut KeccakfCoreCols<F> = row[core_start..].borrow_mut();
+    cols.output[0][0] = F::from_canonical_u32((cols.output[0][0].as_canonical_u32() + 1) % 256);
+
+    disable_debug_builder();
+    tester.simple_test_with_expected_error(VerificationError::ChallengePhaseError);
+}
@@ -21,6 +21,9 @@ pub mod utils;
 mod extension;
 pub use extension::*;
 
+mod keccakf;
+pub use keccakf::*;
+
 #[cfg(test)]
 mod tests;
 
@@ -4,6 +4,29 @@
 pub const OPCODE: u8 = 0x0b;
 pub const KECCAK256_FUNCT3: u8 = 0b100;
 pub const KECCAK256_FUNCT7: u8 = 0;
+/// Permutes the 25 lanes at `rs1` with keccak-f and writes the result to `rd`.
+pub const KECCAKF_FUNCT7: u8 = 0x9;
+
+#[cfg(not(feature = "fallback"))]
+openvm_platform::require_extension_version!("keccak256", 1);
+#[cfg(feature = "fallback")]
+openvm_platform::optional_extension_version!("keccak256", 1);
+
+/// The number of 64-bit lanes of the keccak-f state.
+pub const KECCAK_WIDTH_U64S: usize = 25;
+
+/// Returns whether the VM has the keccak256 extension. Without it, [native_keccak256] and
+/// [keccakf] fall back to `tiny-keccak`. Without the `fallback` feature, the guest program
+/// requires the extension, so this is always true in the zkVM.
+#[inline(always)]
+pub fn has_keccak256() -> bool {
+    cfg!(all(target_os = "zkvm", not(feature = "fallback")))
+        || openvm_platform::has_insn!(openvm_platform::capabilities::insn_r(
+            OPCODE,
+            KECCAK256_FUNCT3,
+            KECCAK256_FUNCT7
+        ))
+}
 
 /// Native hook for keccak256 for use with `alloy-primitives` "native-keccak" feature.
 ///
@@ -21,6 +44,14 @@ pub const KECCAK256_FUNCT7: u8 = 0;
 #[inline(always)]
 #[no_mangle]
 pub extern "C" fn native_keccak256(bytes: *const u8, len: usize, output: *mut u8) {
+    if !has_keccak256() {
+        use tiny_keccak::Hasher;
+
+        let mut hasher = tiny_keccak::Keccak::v256();
+        hasher.update(unsafe { core::slice::from_raw_parts(bytes, len) });
+        hasher.finalize(unsafe { &mut *(output as *mut [u8; 32]) });
+        return;
+    }
     openvm_platform::custom_insn_r!(
         opcode = OPCODE,
         funct3 = KECCAK256_FUNCT3,
@@ -30,3 +61,26 @@ pub extern "C" fn native_keccak256(bytes: *const u8, len: usize, output: *mut u8
         rs2 = In len
     );
 }
+
+/// Applies the keccak-f permutation to `state` in place. Lane `x + 5y` of the state is
+/// `state[x + 5 * y]`, stored as a little-endian `u64`.
+///
+/// In the zkVM this is a single instruction. Outside the zkVM, or if the VM does not have the
+/// keccak256 extension, it falls back to `tiny-keccak`.
+#[inline(always)]
+pub fn keccakf(state: &mut [u64; KECCAK_WIDTH_U64S]) {
+    #[cfg(target_os = "zkvm")]
+    if has_keccak256() {
+        let ptr = state.as_mut_ptr();
+        openvm_platform::custom_insn_r!(
+            opcode = OPCODE,
+            funct3 = KECCAK256_FUNCT3,
+            funct7 = KECCAKF_FUNCT7,
+            rd = In ptr,
+            rs1 = In ptr,
+            rs2 = Const "x0"
+        );
+        return;
+    }
+    tiny_keccak::keccakf(state);
+}
@@ -1,10 +1,12 @@
 use openvm_instructions::LocalOpcode;
 use openvm_instructions_derive::LocalOpcode;
-use openvm_keccak256_guest::{KECCAK256_FUNCT3, KECCAK256_FUNCT7, OPCODE};
+use openvm_keccak256_guest::{KECCAK256_FUNCT3, KECCAK256_FUNCT7, KECCAKF_FUNCT7, OPCODE};
 use openvm_stark_backend::p3_field::PrimeField32;
-use openvm_transpiler::{util::from_r_type, TranspilerExtension, TranspilerOutput};
+use openvm_transpiler::{
+    metadata::OpcodeMap, util::from_r_type, TranspilerExtension, TranspilerOutput,
+};
 use rrs_lib::instruction_formats::RType;
-use strum::{EnumCount, EnumIter, FromRepr};
+use strum::{EnumCount, EnumIter, FromRepr, IntoEnumIterator};
 
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
@@ -15,6 +17,15 @@ pub enum Rv32KeccakOpcode {
     KECCAK256,
 }
 
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
+)]
+#[opcode_offset = 0x340]
+#[repr(usize)]
+pub enum Rv32KeccakfOpcode {
+    KECCAKF,
+}
+
 #[derive(Default)]
 pub struct Keccak256TranspilerExtension;
 
@@ -31,15 +42,18 @@ impl<F: PrimeField32> TranspilerExtension<F> for Keccak256TranspilerExtension {
             return None;
         }
         let dec_insn = RType::new(instruction_u32);
-        if dec_insn.funct7 != KECCAK256_FUNCT7 as u32 {
-            return None;
-        }
-        let instruction = from_r_type(
-            Rv32KeccakOpcode::KECCAK256.global_opcode().as_usize(),
-            2,
-            &dec_insn,
-            true,
-        );
+        let global_opcode = match dec_insn.funct7 as u8 {
+            KECCAK256_FUNCT7 => Rv32KeccakOpcode::KECCAK256.global_opcode(),
+            KECCAKF_FUNCT7 => Rv32KeccakfOpcode::KECCAKF.global_opcode(),
+            _ => return None,
+        };
+        let instruction = from_r_type(global_opcode.as_usize(), 2, &dec_insn, true);
         Some(TranspilerOutput::one_to_one(instruction))
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default()
+            .with_opcodes(Rv32KeccakOpcode::iter())
+            .with_opcodes(Rv32KeccakfOpcode::iter())
+    }
 }
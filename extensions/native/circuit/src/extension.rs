@@ -87,6 +87,10 @@ impl<F: PrimeField32> VmExtension<F> for Native {
     type Executor = NativeExecutor<F>;
     type Periphery = NativePeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("native", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -302,7 +306,7 @@ pub(crate) mod phantom {
         fn phantom_execute(
             &mut self,
             memory: &MemoryController<F>,
-            _: &mut Streams<F>,
+            streams: &mut Streams<F>,
             _: PhantomDiscriminant,
             a: F,
             _: F,
@@ -310,9 +314,13 @@ pub(crate) mod phantom {
         ) -> eyre::Result<()> {
             let addr_space = F::from_canonical_u16(c_upper);
             let value = memory.unsafe_read_cell(addr_space, a);
-            println!("{}", value);
+            streams.print(&format!("{value}\n"));
             Ok(())
         }
+
+        fn provides_hints(&self) -> bool {
+            false
+        }
     }
 
     impl<F: PrimeField32> PhantomSubExecutor<F> for NativeHintBitsSubEx {
@@ -384,6 +392,10 @@ impl<F: PrimeField32> VmExtension<F> for CastFExtension {
     type Executor = CastFExtensionExecutor<F>;
     type Periphery = CastFExtensionPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("castf", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -3,12 +3,12 @@ extern crate alloc;
 extern crate proc_macro;
 
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use syn::{
     parse::{Parse, ParseStream},
-    parse_macro_input,
+    parse_macro_input, parse_quote,
     punctuated::Punctuated,
-    Data, DeriveInput, Expr, Fields, GenericParam, Generics, Token, TypeParamBound,
+    Data, DeriveInput, Expr, Fields, GenericParam, Generics, Ident, Token, Type, TypeParamBound,
 };
 
 /// Returns true if the generic parameter C: Config exists.
@@ -159,6 +159,339 @@ pub fn derive_variable(input: TokenStream) -> TokenStream {
     gen.into()
 }
 
+/// Derives `Hintable<C>` for every `C: Config` on a struct or an enum, all of whose fields are
+/// `Hintable<C>`, together with a new struct `{Name}Variable<C>` which is its `HintVariable`. The
+/// `Hintable` trait must be in scope.
+///
+/// The fields of a struct are hinted in order, and the variable has a field of the same name for
+/// each of them, or `_0`, `_1`, ... for a tuple struct. An enum is hinted as the index of its
+/// variant followed by the fields of the variant. Its variable has a `tag` field holding the index,
+/// which is asserted to be less than the number of variants, and a field `{variant}_{field}` for
+/// each field of each variant, in snake case, of which only the fields of the hinted variant are
+/// assigned. Type parameters are supported, and get a
+/// `Hintable<C>` bound through the fields which use them.
+#[proc_macro_derive(Hintable)]
+pub fn derive_hintable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    create_new_struct_and_impl_hintable(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A field hinted by a `Hintable` derive.
+struct HintField {
+    /// The name of the field in the variable struct, also used to bind the field when writing.
+    name: Ident,
+    /// The member of the field in the struct or enum variant.
+    member: syn::Member,
+    ty: Type,
+}
+
+impl HintField {
+    fn from_fields(fields: &Fields, prefix: Option<&str>) -> Vec<Self> {
+        fields
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let (member, name) = match &field.ident {
+                    Some(ident) => (syn::Member::Named(ident.clone()), ident.to_string()),
+                    None => (syn::Member::Unnamed(i.into()), format!("_{i}")),
+                };
+                let name = match prefix {
+                    Some(prefix) => format_ident!("{}{}", prefix, name.trim_start_matches('_')),
+                    None => format_ident!("{}", name),
+                };
+                HintField {
+                    name,
+                    member,
+                    ty: field.ty.clone(),
+                }
+            })
+            .collect()
+    }
+
+    fn variable_type(&self) -> proc_macro2::TokenStream {
+        let ty = &self.ty;
+        quote! { <#ty as Hintable<C>>::HintVariable }
+    }
+}
+
+/// Returns the pattern `path { member: name, .. }` binding each field to its name.
+fn fields_pattern(
+    path: proc_macro2::TokenStream,
+    fields: &[HintField],
+) -> proc_macro2::TokenStream {
+    let members = fields.iter().map(|f| &f.member);
+    let names = fields.iter().map(|f| &f.name);
+    quote! { #path { #(#members: #names,)* .. } }
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
+
+fn create_new_struct_and_impl_hintable(
+    input: DeriveInput,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let DeriveInput {
+        vis,
+        ident: name,
+        generics,
+        data,
+        ..
+    } = input;
+    let var_name = format_ident!("{}Variable", name);
+    for param in &generics.params {
+        match param {
+            GenericParam::Lifetime(lifetime) => {
+                return Err(syn::Error::new_spanned(
+                    lifetime,
+                    "Hintable cannot be derived for types with lifetime parameters",
+                ));
+            }
+            GenericParam::Type(ty) if ty.ident == "C" => {
+                return Err(syn::Error::new_spanned(
+                    ty,
+                    "Hintable derive uses the name `C` for the config, rename this parameter",
+                ));
+            }
+            GenericParam::Type(_) | GenericParam::Const(_) => {}
+        }
+    }
+
+    // Without fields, the variable struct would not use the config.
+    let (marker_field, marker_init) = if matches!(&data, Data::Struct(data) if data.fields.is_empty())
+    {
+        (
+            quote! { _marker: ::core::marker::PhantomData<C>, },
+            quote! { _marker: ::core::marker::PhantomData, },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
+
+    let (var_fields, hinted_fields, read_body, write_body) = match &data {
+        Data::Struct(data) => {
+            let fields = HintField::from_fields(&data.fields, None);
+            let var_fields: Vec<_> = fields
+                .iter()
+                .map(|f| (f.name.clone(), f.variable_type()))
+                .collect();
+            let names: Vec<_> = fields.iter().map(|f| &f.name).collect();
+            let tys: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+            let read_body = quote! {
+                #(let #names = <#tys as Hintable<C>>::read(builder);)*
+                #var_name { #(#names,)* #marker_init }
+            };
+            let pattern = fields_pattern(quote! { Self }, &fields);
+            let write_body = quote! {
+                let #pattern = self;
+                let mut stream = Vec::new();
+                #(stream.extend(<#tys as Hintable<C>>::write(#names));)*
+                stream
+            };
+            (var_fields, fields, read_body, write_body)
+        }
+        Data::Enum(data) => {
+            let mut var_fields = vec![(
+                format_ident!("tag"),
+                quote! { ::openvm_native_compiler::ir::Var<C::N> },
+            )];
+            let mut hinted_fields = vec![];
+            let mut read_variants = vec![];
+            let mut write_arms = vec![];
+            for (tag, variant) in data.variants.iter().enumerate() {
+                let prefix = format!("{}_", to_snake_case(&variant.ident.to_string()));
+                let fields = HintField::from_fields(&variant.fields, Some(&prefix));
+                var_fields.extend(fields.iter().map(|f| (f.name.clone(), f.variable_type())));
+                let names: Vec<_> = fields.iter().map(|f| &f.name).collect();
+                let tys: Vec<_> = fields.iter().map(|f| &f.ty).collect();
+                if !fields.is_empty() {
+                    read_variants.push(quote! {
+                        builder
+                            .if_eq(tag, ::openvm_native_compiler::ir::RVar::from(#tag))
+                            .then(|builder| {
+                                #(
+                                    let value = <#tys as Hintable<C>>::read(builder);
+                                    builder.assign(&variable.#names, value);
+                                )*
+                            });
+                    });
+                }
+                let variant_name = &variant.ident;
+                let pattern = fields_pattern(quote! { Self::#variant_name }, &fields);
+                write_arms.push(quote! {
+                    #pattern => {
+                        let mut stream = <usize as Hintable<C>>::write(&#tag);
+                        #(stream.extend(<#tys as Hintable<C>>::write(#names));)*
+                        stream
+                    }
+                });
+                hinted_fields.extend(fields);
+            }
+            let uninit_names = hinted_fields.iter().map(|f| &f.name);
+            let num_variants = data.variants.len();
+            // The tag is hinted, so a tag past the last variant must fail rather than leave every
+            // field unassigned.
+            let check_tag = if num_variants == 0 {
+                quote! { builder.error(); }
+            } else {
+                quote! {
+                    builder.assert_less_than_slow_small_rhs(
+                        tag,
+                        ::openvm_native_compiler::ir::RVar::from(#num_variants),
+                    );
+                }
+            };
+            let read_body = quote! {
+                let tag = builder.hint_var();
+                #check_tag
+                let variable = #var_name {
+                    tag,
+                    #(#uninit_names: builder.uninit(),)*
+                    #marker_init
+                };
+                #(#read_variants)*
+                variable
+            };
+            let write_body = if write_arms.is_empty() {
+                quote! { match *self {} }
+            } else {
+                quote! {
+                    match self {
+                        #(#write_arms)*
+                    }
+                }
+            };
+            (var_fields, hinted_fields, read_body, write_body)
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "Hintable cannot be derived for unions",
+            ));
+        }
+    };
+
+    let mut var_generics = generics.clone();
+    var_generics
+        .params
+        .insert(0, parse_quote!(C: ::openvm_native_compiler::ir::Config));
+    let mut bounded = std::collections::BTreeSet::new();
+    for field in &hinted_fields {
+        let ty = &field.ty;
+        if bounded.insert(quote!(#ty).to_string()) {
+            var_generics
+                .make_where_clause()
+                .predicates
+                .push(parse_quote!(#ty: Hintable<C>));
+        }
+    }
+    let (impl_generics, var_ty_generics, where_clause) = var_generics.split_for_impl();
+    let (_, ty_generics, _) = generics.split_for_impl();
+
+    let field_names: Vec<_> = var_fields.iter().map(|(name, _)| name).collect();
+    let field_types: Vec<_> = var_fields.iter().map(|(_, ty)| ty).collect();
+    let variable = quote! { ::openvm_native_compiler::ir::Variable<C> };
+    let mem_variable = quote! { ::openvm_native_compiler::ir::MemVariable<C> };
+    let builder = quote! { ::openvm_native_compiler::ir::Builder<C> };
+    let ptr = quote! { ::openvm_native_compiler::ir::Ptr<C::N> };
+    let mem_index = quote! { ::openvm_native_compiler::ir::MemIndex<C::N> };
+
+    Ok(quote! {
+        #vis struct #var_name #impl_generics #where_clause {
+            #(pub #field_names: #field_types,)*
+            #marker_field
+        }
+
+        impl #impl_generics Clone for #var_name #var_ty_generics #where_clause {
+            fn clone(&self) -> Self {
+                Self {
+                    #(#field_names: self.#field_names.clone(),)*
+                    #marker_init
+                }
+            }
+        }
+
+        #[allow(unused_variables)]
+        impl #impl_generics #variable for #var_name #var_ty_generics #where_clause {
+            type Expression = Self;
+
+            fn uninit(builder: &mut #builder) -> Self {
+                Self {
+                    #(#field_names: <#field_types as #variable>::uninit(builder),)*
+                    #marker_init
+                }
+            }
+
+            fn assign(&self, src: Self::Expression, builder: &mut #builder) {
+                #(<#field_types as #variable>::assign(
+                    &self.#field_names,
+                    src.#field_names.into(),
+                    builder,
+                );)*
+            }
+
+            fn assert_eq(
+                lhs: impl Into<Self::Expression>,
+                rhs: impl Into<Self::Expression>,
+                builder: &mut #builder,
+            ) {
+                let lhs = lhs.into();
+                let rhs = rhs.into();
+                #(<#field_types as #variable>::assert_eq(lhs.#field_names, rhs.#field_names, builder);)*
+            }
+        }
+
+        #[allow(unused_variables, unused_mut)]
+        impl #impl_generics #mem_variable for #var_name #var_ty_generics #where_clause {
+            fn size_of() -> usize {
+                0 #(+ <#field_types as #mem_variable>::size_of())*
+            }
+
+            fn load(&self, ptr: #ptr, index: #mem_index, builder: &mut #builder) {
+                let mut index = index;
+                #(
+                    <#field_types as #mem_variable>::load(&self.#field_names, ptr, index, builder);
+                    index.offset += <#field_types as #mem_variable>::size_of();
+                )*
+            }
+
+            fn store(&self, ptr: #ptr, index: #mem_index, builder: &mut #builder) {
+                let mut index = index;
+                #(
+                    <#field_types as #mem_variable>::store(&self.#field_names, ptr, index, builder);
+                    index.offset += <#field_types as #mem_variable>::size_of();
+                )*
+            }
+        }
+
+        #[allow(unused_variables, unused_mut)]
+        impl #impl_generics Hintable<C> for #name #ty_generics #where_clause {
+            type HintVariable = #var_name #var_ty_generics;
+
+            fn read(builder: &mut #builder) -> Self::HintVariable {
+                #read_body
+            }
+
+            fn write(&self) -> Vec<Vec<C::N>> {
+                #write_body
+            }
+        }
+    })
+}
+
 struct IterZipArgs {
     builder: Expr,
     args: Punctuated<Expr, Token![,]>,
@@ -6,6 +6,7 @@ use openvm_native_compiler::ir::{
     DIGEST_SIZE,
 };
 use openvm_native_compiler_derive::iter_zip;
+pub use openvm_native_compiler_derive::Hintable;
 use openvm_stark_backend::{
     config::{Com, PcsProof},
     keygen::types::TraceWidth,
@@ -0,0 +1,76 @@
+use openvm_native_circuit::execute_program;
+use openvm_native_compiler::asm::AsmBuilder;
+use openvm_native_recursion::{
+    hints::{Hintable, InnerVal},
+    types::InnerConfig,
+};
+use openvm_stark_backend::p3_field::{extension::BinomialExtensionField, FieldAlgebra};
+use openvm_stark_sdk::p3_baby_bear::BabyBear;
+
+type F = BabyBear;
+type EF = BinomialExtensionField<BabyBear, 4>;
+
+#[derive(Hintable)]
+struct Point {
+    x: usize,
+    y: InnerVal,
+}
+
+#[derive(Hintable)]
+struct Pair<T>(T, T);
+
+#[derive(Hintable)]
+enum Shape {
+    Empty,
+    Circle(usize),
+    Rect { width: usize, corners: Vec<usize> },
+}
+
+#[test]
+fn test_derive_hintable() {
+    let mut stream = Hintable::<InnerConfig>::write(&Point {
+        x: 3,
+        y: F::from_canonical_u32(5),
+    });
+    stream.extend(Hintable::<InnerConfig>::write(&Pair(7usize, 11usize)));
+    stream.extend(Hintable::<InnerConfig>::write(&Shape::Empty));
+    stream.extend(Hintable::<InnerConfig>::write(&Shape::Circle(13)));
+    stream.extend(Hintable::<InnerConfig>::write(&Shape::Rect {
+        width: 17,
+        corners: vec![19, 23],
+    }));
+
+    let mut builder = AsmBuilder::<F, EF>::default();
+    let point = Point::read(&mut builder);
+    builder.assert_var_eq(point.x, F::from_canonical_u32(3));
+    builder.assert_felt_eq(point.y, F::from_canonical_u32(5));
+    let pair = Pair::<usize>::read(&mut builder);
+    builder.assert_var_eq(pair._0, F::from_canonical_u32(7));
+    builder.assert_var_eq(pair._1, F::from_canonical_u32(11));
+    let empty = Shape::read(&mut builder);
+    builder.assert_var_eq(empty.tag, F::ZERO);
+    let circle = Shape::read(&mut builder);
+    builder.assert_var_eq(circle.tag, F::ONE);
+    builder.assert_var_eq(circle.circle_0, F::from_canonical_u32(13));
+    let rect = Shape::read(&mut builder);
+    builder.assert_var_eq(rect.tag, F::TWO);
+    builder.assert_var_eq(rect.rect_width, F::from_canonical_u32(17));
+    builder.assert_usize_eq(rect.rect_corners.len(), F::TWO);
+    let corner = builder.get(&rect.rect_corners, 1);
+    builder.assert_var_eq(corner, F::from_canonical_u32(23));
+    builder.halt();
+
+    execute_program(builder.compile_isa(), stream);
+}
+
+#[test]
+#[should_panic]
+fn test_derive_hintable_invalid_tag() {
+    let stream = Hintable::<InnerConfig>::write(&3usize);
+
+    let mut builder = AsmBuilder::<F, EF>::default();
+    Shape::read(&mut builder);
+    builder.halt();
+
+    execute_program(builder.compile_isa(), stream);
+}
@@ -84,6 +84,10 @@ impl<F: PrimeField32> VmExtension<F> for PairingExtension {
     type Executor = PairingExtensionExecutor<F>;
     type Periphery = PairingExtensionPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("pairing", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -4,7 +4,7 @@ use openvm_instructions::{
 use openvm_instructions_derive::LocalOpcode;
 use openvm_pairing_guest::{PairingBaseFunct7, OPCODE, PAIRING_FUNCT3};
 use openvm_stark_backend::p3_field::PrimeField32;
-use openvm_transpiler::{TranspilerExtension, TranspilerOutput};
+use openvm_transpiler::{util::phantom_discriminant, TranspilerExtension, TranspilerOutput};
 use rrs_lib::instruction_formats::RType;
 use strum::{EnumCount, EnumIter, FromRepr};
 
@@ -111,4 +111,9 @@ impl<F: PrimeField32> TranspilerExtension<F> for PairingTranspilerExtension {
         }
         None
     }
+
+    /// The only phantom sub-instruction of the extension is a hint.
+    fn provides_hints(&self, instruction: &Instruction<F>) -> bool {
+        phantom_discriminant(instruction).is_some()
+    }
 }
@@ -0,0 +1,122 @@
+use derive_more::derive::From;
+use openvm_circuit::{
+    arch::{
+        vm_poseidon2_config, InitFileGenerator, SystemConfig, SystemPort, VmExtension, VmInventory,
+        VmInventoryBuilder, VmInventoryError,
+    },
+    system::phantom::PhantomChip,
+};
+use openvm_circuit_derive::{AnyEnum, InstructionExecutor, VmConfig};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_instructions::LocalOpcode;
+use openvm_poseidon2_transpiler::Rv32Poseidon2Opcode;
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+use openvm_rv32im_circuit::{
+    Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
+    Rv32MExecutor, Rv32MPeriphery,
+};
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Debug, VmConfig, derive_new::new, Serialize, Deserialize)]
+pub struct Poseidon2Rv32Config {
+    #[system]
+    pub system: SystemConfig,
+    #[extension]
+    pub rv32i: Rv32I,
+    #[extension]
+    pub rv32m: Rv32M,
+    #[extension]
+    pub io: Rv32Io,
+    #[extension]
+    pub poseidon2: Poseidon2,
+}
+
+impl Default for Poseidon2Rv32Config {
+    fn default() -> Self {
+        Self {
+            system: SystemConfig::default().with_continuations(),
+            rv32i: Rv32I,
+            rv32m: Rv32M::default(),
+            io: Rv32Io,
+            poseidon2: Poseidon2,
+        }
+    }
+}
+
+// Default implementation uses no init file
+impl InitFileGenerator for Poseidon2Rv32Config {}
+
+/// The Poseidon2 permutation of the VM, with the same round constants as the memory and public
+/// values commitments.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Poseidon2;
+
+#[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
+pub enum Poseidon2Executor<F: PrimeField32> {
+    Permute(Rv32Poseidon2Chip<F>),
+}
+
+#[derive(From, ChipUsageGetter, Chip, AnyEnum)]
+pub enum Poseidon2Periphery<F: PrimeField32> {
+    BitwiseOperationLookup(SharedBitwiseOperationLookupChip<8>),
+    Phantom(PhantomChip<F>),
+}
+
+impl<F: PrimeField32> VmExtension<F> for Poseidon2 {
+    type Executor = Poseidon2Executor<F>;
+    type Periphery = Poseidon2Periphery<F>;
+
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("poseidon2", 1))
+    }
+
+    fn build(
+        &self,
+        builder: &mut VmInventoryBuilder<F>,
+    ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError> {
+        let mut inventory = VmInventory::new();
+        let SystemPort {
+            execution_bus,
+            program_bus,
+            memory_bridge,
+        } = builder.system_port();
+        let bitwise_lu_chip = if let Some(&chip) = builder
+            .find_chip::<SharedBitwiseOperationLookupChip<8>>()
+            .first()
+        {
+            chip.clone()
+        } else {
+            let bitwise_lu_bus = BitwiseOperationLookupBus::new(builder.new_bus_idx());
+            let chip = SharedBitwiseOperationLookupChip::new(bitwise_lu_bus);
+            inventory.add_periphery_chip(chip.clone());
+            chip
+        };
+        let offline_memory = builder.system_base().offline_memory();
+        let address_bits = builder.system_config().memory_config.pointer_max_bits;
+
+        let permute_chip = Rv32Poseidon2Chip::new(
+            Rv32VecHeapAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            ),
+            Poseidon2CoreChip::new(
+                vm_poseidon2_config(),
+                bitwise_lu_chip,
+                Rv32Poseidon2Opcode::CLASS_OFFSET,
+            ),
+            offline_memory,
+        );
+        inventory.add_executor(permute_chip, [Rv32Poseidon2Opcode::PERMUTE.global_opcode()])?;
+
+        Ok(inventory)
+    }
+}
@@ -0,0 +1,16 @@
+//! The Poseidon2 permutation over BabyBear used by the VM memory and public values commitments,
+//! exposed to RISC-V guests on words in memory.
+use openvm_circuit::arch::VmChipWrapper;
+use openvm_rv32_adapters::Rv32VecHeapAdapterChip;
+
+mod extension;
+mod permute;
+pub use extension::*;
+pub use permute::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Reads 16 words from `rs1` and writes their permutation, 16 words, to `rd`.
+pub type Rv32Poseidon2Chip<F> =
+    VmChipWrapper<F, Rv32VecHeapAdapterChip<F, 1, 16, 16, 4, 4>, Poseidon2CoreChip<F>>;
@@ -0,0 +1,295 @@
+use std::{
+    array::from_fn,
+    borrow::{Borrow, BorrowMut},
+    sync::Arc,
+};
+
+use openvm_circuit::arch::{
+    AdapterAirContext, AdapterRuntimeContext, DynAdapterInterface, DynArray, MinimalInstruction,
+    Result, VmAdapterInterface, VmCoreAir, VmCoreChip,
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::{BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip},
+    is_zero::{IsZeroIo, IsZeroSubAir},
+    utils::compose,
+    AlignedBorrow, SubAir, TraceSubRowGenerator,
+};
+use openvm_instructions::{
+    instruction::Instruction,
+    riscv::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS},
+    LocalOpcode,
+};
+use openvm_poseidon2_air::{
+    Poseidon2Config, Poseidon2SubAir, Poseidon2SubChip, Poseidon2SubCols,
+    BABY_BEAR_POSEIDON2_HALF_FULL_ROUNDS, POSEIDON2_WIDTH,
+};
+use openvm_poseidon2_transpiler::Rv32Poseidon2Opcode;
+use openvm_stark_backend::{
+    air_builders::sub::SubAirBuilder,
+    interaction::InteractionBuilder,
+    p3_air::{AirBuilder, BaseAir},
+    p3_field::{Field, FieldAlgebra, PrimeField32},
+    p3_matrix::{dense::RowMajorMatrix, Matrix},
+    rap::BaseAirWithPublicValues,
+};
+use serde::{Deserialize, Serialize};
+
+/// Keeps the constraint degree of the Poseidon2 subair at most 3.
+pub const SBOX_REGISTERS: usize = 1;
+
+/// The most significant byte of the BabyBear prime `0x78000001`.
+const MODULUS_MSB: u32 = 0x78;
+
+// The permutation is constrained by the Poseidon2 subair, whose columns come first so that the
+// subair trace can be copied in. Each of the 16 state elements is read and written as a 4 byte
+// little-endian word. The input words are reduced into the field, while the output words are
+// constrained to be the canonical representation of the output elements: either the most
+// significant byte is less than 0x78, or it is 0x78 and the other bytes are zero.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct Poseidon2CoreCols<T, const SBOX_REGISTERS: usize> {
+    pub inner: Poseidon2SubCols<T, SBOX_REGISTERS>,
+    pub is_valid: T,
+    pub input: [[T; RV32_REGISTER_NUM_LIMBS]; POSEIDON2_WIDTH],
+    pub output: [[T; RV32_REGISTER_NUM_LIMBS]; POSEIDON2_WIDTH],
+    /// Whether the most significant byte of the output word is 0x78.
+    pub output_msb_is_max: [T; POSEIDON2_WIDTH],
+    pub output_msb_is_max_inv: [T; POSEIDON2_WIDTH],
+}
+
+#[derive(Clone, Debug)]
+pub struct Poseidon2CoreAir<F: Field> {
+    pub subair: Arc<Poseidon2SubAir<F, SBOX_REGISTERS>>,
+    pub bus: BitwiseOperationLookupBus,
+    pub offset: usize,
+}
+
+impl<F: Field> BaseAir<F> for Poseidon2CoreAir<F> {
+    fn width(&self) -> usize {
+        Poseidon2CoreCols::<F, SBOX_REGISTERS>::width()
+    }
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for Poseidon2CoreAir<F> {}
+
+impl<AB: InteractionBuilder, I> VmCoreAir<AB, I> for Poseidon2CoreAir<AB::F>
+where
+    I: VmAdapterInterface<AB::Expr>,
+    AdapterAirContext<AB::Expr, I>:
+        From<AdapterAirContext<AB::Expr, DynAdapterInterface<AB::Expr>>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        // The core columns are the last columns of the row. The permutation is constrained on
+        // every row, so padding rows hold the permutation of the zero state.
+        let core_start = builder.main().width() - BaseAir::<AB::F>::width(self);
+        let mut sub_builder =
+            SubAirBuilder::<AB, Poseidon2SubAir<AB::F, SBOX_REGISTERS>, AB::F>::new(
+                builder,
+                core_start..core_start + self.subair.width(),
+            );
+        self.subair.eval(&mut sub_builder);
+
+        let cols: &Poseidon2CoreCols<AB::Var, SBOX_REGISTERS> = local_core.borrow();
+        let is_valid = cols.is_valid;
+        builder.assert_bool(is_valid);
+
+        let output_state =
+            cols.inner.ending_full_rounds[BABY_BEAR_POSEIDON2_HALF_FULL_ROUNDS - 1].post;
+        for i in 0..POSEIDON2_WIDTH {
+            builder.when(is_valid).assert_eq(
+                cols.inner.inputs[i],
+                compose::<AB::Expr>(&cols.input[i], RV32_CELL_BITS),
+            );
+            builder.when(is_valid).assert_eq(
+                output_state[i],
+                compose::<AB::Expr>(&cols.output[i], RV32_CELL_BITS),
+            );
+
+            let [b0, b1, b2, msb] = cols.output[i];
+            IsZeroSubAir.eval(
+                builder,
+                (
+                    IsZeroIo::new(
+                        msb - AB::Expr::from_canonical_u32(MODULUS_MSB),
+                        cols.output_msb_is_max[i].into(),
+                        is_valid.into(),
+                    ),
+                    cols.output_msb_is_max_inv[i],
+                ),
+            );
+            builder
+                .when(cols.output_msb_is_max[i])
+                .assert_zero(b0 + b1 + b2);
+        }
+
+        // Range check the output bytes, and that the most significant bytes are at most 0x78.
+        let output_bytes = cols.output.concat();
+        for pair in output_bytes.chunks_exact(2) {
+            self.bus
+                .send_range(pair[0], pair[1])
+                .eval(builder, is_valid);
+        }
+        for pair in cols.output.chunks_exact(2) {
+            self.bus
+                .send_range(
+                    AB::Expr::from_canonical_u32(MODULUS_MSB) - pair[0][3],
+                    AB::Expr::from_canonical_u32(MODULUS_MSB) - pair[1][3],
+                )
+                .eval(builder, is_valid);
+        }
+
+        let reads: Vec<AB::Expr> = cols.input.concat().into_iter().map(Into::into).collect();
+        let writes: Vec<AB::Expr> = output_bytes.into_iter().map(Into::into).collect();
+        let instruction = MinimalInstruction {
+            is_valid: is_valid.into(),
+            opcode: AB::Expr::from_canonical_usize(
+                self.offset + Rv32Poseidon2Opcode::PERMUTE.local_usize(),
+            ),
+        };
+        let ctx: AdapterAirContext<_, DynAdapterInterface<_>> = AdapterAirContext {
+            to_pc: None,
+            reads: reads.into(),
+            writes: writes.into(),
+            instruction: instruction.into(),
+        };
+        ctx.into()
+    }
+
+    fn start_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Poseidon2CoreRecord<T> {
+    pub input: [[T; RV32_REGISTER_NUM_LIMBS]; POSEIDON2_WIDTH],
+    pub output: [[T; RV32_REGISTER_NUM_LIMBS]; POSEIDON2_WIDTH],
+}
+
+pub struct Poseidon2CoreChip<F: Field> {
+    pub air: Poseidon2CoreAir<F>,
+    pub subchip: Poseidon2SubChip<F, SBOX_REGISTERS>,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl<F: PrimeField32> Poseidon2CoreChip<F> {
+    pub fn new(
+        poseidon2_config: Poseidon2Config<F>,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        offset: usize,
+    ) -> Self {
+        let subchip = Poseidon2SubChip::new(poseidon2_config.constants);
+        Self {
+            air: Poseidon2CoreAir {
+                subair: subchip.air.clone(),
+                bus: bitwise_lookup_chip.bus(),
+                offset,
+            },
+            subchip,
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+/// The field elements of the input words, reduced modulo the prime.
+fn input_state<F: PrimeField32>(
+    input: &[[F; RV32_REGISTER_NUM_LIMBS]; POSEIDON2_WIDTH],
+) -> [F; POSEIDON2_WIDTH] {
+    input.map(|word| {
+        F::from_wrapped_u32(u32::from_le_bytes(word.map(|x| x.as_canonical_u32() as u8)))
+    })
+}
+
+impl<F: PrimeField32, I> VmCoreChip<F, I> for Poseidon2CoreChip<F>
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<DynArray<F>>,
+    AdapterRuntimeContext<F, I>: From<AdapterRuntimeContext<F, DynAdapterInterface<F>>>,
+{
+    type Record = Poseidon2CoreRecord<F>;
+    type Air = Poseidon2CoreAir<F>;
+
+    fn execute_instruction(
+        &self,
+        _instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> Result<(AdapterRuntimeContext<F, I>, Self::Record)> {
+        let data: DynArray<_> = reads.into();
+        assert_eq!(data.0.len(), POSEIDON2_WIDTH * RV32_REGISTER_NUM_LIMBS);
+        let input: [[F; RV32_REGISTER_NUM_LIMBS]; POSEIDON2_WIDTH] =
+            from_fn(|i| from_fn(|j| data.0[i * RV32_REGISTER_NUM_LIMBS + j]));
+
+        let output_state = self.subchip.permute(input_state(&input));
+        let output_bytes = output_state.map(|x| x.as_canonical_u32().to_le_bytes());
+        for pair in output_bytes.concat().chunks_exact(2) {
+            self.bitwise_lookup_chip
+                .request_range(pair[0] as u32, pair[1] as u32);
+        }
+        for pair in output_bytes.chunks_exact(2) {
+            self.bitwise_lookup_chip.request_range(
+                MODULUS_MSB - pair[0][3] as u32,
+                MODULUS_MSB - pair[1][3] as u32,
+            );
+        }
+
+        let record = Poseidon2CoreRecord {
+            input,
+            output: output_bytes.map(|word| word.map(F::from_canonical_u8)),
+        };
+        let ctx =
+            AdapterRuntimeContext::<_, DynAdapterInterface<_>>::without_pc(record.output.concat());
+        Ok((ctx.into(), record))
+    }
+
+    fn get_opcode_name(&self, _opcode: usize) -> String {
+        format!("{:?}", Rv32Poseidon2Opcode::PERMUTE)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        // WARNING: Poseidon2SubCols must be the first field in Poseidon2CoreCols
+        let inner_width = self.air.subair.width();
+        let inner_trace = self
+            .subchip
+            .generate_trace(vec![input_state(&record.input)]);
+        row_slice[..inner_width].copy_from_slice(inner_trace.values.as_slice());
+
+        let cols: &mut Poseidon2CoreCols<F, SBOX_REGISTERS> = row_slice.borrow_mut();
+        cols.is_valid = F::ONE;
+        cols.input = record.input;
+        cols.output = record.output;
+        for i in 0..POSEIDON2_WIDTH {
+            IsZeroSubAir.generate_subrow(
+                cols.output[i][3] - F::from_canonical_u32(MODULUS_MSB),
+                (
+                    &mut cols.output_msb_is_max_inv[i],
+                    &mut cols.output_msb_is_max[i],
+                ),
+            );
+        }
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+
+    fn finalize(&self, trace: &mut RowMajorMatrix<F>, num_records: usize) {
+        if num_records == trace.height() {
+            return;
+        }
+        let core_width = <Self::Air as BaseAir<F>>::width(&self.air);
+        let core_start = trace.width() - core_width;
+        let inner_width = self.air.subair.width();
+        let padding_trace = self
+            .subchip
+            .generate_trace(vec![[F::ZERO; POSEIDON2_WIDTH]]);
+        for row in trace.rows_mut().skip(num_records) {
+            row[core_start..core_start + inner_width].copy_from_slice(&padding_trace.values);
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::{array::from_fn, borrow::BorrowMut};
+
+use openvm_circuit::arch::{
+    testing::{VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS},
+    vm_poseidon2_config,
+};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_instructions::{riscv::RV32_CELL_BITS, LocalOpcode};
+use openvm_poseidon2_air::{Poseidon2SubChip, POSEIDON2_WIDTH};
+use openvm_poseidon2_transpiler::Rv32Poseidon2Opcode;
+use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapAdapterChip};
+use openvm_stark_backend::{
+    p3_field::{FieldAlgebra, PrimeField32},
+    p3_matrix::Matrix,
+    utils::disable_debug_builder,
+    verifier::VerificationError,
+};
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, utils::create_seeded_rng};
+use rand::Rng;
+
+use crate::{Poseidon2CoreChip, Poseidon2CoreCols, Rv32Poseidon2Chip, SBOX_REGISTERS};
+
+type F = BabyBear;
+
+fn build_poseidon2_test(
+    inputs: Vec<[u32; POSEIDON2_WIDTH]>,
+) -> (
+    VmChipTestBuilder<F>,
+    Rv32Poseidon2Chip<F>,
+    SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+) {
+    let mut tester = VmChipTestBuilder::default();
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let mut chip = Rv32Poseidon2Chip::new(
+        Rv32VecHeapAdapterChip::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        ),
+        Poseidon2CoreChip::new(
+            vm_poseidon2_config(),
+            bitwise_chip.clone(),
+            Rv32Poseidon2Opcode::CLASS_OFFSET,
+        ),
+        tester.offline_memory_mutex_arc(),
+    );
+
+    let subchip = Poseidon2SubChip::<F, 0>::new(vm_poseidon2_config().constants);
+    for input in inputs {
+        let instruction = rv32_write_heap_default(
+            &mut tester,
+            input
+                .iter()
+                .map(|word| word.to_le_bytes().map(F::from_canonical_u8))
+                .collect(),
+            vec![],
+            Rv32Poseidon2Opcode::PERMUTE.global_opcode().as_usize(),
+        );
+        tester.execute(&mut chip, &instruction);
+
+        let rd_ptr = instruction.a.as_canonical_u32() as usize;
+        let out_ptr = u32::from_le_bytes(
+            tester
+                .read::<4>(1, rd_ptr)
+                .map(|x| x.as_canonical_u32() as u8),
+        ) as usize;
+        let output: [u32; POSEIDON2_WIDTH] = from_fn(|i| {
+            u32::from_le_bytes(
+                tester
+                    .read::<4>(2, out_ptr + 4 * i)
+                    .map(|x| x.as_canonical_u32() as u8),
+            )
+        });
+        let expected = subchip
+            .permute(input.map(F::from_wrapped_u32))
+            .map(|x| x.as_canonical_u32());
+        assert_eq!(output, expected);
+    }
+    (tester, chip, bitwise_chip)
+}
+
+#[test]
+fn test_poseidon2_permute() {
+    let mut rng = create_seeded_rng();
+    let inputs = (0..8)
+        .map(|_| from_fn(|_| rng.gen::<u32>() % F::ORDER_U32))
+        .collect();
+    let (tester, chip, bitwise_chip) = build_poseidon2_test(inputs);
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_poseidon2_permute_non_canonical_input() {
+    let mut rng = create_seeded_rng();
+    let inputs = vec![
+        [0; POSEIDON2_WIDTH],
+        [u32::MAX; POSEIDON2_WIDTH],
+        [F::ORDER_U32; POSEIDON2_WIDTH],
+        from_fn(|_| rng.gen()),
+    ];
+    let (tester, chip, bitwise_chip) = build_poseidon2_test(inputs);
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+// The output word x + p has the same value in the field as x, but must be rejected.
+#[test]
+fn test_poseidon2_non_canonical_output() {
+    let (tester, chip, bitwise_chip) = build_poseidon2_test(vec![[1; POSEIDON2_WIDTH]]);
+    let mut tester = tester.build().load(chip).load(bitwise_chip).finalize();
+
+    let trace = tester.air_proof_inputs[2]
+        .1
+        .raw
+        .common_main
+        .as_mut()
+        .unwrap();
+    let core_start = trace.width() - Poseidon2CoreCols::<F, SBOX_REGISTERS>::width();
+    let row = trace.row_mut(0);
+    let cols: &mut Poseidon2CoreCols<F, SBOX_REGISTERS> = row[core_start..].borrow_mut();
+    let word = u32::from_le_bytes(cols.output[0].map(|x| x.as_canonical_u32() as u8));
+    cols.output[0] = (word + F::ORDER_U32)
+        .to_le_bytes()
+        .map(F::from_canonical_u8);
+
+    disable_debug_builder();
+    assert_eq!(
+        tester.simple_test().err(),
+        Some(VerificationError::OodEvaluationMismatch)
+    );
+}
@@ -0,0 +1,98 @@
+#![no_std]
+
+//! The Poseidon2 permutation over BabyBear used by the VM to hash its memory and public values.
+//!
+//! Field elements are passed as `u32` words. Inputs are reduced modulo the BabyBear prime and
+//! outputs are always canonical, i.e. less than [BABY_BEAR_MODULUS].
+//!
+//! In the zkVM, [poseidon2_permute] is a single instruction of the Poseidon2 extension. Outside
+//! the zkVM the same functions fall back to `openvm-poseidon2-air`.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+/// This is custom-0 defined in RISC-V spec document
+pub const OPCODE: u8 = 0x0b;
+pub const POSEIDON2_FUNCT3: u8 = 0b100;
+/// Permutes the 16 words at `rs1` and writes the result to `rd`.
+pub const POSEIDON2_FUNCT7: u8 = 0x4;
+
+openvm_platform::require_extension_version!("poseidon2", 1);
+
+/// The number of field elements of the Poseidon2 state.
+pub const POSEIDON2_WIDTH: usize = 16;
+/// The number of field elements of a Poseidon2 digest.
+pub const POSEIDON2_CHUNK: usize = 8;
+/// The BabyBear prime `2^31 - 2^27 + 1`.
+pub const BABY_BEAR_MODULUS: u32 = 0x7800_0001;
+
+/// Applies the Poseidon2 permutation to `state` in place.
+#[inline(always)]
+pub fn poseidon2_permute(state: &mut [u32; POSEIDON2_WIDTH]) {
+    #[cfg(target_os = "zkvm")]
+    {
+        let ptr = state.as_mut_ptr();
+        openvm_platform::custom_insn_r!(
+            opcode = OPCODE,
+            funct3 = POSEIDON2_FUNCT3,
+            funct7 = POSEIDON2_FUNCT7,
+            rd = In ptr,
+            rs1 = In ptr,
+            rs2 = Const "x0"
+        );
+    }
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        use openvm_poseidon2_air::{p3_baby_bear::BabyBear, Poseidon2Config, Poseidon2SubChip};
+        use openvm_stark_backend::p3_field::{FieldAlgebra, PrimeField32};
+
+        let subchip = Poseidon2SubChip::<BabyBear, 0>::new(Poseidon2Config::default().constants);
+        let output = subchip.permute(state.map(BabyBear::from_wrapped_u32));
+        *state = output.map(|x| x.as_canonical_u32());
+    }
+}
+
+/// Compresses two digests into one by permuting their concatenation and truncating the result
+/// to its first [POSEIDON2_CHUNK] elements. This is the compression function of the VM memory
+/// Merkle tree.
+pub fn poseidon2_compress(
+    lhs: &[u32; POSEIDON2_CHUNK],
+    rhs: &[u32; POSEIDON2_CHUNK],
+) -> [u32; POSEIDON2_CHUNK] {
+    let mut state = [0u32; POSEIDON2_WIDTH];
+    state[..POSEIDON2_CHUNK].copy_from_slice(lhs);
+    state[POSEIDON2_CHUNK..].copy_from_slice(rhs);
+    poseidon2_permute(&mut state);
+    let mut output = [0u32; POSEIDON2_CHUNK];
+    output.copy_from_slice(&state[..POSEIDON2_CHUNK]);
+    output
+}
+
+/// Hashes `values` as the root of a Merkle tree whose leaves are chunks of [POSEIDON2_CHUNK]
+/// values. The values are right-padded with zeros to a power of two number of chunks, each chunk
+/// is compressed with a chunk of zeros and each internal node is the compression of its
+/// children. Empty input is hashed as a single chunk of zeros.
+///
+/// When the number of values is a power of two multiple of [POSEIDON2_CHUNK], this is the
+/// Poseidon2 commitment to the user public values that the VM computes.
+pub fn poseidon2_hash_felts(values: &[u32]) -> [u32; POSEIDON2_CHUNK] {
+    let num_leaves = values.len().div_ceil(POSEIDON2_CHUNK).next_power_of_two();
+    let zeros = [0u32; POSEIDON2_CHUNK];
+    let mut nodes: Vec<[u32; POSEIDON2_CHUNK]> = (0..num_leaves)
+        .map(|i| {
+            let mut leaf = zeros;
+            let start = (i * POSEIDON2_CHUNK).min(values.len());
+            let end = ((i + 1) * POSEIDON2_CHUNK).min(values.len());
+            leaf[..end - start].copy_from_slice(&values[start..end]);
+            poseidon2_compress(&leaf, &zeros)
+        })
+        .collect();
+    while nodes.len() > 1 {
+        nodes = nodes
+            .chunks_exact(2)
+            .map(|pair| poseidon2_compress(&pair[0], &pair[1]))
+            .collect();
+    }
+    nodes[0]
+}
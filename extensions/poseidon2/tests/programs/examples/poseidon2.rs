@@ -0,0 +1,30 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::io::read;
+use openvm_poseidon2_guest::{
+    poseidon2_hash_felts, poseidon2_permute, POSEIDON2_CHUNK, POSEIDON2_WIDTH,
+};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let states: Vec<[u32; POSEIDON2_WIDTH]> = read();
+    let permuted: Vec<[u32; POSEIDON2_WIDTH]> = read();
+    assert_eq!(states.len(), permuted.len());
+    for (mut state, expected) in states.into_iter().zip(permuted) {
+        poseidon2_permute(&mut state);
+        assert_eq!(state, expected);
+    }
+
+    let values: Vec<Vec<u32>> = read();
+    let digests: Vec<[u32; POSEIDON2_CHUNK]> = read();
+    assert_eq!(values.len(), digests.len());
+    for (values, expected) in values.iter().zip(digests) {
+        assert_eq!(poseidon2_hash_felts(values), expected);
+    }
+}
@@ -0,0 +1,97 @@
+#[cfg(test)]
+mod tests {
+    use std::array::from_fn;
+
+    use eyre::Result;
+    use openvm_circuit::{
+        arch::{
+            hasher::{poseidon2::vm_poseidon2_hasher, Hasher},
+            instructions::exe::VmExe,
+        },
+        utils::air_test_with_min_segments,
+    };
+    use openvm_poseidon2_guest::{
+        poseidon2_hash_felts, poseidon2_permute, BABY_BEAR_MODULUS, POSEIDON2_WIDTH,
+    };
+    use openvm_sdk::{
+        config::{AppConfig, SdkVmConfig},
+        StdIn,
+    };
+    use openvm_stark_sdk::{
+        openvm_stark_backend::p3_field::{FieldAlgebra, PrimeField32},
+        p3_baby_bear::BabyBear,
+        utils::create_seeded_rng,
+    };
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::FromElf;
+    use rand::Rng;
+
+    fn poseidon2_config() -> Result<SdkVmConfig> {
+        Ok(toml::from_str::<AppConfig<SdkVmConfig>>(include_str!(
+            "../programs/openvm_poseidon2.toml"
+        ))?
+        .app_vm_config)
+    }
+
+    #[test]
+    fn test_poseidon2() -> Result<()> {
+        let mut rng = create_seeded_rng();
+        let config = poseidon2_config()?;
+        let elf = build_example_program_at_path(get_programs_dir!(), "poseidon2", &config)?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+
+        let states: Vec<[u32; POSEIDON2_WIDTH]> = vec![
+            [0; POSEIDON2_WIDTH],
+            [BABY_BEAR_MODULUS - 1; POSEIDON2_WIDTH],
+            // Words that are not canonical are reduced modulo the prime.
+            [u32::MAX; POSEIDON2_WIDTH],
+            from_fn(|_| rng.gen()),
+        ];
+        let permuted: Vec<_> = states
+            .iter()
+            .map(|state| {
+                let mut state = *state;
+                poseidon2_permute(&mut state);
+                state
+            })
+            .collect();
+        let values: Vec<Vec<u32>> = [0, 1, 8, 13, 32, 100]
+            .into_iter()
+            .map(|len| (0..len).map(|_| rng.gen()).collect())
+            .collect();
+        let digests: Vec<_> = values
+            .iter()
+            .map(|values| poseidon2_hash_felts(values))
+            .collect();
+
+        let mut input = StdIn::default();
+        input.write(&states);
+        input.write(&permuted);
+        input.write(&values);
+        input.write(&digests);
+        air_test_with_min_segments(config, openvm_exe, input, 1);
+        Ok(())
+    }
+
+    /// For a power of two multiple of 8 values, `poseidon2_hash_felts` is the Poseidon2 commitment
+    /// to the user public values.
+    #[test]
+    fn test_hash_felts_matches_public_values_commit() {
+        let mut rng = create_seeded_rng();
+        let hasher = vm_poseidon2_hasher::<BabyBear>();
+        for len in [8, 16, 32, 64] {
+            let values: Vec<u32> = (0..len)
+                .map(|_| rng.gen_range(0..BABY_BEAR_MODULUS))
+                .collect();
+            let felts: Vec<BabyBear> = values
+                .iter()
+                .map(|&x| BabyBear::from_canonical_u32(x))
+                .collect();
+            let root = hasher.merkle_root(&felts);
+            assert_eq!(
+                poseidon2_hash_felts(&values),
+                root.map(|x| x.as_canonical_u32())
+            );
+        }
+    }
+}
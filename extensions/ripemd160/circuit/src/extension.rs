@@ -0,0 +1,128 @@
+use derive_more::derive::From;
+use openvm_circuit::{
+    arch::{
+        InitFileGenerator, SystemConfig, SystemPort, VmExtension, VmInventory, VmInventoryBuilder,
+        VmInventoryError,
+    },
+    system::phantom::PhantomChip,
+};
+use openvm_circuit_derive::{AnyEnum, InstructionExecutor, VmConfig};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_instructions::LocalOpcode;
+use openvm_ripemd160_transpiler::Rv32Ripemd160Opcode;
+use openvm_rv32_adapters::Rv32VecHeapTwoReadsAdapterChip;
+use openvm_rv32im_circuit::{
+    Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
+    Rv32MExecutor, Rv32MPeriphery,
+};
+use openvm_stark_backend::{interaction::LookupBus, p3_field::PrimeField32};
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Debug, VmConfig, derive_new::new, Serialize, Deserialize)]
+pub struct Ripemd160Rv32Config {
+    #[system]
+    pub system: SystemConfig,
+    #[extension]
+    pub rv32i: Rv32I,
+    #[extension]
+    pub rv32m: Rv32M,
+    #[extension]
+    pub io: Rv32Io,
+    #[extension]
+    pub ripemd160: Ripemd160,
+}
+
+impl Default for Ripemd160Rv32Config {
+    fn default() -> Self {
+        Self {
+            system: SystemConfig::default().with_continuations(),
+            rv32i: Rv32I,
+            rv32m: Rv32M::default(),
+            io: Rv32Io,
+            ripemd160: Ripemd160,
+        }
+    }
+}
+
+// Default implementation uses no init file
+impl InitFileGenerator for Ripemd160Rv32Config {}
+
+/// The RIPEMD-160 compression function.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Ripemd160;
+
+#[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
+pub enum Ripemd160Executor<F: PrimeField32> {
+    Compress(Rv32Ripemd160Chip<F>),
+}
+
+#[derive(From, ChipUsageGetter, Chip, AnyEnum)]
+pub enum Ripemd160Periphery<F: PrimeField32> {
+    BitwiseOperationLookup(SharedBitwiseOperationLookupChip<8>),
+    Compress(SharedRipemd160CompressChip),
+    Phantom(PhantomChip<F>),
+}
+
+impl<F: PrimeField32> VmExtension<F> for Ripemd160 {
+    type Executor = Ripemd160Executor<F>;
+    type Periphery = Ripemd160Periphery<F>;
+
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("ripemd160", 1))
+    }
+
+    fn build(
+        &self,
+        builder: &mut VmInventoryBuilder<F>,
+    ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError> {
+        let mut inventory = VmInventory::new();
+        let SystemPort {
+            execution_bus,
+            program_bus,
+            memory_bridge,
+        } = builder.system_port();
+        let bitwise_lu_chip = if let Some(&chip) = builder
+            .find_chip::<SharedBitwiseOperationLookupChip<8>>()
+            .first()
+        {
+            chip.clone()
+        } else {
+            let bitwise_lu_bus = BitwiseOperationLookupBus::new(builder.new_bus_idx());
+            let chip = SharedBitwiseOperationLookupChip::new(bitwise_lu_bus);
+            inventory.add_periphery_chip(chip.clone());
+            chip
+        };
+        let offline_memory = builder.system_base().offline_memory();
+        let address_bits = builder.system_config().memory_config.pointer_max_bits;
+
+        let compress_chip = SharedRipemd160CompressChip::new(LookupBus::new(builder.new_bus_idx()));
+        inventory.add_periphery_chip(compress_chip.clone());
+
+        let ripemd160_chip = Rv32Ripemd160Chip::new(
+            Rv32VecHeapTwoReadsAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            ),
+            Ripemd160CoreChip::new(
+                compress_chip,
+                bitwise_lu_chip,
+                Rv32Ripemd160Opcode::CLASS_OFFSET,
+            ),
+            offline_memory,
+        );
+        inventory.add_executor(
+            ripemd160_chip,
+            [Rv32Ripemd160Opcode::COMPRESS.global_opcode()],
+        )?;
+
+        Ok(inventory)
+    }
+}
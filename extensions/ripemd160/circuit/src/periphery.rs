@@ -0,0 +1,594 @@
+use std::{
+    array::from_fn,
+    borrow::{Borrow, BorrowMut},
+    sync::{Arc, Mutex},
+};
+
+use openvm_circuit_primitives::{
+    utils::{compose, next_power_of_two_or_zero},
+    AlignedBorrow,
+};
+use openvm_ripemd160_guest::{
+    ripemd160_compress, K_LEFT, K_RIGHT, R_LEFT, R_RIGHT, S_LEFT, S_RIGHT,
+};
+use openvm_stark_backend::{
+    config::{StarkGenericConfig, Val},
+    interaction::{InteractionBuilder, LookupBus},
+    p3_air::{Air, AirBuilder, BaseAir},
+    p3_field::{Field, FieldAlgebra, PrimeField32},
+    p3_matrix::{dense::RowMajorMatrix, Matrix},
+    p3_maybe_rayon::prelude::*,
+    prover::types::AirProofInput,
+    rap::{get_air_name, BaseAirWithPublicValues, PartitionedBaseAir},
+    AirRef, Chip, ChipUsageGetter,
+};
+
+pub const RIPEMD160_ROUNDS: usize = 5;
+pub const RIPEMD160_ROUND_STEPS: usize = 16;
+pub const RIPEMD160_STATE_WORDS: usize = 5;
+pub const RIPEMD160_BLOCK_WORDS: usize = 16;
+/// Words are constrained as bits, and sent on the bus as 16-bit limbs.
+pub const WORD_BITS: usize = 32;
+pub const LIMB_BITS: usize = 16;
+pub const WORD_LIMBS: usize = WORD_BITS / LIMB_BITS;
+/// The number of fields of a lookup on the compression bus: the chaining value, the block and
+/// the new chaining value, as 16-bit limbs.
+pub const RIPEMD160_BUS_WIDTH: usize =
+    (RIPEMD160_STATE_WORDS + RIPEMD160_BLOCK_WORDS + RIPEMD160_STATE_WORDS) * WORD_LIMBS;
+
+/// The intermediate values of one step of a line, which updates the words `[a, b, c, d, e]` to
+/// `[e, t, b, c <<< 10, d]`.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct Ripemd160StepCols<T> {
+    /// `b ^ c`, `b | !c` or `c | !d` in the rounds whose boolean function is computed from it,
+    /// and zero otherwise. Storing it keeps the boolean functions at degree 2.
+    pub aux: [T; WORD_BITS],
+    /// `a + f(b, c, d) + x + k`.
+    pub sum: [T; WORD_BITS],
+    /// `(sum <<< s) + e`.
+    pub t: [T; WORD_BITS],
+    /// The carries out of each limb of `sum`, in `{0, 1, 2, 3}`, as two bits each.
+    pub sum_carry: [[T; 2]; WORD_LIMBS],
+    /// The carries out of each limb of `t`, in `{0, 1}`.
+    pub t_carry: [T; WORD_LIMBS],
+}
+
+// Each compression takes 5 rows, one per round. Row `r` of a compression holds the words of both
+// lines before round `r` in bits, and the 16 steps of each line in the round. The chaining value,
+// the block and the new chaining value are copied to all rows of the compression, and the
+// compression is added to the bus on its first row.
+#[repr(C)]
+#[derive(AlignedBorrow)]
+pub struct Ripemd160CompressCols<T> {
+    /// One-hot round indicator, all zero on padding rows.
+    pub round: [T; RIPEMD160_ROUNDS],
+    /// The number of lookups of the compression, only nonzero on the first row.
+    pub mult: T,
+    pub left: [[T; WORD_BITS]; RIPEMD160_STATE_WORDS],
+    pub right: [[T; WORD_BITS]; RIPEMD160_STATE_WORDS],
+    pub input: [[T; WORD_LIMBS]; RIPEMD160_STATE_WORDS],
+    pub msg: [[T; WORD_LIMBS]; RIPEMD160_BLOCK_WORDS],
+    pub left_steps: [Ripemd160StepCols<T>; RIPEMD160_ROUND_STEPS],
+    pub right_steps: [Ripemd160StepCols<T>; RIPEMD160_ROUND_STEPS],
+    /// The output limbs are not range checked here: they are looked up by the instructions, which
+    /// range check them.
+    pub output: [[T; WORD_LIMBS]; RIPEMD160_STATE_WORDS],
+    /// The carries out of each limb of the output words, in `{0, 1, 2}`.
+    pub output_carry: [[T; WORD_LIMBS]; RIPEMD160_STATE_WORDS],
+}
+
+#[derive(Clone, Copy, Debug, derive_new::new)]
+pub struct Ripemd160CompressAir {
+    pub bus: LookupBus,
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for Ripemd160CompressAir {}
+impl<F: Field> PartitionedBaseAir<F> for Ripemd160CompressAir {}
+impl<F: Field> BaseAir<F> for Ripemd160CompressAir {
+    fn width(&self) -> usize {
+        Ripemd160CompressCols::<F>::width()
+    }
+}
+
+/// The constants of one of the two lines.
+struct Line {
+    k: [u32; RIPEMD160_ROUNDS],
+    r: [[usize; RIPEMD160_ROUND_STEPS]; RIPEMD160_ROUNDS],
+    s: [[u32; RIPEMD160_ROUND_STEPS]; RIPEMD160_ROUNDS],
+    /// Whether the boolean functions are used in reverse order.
+    reversed: bool,
+}
+
+impl Line {
+    fn f_index(&self, round: usize) -> usize {
+        if self.reversed {
+            RIPEMD160_ROUNDS - 1 - round
+        } else {
+            round
+        }
+    }
+}
+
+const LEFT: Line = Line {
+    k: K_LEFT,
+    r: R_LEFT,
+    s: S_LEFT,
+    reversed: false,
+};
+const RIGHT: Line = Line {
+    k: K_RIGHT,
+    r: R_RIGHT,
+    s: S_RIGHT,
+    reversed: true,
+};
+
+impl<AB: InteractionBuilder> Air<AB> for Ripemd160CompressAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &Ripemd160CompressCols<AB::Var> = (*local).borrow();
+        let next: &Ripemd160CompressCols<AB::Var> = (*next).borrow();
+
+        // A compression starts with round 0 and goes through every round on consecutive rows.
+        for flag in local.round {
+            builder.assert_bool(flag);
+        }
+        let is_enabled = sum::<AB>(&local.round);
+        let is_not_last_round = sum::<AB>(&local.round[..RIPEMD160_ROUNDS - 1]);
+        builder.assert_bool(is_enabled);
+        builder
+            .when_first_row()
+            .assert_zero(sum::<AB>(&local.round[1..]));
+        for r in 0..RIPEMD160_ROUNDS - 1 {
+            builder
+                .when_transition()
+                .assert_eq(next.round[r + 1], local.round[r]);
+        }
+        builder
+            .when_last_row()
+            .assert_zero(is_not_last_round.clone());
+        builder.assert_zero(local.mult * (AB::Expr::ONE - local.round[0]));
+
+        // Both lines start from the chaining value.
+        for bit in [local.left, local.right].as_flattened().as_flattened() {
+            builder.assert_bool(*bit);
+        }
+        for i in 0..RIPEMD160_STATE_WORDS {
+            for (&left, &right) in local.left[i].iter().zip(&local.right[i]) {
+                builder.when(local.round[0]).assert_eq(left, right);
+            }
+            for (&limb, expected) in local.input[i]
+                .iter()
+                .zip(limbs(&local.left[i].map(Into::<AB::Expr>::into)))
+            {
+                builder.when(local.round[0]).assert_eq(limb, expected);
+            }
+        }
+
+        let left = eval_round(
+            builder,
+            &local.round,
+            &LEFT,
+            &local.left,
+            &local.left_steps,
+            &local.msg,
+        );
+        let right = eval_round(
+            builder,
+            &local.round,
+            &RIGHT,
+            &local.right,
+            &local.right_steps,
+            &local.msg,
+        );
+
+        // Hand the words of both lines to the next round, and copy the chaining value, the block
+        // and the output.
+        let mut when_next_round = builder.when_transition();
+        let mut when_next_round = when_next_round.when(is_not_last_round);
+        for (next_words, words) in [(&next.left, &left), (&next.right, &right)] {
+            for (next_word, word) in next_words.iter().zip(words) {
+                for (&next_bit, bit) in next_word.iter().zip(word) {
+                    when_next_round.assert_eq(next_bit, bit.clone());
+                }
+            }
+        }
+        for (next_limbs, local_limbs) in [
+            (next.input.as_flattened(), local.input.as_flattened()),
+            (next.msg.as_flattened(), local.msg.as_flattened()),
+            (next.output.as_flattened(), local.output.as_flattened()),
+        ] {
+            for (&next_limb, &limb) in next_limbs.iter().zip(local_limbs) {
+                when_next_round.assert_eq(next_limb, limb);
+            }
+        }
+
+        // After the last round, word `i` of the output is the sum of words `i + 1` of the
+        // chaining value, `i + 2` of the left line and `i + 3` of the right line.
+        for carry in local.output_carry.as_flattened() {
+            builder.assert_zero(*carry * (AB::Expr::ONE - *carry) * (AB::Expr::TWO - *carry));
+        }
+        for i in 0..RIPEMD160_STATE_WORDS {
+            let summands = [
+                local.input[(i + 1) % RIPEMD160_STATE_WORDS].map(Into::into),
+                limbs(&left[(i + 2) % RIPEMD160_STATE_WORDS]),
+                limbs(&right[(i + 3) % RIPEMD160_STATE_WORDS]),
+            ];
+            let mut carry_in = AB::Expr::ZERO;
+            for j in 0..WORD_LIMBS {
+                let total = summands
+                    .iter()
+                    .fold(carry_in, |acc, summand| acc + summand[j].clone());
+                builder.when(local.round[RIPEMD160_ROUNDS - 1]).assert_eq(
+                    total,
+                    local.output[i][j]
+                        + local.output_carry[i][j] * AB::Expr::from_canonical_u32(1 << LIMB_BITS),
+                );
+                carry_in = local.output_carry[i][j].into();
+            }
+        }
+
+        let fields = local
+            .input
+            .iter()
+            .chain(&local.msg)
+            .chain(&local.output)
+            .flat_map(|word| word.map(Into::into));
+        self.bus.add_key_with_lookups(builder, fields, local.mult);
+    }
+}
+
+fn sum<AB: AirBuilder>(vars: &[AB::Var]) -> AB::Expr {
+    vars.iter().fold(AB::Expr::ZERO, |acc, &var| acc + var)
+}
+
+fn xor<E: FieldAlgebra>(x: E, y: E) -> E {
+    x.clone() + y.clone() - x * y * E::TWO
+}
+
+fn limbs<E: FieldAlgebra>(bits: &[E; WORD_BITS]) -> [E; WORD_LIMBS] {
+    from_fn(|i| compose(&bits[i * LIMB_BITS..(i + 1) * LIMB_BITS], 1))
+}
+
+/// Bit `j` of `x` rotated left by `n` is bit `j - n` of `x`.
+fn rotate_left<E: Clone>(bits: &[E; WORD_BITS], n: u32) -> [E; WORD_BITS] {
+    from_fn(|j| bits[(j + WORD_BITS - n as usize) % WORD_BITS].clone())
+}
+
+/// The value of `aux` for the boolean function `f_index` on bits `b, c, d`, if it uses one.
+fn aux_bit<E: FieldAlgebra>(f_index: usize, b: E, c: E, d: E) -> Option<E> {
+    match f_index {
+        0 => Some(xor(b, c)),
+        2 => Some(E::ONE - c.clone() + b * c),
+        4 => Some(E::ONE - d.clone() + c * d),
+        _ => None,
+    }
+}
+
+/// The boolean function `f_index` on bits `b, c, d`, using `aux` from [aux_bit]:
+/// ```text
+/// f0 = b ^ c ^ d,  f1 = (b & c) | (!b & d),  f2 = (b | !c) ^ d,
+/// f3 = (b & d) | (c & !d),  f4 = b ^ (c | !d).
+/// ```
+fn f_bit<E: FieldAlgebra>(f_index: usize, b: E, c: E, d: E, aux: E) -> E {
+    match f_index {
+        0 | 2 => xor(aux, d),
+        1 => b.clone() * c + (E::ONE - b) * d,
+        3 => b * d.clone() + c * (E::ONE - d),
+        _ => xor(b, aux),
+    }
+}
+
+/// Constrains the 16 steps of a round of `line` starting from the words `state`, and returns the
+/// words after the round. The round is selected by the one-hot flags `round`, so all constraints
+/// have degree at most 3.
+fn eval_round<AB: AirBuilder>(
+    builder: &mut AB,
+    round: &[AB::Var; RIPEMD160_ROUNDS],
+    line: &Line,
+    state: &[[AB::Var; WORD_BITS]; RIPEMD160_STATE_WORDS],
+    steps: &[Ripemd160StepCols<AB::Var>; RIPEMD160_ROUND_STEPS],
+    msg: &[[AB::Var; WORD_LIMBS]; RIPEMD160_BLOCK_WORDS],
+) -> [[AB::Expr; WORD_BITS]; RIPEMD160_STATE_WORDS] {
+    let expr = |bits: &[AB::Var; WORD_BITS]| bits.map(Into::<AB::Expr>::into);
+    let mut words = state.each_ref().map(expr);
+    for (j, step) in steps.iter().enumerate() {
+        for bit in step
+            .sum
+            .iter()
+            .chain(&step.t)
+            .chain(step.sum_carry.as_flattened())
+        {
+            builder.assert_bool(*bit);
+        }
+        for carry in step.t_carry {
+            builder.assert_bool(carry);
+        }
+
+        let [a, b, c, d, e] = words;
+        let mut f: [AB::Expr; WORD_BITS] = from_fn(|_| AB::Expr::ZERO);
+        let mut x: [AB::Expr; WORD_LIMBS] = from_fn(|_| AB::Expr::ZERO);
+        let mut k: [AB::Expr; WORD_LIMBS] = from_fn(|_| AB::Expr::ZERO);
+        let mut rotated: [AB::Expr; WORD_BITS] = from_fn(|_| AB::Expr::ZERO);
+        let mut aux: [AB::Expr; WORD_BITS] = from_fn(|_| AB::Expr::ZERO);
+        let sum_bits = expr(&step.sum);
+        for (r, &flag) in round.iter().enumerate() {
+            let f_index = line.f_index(r);
+            for i in 0..WORD_BITS {
+                let (b, c, d) = (b[i].clone(), c[i].clone(), d[i].clone());
+                if let Some(expected) = aux_bit(f_index, b.clone(), c.clone(), d.clone()) {
+                    aux[i] += expected * flag;
+                }
+                f[i] += f_bit(f_index, b, c, d, step.aux[i].into()) * flag;
+            }
+            for (limb, &msg_limb) in x.iter_mut().zip(&msg[line.r[r][j]]) {
+                *limb += msg_limb * flag;
+            }
+            for (i, limb) in k.iter_mut().enumerate() {
+                let k_limb = (line.k[r] >> (i * LIMB_BITS)) & ((1 << LIMB_BITS) - 1);
+                *limb += AB::Expr::from_canonical_u32(k_limb) * flag;
+            }
+            for (bit, rotated_bit) in rotated.iter_mut().zip(rotate_left(&sum_bits, line.s[r][j])) {
+                *bit += rotated_bit * flag;
+            }
+        }
+        for (&bit, expected) in step.aux.iter().zip(aux) {
+            builder.assert_eq(bit, expected);
+        }
+
+        let sum_carry = step
+            .sum_carry
+            .map(|[lo, hi]| AB::Expr::from(lo) + AB::Expr::TWO * hi);
+        eval_add(
+            builder,
+            &[limbs(&a), limbs(&f), x, k],
+            &sum_bits,
+            &sum_carry,
+        );
+        let t = expr(&step.t);
+        eval_add(
+            builder,
+            &[limbs(&rotated), limbs(&e)],
+            &t,
+            &step.t_carry.map(Into::into),
+        );
+
+        words = [e, t, b, rotate_left(&c, 10), d];
+    }
+    words
+}
+
+/// Constrains `sum` to be the sum of `summands` modulo `2^32`, with the given carries out of
+/// each limb. The carry out of the high limb is dropped.
+fn eval_add<AB: AirBuilder>(
+    builder: &mut AB,
+    summands: &[[AB::Expr; WORD_LIMBS]],
+    sum: &[AB::Expr; WORD_BITS],
+    carry: &[AB::Expr; WORD_LIMBS],
+) {
+    let sum = limbs(sum);
+    let mut carry_in = AB::Expr::ZERO;
+    for i in 0..WORD_LIMBS {
+        let total = summands
+            .iter()
+            .fold(carry_in, |acc, summand| acc + summand[i].clone());
+        builder.assert_eq(
+            total,
+            sum[i].clone() + carry[i].clone() * AB::Expr::from_canonical_u32(1 << LIMB_BITS),
+        );
+        carry_in = carry[i].clone();
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Ripemd160CompressRecord {
+    pub state: [u32; RIPEMD160_STATE_WORDS],
+    pub block: [u32; RIPEMD160_BLOCK_WORDS],
+}
+
+/// Proves the RIPEMD-160 compressions looked up on its bus by the RIPEMD-160 instructions.
+#[derive(Debug)]
+pub struct Ripemd160CompressChip {
+    pub air: Ripemd160CompressAir,
+    records: Mutex<Vec<Ripemd160CompressRecord>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct SharedRipemd160CompressChip(Arc<Ripemd160CompressChip>);
+
+impl Ripemd160CompressChip {
+    pub fn new(bus: LookupBus) -> Self {
+        Self {
+            air: Ripemd160CompressAir::new(bus),
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn bus(&self) -> LookupBus {
+        self.air.bus
+    }
+
+    /// Returns the new chaining value of the compression of `block` into `state`, and records
+    /// the compression to be proven.
+    pub fn compress_and_record(
+        &self,
+        state: [u32; RIPEMD160_STATE_WORDS],
+        block: [u32; RIPEMD160_BLOCK_WORDS],
+    ) -> [u32; RIPEMD160_STATE_WORDS] {
+        let output = ripemd160_compress(&state, &block);
+        self.records
+            .lock()
+            .unwrap()
+            .push(Ripemd160CompressRecord { state, block });
+        output
+    }
+
+    /// Generates the trace of the compressions recorded so far, and clears them.
+    pub fn generate_trace<F: PrimeField32>(&self) -> RowMajorMatrix<F> {
+        let records = std::mem::take(&mut *self.records.lock().unwrap());
+        let width = Ripemd160CompressCols::<F>::width();
+        let height = next_power_of_two_or_zero(records.len() * RIPEMD160_ROUNDS);
+        let mut values = F::zero_vec(height * width);
+        values
+            .par_chunks_mut(width * RIPEMD160_ROUNDS)
+            .zip(records.par_iter())
+            .for_each(|(rows, record)| generate_compression_rows(rows, record));
+        RowMajorMatrix::new(values, width)
+    }
+}
+
+impl SharedRipemd160CompressChip {
+    pub fn new(bus: LookupBus) -> Self {
+        Self(Arc::new(Ripemd160CompressChip::new(bus)))
+    }
+
+    pub fn bus(&self) -> LookupBus {
+        self.0.bus()
+    }
+
+    pub fn compress_and_record(
+        &self,
+        state: [u32; RIPEMD160_STATE_WORDS],
+        block: [u32; RIPEMD160_BLOCK_WORDS],
+    ) -> [u32; RIPEMD160_STATE_WORDS] {
+        self.0.compress_and_record(state, block)
+    }
+}
+
+fn generate_compression_rows<F: PrimeField32>(rows: &mut [F], record: &Ripemd160CompressRecord) {
+    let width = Ripemd160CompressCols::<F>::width();
+    let mut left = record.state;
+    let mut right = record.state;
+    let output = ripemd160_compress(&record.state, &record.block);
+    for (r, row) in rows.chunks_exact_mut(width).enumerate() {
+        let cols: &mut Ripemd160CompressCols<F> = row.borrow_mut();
+        cols.round[r] = F::ONE;
+        if r == 0 {
+            cols.mult = F::ONE;
+        }
+        cols.left = left.map(word_bits);
+        cols.right = right.map(word_bits);
+        cols.input = record.state.map(word_limbs);
+        cols.msg = record.block.map(word_limbs);
+        for j in 0..RIPEMD160_ROUND_STEPS {
+            left = generate_step(&mut cols.left_steps[j], &LEFT, r, j, left, &record.block);
+            right = generate_step(&mut cols.right_steps[j], &RIGHT, r, j, right, &record.block);
+        }
+        cols.output = output.map(word_limbs);
+        if r == RIPEMD160_ROUNDS - 1 {
+            for i in 0..RIPEMD160_STATE_WORDS {
+                let (_, carry) = add_with_carries(&[
+                    record.state[(i + 1) % RIPEMD160_STATE_WORDS],
+                    left[(i + 2) % RIPEMD160_STATE_WORDS],
+                    right[(i + 3) % RIPEMD160_STATE_WORDS],
+                ]);
+                cols.output_carry[i] = carry.map(F::from_canonical_u32);
+            }
+        }
+    }
+}
+
+fn generate_step<F: PrimeField32>(
+    step: &mut Ripemd160StepCols<F>,
+    line: &Line,
+    r: usize,
+    j: usize,
+    [a, b, c, d, e]: [u32; RIPEMD160_STATE_WORDS],
+    block: &[u32; RIPEMD160_BLOCK_WORDS],
+) -> [u32; RIPEMD160_STATE_WORDS] {
+    let (aux, f) = match line.f_index(r) {
+        0 => (b ^ c, b ^ c ^ d),
+        1 => (0, (b & c) | (!b & d)),
+        2 => (b | !c, (b | !c) ^ d),
+        3 => (0, (b & d) | (c & !d)),
+        _ => (c | !d, b ^ (c | !d)),
+    };
+    let (sum, sum_carry) = add_with_carries(&[a, f, block[line.r[r][j]], line.k[r]]);
+    let (t, t_carry) = add_with_carries(&[sum.rotate_left(line.s[r][j]), e]);
+
+    step.aux = word_bits(aux);
+    step.sum = word_bits(sum);
+    step.t = word_bits(t);
+    step.sum_carry = sum_carry.map(|carry| [carry & 1, carry >> 1].map(F::from_canonical_u32));
+    step.t_carry = t_carry.map(F::from_canonical_u32);
+    [e, t, b, c.rotate_left(10), d]
+}
+
+/// The sum modulo `2^32` and the carries out of each 16-bit limb.
+fn add_with_carries(summands: &[u32]) -> (u32, [u32; WORD_LIMBS]) {
+    let lo: u32 = summands.iter().map(|x| x & 0xffff).sum();
+    let hi: u32 = summands.iter().map(|x| x >> LIMB_BITS).sum::<u32>() + (lo >> LIMB_BITS);
+    (
+        ((hi & 0xffff) << LIMB_BITS) | (lo & 0xffff),
+        [lo >> LIMB_BITS, hi >> LIMB_BITS],
+    )
+}
+
+fn word_bits<F: FieldAlgebra>(word: u32) -> [F; WORD_BITS] {
+    from_fn(|j| F::from_bool((word >> j) & 1 == 1))
+}
+
+fn word_limbs<F: FieldAlgebra>(word: u32) -> [F; WORD_LIMBS] {
+    from_fn(|i| F::from_canonical_u32((word >> (i * LIMB_BITS)) & 0xffff))
+}
+
+impl<SC: StarkGenericConfig> Chip<SC> for Ripemd160CompressChip
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        Arc::new(self.air)
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        AirProofInput::simple_no_pis(self.generate_trace::<Val<SC>>())
+    }
+}
+
+impl<SC: StarkGenericConfig> Chip<SC> for SharedRipemd160CompressChip
+where
+    Val<SC>: PrimeField32,
+{
+    fn air(&self) -> AirRef<SC> {
+        self.0.air()
+    }
+
+    fn generate_air_proof_input(self) -> AirProofInput<SC> {
+        AirProofInput::simple_no_pis(self.0.generate_trace::<Val<SC>>())
+    }
+}
+
+impl ChipUsageGetter for Ripemd160CompressChip {
+    fn air_name(&self) -> String {
+        get_air_name(&self.air)
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.records.lock().unwrap().len() * RIPEMD160_ROUNDS
+    }
+
+    fn trace_width(&self) -> usize {
+        Ripemd160CompressCols::<u8>::width()
+    }
+}
+
+impl ChipUsageGetter for SharedRipemd160CompressChip {
+    fn air_name(&self) -> String {
+        self.0.air_name()
+    }
+
+    fn current_trace_height(&self) -> usize {
+        self.0.current_trace_height()
+    }
+
+    fn trace_width(&self) -> usize {
+        self.0.trace_width()
+    }
+}
+
+impl AsRef<Ripemd160CompressChip> for SharedRipemd160CompressChip {
+    fn as_ref(&self) -> &Ripemd160CompressChip {
+        &self.0
+    }
+}
@@ -0,0 +1,140 @@
+use std::{array::from_fn, borrow::BorrowMut};
+
+use openvm_circuit::arch::testing::{VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_instructions::{riscv::RV32_CELL_BITS, LocalOpcode};
+use openvm_ripemd160_guest::{ripemd160_compress, IV};
+use openvm_ripemd160_transpiler::Rv32Ripemd160Opcode;
+use openvm_rv32_adapters::{rv32_write_heap_default, Rv32VecHeapTwoReadsAdapterChip};
+use openvm_stark_backend::{
+    interaction::{BusIndex, LookupBus},
+    p3_field::{FieldAlgebra, PrimeField32},
+    p3_matrix::Matrix,
+    utils::disable_debug_builder,
+    verifier::VerificationError,
+};
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, utils::create_seeded_rng};
+use rand::Rng;
+
+use crate::{
+    Ripemd160CoreChip, Ripemd160CoreCols, Rv32Ripemd160Chip, SharedRipemd160CompressChip,
+    RIPEMD160_BLOCK_WORDS, RIPEMD160_STATE_WORDS,
+};
+
+type F = BabyBear;
+
+const RIPEMD160_COMPRESS_BUS: BusIndex = 13;
+
+fn to_heap(words: &[u32]) -> Vec<[F; 4]> {
+    words
+        .iter()
+        .map(|word| word.to_le_bytes().map(F::from_canonical_u8))
+        .collect()
+}
+
+fn build_ripemd160_test(
+    inputs: Vec<([u32; RIPEMD160_STATE_WORDS], [u32; RIPEMD160_BLOCK_WORDS])>,
+) -> (
+    VmChipTestBuilder<F>,
+    Rv32Ripemd160Chip<F>,
+    SharedRipemd160CompressChip,
+    SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+) {
+    let mut tester = VmChipTestBuilder::default();
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let compress_chip = SharedRipemd160CompressChip::new(LookupBus::new(RIPEMD160_COMPRESS_BUS));
+    let mut chip = Rv32Ripemd160Chip::new(
+        Rv32VecHeapTwoReadsAdapterChip::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        ),
+        Ripemd160CoreChip::new(
+            compress_chip.clone(),
+            bitwise_chip.clone(),
+            Rv32Ripemd160Opcode::CLASS_OFFSET,
+        ),
+        tester.offline_memory_mutex_arc(),
+    );
+
+    for (state, block) in inputs {
+        let instruction = rv32_write_heap_default(
+            &mut tester,
+            to_heap(&state),
+            to_heap(&block),
+            Rv32Ripemd160Opcode::COMPRESS.global_opcode().as_usize(),
+        );
+        tester.execute(&mut chip, &instruction);
+
+        let rd_ptr = instruction.a.as_canonical_u32() as usize;
+        let out_ptr = u32::from_le_bytes(
+            tester
+                .read::<4>(1, rd_ptr)
+                .map(|x| x.as_canonical_u32() as u8),
+        ) as usize;
+        let output: [u32; RIPEMD160_STATE_WORDS] = from_fn(|i| {
+            u32::from_le_bytes(
+                tester
+                    .read::<4>(2, out_ptr + 4 * i)
+                    .map(|x| x.as_canonical_u32() as u8),
+            )
+        });
+        assert_eq!(output, ripemd160_compress(&state, &block));
+    }
+    (tester, chip, compress_chip, bitwise_chip)
+}
+
+#[test]
+fn test_ripemd160_compress() {
+    let mut rng = create_seeded_rng();
+    let mut inputs: Vec<_> = (0..6)
+        .map(|_| (from_fn(|_| rng.gen()), from_fn(|_| rng.gen())))
+        .collect();
+    inputs.push((IV, from_fn(|_| rng.gen())));
+    inputs.push(([0; RIPEMD160_STATE_WORDS], [0; RIPEMD160_BLOCK_WORDS]));
+    inputs.push((
+        [u32::MAX; RIPEMD160_STATE_WORDS],
+        [u32::MAX; RIPEMD160_BLOCK_WORDS],
+    ));
+
+    let (tester, chip, compress_chip, bitwise_chip) = build_ripemd160_test(inputs);
+    let tester = tester
+        .build()
+        .load(chip)
+        .load(compress_chip)
+        .load(bitwise_chip)
+        .finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+// An output that is not the compression of the inputs has no matching compression on the bus.
+#[test]
+fn test_ripemd160_compress_wrong_output() {
+    let (tester, chip, compress_chip, bitwise_chip) =
+        build_ripemd160_test(vec![(IV, [2; RIPEMD160_BLOCK_WORDS])]);
+    let mut tester = tester
+        .build()
+        .load(chip)
+        .load(compress_chip)
+        .load(bitwise_chip)
+        .finalize();
+
+    let trace = tester.air_proof_inputs[2]
+        .1
+        .raw
+        .common_main
+        .as_mut()
+        .unwrap();
+    let core_start = trace.width() - Ripemd160CoreCols::<F>::width();
+    let row = trace.row_mut(0);
+    let cols: &mut Ripemd160CoreCols<F> = row[core_start..].borrow_mut();
+    cols.output[0][0] = F::from_canonical_u32((cols.output[0][0].as_canonical_u32() + 1) % 256);
+
+    disable_debug_builder();
+    tester.simple_test_with_expected_error(VerificationError::ChallengePhaseError);
+}
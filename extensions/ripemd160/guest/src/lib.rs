@@ -0,0 +1,168 @@
+#![no_std]
+
+//! The RIPEMD-160 hash function.
+//!
+//! In the zkVM, [ripemd160_compress] is a single instruction of the RIPEMD-160 extension, and
+//! [ripemd160] pads the message and compresses it block by block. Outside the zkVM the
+//! compression function falls back to a portable implementation.
+
+/// This is custom-0 defined in RISC-V spec document
+pub const OPCODE: u8 = 0x0b;
+pub const RIPEMD160_FUNCT3: u8 = 0b100;
+/// Compresses the chaining value at `rs1` with the block at `rs2` and writes the new chaining
+/// value to `rd`.
+pub const RIPEMD160_FUNCT7: u8 = 0x8;
+
+openvm_platform::require_extension_version!("ripemd160", 1);
+
+/// The number of bytes of a RIPEMD-160 digest.
+pub const RIPEMD160_OUT_LEN: usize = 20;
+/// The number of bytes of a message block.
+pub const RIPEMD160_BLOCK_LEN: usize = 64;
+
+pub const IV: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+
+/// The constants added in each round of the left line.
+pub const K_LEFT: [u32; 5] = [0x00000000, 0x5A827999, 0x6ED9EBA1, 0x8F1BBCDC, 0xA953FD4E];
+/// The constants added in each round of the right line.
+pub const K_RIGHT: [u32; 5] = [0x50A28BE6, 0x5C4DD124, 0x6D703EF3, 0x7A6D76E9, 0x00000000];
+
+/// The message word used by each step of each round of the left line.
+pub const R_LEFT: [[usize; 16]; 5] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+    [7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5, 2, 14, 11, 8],
+    [3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12],
+    [1, 9, 11, 10, 0, 8, 12, 4, 13, 3, 7, 15, 14, 5, 6, 2],
+    [4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13],
+];
+/// The message word used by each step of each round of the right line.
+pub const R_RIGHT: [[usize; 16]; 5] = [
+    [5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12],
+    [6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12, 4, 9, 1, 2],
+    [15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13],
+    [8, 6, 4, 1, 3, 11, 15, 0, 5, 12, 2, 13, 9, 7, 10, 14],
+    [12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11],
+];
+/// The left rotation of each step of each round of the left line.
+pub const S_LEFT: [[u32; 16]; 5] = [
+    [11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8],
+    [7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15, 9, 11, 7, 13, 12],
+    [11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5],
+    [11, 12, 14, 15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12],
+    [9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6],
+];
+/// The left rotation of each step of each round of the right line.
+pub const S_RIGHT: [[u32; 16]; 5] = [
+    [8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6],
+    [9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12, 7, 6, 15, 13, 11],
+    [9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5],
+    [15, 5, 8, 11, 14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8],
+    [8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11],
+];
+
+/// The RIPEMD-160 compression function.
+///
+/// The instruction reads the 5 words of `state` from `rs1` and the 16 words of `block` from
+/// `rs2`.
+#[inline(always)]
+pub fn ripemd160_compress(state: &[u32; 5], block: &[u32; 16]) -> [u32; 5] {
+    #[cfg(target_os = "zkvm")]
+    {
+        let mut output = core::mem::MaybeUninit::<[u32; 5]>::uninit();
+        openvm_platform::custom_insn_r!(
+            opcode = OPCODE,
+            funct3 = RIPEMD160_FUNCT3,
+            funct7 = RIPEMD160_FUNCT7,
+            rd = In output.as_mut_ptr(),
+            rs1 = In state.as_ptr(),
+            rs2 = In block.as_ptr()
+        );
+        unsafe { output.assume_init() }
+    }
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        let mut left = *state;
+        let mut right = *state;
+        for round in 0..5 {
+            for j in 0..16 {
+                left = step(
+                    left,
+                    round,
+                    block[R_LEFT[round][j]],
+                    K_LEFT[round],
+                    S_LEFT[round][j],
+                );
+                right = step(
+                    right,
+                    4 - round,
+                    block[R_RIGHT[round][j]],
+                    K_RIGHT[round],
+                    S_RIGHT[round][j],
+                );
+            }
+        }
+        [
+            state[1].wrapping_add(left[2]).wrapping_add(right[3]),
+            state[2].wrapping_add(left[3]).wrapping_add(right[4]),
+            state[3].wrapping_add(left[4]).wrapping_add(right[0]),
+            state[4].wrapping_add(left[0]).wrapping_add(right[1]),
+            state[0].wrapping_add(left[1]).wrapping_add(right[2]),
+        ]
+    }
+}
+
+/// One step of either line on the words `[a, b, c, d, e]`, where `f` is the index of the boolean
+/// function of the step.
+#[cfg(not(target_os = "zkvm"))]
+#[inline(always)]
+fn step([a, b, c, d, e]: [u32; 5], f: usize, x: u32, k: u32, s: u32) -> [u32; 5] {
+    let f = match f {
+        0 => b ^ c ^ d,
+        1 => (b & c) | (!b & d),
+        2 => (b | !c) ^ d,
+        3 => (b & d) | (c & !d),
+        _ => b ^ (c | !d),
+    };
+    let t = a
+        .wrapping_add(f)
+        .wrapping_add(x)
+        .wrapping_add(k)
+        .rotate_left(s)
+        .wrapping_add(e);
+    [e, t, b, c.rotate_left(10), d]
+}
+
+/// Hashes `input` with RIPEMD-160.
+pub fn ripemd160(input: &[u8]) -> [u8; RIPEMD160_OUT_LEN] {
+    let mut state = IV;
+    let mut blocks = input.chunks_exact(RIPEMD160_BLOCK_LEN);
+    for block in &mut blocks {
+        state = ripemd160_compress(&state, &to_words(block.try_into().unwrap()));
+    }
+
+    // The message is followed by a 1 bit, zeros, and the length in bits as a little-endian u64,
+    // which takes one more block if it does not fit after the remainder.
+    let remainder = blocks.remainder();
+    let mut last = [0u8; 2 * RIPEMD160_BLOCK_LEN];
+    last[..remainder.len()].copy_from_slice(remainder);
+    last[remainder.len()] = 0x80;
+    let padded_len = if remainder.len() < RIPEMD160_BLOCK_LEN - 8 {
+        RIPEMD160_BLOCK_LEN
+    } else {
+        2 * RIPEMD160_BLOCK_LEN
+    };
+    last[padded_len - 8..padded_len].copy_from_slice(&((input.len() as u64) << 3).to_le_bytes());
+    for block in last[..padded_len].chunks_exact(RIPEMD160_BLOCK_LEN) {
+        state = ripemd160_compress(&state, &to_words(block.try_into().unwrap()));
+    }
+
+    let mut output = [0u8; RIPEMD160_OUT_LEN];
+    for (bytes, word) in output.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    output
+}
+
+fn to_words(block: &[u8; RIPEMD160_BLOCK_LEN]) -> [u32; 16] {
+    core::array::from_fn(|i| u32::from_le_bytes(block[4 * i..4 * i + 4].try_into().unwrap()))
+}
@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::io::read;
+use openvm_ripemd160_guest::{ripemd160, RIPEMD160_OUT_LEN};
+use openvm_sha2::sha256;
+
+openvm::entry!(main);
+
+pub fn main() {
+    let inputs: Vec<Vec<u8>> = read();
+    let digests: Vec<[u8; RIPEMD160_OUT_LEN]> = read();
+    let hash160s: Vec<[u8; RIPEMD160_OUT_LEN]> = read();
+    assert_eq!(inputs.len(), digests.len());
+    assert_eq!(inputs.len(), hash160s.len());
+    for ((input, expected), expected_hash160) in inputs.iter().zip(digests).zip(hash160s) {
+        assert_eq!(ripemd160(input), expected);
+        // The `hash160` of Bitcoin addresses.
+        assert_eq!(ripemd160(&sha256(input)), expected_hash160);
+    }
+}
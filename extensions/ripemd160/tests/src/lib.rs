@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+    use openvm_circuit::{arch::instructions::exe::VmExe, utils::air_test_with_min_segments};
+    use openvm_ripemd160_guest::ripemd160;
+    use openvm_sdk::{
+        config::{AppConfig, SdkVmConfig},
+        StdIn,
+    };
+    use openvm_stark_sdk::utils::create_seeded_rng;
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::FromElf;
+    use rand::Rng;
+    use sha2::{Digest, Sha256};
+
+    fn ripemd160_config() -> Result<SdkVmConfig> {
+        Ok(toml::from_str::<AppConfig<SdkVmConfig>>(include_str!(
+            "../programs/openvm_ripemd160.toml"
+        ))?
+        .app_vm_config)
+    }
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_ripemd160() -> Result<()> {
+        let mut rng = create_seeded_rng();
+        let config = ripemd160_config()?;
+        let elf = build_example_program_at_path(get_programs_dir!(), "ripemd160", &config)?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+
+        // Lengths around the block boundaries, where the padding takes one or two blocks, and a
+        // compressed public key.
+        let inputs: Vec<Vec<u8>> = [0, 1, 33, 55, 56, 63, 64, 65, 128, 1000]
+            .into_iter()
+            .map(|len| (0..len).map(|_| rng.gen()).collect())
+            .collect();
+        let digests: Vec<_> = inputs.iter().map(|input| ripemd160(input)).collect();
+        let hash160s: Vec<_> = inputs
+            .iter()
+            .map(|input| ripemd160(&Sha256::digest(input)))
+            .collect();
+
+        let mut input = StdIn::default();
+        input.write(&inputs);
+        input.write(&digests);
+        input.write(&hash160s);
+        air_test_with_min_segments(config, openvm_exe, input, 1);
+        Ok(())
+    }
+
+    /// Test vectors from the RIPEMD-160 specification.
+    #[test]
+    fn test_ripemd160_vectors() {
+        let vectors = [
+            ("", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+            ("a", "0bdc9d2d256b3ee9daae347be6f4dc835a467ffe"),
+            ("abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            ("message digest", "5d0689ef49d2fae572b881b123a85ffa21595f36"),
+            (
+                "abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+                "12a053384a9c0c88e405a06c27dcf49ada62eb2b",
+            ),
+            (
+                "12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            ),
+        ];
+        for (input, expected) in vectors {
+            assert_eq!(hex(&ripemd160(input.as_bytes())), expected);
+        }
+    }
+}
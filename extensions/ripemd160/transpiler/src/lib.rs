@@ -0,0 +1,52 @@
+use openvm_instructions::LocalOpcode;
+use openvm_instructions_derive::LocalOpcode;
+use openvm_ripemd160_guest::{OPCODE, RIPEMD160_FUNCT3, RIPEMD160_FUNCT7};
+use openvm_stark_backend::p3_field::PrimeField32;
+use openvm_transpiler::{
+    metadata::OpcodeMap, util::from_r_type, TranspilerExtension, TranspilerOutput,
+};
+use rrs_lib::instruction_formats::RType;
+use strum::{EnumCount, EnumIter, FromRepr, IntoEnumIterator};
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
+)]
+#[opcode_offset = 0x830]
+#[repr(usize)]
+#[allow(non_camel_case_types)]
+pub enum Rv32Ripemd160Opcode {
+    COMPRESS,
+}
+
+#[derive(Default)]
+pub struct Ripemd160TranspilerExtension;
+
+impl<F: PrimeField32> TranspilerExtension<F> for Ripemd160TranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        if instruction_stream.is_empty() {
+            return None;
+        }
+        let instruction_u32 = instruction_stream[0];
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+
+        if (opcode, funct3) != (OPCODE, RIPEMD160_FUNCT3) {
+            return None;
+        }
+        let dec_insn = RType::new(instruction_u32);
+        if dec_insn.funct7 != RIPEMD160_FUNCT7 as u32 {
+            return None;
+        }
+        let instruction = from_r_type(
+            Rv32Ripemd160Opcode::COMPRESS.global_opcode().as_usize(),
+            2,
+            &dec_insn,
+            true,
+        );
+        Some(TranspilerOutput::one_to_one(instruction))
+    }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default().with_opcodes(Rv32Ripemd160Opcode::iter())
+    }
+}
@@ -0,0 +1,152 @@
+use derive_more::derive::From;
+use openvm_circuit::{
+    arch::{
+        InitFileGenerator, SystemConfig, SystemPort, VmExtension, VmInventory, VmInventoryBuilder,
+        VmInventoryError,
+    },
+    system::phantom::PhantomChip,
+};
+use openvm_circuit_derive::{AnyEnum, InstructionExecutor, VmConfig};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_circuit_primitives_derive::{Chip, ChipUsageGetter};
+use openvm_instructions::LocalOpcode;
+use openvm_rsa_transpiler::Rv32WideMulModOpcode;
+use openvm_rv32_adapters::Rv32VecHeapTwoReadsAdapterChip;
+use openvm_rv32im_circuit::{
+    Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
+    Rv32MExecutor, Rv32MPeriphery,
+};
+use openvm_stark_backend::p3_field::PrimeField32;
+use serde::{Deserialize, Serialize};
+
+use crate::*;
+
+#[derive(Clone, Debug, VmConfig, derive_new::new, Serialize, Deserialize)]
+pub struct RsaRv32Config {
+    #[system]
+    pub system: SystemConfig,
+    #[extension]
+    pub rv32i: Rv32I,
+    #[extension]
+    pub rv32m: Rv32M,
+    #[extension]
+    pub io: Rv32Io,
+    #[extension]
+    pub rsa: Rsa,
+}
+
+impl Default for RsaRv32Config {
+    fn default() -> Self {
+        let mut system = SystemConfig::default().with_continuations();
+        // The carries of 512-limb products need a 19-bit range checker.
+        system.memory_config.decomp = 19;
+        Self {
+            system,
+            rv32i: Rv32I,
+            rv32m: Rv32M::default(),
+            io: Rv32Io,
+            rsa: Rsa,
+        }
+    }
+}
+
+// Default implementation uses no init file
+impl InitFileGenerator for RsaRv32Config {}
+
+/// Wide modular multiplication for RSA. Its chips need a range checker with at least 19 bits, set
+/// by `decomp` in the memory config.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct Rsa;
+
+#[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
+pub enum RsaExecutor<F: PrimeField32> {
+    MulMod2048(Rv32MulMod2048Chip<F>),
+    MulMod4096(Rv32MulMod4096Chip<F>),
+}
+
+#[derive(From, ChipUsageGetter, Chip, AnyEnum)]
+pub enum RsaPeriphery<F: PrimeField32> {
+    BitwiseOperationLookup(SharedBitwiseOperationLookupChip<8>),
+    Phantom(PhantomChip<F>),
+}
+
+impl<F: PrimeField32> VmExtension<F> for Rsa {
+    type Executor = RsaExecutor<F>;
+    type Periphery = RsaPeriphery<F>;
+
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("rsa", 1))
+    }
+
+    fn build(
+        &self,
+        builder: &mut VmInventoryBuilder<F>,
+    ) -> Result<VmInventory<Self::Executor, Self::Periphery>, VmInventoryError> {
+        let mut inventory = VmInventory::new();
+        let SystemPort {
+            execution_bus,
+            program_bus,
+            memory_bridge,
+        } = builder.system_port();
+        let range_checker = builder.system_base().range_checker_chip.clone();
+        let bitwise_lu_chip = if let Some(&chip) = builder
+            .find_chip::<SharedBitwiseOperationLookupChip<8>>()
+            .first()
+        {
+            chip.clone()
+        } else {
+            let bitwise_lu_bus = BitwiseOperationLookupBus::new(builder.new_bus_idx());
+            let chip = SharedBitwiseOperationLookupChip::new(bitwise_lu_bus);
+            inventory.add_periphery_chip(chip.clone());
+            chip
+        };
+        let offline_memory = builder.system_base().offline_memory();
+        let address_bits = builder.system_config().memory_config.pointer_max_bits;
+
+        let mulmod_2048_chip = Rv32MulMod2048Chip::new(
+            Rv32VecHeapTwoReadsAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            ),
+            WideMulModCoreChip::new(
+                Rv32WideMulModOpcode::MULMOD_2048,
+                bitwise_lu_chip.clone(),
+                range_checker.clone(),
+                Rv32WideMulModOpcode::CLASS_OFFSET,
+            ),
+            offline_memory.clone(),
+        );
+        inventory.add_executor(
+            mulmod_2048_chip,
+            [Rv32WideMulModOpcode::MULMOD_2048.global_opcode()],
+        )?;
+
+        let mulmod_4096_chip = Rv32MulMod4096Chip::new(
+            Rv32VecHeapTwoReadsAdapterChip::new(
+                execution_bus,
+                program_bus,
+                memory_bridge,
+                address_bits,
+                bitwise_lu_chip.clone(),
+            ),
+            WideMulModCoreChip::new(
+                Rv32WideMulModOpcode::MULMOD_4096,
+                bitwise_lu_chip,
+                range_checker,
+                Rv32WideMulModOpcode::CLASS_OFFSET,
+            ),
+            offline_memory,
+        );
+        inventory.add_executor(
+            mulmod_4096_chip,
+            [Rv32WideMulModOpcode::MULMOD_4096.global_opcode()],
+        )?;
+
+        Ok(inventory)
+    }
+}
@@ -0,0 +1,22 @@
+//! Wide modular multiplication with a modulus read from memory at runtime, for RSA moduli of up
+//! to 4096 bits.
+use openvm_circuit::arch::VmChipWrapper;
+use openvm_rv32_adapters::Rv32VecHeapTwoReadsAdapterChip;
+
+mod extension;
+mod mul_mod;
+pub use extension::*;
+pub use mul_mod::*;
+
+#[cfg(test)]
+mod tests;
+
+/// Reads 256 bytes from `rs1` and 512 bytes (multiplier and modulus) from `rs2`, and writes 256
+/// bytes to `rd`.
+pub type Rv32MulMod2048Chip<F> =
+    VmChipWrapper<F, Rv32VecHeapTwoReadsAdapterChip<F, 8, 16, 8, 32, 32>, WideMulModCoreChip>;
+
+/// Reads 512 bytes from `rs1` and 1024 bytes (multiplier and modulus) from `rs2`, and writes 512
+/// bytes to `rd`.
+pub type Rv32MulMod4096Chip<F> =
+    VmChipWrapper<F, Rv32VecHeapTwoReadsAdapterChip<F, 16, 32, 16, 32, 32>, WideMulModCoreChip>;
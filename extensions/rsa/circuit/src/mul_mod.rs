@@ -0,0 +1,396 @@
+use num_bigint::BigUint;
+use num_traits::Zero;
+use openvm_circuit::arch::{
+    AdapterAirContext, AdapterRuntimeContext, DynAdapterInterface, DynArray, ExecutionError,
+    MinimalInstruction, Result, VmAdapterInterface, VmCoreAir, VmCoreChip,
+};
+use openvm_circuit_primitives::{
+    bigint::{
+        check_carry_to_zero::{
+            get_carry_max_abs_and_bits, CheckCarryToZeroCols, CheckCarryToZeroSubAir,
+        },
+        OverflowInt,
+    },
+    bitwise_op_lookup::{BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip},
+    var_range::{SharedVariableRangeCheckerChip, VariableRangeCheckerBus},
+    SubAir,
+};
+use openvm_instructions::{instruction::Instruction, utils::isize_to_field};
+use openvm_rsa_transpiler::Rv32WideMulModOpcode;
+use openvm_stark_backend::{
+    interaction::InteractionBuilder,
+    p3_air::{AirBuilder, BaseAir},
+    p3_field::{Field, FieldAlgebra, PrimeField32},
+    rap::BaseAirWithPublicValues,
+};
+use serde::{Deserialize, Serialize};
+
+const LIMB_BITS: usize = 8;
+
+// Given x, y and a modulus n read from memory at runtime, all of `num_limbs` bytes, we prove that
+// r = x * y mod n by constraining x * y = q * n + r over the integers, with q and r of `num_limbs`
+// bytes, and r < n. The product is checked limb by limb with carries, so the quotient must fit in
+// `num_limbs` bytes, which holds whenever x, y < n. Execution fails if it does not, or if n is
+// zero.
+//
+// The width depends on `num_limbs`, so the columns are laid out by
+// `WideMulModCoreCols::from_slice`: is_valid, x, y, n, q, r, carries (2 * num_limbs - 1), lt_marker
+// (num_limbs), lt_diff.
+pub struct WideMulModCoreCols<'a, T> {
+    pub is_valid: &'a T,
+    pub x: &'a [T],
+    pub y: &'a [T],
+    pub n: &'a [T],
+    pub q: &'a [T],
+    pub r: &'a [T],
+    // Carries of x * y - q * n - r, which must be zero.
+    pub carries: &'a [T],
+    // Auxiliary columns to ensure r < n. Let diff_idx be the most significant index such that
+    // r[diff_idx] != n[diff_idx], which exists iff r != n. Then lt_marker is 1 at diff_idx and 0
+    // elsewhere, and lt_diff = n[diff_idx] - r[diff_idx] must be in [1, 2^LIMB_BITS].
+    pub lt_marker: &'a [T],
+    pub lt_diff: &'a T,
+}
+
+impl<'a, T> WideMulModCoreCols<'a, T> {
+    pub fn width(num_limbs: usize) -> usize {
+        8 * num_limbs + 1
+    }
+
+    pub fn from_slice(slice: &'a [T], num_limbs: usize) -> Self {
+        assert_eq!(slice.len(), Self::width(num_limbs));
+        let (is_valid, rest) = slice.split_first().unwrap();
+        let (x, rest) = rest.split_at(num_limbs);
+        let (y, rest) = rest.split_at(num_limbs);
+        let (n, rest) = rest.split_at(num_limbs);
+        let (q, rest) = rest.split_at(num_limbs);
+        let (r, rest) = rest.split_at(num_limbs);
+        let (carries, rest) = rest.split_at(2 * num_limbs - 1);
+        let (lt_marker, rest) = rest.split_at(num_limbs);
+        Self {
+            is_valid,
+            x,
+            y,
+            n,
+            q,
+            r,
+            carries,
+            lt_marker,
+            lt_diff: &rest[0],
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct WideMulModCoreAir {
+    pub num_limbs: usize,
+    pub bus: BitwiseOperationLookupBus,
+    pub carry_subair: CheckCarryToZeroSubAir,
+    pub offset: usize,
+    pub local_opcode: Rv32WideMulModOpcode,
+}
+
+impl WideMulModCoreAir {
+    pub fn new(
+        local_opcode: Rv32WideMulModOpcode,
+        bus: BitwiseOperationLookupBus,
+        range_bus: VariableRangeCheckerBus,
+        offset: usize,
+    ) -> Self {
+        let num_limbs = num_limbs(local_opcode);
+        let range_max_bits = range_bus.range_max_bits;
+        let carry_bits = carry_bits(num_limbs);
+        assert!(
+            carry_bits <= range_max_bits,
+            "{local_opcode:?} requires a range checker with at least {carry_bits} bits, but it has {range_max_bits} bits; increase `decomp` in the memory config"
+        );
+        Self {
+            num_limbs,
+            bus,
+            carry_subair: CheckCarryToZeroSubAir::new(LIMB_BITS, range_bus.index(), range_max_bits),
+            offset,
+            local_opcode,
+        }
+    }
+}
+
+/// The number of bytes of the operands of `opcode`.
+pub fn num_limbs(opcode: Rv32WideMulModOpcode) -> usize {
+    match opcode {
+        Rv32WideMulModOpcode::MULMOD_2048 => 256,
+        Rv32WideMulModOpcode::MULMOD_4096 => 512,
+    }
+}
+
+/// The number of bits the range checker must support to check the carries of products of
+/// `num_limbs` bytes.
+pub fn carry_bits(num_limbs: usize) -> usize {
+    let zero = vec![0isize; num_limbs];
+    let expr = mul_mod_expr(&zero, &zero, &zero, &zero, &zero);
+    get_carry_max_abs_and_bits(expr.max_overflow_bits(), LIMB_BITS).1
+}
+
+/// Returns x * y - q * n - r as an overflow integer.
+fn mul_mod_expr<T>(x: &[T], y: &[T], n: &[T], q: &[T], r: &[T]) -> OverflowInt<T>
+where
+    T: Clone
+        + Default
+        + std::ops::Add<Output = T>
+        + std::ops::Sub<Output = T>
+        + std::ops::Mul<Output = T>,
+{
+    let to_int =
+        |limbs: &[T]| OverflowInt::from_canonical_unsigned_limbs(limbs.to_vec(), LIMB_BITS);
+    to_int(x) * to_int(y) - to_int(q) * to_int(n) - to_int(r)
+}
+
+impl<F: Field> BaseAir<F> for WideMulModCoreAir {
+    fn width(&self) -> usize {
+        WideMulModCoreCols::<F>::width(self.num_limbs)
+    }
+}
+
+impl<F: Field> BaseAirWithPublicValues<F> for WideMulModCoreAir {}
+
+impl<AB: InteractionBuilder, I> VmCoreAir<AB, I> for WideMulModCoreAir
+where
+    I: VmAdapterInterface<AB::Expr>,
+    AdapterAirContext<AB::Expr, I>:
+        From<AdapterAirContext<AB::Expr, DynAdapterInterface<AB::Expr>>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols = WideMulModCoreCols::from_slice(local_core, self.num_limbs);
+        let is_valid = *cols.is_valid;
+        builder.assert_bool(is_valid);
+
+        // Constrain x * y - q * n - r = 0 over the integers.
+        let to_exprs =
+            |limbs: &[AB::Var]| limbs.iter().map(|&v| v.into()).collect::<Vec<AB::Expr>>();
+        let expr = mul_mod_expr(
+            &to_exprs(cols.x),
+            &to_exprs(cols.y),
+            &to_exprs(cols.n),
+            &to_exprs(cols.q),
+            &to_exprs(cols.r),
+        );
+        self.carry_subair.eval(
+            builder,
+            (
+                expr,
+                CheckCarryToZeroCols {
+                    carries: cols.carries.to_vec(),
+                },
+                is_valid.into(),
+            ),
+        );
+
+        // Range check q and r to be bytes.
+        for pair in cols.q.chunks_exact(2).chain(cols.r.chunks_exact(2)) {
+            self.bus
+                .send_range(pair[0], pair[1])
+                .eval(builder, is_valid);
+        }
+
+        // Constrain r < n. The marker sum is is_valid, so on valid rows exactly one marker is set
+        // and r[i] = n[i] above it.
+        let mut prefix_sum = AB::Expr::ZERO;
+        for i in (0..self.num_limbs).rev() {
+            let marker = cols.lt_marker[i];
+            builder.assert_bool(marker);
+            prefix_sum += marker.into();
+            builder.assert_zero((is_valid - prefix_sum.clone()) * (cols.n[i] - cols.r[i]));
+            builder
+                .when(marker)
+                .assert_eq(cols.n[i] - cols.r[i], *cols.lt_diff);
+        }
+        builder.assert_eq(prefix_sum, is_valid);
+        self.bus
+            .send_range(*cols.lt_diff - AB::Expr::ONE, AB::Expr::ZERO)
+            .eval(builder, is_valid);
+
+        let reads: Vec<AB::Expr> = [cols.x, cols.y, cols.n]
+            .concat()
+            .into_iter()
+            .map(Into::into)
+            .collect();
+        let writes: Vec<AB::Expr> = cols.r.iter().map(|&v| v.into()).collect();
+        let instruction = MinimalInstruction {
+            is_valid: is_valid.into(),
+            opcode: AB::Expr::from_canonical_usize(self.offset + self.local_opcode as usize),
+        };
+        let ctx: AdapterAirContext<_, DynAdapterInterface<_>> = AdapterAirContext {
+            to_pc: None,
+            reads: reads.into(),
+            writes: writes.into(),
+            instruction: instruction.into(),
+        };
+        ctx.into()
+    }
+
+    fn start_offset(&self) -> usize {
+        self.offset
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct WideMulModCoreRecord<T> {
+    pub x: Vec<T>,
+    pub y: Vec<T>,
+    pub n: Vec<T>,
+    pub q: Vec<T>,
+    pub r: Vec<T>,
+    pub carries: Vec<isize>,
+    pub diff_idx: usize,
+}
+
+pub struct WideMulModCoreChip {
+    pub air: WideMulModCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<LIMB_BITS>,
+    pub range_checker: SharedVariableRangeCheckerChip,
+}
+
+impl WideMulModCoreChip {
+    pub fn new(
+        local_opcode: Rv32WideMulModOpcode,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<LIMB_BITS>,
+        range_checker: SharedVariableRangeCheckerChip,
+        offset: usize,
+    ) -> Self {
+        Self {
+            air: WideMulModCoreAir::new(
+                local_opcode,
+                bitwise_lookup_chip.bus(),
+                range_checker.bus(),
+                offset,
+            ),
+            bitwise_lookup_chip,
+            range_checker,
+        }
+    }
+}
+
+impl<F: PrimeField32, I> VmCoreChip<F, I> for WideMulModCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<DynArray<F>>,
+    AdapterRuntimeContext<F, I>: From<AdapterRuntimeContext<F, DynAdapterInterface<F>>>,
+{
+    type Record = WideMulModCoreRecord<F>;
+    type Air = WideMulModCoreAir;
+
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        from_pc: u32,
+        reads: I::Reads,
+    ) -> Result<(AdapterRuntimeContext<F, I>, Self::Record)> {
+        let num_limbs = self.air.num_limbs;
+        let data: DynArray<_> = reads.into();
+        let data: Vec<u8> = data.0.iter().map(|x| x.as_canonical_u32() as u8).collect();
+        assert_eq!(data.len(), 3 * num_limbs);
+        let (x, rest) = data.split_at(num_limbs);
+        let (y, n) = rest.split_at(num_limbs);
+
+        // The operands are read from guest memory, so they are rejected rather than asserted on.
+        let invalid_operands = |reason: String| ExecutionError::InvalidOperands {
+            pc: from_pc,
+            opcode: instruction.opcode,
+            reason,
+        };
+        let n_big = BigUint::from_bytes_le(n);
+        if n_big.is_zero() {
+            return Err(invalid_operands("modulus is zero".to_string()));
+        }
+        let product = BigUint::from_bytes_le(x) * BigUint::from_bytes_le(y);
+        let q_big = &product / &n_big;
+        let r_big = product % &n_big;
+        let to_limbs = |v: &BigUint| {
+            let mut limbs = v.to_bytes_le();
+            limbs.resize(num_limbs, 0);
+            limbs
+        };
+        if q_big.bits() > 8 * num_limbs as u64 {
+            return Err(invalid_operands(format!(
+                "quotient does not fit in {num_limbs} bytes"
+            )));
+        }
+        let q = to_limbs(&q_big);
+        let r = to_limbs(&r_big);
+
+        for pair in q.chunks_exact(2).chain(r.chunks_exact(2)) {
+            self.bitwise_lookup_chip
+                .request_range(pair[0] as u32, pair[1] as u32);
+        }
+        let diff_idx = (0..num_limbs).rev().find(|&i| r[i] != n[i]).unwrap();
+        self.bitwise_lookup_chip
+            .request_range((n[diff_idx] - r[diff_idx] - 1) as u32, 0);
+
+        let to_isize = |limbs: &[u8]| limbs.iter().map(|&v| v as isize).collect::<Vec<_>>();
+        let expr = mul_mod_expr(
+            &to_isize(x),
+            &to_isize(y),
+            &to_isize(n),
+            &to_isize(&q),
+            &to_isize(&r),
+        );
+        let carries = expr.calculate_carries(LIMB_BITS);
+        let (carry_min_abs, carry_bits) =
+            get_carry_max_abs_and_bits(expr.max_overflow_bits(), LIMB_BITS);
+        for &carry in carries.iter() {
+            self.range_checker
+                .add_count((carry + carry_min_abs as isize) as u32, carry_bits);
+        }
+
+        let to_field = |limbs: &[u8]| {
+            limbs
+                .iter()
+                .map(|&v| F::from_canonical_u8(v))
+                .collect::<Vec<_>>()
+        };
+        let record = WideMulModCoreRecord {
+            x: to_field(x),
+            y: to_field(y),
+            n: to_field(n),
+            q: to_field(&q),
+            r: to_field(&r),
+            carries,
+            diff_idx,
+        };
+        let ctx = AdapterRuntimeContext::<_, DynAdapterInterface<_>>::without_pc(record.r.clone());
+        Ok((ctx.into(), record))
+    }
+
+    fn get_opcode_name(&self, _opcode: usize) -> String {
+        format!("{:?}", self.air.local_opcode)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let num_limbs = self.air.num_limbs;
+        let (is_valid, rest) = row_slice.split_first_mut().unwrap();
+        *is_valid = F::ONE;
+        let mut rest = rest.iter_mut();
+        for (cell, value) in (&mut rest).zip(
+            [&record.x, &record.y, &record.n, &record.q, &record.r]
+                .into_iter()
+                .flatten(),
+        ) {
+            *cell = *value;
+        }
+        for (cell, &carry) in (&mut rest).zip(record.carries.iter()) {
+            *cell = isize_to_field(carry);
+        }
+        for (i, cell) in (&mut rest).take(num_limbs).enumerate() {
+            *cell = F::from_bool(i == record.diff_idx);
+        }
+        *rest.next().unwrap() = record.n[record.diff_idx] - record.r[record.diff_idx];
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
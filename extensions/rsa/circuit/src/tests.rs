@@ -0,0 +1,214 @@
+use num_bigint::BigUint;
+use openvm_circuit::arch::{
+    testing::{VmChipTestBuilder, BITWISE_OP_LOOKUP_BUS},
+    ExecutionError, ExecutionState, InstructionExecutor, MemoryConfig,
+};
+use openvm_circuit_primitives::bitwise_op_lookup::{
+    BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
+};
+use openvm_instructions::{riscv::RV32_CELL_BITS, LocalOpcode};
+use openvm_rsa_transpiler::Rv32WideMulModOpcode;
+use openvm_rv32_adapters::{
+    rv32_write_heap_default_with_increment, Rv32VecHeapTwoReadsAdapterChip,
+};
+use openvm_stark_backend::p3_field::{FieldAlgebra, PrimeField32};
+use openvm_stark_sdk::{p3_baby_bear::BabyBear, utils::create_seeded_rng};
+use rand::{rngs::StdRng, Rng};
+
+use crate::{num_limbs, Rv32MulMod2048Chip, Rv32MulMod4096Chip, WideMulModCoreChip};
+
+type F = BabyBear;
+
+const BLOCK_SIZE: usize = 32;
+
+fn random_below(rng: &mut StdRng, n: &BigUint, num_limbs: usize) -> BigUint {
+    let bytes: Vec<u8> = (0..num_limbs).map(|_| rng.gen()).collect();
+    BigUint::from_bytes_le(&bytes) % n
+}
+
+fn to_blocks(x: &BigUint, num_limbs: usize) -> Vec<[F; BLOCK_SIZE]> {
+    let mut bytes = x.to_bytes_le();
+    bytes.resize(num_limbs, 0);
+    bytes
+        .chunks_exact(BLOCK_SIZE)
+        .map(|chunk| std::array::from_fn(|i| F::from_canonical_u8(chunk[i])))
+        .collect()
+}
+
+fn set_and_execute<E: InstructionExecutor<F>>(
+    tester: &mut VmChipTestBuilder<F>,
+    chip: &mut E,
+    rng: &mut StdRng,
+    opcode: Rv32WideMulModOpcode,
+) {
+    let num_limbs = num_limbs(opcode);
+    let mut n_bytes: Vec<u8> = (0..num_limbs).map(|_| rng.gen()).collect();
+    n_bytes[num_limbs - 1] |= 0x80;
+    let n = BigUint::from_bytes_le(&n_bytes);
+    let x = random_below(rng, &n, num_limbs);
+    let y = random_below(rng, &n, num_limbs);
+
+    let instruction = rv32_write_heap_default_with_increment(
+        tester,
+        to_blocks(&x, num_limbs),
+        [to_blocks(&y, num_limbs), to_blocks(&n, num_limbs)].concat(),
+        4 * num_limbs,
+        opcode.global_opcode().as_usize(),
+    );
+    tester.execute(chip, &instruction);
+
+    let rd_ptr = instruction.a.as_canonical_u32() as usize;
+    let out_ptr = u32::from_le_bytes(
+        tester
+            .read::<4>(1, rd_ptr)
+            .map(|x| x.as_canonical_u32() as u8),
+    ) as usize;
+    let out: Vec<u8> = (0..num_limbs / BLOCK_SIZE)
+        .flat_map(|i| tester.read::<BLOCK_SIZE>(2, out_ptr + i * BLOCK_SIZE))
+        .map(|x| x.as_canonical_u32() as u8)
+        .collect();
+    assert_eq!(BigUint::from_bytes_le(&out), x * y % n);
+}
+
+#[test]
+fn test_mulmod_2048() {
+    let mut rng = create_seeded_rng();
+    let mut tester = VmChipTestBuilder::volatile(MemoryConfig {
+        decomp: 18,
+        ..Default::default()
+    });
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let mut chip = Rv32MulMod2048Chip::new(
+        Rv32VecHeapTwoReadsAdapterChip::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        ),
+        WideMulModCoreChip::new(
+            Rv32WideMulModOpcode::MULMOD_2048,
+            bitwise_chip.clone(),
+            tester.memory_controller().borrow().range_checker.clone(),
+            Rv32WideMulModOpcode::CLASS_OFFSET,
+        ),
+        tester.offline_memory_mutex_arc(),
+    );
+    for _ in 0..4 {
+        set_and_execute(
+            &mut tester,
+            &mut chip,
+            &mut rng,
+            Rv32WideMulModOpcode::MULMOD_2048,
+        );
+    }
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_mulmod_4096() {
+    let mut rng = create_seeded_rng();
+    let mut tester = VmChipTestBuilder::volatile(MemoryConfig {
+        decomp: 19,
+        ..Default::default()
+    });
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let mut chip = Rv32MulMod4096Chip::new(
+        Rv32VecHeapTwoReadsAdapterChip::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        ),
+        WideMulModCoreChip::new(
+            Rv32WideMulModOpcode::MULMOD_4096,
+            bitwise_chip.clone(),
+            tester.memory_controller().borrow().range_checker.clone(),
+            Rv32WideMulModOpcode::CLASS_OFFSET,
+        ),
+        tester.offline_memory_mutex_arc(),
+    );
+    for _ in 0..2 {
+        set_and_execute(
+            &mut tester,
+            &mut chip,
+            &mut rng,
+            Rv32WideMulModOpcode::MULMOD_4096,
+        );
+    }
+    let tester = tester.build().load(chip).load(bitwise_chip).finalize();
+    tester.simple_test().expect("Verification failed");
+}
+
+#[test]
+fn test_mulmod_invalid_operands() {
+    let mut tester = VmChipTestBuilder::volatile(MemoryConfig {
+        decomp: 18,
+        ..Default::default()
+    });
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    let mut chip = Rv32MulMod2048Chip::new(
+        Rv32VecHeapTwoReadsAdapterChip::new(
+            tester.execution_bus(),
+            tester.program_bus(),
+            tester.memory_bridge(),
+            tester.address_bits(),
+            bitwise_chip.clone(),
+        ),
+        WideMulModCoreChip::new(
+            Rv32WideMulModOpcode::MULMOD_2048,
+            bitwise_chip.clone(),
+            tester.memory_controller().borrow().range_checker.clone(),
+            Rv32WideMulModOpcode::CLASS_OFFSET,
+        ),
+        tester.offline_memory_mutex_arc(),
+    );
+    let opcode = Rv32WideMulModOpcode::MULMOD_2048;
+    let num_limbs = num_limbs(opcode);
+    let max = BigUint::from_bytes_le(&vec![u8::MAX; num_limbs]);
+    // Operands come from guest memory, so invalid ones must fail execution instead of panicking.
+    for (n, expected) in [
+        (BigUint::from(0u32), "modulus is zero"),
+        (BigUint::from(3u32), "quotient does not fit"),
+    ] {
+        let instruction = rv32_write_heap_default_with_increment(
+            &mut tester,
+            to_blocks(&max, num_limbs),
+            [to_blocks(&max, num_limbs), to_blocks(&n, num_limbs)].concat(),
+            4 * num_limbs,
+            opcode.global_opcode().as_usize(),
+        );
+        let from_state = ExecutionState::new(0, tester.memory_controller().borrow().timestamp());
+        let result = chip.execute(
+            &mut tester.memory_controller().borrow_mut(),
+            &instruction,
+            from_state,
+        );
+        assert!(matches!(
+            result,
+            Err(ExecutionError::InvalidOperands { reason, .. }) if reason.starts_with(expected)
+        ));
+    }
+}
+
+#[test]
+#[should_panic(expected = "requires a range checker with at least 19 bits")]
+fn test_mulmod_4096_small_range_checker() {
+    let tester = VmChipTestBuilder::<F>::volatile(MemoryConfig {
+        decomp: 18,
+        ..Default::default()
+    });
+    let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+    let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+    WideMulModCoreChip::new(
+        Rv32WideMulModOpcode::MULMOD_4096,
+        bitwise_chip,
+        tester.memory_controller().borrow().range_checker.clone(),
+        Rv32WideMulModOpcode::CLASS_OFFSET,
+    );
+}
@@ -0,0 +1,60 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{uint::pow_mod, Error};
+
+/// An RSA public key with a 2048, 3072 or 4096-bit modulus.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RsaPublicKey {
+    /// Big-endian modulus without leading zeros.
+    n: Vec<u8>,
+    e: u32,
+}
+
+impl RsaPublicKey {
+    /// Creates a public key from the big-endian modulus `n` and the public exponent `e`. Leading
+    /// zero bytes of `n` are ignored.
+    pub fn new(n: &[u8], e: u32) -> Result<Self, Error> {
+        let start = n.iter().position(|&byte| byte != 0).unwrap_or(n.len());
+        let n = &n[start..];
+        if !matches!(n.len(), 256 | 384 | 512) || n[n.len() - 1] & 1 == 0 {
+            return Err(Error::UnsupportedModulus);
+        }
+        if e < 3 || e & 1 == 0 {
+            return Err(Error::InvalidExponent);
+        }
+        Ok(Self { n: n.to_vec(), e })
+    }
+
+    /// The big-endian modulus.
+    pub fn n(&self) -> &[u8] {
+        &self.n
+    }
+
+    /// The public exponent.
+    pub fn e(&self) -> u32 {
+        self.e
+    }
+
+    /// The size of the modulus in bytes, which is also the length of signatures.
+    pub fn size(&self) -> usize {
+        self.n.len()
+    }
+
+    /// Applies the RSAVP1 verification primitive (RFC 8017, Section 5.2.2) to the big-endian
+    /// `signature` and returns the encoded message, which is [`size`](Self::size) bytes long.
+    pub fn verify_raw(&self, signature: &[u8]) -> Result<Vec<u8>, Error> {
+        if signature.len() != self.size() {
+            return Err(Error::InvalidSignatureLength);
+        }
+        let mut em = vec![0u8; self.size()];
+        let in_range = if self.size() <= 256 {
+            pow_mod::<256>(&self.n, signature, self.e, &mut em)
+        } else {
+            pow_mod::<512>(&self.n, signature, self.e, &mut em)
+        };
+        if !in_range {
+            return Err(Error::SignatureOutOfRange);
+        }
+        Ok(em)
+    }
+}
@@ -0,0 +1,62 @@
+#![no_std]
+
+//! Verification of RSA signatures with 2048, 3072 and 4096-bit moduli.
+//!
+//! [RsaPublicKey] verifies RSASSA-PKCS1-v1_5 signatures over SHA-256, SHA-384 and SHA-512
+//! digests with [RsaPublicKey::verify_pkcs1v15], and RSASSA-PSS signatures with SHA-256 and
+//! MGF1-SHA-256 with [RsaPublicKey::verify_pss_sha256].
+//!
+//! In the zkVM, the modular multiplications of the public exponentiation use the wide modular
+//! multiplication intrinsics of the RSA extension, whose modulus is read from memory with the
+//! operands. 3072-bit moduli are handled by the 4096-bit intrinsic. Outside the zkVM the same
+//! functions fall back to `num-bigint`.
+
+extern crate alloc;
+
+mod key;
+mod pkcs1v15;
+mod pss;
+mod uint;
+
+pub use key::*;
+pub use pkcs1v15::*;
+
+/// This is custom-0 defined in RISC-V spec document
+pub const OPCODE: u8 = 0x0b;
+pub const RSA_FUNCT3: u8 = 0b100;
+/// `rd = rs1 * rs2 mod (rs2 + 256)` on 256-byte little-endian integers.
+pub const MULMOD2048_FUNCT7: u8 = 0x2;
+/// `rd = rs1 * rs2 mod (rs2 + 512)` on 512-byte little-endian integers.
+pub const MULMOD4096_FUNCT7: u8 = 0x3;
+
+openvm_platform::require_extension_version!("rsa", 1);
+
+/// Errors returned when parsing an RSA public key or verifying a signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The modulus is not an odd integer of 2048, 3072 or 4096 bits.
+    UnsupportedModulus,
+    /// The public exponent is not odd or is less than 3.
+    InvalidExponent,
+    /// The signature length does not match the modulus size.
+    InvalidSignatureLength,
+    /// The signature, as an integer, is not less than the modulus.
+    SignatureOutOfRange,
+    /// The digest length does not match the hash algorithm.
+    InvalidDigestLength,
+    /// The signature does not match the digest.
+    VerificationFailed,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::UnsupportedModulus => write!(f, "unsupported RSA modulus"),
+            Error::InvalidExponent => write!(f, "invalid RSA public exponent"),
+            Error::InvalidSignatureLength => write!(f, "invalid signature length"),
+            Error::SignatureOutOfRange => write!(f, "signature is not less than the modulus"),
+            Error::InvalidDigestLength => write!(f, "invalid digest length"),
+            Error::VerificationFailed => write!(f, "signature verification failed"),
+        }
+    }
+}
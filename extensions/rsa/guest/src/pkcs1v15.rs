@@ -0,0 +1,74 @@
+use crate::{Error, RsaPublicKey};
+
+/// Hash functions of the digests signed with RSASSA-PKCS1-v1_5.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// The digest length in bytes.
+    pub const fn output_size(self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 => 32,
+            HashAlgorithm::Sha384 => 48,
+            HashAlgorithm::Sha512 => 64,
+        }
+    }
+
+    /// The DER encoding of the `DigestInfo` that precedes the digest (RFC 8017, Section 9.2,
+    /// Note 1).
+    const fn digest_info_prefix(self) -> &'static [u8] {
+        match self {
+            HashAlgorithm::Sha256 => &[
+                0x30, 0x31, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x01, 0x05, 0x00, 0x04, 0x20,
+            ],
+            HashAlgorithm::Sha384 => &[
+                0x30, 0x41, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x02, 0x05, 0x00, 0x04, 0x30,
+            ],
+            HashAlgorithm::Sha512 => &[
+                0x30, 0x51, 0x30, 0x0d, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x02,
+                0x03, 0x05, 0x00, 0x04, 0x40,
+            ],
+        }
+    }
+}
+
+impl RsaPublicKey {
+    /// Verifies an RSASSA-PKCS1-v1_5 `signature` (RFC 8017, Section 8.2.2) of a message whose
+    /// `hash` digest is `digest`.
+    pub fn verify_pkcs1v15(
+        &self,
+        hash: HashAlgorithm,
+        digest: &[u8],
+        signature: &[u8],
+    ) -> Result<(), Error> {
+        if digest.len() != hash.output_size() {
+            return Err(Error::InvalidDigestLength);
+        }
+        let em = self.verify_raw(signature)?;
+
+        // EM = 0x00 || 0x01 || PS || 0x00 || DigestInfo || digest, where PS is 0xff padding. The
+        // smallest modulus leaves more than the required 8 bytes of padding.
+        let prefix = hash.digest_info_prefix();
+        let ps_len = em.len() - prefix.len() - digest.len() - 3;
+        let (header, rest) = em.split_at(2);
+        let (ps, rest) = rest.split_at(ps_len);
+        let (separator, rest) = rest.split_at(1);
+        let (digest_info, em_digest) = rest.split_at(prefix.len());
+        if header == [0x00, 0x01]
+            && ps.iter().all(|&byte| byte == 0xff)
+            && separator == [0x00]
+            && digest_info == prefix
+            && em_digest == digest
+        {
+            Ok(())
+        } else {
+            Err(Error::VerificationFailed)
+        }
+    }
+}
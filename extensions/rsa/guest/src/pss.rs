@@ -0,0 +1,61 @@
+use alloc::vec::Vec;
+
+use openvm_sha2::sha256;
+
+use crate::{Error, RsaPublicKey};
+
+const SHA256_LEN: usize = 32;
+
+impl RsaPublicKey {
+    /// Verifies an RSASSA-PSS `signature` (RFC 8017, Section 8.1.2) of a message with SHA-256
+    /// digest `digest`, using SHA-256 for MGF1 and a salt of `salt_len` bytes.
+    pub fn verify_pss_sha256(
+        &self,
+        digest: &[u8; 32],
+        signature: &[u8],
+        salt_len: usize,
+    ) -> Result<(), Error> {
+        // The supported moduli have a multiple of 8 bits, so the encoded message has emBits =
+        // 8 * size - 1 bits and is `size` bytes long, with its most significant bit unused.
+        let em = self.verify_raw(signature)?;
+        let em_len = em.len();
+        if em_len < SHA256_LEN + salt_len + 2 || em[em_len - 1] != 0xbc || em[0] & 0x80 != 0 {
+            return Err(Error::VerificationFailed);
+        }
+        let (masked_db, h) = em[..em_len - 1].split_at(em_len - SHA256_LEN - 1);
+
+        let mut db = masked_db.to_vec();
+        mgf1_xor_sha256(&mut db, h);
+        db[0] &= 0x7f;
+
+        // DB = PS || 0x01 || salt, where PS is zero padding.
+        let ps_len = em_len - SHA256_LEN - salt_len - 2;
+        if db[..ps_len].iter().any(|&byte| byte != 0) || db[ps_len] != 0x01 {
+            return Err(Error::VerificationFailed);
+        }
+        let salt = &db[ps_len + 1..];
+
+        let mut m_prime = Vec::with_capacity(8 + SHA256_LEN + salt_len);
+        m_prime.extend_from_slice(&[0u8; 8]);
+        m_prime.extend_from_slice(digest);
+        m_prime.extend_from_slice(salt);
+        if sha256(&m_prime) != h {
+            return Err(Error::VerificationFailed);
+        }
+        Ok(())
+    }
+}
+
+/// XORs `out` with the MGF1 mask generated by SHA-256 from `seed` (RFC 8017, Appendix B.2.1).
+fn mgf1_xor_sha256(out: &mut [u8], seed: &[u8]) {
+    let mut input = Vec::with_capacity(seed.len() + 4);
+    input.extend_from_slice(seed);
+    input.extend_from_slice(&[0u8; 4]);
+    for (counter, chunk) in out.chunks_mut(SHA256_LEN).enumerate() {
+        input[seed.len()..].copy_from_slice(&(counter as u32).to_be_bytes());
+        let mask = sha256(&input);
+        for (byte, mask) in chunk.iter_mut().zip(mask) {
+            *byte ^= mask;
+        }
+    }
+}
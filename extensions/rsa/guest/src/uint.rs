@@ -0,0 +1,110 @@
+/// Little-endian unsigned integer of `LIMBS` bytes, in the layout the wide modular multiplication
+/// intrinsics read from and write to memory.
+#[derive(Clone, Copy, PartialEq, Eq)]
+#[repr(C, align(32))]
+pub(crate) struct Uint<const LIMBS: usize>(pub [u8; LIMBS]);
+
+impl<const LIMBS: usize> Uint<LIMBS> {
+    /// Reads the big-endian `bytes`, which must be at most `LIMBS` long.
+    pub fn from_be_bytes(bytes: &[u8]) -> Self {
+        assert!(bytes.len() <= LIMBS);
+        let mut limbs = [0u8; LIMBS];
+        for (limb, byte) in limbs.iter_mut().zip(bytes.iter().rev()) {
+            *limb = *byte;
+        }
+        Self(limbs)
+    }
+
+    /// Writes the `out.len()` least significant bytes to `out` in big-endian order.
+    pub fn write_be_bytes(&self, out: &mut [u8]) {
+        for (byte, limb) in out.iter_mut().rev().zip(self.0.iter()) {
+            *byte = *limb;
+        }
+    }
+
+    pub fn lt(&self, other: &Self) -> bool {
+        self.0.iter().rev().lt(other.0.iter().rev())
+    }
+}
+
+/// The second operand of the wide modular multiplication: the multiplier immediately followed by
+/// the modulus.
+#[repr(C, align(32))]
+pub(crate) struct MulModOperand<const LIMBS: usize> {
+    pub y: Uint<LIMBS>,
+    pub n: Uint<LIMBS>,
+}
+
+/// Returns `x * y mod n`, where `y` and `n` are given by `operand`. Both `x` and `y` must be less
+/// than `n`.
+#[inline(always)]
+pub(crate) fn mul_mod<const LIMBS: usize>(
+    x: &Uint<LIMBS>,
+    operand: &MulModOperand<LIMBS>,
+) -> Uint<LIMBS> {
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        use num_bigint::BigUint;
+
+        let x = BigUint::from_bytes_le(&x.0);
+        let y = BigUint::from_bytes_le(&operand.y.0);
+        let n = BigUint::from_bytes_le(&operand.n.0);
+        let r = (x * y % n).to_bytes_le();
+        let mut out = Uint([0u8; LIMBS]);
+        out.0[..r.len()].copy_from_slice(&r);
+        out
+    }
+    #[cfg(target_os = "zkvm")]
+    {
+        let mut out = core::mem::MaybeUninit::<Uint<LIMBS>>::uninit();
+        match LIMBS {
+            256 => {
+                openvm_platform::custom_insn_r!(
+                    opcode = crate::OPCODE,
+                    funct3 = crate::RSA_FUNCT3,
+                    funct7 = crate::MULMOD2048_FUNCT7,
+                    rd = In out.as_mut_ptr(),
+                    rs1 = In x as *const Uint<LIMBS>,
+                    rs2 = In operand as *const MulModOperand<LIMBS>
+                );
+            }
+            512 => {
+                openvm_platform::custom_insn_r!(
+                    opcode = crate::OPCODE,
+                    funct3 = crate::RSA_FUNCT3,
+                    funct7 = crate::MULMOD4096_FUNCT7,
+                    rd = In out.as_mut_ptr(),
+                    rs1 = In x as *const Uint<LIMBS>,
+                    rs2 = In operand as *const MulModOperand<LIMBS>
+                );
+            }
+            _ => unreachable!("unsupported number of limbs"),
+        }
+        unsafe { out.assume_init() }
+    }
+}
+
+/// Writes `base^exp mod n` to `out` in big-endian order, where `n` and `base` are big-endian and
+/// `exp > 1`. Returns `false` without writing if `base` is not less than `n`.
+pub(crate) fn pow_mod<const LIMBS: usize>(n: &[u8], base: &[u8], exp: u32, out: &mut [u8]) -> bool {
+    let n = Uint::<LIMBS>::from_be_bytes(n);
+    let base = Uint::<LIMBS>::from_be_bytes(base);
+    if !base.lt(&n) {
+        return false;
+    }
+    // Left-to-right square-and-multiply. The multiplier of a squaring is a copy of the
+    // accumulator, placed next to the modulus.
+    let by_base = MulModOperand { y: base, n };
+    let mut by_acc = MulModOperand { y: base, n };
+    let mut acc = base;
+    let exp_bits = u32::BITS - exp.leading_zeros();
+    for i in (0..exp_bits - 1).rev() {
+        by_acc.y = acc;
+        acc = mul_mod(&acc, &by_acc);
+        if (exp >> i) & 1 == 1 {
+            acc = mul_mod(&acc, &by_base);
+        }
+    }
+    acc.write_be_bytes(out);
+    true
+}
@@ -0,0 +1,85 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+use openvm_rsa_guest::{Error, HashAlgorithm, RsaPublicKey};
+use openvm_sha2::sha256;
+
+openvm::entry!(main);
+
+const MESSAGE: &[u8] = b"OpenVM RSA signature verification";
+const E: u32 = 65537;
+
+// Keys and signatures generated with the Python `cryptography` package.
+const N_2048: [u8; 256] = hex!(
+    "8e1578342be186a92a86f304841b1bd5ab766bf316865b56a5b16d55989b0b1300917b5439bbf2b1f3b9820074f45d84"
+    "a9078119cad608f0daa917ee427293ae34f65b74fc5cda60ef64e545ab732c735a3c56826fa09ce5d19319a631b831c3"
+    "66b6333ea95809f9c8e2a18c0724af8e357c2cc4d27018ee3d81be89152f2aa9cbfaccd65128b9cd28963fc2cf0c2967"
+    "a54338b4269e4d0419081f4476536391fa02182bad9c39ebf7ff5f98e0e0619a6249d2e877c63184339f888c896798c5"
+    "f88f2ec326de48b187be911d3155c55d4606669c5aed889a514559a950c1528be5d3d30fc204bef2b646683afd43732f"
+    "a3ab907c3043962a0be620e63ee317bd"
+);
+const SIG_2048: [u8; 256] = hex!(
+    "283e4fb1b24d8ee2be9b47e0a65741e0b77a127215388d0ea437b86c81a1570770d7171e7ccc3c47edb479090a320f97"
+    "74a5e07618b8d5006f18fa88ea0199df6e32d63d35e8ef9532b01db02f2092d15ed88fd841a385a3d54adb814513f222"
+    "1cb679383a2b4bb29cc7f29f7b70db07fc954d3a9e4bff8266fd3263e5dfdc630a94180654a16af4876307973273b947"
+    "efda5f5aee9dcb6dadb23920f9b191f3d715389aac8ea100f0dc2d2bd02eb3f586aa31ef9423ab9e2d321d8d0c0b8a14"
+    "b312d9586b8941f464efda6eeb1564a6d8f18fcda315fbd65683674f087ce5e8cd73e9918b7f7f48fbfaaf2e2081073d"
+    "e4f05327e116df36afd2dd46a05870af"
+);
+const N_4096: [u8; 512] = hex!(
+    "ded8b4149a294949f9a6c9be1e31fb0bdd3cc1ba95bf18efe5570ecec23dd11d97be873fe34c7b74926bc856e8f3dee7"
+    "8df200ad16e6dfdaf2372e9ee146deb2e4bf547e3e029fca3568a48b10a9aea155b3e73f121cf6be22edbf640b19d170"
+    "982f61877a791432c5f52bbdb30c62a2fd3ca307fb9c0e3da540c877f28c505351398e3bc4b853ea86607a11d774f78c"
+    "f06e215eb9590b7f2d695d46693617aea5ee873a0ff1083bd35e6051f485c1b639e7c469293b4b0dfaaa56097749c50f"
+    "ddee1000c320ffd23803e7dd087a0429ca699d93c1a5e46591b1b22b223d3edea2afeb7d6d9ecb7bc420f5bd17e21262"
+    "379697d20b48182414102409b6552c02c621b5c93dae27fbc7e3e01f8ccc2dfc5bb87f893bd8824a98b70aaad2d80757"
+    "68b0133f0bceebb3cdb16cb8e920469158ffb37598555125f85c83d1e8fec3d8ccd81193b3e0e4f1863647fadc1355b5"
+    "e8220da245fe7af368cead5e74159b52537a2f000c8608d818bed5c742972b8f6f485cacc5ce8826799179d374a1274d"
+    "9d75fa109bab9f19ab71b3dd7e874d587da52d1fe4c1515c4c867b90ac0a9b2d9e639f3932b84a228b41e03f75ffc6f2"
+    "7f90793b5493f821f4282e0b2629838e6c8fd2761b5dde6535162ab2a77132374673a285699c06120f88d4f195fbc04a"
+    "341e30cf69d25652cdd041c505fd5be36e7c6ef2bb3efcd604f813696a483511"
+);
+const SIG_4096: [u8; 512] = hex!(
+    "9e4f9f469d4e48b28b29ae20ade092a57c17c1234269fce1857f831bb649316abe831153173511cf35fff8c27d1ffb75"
+    "04d079af091b4080da9dc89f48e4bce7ef6d9a36aef0826206a048b5b9c5276786ab56bd39037a5123bbcfe695404ce8"
+    "5940558ab51193e9974ea1ef1447c53d95eeb988189c802d2235f709b20ad91a1b07f8d4370c6a4314c7fef08974f694"
+    "e32f1188b0fb63d21fbdfd8fcd54656d3a16009e2e20863573b3cd62b67c929e05fed643e3b793804503f55e86aa139b"
+    "9efb557aa90085522f21f3dc051891e6e2430d0891f3a4eb4415796db05d4cdbd68abe703e99a30f21cdb1524433417d"
+    "c4e8bb7534afc538c051565e7a344c905423ad72c683997d2ed24ba51e81a176b93f6f33480e4fc938c2d741f49e8a7e"
+    "dce4867a8e89d7cd71252c7efee2cebb12f492f659b77cff744e3f6665c8e8708a38240addc0fa5ebd9d9b202794f653"
+    "d250d16eb894ca381491742f972d9c5fae3b4e3e531054988e25c963f048fe0ccd005db5e80e0c179ee6776eab23e7aa"
+    "5deb005d77e373469899c6c20eafb2f6fa2ce18f32fbcf472c65f1dccec3048e8fa72c157616ea7a7f4efd6956881e30"
+    "88fb440b8f7cde40774aa5208f20ec3d6272f2c8f0c38dede0b71e5c10dd425174d900c0c9d6e791878f725615ca79db"
+    "319bddb978e368d5a798b498097a27c702255340da9a4cf5cf69d0050006f9ad"
+);
+const SHA512_DIGEST: [u8; 64] = hex!(
+    "b6f40a82a77dcb0b1a54288af4588f4ded1fd0c7f27f8d8bc101a56763d158a65a94b7a50279a2d9579e0927a6e23e0c"
+    "d66695f8a9fc767a4e7f63be2e392905"
+);
+
+fn main() {
+    let digest = sha256(MESSAGE);
+    let key = RsaPublicKey::new(&N_2048, E).unwrap();
+    key.verify_pkcs1v15(HashAlgorithm::Sha256, &digest, &SIG_2048)
+        .unwrap();
+
+    let mut wrong_digest = digest;
+    wrong_digest[0] ^= 1;
+    assert_eq!(
+        key.verify_pkcs1v15(HashAlgorithm::Sha256, &wrong_digest, &SIG_2048),
+        Err(Error::VerificationFailed)
+    );
+    assert_eq!(
+        key.verify_pkcs1v15(HashAlgorithm::Sha256, &digest, &N_2048),
+        Err(Error::SignatureOutOfRange)
+    );
+
+    let key = RsaPublicKey::new(&N_4096, E).unwrap();
+    key.verify_pkcs1v15(HashAlgorithm::Sha512, &SHA512_DIGEST, &SIG_4096)
+        .unwrap();
+    assert_eq!(
+        key.verify_pkcs1v15(HashAlgorithm::Sha384, &SHA512_DIGEST[..48], &SIG_4096),
+        Err(Error::VerificationFailed)
+    );
+}
@@ -0,0 +1,72 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use hex_literal::hex;
+use openvm_rsa_guest::{Error, RsaPublicKey};
+use openvm_sha2::sha256;
+
+openvm::entry!(main);
+
+const MESSAGE: &[u8] = b"OpenVM RSA signature verification";
+const E: u32 = 65537;
+
+const SALT_LEN: usize = 32;
+
+// Keys and signatures generated with the Python `cryptography` package, using MGF1-SHA-256 and a
+// 32-byte salt.
+const N_2048: [u8; 256] = hex!(
+    "bcb4ac5a49722ed08ca7c79aa0d5629b6755bd1a5d01daf953b5fd6247ffee7a0f63e8d4f1266a560dd71f037ec0402a"
+    "b262bf29d77585c6540d3d2b68520daaa33239c8b92869d1dc43ba2f5da89e15da84081d75f1f04cdfc99ab2984a22f5"
+    "6e407fc19b130ec596385f61f36345e5cfeaa7ab9c83887c864a5cc2e8a18d535c2bb0d0a821ab0e9bb326e431f4dbe3"
+    "1ad6cb6afdb743f2ec2d9ec1d726917150ec577a95059baf362c780a3eb0a1b2ffea7647666d8d1368e58901ca4fa787"
+    "aa9fb6bdc70fb712ba1eb47f43eabaf49f220f21a16659aef24daa0d61f4bfb86a76863d7d37ef6ca95e7300c35ef8f3"
+    "48805bb4a93fd634492373e931b5e301"
+);
+const SIG_2048: [u8; 256] = hex!(
+    "411988e0ee467977a4388c83b178805c334048659a30c4fb25e03259a1d56b3ae2f8ab46de43a6a883d66f40590d3c59"
+    "1e32d6c52fad4dcf1980c73a31ce12712da82b457408879aea014fdecd6a09eb700a8e3be1771735e94ccdb7be84e269"
+    "0c25873bee78611ae9ca7682d2f27c9473faaadf3440d177f831de5e5f334f8ad4b5ccb84682f3e7c26d9bf01c3b7709"
+    "34c95e1cd82ae4db65e88024a5df1d489e2c89443152f62e84b4ed8e164880356c31ea2c83084fc933764fe67c5ec029"
+    "13c79febf3cd53c26bb3a1b53219094dedc2763c16c73f0b29c5506d3cbb40ca29c9bb0784131c57e6b36b08a1d84ccb"
+    "d19286e04c04c216608859b5df7698ce"
+);
+const N_3072: [u8; 384] = hex!(
+    "abb3bf9cafff7764da6f7c742e4465f5cb7d16ba2f0cc543c9a33a9fe4c6b7af9a9c3b2990a9bf8011f7b125c2e338c9"
+    "af85774735ace1c9f7fc29e6f1c25cd888ad8764d1801437fb0cfab67dea1593c29b229a707448a22f7bfa6f9a3bd65a"
+    "bbeea5033324cb1ab607e2489362dbae610829aa6a8d6b61fa1aa206125a21600b96d7b27e7f842132acbe2841ef88be"
+    "30ecefef0cc2a215a52c53643f4a564d10a2f37064e5d93631f3b9ed4722c7b0ddbb24f1537e723b2ab4fe2dc1a12144"
+    "7fdfd485cafb40c3733d8860233d3490538a95b28b069187ca8792bbfe474b4037786902e1621fb725edaf312927edbd"
+    "3c3492ffdbbefc7a4e86496856f4395b2292251a70aae7e3a9ca530db3d5e7c0b135bda6243d6fb9b3c1c1263e391c20"
+    "94bb250281610381eb31a5d64c76897deaa74b9ed406231d96ff0aca834694e053d2f012f6f8d87845620dfa3c13b83d"
+    "078480b37c18f42bca92a3dd0d893f42cb5d55509ed59f5bc56b43562f37db18dd88fbc8693e079bc074bf071e16b06f"
+);
+const SIG_3072: [u8; 384] = hex!(
+    "4da7b7e133d78fa24d8e4bee851de23a2023640f56368e549f9a753976cb65ecc08b539e1ab5dfd562f0fb3cba2b8e9d"
+    "3fa5dbaf2445e4b9b52c955434be16cb9d1665d4e04c2899e6897c1943ea03277cc3190fa24954e92fda75bdb95078cf"
+    "dba81da96cc7532e85f04fcf0a18984178734ffbb09fe8a814ebd8b449972165f3ced9f114f3af21c9896b41e8a9381a"
+    "fedf52715a21efcbeed93db10317ce90108941c75c989dfe1bfa512101260d866c4a5f9fb6be4d9e233c88421051317a"
+    "e37e7a6f0f943b259227b962aa11d5268d6eadd81a1206998d2a3a48b0906dd04ca2e8b098830fd750ab0c195c3813a5"
+    "f2fb8428ac6238854613e3a38f15e6bfd2dc56c1a66ff3b3aefedd54e8103984e9fab34226319ecc11c99dd1deded25c"
+    "941c4834a0b85622b8ac25df767b440c97e4c4e0587e222c13c8331dfe08a962f2c8c0bd014c9bda5d511e4883e60829"
+    "7f8ca7238f16a70e815d005067565142976c7be8e2abb6b46d75c2645ae635a6b38110cfb16ea066f98b2d405322f6ee"
+);
+
+fn main() {
+    let digest = sha256(MESSAGE);
+    let key = RsaPublicKey::new(&N_2048, E).unwrap();
+    key.verify_pss_sha256(&digest, &SIG_2048, SALT_LEN).unwrap();
+
+    let key = RsaPublicKey::new(&N_3072, E).unwrap();
+    key.verify_pss_sha256(&digest, &SIG_3072, SALT_LEN).unwrap();
+
+    let mut wrong_digest = digest;
+    wrong_digest[31] ^= 1;
+    assert_eq!(
+        key.verify_pss_sha256(&wrong_digest, &SIG_3072, SALT_LEN),
+        Err(Error::VerificationFailed)
+    );
+    assert_eq!(
+        key.verify_pss_sha256(&digest, &SIG_3072, SALT_LEN + 1),
+        Err(Error::VerificationFailed)
+    );
+}
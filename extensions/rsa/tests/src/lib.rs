@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use eyre::Result;
+    use openvm_circuit::{arch::instructions::exe::VmExe, utils::air_test};
+    use openvm_sdk::config::{AppConfig, SdkVmConfig};
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::FromElf;
+
+    fn rsa_config() -> Result<SdkVmConfig> {
+        Ok(
+            toml::from_str::<AppConfig<SdkVmConfig>>(include_str!("../programs/openvm_rsa.toml"))?
+                .app_vm_config,
+        )
+    }
+
+    #[test]
+    fn test_pkcs1v15() -> Result<()> {
+        let config = rsa_config()?;
+        let elf = build_example_program_at_path(get_programs_dir!(), "pkcs1v15", &config)?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pss() -> Result<()> {
+        let config = rsa_config()?;
+        let elf = build_example_program_at_path(get_programs_dir!(), "pss", &config)?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+        air_test(config, openvm_exe);
+        Ok(())
+    }
+}
@@ -0,0 +1,50 @@
+use openvm_instructions::LocalOpcode;
+use openvm_instructions_derive::LocalOpcode;
+use openvm_rsa_guest::{MULMOD2048_FUNCT7, MULMOD4096_FUNCT7, OPCODE, RSA_FUNCT3};
+use openvm_stark_backend::p3_field::PrimeField32;
+use openvm_transpiler::{
+    metadata::OpcodeMap, util::from_r_type, TranspilerExtension, TranspilerOutput,
+};
+use rrs_lib::instruction_formats::RType;
+use strum::{EnumCount, EnumIter, FromRepr, IntoEnumIterator};
+
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
+)]
+#[opcode_offset = 0x800]
+#[repr(usize)]
+#[allow(non_camel_case_types)]
+pub enum Rv32WideMulModOpcode {
+    MULMOD_2048,
+    MULMOD_4096,
+}
+
+#[derive(Default)]
+pub struct RsaTranspilerExtension;
+
+impl<F: PrimeField32> TranspilerExtension<F> for RsaTranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        if instruction_stream.is_empty() {
+            return None;
+        }
+        let instruction_u32 = instruction_stream[0];
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+
+        if (opcode, funct3) != (OPCODE, RSA_FUNCT3) {
+            return None;
+        }
+        let dec_insn = RType::new(instruction_u32);
+        let local_opcode = match dec_insn.funct7 as u8 {
+            MULMOD2048_FUNCT7 => Rv32WideMulModOpcode::MULMOD_2048,
+            MULMOD4096_FUNCT7 => Rv32WideMulModOpcode::MULMOD_4096,
+            _ => return None,
+        };
+        let instruction = from_r_type(local_opcode.global_opcode().as_usize(), 2, &dec_insn, true);
+        Some(TranspilerOutput::one_to_one(instruction))
+    }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default().with_opcodes(Rv32WideMulModOpcode::iter())
+    }
+}
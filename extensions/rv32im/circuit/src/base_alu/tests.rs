@@ -12,6 +12,7 @@ use openvm_circuit::{
 use openvm_circuit_primitives::bitwise_op_lookup::{
     BitwiseOperationLookupBus, SharedBitwiseOperationLookupChip,
 };
+use openvm_constraint_tests::{ConstraintTestCase, ConstraintTests};
 use openvm_instructions::{instruction::Instruction, LocalOpcode};
 use openvm_rv32im_transpiler::BaseAluOpcode;
 use openvm_stark_backend::{
@@ -120,6 +121,54 @@ fn rv32_alu_and_rand_test() {
     run_rv32_alu_rand_test(BaseAluOpcode::AND, 100);
 }
 
+// Immediate operands are not generated, as the auxiliary columns of the rs2 read are unused and
+// unconstrained for them.
+#[test]
+fn rv32_alu_constraint_tests() {
+    ConstraintTests::new(|rng| {
+        let bitwise_bus = BitwiseOperationLookupBus::new(BITWISE_OP_LOOKUP_BUS);
+        let bitwise_chip = SharedBitwiseOperationLookupChip::<RV32_CELL_BITS>::new(bitwise_bus);
+
+        let mut tester = VmChipTestBuilder::default();
+        let mut chip = Rv32BaseAluChip::<F>::new(
+            Rv32BaseAluAdapterChip::new(
+                tester.execution_bus(),
+                tester.program_bus(),
+                tester.memory_bridge(),
+                bitwise_chip.clone(),
+            ),
+            BaseAluCoreChip::new(bitwise_chip.clone(), BaseAluOpcode::CLASS_OFFSET),
+            tester.offline_memory_mutex_arc(),
+        );
+
+        for _ in 0..8 {
+            let opcode = [
+                BaseAluOpcode::ADD,
+                BaseAluOpcode::SUB,
+                BaseAluOpcode::XOR,
+                BaseAluOpcode::OR,
+                BaseAluOpcode::AND,
+            ][rng.gen_range(0..5)];
+            let b = generate_long_number::<RV32_REGISTER_NUM_LIMBS, RV32_CELL_BITS>(rng);
+            let c = generate_long_number::<RV32_REGISTER_NUM_LIMBS, RV32_CELL_BITS>(rng);
+            let (instruction, _) = rv32_rand_write_register_or_imm(
+                &mut tester,
+                b,
+                c,
+                None,
+                opcode.global_opcode().as_usize(),
+                rng,
+            );
+            tester.execute(&mut chip, &instruction);
+        }
+
+        ConstraintTestCase::new(tester.build(), chip)
+            .load(bitwise_chip)
+            .finalize()
+    })
+    .run();
+}
+
 //////////////////////////////////////////////////////////////////////////////////////
 // NEGATIVE TESTS
 //
@@ -192,6 +192,10 @@ impl<F: PrimeField32> VmExtension<F> for Rv32I {
     type Executor = Rv32IExecutor<F>;
     type Periphery = Rv32IPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("rv32i", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -363,6 +367,42 @@ impl<F: PrimeField32> VmExtension<F> for Rv32I {
             phantom::Rv32HintLoadByKeySubEx,
             PhantomDiscriminant(Rv32Phantom::HintLoadByKey as u16),
         )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32HintByKeySubEx,
+            PhantomDiscriminant(Rv32Phantom::HintByKey as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32HintSelectSubEx,
+            PhantomDiscriminant(Rv32Phantom::HintSelect as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32RecordPanicSubEx,
+            PhantomDiscriminant(Rv32Phantom::RecordPanic as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32RecordHeapStatsSubEx,
+            PhantomDiscriminant(Rv32Phantom::RecordHeapStats as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32HintInputLenSubEx,
+            PhantomDiscriminant(Rv32Phantom::HintInputLen as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32ChannelSendSubEx,
+            PhantomDiscriminant(Rv32Phantom::ChannelSend as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32ChannelRecvSubEx,
+            PhantomDiscriminant(Rv32Phantom::ChannelRecv as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32RegionStartSubEx,
+            PhantomDiscriminant(Rv32Phantom::RegionStart as u16),
+        )?;
+        builder.add_phantom_sub_executor(
+            phantom::Rv32RegionEndSubEx,
+            PhantomDiscriminant(Rv32Phantom::RegionEnd as u16),
+        )?;
 
         Ok(inventory)
     }
@@ -372,6 +412,10 @@ impl<F: PrimeField32> VmExtension<F> for Rv32M {
     type Executor = Rv32MExecutor<F>;
     type Periphery = Rv32MPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("rv32m", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -448,6 +492,10 @@ impl<F: PrimeField32> VmExtension<F> for Rv32Io {
     type Executor = Rv32IoExecutor<F>;
     type Periphery = Rv32IoPeriphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("rv32io", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -496,7 +544,7 @@ impl<F: PrimeField32> VmExtension<F> for Rv32Io {
 mod phantom {
     use eyre::bail;
     use openvm_circuit::{
-        arch::{PhantomSubExecutor, Streams},
+        arch::{HeapProfile, PhantomSubExecutor, Streams},
         system::memory::MemoryController,
     };
     use openvm_instructions::PhantomDiscriminant;
@@ -516,6 +564,15 @@ mod phantom {
     }
     pub struct Rv32PrintStrSubEx;
     pub struct Rv32HintLoadByKeySubEx;
+    pub struct Rv32HintByKeySubEx;
+    pub struct Rv32HintSelectSubEx;
+    pub struct Rv32RecordPanicSubEx;
+    pub struct Rv32RecordHeapStatsSubEx;
+    pub struct Rv32HintInputLenSubEx;
+    pub struct Rv32ChannelSendSubEx;
+    pub struct Rv32ChannelRecvSubEx;
+    pub struct Rv32RegionStartSubEx;
+    pub struct Rv32RegionEndSubEx;
 
     impl<F: Field> PhantomSubExecutor<F> for Rv32HintInputSubEx {
         fn phantom_execute(
@@ -571,7 +628,7 @@ mod phantom {
         fn phantom_execute(
             &mut self,
             memory: &MemoryController<F>,
-            _: &mut Streams<F>,
+            streams: &mut Streams<F>,
             _: PhantomDiscriminant,
             a: F,
             b: F,
@@ -587,9 +644,13 @@ mod phantom {
                 })
                 .collect::<eyre::Result<Vec<u8>>>()?;
             let peeked_str = String::from_utf8(bytes)?;
-            print!("{peeked_str}");
+            streams.print(&peeked_str);
             Ok(())
         }
+
+        fn provides_hints(&self) -> bool {
+            false
+        }
     }
 
     impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32HintLoadByKeySubEx {
@@ -602,15 +663,7 @@ mod phantom {
             b: F,
             _: u16,
         ) -> eyre::Result<()> {
-            let ptr = unsafe_read_rv32_register(memory, a);
-            let len = unsafe_read_rv32_register(memory, b);
-            let key: Vec<u8> = (0..len)
-                .map(|i| {
-                    memory
-                        .unsafe_read_cell(F::TWO, F::from_canonical_u32(ptr + i))
-                        .as_canonical_u32() as u8
-                })
-                .collect();
+            let key = unsafe_read_bytes(memory, a, b);
             if let Some(val) = streams.kv_store.get(&key) {
                 let to_push = hint_load_by_key_decode::<F>(val);
                 for input in to_push.into_iter().rev() {
@@ -623,6 +676,224 @@ mod phantom {
         }
     }
 
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32HintByKeySubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let key = unsafe_read_bytes(memory, a, b);
+            let Some(val) = streams.kv_store.get(&key) else {
+                bail!("Rv32HintByKey: key not found");
+            };
+            streams.hint_stream.clear();
+            streams.hint_stream.extend(
+                (val.len() as u32)
+                    .to_le_bytes()
+                    .iter()
+                    .chain(val)
+                    .map(|b| F::from_canonical_u8(*b)),
+            );
+            // Extend by 0 for 4 byte alignment
+            let capacity = 4 + val.len().div_ceil(4) * 4;
+            streams.hint_stream.resize(capacity, F::ZERO);
+            Ok(())
+        }
+    }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32HintSelectSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            _: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let queue = unsafe_read_rv32_register(memory, a);
+            streams.select_hint_queue(queue);
+            Ok(())
+        }
+    }
+
+    impl<F: Field> PhantomSubExecutor<F> for Rv32HintInputLenSubEx {
+        fn phantom_execute(
+            &mut self,
+            _: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            _: F,
+            _: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let count = streams.input_stream.len();
+            let next = streams.input_stream.front().map_or(0, Vec::len);
+            let total: usize = streams.input_stream.iter().map(Vec::len).sum();
+            streams.hint_stream.clear();
+            streams.hint_stream.extend(
+                [count, next, total]
+                    .into_iter()
+                    .flat_map(|len| (len as u32).to_le_bytes())
+                    .map(F::from_canonical_u8),
+            );
+            Ok(())
+        }
+    }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32ChannelSendSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let message = unsafe_read_bytes(memory, a, b);
+            let Some((channel, data)) = message.split_first_chunk::<4>() else {
+                bail!("Rv32ChannelSend: message without channel id");
+            };
+            streams
+                .channels
+                .send(u32::from_le_bytes(*channel), data.to_vec())
+        }
+
+        fn provides_hints(&self) -> bool {
+            false
+        }
+    }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32ChannelRecvSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            _: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let channel = unsafe_read_rv32_register(memory, a);
+            let data = streams.channels.recv(channel)?;
+            streams.hint_stream.clear();
+            streams.hint_stream.extend(
+                (data.len() as u32)
+                    .to_le_bytes()
+                    .iter()
+                    .chain(&data)
+                    .map(|b| F::from_canonical_u8(*b)),
+            );
+            // Extend by 0 for 4 byte alignment
+            let capacity = 4 + data.len().div_ceil(4) * 4;
+            streams.hint_stream.resize(capacity, F::ZERO);
+            Ok(())
+        }
+    }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32RecordPanicSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            // The message is only reported, so invalid UTF-8 should not fail execution.
+            let message = String::from_utf8_lossy(&unsafe_read_bytes(memory, a, b)).into_owned();
+            streams.panic_message = Some(message);
+            Ok(())
+        }
+
+        fn provides_hints(&self) -> bool {
+            false
+        }
+    }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32RecordHeapStatsSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let Some(profile) = HeapProfile::decode(&unsafe_read_bytes(memory, a, b)) else {
+                bail!("Rv32RecordHeapStats: invalid heap profile");
+            };
+            streams.heap_profile = Some(profile);
+            Ok(())
+        }
+
+        fn provides_hints(&self) -> bool {
+            false
+        }
+    }
+
+    impl<F: PrimeField32> PhantomSubExecutor<F> for Rv32RegionStartSubEx {
+        fn phantom_execute(
+            &mut self,
+            memory: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            a: F,
+            b: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            // As for panic messages, an invalid name should not fail execution.
+            let name = String::from_utf8_lossy(&unsafe_read_bytes(memory, a, b)).into_owned();
+            let instret = streams.instret;
+            streams.region_profile.start(name, instret);
+            Ok(())
+        }
+
+        fn provides_hints(&self) -> bool {
+            false
+        }
+    }
+
+    impl<F: Field> PhantomSubExecutor<F> for Rv32RegionEndSubEx {
+        fn phantom_execute(
+            &mut self,
+            _: &MemoryController<F>,
+            streams: &mut Streams<F>,
+            _: PhantomDiscriminant,
+            _: F,
+            _: F,
+            _: u16,
+        ) -> eyre::Result<()> {
+            let instret = streams.instret;
+            streams.region_profile.end(instret);
+            Ok(())
+        }
+
+        fn provides_hints(&self) -> bool {
+            false
+        }
+    }
+
+    /// Reads the bytes `[r32{0}(a)..r32{0}(a) + r32{0}(b)]_2` from memory.
+    fn unsafe_read_bytes<F: PrimeField32>(memory: &MemoryController<F>, a: F, b: F) -> Vec<u8> {
+        let ptr = unsafe_read_rv32_register(memory, a);
+        let len = unsafe_read_rv32_register(memory, b);
+        (0..len)
+            .map(|i| {
+                memory
+                    .unsafe_read_cell(F::TWO, F::from_canonical_u32(ptr + i))
+                    .as_canonical_u32() as u8
+            })
+            .collect()
+    }
+
     pub fn hint_load_by_key_decode<F: PrimeField32>(value: &[u8]) -> Vec<Vec<F>> {
         let mut offset = 0;
         let len = extract_u32(value, offset) as usize;
@@ -1,5 +1,5 @@
 #![allow(unused_imports)]
-use crate::{PhantomImm, PHANTOM_FUNCT3, SYSTEM_OPCODE};
+use crate::{HintQueue, PhantomImm, PHANTOM_FUNCT3, SYSTEM_OPCODE};
 
 /// Store the next 4 bytes from the hint stream to [[rd]_1]_2.
 #[macro_export]
@@ -67,6 +67,130 @@ pub fn hint_load_by_key(ptr: *const u8, len: u32) {
     );
 }
 
+/// Reset the hint stream with the keyed hint with key = [ptr: len], prepended with its length.
+#[inline(always)]
+pub fn hint_by_key(ptr: *const u8, len: u32) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In ptr,
+        rs1 = In len,
+        imm = Const PhantomImm::HintByKey as u16,
+    );
+}
+
+/// The id of the selected hint queue, kept so that [with_hint_queue] can select it again.
+static mut SELECTED_HINT_QUEUE: u32 = HintQueue::User as u32;
+
+/// Select the hint queue with id `queue`, which subsequent hints are written to and read from.
+/// The hints in the previously selected queue are kept until it is selected again.
+#[inline(always)]
+pub fn hint_select(queue: u32) {
+    unsafe { SELECTED_HINT_QUEUE = queue };
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In queue,
+        rs1 = Const "x0",
+        imm = Const PhantomImm::HintSelect as u16,
+    );
+}
+
+/// Reset the hint stream with the number of remaining input vectors, the length in bytes of the
+/// next one and their total length in bytes, as three little-endian `u32`s. No input vector is
+/// consumed.
+#[inline(always)]
+pub fn hint_input_len() {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = Const "x0",
+        rs1 = Const "x0",
+        imm = Const PhantomImm::HintInputLen as u16,
+    );
+}
+
+/// Send the message `[ptr: len]` through the channel whose id is the little-endian `u32` at the
+/// start of the message.
+#[inline(always)]
+pub fn channel_send(ptr: *const u8, len: usize) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In ptr,
+        rs1 = In len,
+        imm = Const PhantomImm::ChannelSend as u16,
+    );
+}
+
+/// Reset the hint stream with the next message received from the channel with id `channel`,
+/// prepended with its length.
+#[inline(always)]
+pub fn channel_recv(channel: u32) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In channel,
+        rs1 = Const "x0",
+        imm = Const PhantomImm::ChannelRecv as u16,
+    );
+}
+
+/// End the current segment after this instruction, so that the host can supply the input of the
+/// next phase of the program before execution continues.
+#[inline(always)]
+pub fn pause() {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = Const "x0",
+        rs1 = Const "x0",
+        imm = Const PhantomImm::Pause as u16,
+    );
+}
+
+/// Start the profiling region named by the UTF-8 string `[name_ptr: len]`, which the host
+/// attributes the cycles executed until the matching [region_end] to.
+#[inline(always)]
+pub fn raw_region_start(name_ptr: *const u8, len: usize) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In name_ptr,
+        rs1 = In len,
+        imm = Const PhantomImm::RegionStart as u16,
+    );
+}
+
+/// End the innermost profiling region started with [raw_region_start].
+#[inline(always)]
+pub fn region_end() {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = Const "x0",
+        rs1 = Const "x0",
+        imm = Const PhantomImm::RegionEnd as u16,
+    );
+}
+
+/// The id of the hint queue selected with [hint_select].
+#[inline(always)]
+pub fn selected_hint_queue() -> u32 {
+    unsafe { SELECTED_HINT_QUEUE }
+}
+
+/// Run `f` with the hint queue `queue` selected, then select the previously selected queue again,
+/// so that calls can be nested.
+#[inline(always)]
+pub fn with_hint_queue<T>(queue: HintQueue, f: impl FnOnce() -> T) -> T {
+    let previous = selected_hint_queue();
+    hint_select(queue as u32);
+    let output = f();
+    hint_select(previous);
+    output
+}
+
 /// Store rs1 to [[rd] + imm]_3.
 #[macro_export]
 macro_rules! reveal {
@@ -112,3 +236,29 @@ pub fn raw_print_str_from_bytes(msg_ptr: *const u8, len: usize) {
         imm = Const PhantomImm::PrintStr as u16
     );
 }
+
+/// Record the UTF-8 string `[msg_ptr: len]` as the panic message of the program, which the host
+/// reports if the program terminates with a nonzero exit code.
+#[inline(always)]
+pub fn raw_record_panic_from_bytes(msg_ptr: *const u8, len: usize) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In msg_ptr,
+        rs1 = In len,
+        imm = Const PhantomImm::RecordPanic as u16
+    );
+}
+
+/// Record the heap profile encoded as little-endian words in `[ptr: len]`, which the host reports
+/// when the program terminates. Used by the `heap-profile` allocator of `openvm_platform`.
+#[inline(always)]
+pub fn raw_record_heap_stats(ptr: *const u8, len: usize) {
+    openvm_custom_insn::custom_insn_i!(
+        opcode = SYSTEM_OPCODE,
+        funct3 = PHANTOM_FUNCT3,
+        rd = In ptr,
+        rs1 = In len,
+        imm = Const PhantomImm::RecordHeapStats as u16
+    );
+}
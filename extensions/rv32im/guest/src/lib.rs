@@ -16,6 +16,9 @@ pub const RV32_ALU_OPCODE: u8 = 0b0110011;
 pub const RV32M_FUNCT7: u8 = 0x01;
 pub const NATIVE_STOREW_FUNCT3: u8 = 0b111;
 pub const NATIVE_STOREW_FUNCT7: u32 = 2;
+/// Major opcode of the atomic memory operations of the RISC-V A extension.
+pub const AMO_OPCODE: u8 = 0b0101111;
+pub const AMO_W_FUNCT3: u8 = 0b010;
 
 pub const TERMINATE_FUNCT3: u8 = 0b000;
 pub const HINT_FUNCT3: u8 = 0b001;
@@ -33,6 +36,38 @@ pub enum PhantomImm {
     PrintStr,
     HintRandom,
     HintLoadByKey,
+    HintByKey,
+    HintSelect,
+    RecordPanic,
+    Pause,
+    RecordHeapStats,
+    HintInputLen,
+    ChannelSend,
+    ChannelRecv,
+    RegionStart,
+    RegionEnd,
+}
+
+/// Ids of the hint queues selected with `hint_select`. Each extension which hints through its own
+/// phantom instructions reads its hints from its own queue, so that they never interleave with user
+/// hints or the hints of other extensions. Custom extensions should use ids from
+/// [`HintQueue::Custom`] onwards.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, FromRepr)]
+#[repr(u32)]
+pub enum HintQueue {
+    /// The queue selected at the start of execution, used by `openvm::io`.
+    User = 0,
+    Modular,
+    Pairing,
+    /// The queue `openvm::io::peek_len` reads the lengths of the remaining inputs from.
+    InputLen,
+    /// The queue `openvm::io::hint_by_key` reads keyed hints from.
+    ByKey,
+    /// The queue `openvm::io::Channel::recv` reads messages from.
+    Channel,
+    /// The queue `sys_rand` reads random words from.
+    Random,
+    Custom = 0x100,
 }
 
 /// Encode a 2d-array of field elements into bytes for `hint_load_by_key`
@@ -0,0 +1,28 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm_platform::{capabilities::insn_r, has_insn};
+use openvm_rv32im_guest::{RV32M_FUNCT7, RV32_ALU_OPCODE};
+
+openvm::entry!(main);
+
+/// The custom-0 funct3 of the keccak256 extension, which the VM of the test does not have.
+const KECCAK_FUNCT3: u8 = 0b100;
+
+pub fn main() {
+    // `mul x0, x0, x0`
+    if !has_insn!(insn_r(RV32_ALU_OPCODE, 0, RV32M_FUNCT7)) {
+        openvm::process::panic();
+    }
+    if has_insn!(insn_r(0x0b, KECCAK_FUNCT3, 0)) {
+        // Not reached, but the transpiler must accept it.
+        openvm_custom_insn::custom_insn_r!(
+            opcode = 0x0b,
+            funct3 = KECCAK_FUNCT3,
+            funct7 = 0,
+            rd = Const "x0",
+            rs1 = Const "x0",
+            rs2 = Const "x0"
+        );
+        openvm::process::panic();
+    }
+}
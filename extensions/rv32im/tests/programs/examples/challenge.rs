@@ -0,0 +1,12 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm::io::{challenge, publish_bytes, read_vec, reveal_bytes32};
+
+openvm::entry!(main);
+
+pub fn main() {
+    publish_bytes(&read_vec());
+    challenge(b"alpha");
+    reveal_bytes32(challenge(b"alpha"));
+}
@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::{channel, Reader};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let mut reader = Reader::new();
+    if reader.read_frame().unwrap() != [1, 2, 3] {
+        openvm::process::panic();
+    }
+    // The host answers every message with its bytes reversed.
+    let channel = channel(7);
+    for message in [&b"ping"[..], b"hello, host"] {
+        channel.send(message);
+        let reply = channel.recv();
+        if reply.len() != message.len() || reply.iter().rev().ne(message) {
+            openvm::process::panic();
+        }
+    }
+    // Receiving through the channel leaves the rest of the input stream in the user queue.
+    if reader.read_frame().unwrap() != [4, 5] {
+        openvm::process::panic();
+    }
+}
@@ -0,0 +1,33 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::codec::{
+    decode_base64, decode_base64_url, decode_hex, encode_base64, encode_base64_url, encode_hex,
+    DecodeError,
+};
+
+openvm::entry!(main);
+
+fn main() {
+    assert_eq!(encode_hex(b"\x00\x7f\xff"), "007fff");
+    assert_eq!(encode_base64(b"foobar!"), "Zm9vYmFyIQ==");
+    assert_eq!(encode_base64_url(&[0xfb, 0xff]), "-_8");
+
+    let data: Vec<u8> = (0..=255).collect();
+    for len in [0, 1, 2, 3, 4, 5, 255, 256] {
+        let data = &data[..len];
+        assert_eq!(decode_hex(encode_hex(data).as_bytes()).unwrap(), data);
+        assert_eq!(decode_base64(encode_base64(data).as_bytes()).unwrap(), data);
+        assert_eq!(
+            decode_base64_url(encode_base64_url(data).as_bytes()).unwrap(),
+            data
+        );
+    }
+
+    assert_eq!(decode_hex(b"0g"), Err(DecodeError::InvalidByte(1)));
+    assert_eq!(decode_base64(b"Zh=="), Err(DecodeError::InvalidPadding));
+}
@@ -0,0 +1,15 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::io::{commit, read};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let values: Vec<u32> = read();
+    commit(&values);
+}
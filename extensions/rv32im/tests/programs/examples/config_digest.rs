@@ -0,0 +1,10 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::reveal_bytes32;
+use openvm_platform::capabilities::config_digest;
+
+openvm::entry!(main);
+
+pub fn main() {
+    reveal_bytes32(config_digest());
+}
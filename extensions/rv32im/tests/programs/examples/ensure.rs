@@ -0,0 +1,12 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+openvm::entry!(main);
+
+const ERR_TOO_LARGE: u32 = 42;
+
+pub fn main() {
+    let x: u32 = core::hint::black_box(7);
+    openvm::ensure!(x > 0, 1);
+    openvm::ensure!(x < 5, ERR_TOO_LARGE);
+}
@@ -0,0 +1,17 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+openvm::entry!(main);
+
+pub fn main() {
+    let mut first = [0u8; 16];
+    let mut second = [0u8; 16];
+    getrandom::fill(&mut first).unwrap();
+    getrandom::fill(&mut second).unwrap();
+    assert_ne!(first, second);
+
+    let mut bytes = [0u8; 32];
+    bytes[..16].copy_from_slice(&first);
+    bytes[16..].copy_from_slice(&second);
+    openvm::io::reveal_bytes32(bytes);
+}
@@ -0,0 +1,67 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    alloc::{alloc, dealloc, Layout},
+    boxed::Box,
+    collections::BTreeMap,
+    vec,
+    vec::Vec,
+};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Freed memory is reused.
+    let first = Box::new([1u8; 4096]);
+    let first_ptr = first.as_ptr();
+    drop(first);
+    for i in 0..1000u32 {
+        let block = Box::new([i as u8; 4096]);
+        assert_eq!(block.as_ptr(), first_ptr);
+        core::hint::black_box(&block);
+    }
+
+    // Reused memory is zeroed by `alloc_zeroed`.
+    drop(vec![0xffu8; 256]);
+    assert!(vec![0u8; 256].iter().all(|&byte| byte == 0));
+
+    // Over-aligned allocations.
+    for align in [16, 64, 4096] {
+        let layout = Layout::from_size_align(100, align).unwrap();
+        let ptr = unsafe { alloc(layout) };
+        assert_eq!(ptr as usize % align, 0);
+        unsafe { dealloc(ptr, layout) };
+    }
+
+    // Interleaved allocations, reallocations and deallocations keep their contents.
+    let mut map = BTreeMap::new();
+    for i in 0..500u32 {
+        let len = (i * 37 % 300) as usize;
+        map.insert(i, vec![i as u8; len]);
+        if i % 3 == 0 {
+            map.remove(&(i / 2));
+        }
+        if let Some(value) = map.get_mut(&(i / 4)) {
+            value.extend_from_slice(&[(i / 4) as u8; 50]);
+        }
+    }
+    for (key, value) in &map {
+        assert!(value.iter().all(|&byte| byte == *key as u8));
+    }
+    let total: usize = map.values().map(Vec::len).sum();
+    assert!(total > 0);
+    drop(map);
+
+    // Memory freed at the end of the heap is allocated again after compaction, and is zeroed by
+    // `alloc_zeroed` although it was part of the heap.
+    let tail = vec![0xffu8; 1 << 16];
+    let tail_ptr = tail.as_ptr();
+    drop(tail);
+    openvm::platform::heap::compact();
+    let zeroed = vec![0u8; 1 << 16];
+    assert_eq!(zeroed.as_ptr(), tail_ptr);
+    assert!(zeroed.iter().all(|&byte| byte == 0));
+}
@@ -0,0 +1,22 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::{io::reveal_u32, process::heap_stats};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let buffer: Vec<u8> = Vec::with_capacity(1024);
+    core::hint::black_box(&buffer);
+    drop(buffer);
+    let stats = heap_stats();
+    if stats.allocations == 0 || stats.peak_bytes < 1024 {
+        openvm::process::panic();
+    }
+    reveal_u32(stats.allocations, 0);
+    reveal_u32(stats.peak_bytes, 1);
+}
@@ -0,0 +1,16 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+
+use openvm::platform::memory::HEAP_START;
+
+openvm::entry!(main);
+
+pub fn main() {
+    // The heap begins at a fixed address, independently of the size of the program.
+    let v = vec![1u32, 2, 3];
+    assert_eq!(v.as_ptr() as u32, HEAP_START);
+}
@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::{hint_by_key, read_vec};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Keyed hints can be read in any order, any number of times, between input streams.
+    if hint_by_key(b"second") != [4, 5, 6, 7, 8] {
+        openvm::process::panic();
+    }
+    if read_vec() != [0, 1, 2] {
+        openvm::process::panic();
+    }
+    if hint_by_key(b"first") != [1, 2, 3] {
+        openvm::process::panic();
+    }
+    if hint_by_key(b"second") != [4, 5, 6, 7, 8] {
+        openvm::process::panic();
+    }
+}
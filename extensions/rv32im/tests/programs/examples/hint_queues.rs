@@ -0,0 +1,32 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::{hint_by_key, read_u32, Reader};
+use openvm_rv32im_guest::{hint_input, with_hint_queue, HintQueue};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let mut reader = Reader::new();
+    if reader.read_frame().unwrap() != [1, 2, 3] {
+        openvm::process::panic();
+    }
+    // Keyed hints are read through their own queue, which leaves the rest of the input stream in
+    // the user queue.
+    if hint_by_key(b"key") != [9, 8, 7, 6, 5] {
+        openvm::process::panic();
+    }
+    // Nested queues select the enclosing queue again instead of the user queue.
+    let words = with_hint_queue(HintQueue::Custom, || {
+        hint_input();
+        let len = read_u32();
+        let first = read_u32();
+        let hint = hint_by_key(b"key");
+        [len, first, read_u32(), hint.len() as u32]
+    });
+    if words != [8, 11, 12, 5] {
+        openvm::process::panic();
+    }
+    if reader.read_frame().unwrap() != [4, 5] {
+        openvm::process::panic();
+    }
+}
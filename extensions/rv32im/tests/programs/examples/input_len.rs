@@ -0,0 +1,27 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::{peek_len, read_vec, remaining_hint_bytes, remaining_inputs};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // The host supplies inputs of lengths 0, 1, .. n - 1.
+    let n = remaining_inputs();
+    if remaining_hint_bytes() != n * n.saturating_sub(1) / 2 {
+        openvm::process::panic();
+    }
+    let mut read = 0;
+    while let Some(len) = peek_len() {
+        if len != read || peek_len() != Some(len) {
+            openvm::process::panic();
+        }
+        let vec = read_vec();
+        if vec.len() != len || vec.iter().any(|&b| b != len as u8) {
+            openvm::process::panic();
+        }
+        read += 1;
+    }
+    if read != n || remaining_inputs() != 0 || remaining_hint_bytes() != 0 {
+        openvm::process::panic();
+    }
+}
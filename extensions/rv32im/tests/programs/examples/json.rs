@@ -0,0 +1,29 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+use openvm::io::json::{read_json, reveal_json};
+
+openvm::entry!(main);
+
+#[derive(serde::Deserialize)]
+struct Quote {
+    symbol: String,
+    prices: Vec<u64>,
+}
+
+#[derive(serde::Serialize)]
+struct Summary<'a> {
+    symbol: &'a str,
+    max: u64,
+}
+
+pub fn main() {
+    let quote: Quote = read_json();
+    let max = quote.prices.iter().copied().max().unwrap_or(0);
+    reveal_json(&Summary {
+        symbol: &quote.symbol,
+        max,
+    });
+}
@@ -0,0 +1,27 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm::overlay::{include_overlay_bytes, Overlay, PAGE_SIZE};
+
+openvm::entry!(main);
+
+static TABLE: &Overlay = include_overlay_bytes!("data/overlay.bin");
+
+fn expected(i: usize) -> u8 {
+    (i * 7 + i / 256) as u8
+}
+
+pub fn main() {
+    assert_eq!(TABLE.len(), 5000);
+    // Reads within a page, across a page boundary and in the last, partial page. The pages in
+    // between are never loaded.
+    for range in [10..20, PAGE_SIZE - 3..PAGE_SIZE + 5, 4990..5000, 0..0] {
+        let bytes = TABLE.get(range.clone());
+        assert_eq!(bytes.len(), range.len());
+        for (&byte, i) in bytes.iter().zip(range) {
+            assert_eq!(byte, expected(i));
+        }
+    }
+    // Reads from a page which is already loaded.
+    assert_eq!(TABLE.get(12..13), [expected(12)]);
+}
@@ -0,0 +1,11 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+openvm::entry!(main);
+
+pub fn main() {
+    let x: u32 = core::hint::black_box(7);
+    if x == 7 {
+        panic!("x is {x}");
+    }
+}
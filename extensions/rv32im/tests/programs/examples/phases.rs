@@ -0,0 +1,21 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm::io::{read, reveal_u32};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Phase 1: commit to a challenge derived from the input.
+    let seed: u32 = read();
+    let challenge = seed.wrapping_mul(0x9e37_79b9);
+    reveal_u32(challenge, 0);
+    openvm::process::pause();
+
+    // Phase 2: check the response, which the host computed from the revealed challenge.
+    let response: u32 = read();
+    if response != challenge.rotate_left(7) {
+        openvm::process::panic();
+    }
+    reveal_u32(response, 1);
+}
@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm::{
+    io::reveal_u32,
+    profile::{end_region, start_region},
+};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let mut sum: u32 = 0;
+    start_region!("outer");
+    for round in 0..3 {
+        start_region!("inner");
+        for i in 0..100 * (round + 1) {
+            sum = core::hint::black_box(sum.wrapping_add(i));
+        }
+        end_region!();
+    }
+    end_region!();
+    // Ending a region when none is open is ignored.
+    end_region!();
+    reveal_u32(sum, 0);
+}
@@ -0,0 +1,32 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm::rayon::{self, prelude::*};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let mut values: Vec<u32> = (0..64u32)
+        .into_par_iter()
+        .map(|x| core::hint::black_box(x * 7 % 64))
+        .collect();
+    values.par_sort_unstable();
+
+    let sum_of_squares: u32 = values.par_iter().map(|x| x * x).sum();
+    let (evens, odds) = rayon::join(
+        || values.par_iter().filter(|&&x| x % 2 == 0).count(),
+        || values.par_iter().filter(|&&x| x % 2 == 1).count(),
+    );
+    let chunk_sums: Vec<u32> = values
+        .par_chunks(16)
+        .map(|chunk| chunk.iter().sum())
+        .collect();
+
+    if sum_of_squares != 85344 || (evens, odds) != (32, 32) || chunk_sums != [120, 376, 632, 888] {
+        openvm::process::panic();
+    }
+}
@@ -0,0 +1,26 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+use openvm::io::read_borrowed;
+
+openvm::entry!(main);
+
+#[derive(serde::Deserialize)]
+struct Witness<'a> {
+    name: &'a str,
+    data: &'a [u8],
+}
+
+pub fn main() {
+    let witness: Witness = read_borrowed();
+    if witness.name != "witness" {
+        openvm::process::panic();
+    }
+    if witness.data.len() != 1000 {
+        openvm::process::panic();
+    }
+    for (i, &byte) in witness.data.iter().enumerate() {
+        if byte != i as u8 {
+            openvm::process::panic();
+        }
+    }
+}
@@ -0,0 +1,33 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+extern crate alloc;
+use openvm::io::Reader;
+
+openvm::entry!(main);
+
+#[derive(serde::Deserialize)]
+struct Foo {
+    bar: u32,
+    baz: alloc::vec::Vec<u32>,
+}
+
+#[allow(clippy::disallowed_names)]
+pub fn main() {
+    let mut reader = Reader::new();
+    if reader.peek_len() != Some(3) {
+        openvm::process::panic();
+    }
+    if reader.read_frame().unwrap() != [1, 2, 3] {
+        openvm::process::panic();
+    }
+    let foo: Foo = reader.read_as().unwrap();
+    if foo.bar != 42 || foo.baz != [0, 1, 2, 3] {
+        openvm::process::panic();
+    }
+    if !reader.read_frame().unwrap().is_empty() {
+        openvm::process::panic();
+    }
+    if reader.peek_len().is_some() {
+        openvm::process::panic();
+    }
+}
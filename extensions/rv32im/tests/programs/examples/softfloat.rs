@@ -0,0 +1,268 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Executes instructions of the F and D extensions, which trap into the handler of
+// `openvm-softfloat-guest`, and checks their results against the software floating-point routines
+// of `compiler_builtins`, which the program uses for `f32` and `f64` since it is built without
+// the F and D extensions. The instructions are written with `.insn` for the same reason.
+
+use core::arch::asm;
+
+openvm::entry!(main);
+
+/// Runs `$insn` on the binary32 values of `f1`, `f2` and `f3`, and returns the binary32 value it
+/// writes to `f4`.
+macro_rules! op_s {
+    ($insn:literal, $a:expr, $b:expr, $c:expr) => {{
+        let out: u32;
+        unsafe {
+            asm!(
+                ".insn r 0x53, 0, 0x78, f1, {a}, x0",
+                ".insn r 0x53, 0, 0x78, f2, {b}, x0",
+                ".insn r 0x53, 0, 0x78, f3, {c}, x0",
+                $insn,
+                ".insn r 0x53, 0, 0x70, {out}, f4, x0",
+                a = in(reg) f32::to_bits($a),
+                b = in(reg) f32::to_bits($b),
+                c = in(reg) f32::to_bits($c),
+                out = lateout(reg) out,
+            );
+        }
+        f32::from_bits(out)
+    }};
+}
+
+/// Runs `$insn` on the binary32 values of `f1` and `f2`, and returns the integer register `{out}`
+/// it writes.
+macro_rules! op_s_to_x {
+    ($insn:literal, $a:expr, $b:expr) => {{
+        let out: u32;
+        unsafe {
+            asm!(
+                ".insn r 0x53, 0, 0x78, f1, {a}, x0",
+                ".insn r 0x53, 0, 0x78, f2, {b}, x0",
+                $insn,
+                a = in(reg) f32::to_bits($a),
+                b = in(reg) f32::to_bits($b),
+                out = lateout(reg) out,
+            );
+        }
+        out
+    }};
+}
+
+/// Runs `$insn` on the binary64 values of `f1`, `f2` and `f3`, and returns the binary64 value it
+/// writes to `f4`. The values are loaded and stored with `fld` and `fsd`.
+macro_rules! op_d {
+    ($insn:literal, $a:expr, $b:expr, $c:expr) => {{
+        let mut values = [f64::to_bits($a), f64::to_bits($b), f64::to_bits($c), 0];
+        unsafe {
+            asm!(
+                ".insn i 0x07, 3, f1, 0({p})",
+                ".insn i 0x07, 3, f2, 8({p})",
+                ".insn i 0x07, 3, f3, 16({p})",
+                $insn,
+                ".insn s 0x27, 3, f4, 24({p})",
+                p = in(reg) values.as_mut_ptr(),
+            );
+        }
+        f64::from_bits(values[3])
+    }};
+}
+
+/// Runs `$insn` on the binary64 values of `f1` and `f2`, and returns the integer register `{out}`
+/// it writes.
+macro_rules! op_d_to_x {
+    ($insn:literal, $a:expr, $b:expr) => {{
+        let values = [f64::to_bits($a), f64::to_bits($b)];
+        let out: u32;
+        unsafe {
+            asm!(
+                ".insn i 0x07, 3, f1, 0({p})",
+                ".insn i 0x07, 3, f2, 8({p})",
+                $insn,
+                p = in(reg) values.as_ptr(),
+                out = lateout(reg) out,
+            );
+        }
+        out
+    }};
+}
+
+fn assert_same_s(result: f32, expected: f32) {
+    assert_eq!(result.to_bits(), expected.to_bits());
+}
+
+fn assert_same_d(result: f64, expected: f64) {
+    assert_eq!(result.to_bits(), expected.to_bits());
+}
+
+pub fn main() {
+    let (a, b, c) = (1.5f32, -0.1f32, 3.0e-3f32);
+
+    // fadd.s, fsub.s, fmul.s and fdiv.s with the dynamic rounding mode, which is to the nearest.
+    assert_same_s(op_s!(".insn r 0x53, 7, 0x00, f4, f1, f2", a, b, c), a + b);
+    assert_same_s(op_s!(".insn r 0x53, 7, 0x04, f4, f1, f2", a, b, c), a - b);
+    assert_same_s(op_s!(".insn r 0x53, 7, 0x08, f4, f1, f2", a, b, c), a * b);
+    assert_same_s(op_s!(".insn r 0x53, 7, 0x0c, f4, f1, f2", a, b, c), a / b);
+    // A subnormal product.
+    assert_same_s(
+        op_s!(
+            ".insn r 0x53, 7, 0x08, f4, f1, f2",
+            f32::MIN_POSITIVE,
+            0.5,
+            0.0
+        ),
+        f32::MIN_POSITIVE * 0.5,
+    );
+    // 0 / 0 is the canonical NaN.
+    assert_eq!(
+        op_s!(".insn r 0x53, 7, 0x0c, f4, f1, f2", 0.0, 0.0, 0.0).to_bits(),
+        0x7fc0_0000
+    );
+    // fsqrt.s
+    assert_eq!(
+        op_s!(".insn r 0x53, 7, 0x2c, f4, f1, x0", 2.0, 0.0, 0.0).to_bits(),
+        0x3fb5_04f3
+    );
+    // fmadd.s rounds once: a * b + c, which is exact in binary64.
+    let fused = op_s!(".insn r4 0x43, 7, 0, f4, f1, f2, f3", a, b, c);
+    assert_same_s(fused, (a as f64 * b as f64 + c as f64) as f32);
+    // fnmsub.s: -(a * b) + c.
+    assert_same_s(
+        op_s!(".insn r4 0x4b, 7, 0, f4, f1, f2, f3", a, b, c),
+        (-(a as f64 * b as f64) + c as f64) as f32,
+    );
+    // fsgnj.s, fsgnjn.s and fsgnjx.s.
+    assert_same_s(op_s!(".insn r 0x53, 0, 0x10, f4, f1, f2", a, b, c), -a);
+    assert_same_s(op_s!(".insn r 0x53, 1, 0x10, f4, f1, f2", a, b, c), a);
+    assert_same_s(op_s!(".insn r 0x53, 2, 0x10, f4, f1, f2", -a, b, c), a);
+    // fmin.s and fmax.s order -0.0 below +0.0, and ignore a NaN operand.
+    assert_same_s(
+        op_s!(".insn r 0x53, 0, 0x14, f4, f1, f2", 0.0, -0.0, c),
+        -0.0,
+    );
+    assert_same_s(
+        op_s!(".insn r 0x53, 1, 0x14, f4, f1, f2", f32::NAN, b, c),
+        b,
+    );
+
+    // feq.s, flt.s and fle.s.
+    assert_eq!(op_s_to_x!(".insn r 0x53, 2, 0x50, {out}, f1, f2", a, a), 1);
+    assert_eq!(op_s_to_x!(".insn r 0x53, 1, 0x50, {out}, f1, f2", b, a), 1);
+    assert_eq!(op_s_to_x!(".insn r 0x53, 0, 0x50, {out}, f1, f2", a, b), 0);
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 2, 0x50, {out}, f1, f2", f32::NAN, f32::NAN),
+        0
+    );
+    // fclass.s of -0.0, +inf and a quiet NaN.
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 1, 0x70, {out}, f1, x0", -0.0, 0.0),
+        1 << 3
+    );
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 1, 0x70, {out}, f1, x0", f32::INFINITY, 0.0),
+        1 << 7
+    );
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 1, 0x70, {out}, f1, x0", f32::NAN, 0.0),
+        1 << 9
+    );
+    // fcvt.w.s with the static rounding modes to the nearest, toward zero and down, and
+    // saturating out of range.
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 0, 0x60, {out}, f1, x0", -2.5, 0.0) as i32,
+        -2
+    );
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 1, 0x60, {out}, f1, x0", -2.5, 0.0) as i32,
+        -2
+    );
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 2, 0x60, {out}, f1, x0", -2.5, 0.0) as i32,
+        -3
+    );
+    assert_eq!(
+        op_s_to_x!(".insn r 0x53, 1, 0x60, {out}, f1, x0", 3.0e9, 0.0) as i32,
+        i32::MAX
+    );
+
+    // fcvt.s.w
+    let x: i32 = -123_456_789;
+    let converted: u32;
+    unsafe {
+        asm!(
+            ".insn r 0x53, 7, 0x68, f4, {x}, x0",
+            ".insn r 0x53, 0, 0x70, {out}, f4, x0",
+            x = in(reg) x,
+            out = lateout(reg) converted,
+        );
+    }
+    assert_same_s(f32::from_bits(converted), x as f32);
+
+    let (a, b, c) = (0.1f64, 10.0f64, -1.0f64);
+
+    // fadd.d, fmul.d and fdiv.d.
+    assert_same_d(op_d!(".insn r 0x53, 7, 0x01, f4, f1, f2", a, b, c), a + b);
+    assert_same_d(op_d!(".insn r 0x53, 7, 0x09, f4, f1, f2", a, b, c), a * b);
+    assert_same_d(op_d!(".insn r 0x53, 7, 0x0d, f4, f1, f2", c, a, b), c / a);
+    // fsqrt.d
+    assert_eq!(
+        op_d!(".insn r 0x53, 7, 0x2d, f4, f1, x0", 2.0, 0.0, 0.0).to_bits(),
+        0x3ff6_a09e_667f_3bcd
+    );
+    // fmadd.d rounds once: 0.1 * 10 - 1 is 2^-54, while the rounded product is exactly 1.
+    assert_eq!(a * b + c, 0.0);
+    assert_eq!(
+        op_d!(".insn r4 0x43, 7, 1, f4, f1, f2, f3", a, b, c).to_bits(),
+        0x3c90_0000_0000_0000
+    );
+    // flt.d and fcvt.w.d toward zero.
+    assert_eq!(op_d_to_x!(".insn r 0x53, 1, 0x51, {out}, f1, f2", c, a), 1);
+    assert_eq!(
+        op_d_to_x!(".insn r 0x53, 1, 0x61, {out}, f1, x0", -7.9, 0.0) as i32,
+        -7
+    );
+
+    // fcvt.d.s and fcvt.s.d, whose rs2 field is the format of the source: 0 for binary32 and 1
+    // (written `f1`) for binary64.
+    let single = 0.1f32;
+    let mut double = 0u64;
+    unsafe {
+        asm!(
+            ".insn r 0x53, 0, 0x78, f1, {x}, x0",
+            ".insn r 0x53, 7, 0x21, f4, f1, x0",
+            ".insn s 0x27, 3, f4, 0({p})",
+            x = in(reg) single.to_bits(),
+            p = in(reg) &mut double as *mut u64,
+        );
+    }
+    assert_same_d(f64::from_bits(double), single as f64);
+    let narrowed: u32;
+    unsafe {
+        asm!(
+            ".insn i 0x07, 3, f1, 0({p})",
+            ".insn r 0x53, 7, 0x20, f4, f1, f1",
+            ".insn r 0x53, 0, 0x70, {out}, f4, x0",
+            p = in(reg) &0.1f64 as *const f64,
+            out = lateout(reg) narrowed,
+        );
+    }
+    assert_same_s(f32::from_bits(narrowed), 0.1f64 as f32);
+
+    // fsw and flw.
+    let mut word = 0u32;
+    let loaded: u32;
+    unsafe {
+        asm!(
+            ".insn r 0x53, 0, 0x78, f1, {x}, x0",
+            ".insn s 0x27, 2, f1, 0({p})",
+            ".insn i 0x07, 2, f2, 0({p})",
+            ".insn r 0x53, 0, 0x70, {out}, f2, x0",
+            x = in(reg) 0x4049_0fdbu32,
+            p = in(reg) &mut word as *mut u32,
+            out = lateout(reg) loaded,
+        );
+    }
+    assert_eq!((word, loaded), (0x4049_0fdb, 0x4049_0fdb));
+}
@@ -0,0 +1,29 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::hint::black_box;
+
+use openvm::io::read;
+use openvm_platform::memory::STACK_BOTTOM;
+use openvm_rv32im_guest::{hint_input, hint_store_u32};
+
+openvm::entry!(main);
+
+/// Recurses `depth` times, with stack frames of at least 64 bytes.
+fn recurse(depth: u32) -> u32 {
+    let frame = black_box([depth; 16]);
+    if depth == 0 {
+        return frame[0];
+    }
+    recurse(depth - 1).wrapping_add(frame[15])
+}
+
+fn main() {
+    let depth: u32 = read();
+    if depth == u32::MAX {
+        // Writes to the guard region with a hint store instead of a store.
+        hint_input();
+        hint_store_u32!(STACK_BOTTOM - 4);
+    }
+    black_box(recurse(depth));
+}
@@ -0,0 +1,67 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use openvm::thread;
+
+openvm::entry!(main);
+
+pub fn main() {
+    let inputs: Vec<u32> = (1..=8).map(core::hint::black_box).collect();
+
+    let handles: Vec<_> = inputs
+        .chunks(2)
+        .map(|chunk| {
+            let chunk = chunk.to_vec();
+            thread::spawn(move || chunk.iter().map(|x| x * x).sum::<u32>())
+        })
+        .collect();
+    let sum: u32 = handles.into_iter().map(|h| h.join().unwrap()).sum();
+
+    let mut order = Vec::new();
+    thread::scope(|s| {
+        let first = s.spawn(|| 1u32);
+        let second = s.spawn(|| 2u32);
+        order.push(second.join().unwrap());
+        order.push(first.join().unwrap());
+    });
+
+    // Two threads take turns incrementing a counter, each yielding until it is its turn.
+    let counter = AtomicU32::new(0);
+    let turns: Vec<u32> = thread::scope(|s| {
+        let take_turns = |parity: u32| {
+            let counter = &counter;
+            move || {
+                let mut turns = 0;
+                loop {
+                    let value = counter.load(Ordering::Relaxed);
+                    if value >= 10 {
+                        return turns;
+                    }
+                    if value % 2 == parity {
+                        counter.store(value + 1, Ordering::Relaxed);
+                        turns += 1;
+                    }
+                    thread::yield_now();
+                }
+            }
+        };
+        let even = s.spawn(take_turns(0));
+        let odd = s.spawn(take_turns(1));
+        [even, odd].map(|h| h.join().unwrap()).to_vec()
+    });
+
+    // A detached thread runs once the main thread yields.
+    static DETACHED: AtomicU32 = AtomicU32::new(0);
+    drop(thread::spawn(|| DETACHED.store(1, Ordering::Relaxed)));
+    thread::yield_now();
+    let detached = DETACHED.load(Ordering::Relaxed);
+
+    if sum != 204 || order != [2, 1] || turns != [5, 5] || detached != 1 {
+        openvm::process::panic();
+    }
+}
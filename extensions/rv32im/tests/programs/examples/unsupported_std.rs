@@ -0,0 +1,19 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Uses networking, the file system and clocks, which the zkVM does not support, when its input is
+// not empty. It is only built, to find these features in its ELF.
+
+use std::{fs::File, net::TcpStream, time::Instant};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let address: String = openvm::io::read();
+    if !address.is_empty() {
+        let start = Instant::now();
+        let connected = TcpStream::connect(address.as_str()).is_ok();
+        let opened = File::open(&address).is_ok();
+        println!("{connected} {opened} {:?}", start.elapsed());
+    }
+}
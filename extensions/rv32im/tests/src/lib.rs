@@ -3,9 +3,14 @@ mod tests {
     use std::{collections::HashMap, sync::Arc};
 
     use eyre::Result;
+    use openvm::io::{Journal, JOURNAL_INLINE_SIZE, JOURNAL_SIZE, PUBLISHED_OFFSET};
+    use openvm_build::find_unsupported_std_features;
     use openvm_circuit::{
-        arch::{hasher::poseidon2::vm_poseidon2_hasher, ExecutionError, Streams, VmExecutor},
-        system::memory::tree::public_values::UserPublicValuesProof,
+        arch::{
+            hasher::poseidon2::vm_poseidon2_hasher, ChannelHandler, ChannelMessage, ExecutionError,
+            HostChannels, Streams, SystemConfig, VmExecutor, VmMemoryState,
+        },
+        system::memory::tree::public_values::{extract_public_values, UserPublicValuesProof},
         utils::{air_test, air_test_with_min_segments},
     };
     use openvm_instructions::exe::VmExe;
@@ -14,12 +19,23 @@ mod tests {
     use openvm_rv32im_transpiler::{
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
     };
-    use openvm_stark_sdk::{openvm_stark_backend::p3_field::FieldAlgebra, p3_baby_bear::BabyBear};
+    use openvm_sha256_circuit::Sha256Rv32Config;
+    use openvm_sha256_transpiler::Sha256TranspilerExtension;
+    use openvm_softfloat_transpiler::SoftfloatTranspilerExtension;
+    use openvm_stark_sdk::{
+        openvm_stark_backend::p3_field::{FieldAlgebra, PrimeField32},
+        p3_baby_bear::BabyBear,
+    };
     use openvm_toolchain_tests::{
-        build_example_program_at_path, build_example_program_at_path_with_features,
+        build_example_elf_at_path, build_example_program_at_path,
+        build_example_program_at_path_with_debug_info, build_example_program_at_path_with_features,
         get_programs_dir,
     };
-    use openvm_transpiler::{transpiler::Transpiler, FromElf};
+    use openvm_transpiler::{
+        metadata::{TranspilerMetadata, TranspilerMetadataError},
+        transpiler::{Transpiler, TranspilerError},
+        FromElf,
+    };
     use test_case::test_case;
 
     type F = BabyBear;
@@ -40,6 +56,9 @@ mod tests {
     }
 
     #[test_case("collatz", 1)]
+    #[test_case("codec", 1)]
+    #[test_case("heap_start", 1)]
+    #[test_case("thread", 1)]
     fn test_rv32im(example_name: &str, min_segments: usize) -> Result<()> {
         let config = Rv32ImConfig::default();
         let elf = build_example_program_at_path(get_programs_dir!(), example_name, &config)?;
@@ -75,6 +94,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_transpiler_metadata() -> Result<()> {
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "fibonacci", &config)?;
+        let transpiler = Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension);
+        let metadata = transpiler.metadata();
+        let exe = VmExe::from_elf(elf, transpiler)?;
+        assert_eq!(TranspilerMetadata::read(&exe), Some(metadata));
+
+        let transpiler = Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension);
+        transpiler.check_metadata(&exe)?;
+        // A transpiler without the M extension has a different opcode map.
+        let transpiler = Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension);
+        assert!(matches!(
+            transpiler.check_metadata(&exe),
+            Err(TranspilerMetadataError::OpcodeMapMismatch { .. })
+        ));
+        // The metadata is stored in the initial memory image.
+        assert!(matches!(
+            transpiler.check_metadata(&VmExe::new(exe.program.clone())),
+            Err(TranspilerMetadataError::Missing)
+        ));
+        Ok(())
+    }
+
     #[test]
     fn test_read_vec() -> Result<()> {
         let config = Rv32IConfig::default();
@@ -91,6 +143,154 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_input_len() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "input_len", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let input = (0..6u8)
+            .map(|len| vec![F::from_canonical_u8(len); len as usize])
+            .collect::<Vec<_>>();
+        air_test_with_min_segments(config, exe, input, 1);
+        Ok(())
+    }
+
+    /// Answers every message with its bytes reversed.
+    #[derive(Default)]
+    struct Reverse {
+        pending: Vec<Vec<u8>>,
+    }
+
+    impl ChannelHandler for Reverse {
+        fn send(&mut self, _channel: u32, data: &[u8]) {
+            self.pending.push(data.iter().rev().copied().collect());
+        }
+
+        fn recv(&mut self, _channel: u32) -> Vec<u8> {
+            self.pending.remove(0)
+        }
+    }
+
+    #[test]
+    fn test_channel() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "channel", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let frames: [&[u8]; 2] = [&[1, 2, 3], &[4, 5]];
+        let mut input = vec![];
+        for frame in frames {
+            input.extend((frame.len() as u32).to_le_bytes());
+            input.extend(frame);
+            input.resize(input.len().next_multiple_of(4), 0);
+        }
+        let streams: Streams<F> =
+            vec![input.into_iter().map(F::from_canonical_u8).collect()].into();
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let channels = HostChannels::interactive(Reverse::default());
+        let transcript = executor
+            .execute(exe.clone(), streams.clone().with_channels(channels))?
+            .channel_transcript;
+        let expected = [&b"ping"[..], b"hello, host"]
+            .into_iter()
+            .flat_map(|message| {
+                [
+                    ChannelMessage::Sent {
+                        channel: 7,
+                        data: message.to_vec(),
+                    },
+                    ChannelMessage::Received {
+                        channel: 7,
+                        data: message.iter().rev().copied().collect(),
+                    },
+                ]
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(transcript.messages, expected);
+
+        // Replaying fails once the guest reacts to a reply which was not recorded.
+        let mut tampered = transcript.clone();
+        tampered.messages[1] = ChannelMessage::Received {
+            channel: 7,
+            data: b"gnop".to_vec(),
+        };
+        let channels = HostChannels::replay(tampered, 0);
+        assert!(executor
+            .execute(exe.clone(), streams.clone().with_channels(channels))
+            .is_err());
+
+        let channels = HostChannels::replay(transcript, 0);
+        air_test_with_min_segments(config, exe, streams.with_channels(channels), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_capabilities() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "capabilities", &config)?;
+        // The program holds a keccak256 instruction, which no extension of the transpiler claims.
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        air_test(config, exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_config_digest() -> Result<()> {
+        let config = Rv32IConfig::with_public_values(64);
+        let elf = build_example_program_at_path(get_programs_dir!(), "config_digest", &config)?;
+        let transpiler = Transpiler::<F>::default()
+            .with_extension(Rv32ITranspilerExtension)
+            .with_extension(Rv32MTranspilerExtension)
+            .with_extension(Rv32IoTranspilerExtension);
+        assert!(matches!(
+            VmExe::<F>::from_elf(elf.clone(), Transpiler::default()),
+            Err(TranspilerError::MissingConfigDigest)
+        ));
+        // Bytes with the high bit set, which `addi` sign-extends.
+        let digest: [u8; 32] = std::array::from_fn(|i| (i as u8).wrapping_mul(0x9d));
+        let exe = VmExe::from_elf(
+            elf,
+            transpiler
+                .with_config_digest(digest)
+                .with_config_digest_public_value(32),
+        )?;
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let final_memory = executor.execute(exe, vec![])?.final_memory.unwrap();
+        let pv_proof = UserPublicValuesProof::compute(
+            config.system.memory_config.memory_dimensions(),
+            64,
+            &vm_poseidon2_hasher(),
+            &final_memory,
+        );
+        // Revealed by the program, and written by the transpiler.
+        assert_eq!(
+            pv_proof.public_values,
+            [digest, digest]
+                .concat()
+                .into_iter()
+                .map(F::from_canonical_u8)
+                .collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_hint_load_by_key() -> Result<()> {
         let config = Rv32IConfig::default();
@@ -114,6 +314,138 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_hint_by_key() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "hint_by_key", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let stdin = vec![[0, 1, 2].map(F::from_canonical_u8).to_vec()];
+        let mut streams: Streams<F> = stdin.into();
+        streams.kv_store = Arc::new(HashMap::from([
+            (b"first".to_vec(), vec![1u8, 2, 3]),
+            (b"second".to_vec(), vec![4u8, 5, 6, 7, 8]),
+        ]));
+        air_test_with_min_segments(config, exe, streams, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overlay() -> Result<()> {
+        // Pages are verified with the sha256 extension.
+        let config = Sha256Rv32Config::default();
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "overlay",
+            ["overlay"],
+            &config,
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Sha256TranspilerExtension),
+        )?;
+        let data = std::fs::read(get_programs_dir!().join("data/overlay.bin"))?;
+        assert_eq!(exe.overlays.values().collect::<Vec<_>>(), [&data]);
+        air_test(config, exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_json() -> Result<()> {
+        // Published data is hashed with the sha256 extension.
+        let config = Sha256Rv32Config {
+            system: SystemConfig::default()
+                .with_continuations()
+                .with_public_values(64),
+            ..Default::default()
+        };
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "json",
+            ["json"],
+            &config,
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Sha256TranspilerExtension),
+        )?;
+        let input = br#"{"symbol": "ETH", "prices": [3100, 3250, 3175]}"#
+            .iter()
+            .map(|&b| F::from_canonical_u8(b))
+            .collect();
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let final_memory = executor.execute(exe, vec![input])?.final_memory.unwrap();
+        let public_values: Vec<u8> = UserPublicValuesProof::compute(
+            config.system.memory_config.memory_dimensions(),
+            64,
+            &vm_poseidon2_hasher(),
+            &final_memory,
+        )
+        .public_values
+        .iter()
+        .map(|x| x.as_canonical_u32() as u8)
+        .collect();
+        assert_eq!(
+            public_values[PUBLISHED_OFFSET..],
+            openvm::io::published_digest([br#"{"symbol":"ETH","max":3250}"#.as_slice()])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_rayon() -> Result<()> {
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "rayon",
+            ["rayon-compat"],
+            &config,
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        air_test(config, exe);
+        Ok(())
+    }
+
+    #[test]
+    fn test_softfloat() -> Result<()> {
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "softfloat",
+            ["softfloat"],
+            &config,
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(SoftfloatTranspilerExtension),
+        )?;
+        air_test(config, exe);
+        Ok(())
+    }
+
     #[test]
     fn test_read() -> Result<()> {
         let config = Rv32IConfig::default();
@@ -145,6 +477,196 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_read_borrowed() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "read_borrowed", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        #[derive(serde::Serialize)]
+        struct Witness<'a> {
+            name: &'a str,
+            // Byte slices are borrowed only if they are serialized as bytes.
+            #[serde(serialize_with = "serialize_bytes")]
+            data: &'a [u8],
+        }
+        fn serialize_bytes<S: serde::Serializer>(
+            bytes: &&[u8],
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.serialize_bytes(bytes)
+        }
+        let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+        let witness = Witness {
+            name: "witness",
+            data: &data,
+        };
+        let input = openvm::serde::to_vec(&witness)
+            .unwrap()
+            .into_iter()
+            .flat_map(|w| w.to_le_bytes())
+            .map(F::from_canonical_u8)
+            .collect();
+        air_test_with_min_segments(config, exe, vec![input], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_phases() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "phases", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        let encode = |x: u32| -> Vec<F> {
+            openvm::serde::to_vec(&x)
+                .unwrap()
+                .into_iter()
+                .flat_map(|w| w.to_le_bytes())
+                .map(F::from_canonical_u8)
+                .collect()
+        };
+        let public_value_word = |memory: &VmMemoryState<F>, i: usize| {
+            let public_values = extract_public_values(
+                &config.system.memory_config.memory_dimensions(),
+                config.system.num_public_values,
+                memory,
+            );
+            u32::from_le_bytes(std::array::from_fn(|j| {
+                public_values[4 * i + j].as_canonical_u32() as u8
+            }))
+        };
+
+        let seed = 12345u32;
+        let challenge = seed.wrapping_mul(0x9e37_79b9);
+        let mut num_pauses = 0;
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let result = executor.execute_phases(exe, vec![encode(seed)], |memory| {
+            num_pauses += 1;
+            // The response is computed from the challenge revealed in the first phase.
+            vec![encode(public_value_word(memory, 0).rotate_left(7))]
+        })?;
+        assert_eq!(num_pauses, 1);
+        let final_memory = result.final_memory.as_ref().unwrap();
+        assert_eq!(public_value_word(final_memory, 0), challenge);
+        assert_eq!(public_value_word(final_memory, 1), challenge.rotate_left(7));
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_frames() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "read_frames", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        #[derive(serde::Serialize)]
+        struct Foo {
+            bar: u32,
+            baz: Vec<u32>,
+        }
+        let foo = Foo {
+            bar: 42,
+            baz: vec![0, 1, 2, 3],
+        };
+        let serialized_foo: Vec<u8> = openvm::serde::to_vec(&foo)
+            .unwrap()
+            .into_iter()
+            .flat_map(|w| w.to_le_bytes())
+            .collect();
+        // Each frame is its length followed by its bytes, padded to a multiple of 4 bytes.
+        let frames: [&[u8]; 3] = [&[1, 2, 3], &serialized_foo, &[]];
+        let mut input = vec![];
+        for frame in frames {
+            input.extend((frame.len() as u32).to_le_bytes());
+            input.extend(frame);
+            input.resize(input.len().next_multiple_of(4), 0);
+        }
+        let input = input.into_iter().map(F::from_canonical_u8).collect();
+        air_test_with_min_segments(config, exe, vec![input], 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hint_queues() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "hint_queues", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let frames: [&[u8]; 2] = [&[1, 2, 3], &[4, 5]];
+        let mut input = vec![];
+        for frame in frames {
+            input.extend((frame.len() as u32).to_le_bytes());
+            input.extend(frame);
+            input.resize(input.len().next_multiple_of(4), 0);
+        }
+        let custom = [11u32, 12].into_iter().flat_map(u32::to_le_bytes);
+        let mut streams: Streams<F> = vec![
+            input.into_iter().map(F::from_canonical_u8).collect(),
+            custom.map(F::from_canonical_u8).collect(),
+        ]
+        .into();
+        streams.kv_store = Arc::new(HashMap::from([(b"key".to_vec(), vec![9u8, 8, 7, 6, 5])]));
+        air_test_with_min_segments(config, exe, streams, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_profile_regions() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "profile_regions", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        // Regions spanning several segments are recorded as a whole.
+        let mut segmented_config = config.clone();
+        segmented_config.system = segmented_config.system.with_max_segment_len(200);
+        let [profile, segmented_profile] = [config, segmented_config].map(|config| {
+            let executor = VmExecutor::<F, _>::new(config);
+            executor
+                .execute(exe.clone(), vec![])
+                .unwrap()
+                .region_profile
+        });
+        assert_eq!(profile, segmented_profile);
+
+        let regions = profile.regions();
+        assert_eq!(regions.len(), 2);
+        let (outer, inner) = (&regions[0], &regions[1]);
+        assert_eq!((outer.name.as_str(), outer.count), ("outer", 1));
+        assert_eq!((inner.name.as_str(), inner.count), ("inner", 3));
+        // The loops of the inner regions run 600 times.
+        assert!(inner.total_cycles >= 600);
+        assert!(outer.total_cycles > inner.total_cycles);
+        Ok(())
+    }
+
     #[test]
     fn test_reveal() -> Result<()> {
         let config = Rv32IConfig::default();
@@ -157,7 +679,7 @@ mod tests {
                 .with_extension(Rv32IoTranspilerExtension),
         )?;
         let executor = VmExecutor::<F, _>::new(config.clone());
-        let final_memory = executor.execute(exe, vec![])?.unwrap();
+        let final_memory = executor.execute(exe, vec![])?.final_memory.unwrap();
         let hasher = vm_poseidon2_hasher();
         let pv_proof = UserPublicValuesProof::compute(
             config.system.memory_config.memory_dimensions(),
@@ -184,6 +706,94 @@ mod tests {
         Ok(())
     }
 
+    #[test_case(vec![1, 2, 3])]
+    #[test_case((0..100).collect())]
+    fn test_commit(values: Vec<u32>) -> Result<()> {
+        // Values which do not fit in the journal are hashed with the sha256 extension.
+        let config = Sha256Rv32Config::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "commit", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Sha256TranspilerExtension),
+        )?;
+        let input = openvm::serde::to_vec(&values)
+            .unwrap()
+            .into_iter()
+            .flat_map(|w| w.to_le_bytes())
+            .map(F::from_canonical_u8)
+            .collect();
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let final_memory = executor.execute(exe, vec![input])?.final_memory.unwrap();
+        let hasher = vm_poseidon2_hasher();
+        let pv_proof = UserPublicValuesProof::compute(
+            config.system.memory_config.memory_dimensions(),
+            64,
+            &hasher,
+            &final_memory,
+        );
+        let public_values: Vec<u8> = pv_proof
+            .public_values
+            .iter()
+            .map(|x| x.as_canonical_u32() as u8)
+            .collect();
+        assert_eq!(
+            public_values[..JOURNAL_SIZE],
+            openvm::io::encode_journal(&values)
+        );
+        let journal = openvm::io::journal::<Vec<u32>>(&public_values)?;
+        assert!(journal.matches(&values));
+        if values.len() * 4 + 4 <= JOURNAL_INLINE_SIZE {
+            assert_eq!(journal, Journal::Value(values));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_challenge() -> Result<()> {
+        // Challenges are hashed with the sha256 extension.
+        let config = Sha256Rv32Config {
+            system: SystemConfig::default()
+                .with_continuations()
+                .with_public_values(64),
+            ..Default::default()
+        };
+        let elf = build_example_program_at_path(get_programs_dir!(), "challenge", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Sha256TranspilerExtension),
+        )?;
+        let message = b"message";
+        let input = message.iter().map(|&b| F::from_canonical_u8(b)).collect();
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let final_memory = executor.execute(exe, vec![input])?.final_memory.unwrap();
+        let public_values: Vec<u8> = UserPublicValuesProof::compute(
+            config.system.memory_config.memory_dimensions(),
+            64,
+            &vm_poseidon2_hasher(),
+            &final_memory,
+        )
+        .public_values
+        .iter()
+        .map(|x| x.as_canonical_u32() as u8)
+        .collect();
+        // The guest reveals the second challenge drawn after publishing the message.
+        let state = openvm::io::published_digest([message]);
+        assert_eq!(public_values[PUBLISHED_OFFSET..], state);
+        assert_eq!(
+            public_values[..32],
+            openvm::io::derive_challenge(&state, 1, b"alpha")
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_print() -> Result<()> {
         let config = Rv32IConfig::default();
@@ -199,6 +809,61 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_print_captured() -> Result<()> {
+        let config = Rv32IConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "print", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        let executor = VmExecutor::<F, _>::new(config);
+        let result = executor.execute(exe, Streams::default().with_output_capture())?;
+        let output = result.output.unwrap();
+        assert_eq!(output.text(), "Hello, world!");
+        assert_eq!(output.entries.len(), 1);
+        assert_eq!(output.entries[0].segment, 0);
+        assert!(output.entries[0].instret > 0);
+        assert!(output.entries[0].instret < result.total_cycles as u64);
+        Ok(())
+    }
+
+    #[test]
+    fn test_without_hints() -> Result<()> {
+        let mut config = Rv32IConfig::default();
+        config.system = config.system.without_hints();
+        let transpiler = || {
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+        };
+        let build = |example_name: &str| {
+            build_example_program_at_path(get_programs_dir!(), example_name, &config)
+        };
+        let executor = VmExecutor::<F, _>::new(config.clone());
+
+        // Printing does not provide hints.
+        let exe = VmExe::from_elf(build("print")?, transpiler().without_hints())?;
+        executor.execute(exe, vec![])?;
+
+        let result = VmExe::<F>::from_elf(build("hint")?, transpiler().without_hints());
+        assert!(matches!(result, Err(TranspilerError::HintsDisabled(_))));
+
+        // Programs which were transpiled with hints fail during execution.
+        let exe = VmExe::from_elf(build("hint")?, transpiler())?;
+        let input = vec![[0, 1, 2, 3].map(F::from_canonical_u8).to_vec()];
+        match executor.execute(exe, input) {
+            Err(ExecutionError::HintsDisabled { .. }) => Ok(()),
+            Err(err) => panic!("should fail with `HintsDisabled`, got {err}"),
+            Ok(_) => panic!("should fail"),
+        }
+    }
+
     #[test]
     fn test_heap_overflow() -> Result<()> {
         let config = Rv32ImConfig::default();
@@ -219,6 +884,182 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_stack_overflow() -> Result<()> {
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "stack_overflow", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        let executor = VmExecutor::<F, _>::new(config);
+        let input = |depth: u32| vec![depth.to_le_bytes().map(F::from_canonical_u8).to_vec()];
+        executor.execute(exe.clone(), input(1000))?;
+        match executor.execute(exe.clone(), input(100_000)) {
+            Err(ExecutionError::StackOverflow { .. }) => {}
+            Err(err) => panic!("should fail with `StackOverflow`, got {err}"),
+            Ok(_) => panic!("should fail"),
+        }
+
+        // Any access to the guard region fails, not only loads and stores.
+        let mut hint_store = input(u32::MAX);
+        hint_store.push(vec![F::ZERO; 4]);
+        match executor.execute(exe, hint_store) {
+            Err(ExecutionError::StackOverflow { address, .. }) => {
+                assert_eq!(address, openvm::platform::memory::STACK_BOTTOM - 4);
+                Ok(())
+            }
+            Err(err) => panic!("should fail with `StackOverflow`, got {err}"),
+            Ok(_) => panic!("should fail"),
+        }
+    }
+
+    #[test]
+    fn test_source_map() -> Result<()> {
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path_with_debug_info(
+            get_programs_dir!(),
+            "fibonacci",
+            &config,
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        // The `main` generated by `openvm::entry!` calls the `main` of the example, unless it is
+        // inlined.
+        let locations: Vec<_> = exe
+            .fn_bounds
+            .values()
+            .filter(|bound| bound.name == "main" || bound.name == "fibonacci::main")
+            .flat_map(|bound| (bound.start..=bound.end).step_by(4))
+            .filter_map(|pc| exe.source_map.location(pc))
+            .collect();
+        assert!(
+            locations
+                .iter()
+                .any(|(file, line)| file.ends_with("examples/fibonacci.rs")
+                    && (6..=17).contains(line)),
+            "the body of the example's `main` should be located, got {locations:?}"
+        );
+
+        // The line info ends after the last instruction.
+        let (&end, _) = exe.source_map.lines.last_key_value().unwrap();
+        assert_eq!(exe.source_map.location(end), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_unsupported_std_features() -> Result<()> {
+        // Without optimizations, the calls to the standard library are not inlined into `main`.
+        let elf = build_example_elf_at_path(
+            get_programs_dir!(),
+            "unsupported_std",
+            ["std"],
+            [
+                "--config",
+                "profile.release.opt-level=0",
+                "--config",
+                "profile.release.lto=false",
+            ],
+            &Rv32ImConfig::default(),
+        )?;
+        let features = find_unsupported_std_features(&elf)?;
+        let summary: Vec<_> = features
+            .iter()
+            .map(|feature| {
+                (
+                    feature.feature,
+                    feature.crates.iter().cloned().collect::<Vec<_>>(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("clocks", vec!["unsupported_std".to_string()]),
+                ("networking", vec!["unsupported_std".to_string()]),
+                ("the file system", vec!["unsupported_std".to_string()]),
+            ]
+        );
+        let functions = |name: &str| {
+            &features
+                .iter()
+                .find(|f| f.feature == name)
+                .unwrap()
+                .functions
+        };
+        assert!(functions("clocks").contains("std::time::Instant::now"));
+        assert!(functions("networking").contains("std::net::tcp::TcpStream::connect"));
+        assert!(functions("the file system").contains("std::fs::File::open"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_panic_message() -> Result<()> {
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path(get_programs_dir!(), "panic", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        let executor = VmExecutor::<F, _>::new(config);
+        match executor.execute(exe, vec![]) {
+            Err(ExecutionError::Panicked { exit_code, message }) => {
+                assert_eq!(exit_code, 1);
+                assert!(message.contains("x is 7"), "unexpected message: {message}");
+                Ok(())
+            }
+            Err(err) => panic!("should fail with `Panicked`, got {err}"),
+            Ok(_) => panic!("should fail"),
+        }
+    }
+
+    #[test]
+    fn test_ensure() -> Result<()> {
+        let num_public_values = openvm::io::FAILURE_OFFSET + openvm::io::FAILURE_SIZE;
+        let config = Rv32ImConfig::with_public_values(num_public_values);
+        let elf = build_example_program_at_path(get_programs_dir!(), "ensure", &config)?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+
+        // The failed check ends the program successfully, so that its execution can be proven.
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let final_memory = executor.execute(exe, vec![])?.final_memory.unwrap();
+        let public_values: Vec<u8> = UserPublicValuesProof::compute(
+            config.system.memory_config.memory_dimensions(),
+            num_public_values,
+            &vm_poseidon2_hasher(),
+            &final_memory,
+        )
+        .public_values
+        .iter()
+        .map(|x| x.as_canonical_u32() as u8)
+        .collect();
+        let failure = openvm::io::extract_failure(&public_values).expect("should fail");
+        assert_eq!(failure.code, 42);
+        assert!(failure.is_in_file("examples/ensure.rs"));
+        assert_eq!(failure.line, 11);
+        Ok(())
+    }
+
     #[test]
     fn test_hashmap() -> Result<()> {
         let config = Rv32ImConfig::default();
@@ -240,12 +1081,43 @@ mod tests {
     }
 
     #[test]
-    fn test_tiny_mem_test() -> Result<()> {
+    fn test_heap_profile() -> Result<()> {
+        let config = Rv32ImConfig::default();
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "heap_profile",
+            ["heap-profile", "std"],
+            &config,
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension),
+        )?;
+        let executor = VmExecutor::<F, _>::new(config.clone());
+        let profile = executor
+            .execute(exe.clone(), vec![])?
+            .heap_profile
+            .expect("heap profile should be recorded");
+        assert!(profile.allocations >= 1);
+        assert!(profile.peak_bytes >= 1024);
+        assert!(profile.allocated_bytes >= profile.peak_bytes);
+
+        air_test(config, exe);
+        Ok(())
+    }
+
+    #[test_case("tiny-mem-test", "heap-embedded-alloc")]
+    #[test_case("tiny-mem-test", "heap-tlsf")]
+    #[test_case("heap_churn", "heap-tlsf")]
+    fn test_heap_allocator(example_name: &str, allocator: &str) -> Result<()> {
         let config = Rv32ImConfig::default();
         let elf = build_example_program_at_path_with_features(
             get_programs_dir!(),
-            "tiny-mem-test",
-            ["heap-embedded-alloc"],
+            example_name,
+            [allocator],
             &config,
         )?;
         let exe = VmExe::from_elf(
@@ -276,6 +1148,62 @@ mod tests {
         executor.execute(exe, vec![]).unwrap();
     }
 
+    #[test]
+    fn test_getrandom_seeded() -> Result<()> {
+        // The seed is published, which hashes it with the sha256 extension.
+        let config = Sha256Rv32Config {
+            system: SystemConfig::default()
+                .with_continuations()
+                .with_public_values(64),
+            ..Default::default()
+        };
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!(),
+            "getrandom_seeded",
+            ["getrandom", "getrandom-publish-seed"],
+            &config,
+        )?;
+        let exe = VmExe::from_elf(
+            elf,
+            Transpiler::<F>::default()
+                .with_extension(Rv32ITranspilerExtension)
+                .with_extension(Rv32MTranspilerExtension)
+                .with_extension(Rv32IoTranspilerExtension)
+                .with_extension(Sha256TranspilerExtension),
+        )?;
+        let run = |seed: [u8; 32]| -> Result<Vec<u8>> {
+            let mut streams = Streams::<F>::default();
+            streams.kv_store = Arc::new(HashMap::from([(
+                openvm::getrandom::SEED_KEY.to_vec(),
+                seed.to_vec(),
+            )]));
+            let executor = VmExecutor::<F, _>::new(config.clone());
+            let final_memory = executor
+                .execute(exe.clone(), streams)?
+                .final_memory
+                .unwrap();
+            let public_values: Vec<u8> = UserPublicValuesProof::compute(
+                config.system.memory_config.memory_dimensions(),
+                64,
+                &vm_poseidon2_hasher(),
+                &final_memory,
+            )
+            .public_values
+            .iter()
+            .map(|x| x.as_canonical_u32() as u8)
+            .collect();
+            assert_eq!(
+                public_values[PUBLISHED_OFFSET..],
+                openvm::io::published_digest([seed.as_slice()])
+            );
+            Ok(public_values[..32].to_vec())
+        };
+        let random = run([1; 32])?;
+        assert_eq!(run([1; 32])?, random);
+        assert_ne!(run([2; 32])?, random);
+        Ok(())
+    }
+
     #[test_case("getrandom", vec!["getrandom", "getrandom-unsupported"])]
     #[test_case("getrandom", vec!["getrandom"])]
     #[test_case("getrandom_v02", vec!["getrandom-v02", "getrandom-unsupported"])]
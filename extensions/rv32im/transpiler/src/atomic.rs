@@ -0,0 +1,162 @@
+//! Lowering of the word-sized instructions of the RISC-V A extension.
+//!
+//! The VM is single-threaded, so an atomic memory operation is a load, an ALU operation and a
+//! store. A load-reserved reserves its address, and a store-conditional succeeds if and only if
+//! its address is reserved. Every store-conditional clears the reservation. The `aq` and `rl`
+//! ordering bits have no effect.
+//!
+//! An instruction with a misaligned address terminates the program with exit code 2, like
+//! [unimp].
+
+use openvm_instructions::{instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, LocalOpcode};
+use openvm_rv32im_guest::{AMO_OPCODE, AMO_W_FUNCT3};
+use openvm_stark_backend::p3_field::PrimeField32;
+use openvm_transpiler::{
+    util::unimp, InstructionDispatch, OutOfLine, TranspilerExtension, TranspilerOutput,
+};
+use rrs_lib::instruction_formats::RType;
+
+use crate::{
+    lower::{alu, alu_imm, jump_back, jump_forward, load, store, RESERVATION, TMP, TMP2},
+    BaseAluOpcode, BranchEqualOpcode, BranchLessThanOpcode,
+};
+
+/// Transpiles `lr.w`, `sc.w` and `amo*.w` into instructions of the RV32I extension, which are
+/// placed out of line, see [OutOfLine].
+///
+/// The intermediate values of the lowered instructions and the reservation are held in registers
+/// past the 32 RISC-V registers, which the guest program cannot observe. The reservation is the
+/// reserved address plus one, so that the initial zero register holds no reservation.
+#[derive(Default)]
+pub struct Rv32ATranspilerExtension;
+
+/// `funct5` of the A extension instructions.
+const LR: u32 = 0b00010;
+const SC: u32 = 0b00011;
+const AMOSWAP: u32 = 0b00001;
+const AMOADD: u32 = 0b00000;
+const AMOXOR: u32 = 0b00100;
+const AMOAND: u32 = 0b01100;
+const AMOOR: u32 = 0b01000;
+const AMOMIN: u32 = 0b10000;
+const AMOMAX: u32 = 0b10100;
+const AMOMINU: u32 = 0b11000;
+const AMOMAXU: u32 = 0b11100;
+
+impl<F: PrimeField32> TranspilerExtension<F> for Rv32ATranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        let instruction_u32 = *instruction_stream.first()?;
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+        if (opcode, funct3) != (AMO_OPCODE, AMO_W_FUNCT3) {
+            return None;
+        }
+
+        let dec_insn = RType::new(instruction_u32);
+        let rd = RV32_REGISTER_NUM_LIMBS * dec_insn.rd;
+        let rs1 = RV32_REGISTER_NUM_LIMBS * dec_insn.rs1;
+        let rs2 = RV32_REGISTER_NUM_LIMBS * dec_insn.rs2;
+        let mut body = check_aligned(rs1);
+        match dec_insn.funct7 >> 2 {
+            LR => {
+                if dec_insn.rs2 != 0 {
+                    return None;
+                }
+                body.push(alu_imm(BaseAluOpcode::ADD, RESERVATION, rs1, 1));
+                if dec_insn.rd != 0 {
+                    body.push(load(rd, rs1));
+                }
+            }
+            SC => {
+                // TMP2 = 1, replaced by 0 unless the branch skips over the store.
+                body.extend([
+                    alu_imm(BaseAluOpcode::ADD, TMP, rs1, 1),
+                    alu_imm(BaseAluOpcode::ADD, TMP2, 0, 1),
+                    branch(BranchEqualOpcode::BNE, TMP, RESERVATION, 12),
+                    store(rs2, rs1),
+                    alu(BaseAluOpcode::ADD, TMP2, 0, 0),
+                    alu(BaseAluOpcode::ADD, RESERVATION, 0, 0),
+                ]);
+                if dec_insn.rd != 0 {
+                    body.push(alu(BaseAluOpcode::ADD, rd, TMP2, 0));
+                }
+            }
+            AMOSWAP => {
+                if dec_insn.rd == 0 {
+                    body.push(store(rs2, rs1));
+                } else {
+                    body.extend([
+                        load(TMP, rs1),
+                        store(rs2, rs1),
+                        alu(BaseAluOpcode::ADD, rd, TMP, 0),
+                    ]);
+                }
+            }
+            funct5 => {
+                body.push(load(TMP, rs1));
+                match funct5 {
+                    AMOADD => body.push(alu(BaseAluOpcode::ADD, TMP2, TMP, rs2)),
+                    AMOXOR => body.push(alu(BaseAluOpcode::XOR, TMP2, TMP, rs2)),
+                    AMOAND => body.push(alu(BaseAluOpcode::AND, TMP2, TMP, rs2)),
+                    AMOOR => body.push(alu(BaseAluOpcode::OR, TMP2, TMP, rs2)),
+                    AMOMIN | AMOMAX | AMOMINU | AMOMAXU => {
+                        // TMP2 = rs2, replaced by TMP unless the branch skips over it.
+                        let skip_if = match funct5 {
+                            AMOMIN => BranchLessThanOpcode::BGE,
+                            AMOMAX => BranchLessThanOpcode::BLT,
+                            AMOMINU => BranchLessThanOpcode::BGEU,
+                            _ => BranchLessThanOpcode::BLTU,
+                        };
+                        body.extend([
+                            alu(BaseAluOpcode::ADD, TMP2, rs2, 0),
+                            branch(skip_if, TMP, TMP2, 8),
+                            alu(BaseAluOpcode::ADD, TMP2, TMP, 0),
+                        ]);
+                    }
+                    _ => return None,
+                }
+                body.push(store(TMP2, rs1));
+                if dec_insn.rd != 0 {
+                    body.push(alu(BaseAluOpcode::ADD, rd, TMP, 0));
+                }
+            }
+        }
+
+        Some(TranspilerOutput::out_of_line(OutOfLine {
+            body: Box::new(move |_| body),
+            jump_forward,
+            jump_back,
+        }))
+    }
+
+    fn dispatch(&self) -> Vec<InstructionDispatch> {
+        vec![InstructionDispatch::all_funct7(AMO_OPCODE, AMO_W_FUNCT3)]
+    }
+}
+
+/// `ANDI TMP, rs1, 3; BEQ TMP, x0, 8; UNIMP`, which terminates the program unless `rs1` is
+/// word-aligned.
+fn check_aligned<F: PrimeField32>(rs1: usize) -> Vec<Instruction<F>> {
+    vec![
+        alu_imm(BaseAluOpcode::AND, TMP, rs1, 3),
+        branch(BranchEqualOpcode::BEQ, TMP, 0, 8),
+        unimp(),
+    ]
+}
+
+/// The branch of `opcode` on `rs1` and `rs2` by `offset` bytes.
+fn branch<F: PrimeField32>(
+    opcode: impl LocalOpcode,
+    rs1: usize,
+    rs2: usize,
+    offset: isize,
+) -> Instruction<F> {
+    Instruction::from_isize(
+        opcode.global_opcode(),
+        rs1 as isize,
+        rs2 as isize,
+        offset,
+        1,
+        1,
+    )
+}
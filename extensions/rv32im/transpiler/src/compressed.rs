@@ -0,0 +1,132 @@
+//! Transpilation of the 16-bit instructions of the RISC-V compressed (C) extension.
+//!
+//! Every instruction of the VM advances the pc by 4, while the instruction after a 16-bit
+//! instruction is 2 bytes after it. Only the 16-bit instructions which do not continue to the
+//! next instruction, such as `c.j`, can take their slot. The others are placed out of line: the
+//! slot jumps to their body, which is followed by a jump back to the next instruction. This
+//! costs 2 extra cycles, or 3 if the jump back is out of range of `JAL` and takes `AUIPC` and
+//! `JALR`. `c.jal` and `c.jalr` also compute their link address with `AUIPC` and `ADD`.
+//!
+//! The out-of-line instructions also grow the program, and so its trace and commitment: each
+//! 16-bit instruction placed out of line takes 3 or more instructions instead of 1.
+
+use std::marker::PhantomData;
+
+use openvm_instructions::{instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, LocalOpcode};
+use openvm_stark_backend::p3_field::PrimeField32;
+use openvm_transpiler::{
+    compressed::{expand, instruction_len},
+    util::{nop, unimp},
+    OutOfLine, TranspilerExtension, TranspilerOutput,
+};
+use rrs_lib::{
+    instruction_formats::{IType, JType},
+    process_instruction,
+};
+
+use crate::{
+    lower::{add_pc, alu, jump, jump_back, jump_forward, TMP},
+    rrs::InstructionTranspiler,
+    BaseAluOpcode, Rv32JalLuiOpcode, Rv32JalrOpcode,
+};
+
+const JAL_OPCODE: u32 = 0b1101111;
+const JALR_OPCODE: u32 = 0b1100111;
+const BRANCH_OPCODE: u32 = 0b1100011;
+/// The expansion of `c.ebreak`.
+const EBREAK: u32 = 0x00100073;
+
+/// Transpiles the 16-bit instructions of programs built for a target with the compressed (C)
+/// extension, such as `riscv32imc`, into instructions of the RV32I extension, see
+/// [Transpiler::transpile_compressed](openvm_transpiler::transpiler::Transpiler::transpile_compressed).
+/// The 32-bit instructions of these programs are transpiled by the other extensions.
+#[derive(Default)]
+pub struct Rv32CTranspilerExtension;
+
+impl<F: PrimeField32> TranspilerExtension<F> for Rv32CTranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        let instruction_u32 = *instruction_stream.first()?;
+        if instruction_len(instruction_u32) != 2 {
+            return None;
+        }
+        // The all-zero instruction is defined to be illegal.
+        if instruction_u32 == 0 {
+            return Some(TranspilerOutput::one_to_one(unimp()));
+        }
+        let expanded = expand(instruction_u32 as u16)?;
+        if expanded == EBREAK {
+            return Some(TranspilerOutput::one_to_one(unimp()));
+        }
+        let transpile =
+            || process_instruction(&mut InstructionTranspiler::<F>(PhantomData), expanded);
+        // c.j and c.jr do not link, and jump relative to their slot or to a register.
+        let opcode = expanded & 0x7f;
+        if (opcode == JAL_OPCODE || opcode == JALR_OPCODE) && (expanded >> 7) & 0x1f == 0 {
+            return transpile().map(TranspilerOutput::one_to_one);
+        }
+        let ra = RV32_REGISTER_NUM_LIMBS;
+
+        let body: Box<dyn FnOnce(usize) -> Vec<Instruction<F>>> = match opcode {
+            JAL_OPCODE => {
+                let dec_insn = JType::new(expanded);
+                // c.jal, which links to the instruction 2 bytes after it. The body starts
+                // `distance` bytes after the slot of c.jal.
+                Box::new(move |distance| {
+                    let distance = distance as i32;
+                    let mut body = add_pc(ra, 2 - distance).to_vec();
+                    body.extend(jump(dec_insn.imm - distance - 8));
+                    body
+                })
+            }
+            JALR_OPCODE => {
+                let rs1 = RV32_REGISTER_NUM_LIMBS * IType::new(expanded).rs1;
+                // c.jalr, which reads rs1 before linking, since rs1 may be ra.
+                Box::new(move |distance| {
+                    let mut body = vec![alu(BaseAluOpcode::ADD, TMP, rs1, 0)];
+                    body.extend(add_pc(ra, -2 - distance as i32));
+                    body.push(Instruction::large_from_isize(
+                        Rv32JalrOpcode::JALR.global_opcode(),
+                        0,
+                        TMP as isize,
+                        0,
+                        1,
+                        0,
+                        0,
+                        0,
+                    ));
+                    body
+                })
+            }
+            BRANCH_OPCODE => {
+                // c.beqz and c.bnez, whose target is relative to their slot. The body falls
+                // through to the jump back.
+                let mut branch = transpile()?;
+                Box::new(move |distance| {
+                    branch.c -= F::from_canonical_usize(distance);
+                    vec![branch]
+                })
+            }
+            _ => {
+                let instruction = transpile()?;
+                if instruction == nop() {
+                    // The hints of the C extension, such as c.nop, only continue to the next
+                    // instruction.
+                    return Some(TranspilerOutput::one_to_one(Instruction::from_isize(
+                        Rv32JalLuiOpcode::JAL.global_opcode(),
+                        0,
+                        0,
+                        2,
+                        1,
+                        0,
+                    )));
+                }
+                Box::new(move |_| vec![instruction])
+            }
+        };
+        Some(TranspilerOutput::out_of_line(OutOfLine {
+            body,
+            jump_forward,
+            jump_back,
+        }))
+    }
+}
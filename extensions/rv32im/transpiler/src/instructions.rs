@@ -281,4 +281,24 @@ pub enum Rv32Phantom {
     HintRandom,
     /// Hint the VM to load values from the stream KV store into input streams.
     HintLoadByKey,
+    /// Reset the hint stream to a value from the stream KV store, prepended with its length.
+    HintByKey,
+    /// Select the hint queue which subsequent hints are written to and read from.
+    HintSelect,
+    /// Peek string from memory and record it as the panic message of the program.
+    RecordPanic,
+    /// Peek the heap profile of the program from memory and record it.
+    RecordHeapStats,
+    /// Reset the hint stream to the number of remaining input vectors, the length of the next one
+    /// and their total length, without consuming any of them.
+    HintInputLen,
+    /// Peek a message from memory and send it through the channel whose id prefixes it.
+    ChannelSend,
+    /// Reset the hint stream to the next message received from a channel, prepended with its
+    /// length.
+    ChannelRecv,
+    /// Peek the name of a profiling region from memory and start the region.
+    RegionStart,
+    /// End the innermost profiling region.
+    RegionEnd,
 }
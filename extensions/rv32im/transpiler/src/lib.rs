@@ -2,7 +2,7 @@ use std::marker::PhantomData;
 
 use openvm_instructions::{
     instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, LocalOpcode, PhantomDiscriminant,
-    SystemOpcode,
+    SysPhantom, SystemOpcode,
 };
 use openvm_rv32im_guest::{
     PhantomImm, CSRRW_FUNCT3, CSR_OPCODE, HINT_BUFFER_IMM, HINT_FUNCT3, HINT_STOREW_IMM,
@@ -11,7 +11,8 @@ use openvm_rv32im_guest::{
 };
 use openvm_stark_backend::p3_field::PrimeField32;
 use openvm_transpiler::{
-    util::{nop, unimp},
+    metadata::OpcodeMap,
+    util::{nop, phantom_discriminant, unimp},
     TranspilerExtension, TranspilerOutput,
 };
 use rrs::InstructionTranspiler;
@@ -19,11 +20,28 @@ use rrs_lib::{
     instruction_formats::{IType, RType},
     process_instruction,
 };
+use strum::IntoEnumIterator;
 
+mod atomic;
+mod compressed;
 mod instructions;
+mod lower;
 pub mod rrs;
+pub use atomic::Rv32ATranspilerExtension;
+pub use compressed::Rv32CTranspilerExtension;
 pub use instructions::*;
 
+/// The phantom sub-instructions of [Rv32ITranspilerExtension] which provide hints.
+const HINT_PHANTOMS: [Rv32Phantom; 7] = [
+    Rv32Phantom::HintInput,
+    Rv32Phantom::HintRandom,
+    Rv32Phantom::HintLoadByKey,
+    Rv32Phantom::HintByKey,
+    Rv32Phantom::HintSelect,
+    Rv32Phantom::HintInputLen,
+    Rv32Phantom::ChannelRecv,
+];
+
 #[derive(Default)]
 pub struct Rv32ITranspilerExtension;
 
@@ -98,6 +116,66 @@ impl<F: PrimeField32> TranspilerExtension<F> for Rv32ITranspilerExtension {
                         F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
                         0,
                     ),
+                    PhantomImm::HintByKey => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::HintByKey as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                        0,
+                    ),
+                    PhantomImm::HintSelect => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::HintSelect as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::ZERO,
+                        0,
+                    ),
+                    PhantomImm::RecordPanic => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::RecordPanic as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                        0,
+                    ),
+                    PhantomImm::Pause => Instruction::phantom(
+                        PhantomDiscriminant(SysPhantom::Pause as u16),
+                        F::ZERO,
+                        F::ZERO,
+                        0,
+                    ),
+                    PhantomImm::RecordHeapStats => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::RecordHeapStats as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                        0,
+                    ),
+                    PhantomImm::HintInputLen => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::HintInputLen as u16),
+                        F::ZERO,
+                        F::ZERO,
+                        0,
+                    ),
+                    PhantomImm::ChannelSend => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::ChannelSend as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                        0,
+                    ),
+                    PhantomImm::ChannelRecv => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::ChannelRecv as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::ZERO,
+                        0,
+                    ),
+                    PhantomImm::RegionStart => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::RegionStart as u16),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rd),
+                        F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS * dec_insn.rs1),
+                        0,
+                    ),
+                    PhantomImm::RegionEnd => Instruction::phantom(
+                        PhantomDiscriminant(Rv32Phantom::RegionEnd as u16),
+                        F::ZERO,
+                        F::ZERO,
+                        0,
+                    ),
                 })
             }
             (RV32_ALU_OPCODE, _) => {
@@ -114,6 +192,28 @@ impl<F: PrimeField32> TranspilerExtension<F> for Rv32ITranspilerExtension {
 
         instruction.map(TranspilerOutput::one_to_one)
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default()
+            .with_opcodes(SystemOpcode::iter())
+            .with_opcodes(BaseAluOpcode::iter())
+            .with_opcodes(ShiftOpcode::iter())
+            .with_opcodes(LessThanOpcode::iter())
+            .with_opcodes(Rv32LoadStoreOpcode::iter())
+            .with_opcodes(BranchEqualOpcode::iter())
+            .with_opcodes(BranchLessThanOpcode::iter())
+            .with_opcodes(Rv32JalLuiOpcode::iter())
+            .with_opcodes(Rv32JalrOpcode::iter())
+            .with_opcodes(Rv32AuipcOpcode::iter())
+    }
+
+    fn provides_hints(&self, instruction: &Instruction<F>) -> bool {
+        phantom_discriminant(instruction).is_some_and(|discriminant| {
+            HINT_PHANTOMS
+                .iter()
+                .any(|&phantom| discriminant.0 == phantom as u16)
+        })
+    }
 }
 
 impl<F: PrimeField32> TranspilerExtension<F> for Rv32MTranspilerExtension {
@@ -141,6 +241,13 @@ impl<F: PrimeField32> TranspilerExtension<F> for Rv32MTranspilerExtension {
 
         instruction.map(TranspilerOutput::one_to_one)
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default()
+            .with_opcodes(MulOpcode::iter())
+            .with_opcodes(MulHOpcode::iter())
+            .with_opcodes(DivRemOpcode::iter())
+    }
 }
 
 impl<F: PrimeField32> TranspilerExtension<F> for Rv32IoTranspilerExtension {
@@ -218,4 +325,15 @@ impl<F: PrimeField32> TranspilerExtension<F> for Rv32IoTranspilerExtension {
 
         instruction.map(TranspilerOutput::one_to_one)
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default()
+            .with_opcodes(SystemOpcode::iter())
+            .with_opcodes(Rv32HintStoreOpcode::iter())
+            .with_opcodes(Rv32LoadStoreOpcode::iter())
+    }
+
+    fn provides_hints(&self, instruction: &Instruction<F>) -> bool {
+        Rv32HintStoreOpcode::iter().any(|opcode| instruction.opcode == opcode.global_opcode())
+    }
 }
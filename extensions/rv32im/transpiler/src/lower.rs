@@ -0,0 +1,163 @@
+//! Instructions of the RV32I extension used by the extensions which lower a RISC-V instruction
+//! into several instructions, placed out of line with [OutOfLine](openvm_transpiler::OutOfLine).
+
+use openvm_instructions::{instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, LocalOpcode};
+use openvm_stark_backend::p3_field::PrimeField32;
+
+use crate::{
+    BaseAluOpcode, Rv32AuipcOpcode, Rv32JalLuiOpcode, Rv32JalrOpcode, Rv32LoadStoreOpcode,
+};
+
+/// Pointers of the registers holding intermediate values. They are past the 32 RISC-V
+/// registers, so the guest program cannot observe them.
+pub(crate) const TMP: usize = RV32_REGISTER_NUM_LIMBS * 32;
+pub(crate) const TMP2: usize = RV32_REGISTER_NUM_LIMBS * 33;
+/// Pointer of the register holding the reservation of the last `lr.w`, see
+/// [Rv32ATranspilerExtension](crate::Rv32ATranspilerExtension). Registers 34 and 35 are used by
+/// the softfloat extension.
+pub(crate) const RESERVATION: usize = RV32_REGISTER_NUM_LIMBS * 36;
+
+/// `LOADW rd, 0(rs1)`
+pub(crate) fn load<F: PrimeField32>(rd: usize, rs1: usize) -> Instruction<F> {
+    Instruction::large_from_isize(
+        Rv32LoadStoreOpcode::LOADW.global_opcode(),
+        rd as isize,
+        rs1 as isize,
+        0,
+        1,
+        2,
+        1,
+        0,
+    )
+}
+
+/// `STOREW rs2, 0(rs1)`
+pub(crate) fn store<F: PrimeField32>(rs2: usize, rs1: usize) -> Instruction<F> {
+    Instruction::large_from_isize(
+        Rv32LoadStoreOpcode::STOREW.global_opcode(),
+        rs2 as isize,
+        rs1 as isize,
+        0,
+        1,
+        2,
+        1,
+        0,
+    )
+}
+
+pub(crate) fn alu<F: PrimeField32>(
+    opcode: BaseAluOpcode,
+    rd: usize,
+    rs1: usize,
+    rs2: usize,
+) -> Instruction<F> {
+    Instruction::from_isize(
+        opcode.global_opcode(),
+        rd as isize,
+        rs1 as isize,
+        rs2 as isize,
+        1,
+        1,
+    )
+}
+
+/// The ALU operation of `opcode` with the immediate `imm < 2^11`, e.g. `ADDI rd, rs1, imm`.
+pub(crate) fn alu_imm<F: PrimeField32>(
+    opcode: BaseAluOpcode,
+    rd: usize,
+    rs1: usize,
+    imm: u16,
+) -> Instruction<F> {
+    Instruction::from_isize(
+        opcode.global_opcode(),
+        rd as isize,
+        rs1 as isize,
+        imm as isize,
+        1,
+        0,
+    )
+}
+
+/// `AUIPC rd, hi; ADDI rd, rd, lo` with `hi + lo = offset`, which sets `rd` to the pc of the
+/// first instruction plus `offset`.
+pub(crate) fn add_pc<F: PrimeField32>(rd: usize, offset: i32) -> [Instruction<F>; 2] {
+    let (hi, lo) = split_offset(offset);
+    [
+        auipc(rd, hi),
+        Instruction::from_isize(
+            BaseAluOpcode::ADD.global_opcode(),
+            rd as isize,
+            rd as isize,
+            (lo as u32 & 0xffffff) as isize,
+            1,
+            0,
+        ),
+    ]
+}
+
+/// `JAL x0, offset`, which supports forward jumps past the range of RISC-V immediates.
+pub(crate) fn jump_forward<F: PrimeField32>(offset: usize) -> Instruction<F> {
+    Instruction::from_isize(
+        Rv32JalLuiOpcode::JAL.global_opcode(),
+        0,
+        0,
+        offset as isize,
+        1,
+        0,
+    )
+}
+
+/// Jumps by `offset` bytes from the pc of the first returned instruction: `JAL x0, offset` if
+/// `JAL` supports the offset, else `AUIPC TMP, hi; JALR x0, lo(TMP)` with `hi + lo = offset`.
+pub(crate) fn jump<F: PrimeField32>(offset: i32) -> Vec<Instruction<F>> {
+    if offset >= -(1 << 20) {
+        return vec![Instruction::from_isize(
+            Rv32JalLuiOpcode::JAL.global_opcode(),
+            0,
+            0,
+            offset as isize,
+            1,
+            0,
+        )];
+    }
+    let (hi, lo) = split_offset(offset);
+    vec![
+        auipc(TMP, hi),
+        Instruction::large_from_isize(
+            Rv32JalrOpcode::JALR.global_opcode(),
+            0,
+            TMP as isize,
+            (lo as u32 & 0xffff) as isize,
+            1,
+            0,
+            0,
+            (lo < 0) as isize,
+        ),
+    ]
+}
+
+/// Jumps back by `offset` bytes from the pc of the first returned instruction.
+pub(crate) fn jump_back<F: PrimeField32>(offset: usize) -> Vec<Instruction<F>> {
+    jump(-(offset as i32))
+}
+
+/// `AUIPC rd, hi` for `hi` with zero low 12 bits.
+fn auipc<F: PrimeField32>(rd: usize, hi: u32) -> Instruction<F> {
+    Instruction::new(
+        Rv32AuipcOpcode::AUIPC.global_opcode(),
+        F::from_canonical_usize(rd),
+        F::ZERO,
+        F::from_canonical_u32(hi >> 8),
+        F::ONE,
+        F::ZERO,
+        F::ZERO,
+        F::ZERO,
+    )
+}
+
+/// Splits `offset` into `hi` with zero low 12 bits and the 12-bit signed `lo`, with
+/// `hi + lo = offset`. `lo` is sign-extended, so that `hi` absorbs the borrow.
+fn split_offset(offset: i32) -> (u32, i32) {
+    let lo = ((offset as u32) << 20) as i32 >> 20;
+    ((offset as u32).wrapping_sub(lo as u32), lo)
+}
@@ -16,7 +16,8 @@ use openvm_rv32im_circuit::{
     Rv32I, Rv32IExecutor, Rv32IPeriphery, Rv32Io, Rv32IoExecutor, Rv32IoPeriphery, Rv32M,
     Rv32MExecutor, Rv32MPeriphery,
 };
-use openvm_sha256_transpiler::Rv32Sha256Opcode;
+use openvm_sha256_transpiler::{Rv32Sha256Opcode, Rv32Sha512Opcode};
+use openvm_sha512_air::Sha512Variant;
 use openvm_stark_backend::p3_field::PrimeField32;
 use serde::{Deserialize, Serialize};
 use strum::IntoEnumIterator;
@@ -58,6 +59,8 @@ pub struct Sha256;
 #[derive(ChipUsageGetter, Chip, InstructionExecutor, From, AnyEnum)]
 pub enum Sha256Executor<F: PrimeField32> {
     Sha256(Sha256VmChip<F>),
+    /// One chip for each of SHA-512 and SHA-384
+    Sha512(Sha512VmChip<F>),
 }
 
 #[derive(From, ChipUsageGetter, Chip, AnyEnum)]
@@ -70,6 +73,10 @@ impl<F: PrimeField32> VmExtension<F> for Sha256 {
     type Executor = Sha256Executor<F>;
     type Periphery = Sha256Periphery<F>;
 
+    fn version(&self) -> Option<(&'static str, u32)> {
+        Some(("sha256", 1))
+    }
+
     fn build(
         &self,
         builder: &mut VmInventoryBuilder<F>,
@@ -90,7 +97,7 @@ impl<F: PrimeField32> VmExtension<F> for Sha256 {
         let sha256_chip = Sha256VmChip::new(
             builder.system_port(),
             builder.system_config().memory_config.pointer_max_bits,
-            bitwise_lu_chip,
+            bitwise_lu_chip.clone(),
             builder.new_bus_idx(),
             Rv32Sha256Opcode::CLASS_OFFSET,
             builder.system_base().offline_memory(),
@@ -100,6 +107,22 @@ impl<F: PrimeField32> VmExtension<F> for Sha256 {
             Rv32Sha256Opcode::iter().map(|x| x.global_opcode()),
         )?;
 
+        for variant in [Sha512Variant::Sha512, Sha512Variant::Sha384] {
+            let sha512_chip = Sha512VmChip::new(
+                builder.system_port(),
+                builder.system_config().memory_config.pointer_max_bits,
+                bitwise_lu_chip.clone(),
+                builder.new_bus_idx(),
+                variant,
+                Rv32Sha512Opcode::CLASS_OFFSET,
+                builder.system_base().offline_memory(),
+            );
+            inventory.add_executor(
+                sha512_chip,
+                [sha512_variant_opcode(variant).global_opcode()],
+            )?;
+        }
+
         Ok(inventory)
     }
 }
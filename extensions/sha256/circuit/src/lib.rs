@@ -1,5 +1,8 @@
 mod sha256_chip;
 pub use sha256_chip::*;
 
+mod sha512_chip;
+pub use sha512_chip::*;
+
 mod extension;
 pub use extension::*;
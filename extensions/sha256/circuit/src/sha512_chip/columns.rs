@@ -0,0 +1,70 @@
+//! WARNING: the order of fields in the structs is important, do not change it
+
+use openvm_circuit::{
+    arch::ExecutionState,
+    system::memory::offline_checker::{MemoryReadAuxCols, MemoryWriteAuxCols},
+};
+use openvm_circuit_primitives::AlignedBorrow;
+use openvm_instructions::riscv::RV32_REGISTER_NUM_LIMBS;
+use openvm_sha512_air::{Sha512DigestCols, Sha512RoundCols};
+
+use super::{SHA512_NUM_WRITES, SHA512_REGISTER_READS, SHA512_WRITE_SIZE};
+
+/// the first 20 rows of every SHA512 block will be of type Sha512VmRoundCols and the last row will
+/// be of type Sha512VmDigestCols
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512VmRoundCols<T> {
+    pub control: Sha512VmControlCols<T>,
+    pub inner: Sha512RoundCols<T>,
+    pub read_aux: MemoryReadAuxCols<T>,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512VmDigestCols<T> {
+    pub control: Sha512VmControlCols<T>,
+    pub inner: Sha512DigestCols<T>,
+
+    pub from_state: ExecutionState<T>,
+    /// It is counter intuitive, but we will constrain the register reads on the very last row of
+    /// every message
+    pub rd_ptr: T,
+    pub rs1_ptr: T,
+    pub rs2_ptr: T,
+    pub dst_ptr: [T; RV32_REGISTER_NUM_LIMBS],
+    pub src_ptr: [T; RV32_REGISTER_NUM_LIMBS],
+    pub len_data: [T; RV32_REGISTER_NUM_LIMBS],
+    pub register_reads_aux: [MemoryReadAuxCols<T>; SHA512_REGISTER_READS],
+    pub writes_aux: [MemoryWriteAuxCols<T, SHA512_WRITE_SIZE>; SHA512_NUM_WRITES],
+}
+
+/// These are the columns that are used on both round and digest rows
+#[repr(C)]
+#[derive(Clone, Copy, Debug, AlignedBorrow)]
+pub struct Sha512VmControlCols<T> {
+    /// Note: We will use the buffer in `inner.message_schedule` as the message data
+    /// This is the length of the entire message in bytes
+    pub len: T,
+    /// Need to keep timestamp and read_ptr since block reads don't have the necessary information
+    pub cur_timestamp: T,
+    pub read_ptr: T,
+    /// Padding flags which will be used to encode the the number of non-padding cells in the
+    /// current row
+    pub pad_flags: [T; 9],
+    /// A boolean flag that indicates whether a padding already occurred
+    pub padding_occurred: T,
+}
+
+/// Width of the Sha512VmControlCols
+pub const SHA512VM_CONTROL_WIDTH: usize = Sha512VmControlCols::<u8>::width();
+/// Width of the Sha512VmRoundCols
+pub const SHA512VM_ROUND_WIDTH: usize = Sha512VmRoundCols::<u8>::width();
+/// Width of the Sha512VmDigestCols
+pub const SHA512VM_DIGEST_WIDTH: usize = Sha512VmDigestCols::<u8>::width();
+/// Width of the Sha512Cols
+pub const SHA512VM_WIDTH: usize = if SHA512VM_ROUND_WIDTH > SHA512VM_DIGEST_WIDTH {
+    SHA512VM_ROUND_WIDTH
+} else {
+    SHA512VM_DIGEST_WIDTH
+};
@@ -0,0 +1,248 @@
+//! Sha512 hasher. Handles full sha512 and sha384 hashing with padding.
+//! variable length inputs read from VM memory.
+use std::{
+    array,
+    cmp::{max, min},
+    sync::{Arc, Mutex},
+};
+
+use openvm_circuit::arch::{
+    ExecutionBridge, ExecutionError, ExecutionState, InstructionExecutor, SystemPort,
+};
+use openvm_circuit_primitives::{
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip, encoder::Encoder,
+};
+use openvm_instructions::{
+    instruction::Instruction,
+    program::DEFAULT_PC_STEP,
+    riscv::{RV32_CELL_BITS, RV32_MEMORY_AS, RV32_REGISTER_AS},
+    LocalOpcode,
+};
+use openvm_rv32im_circuit::adapters::read_rv32_register;
+use openvm_sha256_transpiler::Rv32Sha512Opcode;
+use openvm_sha512_air::{Sha512Air, Sha512Variant, SHA512_BLOCK_BITS};
+use openvm_stark_backend::{interaction::BusIndex, p3_field::PrimeField32};
+use serde::{Deserialize, Serialize};
+
+mod air;
+mod columns;
+mod trace;
+
+pub use air::*;
+pub use columns::*;
+use openvm_circuit::system::memory::{MemoryController, OfflineMemory, RecordId};
+
+#[cfg(test)]
+mod tests;
+
+// ==== Constants for register/memory adapter ====
+/// Register reads to get dst, src, len
+const SHA512_REGISTER_READS: usize = 3;
+/// Number of cells to read in a single memory access
+const SHA512_READ_SIZE: usize = 32;
+/// Number of cells to write in a single memory access
+const SHA512_WRITE_SIZE: usize = 32;
+/// Number of memory accesses to write the final hash
+const SHA512_NUM_WRITES: usize = 2;
+/// Number of cells of the final hash. For sha384, the hash is the first 48 cells.
+pub const SHA512_DIGEST_CELLS: usize = SHA512_WRITE_SIZE * SHA512_NUM_WRITES;
+/// Number of rv32 cells read in a SHA512 block
+pub const SHA512_BLOCK_CELLS: usize = SHA512_BLOCK_BITS / RV32_CELL_BITS;
+/// Number of rows we will do a read on for each SHA512 block
+pub const SHA512_NUM_READ_ROWS: usize = SHA512_BLOCK_CELLS / SHA512_READ_SIZE;
+
+/// Each chip handles a single variant, so that the initial hash value is the same for all messages
+/// in its trace. The two variants are separate chips with separate AIRs.
+pub struct Sha512VmChip<F: PrimeField32> {
+    pub air: Sha512VmAir,
+    /// IO and memory data necessary for each opcode call
+    pub records: Vec<Sha512Record<F>>,
+    pub offline_memory: Arc<Mutex<OfflineMemory<F>>>,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<8>,
+
+    offset: usize,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Sha512Record<F> {
+    pub from_state: ExecutionState<F>,
+    pub dst_read: RecordId,
+    pub src_read: RecordId,
+    pub len_read: RecordId,
+    pub input_records: Vec<[RecordId; SHA512_NUM_READ_ROWS]>,
+    pub input_message: Vec<[[u8; SHA512_READ_SIZE]; SHA512_NUM_READ_ROWS]>,
+    pub digest_writes: [RecordId; SHA512_NUM_WRITES],
+}
+
+impl<F: PrimeField32> Sha512VmChip<F> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        SystemPort {
+            execution_bus,
+            program_bus,
+            memory_bridge,
+        }: SystemPort,
+        address_bits: usize,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<8>,
+        self_bus_idx: BusIndex,
+        variant: Sha512Variant,
+        offset: usize,
+        offline_memory: Arc<Mutex<OfflineMemory<F>>>,
+    ) -> Self {
+        Self {
+            air: Sha512VmAir::new(
+                ExecutionBridge::new(execution_bus, program_bus),
+                memory_bridge,
+                bitwise_lookup_chip.bus(),
+                address_bits,
+                Sha512Air::new(bitwise_lookup_chip.bus(), self_bus_idx, variant),
+                Encoder::new(PaddingFlags::COUNT, 2, false),
+            ),
+            bitwise_lookup_chip,
+            records: Vec::new(),
+            offset,
+            offline_memory,
+        }
+    }
+
+    pub fn variant(&self) -> Sha512Variant {
+        self.air.sha512_subair.variant
+    }
+}
+
+/// The opcode of the instruction that computes the hash function `variant`
+pub fn sha512_variant_opcode(variant: Sha512Variant) -> Rv32Sha512Opcode {
+    match variant {
+        Sha512Variant::Sha512 => Rv32Sha512Opcode::SHA512,
+        Sha512Variant::Sha384 => Rv32Sha512Opcode::SHA384,
+    }
+}
+
+impl<F: PrimeField32> InstructionExecutor<F> for Sha512VmChip<F> {
+    fn execute(
+        &mut self,
+        memory: &mut MemoryController<F>,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<ExecutionState<u32>, ExecutionError> {
+        let &Instruction {
+            opcode,
+            a,
+            b,
+            c,
+            d,
+            e,
+            ..
+        } = instruction;
+        let local_opcode = opcode.local_opcode_idx(self.offset);
+        debug_assert_eq!(
+            local_opcode,
+            sha512_variant_opcode(self.variant()).local_usize()
+        );
+        debug_assert_eq!(d, F::from_canonical_u32(RV32_REGISTER_AS));
+        debug_assert_eq!(e, F::from_canonical_u32(RV32_MEMORY_AS));
+
+        debug_assert_eq!(from_state.timestamp, memory.timestamp());
+
+        let (dst_read, dst) = read_rv32_register(memory, d, a);
+        let (src_read, src) = read_rv32_register(memory, d, b);
+        let (len_read, len) = read_rv32_register(memory, d, c);
+
+        #[cfg(debug_assertions)]
+        {
+            assert!(dst < (1 << self.air.ptr_max_bits));
+            assert!(src < (1 << self.air.ptr_max_bits));
+            assert!(len < (1 << self.air.ptr_max_bits));
+        }
+
+        // need to pad with one 1 bit, 128 bits for the message length and then pad until the
+        // length is divisible by [SHA512_BLOCK_BITS]
+        let num_blocks = ((len << 3) as usize + 1 + 128).div_ceil(SHA512_BLOCK_BITS);
+
+        // we will read [num_blocks] * [SHA512_BLOCK_CELLS] cells but only [len] cells will be used
+        debug_assert!(
+            src as usize + num_blocks * SHA512_BLOCK_CELLS <= (1 << self.air.ptr_max_bits)
+        );
+        let mut message = Vec::with_capacity(len as usize);
+        let mut input_records = Vec::with_capacity(num_blocks * SHA512_NUM_READ_ROWS);
+        let mut input_message = Vec::with_capacity(num_blocks * SHA512_NUM_READ_ROWS);
+        let mut read_ptr = src;
+        for _ in 0..num_blocks {
+            let block_reads_records = array::from_fn(|i| {
+                memory.read(
+                    e,
+                    F::from_canonical_u32(read_ptr + (i * SHA512_READ_SIZE) as u32),
+                )
+            });
+            let block_reads_bytes = array::from_fn(|i| {
+                // we add to the message only the bytes that are part of the message
+                let num_reads = min(
+                    SHA512_READ_SIZE,
+                    (max(read_ptr, src + len) - read_ptr) as usize,
+                );
+                let row_input = block_reads_records[i]
+                    .1
+                    .map(|x| x.as_canonical_u32().try_into().unwrap());
+                message.extend_from_slice(&row_input[..num_reads]);
+                read_ptr += SHA512_READ_SIZE as u32;
+                row_input
+            });
+            input_records.push(block_reads_records.map(|x| x.0));
+            input_message.push(block_reads_bytes);
+        }
+
+        let digest = sha512_solve(&message, self.variant());
+        let digest_writes = array::from_fn(|i| {
+            let (digest_write, _) = memory.write(
+                e,
+                F::from_canonical_u32(dst + (i * SHA512_WRITE_SIZE) as u32),
+                array::from_fn(|j| F::from_canonical_u8(digest[i * SHA512_WRITE_SIZE + j])),
+            );
+            digest_write
+        });
+
+        self.records.push(Sha512Record {
+            from_state: from_state.map(F::from_canonical_u32),
+            dst_read,
+            src_read,
+            len_read,
+            input_records,
+            input_message,
+            digest_writes,
+        });
+
+        Ok(ExecutionState {
+            pc: from_state.pc + DEFAULT_PC_STEP,
+            timestamp: memory.timestamp(),
+        })
+    }
+
+    fn get_opcode_name(&self, _: usize) -> String {
+        format!("{:?}", sha512_variant_opcode(self.variant()))
+    }
+}
+
+/// Returns the final hash of `input_message` for the given variant. For sha384, the hash is the
+/// first 48 bytes.
+pub fn sha512_solve(input_message: &[u8], variant: Sha512Variant) -> [u8; SHA512_DIGEST_CELLS] {
+    let mut padded_message = input_message.to_vec();
+    padded_message.push(1 << (RV32_CELL_BITS - 1));
+    let len_offset = SHA512_BLOCK_CELLS - 16;
+    padded_message.resize(
+        (padded_message.len() + 16).next_multiple_of(SHA512_BLOCK_CELLS) - 16,
+        0,
+    );
+    debug_assert_eq!(padded_message.len() % SHA512_BLOCK_CELLS, len_offset);
+    padded_message.extend_from_slice(&((input_message.len() as u128) << 3).to_be_bytes());
+
+    let hash = padded_message
+        .chunks_exact(SHA512_BLOCK_CELLS)
+        .fold(variant.initial_hash(), |hash, block| {
+            Sha512Air::get_block_hash(&hash, block.try_into().unwrap())
+        });
+    let mut output = [0u8; SHA512_DIGEST_CELLS];
+    for (chunk, word) in output.chunks_exact_mut(8).zip(hash) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    output
+}
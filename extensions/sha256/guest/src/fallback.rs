@@ -0,0 +1,57 @@
+//! The `sha2` implementations of the SHA-2 instructions, for VMs without the sha256 extension.
+
+use core::slice;
+
+use sha2::{compress512, digest::generic_array::GenericArray, Digest, Sha256, Sha512};
+
+/// The initial state of sha384.
+const SHA384_IV: [u64; 8] = [
+    0xcbbb9d5dc1059ed8,
+    0x629a292a367cd507,
+    0x9159015a3070dd17,
+    0x152fecd8f70e5939,
+    0x67332667ffc00b31,
+    0x8eb44a8768581511,
+    0xdb0c2e0d64f98fa7,
+    0x47b5481dbefa4fa4,
+];
+
+pub(crate) fn sha256(bytes: *const u8, len: usize, output: *mut u8) {
+    let input = unsafe { slice::from_raw_parts(bytes, len) };
+    let output = unsafe { slice::from_raw_parts_mut(output, 32) };
+    output.copy_from_slice(&Sha256::digest(input));
+}
+
+pub(crate) fn sha512(bytes: *const u8, len: usize, output: *mut u8) {
+    let input = unsafe { slice::from_raw_parts(bytes, len) };
+    let output = unsafe { slice::from_raw_parts_mut(output, 64) };
+    output.copy_from_slice(&Sha512::digest(input));
+}
+
+/// Writes the entire final state like the VM, since the sha384 hash only holds 6 of its 8 words.
+pub(crate) fn sha384(bytes: *const u8, len: usize, output: *mut u8) {
+    let input = unsafe { slice::from_raw_parts(bytes, len) };
+    let output = unsafe { slice::from_raw_parts_mut(output, 64) };
+    let mut state = SHA384_IV;
+    let mut blocks = input.chunks_exact(128);
+    for block in &mut blocks {
+        compress512(&mut state, slice::from_ref(GenericArray::from_slice(block)));
+    }
+    // The last block is followed by a 0x80 byte and the length of the input in bits.
+    let remainder = blocks.remainder();
+    let mut padding = [0u8; 256];
+    padding[..remainder.len()].copy_from_slice(remainder);
+    padding[remainder.len()] = 0x80;
+    let padded_len = if remainder.len() + 17 <= 128 {
+        128
+    } else {
+        256
+    };
+    padding[padded_len - 16..padded_len].copy_from_slice(&(len as u128 * 8).to_be_bytes());
+    for block in padding[..padded_len].chunks_exact(128) {
+        compress512(&mut state, slice::from_ref(GenericArray::from_slice(block)));
+    }
+    for (word, bytes) in state.iter().zip(output.chunks_exact_mut(8)) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+}
@@ -1,10 +1,37 @@
 #![no_std]
 
+use openvm_platform::capabilities::insn_r;
+
 /// This is custom-0 defined in RISC-V spec document
 pub const OPCODE: u8 = 0x0b;
 pub const SHA256_FUNCT3: u8 = 0b100;
 pub const SHA256_FUNCT7: u8 = 0x1;
 
+#[cfg(not(feature = "fallback"))]
+openvm_platform::require_extension_version!("sha256", 1);
+#[cfg(feature = "fallback")]
+openvm_platform::optional_extension_version!("sha256", 1);
+
+#[cfg(all(target_os = "zkvm", feature = "fallback"))]
+mod fallback;
+
+/// Returns whether the VM supports the SHA-2 instruction with the given `funct7`. Without it, the
+/// functions of this crate fall back to the `sha2` crate. Without the `fallback` feature, the
+/// guest program requires the sha256 extension, so this is true for all SHA-2 instructions in the
+/// zkVM.
+#[inline(always)]
+pub fn has_sha2(funct7: u8) -> bool {
+    if cfg!(all(target_os = "zkvm", not(feature = "fallback"))) {
+        return matches!(funct7, SHA256_FUNCT7 | SHA512_FUNCT7 | SHA384_FUNCT7);
+    }
+    match funct7 {
+        SHA256_FUNCT7 => openvm_platform::has_insn!(insn_r(OPCODE, SHA256_FUNCT3, SHA256_FUNCT7)),
+        SHA512_FUNCT7 => openvm_platform::has_insn!(insn_r(OPCODE, SHA256_FUNCT3, SHA512_FUNCT7)),
+        SHA384_FUNCT7 => openvm_platform::has_insn!(insn_r(OPCODE, SHA256_FUNCT3, SHA384_FUNCT7)),
+        _ => false,
+    }
+}
+
 /// zkvm native implementation of sha256
 /// # Safety
 ///
@@ -18,5 +45,52 @@ pub const SHA256_FUNCT7: u8 = 0x1;
 #[inline(always)]
 #[no_mangle]
 pub extern "C" fn zkvm_sha256_impl(bytes: *const u8, len: usize, output: *mut u8) {
+    #[cfg(feature = "fallback")]
+    if !has_sha2(SHA256_FUNCT7) {
+        return fallback::sha256(bytes, len, output);
+    }
     openvm_platform::custom_insn_r!(opcode = OPCODE, funct3 = SHA256_FUNCT3, funct7 = SHA256_FUNCT7, rd = In output, rs1 = In bytes, rs2 = In len);
 }
+
+pub const SHA512_FUNCT7: u8 = 0x6;
+pub const SHA384_FUNCT7: u8 = 0x7;
+
+/// zkvm native implementation of sha512
+/// # Safety
+///
+/// The VM accepts the preimage by pointer and length, and writes the
+/// 64-byte hash.
+/// - `bytes` must point to an input buffer at least `len` long.
+/// - `output` must point to a buffer that is at least 64-bytes long.
+///
+/// [`sha2-512`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+#[no_mangle]
+pub extern "C" fn zkvm_sha512_impl(bytes: *const u8, len: usize, output: *mut u8) {
+    #[cfg(feature = "fallback")]
+    if !has_sha2(SHA512_FUNCT7) {
+        return fallback::sha512(bytes, len, output);
+    }
+    openvm_platform::custom_insn_r!(opcode = OPCODE, funct3 = SHA256_FUNCT3, funct7 = SHA512_FUNCT7, rd = In output, rs1 = In bytes, rs2 = In len);
+}
+
+/// zkvm native implementation of sha384
+/// # Safety
+///
+/// The VM accepts the preimage by pointer and length, and writes the
+/// 64-byte final state of sha384, whose first 48 bytes are the hash.
+/// - `bytes` must point to an input buffer at least `len` long.
+/// - `output` must point to a buffer that is at least 64-bytes long.
+///
+/// [`sha2-384`]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+#[no_mangle]
+pub extern "C" fn zkvm_sha384_impl(bytes: *const u8, len: usize, output: *mut u8) {
+    #[cfg(feature = "fallback")]
+    if !has_sha2(SHA384_FUNCT7) {
+        return fallback::sha384(bytes, len, output);
+    }
+    openvm_platform::custom_insn_r!(opcode = OPCODE, funct3 = SHA256_FUNCT3, funct7 = SHA384_FUNCT7, rd = In output, rs1 = In bytes, rs2 = In len);
+}
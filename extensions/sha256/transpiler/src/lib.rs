@@ -1,10 +1,12 @@
 use openvm_instructions::{riscv::RV32_MEMORY_AS, LocalOpcode};
 use openvm_instructions_derive::LocalOpcode;
-use openvm_sha256_guest::{OPCODE, SHA256_FUNCT3, SHA256_FUNCT7};
+use openvm_sha256_guest::{OPCODE, SHA256_FUNCT3, SHA256_FUNCT7, SHA384_FUNCT7, SHA512_FUNCT7};
 use openvm_stark_backend::p3_field::PrimeField32;
-use openvm_transpiler::{util::from_r_type, TranspilerExtension, TranspilerOutput};
+use openvm_transpiler::{
+    metadata::OpcodeMap, util::from_r_type, TranspilerExtension, TranspilerOutput,
+};
 use rrs_lib::instruction_formats::RType;
-use strum::{EnumCount, EnumIter, FromRepr};
+use strum::{EnumCount, EnumIter, FromRepr, IntoEnumIterator};
 
 #[derive(
     Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
@@ -15,6 +17,16 @@ pub enum Rv32Sha256Opcode {
     SHA256,
 }
 
+#[derive(
+    Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, EnumCount, EnumIter, FromRepr, LocalOpcode,
+)]
+#[opcode_offset = 0x330]
+#[repr(usize)]
+pub enum Rv32Sha512Opcode {
+    SHA512,
+    SHA384,
+}
+
 #[derive(Default)]
 pub struct Sha256TranspilerExtension;
 
@@ -32,15 +44,24 @@ impl<F: PrimeField32> TranspilerExtension<F> for Sha256TranspilerExtension {
         }
         let dec_insn = RType::new(instruction_u32);
 
-        if dec_insn.funct7 != SHA256_FUNCT7 as u32 {
-            return None;
-        }
+        let global_opcode = match dec_insn.funct7 as u8 {
+            SHA256_FUNCT7 => Rv32Sha256Opcode::SHA256.global_opcode(),
+            SHA512_FUNCT7 => Rv32Sha512Opcode::SHA512.global_opcode(),
+            SHA384_FUNCT7 => Rv32Sha512Opcode::SHA384.global_opcode(),
+            _ => return None,
+        };
         let instruction = from_r_type(
-            Rv32Sha256Opcode::SHA256.global_opcode().as_usize(),
+            global_opcode.as_usize(),
             RV32_MEMORY_AS as usize,
             &dec_insn,
             true,
         );
         Some(TranspilerOutput::one_to_one(instruction))
     }
+
+    fn opcode_map(&self) -> OpcodeMap {
+        OpcodeMap::default()
+            .with_opcodes(Rv32Sha256Opcode::iter())
+            .with_opcodes(Rv32Sha512Opcode::iter())
+    }
 }
@@ -0,0 +1,284 @@
+//! Decoding of the instructions of the RISC-V F and D extensions.
+
+use crate::softfloat::{Format, RoundingMode, BINARY32, BINARY64};
+
+const OPCODE_LOAD_FP: u32 = 0b0000111;
+const OPCODE_STORE_FP: u32 = 0b0100111;
+const OPCODE_MADD: u32 = 0b1000011;
+const OPCODE_MSUB: u32 = 0b1000111;
+const OPCODE_NMSUB: u32 = 0b1001011;
+const OPCODE_NMADD: u32 = 0b1001111;
+const OPCODE_OP_FP: u32 = 0b1010011;
+
+/// The major opcodes of the F and D extensions.
+pub const FP_OPCODES: [u8; 7] = [
+    OPCODE_LOAD_FP as u8,
+    OPCODE_STORE_FP as u8,
+    OPCODE_MADD as u8,
+    OPCODE_MSUB as u8,
+    OPCODE_NMSUB as u8,
+    OPCODE_NMADD as u8,
+    OPCODE_OP_FP as u8,
+];
+
+/// The operations of `fmadd`, `fmsub`, `fnmsub` and `fnmadd`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FusedOp {
+    /// `rs1 * rs2 + rs3`
+    Madd,
+    /// `rs1 * rs2 - rs3`
+    Msub,
+    /// `-(rs1 * rs2) + rs3`
+    Nmsub,
+    /// `-(rs1 * rs2) - rs3`
+    Nmadd,
+}
+
+/// The operations on two floating-point registers which write a floating-point register.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Min,
+    Max,
+    /// Sign injection: the magnitude of `rs1` with the sign of `rs2`.
+    SignInject,
+    /// The magnitude of `rs1` with the opposite of the sign of `rs2`.
+    SignInjectNeg,
+    /// The magnitude of `rs1` with the xor of the signs of `rs1` and `rs2`.
+    SignInjectXor,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CompareOp {
+    Eq,
+    Lt,
+    Le,
+}
+
+/// An instruction of the F and D extensions of RV32. The registers are register indices, and
+/// `format` is the format of the floating-point operands, or of the result of a conversion to
+/// floating point.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FpInstruction {
+    /// `flw` and `fld`
+    Load {
+        format: Format,
+        rd: usize,
+        rs1: usize,
+        imm: i32,
+    },
+    /// `fsw` and `fsd`
+    Store {
+        format: Format,
+        rs1: usize,
+        rs2: usize,
+        imm: i32,
+    },
+    Fused {
+        op: FusedOp,
+        format: Format,
+        rd: usize,
+        rs1: usize,
+        rs2: usize,
+        rs3: usize,
+        rm: RoundingMode,
+    },
+    Binary {
+        op: BinaryOp,
+        format: Format,
+        rd: usize,
+        rs1: usize,
+        rs2: usize,
+        rm: RoundingMode,
+    },
+    Sqrt {
+        format: Format,
+        rd: usize,
+        rs1: usize,
+        rm: RoundingMode,
+    },
+    /// `feq`, `flt` and `fle`, which write an integer register.
+    Compare {
+        op: CompareOp,
+        format: Format,
+        rd: usize,
+        rs1: usize,
+        rs2: usize,
+    },
+    /// `fclass`, which writes an integer register.
+    Classify {
+        format: Format,
+        rd: usize,
+        rs1: usize,
+    },
+    /// `fcvt.w` and `fcvt.wu`, from a floating-point register to an integer register.
+    ToInt {
+        format: Format,
+        signed: bool,
+        rd: usize,
+        rs1: usize,
+        rm: RoundingMode,
+    },
+    /// `fcvt.s.w[u]` and `fcvt.d.w[u]`, from an integer register to a floating-point register.
+    FromInt {
+        format: Format,
+        signed: bool,
+        rd: usize,
+        rs1: usize,
+        rm: RoundingMode,
+    },
+    /// `fcvt.s.d` and `fcvt.d.s`, to `format`.
+    Convert {
+        format: Format,
+        rd: usize,
+        rs1: usize,
+        rm: RoundingMode,
+    },
+    /// `fmv.x.w`, from the low bits of a floating-point register to an integer register.
+    MoveToInt { rd: usize, rs1: usize },
+    /// `fmv.w.x`, from an integer register to a floating-point register.
+    MoveFromInt { rd: usize, rs1: usize },
+}
+
+/// Decodes `instruction`, or returns `None` if it is not an instruction of the F and D extensions
+/// of RV32. The rounding mode of the dynamic rounding mode field is to the nearest, ties to even,
+/// since the `fcsr` register is not supported.
+pub fn decode(instruction: u32) -> Option<FpInstruction> {
+    let bits = |hi: u32, lo: u32| (instruction >> lo) & ((1 << (hi - lo + 1)) - 1);
+    let rd = bits(11, 7) as usize;
+    let rs1 = bits(19, 15) as usize;
+    let rs2 = bits(24, 20) as usize;
+    let funct3 = bits(14, 12);
+    let rm = || match funct3 {
+        0b000 | 0b111 => Some(RoundingMode::NearestEven),
+        0b001 => Some(RoundingMode::TowardZero),
+        0b010 => Some(RoundingMode::Down),
+        0b011 => Some(RoundingMode::Up),
+        0b100 => Some(RoundingMode::NearestMaxMagnitude),
+        _ => None,
+    };
+    let format = |fmt: u32| match fmt {
+        0b00 => Some(BINARY32),
+        0b01 => Some(BINARY64),
+        _ => None,
+    };
+
+    let instruction = match bits(6, 0) {
+        OPCODE_LOAD_FP => FpInstruction::Load {
+            format: format(funct3.checked_sub(0b010)?)?,
+            rd,
+            rs1,
+            imm: instruction as i32 >> 20,
+        },
+        OPCODE_STORE_FP => FpInstruction::Store {
+            format: format(funct3.checked_sub(0b010)?)?,
+            rs1,
+            rs2,
+            imm: ((instruction as i32 >> 25) << 5) | bits(11, 7) as i32,
+        },
+        opcode @ (OPCODE_MADD | OPCODE_MSUB | OPCODE_NMSUB | OPCODE_NMADD) => {
+            FpInstruction::Fused {
+                op: match opcode {
+                    OPCODE_MADD => FusedOp::Madd,
+                    OPCODE_MSUB => FusedOp::Msub,
+                    OPCODE_NMSUB => FusedOp::Nmsub,
+                    _ => FusedOp::Nmadd,
+                },
+                format: format(bits(26, 25))?,
+                rd,
+                rs1,
+                rs2,
+                rs3: bits(31, 27) as usize,
+                rm: rm()?,
+            }
+        }
+        OPCODE_OP_FP => {
+            let format = format(bits(26, 25))?;
+            let binary = |op| {
+                Some(FpInstruction::Binary {
+                    op,
+                    format,
+                    rd,
+                    rs1,
+                    rs2,
+                    rm: rm()?,
+                })
+            };
+            let single = format == BINARY32;
+            match (bits(31, 27), funct3, rs2) {
+                (0b00000, _, _) => binary(BinaryOp::Add)?,
+                (0b00001, _, _) => binary(BinaryOp::Sub)?,
+                (0b00010, _, _) => binary(BinaryOp::Mul)?,
+                (0b00011, _, _) => binary(BinaryOp::Div)?,
+                (0b01011, _, 0) => FpInstruction::Sqrt {
+                    format,
+                    rd,
+                    rs1,
+                    rm: rm()?,
+                },
+                (0b00100 | 0b00101, 0b000..=0b010, _) => FpInstruction::Binary {
+                    op: match (bits(31, 27), funct3) {
+                        (0b00100, 0b000) => BinaryOp::SignInject,
+                        (0b00100, 0b001) => BinaryOp::SignInjectNeg,
+                        (0b00100, 0b010) => BinaryOp::SignInjectXor,
+                        (0b00101, 0b000) => BinaryOp::Min,
+                        (0b00101, 0b001) => BinaryOp::Max,
+                        _ => return None,
+                    },
+                    format,
+                    rd,
+                    rs1,
+                    rs2,
+                    rm: RoundingMode::NearestEven,
+                },
+                (0b10100, 0b000..=0b010, _) => FpInstruction::Compare {
+                    op: match funct3 {
+                        0b000 => CompareOp::Le,
+                        0b001 => CompareOp::Lt,
+                        _ => CompareOp::Eq,
+                    },
+                    format,
+                    rd,
+                    rs1,
+                    rs2,
+                },
+                (0b11000, _, 0 | 1) => FpInstruction::ToInt {
+                    format,
+                    signed: rs2 == 0,
+                    rd,
+                    rs1,
+                    rm: rm()?,
+                },
+                (0b11010, _, 0 | 1) => FpInstruction::FromInt {
+                    format,
+                    signed: rs2 == 0,
+                    rd,
+                    rs1,
+                    rm: rm()?,
+                },
+                // fcvt.s.d and fcvt.d.s, whose rs2 field is the format of the source.
+                (0b01000, _, 1) if single => FpInstruction::Convert {
+                    format,
+                    rd,
+                    rs1,
+                    rm: rm()?,
+                },
+                (0b01000, _, 0) if !single => FpInstruction::Convert {
+                    format,
+                    rd,
+                    rs1,
+                    rm: rm()?,
+                },
+                (0b11100, 0b000, 0) if single => FpInstruction::MoveToInt { rd, rs1 },
+                (0b11100, 0b001, 0) => FpInstruction::Classify { format, rd, rs1 },
+                (0b11110, 0b000, 0) if single => FpInstruction::MoveFromInt { rd, rs1 },
+                _ => return None,
+            }
+        }
+        _ => return None,
+    };
+    Some(instruction)
+}
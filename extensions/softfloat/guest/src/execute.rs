@@ -0,0 +1,170 @@
+//! Execution of the instructions of the F and D extensions on the registers of the guest.
+
+use crate::{
+    decode::{BinaryOp, CompareOp, FpInstruction, FusedOp},
+    softfloat::{self, Format, BINARY32, BINARY64},
+};
+
+/// The floating-point registers. Binary32 values are NaN-boxed: their upper 32 bits are set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FpRegisters(pub [u64; 32]);
+
+impl FpRegisters {
+    /// Reads register `r` in `format`. A binary32 value which is not NaN-boxed reads as the
+    /// canonical NaN.
+    pub fn read(&self, format: Format, r: usize) -> u64 {
+        let value = self.0[r];
+        if format == BINARY64 {
+            value
+        } else if value >> 32 == 0xffff_ffff {
+            value & 0xffff_ffff
+        } else {
+            BINARY32.canonical_nan()
+        }
+    }
+
+    pub fn write(&mut self, format: Format, r: usize, value: u64) {
+        self.0[r] = if format == BINARY64 {
+            value
+        } else {
+            value | (0xffff_ffff << 32)
+        };
+    }
+}
+
+/// Executes `instruction` on the integer registers `x` and the floating-point registers `f`.
+///
+/// # Safety
+///
+/// The memory accessed by `instruction`, if it is a load or a store, must be valid.
+pub unsafe fn execute(instruction: FpInstruction, x: &mut [u32; 32], f: &mut FpRegisters) {
+    match instruction {
+        FpInstruction::Load {
+            format,
+            rd,
+            rs1,
+            imm,
+        } => {
+            let address = x[rs1].wrapping_add(imm as u32) as usize;
+            let value = if format == BINARY64 {
+                core::ptr::read_unaligned(address as *const u64)
+            } else {
+                core::ptr::read_unaligned(address as *const u32) as u64
+            };
+            f.write(format, rd, value);
+        }
+        FpInstruction::Store {
+            format,
+            rs1,
+            rs2,
+            imm,
+        } => {
+            let address = x[rs1].wrapping_add(imm as u32) as usize;
+            // Stores write the raw bits of the register, whether it is NaN-boxed or not.
+            if format == BINARY64 {
+                core::ptr::write_unaligned(address as *mut u64, f.0[rs2]);
+            } else {
+                core::ptr::write_unaligned(address as *mut u32, f.0[rs2] as u32);
+            }
+        }
+        FpInstruction::Fused {
+            op,
+            format,
+            rd,
+            rs1,
+            rs2,
+            rs3,
+            rm,
+        } => {
+            let (a, b, c) = (
+                f.read(format, rs1),
+                f.read(format, rs2),
+                f.read(format, rs3),
+            );
+            let (a, c) = match op {
+                FusedOp::Madd => (a, c),
+                FusedOp::Msub => (a, c ^ format.sign_bit()),
+                FusedOp::Nmsub => (a ^ format.sign_bit(), c),
+                FusedOp::Nmadd => (a ^ format.sign_bit(), c ^ format.sign_bit()),
+            };
+            f.write(format, rd, softfloat::fma(format, a, b, c, rm));
+        }
+        FpInstruction::Binary {
+            op,
+            format,
+            rd,
+            rs1,
+            rs2,
+            rm,
+        } => {
+            let (a, b) = (f.read(format, rs1), f.read(format, rs2));
+            let sign = format.sign_bit();
+            let result = match op {
+                BinaryOp::Add => softfloat::add(format, a, b, rm),
+                BinaryOp::Sub => softfloat::sub(format, a, b, rm),
+                BinaryOp::Mul => softfloat::mul(format, a, b, rm),
+                BinaryOp::Div => softfloat::div(format, a, b, rm),
+                BinaryOp::Min => softfloat::min(format, a, b),
+                BinaryOp::Max => softfloat::max(format, a, b),
+                BinaryOp::SignInject => a & !sign | b & sign,
+                BinaryOp::SignInjectNeg => a & !sign | !b & sign,
+                BinaryOp::SignInjectXor => a ^ b & sign,
+            };
+            f.write(format, rd, result);
+        }
+        FpInstruction::Sqrt {
+            format,
+            rd,
+            rs1,
+            rm,
+        } => f.write(format, rd, softfloat::sqrt(format, f.read(format, rs1), rm)),
+        FpInstruction::Compare {
+            op,
+            format,
+            rd,
+            rs1,
+            rs2,
+        } => {
+            let (a, b) = (f.read(format, rs1), f.read(format, rs2));
+            x[rd] = match op {
+                CompareOp::Eq => softfloat::eq(format, a, b),
+                CompareOp::Lt => softfloat::lt(format, a, b),
+                CompareOp::Le => softfloat::le(format, a, b),
+            } as u32;
+        }
+        FpInstruction::Classify { format, rd, rs1 } => {
+            x[rd] = softfloat::classify(format, f.read(format, rs1));
+        }
+        FpInstruction::ToInt {
+            format,
+            signed,
+            rd,
+            rs1,
+            rm,
+        } => x[rd] = softfloat::to_int(format, f.read(format, rs1), signed, rm),
+        FpInstruction::FromInt {
+            format,
+            signed,
+            rd,
+            rs1,
+            rm,
+        } => f.write(format, rd, softfloat::from_int(format, x[rs1], signed, rm)),
+        FpInstruction::Convert {
+            format,
+            rd,
+            rs1,
+            rm,
+        } => {
+            let from = if format == BINARY32 {
+                BINARY64
+            } else {
+                BINARY32
+            };
+            let value = softfloat::convert(from, format, f.read(from, rs1), rm);
+            f.write(format, rd, value);
+        }
+        FpInstruction::MoveToInt { rd, rs1 } => x[rd] = f.0[rs1] as u32,
+        FpInstruction::MoveFromInt { rd, rs1 } => f.write(BINARY32, rd, x[rs1] as u64),
+    }
+    x[0] = 0;
+}
@@ -0,0 +1,33 @@
+#![no_std]
+
+//! Floating-point support for guest programs compiled with the RISC-V F and D extensions, e.g.
+//! with `-C target-feature=+f,+d`.
+//!
+//! The VM has no floating-point instructions. The softfloat transpiler extension replaces each of
+//! them with a trap into the handler of this crate, which decodes the instruction and executes it
+//! with the IEEE 754 arithmetic of [softfloat], using integer instructions only. The floating-point
+//! registers are kept in memory. The handler is registered by [init].
+//!
+//! The `fcsr` register is not supported: the dynamic rounding mode is always to the nearest, ties
+//! to even, the accrued exception flags are not recorded, and the CSR instructions accessing
+//! `fcsr`, `frm` or `fflags` are not transpiled, so a program containing them fails to transpile.
+
+pub mod decode;
+pub mod execute;
+pub mod softfloat;
+#[cfg(target_os = "zkvm")]
+mod trap;
+
+#[cfg(target_os = "zkvm")]
+pub use trap::init;
+
+/// This is custom-0 defined in RISC-V spec document
+pub const OPCODE: u8 = 0x0b;
+pub const SOFTFLOAT_FUNCT3: u8 = 0b100;
+/// Sets the address of the trap handler to `rs1`.
+pub const SET_TRAP_VECTOR_FUNCT7: u8 = 0xa;
+/// Writes the return address of the current trap, which is 4 bytes after the trapping
+/// instruction, to `rd`.
+pub const TRAP_RETURN_ADDRESS_FUNCT7: u8 = 0xb;
+/// Jumps to the return address of the current trap.
+pub const TRAP_RETURN_FUNCT7: u8 = 0xc;
@@ -0,0 +1,777 @@
+//! IEEE 754 binary32 and binary64 arithmetic on the bits of the values, with integer operations
+//! only.
+//!
+//! The results are those of the RISC-V F and D extensions: every NaN result is the canonical NaN
+//! of its format, and the exception flags are not computed. Binary32 values are passed in the low
+//! 32 bits of a `u64`.
+
+use core::cmp::Ordering;
+
+/// A static rounding mode of the RISC-V F extension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// To the nearest value, with ties to the value with an even least significant digit.
+    NearestEven,
+    TowardZero,
+    /// Toward negative infinity.
+    Down,
+    /// Toward positive infinity.
+    Up,
+    /// To the nearest value, with ties to the value of larger magnitude.
+    NearestMaxMagnitude,
+}
+
+/// An IEEE 754 binary interchange format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Format {
+    exp_bits: u32,
+    frac_bits: u32,
+}
+
+/// The format of `f32`.
+pub const BINARY32: Format = Format {
+    exp_bits: 8,
+    frac_bits: 23,
+};
+/// The format of `f64`.
+pub const BINARY64: Format = Format {
+    exp_bits: 11,
+    frac_bits: 52,
+};
+
+impl Format {
+    /// The biased exponent of infinities and NaNs.
+    const fn max_biased_exp(self) -> u64 {
+        (1 << self.exp_bits) - 1
+    }
+
+    const fn frac_mask(self) -> u64 {
+        (1 << self.frac_bits) - 1
+    }
+
+    pub const fn sign_bit(self) -> u64 {
+        1 << (self.exp_bits + self.frac_bits)
+    }
+
+    /// The exponent of the least significant bit of the subnormals, which is also that of the
+    /// smallest normals.
+    const fn min_exp(self) -> i32 {
+        2 - (1 << (self.exp_bits - 1)) - self.frac_bits as i32
+    }
+
+    pub const fn canonical_nan(self) -> u64 {
+        (self.max_biased_exp() << self.frac_bits) | (1 << (self.frac_bits - 1))
+    }
+
+    fn infinity(self, sign: bool) -> u64 {
+        self.signed(sign, self.max_biased_exp() << self.frac_bits)
+    }
+
+    fn zero(self, sign: bool) -> u64 {
+        self.signed(sign, 0)
+    }
+
+    fn signed(self, sign: bool, magnitude: u64) -> u64 {
+        if sign {
+            magnitude | self.sign_bit()
+        } else {
+            magnitude
+        }
+    }
+
+    fn is_nan(self, a: u64) -> bool {
+        a & !self.sign_bit() > self.max_biased_exp() << self.frac_bits
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Value {
+    Nan,
+    Infinity(bool),
+    Zero(bool),
+    /// `(-1)^sign * sig * 2^exp`, with a nonzero `sig`.
+    Finite {
+        sign: bool,
+        exp: i32,
+        sig: u128,
+    },
+}
+
+fn unpack(fmt: Format, a: u64) -> Value {
+    let sign = a & fmt.sign_bit() != 0;
+    let biased_exp = (a >> fmt.frac_bits) & fmt.max_biased_exp();
+    let frac = a & fmt.frac_mask();
+    if biased_exp == fmt.max_biased_exp() {
+        if frac == 0 {
+            Value::Infinity(sign)
+        } else {
+            Value::Nan
+        }
+    } else if biased_exp == 0 {
+        if frac == 0 {
+            Value::Zero(sign)
+        } else {
+            Value::Finite {
+                sign,
+                exp: fmt.min_exp(),
+                sig: frac as u128,
+            }
+        }
+    } else {
+        Value::Finite {
+            sign,
+            exp: fmt.min_exp() + biased_exp as i32 - 1,
+            sig: (frac | (1 << fmt.frac_bits)) as u128,
+        }
+    }
+}
+
+/// Shifts `sig` left so that its most significant bit is the hidden bit of `fmt`.
+fn normalize(fmt: Format, exp: i32, sig: u128) -> (i32, u128) {
+    let shift = sig.leading_zeros() as i32 - (127 - fmt.frac_bits as i32);
+    (exp - shift, sig << shift)
+}
+
+/// Shifts `sig` right by `shift` bits and rounds the result to an integer in mode `rm`, for a
+/// value of sign `sign`.
+fn shift_round(sign: bool, sig: u128, shift: u32, rm: RoundingMode) -> u128 {
+    if shift == 0 {
+        return sig;
+    }
+    // The quotient, how the remainder compares to half of the divisor, and whether it is nonzero.
+    let (quotient, half, inexact) = match shift {
+        0..=127 => {
+            let remainder = sig & ((1 << shift) - 1);
+            (
+                sig >> shift,
+                remainder.cmp(&(1 << (shift - 1))),
+                remainder != 0,
+            )
+        }
+        128 => (0, sig.cmp(&(1 << 127)), sig != 0),
+        _ => (0, Ordering::Less, sig != 0),
+    };
+    let increment = match rm {
+        RoundingMode::NearestEven => {
+            half == Ordering::Greater || (half == Ordering::Equal && quotient & 1 == 1)
+        }
+        RoundingMode::NearestMaxMagnitude => half != Ordering::Less,
+        RoundingMode::TowardZero => false,
+        RoundingMode::Down => inexact && sign,
+        RoundingMode::Up => inexact && !sign,
+    };
+    quotient + increment as u128
+}
+
+/// Rounds `(-1)^sign * sig * 2^exp` to `fmt` in mode `rm`. `sig` must be nonzero. If the exact
+/// value is not a multiple of `2^exp`, `sig` must have at least 2 bits below the least significant
+/// bit of the result, and its least significant bit must be set.
+fn round(fmt: Format, sign: bool, exp: i32, sig: u128, rm: RoundingMode) -> u64 {
+    let msb = 127 - sig.leading_zeros() as i32;
+    // The exponent of the least significant bit of the result, which is at most that of the
+    // subnormals.
+    let mut lsb_exp = (exp + msb - fmt.frac_bits as i32).max(fmt.min_exp());
+    let mut rounded = if lsb_exp <= exp {
+        sig << (exp - lsb_exp)
+    } else {
+        shift_round(sign, sig, (lsb_exp - exp) as u32, rm)
+    };
+    // Rounding up may carry into the next power of two.
+    if rounded >> (fmt.frac_bits + 1) != 0 {
+        rounded >>= 1;
+        lsb_exp += 1;
+    }
+    let rounded = rounded as u64;
+    if rounded >> fmt.frac_bits == 0 {
+        // A subnormal or zero.
+        return fmt.signed(sign, rounded);
+    }
+    let biased_exp = (lsb_exp - fmt.min_exp() + 1) as u64;
+    if biased_exp >= fmt.max_biased_exp() {
+        let to_infinity = match rm {
+            RoundingMode::NearestEven | RoundingMode::NearestMaxMagnitude => true,
+            RoundingMode::TowardZero => false,
+            RoundingMode::Down => sign,
+            RoundingMode::Up => !sign,
+        };
+        return if to_infinity {
+            fmt.infinity(sign)
+        } else {
+            fmt.infinity(sign) - 1
+        };
+    }
+    fmt.signed(
+        sign,
+        (biased_exp << fmt.frac_bits) | rounded & fmt.frac_mask(),
+    )
+}
+
+/// The sum of `(-1)^sign * sig * 2^exp` for the two nonzero values with significands below
+/// `2^124`, with the bits shifted out of the smaller value jammed into the least significant bit,
+/// or `None` if it is zero.
+fn add_finite(a: (bool, i32, u128), b: (bool, i32, u128)) -> Option<(bool, i32, u128)> {
+    // Aligns the most significant bits of both values at bit 124, so the larger exponent is that
+    // of the value of larger magnitude, up to a factor of 2.
+    let align = |(sign, exp, sig): (bool, i32, u128)| {
+        let shift = sig.leading_zeros() as i32 - 3;
+        (sign, exp - shift, sig << shift)
+    };
+    let (a, b) = (align(a), align(b));
+    let ((sign_a, exp, sig_a), (sign_b, exp_b, sig_b)) = if a.1 >= b.1 { (a, b) } else { (b, a) };
+    // The bits shifted out are more than 2 bits below the least significant bit of any rounded
+    // sum, since the sum is at least `2^123` if they are not all zero.
+    let shift = (exp - exp_b) as u32;
+    let sig_b = if shift < 128 {
+        (sig_b >> shift) | (sig_b & ((1 << shift) - 1) != 0) as u128
+    } else {
+        1
+    };
+    let (sign, sig) = if sign_a == sign_b {
+        (sign_a, sig_a + sig_b)
+    } else if sig_a >= sig_b {
+        (sign_a, sig_a - sig_b)
+    } else {
+        (sign_b, sig_b - sig_a)
+    };
+    (sig != 0).then_some((sign, exp, sig))
+}
+
+pub fn add(fmt: Format, a: u64, b: u64, rm: RoundingMode) -> u64 {
+    match (unpack(fmt, a), unpack(fmt, b)) {
+        (Value::Nan, _) | (_, Value::Nan) => fmt.canonical_nan(),
+        (Value::Infinity(sign_a), Value::Infinity(sign_b)) if sign_a != sign_b => {
+            fmt.canonical_nan()
+        }
+        (Value::Infinity(_), _) => a,
+        (_, Value::Infinity(_)) => b,
+        (Value::Zero(sign_a), Value::Zero(sign_b)) => fmt.zero(if sign_a == sign_b {
+            sign_a
+        } else {
+            rm == RoundingMode::Down
+        }),
+        (Value::Zero(_), _) => b,
+        (_, Value::Zero(_)) => a,
+        (
+            Value::Finite {
+                sign: sign_a,
+                exp: exp_a,
+                sig: sig_a,
+            },
+            Value::Finite {
+                sign: sign_b,
+                exp: exp_b,
+                sig: sig_b,
+            },
+        ) => match add_finite((sign_a, exp_a, sig_a), (sign_b, exp_b, sig_b)) {
+            Some((sign, exp, sig)) => round(fmt, sign, exp, sig, rm),
+            None => fmt.zero(rm == RoundingMode::Down),
+        },
+    }
+}
+
+pub fn sub(fmt: Format, a: u64, b: u64, rm: RoundingMode) -> u64 {
+    add(fmt, a, b ^ fmt.sign_bit(), rm)
+}
+
+pub fn mul(fmt: Format, a: u64, b: u64, rm: RoundingMode) -> u64 {
+    let sign = (a ^ b) & fmt.sign_bit() != 0;
+    match (unpack(fmt, a), unpack(fmt, b)) {
+        (Value::Nan, _) | (_, Value::Nan) => fmt.canonical_nan(),
+        (Value::Infinity(_), Value::Zero(_)) | (Value::Zero(_), Value::Infinity(_)) => {
+            fmt.canonical_nan()
+        }
+        (Value::Infinity(_), _) | (_, Value::Infinity(_)) => fmt.infinity(sign),
+        (Value::Zero(_), _) | (_, Value::Zero(_)) => fmt.zero(sign),
+        (
+            Value::Finite {
+                exp: exp_a,
+                sig: sig_a,
+                ..
+            },
+            Value::Finite {
+                exp: exp_b,
+                sig: sig_b,
+                ..
+            },
+        ) => round(fmt, sign, exp_a + exp_b, sig_a * sig_b, rm),
+    }
+}
+
+pub fn div(fmt: Format, a: u64, b: u64, rm: RoundingMode) -> u64 {
+    let sign = (a ^ b) & fmt.sign_bit() != 0;
+    match (unpack(fmt, a), unpack(fmt, b)) {
+        (Value::Nan, _) | (_, Value::Nan) => fmt.canonical_nan(),
+        (Value::Infinity(_), Value::Infinity(_)) | (Value::Zero(_), Value::Zero(_)) => {
+            fmt.canonical_nan()
+        }
+        (Value::Infinity(_), _) | (_, Value::Zero(_)) => fmt.infinity(sign),
+        (_, Value::Infinity(_)) | (Value::Zero(_), _) => fmt.zero(sign),
+        (
+            Value::Finite {
+                exp: exp_a,
+                sig: sig_a,
+                ..
+            },
+            Value::Finite {
+                exp: exp_b,
+                sig: sig_b,
+                ..
+            },
+        ) => {
+            let (exp_a, sig_a) = normalize(fmt, exp_a, sig_a);
+            let (exp_b, sig_b) = normalize(fmt, exp_b, sig_b);
+            // The quotient has at least `frac_bits + 3` bits, so 2 bits below the least
+            // significant bit of the result.
+            let shift = fmt.frac_bits + 3;
+            let dividend = sig_a << shift;
+            let (quotient, remainder) = (dividend / sig_b, dividend % sig_b);
+            let quotient = quotient | (remainder != 0) as u128;
+            round(fmt, sign, exp_a - exp_b - shift as i32, quotient, rm)
+        }
+    }
+}
+
+pub fn sqrt(fmt: Format, a: u64, rm: RoundingMode) -> u64 {
+    match unpack(fmt, a) {
+        Value::Zero(_) | Value::Infinity(false) => a,
+        Value::Nan | Value::Infinity(true) | Value::Finite { sign: true, .. } => {
+            fmt.canonical_nan()
+        }
+        Value::Finite { exp, sig, .. } => {
+            let (mut exp, mut sig) = normalize(fmt, exp, sig);
+            if exp & 1 != 0 {
+                exp -= 1;
+                sig <<= 1;
+            }
+            // An even shift for which the root has at least `frac_bits + 3` bits.
+            let shift = (fmt.frac_bits + 7) & !1;
+            let (root, exact) = isqrt(sig << shift);
+            round(
+                fmt,
+                false,
+                (exp - shift as i32) / 2,
+                root | !exact as u128,
+                rm,
+            )
+        }
+    }
+}
+
+/// The integer square root of `n`, and whether it is exact.
+fn isqrt(n: u128) -> (u128, bool) {
+    let mut remainder = n;
+    let mut root = 0;
+    let mut bit = 1 << 126;
+    while bit > n {
+        bit >>= 2;
+    }
+    while bit != 0 {
+        if remainder >= root + bit {
+            remainder -= root + bit;
+            root = (root >> 1) + bit;
+        } else {
+            root >>= 1;
+        }
+        bit >>= 2;
+    }
+    (root, remainder == 0)
+}
+
+/// `a * b + c`, rounded once.
+pub fn fma(fmt: Format, a: u64, b: u64, c: u64, rm: RoundingMode) -> u64 {
+    let sign = (a ^ b) & fmt.sign_bit() != 0;
+    match (unpack(fmt, a), unpack(fmt, b), unpack(fmt, c)) {
+        (Value::Nan, _, _) | (_, Value::Nan, _) | (_, _, Value::Nan) => fmt.canonical_nan(),
+        (Value::Infinity(_), Value::Zero(_), _) | (Value::Zero(_), Value::Infinity(_), _) => {
+            fmt.canonical_nan()
+        }
+        (Value::Infinity(_), _, Value::Infinity(sign_c))
+        | (_, Value::Infinity(_), Value::Infinity(sign_c))
+            if sign_c != sign =>
+        {
+            fmt.canonical_nan()
+        }
+        (Value::Infinity(_), _, _) | (_, Value::Infinity(_), _) => fmt.infinity(sign),
+        (_, _, Value::Infinity(_)) => c,
+        (Value::Zero(_), _, Value::Zero(sign_c)) | (_, Value::Zero(_), Value::Zero(sign_c)) => fmt
+            .zero(if sign == sign_c {
+                sign
+            } else {
+                rm == RoundingMode::Down
+            }),
+        (Value::Zero(_), _, _) | (_, Value::Zero(_), _) => c,
+        (
+            Value::Finite {
+                exp: exp_a,
+                sig: sig_a,
+                ..
+            },
+            Value::Finite {
+                exp: exp_b,
+                sig: sig_b,
+                ..
+            },
+            c,
+        ) => {
+            let product = (sign, exp_a + exp_b, sig_a * sig_b);
+            let sum = match c {
+                Value::Finite { sign, exp, sig } => add_finite(product, (sign, exp, sig)),
+                _ => Some(product),
+            };
+            match sum {
+                Some((sign, exp, sig)) => round(fmt, sign, exp, sig, rm),
+                None => fmt.zero(rm == RoundingMode::Down),
+            }
+        }
+    }
+}
+
+/// The minimum of `a` and `b`, where `-0` is less than `+0`, or the other value if one is NaN.
+pub fn min(fmt: Format, a: u64, b: u64) -> u64 {
+    match (fmt.is_nan(a), fmt.is_nan(b)) {
+        (true, true) => fmt.canonical_nan(),
+        (true, false) => b,
+        (false, true) => a,
+        (false, false) => match order_key(fmt, a).cmp(&order_key(fmt, b)) {
+            Ordering::Less => a,
+            Ordering::Greater => b,
+            Ordering::Equal => a | b,
+        },
+    }
+}
+
+/// The maximum of `a` and `b`, where `-0` is less than `+0`, or the other value if one is NaN.
+pub fn max(fmt: Format, a: u64, b: u64) -> u64 {
+    match (fmt.is_nan(a), fmt.is_nan(b)) {
+        (true, true) => fmt.canonical_nan(),
+        (true, false) => b,
+        (false, true) => a,
+        (false, false) => match order_key(fmt, a).cmp(&order_key(fmt, b)) {
+            Ordering::Less => b,
+            Ordering::Greater => a,
+            Ordering::Equal => a & b,
+        },
+    }
+}
+
+pub fn eq(fmt: Format, a: u64, b: u64) -> bool {
+    !fmt.is_nan(a) && !fmt.is_nan(b) && order_key(fmt, a) == order_key(fmt, b)
+}
+
+pub fn lt(fmt: Format, a: u64, b: u64) -> bool {
+    !fmt.is_nan(a) && !fmt.is_nan(b) && order_key(fmt, a) < order_key(fmt, b)
+}
+
+pub fn le(fmt: Format, a: u64, b: u64) -> bool {
+    !fmt.is_nan(a) && !fmt.is_nan(b) && order_key(fmt, a) <= order_key(fmt, b)
+}
+
+/// An integer ordered like the non-NaN value `a`, equal for both zeros.
+fn order_key(fmt: Format, a: u64) -> i64 {
+    let magnitude = (a & !fmt.sign_bit()) as i64;
+    if a & fmt.sign_bit() != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// The class of `a` as written by `fclass`: a mask with one of the bits set, for negative
+/// infinity, negative normals, negative subnormals, `-0`, `+0`, positive subnormals, positive
+/// normals, positive infinity, signaling NaNs and quiet NaNs in order.
+pub fn classify(fmt: Format, a: u64) -> u32 {
+    let sign = a & fmt.sign_bit() != 0;
+    let biased_exp = (a >> fmt.frac_bits) & fmt.max_biased_exp();
+    let frac = a & fmt.frac_mask();
+    let bit = if biased_exp == fmt.max_biased_exp() {
+        match (frac, frac >> (fmt.frac_bits - 1)) {
+            (0, _) if sign => 0,
+            (0, _) => 7,
+            (_, 0) => 8,
+            _ => 9,
+        }
+    } else {
+        let positive = match (biased_exp, frac) {
+            (0, 0) => 4,
+            (0, _) => 5,
+            _ => 6,
+        };
+        if sign {
+            7 - positive
+        } else {
+            positive
+        }
+    };
+    1 << bit
+}
+
+/// Converts `a` to a 32-bit integer, signed if `signed`, rounded in mode `rm`. Out of range
+/// values saturate, and NaNs convert to the largest integer.
+pub fn to_int(fmt: Format, a: u64, signed: bool, rm: RoundingMode) -> u32 {
+    let (min, max) = if signed {
+        (i32::MIN as i64, i32::MAX as i64)
+    } else {
+        (0, u32::MAX as i64)
+    };
+    let value = match unpack(fmt, a) {
+        Value::Nan | Value::Infinity(false) => max,
+        Value::Infinity(true) => min,
+        Value::Zero(_) => 0,
+        Value::Finite { sign, exp, sig } => {
+            let magnitude = if exp >= 64 {
+                u128::MAX
+            } else if exp >= 0 {
+                sig << exp
+            } else {
+                shift_round(sign, sig, -exp as u32, rm)
+            };
+            let magnitude = magnitude.min(1 << 40) as i64;
+            if sign {
+                -magnitude
+            } else {
+                magnitude
+            }
+        }
+    };
+    value.clamp(min, max) as u32
+}
+
+/// Converts the 32-bit integer `a`, signed if `signed`, to `fmt`, rounded in mode `rm`.
+pub fn from_int(fmt: Format, a: u32, signed: bool, rm: RoundingMode) -> u64 {
+    let (sign, magnitude) = if signed && (a as i32) < 0 {
+        (true, (a as i32).unsigned_abs())
+    } else {
+        (false, a)
+    };
+    if magnitude == 0 {
+        return 0;
+    }
+    round(fmt, sign, 0, magnitude as u128, rm)
+}
+
+/// Converts `a` from the format `from` to the format `to`, rounded in mode `rm`.
+pub fn convert(from: Format, to: Format, a: u64, rm: RoundingMode) -> u64 {
+    match unpack(from, a) {
+        Value::Nan => to.canonical_nan(),
+        Value::Infinity(sign) => to.infinity(sign),
+        Value::Zero(sign) => to.zero(sign),
+        Value::Finite { sign, exp, sig } => round(to, sign, exp, sig, rm),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+
+    /// Values covering the special values, subnormals and values of extreme exponents, followed by
+    /// pseudo-random bit patterns.
+    fn values64() -> impl Iterator<Item = f64> {
+        let special = [
+            0.0,
+            -0.0,
+            1.0,
+            -1.5,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NAN,
+            f64::MAX,
+            f64::MIN_POSITIVE,
+            f64::MIN_POSITIVE / 3.0,
+            -f64::from_bits(1),
+            f64::EPSILON,
+        ];
+        let mut state = 0x9e37_79b9_7f4a_7c15u64;
+        let random = (0..2000).map(move |_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            f64::from_bits(state)
+        });
+        special.into_iter().chain(random)
+    }
+
+    fn canonical64(a: f64) -> u64 {
+        if a.is_nan() {
+            BINARY64.canonical_nan()
+        } else {
+            a.to_bits()
+        }
+    }
+
+    fn canonical32(a: f32) -> u64 {
+        if a.is_nan() {
+            BINARY32.canonical_nan()
+        } else {
+            a.to_bits() as u64
+        }
+    }
+
+    #[test]
+    fn test_binary64_matches_native() {
+        let rm = RoundingMode::NearestEven;
+        let values: std::vec::Vec<f64> = values64().collect();
+        for (&a, (&b, &c)) in values
+            .iter()
+            .zip(values.iter().skip(1).zip(values.iter().skip(2)))
+        {
+            let (x, y, z) = (a.to_bits(), b.to_bits(), c.to_bits());
+            assert_eq!(add(BINARY64, x, y, rm), canonical64(a + b), "{a:e} + {b:e}");
+            assert_eq!(sub(BINARY64, x, y, rm), canonical64(a - b), "{a:e} - {b:e}");
+            assert_eq!(mul(BINARY64, x, y, rm), canonical64(a * b), "{a:e} * {b:e}");
+            assert_eq!(div(BINARY64, x, y, rm), canonical64(a / b), "{a:e} / {b:e}");
+            assert_eq!(sqrt(BINARY64, x, rm), canonical64(a.sqrt()), "sqrt {a:e}");
+            assert_eq!(
+                fma(BINARY64, x, y, z, rm),
+                canonical64(a.mul_add(b, c)),
+                "fma {a:e} {b:e} {c:e}"
+            );
+            // Cancels the rounded product, leaving its rounding error.
+            let w = -(a * b);
+            assert_eq!(
+                fma(BINARY64, x, y, w.to_bits(), rm),
+                canonical64(a.mul_add(b, w)),
+                "fma {a:e} {b:e} {w:e}"
+            );
+            assert_eq!(lt(BINARY64, x, y), a < b);
+            assert_eq!(eq(BINARY64, x, y), a == b);
+            assert_eq!(
+                convert(BINARY64, BINARY32, x, rm),
+                canonical32(a as f32),
+                "{a:e} as f32"
+            );
+            if !a.is_nan() {
+                assert_eq!(
+                    to_int(BINARY64, x, true, RoundingMode::TowardZero),
+                    a as i32 as u32
+                );
+                assert_eq!(
+                    to_int(BINARY64, x, false, RoundingMode::TowardZero),
+                    a as u32
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_binary32_matches_native() {
+        let rm = RoundingMode::NearestEven;
+        let values: std::vec::Vec<f32> = values64()
+            .map(|a| f32::from_bits((a.to_bits() >> 32) as u32))
+            .chain([f32::MIN_POSITIVE / 5.0, f32::MAX, 3.0])
+            .collect();
+        for (&a, (&b, &c)) in values
+            .iter()
+            .zip(values.iter().skip(1).zip(values.iter().skip(2)))
+        {
+            let (x, y, z) = (a.to_bits() as u64, b.to_bits() as u64, c.to_bits() as u64);
+            assert_eq!(add(BINARY32, x, y, rm), canonical32(a + b), "{a:e} + {b:e}");
+            assert_eq!(mul(BINARY32, x, y, rm), canonical32(a * b), "{a:e} * {b:e}");
+            assert_eq!(div(BINARY32, x, y, rm), canonical32(a / b), "{a:e} / {b:e}");
+            assert_eq!(sqrt(BINARY32, x, rm), canonical32(a.sqrt()), "sqrt {a:e}");
+            assert_eq!(
+                fma(BINARY32, x, y, z, rm),
+                canonical32(a.mul_add(b, c)),
+                "fma {a:e} {b:e} {c:e}"
+            );
+            assert_eq!(
+                convert(BINARY32, BINARY64, x, rm),
+                canonical64(a as f64),
+                "{a:e} as f64"
+            );
+            let i = y as u32;
+            assert_eq!(
+                from_int(BINARY32, i, true, rm),
+                canonical32(i as i32 as f32)
+            );
+            assert_eq!(from_int(BINARY32, i, false, rm), canonical32(i as f32));
+        }
+    }
+
+    #[test]
+    fn test_rounding_modes() {
+        let one = 1f32.to_bits() as u64;
+        let tiny = f32::from_bits(1).to_bits() as u64;
+        let next_up = 1f32.next_up().to_bits() as u64;
+        let next_down = 1f32.next_down().to_bits() as u64;
+        assert_eq!(add(BINARY32, one, tiny, RoundingMode::NearestEven), one);
+        assert_eq!(add(BINARY32, one, tiny, RoundingMode::Up), next_up);
+        assert_eq!(add(BINARY32, one, tiny, RoundingMode::Down), one);
+        assert_eq!(
+            sub(BINARY32, one, tiny, RoundingMode::TowardZero),
+            next_down
+        );
+        assert_eq!(sub(BINARY32, one, tiny, RoundingMode::Up), one);
+
+        // 2^24 + 1 is halfway between two binary32 values.
+        let tie = (1 << 24) + 1;
+        let even = 16777216f32.to_bits() as u64;
+        let odd = 16777218f32.to_bits() as u64;
+        assert_eq!(
+            from_int(BINARY32, tie, false, RoundingMode::NearestEven),
+            even
+        );
+        assert_eq!(
+            from_int(BINARY32, tie, false, RoundingMode::NearestMaxMagnitude),
+            odd
+        );
+
+        // An exact zero difference is negative only when rounding down.
+        assert_eq!(sub(BINARY32, one, one, RoundingMode::NearestEven), 0);
+        assert_eq!(
+            sub(BINARY32, one, one, RoundingMode::Down),
+            BINARY32.sign_bit()
+        );
+
+        let max = f64::MAX.to_bits();
+        assert_eq!(add(BINARY64, max, max, RoundingMode::TowardZero), max);
+        assert_eq!(
+            add(BINARY64, max, max, RoundingMode::Up),
+            f64::INFINITY.to_bits()
+        );
+
+        let half = (-2.5f64).to_bits();
+        assert_eq!(
+            to_int(BINARY64, half, true, RoundingMode::NearestEven),
+            -2i32 as u32
+        );
+        assert_eq!(
+            to_int(BINARY64, half, true, RoundingMode::NearestMaxMagnitude),
+            -3i32 as u32
+        );
+        assert_eq!(to_int(BINARY64, half, true, RoundingMode::Up), -2i32 as u32);
+        assert_eq!(to_int(BINARY64, half, false, RoundingMode::Down), 0);
+        assert_eq!(
+            to_int(
+                BINARY64,
+                f64::NAN.to_bits(),
+                true,
+                RoundingMode::NearestEven
+            ),
+            i32::MAX as u32
+        );
+    }
+
+    #[test]
+    fn test_min_max_classify() {
+        let (pos, neg) = (0f64.to_bits(), (-0f64).to_bits());
+        let nan = f64::NAN.to_bits();
+        let one = 1f64.to_bits();
+        assert_eq!(min(BINARY64, pos, neg), neg);
+        assert_eq!(max(BINARY64, neg, pos), pos);
+        assert_eq!(min(BINARY64, nan, one), one);
+        assert_eq!(max(BINARY64, nan, nan), BINARY64.canonical_nan());
+
+        assert_eq!(classify(BINARY64, f64::NEG_INFINITY.to_bits()), 1 << 0);
+        assert_eq!(classify(BINARY64, (-1f64).to_bits()), 1 << 1);
+        assert_eq!(classify(BINARY64, (-f64::from_bits(1)).to_bits()), 1 << 2);
+        assert_eq!(classify(BINARY64, neg), 1 << 3);
+        assert_eq!(classify(BINARY64, pos), 1 << 4);
+        assert_eq!(classify(BINARY64, 1), 1 << 5);
+        assert_eq!(classify(BINARY64, one), 1 << 6);
+        assert_eq!(classify(BINARY64, f64::INFINITY.to_bits()), 1 << 7);
+        assert_eq!(classify(BINARY64, 0x7ff0_0000_0000_0001), 1 << 8);
+        assert_eq!(classify(BINARY64, nan), 1 << 9);
+    }
+}
@@ -0,0 +1,128 @@
+//! The trap handler emulating the instructions of the F and D extensions in the zkVM.
+//!
+//! The softfloat transpiler extension replaces each of these instructions with a jump to the
+//! handler registered by [init], which saves the integer registers on the stack, decodes the
+//! instruction from the program in memory, executes it and returns to the next instruction.
+
+use core::ptr::{addr_of_mut, read_volatile};
+
+use crate::{
+    decode::decode,
+    execute::{execute, FpRegisters},
+    OPCODE, SET_TRAP_VECTOR_FUNCT7, SOFTFLOAT_FUNCT3,
+};
+
+static mut FP_REGISTERS: FpRegisters = FpRegisters([0; 32]);
+
+// The frame holds x0 to x31 at offsets 0 to 124, where x0 is unused and x2 is the stack pointer
+// before the trap. The custom instructions read the return address of the trap
+// (TRAP_RETURN_ADDRESS_FUNCT7) and return from the trap (TRAP_RETURN_FUNCT7).
+core::arch::global_asm!(
+    r#"
+.section .text.openvm_softfloat_trap;
+.globl openvm_softfloat_trap;
+.p2align 2;
+openvm_softfloat_trap:
+    addi sp, sp, -128
+    sw x1, 4(sp)
+    sw x3, 12(sp)
+    sw x4, 16(sp)
+    sw x5, 20(sp)
+    sw x6, 24(sp)
+    sw x7, 28(sp)
+    sw x8, 32(sp)
+    sw x9, 36(sp)
+    sw x10, 40(sp)
+    sw x11, 44(sp)
+    sw x12, 48(sp)
+    sw x13, 52(sp)
+    sw x14, 56(sp)
+    sw x15, 60(sp)
+    sw x16, 64(sp)
+    sw x17, 68(sp)
+    sw x18, 72(sp)
+    sw x19, 76(sp)
+    sw x20, 80(sp)
+    sw x21, 84(sp)
+    sw x22, 88(sp)
+    sw x23, 92(sp)
+    sw x24, 96(sp)
+    sw x25, 100(sp)
+    sw x26, 104(sp)
+    sw x27, 108(sp)
+    sw x28, 112(sp)
+    sw x29, 116(sp)
+    sw x30, 120(sp)
+    sw x31, 124(sp)
+    addi t0, sp, 128
+    sw t0, 8(sp)
+    mv a0, sp
+    .insn r 0x0b, 4, 0x0b, a1, x0, x0
+    call openvm_softfloat_emulate
+    lw x1, 4(sp)
+    lw x3, 12(sp)
+    lw x4, 16(sp)
+    lw x5, 20(sp)
+    lw x6, 24(sp)
+    lw x7, 28(sp)
+    lw x8, 32(sp)
+    lw x9, 36(sp)
+    lw x10, 40(sp)
+    lw x11, 44(sp)
+    lw x12, 48(sp)
+    lw x13, 52(sp)
+    lw x14, 56(sp)
+    lw x15, 60(sp)
+    lw x16, 64(sp)
+    lw x17, 68(sp)
+    lw x18, 72(sp)
+    lw x19, 76(sp)
+    lw x20, 80(sp)
+    lw x21, 84(sp)
+    lw x22, 88(sp)
+    lw x23, 92(sp)
+    lw x24, 96(sp)
+    lw x25, 100(sp)
+    lw x26, 104(sp)
+    lw x27, 108(sp)
+    lw x28, 112(sp)
+    lw x29, 116(sp)
+    lw x30, 120(sp)
+    lw x31, 124(sp)
+    addi sp, sp, 128
+    .insn r 0x0b, 4, 0x0c, x0, x0, x0
+"#
+);
+
+extern "C" {
+    fn openvm_softfloat_trap();
+}
+
+/// Emulates the instruction before `return_address` on the registers saved in `frame`.
+#[no_mangle]
+unsafe extern "C" fn openvm_softfloat_emulate(frame: &mut [u32; 32], return_address: u32) {
+    // The instruction is 2-byte aligned in programs using the compressed (C) extension.
+    let pc = return_address - 4;
+    let instruction = read_volatile(pc as usize as *const u16) as u32
+        | (read_volatile((pc + 2) as usize as *const u16) as u32) << 16;
+    let Some(instruction) = decode(instruction) else {
+        panic!("illegal floating-point instruction {instruction:#010x} at pc {pc:#x}");
+    };
+    let saved_sp = frame[2];
+    execute(instruction, frame, &mut *addr_of_mut!(FP_REGISTERS));
+    // The stack pointer is restored by the handler, so it cannot be written.
+    frame[2] = saved_sp;
+}
+
+/// Registers the trap handler of the floating-point instructions. It must be called before the
+/// first floating-point instruction, which `openvm` does when its `softfloat` feature is enabled.
+pub fn init() {
+    openvm_platform::custom_insn_r!(
+        opcode = OPCODE,
+        funct3 = SOFTFLOAT_FUNCT3,
+        funct7 = SET_TRAP_VECTOR_FUNCT7,
+        rd = Const "x0",
+        rs1 = In openvm_softfloat_trap as usize,
+        rs2 = Const "x0"
+    );
+}
@@ -0,0 +1,98 @@
+//! Transpilation of the instructions of the RISC-V F and D extensions into traps to the runtime
+//! of `openvm-softfloat-guest`, which emulates them with integer instructions.
+
+use openvm_instructions::{instruction::Instruction, riscv::RV32_REGISTER_NUM_LIMBS, LocalOpcode};
+use openvm_rv32im_transpiler::{BaseAluOpcode, Rv32JalrOpcode};
+use openvm_softfloat_guest::{
+    decode::{decode, FP_OPCODES},
+    OPCODE, SET_TRAP_VECTOR_FUNCT7, SOFTFLOAT_FUNCT3, TRAP_RETURN_ADDRESS_FUNCT7,
+    TRAP_RETURN_FUNCT7,
+};
+use openvm_stark_backend::p3_field::PrimeField32;
+use openvm_transpiler::{util::nop, InstructionDispatch, TranspilerExtension, TranspilerOutput};
+use rrs_lib::instruction_formats::RType;
+
+/// Pointers of the registers holding the address of the trap handler and the return address of
+/// the current trap. They are past the 32 RISC-V registers and the registers of the RV32I
+/// lowerings, so the guest program can only access them with the custom instructions.
+const TRAP_VECTOR: usize = RV32_REGISTER_NUM_LIMBS * 34;
+const TRAP_RETURN_ADDRESS: usize = RV32_REGISTER_NUM_LIMBS * 35;
+
+/// Transpiles each instruction of the F and D extensions into `JALR` to the trap handler, linking
+/// the next instruction in a hidden register, and the custom instructions with which the guest
+/// registers the handler and returns from it. The VM has no floating-point instructions, so the
+/// guest must link the runtime of `openvm-softfloat-guest`, e.g. with the `softfloat` feature of
+/// `openvm`.
+///
+/// The handler must not execute floating-point instructions, since traps do not nest. The
+/// compressed floating-point loads and stores and the instructions accessing the `fcsr` register
+/// are not supported.
+#[derive(Default)]
+pub struct SoftfloatTranspilerExtension;
+
+impl<F: PrimeField32> TranspilerExtension<F> for SoftfloatTranspilerExtension {
+    fn process_custom(&self, instruction_stream: &[u32]) -> Option<TranspilerOutput<F>> {
+        let instruction_u32 = *instruction_stream.first()?;
+        let opcode = (instruction_u32 & 0x7f) as u8;
+        let funct3 = ((instruction_u32 >> 12) & 0b111) as u8;
+
+        let instruction = if (opcode, funct3) == (OPCODE, SOFTFLOAT_FUNCT3) {
+            let dec_insn = RType::new(instruction_u32);
+            match dec_insn.funct7 as u8 {
+                SET_TRAP_VECTOR_FUNCT7 => Instruction::from_isize(
+                    BaseAluOpcode::ADD.global_opcode(),
+                    TRAP_VECTOR as isize,
+                    (RV32_REGISTER_NUM_LIMBS * dec_insn.rs1) as isize,
+                    0,
+                    1,
+                    0,
+                ),
+                TRAP_RETURN_ADDRESS_FUNCT7 if dec_insn.rd == 0 => nop(),
+                TRAP_RETURN_ADDRESS_FUNCT7 => Instruction::from_isize(
+                    BaseAluOpcode::ADD.global_opcode(),
+                    (RV32_REGISTER_NUM_LIMBS * dec_insn.rd) as isize,
+                    TRAP_RETURN_ADDRESS as isize,
+                    0,
+                    1,
+                    0,
+                ),
+                TRAP_RETURN_FUNCT7 => Instruction::large_from_isize(
+                    Rv32JalrOpcode::JALR.global_opcode(),
+                    0,
+                    TRAP_RETURN_ADDRESS as isize,
+                    0,
+                    1,
+                    0,
+                    0,
+                    0,
+                ),
+                _ => return None,
+            }
+        } else {
+            decode(instruction_u32)?;
+            Instruction::large_from_isize(
+                Rv32JalrOpcode::JALR.global_opcode(),
+                TRAP_RETURN_ADDRESS as isize,
+                TRAP_VECTOR as isize,
+                0,
+                1,
+                0,
+                1,
+                0,
+            )
+        };
+        Some(TranspilerOutput::one_to_one(instruction))
+    }
+
+    fn dispatch(&self) -> Vec<InstructionDispatch> {
+        let mut dispatch = vec![InstructionDispatch::new(
+            OPCODE,
+            SOFTFLOAT_FUNCT3,
+            SET_TRAP_VECTOR_FUNCT7..=TRAP_RETURN_FUNCT7,
+        )];
+        dispatch.extend(FP_OPCODES.into_iter().flat_map(|opcode| {
+            (0..8).map(move |funct3| InstructionDispatch::all_funct7(opcode, funct3))
+        }));
+        dispatch
+    }
+}
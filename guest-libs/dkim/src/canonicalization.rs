@@ -0,0 +1,94 @@
+//! The canonicalization algorithms of header fields and bodies, see
+//! [RFC 6376 Section 3.4](https://datatracker.ietf.org/doc/html/rfc6376#section-3.4).
+
+use alloc::vec::Vec;
+
+use crate::message::{is_whitespace, trim_whitespace};
+
+/// A canonicalization algorithm, which a signer picks separately for the header fields and for
+/// the body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Canonicalization {
+    /// Tolerates no change.
+    Simple,
+    /// Tolerates changes of whitespace and of the case of header field names.
+    Relaxed,
+}
+
+/// Appends the canonical form of the header field `raw`, whose name ends at `colon`, to `out`.
+pub(crate) fn canonicalize_header(
+    canonicalization: Canonicalization,
+    raw: &[u8],
+    colon: usize,
+    out: &mut Vec<u8>,
+) {
+    match canonicalization {
+        Canonicalization::Simple => out.extend_from_slice(raw),
+        Canonicalization::Relaxed => {
+            let name = trim_whitespace(&raw[..colon]);
+            out.extend(name.iter().map(u8::to_ascii_lowercase));
+            out.push(b':');
+            // Unfolding removes the line breaks, and the sequences of whitespace are reduced.
+            let value = raw[colon + 1..]
+                .iter()
+                .copied()
+                .filter(|&c| c != b'\r' && c != b'\n');
+            push_reduced_whitespace(trim_whitespace(&value.collect::<Vec<_>>()), out);
+            out.extend_from_slice(b"\r\n");
+        }
+    }
+}
+
+/// Returns the canonical form of `body`.
+pub(crate) fn canonicalize_body(canonicalization: Canonicalization, body: &[u8]) -> Vec<u8> {
+    let mut out = match canonicalization {
+        Canonicalization::Simple => body.to_vec(),
+        Canonicalization::Relaxed => {
+            let mut out = Vec::with_capacity(body.len());
+            let mut lines = body.split(|&c| c == b'\n').peekable();
+            while let Some(line) = lines.next() {
+                let is_last = lines.peek().is_none();
+                let line = line
+                    .strip_suffix(b"\r")
+                    .filter(|_| !is_last)
+                    .unwrap_or(line);
+                if is_last && line.is_empty() {
+                    break;
+                }
+                let end = line
+                    .iter()
+                    .rposition(|&c| !is_whitespace(c))
+                    .map_or(0, |idx| idx + 1);
+                push_reduced_whitespace(&line[..end], &mut out);
+                out.extend_from_slice(b"\r\n");
+            }
+            out
+        }
+    };
+    // Empty lines at the end of the body are ignored, and the body ends with a line break.
+    while out.ends_with(b"\r\n\r\n") {
+        out.truncate(out.len() - 2);
+    }
+    match canonicalization {
+        Canonicalization::Simple if !out.ends_with(b"\r\n") => out.extend_from_slice(b"\r\n"),
+        Canonicalization::Relaxed if out == b"\r\n" => out.clear(),
+        _ => {}
+    }
+    out
+}
+
+/// Appends `bytes` to `out`, with every sequence of whitespace reduced to a single space.
+fn push_reduced_whitespace(bytes: &[u8], out: &mut Vec<u8>) {
+    let mut in_whitespace = false;
+    for &c in bytes {
+        if is_whitespace(c) {
+            if !in_whitespace {
+                out.push(b' ');
+            }
+            in_whitespace = true;
+        } else {
+            out.push(c);
+            in_whitespace = false;
+        }
+    }
+}
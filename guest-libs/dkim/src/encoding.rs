@@ -0,0 +1,65 @@
+//! The base64 (RFC 2045 Section 6.8) and quoted-printable (RFC 2045 Section 6.7) content transfer
+//! encodings.
+
+use alloc::vec::Vec;
+
+use crate::{Error, Result};
+
+/// Decodes base64 with the standard alphabet. Whitespace, e.g. line breaks and the folding
+/// whitespace of DKIM tags, is ignored, and the padding is optional.
+pub fn decode_base64(input: &[u8]) -> Result<Vec<u8>> {
+    let digits: Vec<u8> = input
+        .iter()
+        .copied()
+        .filter(|c| !matches!(c, b' ' | b'\t' | b'\r' | b'\n'))
+        .collect();
+    openvm::codec::decode_base64(&digits).map_err(|_| Error::Encoding)
+}
+
+/// Decodes quoted-printable. Line breaks are output as CRLF, soft line breaks are removed, and so
+/// is the whitespace at the end of lines, which may have been added in transport.
+pub fn decode_quoted_printable(input: &[u8]) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(input.len());
+    let mut lines = input.split(|&c| c == b'\n').peekable();
+    while let Some(line) = lines.next() {
+        let has_line_break = lines.peek().is_some();
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let line = trim_end_whitespace(line);
+        let (line, soft_break) = match line.strip_suffix(b"=") {
+            Some(line) => (line, true),
+            None => (line, false),
+        };
+        let mut bytes = line.iter();
+        while let Some(&c) = bytes.next() {
+            if c == b'=' {
+                let (Some(hi), Some(lo)) = (bytes.next(), bytes.next()) else {
+                    return Err(Error::Encoding);
+                };
+                out.push((hex_digit(*hi)? << 4) | hex_digit(*lo)?);
+            } else {
+                out.push(c);
+            }
+        }
+        if has_line_break && !soft_break {
+            out.extend_from_slice(b"\r\n");
+        }
+    }
+    Ok(out)
+}
+
+fn hex_digit(c: u8) -> Result<u8> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        _ => Err(Error::Encoding),
+    }
+}
+
+fn trim_end_whitespace(line: &[u8]) -> &[u8] {
+    let end = line
+        .iter()
+        .rposition(|&c| c != b' ' && c != b'\t')
+        .map_or(0, |idx| idx + 1);
+    &line[..end]
+}
@@ -0,0 +1,91 @@
+//! DKIM keys and their DNS records, see
+//! [RFC 6376 Section 3.6.1](https://datatracker.ietf.org/doc/html/rfc6376#section-3.6.1).
+
+use alloc::string::String;
+
+use openvm_rsa_guest::RsaPublicKey;
+use x509_cert::{
+    der::{asn1::UintRef, oid::db::rfc5912::RSA_ENCRYPTION, Decode, Reader, SliceReader},
+    spki::SubjectPublicKeyInfoRef,
+};
+
+use crate::{decode_base64, message::trim_whitespace, signature::tag_list, Error, Result};
+
+/// The public key a domain signs emails with under a selector, published in DNS as a TXT record
+/// at `<selector>._domainkey.<domain>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DomainKey {
+    pub domain: String,
+    pub selector: String,
+    pub key: RsaPublicKey,
+}
+
+impl DomainKey {
+    pub fn new(domain: &str, selector: &str, key: RsaPublicKey) -> Self {
+        Self {
+            domain: domain.into(),
+            selector: selector.into(),
+            key,
+        }
+    }
+
+    /// Parses the DKIM key record of `domain` and `selector`, e.g.
+    /// `v=DKIM1; k=rsa; p=MIIBIjANBgkqhkiG9w0BAQEFAAOCAQ8AMIIBCgKCAQEA...`.
+    pub fn from_dns_record(domain: &str, selector: &str, record: &str) -> Result<Self> {
+        let tags = tag_list(record.as_bytes()).ok_or(Error::InvalidKey)?;
+        let tag = |name: &str| {
+            tags.iter()
+                .find(|tag| tag.name == name)
+                .map(|tag| tag.value)
+        };
+        if tag("v").is_some_and(|version| version != b"DKIM1") {
+            return Err(Error::InvalidKey);
+        }
+        if tag("k").is_some_and(|key_type| key_type != b"rsa") {
+            return Err(Error::Unsupported);
+        }
+        if tag("h").is_some_and(|hashes| {
+            !hashes
+                .split(|&c| c == b':')
+                .any(|hash| trim_whitespace(hash) == b"sha256")
+        }) {
+            return Err(Error::Unsupported);
+        }
+        // An empty key has been revoked.
+        let der = decode_base64(tag("p").ok_or(Error::InvalidKey)?)?;
+        if der.is_empty() {
+            return Err(Error::InvalidKey);
+        }
+        Ok(Self::new(domain, selector, rsa_public_key(&der)?))
+    }
+}
+
+/// Parses a DER-encoded RSA public key, either as a SubjectPublicKeyInfo or as an RSAPublicKey.
+fn rsa_public_key(der: &[u8]) -> Result<RsaPublicKey> {
+    let key = match SubjectPublicKeyInfoRef::from_der(der) {
+        Ok(spki) => {
+            if spki.algorithm.oid != RSA_ENCRYPTION {
+                return Err(Error::Unsupported);
+            }
+            spki.subject_public_key
+                .as_bytes()
+                .ok_or(Error::InvalidKey)?
+        }
+        Err(_) => der,
+    };
+    // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+    let mut reader = SliceReader::new(key).map_err(|_| Error::InvalidKey)?;
+    let (n, e) = reader
+        .sequence(|reader| Ok((UintRef::decode(reader)?, UintRef::decode(reader)?)))
+        .and_then(|key| reader.finish(key))
+        .map_err(|_| Error::InvalidKey)?;
+    let e = e.as_bytes();
+    if e.len() > 4 {
+        return Err(Error::Unsupported);
+    }
+    let e = e.iter().fold(0, |e, &byte| (e << 8) | byte as u32);
+    RsaPublicKey::new(n.as_bytes(), e).map_err(|err| match err {
+        openvm_rsa_guest::Error::UnsupportedModulus => Error::Unsupported,
+        _ => Error::InvalidKey,
+    })
+}
@@ -0,0 +1,238 @@
+#![no_std]
+
+//! Verification of DKIM signatures (RFC 6376) of emails, for proving claims about the headers and
+//! the body of an email sent by a domain.
+//!
+//! [verify] finds a DKIM-Signature header field of the email made with one of the given
+//! [DomainKey]s, and:
+//! - checks the SHA-256 hash of the canonicalized body against the body hash of the signature;
+//! - verifies the RSASSA-PKCS1-v1_5 signature of the canonicalized signed header fields with
+//!   SHA-256.
+//!
+//! The verified [Email] holds the signed header fields and the body, from which the guest can
+//! extract its claims, e.g. the sender with [Email::from_address], or the body without its
+//! content transfer encoding with [Email::decoded_body].
+//!
+//! Only the `rsa-sha256` algorithm with RSA keys of 2048, 3072 or 4096 bits is supported, which
+//! excludes the 1024-bit keys some domains still use. Signatures of a prefix of the body, with the
+//! `l=` tag, are rejected, as anything could be appended to the body.
+//!
+//! Note that the keys are inputs of the guest: a proof only attests to an email sent by a domain
+//! if the key it was verified with is the key the domain publishes in DNS, so the guest should
+//! reveal the key, or a hash of it, along with its claims.
+//!
+//! In the zkVM, the library uses the SHA-256 and RSA intrinsics, so the VM must be configured with
+//! the SHA-256 and RSA extensions.
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use canonicalization::{canonicalize_body, canonicalize_header};
+use message::{trim_whitespace, Message};
+use openvm_rsa_guest::{HashAlgorithm, RsaPublicKey};
+use openvm_sha2::sha256;
+use signature::{canonicalize_signature_header, DkimSignature, DKIM_SIGNATURE};
+
+mod canonicalization;
+mod encoding;
+mod key;
+mod message;
+mod signature;
+
+pub use encoding::*;
+pub use key::*;
+
+/// Errors returned when verifying an email.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The header section of the email is malformed, or a signed header field is not UTF-8.
+    Decode,
+    /// A base64 or quoted-printable encoding is malformed.
+    Encoding,
+    /// The email has several From header fields, which could show a different sender than the
+    /// signed one.
+    MultipleFrom,
+    /// The email has no DKIM-Signature header field.
+    NoSignature,
+    /// A DKIM-Signature header field is malformed or lacks a required tag.
+    InvalidSignature,
+    /// A DKIM key record is malformed, or its key was revoked.
+    InvalidKey,
+    /// The signature or the key uses a version, algorithm, canonicalization or tag which is not
+    /// supported.
+    Unsupported,
+    /// None of the keys is the key of the domain and selector of a signature.
+    UnknownKey,
+    /// The hash of the body does not match the body hash of the signature.
+    BodyHashMismatch,
+    /// The signature of the header fields is invalid.
+    BadSignature,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Decode => write!(f, "malformed email"),
+            Error::Encoding => write!(f, "malformed base64 or quoted-printable encoding"),
+            Error::MultipleFrom => write!(f, "email has several From header fields"),
+            Error::NoSignature => write!(f, "email has no DKIM signature"),
+            Error::InvalidSignature => write!(f, "malformed DKIM signature"),
+            Error::InvalidKey => write!(f, "malformed or revoked DKIM key"),
+            Error::Unsupported => write!(f, "unsupported DKIM signature or key"),
+            Error::UnknownKey => write!(f, "no key for the domain and selector of the signature"),
+            Error::BodyHashMismatch => write!(f, "body hash mismatch"),
+            Error::BadSignature => write!(f, "invalid signature"),
+        }
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Verifies a DKIM signature of `email`, whose lines end with CRLF as when it is transmitted, with
+/// the key of its domain and selector among `keys`. If the email has several signatures, the
+/// first valid one is used, and otherwise the error of the last one is returned.
+pub fn verify(email: &[u8], keys: &[DomainKey]) -> Result<Email> {
+    let message = Message::parse(email)?;
+    if message
+        .headers
+        .iter()
+        .filter(|field| field.is("from"))
+        .count()
+        > 1
+    {
+        return Err(Error::MultipleFrom);
+    }
+    let mut result = Err(Error::NoSignature);
+    for field in message
+        .headers
+        .iter()
+        .filter(|field| field.is(DKIM_SIGNATURE))
+    {
+        result = verify_signature(&message, field, keys);
+        if result.is_ok() {
+            break;
+        }
+    }
+    result
+}
+
+fn verify_signature(
+    message: &Message,
+    field: &message::HeaderField,
+    keys: &[DomainKey],
+) -> Result<Email> {
+    let signature = DkimSignature::parse(field)?;
+    let key = keys
+        .iter()
+        .find(|key| {
+            key.domain.eq_ignore_ascii_case(&signature.domain)
+                && key.selector.eq_ignore_ascii_case(&signature.selector)
+        })
+        .ok_or(Error::UnknownKey)?;
+
+    let body = canonicalize_body(signature.body_canonicalization, message.body);
+    if sha256(&body)[..] != signature.body_hash[..] {
+        return Err(Error::BodyHashMismatch);
+    }
+
+    // Every name of the signed header fields selects the last instance of the field not selected
+    // yet. Names without such an instance sign the absence of the field.
+    let mut selected = alloc::vec![false; message.headers.len()];
+    let mut data = Vec::new();
+    let mut headers = Vec::new();
+    for name in &signature.signed_headers {
+        let Some(idx) = (0..message.headers.len())
+            .rev()
+            .find(|&idx| !selected[idx] && message.headers[idx].is(name))
+        else {
+            continue;
+        };
+        selected[idx] = true;
+        let header = &message.headers[idx];
+        canonicalize_header(
+            signature.header_canonicalization,
+            header.raw,
+            header.colon,
+            &mut data,
+        );
+        let value: Vec<u8> = header
+            .value()
+            .iter()
+            .copied()
+            .filter(|&c| c != b'\r' && c != b'\n')
+            .collect();
+        let value = core::str::from_utf8(trim_whitespace(&value)).map_err(|_| Error::Decode)?;
+        headers.push((name.clone(), String::from(value)));
+    }
+    canonicalize_signature_header(signature.header_canonicalization, field, &mut data)?;
+    key.key
+        .verify_pkcs1v15(HashAlgorithm::Sha256, &sha256(&data), &signature.signature)
+        .map_err(|_| Error::BadSignature)?;
+
+    Ok(Email {
+        domain: signature.domain,
+        selector: signature.selector,
+        key: key.key.clone(),
+        headers,
+        body: message.body.to_vec(),
+        timestamp: signature.timestamp,
+        expiration: signature.expiration,
+    })
+}
+
+/// An email with a verified DKIM signature.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Email {
+    /// The signing domain, from the `d=` tag of the signature.
+    pub domain: String,
+    /// The selector of the key, from the `s=` tag of the signature.
+    pub selector: String,
+    /// The key the signature was verified with.
+    pub key: RsaPublicKey,
+    /// The signed header fields as `(name, value)`, with lowercase names and unfolded values
+    /// without surrounding whitespace, in the order of the `h=` tag of the signature. Fields of
+    /// the email which are not signed are left out.
+    pub headers: Vec<(String, String)>,
+    /// The body of the email, as it was sent.
+    pub body: Vec<u8>,
+    /// When the signature was made, in seconds since the Unix epoch, from the `t=` tag.
+    pub timestamp: Option<u64>,
+    /// When the signature expires, in seconds since the Unix epoch, from the `x=` tag.
+    pub expiration: Option<u64>,
+}
+
+impl Email {
+    /// The value of the signed header field `name`. If several instances of the field are signed,
+    /// this is the last one of the email.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(field, _)| field.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The address of the sender in the From header field, e.g. `alice@example.com` for
+    /// `Alice <alice@example.com>`.
+    pub fn from_address(&self) -> Option<&str> {
+        let from = self.header("from")?;
+        let address = match from.rfind('<') {
+            Some(start) => from[start + 1..].strip_suffix('>')?,
+            None => from,
+        };
+        Some(address.trim())
+    }
+
+    /// The body without the content transfer encoding of the signed Content-Transfer-Encoding
+    /// header field, if it is `base64` or `quoted-printable`. The parts of multipart bodies are
+    /// not decoded.
+    pub fn decoded_body(&self) -> Result<Vec<u8>> {
+        match self.header("content-transfer-encoding") {
+            Some(encoding) if encoding.eq_ignore_ascii_case("base64") => decode_base64(&self.body),
+            Some(encoding) if encoding.eq_ignore_ascii_case("quoted-printable") => {
+                decode_quoted_printable(&self.body)
+            }
+            _ => Ok(self.body.clone()),
+        }
+    }
+}
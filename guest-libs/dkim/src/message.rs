@@ -0,0 +1,94 @@
+//! Parsing of the header section and body of an email (RFC 5322).
+
+use alloc::vec::Vec;
+
+use crate::{Error, Result};
+
+/// A header field of an email.
+pub(crate) struct HeaderField<'a> {
+    /// The field as it appears in the email, from its name to the CRLF ending its last line.
+    pub raw: &'a [u8],
+    /// The index of the colon after the name in `raw`.
+    pub colon: usize,
+}
+
+impl<'a> HeaderField<'a> {
+    /// The name of the field, without the whitespace before the colon.
+    pub fn name(&self) -> &'a [u8] {
+        trim_whitespace(&self.raw[..self.colon])
+    }
+
+    /// Whether the name of the field is `name`, ignoring case.
+    pub fn is(&self, name: &str) -> bool {
+        self.name().eq_ignore_ascii_case(name.as_bytes())
+    }
+
+    /// The value of the field, from after the colon to before the final CRLF.
+    pub fn value(&self) -> &'a [u8] {
+        &self.raw[self.colon + 1..self.raw.len() - 2]
+    }
+}
+
+/// An email, split into its header fields and its body.
+pub(crate) struct Message<'a> {
+    pub headers: Vec<HeaderField<'a>>,
+    pub body: &'a [u8],
+}
+
+impl<'a> Message<'a> {
+    /// Splits `email`, whose lines end with CRLF, into its header fields and its body.
+    pub fn parse(email: &'a [u8]) -> Result<Self> {
+        let mut headers = Vec::new();
+        let mut pos = 0;
+        let body = loop {
+            let rest = &email[pos..];
+            if rest.is_empty() {
+                break rest;
+            }
+            if let Some(body) = rest.strip_prefix(b"\r\n") {
+                break body;
+            }
+            if is_whitespace(rest[0]) {
+                return Err(Error::Decode);
+            }
+            // A field continues on the following lines starting with whitespace.
+            let start = pos;
+            loop {
+                pos += find_crlf(&email[pos..]).ok_or(Error::Decode)? + 2;
+                if !email.get(pos).copied().is_some_and(is_whitespace) {
+                    break;
+                }
+            }
+            let raw = &email[start..pos];
+            let colon = raw.iter().position(|&c| c == b':').ok_or(Error::Decode)?;
+            let field = HeaderField { raw, colon };
+            if field.name().is_empty() || field.name().iter().any(|&c| c <= b' ') {
+                return Err(Error::Decode);
+            }
+            headers.push(field);
+        };
+        Ok(Self { headers, body })
+    }
+}
+
+pub(crate) fn is_whitespace(c: u8) -> bool {
+    c == b' ' || c == b'\t'
+}
+
+/// Removes the leading and trailing whitespace of `bytes`, including line breaks.
+pub(crate) fn trim_whitespace(bytes: &[u8]) -> &[u8] {
+    let is_space = |c: &u8| matches!(c, b' ' | b'\t' | b'\r' | b'\n');
+    let start = bytes
+        .iter()
+        .position(|c| !is_space(c))
+        .unwrap_or(bytes.len());
+    let end = bytes
+        .iter()
+        .rposition(|c| !is_space(c))
+        .map_or(start, |idx| idx + 1);
+    &bytes[start..end]
+}
+
+fn find_crlf(bytes: &[u8]) -> Option<usize> {
+    bytes.windows(2).position(|window| window == b"\r\n")
+}
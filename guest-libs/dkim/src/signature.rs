@@ -0,0 +1,186 @@
+//! Parsing of DKIM-Signature header fields, see
+//! [RFC 6376 Section 3.5](https://datatracker.ietf.org/doc/html/rfc6376#section-3.5).
+
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use crate::{
+    canonicalization::{canonicalize_header, Canonicalization},
+    decode_base64,
+    message::{trim_whitespace, HeaderField},
+    Error, Result,
+};
+
+/// A tag of a tag list, e.g. `d=example.com`.
+pub(crate) struct Tag<'a> {
+    pub name: &'a str,
+    /// The value without surrounding whitespace.
+    pub value: &'a [u8],
+    /// The range of the value in the tag list, including surrounding whitespace.
+    pub span: Range<usize>,
+}
+
+/// Parses a tag list (RFC 6376 Section 3.2), such as the value of a DKIM-Signature header field or
+/// a DKIM key record. Tags may not be repeated.
+pub(crate) fn tag_list(list: &[u8]) -> Option<Vec<Tag<'_>>> {
+    let mut tags: Vec<Tag> = Vec::new();
+    let mut start = 0;
+    for spec in list.split(|&c| c == b';') {
+        let span_end = start + spec.len();
+        if !trim_whitespace(spec).is_empty() {
+            let eq = spec.iter().position(|&c| c == b'=')?;
+            let name = core::str::from_utf8(trim_whitespace(&spec[..eq])).ok()?;
+            let valid_name = name.bytes().next().is_some_and(|c| c.is_ascii_alphabetic())
+                && name.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_');
+            if !valid_name || tags.iter().any(|tag| tag.name == name) {
+                return None;
+            }
+            tags.push(Tag {
+                name,
+                value: trim_whitespace(&spec[eq + 1..]),
+                span: start + eq + 1..span_end,
+            });
+        }
+        start = span_end + 1;
+    }
+    Some(tags)
+}
+
+/// The name of the header field holding DKIM signatures.
+pub(crate) const DKIM_SIGNATURE: &str = "DKIM-Signature";
+
+/// A parsed DKIM-Signature header field.
+pub(crate) struct DkimSignature {
+    pub domain: String,
+    pub selector: String,
+    pub header_canonicalization: Canonicalization,
+    pub body_canonicalization: Canonicalization,
+    /// The names of the signed header fields, lowercase, in the order of the `h=` tag.
+    pub signed_headers: Vec<String>,
+    pub body_hash: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub timestamp: Option<u64>,
+    pub expiration: Option<u64>,
+}
+
+impl DkimSignature {
+    pub fn parse(field: &HeaderField) -> Result<Self> {
+        let tags = tag_list(field.value()).ok_or(Error::InvalidSignature)?;
+        let tag = |name: &str| {
+            tags.iter()
+                .find(|tag| tag.name == name)
+                .map(|tag| tag.value)
+        };
+        let required = |name: &str| tag(name).ok_or(Error::InvalidSignature);
+        let text = |value: &[u8]| {
+            core::str::from_utf8(value)
+                .map(String::from)
+                .map_err(|_| Error::InvalidSignature)
+        };
+        let number = |value: Option<&[u8]>| {
+            value
+                .map(|value| {
+                    text(value)?
+                        .parse::<u64>()
+                        .map_err(|_| Error::InvalidSignature)
+                })
+                .transpose()
+        };
+
+        if required("v")? != b"1" {
+            return Err(Error::Unsupported);
+        }
+        if required("a")? != b"rsa-sha256" {
+            return Err(Error::Unsupported);
+        }
+        // Signatures of a prefix of the body leave the rest of the body unsigned.
+        if tag("l").is_some() {
+            return Err(Error::Unsupported);
+        }
+        let (header_canonicalization, body_canonicalization) = match tag("c")
+            .unwrap_or(b"simple".as_slice())
+        {
+            b"simple" | b"simple/simple" => (Canonicalization::Simple, Canonicalization::Simple),
+            b"simple/relaxed" => (Canonicalization::Simple, Canonicalization::Relaxed),
+            b"relaxed" | b"relaxed/simple" => (Canonicalization::Relaxed, Canonicalization::Simple),
+            b"relaxed/relaxed" => (Canonicalization::Relaxed, Canonicalization::Relaxed),
+            _ => return Err(Error::Unsupported),
+        };
+
+        let domain = text(required("d")?)?;
+        let selector = text(required("s")?)?;
+        if domain.is_empty() || selector.is_empty() {
+            return Err(Error::InvalidSignature);
+        }
+        // The agent or user identifier must be in the signing domain or one of its subdomains.
+        if let Some(identity) = tag("i") {
+            let identity = text(identity)?;
+            let (_, identity_domain) = identity.rsplit_once('@').ok_or(Error::InvalidSignature)?;
+            let in_domain = identity_domain.eq_ignore_ascii_case(&domain)
+                || identity_domain
+                    .len()
+                    .checked_sub(domain.len() + 1)
+                    .is_some_and(|dot| {
+                        identity_domain.as_bytes()[dot] == b'.'
+                            && identity_domain[dot + 1..].eq_ignore_ascii_case(&domain)
+                    });
+            if !in_domain {
+                return Err(Error::InvalidSignature);
+            }
+        }
+
+        let signed_headers = text(required("h")?)?
+            .split(':')
+            .map(|name| {
+                let name = name.trim_matches(|c| matches!(c, ' ' | '\t' | '\r' | '\n'));
+                if name.is_empty() {
+                    return Err(Error::InvalidSignature);
+                }
+                Ok(name.to_ascii_lowercase())
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !signed_headers.iter().any(|name| name == "from") {
+            return Err(Error::InvalidSignature);
+        }
+
+        let timestamp = number(tag("t"))?;
+        let expiration = number(tag("x"))?;
+        if let (Some(timestamp), Some(expiration)) = (timestamp, expiration) {
+            if expiration < timestamp {
+                return Err(Error::InvalidSignature);
+            }
+        }
+
+        Ok(Self {
+            domain,
+            selector,
+            header_canonicalization,
+            body_canonicalization,
+            signed_headers,
+            body_hash: decode_base64(required("bh")?).map_err(|_| Error::InvalidSignature)?,
+            signature: decode_base64(required("b")?).map_err(|_| Error::InvalidSignature)?,
+            timestamp,
+            expiration,
+        })
+    }
+}
+
+/// Appends the canonical form of the DKIM-Signature header field `field` to `out`, with the value
+/// of its `b=` tag removed and without the final CRLF, as it is signed.
+pub(crate) fn canonicalize_signature_header(
+    canonicalization: Canonicalization,
+    field: &HeaderField,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    let tags = tag_list(field.value()).ok_or(Error::InvalidSignature)?;
+    let b = tags
+        .iter()
+        .find(|tag| tag.name == "b")
+        .ok_or(Error::InvalidSignature)?;
+    let offset = field.colon + 1;
+    let mut raw = field.raw[..offset + b.span.start].to_vec();
+    raw.extend_from_slice(&field.raw[offset + b.span.end..]);
+    canonicalize_header(canonicalization, &raw, field.colon, out);
+    out.truncate(out.len() - 2);
+    Ok(())
+}
@@ -0,0 +1,197 @@
+/// An email signed by `programs/examples/test_vectors/generate.py`, with the DKIM key record of
+/// its domain.
+struct TestVector {
+    email: &'static [u8],
+    record: &'static str,
+}
+
+macro_rules! test_vector {
+    ($kind:literal) => {
+        TestVector {
+            email: include_bytes!(concat!(
+                "programs/examples/test_vectors/",
+                $kind,
+                "/email.eml"
+            )),
+            record: include_str!(concat!(
+                "programs/examples/test_vectors/",
+                $kind,
+                "/record.txt"
+            )),
+        }
+    };
+}
+
+/// An email signed with the `relaxed/relaxed` canonicalization.
+const RELAXED: TestVector = test_vector!("relaxed");
+/// An email signed with the `simple/simple` canonicalization.
+const SIMPLE: TestVector = test_vector!("simple");
+
+const DOMAIN: &str = "openvm.test";
+const SELECTOR: &str = "test";
+
+mod guest_tests {
+    use eyre::Result;
+    use openvm_circuit::{arch::instructions::exe::VmExe, utils::air_test_with_min_segments};
+    use openvm_sdk::{
+        config::{AppConfig, SdkVmConfig},
+        StdIn,
+    };
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::FromElf;
+
+    use super::{TestVector, RELAXED, SIMPLE};
+
+    fn test_verify(vector: &TestVector) -> Result<()> {
+        let config =
+            toml::from_str::<AppConfig<SdkVmConfig>>(include_str!("programs/openvm_dkim.toml"))?
+                .app_vm_config;
+        let elf =
+            build_example_program_at_path(get_programs_dir!("tests/programs"), "verify", &config)?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+
+        let mut stdin = StdIn::default();
+        stdin.write_bytes(vector.record.as_bytes());
+        stdin.write_bytes(vector.email);
+        air_test_with_min_segments(config, openvm_exe, stdin, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_relaxed() -> Result<()> {
+        test_verify(&RELAXED)
+    }
+
+    #[test]
+    fn test_verify_simple() -> Result<()> {
+        test_verify(&SIMPLE)
+    }
+}
+
+mod host_tests {
+    use openvm_dkim::{decode_base64, decode_quoted_printable, verify, DomainKey, Email, Error};
+
+    use super::{TestVector, DOMAIN, RELAXED, SELECTOR, SIMPLE};
+
+    fn key(vector: &TestVector) -> DomainKey {
+        DomainKey::from_dns_record(DOMAIN, SELECTOR, vector.record).unwrap()
+    }
+
+    fn verify_with(vector: &TestVector, email: &[u8]) -> Result<Email, Error> {
+        verify(email, &[key(vector)])
+    }
+
+    /// Replaces the first occurrence of `from` in `email` with `to`.
+    fn replace(email: &[u8], from: &[u8], to: &[u8]) -> Vec<u8> {
+        let idx = email
+            .windows(from.len())
+            .position(|window| window == from)
+            .unwrap();
+        [&email[..idx], to, &email[idx + from.len()..]].concat()
+    }
+
+    #[test]
+    fn test_verify() {
+        for vector in [&RELAXED, &SIMPLE] {
+            let email = verify_with(vector, vector.email).unwrap();
+            assert_eq!(email.domain, DOMAIN);
+            assert_eq!(email.selector, SELECTOR);
+            assert_eq!(email.key, key(vector).key);
+            assert_eq!(email.from_address(), Some("alice@openvm.test"));
+            assert_eq!(email.header("To"), Some("Bob <bob@example.com>"));
+            assert_eq!(email.header("subject"), Some("Your balance is ready"));
+            // The Received header field is not signed.
+            assert_eq!(email.header("received"), None);
+            assert_eq!(email.timestamp, Some(1_767_225_600));
+            assert_eq!(email.expiration, Some(1_767_830_400));
+
+            let body = email.decoded_body().unwrap();
+            let body = core::str::from_utf8(&body).unwrap();
+            assert!(body.starts_with("Hi Bob,\r\n\r\n"));
+            assert!(body.contains(
+                "Your balance is 1234 €. This line is long enough to need a soft line break.\r\n"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_canonicalization() {
+        // The relaxed canonicalization tolerates changes of whitespace and of the case of header
+        // field names, and the simple one does not.
+        let email = replace(RELAXED.email, b"Subject: Your", b"SUBJECT :  Your");
+        let email = replace(&email, b"Alice\r\n", b"Alice \t\r\n\r\n");
+        assert!(verify_with(&RELAXED, &email).is_ok());
+
+        let email = replace(SIMPLE.email, b"Subject: Your", b"SUBJECT :  Your");
+        assert_eq!(verify_with(&SIMPLE, &email), Err(Error::BadSignature));
+        let email = replace(SIMPLE.email, b"Alice\r\n", b"Alice \t\r\n");
+        assert_eq!(verify_with(&SIMPLE, &email), Err(Error::BodyHashMismatch));
+        // Empty lines at the end of the body are ignored.
+        let email = [SIMPLE.email, b"\r\n\r\n"].concat();
+        assert!(verify_with(&SIMPLE, &email).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_email() {
+        for vector in [&RELAXED, &SIMPLE] {
+            let email = replace(vector.email, b"balance is 1234", b"balance is 9234");
+            assert_eq!(verify_with(vector, &email), Err(Error::BodyHashMismatch));
+            let email = replace(vector.email, b"To: Bob", b"To: Eve");
+            assert_eq!(verify_with(vector, &email), Err(Error::BadSignature));
+            // Unsigned header fields may change.
+            let email = replace(vector.email, b"by mx.example.com", b"by mx.example.org");
+            assert!(verify_with(vector, &email).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_multiple_from() {
+        // A second From field could be shown instead of the signed one.
+        let email = [b"From: mallory@example.com\r\n".as_slice(), RELAXED.email].concat();
+        assert_eq!(verify_with(&RELAXED, &email), Err(Error::MultipleFrom));
+    }
+
+    #[test]
+    fn test_unsupported_signature() {
+        // Signatures of a prefix of the body are rejected.
+        let email = replace(RELAXED.email, b"v=1; ", b"v=1; l=10; ");
+        assert_eq!(verify_with(&RELAXED, &email), Err(Error::Unsupported));
+        let email = replace(RELAXED.email, b"a=rsa-sha256", b"a=rsa-sha1");
+        assert_eq!(verify_with(&RELAXED, &email), Err(Error::Unsupported));
+        let email = replace(RELAXED.email, b"DKIM-Signature", b"X-Signature");
+        assert_eq!(verify_with(&RELAXED, &email), Err(Error::NoSignature));
+    }
+
+    #[test]
+    fn test_keys() {
+        let other_selector = DomainKey::new(DOMAIN, "other", key(&RELAXED).key);
+        assert_eq!(
+            verify(RELAXED.email, &[other_selector]),
+            Err(Error::UnknownKey)
+        );
+        assert!(DomainKey::from_dns_record(DOMAIN, SELECTOR, "v=DKIM1; p=")
+            .is_err_and(|err| err == Error::InvalidKey));
+        let record = RELAXED.record.replace("k=rsa", "k=ed25519");
+        assert!(DomainKey::from_dns_record(DOMAIN, SELECTOR, &record)
+            .is_err_and(|err| err == Error::Unsupported));
+    }
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(decode_base64(b"T3Blbl\r\n ZN").unwrap(), b"OpenVM");
+        assert_eq!(decode_base64(b"T3BlblZNIQ==").unwrap(), b"OpenVM!");
+        assert_eq!(decode_base64(b"T3BlblZNIQ=").unwrap_err(), Error::Encoding);
+        assert_eq!(
+            decode_quoted_printable(b"caf=C3=A9 =\r\nau lait  \r\n").unwrap(),
+            "café au lait\r\n".as_bytes()
+        );
+        assert_eq!(
+            decode_quoted_printable(b"=E2=82").unwrap(),
+            [0xe2, 0x82].as_slice()
+        );
+        assert_eq!(
+            decode_quoted_printable(b"=G0").unwrap_err(),
+            Error::Encoding
+        );
+    }
+}
@@ -0,0 +1,149 @@
+"""Generates the DKIM-signed emails of the tests of openvm-dkim.
+
+    python3 generate.py
+
+writes `{relaxed,simple}/email.eml` with an email signed by `openvm.test` with the selector
+`test`, and `{relaxed,simple}/record.txt` with the DKIM key record of the domain. The emails are
+signed with the `relaxed/relaxed` and `simple/simple` canonicalizations respectively. Requires
+the `cryptography` package.
+"""
+
+import base64
+import hashlib
+import os
+import re
+
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import padding, rsa
+
+DOMAIN = "openvm.test"
+SELECTOR = "test"
+# 2026-01-01T00:00:00Z, and a week later.
+TIMESTAMP = 1_767_225_600
+EXPIRATION = TIMESTAMP + 7 * 24 * 3600
+
+HEADERS = [
+    b"Received: from mail.openvm.test (mail.openvm.test [192.0.2.1])\r\n"
+    b"\tby mx.example.com with ESMTPS;\r\n"
+    b"\tThu, 01 Jan 2026 00:00:00 +0000",
+    b"From: Alice  <alice@openvm.test>",
+    b"To: Bob <bob@example.com>",
+    b"Subject: Your balance\r\n is ready  ",
+    b"Date: Thu, 01 Jan 2026 00:00:00 +0000",
+    b"Message-ID: <balance-1234@openvm.test>",
+    b"MIME-Version: 1.0",
+    b"Content-Type: text/plain; charset=utf-8",
+    b"Content-Transfer-Encoding: quoted-printable",
+]
+SIGNED_HEADERS = [
+    "from",
+    "to",
+    "subject",
+    "date",
+    "message-id",
+    "mime-version",
+    "content-type",
+    "content-transfer-encoding",
+]
+BODY = (
+    b"Hi Bob,  \r\n"
+    b"\r\n"
+    b"Your balance is 1234 =E2=82=AC. This line is long enough to need a soft line=\r\n"
+    b" break.\r\n"
+    b"\r\n"
+    b"Alice\r\n"
+    b"\r\n"
+    b"\r\n"
+)
+
+
+def relaxed_header(field):
+    name, value = field.split(b":", 1)
+    value = re.sub(rb"[ \t]+", b" ", value.replace(b"\r\n", b"")).strip(b" \t")
+    return name.strip(b" \t").lower() + b":" + value + b"\r\n"
+
+
+def simple_header(field):
+    return field + b"\r\n"
+
+
+def relaxed_body(body):
+    lines = body.split(b"\r\n")
+    if lines[-1] == b"":
+        lines.pop()
+    lines = [re.sub(rb"[ \t]+", b" ", line).rstrip(b" ") for line in lines]
+    while lines and lines[-1] == b"":
+        lines.pop()
+    return b"".join(line + b"\r\n" for line in lines)
+
+
+def simple_body(body):
+    while body.endswith(b"\r\n\r\n"):
+        body = body[:-2]
+    if not body.endswith(b"\r\n"):
+        body += b"\r\n"
+    return body
+
+
+CANONICALIZATIONS = {
+    "relaxed": (relaxed_header, relaxed_body),
+    "simple": (simple_header, simple_body),
+}
+
+
+def header_name(field):
+    return field.split(b":", 1)[0].strip(b" \t").lower().decode()
+
+
+def sign(key, canonicalization):
+    canonicalize_header, canonicalize_body = CANONICALIZATIONS[canonicalization]
+    body_hash = base64.b64encode(hashlib.sha256(canonicalize_body(BODY)).digest())
+    signature_field = (
+        f"DKIM-Signature: v=1; a=rsa-sha256; c={canonicalization}/{canonicalization};\r\n"
+        f"\td={DOMAIN}; s={SELECTOR}; t={TIMESTAMP}; x={EXPIRATION};\r\n"
+        f"\th={':'.join(SIGNED_HEADERS)};\r\n"
+        f"\tbh={body_hash.decode()};\r\n"
+        "\tb="
+    ).encode()
+
+    # Each name selects the last unselected instance of the field.
+    data = b""
+    selected = set()
+    for name in SIGNED_HEADERS:
+        for idx in reversed(range(len(HEADERS))):
+            if idx not in selected and header_name(HEADERS[idx]) == name:
+                selected.add(idx)
+                data += canonicalize_header(HEADERS[idx])
+                break
+    data += canonicalize_header(signature_field)[:-2]
+    signature = base64.b64encode(key.sign(data, padding.PKCS1v15(), hashes.SHA256())).decode()
+    folded = "\r\n\t".join(signature[i : i + 64] for i in range(0, len(signature), 64))
+    return signature_field + folded.encode()
+
+
+def main():
+    # The RFC 6376 Appendix A.2 body hash checks the simple body canonicalization.
+    rfc_body = b"Hi.\r\n\r\nWe lost the game. Are you hungry yet?\r\n\r\nJoe.\r\n"
+    assert (
+        base64.b64encode(hashlib.sha256(simple_body(rfc_body)).digest())
+        == b"2jUSOH9NhtVGCQWNr9BrIAPreKQjO6Sn7XIkfJVOzv8="
+    )
+
+    key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    spki = key.public_key().public_bytes(
+        serialization.Encoding.DER, serialization.PublicFormat.SubjectPublicKeyInfo
+    )
+    record = f"v=DKIM1; k=rsa; p={base64.b64encode(spki).decode()}"
+    directory = os.path.dirname(os.path.abspath(__file__))
+    for canonicalization in CANONICALIZATIONS:
+        fields = [sign(key, canonicalization)] + HEADERS
+        email = b"".join(field + b"\r\n" for field in fields) + b"\r\n" + BODY
+        os.makedirs(os.path.join(directory, canonicalization), exist_ok=True)
+        with open(os.path.join(directory, canonicalization, "email.eml"), "wb") as f:
+            f.write(email)
+        with open(os.path.join(directory, canonicalization, "record.txt"), "w") as f:
+            f.write(record)
+
+
+if __name__ == "__main__":
+    main()
@@ -0,0 +1,36 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::string::String;
+
+use openvm::io::{read_vec, reveal_bytes32, reveal_u32};
+use openvm_dkim::{verify, DomainKey};
+use openvm_sha2::sha256;
+
+openvm::entry!(main);
+
+const DOMAIN: &str = "openvm.test";
+const SELECTOR: &str = "test";
+
+/// Verifies an email from `openvm.test` and checks the balance in its body. The hash of the key
+/// modulus is revealed, to be checked against the key the domain publishes in DNS, followed by
+/// the hash of the address of the sender.
+pub fn main() {
+    let record = String::from_utf8(read_vec()).unwrap();
+    let email = read_vec();
+
+    let key = DomainKey::from_dns_record(DOMAIN, SELECTOR, &record).unwrap();
+    let email = verify(&email, &[key]).unwrap();
+    assert_eq!(email.header("subject"), Some("Your balance is ready"));
+    let body = email.decoded_body().unwrap();
+    let balance = "balance is 1234 €".as_bytes();
+    assert!(body.windows(balance.len()).any(|window| window == balance));
+
+    reveal_bytes32(sha256(email.key.n()));
+    let sender = sha256(email.from_address().unwrap().as_bytes());
+    for (i, chunk) in sender.chunks_exact(4).enumerate() {
+        reveal_u32(u32::from_le_bytes(chunk.try_into().unwrap()), 8 + i);
+    }
+}
@@ -83,13 +83,10 @@ impl Field for Secp256k1Scalar {
     }
 
     fn invert(&self) -> CtOption<Self> {
-        // needs to be in canonical form for ct_eq
-        self.assert_reduced();
-        let is_zero = self.ct_eq(&<Self as IntMod>::ZERO);
-        CtOption::new(
-            <Secp256k1Scalar as openvm_algebra_guest::Field>::invert(self),
-            !is_zero,
-        )
+        match <Self as openvm_algebra_guest::Field>::invert(self) {
+            Some(inv) => CtOption::new(inv, 1.into()),
+            None => CtOption::new(<Self as IntMod>::ZERO, 0.into()),
+        }
     }
 
     #[allow(clippy::many_single_char_names)]
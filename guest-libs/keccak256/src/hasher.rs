@@ -0,0 +1,125 @@
+use digest::{
+    consts::U32, FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update,
+};
+use openvm_keccak256_guest::{keccakf, KECCAK_WIDTH_U64S};
+
+/// The number of bytes absorbed per permutation.
+pub const KECCAK256_RATE: usize = 136;
+
+/// Incremental keccak256 hasher.
+///
+/// Unlike [crate::keccak256], which hashes an entire message in one instruction, this keeps the
+/// sponge state in guest memory and permutes it once per [KECCAK256_RATE] bytes of input, so the
+/// message never has to be held in memory at once. It implements the `digest` traits, and hence
+/// `digest::Digest`.
+#[derive(Clone, Debug)]
+pub struct Keccak256 {
+    state: [u64; KECCAK_WIDTH_U64S],
+    buffer: [u8; KECCAK256_RATE],
+    buffer_len: usize,
+}
+
+impl Default for Keccak256 {
+    fn default() -> Self {
+        Self {
+            state: [0; KECCAK_WIDTH_U64S],
+            buffer: [0; KECCAK256_RATE],
+            buffer_len: 0,
+        }
+    }
+}
+
+impl Keccak256 {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `data` to the message being hashed.
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let mut data = data.as_ref();
+        if self.buffer_len > 0 {
+            let len = data.len().min(KECCAK256_RATE - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + len].copy_from_slice(&data[..len]);
+            self.buffer_len += len;
+            data = &data[len..];
+            if self.buffer_len < KECCAK256_RATE {
+                return;
+            }
+            let block = self.buffer;
+            self.absorb(&block);
+            self.buffer_len = 0;
+        }
+        let mut blocks = data.chunks_exact(KECCAK256_RATE);
+        for block in &mut blocks {
+            self.absorb(block.try_into().unwrap());
+        }
+        let remainder = blocks.remainder();
+        self.buffer[..remainder.len()].copy_from_slice(remainder);
+        self.buffer_len = remainder.len();
+    }
+
+    /// Appends `data` to the message being hashed, returning `self` for chaining.
+    pub fn chain_update(mut self, data: impl AsRef<[u8]>) -> Self {
+        self.update(data);
+        self
+    }
+
+    /// Returns the hash of all data passed to [`Self::update`].
+    pub fn finalize(mut self) -> [u8; 32] {
+        // Keccak pads with the 10*1 rule, the first and last padding bits being the low bit of the
+        // byte after the message and the high bit of the last byte of the block.
+        let mut block = [0u8; KECCAK256_RATE];
+        block[..self.buffer_len].copy_from_slice(&self.buffer[..self.buffer_len]);
+        block[self.buffer_len] ^= 0x01;
+        block[KECCAK256_RATE - 1] ^= 0x80;
+        self.absorb(&block);
+
+        let mut output = [0u8; 32];
+        for (bytes, lane) in output.chunks_exact_mut(8).zip(self.state) {
+            bytes.copy_from_slice(&lane.to_le_bytes());
+        }
+        output
+    }
+
+    /// Returns the hash of `data` in one shot.
+    pub fn digest(data: impl AsRef<[u8]>) -> [u8; 32] {
+        crate::keccak256(data.as_ref())
+    }
+
+    fn absorb(&mut self, block: &[u8; KECCAK256_RATE]) {
+        for (lane, bytes) in self.state.iter_mut().zip(block.chunks_exact(8)) {
+            *lane ^= u64::from_le_bytes(bytes.try_into().unwrap());
+        }
+        keccakf(&mut self.state);
+    }
+}
+
+impl HashMarker for Keccak256 {}
+
+impl OutputSizeUser for Keccak256 {
+    type OutputSize = U32;
+}
+
+impl Update for Keccak256 {
+    fn update(&mut self, data: &[u8]) {
+        Keccak256::update(self, data);
+    }
+}
+
+impl FixedOutput for Keccak256 {
+    fn finalize_into(self, out: &mut Output<Self>) {
+        out.copy_from_slice(&Keccak256::finalize(self));
+    }
+}
+
+impl Reset for Keccak256 {
+    fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+impl FixedOutputReset for Keccak256 {
+    fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+        out.copy_from_slice(&core::mem::take(self).finalize());
+    }
+}
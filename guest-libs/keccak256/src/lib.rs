@@ -3,6 +3,19 @@
 #[cfg(target_os = "zkvm")]
 use core::mem::MaybeUninit;
 
+mod hasher;
+pub use hasher::*;
+
+/// Inputs of up to this many bytes which are not 4-byte aligned are copied to an aligned buffer on
+/// the stack and hashed by a single instruction. Longer unaligned inputs are hashed with
+/// [Keccak256], one block at a time.
+///
+/// The keccak256 instruction reads its input in 4-byte words. A word read at an unaligned address
+/// straddles two words of memory, which the VM splits into bytes and merges back, at the cost of
+/// several rows of memory access adapters per word read. Copying is cheaper for short inputs,
+/// costing roughly one instruction per byte.
+pub const KECCAK256_BOUNCE_SIZE: usize = 512;
+
 /// The keccak256 cryptographic hash function.
 #[inline(always)]
 pub fn keccak256(input: &[u8]) -> [u8; 32] {
@@ -15,11 +28,7 @@ pub fn keccak256(input: &[u8]) -> [u8; 32] {
     #[cfg(target_os = "zkvm")]
     {
         let mut output = MaybeUninit::<[u8; 32]>::uninit();
-        openvm_keccak256_guest::native_keccak256(
-            input.as_ptr(),
-            input.len(),
-            output.as_mut_ptr() as *mut u8,
-        );
+        keccak256_into(input, output.as_mut_ptr() as *mut u8);
         unsafe { output.assume_init() }
     }
 }
@@ -34,9 +43,25 @@ pub fn set_keccak256(input: &[u8], output: &mut [u8; 32]) {
         hasher.finalize(output);
     }
     #[cfg(target_os = "zkvm")]
-    openvm_keccak256_guest::native_keccak256(
-        input.as_ptr(),
-        input.len(),
-        output.as_mut_ptr() as *mut u8,
-    );
+    keccak256_into(input, output.as_mut_ptr());
+}
+
+/// Writes the keccak256 hash of `input`, which may start at any address, to the 32 bytes at
+/// `output`. See [KECCAK256_BOUNCE_SIZE].
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+fn keccak256_into(input: &[u8], output: *mut u8) {
+    if input.as_ptr() as usize % 4 == 0 {
+        openvm_keccak256_guest::native_keccak256(input.as_ptr(), input.len(), output);
+    } else if input.len() <= KECCAK256_BOUNCE_SIZE {
+        let mut buffer = MaybeUninit::<[u32; KECCAK256_BOUNCE_SIZE / 4]>::uninit();
+        let ptr = buffer.as_mut_ptr() as *mut u8;
+        // SAFETY: the buffer holds at least `input.len()` bytes and does not overlap `input`.
+        unsafe { core::ptr::copy_nonoverlapping(input.as_ptr(), ptr, input.len()) };
+        openvm_keccak256_guest::native_keccak256(ptr, input.len(), output);
+    } else {
+        let digest = Keccak256::new().chain_update(input).finalize();
+        // SAFETY: `output` points to 32 writable bytes.
+        unsafe { core::ptr::copy_nonoverlapping(digest.as_ptr(), output, digest.len()) };
+    }
 }
@@ -0,0 +1,39 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use digest::Digest;
+use openvm_keccak256::{keccak256, Keccak256, KECCAK256_RATE};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Lengths around the rate, where the padding fills a block exactly or takes another block.
+    for len in [
+        0,
+        1,
+        KECCAK256_RATE - 1,
+        KECCAK256_RATE,
+        KECCAK256_RATE + 1,
+        3 * KECCAK256_RATE + 17,
+    ] {
+        let input: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+        let expected = keccak256(black_box(&input));
+
+        // Chunk sizes that do and do not divide the rate.
+        for chunk_size in [1, 7, KECCAK256_RATE, 200] {
+            let mut hasher = Keccak256::new();
+            for chunk in input.chunks(chunk_size) {
+                hasher.update(chunk);
+            }
+            assert_eq!(hasher.finalize(), expected);
+        }
+
+        let output = <Keccak256 as Digest>::digest(&input);
+        assert_eq!(output.as_slice(), &expected);
+    }
+}
@@ -0,0 +1,30 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use openvm_keccak256::{keccak256, KECCAK256_BOUNCE_SIZE};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Lengths which are copied to the stack and hashed block by block when unaligned.
+    for len in [
+        0,
+        1,
+        135,
+        136,
+        KECCAK256_BOUNCE_SIZE,
+        KECCAK256_BOUNCE_SIZE + 1,
+    ] {
+        let buffer: Vec<u8> = (0..len + 3).map(|i| (i % 251) as u8).collect();
+        for offset in 1..4 {
+            let unaligned = &buffer[offset..offset + len];
+            let aligned = unaligned.to_vec();
+            assert_eq!(keccak256(black_box(unaligned)), keccak256(&aligned));
+        }
+    }
+}
@@ -0,0 +1,24 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use hex::FromHex;
+use openvm::io::public_values_commit;
+use openvm_keccak256::keccak256;
+
+openvm::entry!(main);
+
+pub fn main() {
+    // The commitment of the SDK to 100 user public values 0, 1, ..., 99 with the Keccak256 public
+    // values hasher, which pads them to four leaves.
+    let public_values: Vec<u8> = (0..100).collect();
+    let expected =
+        Vec::from_hex("9d276227329b3577df22e0b356a29a1aec9c613abdd145409c2a54fd580a3534").unwrap();
+    if public_values_commit(black_box(&public_values), keccak256) != *expected {
+        panic!();
+    }
+}
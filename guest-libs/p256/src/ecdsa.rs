@@ -3,6 +3,8 @@
 // Use these types instead of unpatched p256::ecdsa::{Signature, VerifyingKey}
 // because those are type aliases that use non-zkvm implementations
 
+#[cfg(any(feature = "ecdsa", feature = "sha256"))]
+pub use ecdsa_core::hazmat;
 pub use ecdsa_core::signature::{self, Error};
 #[cfg(feature = "ecdsa")]
 use openvm_ecc_guest::ecdsa::VerifyCustomHook;
@@ -11,17 +13,24 @@ use {super::P256Point, ecdsa_core::hazmat::VerifyPrimitive};
 
 use super::NistP256;
 
-/// ECDSA/secp256k1 signature (fixed-size)
+/// ECDSA/P-256 signature (fixed-size)
 pub type Signature = ecdsa_core::Signature<NistP256>;
 
-/// ECDSA/secp256k1 signing key
+/// ECDSA/P-256 signing key
 #[cfg(feature = "ecdsa")]
 pub type SigningKey = ecdsa_core::SigningKey<NistP256>;
 
-/// ECDSA/secp256k1 verification key (i.e. public key)
+/// ECDSA/P-256 verification key (i.e. public key)
 #[cfg(feature = "ecdsa")]
 pub type VerifyingKey = openvm_ecc_guest::ecdsa::VerifyingKey<NistP256>;
 
+/// Use SHA-256 as the default digest for ECDSA/P-256, so that [VerifyingKey] implements
+/// [signature::Verifier]. Inside the guest, the message is hashed with the SHA-256 extension.
+#[cfg(feature = "sha256")]
+impl hazmat::DigestPrimitive for NistP256 {
+    type Digest = openvm_sha2::Sha256;
+}
+
 // No custom hook
 #[cfg(feature = "ecdsa")]
 impl VerifyCustomHook<NistP256> for P256Point {}
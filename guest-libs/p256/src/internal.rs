@@ -25,9 +25,13 @@ pub const CURVE_A: P256Coord = P256Coord::from_const_bytes(hex!(
 pub const CURVE_B: P256Coord = P256Coord::from_const_bytes(hex!(
     "4b60d2273e3cce3bf6b053ccb0061d65bc86987655bdebb3e7933aaad835c65a"
 ));
+// Z = -10 for the simplified SWU map, see RFC 9380, Section 8.2
+pub const SSWU_Z: P256Coord = P256Coord::from_const_bytes(hex!(
+    "f5ffffffffffffffffffffff00000000000000000000000001000000ffffffff"
+));
 
 sw_declare! {
-    P256Point { mod_type = P256Coord, a = CURVE_A, b = CURVE_B },
+    P256Point { mod_type = P256Coord, a = CURVE_A, b = CURVE_B, z = SSWU_Z },
 }
 
 // --- Implement internal traits ---
@@ -3,7 +3,7 @@
 
 extern crate alloc;
 
-use ecdsa::signature::hazmat::PrehashVerifier;
+use ecdsa::signature::{hazmat::PrehashVerifier, Verifier};
 use elliptic_curve::{sec1::FromEncodedPoint, CurveArithmetic};
 use hex_literal::hex;
 // clippy thinks this is unused, but it's used in the init! macro
@@ -43,4 +43,25 @@ fn main() {
             &signature,
         );
     assert!(result.is_ok());
+
+    // Verify against the message with the default SHA-256 digest.
+    // Test vector from RFC 6979, Appendix A.2.5 (P-256, SHA-256, message "sample").
+    let verifier = VerifyingKey::from_affine(
+        <NistP256 as CurveArithmetic>::AffinePoint::from_encoded_point(
+            &EncodedPoint::from_affine_coordinates(
+                &hex!("60fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6").into(),
+                &hex!("7903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299").into(),
+                false,
+            ),
+        )
+        .unwrap(),
+    )
+    .unwrap();
+    let signature = Signature::from_scalars(
+        hex!("efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716"),
+        hex!("f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8"),
+    )
+    .unwrap();
+    assert!(verifier.verify(b"sample", &signature).is_ok());
+    assert!(verifier.verify(b"test", &signature).is_err());
 }
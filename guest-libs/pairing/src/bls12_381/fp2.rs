@@ -3,8 +3,9 @@ extern crate alloc;
 use alloc::vec::Vec;
 use core::ops::Neg;
 
+use hex_literal::hex;
 use openvm_algebra_complex_macros::{complex_declare, complex_impl_field};
-use openvm_algebra_guest::{field::FieldExtension, Field, IntMod};
+use openvm_algebra_guest::{field::FieldExtension, DivUnsafe, Field, IntMod, Sqrt};
 
 use super::Fp;
 
@@ -72,3 +73,37 @@ impl FieldExtension<Fp> for Fp2 {
         }
     }
 }
+
+// 1/2 in Fp, little-endian
+const FP_TWO_INV: Fp = Fp::from_const_bytes(hex!(
+    "56D5FFFFFF7FFFDCFFFFA958FFFF550F127B587B506998B35F89C279C2A53BB26BD6A521DBD38D254DF3BF1CF588000D"
+));
+
+impl Sqrt for Fp2 {
+    /// Square root in `Fp2 = Fp[u] / (u^2 + 1)` using square roots in `Fp`.
+    ///
+    /// Writing `self = a + bu` and `sqrt(self) = x + yu`, we need `x^2 - y^2 = a` and `2xy = b`.
+    /// Then `x^2 + y^2 = sqrt(a^2 + b^2)`, so `x^2 = (a +- sqrt(a^2 + b^2)) / 2` and
+    /// `y = b / 2x`. Since `self` is a square if and only if its norm `a^2 + b^2` is a square in
+    /// `Fp`, all non-existence claims reduce to the (hint-proven) non-existence of square roots in
+    /// `Fp`.
+    fn sqrt(&self) -> Option<Self> {
+        if self.c1 == <Fp as Field>::ZERO {
+            // sqrt(a) is either in Fp or in u * Fp since -1 is a non-residue in Fp
+            return match self.c0.sqrt() {
+                Some(x) => Some(Self::new(x, <Fp as Field>::ZERO)),
+                None => (-&self.c0)
+                    .sqrt()
+                    .map(|y| Self::new(<Fp as Field>::ZERO, y)),
+            };
+        }
+        let norm_sqrt = (&self.c0 * &self.c0 + &self.c1 * &self.c1).sqrt()?;
+        let x = match ((&self.c0 + &norm_sqrt) * &FP_TWO_INV).sqrt() {
+            Some(x) => x,
+            None => ((&self.c0 - &norm_sqrt) * &FP_TWO_INV).sqrt()?,
+        };
+        // x != 0 since b != 0
+        let y = self.c1.clone().div_unsafe(&(&x + &x));
+        Some(Self::new(x, y))
+    }
+}
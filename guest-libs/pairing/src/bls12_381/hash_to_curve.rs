@@ -0,0 +1,281 @@
+//! Hashing to G2 following the `BLS12381G2_XMD:SHA-256_SSWU_RO_` suite of
+//! [RFC 9380](https://www.rfc-editor.org/rfc/rfc9380).
+//!
+//! The implementation branches on its inputs and is not constant time. This is fine for
+//! verification, where all inputs are public.
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use hex_literal::hex;
+use openvm_algebra_guest::{DivUnsafe, Field, IntMod, Reduce, Sqrt};
+use openvm_ecc_guest::Group;
+use openvm_sha2::sha256;
+
+use super::{mul_by_seed, psi, Fp, Fp2, G2Affine};
+
+const SHA256_BLOCK_BYTES: usize = 64;
+const SHA256_OUTPUT_BYTES: usize = 32;
+// Number of uniform bytes reduced into each element of Fp: ceil((ceil(log2(p)) + 128) / 8)
+const FP_HASH_BYTES: usize = 64;
+
+// Coefficients of the curve E2': y^2 = x^3 + A' x + B' which is 3-isogenous to E'(Fp2), and the
+// non-square Z used by the simplified SWU map. See RFC 9380 Section 8.8.2.
+const SSWU_A: Fp2 = Fp2::new(<Fp as Field>::ZERO, Fp::from_const_u8(240));
+const SSWU_B: Fp2 = Fp2::new(
+    Fp::from_const_bytes(hex!(
+        "F40300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+    )),
+    Fp::from_const_bytes(hex!(
+        "F40300000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000"
+    )),
+);
+const SSWU_Z: Fp2 = Fp2::new(
+    Fp::from_const_bytes(hex!(
+        "A9AAFFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+    )),
+    Fp::from_const_bytes(hex!(
+        "AAAAFFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+    )),
+);
+// -B' / A'
+const SSWU_MINUS_B_OVER_A: Fp2 = Fp2::new(
+    Fp::from_const_bytes(hex!(
+        "C3B1CCCCCC8C5D72888849DA434483D6F14DFC2BE675CF0223793F6F3F2D8C9BAAE5C60C4F282FFED2D5BD1A79123C08"
+    )),
+    Fp::from_const_bytes(hex!(
+        "E8F832333373A14777770AD7BABB284832A8B4CABA5C61649C994584451EEBC82CC78436677FEC4CC710C21E71FFC411"
+    )),
+);
+// B' / (Z A'), the value of x1 in the exceptional case
+const SSWU_B_OVER_ZA: Fp2 = Fp2::new(
+    Fp::from_const_bytes(hex!(
+        "27BDF5285C4FACE3B481DB5EDA401A5E30A965A2C74AF6666D4BA67CD9D5E8EBEE2D8E02433BD6322A91BF6B4B9DA501"
+    )),
+    Fp::from_const_bytes(hex!(
+        "36731E85EB11FA0EE17AC1946F3D5D0493FA7F0F4AF24D327730927DF8C9BCA00A23A13BEDF598B21B3341F6073A1015"
+    )),
+);
+// Coefficients, in increasing degree, of the 3-isogeny map from E2' to E'(Fp2). See RFC 9380
+// Appendix E.3.
+const ISO3_XNUM: [Fp2; 4] = [
+    Fp2::new(
+        Fp::from_const_bytes(hex!(
+            "D697AAAAAAAA38621CC7D943E338265C5DE10AC52384B5882A043AFD392DC53285EDD7479A7A5BBB3E338E7E5059C705"
+        )),
+        Fp::from_const_bytes(hex!(
+            "D697AAAAAAAA38621CC7D943E338265C5DE10AC52384B5882A043AFD392DC53285EDD7479A7A5BBB3E338E7E5059C705"
+        )),
+    ),
+    Fp2::new(
+        <Fp as Field>::ZERO,
+        Fp::from_const_bytes(hex!(
+            "1AC7FFFFFFFFA92655558DCBA9AA721418A4204F6B8C209A7F0CAEF7AD874F988FC887D7CE6F1232BC99AA7BF10B5611"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_bytes(hex!(
+            "1EC7FFFFFFFFA92655558DCBA9AA721418A4204F6B8C209A7F0CAEF7AD874F988FC887D7CE6F1232BC99AA7BF10B5611"
+        )),
+        Fp::from_const_bytes(hex!(
+            "8DE3FFFFFFFF5493AAAAC6E55455390A0C5290A7354610CD3F06D7FBD6C327CC47E4C36BE7370919DE4CD5BDF805AB08"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_bytes(hex!(
+            "D15EAAAAAAAAE288711C670F8DE3987075852B148F10D622AA10E8F4E7B414CB14B65F1F69EA6DEDFACC38FA41651D17"
+        )),
+        <Fp as Field>::ZERO,
+    ),
+];
+const ISO3_XDEN: [Fp2; 3] = [
+    Fp2::new(
+        <Fp as Field>::ZERO,
+        Fp::from_const_bytes(hex!(
+            "63AAFFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_u8(12),
+        Fp::from_const_bytes(hex!(
+            "9FAAFFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_u8(1),
+        <Fp as Field>::ZERO,
+    ),
+];
+const ISO3_YNUM: [Fp2; 4] = [
+    Fp2::new(
+        Fp::from_const_bytes(hex!(
+            "06D7C6711CC7CF12682FC9F8EB258CFC00E5277DD83944F59B647FA0D4A57D0F93116D078BC1A4593B11B47A7C473015"
+        )),
+        Fp::from_const_bytes(hex!(
+            "06D7C6711CC7CF12682FC9F8EB258CFC00E5277DD83944F59B647FA0D4A57D0F93116D078BC1A4593B11B47A7C473015"
+        )),
+    ),
+    Fp2::new(
+        <Fp as Field>::ZERO,
+        Fp::from_const_bytes(hex!(
+            "BE97AAAAAAAA38621CC7D943E338265C5DE10AC52384B5882A043AFD392DC53285EDD7479A7A5BBB3E338E7E5059C705"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_bytes(hex!(
+            "1CC7FFFFFFFFA92655558DCBA9AA721418A4204F6B8C209A7F0CAEF7AD874F988FC887D7CE6F1232BC99AA7BF10B5611"
+        )),
+        Fp::from_const_bytes(hex!(
+            "8FE3FFFFFFFF5493AAAAC6E55455390A0C5290A7354610CD3F06D7FBD6C327CC47E4C36BE7370919DE4CD5BDF805AB08"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_bytes(hex!(
+            "108B711CC771B3E1D94BDC567A09794E5274A29AC677E9B08662E2A1370F1B76D01A81E33D04F7FB9BF76C3BD49A4C12"
+        )),
+        <Fp as Field>::ZERO,
+    ),
+];
+const ISO3_YDEN: [Fp2; 4] = [
+    Fp2::new(
+        Fp::from_const_bytes(hex!(
+            "FBA8FFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+        )),
+        Fp::from_const_bytes(hex!(
+            "FBA8FFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+        )),
+    ),
+    Fp2::new(
+        <Fp as Field>::ZERO,
+        Fp::from_const_bytes(hex!(
+            "D3A9FFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_u8(18),
+        Fp::from_const_bytes(hex!(
+            "99AAFFFFFFFFFEB9FFFF53B1FEFFAB1E24F6B0F6A0D23067BF1285F3844B7764D7AC4B43B6A71B4B9AE67F39EA11011A"
+        )),
+    ),
+    Fp2::new(
+        Fp::from_const_u8(1),
+        <Fp as Field>::ZERO,
+    ),
+];
+
+/// Hashes `msg` to a point in G2 using the domain separation tag `dst`.
+pub fn hash_to_g2(msg: &[u8], dst: &[u8]) -> G2Affine {
+    let [u0, u1] = hash_to_field(msg, dst);
+    let q0 = iso_map(map_to_curve_sswu(&u0));
+    let q1 = iso_map(map_to_curve_sswu(&u1));
+    clear_cofactor(&(q0 + q1))
+}
+
+/// `expand_message_xmd` using SHA-256, see RFC 9380 Section 5.3.1.
+fn expand_message_xmd(msg: &[u8], dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+    let ell = len_in_bytes.div_ceil(SHA256_OUTPUT_BYTES);
+    assert!(ell <= 255, "requested too many bytes");
+    assert!(dst.len() <= 255, "domain separation tag is too long");
+
+    // b_0 = H(Z_pad || msg || l_i_b_str || I2OSP(0, 1) || DST_prime)
+    let mut input = Vec::with_capacity(SHA256_BLOCK_BYTES + msg.len() + dst.len() + 4);
+    input.extend_from_slice(&[0; SHA256_BLOCK_BYTES]);
+    input.extend_from_slice(msg);
+    input.extend_from_slice(&(len_in_bytes as u16).to_be_bytes());
+    input.push(0);
+    input.extend_from_slice(dst);
+    input.push(dst.len() as u8);
+    let b_0 = sha256(&input);
+
+    // b_i = H(strxor(b_0, b_(i - 1)) || I2OSP(i, 1) || DST_prime), where b_1 uses b_0 directly
+    let mut uniform_bytes = Vec::with_capacity(ell * SHA256_OUTPUT_BYTES);
+    let mut b_i = [0; SHA256_OUTPUT_BYTES];
+    for i in 1..=ell {
+        input.clear();
+        input.extend(b_0.iter().zip(b_i.iter()).map(|(b0, bi)| b0 ^ bi));
+        input.push(i as u8);
+        input.extend_from_slice(dst);
+        input.push(dst.len() as u8);
+        b_i = sha256(&input);
+        uniform_bytes.extend_from_slice(&b_i);
+    }
+    uniform_bytes.truncate(len_in_bytes);
+    uniform_bytes
+}
+
+/// Hashes `msg` to two elements of Fp2, see RFC 9380 Section 5.2.
+fn hash_to_field(msg: &[u8], dst: &[u8]) -> [Fp2; 2] {
+    let uniform_bytes = expand_message_xmd(msg, dst, 4 * FP_HASH_BYTES);
+    let mut coeffs = uniform_bytes
+        .chunks_exact(FP_HASH_BYTES)
+        .map(Fp::reduce_be_bytes);
+    core::array::from_fn(|_| Fp2::new(coeffs.next().unwrap(), coeffs.next().unwrap()))
+}
+
+/// The simplified SWU map to E2', see RFC 9380 Section 6.6.2.
+fn map_to_curve_sswu(u: &Fp2) -> (Fp2, Fp2) {
+    let z_u2 = SSWU_Z * &(u * u);
+    let tv1 = &z_u2 * &z_u2 + &z_u2;
+    let x1 = if tv1 == Fp2::ZERO {
+        SSWU_B_OVER_ZA
+    } else {
+        SSWU_MINUS_B_OVER_A * &(Fp2::ONE + Fp2::ONE.div_unsafe(&tv1))
+    };
+    let (x, y) = match iso_curve_rhs(&x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            // Z is chosen such that g(x2) = Z^3 u^6 g(x1) is a square whenever g(x1) is not
+            let x2 = z_u2 * &x1;
+            let y = iso_curve_rhs(&x2)
+                .sqrt()
+                .expect("g(x2) must be a square when g(x1) is not");
+            (x2, y)
+        }
+    };
+    let y = if sgn0(u) != sgn0(&y) { -y } else { y };
+    (x, y)
+}
+
+// x^3 + A' x + B'
+fn iso_curve_rhs(x: &Fp2) -> Fp2 {
+    (x * x + &SSWU_A) * x + &SSWU_B
+}
+
+// sgn0 of RFC 9380 Section 4.1 for an element of Fp2
+fn sgn0(x: &Fp2) -> bool {
+    x.c0.assert_reduced();
+    x.c1.assert_reduced();
+    let sign_0 = x.c0.as_le_bytes()[0] & 1 == 1;
+    let zero_0 = x.c0 == <Fp as Field>::ZERO;
+    let sign_1 = x.c1.as_le_bytes()[0] & 1 == 1;
+    sign_0 || (zero_0 && sign_1)
+}
+
+/// The 3-isogeny map from E2' to E'(Fp2), see RFC 9380 Appendix E.3.
+fn iso_map((x, y): (Fp2, Fp2)) -> G2Affine {
+    let x_num = eval_poly(&ISO3_XNUM, &x);
+    let x_den = eval_poly(&ISO3_XDEN, &x);
+    let y_num = eval_poly(&ISO3_YNUM, &x);
+    let y_den = eval_poly(&ISO3_YDEN, &x);
+    // The denominators only vanish on the kernel of the isogeny
+    if x_den == Fp2::ZERO || y_den == Fp2::ZERO {
+        return <G2Affine as Group>::IDENTITY;
+    }
+    G2Affine::new(x_num.div_unsafe(&x_den), y * &y_num.div_unsafe(&y_den))
+}
+
+// Evaluates the polynomial with coefficients `coeffs` (in increasing degree) at `x`.
+fn eval_poly(coeffs: &[Fp2], x: &Fp2) -> Fp2 {
+    let (leading, rest) = coeffs.split_last().unwrap();
+    rest.iter()
+        .rev()
+        .fold(leading.clone(), |acc, coeff| acc * x + coeff)
+}
+
+/// Multiplies `p` by `h_eff` to map it into G2, computed as
+/// `[z^2 - z - 1] p + [z - 1] psi(p) + psi^2(2p)` following RFC 9380 Appendix G.3.
+fn clear_cofactor(p: &G2Affine) -> G2Affine {
+    let t1 = mul_by_seed(p);
+    let t2 = psi(p);
+    psi(&psi(&p.double())) + mul_by_seed(&(t1.clone() + &t2)) - t1 - t2 - p
+}
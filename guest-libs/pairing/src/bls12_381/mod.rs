@@ -8,15 +8,21 @@ use openvm_ecc_guest::{weierstrass::IntrinsicCurve, CyclicGroup, Group};
 
 mod fp12;
 mod fp2;
+mod hash_to_curve;
 mod pairing;
+mod signature;
+mod subgroup;
 #[cfg(all(feature = "halo2curves", not(target_os = "zkvm")))]
 pub(crate) mod utils;
 
 pub use fp12::*;
 pub use fp2::*;
+pub use hash_to_curve::*;
 use hex_literal::hex;
 use openvm_ecc_sw_macros::sw_declare;
 use openvm_pairing_guest::pairing::PairingIntrinsics;
+pub use signature::*;
+pub use subgroup::*;
 
 #[cfg(all(test, feature = "halo2curves", not(target_os = "zkvm")))]
 mod tests;
@@ -25,7 +25,7 @@ use {
     openvm_pairing_guest::{PairingBaseFunct7, OPCODE, PAIRING_FUNCT3},
     openvm_platform::custom_insn_r,
     openvm_rv32im_guest,
-    openvm_rv32im_guest::hint_buffer_u32,
+    openvm_rv32im_guest::{hint_buffer_u32, hint_select, selected_hint_queue, HintQueue},
 };
 
 use super::{Bls12_381, Fp, Fp12, Fp2};
@@ -35,6 +35,8 @@ use crate::bls12_381::utils::{
     convert_bls12381_halo2_fq12_to_fp12,
 };
 
+openvm_platform::require_extension_version!("pairing", 1);
+
 impl Evaluatable<Fp, Fp2> for UnevaluatedLine<Fp2> {
     fn evaluate(&self, xy_frac: &(Fp, Fp)) -> EvaluatedLine<Fp2> {
         let (x_over_y, y_inv) = xy_frac;
@@ -286,6 +288,8 @@ impl PairingCheck for Bls12_381 {
             let p_fat_ptr = (P.as_ptr() as u32, P.len() as u32);
             let q_fat_ptr = (Q.as_ptr() as u32, Q.len() as u32);
             unsafe {
+                let previous_hint_queue = selected_hint_queue();
+                hint_select(HintQueue::Pairing as u32);
                 custom_insn_r!(
                     opcode = OPCODE,
                     funct3 = PAIRING_FUNCT3,
@@ -296,6 +300,7 @@ impl PairingCheck for Bls12_381 {
                 );
                 let ptr = hint.as_ptr() as *const u8;
                 hint_buffer_u32!(ptr, (48 * 12 * 2) / 4);
+                hint_select(previous_hint_queue);
                 hint.assume_init()
             }
         }
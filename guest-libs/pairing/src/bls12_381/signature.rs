@@ -0,0 +1,95 @@
+//! BLS signatures with public keys in G1 and signatures in G2 (the "minimal-pubkey-size"
+//! variant used by the Ethereum consensus layer), following the
+//! [IETF BLS signature draft](https://datatracker.ietf.org/doc/html/draft-irtf-cfrg-bls-signature-05).
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::fmt;
+
+use openvm_ecc_guest::{weierstrass::WeierstrassPoint, AffinePoint, CyclicGroup, Group};
+use openvm_pairing_guest::pairing::PairingCheck;
+
+use super::{g1_subgroup_check, g2_subgroup_check, hash_to_g2, Bls12_381, G1Affine, G2Affine};
+
+/// Domain separation tag of the proof-of-possession ciphersuite
+/// `BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_`, which is the one used by Ethereum.
+pub const BLS_SIG_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BlsError {
+    /// A public key is the identity, is not on the curve, or is not in G1.
+    InvalidPublicKey,
+    /// The signature is the identity, is not on the curve, or is not in G2.
+    InvalidSignature,
+    /// There are no messages, or the number of public keys and messages differ.
+    LengthMismatch,
+    /// The pairing equation does not hold.
+    VerificationFailed,
+}
+
+impl core::error::Error for BlsError {}
+impl fmt::Display for BlsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidPublicKey => write!(f, "Invalid BLS public key"),
+            Self::InvalidSignature => write!(f, "Invalid BLS signature"),
+            Self::LengthMismatch => write!(f, "Mismatched number of public keys and messages"),
+            Self::VerificationFailed => write!(f, "BLS signature verification failed"),
+        }
+    }
+}
+
+/// Verifies the signature `sig` of `msg` under the public key `pk` with the [BLS_SIG_DST]
+/// ciphersuite.
+///
+/// Both `pk` and `sig` are validated (on the curve, in the prime order subgroup and not the
+/// identity) before checking `e(pk, H(msg)) = e(g1, sig)`.
+pub fn bls_verify(pk: &G1Affine, msg: &[u8], sig: &G2Affine) -> Result<(), BlsError> {
+    bls_aggregate_verify(core::slice::from_ref(pk), &[msg], sig)
+}
+
+/// Verifies the aggregate signature `sig` of `msgs[i]` under `pks[i]` for all `i` with the
+/// [BLS_SIG_DST] ciphersuite, by checking `prod_i e(pks[i], H(msgs[i])) = e(g1, sig)` with a
+/// single pairing check.
+///
+/// Messages do not need to be distinct: the proof-of-possession ciphersuite assumes that the
+/// caller only accepts public keys whose proof of possession has been verified, which rules out
+/// rogue key attacks.
+pub fn bls_aggregate_verify(
+    pks: &[G1Affine],
+    msgs: &[&[u8]],
+    sig: &G2Affine,
+) -> Result<(), BlsError> {
+    if pks.is_empty() || pks.len() != msgs.len() {
+        return Err(BlsError::LengthMismatch);
+    }
+    if !pks.iter().all(is_valid_public_key) {
+        return Err(BlsError::InvalidPublicKey);
+    }
+    if !is_valid_signature(sig) {
+        return Err(BlsError::InvalidSignature);
+    }
+
+    let mut p = Vec::with_capacity(pks.len() + 1);
+    let mut q = Vec::with_capacity(pks.len() + 1);
+    for (pk, msg) in pks.iter().zip(msgs) {
+        p.push(AffinePoint::new(pk.x().clone(), pk.y().clone()));
+        let h = hash_to_g2(msg, BLS_SIG_DST);
+        q.push(AffinePoint::new(h.x().clone(), h.y().clone()));
+    }
+    let neg_g1 = G1Affine::NEG_GENERATOR;
+    p.push(AffinePoint::new(neg_g1.x().clone(), neg_g1.y().clone()));
+    q.push(AffinePoint::new(sig.x().clone(), sig.y().clone()));
+    Bls12_381::pairing_check(&p, &q).map_err(|_| BlsError::VerificationFailed)
+}
+
+// KeyValidate of the IETF draft, together with an on-curve check
+fn is_valid_public_key(pk: &G1Affine) -> bool {
+    let (x, y) = (pk.x(), pk.y());
+    !pk.is_identity() && y * y == x * x * x + &G1Affine::CURVE_B && g1_subgroup_check(pk)
+}
+
+fn is_valid_signature(sig: &G2Affine) -> bool {
+    let (x, y) = (sig.x(), sig.y());
+    !sig.is_identity() && y * y == x * x * x + &G2Affine::CURVE_B && g2_subgroup_check(sig)
+}
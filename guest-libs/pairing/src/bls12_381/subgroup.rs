@@ -0,0 +1,70 @@
+use hex_literal::hex;
+use openvm_algebra_guest::{field::ComplexConjugate, Field};
+use openvm_ecc_guest::{weierstrass::WeierstrassPoint, Group};
+use openvm_pairing_guest::bls12_381::BLS12_381_SEED_ABS;
+
+use super::{Fp, Fp2, G1Affine, G2Affine};
+
+// Primitive cube root of unity in Fp such that phi(P) = [-z^2] P for P in G1, where
+// phi(x, y) = (BETA * x, y).
+const BETA: Fp = Fp::from_const_bytes(hex!(
+    "FEFFFEFFFFFF012E02000A6213D817DE8896F8E63BA9B3DDEA770F6A07C669BA51CE76DF2F67195F0000000000000000"
+));
+// 1 / (1 + u)^((p - 1) / 3)
+const PSI_X: Fp2 = Fp2::new(
+    <Fp as Field>::ZERO,
+    Fp::from_const_bytes(hex!(
+        "ADAA00000000FD8BFDFF494FEB2794409B5FB80F65297D89D49A75897D850DAA85DED463864002EC99E67F39EA11011A"
+    )),
+);
+// 1 / (1 + u)^((p - 1) / 2)
+const PSI_Y: Fp2 = Fp2::new(
+    Fp::from_const_bytes(hex!(
+        "A2DE1B12047BEEF10AFA673ECF6644305EB41EF6896439EF60CFB130D9ED3D1CD92C7AD748C4E9E28EA68001E6035213"
+    )),
+    Fp::from_const_bytes(hex!(
+        "09CCE3EDFB8410C8F405EC722F9967EEC5419200176EF7775E43D3C2AB5D3948FE7FD16B6DE331680B40FF37040EAF06"
+    )),
+);
+
+/// Returns whether `p` lies in the prime order subgroup G1, assuming `p` is on the curve.
+///
+/// Uses the endomorphism test of [Scott](https://eprint.iacr.org/2021/1130): `p` is in G1 if and
+/// only if `phi(p) = [-z^2] p`, where `z` is the curve seed.
+pub fn g1_subgroup_check(p: &G1Affine) -> bool {
+    let phi_p = G1Affine::from_xy_unchecked(&BETA * p.x(), p.y().clone());
+    let seed = BLS12_381_SEED_ABS as u128;
+    phi_p == -mul_by_u128(p, seed * seed)
+}
+
+/// Returns whether `p` lies in the prime order subgroup G2, assuming `p` is on the curve.
+///
+/// Uses the endomorphism test of [Scott](https://eprint.iacr.org/2021/1130): `p` is in G2 if and
+/// only if `psi(p) = [z] p`, where `z` is the curve seed.
+pub fn g2_subgroup_check(p: &G2Affine) -> bool {
+    psi(p) == mul_by_seed(p)
+}
+
+/// The untwist-Frobenius-twist endomorphism of `E'(Fp2)`.
+pub(crate) fn psi(p: &G2Affine) -> G2Affine {
+    let x = p.x().clone().conjugate() * &PSI_X;
+    let y = p.y().clone().conjugate() * &PSI_Y;
+    G2Affine::from_xy_unchecked(x, y)
+}
+
+/// Multiplies `p` by the (negative) curve seed `z`.
+pub(crate) fn mul_by_seed<G: Group>(p: &G) -> G {
+    -mul_by_u128(p, BLS12_381_SEED_ABS as u128)
+}
+
+// Double-and-add scalar multiplication by a small scalar.
+fn mul_by_u128<G: Group>(p: &G, scalar: u128) -> G {
+    let mut acc = G::IDENTITY;
+    for i in (0..u128::BITS - scalar.leading_zeros()).rev() {
+        acc.double_assign();
+        if (scalar >> i) & 1 == 1 {
+            acc += p;
+        }
+    }
+    acc
+}
@@ -451,7 +451,10 @@ mod bn254 {
 mod bls12_381 {
     use eyre::Result;
     use halo2curves_axiom::{
-        bls12_381::{Fq12, Fq2, Fr, G1Affine, G2Affine},
+        bls12_381::{
+            hash_to_curve::{ExpandMsgXmd, HashToCurve},
+            Fq12, Fq2, Fr, G1Affine, G2Affine, G2,
+        },
         ff::Field,
     };
     use num_bigint::BigUint;
@@ -481,6 +484,10 @@ mod bls12_381 {
     use openvm_rv32im_transpiler::{
         Rv32ITranspilerExtension, Rv32IoTranspilerExtension, Rv32MTranspilerExtension,
     };
+    use openvm_sdk::{
+        config::{AppConfig, SdkVmConfig},
+        StdIn,
+    };
     use openvm_stark_sdk::{openvm_stark_backend::p3_field::FieldAlgebra, p3_baby_bear::BabyBear};
     use openvm_toolchain_tests::{build_example_program_at_path_with_features, get_programs_dir};
     use openvm_transpiler::{transpiler::Transpiler, FromElf};
@@ -906,4 +913,56 @@ mod bls12_381 {
         air_test_with_min_segments(config, openvm_exe, vec![io], 1);
         Ok(())
     }
+
+    #[test]
+    fn test_bls_verify() -> Result<()> {
+        let config = toml::from_str::<AppConfig<SdkVmConfig>>(include_str!(
+            "programs/openvm_bls12_381.toml"
+        ))?
+        .app_vm_config;
+        let elf = build_example_program_at_path_with_features(
+            get_programs_dir!("tests/programs"),
+            "bls_verify",
+            ["bls12_381"],
+            &config,
+        )?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+
+        let g1_to_bytes = |p: G1Affine| [p.x.to_bytes(), p.y.to_bytes()].concat();
+        let g2_to_bytes = |p: G2Affine| [p.x.to_bytes(), p.y.to_bytes()].concat();
+        let sign = |sk: &Fr, msg: &[u8]| {
+            let h = <G2 as HashToCurve<ExpandMsgXmd<sha2::Sha256>>>::hash_to_curve(
+                msg,
+                b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_",
+            );
+            h * sk
+        };
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(2);
+        let mut stdin = StdIn::default();
+
+        let sk = Fr::random(&mut rng);
+        let msg = b"hello openvm";
+        stdin.write_bytes(&g1_to_bytes(G1Affine::from(G1Affine::generator() * sk)));
+        stdin.write_bytes(msg);
+        stdin.write_bytes(&g2_to_bytes(G2Affine::from(sign(&sk, msg))));
+
+        let sks: Vec<_> = (0..3).map(|_| Fr::random(&mut rng)).collect();
+        let msgs = [b"message 0".as_slice(), b"message 1", b"message 2"];
+        for sk in &sks {
+            stdin.write_bytes(&g1_to_bytes(G1Affine::from(G1Affine::generator() * sk)));
+        }
+        for msg in msgs {
+            stdin.write_bytes(msg);
+        }
+        let agg_sig = sks
+            .iter()
+            .zip(msgs)
+            .map(|(sk, msg)| sign(sk, msg))
+            .fold(G2::identity(), |acc, sig| acc + sig);
+        stdin.write_bytes(&g2_to_bytes(G2Affine::from(agg_sig)));
+
+        air_test_with_min_segments(config, openvm_exe, stdin, 1);
+        Ok(())
+    }
 }
@@ -0,0 +1,163 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use hex_literal::hex;
+use openvm::io::read_vec;
+use openvm_algebra_guest::{field::FieldExtension, IntMod};
+use openvm_ecc_guest::{weierstrass::WeierstrassPoint, CyclicGroup, Group};
+use openvm_pairing::bls12_381::{
+    bls_aggregate_verify, bls_verify, g1_subgroup_check, g2_subgroup_check, hash_to_g2, BlsError,
+    Fp, Fp2, G1Affine, G2Affine,
+};
+
+openvm::init!("openvm_init_bls_verify_bls12_381.rs");
+
+openvm::entry!(main);
+
+fn read_g1() -> G1Affine {
+    let bytes = read_vec();
+    G1Affine::from_xy(
+        Fp::from_le_bytes_unchecked(&bytes[..48]),
+        Fp::from_le_bytes_unchecked(&bytes[48..96]),
+    )
+    .unwrap()
+}
+
+fn read_g2() -> G2Affine {
+    let bytes = read_vec();
+    G2Affine::from_xy(
+        Fp2::from_bytes(&bytes[..96]),
+        Fp2::from_bytes(&bytes[96..192]),
+    )
+    .unwrap()
+}
+
+// Builds an element of Fp2 from the big-endian encodings of `c1` and `c0`, in the order used by
+// the uncompressed serialization of G2 points.
+fn fp2_from_be(c1: &[u8], c0: &[u8]) -> Fp2 {
+    Fp2::new(
+        Fp::from_be_bytes_unchecked(c0),
+        Fp::from_be_bytes_unchecked(c1),
+    )
+}
+
+fn test_hash_to_g2() {
+    // Test vectors from RFC 9380 Appendix J.10.1
+    const DST: &[u8] = b"QUUX-V01-CS02-with-BLS12381G2_XMD:SHA-256_SSWU_RO_";
+    let h = hash_to_g2(b"", DST);
+    let expected = G2Affine::from_xy_unchecked(
+        fp2_from_be(
+            &hex!("05cb8437535e20ecffaef7752baddf98034139c38452458baeefab379ba13dff5bf5dd71b72418717047f5b0f37da03d"),
+            &hex!("0141ebfbdca40eb85b87142e130ab689c673cf60f1a3e98d69335266f30d9b8d4ac44c1038e9dcdd5393faf5c41fb78a"),
+        ),
+        fp2_from_be(
+            &hex!("12424ac32561493f3fe3c260708a12b7c620e7be00099a974e259ddc7d1f6395c3c811cdd19f1e8dbf3e9ecfdcbab8d6"),
+            &hex!("0503921d7f6a12805e72940b963c0cf3471c7b2a524950ca195d11062ee75ec076daf2d4bc358c4b190c0c98064fdd92"),
+        ),
+    );
+    assert_eq!(h, expected);
+
+    let h = hash_to_g2(b"abc", DST);
+    let expected = G2Affine::from_xy_unchecked(
+        fp2_from_be(
+            &hex!("139cddbccdc5e91b9623efd38c49f81a6f83f175e80b06fc374de9eb4b41dfe4ca3a230ed250fbe3a2acf73a41177fd8"),
+            &hex!("02c2d18e033b960562aae3cab37a27ce00d80ccd5ba4b7fe0e7a210245129dbec7780ccc7954725f4168aff2787776e6"),
+        ),
+        fp2_from_be(
+            &hex!("00aa65dae3c8d732d10ecd2c50f8a1baf3001578f71c694e03866e9f3d49ac1e1ce70dd94a733534f106d4cec0eddd16"),
+            &hex!("1787327b68159716a37440985269cf584bcb1e621d3a7202be6ea05c4cfe244aeb197642555a0645fb87bf7466b2ba48"),
+        ),
+    );
+    assert_eq!(h, expected);
+}
+
+fn test_subgroup_checks() {
+    assert!(g1_subgroup_check(&G1Affine::GENERATOR));
+    assert!(g1_subgroup_check(
+        &(G1Affine::GENERATOR.double() + &G1Affine::GENERATOR)
+    ));
+    // (5, sqrt(5^3 + 4)) is on the curve but not in G1
+    let p = G1Affine::from_xy(
+        Fp::from_u8(5),
+        Fp::from_le_bytes_unchecked(&hex!(
+            "0dd6ba7df67dea4348b6cea7c892f05d71535f18b22b6d50256819598e0a189b667d6e1e0f79c07b79be1e21a16d3c0d"
+        )),
+    )
+    .unwrap();
+    assert!(!g1_subgroup_check(&p));
+
+    // A point on the twist obtained from the isogeny map before clearing the cofactor
+    let q = G2Affine::from_xy(
+        Fp2::new(
+            Fp::from_le_bytes_unchecked(&hex!(
+                "d8f66ee5052735cb71bda82421ae6566bb9ba96a1afb9f9a34ed36c981ea422b5ce5663037842c4719cb62a020c8290f"
+            )),
+            Fp::from_le_bytes_unchecked(&hex!(
+                "2332fcc23cb2bb6ae67ccf5fe69eb10dc9f49fe7e4501b56e808c4321c0920b968c89f24ebfa0960603ef123a7218c18"
+            )),
+        ),
+        Fp2::new(
+            Fp::from_le_bytes_unchecked(&hex!(
+                "4efebdb4cba705b9f8dbffdba398e3ddd8d7939a194151e93d103e56c0daa82002be597b124524f1fe72c48be4fbe905"
+            )),
+            Fp::from_le_bytes_unchecked(&hex!(
+                "9597973e6042db0ebaf87fd4567761c62dc9d884b18a534b7fd4eb40d0fa35789a3dbfce355748fa7832aff75e19f10a"
+            )),
+        ),
+    )
+    .unwrap();
+    assert!(!g2_subgroup_check(&q));
+    assert!(g2_subgroup_check(&hash_to_g2(b"abc", b"DST")));
+}
+
+pub fn main() {
+    test_hash_to_g2();
+    test_subgroup_checks();
+
+    // Single signature
+    let pk = read_g1();
+    let msg = read_vec();
+    let sig = read_g2();
+    assert_eq!(bls_verify(&pk, &msg, &sig), Ok(()));
+    assert_eq!(
+        bls_verify(&pk, b"wrong message", &sig),
+        Err(BlsError::VerificationFailed)
+    );
+    assert_eq!(
+        bls_verify(&G1Affine::GENERATOR, &msg, &sig),
+        Err(BlsError::VerificationFailed)
+    );
+    assert_eq!(
+        bls_verify(&<G1Affine as Group>::IDENTITY, &msg, &sig),
+        Err(BlsError::InvalidPublicKey)
+    );
+    assert_eq!(
+        bls_verify(&pk, &msg, &<G2Affine as Group>::IDENTITY),
+        Err(BlsError::InvalidSignature)
+    );
+
+    // Aggregate signature
+    let num_signers = 3;
+    let pks: Vec<_> = (0..num_signers).map(|_| read_g1()).collect();
+    let msgs: Vec<Vec<u8>> = (0..num_signers).map(|_| read_vec()).collect();
+    let msgs: Vec<&[u8]> = msgs.iter().map(|msg| msg.as_slice()).collect();
+    let agg_sig = read_g2();
+    assert_eq!(bls_aggregate_verify(&pks, &msgs, &agg_sig), Ok(()));
+    let swapped_msgs = [msgs[1], msgs[0], msgs[2]];
+    assert_eq!(
+        bls_aggregate_verify(&pks, &swapped_msgs, &agg_sig),
+        Err(BlsError::VerificationFailed)
+    );
+    assert_eq!(
+        bls_aggregate_verify(&pks[..2], &msgs, &agg_sig),
+        Err(BlsError::LengthMismatch)
+    );
+    assert_eq!(
+        bls_aggregate_verify(&pks, &msgs, &sig),
+        Err(BlsError::VerificationFailed)
+    );
+}
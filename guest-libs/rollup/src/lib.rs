@@ -0,0 +1,188 @@
+#![no_std]
+
+//! A framework for guests proving the state transitions of rollups: the guest reads the root of
+//! the previous state, applies batches of transactions to it with a user-supplied
+//! [StateMachine], and commits the root of the new state.
+//!
+//! The state is a key-value store committed to by the root of a [SparseMerkleTree]. The guest
+//! never holds the whole state: the host executes every batch with a [Sequencer] over the full
+//! tree, recording the value and Merkle proof of every access to the state, and the guest
+//! executes the same transactions against this witness with a [Rollup], checking every access
+//! against the current root and updating the root on every write. A witness which does not match
+//! the root makes the guest panic, so a proof attests that the new root is the result of
+//! applying the transactions to the state committed to by the previous root.
+//!
+//! A guest is a few lines:
+//!
+//! ```ignore
+//! openvm::entry!(main);
+//!
+//! fn main() {
+//!     openvm_rollup::Rollup::new(Transfers).with_checkpoints().run();
+//! }
+//! ```
+//!
+//! and reads its input in the order the host writes it:
+//!
+//! ```ignore
+//! let mut sequencer = Sequencer::new(Transfers, tree);
+//! stdin.write(&sequencer.tree().root());
+//! for transactions in batches {
+//!     stdin.write(&sequencer.execute(transactions));
+//! }
+//! ```
+//!
+//! The guest commits a [RollupOutput] to the journal and publishes the digest of every batch with
+//! `openvm::io::publish_bytes`, so that the verifier learns which transactions were applied.
+//! A transaction for which [StateMachine::apply] returns an error is rejected: its writes are
+//! discarded and the batch goes on.
+//!
+//! With [Rollup::with_checkpoints], the guest pauses after each batch, which ends the segment, so
+//! the segments of long runs line up with batches and can be proven and resumed batch by batch,
+//! e.g. with `AppProver::generate_app_proof_with_checkpoint`.
+//!
+//! Other commitments to the state, such as a Merkle Patricia trie, can be used by implementing
+//! [StateBackend] for the host and the guest and driving them with [apply_batch].
+//!
+//! In the zkVM, the library uses the SHA-256 intrinsic, so the VM must be configured with the
+//! SHA-256 extension.
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+
+use openvm_sha2::sha256;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+pub mod merkle;
+mod sequencer;
+mod state;
+
+pub use merkle::{Digest, MerkleProof, SparseMerkleTree};
+pub use sequencer::Sequencer;
+pub use state::{AccessWitness, RecordingState, State, StateBackend, WitnessedState};
+
+/// The state transition function of a rollup.
+pub trait StateMachine {
+    type Transaction: Serialize + DeserializeOwned;
+    type Error;
+
+    /// The depth of the state tree, so keys are below `2^DEPTH`.
+    const DEPTH: usize = 32;
+
+    /// Applies `tx` to `state`. If it returns an error, the transaction is rejected and its writes
+    /// are discarded.
+    ///
+    /// It must be deterministic, as the host and the guest must make the same accesses to the
+    /// state.
+    fn apply(&self, state: &mut State, tx: &Self::Transaction) -> Result<(), Self::Error>;
+}
+
+/// A batch of transactions, with the witness of the accesses to the state made by applying them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(bound = "T: Serialize + DeserializeOwned")]
+pub struct Batch<T> {
+    pub transactions: Vec<T>,
+    pub witness: Vec<AccessWitness>,
+}
+
+impl<T: Serialize> Batch<T> {
+    /// The digest of the transactions of the batch, which the guest publishes.
+    pub fn digest(&self) -> Digest {
+        batch_digest(&self.transactions)
+    }
+}
+
+/// The output committed by a [Rollup] to the journal.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RollupOutput {
+    pub prev_root: Digest,
+    pub new_root: Digest,
+    /// Number of batches applied.
+    pub batches: u32,
+    /// Number of transactions applied successfully.
+    pub applied: u32,
+    /// Number of transactions rejected by [StateMachine::apply].
+    pub rejected: u32,
+}
+
+/// Returns the SHA-256 hash of the serialization of `transactions`.
+pub fn batch_digest<T: Serialize>(transactions: &[T]) -> Digest {
+    let words = openvm::serde::to_vec(transactions).expect("failed to serialize transactions");
+    let bytes: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    sha256(&bytes)
+}
+
+/// Applies `transactions` to `backend` in order, and returns the number of transactions applied
+/// successfully.
+pub fn apply_batch<M: StateMachine>(
+    machine: &M,
+    backend: &mut dyn StateBackend,
+    transactions: &[M::Transaction],
+) -> u32 {
+    let mut applied = 0;
+    for tx in transactions {
+        let mut state = State::new(backend);
+        if machine.apply(&mut state, tx).is_ok() {
+            state.commit();
+            applied += 1;
+        }
+    }
+    applied
+}
+
+/// The guest side of a rollup, applying the batches of its input to the previous state.
+pub struct Rollup<M> {
+    machine: M,
+    checkpoints: bool,
+}
+
+impl<M: StateMachine> Rollup<M> {
+    pub fn new(machine: M) -> Self {
+        Self {
+            machine,
+            checkpoints: false,
+        }
+    }
+
+    /// Pauses after each batch, ending the segment.
+    pub fn with_checkpoints(mut self) -> Self {
+        self.checkpoints = true;
+        self
+    }
+
+    /// Reads the previous root and then batches until the input is exhausted, applies them, and
+    /// commits and returns the [RollupOutput].
+    ///
+    /// # Panics
+    ///
+    /// If the witness of a batch does not match the state, including if the batch does not use
+    /// all of it.
+    pub fn run(self) -> RollupOutput {
+        let prev_root: Digest = openvm::io::read();
+        let mut output = RollupOutput {
+            prev_root,
+            new_root: prev_root,
+            ..Default::default()
+        };
+        while openvm::io::remaining_inputs() > 0 {
+            let batch: Batch<M::Transaction> = openvm::io::read();
+            openvm::io::publish_bytes(&batch.digest());
+
+            let mut state = WitnessedState::new(M::DEPTH, output.new_root, batch.witness);
+            let applied = apply_batch(&self.machine, &mut state, &batch.transactions);
+            assert!(state.is_exhausted(), "state witness is too long");
+
+            output.new_root = state.root();
+            output.batches += 1;
+            output.applied += applied;
+            output.rejected += batch.transactions.len() as u32 - applied;
+            if self.checkpoints {
+                #[cfg(target_os = "zkvm")]
+                openvm::process::pause();
+            }
+        }
+        openvm::io::commit(&output);
+        output
+    }
+}
@@ -0,0 +1,159 @@
+//! The sparse Merkle tree committing to the state of a rollup.
+//!
+//! The tree of depth `d` has a leaf for every key below `2^d`. The digest of the leaf of a key
+//! without a value is [EMPTY_DIGEST], and otherwise a SHA-256 hash of the value. Leaves and
+//! internal nodes are hashed with distinct domain separators, so a leaf cannot pass for a node.
+
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+
+use openvm_sha2::sha256;
+use serde::{Deserialize, Serialize};
+
+/// Number of bytes in a digest.
+pub const DIGEST_SIZE: usize = 32;
+/// Digest of the leaf of a key without a value.
+pub const EMPTY_DIGEST: Digest = [0; DIGEST_SIZE];
+/// Maximum depth of a tree, for which keys are any `u64`.
+pub const MAX_DEPTH: usize = 64;
+
+const LEAF_DOMAIN: u8 = 0;
+const NODE_DOMAIN: u8 = 1;
+
+pub type Digest = [u8; DIGEST_SIZE];
+
+/// Returns the digest of the leaf holding `value`.
+pub fn hash_leaf(value: Option<&[u8]>) -> Digest {
+    let Some(value) = value else {
+        return EMPTY_DIGEST;
+    };
+    let mut input = Vec::with_capacity(1 + value.len());
+    input.push(LEAF_DOMAIN);
+    input.extend_from_slice(value);
+    sha256(&input)
+}
+
+/// Returns the digest of an internal node with children `left` and `right`.
+pub fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut input = [0u8; 1 + 2 * DIGEST_SIZE];
+    input[0] = NODE_DOMAIN;
+    input[1..1 + DIGEST_SIZE].copy_from_slice(left);
+    input[1 + DIGEST_SIZE..].copy_from_slice(right);
+    sha256(&input)
+}
+
+/// Returns whether `key` has a leaf in a tree of depth `depth`.
+pub fn in_range(depth: usize, key: u64) -> bool {
+    depth >= MAX_DEPTH || key >> depth == 0
+}
+
+/// The siblings of the path from a leaf to the root.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// The digests of the siblings, from the sibling of the leaf up to the children of the root.
+    pub siblings: Vec<Digest>,
+}
+
+impl MerkleProof {
+    /// Returns the root of the tree in which the leaf of `key` has digest `leaf`.
+    pub fn root(&self, key: u64, leaf: &Digest) -> Digest {
+        self.siblings
+            .iter()
+            .enumerate()
+            .fold(*leaf, |node, (height, sibling)| {
+                if (key >> height) & 1 == 0 {
+                    hash_node(&node, sibling)
+                } else {
+                    hash_node(sibling, &node)
+                }
+            })
+    }
+}
+
+/// A sparse Merkle tree holding the whole state, as the host keeps it. Only the nodes of subtrees
+/// with a value are stored.
+#[derive(Clone, Debug)]
+pub struct SparseMerkleTree {
+    depth: usize,
+    values: BTreeMap<u64, Vec<u8>>,
+    /// The digests of the nodes which are not roots of empty subtrees, by height and index at
+    /// that height. Leaves are at height 0.
+    nodes: BTreeMap<(usize, u64), Digest>,
+    /// The roots of the empty subtrees of each height.
+    empty: Vec<Digest>,
+}
+
+impl SparseMerkleTree {
+    /// Creates the empty tree of depth `depth`, which must be at most [MAX_DEPTH].
+    pub fn new(depth: usize) -> Self {
+        assert!(depth <= MAX_DEPTH, "tree depth must be at most {MAX_DEPTH}");
+        let mut empty = vec![EMPTY_DIGEST];
+        for height in 0..depth {
+            empty.push(hash_node(&empty[height], &empty[height]));
+        }
+        Self {
+            depth,
+            values: BTreeMap::new(),
+            nodes: BTreeMap::new(),
+            empty,
+        }
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn root(&self) -> Digest {
+        self.node(self.depth, 0)
+    }
+
+    pub fn get(&self, key: u64) -> Option<&[u8]> {
+        self.values.get(&key).map(Vec::as_slice)
+    }
+
+    /// Sets the value of `key`, or removes it if `value` is `None`.
+    ///
+    /// # Panics
+    ///
+    /// If `key` has no leaf in the tree.
+    pub fn set(&mut self, key: u64, value: Option<Vec<u8>>) {
+        assert!(in_range(self.depth, key), "key {key} is out of range");
+        let mut digest = hash_leaf(value.as_deref());
+        match value {
+            Some(value) => self.values.insert(key, value),
+            None => self.values.remove(&key),
+        };
+        for height in 0..=self.depth {
+            let index = key.checked_shr(height as u32).unwrap_or(0);
+            if digest == self.empty[height] {
+                self.nodes.remove(&(height, index));
+            } else {
+                self.nodes.insert((height, index), digest);
+            }
+            if height < self.depth {
+                let (left, right) = (self.node(height, index & !1), self.node(height, index | 1));
+                digest = hash_node(&left, &right);
+            }
+        }
+    }
+
+    /// Returns the proof of the leaf of `key` against [Self::root].
+    ///
+    /// # Panics
+    ///
+    /// If `key` has no leaf in the tree.
+    pub fn prove(&self, key: u64) -> MerkleProof {
+        assert!(in_range(self.depth, key), "key {key} is out of range");
+        MerkleProof {
+            siblings: (0..self.depth)
+                .map(|height| self.node(height, (key >> height) ^ 1))
+                .collect(),
+        }
+    }
+
+    fn node(&self, height: usize, index: u64) -> Digest {
+        self.nodes
+            .get(&(height, index))
+            .copied()
+            .unwrap_or(self.empty[height])
+    }
+}
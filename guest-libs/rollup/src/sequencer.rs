@@ -0,0 +1,71 @@
+use alloc::vec::Vec;
+
+use crate::{
+    apply_batch, merkle::SparseMerkleTree, Batch, Digest, RecordingState, RollupOutput,
+    StateMachine,
+};
+
+/// The host side of a rollup, executing batches over the full state to produce the input of a
+/// [Rollup](crate::Rollup) and the output it will commit.
+pub struct Sequencer<M> {
+    machine: M,
+    tree: SparseMerkleTree,
+    output: RollupOutput,
+    batch_digests: Vec<Digest>,
+}
+
+impl<M: StateMachine> Sequencer<M> {
+    /// Creates a sequencer continuing from the state held by `tree`.
+    ///
+    /// # Panics
+    ///
+    /// If the depth of `tree` is not [StateMachine::DEPTH].
+    pub fn new(machine: M, tree: SparseMerkleTree) -> Self {
+        assert_eq!(tree.depth(), M::DEPTH, "state tree has the wrong depth");
+        let root = tree.root();
+        Self {
+            machine,
+            tree,
+            output: RollupOutput {
+                prev_root: root,
+                new_root: root,
+                ..Default::default()
+            },
+            batch_digests: Vec::new(),
+        }
+    }
+
+    /// Applies `transactions` to the state and returns the batch to write to the input of the
+    /// guest.
+    pub fn execute(&mut self, transactions: Vec<M::Transaction>) -> Batch<M::Transaction> {
+        let mut state = RecordingState::new(&mut self.tree);
+        let applied = apply_batch(&self.machine, &mut state, &transactions);
+        let batch = Batch {
+            witness: state.into_witness(),
+            transactions,
+        };
+
+        self.output.new_root = self.tree.root();
+        self.output.batches += 1;
+        self.output.applied += applied;
+        self.output.rejected += batch.transactions.len() as u32 - applied;
+        self.batch_digests.push(batch.digest());
+        batch
+    }
+
+    /// The current state.
+    pub fn tree(&self) -> &SparseMerkleTree {
+        &self.tree
+    }
+
+    /// The output the guest commits after applying the batches executed so far.
+    pub fn output(&self) -> &RollupOutput {
+        &self.output
+    }
+
+    /// The digest the guest publishes after applying the batches executed so far, which can be
+    /// compared with `openvm::io::extract_published_digest`.
+    pub fn published_digest(&self) -> [u8; 32] {
+        openvm::io::published_digest(&self.batch_digests)
+    }
+}
@@ -0,0 +1,201 @@
+//! Access to the state from the transition function, and the backends holding the state on the
+//! host and in the guest.
+
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use serde::{Deserialize, Serialize};
+
+use crate::merkle::{hash_leaf, in_range, Digest, MerkleProof, SparseMerkleTree};
+
+/// A key-value store committed to by a root, such as the state of a rollup.
+///
+/// The host and the guest hold the state in different backends, which must make the same calls
+/// succeed: the host in a [RecordingState], which records the witness of every access, and the
+/// guest in a [WitnessedState], which checks every access against the witness. Other commitments,
+/// e.g. a Merkle Patricia trie, can be plugged in by implementing this trait for both sides.
+pub trait StateBackend {
+    /// The commitment to the current state.
+    fn root(&self) -> Digest;
+
+    /// Returns the value of `key`.
+    fn get(&mut self, key: u64) -> Option<Vec<u8>>;
+
+    /// Sets the value of `key`, or removes it if `value` is `None`.
+    fn set(&mut self, key: u64, value: Option<Vec<u8>>);
+}
+
+/// The value of a key, with its proof against the root before the access, as recorded by the host
+/// for every call to [StateBackend::get] and [StateBackend::set].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessWitness {
+    pub value: Option<Vec<u8>>,
+    pub proof: MerkleProof,
+}
+
+/// The state of a [SparseMerkleTree] held by the host, recording the witness of every access.
+pub struct RecordingState<'a> {
+    tree: &'a mut SparseMerkleTree,
+    witness: Vec<AccessWitness>,
+}
+
+impl<'a> RecordingState<'a> {
+    pub fn new(tree: &'a mut SparseMerkleTree) -> Self {
+        Self {
+            tree,
+            witness: Vec::new(),
+        }
+    }
+
+    /// The witnesses of the accesses so far, in order.
+    pub fn into_witness(self) -> Vec<AccessWitness> {
+        self.witness
+    }
+
+    fn record(&mut self, key: u64) -> Option<Vec<u8>> {
+        let value = self.tree.get(key).map(<[u8]>::to_vec);
+        self.witness.push(AccessWitness {
+            value: value.clone(),
+            proof: self.tree.prove(key),
+        });
+        value
+    }
+}
+
+impl StateBackend for RecordingState<'_> {
+    fn root(&self) -> Digest {
+        self.tree.root()
+    }
+
+    fn get(&mut self, key: u64) -> Option<Vec<u8>> {
+        self.record(key)
+    }
+
+    fn set(&mut self, key: u64, value: Option<Vec<u8>>) {
+        self.record(key);
+        self.tree.set(key, value);
+    }
+}
+
+/// The state of a sparse Merkle tree of which the guest only knows the root, checking every
+/// access against the witness recorded by the host.
+///
+/// Every access panics if its witness is missing or does not match the root, so that no proof can
+/// be made for a state the root does not commit to.
+pub struct WitnessedState {
+    depth: usize,
+    root: Digest,
+    witness: alloc::vec::IntoIter<AccessWitness>,
+}
+
+impl WitnessedState {
+    pub fn new(depth: usize, root: Digest, witness: Vec<AccessWitness>) -> Self {
+        Self {
+            depth,
+            root,
+            witness: witness.into_iter(),
+        }
+    }
+
+    /// Whether every access of the witness was made.
+    pub fn is_exhausted(&self) -> bool {
+        self.witness.len() == 0
+    }
+
+    /// Returns the next witness after checking it for `key`.
+    fn verify(&mut self, key: u64) -> AccessWitness {
+        assert!(in_range(self.depth, key), "key {key} is out of range");
+        let witness = self.witness.next().expect("state witness is too short");
+        assert!(
+            witness.proof.siblings.len() == self.depth
+                && witness
+                    .proof
+                    .root(key, &hash_leaf(witness.value.as_deref()))
+                    == self.root,
+            "invalid state witness for key {key}"
+        );
+        witness
+    }
+}
+
+impl StateBackend for WitnessedState {
+    fn root(&self) -> Digest {
+        self.root
+    }
+
+    fn get(&mut self, key: u64) -> Option<Vec<u8>> {
+        self.verify(key).value
+    }
+
+    fn set(&mut self, key: u64, value: Option<Vec<u8>>) {
+        let witness = self.verify(key);
+        self.root = witness.proof.root(key, &hash_leaf(value.as_deref()));
+    }
+}
+
+/// The state as seen by a transaction. Values are read from the backend once and writes are
+/// buffered, so that the writes of a rejected transaction are discarded.
+pub struct State<'a> {
+    backend: &'a mut dyn StateBackend,
+    /// The values read or written by the transaction.
+    entries: BTreeMap<u64, Entry>,
+}
+
+struct Entry {
+    value: Option<Vec<u8>>,
+    written: bool,
+}
+
+impl<'a> State<'a> {
+    pub(crate) fn new(backend: &'a mut dyn StateBackend) -> Self {
+        Self {
+            backend,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the value of `key`.
+    ///
+    /// # Panics
+    ///
+    /// If `key` has no leaf in the state tree.
+    pub fn get(&mut self, key: u64) -> Option<&[u8]> {
+        let backend = &mut *self.backend;
+        self.entries
+            .entry(key)
+            .or_insert_with(|| Entry {
+                value: backend.get(key),
+                written: false,
+            })
+            .value
+            .as_deref()
+    }
+
+    /// Sets the value of `key`.
+    pub fn set(&mut self, key: u64, value: Vec<u8>) {
+        self.write(key, Some(value));
+    }
+
+    /// Removes the value of `key`.
+    pub fn remove(&mut self, key: u64) {
+        self.write(key, None);
+    }
+
+    fn write(&mut self, key: u64, value: Option<Vec<u8>>) {
+        self.entries.insert(
+            key,
+            Entry {
+                value,
+                written: true,
+            },
+        );
+    }
+
+    /// Writes the values written by the transaction to the backend, by increasing key.
+    pub(crate) fn commit(self) {
+        for (key, entry) in self.entries {
+            if entry.written {
+                self.backend.set(key, entry.value);
+            }
+        }
+    }
+}
@@ -0,0 +1,260 @@
+/// The token ledger of `programs/examples/transfers.rs`, which holds the balance of each account
+/// as a little-endian `u64`, with no value for an empty account.
+mod ledger {
+    use openvm_rollup::{State, StateMachine};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Clone, Debug, Serialize, Deserialize)]
+    pub enum Tx {
+        Mint { to: u64, amount: u64 },
+        Transfer { from: u64, to: u64, amount: u64 },
+    }
+
+    #[derive(Debug, PartialEq, Eq)]
+    pub enum Error {
+        InsufficientBalance,
+        Overflow,
+    }
+
+    pub struct Ledger;
+
+    impl StateMachine for Ledger {
+        type Transaction = Tx;
+        type Error = Error;
+        const DEPTH: usize = 16;
+
+        fn apply(&self, state: &mut State, tx: &Tx) -> Result<(), Error> {
+            match *tx {
+                Tx::Mint { to, amount } => credit(state, to, amount),
+                Tx::Transfer { from, to, amount } => {
+                    let debited = balance(state, from).checked_sub(amount);
+                    set_balance(state, from, debited.ok_or(Error::InsufficientBalance)?);
+                    credit(state, to, amount)
+                }
+            }
+        }
+    }
+
+    fn credit(state: &mut State, account: u64, amount: u64) -> Result<(), Error> {
+        let credited = balance(state, account).checked_add(amount);
+        set_balance(state, account, credited.ok_or(Error::Overflow)?);
+        Ok(())
+    }
+
+    pub fn balance(state: &mut State, account: u64) -> u64 {
+        state
+            .get(account)
+            .map_or(0, |value| u64::from_le_bytes(value.try_into().unwrap()))
+    }
+
+    fn set_balance(state: &mut State, account: u64, balance: u64) {
+        if balance == 0 {
+            state.remove(account);
+        } else {
+            state.set(account, balance.to_le_bytes().to_vec());
+        }
+    }
+
+    /// Batches exercising new, emptied and rejected accounts.
+    pub fn batches() -> Vec<Vec<Tx>> {
+        vec![
+            vec![
+                Tx::Mint { to: 1, amount: 100 },
+                Tx::Mint { to: 2, amount: 50 },
+                Tx::Transfer {
+                    from: 1,
+                    to: 3,
+                    amount: 30,
+                },
+            ],
+            vec![
+                // Rejected.
+                Tx::Transfer {
+                    from: 2,
+                    to: 1,
+                    amount: 60,
+                },
+                Tx::Transfer {
+                    from: 2,
+                    to: 2,
+                    amount: 50,
+                },
+                Tx::Transfer {
+                    from: 3,
+                    to: 4,
+                    amount: 30,
+                },
+            ],
+            vec![
+                Tx::Mint {
+                    to: 4,
+                    amount: u64::MAX - 30,
+                },
+                // Rejected after debiting account 1, which is reverted.
+                Tx::Transfer {
+                    from: 1,
+                    to: 4,
+                    amount: 10,
+                },
+            ],
+        ]
+    }
+}
+
+mod guest_tests {
+    use eyre::Result;
+    use openvm_circuit::{arch::instructions::exe::VmExe, utils::air_test_with_min_segments};
+    use openvm_rollup::{Batch, RollupOutput, Sequencer, SparseMerkleTree, StateMachine};
+    use openvm_sdk::{
+        config::{AppConfig, SdkVmConfig},
+        Sdk, StdIn,
+    };
+    use openvm_stark_backend::p3_field::PrimeField32;
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::FromElf;
+
+    use super::ledger::{batches, Ledger};
+
+    #[test]
+    fn test_transfers() -> Result<()> {
+        let config =
+            toml::from_str::<AppConfig<SdkVmConfig>>(include_str!("programs/openvm_rollup.toml"))?
+                .app_vm_config;
+        let elf = build_example_program_at_path(
+            get_programs_dir!("tests/programs"),
+            "transfers",
+            &config,
+        )?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+
+        let mut sequencer = Sequencer::new(Ledger, SparseMerkleTree::new(Ledger::DEPTH));
+        let mut stdin = StdIn::default();
+        stdin.write(&sequencer.tree().root());
+        let batches: Vec<Batch<_>> = batches()
+            .into_iter()
+            .map(|transactions| sequencer.execute(transactions))
+            .collect();
+        for batch in &batches {
+            stdin.write(batch);
+        }
+
+        let sdk = Sdk::new();
+        let public_values = sdk.execute(openvm_exe.clone(), config.clone(), stdin.clone())?;
+        let bytes: Vec<u8> = public_values
+            .iter()
+            .map(|v| v.as_canonical_u32() as u8)
+            .collect();
+        let journal = openvm::io::journal::<RollupOutput>(&bytes)?;
+        assert!(journal.matches(sequencer.output()));
+        sdk.verify_published_bytes(&public_values, batches.iter().map(Batch::digest))?;
+        assert_eq!(
+            openvm::io::extract_published_digest(&bytes),
+            Some(sequencer.published_digest())
+        );
+
+        // The guest pauses after each batch.
+        air_test_with_min_segments(config, openvm_exe, stdin, batches.len());
+        Ok(())
+    }
+}
+
+mod host_tests {
+    use openvm_rollup::{
+        apply_batch,
+        merkle::{hash_leaf, EMPTY_DIGEST},
+        Batch, SparseMerkleTree, StateBackend, StateMachine, WitnessedState,
+    };
+
+    use super::ledger::{batches, Ledger, Tx};
+
+    /// Replays `batch` against `root` as the guest does, and returns the new root and the number
+    /// of transactions applied.
+    fn replay(root: [u8; 32], batch: Batch<Tx>) -> ([u8; 32], u32) {
+        let mut state = WitnessedState::new(Ledger::DEPTH, root, batch.witness);
+        let applied = apply_batch(&Ledger, &mut state, &batch.transactions);
+        assert!(state.is_exhausted());
+        (state.root(), applied)
+    }
+
+    fn balance(tree: &SparseMerkleTree, account: u64) -> u64 {
+        tree.get(account)
+            .map_or(0, |value| u64::from_le_bytes(value.try_into().unwrap()))
+    }
+
+    #[test]
+    fn test_merkle_proofs() {
+        let mut tree = SparseMerkleTree::new(8);
+        let empty_root = tree.root();
+        tree.set(0, Some(b"zero".to_vec()));
+        tree.set(255, Some(b"max".to_vec()));
+        tree.set(77, Some(vec![]));
+        for (key, value) in [(0, Some(&b"zero"[..])), (77, Some(&[][..])), (1, None)] {
+            assert_eq!(tree.get(key), value);
+            let proof = tree.prove(key);
+            assert_eq!(proof.siblings.len(), 8);
+            assert_eq!(proof.root(key, &hash_leaf(value)), tree.root());
+            assert_ne!(proof.root(key ^ 1, &hash_leaf(value)), tree.root());
+        }
+        // An empty value is distinct from no value.
+        assert_ne!(hash_leaf(Some(&[])), EMPTY_DIGEST);
+
+        // Removing all values restores the empty tree.
+        for key in [0, 255, 77] {
+            tree.set(key, None);
+        }
+        assert_eq!(tree.root(), empty_root);
+        assert_eq!(SparseMerkleTree::new(64).prove(u64::MAX).siblings.len(), 64);
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut tree = SparseMerkleTree::new(Ledger::DEPTH);
+        let mut sequencer = openvm_rollup::Sequencer::new(Ledger, tree.clone());
+        let mut root = tree.root();
+        let mut applied = vec![];
+        for transactions in batches() {
+            let batch = sequencer.execute(transactions);
+            let (new_root, count) = replay(root, batch);
+            assert_eq!(new_root, sequencer.tree().root());
+            root = new_root;
+            applied.push(count);
+        }
+        assert_eq!(applied, [3, 2, 1]);
+
+        let output = sequencer.output();
+        assert_eq!(output.prev_root, tree.root());
+        assert_eq!(output.new_root, root);
+        assert_eq!((output.batches, output.applied, output.rejected), (3, 6, 2));
+
+        tree = sequencer.tree().clone();
+        assert_eq!(
+            [1, 2, 3, 4].map(|account| balance(&tree, account)),
+            [70, 50, 0, u64::MAX]
+        );
+        // Emptied accounts have no value.
+        assert_eq!(tree.get(3), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid state witness")]
+    fn test_tampered_witness() {
+        let tree = SparseMerkleTree::new(Ledger::DEPTH);
+        let root = tree.root();
+        let mut sequencer = openvm_rollup::Sequencer::new(Ledger, tree);
+        let mut batch = sequencer.execute(batches().remove(0));
+        // Claim that account 1 already holds a balance.
+        batch.witness[0].value = Some(1000u64.to_le_bytes().to_vec());
+        replay(root, batch);
+    }
+
+    #[test]
+    #[should_panic(expected = "state witness is too short")]
+    fn test_truncated_witness() {
+        let tree = SparseMerkleTree::new(Ledger::DEPTH);
+        let root = tree.root();
+        let mut sequencer = openvm_rollup::Sequencer::new(Ledger, tree);
+        let mut batch = sequencer.execute(batches().remove(0));
+        batch.witness.pop();
+        replay(root, batch);
+    }
+}
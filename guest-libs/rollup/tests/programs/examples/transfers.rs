@@ -0,0 +1,65 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use openvm_rollup::{Rollup, State, StateMachine};
+use serde::{Deserialize, Serialize};
+
+openvm::entry!(main);
+
+/// A token ledger holding the balance of each account as a little-endian `u64`, with no value
+/// for an empty account.
+struct Ledger;
+
+#[derive(Serialize, Deserialize)]
+enum Tx {
+    Mint { to: u64, amount: u64 },
+    Transfer { from: u64, to: u64, amount: u64 },
+}
+
+enum Error {
+    InsufficientBalance,
+    Overflow,
+}
+
+impl StateMachine for Ledger {
+    type Transaction = Tx;
+    type Error = Error;
+    const DEPTH: usize = 16;
+
+    fn apply(&self, state: &mut State, tx: &Tx) -> Result<(), Error> {
+        match *tx {
+            Tx::Mint { to, amount } => credit(state, to, amount),
+            Tx::Transfer { from, to, amount } => {
+                let debited = balance(state, from).checked_sub(amount);
+                set_balance(state, from, debited.ok_or(Error::InsufficientBalance)?);
+                credit(state, to, amount)
+            }
+        }
+    }
+}
+
+fn credit(state: &mut State, account: u64, amount: u64) -> Result<(), Error> {
+    let credited = balance(state, account).checked_add(amount);
+    set_balance(state, account, credited.ok_or(Error::Overflow)?);
+    Ok(())
+}
+
+fn balance(state: &mut State, account: u64) -> u64 {
+    state
+        .get(account)
+        .map_or(0, |value| u64::from_le_bytes(value.try_into().unwrap()))
+}
+
+fn set_balance(state: &mut State, account: u64, balance: u64) {
+    if balance == 0 {
+        state.remove(account);
+    } else {
+        state.set(account, balance.to_le_bytes().to_vec());
+    }
+}
+
+pub fn main() {
+    Rollup::new(Ledger).with_checkpoints().run();
+}
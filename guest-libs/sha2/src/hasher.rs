@@ -0,0 +1,124 @@
+use alloc::vec::Vec;
+
+use digest::{
+    consts::{U128, U32, U48, U64},
+    crypto_common::BlockSizeUser,
+    FixedOutput, FixedOutputReset, HashMarker, Output, OutputSizeUser, Reset, Update,
+};
+
+use crate::{set_sha256, set_sha384, set_sha512};
+
+macro_rules! buffered_hasher {
+    (
+        $name:ident,
+        $set_hash:ident,
+        $output_size:expr,
+        $output_size_type:ty,
+        $block_size_type:ty,
+        $doc:literal
+    ) => {
+        #[doc = $doc]
+        ///
+        /// Mirrors the `new`/`update`/`finalize` interface of the `sha2` crate. Since the
+        /// VM hashes an entire message in a single instruction, input is buffered until
+        /// [`Self::finalize`] is called. It implements the `digest` traits, and hence
+        /// `digest::Digest`, so it can be passed to code generic over the hash function.
+        #[derive(Clone, Debug, Default)]
+        pub struct $name {
+            buffer: Vec<u8>,
+        }
+
+        impl $name {
+            pub fn new() -> Self {
+                Self::default()
+            }
+
+            /// Appends `data` to the message being hashed.
+            pub fn update(&mut self, data: impl AsRef<[u8]>) {
+                self.buffer.extend_from_slice(data.as_ref());
+            }
+
+            /// Appends `data` to the message being hashed, returning `self` for chaining.
+            pub fn chain_update(mut self, data: impl AsRef<[u8]>) -> Self {
+                self.update(data);
+                self
+            }
+
+            /// Returns the hash of all data passed to [`Self::update`].
+            pub fn finalize(self) -> [u8; $output_size] {
+                let mut output = [0u8; $output_size];
+                $set_hash(&self.buffer, &mut output);
+                output
+            }
+
+            /// Returns the hash of `data` in one shot.
+            pub fn digest(data: impl AsRef<[u8]>) -> [u8; $output_size] {
+                let mut output = [0u8; $output_size];
+                $set_hash(data.as_ref(), &mut output);
+                output
+            }
+        }
+
+        impl HashMarker for $name {}
+
+        impl OutputSizeUser for $name {
+            type OutputSize = $output_size_type;
+        }
+
+        impl BlockSizeUser for $name {
+            type BlockSize = $block_size_type;
+        }
+
+        impl Update for $name {
+            fn update(&mut self, data: &[u8]) {
+                $name::update(self, data);
+            }
+        }
+
+        impl FixedOutput for $name {
+            fn finalize_into(self, out: &mut Output<Self>) {
+                out.copy_from_slice(&$name::finalize(self));
+            }
+        }
+
+        impl Reset for $name {
+            fn reset(&mut self) {
+                self.buffer.clear();
+            }
+        }
+
+        impl FixedOutputReset for $name {
+            fn finalize_into_reset(&mut self, out: &mut Output<Self>) {
+                let mut output = [0u8; $output_size];
+                $set_hash(&self.buffer, &mut output);
+                out.copy_from_slice(&output);
+                self.buffer.clear();
+            }
+        }
+    };
+}
+
+buffered_hasher!(
+    Sha256,
+    set_sha256,
+    32,
+    U32,
+    U64,
+    "Incremental sha256 hasher."
+);
+buffered_hasher!(
+    Sha512,
+    set_sha512,
+    64,
+    U64,
+    U128,
+    "Incremental sha512 hasher."
+);
+buffered_hasher!(
+    Sha384,
+    set_sha384,
+    48,
+    U48,
+    U128,
+    "Incremental sha384 hasher."
+);
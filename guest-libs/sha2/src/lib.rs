@@ -1,5 +1,24 @@
 #![no_std]
 
+extern crate alloc;
+
+#[cfg(target_os = "zkvm")]
+use core::mem::MaybeUninit;
+
+mod hasher;
+pub use hasher::*;
+
+/// Inputs which the SHA-2 instructions cannot read in place and which fit in this many bytes once
+/// padded are copied to an aligned buffer on the stack. Longer ones are copied to the heap.
+///
+/// The instructions read whole padded blocks starting at the input, in words of 16 (SHA-256) or 32
+/// (SHA-512 and SHA-384) bytes, so they read up to a block past its end. An input is copied if it
+/// is not 4-byte aligned, since the VM then splits every word of memory it reads into bytes at the
+/// cost of several rows of memory access adapters per word, or if its last block would extend past
+/// the end of guest memory. Copying costs roughly one instruction per byte. A heap copy is never
+/// freed by the default bump allocator.
+pub const SHA2_BOUNCE_SIZE: usize = 512;
+
 /// The sha256 cryptographic hash function.
 #[inline(always)]
 pub fn sha256(input: &[u8]) -> [u8; 32] {
@@ -18,11 +37,89 @@ pub fn set_sha256(input: &[u8], output: &mut [u8; 32]) {
         output.copy_from_slice(hasher.finalize().as_ref());
     }
     #[cfg(target_os = "zkvm")]
+    with_readable_input(input, 64, 8, |ptr| {
+        openvm_sha256_guest::zkvm_sha256_impl(ptr, input.len(), output.as_mut_ptr() as *mut u8);
+    });
+}
+
+/// The sha512 cryptographic hash function.
+#[inline(always)]
+pub fn sha512(input: &[u8]) -> [u8; 64] {
+    let mut output = [0u8; 64];
+    set_sha512(input, &mut output);
+    output
+}
+
+/// Sets `output` to the sha512 hash of `input`.
+pub fn set_sha512(input: &[u8], output: &mut [u8; 64]) {
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        use sha2::{Digest, Sha512};
+        let mut hasher = Sha512::new();
+        hasher.update(input);
+        output.copy_from_slice(hasher.finalize().as_ref());
+    }
+    #[cfg(target_os = "zkvm")]
+    with_readable_input(input, 128, 16, |ptr| {
+        openvm_sha256_guest::zkvm_sha512_impl(ptr, input.len(), output.as_mut_ptr() as *mut u8);
+    });
+}
+
+/// The sha384 cryptographic hash function.
+#[inline(always)]
+pub fn sha384(input: &[u8]) -> [u8; 48] {
+    let mut output = [0u8; 48];
+    set_sha384(input, &mut output);
+    output
+}
+
+/// Sets `output` to the sha384 hash of `input`.
+pub fn set_sha384(input: &[u8], output: &mut [u8; 48]) {
+    #[cfg(not(target_os = "zkvm"))]
+    {
+        use sha2::{Digest, Sha384};
+        let mut hasher = Sha384::new();
+        hasher.update(input);
+        output.copy_from_slice(hasher.finalize().as_ref());
+    }
+    #[cfg(target_os = "zkvm")]
     {
-        openvm_sha256_guest::zkvm_sha256_impl(
-            input.as_ptr(),
-            input.len(),
-            output.as_mut_ptr() as *mut u8,
-        );
+        // The VM writes the entire 64-byte state, of which the hash is the first 48 bytes
+        let mut state = [0u8; 64];
+        with_readable_input(input, 128, 16, |ptr| {
+            openvm_sha256_guest::zkvm_sha384_impl(ptr, input.len(), state.as_mut_ptr() as *mut u8);
+        });
+        output.copy_from_slice(&state[..48]);
+    }
+}
+
+/// Calls `hash` with a pointer to `input`, or to a copy of it if the SHA-2 instruction with the
+/// given block size and length field size, in bytes, cannot read it in place. See
+/// [SHA2_BOUNCE_SIZE].
+#[cfg(target_os = "zkvm")]
+#[inline(always)]
+fn with_readable_input(
+    input: &[u8],
+    block_size: usize,
+    len_size: usize,
+    hash: impl FnOnce(*const u8),
+) {
+    // The message is followed by a 0x80 byte and its length in bits, padded to whole blocks.
+    let padded_len = (input.len() + 1 + len_size).div_ceil(block_size) * block_size;
+    let addr = input.as_ptr() as usize;
+    if addr % 4 == 0 && addr + padded_len <= openvm_platform::memory::GUEST_MAX_MEM {
+        hash(input.as_ptr());
+    } else if padded_len <= SHA2_BOUNCE_SIZE {
+        let mut buffer = MaybeUninit::<[u32; SHA2_BOUNCE_SIZE / 4]>::uninit();
+        let ptr = buffer.as_mut_ptr() as *mut u8;
+        // SAFETY: the buffer holds at least `input.len()` bytes and does not overlap `input`.
+        unsafe { core::ptr::copy_nonoverlapping(input.as_ptr(), ptr, input.len()) };
+        hash(ptr);
+    } else {
+        let mut buffer = alloc::vec::Vec::<u32>::with_capacity(padded_len / 4);
+        let ptr = buffer.as_mut_ptr() as *mut u8;
+        // SAFETY: the buffer holds `padded_len >= input.len()` bytes and does not overlap `input`.
+        unsafe { core::ptr::copy_nonoverlapping(input.as_ptr(), ptr, input.len()) };
+        hash(ptr);
     }
 }
@@ -0,0 +1,51 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use digest::Digest;
+use openvm_sha2::{sha256, sha384, sha512, Sha256, Sha384, Sha512};
+
+openvm::entry!(main);
+
+// Hashes `data` in two halves through the `Digest` trait, as code generic over the hash function
+// would.
+fn digest_in_halves<D: Digest>(data: &[u8]) -> Vec<u8> {
+    let (first, second) = data.split_at(data.len() / 2);
+    let mut hasher = D::new();
+    Digest::update(&mut hasher, first);
+    Digest::update(&mut hasher, second);
+    hasher.finalize().to_vec()
+}
+
+pub fn main() {
+    for len in [0, 1, 55, 56, 64, 111, 112, 128, 300] {
+        let input: Vec<u8> = (0..len).map(|i| (i * 7 + 3) as u8).collect();
+        let input = black_box(input);
+
+        if digest_in_halves::<Sha256>(&input) != sha256(&input) {
+            panic!();
+        }
+        if digest_in_halves::<Sha512>(&input) != sha512(&input) {
+            panic!();
+        }
+        if digest_in_halves::<Sha384>(&input) != sha384(&input) {
+            panic!();
+        }
+        if <Sha256 as Digest>::digest(&input).as_slice() != sha256(&input) {
+            panic!();
+        }
+
+        // Finalizing with reset leaves the hasher ready for a new message.
+        let mut hasher = <Sha256 as Digest>::new();
+        Digest::update(&mut hasher, b"discarded");
+        let _ = hasher.finalize_reset();
+        Digest::update(&mut hasher, &input);
+        if hasher.finalize().as_slice() != sha256(&input) {
+            panic!();
+        }
+    }
+}
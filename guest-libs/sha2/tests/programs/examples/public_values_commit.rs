@@ -0,0 +1,24 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use hex::FromHex;
+use openvm::io::public_values_commit;
+use openvm_sha2::sha256;
+
+openvm::entry!(main);
+
+pub fn main() {
+    // The commitment of the SDK to 100 user public values 0, 1, ..., 99 with the SHA-256 public
+    // values hasher, which pads them to four leaves.
+    let public_values: Vec<u8> = (0..100).collect();
+    let expected =
+        Vec::from_hex("0ad0ba6325cd9bc674194b893639e4ea3f375c6fa65794f1c03d05822681c07e").unwrap();
+    if public_values_commit(black_box(&public_values), sha256) != *expected {
+        panic!();
+    }
+}
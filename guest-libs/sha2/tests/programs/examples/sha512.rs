@@ -0,0 +1,57 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use hex::FromHex;
+use openvm_sha2::{sha384, sha512, Sha384, Sha512};
+
+openvm::entry!(main);
+
+pub fn main() {
+    let sha512_test_vectors = [
+        ("", "cf83e1357eefb8bdf1542850d66d8007d620e4050b5715dc83f4a921d36ce9ce47d0d13c5d85f2b0ff8318d2877eec2f63b931bd47417a81a538327af927da3e"),
+        ("98c1c0bdb7d5fea9a88859f06c6c439f", "eb576959c531f116842c0cc915a29c8f71d7a285c894c349b83469002ef093d51f9f14ce4248488bff143025e47ed27c12badb9cd43779cb147408eea062d583"),
+        ("5b58f4163e248467cc1cd3eecafe749e8e2baaf82c0f63af06df0526347d7a11327463c115210a46b6740244eddf370be89c", "a20d5fb14814d045a7d2861e80d2b688f1cd1daaba69e6bb1cc5233f514141ea4623b3373af702e78e3ec5dc8c1b716a37a9a2f5fbc9493b9df7043f5e99a8da"),
+        ("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", "77ddd3a542e530fd047b8977c657ba6ce72f1492e360b2b2212cd264e75ec03882e4ff0525517ab4207d14c70c2259ba88d4d335ee0e7e20543d22102ab1788c"),
+        ("abababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababab", "849783b0ae742d455e67e8146c38843b5eafbef1f45c93812ec26ddad51b65f3c9e70afc8e2dc543b577fce0e7e445b327bb52df0faa6eeadf94fa3d34a63b1c"),
+    ];
+    for (input, expected_output) in sha512_test_vectors.iter() {
+        let input = Vec::from_hex(input).unwrap();
+        let expected_output = Vec::from_hex(expected_output).unwrap();
+        let output = sha512(&black_box(&input));
+        if output != *expected_output {
+            panic!();
+        }
+        let (first, second) = input.split_at(input.len() / 2);
+        let output = Sha512::new()
+            .chain_update(first)
+            .chain_update(second)
+            .finalize();
+        if output != *expected_output {
+            panic!();
+        }
+    }
+
+    let sha384_test_vectors = [
+        ("", "38b060a751ac96384cd9327eb1b1e36a21fdb71114be07434c0cc7bf63f6e1da274edebfe76f65fbd51ad2f14898b95b"),
+        ("98c1c0bdb7d5fea9a88859f06c6c439f", "63e3061aab01f335ea3a4e617b9d14af9b63a5240229164ee962f6d5335ff25f0f0bf8e46723e83c41b9d17413b6a3c7"),
+        ("5b58f4163e248467cc1cd3eecafe749e8e2baaf82c0f63af06df0526347d7a11327463c115210a46b6740244eddf370be89c", "eac4b72b0540486bc088834860873338e31e9e4062532bf509191ef63b9298c67db5654a28fe6f07e4cc6ff466d1be24"),
+        ("000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000", "435770712c611be7293a66dd0dc8d1450dc7ff7337bfe115bf058ef2eb9bed09cee85c26963a5bcc0905dc2df7cc6a76"),
+        ("abababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababababab", "a94e8f52f202458a5d86fb8f41329ba8aefe8406ef35674a920de2959a71bbb0a10dd8e51e8b6f14a616526a7531620e"),
+    ];
+    for (input, expected_output) in sha384_test_vectors.iter() {
+        let input = Vec::from_hex(input).unwrap();
+        let expected_output = Vec::from_hex(expected_output).unwrap();
+        let output = sha384(&black_box(&input));
+        if output != *expected_output {
+            panic!();
+        }
+        if Sha384::digest(&input) != *expected_output {
+            panic!();
+        }
+    }
+}
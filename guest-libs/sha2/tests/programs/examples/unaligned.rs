@@ -0,0 +1,25 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::hint::black_box;
+
+use openvm_sha2::{sha256, sha384, sha512, SHA2_BOUNCE_SIZE};
+
+openvm::entry!(main);
+
+pub fn main() {
+    // Lengths which are copied to the stack and to the heap when unaligned.
+    for len in [0, 1, 55, 56, 200, SHA2_BOUNCE_SIZE + 3] {
+        let buffer: Vec<u8> = (0..len + 3).map(|i| (i % 251) as u8).collect();
+        for offset in 1..4 {
+            let unaligned = &buffer[offset..offset + len];
+            let aligned = unaligned.to_vec();
+            assert_eq!(sha256(black_box(unaligned)), sha256(&aligned));
+            assert_eq!(sha512(black_box(unaligned)), sha512(&aligned));
+            assert_eq!(sha384(black_box(unaligned)), sha384(&aligned));
+        }
+    }
+}
@@ -0,0 +1,171 @@
+//! Verification of the certificate chain of the server, see
+//! [RFC 5280 Section 6](https://datatracker.ietf.org/doc/html/rfc5280#section-6).
+
+use alloc::vec::Vec;
+
+use x509_cert::{
+    der::{
+        oid::{
+            db::rfc5912::{
+                ECDSA_WITH_SHA_256, ECDSA_WITH_SHA_384, ID_KP_SERVER_AUTH,
+                SHA_256_WITH_RSA_ENCRYPTION, SHA_384_WITH_RSA_ENCRYPTION,
+            },
+            AssociatedOid,
+        },
+        Decode, Encode,
+    },
+    ext::pkix::{name::GeneralName, BasicConstraints, ExtendedKeyUsage, KeyUsage, SubjectAltName},
+    Certificate,
+};
+
+use crate::{
+    signature::{PublicKey, SignatureScheme},
+    Error, Result, Verifier,
+};
+
+/// Verifies the DER-encoded certificate chain `chain` sent by the server, starting with the
+/// certificate of the server, and returns the public key of the server.
+pub(crate) fn verify_chain(verifier: &Verifier, chain: &[&[u8]]) -> Result<PublicKey> {
+    let certs = chain
+        .iter()
+        .map(|der| Certificate::from_der(der).map_err(|_| Error::BadCertificate))
+        .collect::<Result<Vec<_>>>()?;
+    let leaf = certs.first().ok_or(Error::BadCertificate)?;
+    if !matches_server_name(leaf, &verifier.server_name)? {
+        return Err(Error::NameMismatch);
+    }
+
+    // The chain is followed from the leaf until a certificate is a trust anchor or is issued by
+    // one. Certificates after that are ignored.
+    for (depth, cert) in certs.iter().enumerate() {
+        check_certificate(cert, verifier.time, depth)?;
+        for (der, root) in &verifier.roots {
+            if chain[depth] == der.as_slice() {
+                return PublicKey::from_spki(&leaf.tbs_certificate.subject_public_key_info);
+            }
+            if root.tbs_certificate.subject == cert.tbs_certificate.issuer
+                && check_ca(root, depth).is_ok()
+                && verify_signature(root, cert).is_ok()
+            {
+                return PublicKey::from_spki(&leaf.tbs_certificate.subject_public_key_info);
+            }
+        }
+        let issuer = certs.get(depth + 1).ok_or(Error::UnknownIssuer)?;
+        if issuer.tbs_certificate.subject != cert.tbs_certificate.issuer {
+            return Err(Error::UnknownIssuer);
+        }
+        verify_signature(issuer, cert)?;
+    }
+    unreachable!()
+}
+
+/// Checks the validity period and the extensions of the certificate at `depth` in the chain.
+fn check_certificate(cert: &Certificate, time: u64, depth: usize) -> Result<()> {
+    let validity = &cert.tbs_certificate.validity;
+    if time < validity.not_before.to_unix_duration().as_secs()
+        || time > validity.not_after.to_unix_duration().as_secs()
+    {
+        return Err(Error::CertificateExpired);
+    }
+    let known = [
+        BasicConstraints::OID,
+        KeyUsage::OID,
+        ExtendedKeyUsage::OID,
+        SubjectAltName::OID,
+    ];
+    let extensions = cert
+        .tbs_certificate
+        .extensions
+        .as_deref()
+        .unwrap_or_default();
+    if extensions
+        .iter()
+        .any(|ext| ext.critical && !known.contains(&ext.extn_id))
+    {
+        return Err(Error::Unsupported);
+    }
+    if depth > 0 {
+        check_ca(cert, depth - 1)
+    } else {
+        check_server(cert)
+    }
+}
+
+/// Checks that the key of the server certificate `cert` may sign the handshake and authenticate a
+/// TLS server. Absent key usage extensions do not restrict the key.
+fn check_server(cert: &Certificate) -> Result<()> {
+    match cert.tbs_certificate.get::<KeyUsage>() {
+        Ok(Some((_, usage))) if !usage.digital_signature() => return Err(Error::BadCertificate),
+        Ok(_) => {}
+        Err(_) => return Err(Error::BadCertificate),
+    }
+    match cert.tbs_certificate.get::<ExtendedKeyUsage>() {
+        Ok(Some((_, ExtendedKeyUsage(purposes)))) if !purposes.contains(&ID_KP_SERVER_AUTH) => {
+            Err(Error::BadCertificate)
+        }
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::BadCertificate),
+    }
+}
+
+/// Checks that `cert` may issue certificates, with `intermediates` certificates between it and
+/// the certificate of the server. If the key usage of `cert` is restricted, it must allow signing
+/// certificates and, as the CA/Browser Forum Baseline Requirements demand of CAs, CRLs.
+fn check_ca(cert: &Certificate, intermediates: usize) -> Result<()> {
+    match cert.tbs_certificate.get::<BasicConstraints>() {
+        Ok(Some((_, constraints)))
+            if constraints.ca
+                && constraints
+                    .path_len_constraint
+                    .is_none_or(|len| intermediates <= len as usize) => {}
+        _ => return Err(Error::BadCertificate),
+    }
+    match cert.tbs_certificate.get::<KeyUsage>() {
+        Ok(Some((_, usage))) if !(usage.key_cert_sign() && usage.crl_sign()) => {
+            Err(Error::BadCertificate)
+        }
+        Ok(_) => Ok(()),
+        Err(_) => Err(Error::BadCertificate),
+    }
+}
+
+/// Verifies the signature of `cert` with the public key of `issuer`.
+fn verify_signature(issuer: &Certificate, cert: &Certificate) -> Result<()> {
+    let scheme = match cert.signature_algorithm.oid {
+        ECDSA_WITH_SHA_256 => SignatureScheme::EcdsaSha256,
+        ECDSA_WITH_SHA_384 => SignatureScheme::EcdsaSha384,
+        SHA_256_WITH_RSA_ENCRYPTION => SignatureScheme::RsaPkcs1Sha256,
+        SHA_384_WITH_RSA_ENCRYPTION => SignatureScheme::RsaPkcs1Sha384,
+        _ => return Err(Error::Unsupported),
+    };
+    let key = PublicKey::from_spki(&issuer.tbs_certificate.subject_public_key_info)?;
+    let tbs = cert
+        .tbs_certificate
+        .to_der()
+        .map_err(|_| Error::BadCertificate)?;
+    let signature = cert.signature.as_bytes().ok_or(Error::BadCertificate)?;
+    key.verify(scheme, &tbs, signature)
+}
+
+/// Whether one of the DNS names of the subject alternative name extension of `cert` matches
+/// `server_name`. Wildcards are only allowed as the leftmost label, and match one label.
+fn matches_server_name(cert: &Certificate, server_name: &str) -> Result<bool> {
+    let names = match cert.tbs_certificate.get::<SubjectAltName>() {
+        Ok(Some((_, SubjectAltName(names)))) => names,
+        Ok(None) => return Ok(false),
+        Err(_) => return Err(Error::BadCertificate),
+    };
+    Ok(names.iter().any(|name| match name {
+        GeneralName::DnsName(name) => {
+            let name = name.as_str();
+            match (name.strip_prefix("*."), server_name.split_once('.')) {
+                (Some(suffix), Some((label, rest))) => {
+                    !label.is_empty() && suffix.eq_ignore_ascii_case(rest)
+                }
+                (Some(_), None) => false,
+                (None, _) => name.eq_ignore_ascii_case(server_name),
+            }
+        }
+        _ => false,
+    }))
+}
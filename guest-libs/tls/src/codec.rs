@@ -0,0 +1,67 @@
+use crate::{Error, Result};
+
+/// Reads the big-endian integers and the length-prefixed vectors of the TLS presentation
+/// language, see [RFC 8446 Section 3](https://datatracker.ietf.org/doc/html/rfc8446#section-3).
+pub(crate) struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.data.len() {
+            return Err(Error::Decode);
+        }
+        let (taken, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(taken)
+    }
+
+    pub fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn u16(&mut self) -> Result<u16> {
+        let bytes = self.take(2)?;
+        Ok(u16::from_be_bytes([bytes[0], bytes[1]]))
+    }
+
+    pub fn u24(&mut self) -> Result<usize> {
+        let bytes = self.take(3)?;
+        Ok(u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]) as usize)
+    }
+
+    /// Reads a vector with a length prefix of one byte.
+    pub fn vec_u8(&mut self) -> Result<&'a [u8]> {
+        let len = self.u8()? as usize;
+        self.take(len)
+    }
+
+    /// Reads a vector with a length prefix of two bytes.
+    pub fn vec_u16(&mut self) -> Result<&'a [u8]> {
+        let len = self.u16()? as usize;
+        self.take(len)
+    }
+
+    /// Reads a vector with a length prefix of three bytes.
+    pub fn vec_u24(&mut self) -> Result<&'a [u8]> {
+        let len = self.u24()?;
+        self.take(len)
+    }
+
+    /// Fails if there are bytes left, e.g. at the end of a message.
+    pub fn finish(self) -> Result<()> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::Decode)
+        }
+    }
+}
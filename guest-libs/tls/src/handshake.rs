@@ -0,0 +1,303 @@
+//! The handshake, see
+//! [RFC 8446 Section 4](https://datatracker.ietf.org/doc/html/rfc8446#section-4).
+
+use alloc::vec::Vec;
+
+use openvm_algebra_guest::IntMod;
+use openvm_ecc_guest::{weierstrass::WeierstrassPoint, CyclicGroup, Group};
+use openvm_p256::{ecdsa::VerifyingKey, P256Point, P256Scalar};
+use openvm_sha2::sha256;
+
+use crate::{
+    cert::verify_chain,
+    codec::Reader,
+    key_schedule::{finished_verify_data, KeySchedule, HASH_LEN},
+    record::Flow,
+    signature::SignatureScheme,
+    Error, Result, Verifier,
+};
+
+const CLIENT_HELLO: u8 = 1;
+const SERVER_HELLO: u8 = 2;
+const NEW_SESSION_TICKET: u8 = 4;
+const ENCRYPTED_EXTENSIONS: u8 = 8;
+const CERTIFICATE: u8 = 11;
+const CERTIFICATE_REQUEST: u8 = 13;
+const CERTIFICATE_VERIFY: u8 = 15;
+const FINISHED: u8 = 20;
+const KEY_UPDATE: u8 = 24;
+
+const TLS12: u16 = 0x0303;
+const TLS13: u16 = 0x0304;
+const TLS_AES_128_GCM_SHA256: u16 = 0x1301;
+const SECP256R1: u16 = 0x0017;
+const ECDSA_SECP256R1_SHA256: u16 = 0x0403;
+const RSA_PSS_RSAE_SHA256: u16 = 0x0804;
+
+const SUPPORTED_VERSIONS: u16 = 43;
+const KEY_SHARE: u16 = 51;
+
+/// The random of a ServerHello which is a HelloRetryRequest.
+const HELLO_RETRY_REQUEST_RANDOM: [u8; 32] = [
+    0xcf, 0x21, 0xad, 0x74, 0xe5, 0x9a, 0x61, 0x11, 0xbe, 0x1d, 0x8c, 0x02, 0x1e, 0x65, 0xb8, 0x91,
+    0xc2, 0xa2, 0x11, 0x16, 0x7a, 0xbb, 0x8c, 0x5e, 0x07, 0x9e, 0x09, 0xe2, 0xc8, 0xa8, 0x33, 0x9c,
+];
+
+/// The length of an uncompressed SEC1 encoding of a P-256 point.
+const KEY_SHARE_LEN: usize = 65;
+
+/// The application data sent in one direction of a session.
+pub(crate) struct Direction {
+    pub data: Vec<u8>,
+    /// Whether the data ended with a close_notify alert.
+    pub closed: bool,
+}
+
+/// Verifies the handshake of the session and returns the application data sent by the client and
+/// by the server.
+pub(crate) fn verify(
+    verifier: &Verifier,
+    secret: &[u8; 32],
+    client_stream: &[u8],
+    server_stream: &[u8],
+) -> Result<(Direction, Direction)> {
+    let mut client = Flow::new(client_stream);
+    let mut server = Flow::new(server_stream);
+    let mut transcript = Vec::new();
+
+    let secret = P256Scalar::from_be_bytes(secret)
+        .filter(|secret| *secret != <P256Scalar as IntMod>::ZERO)
+        .ok_or(Error::IllegalParameter)?;
+    let client_hello = client.next_handshake_message()?;
+    let session_id = read_client_hello(&client_hello, &public_key(&secret))?;
+    transcript.extend_from_slice(&client_hello);
+
+    let server_hello = server.next_handshake_message()?;
+    let server_share = read_server_hello(&server_hello, session_id)?;
+    transcript.extend_from_slice(&server_hello);
+
+    // The shared secret is the x-coordinate of the product of the private key and the key share
+    // of the server, see RFC 8446 Section 7.4.2.
+    let shared_point = server_share * secret;
+    if shared_point.is_identity() {
+        return Err(Error::IllegalParameter);
+    }
+    let key_schedule = KeySchedule::new(shared_point.x().to_be_bytes().as_ref());
+    let [client_secret, server_secret] =
+        key_schedule.handshake_traffic_secrets(&sha256(&transcript));
+    server.set_traffic_secret(&server_secret)?;
+    client.set_traffic_secret(&client_secret)?;
+
+    let encrypted_extensions = server.next_handshake_message()?;
+    let mut reader = message(&encrypted_extensions, ENCRYPTED_EXTENSIONS)?;
+    read_extensions(reader.vec_u16()?, |_, _| Ok(()))?;
+    reader.finish()?;
+    transcript.extend_from_slice(&encrypted_extensions);
+
+    let certificate = server.next_handshake_message()?;
+    if certificate.first() == Some(&CERTIFICATE_REQUEST) {
+        return Err(Error::Unsupported);
+    }
+    let server_key = verify_chain(verifier, &read_certificate(&certificate)?)?;
+    transcript.extend_from_slice(&certificate);
+
+    let certificate_verify = server.next_handshake_message()?;
+    let mut reader = message(&certificate_verify, CERTIFICATE_VERIFY)?;
+    let scheme = match reader.u16()? {
+        ECDSA_SECP256R1_SHA256 => SignatureScheme::EcdsaSha256,
+        RSA_PSS_RSAE_SHA256 => SignatureScheme::RsaPssSha256,
+        _ => return Err(Error::Unsupported),
+    };
+    let signature = reader.vec_u16()?;
+    reader.finish()?;
+    // The signed content is 64 spaces, a context string, a zero byte and the transcript hash, see
+    // RFC 8446 Section 4.4.3.
+    let mut content = Vec::with_capacity(64 + 34 + HASH_LEN);
+    content.extend_from_slice(&[0x20; 64]);
+    content.extend_from_slice(b"TLS 1.3, server CertificateVerify\0");
+    content.extend_from_slice(&sha256(&transcript));
+    server_key.verify(scheme, &content, signature)?;
+    transcript.extend_from_slice(&certificate_verify);
+
+    let finished = server.next_handshake_message()?;
+    read_finished(&finished, &server_secret, &transcript)?;
+    transcript.extend_from_slice(&finished);
+    let [client_application_secret, server_application_secret] =
+        key_schedule.application_traffic_secrets(&sha256(&transcript));
+    server.start_application_data(&server_application_secret)?;
+
+    let finished = client.next_handshake_message()?;
+    read_finished(&finished, &client_secret, &transcript)?;
+    client.start_application_data(&client_application_secret)?;
+
+    let sent = read_application_data(client, |_| Err(Error::UnexpectedMessage))?;
+    let received = read_application_data(server, |message| match message[0] {
+        NEW_SESSION_TICKET => Ok(()),
+        KEY_UPDATE => Err(Error::Unsupported),
+        _ => Err(Error::UnexpectedMessage),
+    })?;
+    Ok((sent, received))
+}
+
+/// The uncompressed SEC1 encoding of the public key of `secret`.
+fn public_key(secret: &P256Scalar) -> [u8; KEY_SHARE_LEN] {
+    let point = P256Point::GENERATOR * *secret;
+    let mut key = [0u8; KEY_SHARE_LEN];
+    key[0] = 0x04;
+    key[1..33].copy_from_slice(point.x().to_be_bytes().as_ref());
+    key[33..].copy_from_slice(point.y().to_be_bytes().as_ref());
+    key
+}
+
+/// Returns a reader of the body of the handshake message `message` of type `msg_type`.
+fn message(message: &[u8], msg_type: u8) -> Result<Reader<'_>> {
+    let mut reader = Reader::new(message);
+    if reader.u8()? != msg_type {
+        return Err(Error::UnexpectedMessage);
+    }
+    let body = reader.vec_u24()?;
+    reader.finish()?;
+    Ok(Reader::new(body))
+}
+
+/// Calls `f` with the type and the data of each extension of `extensions`, which may not contain
+/// the same type twice.
+fn read_extensions(extensions: &[u8], mut f: impl FnMut(u16, &[u8]) -> Result<()>) -> Result<()> {
+    let mut reader = Reader::new(extensions);
+    let mut types = Vec::new();
+    while !reader.is_empty() {
+        let extension_type = reader.u16()?;
+        if types.contains(&extension_type) {
+            return Err(Error::IllegalParameter);
+        }
+        types.push(extension_type);
+        f(extension_type, reader.vec_u16()?)?;
+    }
+    Ok(())
+}
+
+/// Checks that the ClientHello offers the `secp256r1` key share `key_share`, and returns its
+/// legacy_session_id.
+fn read_client_hello<'a>(
+    client_hello: &'a [u8],
+    key_share: &[u8; KEY_SHARE_LEN],
+) -> Result<&'a [u8]> {
+    let mut reader = message(client_hello, CLIENT_HELLO)?;
+    let _legacy_version = reader.u16()?;
+    let _random = reader.take(32)?;
+    let session_id = reader.vec_u8()?;
+    let _cipher_suites = reader.vec_u16()?;
+    let _legacy_compression_methods = reader.vec_u8()?;
+    let mut offered = false;
+    read_extensions(reader.vec_u16()?, |extension_type, data| {
+        if extension_type == KEY_SHARE {
+            let mut shares = Reader::new(data);
+            let mut reader = Reader::new(shares.vec_u16()?);
+            shares.finish()?;
+            while !reader.is_empty() {
+                let group = reader.u16()?;
+                let key_exchange = reader.vec_u16()?;
+                offered |= group == SECP256R1 && key_exchange == key_share;
+            }
+        }
+        Ok(())
+    })?;
+    reader.finish()?;
+    if !offered {
+        return Err(Error::IllegalParameter);
+    }
+    Ok(session_id)
+}
+
+/// Checks that the ServerHello selects TLS 1.3 with `TLS_AES_128_GCM_SHA256` and a `secp256r1`
+/// key share, and returns the key share of the server.
+fn read_server_hello(server_hello: &[u8], session_id: &[u8]) -> Result<P256Point> {
+    let mut reader = message(server_hello, SERVER_HELLO)?;
+    if reader.u16()? != TLS12 {
+        return Err(Error::IllegalParameter);
+    }
+    if reader.take(32)? == HELLO_RETRY_REQUEST_RANDOM {
+        return Err(Error::Unsupported);
+    }
+    if reader.vec_u8()? != session_id {
+        return Err(Error::IllegalParameter);
+    }
+    if reader.u16()? != TLS_AES_128_GCM_SHA256 {
+        return Err(Error::Unsupported);
+    }
+    if reader.u8()? != 0 {
+        return Err(Error::IllegalParameter);
+    }
+    let mut version = None;
+    let mut server_share = None;
+    read_extensions(reader.vec_u16()?, |extension_type, data| {
+        let mut reader = Reader::new(data);
+        match extension_type {
+            SUPPORTED_VERSIONS => version = Some(reader.u16()?),
+            KEY_SHARE => {
+                if reader.u16()? != SECP256R1 {
+                    return Err(Error::Unsupported);
+                }
+                let key_exchange = reader.vec_u16()?;
+                if key_exchange.len() != KEY_SHARE_LEN || key_exchange[0] != 0x04 {
+                    return Err(Error::IllegalParameter);
+                }
+                let key = VerifyingKey::from_sec1_bytes(key_exchange)
+                    .map_err(|_| Error::IllegalParameter)?;
+                server_share = Some(*key.as_affine());
+            }
+            // The other extensions allowed in a ServerHello are for pre-shared keys.
+            _ => return Err(Error::Unsupported),
+        }
+        reader.finish()
+    })?;
+    reader.finish()?;
+    if version != Some(TLS13) {
+        return Err(Error::Unsupported);
+    }
+    server_share.ok_or(Error::IllegalParameter)
+}
+
+/// Returns the DER-encoded certificate chain of a Certificate message.
+fn read_certificate(certificate: &[u8]) -> Result<Vec<&[u8]>> {
+    let mut reader = message(certificate, CERTIFICATE)?;
+    if !reader.vec_u8()?.is_empty() {
+        return Err(Error::IllegalParameter);
+    }
+    let mut entries = Reader::new(reader.vec_u24()?);
+    reader.finish()?;
+    let mut chain = Vec::new();
+    while !entries.is_empty() {
+        chain.push(entries.vec_u24()?);
+        let _extensions = entries.vec_u16()?;
+    }
+    Ok(chain)
+}
+
+/// Checks the Finished message `finished` sent with the handshake traffic secret `secret` after
+/// `transcript`.
+fn read_finished(finished: &[u8], secret: &[u8; HASH_LEN], transcript: &[u8]) -> Result<()> {
+    let mut reader = message(finished, FINISHED)?;
+    let verify_data = reader.take(HASH_LEN)?;
+    reader.finish()?;
+    if verify_data != finished_verify_data(secret, &sha256(transcript)) {
+        return Err(Error::BadFinished);
+    }
+    Ok(())
+}
+
+/// Reads the application data of a flow until its end. `on_handshake_message` is called with the
+/// post-handshake messages.
+fn read_application_data(
+    mut flow: Flow,
+    mut on_handshake_message: impl FnMut(&[u8]) -> Result<()>,
+) -> Result<Direction> {
+    let mut data = Vec::new();
+    while let Some(content) = flow.next_application_data(&mut on_handshake_message)? {
+        data.extend_from_slice(&content);
+    }
+    Ok(Direction {
+        data,
+        closed: flow.is_closed(),
+    })
+}
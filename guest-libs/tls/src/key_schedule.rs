@@ -0,0 +1,116 @@
+//! The key schedule of TLS 1.3 with SHA-256, see
+//! [RFC 8446 Section 7.1](https://datatracker.ietf.org/doc/html/rfc8446#section-7.1).
+
+use alloc::vec::Vec;
+
+use openvm_sha2::{sha256, Sha256};
+
+pub(crate) const HASH_LEN: usize = 32;
+const BLOCK_LEN: usize = 64;
+
+/// HMAC-SHA256 of the concatenation of `data`, see
+/// [RFC 2104](https://datatracker.ietf.org/doc/html/rfc2104).
+pub(crate) fn hmac(key: &[u8], data: &[&[u8]]) -> [u8; HASH_LEN] {
+    let mut block = [0u8; BLOCK_LEN];
+    if key.len() > BLOCK_LEN {
+        block[..HASH_LEN].copy_from_slice(&sha256(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let mut inner = Sha256::new();
+    inner.update(block.map(|byte| byte ^ 0x36));
+    for data in data {
+        inner.update(data);
+    }
+    let mut outer = Sha256::new();
+    outer.update(block.map(|byte| byte ^ 0x5c));
+    outer.update(inner.finalize());
+    outer.finalize()
+}
+
+pub(crate) fn hkdf_extract(salt: &[u8; HASH_LEN], ikm: &[u8]) -> [u8; HASH_LEN] {
+    hmac(salt, &[ikm])
+}
+
+/// HKDF-Expand-Label, which fills `output` with the expansion of `secret`.
+pub(crate) fn hkdf_expand_label(
+    secret: &[u8; HASH_LEN],
+    label: &[u8],
+    context: &[u8],
+    output: &mut [u8],
+) {
+    let mut info = Vec::with_capacity(4 + 6 + label.len() + context.len());
+    info.extend_from_slice(&(output.len() as u16).to_be_bytes());
+    info.push((6 + label.len()) as u8);
+    info.extend_from_slice(b"tls13 ");
+    info.extend_from_slice(label);
+    info.push(context.len() as u8);
+    info.extend_from_slice(context);
+
+    let mut block = [0u8; HASH_LEN];
+    for (i, chunk) in output.chunks_mut(HASH_LEN).enumerate() {
+        let previous: &[u8] = if i == 0 { &[] } else { &block };
+        block = hmac(secret, &[previous, &info, &[i as u8 + 1]]);
+        chunk.copy_from_slice(&block[..chunk.len()]);
+    }
+}
+
+/// Derive-Secret, given the hash of the transcript rather than the transcript.
+pub(crate) fn derive_secret(
+    secret: &[u8; HASH_LEN],
+    label: &[u8],
+    transcript_hash: &[u8; HASH_LEN],
+) -> [u8; HASH_LEN] {
+    let mut output = [0u8; HASH_LEN];
+    hkdf_expand_label(secret, label, transcript_hash, &mut output);
+    output
+}
+
+/// The secrets of a handshake without pre-shared key.
+pub(crate) struct KeySchedule {
+    handshake_secret: [u8; HASH_LEN],
+}
+
+impl KeySchedule {
+    /// Starts the key schedule from the shared secret of the (EC)DHE key exchange.
+    pub fn new(shared_secret: &[u8]) -> Self {
+        let early_secret = hkdf_extract(&[0; HASH_LEN], &[0; HASH_LEN]);
+        let salt = derive_secret(&early_secret, b"derived", &sha256(&[]));
+        Self {
+            handshake_secret: hkdf_extract(&salt, shared_secret),
+        }
+    }
+
+    /// The client and server handshake traffic secrets, given the hash of the transcript up to
+    /// the ServerHello.
+    pub fn handshake_traffic_secrets(
+        &self,
+        transcript_hash: &[u8; HASH_LEN],
+    ) -> [[u8; HASH_LEN]; 2] {
+        [b"c hs traffic", b"s hs traffic"]
+            .map(|label| derive_secret(&self.handshake_secret, label, transcript_hash))
+    }
+
+    /// The client and server application traffic secrets, given the hash of the transcript up to
+    /// the Finished message of the server.
+    pub fn application_traffic_secrets(
+        &self,
+        transcript_hash: &[u8; HASH_LEN],
+    ) -> [[u8; HASH_LEN]; 2] {
+        let salt = derive_secret(&self.handshake_secret, b"derived", &sha256(&[]));
+        let master_secret = hkdf_extract(&salt, &[0; HASH_LEN]);
+        [b"c ap traffic", b"s ap traffic"]
+            .map(|label| derive_secret(&master_secret, label, transcript_hash))
+    }
+}
+
+/// The verify_data of the Finished message sent with the traffic secret `secret`, given the hash
+/// of the transcript before the message.
+pub(crate) fn finished_verify_data(
+    secret: &[u8; HASH_LEN],
+    transcript_hash: &[u8; HASH_LEN],
+) -> [u8; HASH_LEN] {
+    let mut finished_key = [0u8; HASH_LEN];
+    hkdf_expand_label(secret, b"finished", &[], &mut finished_key);
+    hmac(&finished_key, &[transcript_hash])
+}
@@ -0,0 +1,170 @@
+#![no_std]
+
+//! Verification of TLS 1.3 sessions, for proving statements about the data exchanged with a web
+//! server.
+//!
+//! [Verifier::verify] takes the bytes sent by the client and by the server during a session,
+//! together with the private key of the client's key share, and:
+//! - checks that the ServerHello answers the ClientHello, and computes the handshake and
+//!   application traffic secrets with SHA-256;
+//! - verifies the certificate chain of the server against trust anchors and the server name, with
+//!   ECDSA P-256 and RSA signatures;
+//! - verifies the CertificateVerify and Finished messages of the handshake;
+//! - decrypts the application data sent in each direction with AES-128-GCM.
+//!
+//! Only the `TLS_AES_128_GCM_SHA256` cipher suite with `secp256r1` key exchange is supported,
+//! without pre-shared keys or client authentication.
+//!
+//! Note that the client knows the traffic secrets of both directions, so the client alone can
+//! produce a session for any data. The data is only attested if the bytes exchanged were recorded
+//! by a party that the client cannot impersonate, e.g. a proxy relaying the session. Such a party
+//! attests to [Session::commitment], which the guest should reveal along with the claims it
+//! proves about the data.
+//!
+//! In the zkVM, the library uses the SHA-256, P-256 and RSA intrinsics, so the VM must be
+//! configured with the SHA-256, modular arithmetic, elliptic curve (P-256) and RSA extensions.
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+
+use openvm_sha2::sha256;
+use x509_cert::{der::Decode, Certificate};
+
+mod cert;
+mod codec;
+mod handshake;
+mod key_schedule;
+mod record;
+mod signature;
+
+/// Errors returned when verifying a TLS session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// A record or message is malformed.
+    Decode,
+    /// A stream ended before the handshake was complete.
+    Truncated,
+    /// A record or message was not expected at its position in the session.
+    UnexpectedMessage,
+    /// A record is longer than allowed.
+    RecordOverflow,
+    /// A record failed to decrypt.
+    BadRecordMac,
+    /// A peer sent an alert other than close_notify, with the given description.
+    Alert(u8),
+    /// The session uses a protocol feature, cipher suite, group or signature algorithm which is
+    /// not supported.
+    Unsupported,
+    /// A parameter of the handshake is inconsistent, e.g. the key share of the client does not
+    /// match its private key.
+    IllegalParameter,
+    /// A certificate is malformed or may not be used in its position in the chain.
+    BadCertificate,
+    /// A certificate is not valid at the verification time.
+    CertificateExpired,
+    /// The certificate chain does not end at a trust anchor.
+    UnknownIssuer,
+    /// The certificate of the server is not valid for the server name.
+    NameMismatch,
+    /// A signature of a certificate or of the handshake is invalid.
+    BadSignature,
+    /// The verify_data of a Finished message is invalid.
+    BadFinished,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::Decode => write!(f, "malformed record or message"),
+            Error::Truncated => write!(f, "stream ended during the handshake"),
+            Error::UnexpectedMessage => write!(f, "unexpected record or message"),
+            Error::RecordOverflow => write!(f, "record too long"),
+            Error::BadRecordMac => write!(f, "record failed to decrypt"),
+            Error::Alert(description) => write!(f, "received alert {description}"),
+            Error::Unsupported => write!(f, "unsupported protocol feature"),
+            Error::IllegalParameter => write!(f, "illegal handshake parameter"),
+            Error::BadCertificate => write!(f, "bad certificate"),
+            Error::CertificateExpired => write!(f, "certificate expired or not yet valid"),
+            Error::UnknownIssuer => write!(f, "certificate chain does not end at a trust anchor"),
+            Error::NameMismatch => write!(f, "certificate not valid for the server name"),
+            Error::BadSignature => write!(f, "invalid signature"),
+            Error::BadFinished => write!(f, "invalid Finished message"),
+        }
+    }
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Verifies TLS 1.3 sessions with a server.
+pub struct Verifier {
+    /// The DER encodings of the trust anchors, with their decoding.
+    roots: Vec<(Vec<u8>, Certificate)>,
+    server_name: String,
+    time: u64,
+}
+
+impl Verifier {
+    /// Creates a verifier of sessions with `server_name`, whose certificate chain ends at one of
+    /// the DER-encoded certificates `roots`. The certificates of the chain must be valid at
+    /// `time`, in seconds since the Unix epoch.
+    pub fn new(roots: &[&[u8]], server_name: &str, time: u64) -> Result<Self> {
+        let roots = roots
+            .iter()
+            .map(|der| {
+                let root = Certificate::from_der(der).map_err(|_| Error::BadCertificate)?;
+                Ok((der.to_vec(), root))
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self {
+            roots,
+            server_name: server_name.into(),
+            time,
+        })
+    }
+
+    /// Verifies the session in which the client sent `client_stream` and the server sent
+    /// `server_stream`. `secret` is the big-endian private key of the `secp256r1` key share of the
+    /// ClientHello.
+    pub fn verify(
+        &self,
+        secret: &[u8; 32],
+        client_stream: &[u8],
+        server_stream: &[u8],
+    ) -> Result<Session> {
+        let (sent, received) = handshake::verify(self, secret, client_stream, server_stream)?;
+        Ok(Session {
+            sent: sent.data,
+            received: received.data,
+            client_closed: sent.closed,
+            server_closed: received.closed,
+            commitment: commitment(client_stream, server_stream),
+        })
+    }
+}
+
+/// A verified TLS session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Session {
+    /// The application data sent by the client.
+    pub sent: Vec<u8>,
+    /// The application data sent by the server.
+    pub received: Vec<u8>,
+    /// Whether the client closed the connection with a close_notify alert. Otherwise the recorded
+    /// stream may have been cut short.
+    pub client_closed: bool,
+    /// Whether the server closed the connection with a close_notify alert. Otherwise the recorded
+    /// stream may have been cut short.
+    pub server_closed: bool,
+    /// The [commitment] to the bytes exchanged.
+    pub commitment: [u8; 32],
+}
+
+/// The commitment to the bytes exchanged in a session, `sha256(sha256(client_stream) ||
+/// sha256(server_stream))`.
+pub fn commitment(client_stream: &[u8], server_stream: &[u8]) -> [u8; 32] {
+    let mut hashes = [0u8; 64];
+    hashes[..32].copy_from_slice(&sha256(client_stream));
+    hashes[32..].copy_from_slice(&sha256(server_stream));
+    sha256(&hashes)
+}
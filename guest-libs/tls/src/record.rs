@@ -0,0 +1,237 @@
+//! The record layer, see
+//! [RFC 8446 Section 5](https://datatracker.ietf.org/doc/html/rfc8446#section-5).
+
+use alloc::vec::Vec;
+
+use aes_gcm::{
+    aead::{AeadInPlace, KeyInit},
+    Aes128Gcm, Nonce,
+};
+
+use crate::{
+    codec::Reader,
+    key_schedule::{hkdf_expand_label, HASH_LEN},
+    Error, Result,
+};
+
+pub(crate) const CHANGE_CIPHER_SPEC: u8 = 20;
+pub(crate) const ALERT: u8 = 21;
+pub(crate) const HANDSHAKE: u8 = 22;
+pub(crate) const APPLICATION_DATA: u8 = 23;
+
+const HEADER_LEN: usize = 5;
+const MAX_PLAINTEXT_LEN: usize = 1 << 14;
+/// The plaintext, its content type and at most 255 bytes of padding and tag.
+const MAX_CIPHERTEXT_LEN: usize = MAX_PLAINTEXT_LEN + 256;
+const CLOSE_NOTIFY: u8 = 0;
+
+/// A record of a stream.
+struct Record<'a> {
+    header: &'a [u8],
+    content_type: u8,
+    fragment: &'a [u8],
+}
+
+/// Decrypts the records sent with a traffic secret, see
+/// [RFC 8446 Section 5.3](https://datatracker.ietf.org/doc/html/rfc8446#section-5.3).
+struct Decrypter {
+    cipher: Aes128Gcm,
+    iv: [u8; 12],
+    sequence_number: u64,
+}
+
+impl Decrypter {
+    fn new(secret: &[u8; HASH_LEN]) -> Self {
+        let mut key = [0u8; 16];
+        hkdf_expand_label(secret, b"key", &[], &mut key);
+        let mut iv = [0u8; 12];
+        hkdf_expand_label(secret, b"iv", &[], &mut iv);
+        Self {
+            cipher: Aes128Gcm::new_from_slice(&key).unwrap(),
+            iv,
+            sequence_number: 0,
+        }
+    }
+
+    /// Returns the content type and the content of the record.
+    fn decrypt(&mut self, record: &Record) -> Result<(u8, Vec<u8>)> {
+        if record.content_type != APPLICATION_DATA {
+            return Err(Error::UnexpectedMessage);
+        }
+        let mut nonce = self.iv;
+        for (byte, seq) in nonce[4..]
+            .iter_mut()
+            .zip(self.sequence_number.to_be_bytes())
+        {
+            *byte ^= seq;
+        }
+        self.sequence_number += 1;
+        let mut inner = record.fragment.to_vec();
+        self.cipher
+            .decrypt_in_place(Nonce::from_slice(&nonce), record.header, &mut inner)
+            .map_err(|_| Error::BadRecordMac)?;
+        // The content is followed by its content type and zero padding.
+        let len = inner
+            .iter()
+            .rposition(|&byte| byte != 0)
+            .ok_or(Error::UnexpectedMessage)?;
+        let content_type = inner[len];
+        inner.truncate(len);
+        if inner.len() > MAX_PLAINTEXT_LEN {
+            return Err(Error::RecordOverflow);
+        }
+        Ok((content_type, inner))
+    }
+}
+
+/// The records sent in one direction of a session, from which the handshake messages and the
+/// application data are read in order.
+pub(crate) struct Flow<'a> {
+    records: Reader<'a>,
+    decrypter: Option<Decrypter>,
+    /// Handshake bytes of the records read which do not form a whole message yet.
+    handshake: Vec<u8>,
+    /// Whether the handshake is over, after which change_cipher_spec records are not allowed.
+    handshake_done: bool,
+    closed: bool,
+}
+
+impl<'a> Flow<'a> {
+    pub fn new(stream: &'a [u8]) -> Self {
+        Self {
+            records: Reader::new(stream),
+            decrypter: None,
+            handshake: Vec::new(),
+            handshake_done: false,
+            closed: false,
+        }
+    }
+
+    /// Decrypts the records which follow with the traffic secret `secret`. Keys only change at
+    /// record boundaries, so no partial handshake message may be pending.
+    pub fn set_traffic_secret(&mut self, secret: &[u8; HASH_LEN]) -> Result<()> {
+        if !self.handshake.is_empty() {
+            return Err(Error::UnexpectedMessage);
+        }
+        self.decrypter = Some(Decrypter::new(secret));
+        Ok(())
+    }
+
+    /// Ends the handshake, decrypting the records which follow with the application traffic
+    /// secret `secret`.
+    pub fn start_application_data(&mut self, secret: &[u8; HASH_LEN]) -> Result<()> {
+        self.set_traffic_secret(secret)?;
+        self.handshake_done = true;
+        Ok(())
+    }
+
+    /// Whether the flow ended with a close_notify alert, rather than with the end of the stream.
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    fn next_record(&mut self) -> Result<Option<Record<'a>>> {
+        if self.records.is_empty() {
+            return Ok(None);
+        }
+        let header = self.records.take(HEADER_LEN)?;
+        let mut reader = Reader::new(header);
+        let content_type = reader.u8()?;
+        let _legacy_record_version = reader.u16()?;
+        let len = reader.u16()? as usize;
+        if len > MAX_CIPHERTEXT_LEN {
+            return Err(Error::RecordOverflow);
+        }
+        Ok(Some(Record {
+            header,
+            content_type,
+            fragment: self.records.take(len)?,
+        }))
+    }
+
+    /// Returns the content type and the content of the next record, or `None` at the end of the
+    /// stream. The dummy change_cipher_spec records sent during the handshake for middlebox
+    /// compatibility are skipped, and alerts other than close_notify are returned as errors.
+    fn next_content(&mut self) -> Result<Option<(u8, Vec<u8>)>> {
+        loop {
+            let Some(record) = self.next_record()? else {
+                return Ok(None);
+            };
+            if self.closed {
+                return Err(Error::UnexpectedMessage);
+            }
+            let (content_type, content) = match &mut self.decrypter {
+                _ if record.content_type == CHANGE_CIPHER_SPEC => {
+                    if self.handshake_done || record.fragment != [1] {
+                        return Err(Error::UnexpectedMessage);
+                    }
+                    continue;
+                }
+                Some(decrypter) => decrypter.decrypt(&record)?,
+                None => (record.content_type, record.fragment.to_vec()),
+            };
+            if content_type == ALERT {
+                match content[..] {
+                    [_, CLOSE_NOTIFY] => self.closed = true,
+                    [_, description] => return Err(Error::Alert(description)),
+                    _ => return Err(Error::Decode),
+                }
+            }
+            return Ok(Some((content_type, content)));
+        }
+    }
+
+    /// Returns the next handshake message, with its header.
+    pub fn next_handshake_message(&mut self) -> Result<Vec<u8>> {
+        loop {
+            if let Some(len) = self.pending_message_len() {
+                let rest = self.handshake.split_off(len);
+                return Ok(core::mem::replace(&mut self.handshake, rest));
+            }
+            match self.next_content()? {
+                Some((HANDSHAKE, content)) => self.handshake.extend_from_slice(&content),
+                None => return Err(Error::Truncated),
+                _ => return Err(Error::UnexpectedMessage),
+            }
+        }
+    }
+
+    /// The length of the first pending handshake message, if all of it has been read.
+    fn pending_message_len(&self) -> Option<usize> {
+        let header = self.handshake.get(..4)?;
+        let len = 4 + u32::from_be_bytes([0, header[1], header[2], header[3]]) as usize;
+        (self.handshake.len() >= len).then_some(len)
+    }
+
+    /// Returns the application data of the next record, or `None` at the end of the stream or
+    /// after a close_notify alert. `on_handshake_message` is called with the post-handshake
+    /// messages in between.
+    pub fn next_application_data(
+        &mut self,
+        mut on_handshake_message: impl FnMut(&[u8]) -> Result<()>,
+    ) -> Result<Option<Vec<u8>>> {
+        loop {
+            match self.next_content()? {
+                None | Some((ALERT, _)) => {
+                    return if self.handshake.is_empty() {
+                        Ok(None)
+                    } else {
+                        Err(Error::Decode)
+                    };
+                }
+                Some((APPLICATION_DATA, content)) if self.handshake.is_empty() => {
+                    return Ok(Some(content))
+                }
+                Some((HANDSHAKE, content)) => {
+                    self.handshake.extend_from_slice(&content);
+                    while let Some(len) = self.pending_message_len() {
+                        let rest = self.handshake.split_off(len);
+                        on_handshake_message(&self.handshake)?;
+                        self.handshake = rest;
+                    }
+                }
+                _ => return Err(Error::UnexpectedMessage),
+            }
+        }
+    }
+}
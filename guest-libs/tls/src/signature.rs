@@ -0,0 +1,107 @@
+//! Public keys of certificates and the verification of their signatures.
+
+use openvm_p256::ecdsa::{signature::hazmat::PrehashVerifier, Signature, VerifyingKey};
+use openvm_rsa_guest::{HashAlgorithm, RsaPublicKey};
+use openvm_sha2::{sha256, sha384};
+use x509_cert::{
+    der::{
+        asn1::{ObjectIdentifier, UintRef},
+        oid::db::rfc5912::{ID_EC_PUBLIC_KEY, RSA_ENCRYPTION, SECP_256_R_1},
+        Decode, Reader, SliceReader,
+    },
+    spki::SubjectPublicKeyInfoOwned,
+};
+
+use crate::{Error, Result};
+
+/// The length of the salt of RSASSA-PSS signatures in TLS 1.3, which is the digest length.
+const PSS_SALT_LEN: usize = 32;
+
+/// The supported signature algorithms.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SignatureScheme {
+    EcdsaSha256,
+    EcdsaSha384,
+    RsaPkcs1Sha256,
+    RsaPkcs1Sha384,
+    RsaPssSha256,
+}
+
+/// A public key of a certificate.
+pub(crate) enum PublicKey {
+    P256(VerifyingKey),
+    Rsa(RsaPublicKey),
+}
+
+impl PublicKey {
+    /// Parses a P-256 or RSA subject public key.
+    pub fn from_spki(spki: &SubjectPublicKeyInfoOwned) -> Result<Self> {
+        let key = spki
+            .subject_public_key
+            .as_bytes()
+            .ok_or(Error::BadCertificate)?;
+        match spki.algorithm.oid {
+            ID_EC_PUBLIC_KEY => {
+                let curve = spki
+                    .algorithm
+                    .parameters
+                    .as_ref()
+                    .ok_or(Error::BadCertificate)?
+                    .decode_as::<ObjectIdentifier>()
+                    .map_err(|_| Error::BadCertificate)?;
+                if curve != SECP_256_R_1 {
+                    return Err(Error::Unsupported);
+                }
+                let key = VerifyingKey::from_sec1_bytes(key).map_err(|_| Error::BadCertificate)?;
+                Ok(PublicKey::P256(key))
+            }
+            RSA_ENCRYPTION => {
+                // RSAPublicKey ::= SEQUENCE { modulus INTEGER, publicExponent INTEGER }
+                let mut reader = SliceReader::new(key).map_err(|_| Error::BadCertificate)?;
+                let (n, e) = reader
+                    .sequence(|reader| Ok((UintRef::decode(reader)?, UintRef::decode(reader)?)))
+                    .and_then(|key| reader.finish(key))
+                    .map_err(|_| Error::BadCertificate)?;
+                let e = e.as_bytes();
+                if e.len() > 4 {
+                    return Err(Error::Unsupported);
+                }
+                let e = e.iter().fold(0, |e, &byte| (e << 8) | byte as u32);
+                let key = RsaPublicKey::new(n.as_bytes(), e).map_err(|_| Error::Unsupported)?;
+                Ok(PublicKey::Rsa(key))
+            }
+            _ => Err(Error::Unsupported),
+        }
+    }
+
+    /// Verifies the `signature` of `message` with the algorithm `scheme`. ECDSA signatures are
+    /// DER-encoded.
+    pub fn verify(&self, scheme: SignatureScheme, message: &[u8], signature: &[u8]) -> Result<()> {
+        let verified = match (self, scheme) {
+            (PublicKey::P256(key), SignatureScheme::EcdsaSha256 | SignatureScheme::EcdsaSha384) => {
+                let signature = Signature::from_der(signature).map_err(|_| Error::BadSignature)?;
+                let verified = if scheme == SignatureScheme::EcdsaSha256 {
+                    key.verify_prehash(&sha256(message), &signature)
+                } else {
+                    key.verify_prehash(&sha384(message), &signature)
+                };
+                verified.is_ok()
+            }
+            (PublicKey::Rsa(key), SignatureScheme::RsaPkcs1Sha256) => key
+                .verify_pkcs1v15(HashAlgorithm::Sha256, &sha256(message), signature)
+                .is_ok(),
+            (PublicKey::Rsa(key), SignatureScheme::RsaPkcs1Sha384) => key
+                .verify_pkcs1v15(HashAlgorithm::Sha384, &sha384(message), signature)
+                .is_ok(),
+            (PublicKey::Rsa(key), SignatureScheme::RsaPssSha256) => key
+                .verify_pss_sha256(&sha256(message), signature, PSS_SALT_LEN)
+                .is_ok(),
+            _ => return Err(Error::BadSignature),
+        };
+        if verified {
+            Ok(())
+        } else {
+            Err(Error::BadSignature)
+        }
+    }
+}
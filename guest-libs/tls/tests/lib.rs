@@ -0,0 +1,226 @@
+/// A session recorded by `programs/examples/test_vectors/generate.py`.
+struct TestVector {
+    root: &'static [u8],
+    secret: [u8; 32],
+    client_stream: &'static [u8],
+    server_stream: &'static [u8],
+}
+
+macro_rules! test_vector {
+    ($kind:literal) => {
+        TestVector {
+            root: include_bytes!(concat!(
+                "programs/examples/test_vectors/",
+                $kind,
+                "/root.der"
+            )),
+            secret: *include_bytes!(concat!(
+                "programs/examples/test_vectors/",
+                $kind,
+                "/secret.bin"
+            )),
+            client_stream: include_bytes!(concat!(
+                "programs/examples/test_vectors/",
+                $kind,
+                "/client.bin"
+            )),
+            server_stream: include_bytes!(concat!(
+                "programs/examples/test_vectors/",
+                $kind,
+                "/server.bin"
+            )),
+        }
+    };
+}
+
+/// A session with a chain of P-256 certificates.
+const ECDSA: TestVector = test_vector!("ecdsa");
+/// A session with a chain of RSA-2048 certificates.
+const RSA: TestVector = test_vector!("rsa");
+/// A session with a chain of P-256 certificates whose key usages allow the chain.
+const KEY_USAGE: TestVector = test_vector!("key_usage");
+/// As [KEY_USAGE], but the intermediate certificate may not sign certificates.
+const CA_KEY_USAGE: TestVector = test_vector!("ca_key_usage");
+/// As [KEY_USAGE], but the intermediate certificate may not sign CRLs.
+const CA_CRL_SIGN: TestVector = test_vector!("ca_crl_sign");
+/// As [KEY_USAGE], but the key of the server may not sign.
+const LEAF_KEY_USAGE: TestVector = test_vector!("leaf_key_usage");
+/// As [KEY_USAGE], but the certificate of the server is only for TLS clients.
+const LEAF_EXTENDED_KEY_USAGE: TestVector = test_vector!("leaf_extended_key_usage");
+
+const SERVER_NAME: &str = "tls.openvm.test";
+/// 2026-01-01T00:00:00Z, when the test certificates are valid.
+const TIME: u64 = 1_767_225_600;
+
+mod guest_tests {
+    use eyre::Result;
+    use openvm_circuit::{arch::instructions::exe::VmExe, utils::air_test_with_min_segments};
+    use openvm_sdk::{
+        config::{AppConfig, SdkVmConfig},
+        StdIn,
+    };
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_transpiler::FromElf;
+
+    use super::{TestVector, ECDSA, RSA};
+
+    fn test_verify(vector: &TestVector) -> Result<()> {
+        let config =
+            toml::from_str::<AppConfig<SdkVmConfig>>(include_str!("programs/openvm_tls.toml"))?
+                .app_vm_config;
+        let elf =
+            build_example_program_at_path(get_programs_dir!("tests/programs"), "verify", &config)?;
+        let openvm_exe = VmExe::from_elf(elf, config.transpiler())?;
+
+        let mut stdin = StdIn::default();
+        stdin.write_bytes(vector.root);
+        stdin.write_bytes(&vector.secret);
+        stdin.write_bytes(vector.client_stream);
+        stdin.write_bytes(vector.server_stream);
+        air_test_with_min_segments(config, openvm_exe, stdin, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_verify_ecdsa() -> Result<()> {
+        test_verify(&ECDSA)
+    }
+
+    #[test]
+    fn test_verify_rsa() -> Result<()> {
+        test_verify(&RSA)
+    }
+}
+
+mod host_tests {
+    use openvm_tls::{commitment, Error, Verifier};
+
+    use super::{
+        TestVector, CA_CRL_SIGN, CA_KEY_USAGE, ECDSA, KEY_USAGE, LEAF_EXTENDED_KEY_USAGE,
+        LEAF_KEY_USAGE, RSA, SERVER_NAME, TIME,
+    };
+
+    const REQUEST: &[u8] = b"GET / HTTP/1.1\r\nHost: tls.openvm.test\r\nConnection: close\r\n\r\n";
+    const RESPONSE: &[u8] = b"HTTP/1.1 200 OK\r\nContent-Length: 17\r\n\r\n{\"balance\": 1234}";
+
+    fn verify(
+        vector: &TestVector,
+        server_name: &str,
+        time: u64,
+        server_stream: &[u8],
+    ) -> Result<openvm_tls::Session, Error> {
+        Verifier::new(&[vector.root], server_name, time)?.verify(
+            &vector.secret,
+            vector.client_stream,
+            server_stream,
+        )
+    }
+
+    #[test]
+    fn test_verify() {
+        for vector in [&ECDSA, &RSA] {
+            let session = verify(vector, SERVER_NAME, TIME, vector.server_stream).unwrap();
+            assert_eq!(session.sent, REQUEST);
+            assert_eq!(session.received, RESPONSE);
+            assert!(session.client_closed && session.server_closed);
+            assert_eq!(
+                session.commitment,
+                commitment(vector.client_stream, vector.server_stream)
+            );
+        }
+    }
+
+    #[test]
+    fn test_server_name() {
+        // The certificate of the server is valid for `*.openvm.test`.
+        assert!(verify(&ECDSA, "TLS.OpenVM.test", TIME, ECDSA.server_stream).is_ok());
+        for server_name in ["openvm.test", "a.tls.openvm.test", "tls.openvm.com"] {
+            assert_eq!(
+                verify(&ECDSA, server_name, TIME, ECDSA.server_stream),
+                Err(Error::NameMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn test_certificate_validity() {
+        // 2036-01-01T00:00:00Z, after the certificates expire.
+        assert_eq!(
+            verify(&ECDSA, SERVER_NAME, 2_082_758_400, ECDSA.server_stream),
+            Err(Error::CertificateExpired)
+        );
+    }
+
+    #[test]
+    fn test_key_usage() {
+        assert!(verify(&KEY_USAGE, SERVER_NAME, TIME, KEY_USAGE.server_stream).is_ok());
+    }
+
+    #[test]
+    fn test_ca_key_usage() {
+        let vector = &CA_KEY_USAGE;
+        assert_eq!(
+            verify(vector, SERVER_NAME, TIME, vector.server_stream),
+            Err(Error::BadCertificate)
+        );
+    }
+
+    #[test]
+    fn test_ca_crl_sign() {
+        let vector = &CA_CRL_SIGN;
+        assert_eq!(
+            verify(vector, SERVER_NAME, TIME, vector.server_stream),
+            Err(Error::BadCertificate)
+        );
+    }
+
+    #[test]
+    fn test_leaf_key_usage() {
+        let vector = &LEAF_KEY_USAGE;
+        assert_eq!(
+            verify(vector, SERVER_NAME, TIME, vector.server_stream),
+            Err(Error::BadCertificate)
+        );
+    }
+
+    #[test]
+    fn test_leaf_extended_key_usage() {
+        let vector = &LEAF_EXTENDED_KEY_USAGE;
+        assert_eq!(
+            verify(vector, SERVER_NAME, TIME, vector.server_stream),
+            Err(Error::BadCertificate)
+        );
+    }
+
+    #[test]
+    fn test_unknown_issuer() {
+        let result = Verifier::new(&[RSA.root], SERVER_NAME, TIME)
+            .unwrap()
+            .verify(&ECDSA.secret, ECDSA.client_stream, ECDSA.server_stream);
+        assert_eq!(result, Err(Error::UnknownIssuer));
+    }
+
+    #[test]
+    fn test_wrong_secret() {
+        let mut secret = ECDSA.secret;
+        secret[31] ^= 1;
+        let result = Verifier::new(&[ECDSA.root], SERVER_NAME, TIME)
+            .unwrap()
+            .verify(&secret, ECDSA.client_stream, ECDSA.server_stream);
+        assert_eq!(result, Err(Error::IllegalParameter));
+    }
+
+    #[test]
+    fn test_tampered_stream() {
+        // Flipping a bit of an encrypted record breaks its authentication tag.
+        let mut server_stream = ECDSA.server_stream.to_vec();
+        let last = server_stream.len() - 1;
+        server_stream[last] ^= 1;
+        assert_eq!(
+            verify(&ECDSA, SERVER_NAME, TIME, &server_stream),
+            Err(Error::BadRecordMac)
+        );
+        let truncated = &ECDSA.server_stream[..ECDSA.server_stream.len() / 2];
+        assert!(verify(&ECDSA, SERVER_NAME, TIME, truncated).is_err());
+    }
+}
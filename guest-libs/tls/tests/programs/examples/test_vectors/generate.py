@@ -0,0 +1,375 @@
+"""Generates the TLS 1.3 sessions of the tests of openvm-tls.
+
+A minimal TLS 1.3 client (secp256r1, TLS_AES_128_GCM_SHA256) talks to an OpenSSL server through
+memory BIOs, so that the bytes sent in each direction are recorded. The server accepting the
+Finished message of the client and answering its request checks the key schedule of the client.
+
+    python3 generate.py [kind...]
+
+writes `<kind>/{client,server}.bin` with the bytes sent by the client and by the server,
+`<kind>/root.der` with the trust anchor and `<kind>/secret.bin` with the private key of the key
+share of the client, for every kind of chain of `CHAINS`. Requires the `cryptography` package.
+"""
+
+import datetime
+import hashlib
+import hmac
+import os
+import ssl
+import struct
+import sys
+import tempfile
+
+from cryptography import x509
+from cryptography.hazmat.primitives import hashes, serialization
+from cryptography.hazmat.primitives.asymmetric import ec, rsa
+from cryptography.hazmat.primitives.ciphers.aead import AESGCM
+from cryptography.x509.oid import NameOID
+
+SERVER_NAME = "tls.openvm.test"
+REQUEST = b"GET / HTTP/1.1\r\nHost: tls.openvm.test\r\nConnection: close\r\n\r\n"
+RESPONSE = b"HTTP/1.1 200 OK\r\nContent-Length: 17\r\n\r\n{\"balance\": 1234}"
+# The certificates are valid from 2025-01-01 to 2035-01-01.
+NOT_BEFORE = datetime.datetime(2025, 1, 1, tzinfo=datetime.timezone.utc)
+NOT_AFTER = datetime.datetime(2035, 1, 1, tzinfo=datetime.timezone.utc)
+
+
+def name(common_name):
+    return x509.Name([x509.NameAttribute(NameOID.COMMON_NAME, common_name)])
+
+
+def certificate(
+    subject, subject_key, issuer, issuer_key, ca, serial, key_usage=None, extended_key_usage=None
+):
+    builder = (
+        x509.CertificateBuilder()
+        .subject_name(name(subject))
+        .issuer_name(name(issuer))
+        .public_key(subject_key.public_key())
+        .serial_number(serial)
+        .not_valid_before(NOT_BEFORE)
+        .not_valid_after(NOT_AFTER)
+        .add_extension(x509.BasicConstraints(ca=ca, path_length=None), critical=True)
+    )
+    if not ca:
+        builder = builder.add_extension(
+            x509.SubjectAlternativeName([x509.DNSName("*.openvm.test")]), critical=False
+        )
+    if key_usage is not None:
+        usages = dict.fromkeys(
+            [
+                "digital_signature",
+                "content_commitment",
+                "key_encipherment",
+                "data_encipherment",
+                "key_agreement",
+                "key_cert_sign",
+                "crl_sign",
+                "encipher_only",
+                "decipher_only",
+            ],
+            False,
+        )
+        usages.update(dict.fromkeys(key_usage, True))
+        builder = builder.add_extension(x509.KeyUsage(**usages), critical=True)
+    if extended_key_usage is not None:
+        builder = builder.add_extension(x509.ExtendedKeyUsage(extended_key_usage), critical=False)
+    return builder.sign(issuer_key, hashes.SHA256())
+
+
+def ecdsa_chain():
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    inter_key = ec.generate_private_key(ec.SECP256R1())
+    leaf_key = ec.generate_private_key(ec.SECP256R1())
+    root = certificate("OpenVM Test Root", root_key, "OpenVM Test Root", root_key, True, 1)
+    inter = certificate("OpenVM Test CA", inter_key, "OpenVM Test Root", root_key, True, 2)
+    leaf = certificate(SERVER_NAME, leaf_key, "OpenVM Test CA", inter_key, False, 3)
+    return root, [leaf, inter], leaf_key
+
+
+def rsa_chain():
+    root_key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    leaf_key = rsa.generate_private_key(public_exponent=65537, key_size=2048)
+    root = certificate("OpenVM Test Root", root_key, "OpenVM Test Root", root_key, True, 1)
+    leaf = certificate(SERVER_NAME, leaf_key, "OpenVM Test Root", root_key, False, 2)
+    return root, [leaf], leaf_key
+
+
+CA_SIGNING = ["key_cert_sign", "crl_sign"]
+SERVER_AUTH = [x509.oid.ExtendedKeyUsageOID.SERVER_AUTH]
+CLIENT_AUTH = [x509.oid.ExtendedKeyUsageOID.CLIENT_AUTH]
+
+
+def key_usage_chain(ca_usage, leaf_usage, leaf_extended_usage):
+    """A chain of P-256 certificates whose intermediate has the key usage `ca_usage` and whose
+    leaf has the key usage `leaf_usage` and the extended key usage `leaf_extended_usage`."""
+    root_key = ec.generate_private_key(ec.SECP256R1())
+    inter_key = ec.generate_private_key(ec.SECP256R1())
+    leaf_key = ec.generate_private_key(ec.SECP256R1())
+    root = certificate(
+        "OpenVM Test Root", root_key, "OpenVM Test Root", root_key, True, 1, CA_SIGNING
+    )
+    inter = certificate(
+        "OpenVM Test CA", inter_key, "OpenVM Test Root", root_key, True, 2, ca_usage
+    )
+    leaf = certificate(
+        SERVER_NAME,
+        leaf_key,
+        "OpenVM Test CA",
+        inter_key,
+        False,
+        3,
+        leaf_usage,
+        leaf_extended_usage,
+    )
+    return root, [leaf, inter], leaf_key
+
+
+CHAINS = {
+    "ecdsa": ecdsa_chain,
+    "rsa": rsa_chain,
+    # Key usages allowing the chain.
+    "key_usage": lambda: key_usage_chain(CA_SIGNING, ["digital_signature"], SERVER_AUTH),
+    # The intermediate may not sign certificates.
+    "ca_key_usage": lambda: key_usage_chain(
+        ["digital_signature", "crl_sign"], ["digital_signature"], SERVER_AUTH
+    ),
+    # The intermediate may not sign CRLs.
+    "ca_crl_sign": lambda: key_usage_chain(["key_cert_sign"], ["digital_signature"], SERVER_AUTH),
+    # The key of the server may not sign the handshake.
+    "leaf_key_usage": lambda: key_usage_chain(CA_SIGNING, ["key_agreement"], SERVER_AUTH),
+    # The certificate of the server is only for clients.
+    "leaf_extended_key_usage": lambda: key_usage_chain(
+        CA_SIGNING, ["digital_signature"], CLIENT_AUTH
+    ),
+}
+
+
+def u8(data):
+    return struct.pack(">B", len(data)) + data
+
+
+def u16(data):
+    return struct.pack(">H", len(data)) + data
+
+
+def u24(data):
+    return struct.pack(">I", len(data))[1:] + data
+
+
+def extension(ext_type, data):
+    return struct.pack(">H", ext_type) + u16(data)
+
+
+def handshake(msg_type, body):
+    return struct.pack(">B", msg_type) + u24(body)
+
+
+def record(content_type, data, version=0x0303):
+    return struct.pack(">BHH", content_type, version, len(data)) + data
+
+
+def hkdf_extract(salt, ikm):
+    return hmac.new(salt, ikm, hashlib.sha256).digest()
+
+
+def hkdf_expand_label(secret, label, context, length):
+    label = b"tls13 " + label
+    info = struct.pack(">H", length) + u8(label) + u8(context)
+    output, block, counter = b"", b"", 1
+    while len(output) < length:
+        block = hmac.new(secret, block + info + bytes([counter]), hashlib.sha256).digest()
+        output += block
+        counter += 1
+    return output[:length]
+
+
+def derive_secret(secret, label, messages):
+    return hkdf_expand_label(secret, label, hashlib.sha256(messages).digest(), 32)
+
+
+class Direction:
+    def __init__(self, secret):
+        self.key = hkdf_expand_label(secret, b"key", b"", 16)
+        self.iv = hkdf_expand_label(secret, b"iv", b"", 12)
+        self.seq = 0
+
+    def nonce(self):
+        nonce = bytes(a ^ b for a, b in zip(self.iv, self.seq.to_bytes(12, "big")))
+        self.seq += 1
+        return nonce
+
+    def seal(self, content_type, data):
+        inner = data + bytes([content_type])
+        header = struct.pack(">BHH", 23, 0x0303, len(inner) + 16)
+        return header + AESGCM(self.key).encrypt(self.nonce(), inner, header)
+
+    def open(self, header, fragment):
+        inner = AESGCM(self.key).decrypt(self.nonce(), fragment, header).rstrip(b"\0")
+        return inner[-1], inner[:-1]
+
+
+def records(stream):
+    while stream:
+        length = struct.unpack(">H", stream[3:5])[0]
+        yield stream[:5], stream[5 : 5 + length]
+        stream = stream[5 + length :]
+
+
+def handshake_messages(data):
+    while data:
+        length = int.from_bytes(data[1:4], "big")
+        yield data[: 4 + length]
+        data = data[4 + length :]
+
+
+def session(chain, key, server_name):
+    context = ssl.SSLContext(ssl.PROTOCOL_TLS_SERVER)
+    context.minimum_version = ssl.TLSVersion.TLSv1_3
+    with tempfile.TemporaryDirectory() as tmp:
+        cert_path, key_path = os.path.join(tmp, "chain.pem"), os.path.join(tmp, "key.pem")
+        with open(cert_path, "wb") as f:
+            for cert in chain:
+                f.write(cert.public_bytes(serialization.Encoding.PEM))
+        with open(key_path, "wb") as f:
+            f.write(
+                key.private_bytes(
+                    serialization.Encoding.PEM,
+                    serialization.PrivateFormat.PKCS8,
+                    serialization.NoEncryption(),
+                )
+            )
+        context.load_cert_chain(cert_path, key_path)
+    incoming, outgoing = ssl.MemoryBIO(), ssl.MemoryBIO()
+    server = context.wrap_bio(incoming, outgoing, server_side=True)
+    client_stream, server_stream = b"", b""
+
+    def send(data):
+        nonlocal client_stream
+        client_stream += data
+        incoming.write(data)
+
+    def receive():
+        nonlocal server_stream
+        data = outgoing.read()
+        server_stream += data
+        return data
+
+    secret = ec.generate_private_key(ec.SECP256R1())
+    key_share = secret.public_key().public_bytes(
+        serialization.Encoding.X962, serialization.PublicFormat.UncompressedPoint
+    )
+    extensions = b"".join(
+        [
+            extension(0, u16(b"\0" + u16(server_name.encode()))),
+            extension(10, u16(struct.pack(">H", 0x0017))),
+            extension(13, u16(struct.pack(">HH", 0x0403, 0x0804))),
+            extension(43, u8(struct.pack(">H", 0x0304))),
+            extension(51, u16(struct.pack(">H", 0x0017) + u16(key_share))),
+        ]
+    )
+    client_hello = handshake(
+        1,
+        struct.pack(">H", 0x0303)
+        + os.urandom(32)
+        + u8(os.urandom(32))
+        + u16(struct.pack(">H", 0x1301))
+        + u8(b"\0")
+        + u16(extensions),
+    )
+    send(record(22, client_hello, version=0x0301))
+    try:
+        server.do_handshake()
+    except ssl.SSLWantReadError:
+        pass
+    flight = list(records(receive()))
+
+    server_hello = flight[0][1]
+    assert server_hello[0] == 2
+    exts = server_hello[4 + 2 + 32 + 1 + 32 + 2 + 1 + 2 :]
+    server_share = None
+    while exts:
+        ext_type, length = struct.unpack(">HH", exts[:4])
+        if ext_type == 51:
+            server_share = exts[4 + 2 + 2 : 4 + length]
+        exts = exts[4 + length :]
+    shared = secret.exchange(
+        ec.ECDH(), ec.EllipticCurvePublicKey.from_encoded_point(ec.SECP256R1(), server_share)
+    )
+
+    early = hkdf_extract(b"\0" * 32, b"\0" * 32)
+    handshake_secret = hkdf_extract(derive_secret(early, b"derived", b""), shared)
+    transcript = client_hello + server_hello
+    client_hs = derive_secret(handshake_secret, b"c hs traffic", transcript)
+    server_hs = derive_secret(handshake_secret, b"s hs traffic", transcript)
+    master = hkdf_extract(derive_secret(handshake_secret, b"derived", b""), b"\0" * 32)
+    server_dir = Direction(server_hs)
+
+    for header, fragment in flight[1:]:
+        if header[0] == 20:
+            continue
+        content_type, data = server_dir.open(header, fragment)
+        assert content_type == 22
+        for message in handshake_messages(data):
+            if message[0] == 20:
+                finished_key = hkdf_expand_label(server_hs, b"finished", b"", 32)
+                expected = hmac.new(
+                    finished_key, hashlib.sha256(transcript).digest(), hashlib.sha256
+                ).digest()
+                assert message[4:] == expected
+            transcript += message
+
+    client_ap = derive_secret(master, b"c ap traffic", transcript)
+    server_ap = derive_secret(master, b"s ap traffic", transcript)
+    finished_key = hkdf_expand_label(client_hs, b"finished", b"", 32)
+    verify_data = hmac.new(
+        finished_key, hashlib.sha256(transcript).digest(), hashlib.sha256
+    ).digest()
+    send(record(20, b"\1"))
+    send(Direction(client_hs).seal(22, handshake(20, verify_data)))
+    server.do_handshake()
+
+    client_dir = Direction(client_ap)
+    send(client_dir.seal(23, REQUEST))
+    assert server.read(len(REQUEST)) == REQUEST
+    server.write(RESPONSE)
+    try:
+        server.unwrap()
+    except ssl.SSLWantReadError:
+        pass
+    server_dir = Direction(server_ap)
+    received = b""
+    for header, fragment in records(receive()):
+        content_type, data = server_dir.open(header, fragment)
+        if content_type == 23:
+            received += data
+        elif content_type == 21:
+            assert data == b"\1\0"
+    assert received == RESPONSE
+    send(client_dir.seal(21, b"\1\0"))
+    server.unwrap()
+
+    secret_bytes = secret.private_numbers().private_value.to_bytes(32, "big")
+    return client_stream, server_stream, secret_bytes
+
+
+def main():
+    here = os.path.dirname(os.path.abspath(__file__))
+    kinds = sys.argv[1:] or CHAINS
+    for kind in kinds:
+        root, chain, key = CHAINS[kind]()
+        client_stream, server_stream, secret = session(chain, key, SERVER_NAME)
+        out = os.path.join(here, kind)
+        os.makedirs(out, exist_ok=True)
+        for file, data in [
+            ("client.bin", client_stream),
+            ("server.bin", server_stream),
+            ("root.der", root.public_bytes(serialization.Encoding.DER)),
+            ("secret.bin", secret),
+        ]:
+            with open(os.path.join(out, file), "wb") as f:
+                f.write(data)
+
+
+if __name__ == "__main__":
+    main()
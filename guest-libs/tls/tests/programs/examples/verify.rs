@@ -0,0 +1,38 @@
+#![cfg_attr(not(feature = "std"), no_main)]
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use openvm::io::{read_vec, reveal_bytes32};
+// clippy thinks this is unused, but it's used in the init! macro
+#[allow(unused)]
+use openvm_p256::P256Point;
+use openvm_tls::Verifier;
+
+openvm::init!("openvm_init_verify.rs");
+
+openvm::entry!(main);
+
+const SERVER_NAME: &str = "tls.openvm.test";
+/// 2026-01-01T00:00:00Z, when the test certificates are valid.
+const TIME: u64 = 1_767_225_600;
+
+/// Verifies a session with `tls.openvm.test` and checks the balance in the response. The
+/// commitment to the session is revealed, to be checked against an attestation of the bytes
+/// exchanged.
+pub fn main() {
+    let root = read_vec();
+    let secret: [u8; 32] = read_vec().try_into().unwrap();
+    let client_stream = read_vec();
+    let server_stream = read_vec();
+
+    let verifier = Verifier::new(&[&root], SERVER_NAME, TIME).unwrap();
+    let session = verifier
+        .verify(&secret, &client_stream, &server_stream)
+        .unwrap();
+    assert!(session
+        .sent
+        .starts_with(b"GET / HTTP/1.1\r\nHost: tls.openvm.test\r\n"));
+    assert!(session.server_closed);
+    assert!(session.received.starts_with(b"HTTP/1.1 200 OK\r\n"));
+    assert!(session.received.ends_with(b"\r\n\r\n{\"balance\": 1234}"));
+    reveal_bytes32(session.commitment);
+}
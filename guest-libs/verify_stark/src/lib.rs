@@ -1,5 +1,7 @@
 #[cfg(not(target_os = "zkvm"))]
 pub mod host;
+#[cfg(feature = "plonky3")]
+pub mod plonky3;
 
 /// Define a function that verifies an OpenVM Stark proof.
 /// To define this function, users need to specify the function name and an ASM file containing the
@@ -0,0 +1,124 @@
+//! Verification of generic Plonky3 uni-STARK proofs inside the guest.
+//!
+//! The configuration mirrors the BabyBear + Keccak256 configuration of `openvm-stark-backend`
+//! (degree 4 binomial extension, Merkle tree MMCS over Keccak256, FRI PCS). All Keccak256
+//! hashing, which dominates the verifier cost, is routed through [`openvm_keccak256::keccak256`]
+//! so it is accelerated by the Keccak256 extension when running in the zkVM.
+//!
+//! BabyBear and its extension field are computed with native 32-bit arithmetic. The modular
+//! arithmetic extension is not used: its chips operate on 256-bit or 384-bit moduli, which would
+//! be slower for a 31-bit prime. The VM only needs the Keccak256 extension besides RV32IM.
+use p3_air::Air;
+use p3_baby_bear::BabyBear;
+use p3_challenger::{HashChallenger, SerializingChallenger32};
+use p3_commit::ExtensionMmcs;
+use p3_dft::Radix2DitParallel;
+use p3_field::extension::BinomialExtensionField;
+use p3_fri::{FriConfig, TwoAdicFriPcs};
+use p3_merkle_tree::MerkleTreeMmcs;
+use p3_symmetric::{CompressionFunctionFromHasher, CryptographicHasher, SerializingHasher32};
+pub use p3_uni_stark::{PcsError, Proof, StarkConfig, StarkGenericConfig, VerificationError};
+use p3_uni_stark::{SymbolicAirBuilder, Val, VerifierConstraintFolder};
+use serde::{Deserialize, Serialize};
+
+pub type Plonky3Val = BabyBear;
+pub type Plonky3Challenge = BinomialExtensionField<Plonky3Val, 4>;
+pub type Plonky3FieldHash = SerializingHasher32<Keccak256Hash>;
+pub type Plonky3Compress = CompressionFunctionFromHasher<Keccak256Hash, 2, 32>;
+pub type Plonky3ValMmcs = MerkleTreeMmcs<Plonky3Val, u8, Plonky3FieldHash, Plonky3Compress, 32>;
+pub type Plonky3ChallengeMmcs = ExtensionMmcs<Plonky3Val, Plonky3Challenge, Plonky3ValMmcs>;
+pub type Plonky3Dft = Radix2DitParallel<Plonky3Val>;
+pub type Plonky3Challenger =
+    SerializingChallenger32<Plonky3Val, HashChallenger<u8, Keccak256Hash, 32>>;
+pub type Plonky3Pcs = TwoAdicFriPcs<Plonky3Val, Plonky3Dft, Plonky3ValMmcs, Plonky3ChallengeMmcs>;
+/// BabyBear + Keccak256 uni-STARK configuration.
+pub type BabyBearKeccakConfig = StarkConfig<Plonky3Pcs, Plonky3Challenge, Plonky3Challenger>;
+
+/// Keccak256 byte hasher backed by the OpenVM Keccak256 guest library.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Keccak256Hash;
+
+impl CryptographicHasher<u8, [u8; 32]> for Keccak256Hash {
+    fn hash_iter<I>(&self, input: I) -> [u8; 32]
+    where
+        I: IntoIterator<Item = u8>,
+    {
+        let input: Vec<u8> = input.into_iter().collect();
+        openvm_keccak256::keccak256(&input)
+    }
+
+    fn hash_iter_slices<'a, I>(&self, input: I) -> [u8; 32]
+    where
+        I: IntoIterator<Item = &'a [u8]>,
+    {
+        let input: Vec<u8> = input.into_iter().flatten().copied().collect();
+        openvm_keccak256::keccak256(&input)
+    }
+}
+
+/// FRI parameters of the proof to verify. These must match the parameters used by the prover.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FriParameters {
+    pub log_blowup: usize,
+    pub log_final_poly_len: usize,
+    pub num_queries: usize,
+    pub proof_of_work_bits: usize,
+}
+
+/// Build the BabyBear + Keccak256 configuration with the given FRI parameters.
+pub fn baby_bear_keccak_config(fri_params: FriParameters) -> BabyBearKeccakConfig {
+    let field_hash = Plonky3FieldHash::new(Keccak256Hash);
+    let compress = Plonky3Compress::new(Keccak256Hash);
+    let val_mmcs = Plonky3ValMmcs::new(field_hash, compress);
+    let challenge_mmcs = Plonky3ChallengeMmcs::new(val_mmcs.clone());
+    let fri_config = FriConfig {
+        log_blowup: fri_params.log_blowup,
+        log_final_poly_len: fri_params.log_final_poly_len,
+        num_queries: fri_params.num_queries,
+        proof_of_work_bits: fri_params.proof_of_work_bits,
+        mmcs: challenge_mmcs,
+    };
+    let pcs = Plonky3Pcs::new(Plonky3Dft::default(), val_mmcs, fri_config);
+    BabyBearKeccakConfig::new(pcs)
+}
+
+/// A fresh Fiat-Shamir challenger for [BabyBearKeccakConfig]. The prover must start from the same
+/// challenger state.
+pub fn baby_bear_keccak_challenger() -> Plonky3Challenger {
+    Plonky3Challenger::from_hasher(vec![], Keccak256Hash)
+}
+
+/// Verify a Plonky3 uni-STARK `proof` of `air` with the given `public_values`.
+pub fn verify<A>(
+    fri_params: FriParameters,
+    air: &A,
+    proof: &Proof<BabyBearKeccakConfig>,
+    public_values: &[Plonky3Val],
+) -> Result<(), VerificationError<PcsError<BabyBearKeccakConfig>>>
+where
+    A: Air<SymbolicAirBuilder<Val<BabyBearKeccakConfig>>>
+        + for<'a> Air<VerifierConstraintFolder<'a, BabyBearKeccakConfig>>,
+{
+    let config = baby_bear_keccak_config(fri_params);
+    let mut challenger = baby_bear_keccak_challenger();
+    // `p3_uni_stark::verify` takes the public values as a `&Vec`.
+    let public_values = public_values.to_vec();
+    p3_uni_stark::verify(&config, air, &mut challenger, proof, &public_values)
+}
+
+/// Read a proof and its public values from the input stream and verify them against `air`.
+/// The host should write the proof followed by the public values, e.g. with `StdIn::write`.
+///
+/// Panics if the proof does not verify. Returns the verified public values.
+pub fn read_and_verify<A>(fri_params: FriParameters, air: &A) -> Vec<Plonky3Val>
+where
+    A: Air<SymbolicAirBuilder<Val<BabyBearKeccakConfig>>>
+        + for<'a> Air<VerifierConstraintFolder<'a, BabyBearKeccakConfig>>,
+{
+    let proof: Proof<BabyBearKeccakConfig> = openvm::io::read();
+    let public_values: Vec<Plonky3Val> = openvm::io::read();
+    if let Err(err) = verify(fri_params, air, &proof, &public_values) {
+        panic!("Plonky3 proof verification failed: {err:?}");
+    }
+    public_values
+}
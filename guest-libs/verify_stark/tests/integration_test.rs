@@ -12,9 +12,14 @@ mod tests {
         Sdk, StdIn,
     };
     use openvm_stark_sdk::config::FriParameters;
-    use openvm_verify_stark::host::{
-        compute_hint_key_for_verify_openvm_stark, encode_proof_to_kv_store_value,
+    use openvm_toolchain_tests::{build_example_program_at_path, get_programs_dir};
+    use openvm_verify_stark::{
+        host::{compute_hint_key_for_verify_openvm_stark, encode_proof_to_kv_store_value},
+        plonky3::{self, Plonky3Val},
     };
+    use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+    use p3_field::{FieldAlgebra, PrimeField32};
+    use p3_matrix::{dense::RowMajorMatrix, Matrix};
 
     const LEAF_LOG_BLOWUP: usize = 2;
     const INTERNAL_LOG_BLOWUP: usize = 3;
@@ -120,4 +125,129 @@ mod tests {
 
         Ok(())
     }
+
+    /// Two-column Fibonacci AIR with public values `[a, b, x]`: the first row is `(a, b)` and the
+    /// second column of the last row is `x`.
+    struct FibonacciAir;
+
+    impl<F> BaseAir<F> for FibonacciAir {
+        fn width(&self) -> usize {
+            2
+        }
+    }
+
+    impl<AB: AirBuilderWithPublicValues> Air<AB> for FibonacciAir {
+        fn eval(&self, builder: &mut AB) {
+            let main = builder.main();
+            let pis = builder.public_values();
+            let (a, b, x) = (pis[0], pis[1], pis[2]);
+            let (local, next) = (main.row_slice(0), main.row_slice(1));
+
+            let mut when_first_row = builder.when_first_row();
+            when_first_row.assert_eq(local[0], a);
+            when_first_row.assert_eq(local[1], b);
+
+            let mut when_transition = builder.when_transition();
+            when_transition.assert_eq(local[1], next[0]);
+            when_transition.assert_eq(local[0] + local[1], next[1]);
+
+            builder.when_last_row().assert_eq(local[1], x);
+        }
+    }
+
+    /// Must match the parameters of `programs/examples/verify_plonky3_fibonacci.rs`.
+    const PLONKY3_FRI_PARAMS: plonky3::FriParameters = plonky3::FriParameters {
+        log_blowup: 1,
+        log_final_poly_len: 0,
+        num_queries: 100,
+        proof_of_work_bits: 16,
+    };
+
+    /// Proves the first 8 Fibonacci numbers with [FibonacciAir], and returns the proof and its
+    /// public values.
+    fn prove_plonky3_fibonacci() -> (
+        plonky3::Proof<plonky3::BabyBearKeccakConfig>,
+        Vec<Plonky3Val>,
+    ) {
+        const NUM_ROWS: usize = 8;
+        let mut values = Vec::with_capacity(NUM_ROWS * 2);
+        let (mut a, mut b) = (Plonky3Val::ZERO, Plonky3Val::ONE);
+        for _ in 0..NUM_ROWS {
+            values.extend([a, b]);
+            (a, b) = (b, a + b);
+        }
+        let trace = RowMajorMatrix::new(values, 2);
+        let public_values = vec![
+            Plonky3Val::ZERO,
+            Plonky3Val::ONE,
+            Plonky3Val::from_canonical_u32(21),
+        ];
+
+        let config = plonky3::baby_bear_keccak_config(PLONKY3_FRI_PARAMS);
+        let mut challenger = plonky3::baby_bear_keccak_challenger();
+        let proof = p3_uni_stark::prove(
+            &config,
+            &FibonacciAir,
+            &mut challenger,
+            trace,
+            &public_values,
+        );
+        (proof, public_values)
+    }
+
+    #[test]
+    fn test_verify_plonky3_fibonacci() {
+        let (proof, public_values) = prove_plonky3_fibonacci();
+        plonky3::verify(PLONKY3_FRI_PARAMS, &FibonacciAir, &proof, &public_values)
+            .expect("valid proof should verify");
+
+        let mut wrong_public_values = public_values.clone();
+        wrong_public_values[2] = Plonky3Val::from_canonical_u32(34);
+        assert!(plonky3::verify(
+            PLONKY3_FRI_PARAMS,
+            &FibonacciAir,
+            &proof,
+            &wrong_public_values
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_verify_plonky3_fibonacci_in_vm() -> Result<()> {
+        let sdk = Sdk::new();
+        let vm_config = SdkVmConfig::builder()
+            .system(Default::default())
+            .rv32i(Default::default())
+            .rv32m(Default::default())
+            .io(Default::default())
+            .keccak(Default::default())
+            .build();
+        let elf = build_example_program_at_path(
+            get_programs_dir!("tests/programs"),
+            "verify_plonky3_fibonacci",
+            &vm_config,
+        )?;
+        let exe = sdk.transpile(elf, vm_config.transpiler())?;
+
+        let (proof, public_values) = prove_plonky3_fibonacci();
+        let mut stdin = StdIn::default();
+        stdin.write(&proof);
+        stdin.write(&public_values);
+        let revealed = sdk.execute(exe.clone(), vm_config.clone(), stdin)?;
+        let expected: Vec<u8> = [0u32, 1, 21].iter().flat_map(|x| x.to_le_bytes()).collect();
+        let revealed: Vec<u8> = revealed[..expected.len()]
+            .iter()
+            .map(|x| x.as_canonical_u32() as u8)
+            .collect();
+        assert_eq!(revealed, expected);
+
+        // The guest panics if the public values do not match the proof.
+        let mut wrong_public_values = public_values;
+        wrong_public_values[2] = Plonky3Val::from_canonical_u32(34);
+        let mut stdin = StdIn::default();
+        stdin.write(&proof);
+        stdin.write(&wrong_public_values);
+        assert!(sdk.execute(exe, vm_config, stdin).is_err());
+        Ok(())
+    }
 }
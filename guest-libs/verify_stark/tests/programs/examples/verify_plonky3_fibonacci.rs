@@ -0,0 +1,49 @@
+use openvm::io::reveal_u32;
+use openvm_verify_stark::plonky3::{self, FriParameters};
+use p3_air::{Air, AirBuilder, AirBuilderWithPublicValues, BaseAir};
+use p3_field::PrimeField32;
+use p3_matrix::Matrix;
+
+/// Must match the parameters the host proves with.
+const FRI_PARAMS: FriParameters = FriParameters {
+    log_blowup: 1,
+    log_final_poly_len: 0,
+    num_queries: 100,
+    proof_of_work_bits: 16,
+};
+
+/// Two-column Fibonacci AIR with public values `[a, b, x]`: the first row is `(a, b)` and the
+/// second column of the last row is `x`.
+struct FibonacciAir;
+
+impl<F> BaseAir<F> for FibonacciAir {
+    fn width(&self) -> usize {
+        2
+    }
+}
+
+impl<AB: AirBuilderWithPublicValues> Air<AB> for FibonacciAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let pis = builder.public_values();
+        let (a, b, x) = (pis[0], pis[1], pis[2]);
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+
+        let mut when_first_row = builder.when_first_row();
+        when_first_row.assert_eq(local[0], a);
+        when_first_row.assert_eq(local[1], b);
+
+        let mut when_transition = builder.when_transition();
+        when_transition.assert_eq(local[1], next[0]);
+        when_transition.assert_eq(local[0] + local[1], next[1]);
+
+        builder.when_last_row().assert_eq(local[1], x);
+    }
+}
+
+pub fn main() {
+    let public_values = plonky3::read_and_verify(FRI_PARAMS, &FibonacciAir);
+    for (i, value) in public_values.iter().enumerate() {
+        reveal_u32(value.as_canonical_u32(), i);
+    }
+}